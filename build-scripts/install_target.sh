@@ -1,7 +1,7 @@
 #!/usr/bin/env bash
 set -e
 
-if [ "$TARGET" = "armv7-unknown-linux-gnueabihf" ] || [ "$TARGET" = "arm-unknown-linux-gnueabihf" ]; then
+if [ "$TARGET" = "armv7-unknown-linux-gnueabihf" ] || [ "$TARGET" = "arm-unknown-linux-gnueabihf" ] || [ "$TARGET" = "armv7-unknown-linux-musleabihf" ]; then
     cargo install --locked bindgen-cli
 fi
 
@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use lapin::tcp::{OwnedIdentity, OwnedTLSConfig};
+use lapin::{Connection, ConnectionProperties};
+use serde::Deserialize;
+
+use fluvio_connector_common::secret::SecretString;
+
+/// TLS settings of `amqps://` connections
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct TlsConfig {
+    /// CA certificates to verify the broker with, PEM encoded
+    #[serde(default)]
+    pub ca_cert: Option<PathBuf>,
+    /// client certificate and key as PKCS#12 archive
+    #[serde(default)]
+    pub client_identity: Option<PathBuf>,
+    #[serde(default)]
+    pub client_identity_password: Option<SecretString>,
+}
+
+pub(crate) async fn connect(uri: &SecretString, tls: Option<&TlsConfig>) -> Result<Connection> {
+    let uri = uri.resolve()?;
+    let mut tls_config = OwnedTLSConfig::default();
+    if let Some(tls) = tls {
+        if let Some(ca_cert) = &tls.ca_cert {
+            tls_config.cert_chain = Some(
+                std::fs::read_to_string(ca_cert)
+                    .with_context(|| format!("failed to read {}", ca_cert.display()))?,
+            );
+        }
+        if let Some(identity) = &tls.client_identity {
+            tls_config.identity = Some(OwnedIdentity {
+                der: std::fs::read(identity)
+                    .with_context(|| format!("failed to read {}", identity.display()))?,
+                password: tls
+                    .client_identity_password
+                    .as_ref()
+                    .map(|password| password.resolve())
+                    .transpose()?
+                    .unwrap_or_default(),
+            });
+        }
+    }
+    let connection =
+        Connection::connect_with_config(&uri, ConnectionProperties::default(), tls_config)
+            .await
+            .context("failed to connect to AMQP broker")?;
+    Ok(connection)
+}
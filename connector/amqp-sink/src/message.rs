@@ -0,0 +1,61 @@
+use lapin::types::{AMQPValue, FieldTable, ShortString};
+use lapin::BasicProperties;
+
+use fluvio::consumer::Record;
+
+const PARTITION_HEADER: &str = "fluvio-partition";
+const OFFSET_HEADER: &str = "fluvio-offset";
+
+/// delivery mode of messages surviving broker restarts
+const PERSISTENT: u8 = 2;
+
+/// Properties shared by all published messages
+pub(crate) fn base_properties(persistent: bool, content_type: Option<&str>) -> BasicProperties {
+    let mut properties = BasicProperties::default();
+    if persistent {
+        properties = properties.with_delivery_mode(PERSISTENT);
+    }
+    if let Some(content_type) = content_type {
+        properties = properties.with_content_type(ShortString::from(content_type));
+    }
+    properties
+}
+
+/// Properties of the message published for `record`
+///
+/// The position of the record is added as headers, so consumers are able to
+/// recognize messages published again after the connector restarted.
+pub(crate) fn properties(base: &BasicProperties, record: &Record) -> BasicProperties {
+    let mut headers = FieldTable::default();
+    headers.insert(
+        ShortString::from(PARTITION_HEADER),
+        AMQPValue::LongUInt(record.partition()),
+    );
+    headers.insert(
+        ShortString::from(OFFSET_HEADER),
+        AMQPValue::LongLongInt(record.offset()),
+    );
+    base.clone().with_headers(headers)
+}
+
+/// The configured routing key, otherwise the record key
+pub(crate) fn routing_key(configured: Option<&str>, record_key: Option<&[u8]>) -> String {
+    match configured {
+        Some(routing_key) => routing_key.to_owned(),
+        None => record_key
+            .map(|key| String::from_utf8_lossy(key).into_owned())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing_key() {
+        assert_eq!(routing_key(Some("events"), Some(b"user-1")), "events");
+        assert_eq!(routing_key(None, Some(b"user-1")), "user-1");
+        assert_eq!(routing_key(None, None), "");
+    }
+}
@@ -0,0 +1,105 @@
+mod row;
+mod sink;
+
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+
+use fluvio_connector_common::{
+    connector, consumer::ConsumerStream, secret::SecretString, tracing::info, Result,
+};
+use row::ColumnMapping;
+use sink::ClickHouseSink;
+
+const DEFAULT_BATCH_SIZE: usize = 10000;
+const DEFAULT_LINGER: Duration = Duration::from_secs(1);
+
+#[connector(sink)]
+async fn start(config: ClickHouseConfig, mut stream: impl ConsumerStream) -> Result<()> {
+    let sink = ClickHouseSink::new(&config).await?;
+    info!(url = %config.url, table = %config.table, "inserting records");
+
+    let mut batch = Vec::with_capacity(config.batch_size);
+    let mut started = Instant::now();
+    loop {
+        let next = if batch.is_empty() {
+            Some(stream.next().await)
+        } else {
+            let remaining = config.linger.saturating_sub(started.elapsed());
+            async_std::future::timeout(remaining, stream.next())
+                .await
+                .ok()
+        };
+        let end_of_stream = match next {
+            Some(Some(record)) => {
+                if batch.is_empty() {
+                    started = Instant::now();
+                }
+                batch.push(record?);
+                if batch.len() < config.batch_size {
+                    continue;
+                }
+                false
+            }
+            Some(None) => true,
+            // linger elapsed
+            None => false,
+        };
+
+        if !batch.is_empty() {
+            sink.write(&batch).await?;
+            batch.clear();
+            stream.offset_commit()?;
+            stream.offset_flush().await?;
+        }
+        if end_of_stream {
+            return Ok(());
+        }
+    }
+}
+
+#[connector(config, name = "clickhouse")]
+#[derive(Debug)]
+struct ClickHouseConfig {
+    /// URL of the HTTP interface, e.g. https://localhost:8443
+    url: String,
+
+    #[serde(default)]
+    database: Option<String>,
+
+    table: String,
+
+    /// Columns filled from record fields, records are inserted as they are if empty
+    #[serde(default)]
+    columns: Vec<ColumnMapping>,
+
+    #[serde(default)]
+    username: Option<String>,
+
+    #[serde(default)]
+    password: Option<SecretString>,
+
+    /// Use asynchronous inserts, buffered by the server
+    #[serde(default)]
+    async_insert: bool,
+
+    /// Maximum number of rows per insert
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+
+    /// Maximum time to wait for a batch to fill up
+    #[serde(default = "default_linger", with = "humantime_serde")]
+    linger: Duration,
+
+    /// Topic receiving records that do not match the table
+    #[serde(default)]
+    dlq_topic: Option<String>,
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+fn default_linger() -> Duration {
+    DEFAULT_LINGER
+}
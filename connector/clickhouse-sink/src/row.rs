@@ -0,0 +1,163 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Column of the target table filled from a record field
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ColumnMapping {
+    /// column name
+    pub name: String,
+    /// path of the record field, nested fields separated by dots, defaults to the column name
+    #[serde(default)]
+    pub field: Option<String>,
+    /// reject records without the field, otherwise the column default is used
+    #[serde(default)]
+    pub required: bool,
+}
+
+struct Column {
+    name: String,
+    path: Vec<String>,
+    required: bool,
+}
+
+/// Maps JSON records to rows of a table
+pub(crate) struct RowMapper {
+    columns: Vec<Column>,
+}
+
+impl RowMapper {
+    /// Without columns, records are inserted as they are and fields are matched to columns by name
+    pub(crate) fn new(columns: &[ColumnMapping]) -> Self {
+        Self {
+            columns: columns
+                .iter()
+                .map(|column| Column {
+                    name: column.name.clone(),
+                    path: column
+                        .field
+                        .as_deref()
+                        .unwrap_or(&column.name)
+                        .split('.')
+                        .map(|name| name.to_owned())
+                        .collect(),
+                    required: column.required,
+                })
+                .collect(),
+        }
+    }
+
+    /// Names of the mapped columns, empty if records are inserted as they are
+    pub(crate) fn column_names(&self) -> impl Iterator<Item = &str> {
+        self.columns.iter().map(|column| column.name.as_str())
+    }
+
+    /// Build row from record value
+    pub(crate) fn map(&self, value: &[u8]) -> Result<Value> {
+        let record: Value = serde_json::from_slice(value)
+            .map_err(|err| anyhow!("record is not valid JSON: {err}"))?;
+        if !record.is_object() {
+            return Err(anyhow!("record is not a JSON object"));
+        }
+        if self.columns.is_empty() {
+            return Ok(record);
+        }
+
+        let mut row = Map::new();
+        for column in &self.columns {
+            match column
+                .path
+                .iter()
+                .try_fold(&record, |value, name| value.get(name))
+            {
+                Some(value) => {
+                    row.insert(column.name.clone(), value.clone());
+                }
+                None if column.required => {
+                    return Err(anyhow!(
+                        "record has no field {} for column {}",
+                        column.path.join("."),
+                        column.name
+                    ))
+                }
+                None => {}
+            }
+        }
+        Ok(Value::Object(row))
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('\\', "\\\\").replace('`', "\\`"))
+}
+
+/// Table identifier quoted for use in queries
+pub(crate) fn quote_table(database: Option<&str>, table: &str) -> String {
+    match database {
+        Some(database) => format!("{}.{}", quote_identifier(database), quote_identifier(table)),
+        None => quote_identifier(table),
+    }
+}
+
+/// INSERT statement reading rows in JSONEachRow format
+pub(crate) fn insert_query(table: &str, mapper: &RowMapper) -> String {
+    let columns: Vec<_> = mapper.column_names().map(quote_identifier).collect();
+    if columns.is_empty() {
+        format!("INSERT INTO {table} FORMAT JSONEachRow")
+    } else {
+        format!(
+            "INSERT INTO {table} ({}) FORMAT JSONEachRow",
+            columns.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn mapping(name: &str, field: Option<&str>, required: bool) -> ColumnMapping {
+        ColumnMapping {
+            name: name.to_owned(),
+            field: field.map(|field| field.to_owned()),
+            required,
+        }
+    }
+
+    #[test]
+    fn test_map_columns() {
+        let mapper = RowMapper::new(&[
+            mapping("id", None, true),
+            mapping("user_name", Some("user.name"), false),
+            mapping("country", Some("geo.country"), false),
+        ]);
+        let row = mapper
+            .map(br#"{"id": 1, "user": {"name": "alice"}, "extra": true}"#)
+            .expect("map");
+        assert_eq!(row, json!({"id": 1, "user_name": "alice"}));
+
+        assert!(mapper.map(br#"{"user": {"name": "bob"}}"#).is_err());
+        assert!(mapper.map(b"[1, 2]").is_err());
+        assert!(mapper.map(b"not json").is_err());
+    }
+
+    #[test]
+    fn test_insert_query() {
+        let table = quote_table(Some("analytics"), "events");
+        assert_eq!(table, "`analytics`.`events`");
+        assert_eq!(
+            insert_query(&table, &RowMapper::new(&[])),
+            "INSERT INTO `analytics`.`events` FORMAT JSONEachRow"
+        );
+        assert_eq!(
+            insert_query(
+                &table,
+                &RowMapper::new(&[mapping("id", None, false), mapping("ts", None, false)])
+            ),
+            "INSERT INTO `analytics`.`events` (`id`, `ts`) FORMAT JSONEachRow"
+        );
+        assert_eq!(quote_table(None, "we`ird"), "`we\\`ird`");
+    }
+}
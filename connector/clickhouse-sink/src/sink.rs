@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+
+use fluvio::consumer::Record;
+use fluvio_connector_common::dlq::{reject, DeadLetterQueue};
+use fluvio_connector_common::tracing::{debug, warn};
+
+use crate::row::{insert_query, quote_table, RowMapper};
+use crate::ClickHouseConfig;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+enum InsertError {
+    /// data does not match the table schema
+    Rejected(String),
+    Failed(anyhow::Error),
+}
+
+pub(crate) struct ClickHouseSink {
+    agent: ureq::Agent,
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+    query: String,
+    async_insert: bool,
+    mapper: RowMapper,
+    dlq: Option<DeadLetterQueue>,
+}
+
+impl ClickHouseSink {
+    pub(crate) async fn new(config: &ClickHouseConfig) -> Result<Self> {
+        let mapper = RowMapper::new(&config.columns);
+        let table = quote_table(config.database.as_deref(), &config.table);
+        let dlq = match &config.dlq_topic {
+            Some(topic) => Some(DeadLetterQueue::connect(topic).await?),
+            None => None,
+        };
+
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            url: config.url.clone(),
+            username: config.username.clone(),
+            password: config
+                .password
+                .as_ref()
+                .map(|password| password.resolve())
+                .transpose()?,
+            query: insert_query(&table, &mapper),
+            async_insert: config.async_insert,
+            mapper,
+            dlq,
+        })
+    }
+
+    /// Insert records as one batch
+    ///
+    /// If the batch is rejected, rows are inserted one by one so that only rows
+    /// not matching the table schema are sent to the dead letter queue.
+    pub(crate) async fn write(&self, records: &[Record]) -> Result<()> {
+        let mut rejected = vec![];
+        let mut rows = vec![];
+        for record in records {
+            match self.mapper.map(record.value()) {
+                Ok(row) => rows.push((record, row.to_string())),
+                Err(err) => rejected.push((record, err.to_string())),
+            }
+        }
+
+        if !rows.is_empty() {
+            let body = rows
+                .iter()
+                .map(|(_, row)| row.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            match self.insert(body).await {
+                Ok(()) => debug!(rows = rows.len(), "inserted batch"),
+                Err(InsertError::Failed(err)) => return Err(err),
+                Err(InsertError::Rejected(error)) => {
+                    warn!(%error, "batch rejected, inserting rows one by one");
+                    for (record, row) in &rows {
+                        match self.insert(row.clone()).await {
+                            Ok(()) => {}
+                            Err(InsertError::Rejected(error)) => rejected.push((record, error)),
+                            Err(InsertError::Failed(err)) => return Err(err),
+                        }
+                    }
+                }
+            }
+        }
+
+        reject(self.dlq.as_ref(), &rejected).await
+    }
+
+    /// ureq is blocking, the request runs on the blocking thread pool
+    async fn insert(&self, body: String) -> Result<(), InsertError> {
+        let mut request = self
+            .agent
+            .post(&self.url)
+            .query("query", &self.query)
+            .query("input_format_skip_unknown_fields", "1");
+        if self.async_insert {
+            // wait so errors are reported before offsets are committed
+            request = request
+                .query("async_insert", "1")
+                .query("wait_for_async_insert", "1");
+        }
+        if let Some(username) = &self.username {
+            request = request.set("X-ClickHouse-User", username);
+        }
+        if let Some(password) = &self.password {
+            request = request.set("X-ClickHouse-Key", password);
+        }
+
+        async_std::task::spawn_blocking(move || match request.send_string(&body) {
+            Ok(_) => Ok(()),
+            // ClickHouse reports data that can not be parsed or converted with 400
+            Err(ureq::Error::Status(400, response)) => Err(InsertError::Rejected(
+                response.into_string().unwrap_or_default().trim().to_owned(),
+            )),
+            Err(ureq::Error::Status(status, response)) => Err(InsertError::Failed(anyhow!(
+                "insert failed with status {status}: {}",
+                response.into_string().unwrap_or_default().trim()
+            ))),
+            Err(err) => Err(InsertError::Failed(anyhow!("insert failed: {err}"))),
+        })
+        .await
+    }
+}
@@ -0,0 +1,140 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Document to index with the bulk API
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BulkItem {
+    pub index: String,
+    pub id: Option<String>,
+    pub document: Value,
+}
+
+#[derive(Serialize)]
+struct IndexAction<'a> {
+    index: IndexMeta<'a>,
+}
+
+#[derive(Serialize)]
+struct IndexMeta<'a> {
+    #[serde(rename = "_index")]
+    index: &'a str,
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+}
+
+/// Newline delimited body of a bulk request
+pub(crate) fn bulk_body<'a>(items: impl IntoIterator<Item = &'a BulkItem>) -> Result<String> {
+    let mut body = String::new();
+    for item in items {
+        let action = IndexAction {
+            index: IndexMeta {
+                index: &item.index,
+                id: item.id.as_deref(),
+            },
+        };
+        body.push_str(&serde_json::to_string(&action)?);
+        body.push('\n');
+        body.push_str(&serde_json::to_string(&item.document)?);
+        body.push('\n');
+    }
+    Ok(body)
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BulkResponse {
+    #[serde(default)]
+    pub errors: bool,
+    #[serde(default)]
+    pub items: Vec<BulkResponseItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BulkResponseItem {
+    index: Option<BulkItemResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BulkItemResult {
+    status: u16,
+    #[serde(default)]
+    error: Option<Value>,
+}
+
+/// Outcome of a single document of a bulk request
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ItemOutcome {
+    Indexed,
+    /// rejected because the cluster is overloaded, can be retried
+    Throttled,
+    Rejected(String),
+}
+
+impl BulkResponseItem {
+    pub(crate) fn outcome(&self) -> ItemOutcome {
+        match &self.index {
+            Some(result) if (200..300).contains(&result.status) => ItemOutcome::Indexed,
+            Some(result) if result.status == 429 => ItemOutcome::Throttled,
+            Some(result) => ItemOutcome::Rejected(
+                result
+                    .error
+                    .as_ref()
+                    .map(|error| error.to_string())
+                    .unwrap_or_else(|| format!("status {}", result.status)),
+            ),
+            None => ItemOutcome::Rejected("missing index result".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_bulk_body() {
+        let items = [
+            BulkItem {
+                index: "events".to_owned(),
+                id: Some("1".to_owned()),
+                document: json!({"a": 1}),
+            },
+            BulkItem {
+                index: "events".to_owned(),
+                id: None,
+                document: json!({"a": 2}),
+            },
+        ];
+        assert_eq!(
+            bulk_body(&items).unwrap(),
+            "{\"index\":{\"_index\":\"events\",\"_id\":\"1\"}}\n{\"a\":1}\n\
+             {\"index\":{\"_index\":\"events\"}}\n{\"a\":2}\n"
+        );
+    }
+
+    #[test]
+    fn test_bulk_response_outcomes() {
+        let response: BulkResponse = serde_json::from_str(
+            r#"{
+                "took": 3,
+                "errors": true,
+                "items": [
+                    {"index": {"_index": "events", "status": 201}},
+                    {"index": {"_index": "events", "status": 429, "error": {"type": "es_rejected_execution_exception"}}},
+                    {"index": {"_index": "events", "status": 400, "error": {"type": "mapper_parsing_exception"}}}
+                ]
+            }"#,
+        )
+        .expect("parse");
+
+        assert!(response.errors);
+        let outcomes: Vec<_> = response.items.iter().map(|item| item.outcome()).collect();
+        assert_eq!(outcomes[0], ItemOutcome::Indexed);
+        assert_eq!(outcomes[1], ItemOutcome::Throttled);
+        assert!(
+            matches!(&outcomes[2], ItemOutcome::Rejected(error) if error.contains("mapper_parsing_exception"))
+        );
+    }
+}
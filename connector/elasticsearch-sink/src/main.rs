@@ -0,0 +1,121 @@
+mod bulk;
+mod sink;
+mod template;
+
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+
+use fluvio_connector_common::{
+    connector, consumer::ConsumerStream, secret::SecretString, tracing::info, Result,
+};
+use sink::ElasticsearchSink;
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+const DEFAULT_LINGER: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RETRIES: u32 = 5;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+#[connector(sink)]
+async fn start(config: ElasticsearchConfig, mut stream: impl ConsumerStream) -> Result<()> {
+    let sink = ElasticsearchSink::new(&config).await?;
+    info!(url = %config.url, index = %config.index, "indexing records");
+
+    let mut batch = Vec::with_capacity(config.batch_size);
+    let mut started = Instant::now();
+    loop {
+        let next = if batch.is_empty() {
+            Some(stream.next().await)
+        } else {
+            let remaining = config.linger.saturating_sub(started.elapsed());
+            async_std::future::timeout(remaining, stream.next())
+                .await
+                .ok()
+        };
+        let end_of_stream = match next {
+            Some(Some(record)) => {
+                if batch.is_empty() {
+                    started = Instant::now();
+                }
+                batch.push(record?);
+                if batch.len() < config.batch_size {
+                    continue;
+                }
+                false
+            }
+            Some(None) => true,
+            // linger elapsed
+            None => false,
+        };
+
+        if !batch.is_empty() {
+            sink.write(&batch).await?;
+            batch.clear();
+            stream.offset_commit()?;
+            stream.offset_flush().await?;
+        }
+        if end_of_stream {
+            return Ok(());
+        }
+    }
+}
+
+#[connector(config, name = "elasticsearch")]
+#[derive(Debug)]
+struct ElasticsearchConfig {
+    /// Base URL of the cluster, e.g. https://localhost:9200
+    url: String,
+
+    /// Index name template, e.g. `logs-{date}`
+    index: String,
+
+    /// Document id template, e.g. `{key}` or `{value.id}`, ids are generated if omitted
+    #[serde(default)]
+    id: Option<String>,
+
+    #[serde(default)]
+    username: Option<String>,
+
+    #[serde(default)]
+    password: Option<SecretString>,
+
+    /// API key, used instead of username and password
+    #[serde(default)]
+    api_key: Option<SecretString>,
+
+    /// Maximum number of records per bulk request
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+
+    /// Maximum time to wait for a batch to fill up
+    #[serde(default = "default_linger", with = "humantime_serde")]
+    linger: Duration,
+
+    /// Number of retries of documents throttled by the cluster
+    #[serde(default = "default_max_retries")]
+    max_retries: u32,
+
+    /// Delay before the first retry, doubled on every following one
+    #[serde(default = "default_retry_backoff", with = "humantime_serde")]
+    retry_backoff: Duration,
+
+    /// Topic receiving records that can not be indexed
+    #[serde(default)]
+    dlq_topic: Option<String>,
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+fn default_linger() -> Duration {
+    DEFAULT_LINGER
+}
+
+fn default_max_retries() -> u32 {
+    DEFAULT_MAX_RETRIES
+}
+
+fn default_retry_backoff() -> Duration {
+    DEFAULT_RETRY_BACKOFF
+}
@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use serde_json::Value;
+
+use fluvio::consumer::Record;
+use fluvio_connector_common::backoff::backoff;
+use fluvio_connector_common::dlq::{reject, DeadLetterQueue};
+use fluvio_connector_common::tracing::{debug, warn};
+
+use crate::bulk::{bulk_body, BulkItem, BulkResponse, ItemOutcome};
+use crate::template::{Template, TemplateContext};
+use crate::ElasticsearchConfig;
+
+const BULK_PATH: &str = "_bulk";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+enum BulkError {
+    /// cluster is unavailable or overloaded, the whole request can be retried
+    Retryable(anyhow::Error),
+    Fatal(anyhow::Error),
+}
+
+pub(crate) struct ElasticsearchSink {
+    agent: ureq::Agent,
+    bulk_url: String,
+    authorization: Option<String>,
+    index: Template,
+    id: Option<Template>,
+    max_retries: u32,
+    retry_backoff: Duration,
+    dlq: Option<DeadLetterQueue>,
+}
+
+impl ElasticsearchSink {
+    pub(crate) async fn new(config: &ElasticsearchConfig) -> Result<Self> {
+        let authorization = match (&config.api_key, &config.username, &config.password) {
+            (Some(api_key), _, _) => Some(format!("ApiKey {}", api_key.resolve()?)),
+            (None, Some(username), password) => {
+                let password = password
+                    .as_ref()
+                    .map(|password| password.resolve())
+                    .transpose()?
+                    .unwrap_or_default();
+                let credentials = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{username}:{password}"));
+                Some(format!("Basic {credentials}"))
+            }
+            (None, None, Some(_)) => return Err(anyhow!("password requires username")),
+            (None, None, None) => None,
+        };
+
+        let dlq = match &config.dlq_topic {
+            Some(topic) => Some(DeadLetterQueue::connect(topic).await?),
+            None => None,
+        };
+
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            bulk_url: format!("{}/{BULK_PATH}", config.url.trim_end_matches('/')),
+            authorization,
+            index: config.index.parse()?,
+            id: config.id.as_deref().map(str::parse).transpose()?,
+            max_retries: config.max_retries,
+            retry_backoff: config.retry_backoff,
+            dlq,
+        })
+    }
+
+    /// Index records, retrying documents throttled by the cluster
+    ///
+    /// Records that can not be indexed are sent to the dead letter queue if configured,
+    /// otherwise an error is returned.
+    pub(crate) async fn write(&self, records: &[Record]) -> Result<()> {
+        let mut rejected = vec![];
+        let mut pending = vec![];
+        for record in records {
+            match self.bulk_item(record) {
+                Ok(item) => pending.push((record, item)),
+                Err(err) => rejected.push((record, err.to_string())),
+            }
+        }
+
+        let mut attempt = 0;
+        while !pending.is_empty() {
+            let outcomes = match self.send_bulk(pending.iter().map(|(_, item)| item)).await {
+                Ok(outcomes) if outcomes.len() == pending.len() => outcomes,
+                Ok(outcomes) => {
+                    return Err(anyhow!(
+                        "bulk response has {} items, expected {}",
+                        outcomes.len(),
+                        pending.len()
+                    ))
+                }
+                Err(BulkError::Retryable(err)) => {
+                    warn!(%err, attempt, "bulk request failed");
+                    vec![ItemOutcome::Throttled; pending.len()]
+                }
+                Err(BulkError::Fatal(err)) => return Err(err),
+            };
+
+            let mut retry = vec![];
+            for ((record, item), outcome) in pending.into_iter().zip(outcomes) {
+                match outcome {
+                    ItemOutcome::Indexed => {}
+                    ItemOutcome::Throttled => retry.push((record, item)),
+                    ItemOutcome::Rejected(error) => rejected.push((record, error)),
+                }
+            }
+            if retry.is_empty() {
+                break;
+            }
+            if attempt >= self.max_retries {
+                rejected.extend(
+                    retry
+                        .into_iter()
+                        .map(|(record, _)| (record, "throttled by cluster".to_owned())),
+                );
+                break;
+            }
+            let backoff = backoff(self.retry_backoff, attempt);
+            debug!(
+                documents = retry.len(),
+                ?backoff,
+                "retrying throttled documents"
+            );
+            async_std::task::sleep(backoff).await;
+            attempt += 1;
+            pending = retry;
+        }
+
+        reject(self.dlq.as_ref(), &rejected).await
+    }
+
+    fn bulk_item(&self, record: &Record) -> Result<BulkItem> {
+        let document: Value = serde_json::from_slice(record.value())
+            .map_err(|err| anyhow!("record is not valid JSON: {err}"))?;
+        if !document.is_object() {
+            return Err(anyhow!("record is not a JSON object"));
+        }
+        let ctx = TemplateContext {
+            key: record.key(),
+            partition: record.partition(),
+            offset: record.offset(),
+            timestamp: record.timestamp(),
+            value: &document,
+        };
+        let index = self.index.render(&ctx)?;
+        let id = self.id.as_ref().map(|id| id.render(&ctx)).transpose()?;
+        Ok(BulkItem {
+            index,
+            id,
+            document,
+        })
+    }
+
+    /// ureq is blocking, the request runs on the blocking thread pool
+    async fn send_bulk<'a>(
+        &self,
+        items: impl Iterator<Item = &'a BulkItem>,
+    ) -> Result<Vec<ItemOutcome>, BulkError> {
+        let body = bulk_body(items).map_err(BulkError::Fatal)?;
+        let mut request = self
+            .agent
+            .post(&self.bulk_url)
+            .set("Content-Type", "application/x-ndjson");
+        if let Some(authorization) = &self.authorization {
+            request = request.set("Authorization", authorization);
+        }
+
+        let body = async_std::task::spawn_blocking(move || {
+            let response = match request.send_string(&body) {
+                Ok(response) => response,
+                Err(ureq::Error::Status(status, response)) => {
+                    let error = anyhow!(
+                        "bulk request failed with status {status}: {}",
+                        response.into_string().unwrap_or_default()
+                    );
+                    return Err(if matches!(status, 429 | 502 | 503 | 504) {
+                        BulkError::Retryable(error)
+                    } else {
+                        BulkError::Fatal(error)
+                    });
+                }
+                Err(err) => return Err(BulkError::Retryable(err.into())),
+            };
+            response
+                .into_string()
+                .map_err(|err| BulkError::Retryable(err.into()))
+        })
+        .await?;
+        let response: BulkResponse = serde_json::from_str(&body)
+            .map_err(|err| BulkError::Fatal(anyhow!("invalid bulk response: {err}")))?;
+        if response.errors {
+            debug!("bulk response contains errors");
+        }
+        Ok(response.items.iter().map(|item| item.outcome()).collect())
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Values available to templates
+pub(crate) struct TemplateContext<'a> {
+    pub key: Option<&'a [u8]>,
+    pub partition: u32,
+    pub offset: i64,
+    /// milliseconds since epoch
+    pub timestamp: i64,
+    pub value: &'a Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Key,
+    Partition,
+    Offset,
+    Date,
+    Field(Vec<String>),
+}
+
+/// String with placeholders resolved per record
+///
+/// Supported placeholders are `{key}`, `{partition}`, `{offset}`, `{date}`
+/// (record date as `YYYY.MM.DD`) and `{value.<path>}` for a field of the record,
+/// where nested fields are separated by dots.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Template {
+    parts: Vec<Part>,
+}
+
+impl std::str::FromStr for Template {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = vec![];
+        let mut rest = s;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_owned()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| anyhow!("unclosed placeholder in template: {s}"))?;
+            let name = &rest[start + 1..start + end];
+            let part = match name {
+                "key" => Part::Key,
+                "partition" => Part::Partition,
+                "offset" => Part::Offset,
+                "date" => Part::Date,
+                _ => match name.strip_prefix("value.") {
+                    Some(path) if !path.is_empty() => {
+                        Part::Field(path.split('.').map(|p| p.to_owned()).collect())
+                    }
+                    _ => return Err(anyhow!("unknown placeholder {{{name}}} in template: {s}")),
+                },
+            };
+            parts.push(part);
+            rest = &rest[start + end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_owned()));
+        }
+        Ok(Self { parts })
+    }
+}
+
+impl Template {
+    pub(crate) fn render(&self, ctx: &TemplateContext) -> Result<String> {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => out.push_str(literal),
+                Part::Key => {
+                    let key = ctx.key.ok_or_else(|| anyhow!("record has no key"))?;
+                    out.push_str(&String::from_utf8_lossy(key));
+                }
+                Part::Partition => out.push_str(&ctx.partition.to_string()),
+                Part::Offset => out.push_str(&ctx.offset.to_string()),
+                Part::Date => out.push_str(&format_date(ctx.timestamp)),
+                Part::Field(path) => {
+                    let field = path
+                        .iter()
+                        .try_fold(ctx.value, |value, name| value.get(name))
+                        .ok_or_else(|| anyhow!("record has no field {}", path.join(".")))?;
+                    match field {
+                        Value::String(s) => out.push_str(s),
+                        Value::Number(n) => out.push_str(&n.to_string()),
+                        Value::Bool(b) => out.push_str(&b.to_string()),
+                        _ => {
+                            return Err(anyhow!(
+                                "field {} is not a string, number or boolean",
+                                path.join(".")
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// UTC date of timestamp as `YYYY.MM.DD`, records without timestamp use the current date
+fn format_date(timestamp: i64) -> String {
+    let time = if timestamp >= 0 {
+        UNIX_EPOCH + Duration::from_millis(timestamp as u64)
+    } else {
+        std::time::SystemTime::now()
+    };
+    let rfc3339 = humantime::format_rfc3339(time).to_string();
+    rfc3339[..10].replace('-', ".")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let value = json!({"user": {"id": 42, "name": "alice"}, "kind": "login"});
+        let ctx = TemplateContext {
+            key: Some(b"k1"),
+            partition: 2,
+            offset: 7,
+            timestamp: 1_704_067_200_000,
+            value: &value,
+        };
+
+        let template: Template = "events-{value.kind}-{date}".parse().expect("parse");
+        assert_eq!(template.render(&ctx).unwrap(), "events-login-2024.01.01");
+
+        let template: Template = "{key}/{partition}-{offset}:{value.user.id}"
+            .parse()
+            .expect("parse");
+        assert_eq!(template.render(&ctx).unwrap(), "k1/2-7:42");
+
+        let template: Template = "{value.missing}".parse().expect("parse");
+        assert!(template.render(&ctx).is_err());
+        let template: Template = "{value.user}".parse().expect("parse");
+        assert!(template.render(&ctx).is_err());
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        assert!("events-{date".parse::<Template>().is_err());
+        assert!("events-{topic}".parse::<Template>().is_err());
+        assert!("{value.}".parse::<Template>().is_err());
+        assert_eq!(
+            "plain".parse::<Template>().unwrap(),
+            Template {
+                parts: vec![Part::Literal("plain".to_owned())]
+            }
+        );
+    }
+}
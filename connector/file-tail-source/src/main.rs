@@ -0,0 +1,178 @@
+mod multiline;
+mod pattern;
+mod tail;
+
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+
+use fluvio::TopicProducerPool;
+use fluvio_connector_common::{
+    checkpoint::CheckpointStore,
+    connector,
+    tracing::{debug, info},
+    Result,
+};
+
+use multiline::{Assembler, MultilineConfig};
+use pattern::FilePattern;
+use tail::{FileId, Line, TailedFile};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Offset following the last produced record of each file
+type Positions = BTreeMap<PathBuf, Position>;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Position {
+    id: FileId,
+    offset: u64,
+}
+
+struct Tail {
+    file: TailedFile,
+    assembler: Assembler,
+}
+
+#[connector(source)]
+async fn start(config: FileTailConfig, producer: TopicProducerPool) -> Result<()> {
+    let patterns = config
+        .paths
+        .iter()
+        .map(|path| path.parse())
+        .collect::<Result<Vec<FilePattern>>>()?;
+    if patterns.is_empty() {
+        return Err(anyhow!("at least one path is required"));
+    }
+    let store = config.checkpoint_file.as_ref().map(CheckpointStore::new);
+    let saved = match &store {
+        Some(store) => store.load::<Positions>()?,
+        None => None,
+    };
+    // without saved positions, this is the first start
+    let mut skip_existing = saved.is_none() && config.read_from == ReadFrom::End;
+    let mut positions = saved.unwrap_or_default();
+
+    let mut files: BTreeMap<PathBuf, Tail> = BTreeMap::new();
+    loop {
+        for pattern in &patterns {
+            for path in pattern.expand()? {
+                if files.contains_key(&path) {
+                    continue;
+                }
+                let offset = match positions.get(&path) {
+                    Some(position) if TailedFile::id_of(&path)? == Some(position.id) => {
+                        Some(position.offset)
+                    }
+                    _ if skip_existing => None,
+                    _ => Some(0),
+                };
+                let file = match TailedFile::open(&path, offset) {
+                    Ok(file) => file,
+                    // removed since listing the directory
+                    Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                    Err(err) => {
+                        return Err(err)
+                            .with_context(|| format!("failed to open {}", path.display()))
+                    }
+                };
+                info!(path = %path.display(), ?offset, "tailing file");
+                files.insert(
+                    path,
+                    Tail {
+                        file,
+                        assembler: Assembler::new(config.multiline.clone()),
+                    },
+                );
+            }
+        }
+        skip_existing = false;
+
+        let mut changed = false;
+        let mut removed = vec![];
+        for (path, tail) in files.iter_mut() {
+            let mut lines = vec![];
+            let exists = tail.file.poll(&mut lines)?;
+            let mut records: Vec<Line> = lines
+                .into_iter()
+                .filter_map(|line| tail.assembler.push(line))
+                .collect();
+            let last = if exists {
+                tail.assembler.flush_idle()
+            } else {
+                tail.assembler.flush()
+            };
+            records.extend(last);
+
+            let key = path.to_string_lossy().into_owned();
+            for record in records {
+                producer.send(key.clone(), record.text).await?;
+                positions.insert(
+                    path.clone(),
+                    Position {
+                        id: record.id,
+                        offset: record.end,
+                    },
+                );
+                changed = true;
+            }
+            if !exists {
+                removed.push(path.clone());
+            }
+        }
+        for path in removed {
+            debug!(path = %path.display(), "file removed");
+            files.remove(&path);
+            positions.remove(&path);
+            changed = true;
+        }
+
+        if changed {
+            producer.flush().await?;
+            if let Some(store) = &store {
+                store.save(&positions)?;
+            }
+        }
+        async_std::task::sleep(config.poll_interval).await;
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReadFrom {
+    Beginning,
+    #[default]
+    End,
+}
+
+#[connector(config, name = "file")]
+#[derive(Debug)]
+struct FileTailConfig {
+    /// Files to tail, wildcards are allowed in file names, e.g. `/var/log/app/*.log`
+    paths: Vec<String>,
+
+    /// Where to start reading files that exist when the connector starts for the first time.
+    /// Files created later, or rotated since the last checkpoint, are read from the beginning.
+    #[serde(default)]
+    read_from: ReadFrom,
+
+    /// File to save read positions to, for resuming after restarts
+    #[serde(default)]
+    checkpoint_file: Option<PathBuf>,
+
+    /// Interval of checking files for new lines
+    #[serde(default = "default_poll_interval", with = "humantime_serde")]
+    poll_interval: Duration,
+
+    /// Join lines into records, e.g. stack traces
+    #[serde(default)]
+    multiline: Option<MultilineConfig>,
+}
+
+fn default_poll_interval() -> Duration {
+    DEFAULT_POLL_INTERVAL
+}
@@ -0,0 +1,167 @@
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::tail::Line;
+
+const DEFAULT_MAX_LINES: usize = 500;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct MultilineConfig {
+    /// Lines matching this expression start a new record, others are appended to the previous one
+    #[serde(with = "serde_regex")]
+    pub start_pattern: Regex,
+    /// Records are cut after this many lines
+    #[serde(default = "default_max_lines")]
+    pub max_lines: usize,
+    /// Time to wait for more lines before a record is complete
+    #[serde(default = "default_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+fn default_max_lines() -> usize {
+    DEFAULT_MAX_LINES
+}
+
+fn default_timeout() -> Duration {
+    DEFAULT_TIMEOUT
+}
+
+mod serde_regex {
+    use regex::Regex;
+    use serde::{Deserialize, Deserializer};
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Regex, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Joins lines into records, one line per record without multiline configuration
+pub(crate) struct Assembler {
+    config: Option<MultilineConfig>,
+    pending: Option<Line>,
+    lines: usize,
+    updated: Instant,
+}
+
+impl Assembler {
+    pub(crate) fn new(config: Option<MultilineConfig>) -> Self {
+        Self {
+            config,
+            pending: None,
+            lines: 0,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Add the next line, returning the record it completes
+    pub(crate) fn push(&mut self, line: Line) -> Option<Line> {
+        let Some(config) = &self.config else {
+            return Some(line);
+        };
+        self.updated = Instant::now();
+        match &mut self.pending {
+            // records never span rotated files
+            Some(pending)
+                if pending.id == line.id && !config.start_pattern.is_match(&line.text) =>
+            {
+                pending.text.push('\n');
+                pending.text.push_str(&line.text);
+                pending.end = line.end;
+                self.lines += 1;
+                if self.lines >= config.max_lines {
+                    return self.flush();
+                }
+                None
+            }
+            _ => {
+                self.lines = 1;
+                self.pending.replace(line)
+            }
+        }
+    }
+
+    /// Complete the pending record if no line was added within the timeout
+    pub(crate) fn flush_idle(&mut self) -> Option<Line> {
+        let timeout = self.config.as_ref()?.timeout;
+        if self.updated.elapsed() >= timeout {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn flush(&mut self) -> Option<Line> {
+        self.lines = 0;
+        self.pending.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tail::FileId;
+
+    fn line(text: &str, end: u64) -> Line {
+        Line {
+            text: text.to_owned(),
+            id: FileId::default(),
+            end,
+        }
+    }
+
+    fn config(max_lines: usize) -> MultilineConfig {
+        MultilineConfig {
+            start_pattern: Regex::new(r"^\d{4}-").unwrap(),
+            max_lines,
+            timeout: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_single_lines() {
+        let mut assembler = Assembler::new(None);
+        assert_eq!(assembler.push(line("a", 2)), Some(line("a", 2)));
+        assert_eq!(assembler.flush_idle(), None);
+    }
+
+    #[test]
+    fn test_stack_trace() {
+        let mut assembler = Assembler::new(Some(config(10)));
+        assert_eq!(assembler.push(line("2024-01-01 ERROR failed", 24)), None);
+        assert_eq!(assembler.push(line("java.lang.Exception: boom", 50)), None);
+        assert_eq!(
+            assembler.push(line("\tat Main.main(Main.java:3)", 77)),
+            None
+        );
+        assert_eq!(
+            assembler.push(line("2024-01-01 INFO next", 98)),
+            Some(line(
+                "2024-01-01 ERROR failed\njava.lang.Exception: boom\n\tat Main.main(Main.java:3)",
+                77
+            ))
+        );
+        assert_eq!(
+            assembler.flush_idle(),
+            Some(line("2024-01-01 INFO next", 98))
+        );
+        assert_eq!(assembler.flush(), None);
+    }
+
+    #[test]
+    fn test_max_lines() {
+        let mut assembler = Assembler::new(Some(config(2)));
+        assert_eq!(assembler.push(line("2024-01-01 a", 13)), None);
+        assert_eq!(
+            assembler.push(line("b", 15)),
+            Some(line("2024-01-01 a\nb", 15))
+        );
+        assert_eq!(assembler.push(line("c", 17)), None);
+        assert_eq!(assembler.flush(), Some(line("c", 17)));
+    }
+}
@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+
+/// Path whose file name may contain `*` and `?` wildcards, e.g. `/var/log/app/*.log`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FilePattern {
+    dir: PathBuf,
+    name: String,
+}
+
+impl FromStr for FilePattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let path = Path::new(s);
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| anyhow!("invalid file pattern: {s}"))?;
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        if dir.to_string_lossy().contains(['*', '?']) {
+            return Err(anyhow!(
+                "wildcards are only supported in the file name: {s}"
+            ));
+        }
+        Ok(Self {
+            dir,
+            name: name.to_owned(),
+        })
+    }
+}
+
+impl FilePattern {
+    pub(crate) fn matches(&self, name: &str) -> bool {
+        wildcard_match(self.name.as_bytes(), name.as_bytes())
+    }
+
+    /// Regular files currently matching the pattern, sorted by path
+    pub(crate) fn expand(&self) -> std::io::Result<Vec<PathBuf>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+        let mut paths = vec![];
+        for entry in entries {
+            let entry = entry?;
+            let matching = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| self.matches(name));
+            if matching && entry.file_type()?.is_file() {
+                paths.push(entry.path());
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+fn wildcard_match(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // position of the last `*` and the name position it was tried at
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == b'?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, tried)) => {
+                    p = star + 1;
+                    n = tried + 1;
+                    backtrack = Some((star, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcards() {
+        let pattern: FilePattern = "/var/log/app/*.log".parse().expect("parse");
+        assert_eq!(pattern.dir, PathBuf::from("/var/log/app"));
+        assert!(pattern.matches("app.log"));
+        assert!(pattern.matches(".log"));
+        assert!(!pattern.matches("app.log.1"));
+
+        let pattern: FilePattern = "app-??.*.log".parse().expect("parse");
+        assert_eq!(pattern.dir, PathBuf::from("."));
+        assert!(pattern.matches("app-01.2024.log"));
+        assert!(!pattern.matches("app-1.2024.log"));
+
+        assert!("/var/*/app.log".parse::<FilePattern>().is_err());
+    }
+
+    #[test]
+    fn test_expand() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for name in ["b.log", "a.log", "a.log.1", "c.txt"] {
+            std::fs::write(dir.path().join(name), "").expect("write");
+        }
+        std::fs::create_dir(dir.path().join("d.log")).expect("mkdir");
+
+        let pattern: FilePattern = dir.path().join("*.log").to_str().unwrap().parse().unwrap();
+        assert_eq!(
+            pattern.expand().expect("expand"),
+            vec![dir.path().join("a.log"), dir.path().join("b.log")]
+        );
+    }
+}
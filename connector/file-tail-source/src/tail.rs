@@ -0,0 +1,219 @@
+use std::fs::{File, Metadata};
+use std::io::{BufRead, BufReader, ErrorKind, Result, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use fluvio_connector_common::tracing::info;
+
+/// Identity of a file that survives renames, used to detect rotation
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FileId {
+    dev: u64,
+    ino: u64,
+}
+
+impl FileId {
+    #[cfg(unix)]
+    fn of(metadata: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+        Self {
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+        }
+    }
+
+    /// Without inodes only truncation is detected
+    #[cfg(not(unix))]
+    fn of(_metadata: &Metadata) -> Self {
+        Self::default()
+    }
+}
+
+/// Complete line, without its line ending
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Line {
+    pub text: String,
+    /// file the line was read from
+    pub id: FileId,
+    /// byte offset following the line
+    pub end: u64,
+}
+
+pub(crate) struct TailedFile {
+    path: PathBuf,
+    id: FileId,
+    reader: BufReader<File>,
+    /// offset following the last complete line
+    offset: u64,
+    partial: Vec<u8>,
+}
+
+impl TailedFile {
+    /// Open `path`, starting at `offset`, or at its end if `offset` is None
+    pub(crate) fn open(path: &Path, offset: Option<u64>) -> Result<Self> {
+        let mut file = File::open(path)?;
+        let metadata = file.metadata()?;
+        let offset = match offset {
+            Some(offset) if offset <= metadata.len() => offset,
+            Some(_) => 0,
+            None => metadata.len(),
+        };
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            id: FileId::of(&metadata),
+            reader: BufReader::new(file),
+            offset,
+            partial: vec![],
+        })
+    }
+
+    pub(crate) fn id(&self) -> FileId {
+        self.id
+    }
+
+    /// Identity of the file at `path`, None if there is none
+    pub(crate) fn id_of(path: &Path) -> Result<Option<FileId>> {
+        match std::fs::metadata(path) {
+            Ok(metadata) => Ok(Some(FileId::of(&metadata))),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Read the lines appended since the last poll
+    ///
+    /// When the file was rotated, the rest of the old file is read before continuing with the
+    /// new file at the same path. Returns false once the path no longer exists.
+    pub(crate) fn poll(&mut self, lines: &mut Vec<Line>) -> Result<bool> {
+        let len = self.reader.get_ref().metadata()?.len();
+        if len < self.offset + self.partial.len() as u64 {
+            info!(path = %self.path.display(), "file truncated, reading from start");
+            self.reader.seek(SeekFrom::Start(0))?;
+            self.offset = 0;
+            self.partial.clear();
+        }
+        self.read_lines(lines)?;
+
+        match Self::id_of(&self.path)? {
+            Some(id) if id == self.id => Ok(true),
+            Some(_) => {
+                info!(path = %self.path.display(), "file rotated");
+                self.finish(lines);
+                *self = Self::open(&self.path, Some(0))?;
+                self.read_lines(lines)?;
+                Ok(true)
+            }
+            None => {
+                self.finish(lines);
+                Ok(false)
+            }
+        }
+    }
+
+    fn read_lines(&mut self, lines: &mut Vec<Line>) -> Result<()> {
+        loop {
+            if self.reader.read_until(b'\n', &mut self.partial)? == 0 {
+                return Ok(());
+            }
+            if self.partial.last() == Some(&b'\n') {
+                self.offset += self.partial.len() as u64;
+                self.push_partial(lines);
+            }
+        }
+    }
+
+    /// A rotated file is not written anymore, so its last line is complete without newline
+    fn finish(&mut self, lines: &mut Vec<Line>) {
+        if !self.partial.is_empty() {
+            self.offset += self.partial.len() as u64;
+            self.push_partial(lines);
+        }
+    }
+
+    fn push_partial(&mut self, lines: &mut Vec<Line>) {
+        let mut line = std::mem::take(&mut self.partial);
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        lines.push(Line {
+            text: String::from_utf8_lossy(&line).into_owned(),
+            id: self.id,
+            end: self.offset,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn texts(lines: &[Line]) -> Vec<&str> {
+        lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    fn append(path: &Path, data: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .expect("open");
+        file.write_all(data.as_bytes()).expect("write");
+    }
+
+    #[test]
+    fn test_partial_lines_and_truncation() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        append(&path, "old\n");
+
+        let mut tailed = TailedFile::open(&path, None).expect("open");
+        let mut lines = vec![];
+        append(&path, "first\r\nsec");
+        assert!(tailed.poll(&mut lines).expect("poll"));
+        assert_eq!(texts(&lines), vec!["first"]);
+        assert_eq!(lines[0].end, 11);
+
+        append(&path, "ond\n");
+        assert!(tailed.poll(&mut lines).expect("poll"));
+        assert_eq!(texts(&lines), vec!["first", "second"]);
+        assert_eq!(lines[1].end, 18);
+
+        std::fs::write(&path, "new\n").expect("truncate");
+        lines.clear();
+        assert!(tailed.poll(&mut lines).expect("poll"));
+        assert_eq!(texts(&lines), vec!["new"]);
+        assert_eq!(lines[0].end, 4);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rotation() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        append(&path, "a\n");
+
+        let mut tailed = TailedFile::open(&path, Some(0)).expect("open");
+        let old_id = tailed.id();
+        let mut lines = vec![];
+        assert!(tailed.poll(&mut lines).expect("poll"));
+
+        append(&path, "b\nc");
+        std::fs::rename(&path, dir.path().join("app.log.1")).expect("rename");
+        append(&path, "d\n");
+        assert!(tailed.poll(&mut lines).expect("poll"));
+        assert_eq!(texts(&lines), vec!["a", "b", "c", "d"]);
+        assert_eq!(lines[2].id, old_id);
+        assert_ne!(lines[3].id, old_id);
+        assert_eq!(lines[3].end, 2);
+
+        std::fs::remove_file(&path).expect("remove");
+        assert!(!tailed.poll(&mut lines).expect("poll"));
+    }
+}
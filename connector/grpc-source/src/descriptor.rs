@@ -0,0 +1,221 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use prost::Message;
+use prost_reflect::{DescriptorPool, MethodDescriptor};
+use prost_types::FileDescriptorProto;
+use tonic::transport::Channel;
+use tonic_reflection::pb::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::ServerReflectionRequest;
+
+use fluvio_connector_common::tracing::debug;
+
+/// Load a `FileDescriptorSet`, as written by `protoc --include_imports --descriptor_set_out`
+pub(crate) fn from_file(path: &Path) -> Result<DescriptorPool> {
+    let data = std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(DescriptorPool::decode(data.as_slice())?)
+}
+
+/// Fetch the descriptors of `service` and everything it depends on with server reflection
+pub(crate) async fn from_reflection(channel: Channel, service: &str) -> Result<DescriptorPool> {
+    let mut client = ServerReflectionClient::new(channel);
+    let mut files = BTreeMap::new();
+    let mut requested = BTreeSet::new();
+    let mut request = MessageRequest::FileContainingSymbol(service.to_owned());
+    loop {
+        for file in reflect(&mut client, request).await? {
+            files.insert(file.name().to_owned(), file);
+        }
+        let Some(dependency) = missing_dependencies(&files).into_iter().next() else {
+            break;
+        };
+        if !requested.insert(dependency.clone()) {
+            return Err(anyhow!(
+                "server does not provide descriptor of {dependency}"
+            ));
+        }
+        debug!(%dependency, "requesting dependency");
+        request = MessageRequest::FileByFilename(dependency);
+    }
+
+    let mut pool = DescriptorPool::new();
+    pool.add_file_descriptor_protos(files.into_values())?;
+    Ok(pool)
+}
+
+async fn reflect(
+    client: &mut ServerReflectionClient<Channel>,
+    request: MessageRequest,
+) -> Result<Vec<FileDescriptorProto>> {
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(request),
+    };
+    let mut responses = client
+        .server_reflection_info(futures::stream::iter([request]))
+        .await?
+        .into_inner();
+    let response = responses
+        .message()
+        .await?
+        .ok_or_else(|| anyhow!("server closed reflection stream"))?;
+    match response.message_response {
+        Some(MessageResponse::FileDescriptorResponse(response)) => response
+            .file_descriptor_proto
+            .iter()
+            .map(|file| Ok(FileDescriptorProto::decode(file.as_slice())?))
+            .collect(),
+        Some(MessageResponse::ErrorResponse(error)) => {
+            Err(anyhow!("reflection failed: {}", error.error_message))
+        }
+        _ => Err(anyhow!("unexpected reflection response")),
+    }
+}
+
+fn missing_dependencies(files: &BTreeMap<String, FileDescriptorProto>) -> BTreeSet<String> {
+    files
+        .values()
+        .flat_map(|file| file.dependency.iter())
+        .filter(|dependency| !files.contains_key(*dependency))
+        .cloned()
+        .collect()
+}
+
+/// Split `package.Service/Method` into service and method
+pub(crate) fn parse_method(method: &str) -> Result<(&str, &str)> {
+    method
+        .trim_start_matches('/')
+        .split_once('/')
+        .filter(|(service, method)| !service.is_empty() && !method.is_empty())
+        .ok_or_else(|| anyhow!("method must be given as package.Service/Method: {method}"))
+}
+
+pub(crate) fn find_method(
+    pool: &DescriptorPool,
+    service: &str,
+    method: &str,
+) -> Result<MethodDescriptor> {
+    let descriptor = pool
+        .get_service_by_name(service)
+        .ok_or_else(|| anyhow!("service {service} not found"))?
+        .methods()
+        .find(|descriptor| descriptor.name() == method)
+        .ok_or_else(|| anyhow!("method {method} not found in {service}"))?;
+    if descriptor.is_client_streaming() || !descriptor.is_server_streaming() {
+        return Err(anyhow!(
+            "{service}/{method} is not a server streaming method"
+        ));
+    }
+    Ok(descriptor)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, FieldDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto,
+    };
+
+    use super::*;
+
+    fn field(name: &str, number: i32, ty: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(ty as i32),
+            json_name: Some(name.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn method(name: &str, server_streaming: bool) -> MethodDescriptorProto {
+        MethodDescriptorProto {
+            name: Some(name.to_owned()),
+            input_type: Some(".events.Filter".to_owned()),
+            output_type: Some(".events.Event".to_owned()),
+            server_streaming: Some(server_streaming),
+            ..Default::default()
+        }
+    }
+
+    /// `events.Events` service with a streaming `Subscribe` and a unary `Get` method
+    pub(crate) fn test_pool() -> DescriptorPool {
+        let file = FileDescriptorProto {
+            name: Some("events.proto".to_owned()),
+            package: Some("events".to_owned()),
+            syntax: Some("proto3".to_owned()),
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("Filter".to_owned()),
+                    field: vec![field("source", 1, Type::String)],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("Event".to_owned()),
+                    field: vec![field("id", 1, Type::Int32), field("name", 2, Type::String)],
+                    ..Default::default()
+                },
+            ],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Events".to_owned()),
+                method: vec![method("Subscribe", true), method("Get", false)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_proto(file)
+            .expect("valid descriptor");
+        pool
+    }
+
+    #[test]
+    fn test_parse_method() {
+        assert_eq!(
+            parse_method("/events.Events/Subscribe").unwrap(),
+            ("events.Events", "Subscribe")
+        );
+        assert_eq!(
+            parse_method("events.Events/Subscribe").unwrap(),
+            ("events.Events", "Subscribe")
+        );
+        assert!(parse_method("events.Events").is_err());
+        assert!(parse_method("events.Events/").is_err());
+    }
+
+    #[test]
+    fn test_find_method() {
+        let pool = test_pool();
+        let method = find_method(&pool, "events.Events", "Subscribe").expect("method");
+        assert_eq!(method.output().full_name(), "events.Event");
+
+        assert!(find_method(&pool, "events.Events", "Get").is_err());
+        assert!(find_method(&pool, "events.Events", "Missing").is_err());
+        assert!(find_method(&pool, "events.Other", "Subscribe").is_err());
+    }
+
+    #[test]
+    fn test_missing_dependencies() {
+        let file = FileDescriptorProto {
+            name: Some("a.proto".to_owned()),
+            dependency: vec!["b.proto".to_owned(), "c.proto".to_owned()],
+            ..Default::default()
+        };
+        let dependency = FileDescriptorProto {
+            name: Some("b.proto".to_owned()),
+            ..Default::default()
+        };
+        let files = BTreeMap::from([
+            ("a.proto".to_owned(), file),
+            ("b.proto".to_owned(), dependency),
+        ]);
+        assert_eq!(
+            missing_dependencies(&files),
+            BTreeSet::from(["c.proto".to_owned()])
+        );
+    }
+}
@@ -0,0 +1,155 @@
+mod codec;
+mod descriptor;
+mod message;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::Deserialize;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{MetadataKey, MetadataValue};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint};
+
+use fluvio::TopicProducerPool;
+use fluvio_connector_common::{
+    connector,
+    secret::SecretString,
+    tracing::{error, info},
+    Result,
+};
+
+use codec::RawCodec;
+use message::Format;
+
+const DEFAULT_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+
+#[connector(source)]
+async fn start(config: GrpcConfig, producer: TopicProducerPool) -> Result<()> {
+    let (service, method) = descriptor::parse_method(&config.method)?;
+    loop {
+        match subscribe(&config, service, method, &producer).await {
+            Ok(()) => info!("stream ended by server, subscribing again"),
+            Err(err) => error!(%err, "subscription failed"),
+        }
+        async_std::task::sleep(config.reconnect_backoff).await;
+    }
+}
+
+async fn subscribe(
+    config: &GrpcConfig,
+    service: &str,
+    method: &str,
+    producer: &TopicProducerPool,
+) -> Result<()> {
+    let channel = connect(config).await?;
+    let pool = match &config.descriptor_set {
+        Some(path) => descriptor::from_file(path)?,
+        None => descriptor::from_reflection(channel.clone(), service).await?,
+    };
+    let method = descriptor::find_method(&pool, service, method)?;
+    let output = method.output();
+
+    let body = message::encode_request(&method.input(), &config.request)?;
+    let mut request = tonic::Request::new(body);
+    for (key, value) in &config.metadata {
+        request.metadata_mut().insert(
+            MetadataKey::from_bytes(key.as_bytes())
+                .with_context(|| format!("invalid metadata key {key}"))?,
+            MetadataValue::try_from(value.resolve()?.as_str())
+                .with_context(|| format!("invalid metadata value of {key}"))?,
+        );
+    }
+    let path = PathAndQuery::try_from(format!(
+        "/{}/{}",
+        method.parent_service().full_name(),
+        method.name()
+    ))?;
+
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready().await?;
+    let mut stream = grpc
+        .server_streaming(request, path, RawCodec)
+        .await?
+        .into_inner();
+    info!(method = %config.method, "subscribed");
+
+    // records are keyed by the message type, so consumers know the schema of raw messages
+    while let Some(message) = stream.message().await? {
+        let value = message::record_value(&output, message, config.format)?;
+        producer.send(output.full_name(), value).await?;
+    }
+    Ok(())
+}
+
+async fn connect(config: &GrpcConfig) -> Result<Channel> {
+    let mut endpoint = Endpoint::from_shared(config.url.clone())?;
+    if let Some(tls) = &config.tls {
+        let mut tls_config = ClientTlsConfig::new();
+        if let Some(ca_cert) = &tls.ca_cert {
+            let pem = std::fs::read(ca_cert)
+                .with_context(|| format!("failed to read {}", ca_cert.display()))?;
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(pem));
+        }
+        if let Some(domain) = &tls.domain {
+            tls_config = tls_config.domain_name(domain);
+        }
+        endpoint = endpoint.tls_config(tls_config)?;
+    }
+    let channel = endpoint
+        .connect()
+        .await
+        .with_context(|| format!("failed to connect to {}", config.url))?;
+    Ok(channel)
+}
+
+#[derive(Debug, Deserialize)]
+struct TlsConfig {
+    /// CA certificates to verify the server with, PEM encoded
+    #[serde(default)]
+    ca_cert: Option<PathBuf>,
+    /// name to verify the server certificate for, the host of the URL by default
+    #[serde(default)]
+    domain: Option<String>,
+}
+
+#[connector(config, name = "grpc")]
+#[derive(Debug)]
+struct GrpcConfig {
+    /// Server URL, e.g. http://localhost:50051
+    url: String,
+
+    /// Server streaming method to subscribe to, e.g. `package.Service/Method`
+    method: String,
+
+    /// Request message in its JSON mapping
+    #[serde(default = "default_request")]
+    request: serde_json::Value,
+
+    /// Descriptors of the service, fetched with server reflection if omitted
+    #[serde(default)]
+    descriptor_set: Option<PathBuf>,
+
+    /// Metadata sent with the request, e.g. authorization headers
+    #[serde(default)]
+    metadata: BTreeMap<String, SecretString>,
+
+    #[serde(default)]
+    format: Format,
+
+    #[serde(default)]
+    tls: Option<TlsConfig>,
+
+    /// Time to wait before subscribing again after the stream ended
+    #[serde(default = "default_reconnect_backoff", with = "humantime_serde")]
+    reconnect_backoff: Duration,
+}
+
+fn default_request() -> serde_json::Value {
+    serde_json::Value::Object(Default::default())
+}
+
+fn default_reconnect_backoff() -> Duration {
+    DEFAULT_RECONNECT_BACKOFF
+}
@@ -0,0 +1,67 @@
+use anyhow::Result;
+use bytes::Bytes;
+use prost::Message;
+use prost_reflect::{DynamicMessage, MessageDescriptor};
+use serde::Deserialize;
+
+/// Encoding of produced records
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Format {
+    /// protobuf JSON mapping
+    #[default]
+    Json,
+    /// messages as received
+    Protobuf,
+}
+
+/// Encode the request message given in its JSON mapping
+pub(crate) fn encode_request(
+    descriptor: &MessageDescriptor,
+    request: &serde_json::Value,
+) -> Result<Bytes> {
+    let message = DynamicMessage::deserialize(descriptor.clone(), request)?;
+    Ok(message.encode_to_vec().into())
+}
+
+pub(crate) fn record_value(
+    descriptor: &MessageDescriptor,
+    message: Bytes,
+    format: Format,
+) -> Result<Vec<u8>> {
+    match format {
+        Format::Protobuf => Ok(message.to_vec()),
+        Format::Json => {
+            let message = DynamicMessage::decode(descriptor.clone(), message)?;
+            Ok(serde_json::to_vec(&message)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::descriptor::tests::test_pool;
+
+    #[test]
+    fn test_json_round_trip() {
+        let pool = test_pool();
+        let event = pool.get_message_by_name("events.Event").expect("event");
+
+        let encoded = encode_request(&event, &json!({"id": 7, "name": "created"})).expect("encode");
+        let value = record_value(&event, encoded.clone(), Format::Json).expect("decode");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&value).unwrap(),
+            json!({"id": 7, "name": "created"})
+        );
+        assert_eq!(
+            record_value(&event, encoded.clone(), Format::Protobuf).unwrap(),
+            encoded.to_vec()
+        );
+
+        assert!(encode_request(&event, &json!({"unknown": 1})).is_err());
+        assert!(record_value(&event, Bytes::from_static(&[0xff]), Format::Json).is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Conversion of JSON records to InfluxDB line protocol
+
+use std::fmt::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Unit of written timestamps
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Precision {
+    Ns,
+    Us,
+    #[default]
+    Ms,
+    S,
+}
+
+impl Precision {
+    /// value of the `precision` query parameter
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ns => "ns",
+            Self::Us => "us",
+            Self::Ms => "ms",
+            Self::S => "s",
+        }
+    }
+
+    fn units(&self, since_epoch: Duration) -> u128 {
+        match self {
+            Self::Ns => since_epoch.as_nanos(),
+            Self::Us => since_epoch.as_micros(),
+            Self::Ms => since_epoch.as_millis(),
+            Self::S => since_epoch.as_secs() as u128,
+        }
+    }
+}
+
+/// Type a field is written as, inferred from the JSON value if not configured
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum FieldType {
+    Float,
+    Integer,
+    String,
+    Boolean,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct TagMapping {
+    pub name: String,
+    /// path of the record field, e.g. `device.id`, the tag name if omitted
+    #[serde(default)]
+    pub field: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct FieldMapping {
+    pub name: String,
+    #[serde(default)]
+    pub field: Option<String>,
+    #[serde(default, rename = "type")]
+    pub ty: Option<FieldType>,
+}
+
+pub(crate) struct LineMapper {
+    pub measurement: String,
+    /// record field overriding the measurement
+    pub measurement_field: Option<String>,
+    pub tags: Vec<TagMapping>,
+    /// all other top level values if empty
+    pub fields: Vec<FieldMapping>,
+    /// record field with the time of the point, the record timestamp is used without it
+    pub timestamp_field: Option<String>,
+    pub precision: Precision,
+}
+
+impl LineMapper {
+    /// Line of the point of a record, `timestamp` is the record timestamp in milliseconds
+    pub(crate) fn line(&self, record: &Value, timestamp: i64) -> Result<String> {
+        if !record.is_object() {
+            return Err(anyhow!("record is not a JSON object"));
+        }
+        let measurement = match &self.measurement_field {
+            Some(path) => match lookup(record, path) {
+                Some(Value::String(measurement)) => measurement.as_str(),
+                _ => self.measurement.as_str(),
+            },
+            None => self.measurement.as_str(),
+        };
+        let mut line = escape(measurement, &[',', ' ']);
+
+        let mut tags: Vec<(&str, String)> = self
+            .tags
+            .iter()
+            .filter_map(|tag| {
+                let value = lookup(record, tag.field.as_deref().unwrap_or(&tag.name))?;
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    Value::Null => return None,
+                    other => other.to_string(),
+                };
+                (!value.is_empty()).then_some((tag.name.as_str(), value))
+            })
+            .collect();
+        // sorted tags are the fastest to write
+        tags.sort();
+        for (name, value) in tags {
+            write!(
+                line,
+                ",{}={}",
+                escape(name, &[',', '=', ' ']),
+                escape(&value, &[',', '=', ' '])
+            )?;
+        }
+
+        let fields = self.fields(record)?;
+        if fields.is_empty() {
+            return Err(anyhow!("record has no fields"));
+        }
+        for (i, (name, value)) in fields.iter().enumerate() {
+            let separator = if i == 0 { ' ' } else { ',' };
+            write!(
+                line,
+                "{separator}{}={value}",
+                escape(name, &[',', '=', ' '])
+            )?;
+        }
+
+        write!(line, " {}", self.timestamp(record, timestamp)?)?;
+        Ok(line)
+    }
+
+    fn fields(&self, record: &Value) -> Result<Vec<(String, String)>> {
+        if self.fields.is_empty() {
+            let Value::Object(object) = record else {
+                return Ok(vec![]);
+            };
+            let mapped: Vec<&str> = self
+                .tags
+                .iter()
+                .map(|tag| tag.field.as_deref().unwrap_or(&tag.name))
+                .chain(self.measurement_field.as_deref())
+                .chain(self.timestamp_field.as_deref())
+                .collect();
+            return object
+                .iter()
+                .filter(|(name, value)| !mapped.contains(&name.as_str()) && !value.is_null())
+                .filter(|(_, value)| !value.is_object() && !value.is_array())
+                .map(|(name, value)| Ok((name.clone(), field_value(name, value, None)?)))
+                .collect();
+        }
+
+        self.fields
+            .iter()
+            .filter_map(|field| {
+                let value = lookup(record, field.field.as_deref().unwrap_or(&field.name))?;
+                (!value.is_null()).then(|| {
+                    Ok((
+                        field.name.clone(),
+                        field_value(&field.name, value, field.ty)?,
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    fn timestamp(&self, record: &Value, timestamp: i64) -> Result<u128> {
+        let record_time = || {
+            self.precision
+                .units(Duration::from_millis(timestamp.max(0) as u64))
+        };
+        let Some(path) = &self.timestamp_field else {
+            return Ok(record_time());
+        };
+        match lookup(record, path) {
+            // numbers are taken to be in the configured precision already
+            Some(Value::Number(number)) => number
+                .as_u64()
+                .map(u128::from)
+                .ok_or_else(|| anyhow!("invalid timestamp {number}")),
+            Some(Value::String(time)) => {
+                let time = humantime::parse_rfc3339_weak(time)
+                    .map_err(|err| anyhow!("invalid timestamp {time}: {err}"))?;
+                let since_epoch = time.duration_since(UNIX_EPOCH)?;
+                Ok(self.precision.units(since_epoch))
+            }
+            Some(other) => Err(anyhow!("invalid timestamp {other}")),
+            None => Ok(record_time()),
+        }
+    }
+}
+
+fn field_value(name: &str, value: &Value, ty: Option<FieldType>) -> Result<String> {
+    let ty = match (ty, value) {
+        (Some(ty), _) => ty,
+        (None, Value::Number(number)) if number.is_f64() => FieldType::Float,
+        (None, Value::Number(_)) => FieldType::Integer,
+        (None, Value::Bool(_)) => FieldType::Boolean,
+        (None, _) => FieldType::String,
+    };
+    let invalid = || anyhow!("field {name} is not of type {ty:?}: {value}");
+    Ok(match ty {
+        FieldType::Float => {
+            let float = match value {
+                Value::Number(number) => number.as_f64(),
+                Value::String(float) => float.parse().ok(),
+                _ => None,
+            }
+            .ok_or_else(invalid)?;
+            if !float.is_finite() {
+                return Err(invalid());
+            }
+            float.to_string()
+        }
+        FieldType::Integer => {
+            let integer: i64 = match value {
+                Value::Number(number) => number.as_i64(),
+                Value::String(integer) => integer.parse().ok(),
+                _ => None,
+            }
+            .ok_or_else(invalid)?;
+            format!("{integer}i")
+        }
+        FieldType::Boolean => match value {
+            Value::Bool(boolean) => boolean.to_string(),
+            _ => return Err(invalid()),
+        },
+        FieldType::String => {
+            let string = match value {
+                Value::String(string) => string.clone(),
+                other => other.to_string(),
+            };
+            format!("\"{}\"", escape(&string, &['"', '\\']))
+        }
+    })
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |value, name| value.get(name))
+}
+
+fn escape(s: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn mapper() -> LineMapper {
+        LineMapper {
+            measurement: "weather".to_owned(),
+            measurement_field: None,
+            tags: vec![
+                TagMapping {
+                    name: "station".to_owned(),
+                    field: Some("station.id".to_owned()),
+                },
+                TagMapping {
+                    name: "city".to_owned(),
+                    field: None,
+                },
+            ],
+            fields: vec![],
+            timestamp_field: None,
+            precision: Precision::Ms,
+        }
+    }
+
+    #[test]
+    fn test_inferred_fields() {
+        let record = json!({
+            "station": {"id": "st 1"},
+            "city": "San Jose,CA",
+            "temperature": 21.5,
+            "humidity": 40,
+            "ok": true,
+            "note": "say \"hi\"",
+            "missing": null
+        });
+        assert_eq!(
+            mapper().line(&record, 1700000000000).unwrap(),
+            r#"weather,city=San\ Jose\,CA,station=st\ 1 humidity=40i,note="say \"hi\"",ok=true,temperature=21.5 1700000000000"#
+        );
+    }
+
+    #[test]
+    fn test_configured_fields_and_timestamp() {
+        let mapper = LineMapper {
+            measurement: "cpu".to_owned(),
+            measurement_field: Some("kind".to_owned()),
+            tags: vec![],
+            fields: vec![
+                FieldMapping {
+                    name: "usage".to_owned(),
+                    field: Some("stats.usage".to_owned()),
+                    ty: Some(FieldType::Float),
+                },
+                FieldMapping {
+                    name: "cores".to_owned(),
+                    field: None,
+                    ty: None,
+                },
+            ],
+            timestamp_field: Some("time".to_owned()),
+            precision: Precision::S,
+        };
+        let record = json!({
+            "kind": "cpu total",
+            "stats": {"usage": 3},
+            "cores": 8,
+            "time": "2023-11-14T22:13:20Z"
+        });
+        assert_eq!(
+            mapper.line(&record, 0).unwrap(),
+            r"cpu\ total usage=3,cores=8i 1700000000"
+        );
+
+        let record = json!({"stats": {"usage": "high"}, "time": 1700000000});
+        assert!(mapper.line(&record, 0).is_err());
+        let record = json!({"cores": 8, "time": 1700000000});
+        assert_eq!(mapper.line(&record, 0).unwrap(), "cpu cores=8i 1700000000");
+        assert!(mapper.line(&json!({"time": 1}), 0).is_err());
+    }
+
+    #[test]
+    fn test_precision() {
+        let mut mapper = mapper();
+        let record = json!({"value": 1});
+        mapper.precision = Precision::Ns;
+        assert_eq!(
+            mapper.line(&record, 1500).unwrap(),
+            "weather value=1i 1500000000"
+        );
+        mapper.precision = Precision::S;
+        assert_eq!(mapper.line(&record, 1500).unwrap(), "weather value=1i 1");
+    }
+}
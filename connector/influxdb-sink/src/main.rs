@@ -0,0 +1,151 @@
+mod line;
+mod sink;
+
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+
+use fluvio_connector_common::{
+    connector, consumer::ConsumerStream, secret::SecretString, tracing::info, Result,
+};
+use line::{FieldMapping, Precision, TagMapping};
+use sink::{Api, InfluxDbSink};
+
+const DEFAULT_BATCH_SIZE: usize = 5000;
+const DEFAULT_LINGER: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RETRIES: u32 = 5;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+#[connector(sink)]
+async fn start(config: InfluxDbConfig, mut stream: impl ConsumerStream) -> Result<()> {
+    let sink = InfluxDbSink::new(&config).await?;
+    info!(url = %config.url, bucket = %config.bucket, "writing points");
+
+    let mut batch = Vec::with_capacity(config.batch_size);
+    let mut started = Instant::now();
+    loop {
+        let next = if batch.is_empty() {
+            Some(stream.next().await)
+        } else {
+            let remaining = config.linger.saturating_sub(started.elapsed());
+            async_std::future::timeout(remaining, stream.next())
+                .await
+                .ok()
+        };
+        let end_of_stream = match next {
+            Some(Some(record)) => {
+                if batch.is_empty() {
+                    started = Instant::now();
+                }
+                batch.push(record?);
+                if batch.len() < config.batch_size {
+                    continue;
+                }
+                false
+            }
+            Some(None) => true,
+            // linger elapsed
+            None => false,
+        };
+
+        if !batch.is_empty() {
+            sink.write(&batch).await?;
+            batch.clear();
+            stream.offset_commit()?;
+            stream.offset_flush().await?;
+        }
+        if end_of_stream {
+            return Ok(());
+        }
+    }
+}
+
+#[connector(config, name = "influxdb")]
+#[derive(Debug)]
+struct InfluxDbConfig {
+    /// Base URL of the server, e.g. http://localhost:8086
+    url: String,
+
+    #[serde(default)]
+    api: Api,
+
+    /// Bucket, or database with the v1 API
+    bucket: String,
+
+    /// Organization of the bucket, required by the v2 API
+    #[serde(default)]
+    org: Option<String>,
+
+    /// Retention policy with the v1 API
+    #[serde(default)]
+    retention_policy: Option<String>,
+
+    /// API token
+    #[serde(default)]
+    token: Option<SecretString>,
+
+    /// User with the v1 API
+    #[serde(default)]
+    username: Option<String>,
+
+    #[serde(default)]
+    password: Option<SecretString>,
+
+    /// Measurement of the points
+    measurement: String,
+
+    /// Record field overriding the measurement
+    #[serde(default)]
+    measurement_field: Option<String>,
+
+    /// Tags set from record fields
+    #[serde(default)]
+    tags: Vec<TagMapping>,
+
+    /// Fields set from record fields, all other top level values if empty
+    #[serde(default)]
+    fields: Vec<FieldMapping>,
+
+    /// Record field with the time of the point, the record timestamp if omitted
+    #[serde(default)]
+    timestamp_field: Option<String>,
+
+    #[serde(default)]
+    precision: Precision,
+
+    /// Maximum number of points per write
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+
+    /// Maximum time to wait for a batch to fill up
+    #[serde(default = "default_linger", with = "humantime_serde")]
+    linger: Duration,
+
+    /// Retries of writes failing because the server is unavailable or overloaded
+    #[serde(default = "default_max_retries")]
+    max_retries: u32,
+
+    /// Backoff before the first retry, doubled for every following retry
+    #[serde(default = "default_retry_backoff", with = "humantime_serde")]
+    retry_backoff: Duration,
+
+    /// Topic receiving records that can not be written
+    #[serde(default)]
+    dlq_topic: Option<String>,
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+fn default_linger() -> Duration {
+    DEFAULT_LINGER
+}
+
+fn default_max_retries() -> u32 {
+    DEFAULT_MAX_RETRIES
+}
+
+fn default_retry_backoff() -> Duration {
+    DEFAULT_RETRY_BACKOFF
+}
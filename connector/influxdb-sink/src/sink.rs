@@ -0,0 +1,224 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::Value;
+
+use fluvio::consumer::Record;
+use fluvio_connector_common::backoff::backoff;
+use fluvio_connector_common::dlq::{reject, DeadLetterQueue};
+use fluvio_connector_common::tracing::{debug, warn};
+
+use crate::line::{LineMapper, Precision};
+use crate::InfluxDbConfig;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// HTTP API used for writes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Api {
+    /// `/write` of InfluxDB 1.x, also offered by 2.x and compatible databases
+    V1,
+    /// `/api/v2/write`
+    #[default]
+    V2,
+}
+
+enum WriteError {
+    /// lines do not match the schema or are outside the retention period
+    Rejected(String),
+    /// server is unavailable or overloaded
+    Retryable(anyhow::Error),
+    Fatal(anyhow::Error),
+}
+
+pub(crate) struct InfluxDbSink {
+    agent: ureq::Agent,
+    write_url: String,
+    query: Vec<(&'static str, String)>,
+    authorization: Option<String>,
+    mapper: LineMapper,
+    max_retries: u32,
+    retry_backoff: Duration,
+    dlq: Option<DeadLetterQueue>,
+}
+
+impl InfluxDbSink {
+    pub(crate) async fn new(config: &InfluxDbConfig) -> Result<Self> {
+        let url = config.url.trim_end_matches('/');
+        let (write_url, query, authorization) = match config.api {
+            Api::V2 => {
+                let org = config
+                    .org
+                    .clone()
+                    .ok_or_else(|| anyhow!("org is required by the v2 API"))?;
+                let query = vec![
+                    ("org", org),
+                    ("bucket", config.bucket.clone()),
+                    ("precision", config.precision.as_str().to_owned()),
+                ];
+                let authorization = config
+                    .token
+                    .as_ref()
+                    .map(|token| Ok::<_, anyhow::Error>(format!("Token {}", token.resolve()?)))
+                    .transpose()?;
+                (format!("{url}/api/v2/write"), query, authorization)
+            }
+            Api::V1 => {
+                let mut query = vec![
+                    ("db", config.bucket.clone()),
+                    ("precision", v1_precision(config.precision).to_owned()),
+                ];
+                if let Some(retention_policy) = &config.retention_policy {
+                    query.push(("rp", retention_policy.clone()));
+                }
+                let authorization = match (&config.username, &config.password) {
+                    (Some(username), password) => {
+                        let password = password
+                            .as_ref()
+                            .map(|password| password.resolve())
+                            .transpose()?
+                            .unwrap_or_default();
+                        let credentials = base64::engine::general_purpose::STANDARD
+                            .encode(format!("{username}:{password}"));
+                        Some(format!("Basic {credentials}"))
+                    }
+                    (None, Some(_)) => return Err(anyhow!("password requires username")),
+                    (None, None) => config
+                        .token
+                        .as_ref()
+                        .map(|token| Ok::<_, anyhow::Error>(format!("Token {}", token.resolve()?)))
+                        .transpose()?,
+                };
+                (format!("{url}/write"), query, authorization)
+            }
+        };
+
+        let dlq = match &config.dlq_topic {
+            Some(topic) => Some(DeadLetterQueue::connect(topic).await?),
+            None => None,
+        };
+
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            write_url,
+            query,
+            authorization,
+            mapper: LineMapper {
+                measurement: config.measurement.clone(),
+                measurement_field: config.measurement_field.clone(),
+                tags: config.tags.clone(),
+                fields: config.fields.clone(),
+                timestamp_field: config.timestamp_field.clone(),
+                precision: config.precision,
+            },
+            max_retries: config.max_retries,
+            retry_backoff: config.retry_backoff,
+            dlq,
+        })
+    }
+
+    /// Write the points of records as one request
+    ///
+    /// When the server rejects the batch, lines are written one by one, so only the
+    /// rejected ones go to the dead letter queue. Writing a point again overwrites it
+    /// with the same values, so lines already accepted from the batch are not duplicated.
+    pub(crate) async fn write(&self, records: &[Record]) -> Result<()> {
+        let mut rejected = vec![];
+        let mut lines = vec![];
+        for record in records {
+            match self.line(record) {
+                Ok(line) => lines.push((record, line)),
+                Err(err) => rejected.push((record, err.to_string())),
+            }
+        }
+
+        if !lines.is_empty() {
+            let body = lines
+                .iter()
+                .map(|(_, line)| line.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            match self.write_with_retries(&body).await {
+                Ok(()) => debug!(lines = lines.len(), "wrote batch"),
+                Err(WriteError::Rejected(error)) => {
+                    warn!(%error, "batch rejected, writing lines one by one");
+                    for (record, line) in &lines {
+                        match self.write_with_retries(line).await {
+                            Ok(()) => {}
+                            Err(WriteError::Rejected(error)) => rejected.push((record, error)),
+                            Err(WriteError::Retryable(err) | WriteError::Fatal(err)) => {
+                                return Err(err)
+                            }
+                        }
+                    }
+                }
+                Err(WriteError::Retryable(err) | WriteError::Fatal(err)) => return Err(err),
+            }
+        }
+
+        reject(self.dlq.as_ref(), &rejected).await
+    }
+
+    fn line(&self, record: &Record) -> Result<String> {
+        let value: Value = serde_json::from_slice(record.value())
+            .map_err(|err| anyhow!("record is not valid JSON: {err}"))?;
+        self.mapper.line(&value, record.timestamp())
+    }
+
+    async fn write_with_retries(&self, body: &str) -> Result<(), WriteError> {
+        let mut attempt = 0;
+        loop {
+            match self.send(body.to_owned()).await {
+                Err(WriteError::Retryable(err)) if attempt < self.max_retries => {
+                    let backoff = backoff(self.retry_backoff, attempt);
+                    warn!(%err, attempt, ?backoff, "write failed, retrying");
+                    async_std::task::sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// ureq is blocking, the request runs on the blocking thread pool
+    async fn send(&self, body: String) -> Result<(), WriteError> {
+        let mut request = self
+            .agent
+            .post(&self.write_url)
+            .set("Content-Type", "text/plain; charset=utf-8");
+        for (name, value) in &self.query {
+            request = request.query(name, value);
+        }
+        if let Some(authorization) = &self.authorization {
+            request = request.set("Authorization", authorization);
+        }
+
+        async_std::task::spawn_blocking(move || match request.send_string(&body) {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(status, response)) => {
+                let error = response.into_string().unwrap_or_default().trim().to_owned();
+                Err(match status {
+                    400 | 422 => WriteError::Rejected(error),
+                    429 | 500 | 502 | 503 | 504 => {
+                        WriteError::Retryable(anyhow!("write failed with status {status}: {error}"))
+                    }
+                    _ => WriteError::Fatal(anyhow!("write failed with status {status}: {error}")),
+                })
+            }
+            Err(err) => Err(WriteError::Retryable(anyhow!("write failed: {err}"))),
+        })
+        .await
+    }
+}
+
+/// the v1 API abbreviates nanoseconds and microseconds
+fn v1_precision(precision: Precision) -> &'static str {
+    match precision {
+        Precision::Ns => "n",
+        Precision::Us => "u",
+        other => other.as_str(),
+    }
+}
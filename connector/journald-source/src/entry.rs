@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Journal entry as produced to the topic
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct JournalEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub cursor: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl JournalEntry {
+    /// Convert a line of `journalctl --output=json`, keeping the listed extra `fields`
+    pub(crate) fn from_json(line: &str, fields: &[String]) -> Result<Self> {
+        let entry: Map<String, Value> = serde_json::from_str(line)?;
+        let get = |name: &str| entry.get(name).and_then(field_value);
+
+        let cursor = get("__CURSOR").ok_or_else(|| anyhow!("journal entry without cursor"))?;
+        let timestamp = get("__REALTIME_TIMESTAMP")
+            .map(|micros| {
+                let micros: u64 = micros
+                    .parse()
+                    .map_err(|_| anyhow!("invalid realtime timestamp: {micros}"))?;
+                let time = SystemTime::UNIX_EPOCH + Duration::from_micros(micros);
+                Ok::<_, anyhow::Error>(humantime::format_rfc3339_micros(time).to_string())
+            })
+            .transpose()?;
+
+        Ok(Self {
+            timestamp,
+            hostname: get("_HOSTNAME"),
+            unit: get("_SYSTEMD_UNIT"),
+            priority: get("PRIORITY").and_then(|priority| priority.parse().ok()),
+            message: get("MESSAGE"),
+            cursor,
+            fields: fields
+                .iter()
+                .filter_map(|name| Some((name.clone(), get(name)?)))
+                .collect(),
+        })
+    }
+}
+
+/// journald encodes values that are not valid UTF-8 as byte arrays,
+/// and fields that occur more than once as arrays of values
+fn field_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Array(values) if values.iter().all(Value::is_u64) => {
+            let bytes: Vec<u8> = values
+                .iter()
+                .filter_map(|byte| byte.as_u64().map(|byte| byte as u8))
+                .collect();
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        Value::Array(values) => values.last().and_then(field_value),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_entry() {
+        let line = r#"{"__CURSOR":"s=1;i=2","__REALTIME_TIMESTAMP":"1700000000123456","_HOSTNAME":"edge-1","_SYSTEMD_UNIT":"sshd.service","PRIORITY":"6","MESSAGE":"Accepted publickey","_PID":"42","SYSLOG_IDENTIFIER":"sshd"}"#;
+        let entry = JournalEntry::from_json(line, &["_PID".to_owned(), "_COMM".to_owned()])
+            .expect("convert");
+
+        assert_eq!(entry.cursor, "s=1;i=2");
+        assert_eq!(
+            entry.timestamp.as_deref(),
+            Some("2023-11-14T22:13:20.123456Z")
+        );
+        assert_eq!(entry.hostname.as_deref(), Some("edge-1"));
+        assert_eq!(entry.unit.as_deref(), Some("sshd.service"));
+        assert_eq!(entry.priority, Some(6));
+        assert_eq!(entry.message.as_deref(), Some("Accepted publickey"));
+        assert_eq!(entry.fields.len(), 1);
+        assert_eq!(entry.fields["_PID"], "42");
+    }
+
+    #[test]
+    fn test_binary_and_repeated_fields() {
+        let line = r#"{"__CURSOR":"c","MESSAGE":[104,105,255],"TAG":["a","b"]}"#;
+        let entry = JournalEntry::from_json(line, &["TAG".to_owned()]).expect("convert");
+        assert_eq!(entry.message.as_deref(), Some("hi\u{fffd}"));
+        assert_eq!(entry.fields["TAG"], "b");
+        assert_eq!(entry.timestamp, None);
+
+        assert!(JournalEntry::from_json(r#"{"MESSAGE":"no cursor"}"#, &[]).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+mod entry;
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, Context};
+use futures::channel::mpsc::unbounded;
+use futures::StreamExt;
+
+use fluvio::{RecordKey, TopicProducerPool};
+use fluvio_connector_common::{
+    connector,
+    tracing::{debug, info, warn},
+    Result,
+};
+
+use entry::JournalEntry;
+
+const DEFAULT_JOURNALCTL: &str = "journalctl";
+const DEFAULT_CHECKPOINT_INTERVAL: usize = 100;
+
+#[connector(source)]
+async fn start(config: JournaldConfig, producer: TopicProducerPool) -> Result<()> {
+    let cursor = match &config.cursor_file {
+        Some(path) => read_cursor(path)?,
+        None => None,
+    };
+
+    let mut command = Command::new(&config.journalctl);
+    command.args(["--follow", "--output=json", "--no-pager"]);
+    for unit in &config.units {
+        command.args(["--unit", unit]);
+    }
+    match (&cursor, &config.since) {
+        (Some(cursor), _) => {
+            info!(%cursor, "resuming after saved cursor");
+            command.args(["--after-cursor", cursor]);
+        }
+        (None, Some(since)) => {
+            command.args(["--since", since]);
+        }
+        (None, None) => {
+            command.args(["--lines", "0"]);
+        }
+    }
+    let mut child = command
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {}", config.journalctl))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("journalctl stdout not captured"))?;
+
+    // journalctl blocks while following, read it on its own thread
+    let (sender, mut receiver) = unbounded();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if sender.unbounded_send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut pending = 0;
+    let mut last_cursor = None;
+    while let Some(line) = receiver.next().await {
+        let line = line?;
+        let entry = match JournalEntry::from_json(&line, &config.fields) {
+            Ok(entry) => entry,
+            Err(err) => {
+                warn!(%err, "skipping invalid journal entry");
+                continue;
+            }
+        };
+        let key = entry
+            .hostname
+            .as_deref()
+            .map(RecordKey::from)
+            .unwrap_or(RecordKey::NULL);
+        producer.send(key, serde_json::to_vec(&entry)?).await?;
+        last_cursor = Some(entry.cursor);
+
+        pending += 1;
+        if pending >= config.checkpoint_interval {
+            checkpoint(
+                &producer,
+                config.cursor_file.as_deref(),
+                last_cursor.as_deref(),
+            )
+            .await?;
+            pending = 0;
+        }
+    }
+    checkpoint(
+        &producer,
+        config.cursor_file.as_deref(),
+        last_cursor.as_deref(),
+    )
+    .await?;
+
+    let status = child.wait()?;
+    Err(anyhow!("journalctl exited with {status}"))
+}
+
+/// Save the cursor once all entries up to it are acknowledged
+async fn checkpoint(
+    producer: &TopicProducerPool,
+    cursor_file: Option<&Path>,
+    cursor: Option<&str>,
+) -> Result<()> {
+    producer.flush().await?;
+    if let (Some(path), Some(cursor)) = (cursor_file, cursor) {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, cursor)?;
+        std::fs::rename(&tmp, path)?;
+        debug!(%cursor, "saved cursor");
+    }
+    Ok(())
+}
+
+fn read_cursor(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(cursor) if cursor.trim().is_empty() => Ok(None),
+        Ok(cursor) => Ok(Some(cursor.trim().to_owned())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[connector(config, name = "journald")]
+#[derive(Debug)]
+struct JournaldConfig {
+    /// Only read entries of these systemd units, all entries if empty
+    #[serde(default)]
+    units: Vec<String>,
+
+    /// Where to start without a saved cursor, in any format accepted by `journalctl --since`.
+    /// Only new entries are read if omitted.
+    #[serde(default)]
+    since: Option<String>,
+
+    /// File to save the cursor of the last produced entry to, for resuming after restarts
+    #[serde(default)]
+    cursor_file: Option<PathBuf>,
+
+    /// Number of entries after which the cursor is saved
+    #[serde(default = "default_checkpoint_interval")]
+    checkpoint_interval: usize,
+
+    /// Additional journal fields to include, e.g. `_PID` or `SYSLOG_IDENTIFIER`
+    #[serde(default)]
+    fields: Vec<String>,
+
+    /// Path of the journalctl binary
+    #[serde(default = "default_journalctl")]
+    journalctl: String,
+}
+
+fn default_checkpoint_interval() -> usize {
+    DEFAULT_CHECKPOINT_INTERVAL
+}
+
+fn default_journalctl() -> String {
+    DEFAULT_JOURNALCTL.to_owned()
+}
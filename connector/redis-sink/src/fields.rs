@@ -0,0 +1,99 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// How records are turned into stream entries
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Format {
+    /// record value in a single field
+    #[default]
+    Raw,
+    /// top level fields of JSON objects as entry fields
+    Json,
+}
+
+pub(crate) struct FieldMapping {
+    pub format: Format,
+    pub value_field: String,
+    pub key_field: Option<String>,
+}
+
+impl FieldMapping {
+    pub(crate) fn fields(
+        &self,
+        key: Option<&[u8]>,
+        value: &[u8],
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut fields = vec![];
+        if let (Some(field), Some(key)) = (&self.key_field, key) {
+            fields.push((field.clone(), key.to_vec()));
+        }
+        match self.format {
+            Format::Raw => fields.push((self.value_field.clone(), value.to_vec())),
+            Format::Json => {
+                let Value::Object(object) = serde_json::from_slice(value)? else {
+                    return Err(anyhow!("record value is not a JSON object"));
+                };
+                for (name, value) in object {
+                    let value = match value {
+                        Value::String(value) => value.into_bytes(),
+                        other => other.to_string().into_bytes(),
+                    };
+                    fields.push((name, value));
+                }
+            }
+        }
+        Ok(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw() {
+        let mapping = FieldMapping {
+            format: Format::Raw,
+            value_field: "value".to_owned(),
+            key_field: Some("key".to_owned()),
+        };
+        assert_eq!(
+            mapping.fields(Some(b"k1"), b"hello").unwrap(),
+            vec![
+                ("key".to_owned(), b"k1".to_vec()),
+                ("value".to_owned(), b"hello".to_vec())
+            ]
+        );
+        assert_eq!(
+            mapping.fields(None, b"hello").unwrap(),
+            vec![("value".to_owned(), b"hello".to_vec())]
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let mapping = FieldMapping {
+            format: Format::Json,
+            value_field: "value".to_owned(),
+            key_field: None,
+        };
+        let mut fields = mapping
+            .fields(
+                Some(b"k1"),
+                br#"{"name":"sensor","reading":{"t":21.5},"ok":true}"#,
+            )
+            .unwrap();
+        fields.sort();
+        assert_eq!(
+            fields,
+            vec![
+                ("name".to_owned(), b"sensor".to_vec()),
+                ("ok".to_owned(), b"true".to_vec()),
+                ("reading".to_owned(), br#"{"t":21.5}"#.to_vec()),
+            ]
+        );
+        assert!(mapping.fields(None, b"[1, 2]").is_err());
+    }
+}
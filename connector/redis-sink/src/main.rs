@@ -0,0 +1,150 @@
+mod fields;
+
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use redis::aio::MultiplexedConnection;
+
+use fluvio::consumer::Record;
+use fluvio_connector_common::{
+    connector, consumer::ConsumerStream, secret::SecretString, tracing::info, Result,
+};
+
+use fields::{FieldMapping, Format};
+
+const DEFAULT_VALUE_FIELD: &str = "value";
+const DEFAULT_BATCH_SIZE: usize = 100;
+const DEFAULT_LINGER: Duration = Duration::from_millis(100);
+
+#[connector(sink)]
+async fn start(config: RedisSinkConfig, mut stream: impl ConsumerStream) -> Result<()> {
+    let client = redis::Client::open(config.url.resolve()?.as_str())?;
+    let mut connection = client.get_multiplexed_async_connection().await?;
+    let mapping = FieldMapping {
+        format: config.format,
+        value_field: config.value_field.clone(),
+        key_field: config.key_field.clone(),
+    };
+    info!(stream = %config.stream, "appending records");
+
+    let mut batch = Vec::with_capacity(config.batch_size);
+    let mut started = Instant::now();
+    loop {
+        let next = if batch.is_empty() {
+            Some(stream.next().await)
+        } else {
+            let remaining = config.linger.saturating_sub(started.elapsed());
+            async_std::future::timeout(remaining, stream.next())
+                .await
+                .ok()
+        };
+        let end_of_stream = match next {
+            Some(Some(record)) => {
+                if batch.is_empty() {
+                    started = Instant::now();
+                }
+                batch.push(record?);
+                if batch.len() < config.batch_size {
+                    continue;
+                }
+                false
+            }
+            Some(None) => true,
+            // linger elapsed
+            None => false,
+        };
+
+        if !batch.is_empty() {
+            append(&mut connection, &config, &mapping, &batch).await?;
+            batch.clear();
+            stream.offset_commit()?;
+            stream.offset_flush().await?;
+        }
+        if end_of_stream {
+            return Ok(());
+        }
+    }
+}
+
+/// Append records with one round trip
+async fn append(
+    connection: &mut MultiplexedConnection,
+    config: &RedisSinkConfig,
+    mapping: &FieldMapping,
+    records: &[Record],
+) -> Result<()> {
+    let mut pipe = redis::pipe();
+    for record in records {
+        let fields = mapping.fields(record.key(), record.value())?;
+        let mut xadd = redis::cmd("XADD");
+        xadd.arg(&config.stream);
+        if let Some(max_len) = config.max_len {
+            xadd.arg("MAXLEN");
+            // trimming whole macro nodes only is much cheaper
+            if config.approximate {
+                xadd.arg("~");
+            }
+            xadd.arg(max_len);
+        }
+        xadd.arg("*");
+        for (name, value) in fields {
+            xadd.arg(name).arg(value);
+        }
+        pipe.add_command(xadd).ignore();
+    }
+    pipe.query_async::<_, ()>(connection).await?;
+    Ok(())
+}
+
+#[connector(config, name = "redis")]
+#[derive(Debug)]
+struct RedisSinkConfig {
+    /// Server URL, e.g. redis://localhost:6379, rediss:// URLs use TLS
+    url: SecretString,
+
+    /// Stream to append to
+    stream: String,
+
+    #[serde(default)]
+    format: Format,
+
+    /// Field holding the record value with the `raw` format
+    #[serde(default = "default_value_field")]
+    value_field: String,
+
+    /// Field holding the record key, the key is not stored if omitted
+    #[serde(default)]
+    key_field: Option<String>,
+
+    /// Trim the stream to this many entries
+    #[serde(default)]
+    max_len: Option<usize>,
+
+    /// Trim approximately, keeping at least `max_len` entries
+    #[serde(default = "default_approximate")]
+    approximate: bool,
+
+    /// Maximum number of entries appended at once
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+
+    /// Maximum time to wait for a batch to fill up
+    #[serde(default = "default_linger", with = "humantime_serde")]
+    linger: Duration,
+}
+
+fn default_value_field() -> String {
+    DEFAULT_VALUE_FIELD.to_owned()
+}
+
+fn default_approximate() -> bool {
+    true
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+fn default_linger() -> Duration {
+    DEFAULT_LINGER
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use redis::streams::StreamId;
+use redis::Value;
+
+/// How stream entries are turned into records
+pub(crate) struct EntryMapping {
+    /// field holding the record value, all fields as JSON object if None
+    pub value_field: Option<String>,
+    pub key_field: Option<String>,
+}
+
+impl EntryMapping {
+    /// Key and value of the record of `entry`
+    pub(crate) fn record(&self, entry: &StreamId) -> Result<(Option<Vec<u8>>, Vec<u8>)> {
+        let key = match &self.key_field {
+            Some(field) => entry.map.get(field).map(bytes).transpose()?,
+            None => None,
+        };
+        let value = match &self.value_field {
+            Some(field) => {
+                let value = entry
+                    .map
+                    .get(field)
+                    .ok_or_else(|| anyhow!("entry {} has no field {field}", entry.id))?;
+                bytes(value)?
+            }
+            None => {
+                let fields = entry
+                    .map
+                    .iter()
+                    .map(|(name, value)| {
+                        Ok((
+                            name.as_str(),
+                            String::from_utf8_lossy(&bytes(value)?).into_owned(),
+                        ))
+                    })
+                    .collect::<Result<BTreeMap<_, _>>>()?;
+                serde_json::to_vec(&fields)?
+            }
+        };
+        Ok((key, value))
+    }
+}
+
+fn bytes(value: &Value) -> Result<Vec<u8>> {
+    Ok(redis::from_redis_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn entry(fields: &[(&str, &str)]) -> StreamId {
+        StreamId {
+            id: "1700000000000-0".to_owned(),
+            map: fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), Value::Data(value.as_bytes().to_vec())))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_fields_as_json() {
+        let mapping = EntryMapping {
+            value_field: None,
+            key_field: Some("user".to_owned()),
+        };
+        let (key, value) = mapping
+            .record(&entry(&[("user", "u1"), ("action", "login")]))
+            .expect("record");
+        assert_eq!(key, Some(b"u1".to_vec()));
+        assert_eq!(value, br#"{"action":"login","user":"u1"}"#.to_vec());
+    }
+
+    #[test]
+    fn test_value_field() {
+        let mapping = EntryMapping {
+            value_field: Some("payload".to_owned()),
+            key_field: Some("user".to_owned()),
+        };
+        let (key, value) = mapping
+            .record(&entry(&[("payload", "{\"a\":1}")]))
+            .expect("record");
+        assert_eq!(key, None);
+        assert_eq!(value, b"{\"a\":1}".to_vec());
+
+        assert!(mapping.record(&entry(&[("other", "x")])).is_err());
+    }
+}
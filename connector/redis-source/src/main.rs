@@ -0,0 +1,133 @@
+mod entry;
+
+use std::time::Duration;
+
+use redis::aio::MultiplexedConnection;
+use redis::streams::{StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+
+use fluvio::{RecordKey, TopicProducerPool};
+use fluvio_connector_common::{connector, secret::SecretString, tracing::info, Result};
+
+use entry::EntryMapping;
+
+const DEFAULT_CONSUMER: &str = "fluvio";
+const DEFAULT_START_ID: &str = "$";
+const DEFAULT_BATCH_SIZE: usize = 100;
+const DEFAULT_BLOCK: Duration = Duration::from_secs(1);
+
+/// id reading the entries delivered to this consumer but not acknowledged
+const PENDING: &str = "0";
+/// id reading entries never delivered to the group
+const NEW: &str = ">";
+
+#[connector(source)]
+async fn start(config: RedisSourceConfig, producer: TopicProducerPool) -> Result<()> {
+    let client = redis::Client::open(config.url.resolve()?.as_str())?;
+    let mut connection = client.get_multiplexed_async_connection().await?;
+    create_group(&mut connection, &config).await?;
+    info!(stream = %config.stream, group = %config.group, "reading stream");
+
+    let mapping = EntryMapping {
+        value_field: config.value_field.clone(),
+        key_field: config.key_field.clone(),
+    };
+    // entries are acknowledged once their records are committed, entries left pending
+    // by a previous run are delivered again before reading new ones
+    let mut id = PENDING;
+    loop {
+        let mut options = StreamReadOptions::default()
+            .group(&config.group, &config.consumer)
+            .count(config.batch_size);
+        if id == NEW {
+            options = options.block(config.block.as_millis() as usize);
+        }
+        let reply: StreamReadReply = connection
+            .xread_options(&[&config.stream], &[id], &options)
+            .await?;
+        let entries: Vec<_> = reply.keys.into_iter().flat_map(|key| key.ids).collect();
+        if entries.is_empty() {
+            id = NEW;
+            continue;
+        }
+
+        for entry in &entries {
+            let (key, value) = mapping.record(entry)?;
+            let key = key.map(RecordKey::from).unwrap_or(RecordKey::NULL);
+            producer.send(key, value).await?;
+        }
+        producer.flush().await?;
+        let ids: Vec<&str> = entries.iter().map(|entry| entry.id.as_str()).collect();
+        let _: usize = connection.xack(&config.stream, &config.group, &ids).await?;
+    }
+}
+
+async fn create_group(
+    connection: &mut MultiplexedConnection,
+    config: &RedisSourceConfig,
+) -> Result<()> {
+    let created: redis::RedisResult<()> = connection
+        .xgroup_create_mkstream(&config.stream, &config.group, &config.start_id)
+        .await;
+    match created {
+        Ok(()) => {
+            info!(group = %config.group, "created consumer group");
+            Ok(())
+        }
+        Err(err) if err.code() == Some("BUSYGROUP") => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[connector(config, name = "redis")]
+#[derive(Debug)]
+struct RedisSourceConfig {
+    /// Server URL, e.g. redis://localhost:6379, rediss:// URLs use TLS
+    url: SecretString,
+
+    /// Stream to read
+    stream: String,
+
+    /// Consumer group reading the stream, created if it does not exist
+    group: String,
+
+    /// Name of the connector within the group
+    #[serde(default = "default_consumer")]
+    consumer: String,
+
+    /// Id the group starts at when it is created, `$` for new entries or `0` for all entries
+    #[serde(default = "default_start_id")]
+    start_id: String,
+
+    /// Field holding the record value, all fields are produced as JSON object if omitted
+    #[serde(default)]
+    value_field: Option<String>,
+
+    /// Field holding the record key
+    #[serde(default)]
+    key_field: Option<String>,
+
+    /// Maximum number of entries read at once
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+
+    /// Maximum time to wait for new entries
+    #[serde(default = "default_block", with = "humantime_serde")]
+    block: Duration,
+}
+
+fn default_consumer() -> String {
+    DEFAULT_CONSUMER.to_owned()
+}
+
+fn default_start_id() -> String {
+    DEFAULT_START_ID.to_owned()
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+fn default_block() -> Duration {
+    DEFAULT_BLOCK
+}
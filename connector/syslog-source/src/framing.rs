@@ -0,0 +1,125 @@
+use anyhow::{anyhow, Result};
+
+/// longest length prefix of an octet counted frame
+const MAX_LENGTH_DIGITS: usize = 10;
+
+/// Splits a stream into syslog messages
+///
+/// Both framing methods of RFC 6587 are supported: octet counting, where every message is
+/// prefixed by its length, and non-transparent framing, where messages end with a newline.
+/// Messages always start with `<`, so a leading digit selects octet counting.
+pub(crate) struct FrameDecoder {
+    buf: Vec<u8>,
+    max_size: usize,
+}
+
+impl FrameDecoder {
+    pub(crate) fn new(max_size: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            max_size,
+        }
+    }
+
+    pub(crate) fn extend(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Next complete message, None if more data is needed
+    pub(crate) fn next_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        let start = self
+            .buf
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(self.buf.len());
+        self.buf.drain(..start);
+        let Some(first) = self.buf.first() else {
+            return Ok(None);
+        };
+
+        if first.is_ascii_digit() {
+            let Some(space) = self
+                .buf
+                .iter()
+                .take(MAX_LENGTH_DIGITS + 1)
+                .position(|b| *b == b' ')
+            else {
+                if self.buf.len() > MAX_LENGTH_DIGITS {
+                    return Err(anyhow!("invalid frame length"));
+                }
+                return Ok(None);
+            };
+            let len: usize = std::str::from_utf8(&self.buf[..space])?
+                .parse()
+                .map_err(|_| anyhow!("invalid frame length"))?;
+            if len > self.max_size {
+                return Err(anyhow!("message of {len} bytes exceeds maximum size"));
+            }
+            let end = space + 1 + len;
+            if self.buf.len() < end {
+                return Ok(None);
+            }
+            let frame = self.buf[space + 1..end].to_vec();
+            self.buf.drain(..end);
+            Ok(Some(frame))
+        } else {
+            match self.buf.iter().position(|b| *b == b'\n') {
+                Some(newline) => {
+                    let mut frame: Vec<u8> = self.buf.drain(..=newline).collect();
+                    frame.pop();
+                    if frame.last() == Some(&b'\r') {
+                        frame.pop();
+                    }
+                    Ok(Some(frame))
+                }
+                None if self.buf.len() > self.max_size => {
+                    Err(anyhow!("message exceeds maximum size"))
+                }
+                None => Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_octet_counting() {
+        let mut decoder = FrameDecoder::new(1024);
+        decoder.extend(b"11 <13>1 - - -");
+        assert_eq!(decoder.next_frame().unwrap(), Some(b"<13>1 - - -".to_vec()));
+        decoder.extend(b"5 <13>1");
+        assert_eq!(decoder.next_frame().unwrap(), Some(b"<13>1".to_vec()));
+        decoder.extend(b"7 <13>");
+        assert_eq!(decoder.next_frame().unwrap(), None);
+        decoder.extend(b"1 a");
+        assert_eq!(decoder.next_frame().unwrap(), Some(b"<13>1 a".to_vec()));
+        assert_eq!(decoder.next_frame().unwrap(), None);
+    }
+
+    #[test]
+    fn test_newline_framing() {
+        let mut decoder = FrameDecoder::new(1024);
+        decoder.extend(b"<13>1 first\r\n<13>1 sec");
+        assert_eq!(decoder.next_frame().unwrap(), Some(b"<13>1 first".to_vec()));
+        assert_eq!(decoder.next_frame().unwrap(), None);
+        decoder.extend(b"ond\n");
+        assert_eq!(
+            decoder.next_frame().unwrap(),
+            Some(b"<13>1 second".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_oversized_messages() {
+        let mut decoder = FrameDecoder::new(4);
+        decoder.extend(b"10 <13>");
+        assert!(decoder.next_frame().is_err());
+
+        let mut decoder = FrameDecoder::new(4);
+        decoder.extend(b"<13>1 no newline");
+        assert!(decoder.next_frame().is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_std::net::{TcpListener, UdpSocket};
+use futures::channel::mpsc::UnboundedSender;
+use futures::{AsyncRead, AsyncReadExt, StreamExt};
+use serde::Deserialize;
+
+use fluvio_connector_common::tracing::{debug, info, warn};
+use fluvio_future::openssl::{SslVerifyMode, TlsAcceptor};
+
+use crate::framing::FrameDecoder;
+
+const READ_BUFFER_SIZE: usize = 16 * 1024;
+
+/// Raw message received from `peer`
+pub(crate) type Received = (SocketAddr, Vec<u8>);
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct TlsListenerConfig {
+    pub addr: SocketAddr,
+    /// server certificate, PEM encoded
+    pub cert: PathBuf,
+    /// server private key, PEM encoded
+    pub key: PathBuf,
+    /// require client certificates signed by this CA
+    #[serde(default)]
+    pub ca: Option<PathBuf>,
+}
+
+/// Receive one message per datagram
+pub(crate) async fn listen_udp(
+    addr: SocketAddr,
+    max_size: usize,
+    sender: UnboundedSender<Received>,
+) -> Result<()> {
+    let socket = UdpSocket::bind(addr).await?;
+    info!(%addr, "listening for syslog over UDP");
+    let mut buf = vec![0u8; max_size];
+    loop {
+        let (len, peer) = socket.recv_from(&mut buf).await?;
+        if sender.unbounded_send((peer, buf[..len].to_vec())).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+pub(crate) async fn listen_tcp(
+    addr: SocketAddr,
+    max_size: usize,
+    sender: UnboundedSender<Received>,
+) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    info!(%addr, "listening for syslog over TCP");
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = stream?;
+        let peer = stream.peer_addr()?;
+        async_std::task::spawn(read_frames(peer, stream, max_size, sender.clone()));
+    }
+    Ok(())
+}
+
+pub(crate) async fn listen_tls(
+    config: TlsListenerConfig,
+    max_size: usize,
+    sender: UnboundedSender<Received>,
+) -> Result<()> {
+    let builder = match &config.ca {
+        Some(ca) => TlsAcceptor::builder()?
+            .with_ssl_verify_mode(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT)
+            .with_ca_from_pem_file(ca)?,
+        None => TlsAcceptor::builder()?,
+    };
+    let acceptor = builder
+        .with_certifiate_and_key_from_pem_files(&config.cert, &config.key)?
+        .build();
+
+    let listener = TcpListener::bind(config.addr).await?;
+    info!(addr = %config.addr, "listening for syslog over TLS");
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = stream?;
+        let peer = stream.peer_addr()?;
+        let acceptor = acceptor.clone();
+        let sender = sender.clone();
+        async_std::task::spawn(async move {
+            match acceptor.accept(stream).await {
+                Ok(stream) => read_frames(peer, stream, max_size, sender).await,
+                Err(err) => warn!(%peer, %err, "TLS handshake failed"),
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Forward framed messages of a connection until it is closed
+async fn read_frames<S>(
+    peer: SocketAddr,
+    mut stream: S,
+    max_size: usize,
+    sender: UnboundedSender<Received>,
+) where
+    S: AsyncRead + Unpin,
+{
+    debug!(%peer, "connection opened");
+    let mut decoder = FrameDecoder::new(max_size);
+    let mut buf = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        let len = match stream.read(&mut buf).await {
+            Ok(0) => break,
+            Ok(len) => len,
+            Err(err) => {
+                warn!(%peer, %err, "connection read failed");
+                break;
+            }
+        };
+        decoder.extend(&buf[..len]);
+        loop {
+            match decoder.next_frame() {
+                Ok(Some(frame)) => {
+                    if sender.unbounded_send((peer, frame)).is_err() {
+                        return;
+                    }
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    warn!(%peer, %err, "closing connection with invalid framing");
+                    return;
+                }
+            }
+        }
+    }
+    debug!(%peer, "connection closed");
+}
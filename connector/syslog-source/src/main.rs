@@ -0,0 +1,134 @@
+mod framing;
+mod listener;
+mod rfc5424;
+
+use std::net::SocketAddr;
+
+use anyhow::anyhow;
+use futures::channel::mpsc::unbounded;
+use futures::StreamExt;
+use serde::Serialize;
+
+use fluvio::{RecordKey, TopicProducerPool};
+use fluvio_connector_common::{
+    connector,
+    tracing::{debug, error},
+    Result,
+};
+
+use listener::TlsListenerConfig;
+use rfc5424::SyslogMessage;
+
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024;
+
+#[connector(source)]
+async fn start(config: SyslogConfig, producer: TopicProducerPool) -> Result<()> {
+    if config.udp.is_none() && config.tcp.is_none() && config.tls.is_none() {
+        return Err(anyhow!(
+            "at least one of udp, tcp or tls listeners is required"
+        ));
+    }
+
+    let (sender, mut receiver) = unbounded();
+    let max_size = config.max_message_size;
+    if let Some(addr) = config.udp {
+        let sender = sender.clone();
+        async_std::task::spawn(async move {
+            if let Err(err) = listener::listen_udp(addr, max_size, sender).await {
+                error!(%err, "UDP listener failed");
+            }
+        });
+    }
+    if let Some(addr) = config.tcp {
+        let sender = sender.clone();
+        async_std::task::spawn(async move {
+            if let Err(err) = listener::listen_tcp(addr, max_size, sender).await {
+                error!(%err, "TCP listener failed");
+            }
+        });
+    }
+    if let Some(tls) = config.tls.clone() {
+        let sender = sender.clone();
+        async_std::task::spawn(async move {
+            if let Err(err) = listener::listen_tls(tls, max_size, sender).await {
+                error!(%err, "TLS listener failed");
+            }
+        });
+    }
+    drop(sender);
+
+    while let Some((peer, raw)) = receiver.next().await {
+        let text = String::from_utf8_lossy(&raw);
+        let message = match text.parse::<SyslogMessage>() {
+            Ok(message) => message,
+            Err(err) if config.forward_invalid => {
+                debug!(%peer, %err, "forwarding invalid syslog message");
+                let value = serde_json::to_vec(&InvalidEntry {
+                    peer,
+                    error: err.to_string(),
+                    raw: &text,
+                })?;
+                producer.send(RecordKey::NULL, value).await?;
+                continue;
+            }
+            Err(err) => {
+                debug!(%peer, %err, "dropping invalid syslog message");
+                continue;
+            }
+        };
+
+        let key = message
+            .hostname
+            .as_ref()
+            .map(|hostname| RecordKey::from(hostname.as_bytes()))
+            .unwrap_or(RecordKey::NULL);
+        let value = serde_json::to_vec(&Entry {
+            peer,
+            message: &message,
+        })?;
+        producer.send(key, value).await?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct Entry<'a> {
+    peer: SocketAddr,
+    #[serde(flatten)]
+    message: &'a SyslogMessage,
+}
+
+#[derive(Serialize)]
+struct InvalidEntry<'a> {
+    peer: SocketAddr,
+    error: String,
+    raw: &'a str,
+}
+
+#[connector(config, name = "syslog")]
+#[derive(Debug)]
+struct SyslogConfig {
+    /// Address to receive datagrams on, e.g. 0.0.0.0:514
+    #[serde(default)]
+    udp: Option<SocketAddr>,
+
+    /// Address to accept plain TCP connections on, e.g. 0.0.0.0:601
+    #[serde(default)]
+    tcp: Option<SocketAddr>,
+
+    /// TLS listener, RFC 5425
+    #[serde(default)]
+    tls: Option<TlsListenerConfig>,
+
+    /// Maximum size of a single message in bytes
+    #[serde(default = "default_max_message_size")]
+    max_message_size: usize,
+
+    /// Produce messages that are not valid RFC 5424 as raw text instead of dropping them
+    #[serde(default)]
+    forward_invalid: bool,
+}
+
+fn default_max_message_size() -> usize {
+    DEFAULT_MAX_MESSAGE_SIZE
+}
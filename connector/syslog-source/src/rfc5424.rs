@@ -0,0 +1,225 @@
+//! Parser of RFC 5424 syslog messages
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+const NIL: &str = "-";
+const BOM: &str = "\u{feff}";
+
+/// Structured data elements, by SD-ID
+pub(crate) type StructuredData = BTreeMap<String, BTreeMap<String, String>>;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct SyslogMessage {
+    pub facility: u8,
+    pub severity: u8,
+    pub version: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proc_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msg_id: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub structured_data: StructuredData,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl std::str::FromStr for SyslogMessage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let line = s.trim_end_matches(['\r', '\n']);
+        let rest = line
+            .strip_prefix('<')
+            .ok_or_else(|| anyhow!("missing priority"))?;
+        let (pri, rest) = rest
+            .split_once('>')
+            .ok_or_else(|| anyhow!("unterminated priority"))?;
+        let pri: u8 = pri
+            .parse()
+            .ok()
+            .filter(|pri| *pri <= 191)
+            .ok_or_else(|| anyhow!("invalid priority: {pri}"))?;
+
+        let (version, rest) = next_field(rest)?;
+        let version = version
+            .parse()
+            .ok()
+            .filter(|version| *version > 0)
+            .ok_or_else(|| anyhow!("not an RFC 5424 message, version: {version}"))?;
+        let (timestamp, rest) = next_field(rest)?;
+        let (hostname, rest) = next_field(rest)?;
+        let (app_name, rest) = next_field(rest)?;
+        let (proc_id, rest) = next_field(rest)?;
+        let (msg_id, rest) = next_field(rest)?;
+        let (structured_data, rest) = parse_structured_data(rest)?;
+
+        let message = match rest.strip_prefix(' ') {
+            Some(message) if !message.is_empty() => {
+                Some(message.strip_prefix(BOM).unwrap_or(message).to_owned())
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            facility: pri >> 3,
+            severity: pri & 0x07,
+            version,
+            timestamp: nil_or(timestamp),
+            hostname: nil_or(hostname),
+            app_name: nil_or(app_name),
+            proc_id: nil_or(proc_id),
+            msg_id: nil_or(msg_id),
+            structured_data,
+            message,
+        })
+    }
+}
+
+fn next_field(s: &str) -> Result<(&str, &str)> {
+    let (field, rest) = s
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("message truncated"))?;
+    if field.is_empty() {
+        return Err(anyhow!("empty header field"));
+    }
+    Ok((field, rest))
+}
+
+fn nil_or(field: &str) -> Option<String> {
+    (field != NIL).then(|| field.to_owned())
+}
+
+fn parse_structured_data(s: &str) -> Result<(StructuredData, &str)> {
+    let mut data = StructuredData::new();
+    if let Some(rest) = s.strip_prefix(NIL) {
+        return Ok((data, rest));
+    }
+
+    let mut rest = s;
+    while let Some(element) = rest.strip_prefix('[') {
+        let id_end = element
+            .find([' ', ']'])
+            .ok_or_else(|| anyhow!("unterminated structured data"))?;
+        let id = &element[..id_end];
+        let mut params = BTreeMap::new();
+        let mut cursor = &element[id_end..];
+        loop {
+            if let Some(after) = cursor.strip_prefix(']') {
+                cursor = after;
+                break;
+            }
+            let param = cursor
+                .strip_prefix(' ')
+                .ok_or_else(|| anyhow!("invalid structured data element {id}"))?;
+            let (name, value) = param
+                .split_once("=\"")
+                .ok_or_else(|| anyhow!("invalid parameter in structured data element {id}"))?;
+            let (value, after) = parse_param_value(value)?;
+            params.insert(name.to_owned(), value);
+            cursor = after;
+        }
+        data.insert(id.to_owned(), params);
+        rest = cursor;
+    }
+    if data.is_empty() {
+        return Err(anyhow!("missing structured data"));
+    }
+    Ok((data, rest))
+}
+
+/// read escaped parameter value up to the closing quote
+fn parse_param_value(s: &str) -> Result<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &s[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('"' | '\\' | ']'))) => value.push(escaped),
+                Some((_, other)) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => break,
+            },
+            _ => value.push(c),
+        }
+    }
+    Err(anyhow!("unterminated parameter value"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        let message: SyslogMessage = "<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut=\"3\" eventSource=\"Application\" eventID=\"1011\"][examplePriority@32473 class=\"high\"] \u{feff}An application event log entry...\n"
+            .parse()
+            .expect("parse");
+
+        assert_eq!(message.facility, 20);
+        assert_eq!(message.severity, 5);
+        assert_eq!(message.version, 1);
+        assert_eq!(
+            message.timestamp.as_deref(),
+            Some("2003-10-11T22:14:15.003Z")
+        );
+        assert_eq!(message.hostname.as_deref(), Some("mymachine.example.com"));
+        assert_eq!(message.app_name.as_deref(), Some("evntslog"));
+        assert_eq!(message.proc_id, None);
+        assert_eq!(message.msg_id.as_deref(), Some("ID47"));
+        assert_eq!(message.structured_data.len(), 2);
+        assert_eq!(
+            message.structured_data["exampleSDID@32473"]["eventSource"],
+            "Application"
+        );
+        assert_eq!(
+            message.message.as_deref(),
+            Some("An application event log entry...")
+        );
+    }
+
+    #[test]
+    fn test_parse_nil_fields_and_escapes() {
+        let message: SyslogMessage = "<34>1 - - su - - [origin x=\"a \\\"b\\\" \\] c\"]"
+            .parse()
+            .expect("parse");
+        assert_eq!(message.facility, 4);
+        assert_eq!(message.severity, 2);
+        assert_eq!(message.timestamp, None);
+        assert_eq!(message.hostname, None);
+        assert_eq!(message.app_name.as_deref(), Some("su"));
+        assert_eq!(message.structured_data["origin"]["x"], "a \"b\" ] c");
+        assert_eq!(message.message, None);
+
+        let message: SyslogMessage = "<13>1 - host app 42 - - hello world"
+            .parse()
+            .expect("parse");
+        assert!(message.structured_data.is_empty());
+        assert_eq!(message.proc_id.as_deref(), Some("42"));
+        assert_eq!(message.message.as_deref(), Some("hello world"));
+    }
+
+    #[test]
+    fn test_reject_invalid() {
+        assert!("no priority".parse::<SyslogMessage>().is_err());
+        assert!("<999>1 - - - - - -".parse::<SyslogMessage>().is_err());
+        // RFC 3164 messages have no version
+        assert!("<13>Oct 11 22:14:15 host app: hi"
+            .parse::<SyslogMessage>()
+            .is_err());
+        assert!("<13>1 - - - - - [unterminated"
+            .parse::<SyslogMessage>()
+            .is_err());
+    }
+}
@@ -59,6 +59,14 @@ pub struct PublishCmd {
     /// Relative path to this connector package README
     #[clap(long, default_value = "./README.md")]
     readme: PathBuf,
+
+    /// Attach the build provenance, git commit and builder, to the package
+    #[arg(long)]
+    provenance: bool,
+
+    /// Attach a software bill of materials to the package, implies --provenance
+    #[arg(long, value_name = "PATH")]
+    sbom: Option<PathBuf>,
 }
 
 impl PublishCmd {
@@ -70,7 +78,7 @@ impl PublishCmd {
                 let opt = self.package.as_opt();
                 let hubdir = self.run_in_cargo_project(&opt)?;
                 let pkgmetapath = self.package_meta_path(&hubdir);
-                let pkgdata = package_assemble(pkgmetapath, &opt.target, &access)?;
+                let pkgdata = package_assemble(pkgmetapath, &opt.target, &access, self)?;
                 package_push(self, &pkgdata, &access)?;
                 Self::cleanup(&hubdir)?;
             }
@@ -80,7 +88,7 @@ impl PublishCmd {
                 let opt = self.package.as_opt();
                 let hubdir = self.run_in_cargo_project(&opt)?;
                 let pkgmetapath = self.package_meta_path(&hubdir);
-                package_assemble(pkgmetapath, &opt.target, &access)?;
+                package_assemble(pkgmetapath, &opt.target, &access, self)?;
             }
 
             // --push only, needs ipkg file or expects to be run in project folder
@@ -169,16 +177,26 @@ pub fn package_assemble<P: AsRef<Path>>(
     pkgmeta: P,
     target: &str,
     access: &HubAccess,
+    opts: &PublishCmd,
 ) -> Result<String> {
-    let pkgname = hubutil::package_assemble_and_sign(
-        &pkgmeta,
-        access,
-        pkgmeta
-            .as_ref()
-            .parent()
-            .ok_or_else(|| anyhow::anyhow!("invalid package meta path"))?,
-        Some(target),
-    )?;
+    let outdir = pkgmeta
+        .as_ref()
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid package meta path"))?;
+    let pkgname = if opts.provenance || opts.sbom.is_some() {
+        let builder = format!("cdk {}", env!("CARGO_PKG_VERSION"));
+        let provenance = hubutil::provenance_from_git(outdir, &builder);
+        hubutil::package_assemble_and_sign_with_provenance(
+            &pkgmeta,
+            access,
+            outdir,
+            Some(target),
+            provenance,
+            opts.sbom.as_deref(),
+        )?
+    } else {
+        hubutil::package_assemble_and_sign(&pkgmeta, access, outdir, Some(target))?
+    };
     println!("Package {pkgname} created");
     Ok(pkgname)
 }
@@ -0,0 +1,93 @@
+//! Stable schemas of Fluvio resources
+//!
+//! Resources are flat representations of SC objects meant for infrastructure
+//! as code tools like Terraform and OpenTofu providers. They are served by the
+//! `/v1/resources` routes of the SC admin HTTP API and don't change when the
+//! internal specs do. Any breaking change bumps [`SCHEMA_VERSION`].
+//!
+//! Authorization policies are read by the SC from a file at startup, so they are
+//! deployed with the SC rather than managed as resources.
+
+mod schema;
+mod spu_group;
+mod topic;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+pub use schema::{Attribute, AttributeType, ResourceSchema};
+pub use spu_group::SpuGroupResource;
+pub use topic::TopicResource;
+
+/// version of the resource schemas
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ResourceError {
+    #[error("invalid {attribute}: {reason}")]
+    InvalidAttribute {
+        attribute: &'static str,
+        reason: String,
+    },
+    #[error("{name} can't be managed as a resource: {reason}")]
+    Unsupported { name: String, reason: String },
+}
+
+/// Conversion between a resource and the spec of the SC object backing it
+pub trait AdminResource: Serialize + DeserializeOwned + Sized {
+    type Spec;
+
+    fn schema() -> ResourceSchema;
+
+    fn from_spec(name: &str, spec: &Self::Spec) -> Result<Self, ResourceError>;
+
+    /// name and spec of the SC object
+    fn into_spec(self) -> Result<(String, Self::Spec), ResourceError>;
+}
+
+/// schemas of every resource
+pub fn schemas() -> Vec<ResourceSchema> {
+    vec![TopicResource::schema(), SpuGroupResource::schema()]
+}
+
+/// document describing every resource, served at `/v1/schemas`
+pub fn schemas_document() -> Value {
+    json!({
+        "version": SCHEMA_VERSION,
+        "resources": schemas(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas_match_resources() {
+        for schema in schemas() {
+            let json_schema = schema.json_schema();
+            assert_eq!(json_schema["required"][0], "name", "{}", schema.kind);
+            assert!(schema.attribute("name").unwrap().force_new);
+        }
+
+        // every attribute of the schema is a field of the resource
+        let topic = serde_json::to_value(TopicResource {
+            retention_secs: Some(1),
+            segment_size: Some(1),
+            max_partition_size: Some(1),
+            compression_type: Some("gzip".to_owned()),
+            ..Default::default()
+        })
+        .unwrap();
+        let schema = TopicResource::schema();
+        assert_eq!(topic.as_object().unwrap().len(), schema.attributes.len());
+        for attribute in &schema.attributes {
+            assert!(topic.get(attribute.name).is_some(), "{}", attribute.name);
+        }
+
+        let document = schemas_document();
+        assert_eq!(document["version"], SCHEMA_VERSION);
+        assert_eq!(document["resources"][1]["kind"], "spu_group");
+    }
+}
@@ -0,0 +1,112 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeType {
+    String,
+    Integer,
+    Bool,
+}
+
+/// Attribute of a resource, flat so it maps to a provider schema attribute
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub ty: AttributeType,
+    pub description: &'static str,
+    /// must be set by the user
+    pub required: bool,
+    /// changing it replaces the resource, as the SC can't update it in place
+    pub force_new: bool,
+    /// allowed values of string attributes, any value if empty
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub one_of: &'static [&'static str],
+}
+
+impl Attribute {
+    pub const fn required(
+        name: &'static str,
+        ty: AttributeType,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            ty,
+            description,
+            required: true,
+            force_new: true,
+            one_of: &[],
+        }
+    }
+
+    pub const fn optional(
+        name: &'static str,
+        ty: AttributeType,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            required: false,
+            ..Self::required(name, ty, description)
+        }
+    }
+
+    pub const fn one_of(mut self, values: &'static [&'static str]) -> Self {
+        self.one_of = values;
+        self
+    }
+
+    fn json_schema(&self) -> Value {
+        let ty = match self.ty {
+            AttributeType::String => "string",
+            AttributeType::Integer => "integer",
+            AttributeType::Bool => "boolean",
+        };
+        let mut schema = json!({ "type": ty, "description": self.description });
+        if !self.one_of.is_empty() {
+            schema["enum"] = json!(self.one_of);
+        }
+        schema
+    }
+}
+
+/// Schema of a resource kind
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ResourceSchema {
+    /// singular name, e.g. `topic`
+    pub kind: &'static str,
+    /// path segment of the resource in the admin HTTP API, e.g. `topics`
+    pub collection: &'static str,
+    pub description: &'static str,
+    pub attributes: Vec<Attribute>,
+}
+
+impl ResourceSchema {
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    /// JSON Schema of the resource, as used in OpenAPI documents
+    pub fn json_schema(&self) -> Value {
+        let mut properties = Map::new();
+        for attribute in &self.attributes {
+            properties.insert(attribute.name.to_owned(), attribute.json_schema());
+        }
+        let required: Vec<&str> = self
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.required)
+            .map(|attribute| attribute.name)
+            .collect();
+        json!({
+            "type": "object",
+            "description": self.description,
+            "required": required,
+            "properties": properties,
+            "additionalProperties": false,
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use fluvio_controlplane_metadata::spg::{SpuConfig, SpuGroupSpec, StorageConfig};
+
+use crate::{AdminResource, Attribute, AttributeType, ResourceError, ResourceSchema};
+
+/// Group of SPUs managed by the SC on Kubernetes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SpuGroupResource {
+    pub name: String,
+    pub replicas: u16,
+    #[serde(default)]
+    pub min_id: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rack: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_size: Option<String>,
+}
+
+impl AdminResource for SpuGroupResource {
+    type Spec = SpuGroupSpec;
+
+    fn schema() -> ResourceSchema {
+        use AttributeType::{Integer, String};
+
+        ResourceSchema {
+            kind: "spu_group",
+            collection: "spu-groups",
+            description: "Group of SPUs deployed by the SC",
+            attributes: vec![
+                Attribute::required("name", String, "name of the group"),
+                Attribute::required("replicas", Integer, "number of SPUs"),
+                Attribute::optional("min_id", Integer, "id of the first SPU of the group"),
+                Attribute::optional("rack", String, "rack of the SPUs"),
+                Attribute::optional(
+                    "storage_size",
+                    String,
+                    "size of the log volume of each SPU, e.g. 10Gi",
+                ),
+            ],
+        }
+    }
+
+    fn from_spec(name: &str, spec: &SpuGroupSpec) -> Result<Self, ResourceError> {
+        Ok(Self {
+            name: name.to_owned(),
+            replicas: spec.replicas,
+            min_id: spec.min_id,
+            rack: spec.spu_config.rack.clone(),
+            storage_size: spec
+                .spu_config
+                .storage
+                .as_ref()
+                .and_then(|storage| storage.size.clone()),
+        })
+    }
+
+    fn into_spec(self) -> Result<(String, SpuGroupSpec), ResourceError> {
+        if self.replicas == 0 {
+            return Err(ResourceError::InvalidAttribute {
+                attribute: "replicas",
+                reason: "must be at least 1".to_owned(),
+            });
+        }
+        let spec = SpuGroupSpec {
+            replicas: self.replicas,
+            min_id: self.min_id,
+            spu_config: SpuConfig {
+                rack: self.rack,
+                storage: self.storage_size.map(|size| StorageConfig {
+                    size: Some(size),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            autoscale: None,
+        };
+        Ok((self.name, spec))
+    }
+}
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use fluvio_controlplane_metadata::topic::{
+    CleanupPolicy, CompressionAlgorithm, SegmentBasedPolicy, TopicSpec, TopicStorageConfig,
+};
+
+use crate::{AdminResource, Attribute, AttributeType, ResourceError, ResourceSchema};
+
+const COMPRESSION_TYPES: &[&str] = &["none", "gzip", "snappy", "lz4", "any", "zstd"];
+
+/// Topic with computed replicas
+///
+/// Topics with assigned or mirror replicas, deduplication, validation, dedup windows
+/// or system topics can't be represented and are left to the CLI.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TopicResource {
+    pub name: String,
+    pub partitions: u32,
+    pub replication_factor: u32,
+    #[serde(default)]
+    pub ignore_rack_assignment: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_secs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment_size: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_partition_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_type: Option<String>,
+}
+
+impl AdminResource for TopicResource {
+    type Spec = TopicSpec;
+
+    fn schema() -> ResourceSchema {
+        use AttributeType::{Bool, Integer, String};
+
+        ResourceSchema {
+            kind: "topic",
+            collection: "topics",
+            description: "Topic with partitions assigned by the SC",
+            attributes: vec![
+                Attribute::required("name", String, "name of the topic"),
+                Attribute::required("partitions", Integer, "number of partitions"),
+                Attribute::required(
+                    "replication_factor",
+                    Integer,
+                    "number of replicas of each partition",
+                ),
+                Attribute::optional(
+                    "ignore_rack_assignment",
+                    Bool,
+                    "place replicas without considering racks",
+                ),
+                Attribute::optional(
+                    "retention_secs",
+                    Integer,
+                    "seconds records are retained, cluster default if not set",
+                ),
+                Attribute::optional("segment_size", Integer, "size of segments in bytes"),
+                Attribute::optional(
+                    "max_partition_size",
+                    Integer,
+                    "maximum size of a partition in bytes",
+                ),
+                Attribute::optional(
+                    "compression_type",
+                    String,
+                    "compression of records stored in the topic",
+                )
+                .one_of(COMPRESSION_TYPES),
+            ],
+        }
+    }
+
+    fn from_spec(name: &str, spec: &TopicSpec) -> Result<Self, ResourceError> {
+        let unsupported = |reason: &str| ResourceError::Unsupported {
+            name: name.to_owned(),
+            reason: reason.to_owned(),
+        };
+        if spec.is_system() {
+            return Err(unsupported("system topic"));
+        }
+        if spec.get_deduplication().is_some() {
+            return Err(unsupported("topic with deduplication"));
+        }
+        if spec.get_validation().is_some() {
+            return Err(unsupported("topic with validation"));
+        }
+        if spec.get_dedup_window().is_some() {
+            return Err(unsupported("topic with dedup window"));
+        }
+        if spec
+            .get_storage()
+            .is_some_and(TopicStorageConfig::is_ephemeral)
+        {
+            return Err(unsupported("ephemeral topic"));
+        }
+        if spec
+            .get_clean_policy()
+            .is_some_and(CleanupPolicy::is_compact)
+        {
+            return Err(unsupported("compacted topic"));
+        }
+        if spec
+            .get_storage()
+            .is_some_and(TopicStorageConfig::is_tiered)
+        {
+            return Err(unsupported("tiered topic"));
+        }
+        if !spec.is_computed() {
+            return Err(unsupported(spec.type_label()));
+        }
+
+        let storage = spec.get_storage();
+        Ok(Self {
+            name: name.to_owned(),
+            partitions: spec.partitions(),
+            replication_factor: spec.replication_factor().unwrap_or_default(),
+            ignore_rack_assignment: spec.ignore_rack_assignment(),
+            retention_secs: spec.get_clean_policy().map(CleanupPolicy::retention_secs),
+            segment_size: storage.and_then(|storage| storage.segment_size),
+            max_partition_size: storage.and_then(|storage| storage.max_partition_size),
+            compression_type: match spec.get_compression_type() {
+                CompressionAlgorithm::Any => None,
+                compression => Some(compression.to_string()),
+            },
+        })
+    }
+
+    fn into_spec(self) -> Result<(String, TopicSpec), ResourceError> {
+        let mut spec = TopicSpec::new_computed(
+            self.partitions,
+            self.replication_factor,
+            Some(self.ignore_rack_assignment),
+        );
+        if let Some(time_in_seconds) = self.retention_secs {
+            spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+                time_in_seconds,
+            }));
+        }
+        if self.segment_size.is_some() || self.max_partition_size.is_some() {
+            spec.set_storage(TopicStorageConfig {
+                segment_size: self.segment_size,
+                max_partition_size: self.max_partition_size,
+                ..Default::default()
+            });
+        }
+        if let Some(compression_type) = &self.compression_type {
+            let compression =
+                compression_type
+                    .parse()
+                    .map_err(|_| ResourceError::InvalidAttribute {
+                        attribute: "compression_type",
+                        reason: format!(
+                            "{compression_type} is not one of {}",
+                            COMPRESSION_TYPES.join(", ")
+                        ),
+                    })?;
+            spec.set_compression_type(compression);
+        }
+        Ok((self.name, spec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topic_round_trip() {
+        let resource: TopicResource = serde_json::from_str(
+            r#"{"name":"orders","partitions":3,"replication_factor":2,"retention_secs":3600,"compression_type":"zstd"}"#,
+        )
+        .expect("resource");
+        let (name, spec) = resource.clone().into_spec().expect("spec");
+        assert_eq!(name, "orders");
+        assert_eq!(spec.partitions(), 3);
+        assert_eq!(spec.retention_secs(), 3600);
+        assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Zstd);
+        assert_eq!(TopicResource::from_spec(&name, &spec).unwrap(), resource);
+    }
+
+    #[test]
+    fn topic_invalid() {
+        let resource = TopicResource {
+            name: "orders".to_owned(),
+            compression_type: Some("brotli".to_owned()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            resource.into_spec(),
+            Err(ResourceError::InvalidAttribute {
+                attribute: "compression_type",
+                ..
+            })
+        ));
+
+        let mut system = TopicSpec::new_computed(1, 1, None);
+        system.set_system(true);
+        assert!(TopicResource::from_spec("consumer-offsets", &system).is_err());
+
+        assert!(serde_json::from_str::<TopicResource>(
+            r#"{"name":"orders","partitions":1,"replication_factor":1,"retention":"1h"}"#
+        )
+        .is_err());
+    }
+}
@@ -3,6 +3,9 @@ mod error;
 
 pub mod root;
 pub mod x509;
+pub mod tls_proxy;
+#[cfg(unix)]
+pub mod mesh;
 
 pub use policy::*;
 pub use error::AuthError;
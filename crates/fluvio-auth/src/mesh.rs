@@ -0,0 +1,367 @@
+//!
+//! # Service Mesh Identity
+//!
+//! When a service mesh such as Istio or Linkerd owns mTLS, the SC receives plaintext
+//! from the sidecar and can't see the client certificate. The mesh proxy forwards the
+//! client identity in a PROXY protocol v2 header instead, either as the certificate
+//! common name (`PP2_SUBTYPE_SSL_CN`) or as an `x-forwarded-client-cert` value in the
+//! custom TLV [`XFCC_TLV`].
+//!
+//! [`start_mesh_proxy`] reads the header, binds the principal to its scopes and forwards
+//! the connection to the plaintext public endpoint, like the TLS proxy does with x509
+//! identities.
+//!
+//! Any peer able to reach the proxy could claim an identity, so headers are only read from
+//! loopback peers, such as a sidecar sharing the network of the pod, and from the peers
+//! trusted with [`MeshAuthenticator::with_trusted_peers`]. Other connections are closed.
+//!
+use std::io::{Error as IoError, ErrorKind};
+use std::net::{IpAddr, Shutdown};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future::{select, try_join, Either};
+use futures_util::io::{copy, AsyncReadExt};
+use tracing::{debug, error, info, warn};
+
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+use crate::x509::{AuthRequest, ScopeBindings, X509Authenticator};
+
+const SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+const CMD_PROXY: u8 = 0x01;
+const PP2_TYPE_SSL: u8 = 0x20;
+const PP2_SUBTYPE_SSL_CN: u8 = 0x22;
+/// custom TLV carrying the `x-forwarded-client-cert` value
+pub const XFCC_TLV: u8 = 0xE0;
+/// time given to the mesh proxy to send the PROXY protocol header
+const HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Identity forwarded by the mesh proxy
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MeshIdentity {
+    /// common name of the client certificate
+    pub common_name: Option<String>,
+    /// `x-forwarded-client-cert` value
+    pub xfcc: Option<String>,
+}
+
+impl MeshIdentity {
+    /// SPIFFE id or subject common name of the client, xfcc takes precedence
+    pub fn principal(&self) -> Option<String> {
+        self.xfcc
+            .as_deref()
+            .and_then(xfcc_principal)
+            .or_else(|| self.common_name.clone())
+    }
+
+    /// read a PROXY protocol v2 header from `stream`, a peer that doesn't send it within
+    /// 5 seconds is an error
+    pub async fn read_from(stream: &mut TcpStream) -> Result<Self, IoError> {
+        match select(
+            Box::pin(Self::read_header(stream)),
+            Box::pin(sleep(HEADER_TIMEOUT)),
+        )
+        .await
+        {
+            Either::Left((identity, _)) => identity,
+            Either::Right(_) => Err(IoError::new(
+                ErrorKind::TimedOut,
+                format!("no PROXY protocol header after {HEADER_TIMEOUT:?}"),
+            )),
+        }
+    }
+
+    async fn read_header(stream: &mut TcpStream) -> Result<Self, IoError> {
+        let mut header = [0; 16];
+        stream.read_exact(&mut header).await?;
+        if header[..12] != SIGNATURE || header[12] >> 4 != 2 {
+            return Err(invalid("expected PROXY protocol v2 header"));
+        }
+        let len = u16::from_be_bytes([header[14], header[15]]) as usize;
+        let mut payload = vec![0; len];
+        stream.read_exact(&mut payload).await?;
+        if header[12] & 0x0F != CMD_PROXY {
+            // LOCAL connections, such as health checks, carry no identity
+            return Ok(Self::default());
+        }
+        Self::decode(header[13], &payload)
+    }
+
+    fn decode(family: u8, payload: &[u8]) -> Result<Self, IoError> {
+        let addresses_len = match family >> 4 {
+            0x0 => 0,
+            0x1 => 12,
+            0x2 => 36,
+            0x3 => 216,
+            _ => return Err(invalid("unknown PROXY protocol address family")),
+        };
+        let tlvs = payload
+            .get(addresses_len..)
+            .ok_or_else(|| invalid("truncated PROXY protocol header"))?;
+
+        let mut identity = Self::default();
+        for (tlv_type, value) in tlvs_of(tlvs)? {
+            match tlv_type {
+                // client flags (1 byte) and verify result (4 bytes) precede the sub TLVs
+                PP2_TYPE_SSL if value.len() >= 5 => {
+                    for (sub_type, sub_value) in tlvs_of(&value[5..])? {
+                        if sub_type == PP2_SUBTYPE_SSL_CN {
+                            identity.common_name = Some(utf8(sub_value)?);
+                        }
+                    }
+                }
+                XFCC_TLV => identity.xfcc = Some(utf8(value)?),
+                _ => {}
+            }
+        }
+        Ok(identity)
+    }
+}
+
+fn tlvs_of(mut buf: &[u8]) -> Result<Vec<(u8, &[u8])>, IoError> {
+    let mut tlvs = Vec::new();
+    while !buf.is_empty() {
+        if buf.len() < 3 {
+            return Err(invalid("truncated PROXY protocol TLV"));
+        }
+        let len = u16::from_be_bytes([buf[1], buf[2]]) as usize;
+        let value = buf
+            .get(3..3 + len)
+            .ok_or_else(|| invalid("truncated PROXY protocol TLV"))?;
+        tlvs.push((buf[0], value));
+        buf = &buf[3 + len..];
+    }
+    Ok(tlvs)
+}
+
+/// principal of the first element of a `x-forwarded-client-cert` value.
+/// The URI (SPIFFE id) is preferred over the common name of the subject.
+pub fn xfcc_principal(xfcc: &str) -> Option<String> {
+    let element = split_unquoted(xfcc, ',').into_iter().next()?;
+    let mut uri = None;
+    let mut common_name = None;
+    for pair in split_unquoted(element, ';') {
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+        match key.trim().to_ascii_lowercase().as_str() {
+            "uri" if !value.is_empty() => uri = Some(value.to_owned()),
+            "subject" => {
+                common_name = split_unquoted(value, ',')
+                    .into_iter()
+                    .find_map(|rdn| rdn.trim().strip_prefix("CN="))
+                    .map(str::to_owned)
+            }
+            _ => {}
+        }
+    }
+    uri.or(common_name)
+}
+
+/// split on `separator` outside of double quotes
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (index, c) in value.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                parts.push(&value[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+fn utf8(value: &[u8]) -> Result<String, IoError> {
+    String::from_utf8(value.to_vec()).map_err(|_| invalid("identity is not valid utf8"))
+}
+
+fn invalid(msg: &str) -> IoError {
+    IoError::new(ErrorKind::InvalidData, msg.to_owned())
+}
+
+/// Binds mesh identities to scopes
+#[derive(Debug, Default)]
+pub struct MeshAuthenticator {
+    scope_bindings: ScopeBindings,
+    trusted_peers: Vec<IpAddr>,
+}
+
+impl MeshAuthenticator {
+    /// scopes are read from the same bindings file as x509 identities
+    pub fn new(scope_binding_file_path: Option<&Path>) -> Result<Self, anyhow::Error> {
+        let scope_bindings = match scope_binding_file_path {
+            Some(path) => ScopeBindings::load(path)?,
+            None => ScopeBindings::default(),
+        };
+        Ok(Self {
+            scope_bindings,
+            trusted_peers: vec![],
+        })
+    }
+
+    /// peers besides loopback allowed to send identities, e.g. a proxy of another host
+    pub fn with_trusted_peers(mut self, trusted_peers: Vec<IpAddr>) -> Self {
+        self.trusted_peers = trusted_peers;
+        self
+    }
+
+    /// only trusted peers can assert identities in PROXY protocol headers
+    fn is_trusted(&self, peer: IpAddr) -> bool {
+        peer.is_loopback() || self.trusted_peers.contains(&peer)
+    }
+
+    async fn authenticate(
+        &self,
+        inbound: &mut TcpStream,
+        target: &TcpStream,
+    ) -> Result<(), IoError> {
+        let principal = MeshIdentity::read_from(inbound)
+            .await?
+            .principal()
+            .ok_or_else(|| IoError::new(ErrorKind::PermissionDenied, "no mesh identity"))?;
+        let scopes = self.scope_bindings.get_scopes(&principal);
+        debug!(%principal, ?scopes, "mesh identity");
+        let success = X509Authenticator::send_authorization_request(
+            target,
+            AuthRequest::new(principal, scopes),
+        )
+        .await?;
+        if success {
+            Ok(())
+        } else {
+            Err(IoError::new(ErrorKind::PermissionDenied, "not authorized"))
+        }
+    }
+}
+
+/// accept mesh connections on `addr` and forward them to `target`
+pub async fn start_mesh_proxy(
+    addr: &str,
+    target: String,
+    authenticator: MeshAuthenticator,
+) -> Result<(), IoError> {
+    let listener = TcpListener::bind(addr).await?;
+    info!(addr, %target, "mesh proxy started");
+    let authenticator = Arc::new(authenticator);
+    spawn(async move {
+        loop {
+            let (inbound, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    error!("mesh proxy accept failed: {err}");
+                    continue;
+                }
+            };
+            if !authenticator.is_trusted(peer.ip()) {
+                warn!(%peer, "mesh proxy connection from untrusted peer closed");
+                continue;
+            }
+            let target = target.clone();
+            let authenticator = authenticator.clone();
+            spawn(async move {
+                if let Err(err) = forward(inbound, &target, &authenticator).await {
+                    debug!(%peer, "mesh proxy connection error: {err}");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+async fn forward(
+    mut inbound: TcpStream,
+    target: &str,
+    authenticator: &MeshAuthenticator,
+) -> Result<(), IoError> {
+    let outbound = TcpStream::connect(target).await?;
+    authenticator.authenticate(&mut inbound, &outbound).await?;
+
+    let upstream = async {
+        copy(inbound.clone(), &mut outbound.clone()).await?;
+        outbound.shutdown(Shutdown::Write)
+    };
+    let downstream = async {
+        copy(outbound.clone(), &mut inbound.clone()).await?;
+        inbound.shutdown(Shutdown::Write)
+    };
+    try_join(upstream, downstream).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tlv(tlv_type: u8, value: &[u8]) -> Vec<u8> {
+        let mut out = vec![tlv_type];
+        out.extend((value.len() as u16).to_be_bytes());
+        out.extend_from_slice(value);
+        out
+    }
+
+    #[test]
+    fn test_decode_proxy_tlvs() {
+        let mut ssl = vec![0x07, 0, 0, 0, 0];
+        ssl.extend(tlv(PP2_SUBTYPE_SSL_CN, b"client-a"));
+
+        let mut payload = vec![0; 12]; // TCP over IPv4 addresses and ports
+        payload.extend(tlv(0x04, b"ignored"));
+        payload.extend(tlv(PP2_TYPE_SSL, &ssl));
+
+        let identity = MeshIdentity::decode(0x11, &payload).expect("decode");
+        assert_eq!(identity.common_name.as_deref(), Some("client-a"));
+        assert_eq!(identity.principal().as_deref(), Some("client-a"));
+
+        payload.extend(tlv(
+            XFCC_TLV,
+            b"By=spiffe://cluster.local/ns/fluvio/sa/fluvio;URI=spiffe://cluster.local/ns/apps/sa/producer",
+        ));
+        let identity = MeshIdentity::decode(0x11, &payload).expect("decode");
+        assert_eq!(
+            identity.principal().as_deref(),
+            Some("spiffe://cluster.local/ns/apps/sa/producer")
+        );
+
+        assert!(MeshIdentity::decode(0x11, &payload[..8]).is_err());
+        assert!(MeshIdentity::decode(0x11, &payload[..payload.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_trusted_peers() {
+        let authenticator = MeshAuthenticator::default();
+        assert!(authenticator.is_trusted("127.0.0.6".parse().unwrap()));
+        assert!(authenticator.is_trusted("::1".parse().unwrap()));
+        assert!(!authenticator.is_trusted("10.0.0.7".parse().unwrap()));
+
+        let authenticator = authenticator.with_trusted_peers(vec!["10.0.0.7".parse().unwrap()]);
+        assert!(authenticator.is_trusted("10.0.0.7".parse().unwrap()));
+        assert!(!authenticator.is_trusted("10.0.0.8".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_xfcc_principal() {
+        assert_eq!(
+            xfcc_principal(
+                r#"Hash=abc;Subject="CN=producer,OU=apps,O=Example";URI=,By=spiffe://other"#
+            )
+            .as_deref(),
+            Some("producer")
+        );
+        assert_eq!(
+            xfcc_principal("By=spiffe://a;URI=spiffe://cluster.local/ns/apps/sa/b").as_deref(),
+            Some("spiffe://cluster.local/ns/apps/sa/b")
+        );
+        assert_eq!(xfcc_principal("Hash=abc"), None);
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use fluvio_controlplane_metadata::acl::AclPermission;
 use fluvio_controlplane_metadata::extended::ObjectType;
 use fluvio_socket::FluvioSocket;
 
@@ -35,6 +36,16 @@ pub trait AuthContext: Debug + Send + Sync + 'static {
         action: InstanceAction,
         key: &str,
     ) -> Result<bool, AuthError>;
+
+    /// check if records of a topic can be produced, consumed or the topic administered.
+    /// Contexts without per topic rules allow it.
+    async fn allow_topic_action(
+        &self,
+        _topic: &str,
+        _permission: AclPermission,
+    ) -> Result<bool, AuthError> {
+        Ok(true)
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,84 @@
+//!
+//! # TLS proxy
+//!
+//! Terminates TLS on `addr` and forwards the connections to the plaintext public endpoint.
+//! Each handshake uses the current acceptor of a [`ReloadableTlsAcceptor`], so renewed
+//! certificates apply to new connections without dropping the established ones.
+//!
+use std::io::Error as IoError;
+use std::sync::Arc;
+
+use futures_util::future::try_join;
+use futures_util::io::{copy, AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, error, info};
+
+use flv_tls_proxy::authenticator::Authenticator;
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::task::spawn;
+use fluvio_future::openssl::DefaultServerTlsStream;
+use fluvio_socket::ReloadableTlsAcceptor;
+
+/// accept TLS connections on `addr` and forward them to `target`, principals of client
+/// certificates are sent by the `authenticator` if any
+pub async fn start_tls_proxy(
+    addr: &str,
+    acceptor: ReloadableTlsAcceptor,
+    target: String,
+    authenticator: Option<Box<dyn Authenticator>>,
+) -> Result<(), IoError> {
+    let listener = TcpListener::bind(addr).await?;
+    info!(addr, %target, "tls proxy started");
+    let authenticator: Option<Arc<dyn Authenticator>> = authenticator.map(Arc::from);
+    spawn(async move {
+        loop {
+            let (inbound, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    error!("tls proxy accept failed: {err}");
+                    continue;
+                }
+            };
+            let tls_acceptor = acceptor.current();
+            let target = target.clone();
+            let authenticator = authenticator.clone();
+            spawn(async move {
+                let result = match tls_acceptor.accept(inbound).await {
+                    Ok(tls_stream) => forward(tls_stream, &target, authenticator.as_deref()).await,
+                    Err(err) => Err(IoError::other(format!("tls handshake failed: {err}"))),
+                };
+                if let Err(err) = result {
+                    debug!(%peer, "tls proxy connection error: {err}");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+async fn forward(
+    inbound: DefaultServerTlsStream,
+    target: &str,
+    authenticator: Option<&dyn Authenticator>,
+) -> Result<(), IoError> {
+    let outbound = TcpStream::connect(target).await?;
+    if let Some(authenticator) = authenticator {
+        if !authenticator.authenticate(&inbound, &outbound).await? {
+            return Err(IoError::new(
+                std::io::ErrorKind::PermissionDenied,
+                "not authorized",
+            ));
+        }
+    }
+
+    let (mut inbound_read, mut inbound_write) = inbound.split();
+    let upstream = async {
+        copy(&mut inbound_read, &mut outbound.clone()).await?;
+        outbound.shutdown(std::net::Shutdown::Write)
+    };
+    let downstream = async {
+        copy(&mut outbound.clone(), &mut inbound_write).await?;
+        inbound_write.close().await
+    };
+    try_join(upstream, downstream).await?;
+    Ok(())
+}
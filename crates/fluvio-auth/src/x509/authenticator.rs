@@ -13,8 +13,8 @@ use flv_tls_proxy::authenticator::Authenticator;
 
 use super::request::AuthRequest;
 
-#[derive(Debug)]
-struct ScopeBindings(HashMap<String, Vec<String>>);
+#[derive(Debug, Default)]
+pub(crate) struct ScopeBindings(HashMap<String, Vec<String>>);
 
 impl ScopeBindings {
     pub fn load(scope_binding_file_path: &Path) -> Result<Self, Error> {
@@ -48,7 +48,7 @@ impl X509Authenticator {
         }
     }
 
-    async fn send_authorization_request(
+    pub(crate) async fn send_authorization_request(
         tcp_stream: &TcpStream,
         authorization_request: AuthRequest,
     ) -> Result<bool, IoError> {
@@ -72,6 +72,9 @@ impl X509Authenticator {
                     | fluvio_socket::SocketError::SocketStale => {
                         IoError::new(IoErrorKind::BrokenPipe, "connection closed")
                     }
+                    err @ fluvio_socket::SocketError::CircuitOpen { .. } => {
+                        IoError::new(IoErrorKind::NotConnected, err.to_string())
+                    }
                 })?;
 
         Ok(response.success)
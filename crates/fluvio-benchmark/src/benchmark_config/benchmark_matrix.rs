@@ -36,6 +36,14 @@ pub struct FluvioProducerConfig {
     pub compression: Vec<Compression>,
     pub isolation: Vec<Isolation>,
     pub delivery_semantic: Vec<DeliverySemanticStrategy>, // TODO
+    /// SmartModule applied by the SPU to the produced records, null for none.
+    /// Records must not be filtered out, the consumers wait for all of them.
+    #[serde(default = "no_smartmodule")]
+    pub smartmodule: Vec<Option<String>>,
+}
+
+fn no_smartmodule() -> Vec<Option<String>> {
+    vec![None]
 }
 
 impl Default for FluvioProducerConfig {
@@ -50,6 +58,7 @@ impl Default for FluvioProducerConfig {
             delivery_semantic: vec![DeliverySemanticStrategy::AtLeastOnce(
                 AtLeastOnceStrategy::Exponential,
             )],
+            smartmodule: no_smartmodule(),
         }
     }
 }
@@ -74,8 +83,6 @@ impl Default for FluvioConsumerConfig {
 pub struct FluvioTopicConfig {
     pub num_partitions: Vec<u64>,
     // TODO
-    // pub use_smart_module: Vec<bool>,
-    // TODO
     // IgnoreRack
     // TODO
     // pub num_replicas: Vec<u64>,
@@ -185,6 +192,9 @@ impl BenchmarkMatrix {
             .cross_iterate(&self.producer_config.delivery_semantic, |v, b| {
                 b.producer_delivery_semantic(v.into());
             })
+            .cross_iterate(&self.producer_config.smartmodule, |v, b| {
+                b.producer_smartmodule(v);
+            })
             // Fluvio Consumer
             .cross_iterate(&self.consumer_config.max_bytes, |v, b| {
                 b.consumer_max_bytes(v);
@@ -11,6 +11,7 @@ use fluvio::{Compression, Isolation, DeliverySemantic};
 use self::benchmark_matrix::{RecordKeyAllocationStrategy, SharedConfig};
 
 pub mod benchmark_matrix;
+pub mod scenario;
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 
 pub struct Millis(u64);
@@ -60,6 +61,7 @@ pub struct BenchmarkConfig {
     pub producer_compression: Compression,
     pub producer_isolation: Isolation,
     pub producer_delivery_semantic: DeliverySemantic,
+    pub producer_smartmodule: Option<String>,
     pub consumer_max_bytes: u64,
     pub consumer_isolation: Isolation,
     pub num_concurrent_producer_workers: u64,
@@ -68,8 +70,6 @@ pub struct BenchmarkConfig {
     pub num_partitions: u64,
     pub record_size: u64,
     pub record_key_allocation_strategy: RecordKeyAllocationStrategy,
-    // TODO
-    // pub use_smart_module: Vec<bool>,
 }
 
 impl Eq for BenchmarkConfig {}
@@ -89,6 +89,7 @@ impl PartialEq for BenchmarkConfig {
             && self.producer_compression == other.producer_compression
             && self.producer_delivery_semantic == other.producer_delivery_semantic
             && self.producer_isolation == other.producer_isolation
+            && self.producer_smartmodule == other.producer_smartmodule
             && self.consumer_max_bytes == other.consumer_max_bytes
             && self.consumer_isolation == other.consumer_isolation
             && self.num_concurrent_producer_workers == other.num_concurrent_producer_workers
@@ -115,6 +116,7 @@ impl Hash for BenchmarkConfig {
         self.producer_compression.hash(state);
         self.producer_isolation.hash(state);
         self.producer_delivery_semantic.hash(state);
+        self.producer_smartmodule.hash(state);
         self.consumer_max_bytes.hash(state);
         self.consumer_isolation.hash(state);
         self.num_concurrent_producer_workers.hash(state);
@@ -0,0 +1,40 @@
+use std::{collections::BTreeMap, fs::File, path::Path};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::stats::Variable;
+use super::benchmark_matrix::BenchmarkMatrix;
+
+/// Matrices run together and the regressions tolerated against the baseline.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BenchmarkScenario {
+    pub matrices: Vec<BenchmarkMatrix>,
+    /// Max regression of each variable in percent, variables without threshold are not checked
+    #[serde(default)]
+    pub thresholds: BTreeMap<Variable, f64>,
+}
+
+impl BenchmarkScenario {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("unable to open scenario {}", path.display()))?;
+        serde_yaml::from_reader(file)
+            .with_context(|| format!("invalid scenario {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenario_from_file() {
+        let scenario =
+            BenchmarkScenario::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios/ci.yaml"))
+                .expect("scenario");
+        assert!(!scenario.matrices.is_empty());
+        assert!(scenario.thresholds.contains_key(&Variable::Latency));
+    }
+}
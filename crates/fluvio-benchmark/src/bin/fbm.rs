@@ -1,14 +1,15 @@
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
     mem,
     time::Duration,
 };
 
 use clap::{arg, Parser};
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 
 use fluvio_cli_common::install::fluvio_base_dir;
 use fluvio_future::{task::run_block_on, sync::Mutex, future::timeout};
@@ -21,6 +22,7 @@ use fluvio_benchmark::{
             FluvioProducerConfig, FluvioConsumerConfig, FluvioTopicConfig, BenchmarkLoadConfig,
             DeliverySemanticStrategy, AtLeastOnceStrategy,
         },
+        scenario::BenchmarkScenario,
         Seconds, Millis,
     },
     benchmark_driver::BenchmarkDriver,
@@ -38,17 +40,24 @@ fn main() -> Result<()> {
     }
 
     // TODO accept directory of files.
-    let matrices = if args.test_cluster {
-        test_configs()
+    let (matrices, thresholds) = if args.test_cluster {
+        (test_configs(), BTreeMap::new())
+    } else if let Some(path) = &args.scenario {
+        let scenario = BenchmarkScenario::from_file(path)?;
+        (scenario.matrices, scenario.thresholds)
     } else {
         match args.config {
-            Some(path) => get_config_from_file(&path),
-            None => default_configs(),
+            Some(path) => (get_config_from_file(&path), BTreeMap::new()),
+            None => (default_configs(), BTreeMap::new()),
         }
     };
 
     let all_stats = Arc::new(Mutex::new(AllStats::default()));
-    let previous = load_previous_stats();
+    let previous = match &args.baseline {
+        Some(path) => Some(load_stats(path)?),
+        None => load_previous_stats(),
+    };
+    let mut regressions = vec![];
 
     println!("# Fluvio Benchmark Results");
     for matrix in matrices {
@@ -74,16 +83,39 @@ fn main() -> Result<()> {
                         .map(|a| println!("{}", a.compare_stats(&config, other))),
                 );
                 println!();
+                let found = run_block_on(
+                    all_stats
+                        .lock()
+                        .map(|a| a.regressions(&config, other, &thresholds)),
+                );
+                regressions.extend(found.into_iter().map(|regression| {
+                    format!("{}: Iteration {i}: {regression}", config.matrix_name)
+                }));
             }
         }
     }
 
     let mut all_stats = run_block_on(take_stats(all_stats));
 
+    if let Some(path) = &args.save_baseline {
+        write_stats_to(&all_stats, path)?;
+    }
     if let Some(previous) = previous {
         all_stats.merge(&previous)
     }
-    write_stats(all_stats)
+    write_stats_to(&all_stats, &historic_run_path()?)?;
+
+    if !regressions.is_empty() {
+        println!("## Regressions");
+        for regression in &regressions {
+            println!("- {regression}");
+        }
+        return Err(anyhow!(
+            "{} benchmark regressions above thresholds",
+            regressions.len()
+        ));
+    }
+    Ok(())
 }
 
 async fn take_stats(all_stats: AllStatsSync) -> AllStats {
@@ -105,20 +137,25 @@ fn historic_run_path() -> Result<PathBuf> {
 }
 
 fn load_previous_stats() -> Option<AllStats> {
-    let mut file = File::open(historic_run_path().ok()?).ok()?;
+    load_stats(&historic_run_path().ok()?).ok()
+}
+
+fn load_stats(path: &Path) -> Result<AllStats> {
+    let mut file = File::open(path)
+        .with_context(|| format!("unable to open benchmark stats {}", path.display()))?;
     let mut buffer: Vec<u8> = Vec::new();
-    file.read_to_end(&mut buffer).ok()?;
-    AllStats::decode(&buffer).ok()
+    file.read_to_end(&mut buffer)?;
+    Ok(AllStats::decode(&buffer)?)
 }
 
-fn write_stats(stats: AllStats) -> Result<()> {
+fn write_stats_to(stats: &AllStats, path: &Path) -> Result<()> {
     let encoded = stats.encode();
 
     let mut file = std::fs::OpenOptions::new()
         .write(true)
         .create(true)
         .truncate(true)
-        .open(historic_run_path()?)
+        .open(path)
         .map_err(|e| BenchmarkError::ErrorWithExplanation(format!("{e:?}")))?;
     file.write(&encoded)
         .map_err(|e| BenchmarkError::ErrorWithExplanation(format!("{e:?}")))?;
@@ -147,6 +184,7 @@ fn print_example_config() {
             ],
             isolation: vec![Isolation::ReadUncommitted, Isolation::ReadCommitted],
             delivery_semantic: vec![DeliverySemanticStrategy::AtMostOnce],
+            smartmodule: vec![None, Some("my-map".to_string())],
         },
         consumer_config: FluvioConsumerConfig {
             max_bytes: vec![64000],
@@ -235,6 +273,7 @@ fn default_configs() -> Vec<BenchmarkMatrix> {
             delivery_semantic: vec![DeliverySemanticStrategy::AtLeastOnce(
                 AtLeastOnceStrategy::Exponential,
             )],
+            smartmodule: vec![None],
         },
         consumer_config: FluvioConsumerConfig {
             max_bytes: vec![64000],
@@ -268,4 +307,17 @@ struct Args {
     /// Run a suite of tests to ensure fluvio is behaving as expected
     #[arg(short, long, exclusive(true))]
     test_cluster: bool,
+
+    /// Path to a scenario file with the matrices to run and the regression thresholds.
+    /// Exits with an error when a variable regressed by more than its threshold
+    #[arg(short, long)]
+    scenario: Option<PathBuf>,
+
+    /// Stats to compare the results with, instead of the previous run
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Save the stats of this run, to be used as baseline of later runs
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
 }
@@ -3,7 +3,10 @@ use std::time::Instant;
 use async_channel::Sender;
 use anyhow::Result;
 
-use fluvio::{TopicProducerPool, Fluvio, RecordKey, TopicProducerConfigBuilder};
+use fluvio::{
+    TopicProducerPool, Fluvio, RecordKey, TopicProducerConfigBuilder, SmartModuleInvocation,
+    SmartModuleInvocationWasm, SmartModuleKind, SmartModuleContextData,
+};
 
 use crate::{
     benchmark_config::{
@@ -29,6 +32,15 @@ impl ProducerWorker {
     ) -> Result<Self> {
         let fluvio = Fluvio::connect().await?;
 
+        let smartmodules = config
+            .producer_smartmodule
+            .iter()
+            .map(|name| SmartModuleInvocation {
+                wasm: SmartModuleInvocationWasm::Predefined(name.clone()),
+                kind: SmartModuleKind::Generic(SmartModuleContextData::None),
+                params: Default::default(),
+            })
+            .collect();
         let fluvio_config = TopicProducerConfigBuilder::default()
             .batch_size(config.producer_batch_size as usize)
             .batch_queue_size(config.producer_queue_size as usize)
@@ -38,6 +50,7 @@ impl ProducerWorker {
             .timeout(config.producer_server_timeout)
             .isolation(config.producer_isolation)
             .delivery_semantic(config.producer_delivery_semantic)
+            .smartmodules(smartmodules)
             .build()
             .map_err(|e| {
                 BenchmarkError::ErrorWithExplanation(format!("Fluvio topic config error: {e:?}"))
@@ -93,9 +106,12 @@ impl ProducerWorker {
             self.fluvio_producer.send(record.key, record.data).await?;
         }
         self.fluvio_producer.flush().await?;
+        let flush_time = Instant::now();
+        let ack_latency = self.fluvio_producer.metrics().producer_ack_latency().take();
         self.tx_to_stats_collector
             .send(StatsCollectorMessage::ProducerFlushed {
-                flush_time: Instant::now(),
+                flush_time,
+                ack_latency,
             })
             .await?;
         Ok(())
@@ -48,6 +48,33 @@ impl AllStats {
         }
     }
 
+    /// Variables of `config` worse than in `baseline` by more than their threshold.
+    /// Variables without threshold are not checked.
+    pub fn regressions(
+        &self,
+        config: &BenchmarkConfig,
+        baseline: &AllStats,
+        thresholds: &BTreeMap<Variable, f64>,
+    ) -> Vec<Regression> {
+        let (Some(stats), Some(baseline_stats)) = (self.0.get(config), baseline.0.get(config))
+        else {
+            return vec![];
+        };
+        thresholds
+            .iter()
+            .filter_map(|(variable, threshold)| {
+                let (samples, baseline_samples) =
+                    stats.paired_samples(baseline_stats, variable, config.num_samples)?;
+                let percent = variable.regression_percent(&samples, &baseline_samples)?;
+                (percent > *threshold).then_some(Regression {
+                    variable: *variable,
+                    percent,
+                    threshold: *threshold,
+                })
+            })
+            .collect()
+    }
+
     /// Merges the maps of config -> stats, giving priority to self in the case of duplicate
     /// configs
     pub fn merge(&mut self, other: &AllStats) {
@@ -75,6 +102,21 @@ impl AllStats {
             }
             md.push_str("**Per Record E2E Latency**\n\n");
             md.push_str(&mk_md_table_from_yaml(&latency_yaml, &None));
+            if let Some(values) = stats.data.get(&Variable::ProduceAckLatency) {
+                let mut hist: Histogram<u64> = Histogram::new(HIST_PRECISION).unwrap();
+                for v in values.iter() {
+                    hist += *v;
+                }
+                let mut ack_yaml = "- Variable: Produce Ack Latency\n".to_string();
+                for percentile in [0.5, 0.9, 0.99, 0.999, 1.0] {
+                    ack_yaml.push_str(&format!(
+                        "  p{percentile:5.3}: {}\n",
+                        Variable::ProduceAckLatency.format(hist.value_at_quantile(percentile))
+                    ));
+                }
+                md.push_str("\n\n**Produce Request <-> Acknowledgement Latency**\n\n");
+                md.push_str(&mk_md_table_from_yaml(&ack_yaml, &None));
+            }
             let mut throughput_yaml = String::new();
             for (variable, description) in [
                 (Variable::ProducerThroughput, "First Produced Message <-> Producer Flush Complete"),
@@ -146,6 +188,13 @@ impl AllStats {
         let combined_time = last_consume_time.unwrap() - first_produce_time.unwrap();
 
         self.record_data(config, Variable::Latency, latency);
+        if !data.ack_latency.is_empty() {
+            self.record_data(
+                config,
+                Variable::ProduceAckLatency,
+                data.ack_latency.clone(),
+            );
+        }
         self.record_data(
             config,
             Variable::ProducerThroughput,
@@ -183,33 +232,11 @@ impl BenchmarkStats {
     pub fn compare(&self, other: &BenchmarkStats, config: &BenchmarkConfig) -> String {
         let mut md = String::new();
         let mut yaml = String::new();
-        for (variable, samples) in self.data.iter() {
+        for variable in self.data.keys() {
             yaml.push_str(&format!("- Variable: {variable}\n"));
-            if let Some(other_samples) = other.data.get(variable) {
-                let (samples, other_samples) = if samples.len() == config.num_samples {
-                    let samples: Vec<f64> = samples.iter().map(|x| *x as f64).collect();
-                    let other_samples: Vec<f64> = other_samples.iter().map(|x| *x as f64).collect();
-                    (samples, other_samples)
-                } else {
-                    let items_per_sample = samples.len() / config.num_samples;
-                    let samples: Vec<f64> = (0..config.num_samples)
-                        .map(|i| {
-                            *samples[i * items_per_sample..(i + 1) * items_per_sample]
-                                .iter()
-                                .max()
-                                .unwrap() as f64
-                        })
-                        .collect();
-                    let other_samples: Vec<f64> = (0..config.num_samples)
-                        .map(|i| {
-                            *other_samples[i * items_per_sample..(i + 1) * items_per_sample]
-                                .iter()
-                                .max()
-                                .unwrap() as f64
-                        })
-                        .collect();
-                    (samples, other_samples)
-                };
+            if let Some((samples, other_samples)) =
+                self.paired_samples(other, variable, config.num_samples)
+            {
                 match variable.compare(&samples, &other_samples) {
                     CompareResult::Better {
                         previous,
@@ -260,6 +287,37 @@ impl BenchmarkStats {
         md
     }
 
+    /// samples of the variable in both stats, the max of each sample when a sample has many values
+    fn paired_samples(
+        &self,
+        other: &BenchmarkStats,
+        variable: &Variable,
+        num_samples: usize,
+    ) -> Option<(Vec<f64>, Vec<f64>)> {
+        let samples = self.data.get(variable)?;
+        let other_samples = other
+            .data
+            .get(variable)
+            .filter(|other_samples| other_samples.len() == samples.len())?;
+        if samples.len() == num_samples {
+            let samples: Vec<f64> = samples.iter().map(|x| *x as f64).collect();
+            let other_samples: Vec<f64> = other_samples.iter().map(|x| *x as f64).collect();
+            return Some((samples, other_samples));
+        }
+        let items_per_sample = samples.len() / num_samples;
+        let sample_max = |values: &[u64]| -> Vec<f64> {
+            (0..num_samples)
+                .map(|i| {
+                    *values[i * items_per_sample..(i + 1) * items_per_sample]
+                        .iter()
+                        .max()
+                        .unwrap() as f64
+                })
+                .collect()
+        };
+        Some((sample_max(samples), sample_max(other_samples)))
+    }
+
     pub fn new(config: &BenchmarkConfig) -> Self {
         Self {
             data: Default::default(),
@@ -268,12 +326,32 @@ impl BenchmarkStats {
     }
 }
 
+/// A variable worse than in the baseline by more than its threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regression {
+    pub variable: Variable,
+    /// how much worse than the baseline, in percent
+    pub percent: f64,
+    pub threshold: f64,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is {:.1}% worse than the baseline, threshold is {}%",
+            self.variable, self.percent, self.threshold
+        )
+    }
+}
+
 #[derive(Copy, Clone, Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Variable {
     Latency,
     ProducerThroughput,
     ConsumerThroughput,
     CombinedThroughput,
+    ProduceAckLatency,
 }
 impl Display for Variable {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -282,6 +360,7 @@ impl Display for Variable {
             Variable::ProducerThroughput => write!(f, "Producer Throughput"),
             Variable::ConsumerThroughput => write!(f, "Consumer Throughput"),
             Variable::CombinedThroughput => write!(f, "Combined Throughput"),
+            Variable::ProduceAckLatency => write!(f, "Produce Ack Latency"),
         }
     }
 }
@@ -301,9 +380,19 @@ impl Variable {
         }
     }
 
+    /// How much worse the variable is than the baseline in percent, if it's significantly worse
+    pub fn regression_percent(&self, samples: &[f64], baseline: &[f64]) -> Option<f64> {
+        match self.compare(samples, baseline) {
+            CompareResult::Worse { previous, next, .. } if previous > 0.0 => {
+                Some((next - previous).abs() / previous * 100.0)
+            }
+            _ => None,
+        }
+    }
+
     fn greater(&self, a_mean: f64, b_mean: f64, p_value: f64) -> CompareResult {
         match self {
-            Variable::Latency => CompareResult::Worse {
+            Variable::Latency | Variable::ProduceAckLatency => CompareResult::Worse {
                 previous: b_mean,
                 next: a_mean,
                 p_value,
@@ -327,7 +416,7 @@ impl Variable {
     }
     fn less(&self, a_mean: f64, b_mean: f64, p_value: f64) -> CompareResult {
         match self {
-            Variable::Latency => CompareResult::Better {
+            Variable::Latency | Variable::ProduceAckLatency => CompareResult::Better {
                 previous: b_mean,
                 next: a_mean,
                 p_value,
@@ -352,7 +441,9 @@ impl Variable {
 
     fn format(&self, v: u64) -> String {
         match self {
-            Variable::Latency => format!("{:>9?}", Duration::from_micros(v)),
+            Variable::Latency | Variable::ProduceAckLatency => {
+                format!("{:>9?}", Duration::from_micros(v))
+            }
             Variable::ProducerThroughput => format!("{:9.3}mb/s", v as f64 / 1000000.0),
             Variable::ConsumerThroughput => format!("{:9.3}mb/s", v as f64 / 1000000.0),
             Variable::CombinedThroughput => format!("{:9.3}mb/s", v as f64 / 1000000.0),
@@ -473,10 +564,32 @@ impl Display for TTestResult {
 #[cfg(test)]
 mod tests {
 
-    use crate::stats::TTestResult;
+    use crate::stats::{TTestResult, Variable};
 
     use super::two_sample_t_test;
 
+    #[test]
+    fn test_regression_percent() {
+        let baseline = [100.0, 101.0, 99.0, 100.0, 102.0];
+        let doubled = [200.0, 201.0, 199.0, 200.0, 202.0];
+
+        let latency = Variable::Latency.regression_percent(&doubled, &baseline);
+        assert!(latency.is_some_and(|percent| (percent - 99.6).abs() < 0.1));
+
+        // higher throughput is not a regression
+        assert_eq!(
+            Variable::ProducerThroughput.regression_percent(&doubled, &baseline),
+            None
+        );
+        assert!(Variable::ProducerThroughput
+            .regression_percent(&baseline, &doubled)
+            .is_some());
+        assert_eq!(
+            Variable::Latency.regression_percent(&baseline, &baseline),
+            None
+        );
+    }
+
     #[test]
     fn test_two_sample_t_test() {
         // Test cases done using comparisons with results from wolframalpha
@@ -4,6 +4,7 @@ use std::{
 };
 
 use async_channel::{Receiver, Sender};
+use hdrhistogram::Histogram;
 use tracing::debug;
 use anyhow::Result;
 
@@ -16,6 +17,8 @@ use crate::stats::AllStatsSync;
 pub struct BatchStats {
     collected_records: HashMap<u64, RecordMetadata>,
     pub last_flush_time: Option<Instant>,
+    /// produce acknowledgement latencies of all producers, in microseconds
+    pub ack_latency: Vec<u64>,
 }
 impl BatchStats {
     pub fn record_sent(
@@ -38,7 +41,12 @@ impl BatchStats {
         val.mark_recv_time(recv_time, consumer_id)
     }
 
-    pub fn flush_recv(&mut self, flush_time: Instant) {
+    pub fn flush_recv(&mut self, flush_time: Instant, ack_latency: &Histogram<u64>) {
+        for value in ack_latency.iter_recorded() {
+            let latency = value.value_iterated_to();
+            self.ack_latency
+                .extend(std::iter::repeat(latency).take(value.count_at_value() as usize));
+        }
         if let Some(previous) = self.last_flush_time {
             if flush_time > previous {
                 self.last_flush_time = Some(flush_time);
@@ -103,9 +111,10 @@ impl StatsWorker {
                         )
                         .into());
                     }
-                    StatsCollectorMessage::ProducerFlushed { flush_time } => {
-                        self.current_batch.flush_recv(flush_time)
-                    }
+                    StatsCollectorMessage::ProducerFlushed {
+                        flush_time,
+                        ack_latency,
+                    } => self.current_batch.flush_recv(flush_time, &ack_latency),
                 },
                 Err(_) => {
                     return Err(BenchmarkError::ErrorWithExplanation(
@@ -194,6 +203,8 @@ pub enum StatsCollectorMessage {
     },
     ProducerFlushed {
         flush_time: Instant,
+        /// acknowledgement latencies recorded by the producer since the previous flush
+        ack_latency: Histogram<u64>,
     },
     MessageReceived,
 
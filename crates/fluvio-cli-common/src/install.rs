@@ -107,16 +107,7 @@ pub async fn fetch_package_file(
     id: &PackageId<WithVersion>,
     target: &Target,
 ) -> Result<Vec<u8>> {
-    // If the PackageVersion is a tag, try to resolve it to a semver::Version
-    let version = match id.version() {
-        PackageVersion::Semver(version) => version.clone(),
-        PackageVersion::Tag(tag) => {
-            let req = agent.request_tag(id, tag)?;
-            let tag_response = crate::http::get_bytes_req(&req).await?;
-            agent.tag_version_from_response(tag, &tag_response).await?
-        }
-        _ => return Err(anyhow!("unknown PackageVersion type")),
-    };
+    let version = resolve_package_version(agent, id).await?;
 
     // Download the package file from the package registry
     let download_request = agent.request_release_download(id, &version, target)?;
@@ -146,6 +137,43 @@ pub async fn fetch_package_file(
     Ok(package_file.to_vec())
 }
 
+/// Downloads the detached signature of a package file
+#[instrument(
+    skip(agent, id, target),
+    fields(%target, %id)
+)]
+pub async fn fetch_package_signature(
+    agent: &HttpAgent,
+    id: &PackageId<WithVersion>,
+    target: &Target,
+) -> Result<Vec<u8>> {
+    let version = resolve_package_version(agent, id).await?;
+    let signature_request = agent
+        .request_release_signature(id, &version, target)?
+        .uri()
+        .to_string();
+    let signature = crate::http::get_simple(&signature_request)
+        .await
+        .map_err(|err| anyhow!("{id} is not signed: {err}"))?;
+    hex::decode(signature.trim()).map_err(|err| anyhow!("invalid signature of {id}: {err}"))
+}
+
+/// If the PackageVersion is a tag, try to resolve it to a semver::Version
+pub async fn resolve_package_version(
+    agent: &HttpAgent,
+    id: &PackageId<WithVersion>,
+) -> Result<Version> {
+    match id.version() {
+        PackageVersion::Semver(version) => Ok(version.clone()),
+        PackageVersion::Tag(tag) => {
+            let req = agent.request_tag(id, tag)?;
+            let tag_response = crate::http::get_bytes_req(&req).await?;
+            Ok(agent.tag_version_from_response(tag, &tag_response).await?)
+        }
+        _ => Err(anyhow!("unknown PackageVersion type")),
+    }
+}
+
 fn verify_checksum<B: AsRef<[u8]>>(buffer: B, checksum: &str) -> bool {
     let bytes = buffer.as_ref();
     let buffer_checksum = {
@@ -8,6 +8,8 @@ fn main() {
         }
     }
     println!("cargo:rerun-if-changed=build.rs");
+    // Release public key packages are verified against, embedded with option_env!()
+    println!("cargo:rerun-if-env-changed=FLUVIO_RELEASE_PUBLIC_KEY");
 
     // Fetch current git hash to print version output
     let git_version_output = Command::new("git")
@@ -0,0 +1,54 @@
+//!
+//! # Create an Acl
+//!
+//! CLI tree to generate Create Acl spec
+//!
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::acl::{AclPermission, AclResource, AclSpec};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateAclOpt {
+    /// The name of the Acl
+    #[arg(value_name = "name")]
+    pub name: String,
+
+    /// Principal of the client certificate, `*` for every client
+    #[arg(long, value_name = "principal")]
+    pub principal: String,
+
+    /// Topics the Acl applies to, a trailing `*` matches by prefix.
+    /// Applies to the whole cluster if omitted
+    #[arg(long, value_name = "pattern")]
+    pub topic: Option<String>,
+
+    /// Permission granted: produce, consume or admin. Can be repeated
+    #[arg(short, long = "permission", value_name = "permission", required = true)]
+    pub permissions: Vec<AclPermission>,
+}
+
+impl CreateAclOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let resource = match self.topic {
+            Some(pattern) => AclResource::topic(pattern),
+            None => AclResource::Cluster,
+        };
+        let spec = AclSpec::new(self.principal, resource, self.permissions);
+
+        debug!("creating acl: {} spec: {:#?}", self.name, spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("acl \"{}\" created", self.name);
+
+        Ok(())
+    }
+}
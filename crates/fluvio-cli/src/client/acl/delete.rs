@@ -0,0 +1,29 @@
+//!
+//! # Delete an Acl
+//!
+//! CLI tree to generate Delete Acl spec
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::acl::AclSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteAclOpt {
+    /// The name of the acl to delete
+    name: String,
+}
+
+impl DeleteAclOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<AclSpec>(&self.name).await?;
+        println!("acl \"{}\" deleted", self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//! # List Acls CLI
+//!
+//! CLI tree and processing to list Acls
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::acl::AclSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListAclsOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListAclsOpt {
+    /// Process list acls cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let lists = admin.all::<AclSpec>().await?;
+
+        output::acls_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::Row;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::acl::AclSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListAcls(Vec<Metadata<AclSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format Acl list
+    pub fn acls_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_acls: Vec<Metadata<AclSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("acls: {:#?}", list_acls);
+
+        if !list_acls.is_empty() {
+            let acls = ListAcls(list_acls);
+            out.render_list(&acls, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no acls");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListAcls {
+        /// acl header implementation
+        fn header(&self) -> Row {
+            Row::from(["NAME", "PRINCIPAL", "RESOURCE", "PERMISSIONS", "STATUS"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    Row::from([
+                        r.name.clone(),
+                        r.spec.principal.clone(),
+                        r.spec.resource.to_string(),
+                        r.spec
+                            .permissions
+                            .iter()
+                            .map(|permission| permission.to_string())
+                            .collect::<Vec<_>>()
+                            .join(","),
+                        r.status.to_string(),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
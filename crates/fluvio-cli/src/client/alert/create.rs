@@ -0,0 +1,74 @@
+//!
+//! # Create an AlertRule
+//!
+//! CLI tree to generate Create AlertRule spec
+//!
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::alert::{AlertAction, AlertRuleSpec};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser, Default)]
+pub struct CreateAlertRuleOpt {
+    /// The name of the AlertRule
+    #[arg(value_name = "name")]
+    pub name: String,
+
+    /// Condition to alert on, for example `replica_lag{topic="orders"} > 1000`
+    #[arg(short, long, value_name = "expression")]
+    pub expr: String,
+
+    /// Seconds the condition must hold before the alert fires
+    #[arg(long = "for", value_name = "seconds", default_value_t = 0)]
+    pub for_secs: u32,
+
+    /// URL to POST the alert to, can be repeated
+    #[arg(long, value_name = "url")]
+    pub webhook: Vec<String>,
+
+    /// Topic to produce the alert to, can be repeated
+    #[arg(long, value_name = "topic")]
+    pub topic: Vec<String>,
+
+    /// Description included in the alert
+    #[arg(long)]
+    pub description: Option<String>,
+}
+
+impl CreateAlertRuleOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let actions = self
+            .webhook
+            .into_iter()
+            .map(|url| AlertAction::Webhook { url })
+            .chain(
+                self.topic
+                    .into_iter()
+                    .map(|topic| AlertAction::Topic { topic }),
+            )
+            .collect();
+        let spec = AlertRuleSpec {
+            expression: self.expr,
+            for_secs: self.for_secs,
+            actions,
+            description: self.description,
+        };
+        // surface expression errors before reaching the cluster
+        spec.parse_expression()?;
+
+        debug!("creating alert rule: {} spec: {:#?}", self.name, spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("alert rule \"{}\" created", self.name);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+//!
+//! # Delete an AlertRule
+//!
+//! CLI tree to generate Delete AlertRule spec
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::alert::AlertRuleSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteAlertRuleOpt {
+    /// The name of the alert rule to delete
+    name: String,
+}
+
+impl DeleteAlertRuleOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<AlertRuleSpec>(&self.name).await?;
+        println!("alert rule \"{}\" deleted", self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! # List AlertRules CLI
+//!
+//! CLI tree and processing to list AlertRules
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::alert::AlertRuleSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListAlertRulesOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListAlertRulesOpt {
+    /// Process list alert rules cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let lists = admin.all::<AlertRuleSpec>().await?;
+
+        output::alert_rules_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::Row;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::alert::AlertRuleSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListAlertRules(Vec<Metadata<AlertRuleSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format AlertRule list
+    pub fn alert_rules_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_alert_rules: Vec<Metadata<AlertRuleSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("alert rules: {:#?}", list_alert_rules);
+
+        if !list_alert_rules.is_empty() {
+            let alert_rules = ListAlertRules(list_alert_rules);
+            out.render_list(&alert_rules, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no alert rules");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListAlertRules {
+        /// alert rule header implementation
+        fn header(&self) -> Row {
+            Row::from(["NAME", "EXPRESSION", "STATUS", "VALUE", "REASON"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    Row::from([
+                        r.name.clone(),
+                        r.spec.expression.clone(),
+                        r.status.to_string(),
+                        r.status.value.map(|v| v.to_string()).unwrap_or_default(),
+                        r.status.reason.clone().unwrap_or_default(),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//!
+//! # Connector Logs CLI
+//!
+//! CLI to print the log events connectors produce to their log topic
+//!
+
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+use clap::Parser;
+use futures_util::StreamExt;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::{Fluvio, Offset};
+use fluvio::consumer::ConsumerConfigExtBuilder;
+use fluvio::metadata::topic::TopicSpec;
+use fluvio_connector_package::logs::{log_topic, ConnectorLog};
+
+use crate::common::output::Terminal;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct ConnectorLogsOpt {
+    /// The name of the connector
+    #[arg(value_name = "name")]
+    name: String,
+
+    /// Keep printing the logs as the connector produces them
+    #[arg(short, long)]
+    follow: bool,
+
+    /// Print only the last <TAIL> log events
+    #[arg(short = 'n', long, value_name = "TAIL")]
+    tail: Option<u32>,
+}
+
+impl ConnectorLogsOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let topic = log_topic(&self.name);
+        debug!(topic, "connector logs");
+
+        let admin = fluvio.admin().await;
+        let topics = admin.list::<TopicSpec, _>(vec![topic.clone()]).await?;
+        if topics.is_empty() {
+            anyhow::bail!(
+                "no logs for connector \"{}\", it has not produced any",
+                self.name
+            );
+        }
+
+        let offset = match self.tail {
+            Some(tail) => Offset::from_end(tail),
+            None => Offset::beginning(),
+        };
+        let config = ConsumerConfigExtBuilder::default()
+            .topic(topic)
+            .offset_start(offset)
+            .disable_continuous(!self.follow)
+            .build()?;
+        let mut stream = fluvio.consumer_with_config(config).await?;
+        while let Some(record) = stream.next().await {
+            let record = record?;
+            match serde_json::from_slice::<ConnectorLog>(record.value()) {
+                Ok(log) => out.println(&format_log(&log)),
+                Err(_) => out.println(&String::from_utf8_lossy(record.value())),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn format_log(log: &ConnectorLog) -> String {
+    let time = UNIX_EPOCH + Duration::from_millis(log.timestamp);
+    let mut line = format!(
+        "{} {:>5} {}: {}",
+        humantime::format_rfc3339_millis(time),
+        log.level,
+        log.target,
+        log.message
+    );
+    for (name, value) in &log.fields {
+        line.push_str(&format!(" {name}={value}"));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn test_format_log() {
+        let log = ConnectorLog {
+            timestamp: 1_700_000_000_123,
+            level: "INFO".to_owned(),
+            target: "http_source".to_owned(),
+            message: "produced".to_owned(),
+            fields: BTreeMap::from([("records".to_owned(), "3".to_owned())]),
+        };
+        assert_eq!(
+            format_log(&log),
+            "2023-11-14T22:13:20.123Z  INFO http_source: produced records=3"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+mod logs;
+
+pub use cmd::ConnectorCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::logs::ConnectorLogsOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum ConnectorCmd {
+        /// Print the logs a connector produced to the cluster
+        #[command(
+            name = "logs",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Logs(ConnectorLogsOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for ConnectorCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Logs(logs) => {
+                    logs.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -40,13 +40,14 @@ mod cmd {
     use anyhow::Result;
 
     use fluvio_types::PartitionId;
-    use fluvio_spu_schema::server::smartmodule::SmartModuleContextData;
+    use fluvio_spu_schema::server::smartmodule::{SmartModuleContextData, SmartModuleInvocation};
     use fluvio_protocol::record::NO_TIMESTAMP;
     use fluvio::metadata::tableformat::TableFormatSpec;
     use fluvio::{Fluvio, Offset, FluvioError};
     use fluvio::consumer::{ConsumerConfigExt, ConsumerStream, OffsetManagementStrategy};
 
     use fluvio::consumer::Record;
+    use fluvio::lineage::Lineage;
     use fluvio_spu_schema::Isolation;
 
     use crate::monitoring::init_monitoring;
@@ -97,6 +98,10 @@ mod cmd {
         #[arg(short = 'd', long)]
         pub disable_continuous: bool,
 
+        /// Read a consistent snapshot, up to the end offsets at the time the consumer is opened
+        #[arg(long, conflicts_with_all = &["disable_continuous"])]
+        pub snapshot: bool,
+
         /// Disable the progress bar and wait spinner
         #[arg(long)]
         pub disable_progressbar: bool,
@@ -105,11 +110,25 @@ mod cmd {
         #[arg(short, long)]
         pub key_value: bool,
 
+        /// Only consume records whose key matches the regex, filtered by the SPU
+        #[arg(long, value_name = "regex", conflicts_with = "key_equals")]
+        pub key_filter: Option<String>,
+
+        /// Only consume records with the given key, filtered by the SPU
+        #[arg(long, value_name = "key")]
+        pub key_equals: Option<String>,
+
+        /// Only fetch this field of JSON record values, as a JSON pointer like `/user/name`.
+        /// Can be repeated, the SPU sends the values with only these fields.
+        #[arg(long = "field", value_name = "pointer")]
+        pub fields: Vec<String>,
+
         /// Provide a template string to print records with a custom format.
         /// See --help for details.
         ///
         /// Template strings may include the variables {{key}}, {{value}}, {{offset}}, {{partition}} and {{time}}
         /// which will have each record's contents substituted in their place.
+        /// Record headers are available as {{headers.<name>}}, or with {{#each headers}}{{@key}}={{this}} {{/each}}.
         /// Note that timestamp is displayed using RFC3339, is always UTC and ignores system timezone.
         ///
         /// For example, the following template string:
@@ -227,6 +246,10 @@ mod cmd {
         /// Consumer id
         #[arg(short, long)]
         pub consumer: Option<String>,
+
+        /// Print the lineage of records written by connectors and pipelines
+        #[arg(long, conflicts_with_all = &["output", "format"])]
+        pub show_lineage: bool,
     }
 
     #[async_trait]
@@ -282,12 +305,12 @@ mod cmd {
 
     impl ConsumeOpt {
         pub fn metadata() -> FluvioExtensionMetadata {
-            FluvioExtensionMetadata {
-                title: "consume".into(),
-                package: Some("fluvio/fluvio".parse().unwrap()),
-                description: "Consume new data in a stream".into(),
-                version: semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
-            }
+            FluvioExtensionMetadata::new(
+                "consume",
+                "Consume new data in a stream",
+                semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
+            )
+            .with_package("fluvio/fluvio".parse().unwrap())
         }
 
         fn smart_module_ctx(&self) -> SmartModuleContextData {
@@ -334,7 +357,7 @@ mod cmd {
                 Some(params) => params.clone().into_iter().collect(),
             };
 
-            let smart_module = if let Some(smart_module_name) = &self.smartmodule {
+            let mut smart_module = if let Some(smart_module_name) = &self.smartmodule {
                 vec![create_smartmodule(
                     smart_module_name,
                     self.smart_module_ctx(),
@@ -362,12 +385,26 @@ mod cmd {
                 Vec::new()
             };
 
+            // filter on the keys of the records as written, before any transformation
+            if let Some(regex) = &self.key_filter {
+                smart_module.insert(0, SmartModuleInvocation::key_regex_filter(regex));
+            } else if let Some(key) = &self.key_equals {
+                smart_module.insert(0, SmartModuleInvocation::key_equals_filter(key));
+            }
+            if !self.fields.is_empty() {
+                smart_module.push(SmartModuleInvocation::projection(&self.fields));
+            }
+
             builder.smartmodule(smart_module);
 
             if self.disable_continuous {
                 builder.disable_continuous(true);
             }
 
+            if self.snapshot {
+                builder.snapshot(true);
+            }
+
             if let Some(end_offset) = self.end {
                 if let Some(start_offset) = self.start {
                     if end_offset < start_offset {
@@ -395,10 +432,15 @@ mod cmd {
                 .consumer_with_config(consume_config)
                 .await?
                 .take_until(stop_signal.recv());
+            if self.snapshot {
+                for (partition, end) in stream.get_ref().snapshot_end_offsets() {
+                    eprintln!("Snapshot of partition {partition} ends at offset {end}");
+                }
+            }
             self.consume_records_stream(&mut stream, tableformat)
                 .await?;
 
-            if !self.disable_continuous {
+            if !self.disable_continuous && !self.snapshot {
                 eprintln!("Consumer stream has closed");
             }
 
@@ -600,7 +642,7 @@ mod cmd {
 
             let formatted_value = match (&self.output, templates) {
                 (Some(ConsumeOutputType::json), None) => {
-                    format_json(record.value(), self.suppress_unknown)
+                    format_json(record.key(), record.value(), self.suppress_unknown)
                 }
                 (Some(ConsumeOutputType::text), None) => Some(format_text_record(
                     record.get_value(),
@@ -646,12 +688,21 @@ mod cmd {
                         )
                     };
 
+                    // the first value of a repeated header key is shown, as `Record::header` does
+                    let mut headers = serde_json::Map::new();
+                    for header in record.headers() {
+                        headers
+                            .entry(header.key.clone())
+                            .or_insert_with(|| header.value.as_utf8_lossy_string().into());
+                    }
+
                     let object = serde_json::json!({
                         "key": formatted_key,
                         "value": value,
                         "offset": record.offset(),
                         "partition": record.partition(),
                         "time": timestamp_rfc3339,
+                        "headers": headers,
                     });
                     templates.render(USER_TEMPLATE, &object).ok()
                 }
@@ -660,7 +711,9 @@ mod cmd {
             // If the consume type is table, we don't want to accidentally print a newline
             if self.output != Some(ConsumeOutputType::full_table) {
                 match formatted_value {
-                    Some(value) if self.key_value => {
+                    Some(value)
+                        if self.key_value && self.output != Some(ConsumeOutputType::json) =>
+                    {
                         let output = format!("[{formatted_key}] {value}");
                         pb.println(&output);
                     }
@@ -677,6 +730,11 @@ mod cmd {
                     // (Some(_), None) only if JSON cannot be printed, so skip.
                     _ => debug!("Skipping record that cannot be formatted"),
                 }
+                if self.show_lineage {
+                    if let Some(lineage) = Lineage::from_record(record.inner()) {
+                        pb.println(&format!("  lineage: {lineage}"));
+                    }
+                }
             } else if let Some(term) = terminal {
                 if let Some(table) = table_model {
                     table.render(term);
@@ -793,8 +851,12 @@ mod cmd {
                 mirror: Default::default(),
                 all_partitions: Default::default(),
                 disable_continuous: Default::default(),
+                snapshot: Default::default(),
                 disable_progressbar: Default::default(),
                 key_value: Default::default(),
+                key_filter: Default::default(),
+                key_equals: Default::default(),
+                fields: Default::default(),
                 format: Default::default(),
                 table_format: Default::default(),
                 start: Default::default(),
@@ -814,6 +876,7 @@ mod cmd {
                 transforms_line: Default::default(),
                 truncate: Default::default(),
                 consumer: Default::default(),
+                show_lineage: Default::default(),
             }
         }
         #[test]
@@ -18,8 +18,9 @@ use super::TableModel;
 //  JSON
 // -----------------------------------
 
-pub fn format_json(value: &[u8], suppress: bool) -> Option<String> {
-    let maybe_json = match serde_json::from_slice(value) {
+/// Print the value as json, along with the key of the record
+pub fn format_json(key: Option<&[u8]>, value: &[u8], suppress: bool) -> Option<String> {
+    let maybe_json: Option<serde_json::Value> = match serde_json::from_slice(value) {
         Ok(value) => Some(value),
         Err(e) if !suppress => Some(serde_json::json!({
             "error": format!("{e}"),
@@ -27,7 +28,14 @@ pub fn format_json(value: &[u8], suppress: bool) -> Option<String> {
         _ => None,
     };
 
-    maybe_json.and_then(|json| serde_json::to_string_pretty(&json).ok())
+    maybe_json.and_then(|json| {
+        let key = key.map(|key| String::from_utf8_lossy(key).into_owned());
+        serde_json::to_string_pretty(&serde_json::json!({
+            "key": key,
+            "value": json,
+        }))
+        .ok()
+    })
 }
 
 // -----------------------------------
@@ -29,27 +29,17 @@ impl DeleteConsumerOpt {
         if let Some((topic, partition)) = self.topic.as_ref().zip(self.partition.as_ref()) {
             delete(fluvio, self.consumer, topic.clone(), *partition).await?;
         } else {
-            let consumers: Vec<_> = fluvio
-                .consumer_offsets()
-                .await?
-                .into_iter()
-                .filter(|c| c.consumer_id.eq(&self.consumer))
-                .filter(|c| {
-                    self.topic.is_none() || c.topic.eq(self.topic.as_deref().unwrap_or_default())
-                })
-                .collect();
+            let consumers = fluvio
+                .delete_consumer_offsets(&self.consumer, self.topic.as_deref())
+                .await?;
             if consumers.is_empty() {
                 println!("no consumers found");
-            } else {
-                for consumer in consumers {
-                    delete(
-                        fluvio,
-                        consumer.consumer_id,
-                        consumer.topic,
-                        consumer.partition,
-                    )
-                    .await?;
-                }
+            }
+            for consumer in consumers {
+                println!(
+                    "consumer \"{}\" on topic \"{}\" and partition \"{}\" deleted",
+                    consumer.consumer_id, consumer.topic, consumer.partition
+                );
             }
         }
         Ok(())
@@ -1,5 +1,6 @@
 mod list;
 mod delete;
+mod reset;
 
 pub use cmd::ConsumerCmd;
 
@@ -20,6 +21,7 @@ mod cmd {
 
     use super::delete::DeleteConsumerOpt;
     use super::list::ListConsumerOpt;
+    use super::reset::ResetConsumerOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "consumer", about = "Consumer operations")]
@@ -36,6 +38,12 @@ mod cmd {
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         Delete(DeleteConsumerOpt),
+        /// Reset the Consumer Offsets of a topic
+        #[command(
+            name = "reset-offsets",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        ResetOffsets(ResetConsumerOpt),
     }
 
     #[async_trait]
@@ -52,6 +60,9 @@ mod cmd {
                 Self::Delete(delete) => {
                     delete.process(out, fluvio).await?;
                 }
+                Self::ResetOffsets(reset) => {
+                    reset.process(out, fluvio).await?;
+                }
             }
 
             Ok(())
@@ -60,12 +71,12 @@ mod cmd {
 
     impl ConsumerCmd {
         pub fn metadata() -> FluvioExtensionMetadata {
-            FluvioExtensionMetadata {
-                title: "consumer".into(),
-                package: Some("fluvio/fluvio".parse().unwrap()),
-                description: "Consumer Offsets Operations".into(),
-                version: semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
-            }
+            FluvioExtensionMetadata::new(
+                "consumer",
+                "Consumer Offsets Operations",
+                semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
+            )
+            .with_package("fluvio/fluvio".parse().unwrap())
         }
     }
 }
@@ -0,0 +1,191 @@
+use std::time::UNIX_EPOCH;
+
+use clap::Parser;
+use anyhow::{anyhow, Result};
+
+use fluvio::Fluvio;
+use fluvio::consumer::OffsetReset;
+use fluvio_types::PartitionId;
+
+use crate::common::output::Terminal;
+use crate::common::OutputFormat;
+
+/// Option for Resetting Consumer Offsets
+#[derive(Debug, Parser)]
+pub struct ResetConsumerOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+
+    /// Consumer to reset offsets of
+    #[arg(short, long = "group", value_name = "consumer")]
+    consumer: String,
+
+    /// Topic consumed
+    #[arg(short, long)]
+    topic: String,
+
+    /// Partition to reset, all partitions of the topic if omitted
+    #[arg(short, long)]
+    partition: Option<PartitionId>,
+
+    /// Position to reset to: earliest, latest, offset <N> or timestamp <T>,
+    /// where T is milliseconds since epoch or an RFC 3339 date, e.g. 2024-01-01T00:00:00Z
+    #[arg(long, num_args = 1..=2, value_names = ["position", "value"], required = true)]
+    to: Vec<String>,
+
+    /// Apply the changes
+    #[arg(long, conflicts_with = "dry_run")]
+    execute: bool,
+
+    /// Only show the changes, the default
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl ResetConsumerOpt {
+    pub async fn process<O>(self, out: std::sync::Arc<O>, fluvio: &Fluvio) -> Result<()>
+    where
+        O: Terminal,
+    {
+        let target = parse_target(&self.to)?;
+        let execute = self.execute && !self.dry_run;
+        let resets = fluvio
+            .reset_consumer_offsets(self.consumer, self.topic, self.partition, target, execute)
+            .await?;
+
+        display::format_response_output(out.clone(), resets, self.output.format.clone())?;
+        if !execute && self.output.format.is_table() {
+            out.println("Dry run, use --execute to apply the changes");
+        }
+        Ok(())
+    }
+}
+
+fn parse_target(to: &[String]) -> Result<OffsetReset> {
+    match to {
+        [position] if position == "earliest" => Ok(OffsetReset::Earliest),
+        [position] if position == "latest" => Ok(OffsetReset::Latest),
+        [position, value] if position == "offset" => value
+            .parse()
+            .map(OffsetReset::Offset)
+            .map_err(|_| anyhow!("invalid offset: {value}")),
+        [position, value] if position == "timestamp" => parse_timestamp(value),
+        _ => Err(anyhow!(
+            "invalid reset position: {}, expected earliest, latest, offset <N> or timestamp <T>",
+            to.join(" ")
+        )),
+    }
+}
+
+fn parse_timestamp(value: &str) -> Result<OffsetReset> {
+    if let Ok(millis) = value.parse::<i64>() {
+        return Ok(OffsetReset::Timestamp(millis));
+    }
+    let time = humantime::parse_rfc3339_weak(value)
+        .map_err(|err| anyhow!("invalid timestamp {value}: {err}"))?;
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| anyhow!("timestamp {value} is before epoch"))?
+        .as_millis();
+    Ok(OffsetReset::Timestamp(millis as i64))
+}
+
+mod display {
+
+    use comfy_table::{Row, Cell};
+    use serde::Serialize;
+
+    use fluvio::consumer::ConsumerOffsetReset;
+
+    use crate::common::t_println;
+    use crate::common::output::{OutputType, OutputError, Terminal, TableOutputHandler};
+
+    #[derive(Serialize)]
+    struct ResetConsumers(Vec<ConsumerOffsetReset>);
+
+    pub fn format_response_output<O>(
+        out: std::sync::Arc<O>,
+        resets: Vec<ConsumerOffsetReset>,
+        output_type: OutputType,
+    ) -> Result<(), OutputError>
+    where
+        O: Terminal,
+    {
+        if !resets.is_empty() {
+            out.render_list(&ResetConsumers(resets), output_type)?;
+        } else {
+            t_println!(out, "No partitions found");
+        }
+
+        Ok(())
+    }
+
+    impl TableOutputHandler for ResetConsumers {
+        fn header(&self) -> Row {
+            Row::from([
+                "CONSUMER",
+                "TOPIC",
+                "PARTITION",
+                "CURRENT POSITION",
+                "NEW POSITION",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|reset| {
+                    Row::from([
+                        Cell::new(&reset.consumer_id),
+                        Cell::new(&reset.topic),
+                        Cell::new(reset.partition),
+                        Cell::new(
+                            reset
+                                .current_position
+                                .map(|position| position.to_string())
+                                .unwrap_or_else(|| "-".to_owned()),
+                        ),
+                        Cell::new(reset.new_position),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(
+            parse_target(&to(&["earliest"])).unwrap(),
+            OffsetReset::Earliest
+        );
+        assert_eq!(parse_target(&to(&["latest"])).unwrap(), OffsetReset::Latest);
+        assert_eq!(
+            parse_target(&to(&["offset", "42"])).unwrap(),
+            OffsetReset::Offset(42)
+        );
+        assert_eq!(
+            parse_target(&to(&["timestamp", "1700000000000"])).unwrap(),
+            OffsetReset::Timestamp(1_700_000_000_000)
+        );
+        assert_eq!(
+            parse_target(&to(&["timestamp", "2024-01-01T00:00:00Z"])).unwrap(),
+            OffsetReset::Timestamp(1_704_067_200_000)
+        );
+        assert!(parse_target(&to(&["offset"])).is_err());
+        assert!(parse_target(&to(&["latest", "1"])).is_err());
+        assert!(parse_target(&to(&["offset", "x"])).is_err());
+    }
+}
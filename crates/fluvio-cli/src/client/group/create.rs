@@ -0,0 +1,55 @@
+//!
+//! # Create a Consumer Group
+//!
+//! CLI tree to generate Create ConsumerGroup spec
+//!
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::consumer_group::{
+    AssignmentStrategy, ConsumerGroupSpec, DEFAULT_SESSION_TIMEOUT_MS,
+};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateConsumerGroupOpt {
+    /// The name of the consumer group
+    #[arg(value_name = "name")]
+    pub name: String,
+
+    /// Topic whose partitions are shared by the members
+    #[arg(short, long, value_name = "topic")]
+    pub topic: String,
+
+    /// How partitions are assigned to the members: range, round-robin, sticky or cooperative-sticky
+    #[arg(short, long, value_name = "strategy", default_value_t = AssignmentStrategy::default())]
+    pub strategy: AssignmentStrategy,
+
+    /// Milliseconds without heartbeat after which a member leaves the group
+    #[arg(long, value_name = "ms", default_value_t = DEFAULT_SESSION_TIMEOUT_MS)]
+    pub session_timeout_ms: u32,
+}
+
+impl CreateConsumerGroupOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let spec = ConsumerGroupSpec {
+            topic: self.topic,
+            strategy: self.strategy,
+            session_timeout_ms: self.session_timeout_ms,
+        };
+
+        debug!("creating consumer group: {} spec: {:#?}", self.name, spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("consumer group \"{}\" created", self.name);
+
+        Ok(())
+    }
+}
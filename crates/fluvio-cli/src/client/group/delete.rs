@@ -0,0 +1,37 @@
+//!
+//! # Delete a Consumer Group
+//!
+//! CLI tree to generate Delete ConsumerGroup spec
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::consumer_group::ConsumerGroupSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteConsumerGroupOpt {
+    /// The name of the consumer group to delete
+    name: String,
+
+    /// Also delete the offsets committed by the group
+    #[arg(long)]
+    offsets: bool,
+}
+
+impl DeleteConsumerGroupOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<ConsumerGroupSpec>(&self.name).await?;
+        println!("consumer group \"{}\" deleted", self.name);
+        if self.offsets {
+            let offsets = fluvio.delete_consumer_offsets(&self.name, None).await?;
+            println!("{} committed offsets deleted", offsets.len());
+        }
+        Ok(())
+    }
+}
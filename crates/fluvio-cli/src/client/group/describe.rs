@@ -0,0 +1,155 @@
+//!
+//! # Describe Consumer Group CLI
+//!
+//! CLI to describe a consumer group with the partitions of its members
+//!
+
+use std::sync::Arc;
+
+use tracing::debug;
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::consumer_group::ConsumerGroupSpec;
+
+use crate::common::output::Terminal;
+use crate::common::OutputFormat;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DescribeConsumerGroupOpt {
+    /// The name of the consumer group to describe
+    #[arg(value_name = "name")]
+    group: String,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl DescribeConsumerGroupOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let group = self.group;
+        let output_type = self.output.format;
+        debug!("describe consumer group: {}, {:?}", group, output_type);
+
+        let admin = fluvio.admin().await;
+        let groups = admin
+            .list::<ConsumerGroupSpec, _>(vec![group.clone()])
+            .await?;
+        if groups.is_empty() {
+            anyhow::bail!("consumer group \"{group}\" not found");
+        }
+
+        display::describe_consumer_groups(groups, output_type, out)?;
+        Ok(())
+    }
+}
+
+mod display {
+
+    use comfy_table::Row;
+    use serde::Serialize;
+
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::consumer_group::ConsumerGroupSpec;
+
+    use crate::common::output::{
+        OutputType, OutputError, DescribeObjectHandler, KeyValOutputHandler, TableOutputHandler,
+        Terminal,
+    };
+
+    pub fn describe_consumer_groups<O>(
+        groups: Vec<Metadata<ConsumerGroupSpec>>,
+        output_type: OutputType,
+        out: std::sync::Arc<O>,
+    ) -> Result<(), OutputError>
+    where
+        O: Terminal,
+    {
+        let groups: Vec<ConsumerGroupMetadata> =
+            groups.into_iter().map(ConsumerGroupMetadata).collect();
+        out.describe_objects(&groups, output_type)
+    }
+
+    #[derive(Serialize, Clone)]
+    struct ConsumerGroupMetadata(Metadata<ConsumerGroupSpec>);
+
+    impl DescribeObjectHandler for ConsumerGroupMetadata {
+        fn label() -> &'static str {
+            "consumer group"
+        }
+
+        fn label_plural() -> &'static str {
+            "consumer groups"
+        }
+
+        fn is_ok(&self) -> bool {
+            true
+        }
+
+        fn is_error(&self) -> bool {
+            false
+        }
+
+        fn validate(&self) -> Result<(), OutputError> {
+            Ok(())
+        }
+    }
+
+    impl TableOutputHandler for ConsumerGroupMetadata {
+        fn header(&self) -> Row {
+            Row::new()
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            vec![]
+        }
+    }
+
+    impl KeyValOutputHandler for ConsumerGroupMetadata {
+        /// key value hash map implementation
+        fn key_values(&self) -> Vec<(String, Option<String>)> {
+            let spec = &self.0.spec;
+            let status = &self.0.status;
+
+            let mut key_values = vec![
+                ("Name".to_owned(), Some(self.0.name.clone())),
+                ("Topic".to_owned(), Some(spec.topic.clone())),
+                ("Strategy".to_owned(), Some(spec.strategy.to_string())),
+                (
+                    "Session Timeout".to_owned(),
+                    Some(format!("{}ms", spec.session_timeout_ms)),
+                ),
+                ("Status".to_owned(), Some(status.to_string())),
+                ("Generation".to_owned(), Some(status.generation.to_string())),
+                ("Reason".to_owned(), status.reason.clone()),
+            ];
+
+            for member in &status.members {
+                let partitions: Vec<String> =
+                    member.partitions.iter().map(ToString::to_string).collect();
+                let instance = member
+                    .instance_id
+                    .as_ref()
+                    .map(|instance_id| format!(", instance {instance_id}"))
+                    .unwrap_or_default();
+                key_values.push((
+                    format!("Member {}", member.id),
+                    Some(format!("partitions [{}]{instance}", partitions.join(", "))),
+                ));
+            }
+
+            key_values.push(("-----------------".to_owned(), None));
+
+            key_values
+        }
+    }
+}
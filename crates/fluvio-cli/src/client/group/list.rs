@@ -0,0 +1,116 @@
+//! # List Consumer Groups CLI
+//!
+//! CLI tree and processing to list consumer groups
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::consumer_group::ConsumerGroupSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListConsumerGroupsOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListConsumerGroupsOpt {
+    /// Process list consumer groups cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let lists = admin.all::<ConsumerGroupSpec>().await?;
+
+        output::consumer_groups_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::Row;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::consumer_group::ConsumerGroupSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListConsumerGroups(Vec<Metadata<ConsumerGroupSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format consumer group list
+    pub fn consumer_groups_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_consumer_groups: Vec<Metadata<ConsumerGroupSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("consumer groups: {:#?}", list_consumer_groups);
+
+        if !list_consumer_groups.is_empty() {
+            let consumer_groups = ListConsumerGroups(list_consumer_groups);
+            out.render_list(&consumer_groups, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no consumer groups");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListConsumerGroups {
+        /// consumer group header implementation
+        fn header(&self) -> Row {
+            Row::from([
+                "NAME",
+                "TOPIC",
+                "STRATEGY",
+                "STATUS",
+                "GENERATION",
+                "MEMBERS",
+                "REASON",
+            ])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    Row::from([
+                        r.name.clone(),
+                        r.spec.topic.clone(),
+                        r.spec.strategy.to_string(),
+                        r.status.to_string(),
+                        r.status.generation.to_string(),
+                        r.status.members.len().to_string(),
+                        r.status.reason.clone().unwrap_or_default(),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,83 @@
+mod create;
+mod delete;
+mod describe;
+mod list;
+
+pub use cmd::GroupCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreateConsumerGroupOpt;
+    use super::delete::DeleteConsumerGroupOpt;
+    use super::describe::DescribeConsumerGroupOpt;
+    use super::list::ListConsumerGroupsOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum GroupCmd {
+        /// Create a new consumer group
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreateConsumerGroupOpt),
+
+        /// Delete a consumer group
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeleteConsumerGroupOpt),
+
+        /// Show the members of a consumer group and their partitions
+        #[command(
+            name = "describe",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Describe(DescribeConsumerGroupOpt),
+
+        /// List all consumer groups
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListConsumerGroupsOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for GroupCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::Describe(describe) => {
+                    describe.process(out, fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
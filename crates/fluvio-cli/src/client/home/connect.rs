@@ -12,6 +12,9 @@ use fluvio_sc_schema::remote_file::RemoteMetadataExport;
 pub struct ConnectOpt {
     #[arg(long, short = 'f')]
     file: String,
+    /// Outbound proxy used to reach the home cluster, e.g. socks5://proxy:1080
+    #[arg(long)]
+    proxy: Option<String>,
 }
 
 impl ConnectOpt {
@@ -29,7 +32,10 @@ impl ConnectOpt {
         let reader = BufReader::new(File::open(self.file)?);
         let remote_metadata: RemoteMetadataExport = serde_json::from_reader(reader)
             .map_err(|err| anyhow!("unable to load remote metadata: {}", err))?;
-        let home = remote_metadata.home;
+        let mut home = remote_metadata.home;
+        if let Some(proxy) = self.proxy {
+            home.proxy = Some(proxy);
+        }
 
         let home_id = home.id.clone();
 
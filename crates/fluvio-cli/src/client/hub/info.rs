@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::fmt::Debug;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_extension_common::Terminal;
+use fluvio_hub_util as hubutil;
+use hubutil::{CacheMode, HubCache};
+use hubutil::cmd::get_hub_access;
+
+use crate::CliError;
+
+/// Show the metadata and build provenance of a hub package
+#[derive(Debug, Parser)]
+pub struct InfoHubOpts {
+    /// Package name: e.g. infinyon/jolt@v0.0.1
+    #[arg(value_name = "name", required = true)]
+    pkgname: String,
+
+    /// given local package file, show its info
+    #[arg(long)]
+    ipkg: bool,
+
+    /// Only use the local cache of the hub, without reaching it
+    #[arg(long)]
+    offline: bool,
+
+    #[arg(long, hide_short_help = true)]
+    remote: Option<String>,
+}
+
+impl InfoHubOpts {
+    pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
+        let pkgfile = if self.ipkg {
+            self.pkgname.clone()
+        } else {
+            let access = get_hub_access(&self.remote)?;
+            let file_name = hubutil::cli_pkgname_to_filename(&self.pkgname).map_err(|_| {
+                CliError::HubError(format!(
+                    "invalid package name format {}, is it the form infinyon/json-sql@0.1.0",
+                    self.pkgname
+                ))
+            })?;
+            let url = hubutil::cli_pkgname_to_url(&self.pkgname, &access.remote)
+                .map_err(|_| CliError::HubError(format!("invalid pkgname {}", self.pkgname)))?;
+            hubutil::check_download_scope(&self.pkgname)?;
+            let cache = HubCache::default_load()?;
+            let mode = CacheMode::from_offline(self.offline);
+            let data = hubutil::get_package_cached(&url, &access, &cache, mode)
+                .await
+                .map_err(|err| {
+                    CliError::HubError(format!("downloading {}\nServer: {err}", self.pkgname))
+                })?;
+            let pkgfile = std::env::temp_dir().join(file_name);
+            std::fs::write(&pkgfile, data)?;
+            pkgfile.display().to_string()
+        };
+        let info = show_info(&pkgfile, out);
+        if !self.ipkg {
+            std::fs::remove_file(&pkgfile)?;
+        }
+        info
+    }
+}
+
+fn show_info<O: Terminal>(pkgfile: &str, out: Arc<O>) -> Result<()> {
+    let pm = hubutil::package_get_meta(pkgfile)
+        .map_err(|_| CliError::PackageError(format!("accessing metadata in {pkgfile}")))?;
+    out.println(&format!("Package:     {}", pm.pkg_name()));
+    out.println(&format!("Description: {}", pm.description));
+    out.println(&format!("License:     {}", pm.license));
+    out.println(&format!("Visibility:  {:?}", pm.visibility));
+    if let Some(repository_url) = &pm.repository_url {
+        out.println(&format!("Repository:  {repository_url}"));
+    }
+
+    let provenance = match hubutil::package_verify_provenance(pkgfile) {
+        Ok(Some(provenance)) => provenance,
+        Ok(None) => {
+            out.println("Provenance:  none");
+            return Ok(());
+        }
+        Err(err) => {
+            out.println(&format!("Provenance:  failed verification, {err}"));
+            return Ok(());
+        }
+    };
+    out.println("Provenance:  verified");
+    let unknown = "unknown";
+    out.println(&format!(
+        "  Commit:    {}{}",
+        provenance.git_commit.as_deref().unwrap_or(unknown),
+        if provenance.git_dirty {
+            " (uncommitted changes)"
+        } else {
+            ""
+        }
+    ));
+    out.println(&format!(
+        "  Source:    {}",
+        provenance.git_repository.as_deref().unwrap_or(unknown)
+    ));
+    out.println(&format!(
+        "  Builder:   {}",
+        provenance.builder.as_deref().unwrap_or(unknown)
+    ));
+    out.println(&format!(
+        "  Built at:  {}",
+        provenance.built_at.as_deref().unwrap_or(unknown)
+    ));
+    match &provenance.sbom {
+        Some(sbom) => out.println(&format!(
+            "  SBOM:      {} (sha256 {})",
+            sbom.file, sbom.sha256
+        )),
+        None => out.println("  SBOM:      none"),
+    }
+    Ok(())
+}
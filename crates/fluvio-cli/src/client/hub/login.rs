@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use std::fmt::Debug;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_extension_common::Terminal;
+use fluvio_hub_util as hubutil;
+
+/// Log in to InfinyOn Cloud from this host, approving the login on any device
+#[derive(Debug, Parser)]
+pub struct LoginHubOpts {
+    /// Cloud remote to log in to
+    #[arg(long, default_value = hubutil::DEFAULT_CLOUD_REMOTE, hide_short_help = true)]
+    remote: String,
+}
+
+impl LoginHubOpts {
+    pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
+        let login = hubutil::device_login(&self.remote, |auth| {
+            let uri = auth
+                .verification_uri_complete
+                .as_deref()
+                .unwrap_or(&auth.verification_uri);
+            out.println(&format!("Open {uri} and enter the code {}", auth.user_code));
+            out.println("Waiting for the login to be approved...");
+        })
+        .await?;
+        out.println(&format!("Logged in as {}", login.email));
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
 pub use cmd::HubCmd;
 
 mod connector;
+mod info;
+mod login;
+mod org;
+mod publish;
 mod smartmodule;
 
 mod cmd {
@@ -18,6 +22,10 @@ mod cmd {
     use crate::common::output::Terminal;
 
     use super::connector::ConnectorHubSubCmd;
+    use super::info::InfoHubOpts;
+    use super::login::LoginHubOpts;
+    use super::org::OrgHubSubCmd;
+    use super::publish::PublishHubOpts;
     use super::smartmodule::SmartModuleHubSubCmd;
 
     #[derive(Debug, Parser)]
@@ -29,6 +37,18 @@ mod cmd {
         #[clap(visible_alias = "conn")]
         #[command(subcommand)]
         Connector(ConnectorHubSubCmd),
+
+        #[command(subcommand)]
+        Org(OrgHubSubCmd),
+
+        /// Show the metadata and build provenance of a package
+        Info(InfoHubOpts),
+
+        /// Log in to InfinyOn Cloud with a code approved in a browser
+        Login(LoginHubOpts),
+
+        /// Publish a packed package, or check it with --dry-run
+        Publish(PublishHubOpts),
     }
 
     #[async_trait]
@@ -46,6 +66,22 @@ mod cmd {
                 Self::SmartModule(subcmd) => {
                     subcmd.process(out).await?;
                 }
+
+                Self::Org(subcmd) => {
+                    subcmd.process(out).await?;
+                }
+
+                Self::Info(opts) => {
+                    opts.process(out).await?;
+                }
+
+                Self::Login(opts) => {
+                    opts.process(out).await?;
+                }
+
+                Self::Publish(opts) => {
+                    opts.process(out).await?;
+                }
             }
             Ok(())
         }
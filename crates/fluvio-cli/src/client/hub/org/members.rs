@@ -0,0 +1,163 @@
+use std::sync::Arc;
+use std::fmt::Debug;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_extension_common::Terminal;
+use fluvio_hub_util as hubutil;
+use hubutil::cmd::get_hub_access;
+use hubutil::org::{OrgMember, OrgRole, ORG_ALL_PACKAGES};
+
+use crate::common::OutputFormat;
+
+/// List the members of an organization
+#[derive(Debug, Parser)]
+pub struct OrgMembersOpts {
+    /// Organization, the group of its packages: e.g. infinyon
+    #[arg(value_name = "org", required = true)]
+    org: String,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+
+    #[arg(long, hide_short_help = true)]
+    remote: Option<String>,
+}
+
+impl OrgMembersOpts {
+    pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
+        let access = get_hub_access(&self.remote)?;
+        let members = hubutil::get_org_members(&access, &self.org).await?;
+        output::members_response_to_output(out, members.members, self.output.format)?;
+        Ok(())
+    }
+}
+
+/// Add a member to an organization or change their role
+#[derive(Debug, Parser)]
+pub struct OrgSetMemberOpts {
+    /// Organization, the group of its packages: e.g. infinyon
+    #[arg(value_name = "org", required = true)]
+    org: String,
+
+    /// Hub id of the member
+    #[arg(value_name = "user", required = true)]
+    user: String,
+
+    /// viewer, publisher, maintainer or owner
+    #[arg(long, default_value_t = OrgRole::Publisher)]
+    role: OrgRole,
+
+    /// Packages the role applies to, `*` or a prefix ending with `*`: e.g. http-*
+    #[arg(long, default_value = ORG_ALL_PACKAGES)]
+    packages: String,
+
+    #[arg(long, hide_short_help = true)]
+    remote: Option<String>,
+}
+
+impl OrgSetMemberOpts {
+    pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
+        let access = get_hub_access(&self.remote)?;
+        let member = OrgMember {
+            user: self.user,
+            role: self.role,
+            packages: self.packages,
+        };
+        hubutil::set_org_member(&access, &self.org, &member).await?;
+        out.println(&format!(
+            "{} is {} of {}/{}",
+            member.user, member.role, self.org, member.packages
+        ));
+        Ok(())
+    }
+}
+
+/// Remove a member from an organization
+#[derive(Debug, Parser)]
+pub struct OrgRemoveMemberOpts {
+    /// Organization, the group of its packages: e.g. infinyon
+    #[arg(value_name = "org", required = true)]
+    org: String,
+
+    /// Hub id of the member
+    #[arg(value_name = "user", required = true)]
+    user: String,
+
+    #[arg(long, hide_short_help = true)]
+    remote: Option<String>,
+}
+
+impl OrgRemoveMemberOpts {
+    pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
+        let access = get_hub_access(&self.remote)?;
+        hubutil::remove_org_member(&access, &self.org, &self.user).await?;
+        out.println(&format!("{} removed from {}", self.user, self.org));
+        Ok(())
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio hub org members - output processing
+    //!
+    //! Format organization members based on output type
+    use comfy_table::{Cell, Row};
+    use comfy_table::CellAlignment;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+    use fluvio_hub_util::org::OrgMember;
+
+    #[derive(Serialize)]
+    struct ListOrgMembers(Vec<OrgMember>);
+
+    /// Format members based on output type
+    pub fn members_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        members: Vec<OrgMember>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        if !members.is_empty() {
+            out.render_list(&ListOrgMembers(members), output_type)?;
+        } else {
+            t_println!(out, "no members");
+        }
+        Ok(())
+    }
+
+    impl TableOutputHandler for ListOrgMembers {
+        /// table header implementation
+        fn header(&self) -> Row {
+            Row::from(["USER", "ROLE", "PACKAGES", "PUBLISH", "YANK"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation
+        fn content(&self) -> Vec<Row> {
+            let yes_no = |allowed: bool| if allowed { "yes" } else { "no" };
+            self.0
+                .iter()
+                .map(|member| {
+                    Row::from([
+                        Cell::new(&member.user).set_alignment(CellAlignment::Left),
+                        Cell::new(member.role).set_alignment(CellAlignment::Left),
+                        Cell::new(&member.packages).set_alignment(CellAlignment::Left),
+                        Cell::new(yes_no(member.role.can_publish())),
+                        Cell::new(yes_no(member.role.can_yank())),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
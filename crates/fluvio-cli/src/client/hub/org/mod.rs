@@ -0,0 +1,36 @@
+mod members;
+pub use members::{OrgMembersOpts, OrgSetMemberOpts, OrgRemoveMemberOpts};
+
+use std::sync::Arc;
+use std::fmt::Debug;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_extension_common::Terminal;
+
+/// Manage the members of hub organizations
+#[derive(Debug, Parser)]
+pub enum OrgHubSubCmd {
+    /// List the members of an organization and their roles
+    #[command(name = "members")]
+    Members(OrgMembersOpts),
+
+    /// Add a member to an organization or change their role
+    #[command(name = "set-member")]
+    SetMember(OrgSetMemberOpts),
+
+    /// Remove a member from an organization
+    #[command(name = "remove-member")]
+    RemoveMember(OrgRemoveMemberOpts),
+}
+
+impl OrgHubSubCmd {
+    pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
+        match self {
+            OrgHubSubCmd::Members(opts) => opts.process(out).await,
+            OrgHubSubCmd::SetMember(opts) => opts.process(out).await,
+            OrgHubSubCmd::RemoveMember(opts) => opts.process(out).await,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use std::fmt::Debug;
+
+use bytesize::ByteSize;
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_extension_common::Terminal;
+use fluvio_hub_util as hubutil;
+use hubutil::{HubAccess, PackageKind, PackageLint, PkgVisibility};
+use hubutil::cmd::get_hub_access;
+
+use crate::CliError;
+
+/// Publish a package packed by smdk or cdk to the hub
+#[derive(Debug, Parser)]
+pub struct PublishHubOpts {
+    /// Signed package file: e.g. .hub/jolt-0.1.0.ipkg
+    #[arg(value_name = "ipkg", required = true)]
+    ipkg: String,
+
+    /// Target connector packages are published for, defaults to the target they were built for
+    #[arg(long)]
+    target: Option<String>,
+
+    /// Check the package and print what would be uploaded, without uploading it
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Publish a public package without asking for confirmation
+    #[arg(long)]
+    public_yes: bool,
+
+    #[arg(long, hide_short_help = true)]
+    remote: Option<String>,
+}
+
+impl PublishHubOpts {
+    pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
+        let mut lint = hubutil::package_lint(&self.ipkg, self.target.as_deref())
+            .map_err(|err| CliError::PackageError(format!("reading {}: {err}", self.ipkg)))?;
+
+        // the credentials are only needed to upload, a dry run checks them when present
+        let access = if self.dry_run {
+            HubAccess::default_load(&self.remote).ok()
+        } else {
+            Some(get_hub_access(&self.remote)?)
+        };
+        if let Some(access) = &access {
+            if !lint.signers.contains(&access.pubkey) {
+                lint.problems.push(format!(
+                    "package is not signed with the key of hub id {}",
+                    access.hubid
+                ));
+            }
+        }
+
+        print_lint(&lint, access.as_ref(), out.as_ref());
+        if !lint.is_ok() {
+            return Err(CliError::PackageError(format!(
+                "{} has {} problem(s), it would be rejected",
+                self.ipkg,
+                lint.problems.len()
+            ))
+            .into());
+        }
+        let Some(access) = access.filter(|_| !self.dry_run) else {
+            out.println("Dry run, nothing was uploaded");
+            return Ok(());
+        };
+        if lint.meta.visibility == PkgVisibility::Public && !self.public_yes {
+            return Err(CliError::HubError(
+                "package is public, confirm its publishing with --public-yes".to_owned(),
+            )
+            .into());
+        }
+
+        match &lint.kind {
+            PackageKind::SmartModule => hubutil::push_package(&self.ipkg, &access).await?,
+            PackageKind::Connector { target } => {
+                let target = self.target.as_deref().unwrap_or(target);
+                hubutil::push_package_conn(&self.ipkg, &access, target).await?
+            }
+            PackageKind::Sdf => hubutil::push_package_sdf(&self.ipkg, &access).await?,
+        }
+        Ok(())
+    }
+}
+
+fn print_lint<O: Terminal>(lint: &PackageLint, access: Option<&HubAccess>, out: &O) {
+    let size = |len: u64| ByteSize::b(len).to_string_as(true);
+    out.println(&format!("Package:     {}", lint.meta.pkg_name()));
+    out.println(&format!("Visibility:  {:?}", lint.meta.visibility));
+    match &lint.kind {
+        PackageKind::SmartModule => out.println("Kind:        SmartModule"),
+        PackageKind::Connector { target } => {
+            out.println(&format!("Kind:        Connector for {target}"))
+        }
+        PackageKind::Sdf => out.println("Kind:        SDF"),
+    }
+    if let Some(access) = access {
+        out.println(&format!(
+            "Hub:         {} as {}",
+            access.remote, access.hubid
+        ));
+    }
+    out.println(&format!("Upload:      {}", size(lint.size)));
+    for file in &lint.files {
+        out.println(&format!("  {:<28} {}", file.name, size(file.len)));
+    }
+    out.println("Manifest:");
+    for file in &lint.manifest {
+        out.println(&format!("  {:<28} {}", file.name, size(file.len)));
+    }
+    out.println(&format!("Signed by:   {}", lint.signers.join(", ")));
+    if lint.is_ok() {
+        out.println("Checks:      passed");
+    } else {
+        out.println("Problems:");
+        for problem in &lint.problems {
+            out.println(&format!("  {problem}"));
+        }
+    }
+}
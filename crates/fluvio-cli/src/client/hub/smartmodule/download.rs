@@ -15,7 +15,7 @@ use fluvio_controlplane_metadata::smartmodule::{SmartModuleMetadata, SmartModule
 use fluvio_extension_common::Terminal;
 use fluvio_extension_common::target::ClusterTarget;
 use fluvio_hub_util as hubutil;
-use hubutil::HubAccess;
+use hubutil::{CacheMode, HubAccess, HubCache};
 use hubutil::cmd::get_hub_access;
 
 use crate::CliError;
@@ -39,6 +39,14 @@ pub struct SmartModuleDownloadHubOpts {
     #[arg(long)]
     ipkg: bool,
 
+    /// Refuse packages without a verified build provenance
+    #[arg(long)]
+    require_provenance: bool,
+
+    /// Only use the local cache of the hub, without reaching it
+    #[arg(long)]
+    offline: bool,
+
     #[arg(long, hide_short_help = true)]
     remote: Option<String>,
 }
@@ -52,13 +60,19 @@ impl ClientCmd for SmartModuleDownloadHubOpts {
     ) -> Result<()> {
         if self.ipkg {
             // pkgname is a package file
+            verify_provenance(&self.pkgname, self.require_provenance)?;
             let fluvio_config = self.target.load()?;
             download_cluster(fluvio_config, &self.pkgname).await?;
             return Ok(());
         }
         let access = get_hub_access(&self.remote)?;
 
-        let pkgfile = download_local(&self.pkgname, &access, self.output.clone()).await?;
+        let mode = CacheMode::from_offline(self.offline);
+        let pkgfile = download_local(&self.pkgname, &access, self.output.clone(), mode).await?;
+        if let Err(err) = verify_provenance(&pkgfile, self.require_provenance) {
+            std::fs::remove_file(&pkgfile)?;
+            return Err(err);
+        }
         if self.output.is_some() {
             return Ok(());
         }
@@ -75,6 +89,7 @@ async fn download_local(
     pkgname: &str,
     access: &HubAccess,
     output: Option<PathBuf>,
+    mode: CacheMode,
 ) -> Result<String> {
     let file_name = hubutil::cli_pkgname_to_filename(pkgname).map_err(|_| {
         CliError::HubError(format!(
@@ -95,7 +110,9 @@ async fn download_local(
         .map_err(|_| CliError::HubError(format!("invalid pkgname {pkgname}")))?;
     println!("downloading {pkgname} to {}", file_path.display());
 
-    let data = hubutil::get_package(&url, access)
+    hubutil::check_download_scope(pkgname)?;
+    let cache = HubCache::default_load()?;
+    let data = hubutil::get_package_cached(&url, access, &cache, mode)
         .await
         .map_err(|err| CliError::HubError(format!("downloading {pkgname}\nServer: {err}")))?;
 
@@ -104,6 +121,27 @@ async fn download_local(
     Ok(file_path.display().to_string())
 }
 
+/// verify the provenance of a package when it has one
+fn verify_provenance(pkgfile: &str, require_provenance: bool) -> Result<()> {
+    let provenance = hubutil::package_verify_provenance(pkgfile).map_err(|err| {
+        CliError::PackageError(format!("verifying provenance of {pkgfile}: {err}"))
+    })?;
+    match provenance {
+        Some(provenance) => {
+            let commit = provenance.git_commit.as_deref().unwrap_or("unknown commit");
+            println!("... provenance verified, built from {commit}");
+        }
+        None if require_provenance => {
+            return Err(CliError::PackageError(format!(
+                "{pkgfile} has no provenance, it is required by --require-provenance"
+            ))
+            .into());
+        }
+        None => {}
+    }
+    Ok(())
+}
+
 // download smartmodule from pkg to cluster
 async fn download_cluster(config: FluvioConfig, pkgfile: &str) -> Result<()> {
     println!("... checking package");
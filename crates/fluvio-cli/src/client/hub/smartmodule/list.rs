@@ -5,7 +5,7 @@ use clap::Parser;
 use anyhow::Result;
 
 use fluvio_extension_common::Terminal;
-use fluvio_hub_util::HUB_API_LIST_META;
+use fluvio_hub_util::{CacheMode, HUB_API_LIST_META};
 use fluvio_hub_util::cmd::get_pkg_list;
 
 use crate::common::OutputFormat;
@@ -19,13 +19,23 @@ pub struct SmartModuleHubListOpts {
     #[arg(long, hide = true)]
     system: bool,
 
+    /// Only use the local cache of the hub, without reaching it
+    #[arg(long)]
+    offline: bool,
+
     #[arg(long, hide_short_help = true)]
     remote: Option<String>,
 }
 
 impl SmartModuleHubListOpts {
     pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
-        let pl = get_pkg_list(HUB_API_LIST_META, &self.remote, self.system).await?;
+        let pl = get_pkg_list(
+            HUB_API_LIST_META,
+            &self.remote,
+            self.system,
+            CacheMode::from_offline(self.offline),
+        )
+        .await?;
         output::smartmodules_response_to_output(out, pl.packages, self.output.format)?;
         Ok(())
     }
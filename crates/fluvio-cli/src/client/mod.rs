@@ -9,6 +9,12 @@ mod smartmodule_invocation;
 mod consumer;
 mod remote;
 mod home;
+mod alert;
+mod acl;
+mod group;
+mod schema;
+mod connector;
+mod pipeline;
 
 pub use metadata::client_metadata;
 pub use cmd::FluvioCmd;
@@ -57,7 +63,13 @@ mod cmd {
     use super::topic::TopicCmd;
     use super::partition::PartitionCmd;
     use super::tableformat::TableFormatCmd;
+    use super::alert::AlertCmd;
+    use super::acl::AclCmd;
+    use super::group::GroupCmd;
+    use super::schema::SchemaCmd;
     use super::hub::HubCmd;
+    use super::connector::ConnectorCmd;
+    use super::pipeline::PipelineCmd;
 
     #[async_trait]
     pub trait ClientCmd: Sized {
@@ -137,10 +149,54 @@ mod cmd {
         #[command(subcommand, name = "table-format", visible_alias = "tf")]
         TableFormat(TableFormatCmd),
 
+        /// Manage AlertRules evaluated by the cluster
+        ///
+        /// An AlertRule compares a cluster metric such as replica lag or offline
+        /// partitions with a threshold, and calls webhooks or produces to a topic
+        /// when the condition holds.
+        #[command(subcommand, name = "alert")]
+        Alert(AlertCmd),
+
+        /// Manage Acls enforced by the cluster
+        ///
+        /// An Acl grants produce, consume or admin to the principal of a client
+        /// certificate, on matching topics or on the whole cluster. Enforced when
+        /// the cluster is started with `--acl`.
+        #[command(subcommand, name = "acl")]
+        Acl(AclCmd),
+
+        /// Manage consumer groups
+        ///
+        /// Members of a consumer group share the partitions of a topic, the
+        /// cluster assigns the partitions and rebalances them as members come and go.
+        #[command(subcommand, name = "group", visible_alias = "consumer-group")]
+        Group(GroupCmd),
+
+        /// Manage the schemas of topics
+        ///
+        /// Each version of the schema of a topic must be compatible with the latest
+        /// one, producers can check records against the latest version.
+        #[command(subcommand, name = "schema")]
+        Schema(SchemaCmd),
+
         /// Work with the SmartModule Hub
         #[command(subcommand, name = "hub")]
         Hub(HubCmd),
 
+        /// View connectors running against the cluster
+        ///
+        /// Connectors produce their logs to a system topic of the cluster, the same
+        /// for local and Kubernetes deployments.
+        #[command(subcommand, name = "connector")]
+        Connector(ConnectorCmd),
+
+        /// View the dataflows of the cluster
+        ///
+        /// Exports how records flow between topics, SmartModules, connectors and
+        /// mirrored clusters as a graph.
+        #[command(subcommand, name = "pipeline")]
+        Pipeline(PipelineCmd),
+
         /// Manage and view Consumers
         #[command(subcommand, name = "consumer")]
         Consumer(ConsumerCmd),
@@ -180,9 +236,27 @@ mod cmd {
                 Self::TableFormat(tableformat) => {
                     tableformat.process(out, target).await?;
                 }
+                Self::Alert(alert) => {
+                    alert.process(out, target).await?;
+                }
+                Self::Acl(acl) => {
+                    acl.process(out, target).await?;
+                }
+                Self::Group(group) => {
+                    group.process(out, target).await?;
+                }
+                Self::Schema(schema) => {
+                    schema.process(out, target).await?;
+                }
                 Self::Hub(hub) => {
                     hub.process(out, target).await?;
                 }
+                Self::Connector(connector) => {
+                    connector.process(out, target).await?;
+                }
+                Self::Pipeline(pipeline) => {
+                    pipeline.process(out, target).await?;
+                }
                 Self::Consumer(consumer) => {
                     consumer.process(out, target).await?;
                 }
@@ -140,13 +140,19 @@ mod display {
                         _ => bytesize::ByteSize::b(status.size as u64).to_string(),
                     };
 
+                    let resolution = if status.storage_full {
+                        format!("{:?} (storage full)", status.resolution)
+                    } else {
+                        format!("{:?}", status.resolution)
+                    };
+
                     Row::from([
                         Cell::new(topic),
                         Cell::new(partition),
                         Cell::new(spec.leader.to_string()),
                         Cell::new(spec.mirror_string()),
                         Cell::new(format!("{:?}", spec.followers())),
-                        Cell::new(format!("{:?}", status.resolution)),
+                        Cell::new(resolution),
                         Cell::new(printable_size),
                         Cell::new(format!("{:?}", status.base_offset)),
                         Cell::new(status.leader.hw.to_string()),
@@ -49,12 +49,12 @@ mod cmd {
 
     impl PartitionCmd {
         pub fn metadata() -> FluvioExtensionMetadata {
-            FluvioExtensionMetadata {
-                title: "partition".into(),
-                package: Some("fluvio/fluvio".parse().unwrap()),
-                description: "Partition Operations".into(),
-                version: semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
-            }
+            FluvioExtensionMetadata::new(
+                "partition",
+                "Partition Operations",
+                semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
+            )
+            .with_package("fluvio/fluvio".parse().unwrap())
         }
     }
 }
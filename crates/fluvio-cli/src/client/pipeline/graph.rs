@@ -0,0 +1,372 @@
+//!
+//! # Pipeline Graph CLI
+//!
+//! CLI to export the dataflows of the cluster as a DOT or Mermaid graph
+//!
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::{Parser, ValueEnum};
+use tracing::debug;
+use anyhow::{Context, Result};
+
+use fluvio::Fluvio;
+use fluvio::metadata::topic::{MirrorConfig, ReplicaSpec, TopicSpec};
+use fluvio_connector_package::config::ConnectorConfig;
+
+use crate::common::output::Terminal;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct PipelineGraphOpt {
+    /// Format of the graph
+    #[arg(short = 'o', long, value_enum, default_value_t = GraphFormat::Dot)]
+    output: GraphFormat,
+
+    /// Config of a connector to add to the graph, connectors are not known to the cluster
+    #[arg(short, long, value_name = "PATH")]
+    connector: Vec<PathBuf>,
+
+    /// Add system topics to the graph, they are only shown when linked otherwise
+    #[arg(long)]
+    system: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Eq, PartialEq)]
+enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+impl PipelineGraphOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let mut topology = Topology::default();
+
+        let admin = fluvio.admin().await;
+        for topic in admin.all::<TopicSpec>().await? {
+            if self.system || !topic.spec.is_system() {
+                topology.add_topic(&topic.name, &topic.spec);
+            }
+        }
+        for path in &self.connector {
+            let config = ConnectorConfig::from_file(path)
+                .with_context(|| format!("unable to read connector config {}", path.display()))?;
+            topology.add_connector(&config);
+        }
+        debug!(
+            nodes = topology.nodes.len(),
+            edges = topology.edges.len(),
+            "pipeline topology"
+        );
+
+        let graph = match self.output {
+            GraphFormat::Dot => topology.to_dot(),
+            GraphFormat::Mermaid => topology.to_mermaid(),
+        };
+        out.println(graph.trim_end());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum NodeKind {
+    Topic,
+    SmartModule,
+    Connector,
+    /// cluster the topic is mirrored from or to
+    Cluster,
+}
+
+type Node = (NodeKind, String);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Edge {
+    from: Node,
+    to: Node,
+    label: Option<String>,
+    /// records flow along the edge, otherwise it links a SmartModule to what runs it
+    data: bool,
+}
+
+/// Topics, SmartModules, connectors and mirrored clusters with the links between them
+#[derive(Debug, Default)]
+struct Topology {
+    nodes: BTreeSet<Node>,
+    edges: BTreeSet<Edge>,
+}
+
+impl Topology {
+    fn add_topic(&mut self, name: &str, spec: &TopicSpec) {
+        let topic = self.node(NodeKind::Topic, name);
+
+        if let Some(validation) = spec.get_validation() {
+            let module = self.node(NodeKind::SmartModule, &validation.transform.uses);
+            self.link(module, topic.clone(), Some("validates"), false);
+            if let Some(dead_letter) = validation.on_invalid.dead_letter_topic() {
+                let dead_letter = self.node(NodeKind::Topic, dead_letter);
+                self.link(topic.clone(), dead_letter, Some("invalid"), true);
+            }
+        }
+        if let Some(deduplication) = spec.get_deduplication() {
+            let module = self.node(NodeKind::SmartModule, &deduplication.filter.transform.uses);
+            self.link(module, topic.clone(), Some("deduplicates"), false);
+        }
+        if let Some(source) = spec.get_clone_from() {
+            let source = self.node(NodeKind::Topic, source);
+            self.link(source, topic.clone(), Some("clone"), true);
+        }
+
+        if let ReplicaSpec::Mirror(mirror) = spec.replicas() {
+            match mirror {
+                MirrorConfig::Home(home) => {
+                    let remotes: BTreeSet<&str> = home
+                        .partitions()
+                        .iter()
+                        .map(|partition| partition.remote_cluster.as_str())
+                        .collect();
+                    for remote in remotes {
+                        let remote = self.node(NodeKind::Cluster, remote);
+                        if home.source {
+                            self.link(topic.clone(), remote, Some("mirror"), true);
+                        } else {
+                            self.link(remote, topic.clone(), Some("mirror"), true);
+                        }
+                    }
+                }
+                MirrorConfig::Remote(remote) => {
+                    let home = self.node(NodeKind::Cluster, &remote.home_cluster);
+                    if remote.target {
+                        self.link(home, topic, Some("mirror"), true);
+                    } else {
+                        self.link(topic, home, Some("mirror"), true);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_connector(&mut self, config: &ConnectorConfig) {
+        let connector = self.node(NodeKind::Connector, &config.name());
+        let topic = self.node(NodeKind::Topic, config.meta().topic());
+
+        let transforms = config.transforms();
+        for step in &transforms {
+            let module = self.node(NodeKind::SmartModule, &step.uses);
+            self.link(connector.clone(), module, None, false);
+            if let Some(dead_letter) = &step.dead_letter {
+                let dead_letter = self.node(NodeKind::Topic, &dead_letter.topic);
+                self.link(connector.clone(), dead_letter, Some("dead letter"), true);
+            }
+        }
+
+        let chain = (!transforms.is_empty()).then(|| {
+            transforms
+                .iter()
+                .map(|step| step.uses.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+        if config.direction().is_source() {
+            self.link(connector, topic, chain.as_deref(), true);
+        } else {
+            self.link(topic, connector, chain.as_deref(), true);
+        }
+    }
+
+    fn node(&mut self, kind: NodeKind, name: &str) -> Node {
+        let node = (kind, name.to_owned());
+        self.nodes.insert(node.clone());
+        node
+    }
+
+    fn link(&mut self, from: Node, to: Node, label: Option<&str>, data: bool) {
+        self.edges.insert(Edge {
+            from,
+            to,
+            label: label.map(str::to_owned),
+            data,
+        });
+    }
+
+    /// identifiers of the nodes, names may not be valid identifiers in the graph languages
+    fn ids(&self) -> BTreeMap<&Node, String> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node, format!("n{index}")))
+            .collect()
+    }
+
+    fn to_dot(&self) -> String {
+        let ids = self.ids();
+        let mut dot = String::from("digraph pipeline {\n    rankdir=LR;\n");
+        for (node, id) in &ids {
+            let shape = match node.0 {
+                NodeKind::Topic => "box",
+                NodeKind::SmartModule => "ellipse",
+                NodeKind::Connector => "component",
+                NodeKind::Cluster => "box3d",
+            };
+            let _ = writeln!(
+                dot,
+                "    {id} [label=\"{}\", shape={shape}];",
+                dot_escape(&node.1)
+            );
+        }
+        for edge in &self.edges {
+            let mut attributes = Vec::new();
+            if let Some(label) = &edge.label {
+                attributes.push(format!("label=\"{}\"", dot_escape(label)));
+            }
+            if !edge.data {
+                attributes.push("style=dashed".to_owned());
+            }
+            let attributes = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            let _ = writeln!(
+                dot,
+                "    {} -> {}{attributes};",
+                ids[&edge.from], ids[&edge.to]
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        let ids = self.ids();
+        let mut mermaid = String::from("flowchart LR\n");
+        for (node, id) in &ids {
+            let name = mermaid_escape(&node.1);
+            let _ = match node.0 {
+                NodeKind::Topic => writeln!(mermaid, "    {id}[\"{name}\"]"),
+                NodeKind::SmartModule => writeln!(mermaid, "    {id}([\"{name}\"])"),
+                NodeKind::Connector => writeln!(mermaid, "    {id}[[\"{name}\"]]"),
+                NodeKind::Cluster => writeln!(mermaid, "    {id}{{{{\"{name}\"}}}}"),
+            };
+        }
+        for edge in &self.edges {
+            let arrow = if edge.data { "-->" } else { "-.->" };
+            let label = edge
+                .label
+                .as_ref()
+                .map(|label| format!("|\"{}\"|", mermaid_escape(label)))
+                .unwrap_or_default();
+            let _ = writeln!(
+                mermaid,
+                "    {} {arrow}{label} {}",
+                ids[&edge.from], ids[&edge.to]
+            );
+        }
+        mermaid
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio::metadata::topic::{HomeMirrorConfig, OnInvalidRecord, Transform, Validation};
+
+    use super::*;
+
+    fn topology() -> Topology {
+        let mut topology = Topology::default();
+
+        let mut orders = TopicSpec::new_computed(1, 1, None);
+        orders.set_validation(Some(Validation {
+            transform: Transform {
+                uses: "acme/order-check@0.1.0".to_owned(),
+                with: Default::default(),
+            },
+            on_invalid: OnInvalidRecord::DeadLetter {
+                topic: "orders-invalid".to_owned(),
+            },
+        }));
+        topology.add_topic("orders", &orders);
+
+        let mut mirror = HomeMirrorConfig::from_simple("orders-edge", vec!["edge-1".to_owned()]);
+        mirror.set_home_to_remote(true);
+        topology.add_topic(
+            "orders-edge",
+            &TopicSpec::new_mirror(MirrorConfig::Home(mirror)),
+        );
+
+        let config = ConnectorConfig::config_from_str(
+            r#"
+apiVersion: 0.1.0
+meta:
+  version: 0.1.0
+  name: orders-http
+  type: http-source
+  topic: orders
+transforms:
+  - uses: infinyon/jolt@0.1.0
+    dead_letter:
+      topic: orders-failed
+"#,
+        )
+        .expect("connector config");
+        topology.add_connector(&config);
+        topology
+    }
+
+    #[test]
+    fn test_topology() {
+        let topology = topology();
+        assert_eq!(
+            topology.nodes,
+            BTreeSet::from([
+                (NodeKind::Topic, "orders".to_owned()),
+                (NodeKind::Topic, "orders-edge".to_owned()),
+                (NodeKind::Topic, "orders-failed".to_owned()),
+                (NodeKind::Topic, "orders-invalid".to_owned()),
+                (NodeKind::SmartModule, "acme/order-check@0.1.0".to_owned()),
+                (NodeKind::SmartModule, "infinyon/jolt@0.1.0".to_owned()),
+                (NodeKind::Connector, "orders-http".to_owned()),
+                (NodeKind::Cluster, "edge-1".to_owned()),
+            ])
+        );
+        assert_eq!(topology.edges.len(), 6);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = topology().to_dot();
+        assert!(dot.starts_with("digraph pipeline {\n"));
+        assert!(dot.contains("    n0 [label=\"orders\", shape=box];\n"));
+        assert!(dot.contains("    n6 -> n0 [label=\"infinyon/jolt@0.1.0\"];\n"));
+        assert!(dot.contains("    n4 -> n0 [label=\"validates\", style=dashed];\n"));
+        assert!(dot.contains("    n1 -> n7 [label=\"mirror\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mermaid = topology().to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("    n4([\"acme/order-check@0.1.0\"])\n"));
+        assert!(mermaid.contains("    n6[[\"orders-http\"]]\n"));
+        assert!(mermaid.contains("    n7{{\"edge-1\"}}\n"));
+        assert!(mermaid.contains("    n6 -.-> n5\n"));
+        assert!(mermaid.contains("    n0 -->|\"invalid\"| n3\n"));
+        assert_eq!(mermaid_escape("say \"hi\""), "say #quot;hi#quot;");
+    }
+}
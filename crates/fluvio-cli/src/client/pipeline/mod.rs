@@ -0,0 +1,47 @@
+mod graph;
+
+pub use cmd::PipelineCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::graph::PipelineGraphOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum PipelineCmd {
+        /// Print the topology of the dataflows of the cluster as a graph
+        #[command(
+            name = "graph",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Graph(PipelineGraphOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for PipelineCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Graph(graph) => {
+                    graph.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -13,7 +13,9 @@ mod cmd {
 
     use async_trait::async_trait;
     use fluvio_sc_schema::partition::PartitionMirrorConfig;
-    use fluvio_sc_schema::topic::{MirrorConfig, PartitionMap, ReplicaSpec, TopicSpec};
+    use fluvio_sc_schema::topic::{
+        CompressionDictionary, MirrorConfig, PartitionMap, ReplicaSpec, TopicSpec,
+    };
     #[cfg(feature = "producer-file-io")]
     use futures::future::join_all;
     use clap::Parser;
@@ -90,6 +92,11 @@ mod cmd {
         #[arg(long)]
         pub compression: Option<Compression>,
 
+        /// Path to a trained zstd dictionary to compress records with, instead of
+        /// the dictionary of the topic. Requires `--compression zstd`
+        #[arg(long, value_name = "file", requires = "compression")]
+        pub dictionary: Option<std::path::PathBuf>,
+
         #[cfg(feature = "producer-file-io")]
         /// Path to a file to produce to the topic.
         /// Default: Each line treated as single record unless `--raw` specified.
@@ -110,6 +117,27 @@ mod cmd {
         #[arg(long)]
         pub max_request_size: Option<usize>,
 
+        /// Split records larger than max-request-size into chunks, reassembled by consumers
+        #[arg(long)]
+        pub chunking: bool,
+
+        /// Max produce requests sent to a partition without their response.
+        /// Limited to 1 for at_least_once unless --allow-reordering is set
+        #[arg(long)]
+        pub max_inflight_requests: Option<usize>,
+
+        /// Allow retried requests to land after later requests of the same partition
+        #[arg(long)]
+        pub allow_reordering: bool,
+
+        /// Attach sequence numbers to the records so retries do not duplicate them
+        #[arg(long)]
+        pub idempotence: bool,
+
+        /// Reject records whose value does not match the latest JSON schema of the topic
+        #[arg(long)]
+        pub schema_validation: bool,
+
         /// Isolation level that producer must respect.
         /// Supported values: read_committed (ReadCommitted) - wait for records to be committed before response,
         /// read_uncommitted (ReadUncommitted) - just wait for leader to accept records.
@@ -211,6 +239,17 @@ mod cmd {
             } else {
                 config_builder
             };
+            let config_builder = if let Some(path) = &self.dictionary {
+                let dictionary = std::fs::read(path).map_err(|err| {
+                    CliError::InvalidArg(format!(
+                        "unable to read dictionary {}: {err}",
+                        path.display()
+                    ))
+                })?;
+                config_builder.compression_dictionary(CompressionDictionary::new(dictionary))
+            } else {
+                config_builder
+            };
 
             // Linger
             let config_builder = if let Some(linger) = self.linger {
@@ -233,6 +272,18 @@ mod cmd {
                 config_builder
             };
 
+            let config_builder = config_builder.chunking(self.chunking);
+
+            // In flight requests
+            let config_builder = if let Some(max_inflight) = self.max_inflight_requests {
+                config_builder.max_inflight_requests(max_inflight)
+            } else {
+                config_builder
+            };
+            let config_builder = config_builder.strict_ordering(!self.allow_reordering);
+            let config_builder = config_builder.idempotence(self.idempotence);
+            let config_builder = config_builder.schema_validation(self.schema_validation);
+
             // Isolation
             let config_builder = if let Some(isolation) = self.isolation {
                 config_builder.isolation(isolation)
@@ -495,12 +546,12 @@ mod cmd {
         }
 
         pub fn metadata() -> FluvioExtensionMetadata {
-            FluvioExtensionMetadata {
-                title: "produce".into(),
-                package: Some("fluvio/fluvio".parse().unwrap()),
-                description: "Produce new data in a stream".into(),
-                version: semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
-            }
+            FluvioExtensionMetadata::new(
+                "produce",
+                "Produce new data in a stream",
+                semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
+            )
+            .with_package("fluvio/fluvio".parse().unwrap())
         }
 
         fn smartmodule_invocations(
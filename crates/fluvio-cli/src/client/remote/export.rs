@@ -66,6 +66,7 @@ impl ExportOpt {
             remote_id: self.remote_id,
             public_endpoint,
             client_tls,
+            proxy: None,
         };
 
         let metadata = RemoteMetadataExport::new(home_metadata);
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use serde::Serialize;
+use tracing::debug;
+
+use fluvio::Fluvio;
+use fluvio::config::ConfigFile;
+use fluvio::metadata::topic::{ReplicaSpec, TopicSpec};
+use fluvio_extension_common::target::ClusterTarget;
+use fluvio_extension_common::{OutputFormat, Terminal};
+use fluvio_extension_common::t_println;
+
+/// Fail consumers over to the mirror cluster.
+///
+/// The committed offsets of the consumers of mirrored topics are copied to the mirror along
+/// with the records, so consumers connecting to it resume from where they left the primary.
+#[derive(Debug, Parser)]
+pub struct FailoverOpt {
+    /// profile of the mirror cluster to fail over to
+    #[arg(value_name = "profile")]
+    profile: String,
+    /// only show the offsets of this consumer
+    #[arg(long)]
+    consumer: Option<String>,
+    /// make the mirror profile the current one
+    #[arg(long)]
+    switch: bool,
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl FailoverOpt {
+    pub async fn execute<T: Terminal>(
+        self,
+        out: Arc<T>,
+        cluster_target: ClusterTarget,
+    ) -> Result<()> {
+        let mut config_file = ConfigFile::load(None)?;
+        let mirror_config = config_file
+            .config()
+            .cluster_with_profile(&self.profile)
+            .ok_or_else(|| anyhow!("profile {} not found", self.profile))?;
+        let mirror = Fluvio::connect_with_config(mirror_config).await?;
+
+        let mirrored: Vec<String> = mirror
+            .admin()
+            .await
+            .all::<TopicSpec>()
+            .await?
+            .into_iter()
+            .filter(|topic| matches!(topic.spec.replicas(), ReplicaSpec::Mirror(_)))
+            .map(|topic| topic.name)
+            .collect();
+
+        let mut rows: BTreeMap<(String, String, u32), FailoverRow> = BTreeMap::new();
+        for offset in mirror.consumer_offsets().await? {
+            if !mirrored.contains(&offset.topic) || !self.selected(&offset.consumer_id) {
+                continue;
+            }
+            rows.insert(
+                (
+                    offset.consumer_id.clone(),
+                    offset.topic.clone(),
+                    offset.partition,
+                ),
+                FailoverRow {
+                    consumer: offset.consumer_id,
+                    topic: offset.topic,
+                    partition: offset.partition,
+                    primary_offset: None,
+                    mirror_offset: offset.offset,
+                },
+            );
+        }
+
+        // the primary is usually down when failing over, its offsets are only shown if reachable
+        match primary_offsets(cluster_target).await {
+            Ok(offsets) => {
+                for offset in offsets {
+                    if let Some(row) =
+                        rows.get_mut(&(offset.consumer_id, offset.topic, offset.partition))
+                    {
+                        row.primary_offset = Some(offset.offset);
+                    }
+                }
+            }
+            Err(err) => {
+                debug!(%err, "primary cluster unreachable");
+                t_println!(
+                    out,
+                    "primary cluster is unreachable, showing the offsets of the mirror only"
+                );
+            }
+        }
+
+        output::format(
+            out.clone(),
+            rows.into_values().collect(),
+            self.output.format,
+        )?;
+
+        if self.switch {
+            if !config_file.mut_config().set_current_profile(&self.profile) {
+                return Err(anyhow!("profile {} not found", self.profile));
+            }
+            config_file.save()?;
+            t_println!(out, "switched to profile \"{}\"", self.profile);
+        }
+        Ok(())
+    }
+
+    fn selected(&self, consumer_id: &str) -> bool {
+        self.consumer
+            .as_deref()
+            .map_or(true, |consumer| consumer == consumer_id)
+    }
+}
+
+async fn primary_offsets(
+    cluster_target: ClusterTarget,
+) -> Result<Vec<fluvio::consumer::ConsumerOffset>> {
+    let primary = Fluvio::connect_with_config(&cluster_target.load()?).await?;
+    primary.consumer_offsets().await
+}
+
+#[derive(Serialize)]
+struct FailoverRow {
+    consumer: String,
+    topic: String,
+    partition: u32,
+    primary_offset: Option<i64>,
+    mirror_offset: i64,
+}
+
+mod output {
+
+    //!
+    //! # Fluvio remote failover - output processing
+    //!
+    use comfy_table::{Cell, Row};
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    use super::FailoverRow;
+
+    #[derive(Serialize)]
+    struct TableList(Vec<FailoverRow>);
+
+    pub fn format<O: Terminal>(
+        out: std::sync::Arc<O>,
+        listvec: Vec<FailoverRow>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        if !listvec.is_empty() {
+            out.render_list(&TableList(listvec), output_type)?;
+        } else {
+            t_println!(out, "no consumer offsets mirrored");
+        }
+        Ok(())
+    }
+
+    impl TableOutputHandler for TableList {
+        fn header(&self) -> Row {
+            Row::from(["CONSUMER", "TOPIC", "PARTITION", "PRIMARY", "MIRROR"])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|row| {
+                    let primary = row
+                        .primary_offset
+                        .map(|offset| offset.to_string())
+                        .unwrap_or_else(|| "-".to_owned());
+                    Row::from([
+                        Cell::new(&row.consumer),
+                        Cell::new(&row.topic),
+                        Cell::new(row.partition),
+                        Cell::new(primary),
+                        Cell::new(row.mirror_offset),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
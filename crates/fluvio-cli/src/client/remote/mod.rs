@@ -2,6 +2,7 @@ pub mod unregister;
 pub mod list;
 pub mod register;
 pub mod export;
+pub mod failover;
 
 use std::sync::Arc;
 use anyhow::Result;
@@ -13,6 +14,7 @@ use register::RegisterOpt;
 use fluvio::FluvioAdmin;
 use fluvio_extension_common::output::Terminal;
 use self::export::ExportOpt;
+use self::failover::FailoverOpt;
 
 #[derive(Debug, Parser)]
 pub enum RemoteCmd {
@@ -28,6 +30,9 @@ pub enum RemoteCmd {
     /// Generate metadata file for remote cluster
     #[command(name = "export")]
     Export(ExportOpt),
+    /// Show the consumer offsets mirrored to a cluster and switch to it
+    #[command(name = "failover")]
+    Failover(FailoverOpt),
 }
 
 impl RemoteCmd {
@@ -41,6 +46,7 @@ impl RemoteCmd {
             Self::Unregister(del) => del.execute(out, cluster_target).await,
             Self::List(list) => list.execute(out, cluster_target).await,
             Self::Export(meta) => meta.execute(out, cluster_target).await,
+            Self::Failover(failover) => failover.execute(out, cluster_target).await,
         }
     }
 }
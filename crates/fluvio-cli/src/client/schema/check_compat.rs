@@ -0,0 +1,34 @@
+//!
+//! # Check Schema Compatibility
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+
+#[derive(Debug, Parser)]
+pub struct CheckCompatOpt {
+    /// The topic whose schema is checked against
+    #[arg(value_name = "topic")]
+    pub topic: String,
+
+    /// Path to the schema definition
+    #[arg(short, long, value_name = "path")]
+    pub file: PathBuf,
+}
+
+impl CheckCompatOpt {
+    /// Fails when the definition can not be registered
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let definition = std::fs::read_to_string(&self.file)?;
+        let schemas = fluvio.schema_client().await;
+        schemas
+            .check_compatibility(&self.topic, &definition)
+            .await?;
+        println!("schema is compatible with topic \"{}\"", self.topic);
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//!
+//! # Register a Schema
+//!
+//! CLI tree to register a version of the schema of a topic
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::schema::{SchemaCompatibility, SchemaFormat};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateSchemaOpt {
+    /// The topic whose records follow the schema
+    #[arg(value_name = "topic")]
+    pub topic: String,
+
+    /// Format of the schema: json-schema, avro or protobuf
+    #[arg(long, value_name = "format")]
+    pub format: SchemaFormat,
+
+    /// Path to the schema definition
+    #[arg(short, long, value_name = "path")]
+    pub file: PathBuf,
+
+    /// Rule checked when versions are registered: none, backward, forward or full.
+    /// Applies to the schema from this version on.
+    #[arg(long, value_name = "compatibility")]
+    pub compatibility: Option<SchemaCompatibility>,
+}
+
+impl CreateSchemaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let definition = std::fs::read_to_string(&self.file)?;
+        let schemas = fluvio.schema_client().await;
+
+        let exists = schemas.get(&self.topic).await?.is_some();
+        if let (true, Some(compatibility)) = (exists, self.compatibility) {
+            schemas
+                .set_compatibility(&self.topic, compatibility)
+                .await?;
+        }
+
+        debug!(topic = %self.topic, format = %self.format, "registering schema");
+
+        let version = schemas
+            .register(
+                &self.topic,
+                self.format,
+                self.compatibility.unwrap_or_default(),
+                definition,
+            )
+            .await?;
+        println!(
+            "schema of topic \"{}\" registered as version {version}",
+            self.topic
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! # List Schemas CLI
+//!
+//! CLI tree and processing to list schemas
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListSchemasOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListSchemasOpt {
+    /// Process list schemas cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let lists = fluvio.schema_client().await.list().await?;
+
+        output::schemas_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::Row;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::schema::SchemaSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListSchemas(Vec<Metadata<SchemaSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format schema list
+    pub fn schemas_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_schemas: Vec<Metadata<SchemaSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("schemas: {:#?}", list_schemas);
+
+        if !list_schemas.is_empty() {
+            let schemas = ListSchemas(list_schemas);
+            out.render_list(&schemas, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no schemas");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListSchemas {
+        /// schema header implementation
+        fn header(&self) -> Row {
+            Row::from(["TOPIC", "FORMAT", "COMPATIBILITY", "VERSION", "STATUS"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    Row::from([
+                        r.name.clone(),
+                        r.spec.format.to_string(),
+                        r.spec.compatibility.to_string(),
+                        r.spec
+                            .latest()
+                            .map(|latest| latest.version.to_string())
+                            .unwrap_or_default(),
+                        r.status.to_string(),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
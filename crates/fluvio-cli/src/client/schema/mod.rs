@@ -0,0 +1,71 @@
+mod create;
+mod list;
+mod check_compat;
+
+pub use cmd::SchemaCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreateSchemaOpt;
+    use super::list::ListSchemasOpt;
+    use super::check_compat::CheckCompatOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum SchemaCmd {
+        /// Register a new version of the schema of a topic
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreateSchemaOpt),
+
+        /// List the schemas of all topics
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListSchemasOpt),
+
+        /// Check a schema against the latest version, without registering it
+        #[command(
+            name = "check-compat",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        CheckCompat(CheckCompatOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for SchemaCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+                Self::CheckCompat(check) => {
+                    check.process(fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//!
+//! # Clone a Topic
+//!
+//! CLI tree to create a copy of a topic. The copy is made by the SPUs, which hard link the
+//! sealed segments of each partition at its log end offset, so no records are produced.
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::topic::TopicSpec;
+
+#[derive(Debug, Parser)]
+pub struct CloneTopicOpt {
+    /// Name of the Topic to copy
+    #[arg(value_name = "source")]
+    source: String,
+
+    /// Name of the new Topic
+    #[arg(value_name = "name")]
+    name: String,
+
+    /// Validates configuration, does not provision
+    #[arg(short = 'd', long)]
+    dry_run: bool,
+}
+
+impl CloneTopicOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        // the SC replaces the rest of the spec with the spec of the source
+        let mut spec = TopicSpec::default();
+        spec.set_clone_from(Some(self.source.clone()));
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), self.dry_run, spec).await?;
+        println!("topic \"{}\" cloned from \"{}\"", self.name, self.source);
+        Ok(())
+    }
+}
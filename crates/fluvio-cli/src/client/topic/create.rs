@@ -14,10 +14,12 @@ use anyhow::Result;
 use fluvio_types::PartitionCount;
 use fluvio_types::ReplicationFactor;
 use fluvio::metadata::topic::CleanupPolicy;
+use fluvio::metadata::topic::CompactPolicy;
 use fluvio::metadata::topic::ReplicaSpec;
 use fluvio::metadata::topic::SegmentBasedPolicy;
 use fluvio::metadata::topic::TopicStorageConfig;
 use fluvio::metadata::topic::CompressionAlgorithm;
+use fluvio::metadata::topic::CompressionDictionary;
 
 use fluvio_controlplane_metadata::topic::config::TopicConfig;
 use fluvio_sc_schema::shared::validate_resource_name;
@@ -219,7 +221,13 @@ impl CreateTopicOpt {
         };
 
         let mut topic_spec: TopicSpec = replica_spec.into();
-        if let Some(retention) = self.setting.retention_time {
+        if self.setting.compact {
+            let mut policy = CompactPolicy::default();
+            if let Some(delete_horizon) = self.setting.delete_horizon {
+                policy.delete_retention_secs = delete_horizon.as_secs() as u32;
+            }
+            topic_spec.set_cleanup_policy(CleanupPolicy::Compact(policy));
+        } else if let Some(retention) = self.setting.retention_time {
             topic_spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
                 time_in_seconds: retention.as_secs() as u32,
             }));
@@ -229,10 +237,34 @@ impl CreateTopicOpt {
             topic_spec.set_compression_type(compression_type);
         }
 
+        if let Some(path) = &self.setting.compression_dictionary {
+            let dictionary = std::fs::read(path).map_err(|err| {
+                CliError::InvalidArg(format!(
+                    "unable to read compression dictionary {}: {err}",
+                    path.display()
+                ))
+            })?;
+            topic_spec.set_compression_dictionary(Some(CompressionDictionary::new(dictionary)));
+        }
+
         topic_spec.set_system(self.setting.system);
 
-        if self.setting.segment_size.is_some() || self.setting.max_partition_size.is_some() {
-            let mut storage = TopicStorageConfig::default();
+        if self.setting.segment_size.is_some()
+            || self.setting.max_partition_size.is_some()
+            || self.setting.ephemeral
+            || self.setting.tier.is_some()
+            || self.setting.transcode
+        {
+            let mut storage = TopicStorageConfig {
+                ephemeral: self.setting.ephemeral,
+                transcode: self.setting.transcode,
+                tier: self.setting.tier.clone(),
+                tier_hot_window_secs: self
+                    .setting
+                    .tier_hot_window
+                    .map(|window| window.as_secs() as u32),
+                ..Default::default()
+            };
 
             if let Some(segment_size) = self.setting.segment_size {
                 storage.segment_size = Some(segment_size.as_u64() as u32);
@@ -266,6 +298,16 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "time",value_parser=parse_duration)]
     retention_time: Option<Duration>,
 
+    /// Keep only the latest record of each key instead of expiring segments.
+    /// Records with a key and an empty value are tombstones, deleting the key
+    #[arg(long, conflicts_with = "retention_time")]
+    compact: bool,
+
+    /// How long tombstones of a compacted topic are kept
+    /// Ex: '1h', '1d' (default)
+    #[arg(long, value_name = "time", value_parser = parse_duration, requires = "compact")]
+    delete_horizon: Option<Duration>,
+
     /// Segment size (by default measured in bytes)
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
@@ -275,11 +317,37 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "compression")]
     compression_type: Option<CompressionAlgorithm>,
 
+    /// Recompress records produced with another compression than the compression type,
+    /// instead of rejecting them, so that they are all stored with it
+    #[arg(long, requires = "compression_type")]
+    transcode: bool,
+
+    /// Path to a trained zstd dictionary attached to the topic, producers compressing
+    /// with zstd use it. Ex: a dictionary trained with `zstd --train`
+    #[arg(long, value_name = "file")]
+    compression_dictionary: Option<PathBuf>,
+
     /// Max partition size (by default measured in bytes)
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
     max_partition_size: Option<bytesize::ByteSize>,
 
+    /// Store partitions on the tmpfs of the SPUs instead of their disk, for tests and
+    /// throwaway data. Records are removed when an SPU restarts, even with replication
+    #[arg(long)]
+    ephemeral: bool,
+
+    /// Offload sealed segments to object storage, reads of older records fetch them back.
+    /// The SPUs sign requests with the static AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY
+    /// of their environment. Ex: 's3://bucket/prefix', 'gs://bucket/prefix'
+    #[arg(long, value_name = "location", conflicts_with_all = ["ephemeral", "compact"])]
+    tier: Option<String>,
+
+    /// How long offloaded segments stay on the SPU disks
+    /// Ex: '1h', '2d'
+    #[arg(long, value_name = "time", value_parser = parse_duration, requires = "tier")]
+    tier_hot_window: Option<Duration>,
+
     /// Flag to create a system topic
     /// System topics are for internal operations
     #[arg(long, short = 's', hide = true)]
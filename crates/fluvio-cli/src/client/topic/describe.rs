@@ -46,13 +46,14 @@ impl DescribeTopicsOpt {
 
 mod display {
 
-    use fluvio::metadata::topic::ReplicaSpec;
+    use fluvio::metadata::topic::{CleanupPolicy, DedupBy, ReplicaSpec};
     use comfy_table::Row;
     use humantime::format_duration;
     use serde::Serialize;
 
     use fluvio::metadata::objects::Metadata;
     use fluvio::metadata::topic::TopicSpec;
+    use fluvio_types::defaults::SPU_EPHEMERAL_PARTITION_MAX_BYTES;
 
     use crate::common::output::{
         OutputType, OutputError, DescribeObjectHandler, KeyValOutputHandler, TableOutputHandler,
@@ -147,6 +148,48 @@ mod display {
                 ReplicaSpec::Mirror(_config) => {}
             }
 
+            match spec.get_clean_policy() {
+                Some(CleanupPolicy::Segment(policy)) => {
+                    key_values.push((
+                        "Retention".to_owned(),
+                        Some(
+                            format_duration(std::time::Duration::from_secs(
+                                policy.time_in_seconds as u64,
+                            ))
+                            .to_string(),
+                        ),
+                    ));
+                }
+                Some(CleanupPolicy::Compact(policy)) => {
+                    key_values.push((
+                        "Retention".to_owned(),
+                        Some(format!(
+                            "compact, tombstones kept for {}",
+                            format_duration(std::time::Duration::from_secs(
+                                policy.delete_retention_secs as u64
+                            ))
+                        )),
+                    ));
+                }
+                None => {}
+            }
+            // partitions over the size have their oldest segments removed
+            let storage = spec.get_storage();
+            key_values.push((
+                "Max Partition Size".to_owned(),
+                Some(
+                    storage
+                        .and_then(|storage| storage.max_partition_size)
+                        .or_else(|| {
+                            storage
+                                .filter(|storage| storage.is_ephemeral())
+                                .map(|_| SPU_EPHEMERAL_PARTITION_MAX_BYTES)
+                        })
+                        .map(|size| bytesize::ByteSize(size).to_string())
+                        .unwrap_or_else(|| "cluster default".to_owned()),
+                ),
+            ));
+
             if let Some(dedup) = spec.get_deduplication() {
                 key_values.push((
                     "Deduplication Filter".to_owned(),
@@ -165,6 +208,89 @@ mod display {
                 ));
             };
 
+            if let Some(window) = spec.get_dedup_window() {
+                key_values.push((
+                    "Dedup Window By".to_owned(),
+                    Some(
+                        match window.by {
+                            DedupBy::Key => "key",
+                            DedupBy::Hash => "hash",
+                        }
+                        .to_owned(),
+                    ),
+                ));
+                key_values.push((
+                    "Dedup Window Count Bound".to_owned(),
+                    Some(window.bounds.count)
+                        .filter(|c| *c != 0)
+                        .as_ref()
+                        .map(ToString::to_string),
+                ));
+                key_values.push((
+                    "Dedup Window Age Bound".to_owned(),
+                    window.bounds.age.map(|a| format_duration(a).to_string()),
+                ));
+            };
+
+            if let Some(validation) = spec.get_validation() {
+                key_values.push((
+                    "Validation SmartModule".to_owned(),
+                    Some(validation.transform.uses.clone()),
+                ));
+                key_values.push((
+                    "Invalid Records".to_owned(),
+                    Some(match validation.on_invalid.dead_letter_topic() {
+                        Some(topic) => format!("dead letter topic {topic}"),
+                        None => "reject".to_owned(),
+                    }),
+                ));
+            };
+
+            if let Some(source) = spec.get_clone_from() {
+                key_values.push(("Cloned From".to_owned(), Some(source.to_owned())));
+            }
+
+            if spec
+                .get_storage()
+                .is_some_and(|storage| storage.is_ephemeral())
+            {
+                key_values.push((
+                    "Storage".to_owned(),
+                    Some("ephemeral, records are lost when SPUs restart".to_owned()),
+                ));
+            }
+
+            if spec.is_compression_transcoded() {
+                key_values.push((
+                    "Compression".to_owned(),
+                    Some(format!(
+                        "{}, other compressions are transcoded",
+                        spec.get_compression_type()
+                    )),
+                ));
+            }
+
+            if let Some(dictionary) = spec.get_compression_dictionary() {
+                key_values.push((
+                    "Compression dictionary".to_owned(),
+                    Some(format!("id {}", dictionary.id().unwrap_or_default())),
+                ));
+            }
+
+            if let Some(storage) = spec.get_storage().filter(|storage| storage.is_tiered()) {
+                let tier = storage.tier.clone().unwrap_or_default();
+                key_values.push((
+                    "Storage".to_owned(),
+                    Some(match storage.tier_hot_window_secs {
+                        Some(secs) => format!(
+                            "tiered to {tier}, local for {}",
+                            format_duration(std::time::Duration::from_secs(secs as u64))
+                        ),
+                        None => format!("tiered to {tier}"),
+                    }),
+                ));
+            }
+
             key_values.push((
                 "Status".to_owned(),
                 Some(status.resolution.resolution_label().to_string()),
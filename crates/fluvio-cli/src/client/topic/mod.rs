@@ -4,6 +4,8 @@ mod describe;
 mod list;
 mod add_partition;
 mod add_mirror;
+mod peek;
+mod clone;
 
 pub use cmd::TopicCmd;
 
@@ -25,10 +27,12 @@ mod cmd {
 
     use super::add_mirror::AddMirrorOpt;
     use super::add_partition::AddPartitionOpt;
+    use super::clone::CloneTopicOpt;
     use super::create::CreateTopicOpt;
     use super::delete::DeleteTopicOpt;
     use super::describe::DescribeTopicsOpt;
     use super::list::ListTopicsOpt;
+    use super::peek::PeekTopicOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "topic", about = "Topic operations")]
@@ -74,6 +78,20 @@ mod cmd {
             help_template = COMMAND_TEMPLATE,
         )]
         AddMirror(AddMirrorOpt),
+
+        /// Print a few records of a Topic without consuming it
+        #[command(
+            name = "peek",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Peek(PeekTopicOpt),
+
+        /// Copy a Topic with its records, the copy is made by the SPUs
+        #[command(
+            name = "clone",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Clone(CloneTopicOpt),
     }
 
     #[async_trait]
@@ -102,6 +120,12 @@ mod cmd {
                 Self::AddMirror(add_mirror) => {
                     add_mirror.process(fluvio).await?;
                 }
+                Self::Peek(peek) => {
+                    peek.process(out, fluvio).await?;
+                }
+                Self::Clone(clone) => {
+                    clone.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -110,12 +134,12 @@ mod cmd {
 
     impl TopicCmd {
         pub fn metadata() -> FluvioExtensionMetadata {
-            FluvioExtensionMetadata {
-                title: "topic".into(),
-                package: Some("fluvio/fluvio".parse().unwrap()),
-                description: "Topic Operations".into(),
-                version: semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
-            }
+            FluvioExtensionMetadata::new(
+                "topic",
+                "Topic Operations",
+                semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
+            )
+            .with_package("fluvio/fluvio".parse().unwrap())
         }
     }
 }
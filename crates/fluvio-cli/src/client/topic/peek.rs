@@ -0,0 +1,200 @@
+//!
+//! # Peek Topic CLI
+//!
+//! CLI to preview a few records of a Topic without starting a consumer
+//!
+
+use std::sync::Arc;
+
+use clap::{Parser, ValueEnum};
+use tracing::debug;
+use anyhow::{anyhow, Result};
+
+use fluvio::{Fluvio, SampleStrategy};
+use fluvio::metadata::topic::TopicSpec;
+use fluvio_types::PartitionId;
+
+use crate::common::output::Terminal;
+use crate::common::OutputFormat;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct PeekTopicOpt {
+    /// The name of the Topic to peek
+    #[arg(value_name = "name")]
+    topic: String,
+
+    /// Partition to sample, every partition of the topic if not set
+    #[arg(short = 'p', long, value_name = "integer")]
+    partition: Option<PartitionId>,
+
+    /// Number of records to sample from each partition
+    #[arg(short = 'n', long, value_name = "integer", default_value_t = 10)]
+    count: u32,
+
+    /// Which records to sample
+    #[arg(long, value_enum, default_value_t = PeekStrategy::Tail)]
+    strategy: PeekStrategy,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Eq, PartialEq)]
+enum PeekStrategy {
+    /// First records of the partition
+    Head,
+    /// Last records of the partition
+    Tail,
+    /// Records at random offsets
+    Random,
+}
+
+impl From<PeekStrategy> for SampleStrategy {
+    fn from(strategy: PeekStrategy) -> Self {
+        match strategy {
+            PeekStrategy::Head => Self::Head,
+            PeekStrategy::Tail => Self::Tail,
+            PeekStrategy::Random => Self::Random,
+        }
+    }
+}
+
+impl PeekTopicOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        debug!(topic = %self.topic, partition = ?self.partition, "peek topic");
+
+        let partitions: Vec<PartitionId> = match self.partition {
+            Some(partition) => vec![partition],
+            None => {
+                let admin = fluvio.admin().await;
+                let topic = admin
+                    .list::<TopicSpec, _>(vec![self.topic.clone()])
+                    .await?
+                    .into_iter()
+                    .find(|topic| topic.name == self.topic)
+                    .ok_or_else(|| anyhow!("topic \"{}\" not found", self.topic))?;
+                topic.status.replica_map.keys().copied().collect()
+            }
+        };
+
+        let mut records = Vec::new();
+        for partition in partitions {
+            let sampled = fluvio
+                .sample_records(
+                    self.topic.clone(),
+                    partition,
+                    self.count,
+                    self.strategy.into(),
+                )
+                .await?;
+            records.extend(
+                sampled
+                    .into_iter()
+                    .map(|record| display::PeekedRecord::new(partition, record)),
+            );
+        }
+
+        display::format_response_output(out, records, self.output.format)?;
+        Ok(())
+    }
+}
+
+mod display {
+
+    use std::time::{Duration, SystemTime};
+
+    use comfy_table::Row;
+    use serde::Serialize;
+
+    use fluvio::SampledRecord;
+    use fluvio_types::PartitionId;
+
+    use crate::common::output::{OutputType, TableOutputHandler, Terminal, OutputError};
+    use crate::common::t_println;
+
+    #[derive(Serialize)]
+    pub struct PeekedRecord {
+        partition: PartitionId,
+        offset: i64,
+        timestamp: i64,
+        key: Option<String>,
+        value: String,
+    }
+
+    impl PeekedRecord {
+        pub fn new(partition: PartitionId, record: SampledRecord) -> Self {
+            Self {
+                partition,
+                offset: record.offset,
+                timestamp: record.timestamp,
+                key: record
+                    .key
+                    .map(|key| key.as_utf8_lossy_string().into_owned()),
+                value: record.value.as_utf8_lossy_string().into_owned(),
+            }
+        }
+
+        fn timestamp_display(&self) -> String {
+            if self.timestamp < 0 {
+                return String::new();
+            }
+            let time = SystemTime::UNIX_EPOCH + Duration::from_millis(self.timestamp as u64);
+            humantime::format_rfc3339_millis(time).to_string()
+        }
+    }
+
+    #[derive(Serialize)]
+    struct PeekedRecords(Vec<PeekedRecord>);
+
+    /// Process server based on output type
+    pub fn format_response_output<O>(
+        out: std::sync::Arc<O>,
+        records: Vec<PeekedRecord>,
+        output_type: OutputType,
+    ) -> Result<(), OutputError>
+    where
+        O: Terminal,
+    {
+        if !records.is_empty() {
+            out.render_list(&PeekedRecords(records), output_type)
+        } else {
+            t_println!(out, "No records found");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for PeekedRecords {
+        /// table header implementation
+        fn header(&self) -> Row {
+            Row::from(["PARTITION", "OFFSET", "TIMESTAMP", "KEY", "VALUE"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|record| {
+                    Row::from([
+                        record.partition.to_string(),
+                        record.offset.to_string(),
+                        record.timestamp_display(),
+                        record.key.clone().unwrap_or_default(),
+                        record.value.clone(),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod opts;
+pub(crate) mod signature;
+mod state;
 pub mod update;
@@ -0,0 +1,131 @@
+//!
+//! # Package signatures
+//!
+//! Releases are signed with ed25519, the detached signature is published next to
+//! the package file as `<file>.sig`. The release public key is embedded in the
+//! CLI at build time from `FLUVIO_RELEASE_PUBLIC_KEY` and packages are installed
+//! only if their signature matches it. Setting `FLUVIO_PACKAGE_PUBLIC_KEY` at
+//! runtime overrides the embedded key, e.g. for self hosted package registries.
+//! Both accept a hex encoded key or an OpenSSH public key.
+//!
+//! Builds without an embedded key and without an override only verify the
+//! package checksum.
+//!
+use anyhow::{anyhow, Result};
+use tracing::debug;
+
+use fluvio_cli_common::install::{fetch_package_signature, install_println};
+use fluvio_hub_util::keymgmt::{PublicKey, Signature};
+use fluvio_index::{HttpAgent, PackageId, Target, WithVersion};
+
+pub const FLUVIO_PACKAGE_PUBLIC_KEY: &str = "FLUVIO_PACKAGE_PUBLIC_KEY";
+
+/// public key releases are signed with, embedded by the release build
+pub const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("FLUVIO_RELEASE_PUBLIC_KEY");
+
+/// key packages are verified against, the override takes precedence over the release key
+pub fn trusted_key() -> Result<Option<PublicKey>> {
+    select_key(
+        std::env::var(FLUVIO_PACKAGE_PUBLIC_KEY).ok(),
+        RELEASE_PUBLIC_KEY,
+    )
+}
+
+fn select_key(
+    key_override: Option<String>,
+    release_key: Option<&str>,
+) -> Result<Option<PublicKey>> {
+    if let Some(key) = key_override {
+        return parse_key(&key)
+            .map(Some)
+            .map_err(|err| anyhow!("invalid {FLUVIO_PACKAGE_PUBLIC_KEY}: {err}"));
+    }
+    match release_key.map(str::trim).filter(|key| !key.is_empty()) {
+        Some(key) => parse_key(key)
+            .map(Some)
+            .map_err(|err| anyhow!("invalid embedded release public key: {err}")),
+        None => Ok(None),
+    }
+}
+
+fn parse_key(key: &str) -> Result<PublicKey> {
+    let key = key.trim();
+    let public_key = if key.starts_with("ssh-") {
+        PublicKey::from_ssh(key)
+    } else {
+        PublicKey::from_hex(key)
+    };
+    public_key.map_err(|err| anyhow!("{err}"))
+}
+
+/// Verify the signature of a downloaded package before it is installed
+pub async fn verify_package(
+    agent: &HttpAgent,
+    id: &PackageId<WithVersion>,
+    target: &Target,
+    package_file: &[u8],
+) -> Result<()> {
+    let Some(key) = trusted_key()? else {
+        install_println(format!(
+            "❕ no release public key is embedded and {FLUVIO_PACKAGE_PUBLIC_KEY} is not set, only the checksum of {} is verified",
+            id.name()
+        ));
+        return Ok(());
+    };
+    let signature = fetch_package_signature(agent, id, target).await?;
+    verify_signature(&key, package_file, &signature)
+        .map_err(|err| anyhow!("refusing to install {id}: {err}"))?;
+    debug!(%id, "verified package signature");
+    Ok(())
+}
+
+fn verify_signature(key: &PublicKey, package_file: &[u8], signature: &[u8]) -> Result<()> {
+    let signature: [u8; 64] = signature
+        .try_into()
+        .map_err(|_| anyhow!("signature is {} bytes, expected 64", signature.len()))?;
+    key.verify(package_file, &Signature::from_bytes(&signature))
+        .map_err(|_| anyhow!("signature does not match the package"))
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_hub_util::keymgmt::Keypair;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        let keypair = Keypair::new().expect("keypair");
+        let signature = keypair.sign(b"fluvio").expect("sign").to_bytes();
+
+        assert!(verify_signature(&keypair.public(), b"fluvio", &signature).is_ok());
+        assert!(verify_signature(&keypair.public(), b"fluvi0", &signature).is_err());
+        assert!(verify_signature(&keypair.public(), b"fluvio", &signature[1..]).is_err());
+
+        let other = Keypair::new().expect("keypair");
+        assert!(verify_signature(&other.public(), b"fluvio", &signature).is_err());
+    }
+
+    #[test]
+    fn test_select_key() {
+        let release = Keypair::new().expect("keypair").public();
+        let release_hex = release.to_hex();
+        let custom = Keypair::new().expect("keypair").public();
+
+        assert!(select_key(None, None).expect("no key").is_none());
+        assert!(select_key(None, Some("")).expect("no key").is_none());
+
+        let key = select_key(None, Some(&release_hex))
+            .expect("release key")
+            .expect("key");
+        assert_eq!(key.to_hex(), release_hex);
+
+        let key = select_key(Some(custom.to_hex()), Some(&release_hex))
+            .expect("override")
+            .expect("key");
+        assert_eq!(key.to_hex(), custom.to_hex());
+
+        assert!(select_key(Some("not a key".to_owned()), Some(&release_hex)).is_err());
+        assert!(select_key(None, Some("not a key")).is_err());
+    }
+}
@@ -0,0 +1,296 @@
+//!
+//! # Update state
+//!
+//! Pinned component versions, the versions installed by updates and the backups
+//! taken by the last `fluvio update`, stored in `~/.fluvio/update/state.json`.
+//!
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use fluvio_cli_common::install::{fluvio_base_dir, install_bin};
+
+const UPDATE_DIR: &str = "update";
+const STATE_FILE: &str = "state.json";
+const ROLLBACK_DIR: &str = "rollback";
+/// copies taken before an install, moved to the rollback dir once it succeeded
+const PENDING_DIR: &str = "pending";
+
+/// Copy of a binary replaced by an update
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Backup {
+    /// location the binary was installed at
+    pub path: PathBuf,
+    /// location of the saved copy
+    pub backup: PathBuf,
+    /// sha256 of the saved copy
+    pub sha256: String,
+    /// version that was replaced, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<Version>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StateFile {
+    #[serde(default)]
+    pins: BTreeMap<String, Version>,
+    #[serde(default)]
+    backups: BTreeMap<String, Backup>,
+    /// versions installed by updates, for components which can't report theirs
+    #[serde(default)]
+    installed: BTreeMap<String, Version>,
+}
+
+#[derive(Debug)]
+pub struct UpdateState {
+    dir: PathBuf,
+    state: StateFile,
+    /// set once this update replaced the backups of the previous one
+    replacing: bool,
+}
+
+impl UpdateState {
+    /// load state from default location, `~/.fluvio/update`
+    pub fn load() -> Result<Self> {
+        Self::load_from(fluvio_base_dir()?.join(UPDATE_DIR))
+    }
+
+    pub fn load_from(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        let file = dir.join(STATE_FILE);
+        let state = if file.exists() {
+            let content = std::fs::read_to_string(&file)?;
+            serde_json::from_str(&content)
+                .map_err(|err| anyhow!("invalid update state {}: {err}", file.display()))?
+        } else {
+            StateFile::default()
+        };
+        Ok(Self {
+            dir,
+            state,
+            replacing: false,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(
+            self.dir.join(STATE_FILE),
+            serde_json::to_string_pretty(&self.state)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn pin(&mut self, component: &str, version: Version) {
+        self.state.pins.insert(component.to_owned(), version);
+    }
+
+    pub fn unpin(&mut self, component: &str) -> Option<Version> {
+        self.state.pins.remove(component)
+    }
+
+    pub fn pinned(&self, component: &str) -> Option<&Version> {
+        self.state.pins.get(component)
+    }
+
+    pub fn pins(&self) -> impl Iterator<Item = (&String, &Version)> {
+        self.state.pins.iter()
+    }
+
+    pub fn installed(&self, component: &str) -> Option<&Version> {
+        self.state.installed.get(component)
+    }
+
+    pub fn set_installed(&mut self, component: &str, version: Version) {
+        self.state.installed.insert(component.to_owned(), version);
+    }
+
+    pub fn has_backups(&self) -> bool {
+        !self.state.backups.is_empty()
+    }
+
+    /// Forget backups of a previous update
+    pub fn clear_backups(&mut self) -> Result<()> {
+        self.state.backups.clear();
+        let rollback_dir = self.dir.join(ROLLBACK_DIR);
+        if rollback_dir.exists() {
+            std::fs::remove_dir_all(rollback_dir)?;
+        }
+        Ok(())
+    }
+
+    /// Save a copy of the binary at `path` before it is replaced
+    ///
+    /// The copy is kept for rollback by [`Self::commit_backup`] once the new binary is
+    /// installed, until then the backups of the previous update are left as they are.
+    pub fn stage_backup(
+        &mut self,
+        component: &str,
+        path: &Path,
+        version: Option<Version>,
+    ) -> Result<Option<Backup>> {
+        // the binary replaced first by this update is the one to restore
+        if !path.exists() || (self.replacing && self.state.backups.contains_key(component)) {
+            return Ok(None);
+        }
+        let content = std::fs::read(path)?;
+        let backup = self.dir.join(PENDING_DIR).join(component);
+        install_bin(&backup, &content)?;
+        Ok(Some(Backup {
+            path: path.to_owned(),
+            backup,
+            sha256: sha256(&content),
+            version,
+        }))
+    }
+
+    /// Keep a staged backup for rollback, its component has been installed
+    ///
+    /// The first backup kept by an update discards those of the previous update,
+    /// so rollback always returns to the state before the last update.
+    pub fn commit_backup(&mut self, component: &str, mut backup: Backup) -> Result<()> {
+        if !self.replacing {
+            self.clear_backups()?;
+            self.replacing = true;
+        }
+        let rollback_dir = self.dir.join(ROLLBACK_DIR);
+        std::fs::create_dir_all(&rollback_dir)?;
+        let kept = rollback_dir.join(component);
+        std::fs::rename(&backup.backup, &kept)?;
+        backup.backup = kept;
+        self.state.backups.insert(component.to_owned(), backup);
+        self.save()
+    }
+
+    /// Remove a staged backup, its component failed to install
+    pub fn discard_backup(&self, backup: Backup) {
+        if let Err(err) = std::fs::remove_file(&backup.backup) {
+            debug!(%err, backup = %backup.backup.display(), "unable to remove staged backup");
+        }
+    }
+
+    /// Restore all binaries saved by the last update
+    ///
+    /// Every backup is verified before any binary is replaced.
+    pub fn rollback(&mut self) -> Result<Vec<(String, Backup)>> {
+        let mut restore = Vec::with_capacity(self.state.backups.len());
+        for (component, backup) in &self.state.backups {
+            let content = std::fs::read(&backup.backup)
+                .map_err(|err| anyhow!("unable to read backup of {component}: {err}"))?;
+            if sha256(&content) != backup.sha256 {
+                return Err(anyhow!(
+                    "backup of {component} at {} is corrupted",
+                    backup.backup.display()
+                ));
+            }
+            restore.push((component.to_owned(), backup.clone(), content));
+        }
+
+        for (_, backup, content) in &restore {
+            install_bin(&backup.path, content)?;
+        }
+        self.clear_backups()?;
+        self.save()?;
+        Ok(restore
+            .into_iter()
+            .map(|(component, backup, _)| (component, backup))
+            .collect())
+    }
+}
+
+fn sha256(content: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content);
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_and_rollback() {
+        let dir = std::env::temp_dir().join(format!("fluvio-update-{}", std::process::id()));
+        let bin = dir.join("bin").join("fluvio");
+        install_bin(&bin, b"v1").expect("install");
+
+        let mut state = UpdateState::load_from(dir.join(UPDATE_DIR)).expect("load");
+        state.pin("fluvio-cloud", Version::new(0, 2, 0));
+        let backup = state
+            .stage_backup("fluvio", &bin, Some(Version::new(0, 11, 0)))
+            .expect("backup")
+            .expect("staged");
+        install_bin(&bin, b"v2").expect("install");
+        state.commit_backup("fluvio", backup).expect("commit");
+
+        let mut state = UpdateState::load_from(dir.join(UPDATE_DIR)).expect("load");
+        assert_eq!(state.pinned("fluvio-cloud"), Some(&Version::new(0, 2, 0)));
+        assert!(state.has_backups());
+        let restored = state.rollback().expect("rollback");
+        assert_eq!(restored.len(), 1);
+        assert_eq!(std::fs::read(&bin).unwrap(), b"v1");
+        assert!(!state.has_backups());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rollback_rejects_corrupted_backup() {
+        let dir = std::env::temp_dir().join(format!("fluvio-update-bad-{}", std::process::id()));
+        let bin = dir.join("bin").join("fluvio");
+        install_bin(&bin, b"v1").expect("install");
+
+        let mut state = UpdateState::load_from(dir.join(UPDATE_DIR)).expect("load");
+        let backup = state
+            .stage_backup("fluvio", &bin, None)
+            .expect("backup")
+            .expect("staged");
+        state.commit_backup("fluvio", backup).expect("commit");
+        install_bin(
+            dir.join(UPDATE_DIR).join(ROLLBACK_DIR).join("fluvio"),
+            b"xx",
+        )
+        .unwrap();
+        install_bin(&bin, b"v2").expect("install");
+
+        assert!(state.rollback().is_err());
+        assert_eq!(std::fs::read(&bin).unwrap(), b"v2");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_failed_install_keeps_previous_backups() {
+        let dir = std::env::temp_dir().join(format!("fluvio-update-keep-{}", std::process::id()));
+        let bin = dir.join("bin").join("fluvio");
+        install_bin(&bin, b"v1").expect("install");
+
+        let mut state = UpdateState::load_from(dir.join(UPDATE_DIR)).expect("load");
+        let backup = state
+            .stage_backup("fluvio", &bin, None)
+            .expect("backup")
+            .expect("staged");
+        install_bin(&bin, b"v2").expect("install");
+        state.commit_backup("fluvio", backup).expect("commit");
+
+        // next update stages a copy of v2, but its install fails
+        let mut state = UpdateState::load_from(dir.join(UPDATE_DIR)).expect("load");
+        let backup = state
+            .stage_backup("fluvio", &bin, None)
+            .expect("backup")
+            .expect("staged");
+        state.discard_backup(backup);
+
+        let mut state = UpdateState::load_from(dir.join(UPDATE_DIR)).expect("load");
+        assert!(state.has_backups());
+        state.rollback().expect("rollback");
+        assert_eq!(std::fs::read(&bin).unwrap(), b"v1");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -4,30 +4,56 @@ use std::str::FromStr;
 use clap::Parser;
 use tracing::{debug, instrument};
 use semver::Version;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
 use fluvio_channel::{LATEST_CHANNEL_NAME, FLUVIO_RELEASE_CHANNEL};
 use fluvio_cli_common::{FLUVIO_ALWAYS_CHECK_UPDATES, error::PackageNotFound};
-use fluvio_index::{PackageId, HttpAgent};
+use fluvio_hub_util::fvm::Channel;
+use fluvio_index::{PackageId, PackageVersion, HttpAgent, Target};
 use fluvio_cli_common::install::{
     fetch_latest_version, fetch_package_file, install_bin, install_println, fluvio_extensions_dir,
+    resolve_package_version,
 };
 
 use crate::metadata::subcommand_metadata;
 
+use super::signature::verify_package;
+use super::state::UpdateState;
+
 const FLUVIO_CLI_PACKAGE_ID: &str = "fluvio/fluvio";
 const FLUVIO_CHANNEL_PACKAGE_ID: &str = "fluvio/fluvio-channel";
+const FLUVIO_CHANNEL: &str = "fluvio-channel";
 
 #[derive(Parser, Debug)]
 pub struct UpdateOpt {
     /// Update to the latest prerelease rather than the latest release
-    #[arg(long)]
+    #[arg(long, conflicts_with = "channel")]
     pub develop: bool,
 
+    /// Release channel to update to: "stable", "latest" or a specific version, e.g. "0.11.0"
+    ///
+    /// Defaults to the latest release. Pinned components are not affected.
+    #[arg(long)]
+    pub channel: Option<Channel>,
+
     /// Print output for update process but do not install updates
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Pin component to a version, e.g. `fluvio=0.11.0` or `fluvio-cloud=0.2.15`
+    ///
+    /// Pinned components are always updated to their pinned version.
+    #[arg(long, value_name = "COMPONENT=VERSION", value_parser = parse_pin)]
+    pub pin: Vec<(String, Version)>,
+
+    /// Remove the pin of a component, so it follows the channel again
+    #[arg(long, value_name = "COMPONENT")]
+    pub unpin: Vec<String>,
+
+    /// Restore the binaries replaced by the last update
+    #[arg(long, conflicts_with_all = ["channel", "develop", "pin", "unpin", "plugins"])]
+    pub rollback: bool,
+
     // The fluvio-channel binary changes less frequently
     // pub skip_fluvio_channel: bool,
     // pub develop_fluvio_channel: bool,
@@ -39,8 +65,36 @@ pub struct UpdateOpt {
     pub target: Option<String>,
 }
 
+fn parse_pin(s: &str) -> Result<(String, Version)> {
+    let (component, version) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("invalid pin `{s}`, expected COMPONENT=VERSION"))?;
+    Ok((component.to_owned(), Version::parse(version)?))
+}
+
 impl UpdateOpt {
     pub async fn process(self) -> Result<()> {
+        let mut state = UpdateState::load()?;
+        if self.rollback {
+            return self.rollback(&mut state);
+        }
+
+        for component in &self.unpin {
+            if let Some(version) = state.unpin(component) {
+                println!("📌 Unpinned {component} (was {version})");
+            }
+        }
+        for (component, version) in &self.pin {
+            state.pin(component, version.clone());
+            println!("📌 Pinned {component} to {version}");
+        }
+        if !self.dry_run {
+            state.save()?;
+        }
+        for (component, version) in state.pins() {
+            debug!(%component, %version, "pinned component");
+        }
+
         let agent = HttpAgent::default();
         let plugin_meta = subcommand_metadata()?;
 
@@ -68,8 +122,8 @@ impl UpdateOpt {
             }
         }
 
-        self.update_fluvio_cli(&agent).await?;
-        self.update_fluvio_channel(&agent).await?;
+        self.update_fluvio_cli(&agent, &mut state).await?;
+        self.update_fluvio_channel(&agent, &mut state).await?;
 
         if updates.is_empty() {
             println!("👍 No plugins to update, all done!");
@@ -87,14 +141,87 @@ impl UpdateOpt {
         }
 
         for (id, path) in &updates {
-            self.update_plugin(&agent, id, path).await?;
+            self.update_plugin(&agent, &mut state, id, path).await?;
+        }
+
+        Ok(())
+    }
+
+    fn rollback(&self, state: &mut UpdateState) -> Result<()> {
+        if !state.has_backups() {
+            println!("❕ No previous update to roll back");
+            return Ok(());
+        }
+        if self.dry_run {
+            println!("❎ (Dry run) Rollback skipped");
+            return Ok(());
         }
 
+        for (component, backup) in state.rollback()? {
+            match backup.version {
+                Some(version) => install_println(format!(
+                    "✅ Restored {component} {version} at {}",
+                    backup.path.display()
+                )),
+                None => install_println(format!(
+                    "✅ Restored {component} at {}",
+                    backup.path.display()
+                )),
+            }
+        }
         Ok(())
     }
 
-    #[instrument(skip(self, agent))]
-    async fn update_fluvio_cli(&self, agent: &HttpAgent) -> Result<()> {
+    /// Version to install for a package, pinned version takes precedence over the channel
+    async fn resolve_version(
+        &self,
+        agent: &HttpAgent,
+        state: &UpdateState,
+        id: &PackageId,
+        target: &Target,
+    ) -> Result<PackageVersion> {
+        if let Some(pinned) = state.pinned(id.name().as_str()) {
+            debug!(%pinned, "using pinned version");
+            return Ok(pinned.clone().into());
+        }
+        match &self.channel {
+            Some(channel) => Ok(channel.to_string().parse()?),
+            None => Ok(fetch_latest_version(agent, id, target, self.develop)
+                .await?
+                .into()),
+        }
+    }
+
+    /// Replace binary at `path`, keeping a copy of the previous one for rollback
+    ///
+    /// Returns false if the binary at `path` is already the one of the package.
+    fn install_component(
+        &self,
+        state: &mut UpdateState,
+        component: &str,
+        path: &Path,
+        previous_version: Option<Version>,
+        package_file: Vec<u8>,
+    ) -> Result<bool> {
+        if std::fs::read(path).is_ok_and(|installed| installed == package_file) {
+            debug!(component, "binary is unchanged");
+            return Ok(false);
+        }
+        let backup = state.stage_backup(component, path, previous_version)?;
+        if let Err(err) = install_bin(path, package_file) {
+            if let Some(backup) = backup {
+                state.discard_backup(backup);
+            }
+            return Err(err);
+        }
+        if let Some(backup) = backup {
+            state.commit_backup(component, backup)?;
+        }
+        Ok(true)
+    }
+
+    #[instrument(skip(self, agent, state))]
+    async fn update_fluvio_cli(&self, agent: &HttpAgent, state: &mut UpdateState) -> Result<()> {
         let target = if let Some(user_override) = &self.target {
             fluvio_index::Target::from_str(&user_override.to_string())?
         } else {
@@ -107,14 +234,19 @@ impl UpdateOpt {
         let id: PackageId = FLUVIO_CLI_PACKAGE_ID.parse()?;
         debug!(%target, %id, "Fluvio CLI updating self:");
 
-        // Find the latest version of this package
-        install_println("🎣 Fetching latest version for fluvio...");
-        let latest_version = fetch_latest_version(agent, &id, &target, self.develop).await?;
-        let id = id.into_versioned(latest_version.into());
+        // Find the version of this package to install
+        install_println("🎣 Fetching version for fluvio...");
+        let version = self.resolve_version(agent, state, &id, &target).await?;
+        let current_version = Version::parse(crate::VERSION.trim())?;
+        if version == PackageVersion::Semver(current_version.clone()) {
+            install_println(format!("✅ Fluvio CLI is already at {current_version}"));
+            return Ok(());
+        }
+        let id = id.into_versioned(version);
 
         // Download the package file from the package registry
         install_println(format!(
-            "⏳ Downloading Fluvio CLI with latest version: {}...",
+            "⏳ Downloading Fluvio CLI with version: {}...",
             &id.version()
         ));
         let package_result = fetch_package_file(agent, &id, &target).await;
@@ -132,18 +264,27 @@ impl UpdateOpt {
                 None => return Err(err),
             },
         };
+        verify_package(agent, &id, &target, &package_file).await?;
         install_println("🔑 Downloaded and verified package file");
 
         // Install the update over the current executable
         let fluvio_cli_path = std::env::current_exe()?;
 
         if !self.dry_run {
-            install_bin(&fluvio_cli_path, package_file)?;
-
-            install_println(format!(
-                "✅ Successfully updated {}",
-                &fluvio_cli_path.display(),
-            ));
+            if self.install_component(
+                state,
+                "fluvio",
+                &fluvio_cli_path,
+                Some(current_version.clone()),
+                package_file,
+            )? {
+                install_println(format!(
+                    "✅ Successfully updated {}",
+                    &fluvio_cli_path.display(),
+                ));
+            } else {
+                install_println(format!("✅ Fluvio CLI is already at {current_version}"));
+            }
         } else {
             install_println(format!(
                 "❎ (Dry run) Update installation skipped {}",
@@ -154,20 +295,35 @@ impl UpdateOpt {
         Ok(())
     }
 
-    #[instrument(skip(self, agent))]
-    async fn update_fluvio_channel(&self, agent: &HttpAgent) -> Result<()> {
+    #[instrument(skip(self, agent, state))]
+    async fn update_fluvio_channel(
+        &self,
+        agent: &HttpAgent,
+        state: &mut UpdateState,
+    ) -> Result<()> {
         let target = fluvio_index::package_target()?;
         let id: PackageId = FLUVIO_CHANNEL_PACKAGE_ID.parse()?;
         debug!(%target, %id, "Fluvio frontend (fluvio-channel) updating self:");
 
-        // Find the latest version of this package
-        install_println("🎣 Fetching latest version for fluvio-channel...");
-        let latest_version = fetch_latest_version(agent, &id, &target, self.develop).await?;
-        let id = id.into_versioned(latest_version.into());
+        // Find the version of this package to install
+        install_println("🎣 Fetching version for fluvio-channel...");
+        let version = self.resolve_version(agent, state, &id, &target).await?;
+        let id = id.into_versioned(version);
+        let version = resolve_package_version(agent, &id).await?;
+
+        // Install the update over the default fluvio frontend path
+        let fluvio_cli_path = std::env::current_exe()?;
+        let mut fluvio_channel_path = fluvio_cli_path;
+        fluvio_channel_path.set_file_name("fluvio");
+
+        if fluvio_channel_path.exists() && state.installed(FLUVIO_CHANNEL) == Some(&version) {
+            install_println(format!("✅ fluvio-channel is already at {version}"));
+            return Ok(());
+        }
 
         // Download the package file from the package registry
         install_println(format!(
-            "⏳ Downloading fluvio-channel with latest version: {}...",
+            "⏳ Downloading fluvio-channel with version: {}...",
             &id.version()
         ));
         let package_result = fetch_package_file(agent, &id, &target).await;
@@ -186,21 +342,28 @@ impl UpdateOpt {
                 None => return Err(err),
             },
         };
-
+        verify_package(agent, &id, &target, &package_file).await?;
         install_println("🔑 Downloaded and verified package file");
 
-        // Install the update over the default fluvio frontend path
-        //let fluvio_channel_path = std::env::current_dir()?.join("fluvio");
-        let fluvio_cli_path = std::env::current_exe()?;
-        let mut fluvio_channel_path = fluvio_cli_path;
-        fluvio_channel_path.set_file_name("fluvio");
-
         if !self.dry_run {
-            install_bin(&fluvio_channel_path, package_file)?;
-            install_println(format!(
-                "✅ Successfully updated {}",
-                &fluvio_channel_path.display(),
-            ));
+            let previous_version = state.installed(FLUVIO_CHANNEL).cloned();
+            let updated = self.install_component(
+                state,
+                FLUVIO_CHANNEL,
+                &fluvio_channel_path,
+                previous_version,
+                package_file,
+            )?;
+            state.set_installed(FLUVIO_CHANNEL, version.clone());
+            state.save()?;
+            if updated {
+                install_println(format!(
+                    "✅ Successfully updated {}",
+                    &fluvio_channel_path.display(),
+                ));
+            } else {
+                install_println(format!("✅ fluvio-channel is already at {version}"));
+            }
         } else {
             install_println(format!(
                 "❎ (Dry run) Update installation skipped {}",
@@ -210,25 +373,35 @@ impl UpdateOpt {
         Ok(())
     }
 
-    #[instrument(skip(self, agent))]
-    async fn update_plugin(&self, agent: &HttpAgent, id: &PackageId, path: &Path) -> Result<()> {
+    #[instrument(skip(self, agent, state))]
+    async fn update_plugin(
+        &self,
+        agent: &HttpAgent,
+        state: &mut UpdateState,
+        id: &PackageId,
+        path: &Path,
+    ) -> Result<()> {
         let target = fluvio_index::package_target()?;
         debug!(%target, %id, "Fluvio CLI updating plugin:");
 
-        let version = fetch_latest_version(agent, id, &target, self.develop).await?;
+        let version = self.resolve_version(agent, state, id, &target).await?;
 
         println!(
             "⏳ Downloading plugin {} with version {}",
             id.pretty(),
             version
         );
-        let id = id.clone().into_versioned(version.into());
+        let id = id.clone().into_versioned(version);
         let package_file = fetch_package_file(agent, &id, &target).await?;
+        verify_package(agent, &id, &target, &package_file).await?;
         println!("🔑 Downloaded and verified package file");
 
         if !self.dry_run {
-            install_bin(path, package_file)?;
-            println!("✅ Successfully updated {} at ({})", id, path.display());
+            if self.install_component(state, id.name().as_str(), path, None, package_file)? {
+                println!("✅ Successfully updated {} at ({})", id, path.display());
+            } else {
+                println!("✅ {} is already installed at ({})", id, path.display());
+            }
         } else {
             println!(
                 "❎ (Dry run) Update installation skipped {} at ({})",
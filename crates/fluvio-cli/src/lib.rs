@@ -6,6 +6,7 @@ mod error;
 pub mod client;
 pub mod install;
 mod profile;
+mod plugin;
 mod version;
 mod metadata;
 mod render;
@@ -74,7 +75,9 @@ mod root {
     use fluvio_channel::{FLUVIO_RELEASE_CHANNEL, LATEST_CHANNEL_NAME};
 
     use crate::profile::ProfileOpt;
+    use crate::plugin::{PluginCmd, PluginRegistry};
     use crate::install::opts::InstallOpt;
+    use crate::install::update::UpdateOpt;
     use crate::client::FluvioCmd;
     use crate::metadata::{MetadataOpt, subcommand_metadata};
     use crate::version::VersionOpt;
@@ -152,6 +155,20 @@ mod root {
         #[command(name = "install", hide = true)]
         Install(InstallOpt),
 
+        /// Update the Fluvio CLI, its frontend and installed plugins
+        ///
+        /// Updates follow a release channel unless a component is pinned.
+        /// Use `--rollback` to restore the binaries replaced by the last update.
+        #[command(name = "update")]
+        Update(UpdateOpt),
+
+        /// List, install, update and remove Fluvio CLI plugins
+        ///
+        /// Plugins installed with this command are kept in `~/.fluvio/plugins/`
+        /// and take precedence over other `fluvio-` executables.
+        #[command(subcommand, name = "plugin")]
+        Plugin(PluginCmd),
+
         /// Print Fluvio version information
         #[command(name = "version")]
         Version(VersionOpt),
@@ -206,6 +223,12 @@ mod root {
 
                     install.process().await?;
                 }
+                Self::Update(update) => {
+                    update.process().await?;
+                }
+                Self::Plugin(plugin) => {
+                    plugin.process().await?;
+                }
                 Self::Version(version) => {
                     version.process(root.target).await?;
                 }
@@ -299,11 +322,15 @@ mod root {
 
     /// Search for a Fluvio plugin in the following places:
     ///
+    /// - In the plugins installed with `fluvio plugin install`
     /// - In the directory where the `fluvio` executable is located
     /// - In the system PATH
     /// - In the `~/.fluvio/extensions/` directory
     fn find_plugin(name: &str) -> Option<PathBuf> {
         let ext_dir = fluvio_extensions_dir().ok();
+        if let Some(path) = PluginRegistry::load().ok().and_then(|it| it.find(name)) {
+            return Some(path);
+        }
         let self_exe = std::env::current_exe().ok();
         let self_dir = self_exe.as_ref().and_then(|it| it.parent());
         which::which_in(name, self_dir, ".")
@@ -8,6 +8,7 @@ use fluvio_command::CommandExt;
 use fluvio_extension_common::FluvioExtensionMetadata;
 
 use crate::client::client_metadata;
+use crate::plugin::PluginRegistry;
 
 #[derive(Debug, Parser)]
 pub struct MetadataOpt {}
@@ -43,7 +44,25 @@ pub struct SubcommandMetadata {
 pub fn subcommand_metadata() -> Result<Vec<SubcommandMetadata>> {
     let mut metadata = Vec::new();
 
-    let extensions = fluvio_cli_common::install::get_extensions()?;
+    // plugins managed by `fluvio plugin` shadow extensions with the same name
+    let mut extensions: Vec<PathBuf> = PluginRegistry::load()
+        .map(|registry| {
+            registry
+                .plugins()
+                .map(|(_, plugin)| plugin.path.clone())
+                .filter(|path| path.exists())
+                .collect()
+        })
+        .unwrap_or_default();
+    for path in fluvio_cli_common::install::get_extensions()? {
+        if !extensions
+            .iter()
+            .any(|managed| managed.file_name() == path.file_name())
+        {
+            extensions.push(path);
+        }
+    }
+
     for path in extensions {
         let result = Command::new(&path).arg("metadata").result();
         let output = match result {
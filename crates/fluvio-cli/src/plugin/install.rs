@@ -0,0 +1,151 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use semver::Version;
+use tracing::debug;
+
+use fluvio_cli_common::install::{fetch_latest_version, fetch_package_file, install_println};
+use fluvio_index::{HttpAgent, PackageId, Target};
+
+use crate::install::signature::verify_package;
+
+use super::registry::{plugin_bin_name, plugin_name, InstallCheck, InstalledPlugin};
+use super::PluginRegistry;
+
+/// group plugins are published under in the package index
+const PLUGIN_GROUP: &str = "fluvio";
+
+#[derive(Debug, Parser)]
+pub struct PackageOpt {
+    /// override default target arch determination
+    #[arg(long, hide_short_help = true)]
+    target: Option<String>,
+
+    /// Install even if plugin requires a different version of the Fluvio CLI
+    #[arg(long)]
+    force: bool,
+}
+
+impl PackageOpt {
+    fn target(&self) -> Result<Target> {
+        match &self.target {
+            Some(target) => Ok(Target::from_str(target)?),
+            None => Ok(fluvio_index::package_target()?),
+        }
+    }
+
+    /// download plugin and install it into registry, None if it was already up to date
+    async fn install(
+        &self,
+        registry: &mut PluginRegistry,
+        agent: &HttpAgent,
+        name: &str,
+        upgrade_only: bool,
+    ) -> Result<Option<InstalledPlugin>> {
+        let target = self.target()?;
+        let id: PackageId = format!("{PLUGIN_GROUP}/{}", plugin_bin_name(name)).parse()?;
+        let version = fetch_latest_version(agent, &id, &target, false).await?;
+        if upgrade_only {
+            if let Some(installed) = registry.get(name) {
+                if installed.version >= version {
+                    debug!(%name, %version, "plugin is up to date");
+                    return Ok(None);
+                }
+            }
+        }
+
+        let id = id.into_versioned(version.into());
+        debug!(%id, %target, "downloading plugin");
+        let binary = fetch_package_file(agent, &id, &target).await?;
+        // plugin is run to read its metadata, so it has to be verified before
+        verify_package(agent, &id, &target, &binary).await?;
+
+        let check = InstallCheck {
+            cli_version: Version::parse(crate::VERSION.trim())?,
+            force: self.force,
+            upgrade_only,
+        };
+        registry.install(name, &binary, &check)
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct InstallPluginOpt {
+    /// Name of plugin to install, e.g. "cloud"
+    name: String,
+
+    #[command(flatten)]
+    package: PackageOpt,
+}
+
+impl InstallPluginOpt {
+    pub async fn process(self) -> Result<()> {
+        let mut registry = PluginRegistry::load()?;
+        let agent = HttpAgent::default();
+        let name = plugin_name(&self.name);
+
+        install_println(format!("⏳ Downloading plugin {name}..."));
+        if let Some(plugin) = self
+            .package
+            .install(&mut registry, &agent, name, false)
+            .await?
+        {
+            install_println(format!(
+                "✅ Installed plugin {name} {} to {}",
+                plugin.version,
+                plugin.path.display()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct UpdatePluginOpt {
+    /// Plugins to update, all installed plugins if omitted
+    names: Vec<String>,
+
+    #[command(flatten)]
+    package: PackageOpt,
+}
+
+impl UpdatePluginOpt {
+    pub async fn process(self) -> Result<()> {
+        let mut registry = PluginRegistry::load()?;
+        let names: Vec<String> = if self.names.is_empty() {
+            registry
+                .plugins()
+                .map(|(name, _)| name.to_owned())
+                .collect()
+        } else {
+            self.names
+                .iter()
+                .map(|name| plugin_name(name).to_owned())
+                .collect()
+        };
+        if names.is_empty() {
+            println!("No plugins installed");
+            return Ok(());
+        }
+
+        let agent = HttpAgent::default();
+        for name in names {
+            let Some(installed) = registry.get(&name).map(|plugin| plugin.version.clone()) else {
+                return Err(anyhow!("plugin {name} is not installed"));
+            };
+            match self
+                .package
+                .install(&mut registry, &agent, &name, true)
+                .await?
+            {
+                Some(plugin) => install_println(format!(
+                    "✅ Updated plugin {name} {installed} -> {}",
+                    plugin.version
+                )),
+                None => install_println(format!("✅ Plugin {name} {installed} is up to date")),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+//!
+//! # Plugin management
+//!
+//! Install, update and remove Fluvio CLI plugins published on the package index
+//!
+mod install;
+mod registry;
+
+pub(crate) use registry::PluginRegistry;
+
+use clap::Parser;
+use anyhow::Result;
+
+use self::install::{InstallPluginOpt, UpdatePluginOpt};
+use self::registry::plugin_name;
+
+#[derive(Debug, Parser)]
+pub enum PluginCmd {
+    /// List installed plugins
+    #[command(name = "list")]
+    List(ListPluginOpt),
+
+    /// Install plugin from the package index
+    #[command(name = "install")]
+    Install(InstallPluginOpt),
+
+    /// Update installed plugins to the latest compatible version
+    #[command(name = "update")]
+    Update(UpdatePluginOpt),
+
+    /// Remove installed plugin
+    #[command(name = "remove")]
+    Remove(RemovePluginOpt),
+}
+
+impl PluginCmd {
+    pub async fn process(self) -> Result<()> {
+        match self {
+            Self::List(opt) => opt.process(),
+            Self::Install(opt) => opt.process().await,
+            Self::Update(opt) => opt.process().await,
+            Self::Remove(opt) => opt.process(),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct ListPluginOpt {}
+
+impl ListPluginOpt {
+    pub fn process(self) -> Result<()> {
+        let registry = PluginRegistry::load()?;
+        let mut plugins = registry.plugins().peekable();
+        if plugins.peek().is_none() {
+            println!("No plugins installed");
+            return Ok(());
+        }
+
+        println!("{:<20} {:<12} DESCRIPTION", "NAME", "VERSION");
+        for (name, plugin) in plugins {
+            println!(
+                "{:<20} {:<12} {}",
+                name,
+                plugin.version.to_string(),
+                plugin.description
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct RemovePluginOpt {
+    /// Name of plugin to remove, e.g. "cloud"
+    name: String,
+}
+
+impl RemovePluginOpt {
+    pub fn process(self) -> Result<()> {
+        let mut registry = PluginRegistry::load()?;
+        let plugin = registry.remove(&self.name)?;
+        println!(
+            "Removed plugin {} {}",
+            plugin_name(&self.name),
+            plugin.version
+        );
+        Ok(())
+    }
+}
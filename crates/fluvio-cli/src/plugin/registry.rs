@@ -0,0 +1,300 @@
+//!
+//! # Plugin registry
+//!
+//! Managed plugins are installed into their own directory,
+//! `~/.fluvio/plugins/<name>/<version>/`, and recorded in `~/.fluvio/plugins/plugins.json`.
+//!
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use fluvio_cli_common::install::{fluvio_base_dir, install_bin};
+use fluvio_command::CommandExt;
+use fluvio_extension_common::FluvioExtensionMetadata;
+
+pub const PLUGIN_PREFIX: &str = "fluvio-";
+const PLUGINS_DIR: &str = "plugins";
+const REGISTRY_FILE: &str = "plugins.json";
+
+/// Plugin name without the `fluvio-` prefix
+pub fn plugin_name(name: &str) -> &str {
+    name.strip_prefix(PLUGIN_PREFIX).unwrap_or(name)
+}
+
+/// Executable name of plugin
+pub fn plugin_bin_name(name: &str) -> String {
+    format!("{PLUGIN_PREFIX}{}", plugin_name(name))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstalledPlugin {
+    pub version: Version,
+    pub path: PathBuf,
+    pub description: String,
+}
+
+/// Checks applied to a plugin before it is installed
+#[derive(Debug)]
+pub struct InstallCheck {
+    /// version of running CLI, compared against plugin requirement
+    pub cli_version: Version,
+    /// install even if plugin requires a different CLI version
+    pub force: bool,
+    /// skip plugin unless it is newer than the installed version
+    pub upgrade_only: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    plugins: BTreeMap<String, InstalledPlugin>,
+}
+
+#[derive(Debug)]
+pub struct PluginRegistry {
+    dir: PathBuf,
+    plugins: BTreeMap<String, InstalledPlugin>,
+}
+
+impl PluginRegistry {
+    /// load registry from default location, `~/.fluvio/plugins`
+    pub fn load() -> Result<Self> {
+        Self::load_from(fluvio_base_dir()?.join(PLUGINS_DIR))
+    }
+
+    pub fn load_from(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        let file = dir.join(REGISTRY_FILE);
+        let registry: RegistryFile = if file.exists() {
+            let content = std::fs::read_to_string(&file)?;
+            serde_json::from_str(&content)
+                .map_err(|err| anyhow!("invalid plugin registry {}: {err}", file.display()))?
+        } else {
+            RegistryFile::default()
+        };
+        Ok(Self {
+            dir,
+            plugins: registry.plugins,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let registry = RegistryFile {
+            plugins: self.plugins.clone(),
+        };
+        std::fs::write(
+            self.dir.join(REGISTRY_FILE),
+            serde_json::to_string_pretty(&registry)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn plugins(&self) -> impl Iterator<Item = (&String, &InstalledPlugin)> {
+        self.plugins.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&InstalledPlugin> {
+        self.plugins.get(plugin_name(name))
+    }
+
+    /// path to executable of installed plugin, `name` may include the `fluvio-` prefix
+    pub fn find(&self, name: &str) -> Option<PathBuf> {
+        self.get(name)
+            .map(|plugin| plugin.path.clone())
+            .filter(|path| path.exists())
+    }
+
+    /// Install plugin binary if it passes `check`.
+    ///
+    /// Binary is run to read its metadata, so its checksum and signature must have been
+    /// verified already. It is staged and inspected before it replaces any previously
+    /// installed version.
+    /// Returns None if the plugin was skipped because it is not newer than the installed one.
+    pub fn install(
+        &mut self,
+        name: &str,
+        binary: &[u8],
+        check: &InstallCheck,
+    ) -> Result<Option<InstalledPlugin>> {
+        let name = plugin_name(name).to_owned();
+        let bin_name = exe_name(&plugin_bin_name(&name));
+        let staging_dir = self.dir.join(&name).join("staging");
+
+        let staging = staging_dir.join(&bin_name);
+        install_bin(&staging, binary)?;
+        let result = self.install_staged(&name, &bin_name, &staging, check);
+        let _ = std::fs::remove_dir_all(staging_dir);
+        result
+    }
+
+    fn install_staged(
+        &mut self,
+        name: &str,
+        bin_name: &str,
+        staging: &Path,
+        check: &InstallCheck,
+    ) -> Result<Option<InstalledPlugin>> {
+        let metadata = read_metadata(staging)
+            .map_err(|err| anyhow!("{name} is not a valid fluvio plugin: {err}"))?;
+
+        if check.upgrade_only {
+            if let Some(installed) = self.plugins.get(name) {
+                if installed.version >= metadata.version {
+                    debug!(%name, version = %metadata.version, "plugin is up to date");
+                    return Ok(None);
+                }
+            }
+        }
+        check_compatibility(name, &metadata, &check.cli_version, check.force)?;
+
+        let version_dir = self.dir.join(name).join(metadata.version.to_string());
+        std::fs::create_dir_all(&version_dir)?;
+        let path = version_dir.join(bin_name);
+        std::fs::rename(staging, &path)?;
+
+        let plugin = InstalledPlugin {
+            version: metadata.version,
+            path,
+            description: metadata.description,
+        };
+        if let Some(previous) = self.plugins.insert(name.to_owned(), plugin.clone()) {
+            if previous.version != plugin.version {
+                remove_version_dir(&previous);
+            }
+        }
+        self.save()?;
+        Ok(Some(plugin))
+    }
+
+    /// remove installed plugin and its files
+    pub fn remove(&mut self, name: &str) -> Result<InstalledPlugin> {
+        let name = plugin_name(name);
+        let plugin = self
+            .plugins
+            .remove(name)
+            .ok_or_else(|| anyhow!("plugin {name} is not installed"))?;
+        let plugin_dir = self.dir.join(name);
+        if plugin_dir.exists() {
+            std::fs::remove_dir_all(&plugin_dir)?;
+        }
+        self.save()?;
+        Ok(plugin)
+    }
+}
+
+fn remove_version_dir(plugin: &InstalledPlugin) {
+    if let Some(dir) = plugin.path.parent() {
+        if let Err(err) = std::fs::remove_dir_all(dir) {
+            debug!(%err, dir = %dir.display(), "unable to remove previous plugin version");
+        }
+    }
+}
+
+fn exe_name(bin_name: &str) -> String {
+    if cfg!(windows) {
+        format!("{bin_name}.exe")
+    } else {
+        bin_name.to_owned()
+    }
+}
+
+/// query plugin for its metadata
+fn read_metadata(path: &Path) -> Result<FluvioExtensionMetadata> {
+    let output = Command::new(path)
+        .arg("metadata")
+        .result()
+        .map_err(|err| anyhow!("unable to query plugin metadata: {err}"))?;
+    serde_json::from_slice(&output.stdout).map_err(|err| anyhow!("invalid plugin metadata: {err}"))
+}
+
+/// check plugin declared CLI requirement, if any
+pub fn check_compatibility(
+    name: &str,
+    metadata: &FluvioExtensionMetadata,
+    cli_version: &Version,
+    force: bool,
+) -> Result<()> {
+    match &metadata.fluvio_version {
+        Some(req) if !req.matches(cli_version) => {
+            if force {
+                println!(
+                    "warning: {name} {} requires Fluvio CLI {req}, current version is {cli_version}",
+                    metadata.version
+                );
+                Ok(())
+            } else {
+                Err(anyhow!(
+                    "{name} {} requires Fluvio CLI {req}, current version is {cli_version}. Use --force to install anyway",
+                    metadata.version
+                ))
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(fluvio_version: Option<&str>) -> FluvioExtensionMetadata {
+        let metadata = FluvioExtensionMetadata::new("Foo", "foo plugin", Version::new(1, 2, 0));
+        match fluvio_version {
+            Some(req) => metadata.with_fluvio_version(req.parse().unwrap()),
+            None => metadata,
+        }
+    }
+
+    #[test]
+    fn test_plugin_names() {
+        assert_eq!(plugin_name("fluvio-cloud"), "cloud");
+        assert_eq!(plugin_name("cloud"), "cloud");
+        assert_eq!(plugin_bin_name("cloud"), "fluvio-cloud");
+        assert_eq!(plugin_bin_name("fluvio-cloud"), "fluvio-cloud");
+    }
+
+    #[test]
+    fn test_check_compatibility() {
+        let cli = Version::new(0, 12, 1);
+        assert!(check_compatibility("foo", &metadata(None), &cli, false).is_ok());
+        assert!(check_compatibility("foo", &metadata(Some(">=0.12")), &cli, false).is_ok());
+        assert!(check_compatibility("foo", &metadata(Some(">=0.13")), &cli, false).is_err());
+        assert!(check_compatibility("foo", &metadata(Some(">=0.13")), &cli, true).is_ok());
+    }
+
+    #[test]
+    fn test_registry_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("fluvio-plugins-{}", std::process::id()));
+        let mut registry = PluginRegistry::load_from(&dir).expect("load");
+        assert_eq!(registry.plugins().count(), 0);
+
+        let path = dir.join("foo").join("1.2.0").join("fluvio-foo");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, b"").unwrap();
+        registry.plugins.insert(
+            "foo".to_owned(),
+            InstalledPlugin {
+                version: Version::new(1, 2, 0),
+                path: path.clone(),
+                description: "foo plugin".into(),
+            },
+        );
+        registry.save().expect("save");
+
+        let mut registry = PluginRegistry::load_from(&dir).expect("load");
+        assert_eq!(registry.find("fluvio-foo"), Some(path.clone()));
+        registry.remove("foo").expect("remove");
+        assert!(!path.exists());
+        assert!(PluginRegistry::load_from(&dir)
+            .expect("load")
+            .get("foo")
+            .is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
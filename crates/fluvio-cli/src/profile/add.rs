@@ -4,20 +4,38 @@ use anyhow::Result;
 use fluvio::config::{ConfigFile, TlsPolicy};
 use fluvio_extension_common::installation::InstallationType;
 
+use super::discover::discover;
+
 #[derive(Debug, Parser)]
 pub struct ManualAddOpt {
     /// Name of profile to add
     profile_name: String,
 
     /// address of cluster, e.g. 127.0.0.1:9003
-    cluster_address: String,
+    #[arg(required_unless_present = "discover", conflicts_with = "discover")]
+    cluster_address: Option<String>,
 
     /// Installation type of cluster, e.g. local, local-k8, k8
     installation_type: Option<InstallationType>,
+
+    /// Discover cluster address and TLS policy from a domain,
+    /// using `https://<domain>/.well-known/fluvio` or DNS SRV records
+    #[arg(long, value_name = "domain")]
+    discover: Option<String>,
 }
 
 impl ManualAddOpt {
-    pub fn process(self) -> Result<()> {
+    pub async fn process(self) -> Result<()> {
+        let (cluster_address, tls) = match (self.cluster_address, &self.discover) {
+            (Some(address), _) => (address, TlsPolicy::Disabled),
+            (None, Some(domain)) => {
+                let cluster = discover(domain).await?;
+                println!("Discovered cluster {} for {domain}", cluster.endpoint);
+                (cluster.endpoint, cluster.tls)
+            }
+            (None, None) => unreachable!("clap requires address or --discover"),
+        };
+
         let mut config_file = match ConfigFile::load(None) {
             Ok(config_file) => config_file,
             Err(_) => {
@@ -26,8 +44,7 @@ impl ManualAddOpt {
             }
         };
 
-        let def_tls = TlsPolicy::Disabled;
-        config_file.add_or_replace_profile(&self.profile_name, &self.cluster_address, &def_tls)?;
+        config_file.add_or_replace_profile(&self.profile_name, &cluster_address, &tls)?;
         let config = config_file.mut_config().current_cluster_mut()?;
         self.installation_type.unwrap_or_default().save_to(config)?;
         config_file.save()?;
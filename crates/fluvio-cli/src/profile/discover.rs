@@ -0,0 +1,376 @@
+//!
+//! # Profile discovery
+//!
+//! Resolves the SC endpoint and TLS policy of a cluster from a domain name.
+//!
+//! Discovery first looks for a `https://<domain>/.well-known/fluvio` document,
+//! then falls back to DNS SRV records:
+//!
+//! - `_fluvio-tls._tcp.<domain>` - SC accepting anonymous TLS
+//! - `_fluvio._tcp.<domain>` - SC accepting plaintext
+//!
+//! A SRV record with the target `.` means the service is not available.
+//! Truncated UDP responses are retried over TCP.
+//!
+//! An example of the well-known document:
+//!
+//! ```json
+//! {
+//!     "endpoint": "sc.example.com:9003",
+//!     "tls": { "tls_policy": "anonymous" }
+//! }
+//! ```
+//!
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, UdpSocket};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use rand::Rng;
+use tracing::debug;
+
+use fluvio::config::TlsPolicy;
+use fluvio_future::task::spawn_blocking;
+
+const WELL_KNOWN_PATH: &str = ".well-known/fluvio";
+const SRV_TLS_SERVICE: &str = "_fluvio-tls._tcp";
+const SRV_PLAIN_SERVICE: &str = "_fluvio._tcp";
+
+const RESOLV_CONF: &str = "/etc/resolv.conf";
+const DNS_PORT: u16 = 53;
+const DNS_TIMEOUT: Duration = Duration::from_secs(5);
+const DNS_TYPE_SRV: u16 = 33;
+const DNS_CLASS_IN: u16 = 1;
+
+/// Cluster access settings published by an organization
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct DiscoveredCluster {
+    /// address of SC public endpoint, e.g. sc.example.com:9003
+    pub endpoint: String,
+
+    /// TLS policy required to connect to the SC
+    #[serde(default)]
+    pub tls: TlsPolicy,
+}
+
+/// Discover cluster access settings for `domain`
+pub async fn discover(domain: &str) -> Result<DiscoveredCluster> {
+    let domain = domain.trim_end_matches('.');
+
+    match discover_well_known(domain).await {
+        Ok(cluster) => return Ok(cluster),
+        Err(err) => debug!(%err, "well-known discovery failed, trying DNS SRV"),
+    }
+
+    // SRV lookups use a blocking socket
+    let domain = domain.to_owned();
+    spawn_blocking(move || discover_srv(&domain)).await
+}
+
+async fn discover_well_known(domain: &str) -> Result<DiscoveredCluster> {
+    let uri = format!("https://{domain}/{WELL_KNOWN_PATH}");
+    debug!(%uri, "fetching well-known document");
+    let body = fluvio_cli_common::http::get_simple(&uri).await?;
+    let cluster: DiscoveredCluster = serde_json::from_str(&body)
+        .map_err(|err| anyhow!("invalid discovery document at {uri}: {err}"))?;
+    Ok(cluster)
+}
+
+fn discover_srv(domain: &str) -> Result<DiscoveredCluster> {
+    let nameserver = system_nameserver()?;
+
+    for (service, tls) in [
+        (SRV_TLS_SERVICE, TlsPolicy::Anonymous),
+        (SRV_PLAIN_SERVICE, TlsPolicy::Disabled),
+    ] {
+        let name = format!("{service}.{domain}");
+        match lookup_srv(nameserver, &name) {
+            Ok(records) => match select_srv(records) {
+                Some(record) => {
+                    return Ok(DiscoveredCluster {
+                        endpoint: format!("{}:{}", record.target, record.port),
+                        tls,
+                    });
+                }
+                None => debug!(%name, "service not available"),
+            },
+            Err(err) => debug!(%name, %err, "SRV lookup failed"),
+        }
+    }
+
+    Err(anyhow!(
+        "unable to discover cluster for {domain}: no {WELL_KNOWN_PATH} document or SRV records found"
+    ))
+}
+
+/// first nameserver listed in the system resolver configuration
+fn system_nameserver() -> Result<SocketAddr> {
+    let conf = std::fs::read_to_string(RESOLV_CONF)
+        .map_err(|err| anyhow!("unable to read {RESOLV_CONF}: {err}"))?;
+    parse_nameserver(&conf).ok_or_else(|| anyhow!("no nameserver found in {RESOLV_CONF}"))
+}
+
+fn parse_nameserver(conf: &str) -> Option<SocketAddr> {
+    conf.lines()
+        .filter_map(|line| line.trim().strip_prefix("nameserver"))
+        .filter_map(|addr| addr.trim().parse::<std::net::IpAddr>().ok())
+        .map(|ip| SocketAddr::new(ip, DNS_PORT))
+        .next()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SrvRecord {
+    priority: u16,
+    weight: u16,
+    port: u16,
+    target: String,
+}
+
+/// lowest priority wins, ties are broken by the highest weight.
+/// Records with the target `.` mark the service as not available
+fn select_srv(mut records: Vec<SrvRecord>) -> Option<SrvRecord> {
+    records.retain(|record| !record.target.is_empty());
+    records.sort_by(|a, b| a.priority.cmp(&b.priority).then(b.weight.cmp(&a.weight)));
+    records.into_iter().next()
+}
+
+fn lookup_srv(nameserver: SocketAddr, name: &str) -> Result<Vec<SrvRecord>> {
+    let bind_addr = if nameserver.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(bind_addr)?;
+    socket.set_read_timeout(Some(DNS_TIMEOUT))?;
+    socket.connect(nameserver)?;
+
+    // random id, so responses to other queries are not taken for ours
+    let id: u16 = rand::thread_rng().gen();
+    let query = encode_srv_query(id, name)?;
+    socket.send(&query)?;
+
+    let mut buf = [0u8; 4096];
+    let len = socket.recv(&mut buf)?;
+    if is_truncated(&buf[..len]) {
+        debug!(%name, "DNS response truncated, retrying over TCP");
+        return lookup_srv_tcp(nameserver, id, &query);
+    }
+    decode_srv_response(id, &buf[..len])
+}
+
+/// send query over TCP, where messages are prefixed with their length
+fn lookup_srv_tcp(nameserver: SocketAddr, id: u16, query: &[u8]) -> Result<Vec<SrvRecord>> {
+    let mut stream = TcpStream::connect_timeout(&nameserver, DNS_TIMEOUT)?;
+    stream.set_read_timeout(Some(DNS_TIMEOUT))?;
+    stream.set_write_timeout(Some(DNS_TIMEOUT))?;
+
+    stream.write_all(&(query.len() as u16).to_be_bytes())?;
+    stream.write_all(query)?;
+
+    let mut len = [0u8; 2];
+    stream.read_exact(&mut len)?;
+    let mut msg = vec![0u8; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut msg)?;
+    decode_srv_response(id, &msg)
+}
+
+/// TC flag of the header, set when the response did not fit
+fn is_truncated(msg: &[u8]) -> bool {
+    msg.get(2).is_some_and(|flags| flags & 0x02 != 0)
+}
+
+fn encode_srv_query(id: u16, name: &str) -> Result<Vec<u8>> {
+    let mut msg = Vec::with_capacity(name.len() + 18);
+    msg.extend_from_slice(&id.to_be_bytes());
+    // standard query with recursion desired
+    msg.extend_from_slice(&0x0100u16.to_be_bytes());
+    // one question, no answer/authority/additional records
+    msg.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in name.split('.').filter(|label| !label.is_empty()) {
+        if label.len() > 63 {
+            return Err(anyhow!("invalid DNS label: {label}"));
+        }
+        msg.push(label.len() as u8);
+        msg.extend_from_slice(label.as_bytes());
+    }
+    msg.push(0);
+    msg.extend_from_slice(&DNS_TYPE_SRV.to_be_bytes());
+    msg.extend_from_slice(&DNS_CLASS_IN.to_be_bytes());
+    Ok(msg)
+}
+
+fn decode_srv_response(id: u16, msg: &[u8]) -> Result<Vec<SrvRecord>> {
+    if msg.len() < 12 {
+        return Err(anyhow!("DNS response too short"));
+    }
+    if read_u16(msg, 0)? != id {
+        return Err(anyhow!("DNS response id mismatch"));
+    }
+    let rcode = msg[3] & 0x0f;
+    if rcode != 0 {
+        return Err(anyhow!("DNS query failed with rcode {rcode}"));
+    }
+    let questions = read_u16(msg, 4)?;
+    let answers = read_u16(msg, 6)?;
+
+    let mut pos = 12;
+    for _ in 0..questions {
+        let (_, next) = read_name(msg, pos)?;
+        pos = next + 4;
+    }
+
+    let mut records = vec![];
+    for _ in 0..answers {
+        let (_, next) = read_name(msg, pos)?;
+        let rtype = read_u16(msg, next)?;
+        let rdlength = read_u16(msg, next + 8)? as usize;
+        let rdata = next + 10;
+        if rdata + rdlength > msg.len() {
+            return Err(anyhow!("DNS record exceeds response"));
+        }
+        if rtype == DNS_TYPE_SRV {
+            let (target, _) = read_name(msg, rdata + 6)?;
+            records.push(SrvRecord {
+                priority: read_u16(msg, rdata)?,
+                weight: read_u16(msg, rdata + 2)?,
+                port: read_u16(msg, rdata + 4)?,
+                target,
+            });
+        }
+        pos = rdata + rdlength;
+    }
+
+    Ok(records)
+}
+
+fn read_u16(msg: &[u8], pos: usize) -> Result<u16> {
+    msg.get(pos..pos + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| anyhow!("DNS response truncated"))
+}
+
+/// read a possibly compressed domain name, returning the name and the position after it
+fn read_name(msg: &[u8], start: usize) -> Result<(String, usize)> {
+    let mut labels: Vec<String> = vec![];
+    let mut pos = start;
+    let mut end = None;
+    // bound pointer chasing so malformed responses can't loop forever
+    for _ in 0..128 {
+        let len = *msg.get(pos).ok_or_else(|| anyhow!("DNS name truncated"))? as usize;
+        if len == 0 {
+            return Ok((labels.join("."), end.unwrap_or(pos + 1)));
+        }
+        if len & 0xc0 == 0xc0 {
+            let pointer = (read_u16(msg, pos)? & 0x3fff) as usize;
+            end.get_or_insert(pos + 2);
+            pos = pointer;
+            continue;
+        }
+        let label = msg
+            .get(pos + 1..pos + 1 + len)
+            .ok_or_else(|| anyhow!("DNS label truncated"))?;
+        labels.push(String::from_utf8_lossy(label).into_owned());
+        pos += 1 + len;
+    }
+    Err(anyhow!("DNS name compression loop"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn srv_response(id: u16) -> Vec<u8> {
+        let mut msg = encode_srv_query(id, "_fluvio._tcp.example.com").unwrap();
+        // mark as response with one answer
+        msg[2] = 0x81;
+        msg[3] = 0x80;
+        msg[7] = 2;
+        for (priority, weight, target) in [(20u16, 0u16, "sc2"), (10, 5, "sc1")] {
+            // name pointer to question at offset 12
+            msg.extend_from_slice(&[0xc0, 12]);
+            msg.extend_from_slice(&DNS_TYPE_SRV.to_be_bytes());
+            msg.extend_from_slice(&DNS_CLASS_IN.to_be_bytes());
+            msg.extend_from_slice(&300u32.to_be_bytes());
+            let rdlength = 6 + 1 + target.len() + 2;
+            msg.extend_from_slice(&(rdlength as u16).to_be_bytes());
+            msg.extend_from_slice(&priority.to_be_bytes());
+            msg.extend_from_slice(&weight.to_be_bytes());
+            msg.extend_from_slice(&9003u16.to_be_bytes());
+            msg.push(target.len() as u8);
+            msg.extend_from_slice(target.as_bytes());
+            // pointer to "example.com" inside the question
+            msg.extend_from_slice(&[0xc0, 12 + 13]);
+        }
+        msg
+    }
+
+    #[test]
+    fn test_decode_srv_response() {
+        let records = decode_srv_response(7, &srv_response(7)).expect("decode");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].target, "sc2.example.com");
+
+        let selected = select_srv(records).expect("record");
+        assert_eq!(
+            selected,
+            SrvRecord {
+                priority: 10,
+                weight: 5,
+                port: 9003,
+                target: "sc1.example.com".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_mismatched_id() {
+        assert!(decode_srv_response(8, &srv_response(7)).is_err());
+    }
+
+    #[test]
+    fn test_truncated_response() {
+        let mut msg = srv_response(7);
+        assert!(!is_truncated(&msg));
+        msg[2] |= 0x02;
+        assert!(is_truncated(&msg));
+        assert!(!is_truncated(&[0, 7]));
+    }
+
+    #[test]
+    fn test_service_not_available() {
+        let record = |target: &str| SrvRecord {
+            priority: 0,
+            weight: 0,
+            port: 0,
+            target: target.to_owned(),
+        };
+        assert_eq!(select_srv(vec![record("")]), None);
+        assert_eq!(
+            select_srv(vec![record(""), record("sc.example.com")]),
+            Some(record("sc.example.com"))
+        );
+    }
+
+    #[test]
+    fn test_parse_nameserver() {
+        let conf = "# generated\nsearch example.com\nnameserver 10.0.0.2\nnameserver 8.8.8.8\n";
+        assert_eq!(
+            parse_nameserver(conf),
+            Some("10.0.0.2:53".parse().unwrap())
+        );
+        assert_eq!(parse_nameserver("search example.com"), None);
+    }
+
+    #[test]
+    fn test_well_known_document() {
+        let doc = r#"{"endpoint": "sc.example.com:9003", "tls": {"tls_policy": "anonymous"}}"#;
+        let cluster: DiscoveredCluster = serde_json::from_str(doc).expect("parse");
+        assert_eq!(cluster.endpoint, "sc.example.com:9003");
+        assert_eq!(cluster.tls, TlsPolicy::Anonymous);
+
+        let doc = r#"{"endpoint": "sc.example.com:9003"}"#;
+        let cluster: DiscoveredCluster = serde_json::from_str(doc).expect("parse");
+        assert_eq!(cluster.tls, TlsPolicy::Disabled);
+    }
+}
@@ -5,6 +5,7 @@
 //!
 
 mod add;
+mod discover;
 mod sync;
 mod current;
 mod switch;
@@ -115,7 +116,7 @@ impl ProfileCmd {
                 export.process(out)?;
             }
             Self::ManualAdd(add) => {
-                add.process()?;
+                add.process().await?;
             }
         }
 
@@ -36,11 +36,31 @@ pub struct CreateManagedSpuGroupOpt {
     /// The amount of storage to assign to this SPG
     #[arg(long, value_name = "string")]
     pub storage_size: Option<String>,
+
+    /// Bytes kept in partitions of topics without a max partition size, defaults to 100GB
+    #[arg(long, value_name = "bytes")]
+    pub max_partition_size: Option<u64>,
+
+    /// Maximum number of SPUs when scaling with load, enables autoscaling
+    #[arg(long, value_name = "integer")]
+    pub autoscale_max: Option<u16>,
+
+    /// Minimum number of SPUs when scaling with load, defaults to replicas
+    #[arg(long, value_name = "integer", requires = "autoscale_max")]
+    pub autoscale_min: Option<u16>,
+
+    /// Bytes written per second to the leaders of an SPU to scale at
+    #[arg(long, value_name = "bytes", requires = "autoscale_max")]
+    pub autoscale_throughput: Option<u64>,
+
+    /// Percentage of the storage of an SPU in use to scale at
+    #[arg(long, value_name = "percent", requires = "autoscale_max")]
+    pub autoscale_disk_usage: Option<u8>,
 }
 
 impl CreateManagedSpuGroupOpt {
     pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
-        let (name, spec) = self.validate();
+        let (name, spec) = self.validate()?;
         debug!("creating spg: {}, spec: {:#?}", name, spec);
 
         let admin = fluvio.admin().await;
@@ -50,11 +70,15 @@ impl CreateManagedSpuGroupOpt {
     }
 
     /// Validate cli options. Generate target-server and create spu group config.
-    fn validate(self) -> (String, SpuGroupSpec) {
-        let storage = self.storage_size.map(|storage_size| StorageConfig {
-            size: Some(storage_size),
-            ..Default::default()
-        });
+    fn validate(self) -> Result<(String, SpuGroupSpec)> {
+        let storage =
+            (self.storage_size.is_some() || self.max_partition_size.is_some()).then(|| {
+                StorageConfig {
+                    size: self.storage_size,
+                    max_partition_size: self.max_partition_size,
+                    ..Default::default()
+                }
+            });
 
         let spu_config = SpuConfig {
             storage,
@@ -62,11 +86,22 @@ impl CreateManagedSpuGroupOpt {
             ..Default::default()
         };
 
+        let autoscale = self.autoscale_max.map(|max_replicas| SpuGroupAutoscale {
+            min_replicas: self.autoscale_min.unwrap_or(self.replicas),
+            max_replicas,
+            target_throughput: self.autoscale_throughput,
+            target_disk_usage: self.autoscale_disk_usage,
+        });
+        if let Some(autoscale) = &autoscale {
+            autoscale.validate().map_err(anyhow::Error::msg)?;
+        }
+
         let spec = SpuGroupSpec {
             replicas: self.replicas,
             min_id: self.min_id,
             spu_config,
+            autoscale,
         };
-        (self.name, spec)
+        Ok((self.name, spec))
     }
 }
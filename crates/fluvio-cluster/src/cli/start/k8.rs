@@ -64,6 +64,10 @@ pub async fn process_k8(opt: StartOpt, platform_version: Version, upgrade: bool)
         builder.service_type(service_type);
     }
 
+    if let Some(port) = opt.metrics_port {
+        builder.metrics_port(port);
+    }
+
     let config = builder.build()?;
 
     debug!("cluster config: {:#?}", config);
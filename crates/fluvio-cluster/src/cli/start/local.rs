@@ -46,6 +46,11 @@ pub async fn process_local(opt: StartOpt, platform_version: Version) -> Result<(
 
     builder.save_profile(!opt.skip_profile_creation);
 
+    builder.install_service(opt.install_service);
+    if let Some(port) = opt.metrics_port {
+        builder.metrics_port(port);
+    }
+
     if let Some(pub_addr) = opt.sc_pub_addr {
         builder.sc_pub_addr(pub_addr);
     }
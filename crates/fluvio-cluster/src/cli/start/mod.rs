@@ -190,6 +190,16 @@ pub struct StartOpt {
     #[arg(long)]
     pub service_type: Option<String>,
 
+    /// Install local SC and SPUs as system services, so they are restarted on failure.
+    /// Supported on Linux (systemd user services) and Windows (requires an elevated shell)
+    #[arg(long)]
+    pub install_service: bool,
+
+    /// Serve Prometheus metrics on this port at /metrics.
+    /// On a local cluster the SC uses it and the SPUs the following ports
+    #[arg(long, value_name = "port")]
+    pub metrics_port: Option<u16>,
+
     #[command(flatten)]
     pub installation_type: IntallationTypeOpt,
 }
@@ -15,6 +15,7 @@ use crate::check::{ActiveKubernetesCluster, ClusterCheck};
 use crate::render::ProgressRenderer;
 use crate::{cli::ClusterCliError, cli::ClusterTarget};
 use crate::progress::ProgressBarFactory;
+use crate::runtime::local::service::{installed_services, service_state};
 
 #[derive(Debug, Parser)]
 pub struct StatusOpt;
@@ -47,6 +48,11 @@ impl StatusOpt {
         if let InstallationType::K8 | InstallationType::LocalK8 = installation_type {
             let _ = Self::check_k8s_cluster(&pb).await;
         }
+        if let InstallationType::Local | InstallationType::LocalK8 | InstallationType::ReadOnly =
+            installation_type
+        {
+            Self::check_services(&pb);
+        }
         Self::check_sc(&pb, &fluvio_config, &config_file).await?;
         Self::check_spus(&pb, &fluvio_config).await?;
         Self::check_topics(&pb, &fluvio_config).await?;
@@ -85,6 +91,40 @@ impl StatusOpt {
         }
     }
 
+    /// report state of SC and SPUs installed as system services, if any
+    fn check_services(pb: &ProgressRenderer) {
+        let services = match installed_services() {
+            Ok(services) => services,
+            Err(err) => {
+                debug!(%err, "unable to list services");
+                return;
+            }
+        };
+
+        for name in services {
+            match service_state(&name) {
+                Ok(Some(state)) if state.is_active() => {
+                    pb.println(pad_format!(format!(
+                        "{} Service {} is {}",
+                        "✅".bold(),
+                        name.italic(),
+                        state
+                    )));
+                }
+                Ok(Some(state)) => {
+                    pb.println(pad_format!(format!(
+                        "{} Service {} is {}",
+                        "❌",
+                        name.italic(),
+                        state
+                    )));
+                }
+                Ok(None) => {}
+                Err(err) => debug!(%err, service = %name, "unable to get service state"),
+            }
+        }
+    }
+
     async fn check_sc(
         pb: &ProgressRenderer,
         fluvio_config: &FluvioConfig,
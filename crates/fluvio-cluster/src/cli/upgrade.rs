@@ -3,7 +3,8 @@ use color_eyre::owo_colors::OwoColorize;
 use colored::Colorize;
 use fluvio_extension_common::installation::InstallationType;
 use fluvio_sc_schema::{
-    mirror::MirrorSpec, partition::PartitionSpec, smartmodule::SmartModuleSpec, spg::SpuGroupSpec,
+    acl::AclSpec, alert::AlertRuleSpec, consumer_group::ConsumerGroupSpec, mirror::MirrorSpec,
+    partition::PartitionSpec, schema::SchemaSpec, smartmodule::SmartModuleSpec, spg::SpuGroupSpec,
     spu::SpuSpec, store::NameSpace, tableformat::TableFormatSpec, topic::TopicSpec,
 };
 use fluvio_stream_dispatcher::metadata::{local::LocalMetadataStorage, MetadataClient};
@@ -161,6 +162,14 @@ async fn check_all_metadata(pb: &ProgressRenderer, client: &LocalMetadataStorage
         .retrieve_items::<TableFormatSpec>(&NameSpace::All)
         .await?;
     let _ = client.retrieve_items::<MirrorSpec>(&NameSpace::All).await?;
+    let _ = client
+        .retrieve_items::<AlertRuleSpec>(&NameSpace::All)
+        .await?;
+    let _ = client
+        .retrieve_items::<ConsumerGroupSpec>(&NameSpace::All)
+        .await?;
+    let _ = client.retrieve_items::<SchemaSpec>(&NameSpace::All).await?;
+    let _ = client.retrieve_items::<AclSpec>(&NameSpace::All).await?;
 
     pb.println(format!("✅ {}", "Checked All Metadata".bold()));
     Ok(())
@@ -9,11 +9,17 @@ use anyhow::Result;
 use tracing::{debug, warn};
 
 use crate::render::ProgressRenderer;
+use crate::runtime::local::service::uninstall_all_services;
 use crate::start::local::{DEFAULT_DATA_DIR, LOCAL_CONFIG_PATH};
 
 pub async fn kill_local_processes(pb: &ProgressRenderer) -> Result<()> {
     pb.set_message("Uninstalling fluvio local components");
 
+    // services would be restarted by the service manager if only their processes were killed
+    if let Err(err) = uninstall_all_services() {
+        warn!("unable to remove fluvio services: {err}");
+    }
+
     let kill_proc = |name: &str, command_args: Option<&[String]>| {
         sysinfo::set_open_files_limit(0);
         let mut sys = System::new();
@@ -1,5 +1,6 @@
 mod spu;
 mod sc;
+pub mod service;
 
 pub use spu::*;
 pub use sc::*;
@@ -9,6 +9,7 @@ use fluvio::config::TlsPolicy;
 use fluvio_command::CommandExt;
 use tracing::info;
 
+use super::service::{LocalService, SC_SERVICE_NAME};
 use super::{FluvioLocalProcess, LocalRuntimeError};
 
 #[derive(Debug)]
@@ -20,6 +21,10 @@ pub struct ScProcess {
     pub mode: ScMode,
     pub public_address: String,
     pub private_address: Option<String>,
+    /// install as system service instead of spawning a child process
+    pub service: bool,
+    /// address of the metrics endpoint, disabled if not set
+    pub metrics_address: Option<String>,
 }
 
 #[derive(Debug)]
@@ -33,8 +38,7 @@ impl FluvioLocalProcess for ScProcess {}
 
 impl ScProcess {
     pub fn start(&self) -> Result<()> {
-        let outputs = File::create(format!("{}/flv_sc.log", self.log_dir.display()))?;
-        let errors = outputs.try_clone()?;
+        let log_file = self.log_dir.join("flv_sc.log");
 
         let launcher = self.launcher.clone();
         let mut binary = {
@@ -62,11 +66,24 @@ impl ScProcess {
             binary.arg("--bind-private").arg(address);
         }
 
+        if let Some(address) = &self.metrics_address {
+            binary.arg("--bind-metrics").arg(address);
+        }
+
         if let TlsPolicy::Verified(tls) = &self.tls_policy {
             self.set_server_tls(&mut binary, tls, 9005)?;
         }
         binary.env("RUST_LOG", &self.rust_log);
 
+        if self.service {
+            info!(cmd = %binary.display(), "Installing SC service");
+            return LocalService::from_command(SC_SERVICE_NAME, "Fluvio SC", &binary, log_file)
+                .install();
+        }
+
+        let outputs = File::create(&log_file)?;
+        let errors = outputs.try_clone()?;
+
         info!(cmd = %binary.display(),"Invoking command");
         binary
             .stdout(Stdio::from(outputs))
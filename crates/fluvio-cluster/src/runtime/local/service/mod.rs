@@ -0,0 +1,145 @@
+//! Local cluster processes managed as system services
+//!
+//! SC and SPUs are installed as services, so they are restarted on failure and survive
+//! reboots:
+//!  * on Linux as systemd user units (surviving reboots with `loginctl enable-linger`)
+//!  * on Windows as services of the service control manager, hosted by `fluvio-run`
+//!
+//! Other platforms, including macOS, are not supported. Installing services there fails with
+//! an unsupported platform error before anything is started; the cluster can be started
+//! without `--install-service` there.
+
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+
+use fluvio_types::SpuId;
+
+#[cfg(any(not(windows), test))]
+mod systemd;
+#[cfg(any(windows, test))]
+mod windows;
+
+#[cfg(not(windows))]
+use self::systemd as manager;
+#[cfg(windows)]
+use self::windows as manager;
+
+/// prefix shared by all fluvio services
+pub const SERVICE_PREFIX: &str = "fluvio-";
+pub const SC_SERVICE_NAME: &str = "fluvio-sc";
+
+pub fn spu_service_name(id: SpuId) -> String {
+    format!("{SERVICE_PREFIX}spu-{id}")
+}
+
+/// State of service as reported by the service manager
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceState {
+    Active,
+    Activating,
+    Inactive,
+    Failed,
+    Other(String),
+}
+
+impl ServiceState {
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Active)
+    }
+}
+
+impl fmt::Display for ServiceState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Active => write!(f, "active"),
+            Self::Activating => write!(f, "activating"),
+            Self::Inactive => write!(f, "inactive"),
+            Self::Failed => write!(f, "failed"),
+            Self::Other(state) => write!(f, "{state}"),
+        }
+    }
+}
+
+/// Long running fluvio process registered with the service manager
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalService {
+    pub name: String,
+    pub description: String,
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    pub envs: Vec<(String, String)>,
+    pub log_file: PathBuf,
+}
+
+impl LocalService {
+    /// Create service running the same program, arguments and environment as `cmd`
+    pub fn from_command(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        cmd: &Command,
+        log_file: impl Into<PathBuf>,
+    ) -> Self {
+        let lossy = |s: &OsStr| s.to_string_lossy().into_owned();
+        Self {
+            name: name.into(),
+            description: description.into(),
+            program: PathBuf::from(cmd.get_program()),
+            args: cmd.get_args().map(lossy).collect(),
+            envs: cmd
+                .get_envs()
+                .filter_map(|(key, value)| value.map(|value| (lossy(key), lossy(value))))
+                .collect(),
+            log_file: log_file.into(),
+        }
+    }
+
+    /// Install, enable and start the service
+    pub fn install(&self) -> Result<()> {
+        check_platform_supported()?;
+        manager::install(self)
+    }
+}
+
+/// Current state of the named service, None if it is not installed
+pub fn service_state(name: &str) -> Result<Option<ServiceState>> {
+    check_platform_supported()?;
+    manager::service_state(name)
+}
+
+/// Names of all installed fluvio services
+pub fn installed_services() -> Result<Vec<String>> {
+    if check_platform_supported().is_err() {
+        return Ok(vec![]);
+    }
+    manager::installed_services()
+}
+
+/// Stop, disable and remove the named service
+pub fn uninstall_service(name: &str) -> Result<()> {
+    check_platform_supported()?;
+    manager::uninstall_service(name)
+}
+
+/// Remove every installed fluvio service
+pub fn uninstall_all_services() -> Result<()> {
+    for name in installed_services()? {
+        uninstall_service(&name)?;
+    }
+    Ok(())
+}
+
+/// Fails on platforms without a supported service manager
+pub fn check_platform_supported() -> Result<()> {
+    if cfg!(any(target_os = "linux", windows)) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "unsupported platform {}: local cluster services are only supported on Linux with systemd and on Windows",
+            std::env::consts::OS
+        ))
+    }
+}
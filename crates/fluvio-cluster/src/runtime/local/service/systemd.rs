@@ -0,0 +1,214 @@
+//! systemd user units
+//!
+//! Units are written to the systemd user directory and managed with `systemctl --user`.
+#![cfg_attr(windows, allow(dead_code))]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+use tracing::{debug, info};
+
+use super::{LocalService, ServiceState, SERVICE_PREFIX};
+
+const UNIT_SUFFIX: &str = ".service";
+
+impl ServiceState {
+    fn parse(state: &str) -> Self {
+        match state.trim() {
+            "active" => Self::Active,
+            "activating" | "reloading" => Self::Activating,
+            "inactive" | "deactivating" => Self::Inactive,
+            "failed" => Self::Failed,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+}
+
+impl LocalService {
+    /// systemd unit definition for this service
+    pub fn systemd_unit(&self) -> String {
+        let mut exec = vec![systemd_quote(&self.program.to_string_lossy())];
+        exec.extend(self.args.iter().map(|arg| systemd_quote(arg)));
+
+        let mut unit = format!(
+            "[Unit]\nDescription={}\nAfter=network-online.target\n\n[Service]\nType=simple\nExecStart={}\n",
+            self.description,
+            exec.join(" ")
+        );
+        for (key, value) in &self.envs {
+            unit.push_str(&format!(
+                "Environment={}\n",
+                systemd_quote(&format!("{key}={value}"))
+            ));
+        }
+        let log = self.log_file.display();
+        unit.push_str(&format!(
+            "StandardOutput=append:{log}\nStandardError=append:{log}\nRestart=on-failure\nRestartSec=2\n\n[Install]\nWantedBy=default.target\n"
+        ));
+        unit
+    }
+}
+
+pub(super) fn install(service: &LocalService) -> Result<()> {
+    let unit_dir = systemd_user_dir()?;
+    std::fs::create_dir_all(&unit_dir)?;
+    let unit_path = unit_path(&unit_dir, &service.name);
+    std::fs::write(&unit_path, service.systemd_unit())?;
+    info!(unit = %unit_path.display(), "installed service unit");
+
+    systemctl(["daemon-reload"])?;
+    systemctl(["enable", "--now", &unit_name(&service.name)])?;
+    Ok(())
+}
+
+pub(super) fn service_state(name: &str) -> Result<Option<ServiceState>> {
+    let unit_dir = systemd_user_dir()?;
+    if !unit_path(&unit_dir, name).exists() {
+        return Ok(None);
+    }
+    // is-active exits with non zero status for inactive units, so only stdout is relevant
+    let output = systemctl_command()
+        .arg("is-active")
+        .arg(unit_name(name))
+        .output()?;
+    Ok(Some(ServiceState::parse(&String::from_utf8_lossy(
+        &output.stdout,
+    ))))
+}
+
+pub(super) fn installed_services() -> Result<Vec<String>> {
+    let unit_dir = match systemd_user_dir() {
+        Ok(dir) if dir.exists() => dir,
+        _ => return Ok(vec![]),
+    };
+
+    let mut services: Vec<String> = std::fs::read_dir(unit_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_owned()))
+        .filter_map(|name| {
+            name.strip_suffix(UNIT_SUFFIX)
+                .filter(|name| name.starts_with(SERVICE_PREFIX))
+                .map(|name| name.to_owned())
+        })
+        .collect();
+    services.sort();
+    Ok(services)
+}
+
+pub(super) fn uninstall_service(name: &str) -> Result<()> {
+    let unit_dir = systemd_user_dir()?;
+    let unit_path = unit_path(&unit_dir, name);
+    if !unit_path.exists() {
+        debug!(service = name, "service not installed");
+        return Ok(());
+    }
+    if let Err(err) = systemctl(["disable", "--now", &unit_name(name)]) {
+        debug!(service = name, %err, "unable to disable service");
+    }
+    std::fs::remove_file(&unit_path)?;
+    systemctl(["daemon-reload"])?;
+    info!(service = name, "removed service");
+    Ok(())
+}
+
+fn unit_name(name: &str) -> String {
+    format!("{name}{UNIT_SUFFIX}")
+}
+
+fn unit_path(unit_dir: &Path, name: &str) -> PathBuf {
+    unit_dir.join(unit_name(name))
+}
+
+fn systemd_user_dir() -> Result<PathBuf> {
+    directories::BaseDirs::new()
+        .map(|dirs| dirs.config_dir().join("systemd").join("user"))
+        .ok_or_else(|| anyhow!("unable to find user config directory"))
+}
+
+fn systemctl_command() -> Command {
+    let mut cmd = Command::new("systemctl");
+    cmd.arg("--user");
+    cmd
+}
+
+fn systemctl<'a>(args: impl IntoIterator<Item = &'a str>) -> Result<()> {
+    let mut cmd = systemctl_command();
+    cmd.args(args);
+    let output = cmd
+        .output()
+        .map_err(|err| anyhow!("unable to run systemctl: {err}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "systemctl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// quote argument for systemd command lines and assignments
+fn systemd_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && !arg
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | '%' | '$'))
+    {
+        return arg.to_owned();
+    }
+    let escaped = arg
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%")
+        .replace('$', "$$");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{spu_service_name, SC_SERVICE_NAME};
+
+    #[test]
+    fn test_systemd_unit_from_command() {
+        let mut cmd = Command::new("/usr/local/bin/fluvio");
+        cmd.arg("run")
+            .arg("sc")
+            .arg("--local")
+            .arg("/home/user/.fluvio/data dir")
+            .env("RUST_LOG", "info");
+        let service =
+            LocalService::from_command(SC_SERVICE_NAME, "Fluvio SC", &cmd, "/tmp/flv_sc.log");
+
+        assert_eq!(
+            service.systemd_unit(),
+            "[Unit]\nDescription=Fluvio SC\nAfter=network-online.target\n\n\
+             [Service]\nType=simple\n\
+             ExecStart=/usr/local/bin/fluvio run sc --local \"/home/user/.fluvio/data dir\"\n\
+             Environment=RUST_LOG=info\n\
+             StandardOutput=append:/tmp/flv_sc.log\nStandardError=append:/tmp/flv_sc.log\n\
+             Restart=on-failure\nRestartSec=2\n\n\
+             [Install]\nWantedBy=default.target\n"
+        );
+    }
+
+    #[test]
+    fn test_systemd_quote() {
+        assert_eq!(systemd_quote("plain"), "plain");
+        assert_eq!(systemd_quote(""), "\"\"");
+        assert_eq!(systemd_quote("100%"), "\"100%%\"");
+        assert_eq!(systemd_quote("a\"b"), "\"a\\\"b\"");
+    }
+
+    #[test]
+    fn test_parse_state() {
+        assert_eq!(ServiceState::parse("active\n"), ServiceState::Active);
+        assert_eq!(ServiceState::parse("failed"), ServiceState::Failed);
+        assert_eq!(
+            ServiceState::parse("unknown"),
+            ServiceState::Other("unknown".to_owned())
+        );
+        assert_eq!(spu_service_name(5001), "fluvio-spu-5001");
+    }
+}
@@ -0,0 +1,357 @@
+//! Windows services
+//!
+//! Services are registered with the service control manager through `sc.exe`. The service
+//! control manager only runs programs which report their status to it, so services run
+//! `fluvio-run service-host`, which runs the SC or SPU and appends its output to the log file.
+//! A process failing with a non zero exit code is restarted two seconds later.
+//!
+//! Installing and removing services requires an elevated shell, services run as LocalSystem.
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use tracing::{debug, info};
+
+use super::{LocalService, ServiceState, SERVICE_PREFIX};
+
+const RUNNER: &str = "fluvio-run";
+const SERVICE_HOST: &str = "service-host";
+const SERVICES_KEY: &str = r"HKLM\SYSTEM\CurrentControlSet\Services";
+/// exit code of `sc.exe` for services which are not installed
+const ERROR_SERVICE_DOES_NOT_EXIST: i32 = 1060;
+const SERVICE_STOPPED: u32 = 1;
+const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+impl LocalService {
+    /// arguments of `fluvio-run`, the program is either `fluvio-run` or `fluvio run`
+    fn runner_args(&self) -> &[String] {
+        match self.args.split_first() {
+            Some((first, rest)) if first == "run" && !is_runner(&self.program) => rest,
+            _ => &self.args,
+        }
+    }
+
+    /// command line of the service, hosted by `runner`
+    fn windows_command_line(&self, runner: &Path) -> String {
+        let mut args = vec![
+            runner.to_string_lossy().into_owned(),
+            SERVICE_HOST.to_owned(),
+            "--name".to_owned(),
+            self.name.clone(),
+            "--log-file".to_owned(),
+            self.log_file.to_string_lossy().into_owned(),
+        ];
+        for (key, value) in &self.envs {
+            args.push("--env".to_owned());
+            args.push(format!("{key}={value}"));
+        }
+        args.push("--".to_owned());
+        args.extend(self.runner_args().iter().cloned());
+        args.iter()
+            .map(|arg| windows_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+pub(super) fn install(service: &LocalService) -> Result<()> {
+    let runner = find_runner(&service.program)?;
+    let command_line = service.windows_command_line(&runner);
+    let name = service.name.as_str();
+    let config = [
+        "binPath=",
+        command_line.as_str(),
+        "start=",
+        "auto",
+        "DisplayName=",
+        service.description.as_str(),
+    ];
+    if query(name)?.is_some() {
+        stop(name)?;
+        sc(["config", name].into_iter().chain(config))?;
+    } else {
+        sc(["create", name].into_iter().chain(config))?;
+    }
+    sc(["description", name, service.description.as_str()])?;
+    // restart after failures, which are reported as non zero exit code by the service host
+    sc([
+        "failure",
+        name,
+        "reset=",
+        "86400",
+        "actions=",
+        "restart/2000/restart/2000/restart/2000",
+    ])?;
+    sc(["failureflag", name, "1"])?;
+    sc(["start", name])?;
+    info!(service = name, "installed service");
+    Ok(())
+}
+
+pub(super) fn service_state(name: &str) -> Result<Option<ServiceState>> {
+    Ok(query(name)?.map(|output| parse_query(&output)))
+}
+
+pub(super) fn installed_services() -> Result<Vec<String>> {
+    let output = Command::new("reg.exe")
+        .args(["query", SERVICES_KEY, "/k", "/f", SERVICE_PREFIX])
+        .output()
+        .map_err(|err| anyhow!("unable to run reg.exe: {err}"))?;
+    // reg.exe exits with 1 when no key matches
+    if !output.status.success() {
+        return Ok(vec![]);
+    }
+    Ok(parse_service_keys(&String::from_utf8_lossy(&output.stdout)))
+}
+
+pub(super) fn uninstall_service(name: &str) -> Result<()> {
+    if query(name)?.is_none() {
+        debug!(service = name, "service not installed");
+        return Ok(());
+    }
+    stop(name)?;
+    sc(["delete", name])?;
+    info!(service = name, "removed service");
+    Ok(())
+}
+
+/// stop the service and wait until it is stopped
+fn stop(name: &str) -> Result<()> {
+    if let Err(err) = sc(["stop", name]) {
+        debug!(service = name, %err, "unable to stop service");
+    }
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    loop {
+        match query(name)? {
+            Some(output) if state_code(&output) != Some(SERVICE_STOPPED) => {
+                if Instant::now() >= deadline {
+                    return Err(anyhow!("service {name} did not stop in {STOP_TIMEOUT:?}"));
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// output of `sc.exe query`, None if the service is not installed
+fn query(name: &str) -> Result<Option<String>> {
+    let output = sc_command().args(["query", name]).output()?;
+    if output.status.code() == Some(ERROR_SERVICE_DOES_NOT_EXIST) {
+        return Ok(None);
+    }
+    if !output.status.success() {
+        return Err(sc_error(&output));
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+fn query_field<'a>(output: &'a str, name: &str) -> Option<&'a str> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == name).then_some(value.trim())
+    })
+}
+
+fn query_code(output: &str, name: &str) -> Option<u32> {
+    query_field(output, name)?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn state_code(output: &str) -> Option<u32> {
+    query_code(output, "STATE")
+}
+
+fn parse_query(output: &str) -> ServiceState {
+    let exit_code = query_code(output, "WIN32_EXIT_CODE").unwrap_or(0);
+    match state_code(output) {
+        Some(SERVICE_STOPPED) if exit_code != 0 => ServiceState::Failed,
+        // stopped, stop pending
+        Some(SERVICE_STOPPED | 3) => ServiceState::Inactive,
+        // start pending, continue pending
+        Some(2 | 5) => ServiceState::Activating,
+        Some(4) => ServiceState::Active,
+        _ => ServiceState::Other(
+            query_field(output, "STATE")
+                .unwrap_or_default()
+                .to_lowercase(),
+        ),
+    }
+}
+
+/// names of the fluvio services in the output of `reg.exe query`
+fn parse_service_keys(output: &str) -> Vec<String> {
+    let mut services: Vec<String> = output
+        .lines()
+        .filter(|line| line.starts_with("HKEY_"))
+        .filter_map(|key| key.rsplit('\\').next())
+        .filter(|name| name.starts_with(SERVICE_PREFIX))
+        .map(|name| name.trim().to_owned())
+        .collect();
+    services.sort();
+    services
+}
+
+fn is_runner(program: &Path) -> bool {
+    program.file_stem().is_some_and(|stem| stem == RUNNER)
+}
+
+/// `fluvio-run` is looked up like the fluvio CLI looks up its plugins
+fn find_runner(program: &Path) -> Result<PathBuf> {
+    if is_runner(program) {
+        return Ok(program.to_owned());
+    }
+    let extensions_dir = std::env::var_os("FLUVIO_EXTENSIONS_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("FLUVIO_DIR")
+                .map(PathBuf::from)
+                .or_else(|| {
+                    directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".fluvio"))
+                })
+                .map(|dir| dir.join("extensions"))
+        });
+    which::which_in(RUNNER, program.parent(), ".")
+        .or_else(|_| which::which(RUNNER))
+        .or_else(|_| which::which_in(RUNNER, extensions_dir, "."))
+        .map_err(|_| anyhow!("unable to find {RUNNER}, which hosts the Windows services"))
+}
+
+fn sc_command() -> Command {
+    Command::new("sc.exe")
+}
+
+fn sc<'a>(args: impl IntoIterator<Item = &'a str>) -> Result<()> {
+    let output = sc_command()
+        .args(args)
+        .output()
+        .map_err(|err| anyhow!("unable to run sc.exe: {err}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(sc_error(&output))
+    }
+}
+
+/// sc.exe reports errors on stdout
+fn sc_error(output: &Output) -> anyhow::Error {
+    anyhow!(
+        "sc.exe failed: {}",
+        String::from_utf8_lossy(&output.stdout).trim()
+    )
+}
+
+/// quote argument for Windows command lines, as split by `CommandLineToArgvW`
+fn windows_quote(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '"']) {
+        return arg.to_owned();
+    }
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        // backslashes are only escaped in front of quotes
+        if c == '"' {
+            backslashes = backslashes * 2 + 1;
+        }
+        quoted.push_str(&"\\".repeat(backslashes));
+        quoted.push(c);
+        backslashes = 0;
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::SC_SERVICE_NAME;
+
+    #[test]
+    fn test_windows_command_line() {
+        let mut cmd = Command::new(r"C:\Users\user\.fluvio\bin\fluvio.exe");
+        cmd.arg("run")
+            .arg("sc")
+            .arg("--local")
+            .arg(r"C:\Users\user\.fluvio\data dir")
+            .env("RUST_LOG", "info");
+        let service = LocalService::from_command(
+            SC_SERVICE_NAME,
+            "Fluvio SC",
+            &cmd,
+            r"C:\Users\user\.fluvio\log\flv_sc.log",
+        );
+
+        assert_eq!(
+            service.windows_command_line(Path::new(
+                r"C:\Users\user\.fluvio\extensions\fluvio-run.exe"
+            )),
+            r#"C:\Users\user\.fluvio\extensions\fluvio-run.exe service-host --name fluvio-sc --log-file C:\Users\user\.fluvio\log\flv_sc.log --env RUST_LOG=info -- sc --local "C:\Users\user\.fluvio\data dir""#
+        );
+
+        let cmd = Command::new("fluvio-run.exe");
+        let mut service = LocalService::from_command(SC_SERVICE_NAME, "Fluvio SC", &cmd, "sc.log");
+        service.args = vec!["run".to_owned()];
+        assert_eq!(service.runner_args(), ["run"]);
+    }
+
+    #[test]
+    fn test_windows_quote() {
+        assert_eq!(windows_quote("plain"), "plain");
+        assert_eq!(windows_quote(""), "\"\"");
+        assert_eq!(windows_quote(r"C:\data dir\"), r#""C:\data dir\\""#);
+        assert_eq!(windows_quote(r#"a"b"#), r#""a\"b""#);
+        assert_eq!(windows_quote(r#"a\"b"#), r#""a\\\"b""#);
+        assert_eq!(windows_quote(r"a\b c"), r#""a\b c""#);
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = |state: &str, exit_code: &str| {
+            format!(
+                "\r\nSERVICE_NAME: fluvio-sc\r\n        TYPE               : 10  WIN32_OWN_PROCESS\r\n        STATE              : {state}\r\n                                (STOPPABLE, NOT_PAUSABLE, ACCEPTS_SHUTDOWN)\r\n        WIN32_EXIT_CODE    : {exit_code}\r\n        SERVICE_EXIT_CODE  : 0  (0x0)\r\n"
+            )
+        };
+        assert_eq!(
+            parse_query(&query("4  RUNNING", "0  (0x0)")),
+            ServiceState::Active
+        );
+        assert_eq!(
+            parse_query(&query("2  START_PENDING", "0  (0x0)")),
+            ServiceState::Activating
+        );
+        assert_eq!(
+            parse_query(&query("1  STOPPED", "0  (0x0)")),
+            ServiceState::Inactive
+        );
+        assert_eq!(
+            parse_query(&query("1  STOPPED", "1066  (0x42a)")),
+            ServiceState::Failed
+        );
+        assert_eq!(
+            parse_query(&query("7  PAUSED", "0  (0x0)")),
+            ServiceState::Other("7  paused".to_owned())
+        );
+        assert_eq!(state_code(&query("3  STOP_PENDING", "0  (0x0)")), Some(3));
+    }
+
+    #[test]
+    fn test_parse_service_keys() {
+        let output = "\r\nHKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\fluvio-spu-5001\r\nHKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\fluvio-sc\r\nHKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\my-fluvio-app\r\nEnd of search: 3 match(es) found.\r\n";
+        assert_eq!(
+            parse_service_keys(output),
+            vec!["fluvio-sc".to_owned(), "fluvio-spu-5001".to_owned()]
+        );
+    }
+}
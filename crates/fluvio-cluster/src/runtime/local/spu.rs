@@ -12,6 +12,7 @@ use fluvio_types::SpuId;
 
 use crate::runtime::spu::{SpuClusterManager, SpuTarget};
 
+use super::service::{spu_service_name, uninstall_service, LocalService};
 use super::{FluvioLocalProcess, LocalRuntimeError};
 
 /// Process representing SPU
@@ -24,6 +25,10 @@ pub struct LocalSpuProcess {
     pub rust_log: String,
     pub data_dir: PathBuf,
     pub tls_policy: TlsPolicy,
+    /// install as system service instead of spawning a child process
+    pub service: bool,
+    /// port of the metrics endpoint, disabled if not set
+    pub metrics_port: Option<u16>,
 }
 
 impl FluvioLocalProcess for LocalSpuProcess {}
@@ -31,9 +36,6 @@ impl FluvioLocalProcess for LocalSpuProcess {}
 impl SpuTarget for LocalSpuProcess {
     #[instrument(skip(self))]
     fn start(&self) -> AnyResult<()> {
-        let outputs = File::create(&self.log_dir)?;
-        let errors = outputs.try_clone()?;
-
         let launcher = self.launcher.clone();
         let mut binary = {
             let base = launcher.ok_or(LocalRuntimeError::MissingFluvioRunner)?;
@@ -55,9 +57,26 @@ impl SpuTarget for LocalSpuProcess {
             .arg(format!("0.0.0.0:{}", self.spec.private_endpoint.port))
             .arg("--log-base-dir")
             .arg(&self.data_dir);
+        if let Some(port) = self.metrics_port {
+            cmd.arg("--metrics-server").arg(format!("0.0.0.0:{port}"));
+        }
         debug!("Invoking command: \"{}\"", cmd.display());
         info!("SPU<{}> cmd: {:#?}", self.id, cmd);
         info!("SPU log generated at {}", self.log_dir);
+
+        if self.service {
+            info!(cmd = %cmd.display(), "Installing SPU service");
+            return LocalService::from_command(
+                spu_service_name(self.id),
+                format!("Fluvio SPU {}", self.id),
+                cmd,
+                &self.log_dir,
+            )
+            .install();
+        }
+
+        let outputs = File::create(&self.log_dir)?;
+        let errors = outputs.try_clone()?;
         info!(cmd = %cmd.display(),"Invoking command");
         cmd.stdout(Stdio::from(outputs))
             .stderr(Stdio::from(errors))
@@ -89,6 +108,9 @@ pub struct LocalSpuProcessClusterManager {
     pub rust_log: String,
     pub data_dir: PathBuf,
     pub tls_policy: TlsPolicy,
+    pub service: bool,
+    /// metrics port of the first SPU, the others use the following ports
+    pub metrics_port: Option<u16>,
 }
 
 impl SpuClusterManager for LocalSpuProcessClusterManager {
@@ -129,10 +151,15 @@ impl SpuClusterManager for LocalSpuProcessClusterManager {
             launcher: self.launcher.clone(),
             tls_policy: self.tls_policy.clone(),
             data_dir: self.data_dir.clone(),
+            service: self.service,
+            metrics_port: self.metrics_port.map(|port| port + spu_index),
         })
     }
 
     fn terminate_spu(&self, id: SpuId) -> AnyResult<()> {
+        if self.service {
+            return uninstall_service(&spu_service_name(id));
+        }
         let kill_arg = format!("fluvio-run spu -i {id}");
         Command::new("pkill")
             .arg("-f")
@@ -21,7 +21,7 @@ use tempfile::NamedTempFile;
 use semver::Version;
 
 use fluvio::FluvioAdmin;
-use fluvio_controlplane_metadata::spg::SpuConfig;
+use fluvio_controlplane_metadata::spg::{EnvVar, SpuConfig};
 use fluvio_sc_schema::objects::CommonCreateRequest;
 use fluvio_types::defaults::TLS_CLIENT_SECRET_NAME;
 use fluvio_types::defaults::TLS_SERVER_SECRET_NAME;
@@ -312,6 +312,10 @@ pub struct ClusterConfig {
     #[builder(setter(into), default)]
     spu_config: SpuConfig,
 
+    /// Port of the Prometheus metrics endpoint of the SC and the SPUs
+    #[builder(setter(strip_option), default)]
+    metrics_port: Option<u16>,
+
     #[builder(setter(into), default = "TLS_SERVER_SECRET_NAME.to_string()")]
     tls_server_secret_name: String,
 
@@ -834,6 +838,10 @@ impl ClusterInstaller {
             install_settings.push(("scLog", Cow::Borrowed(log)));
         }
 
+        if let Some(port) = self.config.metrics_port {
+            install_settings.push(("metrics.port", Cow::Owned(port.to_string())));
+        }
+
         if let Some(authorization_config_map) = &self.config.authorization_config_map {
             install_settings.push((
                 "authorizationConfigMap",
@@ -1318,6 +1326,18 @@ impl ClusterInstaller {
             .map(|ctx| ctx.name.to_owned())
     }
 
+    /// SPU config of the group, with the metrics endpoint if enabled
+    fn spu_config(&self) -> SpuConfig {
+        let mut spu_config = self.config.spu_config.clone();
+        if let Some(port) = self.config.metrics_port {
+            spu_config.env.push(EnvVar {
+                name: "FLV_METRICS_SERVER".to_owned(),
+                value: format!("0.0.0.0:{port}"),
+            });
+        }
+        spu_config
+    }
+
     /// Provisions a SPU group for the given cluster according to internal config
     #[instrument(skip(self, fluvio))]
     async fn create_managed_spu_group(&self, fluvio: &Fluvio) -> Result<()> {
@@ -1335,7 +1355,8 @@ impl ClusterInstaller {
             let spu_spec = SpuGroupSpec {
                 replicas: self.config.spu_replicas,
                 min_id: 0,
-                spu_config: self.config.spu_config.clone(),
+                spu_config: self.spu_config(),
+                autoscale: None,
             };
 
             admin
@@ -25,6 +25,7 @@ use crate::{ClusterChecker, LocalInstallError, StartStatus, UserChartLocation, I
 use crate::charts::ChartConfig;
 use crate::check::{SysChartCheck, ClusterCheckError};
 use crate::runtime::local::{LocalSpuProcessClusterManager, ScProcess, ScMode};
+use crate::runtime::local::service::check_platform_supported;
 use crate::progress::{InstallProgressMessage, ProgressBarFactory};
 
 use super::constants::MAX_PROVISION_TIME_SEC;
@@ -184,6 +185,24 @@ pub struct LocalConfig {
 
     #[builder(default = "false")]
     save_profile: bool,
+
+    /// Whether to install SC and SPUs as system services (systemd user units on Linux,
+    /// services of the service control manager on Windows) instead of spawning them as
+    /// detached processes.
+    ///
+    /// Services are restarted by the service manager if they fail.
+    /// Only supported on Linux and Windows, installing fails on other platforms.
+    /// Defaults to `false`.
+    #[builder(default = "false")]
+    #[serde(default)]
+    install_service: bool,
+
+    /// Port of the Prometheus metrics endpoint of the SC.
+    ///
+    /// SPUs serve their metrics on the following ports, one per SPU.
+    #[builder(setter(strip_option), default)]
+    #[serde(default)]
+    metrics_port: Option<u16>,
 }
 
 impl LocalConfig {
@@ -221,6 +240,8 @@ impl LocalConfig {
             launcher: self.launcher.clone(),
             tls_policy: self.server_tls_policy.clone(),
             data_dir: self.data_dir.clone(),
+            service: self.install_service,
+            metrics_port: self.metrics_port.map(|port| port + 1),
         }
     }
 
@@ -245,6 +266,8 @@ impl LocalConfig {
             installation_type: Some(self.installation_type),
             read_only_config: Some(self.read_only_config),
             save_profile: Some(self.save_profile),
+            install_service: Some(self.install_service),
+            metrics_port: Some(self.metrics_port),
         }
     }
 }
@@ -448,6 +471,10 @@ impl LocalInstaller {
 
     #[instrument(skip(self))]
     pub async fn install_only(&self) -> Result<StartStatus> {
+        if self.config.install_service {
+            check_platform_supported()?;
+        }
+
         let pb = self.pb_factory.create()?;
 
         debug!("using log dir: {}", self.config.log_dir.display());
@@ -572,6 +599,11 @@ impl LocalInstaller {
             mode,
             private_address,
             public_address: public_address.clone(),
+            service: self.config.install_service,
+            metrics_address: self
+                .config
+                .metrics_port
+                .map(|port| format!("0.0.0.0:{port}")),
         };
 
         sc_process.start()?;
@@ -15,6 +15,12 @@ pub enum CompressionError {
     UnreachableError,
     #[error("unknown compression format: {0}")]
     UnknownCompressionFormat(String),
+    #[error("not a zstd dictionary")]
+    InvalidDictionary,
+    #[error("zstd dictionary {0} is not registered")]
+    UnknownDictionary(u32),
+    #[error("a different zstd dictionary is registered with id {0}")]
+    ConflictingDictionary(u32),
     #[error("error flushing Snap encoder: {0}")]
     #[cfg(feature = "compress")]
     SnapError(#[from] Box<IntoInnerError<FrameEncoder<Writer<BytesMut>>>>),
@@ -17,6 +17,10 @@ mod lz4;
 mod zstd;
 
 pub use error::CompressionError;
+#[cfg(feature = "zstd")]
+pub use self::zstd::{
+    register_dictionary, unregister_dictionary, compress_with_dictionary, DictionaryRegistration,
+};
 use serde::{Serialize, Deserialize};
 
 /// The compression algorithm used to compress and decompress records in fluvio batches
@@ -130,7 +134,7 @@ impl Compression {
             }
             #[cfg(feature = "zstd")]
             Compression::Zstd => {
-                let output = zstd::uncompress(src)?;
+                let output = zstd::uncompress_frame(src)?;
                 Ok(Some(output))
             }
         }
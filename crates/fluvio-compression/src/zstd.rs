@@ -1,10 +1,101 @@
+use std::collections::HashMap;
 use std::io::{Read, Write};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
 
 use bytes::{BufMut, Bytes, BytesMut};
 use zstd::{Decoder, Encoder};
+use zstd::zstd_safe::{get_dict_id_from_dict, get_dict_id_from_frame};
 
 use crate::error::CompressionError;
 
+static DICTIONARIES: OnceLock<RwLock<Dictionaries>> = OnceLock::new();
+
+/// Dictionaries known to this process. Frames only carry the id of their dictionary, so an id
+/// maps to one dictionary across topics, while each topic can only compress with the
+/// dictionaries registered for it. A dictionary is dropped once no topic uses it.
+#[derive(Default)]
+struct Dictionaries {
+    by_id: HashMap<u32, Arc<[u8]>>,
+    /// registrations of each topic, by dictionary id
+    topics: HashMap<String, HashMap<u32, usize>>,
+}
+
+impl Dictionaries {
+    fn register(
+        &mut self,
+        topic: &str,
+        id: u32,
+        dictionary: &[u8],
+    ) -> Result<(), CompressionError> {
+        match self.by_id.get(&id) {
+            Some(registered) if registered.as_ref() != dictionary => {
+                return Err(CompressionError::ConflictingDictionary(id))
+            }
+            Some(_) => {}
+            None => {
+                self.by_id.insert(id, Arc::from(dictionary));
+            }
+        }
+        *self
+            .topics
+            .entry(topic.to_owned())
+            .or_default()
+            .entry(id)
+            .or_default() += 1;
+        Ok(())
+    }
+
+    fn unregister(&mut self, topic: &str, id: u32) -> bool {
+        let Some(registrations) = self.topics.get_mut(topic) else {
+            return false;
+        };
+        let Some(count) = registrations.get_mut(&id) else {
+            return false;
+        };
+        *count -= 1;
+        if *count == 0 {
+            registrations.remove(&id);
+        }
+        if registrations.is_empty() {
+            self.topics.remove(topic);
+        }
+        if !self.topics.values().any(|ids| ids.contains_key(&id)) {
+            self.by_id.remove(&id);
+        }
+        true
+    }
+
+    fn for_topic(&self, topic: &str, id: u32) -> Option<Arc<[u8]>> {
+        self.topics
+            .get(topic)
+            .filter(|ids| ids.contains_key(&id))
+            .and_then(|_| self.by_id.get(&id).cloned())
+    }
+}
+
+/// Dictionary registered for a topic, unregistered when dropped
+#[derive(Debug)]
+pub struct DictionaryRegistration {
+    topic: String,
+    id: u32,
+}
+
+impl DictionaryRegistration {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+}
+
+impl Drop for DictionaryRegistration {
+    fn drop(&mut self) {
+        unregister_dictionary(&self.topic, self.id);
+    }
+}
+
 pub fn compress(src: &[u8]) -> Result<Bytes, CompressionError> {
     let mut encoder = Encoder::new(BytesMut::new().writer(), 1)?;
     encoder.write_all(src)?;
@@ -18,6 +109,77 @@ pub fn uncompress<T: Read>(src: T) -> Result<Vec<u8>, CompressionError> {
     Ok(buffer)
 }
 
+/// Register a trained zstd dictionary for `topic`, it stays registered until the returned
+/// registration is dropped. Frames compressed with a dictionary carry its id, they are
+/// decompressed with the registered dictionary of that id. Registering a different dictionary
+/// with the id of a registered one fails.
+pub fn register_dictionary(
+    topic: &str,
+    dictionary: &[u8],
+) -> Result<DictionaryRegistration, CompressionError> {
+    let id = get_dict_id_from_dict(dictionary)
+        .ok_or(CompressionError::InvalidDictionary)?
+        .get();
+    dictionaries()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .register(topic, id, dictionary)?;
+    Ok(DictionaryRegistration {
+        topic: topic.to_owned(),
+        id,
+    })
+}
+
+/// Release a registration of dictionary `id` for `topic`, returns false if there was none
+pub fn unregister_dictionary(topic: &str, id: u32) -> bool {
+    dictionaries()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .unregister(topic, id)
+}
+
+/// Compress with the dictionary `id` registered for `topic`
+pub fn compress_with_dictionary(
+    src: &[u8],
+    topic: &str,
+    id: u32,
+) -> Result<Bytes, CompressionError> {
+    let dictionary = dictionaries()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .for_topic(topic, id)
+        .ok_or(CompressionError::UnknownDictionary(id))?;
+    let mut encoder = Encoder::with_dictionary(BytesMut::new().writer(), 1, &dictionary)?;
+    encoder.write_all(src)?;
+    Ok(encoder.finish()?.into_inner().freeze())
+}
+
+/// Uncompress a frame, with the dictionary it was compressed with if any
+pub fn uncompress_frame(src: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    let Some(id) = get_dict_id_from_frame(src) else {
+        return uncompress(src);
+    };
+    let dictionary = dictionary(id.get())?;
+    let mut decoder = Decoder::with_dictionary(src, &dictionary)?;
+    let mut buffer: Vec<u8> = Vec::new();
+    decoder.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn dictionaries() -> &'static RwLock<Dictionaries> {
+    DICTIONARIES.get_or_init(Default::default)
+}
+
+fn dictionary(id: u32) -> Result<Arc<[u8]>, CompressionError> {
+    dictionaries()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .by_id
+        .get(&id)
+        .cloned()
+        .ok_or(CompressionError::UnknownDictionary(id))
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
@@ -34,4 +196,78 @@ mod tests {
 
         assert_eq!(uncompressed, text);
     }
+
+    #[test]
+    fn test_dictionary() {
+        let samples: Vec<Vec<u8>> = (0..1000)
+            .map(|i| {
+                format!(
+                    r#"{{"user":"user-{i}","action":"login","count":{}}}"#,
+                    i * 7
+                )
+                .into_bytes()
+            })
+            .collect();
+        let trained = zstd::dict::from_samples(&samples, 4096).expect("dictionary");
+        assert!(register_dictionary("events", b"not a dictionary").is_err());
+        let registration = register_dictionary("events", &trained).expect("register");
+        let id = registration.id();
+
+        let text = br#"{"user":"user-5000","action":"login","count":3}"#;
+        let compressed = compress_with_dictionary(text, "events", id).unwrap();
+        assert!(compressed.len() < compress(text).unwrap().len());
+        assert_eq!(uncompress_frame(&compressed).unwrap(), text);
+
+        assert!(matches!(
+            compress_with_dictionary(text, "events", id.wrapping_add(1)),
+            Err(CompressionError::UnknownDictionary(_))
+        ));
+        // dictionaries are scoped to the topics they are registered for
+        assert!(matches!(
+            compress_with_dictionary(text, "other", id),
+            Err(CompressionError::UnknownDictionary(_))
+        ));
+
+        drop(registration);
+        assert!(matches!(
+            compress_with_dictionary(text, "events", id),
+            Err(CompressionError::UnknownDictionary(_))
+        ));
+        assert!(matches!(
+            uncompress_frame(&compressed),
+            Err(CompressionError::UnknownDictionary(_))
+        ));
+    }
+
+    #[test]
+    fn test_dictionary_registrations() {
+        let dictionary = |content: &[u8]| {
+            let mut dictionaries = Dictionaries::default();
+            dictionaries.register("a", 7, content).expect("register");
+            dictionaries
+        };
+
+        let mut dictionaries = dictionary(b"first");
+        assert!(matches!(
+            dictionaries.register("b", 7, b"second"),
+            Err(CompressionError::ConflictingDictionary(7))
+        ));
+        assert!(dictionaries.for_topic("b", 7).is_none());
+
+        dictionaries
+            .register("b", 7, b"first")
+            .expect("same dictionary");
+        dictionaries.register("a", 7, b"first").expect("same topic");
+        assert!(dictionaries.unregister("a", 7));
+        assert!(dictionaries.for_topic("a", 7).is_some());
+        assert!(dictionaries.unregister("a", 7));
+        assert!(dictionaries.for_topic("a", 7).is_none());
+        assert!(!dictionaries.unregister("a", 7));
+
+        // still used by b
+        assert!(dictionaries.by_id.contains_key(&7));
+        assert!(dictionaries.unregister("b", 7));
+        assert!(dictionaries.by_id.is_empty());
+        assert!(dictionaries.topics.is_empty());
+    }
 }
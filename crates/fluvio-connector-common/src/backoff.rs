@@ -0,0 +1,25 @@
+//! Backoff between retries of requests a connector sends to an external system
+
+use std::time::Duration;
+
+/// longest wait between two attempts
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// exponential backoff starting at `base`, capped at [`MAX_BACKOFF`]
+pub fn backoff(base: Duration, attempt: u32) -> Duration {
+    base.saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let base = Duration::from_millis(500);
+        assert_eq!(backoff(base, 0), base);
+        assert_eq!(backoff(base, 3), Duration::from_secs(4));
+        assert_eq!(backoff(base, 40), MAX_BACKOFF);
+    }
+}
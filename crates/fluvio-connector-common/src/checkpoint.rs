@@ -0,0 +1,67 @@
+//! Positions of source connectors persisted across restarts
+//!
+//! Sources that read from external systems without their own notion of consumer
+//! offsets, such as files, save how far they have read after their records are
+//! acknowledged, and continue from there when restarted.
+
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::Result;
+
+/// Checkpoint stored as JSON in a local file
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    path: PathBuf,
+}
+
+impl CheckpointStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Last saved checkpoint, None if nothing was saved yet
+    pub fn load<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        match std::fs::read(&self.path) {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Replace the saved checkpoint
+    ///
+    /// The checkpoint is written to a temporary file first and then renamed,
+    /// so a crash never leaves a partially written checkpoint behind.
+    pub fn save<T: Serialize>(&self, checkpoint: &T) -> Result<()> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, serde_json::to_vec(checkpoint)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = CheckpointStore::new(dir.path().join("positions.json"));
+        assert_eq!(store.load::<BTreeMap<String, u64>>().expect("load"), None);
+
+        let positions = BTreeMap::from([("a.log".to_owned(), 42u64)]);
+        store.save(&positions).expect("save");
+        assert_eq!(store.load().expect("load"), Some(positions));
+    }
+}
@@ -24,6 +24,7 @@ pub async fn consumer_stream_from_config(
     let mut builder = ConsumerConfigExtBuilder::default();
     builder.topic(config.meta().topic());
     builder.offset_start(fluvio::Offset::end());
+    builder.application(config.meta().name());
     if let Some(consumer_id) = config.meta().consumer().and_then(|c| c.id.as_ref()) {
         builder.offset_consumer(consumer_id);
     }
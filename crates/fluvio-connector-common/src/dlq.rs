@@ -0,0 +1,126 @@
+//! Dead letter queue for records a sink is unable to deliver
+//!
+//! Rejected records are produced to a separate topic as JSON, together with the
+//! reason they were rejected, so they can be inspected and replayed later.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use fluvio::consumer::Record;
+use fluvio::metadata::topic::TopicSpec;
+use fluvio::{Fluvio, FluvioAdmin, RecordKey, TopicProducerPool};
+use fluvio_types::PartitionId;
+
+use crate::Result;
+
+/// Record rejected by a sink
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub partition: PartitionId,
+    pub offset: i64,
+    pub timestamp: i64,
+    /// reason the record was rejected
+    pub error: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub value: String,
+}
+
+impl DeadLetter {
+    pub fn new(record: &Record, error: impl Into<String>) -> Self {
+        Self {
+            partition: record.partition(),
+            offset: record.offset(),
+            timestamp: record.timestamp(),
+            error: error.into(),
+            key: record
+                .key()
+                .map(|key| String::from_utf8_lossy(key).into_owned()),
+            value: String::from_utf8_lossy(record.value()).into_owned(),
+        }
+    }
+}
+
+/// Producer of rejected records to a dead letter topic
+pub struct DeadLetterQueue {
+    topic: String,
+    producer: TopicProducerPool,
+    _fluvio: Fluvio,
+}
+
+impl DeadLetterQueue {
+    /// Connect to the dead letter `topic`, creating it if it does not exist
+    pub async fn connect(topic: impl Into<String>) -> Result<Self> {
+        let topic = topic.into();
+        let admin = FluvioAdmin::connect().await?;
+        let topics = admin.list::<TopicSpec, String>(vec![topic.clone()]).await?;
+        if !topics.iter().any(|t| t.name.eq(&topic)) {
+            admin
+                .create(
+                    topic.clone(),
+                    false,
+                    TopicSpec::new_computed(1, 1, Some(false)),
+                )
+                .await?;
+            info!(topic, "created dead letter topic");
+        }
+
+        let fluvio = Fluvio::connect().await?;
+        let producer = fluvio.topic_producer(&topic).await?;
+        Ok(Self {
+            topic,
+            producer,
+            _fluvio: fluvio,
+        })
+    }
+
+    /// Name of the dead letter topic
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Send rejected record to the dead letter topic
+    pub async fn send(&self, letter: &DeadLetter) -> Result<()> {
+        warn!(
+            partition = letter.partition,
+            offset = letter.offset,
+            error = %letter.error,
+            "sending record to dead letter queue"
+        );
+        let key = letter
+            .key
+            .as_ref()
+            .map(|key| RecordKey::from(key.as_bytes()))
+            .unwrap_or(RecordKey::NULL);
+        self.producer.send(key, serde_json::to_vec(letter)?).await?;
+        Ok(())
+    }
+
+    /// Wait until all sent records are stored
+    pub async fn flush(&self) -> Result<()> {
+        self.producer.flush().await
+    }
+}
+
+/// Send `rejected` records, with the reason of each, to the dead letter queue.
+///
+/// Without a queue, the records can not be skipped, so an error is returned for the batch.
+pub async fn reject(dlq: Option<&DeadLetterQueue>, rejected: &[(&Record, String)]) -> Result<()> {
+    if rejected.is_empty() {
+        return Ok(());
+    }
+    let Some(dlq) = dlq else {
+        let (record, error) = &rejected[0];
+        return Err(anyhow!(
+            "{} records rejected, first at partition {} offset {}: {error}",
+            rejected.len(),
+            record.partition(),
+            record.offset()
+        ));
+    };
+    for (record, error) in rejected {
+        dlq.send(&DeadLetter::new(record, error.as_str())).await?;
+    }
+    dlq.flush().await
+}
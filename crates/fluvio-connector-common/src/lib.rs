@@ -3,6 +3,11 @@ pub mod smartmodule;
 pub mod monitoring;
 pub mod consumer;
 pub mod config;
+pub mod transaction;
+pub mod dlq;
+pub mod backoff;
+pub mod checkpoint;
+pub mod logs;
 
 pub use fluvio_connector_package::render_config_str;
 pub use fluvio_connector_package::secret;
@@ -0,0 +1,202 @@
+//! Forwarding of the connector logs to the cluster
+//!
+//! [`init_logger`] prints the log events like [`crate::future::init_logger`] and also
+//! queues them, [`forward_logs`] then produces the queue to the log topic of the
+//! connector, see [`fluvio_connector_package::logs`]. Events are forwarded from the
+//! `INFO` level regardless of `RUST_LOG`, panics are forwarded as `ERROR` events.
+//! Output written directly to stdout or stderr is not forwarded.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_channel::{Receiver, Sender};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use fluvio::metadata::topic::TopicSpec;
+use fluvio::{Fluvio, FluvioAdmin, RecordKey, TopicProducerPool};
+use fluvio_connector_package::logs::{log_topic, log_topic_spec};
+
+pub use fluvio_connector_package::logs::ConnectorLog;
+
+use crate::config::ConnectorConfig;
+use crate::Result;
+
+/// events queued before they are produced, newer events are dropped when it is full
+const LOG_QUEUE_SIZE: usize = 1024;
+
+static LOG_QUEUE: OnceLock<Receiver<ConnectorLog>> = OnceLock::new();
+
+/// Initialize the logger of the connector, queueing the events for [`forward_logs`]
+pub fn init_logger() {
+    let (sender, receiver) = async_channel::bounded(LOG_QUEUE_SIZE);
+    if LOG_QUEUE.set(receiver).is_err() {
+        return;
+    }
+    let fmt = tracing_subscriber::fmt::layer().with_filter(EnvFilter::from_default_env());
+    let queue = QueueLayer { sender }.with_filter(LevelFilter::INFO);
+    if tracing_subscriber::registry()
+        .with(fmt)
+        .with(queue)
+        .try_init()
+        .is_err()
+    {
+        return;
+    }
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!(target: "panic", "{info}");
+        default_hook(info);
+    }));
+}
+
+/// Produce the queued log events to the log topic of the connector, creating it if it
+/// does not exist. The connector keeps running when the logs can not be forwarded.
+pub async fn forward_logs(config: &ConnectorConfig) {
+    let Some(queue) = LOG_QUEUE.get().cloned() else {
+        return;
+    };
+    let topic = log_topic(config.meta().name());
+    match connect(&topic).await {
+        Ok(fluvio) => {
+            fluvio_future::task::spawn(produce_logs(fluvio, topic, queue));
+        }
+        Err(err) => {
+            queue.close();
+            tracing::warn!(topic, %err, "unable to forward logs");
+        }
+    }
+}
+
+async fn connect(topic: &str) -> Result<Fluvio> {
+    let admin = FluvioAdmin::connect().await?;
+    let topics = admin
+        .list::<TopicSpec, String>(vec![topic.to_owned()])
+        .await?;
+    if !topics.iter().any(|t| t.name.eq(topic)) {
+        admin
+            .create(topic.to_owned(), false, log_topic_spec())
+            .await?;
+    }
+    Fluvio::connect().await
+}
+
+async fn produce_logs(fluvio: Fluvio, topic: String, queue: Receiver<ConnectorLog>) {
+    let producer = match fluvio.topic_producer(&topic).await {
+        Ok(producer) => producer,
+        Err(err) => {
+            queue.close();
+            tracing::warn!(topic, %err, "unable to forward logs");
+            return;
+        }
+    };
+    while let Ok(log) = queue.recv().await {
+        // the failure is logged once, logging every failure would queue more events
+        if let Err(err) = send_log(&producer, &log).await {
+            queue.close();
+            tracing::warn!(topic, %err, "stopped forwarding logs");
+            return;
+        }
+    }
+}
+
+async fn send_log(producer: &TopicProducerPool, log: &ConnectorLog) -> Result<()> {
+    producer
+        .send(RecordKey::NULL, serde_json::to_vec(log)?)
+        .await?;
+    Ok(())
+}
+
+/// Layer queueing the log events, never waiting for room in the queue
+struct QueueLayer {
+    sender: Sender<ConnectorLog>,
+}
+
+impl<S: Subscriber> Layer<S> for QueueLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let _ = self.sender.try_send(to_log(event));
+    }
+}
+
+fn to_log(event: &Event<'_>) -> ConnectorLog {
+    let mut visitor = LogVisitor::default();
+    event.record(&mut visitor);
+    let metadata = event.metadata();
+    ConnectorLog {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default(),
+        level: metadata.level().to_string(),
+        target: metadata.target().to_owned(),
+        message: visitor.message,
+        fields: visitor.fields,
+    }
+}
+
+#[derive(Default)]
+struct LogVisitor {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl LogVisitor {
+    fn insert(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.fields.insert(field.name().to_owned(), value);
+        }
+    }
+}
+
+impl Visit for LogVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.insert(field, format!("{value:?}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_layer() {
+        let (sender, receiver) = async_channel::bounded(2);
+        let subscriber = tracing_subscriber::registry().with(QueueLayer { sender });
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(records = 3, topic = "orders", "produced");
+            tracing::warn!("slow");
+            tracing::error!("dropped, the queue is full");
+        });
+
+        let log = receiver.try_recv().expect("first event");
+        assert_eq!(log.level, "INFO");
+        assert_eq!(log.message, "produced");
+        assert_eq!(
+            log.fields,
+            BTreeMap::from([
+                ("records".to_owned(), "3".to_owned()),
+                ("topic".to_owned(), "orders".to_owned()),
+            ])
+        );
+        assert!(log.timestamp > 0);
+
+        let log = receiver.try_recv().expect("second event");
+        assert_eq!(log.level, "WARN");
+        assert_eq!(log.message, "slow");
+        assert!(log.fields.is_empty());
+        assert!(receiver.try_recv().is_err());
+    }
+}
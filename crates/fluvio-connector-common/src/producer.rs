@@ -1,4 +1,5 @@
 use fluvio::{TopicProducerPool, Fluvio, FluvioConfig, TopicProducerConfigBuilder};
+use fluvio::lineage::Lineage;
 use crate::{config::ConnectorConfig, Result};
 
 use crate::{ensure_topic_exists, smartmodule::smartmodule_chain_from_config};
@@ -28,11 +29,33 @@ pub async fn producer_from_config(config: &ConnectorConfig) -> Result<(Fluvio, T
         };
     };
 
-    let producer_config = config_builder.build()?;
-    let producer = fluvio
+    let transforms = config.transforms();
+    let lineage = Lineage {
+        connector: Some(config.meta().name().to_owned()),
+        transforms: (!transforms.is_empty())
+            .then(|| serde_json::to_vec(&transforms))
+            .transpose()?
+            .map(|chain| Lineage::transforms_hash(&chain)),
+        source: None,
+    };
+    config_builder = config_builder.headers(lineage.to_headers());
+
+    let producer_config = config_builder.clone().build()?;
+    let mut producer = fluvio
         .topic_producer_with_config(config.meta().topic(), producer_config)
         .await?;
 
+    // records failing a transform are produced with the lineage of the connector
+    for dead_letter in transforms
+        .iter()
+        .filter_map(|step| step.dead_letter.as_ref())
+    {
+        let dead_letter_producer = fluvio
+            .topic_producer_with_config(&dead_letter.topic, config_builder.clone().build()?)
+            .await?;
+        producer = producer.with_dead_letter_producer(&dead_letter.topic, dead_letter_producer);
+    }
+
     if let Some(chain) = smartmodule_chain_from_config(config).await? {
         Ok((fluvio, producer.with_chain(chain).await?))
     } else {
@@ -42,16 +42,24 @@ pub fn smartmodule_vec_from_config(config: &ConnectorConfig) -> Option<Vec<Smart
     Some(
         transforms
             .iter()
-            .map(|s| SmartModuleInvocation {
-                wasm: fluvio::SmartModuleInvocationWasm::Predefined(s.uses.clone()),
-                kind: SmartModuleKind::Generic(Default::default()),
-                params: SmartModuleExtraParams::new(
-                    s.with
-                        .iter()
-                        .map(|(k, v)| (k.clone(), v.clone().into()))
-                        .collect::<std::collections::BTreeMap<String, String>>(),
-                    s.lookback.map(Into::into),
-                ),
+            .map(|s| {
+                let invocation = SmartModuleInvocation {
+                    wasm: fluvio::SmartModuleInvocationWasm::Predefined(s.uses.clone()),
+                    kind: SmartModuleKind::Generic(Default::default()),
+                    params: SmartModuleExtraParams::new(
+                        s.with
+                            .iter()
+                            .map(|(k, v)| (k.clone(), v.clone().into()))
+                            .collect::<std::collections::BTreeMap<String, String>>(),
+                        s.lookback.map(Into::into),
+                    ),
+                };
+                match &s.dead_letter {
+                    Some(dead_letter) => {
+                        invocation.with_dead_letter_topic(dead_letter.topic.clone())
+                    }
+                    None => invocation,
+                }
             })
             .collect(),
     )
@@ -63,7 +71,7 @@ mod tests {
 
     use fluvio::SmartModuleInvocationWasm;
     use fluvio_connector_package::config::ConnectorConfigV1;
-    use fluvio_smartengine::transformation::{TransformationStep, Lookback};
+    use fluvio_smartengine::transformation::{DeadLetterConfig, TransformationStep, Lookback};
 
     use super::*;
 
@@ -101,5 +109,27 @@ mod tests {
             inv.params.lookback().unwrap().age,
             Some(Duration::from_secs(10))
         );
+        assert!(inv.dead_letter_topic().is_none());
+    }
+
+    #[test]
+    fn test_config_to_vec_with_dead_letter() {
+        //given
+        let config = ConnectorConfig::V0_1_0(ConnectorConfigV1 {
+            meta: Default::default(),
+            transforms: vec![TransformationStep {
+                uses: "local/sm@0.0.0".to_string(),
+                dead_letter: Some(DeadLetterConfig {
+                    topic: "sm-failures".to_string(),
+                }),
+                ..Default::default()
+            }],
+        });
+
+        //when
+        let inv = smartmodule_vec_from_config(&config).unwrap().remove(0);
+
+        //then
+        assert_eq!(inv.dead_letter_topic(), Some("sm-failures"));
     }
 }
@@ -0,0 +1,336 @@
+//! Effectively-once delivery for sinks supporting transactions
+//!
+//! Records are written to the sink inside a transaction together with the offsets
+//! they were consumed from. Commit happens in two phases: the sink first prepares
+//! the transaction with the offsets of every partition written, then commits it.
+//! Consumer offsets in Fluvio are only updated after the sink commit succeeded.
+//!
+//! Offsets stored in the sink are authoritative. If the connector stops between the
+//! sink commit and the Fluvio offset update, records are consumed again on restart and
+//! skipped by [`TransactionalWriter`] because they are not newer than the sink offsets.
+//! On a failed write the transaction is aborted and the error returned; the connector
+//! should then restart from [`TransactionalWriter::resume_offset`].
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use fluvio::Offset;
+use fluvio::consumer::ConsumerStream;
+use fluvio::dataplane::link::ErrorCode;
+use fluvio_types::PartitionId;
+use tracing::{debug, warn};
+
+use crate::Result;
+
+const DEFAULT_MAX_RECORDS: usize = 1000;
+const DEFAULT_MAX_DURATION: Duration = Duration::from_secs(5);
+
+/// Offset of the last record written, per partition
+pub type PartitionOffsets = BTreeMap<PartitionId, i64>;
+
+/// Sink able to write records and consumed offsets atomically
+#[async_trait]
+pub trait TransactionalSink<I: Send + 'static>: Send {
+    type Transaction: Send;
+
+    /// Start a new transaction
+    async fn begin(&mut self) -> Result<Self::Transaction>;
+
+    /// Write item as part of the transaction
+    async fn write(&mut self, tx: &mut Self::Transaction, item: I) -> Result<()>;
+
+    /// First phase: store `offsets` in the transaction and make it ready to commit
+    async fn prepare(
+        &mut self,
+        tx: &mut Self::Transaction,
+        offsets: &PartitionOffsets,
+    ) -> Result<()>;
+
+    /// Second phase: make the transaction visible
+    async fn commit(&mut self, tx: Self::Transaction) -> Result<()>;
+
+    /// Discard the transaction
+    async fn abort(&mut self, tx: Self::Transaction) -> Result<()>;
+
+    /// Offsets stored by the last committed transaction
+    async fn committed_offsets(&mut self) -> Result<PartitionOffsets>;
+}
+
+/// Limits of a single transaction
+#[derive(Debug, Clone)]
+pub struct TransactionConfig {
+    /// commit after this many records
+    pub max_records: usize,
+    /// commit when the transaction is open for longer than this
+    pub max_duration: Duration,
+}
+
+impl Default for TransactionConfig {
+    fn default() -> Self {
+        Self {
+            max_records: DEFAULT_MAX_RECORDS,
+            max_duration: DEFAULT_MAX_DURATION,
+        }
+    }
+}
+
+struct OpenTransaction<T> {
+    tx: T,
+    started: Instant,
+    records: usize,
+    offsets: PartitionOffsets,
+}
+
+/// Coordinates transactions of a [`TransactionalSink`] with consumed offsets
+pub struct TransactionalWriter<S, I>
+where
+    S: TransactionalSink<I>,
+    I: Send + 'static,
+{
+    sink: S,
+    config: TransactionConfig,
+    committed: PartitionOffsets,
+    open: Option<OpenTransaction<S::Transaction>>,
+}
+
+impl<S, I> TransactionalWriter<S, I>
+where
+    S: TransactionalSink<I>,
+    I: Send + 'static,
+{
+    /// Create writer resuming from the offsets committed in the sink
+    pub async fn new(mut sink: S, config: TransactionConfig) -> Result<Self> {
+        let committed = sink.committed_offsets().await?;
+        debug!(?committed, "loaded offsets committed in sink");
+        Ok(Self {
+            sink,
+            config,
+            committed,
+            open: None,
+        })
+    }
+
+    /// Offsets stored by the last committed transaction
+    pub fn committed_offsets(&self) -> &PartitionOffsets {
+        &self.committed
+    }
+
+    /// Offset to start consuming `partition` from, None if nothing was committed yet
+    pub fn resume_offset(&self, partition: PartitionId) -> Result<Option<Offset>> {
+        self.committed
+            .get(&partition)
+            .map(|offset| Offset::absolute(offset + 1))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Write item consumed from `offset` of `partition`
+    ///
+    /// Returns false if the record was already committed to the sink and was skipped.
+    pub async fn write(&mut self, partition: PartitionId, offset: i64, item: I) -> Result<bool> {
+        if self.is_committed(partition, offset) {
+            debug!(partition, offset, "skipping record already in sink");
+            return Ok(false);
+        }
+
+        let mut open = match self.open.take() {
+            Some(open) => open,
+            None => OpenTransaction {
+                tx: self.sink.begin().await?,
+                started: Instant::now(),
+                records: 0,
+                offsets: PartitionOffsets::new(),
+            },
+        };
+        if let Err(err) = self.sink.write(&mut open.tx, item).await {
+            self.abort_transaction(open).await;
+            return Err(err);
+        }
+        open.records += 1;
+        open.offsets.insert(partition, offset);
+        self.open = Some(open);
+        Ok(true)
+    }
+
+    /// True if the open transaction reached its size or duration limit
+    pub fn should_commit(&self) -> bool {
+        self.open.as_ref().is_some_and(|open| {
+            open.records >= self.config.max_records
+                || open.started.elapsed() >= self.config.max_duration
+        })
+    }
+
+    /// Commit the open transaction in the sink
+    ///
+    /// Returns the offsets committed, None if there was no open transaction.
+    pub async fn commit(&mut self) -> Result<Option<PartitionOffsets>> {
+        let Some(mut open) = self.open.take() else {
+            return Ok(None);
+        };
+
+        let mut offsets = self.committed.clone();
+        offsets.extend(open.offsets.iter().map(|(p, o)| (*p, *o)));
+
+        if let Err(err) = self.sink.prepare(&mut open.tx, &offsets).await {
+            self.abort_transaction(open).await;
+            return Err(err);
+        }
+        // once commit is attempted the outcome is unknown on error, so no abort
+        self.sink.commit(open.tx).await?;
+        debug!(records = open.records, ?offsets, "committed transaction");
+        self.committed = offsets.clone();
+        Ok(Some(offsets))
+    }
+
+    /// Commit the open transaction, then the offsets of `stream` in Fluvio
+    pub async fn commit_stream(&mut self, stream: &mut impl ConsumerStream) -> Result<()> {
+        if self.commit().await?.is_none() {
+            return Ok(());
+        }
+        stream.offset_commit().map_err(offset_error)?;
+        stream.offset_flush().await.map_err(offset_error)?;
+        Ok(())
+    }
+
+    /// Discard the open transaction
+    pub async fn abort(&mut self) -> Result<()> {
+        if let Some(open) = self.open.take() {
+            self.sink.abort(open.tx).await?;
+        }
+        Ok(())
+    }
+
+    /// Return the sink, the open transaction must be committed or aborted first
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    fn is_committed(&self, partition: PartitionId, offset: i64) -> bool {
+        self.committed
+            .get(&partition)
+            .is_some_and(|committed| offset <= *committed)
+    }
+
+    async fn abort_transaction(&mut self, open: OpenTransaction<S::Transaction>) {
+        if let Err(err) = self.sink.abort(open.tx).await {
+            warn!(%err, "unable to abort transaction");
+        }
+    }
+}
+
+fn offset_error(err: ErrorCode) -> anyhow::Error {
+    anyhow::anyhow!("unable to commit consumer offsets: {err}")
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_future::task::run_block_on;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MemorySink {
+        rows: Vec<String>,
+        offsets: PartitionOffsets,
+        fail_on: Option<String>,
+        aborted: usize,
+    }
+
+    #[async_trait]
+    impl TransactionalSink<String> for MemorySink {
+        type Transaction = (Vec<String>, PartitionOffsets);
+
+        async fn begin(&mut self) -> Result<Self::Transaction> {
+            Ok(Default::default())
+        }
+
+        async fn write(&mut self, tx: &mut Self::Transaction, item: String) -> Result<()> {
+            if self.fail_on.as_ref() == Some(&item) {
+                anyhow::bail!("write failed");
+            }
+            tx.0.push(item);
+            Ok(())
+        }
+
+        async fn prepare(
+            &mut self,
+            tx: &mut Self::Transaction,
+            offsets: &PartitionOffsets,
+        ) -> Result<()> {
+            tx.1 = offsets.clone();
+            Ok(())
+        }
+
+        async fn commit(&mut self, tx: Self::Transaction) -> Result<()> {
+            self.rows.extend(tx.0);
+            self.offsets = tx.1;
+            Ok(())
+        }
+
+        async fn abort(&mut self, _tx: Self::Transaction) -> Result<()> {
+            self.aborted += 1;
+            Ok(())
+        }
+
+        async fn committed_offsets(&mut self) -> Result<PartitionOffsets> {
+            Ok(self.offsets.clone())
+        }
+    }
+
+    #[test]
+    fn test_replayed_records_are_skipped() {
+        run_block_on(async {
+            let mut writer = TransactionalWriter::new(MemorySink::default(), Default::default())
+                .await
+                .expect("writer");
+            assert!(writer.write(0, 0, "a".to_owned()).await.unwrap());
+            assert!(writer.write(1, 5, "b".to_owned()).await.unwrap());
+            let offsets = writer.commit().await.unwrap().expect("committed");
+            assert_eq!(offsets, PartitionOffsets::from([(0, 0), (1, 5)]));
+
+            // restart, consumer offsets were not flushed so records are consumed again
+            let mut writer = TransactionalWriter::new(writer.into_inner(), Default::default())
+                .await
+                .expect("writer");
+            assert_eq!(
+                writer.resume_offset(1).unwrap(),
+                Some(Offset::absolute(6).unwrap())
+            );
+            assert!(!writer.write(0, 0, "a".to_owned()).await.unwrap());
+            assert!(!writer.write(1, 5, "b".to_owned()).await.unwrap());
+            assert!(writer.write(0, 1, "c".to_owned()).await.unwrap());
+            writer.commit().await.unwrap();
+
+            let sink = writer.into_inner();
+            assert_eq!(sink.rows, vec!["a", "b", "c"]);
+            assert_eq!(sink.offsets, PartitionOffsets::from([(0, 1), (1, 5)]));
+        });
+    }
+
+    #[test]
+    fn test_failed_write_aborts_transaction() {
+        run_block_on(async {
+            let sink = MemorySink {
+                fail_on: Some("bad".to_owned()),
+                ..Default::default()
+            };
+            let config = TransactionConfig {
+                max_records: 2,
+                ..Default::default()
+            };
+            let mut writer = TransactionalWriter::new(sink, config)
+                .await
+                .expect("writer");
+            writer.write(0, 0, "a".to_owned()).await.unwrap();
+            assert!(!writer.should_commit());
+            assert!(writer.write(0, 1, "bad".to_owned()).await.is_err());
+            assert_eq!(writer.commit().await.unwrap(), None);
+
+            let sink = writer.into_inner();
+            assert_eq!(sink.aborted, 1);
+            assert!(sink.rows.is_empty());
+            assert!(sink.offsets.is_empty());
+        });
+    }
+}
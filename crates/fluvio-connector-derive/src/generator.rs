@@ -22,6 +22,7 @@ fn generate_source(func: &ConnectorFn) -> TokenStream {
             #init_and_parse_config
 
             ::fluvio_connector_common::future::run_block_on(async {
+                ::fluvio_connector_common::logs::forward_logs(&common_config).await;
                 let (fluvio, producer) = ::fluvio_connector_common::producer::producer_from_config(&common_config).await?;
 
                 let metrics = ::std::sync::Arc::new(::fluvio_connector_common::monitoring::ConnectorMetrics::new(fluvio.metrics()));
@@ -48,6 +49,7 @@ fn generate_sink(func: &ConnectorFn) -> TokenStream {
             #init_and_parse_config
 
             ::fluvio_connector_common::future::run_block_on(async {
+                ::fluvio_connector_common::logs::forward_logs(&common_config).await;
                 let (fluvio, stream) = ::fluvio_connector_common::consumer::consumer_stream_from_config(&common_config).await?;
 
                 let metrics = ::std::sync::Arc::new(::fluvio_connector_common::monitoring::ConnectorMetrics::new(fluvio.metrics()));
@@ -94,7 +96,7 @@ fn init_and_parse_config(config_type_path: &Path) -> TokenStream {
             }
         }
 
-        ::fluvio_connector_common::future::init_logger();
+        ::fluvio_connector_common::logs::init_logger();
 
         let opts = ConnectorOpt::parse();
 
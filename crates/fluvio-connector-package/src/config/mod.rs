@@ -719,6 +719,7 @@ mod tests {
                     ),
                     ("param".to_string(), "param_value".into()),
                 ]),
+                dead_letter: None,
             }],
         });
 
@@ -749,13 +750,16 @@ mod tests {
                         replication: Some(2),
                         ignore_rack_assignment: Some(true),
                         maps: None,
+                        ephemeral: None,
                     },
                     retention: RetentionConfig {
                         time: Some(Duration::from_secs(120)),
                         segment_size: Some(bytesize::ByteSize(2000)),
+                        ..Default::default()
                     },
                     compression: CompressionConfig {
                         type_: CompressionAlgorithm::Lz4,
+                        ..Default::default()
                     },
                     deduplication: Some(Deduplication {
                         bounds: Bounds {
@@ -770,6 +774,9 @@ mod tests {
                             },
                         },
                     }),
+                    validation: None,
+                    dedup_window: None,
+                    storage: None,
                 },
                 version: "0.1.0".to_string(),
                 producer: Some(ProducerParameters {
@@ -801,6 +808,7 @@ mod tests {
                     ),
                     ("param".to_string(), "param_value".into()),
                 ]),
+                dead_letter: None,
             }],
         });
 
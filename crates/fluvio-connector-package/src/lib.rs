@@ -1,6 +1,7 @@
 pub mod metadata;
 pub mod config;
 pub mod secret;
+pub mod logs;
 mod render;
 
 pub use render::render_config_str;
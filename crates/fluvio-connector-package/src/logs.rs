@@ -0,0 +1,79 @@
+//! Connector logs kept in the cluster
+//!
+//! Connectors produce their log events to a system topic named after them, so the logs
+//! of a connector read the same wherever it is deployed.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use fluvio_controlplane_metadata::topic::{
+    CleanupPolicy, SegmentBasedPolicy, TopicSpec, TopicStorageConfig,
+};
+
+pub const LOG_TOPIC_PREFIX: &str = "connector-logs-";
+/// how long the log events of a connector are kept
+pub const LOG_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+const LOG_SEGMENT_SIZE: u32 = 10 * 1024 * 1024;
+const LOG_MAX_PARTITION_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Name of the topic the logs of connector `name` are produced to
+pub fn log_topic(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{LOG_TOPIC_PREFIX}{name}")
+}
+
+/// System topic for connector logs, with a retention of [`LOG_RETENTION`]
+pub fn log_topic_spec() -> TopicSpec {
+    let mut spec = TopicSpec::new_computed(1, 1, Some(false));
+    spec.set_system(true);
+    spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+        time_in_seconds: LOG_RETENTION.as_secs() as u32,
+    }));
+    spec.set_storage(TopicStorageConfig {
+        segment_size: Some(LOG_SEGMENT_SIZE),
+        max_partition_size: Some(LOG_MAX_PARTITION_SIZE),
+        ..Default::default()
+    });
+    spec
+}
+
+/// Log event of a connector, produced as JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectorLog {
+    /// milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_topic() {
+        assert_eq!(log_topic("my-http-source"), "connector-logs-my-http-source");
+        assert_eq!(log_topic("My_Sink.1"), "connector-logs-my-sink-1");
+    }
+
+    #[test]
+    fn test_log_topic_spec() {
+        let spec = log_topic_spec();
+        assert!(spec.is_system());
+        assert_eq!(spec.retention_secs(), 24 * 60 * 60);
+    }
+}
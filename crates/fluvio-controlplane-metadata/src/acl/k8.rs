@@ -0,0 +1,24 @@
+use fluvio_stream_model::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::AclSpec;
+use super::AclStatus;
+
+const ACL_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "Acl",
+        plural: "acls",
+        singular: "acl",
+    },
+};
+
+impl Spec for AclSpec {
+    type Header = DefaultHeader;
+    type Status = AclStatus;
+    fn metadata() -> &'static Crd {
+        &ACL_API
+    }
+}
+
+impl Status for AclStatus {}
@@ -0,0 +1,205 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// principal matching every client
+pub const ANY_PRINCIPAL: &str = "*";
+
+/// Permissions of a principal on a resource
+#[derive(Debug, Clone, PartialEq, Eq, Default, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AclSpec {
+    /// common name of the client certificate or subject of the token, `*` for any client
+    pub principal: String,
+    pub resource: AclResource,
+    pub permissions: Vec<AclPermission>,
+}
+
+impl AclSpec {
+    pub fn new(
+        principal: impl Into<String>,
+        resource: AclResource,
+        permissions: Vec<AclPermission>,
+    ) -> Self {
+        Self {
+            principal: principal.into(),
+            resource,
+            permissions,
+        }
+    }
+
+    pub fn applies_to(&self, principal: &str) -> bool {
+        self.principal == ANY_PRINCIPAL || self.principal == principal
+    }
+
+    /// true if the entry grants `permission` on `topic` to `principal`.
+    /// Cluster entries cover every topic.
+    pub fn allows_topic(&self, principal: &str, topic: &str, permission: AclPermission) -> bool {
+        self.applies_to(principal) && self.resource.covers_topic(topic) && self.grants(permission)
+    }
+
+    /// true if the entry grants `permission` on the cluster to `principal`
+    pub fn allows_cluster(&self, principal: &str, permission: AclPermission) -> bool {
+        self.applies_to(principal)
+            && self.resource == AclResource::Cluster
+            && self.grants(permission)
+    }
+
+    pub fn grants(&self, permission: AclPermission) -> bool {
+        self.permissions.iter().any(|it| it.implies(permission))
+    }
+}
+
+impl fmt::Display for AclSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Acl: {} on {}", self.principal, self.resource)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum AclResource {
+    /// admin objects such as SPUs and SmartModules, and every topic
+    #[default]
+    #[fluvio(tag = 0)]
+    Cluster,
+    /// topics matching the pattern, the exact name or a prefix followed by `*`
+    #[fluvio(tag = 1)]
+    Topic { pattern: String },
+}
+
+impl AclResource {
+    pub fn topic(pattern: impl Into<String>) -> Self {
+        Self::Topic {
+            pattern: pattern.into(),
+        }
+    }
+
+    pub fn covers_topic(&self, topic: &str) -> bool {
+        match self {
+            Self::Cluster => true,
+            Self::Topic { pattern } => match pattern.strip_suffix('*') {
+                Some(prefix) => topic.starts_with(prefix),
+                None => pattern == topic,
+            },
+        }
+    }
+
+    /// a pattern may only have a trailing `*`
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Cluster => Ok(()),
+            Self::Topic { pattern } if pattern.is_empty() => {
+                Err("topic pattern is empty".to_owned())
+            }
+            Self::Topic { pattern } if pattern.trim_end_matches('*').contains('*') => {
+                Err(format!("topic pattern '{pattern}' may only end with '*'"))
+            }
+            Self::Topic { .. } => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for AclResource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Cluster => write!(f, "cluster"),
+            Self::Topic { pattern } => write!(f, "topic:{pattern}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum AclPermission {
+    #[default]
+    #[fluvio(tag = 0)]
+    Produce,
+    #[fluvio(tag = 1)]
+    Consume,
+    /// create, update and delete the resource, implies produce and consume
+    #[fluvio(tag = 2)]
+    Admin,
+}
+
+impl AclPermission {
+    pub fn implies(&self, other: Self) -> bool {
+        *self == Self::Admin || *self == other
+    }
+}
+
+impl fmt::Display for AclPermission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Produce => write!(f, "produce"),
+            Self::Consume => write!(f, "consume"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl std::str::FromStr for AclPermission {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "produce" => Ok(Self::Produce),
+            "consume" => Ok(Self::Consume),
+            "admin" => Ok(Self::Admin),
+            _ => Err(format!(
+                "invalid permission '{value}', expected produce, consume or admin"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_acl_allows_topic() {
+        let acl = AclSpec::new(
+            "app",
+            AclResource::topic("orders-*"),
+            vec![AclPermission::Produce],
+        );
+        assert!(acl.allows_topic("app", "orders-eu", AclPermission::Produce));
+        assert!(!acl.allows_topic("app", "orders-eu", AclPermission::Consume));
+        assert!(!acl.allows_topic("app", "payments", AclPermission::Produce));
+        assert!(!acl.allows_topic("other", "orders-eu", AclPermission::Produce));
+        assert!(!acl.allows_cluster("app", AclPermission::Produce));
+
+        let admin = AclSpec::new(
+            ANY_PRINCIPAL,
+            AclResource::Cluster,
+            vec![AclPermission::Admin],
+        );
+        assert!(admin.allows_topic("anyone", "payments", AclPermission::Consume));
+        assert!(admin.allows_cluster("anyone", AclPermission::Admin));
+
+        let exact = AclResource::topic("orders");
+        assert!(exact.covers_topic("orders"));
+        assert!(!exact.covers_topic("orders-eu"));
+    }
+
+    #[test]
+    fn test_validate_resource() {
+        assert!(AclResource::topic("orders*").validate().is_ok());
+        assert!(AclResource::topic("").validate().is_err());
+        assert!(AclResource::topic("or*ders").validate().is_err());
+    }
+}
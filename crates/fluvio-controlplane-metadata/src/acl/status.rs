@@ -0,0 +1,39 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Entries are checked when they are created, the status only records the outcome
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AclStatus {
+    pub resolution: AclResolution,
+
+    /// Reason for Status resolution (if applies)
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for AclStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AclResolution {
+    #[default]
+    #[fluvio(tag = 0)]
+    Active,
+}
+
+impl fmt::Display for AclResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Active => write!(f, "Active"),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//!
+//! # Alert Expressions
+//!
+//! An expression compares a cluster metric with a threshold:
+//!
+//! ```text
+//! offline_partitions > 0
+//! replica_lag{topic="orders"} >= 1000
+//! ```
+//!
+//! Per partition metrics are reduced to their maximum (`replica_lag`, `partition_size`)
+//! or counted (`offline_partitions`, `under_replicated_partitions`) over the partitions
+//! matching the `topic` label, if any.
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(thiserror::Error, Debug, Clone, Eq, PartialEq)]
+#[error("invalid alert expression: {0}")]
+pub struct AlertExprError(String);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AlertMetric {
+    /// partitions without an online leader
+    OfflinePartitions,
+    /// SPUs not online
+    OfflineSpus,
+    /// partitions with fewer in-sync replicas than the replication factor
+    UnderReplicatedPartitions,
+    /// largest number of records a follower is behind its leader
+    ReplicaLag,
+    /// largest partition size in bytes
+    PartitionSize,
+}
+
+impl AlertMetric {
+    const ALL: [AlertMetric; 5] = [
+        Self::OfflinePartitions,
+        Self::OfflineSpus,
+        Self::UnderReplicatedPartitions,
+        Self::ReplicaLag,
+        Self::PartitionSize,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::OfflinePartitions => "offline_partitions",
+            Self::OfflineSpus => "offline_spus",
+            Self::UnderReplicatedPartitions => "under_replicated_partitions",
+            Self::ReplicaLag => "replica_lag",
+            Self::PartitionSize => "partition_size",
+        }
+    }
+
+    /// metric is computed from partitions and can be filtered by topic
+    pub fn is_partition_metric(&self) -> bool {
+        !matches!(self, Self::OfflineSpus)
+    }
+}
+
+impl fmt::Display for AlertMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AlertOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl AlertOp {
+    pub fn compare(&self, value: i64, threshold: i64) -> bool {
+        match self {
+            Self::Gt => value > threshold,
+            Self::Ge => value >= threshold,
+            Self::Lt => value < threshold,
+            Self::Le => value <= threshold,
+            Self::Eq => value == threshold,
+            Self::Ne => value != threshold,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+        }
+    }
+}
+
+/// parsed form of [`super::AlertRuleSpec::expression`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AlertExpr {
+    pub metric: AlertMetric,
+    pub topic: Option<String>,
+    pub op: AlertOp,
+    pub threshold: i64,
+}
+
+impl AlertExpr {
+    /// true if the metric value satisfies the condition
+    pub fn is_met(&self, value: i64) -> bool {
+        self.op.compare(value, self.threshold)
+    }
+}
+
+impl fmt::Display for AlertExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.metric)?;
+        if let Some(topic) = &self.topic {
+            write!(f, "{{topic=\"{topic}\"}}")?;
+        }
+        write!(f, " {} {}", self.op.symbol(), self.threshold)
+    }
+}
+
+impl FromStr for AlertExpr {
+    type Err = AlertExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let name_end = s
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(s.len());
+        let (name, rest) = s.split_at(name_end);
+        let metric = AlertMetric::ALL
+            .into_iter()
+            .find(|metric| metric.name() == name)
+            .ok_or_else(|| AlertExprError(format!("unknown metric \"{name}\"")))?;
+
+        let mut rest = rest.trim_start();
+        let mut topic = None;
+        if let Some(labels) = rest.strip_prefix('{') {
+            let (labels, after) = labels
+                .split_once('}')
+                .ok_or_else(|| AlertExprError("unterminated label set".to_owned()))?;
+            for label in labels.split(',').map(str::trim).filter(|l| !l.is_empty()) {
+                let (key, value) = label
+                    .split_once('=')
+                    .ok_or_else(|| AlertExprError(format!("invalid label \"{label}\"")))?;
+                let value = value
+                    .trim()
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .ok_or_else(|| AlertExprError(format!("label \"{label}\" must be quoted")))?;
+                match key.trim() {
+                    "topic" if metric.is_partition_metric() => topic = Some(value.to_owned()),
+                    other => {
+                        return Err(AlertExprError(format!(
+                            "label \"{other}\" is not supported by {metric}"
+                        )))
+                    }
+                }
+            }
+            rest = after.trim_start();
+        }
+
+        // two character operators first so `>=` is not read as `>`
+        let (op, threshold) = [
+            (">=", AlertOp::Ge),
+            ("<=", AlertOp::Le),
+            ("==", AlertOp::Eq),
+            ("!=", AlertOp::Ne),
+            (">", AlertOp::Gt),
+            ("<", AlertOp::Lt),
+        ]
+        .into_iter()
+        .find_map(|(symbol, op)| rest.strip_prefix(symbol).map(|t| (op, t.trim())))
+        .ok_or_else(|| AlertExprError(format!("expected comparison after {metric}")))?;
+
+        let threshold = threshold
+            .parse()
+            .map_err(|_| AlertExprError(format!("invalid threshold \"{threshold}\"")))?;
+
+        Ok(Self {
+            metric,
+            topic,
+            op,
+            threshold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_expression() {
+        let expr: AlertExpr = "offline_partitions > 0".parse().expect("parse");
+        assert_eq!(expr.metric, AlertMetric::OfflinePartitions);
+        assert_eq!(expr.topic, None);
+        assert_eq!(expr.op, AlertOp::Gt);
+        assert_eq!(expr.threshold, 0);
+        assert!(expr.is_met(1));
+        assert!(!expr.is_met(0));
+
+        let expr: AlertExpr = r#" replica_lag{topic="orders"}>=1000 "#.parse().expect("parse");
+        assert_eq!(expr.metric, AlertMetric::ReplicaLag);
+        assert_eq!(expr.topic.as_deref(), Some("orders"));
+        assert_eq!(expr.op, AlertOp::Ge);
+        assert_eq!(expr.threshold, 1000);
+        assert_eq!(expr.to_string(), r#"replica_lag{topic="orders"} >= 1000"#);
+    }
+
+    #[test]
+    fn test_invalid_expression() {
+        assert!("cpu > 1".parse::<AlertExpr>().is_err());
+        assert!("offline_spus{topic=\"a\"} > 1"
+            .parse::<AlertExpr>()
+            .is_err());
+        assert!("replica_lag{topic=a} > 1".parse::<AlertExpr>().is_err());
+        assert!("replica_lag{topic=\"a\" > 1".parse::<AlertExpr>().is_err());
+        assert!("replica_lag 1".parse::<AlertExpr>().is_err());
+        assert!("replica_lag > many".parse::<AlertExpr>().is_err());
+    }
+}
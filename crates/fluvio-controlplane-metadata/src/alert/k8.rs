@@ -0,0 +1,24 @@
+use fluvio_stream_model::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::AlertRuleSpec;
+use super::AlertRuleStatus;
+
+const ALERT_RULE_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "AlertRule",
+        plural: "alertrules",
+        singular: "alertrule",
+    },
+};
+
+impl Spec for AlertRuleSpec {
+    type Header = DefaultHeader;
+    type Status = AlertRuleStatus;
+    fn metadata() -> &'static Crd {
+        &ALERT_RULE_API
+    }
+}
+
+impl Status for AlertRuleStatus {}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+use super::{AlertExpr, AlertExprError};
+
+/// Rule evaluated by the SC against cluster metrics
+#[derive(Debug, Clone, PartialEq, Eq, Default, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AlertRuleSpec {
+    /// condition such as `replica_lag{topic="orders"} > 1000`, see [`AlertExpr`]
+    pub expression: String,
+    /// seconds the condition must hold before the rule fires
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub for_secs: u32,
+    /// actions taken when the rule fires or resolves
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub actions: Vec<AlertAction>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+}
+
+impl AlertRuleSpec {
+    pub fn new(expression: impl Into<String>) -> Self {
+        Self {
+            expression: expression.into(),
+            ..Default::default()
+        }
+    }
+
+    /// parse the expression of the rule
+    pub fn parse_expression(&self) -> Result<AlertExpr, AlertExprError> {
+        self.expression.parse()
+    }
+}
+
+impl fmt::Display for AlertRuleSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AlertRule: {}", self.expression)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum AlertAction {
+    /// POST the alert as JSON to the url
+    #[fluvio(tag = 0)]
+    Webhook { url: String },
+    /// produce the alert as JSON record to the topic
+    #[fluvio(tag = 1)]
+    Topic { topic: String },
+}
+
+impl Default for AlertAction {
+    fn default() -> Self {
+        Self::Topic {
+            topic: String::new(),
+        }
+    }
+}
+
+impl fmt::Display for AlertAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Webhook { url } => write!(f, "webhook: {url}"),
+            Self::Topic { topic } => write!(f, "topic: {topic}"),
+        }
+    }
+}
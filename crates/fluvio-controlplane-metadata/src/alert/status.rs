@@ -0,0 +1,73 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AlertRuleStatus {
+    pub resolution: AlertRuleResolution,
+
+    /// metric value at the last evaluation
+    pub value: Option<i64>,
+
+    /// unix time in milliseconds since the current resolution holds
+    pub since: Option<i64>,
+
+    /// Reason for Status resolution (if applies)
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for AlertRuleStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+impl AlertRuleStatus {
+    pub fn invalid(reason: String) -> Self {
+        Self {
+            resolution: AlertRuleResolution::Invalid,
+            reason: Some(reason),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_firing(&self) -> bool {
+        self.resolution == AlertRuleResolution::Firing
+    }
+}
+
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AlertRuleResolution {
+    #[default]
+    #[fluvio(tag = 0)]
+    Init,
+    /// expression can't be parsed
+    #[fluvio(tag = 1)]
+    Invalid,
+    /// condition is not met
+    #[fluvio(tag = 2)]
+    Inactive,
+    /// condition is met but not for long enough
+    #[fluvio(tag = 3)]
+    Pending,
+    #[fluvio(tag = 4)]
+    Firing,
+}
+
+impl fmt::Display for AlertRuleResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Init => write!(f, "Init"),
+            Self::Invalid => write!(f, "Invalid"),
+            Self::Inactive => write!(f, "Inactive"),
+            Self::Pending => write!(f, "Pending"),
+            Self::Firing => write!(f, "Firing"),
+        }
+    }
+}
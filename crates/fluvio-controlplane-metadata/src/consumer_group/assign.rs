@@ -0,0 +1,299 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use fluvio_types::PartitionId;
+
+use super::{AssignmentStrategy, ConsumerGroupMember};
+
+/// Assign `partitions` partitions to `members`.
+///
+/// Members are sorted by key, so static members keep their place when they restart
+/// with another id. Every member gets `partitions / members` partitions and the
+/// first `partitions % members` members get one more, the partitions of `members`
+/// are ignored. `previous` is the assignment before the rebalance, the sticky
+/// strategies start from it. Whatever the strategy, partitions moving between members
+/// are revoked from their owner and only assigned once it released them.
+pub fn assign_partitions(
+    strategy: AssignmentStrategy,
+    partitions: PartitionId,
+    members: &[ConsumerGroupMember],
+    previous: &[ConsumerGroupMember],
+) -> Vec<ConsumerGroupMember> {
+    let members: BTreeMap<&str, &ConsumerGroupMember> = members
+        .iter()
+        .map(|member| (member.key(), member))
+        .collect();
+    if members.is_empty() {
+        return vec![];
+    }
+    let mut assignment: Vec<ConsumerGroupMember> = members
+        .values()
+        .map(|member| ConsumerGroupMember::new(&member.id, member.instance_id.clone()))
+        .collect();
+    let count = members.len() as PartitionId;
+
+    match strategy {
+        AssignmentStrategy::Range => {
+            let (base, extra) = (partitions / count, partitions % count);
+            let mut next = 0;
+            for (index, member) in assignment.iter_mut().enumerate() {
+                let size = base + PartitionId::from((index as PartitionId) < extra);
+                member.partitions = (next..next + size).collect();
+                next += size;
+            }
+        }
+        AssignmentStrategy::RoundRobin => {
+            for partition in 0..partitions {
+                assignment[(partition % count) as usize]
+                    .partitions
+                    .push(partition);
+            }
+        }
+        AssignmentStrategy::Sticky | AssignmentStrategy::CooperativeSticky => {
+            sticky(&mut assignment, partitions, previous)
+        }
+    }
+    withhold_moved(&mut assignment, partitions, previous);
+    assignment
+}
+
+/// members keep their previous partitions up to their share, freed partitions go to
+/// the members below their share
+fn sticky(
+    assignment: &mut [ConsumerGroupMember],
+    partitions: PartitionId,
+    previous: &[ConsumerGroupMember],
+) {
+    let count = assignment.len() as PartitionId;
+    let (base, extra) = (partitions / count, partitions % count);
+
+    let kept = |key: &str| -> Vec<PartitionId> {
+        let mut kept: Vec<PartitionId> = previous
+            .iter()
+            .filter(|member| member.key() == key)
+            .flat_map(|member| member.partitions.iter().chain(&member.revoking).copied())
+            .filter(|partition| *partition < partitions)
+            .collect();
+        kept.sort_unstable();
+        kept.dedup();
+        kept
+    };
+    let mut previous_partitions: Vec<Vec<PartitionId>> =
+        assignment.iter().map(|member| kept(member.key())).collect();
+
+    // the extra partitions go to the members that had the most before
+    let mut by_previous: Vec<usize> = (0..assignment.len()).collect();
+    by_previous.sort_by_key(|index| std::cmp::Reverse(previous_partitions[*index].len()));
+    let mut shares = vec![base; assignment.len()];
+    for index in by_previous.into_iter().take(extra as usize) {
+        shares[index] += 1;
+    }
+
+    let mut taken = BTreeSet::new();
+    for (index, member) in assignment.iter_mut().enumerate() {
+        for partition in previous_partitions[index].drain(..) {
+            if (member.partitions.len() as PartitionId) < shares[index] && taken.insert(partition) {
+                member.partitions.push(partition);
+            }
+        }
+    }
+
+    let mut free = (0..partitions).filter(|partition| !taken.contains(partition));
+    for (index, member) in assignment.iter_mut().enumerate() {
+        while (member.partitions.len() as PartitionId) < shares[index] {
+            match free.next() {
+                Some(partition) => member.partitions.push(partition),
+                None => break,
+            }
+        }
+        member.partitions.sort_unstable();
+    }
+}
+
+/// partitions owned by another member are withheld from their new member and revoked
+/// from their owner, the owner keeps revoking them until it reports they are released
+fn withhold_moved(
+    assignment: &mut [ConsumerGroupMember],
+    partitions: PartitionId,
+    previous: &[ConsumerGroupMember],
+) {
+    let owned: Vec<BTreeSet<PartitionId>> = assignment
+        .iter()
+        .map(|member| {
+            previous
+                .iter()
+                .filter(|owner| owner.key() == member.key())
+                .flat_map(|owner| owner.partitions.iter().chain(&owner.revoking).copied())
+                .filter(|partition| *partition < partitions)
+                .collect()
+        })
+        .collect();
+    let held: BTreeSet<PartitionId> = owned.iter().flatten().copied().collect();
+
+    for (member, owned) in assignment.iter_mut().zip(&owned) {
+        let target = std::mem::take(&mut member.partitions);
+        member.revoking = owned
+            .iter()
+            .filter(|partition| !target.contains(partition))
+            .copied()
+            .collect();
+        member.partitions = target
+            .into_iter()
+            .filter(|partition| owned.contains(partition) || !held.contains(partition))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn ids(members: &[&str]) -> Vec<ConsumerGroupMember> {
+        members
+            .iter()
+            .map(|id| ConsumerGroupMember::new(*id, None))
+            .collect()
+    }
+
+    fn partitions(assignment: &[ConsumerGroupMember]) -> Vec<Vec<PartitionId>> {
+        assignment
+            .iter()
+            .map(|member| member.partitions.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_range_assignment() {
+        let assignment = assign_partitions(AssignmentStrategy::Range, 5, &ids(&["b", "a"]), &[]);
+        assert_eq!(assignment[0].id, "a");
+        assert_eq!(partitions(&assignment), [vec![0, 1, 2], vec![3, 4]]);
+
+        let assignment = assign_partitions(AssignmentStrategy::Range, 1, &ids(&["a", "b"]), &[]);
+        assert_eq!(partitions(&assignment), [vec![0], vec![]]);
+        assert!(assign_partitions(AssignmentStrategy::Range, 3, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_round_robin_assignment() {
+        let assignment = assign_partitions(
+            AssignmentStrategy::RoundRobin,
+            5,
+            &ids(&["a", "b", "c"]),
+            &[],
+        );
+        assert_eq!(partitions(&assignment), [vec![0, 3], vec![1, 4], vec![2]]);
+    }
+
+    /// assignment once the revoked partitions were released
+    fn released(
+        strategy: AssignmentStrategy,
+        partitions: PartitionId,
+        members: &[ConsumerGroupMember],
+        previous: &[ConsumerGroupMember],
+    ) -> Vec<ConsumerGroupMember> {
+        let mut revoking = assign_partitions(strategy, partitions, members, previous);
+        for member in &mut revoking {
+            member.revoking.clear();
+        }
+        assign_partitions(strategy, partitions, members, &revoking)
+    }
+
+    #[test]
+    fn test_sticky_assignment() {
+        let first = assign_partitions(AssignmentStrategy::Sticky, 6, &ids(&["a", "b"]), &[]);
+        assert_eq!(partitions(&first), [vec![0, 1, 2], vec![3, 4, 5]]);
+
+        // a new member takes one partition from each member, the others stay
+        let second = released(
+            AssignmentStrategy::Sticky,
+            6,
+            &ids(&["a", "b", "c"]),
+            &first,
+        );
+        assert_eq!(partitions(&second), [vec![0, 1], vec![3, 4], vec![2, 5]]);
+
+        // the partitions of a leaving member are spread, the others stay
+        let third = assign_partitions(AssignmentStrategy::Sticky, 6, &ids(&["b", "c"]), &second);
+        assert_eq!(partitions(&third), [vec![0, 3, 4], vec![1, 2, 5]]);
+
+        // partitions no longer in the topic are dropped
+        let fewer = assign_partitions(AssignmentStrategy::Sticky, 4, &ids(&["b", "c"]), &third);
+        assert_eq!(partitions(&fewer), [vec![0, 3], vec![1, 2]]);
+    }
+
+    #[test]
+    fn test_static_member_assignment() {
+        let static_member = |id: &str, instance_id: &str| {
+            ConsumerGroupMember::new(id, Some(instance_id.to_owned()))
+        };
+        let first = assign_partitions(
+            AssignmentStrategy::Range,
+            4,
+            &[static_member("x", "a"), static_member("y", "b")],
+            &[],
+        );
+        assert_eq!(partitions(&first), [vec![0, 1], vec![2, 3]]);
+
+        // restarted with an id sorting first, keeps its place
+        let restarted = assign_partitions(
+            AssignmentStrategy::Range,
+            4,
+            &[static_member("x", "a"), static_member("m", "b")],
+            &first,
+        );
+        assert_eq!(restarted[1].id, "m");
+        assert_eq!(partitions(&restarted), partitions(&first));
+
+        let sticky = assign_partitions(
+            AssignmentStrategy::Sticky,
+            4,
+            &[static_member("z", "b"), ConsumerGroupMember::new("c", None)],
+            &restarted,
+        );
+        assert_eq!(sticky[0].id, "z");
+        assert_eq!(partitions(&sticky), [vec![2, 3], vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_cooperative_assignment() {
+        let strategy = AssignmentStrategy::CooperativeSticky;
+        let first = assign_partitions(strategy, 4, &ids(&["a", "b"]), &[]);
+        assert_eq!(partitions(&first), [vec![0, 1], vec![2, 3]]);
+
+        // the partition moving to the new member is revoked first
+        let revoking = assign_partitions(strategy, 4, &ids(&["a", "b", "c"]), &first);
+        assert_eq!(partitions(&revoking), [vec![0, 1], vec![2], vec![]]);
+        assert_eq!(revoking[1].revoking, [3]);
+
+        // recomputing keeps waiting for the owner
+        assert_eq!(
+            assign_partitions(strategy, 4, &ids(&["a", "b", "c"]), &revoking),
+            revoking
+        );
+
+        // once released, the partition is assigned
+        let mut released = revoking.clone();
+        released[1].revoking.clear();
+        let handed_over = assign_partitions(strategy, 4, &ids(&["a", "b", "c"]), &released);
+        assert_eq!(partitions(&handed_over), [vec![0, 1], vec![2], vec![3]]);
+        assert!(handed_over.iter().all(|member| member.revoking.is_empty()));
+
+        // partitions of a member that left are assigned right away
+        let left = assign_partitions(strategy, 4, &ids(&["a", "c"]), &handed_over);
+        assert_eq!(partitions(&left), [vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_eager_assignment_withholds_moved_partitions() {
+        let strategy = AssignmentStrategy::Range;
+        let first = assign_partitions(strategy, 4, &ids(&["a", "b"]), &[]);
+
+        // "c" gets partition 3 once "b" released it
+        let revoking = assign_partitions(strategy, 4, &ids(&["a", "b", "c"]), &first);
+        assert_eq!(partitions(&revoking), [vec![0, 1], vec![2], vec![]]);
+        assert_eq!(revoking[1].revoking, [3]);
+
+        let handed_over = released(strategy, 4, &ids(&["a", "b", "c"]), &first);
+        assert_eq!(partitions(&handed_over), [vec![0, 1], vec![2], vec![3]]);
+    }
+}
@@ -0,0 +1,24 @@
+use fluvio_stream_model::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::ConsumerGroupSpec;
+use super::ConsumerGroupStatus;
+
+const CONSUMER_GROUP_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "ConsumerGroup",
+        plural: "consumergroups",
+        singular: "consumergroup",
+    },
+};
+
+impl Spec for ConsumerGroupSpec {
+    type Header = DefaultHeader;
+    type Status = ConsumerGroupStatus;
+    fn metadata() -> &'static Crd {
+        &CONSUMER_GROUP_API
+    }
+}
+
+impl Status for ConsumerGroupStatus {}
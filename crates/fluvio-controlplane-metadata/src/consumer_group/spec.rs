@@ -0,0 +1,110 @@
+use std::fmt;
+use std::str::FromStr;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+pub const DEFAULT_SESSION_TIMEOUT_MS: u32 = 30_000;
+
+/// Members sharing the partitions of a topic, the SC assigns each partition to one member
+#[derive(Debug, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConsumerGroupSpec {
+    pub topic: String,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub strategy: AssignmentStrategy,
+    /// members without heartbeat for this long leave the group
+    #[cfg_attr(feature = "use_serde", serde(default = "default_session_timeout_ms"))]
+    pub session_timeout_ms: u32,
+}
+
+#[cfg(feature = "use_serde")]
+fn default_session_timeout_ms() -> u32 {
+    DEFAULT_SESSION_TIMEOUT_MS
+}
+
+impl Default for ConsumerGroupSpec {
+    fn default() -> Self {
+        Self {
+            topic: String::new(),
+            strategy: AssignmentStrategy::default(),
+            session_timeout_ms: DEFAULT_SESSION_TIMEOUT_MS,
+        }
+    }
+}
+
+impl ConsumerGroupSpec {
+    pub fn new(topic: impl Into<String>) -> Self {
+        Self {
+            topic: topic.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Display for ConsumerGroupSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ConsumerGroup: {} ({})", self.topic, self.strategy)
+    }
+}
+
+/// How the partitions of the topic are spread over the members
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AssignmentStrategy {
+    /// each member gets a contiguous range of partitions
+    #[default]
+    #[fluvio(tag = 0)]
+    Range,
+    /// partitions are dealt to the members in turn
+    #[fluvio(tag = 1)]
+    RoundRobin,
+    /// members keep their partitions across rebalances when the group stays balanced
+    #[fluvio(tag = 2)]
+    Sticky,
+    /// sticky, the partitions moving to another member are first revoked from their
+    /// owner and only assigned once it released them, the other partitions keep streaming
+    #[fluvio(tag = 3)]
+    CooperativeSticky,
+}
+
+impl AssignmentStrategy {
+    /// rebalances only stop the partitions that move
+    pub fn is_cooperative(&self) -> bool {
+        matches!(self, Self::CooperativeSticky)
+    }
+}
+
+impl fmt::Display for AssignmentStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Range => write!(f, "range"),
+            Self::RoundRobin => write!(f, "round-robin"),
+            Self::Sticky => write!(f, "sticky"),
+            Self::CooperativeSticky => write!(f, "cooperative-sticky"),
+        }
+    }
+}
+
+impl FromStr for AssignmentStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "range" => Ok(Self::Range),
+            "round-robin" | "roundrobin" => Ok(Self::RoundRobin),
+            "sticky" => Ok(Self::Sticky),
+            "cooperative-sticky" => Ok(Self::CooperativeSticky),
+            other => Err(format!(
+                "unknown assignment strategy '{other}', expected range, round-robin, sticky or cooperative-sticky"
+            )),
+        }
+    }
+}
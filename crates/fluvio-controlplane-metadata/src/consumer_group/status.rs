@@ -0,0 +1,114 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::PartitionId;
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConsumerGroupStatus {
+    pub resolution: ConsumerGroupResolution,
+
+    /// incremented with each rebalance
+    pub generation: u32,
+
+    /// members with their assigned partitions, ordered by id
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub members: Vec<ConsumerGroupMember>,
+
+    /// Reason for Status resolution (if applies)
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for ConsumerGroupStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+impl ConsumerGroupStatus {
+    pub fn invalid(reason: String) -> Self {
+        Self {
+            resolution: ConsumerGroupResolution::Invalid,
+            reason: Some(reason),
+            ..Default::default()
+        }
+    }
+
+    /// partitions assigned to `member_id`, None if it is not a member
+    pub fn assignment(&self, member_id: &str) -> Option<&[PartitionId]> {
+        self.members
+            .iter()
+            .find(|member| member.id == member_id)
+            .map(|member| member.partitions.as_slice())
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConsumerGroupMember {
+    pub id: String,
+    pub partitions: Vec<PartitionId>,
+    /// identity of a static member, kept when it restarts with another id
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 23)]
+    pub instance_id: Option<String>,
+    /// partitions moving to other members, assigned to them once this member released them
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    #[fluvio(min_version = 23)]
+    pub revoking: Vec<PartitionId>,
+}
+
+impl ConsumerGroupMember {
+    pub fn new(id: impl Into<String>, instance_id: Option<String>) -> Self {
+        Self {
+            id: id.into(),
+            partitions: vec![],
+            instance_id,
+            revoking: vec![],
+        }
+    }
+
+    /// the instance id of static members, the id of the others
+    pub fn key(&self) -> &str {
+        self.instance_id.as_deref().unwrap_or(&self.id)
+    }
+}
+
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ConsumerGroupResolution {
+    /// no members
+    #[default]
+    #[fluvio(tag = 0)]
+    Empty,
+    /// partitions are assigned to the members
+    #[fluvio(tag = 1)]
+    Stable,
+    /// topic of the group does not exist
+    #[fluvio(tag = 2)]
+    Invalid,
+}
+
+impl fmt::Display for ConsumerGroupResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty"),
+            Self::Stable => write!(f, "Stable"),
+            Self::Invalid => write!(f, "Invalid"),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use fluvio_protocol::{Decoder, Encoder};
+
+/// Membership changes of a consumer group, sent by its members
+#[derive(Debug, Encoder, Decoder, Clone)]
+pub enum UpdateConsumerGroupAction {
+    /// join the group, partitions are reassigned
+    #[fluvio(tag = 0)]
+    Join { member_id: String },
+    /// keep the membership alive
+    #[fluvio(tag = 1)]
+    Heartbeat { member_id: String },
+    /// leave the group, partitions are reassigned
+    #[fluvio(tag = 2)]
+    Leave { member_id: String },
+    /// join the group as a static member, a member joining with the instance id of
+    /// a member still in its session takes over its partitions without a rebalance
+    #[fluvio(tag = 3)]
+    JoinStatic {
+        member_id: String,
+        instance_id: String,
+    },
+    /// the member stopped consuming the partitions it was revoking in `generation`
+    #[fluvio(tag = 4)]
+    Revoked { member_id: String, generation: u32 },
+}
+
+impl Default for UpdateConsumerGroupAction {
+    fn default() -> Self {
+        Self::Heartbeat {
+            member_id: String::new(),
+        }
+    }
+}
+
+impl UpdateConsumerGroupAction {
+    pub fn member_id(&self) -> &str {
+        match self {
+            Self::Join { member_id }
+            | Self::Heartbeat { member_id }
+            | Self::Leave { member_id }
+            | Self::JoinStatic { member_id, .. }
+            | Self::Revoked { member_id, .. } => member_id,
+        }
+    }
+}
@@ -7,6 +7,10 @@ pub mod tableformat;
 pub mod message;
 pub mod mirror;
 pub mod mirroring;
+pub mod alert;
+pub mod consumer_group;
+pub mod schema;
+pub mod acl;
 
 pub use fluvio_stream_model::core;
 
@@ -39,6 +43,10 @@ pub mod extended {
         TableFormat,
         DerivedStream,
         Mirror,
+        AlertRule,
+        ConsumerGroup,
+        Schema,
+        Acl,
     }
 
     pub trait SpecExt: Spec {
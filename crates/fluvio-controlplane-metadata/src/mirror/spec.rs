@@ -77,6 +77,14 @@ pub struct Home {
     pub public_endpoint: String,
     #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
     pub client_tls: Option<ClientTls>,
+    /// outbound proxy used to reach the home cluster, e.g. socks5://proxy:1080,
+    /// the environment proxy is used if not set
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 27)]
+    pub proxy: Option<String>,
 }
 
 #[derive(Clone, PartialEq, Eq, Default, Encoder, Decoder)]
@@ -3,7 +3,10 @@
 use fluvio_types::SpuId;
 use fluvio_protocol::{link::ErrorCode, Decoder, Encoder};
 
-use crate::topic::{CleanupPolicy, CompressionAlgorithm, Deduplication, TopicSpec, TopicStorageConfig};
+use crate::topic::{
+    CleanupPolicy, CompressionAlgorithm, CompressionDictionary, Deduplication, TopicSpec,
+    TopicStorageConfig, Validation, DedupWindow,
+};
 
 /// Spec for Partition
 /// Each partition has replicas spread among SPU
@@ -33,6 +36,19 @@ pub struct PartitionSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 14)]
     pub mirror: Option<PartitionMirrorConfig>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 19)]
+    pub validation: Option<Validation>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    pub dedup_window: Option<DedupWindow>,
+    /// topic whose partition with the same index is copied when the replica is created
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 22)]
+    pub clone_from: Option<String>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 25)]
+    pub compression_dictionary: Option<CompressionDictionary>,
 }
 
 impl PartitionSpec {
@@ -61,6 +77,10 @@ impl PartitionSpec {
             compression_type: topic.get_compression_type().clone(),
             deduplication: topic.get_deduplication().cloned(),
             system: topic.is_system(),
+            validation: topic.get_validation().cloned(),
+            dedup_window: topic.get_dedup_window().cloned(),
+            clone_from: topic.get_clone_from().map(str::to_owned),
+            compression_dictionary: topic.get_compression_dictionary().cloned(),
         }
     }
 
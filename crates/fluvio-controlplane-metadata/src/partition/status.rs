@@ -41,6 +41,10 @@ pub struct PartitionStatus {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 16)]
     pub base_offset: i64,
+    /// the disk of the leader is full, produces are rejected until space is freed
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 26)]
+    pub storage_full: bool,
 }
 
 impl Default for PartitionStatus {
@@ -53,6 +57,7 @@ impl Default for PartitionStatus {
             replicas: Default::default(),
             is_being_deleted: Default::default(),
             base_offset: Default::default(),
+            storage_full: Default::default(),
         }
     }
 }
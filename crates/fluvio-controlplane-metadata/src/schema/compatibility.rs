@@ -0,0 +1,808 @@
+//!
+//! # Schema Compatibility
+//!
+//! A schema reads the records written with another schema when every record valid
+//! for the writer schema is understood by the reader schema. The checks follow the
+//! resolution rules of each format and only cover the constructs that change what
+//! a reader accepts: types, required fields and enum symbols.
+//!
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::Value;
+
+use super::{SchemaCompatibility, SchemaFormat};
+
+/// Checks that `definition` is a schema of `format`
+pub fn validate_definition(format: SchemaFormat, definition: &str) -> Result<(), String> {
+    match format {
+        SchemaFormat::JsonSchema => parse_json(definition).map(|_| ()),
+        SchemaFormat::Avro => {
+            let schema = parse_json(definition)?;
+            AvroNames::collect(&schema).map(|_| ())
+        }
+        SchemaFormat::Protobuf => {
+            let messages = ProtoMessages::parse(definition)?;
+            if messages.messages.is_empty() {
+                return Err("no message is defined".to_owned());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Checks that `new` can follow `latest` under `compatibility`
+pub fn check_compatibility(
+    format: SchemaFormat,
+    compatibility: SchemaCompatibility,
+    latest: &str,
+    new: &str,
+) -> Result<(), String> {
+    validate_definition(format, new)?;
+    match compatibility {
+        SchemaCompatibility::None => Ok(()),
+        SchemaCompatibility::Backward => reads(format, new, latest),
+        SchemaCompatibility::Forward => reads(format, latest, new),
+        SchemaCompatibility::Full => {
+            reads(format, new, latest)?;
+            reads(format, latest, new)
+        }
+    }
+}
+
+/// Ok when records written with `writer` are read with `reader`
+fn reads(format: SchemaFormat, reader: &str, writer: &str) -> Result<(), String> {
+    match format {
+        SchemaFormat::JsonSchema => {
+            json_schema_reads(&parse_json(reader)?, &parse_json(writer)?, "$")
+        }
+        SchemaFormat::Avro => {
+            let reader = parse_json(reader)?;
+            let writer = parse_json(writer)?;
+            AvroResolver {
+                reader_names: AvroNames::collect(&reader)?,
+                writer_names: AvroNames::collect(&writer)?,
+                visited: HashSet::new(),
+            }
+            .reads(&reader, &writer, "$")
+        }
+        SchemaFormat::Protobuf => {
+            ProtoMessages::parse(reader)?.reads(&ProtoMessages::parse(writer)?)
+        }
+    }
+}
+
+fn parse_json(definition: &str) -> Result<Value, String> {
+    serde_json::from_str(definition).map_err(|err| format!("invalid json: {err}"))
+}
+
+// JSON Schema
+
+fn json_types(schema: &Value) -> Option<Vec<&str>> {
+    match schema.get("type")? {
+        Value::String(name) => Some(vec![name.as_str()]),
+        Value::Array(names) => Some(names.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn json_object_keys(schema: &Value, key: &str) -> HashSet<String> {
+    match schema.get(key) {
+        Some(Value::Array(names)) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_owned)
+            .collect(),
+        Some(Value::Object(map)) => map.keys().cloned().collect(),
+        _ => HashSet::new(),
+    }
+}
+
+fn json_schema_reads(reader: &Value, writer: &Value, path: &str) -> Result<(), String> {
+    if reader == &Value::Bool(true) || writer == &Value::Bool(false) {
+        return Ok(());
+    }
+    if reader == &Value::Bool(false) {
+        return Err(format!("{path} accepts no value"));
+    }
+
+    if let Some(reader_types) = json_types(reader) {
+        let Some(writer_types) = json_types(writer) else {
+            return Err(format!(
+                "{path} is restricted to {}",
+                reader_types.join(", ")
+            ));
+        };
+        for writer_type in writer_types {
+            let accepted = reader_types.contains(&writer_type)
+                || (writer_type == "integer" && reader_types.contains(&"number"));
+            if !accepted {
+                return Err(format!("{path} no longer accepts {writer_type}"));
+            }
+        }
+    }
+
+    if let Some(Value::Array(reader_values)) = reader.get("enum") {
+        let Some(Value::Array(writer_values)) = writer.get("enum") else {
+            return Err(format!("{path} is restricted to an enum"));
+        };
+        if let Some(missing) = writer_values
+            .iter()
+            .find(|value| !reader_values.contains(value))
+        {
+            return Err(format!("{path} no longer accepts {missing}"));
+        }
+    }
+
+    let writer_required = json_object_keys(writer, "required");
+    for name in json_object_keys(reader, "required") {
+        if !writer_required.contains(&name) {
+            return Err(format!("{path}.{name} is required but may be missing"));
+        }
+    }
+
+    let reader_properties = reader.get("properties").and_then(Value::as_object);
+    let writer_properties = writer.get("properties").and_then(Value::as_object);
+    if let (Some(reader_properties), Some(writer_properties)) =
+        (reader_properties, writer_properties)
+    {
+        for (name, reader_property) in reader_properties {
+            if let Some(writer_property) = writer_properties.get(name) {
+                json_schema_reads(reader_property, writer_property, &format!("{path}.{name}"))?;
+            }
+        }
+    }
+    if reader.get("additionalProperties") == Some(&Value::Bool(false)) {
+        let allowed = json_object_keys(reader, "properties");
+        if let Some(name) = json_object_keys(writer, "properties")
+            .into_iter()
+            .find(|name| !allowed.contains(name))
+        {
+            return Err(format!("{path}.{name} is no longer allowed"));
+        }
+    }
+
+    if let (Some(reader_items), Some(writer_items)) = (reader.get("items"), writer.get("items")) {
+        json_schema_reads(reader_items, writer_items, &format!("{path}[]"))?;
+    }
+
+    Ok(())
+}
+
+// Avro
+
+const AVRO_PRIMITIVES: [&str; 8] = [
+    "null", "boolean", "int", "long", "float", "double", "bytes", "string",
+];
+
+/// named types of a schema, by name and by full name
+struct AvroNames<'a>(HashMap<String, &'a Value>);
+
+impl<'a> AvroNames<'a> {
+    fn collect(schema: &'a Value) -> Result<Self, String> {
+        let mut names = Self(HashMap::new());
+        names.walk(schema, None)?;
+        Ok(names)
+    }
+
+    fn walk(&mut self, schema: &'a Value, namespace: Option<&'a str>) -> Result<(), String> {
+        match schema {
+            Value::Array(branches) => {
+                for branch in branches {
+                    self.walk(branch, namespace)?;
+                }
+                Ok(())
+            }
+            Value::String(_) => Ok(()),
+            Value::Object(object) => {
+                let kind = object
+                    .get("type")
+                    .ok_or_else(|| "avro schema without type".to_owned())?;
+                let Some(kind) = kind.as_str() else {
+                    return self.walk(kind, namespace);
+                };
+                let namespace = object
+                    .get("namespace")
+                    .and_then(Value::as_str)
+                    .or(namespace);
+                match kind {
+                    "record" | "error" | "enum" | "fixed" => {
+                        let name = object
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .ok_or_else(|| format!("avro {kind} without name"))?;
+                        self.0.insert(name.to_owned(), schema);
+                        if let Some(namespace) = namespace {
+                            self.0.insert(format!("{namespace}.{name}"), schema);
+                        }
+                    }
+                    _ => {}
+                }
+                match kind {
+                    "record" | "error" => {
+                        let fields = object
+                            .get("fields")
+                            .and_then(Value::as_array)
+                            .ok_or_else(|| "avro record without fields".to_owned())?;
+                        for field in fields {
+                            let field_type = field
+                                .get("type")
+                                .ok_or_else(|| "avro field without type".to_owned())?;
+                            self.walk(field_type, namespace)?;
+                        }
+                        Ok(())
+                    }
+                    "array" => self.walk(object.get("items").unwrap_or(&Value::Null), namespace),
+                    "map" => self.walk(object.get("values").unwrap_or(&Value::Null), namespace),
+                    _ => Ok(()),
+                }
+            }
+            _ => Err(format!("invalid avro schema {schema}")),
+        }
+    }
+
+    fn resolve(&self, schema: &'a Value) -> Result<&'a Value, String> {
+        match schema {
+            Value::String(name) if !AVRO_PRIMITIVES.contains(&name.as_str()) => self
+                .0
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("unknown avro type {name}")),
+            _ => Ok(schema),
+        }
+    }
+}
+
+struct AvroResolver<'a> {
+    reader_names: AvroNames<'a>,
+    writer_names: AvroNames<'a>,
+    /// named types being compared, recursive types are compared once
+    visited: HashSet<(String, String)>,
+}
+
+fn avro_kind(schema: &Value) -> &str {
+    match schema {
+        Value::String(name) => name.as_str(),
+        Value::Array(_) => "union",
+        Value::Object(object) => match object.get("type") {
+            Some(Value::String(name)) => name.as_str(),
+            Some(inner) => avro_kind(inner),
+            None => "",
+        },
+        _ => "",
+    }
+}
+
+fn avro_promotes(writer: &str, reader: &str) -> bool {
+    matches!(
+        (writer, reader),
+        ("int", "long" | "float" | "double")
+            | ("long", "float" | "double")
+            | ("float", "double")
+            | ("string", "bytes")
+            | ("bytes", "string")
+    )
+}
+
+impl<'a> AvroResolver<'a> {
+    fn reads(&mut self, reader: &'a Value, writer: &'a Value, path: &str) -> Result<(), String> {
+        let reader = self.reader_names.resolve(reader)?;
+        let writer = self.writer_names.resolve(writer)?;
+
+        if let Value::Array(branches) = writer {
+            for branch in branches {
+                self.reads(reader, branch, path)?;
+            }
+            return Ok(());
+        }
+        if let Value::Array(branches) = reader {
+            for branch in branches {
+                let visited = self.visited.clone();
+                if self.reads(branch, writer, path).is_ok() {
+                    return Ok(());
+                }
+                self.visited = visited;
+            }
+            return Err(format!(
+                "{path}: no branch of the union reads {}",
+                avro_kind(writer)
+            ));
+        }
+
+        let reader_kind = avro_kind(reader);
+        let writer_kind = avro_kind(writer);
+        if reader_kind != writer_kind {
+            if avro_promotes(writer_kind, reader_kind) {
+                return Ok(());
+            }
+            return Err(format!(
+                "{path}: {writer_kind} is not read as {reader_kind}"
+            ));
+        }
+
+        let name = |schema: &Value| {
+            schema
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        match reader_kind {
+            "record" | "error" => {
+                let (reader_name, writer_name) = (name(reader), name(writer));
+                if reader_name != writer_name {
+                    return Err(format!(
+                        "{path}: record {writer_name} is not read as {reader_name}"
+                    ));
+                }
+                if !self.visited.insert((reader_name, writer_name)) {
+                    return Ok(());
+                }
+                let fields = |schema: &'a Value| -> &'a [Value] {
+                    schema
+                        .get("fields")
+                        .and_then(Value::as_array)
+                        .map(Vec::as_slice)
+                        .unwrap_or(&[])
+                };
+                let field_type = |field: &'a Value| field.get("type").unwrap_or(&Value::Null);
+                let writer_fields = fields(writer);
+                for reader_field in fields(reader) {
+                    let field_name = name(reader_field);
+                    let field_path = format!("{path}.{field_name}");
+                    let aliases = json_object_keys(reader_field, "aliases");
+                    let writer_field = writer_fields.iter().find(|field| {
+                        let writer_name = name(*field);
+                        writer_name == field_name || aliases.contains(&writer_name)
+                    });
+                    match writer_field {
+                        Some(writer_field) => {
+                            self.reads(
+                                field_type(reader_field),
+                                field_type(writer_field),
+                                &field_path,
+                            )?;
+                        }
+                        None if reader_field.get("default").is_some() => {}
+                        None => {
+                            return Err(format!(
+                                "{field_path} has no default and is missing from records"
+                            ))
+                        }
+                    }
+                }
+                Ok(())
+            }
+            "enum" => {
+                if reader.get("default").is_some() {
+                    return Ok(());
+                }
+                let symbols = json_object_keys(reader, "symbols");
+                match json_object_keys(writer, "symbols")
+                    .into_iter()
+                    .find(|symbol| !symbols.contains(symbol))
+                {
+                    Some(symbol) => Err(format!("{path}: enum symbol {symbol} is not read")),
+                    None => Ok(()),
+                }
+            }
+            "fixed" => {
+                if reader.get("size") != writer.get("size") {
+                    return Err(format!("{path}: fixed size changed"));
+                }
+                Ok(())
+            }
+            "array" => self.reads(
+                reader.get("items").unwrap_or(&Value::Null),
+                writer.get("items").unwrap_or(&Value::Null),
+                &format!("{path}[]"),
+            ),
+            "map" => self.reads(
+                reader.get("values").unwrap_or(&Value::Null),
+                writer.get("values").unwrap_or(&Value::Null),
+                &format!("{path}{{}}"),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Protobuf
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProtoField {
+    name: String,
+    kind: String,
+    repeated: bool,
+    required: bool,
+}
+
+/// fields of the messages of a proto file by field number
+#[derive(Debug, Default)]
+struct ProtoMessages {
+    messages: BTreeMap<String, BTreeMap<u32, ProtoField>>,
+    enums: HashSet<String>,
+}
+
+fn proto_tokens(definition: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut chars = definition.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            '"' | '\'' => {
+                let mut token = String::from(c);
+                for next in chars.by_ref() {
+                    token.push(next);
+                    if next == c {
+                        break;
+                    }
+                }
+                tokens.push(token);
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' => {
+                let mut token = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' || next == '.' {
+                        token.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(token);
+            }
+            '{' | '}' | '=' | ';' | '<' | '>' | ',' | '[' | ']' | '(' | ')' | '-' => {
+                tokens.push(c.to_string())
+            }
+            other => return Err(format!("unexpected character '{other}' in proto")),
+        }
+    }
+    Ok(tokens)
+}
+
+const PROTO_VARINT: [&str; 5] = ["int32", "int64", "uint32", "uint64", "bool"];
+
+impl ProtoMessages {
+    fn parse(definition: &str) -> Result<Self, String> {
+        let tokens = proto_tokens(definition)?;
+        let mut messages = Self::default();
+        let mut pos = 0;
+        messages.parse_body(&tokens, &mut pos, "", false)?;
+        Ok(messages)
+    }
+
+    /// declarations until the closing brace of a block, or the end of the file
+    fn parse_body(
+        &mut self,
+        tokens: &[String],
+        pos: &mut usize,
+        scope: &str,
+        in_message: bool,
+    ) -> Result<(), String> {
+        let mut fields = BTreeMap::new();
+        while let Some(token) = tokens.get(*pos) {
+            *pos += 1;
+            match token.as_str() {
+                "}" => break,
+                ";" => {}
+                "syntax" | "package" | "import" | "option" | "reserved" | "extensions" => {
+                    skip_statement(tokens, pos);
+                }
+                "message" | "enum" | "service" | "extend" => {
+                    let name = tokens
+                        .get(*pos)
+                        .ok_or_else(|| format!("{token} without name"))?
+                        .clone();
+                    *pos += 1;
+                    expect(tokens, pos, "{")?;
+                    let full_name = if scope.is_empty() {
+                        name
+                    } else {
+                        format!("{scope}.{name}")
+                    };
+                    match token.as_str() {
+                        "message" => self.parse_body(tokens, pos, &full_name, true)?,
+                        "enum" => {
+                            self.enums.insert(full_name);
+                            skip_block(tokens, pos);
+                        }
+                        _ => skip_block(tokens, pos),
+                    }
+                }
+                "oneof" if in_message => {
+                    *pos += 1;
+                    expect(tokens, pos, "{")?;
+                    while tokens.get(*pos).is_some_and(|it| it != "}") {
+                        if tokens[*pos] == "option" {
+                            skip_statement(tokens, pos);
+                            continue;
+                        }
+                        let (number, field) = parse_field(tokens, pos, None)?;
+                        fields.insert(number, field);
+                    }
+                    *pos += 1;
+                }
+                _ if in_message => {
+                    *pos -= 1;
+                    let label = match token.as_str() {
+                        "optional" | "repeated" | "required" => {
+                            *pos += 1;
+                            Some(token.as_str())
+                        }
+                        _ => None,
+                    };
+                    let (number, field) = parse_field(tokens, pos, label)?;
+                    if fields.insert(number, field).is_some() {
+                        return Err(format!("field number {number} is used twice in {scope}"));
+                    }
+                }
+                other => return Err(format!("unexpected '{other}' in proto")),
+            }
+        }
+        if in_message {
+            self.messages.insert(scope.to_owned(), fields);
+        }
+        Ok(())
+    }
+
+    /// field types are compared by wire type, so renaming a field is compatible
+    fn field_wire_type(&self, kind: &str) -> String {
+        let short = kind.rsplit('.').next().unwrap_or(kind);
+        if PROTO_VARINT.contains(&kind)
+            || self
+                .enums
+                .iter()
+                .any(|name| name == kind || name.rsplit('.').next() == Some(short))
+        {
+            return "varint".to_owned();
+        }
+        match kind {
+            "sint32" | "sint64" => "zigzag".to_owned(),
+            "fixed32" | "sfixed32" => "fixed32".to_owned(),
+            "fixed64" | "sfixed64" => "fixed64".to_owned(),
+            "string" | "bytes" => "bytes".to_owned(),
+            _ => short.to_owned(),
+        }
+    }
+
+    fn reads(&self, writer: &Self) -> Result<(), String> {
+        for (message, reader_fields) in &self.messages {
+            let Some(writer_fields) = writer.messages.get(message) else {
+                continue;
+            };
+            for (number, reader_field) in reader_fields {
+                let Some(writer_field) = writer_fields.get(number) else {
+                    if reader_field.required {
+                        return Err(format!(
+                            "{message}.{} is required but missing from records",
+                            reader_field.name
+                        ));
+                    }
+                    continue;
+                };
+                if reader_field.repeated != writer_field.repeated
+                    || self.field_wire_type(&reader_field.kind)
+                        != writer.field_wire_type(&writer_field.kind)
+                {
+                    return Err(format!(
+                        "{message} field {number} changed from {} to {}",
+                        writer_field.kind, reader_field.kind
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `type name = number [options];`, the label was already read
+fn parse_field(
+    tokens: &[String],
+    pos: &mut usize,
+    label: Option<&str>,
+) -> Result<(u32, ProtoField), String> {
+    let next = |pos: &mut usize| -> Result<String, String> {
+        let token = tokens
+            .get(*pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of proto".to_owned())?;
+        *pos += 1;
+        Ok(token)
+    };
+    let mut kind = next(pos)?;
+    if kind == "map" {
+        // map<key, value> is a repeated message on the wire
+        while next(pos)? != ">" {}
+        kind = "map".to_owned();
+    }
+    let name = next(pos)?;
+    expect(tokens, pos, "=")?;
+    let number = next(pos)?;
+    let number = number
+        .parse::<u32>()
+        .map_err(|_| format!("invalid field number '{number}' for {name}"))?;
+    skip_statement(tokens, pos);
+    Ok((
+        number,
+        ProtoField {
+            name,
+            repeated: label == Some("repeated") || kind == "map",
+            required: label == Some("required"),
+            kind,
+        },
+    ))
+}
+
+fn expect(tokens: &[String], pos: &mut usize, expected: &str) -> Result<(), String> {
+    match tokens.get(*pos) {
+        Some(token) if token == expected => {
+            *pos += 1;
+            Ok(())
+        }
+        Some(token) => Err(format!("expected '{expected}' but found '{token}'")),
+        None => Err(format!("expected '{expected}' at the end of proto")),
+    }
+}
+
+/// up to and including the next `;`
+fn skip_statement(tokens: &[String], pos: &mut usize) {
+    while let Some(token) = tokens.get(*pos) {
+        *pos += 1;
+        if token == ";" {
+            break;
+        }
+    }
+}
+
+/// up to and including the brace closing the current block
+fn skip_block(tokens: &[String], pos: &mut usize) {
+    let mut depth = 1;
+    while let Some(token) = tokens.get(*pos) {
+        *pos += 1;
+        match token.as_str() {
+            "{" => depth += 1,
+            "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check(
+        format: SchemaFormat,
+        compatibility: SchemaCompatibility,
+        latest: &str,
+        new: &str,
+    ) -> Result<(), String> {
+        check_compatibility(format, compatibility, latest, new)
+    }
+
+    #[test]
+    fn test_json_schema_compatibility() {
+        let latest =
+            r#"{"type": "object", "properties": {"id": {"type": "integer"}}, "required": ["id"]}"#;
+        let optional_field = r#"{"type": "object", "properties": {"id": {"type": "number"}, "name": {"type": "string"}}, "required": ["id"]}"#;
+        let required_field = r#"{"type": "object", "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}, "required": ["id", "name"]}"#;
+
+        let backward = SchemaCompatibility::Backward;
+        assert!(check(SchemaFormat::JsonSchema, backward, latest, optional_field).is_ok());
+        assert_eq!(
+            check(SchemaFormat::JsonSchema, backward, latest, required_field),
+            Err("$.name is required but may be missing".to_owned())
+        );
+        // number is not read as integer
+        assert!(check(
+            SchemaFormat::JsonSchema,
+            SchemaCompatibility::Full,
+            latest,
+            optional_field
+        )
+        .is_err());
+        assert!(check(
+            SchemaFormat::JsonSchema,
+            SchemaCompatibility::None,
+            latest,
+            required_field
+        )
+        .is_ok());
+        assert!(check(SchemaFormat::JsonSchema, backward, latest, "{").is_err());
+    }
+
+    #[test]
+    fn test_avro_compatibility() {
+        let latest =
+            r#"{"type": "record", "name": "User", "fields": [{"name": "id", "type": "int"}]}"#;
+        let with_default = r#"{"type": "record", "name": "User", "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "tier", "type": ["null", "string"], "default": null}]}"#;
+        let without_default = r#"{"type": "record", "name": "User", "fields": [
+            {"name": "id", "type": "int"},
+            {"name": "tier", "type": "string"}]}"#;
+
+        let backward = SchemaCompatibility::Backward;
+        assert!(check(SchemaFormat::Avro, backward, latest, with_default).is_ok());
+        assert_eq!(
+            check(SchemaFormat::Avro, backward, latest, without_default),
+            Err("$.tier has no default and is missing from records".to_owned())
+        );
+        // long is not read as int
+        assert_eq!(
+            check(
+                SchemaFormat::Avro,
+                SchemaCompatibility::Forward,
+                latest,
+                with_default
+            ),
+            Err("$.id: long is not read as int".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_avro_recursive_types() {
+        let node = r#"{"type": "record", "name": "Node", "fields": [
+            {"name": "value", "type": "int"},
+            {"name": "next", "type": ["null", "Node"], "default": null}]}"#;
+        assert!(check(SchemaFormat::Avro, SchemaCompatibility::Full, node, node).is_ok());
+        assert!(validate_definition(SchemaFormat::Avro, r#"{"type": "record"}"#).is_err());
+    }
+
+    #[test]
+    fn test_protobuf_compatibility() {
+        let latest = r#"
+            syntax = "proto3";
+            // a user
+            message User {
+                int64 id = 1;
+                string name = 2;
+                enum Tier { FREE = 0; PAID = 1; }
+            }"#;
+        let added = r#"
+            syntax = "proto3";
+            message User {
+                int64 id = 1;
+                string display_name = 2;
+                repeated string tags = 3;
+                map<string, string> labels = 4;
+            }"#;
+        let changed = r#"
+            syntax = "proto3";
+            message User {
+                int64 id = 1;
+                double name = 2;
+            }"#;
+
+        let full = SchemaCompatibility::Full;
+        assert!(check(SchemaFormat::Protobuf, full, latest, added).is_ok());
+        assert_eq!(
+            check(SchemaFormat::Protobuf, full, latest, changed),
+            Err("User field 2 changed from string to double".to_owned())
+        );
+        assert!(validate_definition(SchemaFormat::Protobuf, "syntax = \"proto3\";").is_err());
+    }
+}
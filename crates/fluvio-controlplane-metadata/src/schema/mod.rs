@@ -0,0 +1,75 @@
+mod spec;
+mod status;
+mod update;
+#[cfg(feature = "use_serde")]
+mod compatibility;
+
+pub use spec::*;
+pub use status::*;
+pub use update::*;
+#[cfg(feature = "use_serde")]
+pub use compatibility::*;
+
+#[cfg(feature = "k8")]
+mod k8;
+
+mod convert {
+
+    use crate::core::{Spec, Status, Removable, Creatable};
+    use crate::extended::{ObjectType, SpecExt};
+    use super::*;
+
+    impl Spec for SchemaSpec {
+        const LABEL: &'static str = "Schema";
+
+        type Status = SchemaStatus;
+
+        type Owner = Self;
+        type IndexKey = String;
+    }
+
+    impl SpecExt for SchemaSpec {
+        const OBJECT_TYPE: ObjectType = ObjectType::Schema;
+    }
+
+    impl Removable for SchemaSpec {
+        type DeleteKey = String;
+    }
+
+    impl Creatable for SchemaSpec {}
+
+    impl Status for SchemaStatus {}
+
+    #[cfg(feature = "k8")]
+    mod extended {
+
+        use crate::store::k8::K8ExtendedSpec;
+        use crate::store::k8::K8ConvertError;
+        use crate::store::k8::K8MetaItem;
+        use crate::store::MetadataStoreObject;
+        use crate::k8_types::K8Obj;
+        use crate::store::k8::default_convert_from_k8;
+
+        use super::SchemaSpec;
+
+        impl K8ExtendedSpec for SchemaSpec {
+            type K8Spec = Self;
+
+            fn convert_from_k8(
+                k8_obj: K8Obj<Self::K8Spec>,
+                multi_namespace_context: bool,
+            ) -> Result<MetadataStoreObject<Self, K8MetaItem>, K8ConvertError<Self::K8Spec>>
+            {
+                default_convert_from_k8(k8_obj, multi_namespace_context)
+            }
+
+            fn convert_status_from_k8(status: Self::Status) -> Self::Status {
+                status
+            }
+
+            fn into_k8(self) -> Self::K8Spec {
+                self
+            }
+        }
+    }
+}
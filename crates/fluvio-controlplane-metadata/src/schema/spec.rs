@@ -0,0 +1,165 @@
+use std::fmt;
+use std::str::FromStr;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Versions of the schema of the records of a topic, the object is named after the topic
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SchemaSpec {
+    pub format: SchemaFormat,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub compatibility: SchemaCompatibility,
+    /// registered versions, oldest first
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub versions: Vec<SchemaVersion>,
+}
+
+impl SchemaSpec {
+    /// spec with `definition` as version 1
+    pub fn new(format: SchemaFormat, definition: impl Into<String>) -> Self {
+        Self {
+            format,
+            compatibility: SchemaCompatibility::default(),
+            versions: vec![SchemaVersion {
+                version: 1,
+                definition: definition.into(),
+            }],
+        }
+    }
+
+    pub fn latest(&self) -> Option<&SchemaVersion> {
+        self.versions.last()
+    }
+
+    pub fn version(&self, version: u32) -> Option<&SchemaVersion> {
+        self.versions.iter().find(|it| it.version == version)
+    }
+
+    /// appends `definition` as the next version and returns its number
+    pub fn add_version(&mut self, definition: impl Into<String>) -> u32 {
+        let version = self.latest().map(|it| it.version + 1).unwrap_or(1);
+        self.versions.push(SchemaVersion {
+            version,
+            definition: definition.into(),
+        });
+        version
+    }
+}
+
+impl fmt::Display for SchemaSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Schema: {} v{}",
+            self.format,
+            self.latest().map(|it| it.version).unwrap_or_default()
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SchemaVersion {
+    pub version: u32,
+    pub definition: String,
+}
+
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SchemaFormat {
+    #[default]
+    #[fluvio(tag = 0)]
+    JsonSchema,
+    #[fluvio(tag = 1)]
+    Avro,
+    #[fluvio(tag = 2)]
+    Protobuf,
+}
+
+impl fmt::Display for SchemaFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::JsonSchema => write!(f, "json-schema"),
+            Self::Avro => write!(f, "avro"),
+            Self::Protobuf => write!(f, "protobuf"),
+        }
+    }
+}
+
+impl FromStr for SchemaFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json-schema" | "json" => Ok(Self::JsonSchema),
+            "avro" => Ok(Self::Avro),
+            "protobuf" | "proto" => Ok(Self::Protobuf),
+            other => Err(format!(
+                "unknown schema format '{other}', expected json-schema, avro or protobuf"
+            )),
+        }
+    }
+}
+
+/// Which records a new version must be able to read, or be read by
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SchemaCompatibility {
+    /// any version is accepted
+    #[fluvio(tag = 0)]
+    None,
+    /// consumers with the new version read records written with the latest version
+    #[default]
+    #[fluvio(tag = 1)]
+    Backward,
+    /// consumers with the latest version read records written with the new version
+    #[fluvio(tag = 2)]
+    Forward,
+    /// both backward and forward
+    #[fluvio(tag = 3)]
+    Full,
+}
+
+impl fmt::Display for SchemaCompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Backward => write!(f, "backward"),
+            Self::Forward => write!(f, "forward"),
+            Self::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl FromStr for SchemaCompatibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "backward" => Ok(Self::Backward),
+            "forward" => Ok(Self::Forward),
+            "full" => Ok(Self::Full),
+            other => Err(format!(
+                "unknown compatibility '{other}', expected none, backward, forward or full"
+            )),
+        }
+    }
+}
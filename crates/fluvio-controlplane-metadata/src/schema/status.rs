@@ -0,0 +1,39 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Schemas are checked when they are registered, the status only records the outcome
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SchemaStatus {
+    pub resolution: SchemaResolution,
+
+    /// Reason for Status resolution (if applies)
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for SchemaStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SchemaResolution {
+    #[default]
+    #[fluvio(tag = 0)]
+    Registered,
+}
+
+impl fmt::Display for SchemaResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Registered => write!(f, "Registered"),
+        }
+    }
+}
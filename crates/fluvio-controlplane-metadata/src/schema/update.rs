@@ -0,0 +1,22 @@
+use fluvio_protocol::{Decoder, Encoder};
+
+use super::SchemaCompatibility;
+
+/// Changes to the schemas of a topic
+#[derive(Debug, Encoder, Decoder, Clone)]
+pub enum UpdateSchemaAction {
+    /// add a version, it must be compatible with the latest version
+    #[fluvio(tag = 0)]
+    Register { definition: String },
+    /// change the rule checked when versions are added
+    #[fluvio(tag = 1)]
+    SetCompatibility(SchemaCompatibility),
+}
+
+impl Default for UpdateSchemaAction {
+    fn default() -> Self {
+        Self::Register {
+            definition: String::new(),
+        }
+    }
+}
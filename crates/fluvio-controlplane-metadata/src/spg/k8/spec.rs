@@ -10,7 +10,7 @@ use fluvio_types::defaults::{SPU_PUBLIC_PORT, SPU_LOG_BASE_DIR, SPU_LOG_SIZE};
 use fluvio_types::defaults::SPU_PRIVATE_PORT;
 
 use crate::spu::EncryptionEnum;
-use crate::spg::SpuGroupStatus;
+use crate::spg::{SpuGroupAutoscale, SpuGroupStatus};
 use crate::k8_types::{Spec, Crd, DefaultHeader, TemplateSpec, Env};
 
 use crd::SPG_API;
@@ -44,6 +44,8 @@ pub struct K8SpuGroupSpec {
     pub replicas: u16,
     #[serde(default)]
     pub min_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autoscale: Option<SpuGroupAutoscale>,
 }
 
 #[derive(Deserialize, Serialize, Default, Debug, Clone)]
@@ -114,6 +116,8 @@ pub struct K8StorageConfig {
     pub log_dir: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_partition_size: Option<u64>,
 }
 
 impl K8StorageConfig {
@@ -140,6 +144,7 @@ mod convert {
                 replicas: spec.replicas,
                 min_id: spec.min_id,
                 spu_config: spec.template.spec.into(),
+                autoscale: spec.autoscale,
             }
         }
     }
@@ -164,6 +169,7 @@ mod convert {
                     spec: spec.spu_config.into(),
                     ..Default::default()
                 },
+                autoscale: spec.autoscale,
             }
         }
     }
@@ -184,6 +190,7 @@ mod convert {
             Self {
                 log_dir: storage.log_dir,
                 size: storage.size,
+                max_partition_size: storage.max_partition_size,
             }
         }
     }
@@ -193,6 +200,7 @@ mod convert {
             Self {
                 log_dir: config.log_dir,
                 size: config.size,
+                max_partition_size: config.max_partition_size,
             }
         }
     }
@@ -19,6 +19,50 @@ pub struct SpuGroupSpec {
 
     /// Configuration elements to be applied to each SPUs in the group
     pub spu_config: SpuConfig,
+
+    /// Scaling of the replicas with the load of the group, disabled when not set
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 21)]
+    pub autoscale: Option<SpuGroupAutoscale>,
+}
+
+/// The SC adds SPUs to the group when the load of its SPUs is above a target,
+/// and removes SPUs which hold no replicas when it is below
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SpuGroupAutoscale {
+    pub min_replicas: u16,
+    pub max_replicas: u16,
+    /// bytes written per second to the leaders of an SPU
+    pub target_throughput: Option<u64>,
+    /// percentage of the log volume of an SPU in use
+    pub target_disk_usage: Option<u8>,
+}
+
+impl SpuGroupAutoscale {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_replicas == 0 || self.min_replicas > self.max_replicas {
+            return Err("autoscale replicas must satisfy 0 < min <= max".to_owned());
+        }
+        if self.target_throughput.is_none() && self.target_disk_usage.is_none() {
+            return Err("autoscale requires a throughput or disk usage target".to_owned());
+        }
+        if self.target_throughput == Some(0)
+            || self
+                .target_disk_usage
+                .is_some_and(|usage| usage == 0 || usage > 100)
+        {
+            return Err("autoscale targets must be positive, disk usage at most 100".to_owned());
+        }
+        Ok(())
+    }
 }
 
 #[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
@@ -63,6 +107,13 @@ pub struct ReplicationConfig {
 pub struct StorageConfig {
     pub log_dir: Option<String>,
     pub size: Option<String>,
+    /// bytes kept in partitions of topics without a max partition size
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 23)]
+    pub max_partition_size: Option<u64>,
 }
 
 impl StorageConfig {
@@ -75,6 +126,7 @@ impl StorageConfig {
                 .clone()
                 .unwrap_or_else(|| SPU_LOG_BASE_DIR.to_owned()),
             size: self.size.clone().unwrap_or_else(|| SPU_LOG_SIZE.to_owned()),
+            max_partition_size: self.max_partition_size,
         }
     }
 }
@@ -83,6 +135,33 @@ impl StorageConfig {
 pub struct RealStorageConfig {
     pub log_dir: String,
     pub size: String,
+    /// SPUs use their own default if not set
+    pub max_partition_size: Option<u64>,
+}
+
+impl RealStorageConfig {
+    /// size in bytes, with Kubernetes quantity suffixes such as `10Gi`
+    pub fn size_in_bytes(&self) -> Option<u64> {
+        const SUFFIXES: [(&str, u64); 8] = [
+            ("Ki", 1 << 10),
+            ("Mi", 1 << 20),
+            ("Gi", 1 << 30),
+            ("Ti", 1 << 40),
+            ("K", 1_000),
+            ("M", 1_000_000),
+            ("G", 1_000_000_000),
+            ("T", 1_000_000_000_000),
+        ];
+        let size = self.size.trim();
+        let (number, multiplier) = SUFFIXES
+            .iter()
+            .find_map(|(suffix, multiplier)| {
+                size.strip_suffix(suffix)
+                    .map(|number| (number, *multiplier))
+            })
+            .unwrap_or((size, 1));
+        number.parse::<u64>().ok()?.checked_mul(multiplier)
+    }
 }
 
 #[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
@@ -95,3 +174,24 @@ pub struct EnvVar {
     pub name: String,
     pub value: String,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_storage_size_in_bytes() {
+        let size = |size: &str| {
+            RealStorageConfig {
+                log_dir: SPU_LOG_BASE_DIR.to_owned(),
+                size: size.to_owned(),
+                max_partition_size: None,
+            }
+            .size_in_bytes()
+        };
+        assert_eq!(size(SPU_LOG_SIZE), Some(10 << 30));
+        assert_eq!(size("500M"), Some(500_000_000));
+        assert_eq!(size("1024"), Some(1024));
+        assert_eq!(size("big"), None);
+    }
+}
@@ -5,9 +5,13 @@ use fluvio_types::{ReplicationFactor, TopicName, PartitionCount, IgnoreRackAssig
 
 use crate::topic::{
     ReplicaSpec, TopicReplicaParam, SegmentBasedPolicy, CleanupPolicy, TopicStorageConfig,
+    CompactPolicy,
 };
 
-use super::{TopicSpec, PartitionMap, CompressionAlgorithm, deduplication::Deduplication};
+use super::{
+    TopicSpec, PartitionMap, CompressionAlgorithm, deduplication::Deduplication,
+    validation::Validation, dedup_window::DedupWindow,
+};
 
 const DEFAULT_PARTITION_COUNT: PartitionCount = 1;
 const DEFAULT_REPLICATION_FACTOR: ReplicationFactor = 1;
@@ -45,6 +49,27 @@ pub struct TopicConfig {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub deduplication: Option<Deduplication>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub validation: Option<Validation>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub dedup_window: Option<DedupWindow>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub storage: Option<StorageConfig>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -93,6 +118,13 @@ pub struct PartitionConfig {
         serde(skip_serializing_if = "Option::is_none", default)
     )]
     pub maps: Option<Vec<PartitionMap>>,
+
+    /// keep partitions on the tmpfs of the SPUs, for test topics
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub ephemeral: Option<bool>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -117,6 +149,65 @@ pub struct RetentionConfig {
         serde(skip_serializing_if = "Option::is_none", default)
     )]
     pub segment_size: Option<bytesize::ByteSize>,
+
+    /// records removed by the cleaner, `time` does not apply to compacted topics
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub policy: Option<RetentionPolicy>,
+
+    /// how long tombstones of compacted topics are kept
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "humantime_serde",
+            default
+        )
+    )]
+    pub delete_horizon: Option<Duration>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum RetentionPolicy {
+    /// segments older than the retention time
+    #[default]
+    Delete,
+    /// records followed by a record with the same key
+    Compact,
+}
+
+/// Tiered storage of the topic
+#[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct StorageConfig {
+    /// object storage sealed segments are offloaded to, like `s3://bucket/prefix`
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub tier: Option<String>,
+
+    /// how long offloaded segments are kept on local disk
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "humantime_serde",
+            default
+        )
+    )]
+    pub hot_window: Option<Duration>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -128,6 +219,14 @@ pub struct RetentionConfig {
 pub struct CompressionConfig {
     #[cfg_attr(feature = "use_serde", serde(rename = "type", default))]
     pub type_: CompressionAlgorithm,
+
+    /// recompress batches produced with another compression instead of rejecting them
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "crate::is_false")
+    )]
+    pub transcode: bool,
 }
 
 impl TopicConfig {
@@ -154,6 +253,7 @@ impl Default for PartitionConfig {
             ignore_rack_assignment: Some(DEFAULT_IGNORE_RACK_ASSIGMENT),
             max_size: Default::default(),
             maps: Default::default(),
+            ephemeral: Default::default(),
         }
     }
 }
@@ -162,6 +262,17 @@ impl From<TopicConfig> for TopicSpec {
     fn from(config: TopicConfig) -> Self {
         let segment_size = config.retention.segment_size.map(|s| s.as_u64() as u32);
         let max_partition_size = config.partition.max_size.map(|s| s.as_u64());
+        let ephemeral = config.partition.ephemeral.unwrap_or_default();
+        let transcode = config.compression.transcode;
+        let (tier, tier_hot_window_secs) = config
+            .storage
+            .map(|storage| {
+                (
+                    storage.tier,
+                    storage.hot_window.map(|window| window.as_secs() as u32),
+                )
+            })
+            .unwrap_or_default();
 
         let replica_spec = match config.partition.maps {
             Some(maps) => ReplicaSpec::Assigned(maps.into()),
@@ -178,7 +289,13 @@ impl From<TopicConfig> for TopicSpec {
             }),
         };
         let mut topic_spec: TopicSpec = replica_spec.into();
-        if let Some(retention_time) = config.retention.time {
+        if config.retention.policy == Some(RetentionPolicy::Compact) {
+            let mut policy = CompactPolicy::default();
+            if let Some(delete_horizon) = config.retention.delete_horizon {
+                policy.delete_retention_secs = delete_horizon.as_secs() as u32;
+            }
+            topic_spec.set_cleanup_policy(CleanupPolicy::Compact(policy));
+        } else if let Some(retention_time) = config.retention.time {
             topic_spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
                 time_in_seconds: retention_time.as_secs() as u32,
             }));
@@ -186,11 +303,22 @@ impl From<TopicConfig> for TopicSpec {
 
         topic_spec.set_compression_type(config.compression.type_);
         topic_spec.set_deduplication(config.deduplication);
-
-        if segment_size.is_some() || max_partition_size.is_some() {
+        topic_spec.set_validation(config.validation);
+        topic_spec.set_dedup_window(config.dedup_window);
+
+        if segment_size.is_some()
+            || max_partition_size.is_some()
+            || ephemeral
+            || tier.is_some()
+            || transcode
+        {
             topic_spec.set_storage(TopicStorageConfig {
                 segment_size,
                 max_partition_size,
+                ephemeral,
+                tier,
+                tier_hot_window_secs,
+                transcode,
             });
         }
 
@@ -237,6 +365,9 @@ deduplication:
   filter:
     transform:
       uses: infinyon/dedup-filter@0.1.0
+storage:
+  tier: s3://archive/topics
+  hot-window: 1h
 "#;
 
         //when
@@ -331,12 +462,67 @@ compression:
         test_spec.set_storage(TopicStorageConfig {
             segment_size: Some(2000),
             max_partition_size: Some(1000),
+            ephemeral: false,
+            tier: Some("s3://archive/topics".to_owned()),
+            tier_hot_window_secs: Some(3600),
+            transcode: false,
         });
         test_spec.set_deduplication(Some(test_deduplication()));
 
         assert_eq!(spec, test_spec);
     }
 
+    #[cfg(feature = "use_serde")]
+    #[test]
+    fn test_compact_config_to_spec() {
+        //given
+        let input = r#"
+meta:
+  name: test_topic
+retention:
+  time: 2m
+  policy: compact
+  delete-horizon: 1h
+"#;
+
+        //when
+        use std::str::FromStr;
+
+        let config = TopicConfig::from_str(input).expect("deserialized");
+        let spec: TopicSpec = config.into();
+
+        //then
+        assert_eq!(
+            spec.get_clean_policy(),
+            Some(&CleanupPolicy::Compact(CompactPolicy {
+                delete_retention_secs: 3600
+            }))
+        );
+    }
+
+    #[cfg(feature = "use_serde")]
+    #[test]
+    fn test_transcode_config_to_spec() {
+        //given
+        let input = r#"
+meta:
+  name: test_topic
+compression:
+  type: Zstd
+  transcode: true
+"#;
+
+        //when
+        use std::str::FromStr;
+
+        let config = TopicConfig::from_str(input).expect("deserialized");
+        let spec: TopicSpec = config.into();
+
+        //then
+        assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Zstd);
+        assert!(spec.is_compression_transcoded());
+    }
+
     fn test_config() -> TopicConfig {
         TopicConfig {
             version: "0.1.1".to_string(),
@@ -353,15 +539,24 @@ compression:
                     replicas: vec![1, 2],
                     ..Default::default()
                 }]),
+                ephemeral: None,
             },
             retention: RetentionConfig {
                 time: Some(Duration::from_secs(120)),
                 segment_size: Some(bytesize::ByteSize(2000)),
+                ..Default::default()
             },
             compression: CompressionConfig {
                 type_: CompressionAlgorithm::Lz4,
+                ..Default::default()
             },
             deduplication: Some(test_deduplication()),
+            validation: None,
+            dedup_window: None,
+            storage: Some(StorageConfig {
+                tier: Some("s3://archive/topics".to_owned()),
+                hot_window: Some(Duration::from_secs(3600)),
+            }),
         }
     }
 
@@ -0,0 +1,48 @@
+use derive_builder::Builder;
+use fluvio_protocol::{Encoder, Decoder};
+
+use super::deduplication::Bounds;
+
+/// Duplicate detection done by the partition leader without a SmartModule.
+///
+/// A record is dropped when its key, or the hash of its content, was already
+/// seen within the window. The window is kept in memory by the leader and starts
+/// empty when leadership moves.
+#[derive(Debug, Default, Builder, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct DedupWindow {
+    /// at least one of `count` or `age` must be set
+    pub bounds: Bounds,
+    #[builder(default)]
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub by: DedupBy,
+}
+
+impl DedupWindow {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bounds.count == 0 && self.bounds.age.is_none() {
+            return Err("dedup window requires a count or age bound".to_owned());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum DedupBy {
+    /// records with the same key are duplicates, records without key are kept
+    #[default]
+    #[fluvio(tag = 0)]
+    Key,
+    /// records with the same key and value are duplicates
+    #[fluvio(tag = 1)]
+    Hash,
+}
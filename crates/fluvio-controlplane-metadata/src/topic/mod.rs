@@ -1,6 +1,8 @@
 mod spec;
 mod status;
 mod deduplication;
+mod validation;
+mod dedup_window;
 mod update;
 pub mod config;
 
@@ -8,6 +10,8 @@ pub use self::update::*;
 pub use self::spec::*;
 pub use self::status::*;
 pub use self::deduplication::*;
+pub use self::validation::*;
+pub use self::dedup_window::*;
 
 pub const PENDING_REASON: &str = "waiting for live spus";
 
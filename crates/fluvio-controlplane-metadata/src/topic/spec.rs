@@ -6,14 +6,17 @@ use fluvio_protocol::record::ReplicaKey;
 use fluvio_types::defaults::{
     STORAGE_RETENTION_SECONDS, SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN, STORAGE_RETENTION_SECONDS_MIN,
     SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES,
+    STORAGE_COMPACT_DELETE_RETENTION_SECONDS,
 };
 use fluvio_types::SpuId;
 use fluvio_types::{PartitionId, PartitionCount, ReplicationFactor, IgnoreRackAssignment};
-use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::{ByteBuf, Encoder, Decoder};
 
 use crate::partition::{HomePartitionConfig, PartitionMirrorConfig, RemotePartitionConfig};
 
 use super::deduplication::Deduplication;
+use super::validation::Validation;
+use super::dedup_window::DedupWindow;
 
 #[derive(Debug, Clone, PartialEq, Default, Encoder, Decoder)]
 #[cfg_attr(
@@ -39,6 +42,30 @@ pub struct TopicSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 13)]
     system: bool,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 19)]
+    validation: Option<Validation>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    dedup_window: Option<DedupWindow>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 22)]
+    clone_from: Option<String>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 25)]
+    compression_dictionary: Option<CompressionDictionary>,
 }
 
 impl From<ReplicaSpec> for TopicSpec {
@@ -119,6 +146,21 @@ impl TopicSpec {
         &self.compression_type
     }
 
+    pub fn get_compression_dictionary(&self) -> Option<&CompressionDictionary> {
+        self.compression_dictionary.as_ref()
+    }
+
+    pub fn set_compression_dictionary(&mut self, dictionary: Option<CompressionDictionary>) {
+        self.compression_dictionary = dictionary;
+    }
+
+    /// batches are recompressed with the compression type of the topic when produced with another one
+    pub fn is_compression_transcoded(&self) -> bool {
+        self.storage
+            .as_ref()
+            .is_some_and(TopicStorageConfig::is_transcoded)
+    }
+
     pub fn get_storage(&self) -> Option<&TopicStorageConfig> {
         self.storage.as_ref()
     }
@@ -139,6 +181,31 @@ impl TopicSpec {
         self.deduplication = deduplication;
     }
 
+    pub fn get_validation(&self) -> Option<&Validation> {
+        self.validation.as_ref()
+    }
+
+    pub fn set_validation(&mut self, validation: Option<Validation>) {
+        self.validation = validation;
+    }
+
+    pub fn get_dedup_window(&self) -> Option<&DedupWindow> {
+        self.dedup_window.as_ref()
+    }
+
+    pub fn set_dedup_window(&mut self, dedup_window: Option<DedupWindow>) {
+        self.dedup_window = dedup_window;
+    }
+
+    /// topic this topic is a snapshot of
+    pub fn get_clone_from(&self) -> Option<&str> {
+        self.clone_from.as_deref()
+    }
+
+    pub fn set_clone_from(&mut self, clone_from: Option<String>) {
+        self.clone_from = clone_from;
+    }
+
     pub fn is_system(&self) -> bool {
         self.system
     }
@@ -187,6 +254,42 @@ impl TopicSpec {
                     ));
                 }
             }
+            if let Some(tier) = &storage.tier {
+                if storage.ephemeral {
+                    return Some("ephemeral topics can't be tiered".to_owned());
+                }
+                if self
+                    .get_clean_policy()
+                    .is_some_and(CleanupPolicy::is_compact)
+                {
+                    return Some("compacted topics can't be tiered".to_owned());
+                }
+                if let Err(err) = validate_tier(tier) {
+                    return Some(err);
+                }
+            }
+            if storage.transcode && self.compression_type == CompressionAlgorithm::Any {
+                return Some("transcoding requires a compression type other than any".to_owned());
+            }
+        }
+
+        if let Some(Err(err)) = self.dedup_window.as_ref().map(DedupWindow::validate) {
+            return Some(err);
+        }
+
+        if let Some(dictionary) = &self.compression_dictionary {
+            if dictionary.id().is_none() {
+                return Some("compression dictionary is not a trained zstd dictionary".to_owned());
+            }
+            if !matches!(
+                self.compression_type,
+                CompressionAlgorithm::Any | CompressionAlgorithm::Zstd
+            ) {
+                return Some(format!(
+                    "compression dictionary requires zstd compression, not {}",
+                    self.compression_type
+                ));
+            }
         }
 
         None
@@ -940,6 +1043,10 @@ pub enum CleanupPolicy {
     #[cfg_attr(feature = "use_serde", serde(rename = "segment"))]
     #[fluvio(tag = 0)]
     Segment(SegmentBasedPolicy),
+    /// keep only the latest record of each key
+    #[cfg_attr(feature = "use_serde", serde(rename = "compact"))]
+    #[fluvio(tag = 1)]
+    Compact(CompactPolicy),
 }
 
 impl Default for CleanupPolicy {
@@ -952,8 +1059,13 @@ impl CleanupPolicy {
     pub fn retention_secs(&self) -> u32 {
         match self {
             CleanupPolicy::Segment(policy) => policy.retention_secs(),
+            CleanupPolicy::Compact(policy) => policy.retention_secs(),
         }
     }
+
+    pub fn is_compact(&self) -> bool {
+        matches!(self, CleanupPolicy::Compact(_))
+    }
 }
 
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
@@ -972,6 +1084,34 @@ impl SegmentBasedPolicy {
     }
 }
 
+/// Compaction of keyed topics. Records without a key are kept.
+#[derive(Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CompactPolicy {
+    /// seconds tombstones, records with a key and an empty value, are kept once their
+    /// segment is sealed
+    pub delete_retention_secs: u32,
+}
+
+impl Default for CompactPolicy {
+    fn default() -> Self {
+        Self {
+            delete_retention_secs: STORAGE_COMPACT_DELETE_RETENTION_SECONDS,
+        }
+    }
+}
+
+impl CompactPolicy {
+    /// latest records of compacted topics don't expire
+    pub fn retention_secs(&self) -> u32 {
+        u32::MAX
+    }
+}
+
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "use_serde",
@@ -981,6 +1121,67 @@ impl SegmentBasedPolicy {
 pub struct TopicStorageConfig {
     pub segment_size: Option<u32>,       // segment size
     pub max_partition_size: Option<u64>, // max partition size
+    /// keep data on the tmpfs of the SPU, it is removed when the SPU restarts
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "crate::is_false")
+    )]
+    #[fluvio(min_version = 21)]
+    pub ephemeral: bool,
+    /// object storage sealed segments are offloaded to, like `s3://bucket/prefix`
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 23)]
+    pub tier: Option<String>,
+    /// seconds offloaded segments are kept on local disk
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 23)]
+    pub tier_hot_window_secs: Option<u32>,
+    /// batches compressed with another codec than the compression type of the topic
+    /// are recompressed with it instead of being rejected
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "crate::is_false")
+    )]
+    #[fluvio(min_version = 24)]
+    pub transcode: bool,
+}
+
+impl TopicStorageConfig {
+    pub fn is_ephemeral(&self) -> bool {
+        self.ephemeral
+    }
+
+    pub fn is_tiered(&self) -> bool {
+        self.tier.is_some()
+    }
+
+    pub fn is_transcoded(&self) -> bool {
+        self.transcode
+    }
+}
+
+/// schemes of the object storage locations of tiered topics
+pub const STORAGE_TIER_SCHEMES: &[&str] = &["s3", "gs", "file"];
+
+/// check tier location is `<scheme>://<bucket or path>` with a supported scheme
+fn validate_tier(tier: &str) -> Result<(), String> {
+    match tier.split_once("://") {
+        Some((scheme, location))
+            if STORAGE_TIER_SCHEMES.contains(&scheme) && !location.trim_matches('/').is_empty() =>
+        {
+            Ok(())
+        }
+        _ => Err(format!(
+            "tier {tier} is not a location like s3://bucket/prefix, supported schemes: {}",
+            STORAGE_TIER_SCHEMES.join(", ")
+        )),
+    }
 }
 
 #[derive(Decoder, Default, Encoder, Debug, Clone, Eq, PartialEq)]
@@ -1033,12 +1234,72 @@ impl std::fmt::Display for CompressionAlgorithm {
     }
 }
 
+/// first bytes of a trained zstd dictionary, followed by its id
+const ZSTD_DICTIONARY_MAGIC: [u8; 4] = [0x37, 0xA4, 0x30, 0xEC];
+
+/// Trained zstd dictionary of a topic, records are compressed with it by the producers
+/// using zstd. Clients and SPUs get it with the topic to decompress them.
+#[derive(Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+pub struct CompressionDictionary(ByteBuf);
+
+impl std::fmt::Debug for CompressionDictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompressionDictionary")
+            .field("id", &self.id())
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+impl CompressionDictionary {
+    pub fn new(dictionary: Vec<u8>) -> Self {
+        Self(ByteBuf::from(dictionary))
+    }
+
+    /// id of the dictionary, `None` if it is not a trained zstd dictionary
+    pub fn id(&self) -> Option<u32> {
+        let header = self.0.get(..8)?;
+        if header[..4] != ZSTD_DICTIONARY_MAGIC {
+            return None;
+        }
+        Some(u32::from_le_bytes([
+            header[4], header[5], header[6], header[7],
+        ]))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "use_serde")]
+impl serde::Serialize for CompressionDictionary {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use base64::Engine;
+
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(self.as_bytes()))
+    }
+}
+
+#[cfg(feature = "use_serde")]
+impl<'de> serde::Deserialize<'de> for CompressionDictionary {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use base64::Engine;
+
+        let encoded = String::deserialize(deserializer)?;
+        let dictionary = base64::engine::general_purpose::STANDARD
+            .decode(encoded.as_bytes())
+            .map_err(serde::de::Error::custom)?;
+        Ok(Self::new(dictionary))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use std::io::Cursor;
 
-    use crate::topic::{Bounds, Filter, Transform};
+    use crate::topic::{Bounds, DedupBy, Filter, OnInvalidRecord, Transform};
 
     use super::*;
 
@@ -1253,6 +1514,178 @@ mod test {
         assert!(topic_spec_decoded.deduplication.is_none());
     }
 
+    #[test]
+    fn test_topic_with_validation_prev_version_compatibility() {
+        //given
+        let prev_version = 18;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        let validation = Validation {
+            transform: Transform {
+                uses: "validator".to_string(),
+                ..Default::default()
+            },
+            on_invalid: OnInvalidRecord::DeadLetter {
+                topic: "dlq".to_string(),
+            },
+        };
+        topic_spec.set_validation(Some(validation.clone()));
+
+        //when
+        let mut prev = vec![];
+        topic_spec.encode(&mut prev, prev_version).expect("encoded");
+        let mut prev_decoded = TopicSpec::default();
+        prev_decoded
+            .decode(&mut Cursor::new(&prev), prev_version)
+            .expect("decoded");
+        let mut current = vec![];
+        topic_spec
+            .encode(&mut current, prev_version + 1)
+            .expect("encoded");
+        let mut current_decoded = TopicSpec::default();
+        current_decoded
+            .decode(&mut Cursor::new(&current), prev_version + 1)
+            .expect("decoded");
+
+        //then
+        assert!(prev_decoded.validation.is_none());
+        assert_eq!(current_decoded.get_validation(), Some(&validation));
+    }
+
+    #[test]
+    fn test_topic_with_dedup_window_prev_version_compatibility() {
+        //given
+        let prev_version = 19;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        let dedup_window = DedupWindow {
+            bounds: Bounds {
+                count: 100,
+                age: Some(std::time::Duration::from_secs(300)),
+            },
+            by: DedupBy::Hash,
+        };
+        topic_spec.set_dedup_window(Some(dedup_window.clone()));
+
+        //when
+        let mut prev = vec![];
+        topic_spec.encode(&mut prev, prev_version).expect("encoded");
+        let mut prev_decoded = TopicSpec::default();
+        prev_decoded
+            .decode(&mut Cursor::new(&prev), prev_version)
+            .expect("decoded");
+        let mut current = vec![];
+        topic_spec
+            .encode(&mut current, prev_version + 1)
+            .expect("encoded");
+        let mut current_decoded = TopicSpec::default();
+        current_decoded
+            .decode(&mut Cursor::new(&current), prev_version + 1)
+            .expect("decoded");
+
+        //then
+        assert!(prev_decoded.dedup_window.is_none());
+        assert_eq!(current_decoded.get_dedup_window(), Some(&dedup_window));
+    }
+
+    #[test]
+    fn test_dedup_window_requires_bound() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_dedup_window(Some(DedupWindow::default()));
+        assert!(topic_spec.validate_config().is_some());
+
+        topic_spec.set_dedup_window(Some(DedupWindow {
+            bounds: Bounds {
+                count: 10,
+                age: None,
+            },
+            by: DedupBy::Key,
+        }));
+        assert!(topic_spec.validate_config().is_none());
+    }
+
+    #[test]
+    fn test_validate_tier() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        let mut storage = TopicStorageConfig {
+            tier: Some("s3://archive/orders".to_owned()),
+            ..Default::default()
+        };
+        topic_spec.set_storage(storage.clone());
+        assert!(topic_spec.validate_config().is_none());
+
+        storage.tier = Some("archive/orders".to_owned());
+        topic_spec.set_storage(storage.clone());
+        assert!(topic_spec.validate_config().is_some());
+
+        storage.tier = Some("s3://".to_owned());
+        topic_spec.set_storage(storage.clone());
+        assert!(topic_spec.validate_config().is_some());
+
+        storage.tier = Some("gs://archive".to_owned());
+        storage.ephemeral = true;
+        topic_spec.set_storage(storage);
+        assert!(topic_spec.validate_config().is_some());
+    }
+
+    #[test]
+    fn test_compression_dictionary() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_compression_dictionary(Some(CompressionDictionary::new(b"raw".to_vec())));
+        assert!(topic_spec.validate_config().is_some());
+
+        let dictionary =
+            CompressionDictionary::new(vec![0x37, 0xA4, 0x30, 0xEC, 0x2A, 0x00, 0x00, 0x00, 0x01]);
+        assert_eq!(dictionary.id(), Some(42));
+        topic_spec.set_compression_dictionary(Some(dictionary.clone()));
+        assert!(topic_spec.validate_config().is_none());
+
+        topic_spec.set_compression_type(CompressionAlgorithm::Gzip);
+        assert!(topic_spec.validate_config().is_some());
+
+        topic_spec.set_compression_type(CompressionAlgorithm::Zstd);
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 25).expect("encode");
+        let decoded = TopicSpec::decode_from(&mut Cursor::new(&dest), 25).expect("decode");
+        assert_eq!(decoded.get_compression_dictionary(), Some(&dictionary));
+    }
+
+    #[test]
+    fn test_validate_transcode() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_storage(TopicStorageConfig {
+            transcode: true,
+            ..Default::default()
+        });
+        assert!(topic_spec.is_compression_transcoded());
+        assert!(topic_spec.validate_config().is_some());
+
+        topic_spec.set_compression_type(CompressionAlgorithm::Zstd);
+        assert!(topic_spec.validate_config().is_none());
+    }
+
+    #[test]
+    fn test_compact_policy() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_cleanup_policy(CleanupPolicy::Compact(CompactPolicy::default()));
+        assert!(topic_spec.validate_config().is_none());
+        assert_eq!(topic_spec.retention_secs(), u32::MAX);
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 0).expect("encode");
+        let decoded = TopicSpec::decode_from(&mut Cursor::new(&dest), 0).expect("decode");
+        assert_eq!(
+            decoded.get_clean_policy(),
+            Some(&CleanupPolicy::Compact(CompactPolicy {
+                delete_retention_secs: 24 * 3600
+            }))
+        );
+
+        topic_spec.set_storage(TopicStorageConfig {
+            tier: Some("s3://archive/orders".to_owned()),
+            ..Default::default()
+        });
+        assert!(topic_spec.validate_config().is_some());
+    }
+
     #[test]
     fn test_partition_map_str() {
         // Test multiple
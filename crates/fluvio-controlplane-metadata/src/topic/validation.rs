@@ -0,0 +1,46 @@
+use derive_builder::Builder;
+use fluvio_protocol::{Encoder, Decoder};
+
+use super::deduplication::Transform;
+
+/// SmartModule run by the partition leader on produced records before they are appended.
+///
+/// Records the module filters out or fails on are invalid. A produce request with an
+/// invalid record is not appended, and handled according to `on-invalid`.
+#[derive(Debug, Default, Builder, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct Validation {
+    pub transform: Transform,
+    #[builder(default)]
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub on_invalid: OnInvalidRecord,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum OnInvalidRecord {
+    /// return the validation error to the producer
+    #[default]
+    #[fluvio(tag = 0)]
+    Reject,
+    /// produce the records to the dead letter topic instead
+    #[fluvio(tag = 1)]
+    DeadLetter { topic: String },
+}
+
+impl OnInvalidRecord {
+    pub fn dead_letter_topic(&self) -> Option<&str> {
+        match self {
+            Self::Reject => None,
+            Self::DeadLetter { topic } => Some(topic),
+        }
+    }
+}
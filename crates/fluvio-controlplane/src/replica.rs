@@ -3,7 +3,10 @@
 use std::fmt;
 
 use fluvio_controlplane_metadata::{
-    topic::{CleanupPolicy, TopicStorageConfig, CompressionAlgorithm, Deduplication},
+    topic::{
+        CleanupPolicy, TopicStorageConfig, CompressionAlgorithm, CompressionDictionary,
+        Deduplication, Validation, DedupWindow,
+    },
     core::MetadataItem,
     store::MetadataStoreObject,
     partition::{PartitionSpec, PartitionMirrorConfig},
@@ -25,6 +28,11 @@ pub struct Replica {
     pub storage: Option<TopicStorageConfig>,
     pub compression_type: CompressionAlgorithm,
     pub deduplication: Option<Deduplication>,
+    pub validation: Option<Validation>,
+    pub dedup_window: Option<DedupWindow>,
+    /// replica copied into this replica when it is created
+    pub clone_from: Option<ReplicaKey>,
+    pub compression_dictionary: Option<CompressionDictionary>,
 }
 
 impl Replica {
@@ -59,6 +67,9 @@ where
             inner.status.is_being_deleted || inner.ctx().item().is_being_deleted();
 
         let spec = inner.spec;
+        let clone_from = spec
+            .clone_from
+            .map(|topic| ReplicaKey::new(topic, inner.key.partition));
         Self {
             id: inner.key,
             leader: spec.leader,
@@ -69,6 +80,10 @@ where
             storage: spec.storage,
             compression_type: spec.compression_type,
             deduplication: spec.deduplication,
+            validation: spec.validation,
+            dedup_window: spec.dedup_window,
+            clone_from,
+            compression_dictionary: spec.compression_dictionary,
         }
     }
 }
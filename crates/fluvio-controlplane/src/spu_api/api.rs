@@ -10,6 +10,7 @@ use fluvio_protocol::Encoder;
 use fluvio_protocol::Decoder;
 
 use super::update_mirror::UpdateMirrorRequest;
+use super::update_acl::UpdateAclRequest;
 use super::update_spu::UpdateSpuRequest;
 use super::update_replica::UpdateReplicaRequest;
 use super::update_smartmodule::UpdateSmartModuleRequest;
@@ -23,6 +24,7 @@ pub enum InternalSpuApi {
     UpdateSmartModule = 1003,
     // UpdateDerivedStream = 1004,
     UpdateMirror = 1004,
+    UpdateAcl = 1005,
 }
 
 impl Default for InternalSpuApi {
@@ -41,6 +43,8 @@ pub enum InternalSpuRequest {
     UpdateSmartModuleRequest(RequestMessage<UpdateSmartModuleRequest>),
     #[fluvio(tag = 3)]
     UpdateMirrorRequest(RequestMessage<UpdateMirrorRequest>),
+    #[fluvio(tag = 4)]
+    UpdateAclRequest(RequestMessage<UpdateAclRequest>),
 }
 
 // Added to satisfy Encoder/Decoder traits
@@ -74,6 +78,7 @@ impl ApiMessage for InternalSpuRequest {
             InternalSpuApi::UpdateMirror => {
                 api_decode!(Self, UpdateMirrorRequest, src, header)
             }
+            InternalSpuApi::UpdateAcl => api_decode!(Self, UpdateAclRequest, src, header),
         }
     }
 }
@@ -0,0 +1,41 @@
+use fluvio_controlplane_metadata::{
+    core::MetadataItem,
+    message::{Message, Messages},
+    acl::AclSpec,
+    store::MetadataStoreObject,
+};
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use crate::requests::ControlPlaneRequest;
+
+use super::api::InternalSpuApi;
+
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct Acl {
+    pub name: String,
+    pub spec: AclSpec,
+}
+
+pub type UpdateAclRequest = ControlPlaneRequest<Acl>;
+
+impl Request for UpdateAclRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateAcl as u16;
+    type Response = UpdateAclResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateAclResponse {}
+
+pub type AclMsg = Message<Acl>;
+pub type AclMsgs = Messages<Acl>;
+
+impl<C> From<MetadataStoreObject<AclSpec, C>> for Acl
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<AclSpec, C>) -> Self {
+        let name = mso.key;
+        let spec = mso.spec;
+        Self { name, spec }
+    }
+}
@@ -20,6 +20,8 @@ pub type UpdateMirrorRequest = ControlPlaneRequest<Mirror>;
 
 impl Request for UpdateMirrorRequest {
     const API_KEY: u16 = InternalSpuApi::UpdateMirror as u16;
+    const DEFAULT_API_VERSION: i16 = 27; // align with pubic api to get version encoding
+    const MIN_API_VERSION: i16 = 0;
     type Response = UpdateMirrorResponse;
 }
 
@@ -40,7 +40,10 @@ macro_rules! t_print_cli_err {
 ///
 /// This allows `fluvio` to include external plugins in the help
 /// menu, version printouts, and automatic updates.
+///
+/// Build it with [`FluvioExtensionMetadata::new`], fields may be added in the future.
 #[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct FluvioExtensionMetadata {
     /// The title is a human-readable pretty name
     #[serde(alias = "command")]
@@ -54,6 +57,37 @@ pub struct FluvioExtensionMetadata {
     pub description: String,
     /// The version of this plugin
     pub version: semver::Version,
+    /// Versions of the Fluvio CLI this plugin works with, any if not provided
+    ///
+    /// Example: `>=0.11, <0.13`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fluvio_version: Option<semver::VersionReq>,
+}
+
+impl FluvioExtensionMetadata {
+    pub fn new(
+        title: impl Into<String>,
+        description: impl Into<String>,
+        version: semver::Version,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            package: None,
+            description: description.into(),
+            version,
+            fluvio_version: None,
+        }
+    }
+
+    pub fn with_package(mut self, package: PackageId<MaybeVersion>) -> Self {
+        self.package = Some(package);
+        self
+    }
+
+    pub fn with_fluvio_version(mut self, fluvio_version: semver::VersionReq) -> Self {
+        self.fluvio_version = Some(fluvio_version);
+        self
+    }
 }
 
 #[derive(Debug)]
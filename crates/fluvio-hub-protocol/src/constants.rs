@@ -6,6 +6,13 @@ pub const CLI_CONFIG_HUB: &str = "hub";
 pub const HUB_API_V: &str = "hub/v0";
 pub const HUB_API_ACT: &str = concatcp!(HUB_API_V, "/action");
 pub const HUB_API_HUBID: &str = concatcp!(HUB_API_V, "/hubid");
+pub const HUB_API_ORG: &str = concatcp!(HUB_API_V, "/org");
+
+/// refresh of cloud login tokens, on the cloud remote of the login
+pub const CLOUD_API_TOKEN_REFRESH: &str = "api/v1/token/refresh";
+/// device authorization login, on the cloud remote
+pub const CLOUD_API_DEVICE_CODE: &str = "api/v1/oauth/device/code";
+pub const CLOUD_API_DEVICE_TOKEN: &str = "api/v1/oauth/device/token";
 
 // sm specific api
 pub const HUB_API_SM: &str = concatcp!(HUB_API_V, "/pkg/pub");
@@ -28,6 +35,7 @@ pub const HUB_PACKAGE_EXT: &str = "ipkg";
 pub const HUB_PACKAGE_META: &str = "package-meta.yaml";
 pub const HUB_PACKAGE_META_CLEAN: &str = "package-meta-clean.yaml";
 pub const HUB_PACKAGE_VERSION: &str = "0.3";
+pub const HUB_PROVENANCE: &str = "provenance.json";
 pub const HUB_REMOTE: &str = "https://hub.infinyon.cloud";
 pub const HUB_SIGNFILE_BASE: &str = "signature";
 
@@ -0,0 +1,145 @@
+//
+// messages of the OAuth device authorization flow (RFC 8628) used to log in to a cloud
+// remote without a browser on the host: the client asks for a device code, the user
+// approves it on another device and the client polls for the login token meanwhile
+//
+use serde::{Deserialize, Serialize};
+
+use crate::{HubError, Result};
+
+/// client id of the fluvio cli on cloud remotes
+pub const DEVICE_CLIENT_ID: &str = "fluvio-cli";
+
+/// seconds between token polls when the remote doesn't set them
+const DEFAULT_POLL_INTERVAL: u64 = 5;
+/// added to the poll interval each time the remote asks to slow down
+const SLOW_DOWN_SECS: u64 = 5;
+
+#[derive(Debug, Serialize)]
+pub struct DeviceCodeRequest {
+    pub client_id: String,
+}
+
+/// Device code issued by the remote, to be approved by the user
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    /// verification uri with the user code filled in
+    #[serde(default)]
+    pub verification_uri_complete: Option<String>,
+    /// seconds the device code is valid for
+    pub expires_in: u64,
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+fn default_interval() -> u64 {
+    DEFAULT_POLL_INTERVAL
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeviceTokenRequest {
+    pub client_id: String,
+    pub device_code: String,
+    pub grant_type: String,
+}
+
+impl DeviceTokenRequest {
+    pub fn new(device_code: &str) -> Self {
+        Self {
+            client_id: DEVICE_CLIENT_ID.to_owned(),
+            device_code: device_code.to_owned(),
+            grant_type: "urn:ietf:params:oauth:grant-type:device_code".to_owned(),
+        }
+    }
+}
+
+/// Login granted once the user approved the device code
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceLogin {
+    #[serde(alias = "access_token")]
+    pub token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// seconds the token is valid for
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+    pub email: String,
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceTokenError {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Outcome of a token poll
+#[derive(Debug)]
+pub enum DevicePoll {
+    /// not approved yet, poll again after the interval
+    Pending,
+    /// poll again after the interval, increased by the returned seconds
+    SlowDown(u64),
+    Granted(DeviceLogin),
+}
+
+impl DevicePoll {
+    /// decode the response to a token poll, `success` for 2xx statuses
+    pub fn from_response(success: bool, body: &[u8]) -> Result<Self> {
+        if success {
+            return Ok(Self::Granted(serde_json::from_slice(body)?));
+        }
+        let err: DeviceTokenError = serde_json::from_slice(body)
+            .map_err(|_| HubError::HubAccess("unexpected device login response".to_owned()))?;
+        match err.error.as_str() {
+            "authorization_pending" => Ok(Self::Pending),
+            "slow_down" => Ok(Self::SlowDown(SLOW_DOWN_SECS)),
+            "access_denied" => Err(HubError::HubAccess("login denied".to_owned())),
+            "expired_token" => Err(HubError::HubAccess(
+                "login code expired, try logging in again".to_owned(),
+            )),
+            other => Err(HubError::HubAccess(format!(
+                "login failed: {}",
+                err.error_description.as_deref().unwrap_or(other)
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_token_poll() {
+        let auth: DeviceAuthorization = serde_json::from_str(
+            r#"{"device_code":"dc","user_code":"ABCD-EFGH","verification_uri":"https://infinyon.cloud/device","expires_in":600}"#,
+        )
+        .expect("authorization");
+        assert_eq!(auth.interval, DEFAULT_POLL_INTERVAL);
+
+        let pending = br#"{"error":"authorization_pending"}"#;
+        assert!(matches!(
+            DevicePoll::from_response(false, pending),
+            Ok(DevicePoll::Pending)
+        ));
+        let slow_down = br#"{"error":"slow_down"}"#;
+        assert!(matches!(
+            DevicePoll::from_response(false, slow_down),
+            Ok(DevicePoll::SlowDown(SLOW_DOWN_SECS))
+        ));
+        let denied = br#"{"error":"access_denied"}"#;
+        assert!(DevicePoll::from_response(false, denied).is_err());
+
+        let granted = br#"{"access_token":"tok","refresh_token":"ref","expires_in":3600,"email":"a@b.c","id":"1"}"#;
+        let Ok(DevicePoll::Granted(login)) = DevicePoll::from_response(true, granted) else {
+            panic!("login not granted");
+        };
+        assert_eq!(login.token, "tok");
+        assert_eq!(login.refresh_token.as_deref(), Some("ref"));
+    }
+}
@@ -2,19 +2,56 @@
 // minimal login token read module that just exposes a
 // 'read_infinyon_token' function to read from the current login config
 //
+// With the `keyring` feature, login tokens are kept in the OS keyring (macOS Keychain,
+// Windows Credential Manager, Secret Service on Linux) and the login files only keep
+// the remote, email and id. Plaintext tokens of existing logins are moved to the keyring
+// the first time they are read. Without a usable keyring, e.g. on a headless Linux host
+// without Secret Service, tokens stay in the login files.
+//
+// Expired login tokens, by the expiry stored with the login or the `exp` claim of JWT
+// tokens, are reported as `InfinyonCredentialError::Expired` so callers can refresh them
+// with the refresh token of the login, see `read_refresh_token`.
+//
+// Logins of users in several orgs keep a token per org. The org is picked by the caller,
+// then `INFINYON_ORG`, then the current org of the login; without org the login token
+// is used.
+//
+// Logins are read with async file IO, the `_async` functions can be called from async
+// code without stalling its executor; the sync functions block on them.
+//
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+#[cfg(not(target_arch = "wasm32"))]
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use fluvio_types::defaults::CLI_CONFIG_PATH;
 
+use crate::device_auth::DeviceLogin;
+
 const INFINYON_CONFIG_PATH_ENV: &str = "INFINYON_CONFIG_PATH";
 const DEFAULT_LOGINS_DIR: &str = "logins"; // from logins.rs
 const CURRENT_LOGIN_FILE_NAME: &str = "current";
 
+/// Org whose token is used instead of the current org of the login
+pub const INFINYON_ORG_ENV: &str = "INFINYON_ORG";
+
+/// Scoped hub token used instead of the login credentials, e.g. by CI systems
+pub const INFINYON_HUB_TOKEN_ENV: &str = "INFINYON_HUB_TOKEN";
+const SCOPED_TOKEN_PREFIX: &str = "ihub1";
+const SCOPED_TOKEN_ANY_GROUP: &str = "*";
+/// tokens expiring within this many seconds are treated as expired
+const EXPIRY_MARGIN_SECS: u64 = 30;
+
 type InfinyonToken = String;
 type InfinyonRemote = String;
 
@@ -25,36 +62,329 @@ pub enum InfinyonCredentialError {
 
     #[error("unable to parse credentials")]
     UnableToParseCredentials,
+
+    #[error("invalid scoped hub token, {0}")]
+    InvalidScopedToken(String),
+
+    #[error("scoped hub token does not allow {0}")]
+    OutOfScope(String),
+
+    #[error("login to {0} expired, try 'fluvio cloud login'")]
+    Expired(InfinyonRemote),
+
+    #[error("no token for org {0} in the login, try 'fluvio cloud login'")]
+    OrgNotFound(String),
 }
 
-pub fn read_infinyon_token() -> Result<InfinyonToken, InfinyonCredentialError> {
-    // the ENV variable should point directly to the applicable profile
-    if let Ok(profilepath) = env::var(INFINYON_CONFIG_PATH_ENV) {
-        let cred = Credentials::load(Path::new(&profilepath))?;
-        debug!("{INFINYON_CONFIG_PATH_ENV} {profilepath} loaded");
-        return Ok(cred.token);
-    }
-    let cfgpath = default_file_path();
-    // this will read the indirection file to resolve the profile
-    let cred = Credentials::try_load(cfgpath)?;
-    Ok(cred.token)
+/// Read the token of the current login, for `org` when given
+pub fn read_infinyon_token(org: Option<&str>) -> Result<InfinyonToken, InfinyonCredentialError> {
+    resolve_login(None)?.token_for(org)
+}
+
+/// Read the token of the current login without blocking, for `org` when given
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn read_infinyon_token_async(
+    org: Option<&str>,
+) -> Result<InfinyonToken, InfinyonCredentialError> {
+    resolve_login_async(None).await?.token_for(org)
+}
+
+/// Read the token of `org` in the current login
+pub fn get_org_token(org: &str) -> Result<InfinyonToken, InfinyonCredentialError> {
+    read_infinyon_token(Some(org))
+}
+
+/// Org used by default, from `INFINYON_ORG` or the current org of the login
+pub fn read_current_org() -> Option<String> {
+    resolve_login(None).ok()?.select_org(None)
 }
 
 pub fn read_infinyon_token_rem() -> Result<(InfinyonToken, InfinyonRemote), InfinyonCredentialError>
 {
+    let cred = resolve_login(None)?;
+    Ok((cred.token_for(None)?, cred.remote))
+}
+
+/// Read the token of the login for `remote`
+///
+/// Logins are kept side by side, one per remote, in the logins directory. The login
+/// whose remote is the same host as `remote`, or a parent domain of it like
+/// `infinyon.cloud` for `hub.infinyon.cloud`, is used, otherwise the current login.
+pub fn read_infinyon_token_for_remote(
+    remote: &str,
+) -> Result<(InfinyonToken, InfinyonRemote), InfinyonCredentialError> {
+    let cred = resolve_login(Some(remote))?;
+    Ok((cred.token_for(None)?, cred.remote))
+}
+
+/// Read the token of the login for `remote` without blocking
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn read_infinyon_token_for_remote_async(
+    remote: &str,
+) -> Result<(InfinyonToken, InfinyonRemote), InfinyonCredentialError> {
+    let cred = resolve_login_async(Some(remote)).await?;
+    Ok((cred.token_for(None)?, cred.remote))
+}
+
+/// Token obtained by refreshing an expired login
+#[derive(Debug, Clone, Deserialize)]
+pub struct RefreshedToken {
+    pub token: InfinyonToken,
+    /// replaces the refresh token of the login when the remote rotates them
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// seconds since the unix epoch
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+/// Refresh token of the login for `remote`, if the login has one
+pub fn read_refresh_token(remote: &str) -> Option<String> {
+    resolve_login(Some(remote)).ok()?.refresh_token
+}
+
+/// Refresh token of the login for `remote` without blocking
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn read_refresh_token_async(remote: &str) -> Option<String> {
+    resolve_login_async(Some(remote)).await.ok()?.refresh_token
+}
+
+/// Replace the token of the login for `remote` with a refreshed one
+pub fn update_infinyon_token(
+    remote: &str,
+    refreshed: &RefreshedToken,
+) -> Result<(), InfinyonCredentialError> {
+    let mut cred = resolve_login(Some(remote))?;
+    cred.token = refreshed.token.clone();
+    if let Some(refresh_token) = &refreshed.refresh_token {
+        cred.refresh_token = Some(refresh_token.clone());
+    }
+    cred.expires_at = refreshed.expires_at;
+    cred.write(&cred.path)
+        .map_err(|err| InfinyonCredentialError::Read(format!("unable to store login: {err}")))
+}
+
+/// The login for `remote`, the current login without remote
+#[cfg(not(target_arch = "wasm32"))]
+fn resolve_login(remote: Option<&str>) -> Result<Credentials, InfinyonCredentialError> {
+    fluvio_future::task::run_block_on(resolve_login_async(remote))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn resolve_login(_remote: Option<&str>) -> Result<Credentials, InfinyonCredentialError> {
+    Err(InfinyonCredentialError::Read(
+        "Support for reading logins is not available for `wasm32`".to_owned(),
+    ))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn resolve_login_async(remote: Option<&str>) -> Result<Credentials, InfinyonCredentialError> {
     // the ENV variable should point directly to the applicable profile
     if let Ok(profilepath) = env::var(INFINYON_CONFIG_PATH_ENV) {
-        let cred = Credentials::load(Path::new(&profilepath))?;
+        let cred = Credentials::load_async(Path::new(&profilepath)).await?;
         debug!(
             path = profilepath,
             "profile loaded from INFINYON_CONFIG_PATH_ENV"
         );
-        return Ok((cred.token, cred.remote));
+        return Ok(cred);
     }
     let cfgpath = default_file_path();
+    if let Some(remote) = remote {
+        if let Some(cred) = Credentials::find_for_remote_async(Path::new(&cfgpath), remote).await {
+            debug!(remote = cred.remote, "login selected for {remote}");
+            return Ok(cred);
+        }
+    }
     // this will read the indirection file to resolve the profile
-    let cred = Credentials::try_load(cfgpath)?;
-    Ok((cred.token, cred.remote))
+    Credentials::try_load_async(cfgpath).await
+}
+
+/// Store the login for a remote next to the logins of other remotes
+///
+/// The login becomes the current one only if there was none.
+pub fn store_infinyon_login(
+    remote: &str,
+    email: &str,
+    id: &str,
+    token: &str,
+) -> Result<(), InfinyonCredentialError> {
+    let cred = Credentials {
+        remote: remote.to_owned(),
+        email: email.to_owned(),
+        id: id.to_owned(),
+        token: token.to_owned(),
+        refresh_token: None,
+        expires_at: None,
+        current_org: None,
+        org_tokens: BTreeMap::new(),
+        keyring: false,
+        path: PathBuf::new(),
+    };
+    cred.store(Path::new(&default_file_path()))
+}
+
+/// Store a login granted by the device authorization flow
+pub fn store_device_login(
+    remote: &str,
+    login: &DeviceLogin,
+) -> Result<(), InfinyonCredentialError> {
+    let expires_at = match login.expires_in {
+        Some(expires_in) => Some(now_secs() + expires_in),
+        None => jwt_expiry(&login.token),
+    };
+    let cred = Credentials {
+        remote: remote.to_owned(),
+        email: login.email.clone(),
+        id: login.id.clone(),
+        token: login.token.clone(),
+        refresh_token: login.refresh_token.clone(),
+        expires_at,
+        current_org: None,
+        org_tokens: BTreeMap::new(),
+        keyring: false,
+        path: PathBuf::new(),
+    };
+    cred.store(Path::new(&default_file_path()))
+}
+
+/// Read the scoped hub token from `INFINYON_HUB_TOKEN` if set
+pub fn read_scoped_token() -> Result<Option<ScopedToken>, InfinyonCredentialError> {
+    match env::var(INFINYON_HUB_TOKEN_ENV) {
+        Ok(token) => {
+            debug!("{INFINYON_HUB_TOKEN_ENV} scoped token loaded");
+            token.trim().parse().map(Some)
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Operations a scoped token may be limited to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HubTokenOp {
+    /// list and download packages
+    Download,
+    /// publish packages
+    Publish,
+}
+
+impl fmt::Display for HubTokenOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Download => write!(f, "download"),
+            Self::Publish => write!(f, "publish"),
+        }
+    }
+}
+
+/// Hub token limited to some operations on the packages of some groups
+///
+/// The scope is encoded in the token itself so it can be checked before reaching the
+/// hub, which validates the secret part:
+/// `ihub1.<ops>.<groups>.<expires>.<secret>`, where ops and groups are separated by `+`,
+/// groups may be `*` for any group and expires is a unix timestamp or `0` for never.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopedToken {
+    ops: Vec<HubTokenOp>,
+    groups: Vec<String>,
+    expires_at: Option<u64>,
+    token: String,
+}
+
+impl ScopedToken {
+    pub fn ops(&self) -> &[HubTokenOp] {
+        &self.ops
+    }
+
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    /// seconds since the unix epoch
+    pub fn expires_at(&self) -> Option<u64> {
+        self.expires_at
+    }
+
+    /// The full token, sent to the hub as authorization
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.map(|at| at <= now_secs()).unwrap_or(false)
+    }
+
+    /// Check the token is not expired and allows `op`
+    pub fn check_op(&self, op: HubTokenOp) -> Result<(), InfinyonCredentialError> {
+        if self.is_expired() {
+            return Err(InfinyonCredentialError::InvalidScopedToken(
+                "it is expired".to_owned(),
+            ));
+        }
+        if !self.ops.contains(&op) {
+            return Err(InfinyonCredentialError::OutOfScope(op.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Check the token allows `op` on the packages of `group`
+    pub fn check(&self, op: HubTokenOp, group: &str) -> Result<(), InfinyonCredentialError> {
+        self.check_op(op)?;
+        let any_group = self.groups.iter().any(|g| g == SCOPED_TOKEN_ANY_GROUP);
+        if !any_group && !self.groups.iter().any(|g| g == group) {
+            return Err(InfinyonCredentialError::OutOfScope(format!(
+                "{op} in group {group}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ScopedToken {
+    type Err = InfinyonCredentialError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid = |msg: &str| InfinyonCredentialError::InvalidScopedToken(msg.to_owned());
+        let mut parts = token.splitn(5, '.');
+        if parts.next() != Some(SCOPED_TOKEN_PREFIX) {
+            return Err(invalid("unknown format"));
+        }
+        let (Some(ops), Some(groups), Some(expires), Some(secret)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid("missing parts"));
+        };
+
+        let ops = ops
+            .split('+')
+            .map(|op| match op {
+                "download" => Ok(HubTokenOp::Download),
+                "publish" => Ok(HubTokenOp::Publish),
+                _ => Err(invalid(&format!("unknown operation {op}"))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let groups: Vec<String> = groups
+            .split('+')
+            .filter(|group| !group.is_empty())
+            .map(String::from)
+            .collect();
+        if groups.is_empty() {
+            return Err(invalid("no groups"));
+        }
+        let expires_at = match expires.parse::<u64>() {
+            Ok(0) => None,
+            Ok(at) => Some(at),
+            Err(_) => return Err(invalid("bad expiration")),
+        };
+        if secret.is_empty() {
+            return Err(invalid("missing secret"));
+        }
+        Ok(Self {
+            ops,
+            groups,
+            expires_at,
+            token: token.to_owned(),
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Deserialize, Serialize)]
@@ -62,32 +392,302 @@ struct Credentials {
     remote: String,
     email: String,
     id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+    /// seconds since the unix epoch, the `exp` claim of JWT tokens otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
+    /// org used when no org is selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    current_org: Option<String>,
+    /// tokens by org name, empty when kept in the keyring
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    org_tokens: BTreeMap<String, String>,
+    /// the tokens are kept in the OS keyring
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    keyring: bool,
+    /// file the login was loaded from
+    #[serde(skip)]
+    path: PathBuf,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Credentials {
-    /// Try to load credentials from disk
-    fn try_load<P: AsRef<Path>>(base_path: P) -> Result<Self, InfinyonCredentialError> {
+    /// Try to load credentials from disk without blocking
+    async fn try_load_async<P: AsRef<Path>>(base_path: P) -> Result<Self, InfinyonCredentialError> {
         let current_login_path = base_path.as_ref().join(CURRENT_LOGIN_FILE_NAME);
-        let cfg_path = fs::read_to_string(current_login_path).map_err(|_| {
-            InfinyonCredentialError::Read(
-                "no access credentials, try 'fluvio cloud login'".to_owned(),
-            )
-        })?;
+        let cfg_path = fluvio_future::fs::read_to_string(current_login_path)
+            .await
+            .map_err(|_| {
+                InfinyonCredentialError::Read(
+                    "no access credentials, try 'fluvio cloud login'".to_owned(),
+                )
+            })?;
         let cred_path = base_path.as_ref().join(cfg_path);
-        Self::load(&cred_path)
+        Self::load_async(&cred_path).await
+    }
+
+    /// Find the stored login matching a remote
+    async fn find_for_remote_async(base_path: &Path, remote: &str) -> Option<Self> {
+        let host = remote_host(remote)?;
+        let mut entries = fluvio_future::fs::read_dir(base_path).await.ok()?;
+        let mut creds = Vec::new();
+        while let Some(entry) = entries.next().await {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if entry.file_name() == CURRENT_LOGIN_FILE_NAME {
+                continue;
+            }
+            let Ok(cred) = Self::load_async(&entry.path()).await else {
+                continue;
+            };
+            let matches = remote_host(&cred.remote)
+                .map(|cred_host| host == cred_host || host.ends_with(&format!(".{cred_host}")))
+                .unwrap_or(false);
+            if matches {
+                creds.push(cred);
+            }
+        }
+        // the closest match, an exact host before a parent domain
+        creds.sort_by_key(|cred| std::cmp::Reverse(cred.remote.len()));
+        creds.into_iter().next()
+    }
+
+    async fn load_async(cred_path: &Path) -> Result<Self, InfinyonCredentialError> {
+        let file_str = fluvio_future::fs::read_to_string(cred_path)
+            .await
+            .map_err(|_| {
+                InfinyonCredentialError::Read(
+                    "no access credentials, try 'fluvio cloud login'".to_owned(),
+                )
+            })?;
+        Self::parse(&file_str, cred_path)
     }
+}
 
-    fn load(cred_path: &Path) -> Result<Self, InfinyonCredentialError> {
-        let file_str = fs::read_to_string(cred_path).map_err(|_| {
-            InfinyonCredentialError::Read(
-                "no access credentials, try 'fluvio cloud login'".to_owned(),
-            )
+impl Credentials {
+    fn store(&self, base_path: &Path) -> Result<(), InfinyonCredentialError> {
+        let write_err = |err: std::io::Error| {
+            InfinyonCredentialError::Read(format!("unable to store login: {err}"))
+        };
+        let host = remote_host(&self.remote).ok_or_else(|| {
+            InfinyonCredentialError::Read(format!("invalid remote {}", self.remote))
         })?;
-        let creds: Credentials = toml::from_str(&file_str)
+        fs::create_dir_all(base_path).map_err(write_err)?;
+        self.write(&base_path.join(&host)).map_err(write_err)?;
+
+        let current = base_path.join(CURRENT_LOGIN_FILE_NAME);
+        if !current.exists() {
+            fs::write(current, &host).map_err(write_err)?;
+        }
+        Ok(())
+    }
+
+    /// login read from `cred_path`, with its tokens from the keyring
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    fn parse(file_str: &str, cred_path: &Path) -> Result<Self, InfinyonCredentialError> {
+        let mut creds: Credentials = toml::from_str(file_str)
             .map_err(|_| InfinyonCredentialError::UnableToParseCredentials)?;
+        creds.path = cred_path.to_path_buf();
+        if creds.keyring {
+            creds.token = token_store::read(&creds.remote).ok_or_else(|| {
+                InfinyonCredentialError::Read(
+                    "login token not found in the keyring, try 'fluvio cloud login'".to_owned(),
+                )
+            })?;
+            creds.refresh_token = token_store::read(&refresh_account(&creds.remote));
+            for (org, token) in creds.org_tokens.iter_mut() {
+                *token = token_store::read(&org_account(&creds.remote, org)).unwrap_or_default();
+            }
+        } else if !creds.token.is_empty() && creds.store_tokens() {
+            // move the plaintext token of a login stored before the keyring was used
+            if let Err(err) = creds.write_file(cred_path, true) {
+                debug!(%err, "unable to remove plaintext token of login");
+            }
+        }
         Ok(creds)
     }
+
+    /// write the login to `path`, the tokens go to the keyring when available
+    fn write(&self, path: &Path) -> std::io::Result<()> {
+        let in_keyring = self.store_tokens();
+        self.write_file(path, in_keyring)
+    }
+
+    /// store the tokens in the keyring, false when there is no usable keyring
+    fn store_tokens(&self) -> bool {
+        let stored = token_store::store(&self.remote, &self.token)
+            && self
+                .org_tokens
+                .iter()
+                .all(|(org, token)| token_store::store(&org_account(&self.remote, org), token));
+        match &self.refresh_token {
+            Some(refresh_token) => {
+                stored && token_store::store(&refresh_account(&self.remote), refresh_token)
+            }
+            None => stored,
+        }
+    }
+
+    /// expiry of the token, the stored one or the `exp` claim of a JWT token
+    fn token_expiry(&self) -> Option<u64> {
+        self.expires_at.or_else(|| jwt_expiry(&self.token))
+    }
+
+    fn check_expiry(&self) -> Result<(), InfinyonCredentialError> {
+        self.check_token_expiry(self.token_expiry())
+    }
+
+    fn check_token_expiry(&self, expiry: Option<u64>) -> Result<(), InfinyonCredentialError> {
+        match expiry {
+            Some(at) if at <= now_secs() + EXPIRY_MARGIN_SECS => {
+                debug!(remote = self.remote, expired_at = at, "login token expired");
+                Err(InfinyonCredentialError::Expired(self.remote.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// org picked by `org`, then `INFINYON_ORG`, then the current org
+    fn select_org(&self, org: Option<&str>) -> Option<String> {
+        org.map(str::to_owned)
+            .or_else(|| env::var(INFINYON_ORG_ENV).ok())
+            .filter(|org| !org.is_empty())
+            .or_else(|| self.current_org.clone())
+    }
+
+    fn org_token(&self, org: &str) -> Result<&str, InfinyonCredentialError> {
+        self.org_tokens
+            .get(org)
+            .map(String::as_str)
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| InfinyonCredentialError::OrgNotFound(org.to_owned()))
+    }
+
+    /// token of the selected org, the login token without org
+    fn token_for(&self, org: Option<&str>) -> Result<InfinyonToken, InfinyonCredentialError> {
+        match self.select_org(org) {
+            Some(org) => {
+                let token = self.org_token(&org)?;
+                self.check_token_expiry(jwt_expiry(token))?;
+                debug!(org, "org token selected");
+                Ok(token.to_owned())
+            }
+            None => {
+                self.check_expiry()?;
+                Ok(self.token.clone())
+            }
+        }
+    }
+
+    fn write_file(&self, path: &Path, in_keyring: bool) -> std::io::Result<()> {
+        let stored = Self {
+            remote: self.remote.clone(),
+            email: self.email.clone(),
+            id: self.id.clone(),
+            token: if in_keyring {
+                String::new()
+            } else {
+                self.token.clone()
+            },
+            refresh_token: if in_keyring {
+                None
+            } else {
+                self.refresh_token.clone()
+            },
+            expires_at: self.expires_at,
+            current_org: self.current_org.clone(),
+            org_tokens: self
+                .org_tokens
+                .iter()
+                .map(|(org, token)| {
+                    let token = if in_keyring {
+                        String::new()
+                    } else {
+                        token.clone()
+                    };
+                    (org.clone(), token)
+                })
+                .collect(),
+            keyring: in_keyring,
+            path: PathBuf::new(),
+        };
+        let buf = toml::to_string(&stored)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        fs::write(path, buf)
+    }
+}
+
+#[cfg(all(feature = "keyring", not(test)))]
+mod token_store {
+    use tracing::debug;
+
+    const SERVICE: &str = "fluvio-infinyon-login";
+
+    /// token stored for `account`, the remote of a login
+    pub(super) fn read(account: &str) -> Option<String> {
+        ::keyring::Entry::new(SERVICE, account)
+            .and_then(|entry| entry.get_password())
+            .map_err(|err| debug!(%err, account, "unable to read login token from keyring"))
+            .ok()
+    }
+
+    /// store the token for `account`, false when there is no usable keyring
+    pub(super) fn store(account: &str, token: &str) -> bool {
+        ::keyring::Entry::new(SERVICE, account)
+            .and_then(|entry| entry.set_password(token))
+            .map_err(|err| debug!(%err, account, "unable to store login token in keyring"))
+            .is_ok()
+    }
+}
+
+#[cfg(not(all(feature = "keyring", not(test))))]
+mod token_store {
+    pub(super) fn read(_account: &str) -> Option<String> {
+        None
+    }
+
+    pub(super) fn store(_account: &str, _token: &str) -> bool {
+        false
+    }
+}
+
+/// keyring account of the refresh token of a login
+fn refresh_account(remote: &str) -> String {
+    format!("{remote}#refresh")
+}
+
+/// keyring account of the token of an org
+fn org_account(remote: &str, org: &str) -> String {
+    format!("{remote}#org:{org}")
+}
+
+/// `exp` claim of a JWT token, `None` for other tokens
+fn jwt_expiry(token: &str) -> Option<u64> {
+    let mut parts = token.split('.');
+    let (Some(_header), Some(payload), Some(_signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    let claims: serde_json::Value =
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+    claims.get("exp")?.as_u64()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default()
+}
+
+fn remote_host(remote: &str) -> Option<String> {
+    let url = url::Url::parse(remote).ok()?;
+    url.host_str().map(|host| host.to_ascii_lowercase())
 }
 
 fn default_file_path() -> String {
@@ -99,13 +699,197 @@ fn default_file_path() -> String {
 
 #[cfg(test)]
 mod infinyon_tok_tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use base64::Engine;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
     use super::read_infinyon_token;
+    use super::{Credentials, HubTokenOp, InfinyonCredentialError, ScopedToken};
+
+    fn login(remote: &str) -> Credentials {
+        Credentials {
+            remote: remote.to_owned(),
+            email: "ci@example.com".to_owned(),
+            id: "id".to_owned(),
+            token: format!("token for {remote}"),
+            refresh_token: None,
+            expires_at: None,
+            current_org: None,
+            org_tokens: BTreeMap::new(),
+            keyring: false,
+            path: PathBuf::new(),
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn logins_per_remote() {
+        let base_path =
+            std::env::temp_dir().join(format!("infinyon-logins-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base_path);
+        login("https://infinyon.cloud").store(&base_path).unwrap();
+        login("https://hub.example.com:8080")
+            .store(&base_path)
+            .unwrap();
+
+        // the first login stays the current one
+        let current = Credentials::try_load_async(&base_path).await.unwrap();
+        assert_eq!(current.remote, "https://infinyon.cloud");
+
+        let cred = Credentials::find_for_remote_async(&base_path, "https://hub.infinyon.cloud")
+            .await
+            .unwrap();
+        assert_eq!(cred.remote, "https://infinyon.cloud");
+        let cred = Credentials::find_for_remote_async(&base_path, "https://hub.example.com:8080/")
+            .await
+            .unwrap();
+        assert_eq!(cred.token, "token for https://hub.example.com:8080");
+        assert!(
+            Credentials::find_for_remote_async(&base_path, "https://example.org")
+                .await
+                .is_none()
+        );
+
+        std::fs::remove_dir_all(&base_path).unwrap();
+    }
+
+    #[fluvio_future::test]
+    async fn login_token_in_keyring() {
+        let base_path =
+            std::env::temp_dir().join(format!("infinyon-keyring-{}", std::process::id()));
+        std::fs::create_dir_all(&base_path).unwrap();
+
+        // logins stored before the keyring keep working
+        let plaintext = base_path.join("plaintext");
+        std::fs::write(
+            &plaintext,
+            "remote = \"https://infinyon.cloud\"\nemail = \"a@b.c\"\nid = \"id\"\ntoken = \"t\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            Credentials::load_async(&plaintext).await.unwrap().token,
+            "t"
+        );
+
+        let in_keyring = base_path.join("keyring");
+        login("https://infinyon.cloud")
+            .write_file(&in_keyring, true)
+            .unwrap();
+        let file = std::fs::read_to_string(&in_keyring).unwrap();
+        assert!(file.contains("keyring = true"));
+        assert!(!file.contains("token"));
+        // no keyring in tests, the token can't be found
+        assert!(matches!(
+            Credentials::load_async(&in_keyring).await,
+            Err(InfinyonCredentialError::Read(_))
+        ));
+
+        std::fs::remove_dir_all(&base_path).unwrap();
+    }
+
+    #[test]
+    fn login_token_expiry() {
+        let jwt = |claims: &str| format!("e30.{}.c2ln", URL_SAFE_NO_PAD.encode(claims.as_bytes()));
+        let mut cred = login("https://infinyon.cloud");
+        assert_eq!(cred.token_expiry(), None);
+        assert!(cred.check_expiry().is_ok());
+
+        cred.token = jwt(r#"{"sub":"ci","exp":1700000000}"#);
+        assert_eq!(cred.token_expiry(), Some(1700000000));
+        assert!(matches!(
+            cred.check_expiry(),
+            Err(InfinyonCredentialError::Expired(remote)) if remote == "https://infinyon.cloud"
+        ));
+
+        // the stored expiry wins over the claim
+        cred.expires_at = Some(u64::MAX - 60);
+        assert!(cred.check_expiry().is_ok());
+
+        cred.token = jwt(r#"{"sub":"ci"}"#);
+        cred.expires_at = None;
+        assert_eq!(cred.token_expiry(), None);
+    }
+
+    #[test]
+    fn org_tokens() {
+        let mut cred = login("https://infinyon.cloud");
+        cred.org_tokens = BTreeMap::from([
+            ("acme".to_owned(), "acme token".to_owned()),
+            ("infinyon".to_owned(), "infinyon token".to_owned()),
+        ]);
+        assert_eq!(
+            cred.token_for(Some("acme")).unwrap(),
+            "acme token".to_owned()
+        );
+        assert!(matches!(
+            cred.token_for(Some("other")),
+            Err(InfinyonCredentialError::OrgNotFound(org)) if org == "other"
+        ));
+
+        cred.current_org = Some("infinyon".to_owned());
+        assert_eq!(cred.org_token("infinyon").unwrap(), "infinyon token");
+        assert_eq!(cred.select_org(Some("acme")), Some("acme".to_owned()));
+
+        // org tokens stay out of the login file when they are in the keyring
+        let path = std::env::temp_dir().join(format!("infinyon-orgs-{}", std::process::id()));
+        cred.write_file(&path, true).unwrap();
+        let file = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(file.contains("current_org = \"infinyon\""));
+        assert!(file.contains("[org_tokens]"));
+        assert!(!file.contains("acme token"));
+    }
+
+    #[test]
+    fn scoped_token_parse() {
+        let token: ScopedToken = "ihub1.publish.infinyon+acme.0.s3cr3t".parse().unwrap();
+        assert_eq!(token.ops(), &[HubTokenOp::Publish]);
+        assert_eq!(token.groups(), &["infinyon", "acme"]);
+        assert_eq!(token.expires_at(), None);
+        assert_eq!(token.token(), "ihub1.publish.infinyon+acme.0.s3cr3t");
+
+        for bad in [
+            "s3cr3t",
+            "ihub1.publish.infinyon.0",
+            "ihub1.yank.infinyon.0.s3cr3t",
+            "ihub1.publish..0.s3cr3t",
+            "ihub1.publish.infinyon.soon.s3cr3t",
+            "ihub1.publish.infinyon.0.",
+        ] {
+            assert!(bad.parse::<ScopedToken>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn scoped_token_check() {
+        let token: ScopedToken = "ihub1.download.infinyon.0.s3cr3t".parse().unwrap();
+        assert!(token.check(HubTokenOp::Download, "infinyon").is_ok());
+        assert!(matches!(
+            token.check(HubTokenOp::Download, "acme"),
+            Err(InfinyonCredentialError::OutOfScope(_))
+        ));
+        assert!(matches!(
+            token.check(HubTokenOp::Publish, "infinyon"),
+            Err(InfinyonCredentialError::OutOfScope(_))
+        ));
+
+        let any: ScopedToken = "ihub1.download+publish.*.0.s3cr3t".parse().unwrap();
+        assert!(any.check(HubTokenOp::Publish, "acme").is_ok());
+
+        let expired: ScopedToken = "ihub1.download.*.1.s3cr3t".parse().unwrap();
+        assert!(expired.is_expired());
+        assert!(matches!(
+            expired.check_op(HubTokenOp::Download),
+            Err(InfinyonCredentialError::InvalidScopedToken(_))
+        ));
+    }
 
     // load default credentials (ignore by default becasuse config is not populated in ci env)
     #[ignore]
     #[test]
     fn read_default() {
-        let res_token = read_infinyon_token();
+        let res_token = read_infinyon_token(None);
         assert!(res_token.is_ok(), "{res_token:?}");
         println!("token: {}", res_token.unwrap());
     }
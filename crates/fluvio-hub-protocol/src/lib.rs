@@ -1,9 +1,13 @@
 mod errors;
 mod package_meta;
+mod provenance;
 
 pub mod constants;
+pub mod device_auth;
 pub mod infinyon_tok;
+pub mod org;
 
 pub use errors::{Result, HubError};
 pub use package_meta::{PackageMeta, PkgTag, PkgVisibility};
 pub use package_meta::{validate_allowedchars, validate_noleading_punct};
+pub use provenance::{Provenance, PackageSbom};
@@ -0,0 +1,170 @@
+//! Organization namespaces
+//!
+//! The group of a package, `acme` in `acme/example@0.1.0`, is the organization owning
+//! it. Members of an organization have a role, which decides what they may do, and a
+//! package pattern, which decides where within the namespace they may do it.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::HubError;
+
+/// Pattern matching every package of an organization
+pub const ORG_ALL_PACKAGES: &str = "*";
+
+/// Role of a member, ordered from least to most privileged
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OrgRole {
+    /// may download private packages
+    #[default]
+    Viewer,
+    /// may also publish new versions
+    Publisher,
+    /// may also yank published versions
+    Maintainer,
+    /// may also manage members
+    Owner,
+}
+
+impl OrgRole {
+    pub fn can_publish(&self) -> bool {
+        *self >= Self::Publisher
+    }
+
+    pub fn can_yank(&self) -> bool {
+        *self >= Self::Maintainer
+    }
+
+    pub fn can_manage_members(&self) -> bool {
+        *self == Self::Owner
+    }
+}
+
+impl fmt::Display for OrgRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let role = match self {
+            Self::Viewer => "viewer",
+            Self::Publisher => "publisher",
+            Self::Maintainer => "maintainer",
+            Self::Owner => "owner",
+        };
+        write!(f, "{role}")
+    }
+}
+
+impl FromStr for OrgRole {
+    type Err = HubError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "viewer" => Ok(Self::Viewer),
+            "publisher" => Ok(Self::Publisher),
+            "maintainer" => Ok(Self::Maintainer),
+            "owner" => Ok(Self::Owner),
+            _ => Err(HubError::General(format!(
+                "invalid role {s}, expected viewer, publisher, maintainer or owner"
+            ))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct OrgMember {
+    /// hub id of the member
+    pub user: String,
+    pub role: OrgRole,
+    /// package names the role applies to, `*` or a prefix ending with `*` like `http-*`
+    #[serde(default = "OrgMember::all_packages")]
+    pub packages: String,
+}
+
+impl OrgMember {
+    fn all_packages() -> String {
+        ORG_ALL_PACKAGES.to_string()
+    }
+
+    /// Whether the member's role applies to the package named `name` in the organization
+    pub fn covers(&self, name: &str) -> bool {
+        match self.packages.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => self.packages == name,
+        }
+    }
+
+    pub fn can_publish(&self, name: &str) -> bool {
+        self.covers(name) && self.role.can_publish()
+    }
+
+    pub fn can_yank(&self, name: &str) -> bool {
+        self.covers(name) && self.role.can_yank()
+    }
+}
+
+/// Used by hub server web api and cli exchange organization members
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct OrgMembers {
+    pub org: String,
+    pub members: Vec<OrgMember>,
+}
+
+impl OrgMembers {
+    pub fn member(&self, user: &str) -> Option<&OrgMember> {
+        self.members.iter().find(|member| member.user == user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(role: OrgRole, packages: &str) -> OrgMember {
+        OrgMember {
+            user: "alice".into(),
+            role,
+            packages: packages.into(),
+        }
+    }
+
+    #[test]
+    fn roles() {
+        assert!(!OrgRole::Viewer.can_publish());
+        assert!(OrgRole::Publisher.can_publish());
+        assert!(!OrgRole::Publisher.can_yank());
+        assert!(OrgRole::Maintainer.can_yank());
+        assert!(!OrgRole::Maintainer.can_manage_members());
+        assert!(OrgRole::Owner.can_manage_members());
+        assert_eq!(
+            "maintainer".parse::<OrgRole>().unwrap(),
+            OrgRole::Maintainer
+        );
+        assert!("admin".parse::<OrgRole>().is_err());
+    }
+
+    #[test]
+    fn package_patterns() {
+        let publisher = member(OrgRole::Publisher, "http-*");
+        assert!(publisher.can_publish("http-source"));
+        assert!(!publisher.can_publish("kafka-source"));
+        assert!(!publisher.can_yank("http-source"));
+
+        let maintainer = member(OrgRole::Maintainer, "jolt");
+        assert!(maintainer.can_yank("jolt"));
+        assert!(!maintainer.can_yank("jolt-extra"));
+        assert!(member(OrgRole::Owner, ORG_ALL_PACKAGES).can_yank("anything"));
+    }
+
+    #[test]
+    fn members_default_to_all_packages() {
+        let members: OrgMembers = serde_json::from_str(
+            r#"{"org":"acme","members":[{"user":"alice","role":"publisher"}]}"#,
+        )
+        .unwrap();
+        let alice = members.member("alice").unwrap();
+        assert_eq!(alice.packages, ORG_ALL_PACKAGES);
+        assert!(alice.can_publish("example"));
+        assert!(members.member("bob").is_none());
+    }
+}
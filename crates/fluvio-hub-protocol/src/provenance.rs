@@ -0,0 +1,70 @@
+//! Build provenance of hub packages
+//!
+//! The provenance is stored as a top level file of the package next to the package
+//! meta, so the publisher signature covers it like every other top level file.
+
+use serde::{Deserialize, Serialize};
+
+/// Describes how and from what sources a package was built
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct Provenance {
+    /// commit the package was built from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    /// remote of the repository containing the commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_repository: Option<String>,
+    /// whether the working tree had uncommitted changes at build time
+    #[serde(default)]
+    pub git_dirty: bool,
+    /// tool and environment that built the package, e.g. `smdk 0.11.0 (github-actions)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builder: Option<String>,
+    /// RFC 3339 time of the build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub built_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sbom: Option<PackageSbom>,
+}
+
+/// Software bill of materials attached to the package
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct PackageSbom {
+    /// name of the top level file holding the SBOM
+    pub file: String,
+    /// hex encoded sha256 of the SBOM file
+    pub sha256: String,
+}
+
+impl Provenance {
+    /// Provenance that records the commit of a clean working tree
+    pub fn is_reproducible(&self) -> bool {
+        self.git_commit.is_some() && !self.git_dirty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provenance_serde() {
+        let provenance = Provenance {
+            git_commit: Some("0a1b2c3".into()),
+            builder: Some("smdk 0.11.0".into()),
+            sbom: Some(PackageSbom {
+                file: "sbom.spdx.json".into(),
+                sha256: "00ff".into(),
+            }),
+            ..Default::default()
+        };
+        let serialized = serde_json::to_string(&provenance).expect("serialize");
+        assert!(!serialized.contains("git_repository"));
+        let deserialized: Provenance = serde_json::from_str(&serialized).expect("deserialize");
+        assert_eq!(deserialized, provenance);
+        assert!(deserialized.is_reproducible());
+
+        let empty: Provenance = serde_json::from_str("{}").expect("deserialize");
+        assert!(!empty.is_reproducible());
+    }
+}
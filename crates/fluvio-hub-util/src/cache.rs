@@ -0,0 +1,384 @@
+//! Local cache of hub responses
+//!
+//! Bodies are stored once under `objects/` named by their sha256 digest. Every cached
+//! url has an entry under `index/` pointing to the object along with the validators
+//! and the expiration time taken from the caching headers of the response.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use http::{HeaderMap, StatusCode};
+use http::header::{
+    CACHE_CONTROL, ETAG, EXPIRES, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+use fluvio_hub_protocol::{HubError, Result};
+
+use crate::htclient;
+use crate::default_cfg_path;
+
+const CACHE_DIR: &str = "cache";
+const CACHE_INDEX: &str = "index";
+const CACHE_OBJECTS: &str = "objects";
+
+/// Retries of a request rejected because of rate limits
+const RATE_LIMIT_RETRIES: u32 = 5;
+/// Longest wait between retries, also caps the `Retry-After` of the hub
+const RATE_LIMIT_MAX_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// serve fresh entries from the cache, revalidate or fetch the others
+    #[default]
+    Online,
+    /// only serve entries from the cache, however old, never reach the hub
+    Offline,
+}
+
+impl CacheMode {
+    pub fn from_offline(offline: bool) -> Self {
+        if offline {
+            Self::Offline
+        } else {
+            Self::Online
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub url: String,
+    /// sha256 of the cached body
+    pub digest: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// seconds since the unix epoch, entries without one are always revalidated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self) -> bool {
+        self.expires_at.map(|at| at > unix_now()).unwrap_or(false)
+    }
+
+    /// update validators and expiration time from the headers of a response
+    fn update_from(&mut self, headers: &HeaderMap) {
+        self.etag = header_str(headers, ETAG).or(self.etag.take());
+        self.last_modified = header_str(headers, LAST_MODIFIED).or(self.last_modified.take());
+        self.expires_at = expires_at(headers);
+    }
+}
+
+pub struct HubCache {
+    dir: PathBuf,
+}
+
+impl HubCache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// cache in `~/.fluvio/hub/cache`
+    pub fn default_load() -> Result<Self> {
+        Ok(Self::new(default_cfg_path()?.join(CACHE_DIR)))
+    }
+
+    pub fn lookup(&self, url: &str) -> Option<CacheEntry> {
+        let buf = std::fs::read(self.index_path(url)).ok()?;
+        serde_json::from_slice(&buf)
+            .map_err(|err| warn!(url, %err, "ignoring invalid cache entry"))
+            .ok()
+    }
+
+    /// read the body of an entry, checking it still matches its digest
+    pub fn read(&self, entry: &CacheEntry) -> Result<Vec<u8>> {
+        let buf = std::fs::read(self.object_path(&entry.digest))?;
+        if sha256_hex(&buf) != entry.digest {
+            return Err(HubError::General(format!(
+                "cached response of {} is corrupted",
+                entry.url
+            )));
+        }
+        Ok(buf)
+    }
+
+    /// store a response unless its headers forbid it, returns whether it was stored
+    pub fn store(&self, url: &str, headers: &HeaderMap, body: &[u8]) -> Result<bool> {
+        if cache_directives(headers).any(|directive| directive == "no-store") {
+            return Ok(false);
+        }
+        let digest = sha256_hex(body);
+        let object = self.object_path(&digest);
+        if !object.exists() {
+            write_file(&object, body)?;
+        }
+        let mut entry = CacheEntry {
+            url: url.to_string(),
+            digest,
+            etag: None,
+            last_modified: None,
+            expires_at: None,
+        };
+        entry.update_from(headers);
+        self.write_entry(&entry)?;
+        Ok(true)
+    }
+
+    fn write_entry(&self, entry: &CacheEntry) -> Result<()> {
+        let buf = serde_json::to_vec(entry)?;
+        write_file(&self.index_path(&entry.url), &buf)
+    }
+
+    fn index_path(&self, url: &str) -> PathBuf {
+        self.dir.join(CACHE_INDEX).join(sha256_hex(url.as_bytes()))
+    }
+
+    fn object_path(&self, digest: &str) -> PathBuf {
+        self.dir.join(CACHE_OBJECTS).join(digest)
+    }
+}
+
+/// Get `url` through the cache
+///
+/// The authorization token is only requested when the hub has to be reached.
+pub async fn cached_get<F, Fut>(
+    cache: &HubCache,
+    mode: CacheMode,
+    url: &str,
+    token: F,
+) -> Result<Vec<u8>>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let entry = cache.lookup(url);
+    match (&entry, mode) {
+        (Some(entry), CacheMode::Offline) => return cache.read(entry),
+        (None, CacheMode::Offline) => {
+            return Err(HubError::HubAccess(format!(
+                "{url} is not cached, unable to get it offline"
+            )))
+        }
+        (Some(entry), CacheMode::Online) if entry.is_fresh() => match cache.read(entry) {
+            Ok(buf) => {
+                debug!(url, "cache hit");
+                return Ok(buf);
+            }
+            Err(err) => warn!(url, %err, "fetching again"),
+        },
+        _ => {}
+    }
+
+    let token = token().await?;
+    let resp = send_with_backoff(|| {
+        let mut req = http::Request::get(url).header("Authorization", &token);
+        if let Some(entry) = &entry {
+            if let Some(etag) = &entry.etag {
+                req = req.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                req = req.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        req.body("")
+            .map_err(|e| HubError::HubAccess(format!("request format error {e}")))
+    })
+    .await?;
+
+    match (resp.status(), entry) {
+        (StatusCode::NOT_MODIFIED, Some(mut entry)) => {
+            debug!(url, "cache revalidated");
+            entry.update_from(resp.headers());
+            cache.write_entry(&entry)?;
+            cache.read(&entry)
+        }
+        (StatusCode::OK, _) => {
+            if let Err(err) = cache.store(url, resp.headers(), resp.body()) {
+                warn!(url, %err, "unable to cache response");
+            }
+            Ok(resp.into_body())
+        }
+        (code, _) => {
+            let body = std::str::from_utf8(resp.body()).unwrap_or("couldn't fetch error message");
+            Err(HubError::HubAccess(format!("Status({code}) {body}")))
+        }
+    }
+}
+
+/// Send a request, waiting and retrying while the hub rate limits it
+///
+/// The wait follows the `Retry-After` header of the hub or doubles at every retry.
+pub async fn send_with_backoff<F>(request: F) -> Result<http::Response<Vec<u8>>>
+where
+    F: Fn() -> Result<http::Request<&'static str>>,
+{
+    let mut wait = Duration::from_secs(1);
+    for retry in 0..=RATE_LIMIT_RETRIES {
+        let resp = htclient::send(request()?)
+            .await
+            .map_err(|e| HubError::HubAccess(format!("Failed to connect {e}")))?;
+        let status = resp.status();
+        if !matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        ) || retry == RATE_LIMIT_RETRIES
+        {
+            return Ok(resp);
+        }
+
+        let delay = retry_after(resp.headers())
+            .unwrap_or(wait)
+            .min(RATE_LIMIT_MAX_WAIT);
+        warn!(%status, ?delay, "hub rate limit, retrying");
+        fluvio_future::timer::sleep(delay).await;
+        wait = (wait * 2).min(RATE_LIMIT_MAX_WAIT);
+    }
+    unreachable!("the last retry returns the response")
+}
+
+fn cache_directives(headers: &HeaderMap) -> impl Iterator<Item = String> + '_ {
+    headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|directive| directive.trim().to_ascii_lowercase())
+}
+
+fn expires_at(headers: &HeaderMap) -> Option<u64> {
+    let mut max_age = None;
+    for directive in cache_directives(headers) {
+        if directive == "no-cache" {
+            return None;
+        }
+        if let Some(age) = directive.strip_prefix("max-age=") {
+            max_age = age.parse::<u64>().ok();
+        }
+    }
+    if let Some(max_age) = max_age {
+        return Some(unix_now() + max_age);
+    }
+    let expires = header_str(headers, EXPIRES)?;
+    chrono::DateTime::parse_from_rfc2822(&expires)
+        .ok()
+        .map(|at| at.timestamp().max(0) as u64)
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    header_str(headers, RETRY_AFTER)?
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn header_str(headers: &HeaderMap, name: http::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+fn sha256_hex(buf: &[u8]) -> String {
+    hex::encode(Sha256::digest(buf))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default()
+}
+
+/// write through a temporary file so readers never see partial files
+fn write_file(path: &Path, buf: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| HubError::General(format!("invalid cache path {}", path.display())))?;
+    std::fs::create_dir_all(dir)?;
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    std::io::Write::write_all(&mut tmp, buf)?;
+    tmp.persist(path)
+        .map_err(|err| HubError::General(format!("unable to write cache: {err}")))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    fn headers(pairs: &[(http::header::HeaderName, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(name.clone(), HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn cache_store_and_read() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cache = HubCache::new(dir.path());
+        let url = "https://hub.example/hub/v0/pkg/pub/infinyon/jolt/0.1.0";
+        assert!(cache.lookup(url).is_none());
+
+        let stored = cache
+            .store(
+                url,
+                &headers(&[(CACHE_CONTROL, "public, max-age=600"), (ETAG, "\"v1\"")]),
+                b"package",
+            )
+            .expect("store");
+        assert!(stored);
+        let entry = cache.lookup(url).expect("entry");
+        assert!(entry.is_fresh());
+        assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(cache.read(&entry).expect("read"), b"package");
+
+        // same content is stored once
+        cache
+            .store("https://hub.example/other", &HeaderMap::new(), b"package")
+            .expect("store");
+        let objects = std::fs::read_dir(dir.path().join(CACHE_OBJECTS)).unwrap();
+        assert_eq!(objects.count(), 1);
+
+        std::fs::write(cache.object_path(&entry.digest), b"tampered").unwrap();
+        assert!(cache.read(&entry).is_err());
+    }
+
+    #[test]
+    fn cache_headers() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cache = HubCache::new(dir.path());
+        let stored = cache
+            .store(
+                "https://hub.example/list",
+                &headers(&[(CACHE_CONTROL, "no-store")]),
+                b"[]",
+            )
+            .expect("store");
+        assert!(!stored);
+        assert!(cache.lookup("https://hub.example/list").is_none());
+
+        assert!(expires_at(&headers(&[(CACHE_CONTROL, "no-cache, max-age=60")])).is_none());
+        assert!(expires_at(&HeaderMap::new()).is_none());
+        assert_eq!(
+            expires_at(&headers(&[(EXPIRES, "Wed, 21 Oct 2015 07:28:00 GMT")])),
+            Some(1445412480)
+        );
+        assert_eq!(
+            retry_after(&headers(&[(RETRY_AFTER, "3")])),
+            Some(Duration::from_secs(3))
+        );
+    }
+}
@@ -7,7 +7,9 @@ use anyhow::{Result, anyhow};
 
 use fluvio_extension_common::Terminal;
 
-use crate::{cli_pkgname_to_filename, cli_conn_pkgname_to_url, get_package};
+use crate::{cli_pkgname_to_filename, cli_conn_pkgname_to_url, get_package_cached};
+use crate::{CacheMode, HubCache, check_download_scope};
+use crate::package_verify_provenance;
 
 use super::get_hub_access;
 
@@ -30,6 +32,14 @@ pub struct ConnectorHubDownloadOpts {
     )]
     target: String,
 
+    /// Refuse packages without a verified build provenance
+    #[arg(long)]
+    require_provenance: bool,
+
+    /// Only use the local cache of the hub, without reaching it
+    #[arg(long)]
+    offline: bool,
+
     #[arg(long, hide_short_help = true)]
     remote: Option<String>,
 }
@@ -57,13 +67,34 @@ impl ConnectorHubDownloadOpts {
         let url = cli_conn_pkgname_to_url(&package_name, &access.remote, &self.target)
             .map_err(|_| anyhow!("invalid pkgname {package_name}"))?;
 
-        let data = get_package(&url, &access)
+        check_download_scope(&package_name)?;
+        let cache = HubCache::default_load()?;
+        let mode = CacheMode::from_offline(self.offline);
+        let data = get_package_cached(&url, &access, &cache, mode)
             .await
             .map_err(|err| anyhow!("downloading {package_name} failed\nServer: {err}"))?;
 
-        std::fs::write(file_path, data)
+        std::fs::write(&file_path, data)
             .map_err(|err| anyhow!("unable to write downloaded package to the disk: {err}"))?;
         println!("... downloading complete");
+
+        match package_verify_provenance(&path) {
+            Ok(Some(provenance)) => {
+                let commit = provenance.git_commit.as_deref().unwrap_or("unknown commit");
+                println!("... provenance verified, built from {commit}");
+            }
+            Ok(None) if !self.require_provenance => {}
+            Ok(None) => {
+                std::fs::remove_file(&file_path)?;
+                return Err(anyhow!(
+                    "{package_name} has no provenance, it is required by --require-provenance"
+                ));
+            }
+            Err(err) => {
+                std::fs::remove_file(&file_path)?;
+                return Err(anyhow!("verifying provenance of {package_name}: {err}"));
+            }
+        }
         Ok(())
     }
 }
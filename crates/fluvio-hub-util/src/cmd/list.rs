@@ -6,7 +6,7 @@ use anyhow::Result;
 
 use fluvio_extension_common::Terminal;
 use fluvio_extension_common::OutputFormat;
-use crate::HUB_API_CONN_LIST;
+use crate::{CacheMode, HUB_API_CONN_LIST};
 
 use super::get_pkg_list;
 
@@ -19,13 +19,23 @@ pub struct ConnectorHubListOpts {
     #[arg(long, hide = true)]
     system: bool,
 
+    /// Only use the local cache of the hub, without reaching it
+    #[arg(long)]
+    offline: bool,
+
     #[arg(long, hide_short_help = true)]
     remote: Option<String>,
 }
 
 impl ConnectorHubListOpts {
     pub async fn process<O: Terminal + Debug + Send + Sync>(self, out: Arc<O>) -> Result<()> {
-        let pl = get_pkg_list(HUB_API_CONN_LIST, &self.remote, self.system).await?;
+        let pl = get_pkg_list(
+            HUB_API_CONN_LIST,
+            &self.remote,
+            self.system,
+            CacheMode::from_offline(self.offline),
+        )
+        .await?;
         output::tableformat(out, pl.packages, self.output.format)?;
         Ok(())
     }
@@ -1,6 +1,5 @@
 use anyhow::Result;
 use anyhow::anyhow;
-use http;
 
 mod list;
 mod download;
@@ -8,9 +7,8 @@ mod download;
 pub use list::ConnectorHubListOpts;
 pub use download::ConnectorHubDownloadOpts;
 
-use crate::HubAccess;
-use crate::PackageListMeta;
-use crate::htclient;
+use crate::{HubAccess, HubError, PackageListMeta};
+use crate::{CacheMode, HubCache, cached_get};
 
 pub fn get_hub_access(remote: &Option<String>) -> Result<HubAccess> {
     HubAccess::default_load(remote)
@@ -21,32 +19,27 @@ pub async fn get_pkg_list(
     endpoint: &str,
     remote: &Option<String>,
     sysflag: bool,
+    mode: CacheMode,
 ) -> Result<PackageListMeta> {
-    use crate::htclient::ResponseExt;
-
     let access = get_hub_access(remote)?;
-    let action_token = access
-        .get_list_token()
-        .await
-        .map_err(|_| anyhow!("rejected access credentials, try 'fluvio cloud login'"))?;
 
     let mut uri = format!("{}/{endpoint}", &access.remote);
     if sysflag {
         uri = format!("{uri}?sys=1");
     }
 
-    let req = http::Request::get(&uri)
-        .header("Authorization", action_token)
-        .body("")
-        .map_err(|e| anyhow!("request format error {e}"))?;
-
-    let resp = htclient::send(req)
-        .await
-        .map_err(|e| anyhow!("list api access error {e}"))?;
+    let cache = HubCache::default_load()?;
+    let body = cached_get(&cache, mode, &uri, || async {
+        access.get_list_token().await.map_err(|_| {
+            HubError::HubAccess("rejected access credentials, try 'fluvio cloud login'".into())
+        })
+    })
+    .await
+    .map_err(|e| anyhow!("list api access error {e}"))?;
 
-    resp.json::<PackageListMeta>().map_err(|e| {
-        let body = resp.body_string().unwrap_or_default();
-        tracing::debug!(body, "parse err");
+    serde_json::from_slice::<PackageListMeta>(&body).map_err(|e| {
+        let body = String::from_utf8_lossy(&body);
+        tracing::debug!(%body, "parse err");
         anyhow!("list api data parse error {e}")
     })
 }
@@ -0,0 +1,74 @@
+use std::time::{Duration, Instant};
+
+use http::StatusCode;
+use tracing::debug;
+
+use fluvio_hub_protocol::{Result, HubError};
+use fluvio_hub_protocol::constants::{CLOUD_API_DEVICE_CODE, CLOUD_API_DEVICE_TOKEN};
+use fluvio_hub_protocol::device_auth::{
+    DeviceAuthorization, DeviceCodeRequest, DeviceLogin, DevicePoll, DeviceTokenRequest,
+    DEVICE_CLIENT_ID,
+};
+use fluvio_hub_protocol::infinyon_tok::store_device_login;
+
+use crate::htclient::{self, ResponseExt};
+
+/// Log in to the cloud `remote` with the device authorization flow and store the login
+///
+/// `prompt` is given the code the user approves at the verification uri, the login is
+/// polled for until the user approves or denies it, or the code expires.
+pub async fn device_login<F>(remote: &str, prompt: F) -> Result<DeviceLogin>
+where
+    F: FnOnce(&DeviceAuthorization),
+{
+    let auth = request_device_code(remote).await?;
+    prompt(&auth);
+
+    let deadline = Instant::now() + Duration::from_secs(auth.expires_in);
+    let mut interval = auth.interval;
+    let token_request = serde_json::to_string(&DeviceTokenRequest::new(&auth.device_code))?;
+    loop {
+        if Instant::now() >= deadline {
+            return Err(HubError::HubAccess(
+                "login code expired, try logging in again".to_owned(),
+            ));
+        }
+        fluvio_future::timer::sleep(Duration::from_secs(interval)).await;
+
+        let resp = post_json(format!("{remote}/{CLOUD_API_DEVICE_TOKEN}"), &token_request).await?;
+        match DevicePoll::from_response(resp.status().is_success(), resp.body())? {
+            DevicePoll::Pending => debug!("device login pending"),
+            DevicePoll::SlowDown(secs) => interval += secs,
+            DevicePoll::Granted(login) => {
+                store_device_login(remote, &login)?;
+                debug!(remote, email = login.email, "device login stored");
+                return Ok(login);
+            }
+        }
+    }
+}
+
+async fn request_device_code(remote: &str) -> Result<DeviceAuthorization> {
+    let msg = serde_json::to_string(&DeviceCodeRequest {
+        client_id: DEVICE_CLIENT_ID.to_owned(),
+    })?;
+    let resp = post_json(format!("{remote}/{CLOUD_API_DEVICE_CODE}"), &msg).await?;
+    if resp.status() != StatusCode::OK {
+        return Err(HubError::HubAccess(format!(
+            "device login not available on {remote}: {}",
+            resp.status()
+        )));
+    }
+    resp.json()
+        .map_err(|e| HubError::General(format!("couldn't parse device code {e}")))
+}
+
+async fn post_json(url: String, msg: &str) -> Result<http::Response<Vec<u8>>> {
+    let req = http::Request::post(url)
+        .header(http::header::CONTENT_TYPE, mime::JSON.as_str())
+        .body(msg.to_owned())
+        .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+    htclient::send(req)
+        .await
+        .map_err(|e| HubError::HubAccess(format!("Failed to connect {e}")))
+}
@@ -15,11 +15,19 @@ use tracing::debug;
 use fluvio_future::task::run_block_on;
 
 #[cfg(not(target_arch = "wasm32"))]
-use fluvio_hub_protocol::infinyon_tok::read_infinyon_token_rem;
+use fluvio_hub_protocol::infinyon_tok::{
+    read_infinyon_token_for_remote_async, read_infinyon_token_rem, read_refresh_token_async,
+};
+#[cfg(target_arch = "wasm32")]
+use fluvio_hub_protocol::infinyon_tok::{read_infinyon_token_for_remote, read_refresh_token};
 
 use fluvio_hub_protocol::{Result, HubError};
-use fluvio_hub_protocol::infinyon_tok::read_infinyon_token;
-use fluvio_hub_protocol::constants::{HUB_API_ACT, HUB_API_HUBID, HUB_REMOTE, CLI_CONFIG_HUB};
+use fluvio_hub_protocol::infinyon_tok::{
+    read_scoped_token, update_infinyon_token, HubTokenOp, InfinyonCredentialError, RefreshedToken,
+};
+use fluvio_hub_protocol::constants::{
+    CLOUD_API_TOKEN_REFRESH, HUB_API_ACT, HUB_API_HUBID, HUB_REMOTE, CLI_CONFIG_HUB,
+};
 use fluvio_types::defaults::CLI_CONFIG_PATH;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -32,7 +40,7 @@ use crate::htclient::ResponseExt;
 const ACCESS_FILE_DEF: &str = "default"; // default profile name
 
 #[cfg(not(target_arch = "wasm32"))]
-const DEFAULT_CLOUD_REMOTE: &str = "https://infinyon.cloud";
+pub const DEFAULT_CLOUD_REMOTE: &str = "https://infinyon.cloud";
 
 // in .fluvio/hub/hcurrent
 const ACCESS_FILE_PTR: &str = "hcurrent";
@@ -43,6 +51,8 @@ pub const ACTION_CREATE_HUBID: &str = "chid";
 pub const ACTION_DOWNLOAD: &str = "dl";
 pub const ACTION_PUBLISH: &str = "pbl";
 pub const ACTION_BPKG_GET: &str = "bpkg-get";
+pub const ACTION_ORG_MEMBERS: &str = "orgm";
+pub const ACTION_ORG_ADMIN: &str = "orga";
 pub const INFINYON_HUB_REMOTE: &str = "INFINYON_HUB_REMOTE";
 pub const FLUVIO_HUB_PROFILE_ENV: &str = "FLUVIO_HUB_PROFILE";
 
@@ -144,6 +154,14 @@ impl HubAccess {
         self.get_action_auth(ACTION_PUBLISH).await
     }
 
+    pub async fn get_org_members_token(&self) -> Result<String> {
+        self.get_action_auth(ACTION_ORG_MEMBERS).await
+    }
+
+    pub async fn get_org_admin_token(&self) -> Result<String> {
+        self.get_action_auth(ACTION_ORG_ADMIN).await
+    }
+
     pub async fn get_action_auth_with_token(
         &self,
         action: &str,
@@ -153,10 +171,66 @@ impl HubAccess {
     }
 
     async fn get_action_auth(&self, action: &str) -> Result<String> {
-        let cloud_token = read_infinyon_token().unwrap_or_default();
+        let cloud_token = match read_scoped_token()? {
+            Some(scoped) => {
+                let op = action_token_op(action).ok_or_else(|| {
+                    HubError::HubAccess(format!(
+                        "action {action} needs a login, scoped hub tokens only allow publish and download"
+                    ))
+                })?;
+                scoped.check_op(op)?;
+                scoped.token().to_string()
+            }
+            None => self.read_login_token().await?,
+        };
         self.make_action_token(action, cloud_token).await
     }
 
+    /// token of the cloud login for the hub, refreshed first if it expired
+    async fn read_login_token(&self) -> Result<String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let login = read_infinyon_token_for_remote_async(&self.remote).await;
+        #[cfg(target_arch = "wasm32")]
+        let login = read_infinyon_token_for_remote(&self.remote);
+        match login {
+            Ok((token, _remote)) => Ok(token),
+            Err(InfinyonCredentialError::Expired(login_remote)) => {
+                self.refresh_login_token(&login_remote).await
+            }
+            // without a login, actions are requested anonymously
+            Err(_) => Ok(String::new()),
+        }
+    }
+
+    async fn refresh_login_token(&self, login_remote: &str) -> Result<String> {
+        let expired = || InfinyonCredentialError::Expired(login_remote.to_owned());
+        #[cfg(not(target_arch = "wasm32"))]
+        let refresh_token = read_refresh_token_async(&self.remote).await;
+        #[cfg(target_arch = "wasm32")]
+        let refresh_token = read_refresh_token(&self.remote);
+        let refresh_token = refresh_token.ok_or_else(expired)?;
+        let msg = serde_json::to_string(&MsgRefreshToken { refresh_token })
+            .map_err(|_e| HubError::HubAccess("Failed token refresh setup".to_string()))?;
+        let req = http::Request::post(format!("{login_remote}/{CLOUD_API_TOKEN_REFRESH}"))
+            .header(http::header::CONTENT_TYPE, mime::JSON.as_str())
+            .body(msg)
+            .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+
+        let resp = crate::htclient::send(req)
+            .await
+            .map_err(|e| HubError::HubAccess(format!("Failed to connect {e}")))?;
+        if resp.status() != StatusCode::OK {
+            debug!(status = %resp.status(), "login token refresh rejected");
+            return Err(expired().into());
+        }
+        let refreshed: RefreshedToken = resp
+            .json()
+            .map_err(|e| HubError::General(format!("couldn't parse refreshed token {e}")))?;
+        update_infinyon_token(&self.remote, &refreshed)?;
+        debug!(remote = login_remote, "login token refreshed");
+        Ok(refreshed.token)
+    }
+
     async fn make_action_token(&self, action: &str, authn_token: String) -> Result<String> {
         let host = &self.remote;
         let api_url = format!("{host}/{HUB_API_ACT}");
@@ -317,6 +391,12 @@ pub struct MsgActionToken {
     pub act: String,
 }
 
+/// refresh of an expired cloud login token (client -> server)
+#[derive(Serialize, Deserialize)]
+struct MsgRefreshToken {
+    refresh_token: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct MsgHubIdReq {
     pub hubid: String,
@@ -331,6 +411,25 @@ pub fn default_cfg_path() -> Result<PathBuf> {
     Ok(hub_cfg_path)
 }
 
+/// Operation of a scoped hub token covering the action, None for actions needing a login
+fn action_token_op(action: &str) -> Option<HubTokenOp> {
+    match action {
+        ACTION_LIST | ACTION_LIST_WITH_META | ACTION_DOWNLOAD | ACTION_BPKG_GET => {
+            Some(HubTokenOp::Download)
+        }
+        ACTION_PUBLISH => Some(HubTokenOp::Publish),
+        _ => None,
+    }
+}
+
+/// Check the scoped hub token, when one is used, allows `op` on the packages of `group`
+pub fn check_token_scope(op: HubTokenOp, group: &str) -> Result<()> {
+    if let Some(scoped) = read_scoped_token()? {
+        scoped.check(op, group)?;
+    }
+    Ok(())
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Deserialize)]
 struct ReplyHubref {
@@ -339,8 +438,11 @@ struct ReplyHubref {
 
 #[cfg(not(target_arch = "wasm32"))]
 fn get_hubref() -> Option<String> {
-    let Ok((_, fcremote)) = read_infinyon_token_rem() else {
-        return None;
+    // the remote of an expired login still points to the hub
+    let fcremote = match read_infinyon_token_rem() {
+        Ok((_, fcremote)) => fcremote,
+        Err(InfinyonCredentialError::Expired(fcremote)) => fcremote,
+        Err(_) => return None,
     };
     if fcremote == DEFAULT_CLOUD_REMOTE {
         return None; // use default
@@ -1,6 +1,13 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+mod device_login;
 mod hubaccess;
+mod lint;
+mod org_members;
 mod package;
 mod package_meta_ext;
+mod provenance;
 mod utils;
 
 #[cfg(feature = "connector-cmds")]
@@ -15,9 +22,16 @@ pub mod fvm;
 use const_format::concatcp;
 
 pub use http;
+#[cfg(not(target_arch = "wasm32"))]
+pub use cache::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use device_login::*;
 pub use hubaccess::*;
+pub use lint::*;
+pub use org_members::*;
 pub use package::*;
 pub use package_meta_ext::*;
+pub use provenance::*;
 pub use utils::*;
 pub use utils::sha256_digest;
 
@@ -0,0 +1,282 @@
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+use fluvio_hub_protocol::{HubError, PackageMeta, Result};
+use fluvio_hub_protocol::constants::{HUB_MANIFEST_BLOB, HUB_SIGNFILE_BASE, SDF_PKG_KIND};
+
+use crate::package::{validate_wasm_file, ARCH_TAG_NAME};
+use crate::{
+    package_get_manifest_file, package_get_meta, package_get_topfile, package_getsigs_with_readio,
+    package_verify_sig_from_readio, packagename_validate,
+};
+
+/// largest package file accepted by the hub
+pub const HUB_PACKAGE_MAX_SIZE: u64 = 100 * 1024 * 1024;
+/// largest WASM module accepted in a package
+pub const HUB_WASM_MAX_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Hub endpoint a package is published to, from its tags
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageKind {
+    SmartModule,
+    /// connector binary built for `target`
+    Connector {
+        target: String,
+    },
+    Sdf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageFile {
+    pub name: String,
+    pub len: u64,
+}
+
+/// Result of the checks of a signed package, made locally before it is uploaded
+#[derive(Debug)]
+pub struct PackageLint {
+    pub meta: PackageMeta,
+    pub kind: PackageKind,
+    /// size of the package file
+    pub size: u64,
+    /// top level files, as uploaded
+    pub files: Vec<PackageFile>,
+    /// files of the manifest blob
+    pub manifest: Vec<PackageFile>,
+    /// public keys of the signatures that verified
+    pub signers: Vec<String>,
+    /// reasons the hub would reject the package
+    pub problems: Vec<String>,
+}
+
+impl PackageLint {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Check the package as the hub does on publish: manifest, signatures, target and sizes.
+///
+/// Connector packages are checked against `target` when given. Only failures to read
+/// the package are errors, the findings are in [`PackageLint::problems`].
+pub fn package_lint(pkgfile: &str, target: Option<&str>) -> Result<PackageLint> {
+    let mut problems = Vec::new();
+
+    let size = std::fs::metadata(pkgfile)?.len();
+    if size > HUB_PACKAGE_MAX_SIZE {
+        problems.push(format!(
+            "package is {size} bytes, the hub accepts up to {HUB_PACKAGE_MAX_SIZE} bytes"
+        ));
+    }
+
+    let meta = package_get_meta(pkgfile)?;
+    if let Err(err) = packagename_validate(&meta.name).and_then(|_| meta.naming_check()) {
+        problems.push(err.to_string().trim_end().to_owned());
+    }
+    let file_name = Path::new(pkgfile)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if file_name != meta.packagefile_name() {
+        problems.push(format!(
+            "package file {file_name} should be named {}",
+            meta.packagefile_name()
+        ));
+    }
+
+    let files = tar_files(std::fs::File::open(pkgfile)?)?;
+    let manifest = tar_files(GzDecoder::new(
+        &package_get_topfile(pkgfile, HUB_MANIFEST_BLOB)?[..],
+    ))?;
+    for listed in &meta.manifest {
+        if !manifest.iter().any(|file| &file.name == listed) {
+            problems.push(format!(
+                "manifest file {listed} is missing from the package"
+            ));
+        }
+    }
+
+    let signers = check_signatures(pkgfile, &files, &mut problems)?;
+
+    let kind = package_kind(&meta);
+    match (&kind, target) {
+        (PackageKind::Connector { target: built }, _) if !is_target_triple(built) => {
+            problems.push(format!("connector target {built} is not a target triple"));
+        }
+        (PackageKind::Connector { target: built }, Some(target)) if built != target => {
+            problems.push(format!(
+                "connector is built for {built}, it would be published for {target}"
+            ));
+        }
+        (PackageKind::SmartModule, Some(target)) => {
+            problems.push(format!(
+                "SmartModule packages run on any target, {target} would be ignored"
+            ));
+        }
+        _ => {}
+    }
+
+    // SmartModules run on any target as long as the engine can load them
+    let wasm_files: Vec<&PackageFile> = manifest
+        .iter()
+        .filter(|file| kind == PackageKind::SmartModule && file.name.ends_with(".wasm"))
+        .collect();
+    if kind == PackageKind::SmartModule && wasm_files.is_empty() {
+        problems.push("SmartModule package has no WASM module".to_owned());
+    }
+    for wasm in wasm_files {
+        if wasm.len > HUB_WASM_MAX_SIZE {
+            problems.push(format!(
+                "{} is {} bytes, the hub accepts modules up to {HUB_WASM_MAX_SIZE} bytes",
+                wasm.name, wasm.len
+            ));
+        }
+        let bytes = package_get_manifest_file(pkgfile, &wasm.name)?;
+        if let Err(err) = validate_wasm_file(&bytes) {
+            problems.push(format!("{}: {err}", wasm.name));
+        }
+    }
+
+    Ok(PackageLint {
+        meta,
+        kind,
+        size,
+        files,
+        manifest,
+        signers,
+        problems,
+    })
+}
+
+pub fn package_kind(meta: &PackageMeta) -> PackageKind {
+    let tag = |name: &str| {
+        meta.tag_get(name)
+            .and_then(|tags| tags.into_iter().next())
+            .map(|tag| tag.value)
+    };
+    if tag(SDF_PKG_KIND).is_some() {
+        PackageKind::Sdf
+    } else if let Some(target) = tag(ARCH_TAG_NAME) {
+        PackageKind::Connector { target }
+    } else {
+        PackageKind::SmartModule
+    }
+}
+
+fn tar_files<R: Read>(readio: R) -> Result<Vec<PackageFile>> {
+    let mut ar = tar::Archive::new(readio);
+    let mut files = Vec::new();
+    for entry in ar.entries()? {
+        let entry = entry?;
+        files.push(PackageFile {
+            name: entry.path()?.to_string_lossy().to_string(),
+            len: entry.header().size()?,
+        });
+    }
+    Ok(files)
+}
+
+/// every signature must verify and the publisher signature must cover every file
+fn check_signatures(
+    pkgfile: &str,
+    files: &[PackageFile],
+    problems: &mut Vec<String>,
+) -> Result<Vec<String>> {
+    let mut file = std::fs::File::open(pkgfile)?;
+    let sigs = package_getsigs_with_readio(&mut file, pkgfile)?;
+    let mut sig_names: Vec<&String> = sigs.keys().collect();
+    sig_names.sort();
+
+    let Some(publisher) = sig_names.first().and_then(|name| sigs.get(*name)) else {
+        problems.push("package is not signed".to_owned());
+        return Ok(Vec::new());
+    };
+    for unsigned in files.iter().filter(|file| {
+        !file.name.starts_with(HUB_SIGNFILE_BASE)
+            && !publisher.files.iter().any(|fsig| fsig.name == file.name)
+    }) {
+        problems.push(format!("{} is not signed", unsigned.name));
+    }
+
+    let mut signers = Vec::new();
+    for name in sig_names {
+        let sig = &sigs[name];
+        file.rewind()?;
+        match package_verify_sig_from_readio(&mut file, pkgfile, sig) {
+            Ok(()) => signers.push(sig.pubkey.clone()),
+            Err(HubError::PackageVerify(_)) => {
+                problems.push(format!("{name} of key {} does not verify", sig.pubkey))
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(signers)
+}
+
+/// `arch-vendor-os` with an optional `-env`, like `x86_64-unknown-linux-musl`
+fn is_target_triple(target: &str) -> bool {
+    let parts: Vec<&str> = target.split('-').collect();
+    (3..=4).contains(&parts.len()) && parts.iter().all(|part| !part.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keymgmt::Keypair;
+    use crate::{package_assemble_with_provenance, package_sign};
+
+    use super::*;
+
+    #[test]
+    fn hubutil_package_lint() {
+        let outdir = tempfile::tempdir().expect("tempdir");
+        let tarname = package_assemble_with_provenance(
+            "tests/apackage/package-meta.yaml",
+            outdir.path(),
+            None,
+            None,
+        )
+        .expect("package assemble fail");
+        let ipkgname = tarname.with_extension("ipkg");
+        let keypair = Keypair::new().expect("failed to create keypair");
+        package_sign(&tarname, &keypair, &ipkgname).expect("package sign fail");
+        let ipkgname = ipkgname.to_string_lossy().to_string();
+
+        let lint = package_lint(&ipkgname, Some("aarch64-unknown-linux-gnu")).expect("lint");
+        assert!(lint.is_ok(), "{:?}", lint.problems);
+        assert_eq!(
+            lint.kind,
+            PackageKind::Connector {
+                target: "aarch64-unknown-linux-gnu".to_owned()
+            }
+        );
+        assert_eq!(lint.signers, vec![keypair.public().to_hex()]);
+        assert_eq!(lint.manifest.len(), 1);
+        assert_eq!(lint.manifest[0].name, "module.wasm");
+        assert!(lint.files.iter().any(|file| file.name == "signature.0"));
+
+        let lint = package_lint(&ipkgname, Some("x86_64-unknown-linux-musl")).expect("lint");
+        assert_eq!(lint.problems.len(), 1);
+        assert!(lint.problems[0].starts_with("connector is built for"));
+    }
+
+    #[test]
+    fn hubutil_package_lint_problems() {
+        let lint = package_lint("tests/static-example-0.0.1.ipkg", None).expect("lint");
+        assert_eq!(lint.kind, PackageKind::SmartModule);
+        assert_eq!(lint.signers.len(), 1);
+        // uppercase group, a file name not matching the package and a module that is not WASM
+        assert_eq!(lint.problems.len(), 3, "{:?}", lint.problems);
+        assert!(lint.problems[1].starts_with("package file static-example-0.0.1.ipkg"));
+        assert!(lint.problems[2].starts_with("module.wasm: "));
+    }
+
+    #[test]
+    fn hubutil_target_triple() {
+        assert!(is_target_triple("x86_64-unknown-linux-musl"));
+        assert!(is_target_triple("aarch64-apple-darwin"));
+        assert!(!is_target_triple("linux"));
+        assert!(!is_target_triple("x86_64--linux"));
+    }
+}
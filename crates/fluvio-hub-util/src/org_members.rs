@@ -0,0 +1,88 @@
+use http::StatusCode;
+use tracing::debug;
+
+use fluvio_hub_protocol::{Result, HubError};
+use fluvio_hub_protocol::constants::HUB_API_ORG;
+use fluvio_hub_protocol::org::{OrgMember, OrgMembers};
+
+use crate::htclient;
+use crate::HubAccess;
+use crate::htclient::ResponseExt;
+
+fn members_url(access: &HubAccess, org: &str) -> String {
+    format!("{}/{HUB_API_ORG}/{org}/members", access.remote)
+}
+
+/// List the members of an organization and their permissions
+pub async fn get_org_members(access: &HubAccess, org: &str) -> Result<OrgMembers> {
+    let actiontoken = access.get_org_members_token().await?;
+    let url = members_url(access, org);
+    debug!(url, "get org members");
+    let req = http::Request::get(&url)
+        .header("Authorization", &actiontoken)
+        .body("")
+        .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+    let resp = htclient::send(req)
+        .await
+        .map_err(|e| HubError::HubAccess(format!("Failed to connect {e}")))?;
+    let resp = check_status(resp)?;
+    resp.json::<OrgMembers>()
+        .map_err(|e| HubError::General(format!("org members parse error {e}")))
+}
+
+/// Add a member to an organization, or change the role of an existing member
+pub async fn set_org_member(access: &HubAccess, org: &str, member: &OrgMember) -> Result<()> {
+    let actiontoken = access.get_org_admin_token().await?;
+    let url = format!("{}/{}", members_url(access, org), member.user);
+    debug!(url, "put org member");
+    let body = serde_json::to_string(member)?;
+    let req = http::Request::put(&url)
+        .header("Authorization", &actiontoken)
+        .header(http::header::CONTENT_TYPE, mime::JSON.as_str())
+        .body(body)
+        .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+    let resp = htclient::send(req)
+        .await
+        .map_err(|e| HubError::HubAccess(format!("Failed to connect {e}")))?;
+    check_status(resp)?;
+    Ok(())
+}
+
+/// Remove a member from an organization
+pub async fn remove_org_member(access: &HubAccess, org: &str, user: &str) -> Result<()> {
+    let actiontoken = access.get_org_admin_token().await?;
+    let url = format!("{}/{user}", members_url(access, org));
+    debug!(url, "delete org member");
+    let req = http::Request::delete(&url)
+        .header("Authorization", &actiontoken)
+        .body("")
+        .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+    let resp = htclient::send(req)
+        .await
+        .map_err(|e| HubError::HubAccess(format!("Failed to connect {e}")))?;
+    check_status(resp)?;
+    Ok(())
+}
+
+fn check_status(resp: http::Response<Vec<u8>>) -> Result<http::Response<Vec<u8>>> {
+    match resp.status() {
+        StatusCode::OK | StatusCode::NO_CONTENT => Ok(resp),
+        StatusCode::UNAUTHORIZED => Err(HubError::HubAccess(
+            "Unauthorized, please log in with 'fluvio cloud login'".into(),
+        )),
+        StatusCode::FORBIDDEN => Err(HubError::HubAccess(
+            "Forbidden, your role in the organization does not allow this".into(),
+        )),
+        StatusCode::NOT_FOUND => Err(HubError::HubAccess(
+            "organization or member not found".into(),
+        )),
+        code => {
+            let body_err_message = resp
+                .body_string()
+                .unwrap_or_else(|_err| "couldn't fetch error message".to_string());
+            Err(HubError::HubAccess(format!(
+                "Status({code}) {body_err_message}"
+            )))
+        }
+    }
+}
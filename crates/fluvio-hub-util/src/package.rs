@@ -13,14 +13,15 @@ use sha2::{Digest, Sha512};
 use tracing::{debug, warn};
 use wasmparser::{Parser, Chunk, Payload};
 
-use fluvio_hub_protocol::{HubError, PackageMeta, Result};
+use fluvio_hub_protocol::{HubError, PackageMeta, PackageSbom, Provenance, Result};
 use fluvio_hub_protocol::constants::{
-    HUB_PACKAGE_META, HUB_SIGNFILE_BASE, HUB_MANIFEST_BLOB, HUB_PACKAGE_META_CLEAN,
+    HUB_PACKAGE_META, HUB_SIGNFILE_BASE, HUB_MANIFEST_BLOB, HUB_PACKAGE_META_CLEAN, HUB_PROVENANCE,
 };
 
 use crate::PackageMetaExt;
 use crate::keymgmt::{Keypair, PublicKey, Signature};
 use crate::HubAccess;
+use crate::sha256_digest;
 
 pub(crate) const ARCH_TAG_NAME: &str = "arch";
 
@@ -37,6 +38,25 @@ pub fn package_assemble_and_sign<P: AsRef<Path>, T: AsRef<Path>>(
     target: Option<&str>,
 ) -> Result<String> {
     let tarname = package_assemble(pkgmeta, outdir, target)?;
+    package_sign_tar(tarname, access)
+}
+
+/// assemble and sign a package like [`package_assemble_and_sign`], attaching the
+/// build provenance and optionally an SBOM file as signed top level files
+pub fn package_assemble_and_sign_with_provenance<P: AsRef<Path>, T: AsRef<Path>>(
+    pkgmeta: P,
+    access: &HubAccess,
+    outdir: T,
+    target: Option<&str>,
+    provenance: Provenance,
+    sbom: Option<&Path>,
+) -> Result<String> {
+    let tarname =
+        package_assemble_with_provenance(pkgmeta, outdir, target, Some((provenance, sbom)))?;
+    package_sign_tar(tarname, access)
+}
+
+fn package_sign_tar(tarname: PathBuf, access: &HubAccess) -> Result<String> {
     let ipkgname = tar_to_ipkg(&tarname);
     let keypair = access.keypair()?;
     package_sign(&tarname, &keypair, &ipkgname)?;
@@ -60,6 +80,15 @@ fn package_assemble<P: AsRef<Path>, T: AsRef<Path>>(
     pkgmeta: P,
     outdir: T,
     target: Option<&str>,
+) -> Result<PathBuf> {
+    package_assemble_with_provenance(pkgmeta, outdir, target, None)
+}
+
+pub(crate) fn package_assemble_with_provenance<P: AsRef<Path>, T: AsRef<Path>>(
+    pkgmeta: P,
+    outdir: T,
+    target: Option<&str>,
+    provenance: Option<(Provenance, Option<&Path>)>,
 ) -> Result<PathBuf> {
     debug!(target: "package_assemble", "opening");
     let pm = PackageMeta::read_from_file(&pkgmeta)?;
@@ -119,6 +148,33 @@ fn package_assemble<P: AsRef<Path>, T: AsRef<Path>>(
 
     pkgtar.append_path_with_name(&clean_tmp, HUB_PACKAGE_META)?;
     pkgtar.append_path_with_name(&manipath, HUB_MANIFEST_BLOB)?;
+    if let Some((mut provenance, sbom)) = provenance {
+        if let Some(sbom) = sbom {
+            let file = sbom
+                .file_name()
+                .map(|fname| fname.to_string_lossy().to_string())
+                .ok_or_else(|| HubError::ManifestInvalidFile(sbom.to_string_lossy().to_string()))?;
+            if [HUB_PACKAGE_META, HUB_MANIFEST_BLOB, HUB_PROVENANCE].contains(&file.as_str())
+                || file.starts_with(HUB_SIGNFILE_BASE)
+            {
+                return Err(HubError::UnableToAssemblePackage(format!(
+                    "sbom file name {file} is reserved"
+                )));
+            }
+            let sha256 = sha256_digest(&sbom.to_path_buf())?;
+            pkgtar.append_path_with_name(sbom, &file)?;
+            provenance.sbom = Some(PackageSbom { file, sha256 });
+        }
+
+        debug!(target: "package_assemble", "writing provenance");
+        let provenance_tmp = outdir.as_ref().join(HUB_PROVENANCE);
+        let buf = serde_json::to_vec_pretty(&provenance).map_err(|err| {
+            HubError::UnableToAssemblePackage(format!("provenance serialization: {err}"))
+        })?;
+        std::fs::write(&provenance_tmp, buf)?;
+        pkgtar.append_path_with_name(&provenance_tmp, HUB_PROVENANCE)?;
+        std::fs::remove_file(&provenance_tmp)?;
+    }
     pkgtar.finish()?;
 
     debug!(target: "package_assemble", "removing temporary manifest blob and cleaned manifest");
@@ -402,7 +458,7 @@ pub fn package_get_wasmfile_with_readio<R: std::io::Read, P: AsRef<Path>>(
 
 /// Validates a SmartModule's WASM payload to represent a valid WASM file
 /// in the binary format (*.wasm).
-fn validate_wasm_file(mut data: &[u8]) -> Result<()> {
+pub(crate) fn validate_wasm_file(mut data: &[u8]) -> Result<()> {
     let mut parser = Parser::default();
 
     loop {
@@ -429,7 +485,7 @@ fn validate_wasm_file(mut data: &[u8]) -> Result<()> {
 
 /// verify package signature. the pkgsig should contain the desired
 /// public key to verify sgainst
-fn package_verify_sig_from_readio<R: std::io::Read>(
+pub(crate) fn package_verify_sig_from_readio<R: std::io::Read>(
     readio: &mut R,
     pkgfile: &str,
     pkgsig: &PackageSignature,
@@ -0,0 +1,161 @@
+use std::io::Seek;
+use std::path::Path;
+use std::process::Command;
+
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use fluvio_hub_protocol::{HubError, Provenance, Result};
+use fluvio_hub_protocol::constants::HUB_PROVENANCE;
+
+use crate::{package_get_topfile, package_getsigs_with_readio, package_verify_sig_from_readio};
+
+/// Collect the provenance of a build from the git checkout containing `dir`
+///
+/// Outside of a git checkout only the builder and build time are recorded.
+pub fn provenance_from_git<P: AsRef<Path>>(dir: P, builder: &str) -> Provenance {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir.as_ref())
+            .args(args)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let git_commit = git(&["rev-parse", "HEAD"]);
+    let git_dirty = git_commit.is_some()
+        && git(&["status", "--porcelain"])
+            .map(|status| !status.is_empty())
+            .unwrap_or(true);
+    let builder = if std::env::var_os("GITHUB_ACTIONS").is_some() {
+        format!("{builder} (github-actions)")
+    } else if std::env::var_os("CI").is_some() {
+        format!("{builder} (ci)")
+    } else {
+        builder.to_string()
+    };
+    Provenance {
+        git_repository: git(&["remote", "get-url", "origin"]),
+        git_commit,
+        git_dirty,
+        builder: Some(builder),
+        built_at: Some(chrono::Utc::now().to_rfc3339()),
+        sbom: None,
+    }
+}
+
+/// Read the provenance of a package, packages published without one return None
+pub fn package_get_provenance<P: AsRef<Path>>(pkgfile: P) -> Result<Option<Provenance>> {
+    let buf = match package_get_topfile(pkgfile.as_ref(), HUB_PROVENANCE) {
+        Ok(buf) => buf,
+        Err(HubError::PackageMissingFile(_)) => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let provenance = serde_json::from_slice(&buf).map_err(|_| {
+        HubError::PackageVerify(format!(
+            "{} could not decode provenance",
+            pkgfile.as_ref().to_string_lossy()
+        ))
+    })?;
+    Ok(Some(provenance))
+}
+
+/// Verify the provenance of a package
+///
+/// The provenance and the SBOM it refers to must be covered by a valid signature
+/// and the SBOM must match the recorded digest. Packages published without a
+/// provenance return None.
+pub fn package_verify_provenance(pkgfile: &str) -> Result<Option<Provenance>> {
+    let Some(provenance) = package_get_provenance(pkgfile)? else {
+        return Ok(None);
+    };
+
+    let mut file = std::fs::File::open(pkgfile)?;
+    let sigs = package_getsigs_with_readio(&mut file, pkgfile)?;
+    // the publisher signature is the first one, later signatures sign it in turn
+    let mut sig_names: Vec<&String> = sigs.keys().collect();
+    sig_names.sort();
+    let signs = |fname: &str| {
+        sig_names
+            .iter()
+            .filter_map(|name| sigs.get(*name))
+            .find(|sig| sig.files.iter().any(|fsig| fsig.name == fname))
+    };
+    let sig = signs(HUB_PROVENANCE)
+        .ok_or_else(|| HubError::PackageVerify(format!("{pkgfile} provenance is not signed")))?;
+    file.rewind()?;
+    package_verify_sig_from_readio(&mut file, pkgfile, sig)?;
+
+    if let Some(sbom) = &provenance.sbom {
+        if !sig.files.iter().any(|fsig| fsig.name == sbom.file) {
+            return Err(HubError::PackageVerify(format!(
+                "{pkgfile} sbom {} is not signed",
+                sbom.file
+            )));
+        }
+        let buf = package_get_topfile(pkgfile, &sbom.file)?;
+        let sha256 = hex::encode(Sha256::digest(&buf));
+        if sha256 != sbom.sha256 {
+            return Err(HubError::PackageVerify(format!(
+                "{pkgfile} sbom {} does not match its digest",
+                sbom.file
+            )));
+        }
+    }
+    debug!(pkgfile, "provenance verified");
+    Ok(Some(provenance))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::keymgmt::Keypair;
+    use crate::{package_assemble_with_provenance, package_sign};
+
+    use super::*;
+
+    #[test]
+    fn hubutil_package_provenance() {
+        let outdir = tempfile::tempdir().expect("tempdir");
+        let sbom = Path::new("tests/hub_package_meta_rw_test.yaml");
+        let provenance = Provenance {
+            git_commit: Some("0a1b2c3".into()),
+            builder: Some("hubutil tests".into()),
+            ..Default::default()
+        };
+        let tarname = package_assemble_with_provenance(
+            "tests/apackage/package-meta.yaml",
+            outdir.path(),
+            None,
+            Some((provenance, Some(sbom))),
+        )
+        .expect("package assemble fail");
+        let ipkgname = tarname.with_extension("ipkg");
+        let keypair = Keypair::new().expect("failed to create keypair");
+        package_sign(&tarname, &keypair, &ipkgname).expect("package sign fail");
+
+        let verified = package_verify_provenance(&ipkgname.to_string_lossy())
+            .expect("provenance verify fail")
+            .expect("missing provenance");
+        assert_eq!(verified.git_commit.as_deref(), Some("0a1b2c3"));
+        let package_sbom = verified.sbom.expect("missing sbom");
+        assert_eq!(package_sbom.file, "hub_package_meta_rw_test.yaml");
+        assert_eq!(
+            package_sbom.sha256,
+            crate::sha256_digest(&sbom.to_path_buf()).unwrap()
+        );
+    }
+
+    #[test]
+    fn hubutil_package_without_provenance() {
+        let provenance = package_verify_provenance("tests/static-example-0.0.1.ipkg")
+            .expect("provenance verify fail");
+        assert!(provenance.is_none());
+    }
+}
@@ -9,9 +9,10 @@ use tracing::{debug, info};
 
 use fluvio_hub_protocol::{PackageMeta, Result, HubError};
 use fluvio_hub_protocol::constants::HUB_PACKAGE_EXT;
+use fluvio_hub_protocol::infinyon_tok::HubTokenOp;
 
 use crate::htclient;
-use crate::HubAccess;
+use crate::{HubAccess, check_token_scope};
 use crate::{HUB_API_SM, HUB_API_CONN_PKG};
 use crate::{package_get_meta, packagename_validate};
 use crate::htclient::ResponseExt;
@@ -44,6 +45,12 @@ pub fn cli_pkgname_split(pkgname: &str) -> Result<(&str, &str, &str)> {
     Ok((org, pkg, ver))
 }
 
+/// Check the scoped hub token, when one is used, allows downloading the package
+pub fn check_download_scope(pkgname: &str) -> Result<()> {
+    let (org, _pkg, _ver) = cli_pkgname_split(pkgname)?;
+    check_token_scope(HubTokenOp::Download, org)
+}
+
 /// Returns url string on sucess or Err(InvalidPackageName)
 pub fn cli_pkgname_to_url(pkgname: &str, remote: &str) -> Result<String> {
     let (org, pkg, ver) = cli_pkgname_split(pkgname)?;
@@ -92,6 +99,22 @@ pub async fn get_package(pkgurl: &str, access: &HubAccess) -> Result<Vec<u8>> {
     get_package_with_token(pkgurl, &actiontoken).await
 }
 
+/// get a package like [`get_package`] through the local cache of the hub client
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn get_package_cached(
+    pkgurl: &str,
+    access: &HubAccess,
+    cache: &crate::HubCache,
+    mode: crate::CacheMode,
+) -> Result<Vec<u8>> {
+    crate::cached_get(cache, mode, pkgurl, || access.get_download_token())
+        .await
+        .map_err(|err| match err {
+            HubError::HubAccess(msg) => HubError::PackageDownload(msg),
+            err => err,
+        })
+}
+
 pub async fn get_package_with_token(pkgurl: &str, actiontoken: &str) -> Result<Vec<u8>> {
     let req = http::Request::get(pkgurl)
         .header("Authorization", actiontoken)
@@ -203,6 +226,7 @@ pub async fn push_package_sdf(pkgpath: &str, access: &HubAccess) -> Result<()> {
 pub async fn push_package_api(put_url: &str, pkgpath: &str, access: &HubAccess) -> Result<()> {
     let pm = package_get_meta(pkgpath)?;
     packagename_validate(&pm.name)?;
+    check_token_scope(HubTokenOp::Publish, &pm.group)?;
 
     // check that given pkg file matches name
     let pkgfile = Path::new(pkgpath)
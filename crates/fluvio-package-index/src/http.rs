@@ -106,6 +106,30 @@ impl HttpAgent {
         Ok(Request::get(url.as_str()).body(())?)
     }
 
+    /// detached ed25519 signature of the release file, hex encoded
+    pub fn request_release_signature<T>(
+        &self,
+        id: &PackageId<T>,
+        version: &semver::Version,
+        target: &Target,
+    ) -> Result<Request<()>> {
+        let file_name = if target.to_string().contains("windows") {
+            format!("{}.exe", id.name())
+        } else {
+            id.name().to_string()
+        };
+        let url = self.base_url.join(&format!(
+            "packages/{group}/{name}/{version}/{target}/{file_name}.sig",
+            group = &id.group(),
+            name = &id.name(),
+            file_name = file_name,
+            version = version,
+            target = target.as_str(),
+        ))?;
+
+        Ok(Request::get(url.as_str()).body(())?)
+    }
+
     pub async fn tag_version_from_response(
         &self,
         tag: &TagName,
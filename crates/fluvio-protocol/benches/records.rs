@@ -0,0 +1,51 @@
+use std::io::Cursor;
+
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::record::{Batch, RawRecords, Record};
+
+const RECORDS: usize = 1000;
+const RECORD_SIZE: usize = 1024;
+
+fn encoded_batch() -> Vec<u8> {
+    let mut batch = Batch::new();
+    for _ in 0..RECORDS {
+        batch.add_record(Record::new(vec![b'x'; RECORD_SIZE]));
+    }
+    let mut encoded = vec![];
+    batch.encode(&mut encoded, 0).unwrap();
+    encoded
+}
+
+fn bench_decode_records_slice(c: &mut Criterion) {
+    let encoded = encoded_batch();
+
+    c.bench_function("records decoding from slice", |b| {
+        b.iter(|| {
+            let mut cursor = Cursor::new(&encoded);
+            let batch: Batch<RawRecords> = Batch::decode_from(&mut cursor, 0).unwrap();
+            batch.memory_records().unwrap()
+        })
+    });
+}
+
+fn bench_decode_records_bytes(c: &mut Criterion) {
+    let encoded = Bytes::from(encoded_batch());
+
+    c.bench_function("records decoding from bytes", |b| {
+        b.iter(|| {
+            let mut src = encoded.clone();
+            let batch: Batch<RawRecords> = Batch::decode_from(&mut src, 0).unwrap();
+            batch.memory_records().unwrap()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_decode_records_slice,
+    bench_decode_records_bytes
+);
+criterion_main!(benches);
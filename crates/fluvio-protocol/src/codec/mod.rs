@@ -93,11 +93,16 @@ impl<T: FluvioEncoder> Encoder<FluvioCodecData<T>> for FluvioCodec {
         buf.reserve(4 + size as usize);
 
         // First 4 bytes are the size of the message.
-        // Then the message payload.
-        let mut len_slice = Vec::new();
-        size.encode(&mut len_slice, version)?;
-        buf.extend_from_slice(&len_slice);
-        buf.extend_from_slice(&src.as_bytes(version)?);
+        // Then the message payload, encoded in place in the frame buffer.
+        let start = buf.len();
+        if let Err(err) = size
+            .encode(buf, version)
+            .and_then(|_| src.encode(buf, version))
+        {
+            // don't leave a partial frame behind
+            buf.truncate(start);
+            return Err(err);
+        }
 
         Ok(())
     }
@@ -0,0 +1,75 @@
+use std::io::Error;
+
+use bytes::{Bytes, BytesMut};
+
+use crate::{Encoder, Version};
+
+/// Buffer reused to encode messages.
+///
+/// Each message is split off as reference counted [`Bytes`] sharing the buffer
+/// allocation. Once the bytes of previous messages are dropped, typically after
+/// they were written to the socket, the next message reuses the same allocation
+/// instead of allocating a new one.
+#[derive(Debug)]
+pub struct EncodeBuffer {
+    buf: BytesMut,
+    capacity: usize,
+}
+
+impl Default for EncodeBuffer {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl EncodeBuffer {
+    pub const DEFAULT_CAPACITY: usize = 8192;
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// empty buffer with room for at least `additional` bytes
+    pub fn reserve(&mut self, additional: usize) -> &mut BytesMut {
+        self.buf.reserve(additional.max(self.capacity));
+        &mut self.buf
+    }
+
+    /// bytes written since the last split
+    pub fn split(&mut self) -> Bytes {
+        self.buf.split().freeze()
+    }
+
+    /// encode `msg` into the buffer
+    pub fn encode<E: Encoder>(&mut self, msg: &E, version: Version) -> Result<Bytes, Error> {
+        let size = msg.write_size(version);
+        msg.encode(self.reserve(size), version)?;
+        Ok(self.split())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reuse_allocation() {
+        let mut buffer = EncodeBuffer::with_capacity(64);
+
+        let first = buffer.encode(&vec![1u32, 2, 3], 0).expect("encode");
+        assert_eq!(first.as_ref(), vec![1u32, 2, 3].as_bytes(0).expect("bytes"));
+        let allocation = first.as_ptr();
+
+        // still referenced, the next message goes after it
+        let second = buffer.encode(&7u64, 0).expect("encode");
+        assert_ne!(second.as_ptr(), allocation);
+
+        drop(first);
+        drop(second);
+        let third = buffer.encode(&"message".to_owned(), 0).expect("encode");
+        assert_eq!(third.as_ptr(), allocation);
+    }
+}
@@ -28,10 +28,8 @@ pub trait Encoder {
 
     fn as_bytes(&self, version: Version) -> Result<Bytes, Error> {
         let len = self.write_size(version);
-        let mut out = Vec::with_capacity(len);
-        self.encode(&mut out, version)?;
-        let mut buf = BytesMut::with_capacity(out.len());
-        buf.put_slice(&out);
+        let mut buf = BytesMut::with_capacity(len);
+        self.encode(&mut buf, version)?;
         trace!(len = buf.len(), "encoding as bytes");
         Ok(buf.freeze())
     }
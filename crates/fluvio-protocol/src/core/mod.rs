@@ -1,5 +1,6 @@
 mod bytebuf;
 mod decoder;
+mod encode_buffer;
 mod encoder;
 mod varint;
 mod zerocopy;
@@ -7,6 +8,7 @@ mod zerocopy;
 pub use self::bytebuf::ByteBuf;
 pub use self::decoder::Decoder;
 pub use self::decoder::DecoderVarInt;
+pub use self::encode_buffer::EncodeBuffer;
 pub use self::encoder::Encoder;
 pub use self::encoder::EncoderVarInt;
 
@@ -0,0 +1,167 @@
+//!
+//! # Wire Compatibility Corpus
+//!
+//! Golden encodings of protocol messages, one hex file per message and version.
+//! A corpus entry is captured once and kept when the message changes, so the tests
+//! replay bytes written by previous releases against the current decoders and
+//! compare the current encoders against what previous releases expect to read.
+//!
+//! Set `FLUVIO_UPDATE_CORPUS=1` to write missing entries or to accept an intended
+//! change of the encoding.
+//!
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::{Decoder, Encoder, Version};
+
+pub const UPDATE_CORPUS_ENV: &str = "FLUVIO_UPDATE_CORPUS";
+
+/// Checks messages against golden encodings stored in a directory
+pub struct WireCorpus {
+    dir: PathBuf,
+    update: bool,
+    failures: Vec<String>,
+}
+
+impl WireCorpus {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let update = std::env::var(UPDATE_CORPUS_ENV).is_ok_and(|value| value != "0");
+        Self {
+            dir: dir.into(),
+            update,
+            failures: vec![],
+        }
+    }
+
+    /// Check `value` against the entries `{name}.v{version}.hex` for each version.
+    ///
+    /// The golden bytes must decode completely and encode back to the same bytes,
+    /// and encoding `value` must produce the golden bytes.
+    pub fn check<M>(&mut self, name: &str, value: &M, versions: &[Version]) -> &mut Self
+    where
+        M: Encoder + Decoder,
+    {
+        for version in versions {
+            if let Err(failure) = self.check_version(name, value, *version) {
+                self.failures.push(format!("{name} v{version}: {failure}"));
+            }
+        }
+        self
+    }
+
+    fn check_version<M>(&self, name: &str, value: &M, version: Version) -> Result<(), String>
+    where
+        M: Encoder + Decoder,
+    {
+        let path = self.dir.join(format!("{name}.v{version}.hex"));
+        let current = encode(value, version)?;
+
+        let golden = match fs::read_to_string(&path) {
+            Ok(content) => parse_hex(&content)?,
+            Err(_) if self.update => return self.write(&path, name, version, &current),
+            Err(err) => {
+                return Err(format!(
+                    "no golden encoding at {}: {err}, set {UPDATE_CORPUS_ENV}=1 to capture it",
+                    path.display()
+                ))
+            }
+        };
+
+        let mut src = Cursor::new(&golden);
+        let decoded = M::decode_from(&mut src, version)
+            .map_err(|err| format!("golden bytes no longer decode: {err}"))?;
+        let remaining = golden.len() - src.position() as usize;
+        if remaining > 0 {
+            return Err(format!("{remaining} golden bytes left after decoding"));
+        }
+        let reencoded = encode(&decoded, version)?;
+        if reencoded != golden {
+            return Err(format!(
+                "golden bytes decode to a different message, encoded again as {}",
+                to_hex(&reencoded)
+            ));
+        }
+
+        if current != golden {
+            if self.update {
+                return self.write(&path, name, version, &current);
+            }
+            return Err(format!(
+                "encoding changed, expected {} but encoded {}",
+                to_hex(&golden),
+                to_hex(&current)
+            ));
+        }
+        Ok(())
+    }
+
+    fn write(&self, path: &Path, name: &str, version: Version, bytes: &[u8]) -> Result<(), String> {
+        info!(path = %path.display(), "writing golden encoding");
+        fs::create_dir_all(&self.dir).map_err(|err| err.to_string())?;
+        let content = format!("# {name} version {version}\n{}\n", to_hex(bytes));
+        fs::write(path, content).map_err(|err| err.to_string())
+    }
+
+    /// panics with all the failed checks
+    pub fn finish(&mut self) {
+        if !self.failures.is_empty() {
+            panic!(
+                "wire compatibility broken:\n{}",
+                std::mem::take(&mut self.failures).join("\n")
+            );
+        }
+    }
+}
+
+fn encode<M: Encoder>(value: &M, version: Version) -> Result<Vec<u8>, String> {
+    let mut dest = vec![];
+    value
+        .encode(&mut dest, version)
+        .map_err(|err| format!("encoding failed: {err}"))?;
+    Ok(dest)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{byte:02x}");
+        out
+    })
+}
+
+/// hex digits, whitespace is ignored and `#` starts a comment line
+fn parse_hex(content: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(|line| line.bytes())
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    if digits.len() % 2 != 0 {
+        return Err("odd number of hex digits".to_owned());
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).map_err(|err| err.to_string())?;
+            u8::from_str_radix(pair, 16).map_err(|err| format!("invalid hex '{pair}': {err}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let bytes = vec![0, 1, 0x2a, 0xff];
+        assert_eq!(to_hex(&bytes), "00012aff");
+        assert_eq!(parse_hex("# comment\n0001\n 2a ff\n").unwrap(), bytes);
+        assert!(parse_hex("abc").is_err());
+        assert!(parse_hex("zz").is_err());
+    }
+}
@@ -30,12 +30,16 @@ pub mod link;
 #[cfg(feature = "fixture")]
 pub mod fixture;
 
+#[cfg(feature = "fixture")]
+pub mod corpus;
+
 #[cfg(all(unix, feature = "store"))]
 pub mod store;
 
 pub use self::core::ByteBuf;
 pub use self::core::Decoder;
 pub use self::core::DecoderVarInt;
+pub use self::core::EncodeBuffer;
 pub use self::core::Encoder;
 pub use self::core::EncoderVarInt;
 pub use self::core::Version;
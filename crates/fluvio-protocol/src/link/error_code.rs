@@ -48,6 +48,17 @@ pub enum ErrorCode {
     #[fluvio(tag = 56)]
     #[error("a storage error occurred")]
     StorageError,
+    #[fluvio(tag = 57)]
+    #[error("the checksum of a record batch doesn't match its content")]
+    CorruptBatch,
+    #[fluvio(tag = 58)]
+    #[error("the connection is being drained, continue on a new connection")]
+    ConnectionDraining,
+    #[fluvio(tag = 59)]
+    #[error(
+        "the storage of the partition leader is full, records are rejected until space is freed"
+    )]
+    StorageFull,
     #[fluvio(tag = 60)]
     #[error("invalid create request")]
     InvalidCreateRequest,
@@ -231,6 +242,82 @@ pub enum ErrorCode {
     #[fluvio(tag = 12002)]
     #[error("system {kind} '{name}' can only be updated forcibly")]
     SystemSpecUpdatingAttempt { kind: String, name: String },
+
+    // AlertRule Errors
+    #[fluvio(tag = 13000)]
+    #[error("the alert rule is invalid: {0}")]
+    AlertRuleInvalid(String),
+    #[fluvio(tag = 13001)]
+    #[error("the alert rule was not found")]
+    AlertRuleNotFound,
+    #[fluvio(tag = 13002)]
+    #[error("the alert rule already exists")]
+    AlertRuleAlreadyExists,
+
+    // ConsumerGroup Errors
+    #[fluvio(tag = 15000)]
+    #[error("the consumer group is invalid: {0}")]
+    ConsumerGroupInvalid(String),
+    #[fluvio(tag = 15001)]
+    #[error("the consumer group was not found")]
+    ConsumerGroupNotFound,
+    #[fluvio(tag = 15002)]
+    #[error("the consumer group already exists")]
+    ConsumerGroupAlreadyExists,
+    #[fluvio(tag = 15003)]
+    #[error("the member is not part of the consumer group, join it again")]
+    ConsumerGroupMemberNotFound,
+    #[fluvio(tag = 15004)]
+    #[error("another member joined the consumer group with the instance id of this member")]
+    ConsumerGroupMemberFenced,
+
+    // Validation
+    #[fluvio(tag = 14000)]
+    #[error("Validation SmartModule is not loaded into the cluster")]
+    ValidationSmartModuleNotLoaded,
+    #[fluvio(tag = 14001)]
+    #[error("Validation SmartModule name is invalid: {0}")]
+    ValidationSmartModuleNameInvalid(String),
+    #[fluvio(tag = 14002)]
+    #[error("record rejected by validation SmartModule: {0}")]
+    RecordValidationFailed(super::smartmodule::SmartModuleTransformRuntimeError),
+
+    // Idempotent Producer
+    #[fluvio(tag = 16000)]
+    #[error("out of order sequence for producer {producer_id}, expected {expected} but received {received}")]
+    ProducerSequenceOutOfOrder {
+        producer_id: i64,
+        expected: i32,
+        received: i32,
+    },
+    #[fluvio(tag = 16001)]
+    #[error("producer {producer_id} was fenced by a newer epoch")]
+    ProducerFenced { producer_id: i64 },
+
+    // Schema Registry
+    #[fluvio(tag = 17000)]
+    #[error("the schema is invalid: {0}")]
+    SchemaInvalid(String),
+    #[fluvio(tag = 17001)]
+    #[error("the schema was not found")]
+    SchemaNotFound,
+    #[fluvio(tag = 17002)]
+    #[error("the schema already exists")]
+    SchemaAlreadyExists,
+    #[fluvio(tag = 17003)]
+    #[error("the schema is incompatible with version {version}: {reason}")]
+    SchemaIncompatible { version: u32, reason: String },
+
+    // Access control
+    #[fluvio(tag = 18000)]
+    #[error("the acl is invalid: {0}")]
+    AclInvalid(String),
+    #[fluvio(tag = 18001)]
+    #[error("the acl was not found")]
+    AclNotFound,
+    #[fluvio(tag = 18002)]
+    #[error("the acl already exists")]
+    AclAlreadyExists,
 }
 
 impl ErrorCode {
@@ -303,6 +390,9 @@ mod test {
         assert_tag!(ErrorCode::MessageTooLarge, 10, 0);
         assert_tag!(ErrorCode::PermissionDenied, 13, 0);
         assert_tag!(ErrorCode::StorageError, 56, 0);
+        assert_tag!(ErrorCode::CorruptBatch, 57, 0);
+        assert_tag!(ErrorCode::ConnectionDraining, 58, 0);
+        assert_tag!(ErrorCode::StorageFull, 59, 0);
 
         // Spu errors
         assert_tag!(ErrorCode::SpuError, 1000, 0);
@@ -87,6 +87,8 @@ pub enum SmartModuleKind {
     Join,
     #[fluvio(min_version = 17, tag = 6)]
     Generic,
+    #[fluvio(min_version = 22, tag = 7)]
+    Batch,
 }
 
 impl Default for SmartModuleKind {
@@ -8,6 +8,8 @@ use super::ErrorCode;
 
 pub const VERSIONS_API_KEY: u16 = 18;
 pub const V10_PLATFORM: i16 = 2;
+/// response includes range of client versions supported by the cluster
+pub const V_SUPPORTED_CLIENT: i16 = 3;
 
 // -----------------------------------
 // ApiVersionsRequest
@@ -25,7 +27,7 @@ pub struct ApiVersionsRequest {
 
 impl Request for ApiVersionsRequest {
     const API_KEY: u16 = VERSIONS_API_KEY;
-    const DEFAULT_API_VERSION: i16 = V10_PLATFORM;
+    const DEFAULT_API_VERSION: i16 = V_SUPPORTED_CLIENT;
     type Response = ApiVersionsResponse;
 }
 
@@ -40,6 +42,8 @@ pub struct ApiVersionsResponse {
     pub error_code: ErrorCode,
     pub api_keys: ApiVersions,
     pub platform_version: PlatformVersion,
+    #[fluvio(min_version = 3)]
+    pub supported_client: ClientVersionRange,
 }
 
 #[derive(Decoder, Encoder, Default, Clone, Debug, Eq, PartialEq)]
@@ -119,6 +123,91 @@ impl Encoder for PlatformVersion {
     }
 }
 
+/// Platform versions of clients supported by the cluster, both bounds are inclusive.
+///
+/// A missing bound means any version is accepted on that side.
+#[derive(Default, Clone, Debug, Eq, PartialEq)]
+pub struct ClientVersionRange {
+    pub min: Option<semver::Version>,
+    pub max: Option<semver::Version>,
+}
+
+impl ClientVersionRange {
+    pub fn new(min: Option<semver::Version>, max: Option<semver::Version>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    pub fn contains(&self, version: &semver::Version) -> bool {
+        self.min.as_ref().map_or(true, |min| version >= min)
+            && self.max.as_ref().map_or(true, |max| version <= max)
+    }
+}
+
+impl std::fmt::Display for ClientVersionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => write!(f, ">={min}, <={max}"),
+            (Some(min), None) => write!(f, ">={min}"),
+            (None, Some(max)) => write!(f, "<={max}"),
+            (None, None) => write!(f, "*"),
+        }
+    }
+}
+
+fn encode_bound(bound: &Option<semver::Version>) -> String {
+    bound
+        .as_ref()
+        .map(|version| version.to_string())
+        .unwrap_or_default()
+}
+
+fn decode_bound(bound: String) -> Result<Option<semver::Version>, IoError> {
+    if bound.is_empty() {
+        return Ok(None);
+    }
+    semver::Version::parse(&bound)
+        .map(Some)
+        .map_err(|_| IoError::new(ErrorKind::InvalidData, "client version is not valid semver"))
+}
+
+impl Decoder for ClientVersionRange {
+    fn decode<T>(&mut self, src: &mut T, version: i16) -> Result<(), IoError>
+    where
+        T: Buf,
+    {
+        // clusters that predate this field answer with a shorter response
+        if !src.has_remaining() {
+            return Ok(());
+        }
+        let mut min = String::default();
+        min.decode(src, version)?;
+        let mut max = String::default();
+        max.decode(src, version)?;
+
+        self.min = decode_bound(min)?;
+        self.max = decode_bound(max)?;
+        Ok(())
+    }
+}
+
+impl Encoder for ClientVersionRange {
+    fn write_size(&self, version: Version) -> usize {
+        encode_bound(&self.min).write_size(version) + encode_bound(&self.max).write_size(version)
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: BufMut,
+    {
+        encode_bound(&self.min).encode(dest, version)?;
+        encode_bound(&self.max).encode(dest, version)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +246,7 @@ mod tests {
                 error_code: ErrorCode::None,
                 api_keys: vec![],
                 platform_version,
+                supported_client: ClientVersionRange::default(),
             }
         }
 
@@ -171,4 +261,44 @@ mod tests {
 
         assert_eq!(api_version, decoded_api_version);
     }
+
+    #[test]
+    fn test_supported_client_versions() {
+        let range = ClientVersionRange::new(
+            Some(semver::Version::new(0, 11, 0)),
+            Some(semver::Version::new(0, 13, 0)),
+        );
+        let response = ApiVersionsResponse {
+            supported_client: range.clone(),
+            ..Default::default()
+        };
+        let mut buffer: Vec<u8> = vec![];
+        response
+            .encode(&mut buffer, V_SUPPORTED_CLIENT)
+            .expect("encode");
+
+        let mut decoded = ApiVersionsResponse::default();
+        decoded
+            .decode(&mut (&*buffer), V_SUPPORTED_CLIENT)
+            .expect("decode");
+        assert_eq!(decoded.supported_client, range);
+        assert!(range.contains(&semver::Version::new(0, 12, 1)));
+        assert!(!range.contains(&semver::Version::new(0, 10, 0)));
+        assert!(!range.contains(&semver::Version::new(0, 14, 0)));
+        assert_eq!(range.to_string(), ">=0.11.0, <=0.13.0");
+    }
+
+    #[test]
+    fn test_decode_response_without_supported_client() {
+        // response of cluster without supported client range, decoded by newer client
+        let response = ApiVersionsResponse::default();
+        let mut buffer: Vec<u8> = vec![];
+        response.encode(&mut buffer, V10_PLATFORM).expect("encode");
+
+        let mut decoded = ApiVersionsResponse::default();
+        decoded
+            .decode(&mut (&*buffer), V_SUPPORTED_CLIENT)
+            .expect("decode");
+        assert!(decoded.supported_client.is_unbounded());
+    }
 }
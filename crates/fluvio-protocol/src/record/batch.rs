@@ -19,17 +19,28 @@ use super::Record;
 use super::Offset;
 
 const ATTR_SCHEMA_PRESENT: i16 = 0x10;
+const ATTR_COMPRESSION_DICTIONARY: i16 = 0x20;
 const ATTR_COMPRESSION_CODEC_MASK: i16 = 0x07;
 pub const NO_TIMESTAMP: i64 = -1;
 
 const SCHEMA_ID_NULL: SchemaId = SchemaId(0u32);
 
+/// Sequence number `records` after `sequence`, sequences of a producer wrap to zero.
+pub fn increment_sequence(sequence: i32, records: i32) -> i32 {
+    ((sequence as i64 + records as i64) % (i32::MAX as i64 + 1)) as i32
+}
+
 pub trait BatchRecords: Default + Debug + Encoder + Decoder + Send + Sync {
     /// how many bytes does record wants to process
     #[deprecated]
     fn remainder_bytes(&self, remainder: usize) -> usize {
         remainder
     }
+
+    /// records already encoded, checksummed without encoding them again
+    fn raw_bytes(&self) -> Option<&[u8]> {
+        None
+    }
 }
 
 /// A type describing in-memory records
@@ -61,12 +72,22 @@ impl Decoder for RawRecords {
 }
 impl BatchRecords for MemoryRecords {}
 
-impl BatchRecords for RawRecords {}
+impl BatchRecords for RawRecords {
+    fn raw_bytes(&self) -> Option<&[u8]> {
+        Some(&self.0)
+    }
+}
 
 /// size of the offset and length
 pub const BATCH_PREAMBLE_SIZE: usize = size_of::<Offset>()     // Offset
         + size_of::<i32>(); // i32
 
+/// size of the header fields covered by the crc, without the schema id
+const BATCH_CRC_HEADER_SIZE: usize = BATCH_HEADER_SIZE
+        - size_of::<i32>()      // partition leader epoch
+        - size_of::<u8>()       // magic
+        - size_of::<i32>(); // crc
+
 pub const BATCH_FILE_HEADER_SIZE: usize = BATCH_PREAMBLE_SIZE + BATCH_HEADER_SIZE;
 
 #[derive(Clone, Default, Debug, Encoder, PartialEq)]
@@ -224,6 +245,35 @@ impl TryFrom<Batch> for Batch<RawRecords> {
     }
 }
 
+#[cfg(feature = "compress")]
+impl Batch {
+    /// Compress the records with zstd and the dictionary `dictionary_id`,
+    /// it must be registered for `topic` with [`fluvio_compression::register_dictionary`].
+    pub fn compress_with_dictionary(
+        mut self,
+        topic: &str,
+        dictionary_id: u32,
+    ) -> Result<Batch<RawRecords>, CompressionError> {
+        let mut buf = Vec::new();
+        self.records.encode(&mut buf, 0)?;
+        let records = RawRecords(fluvio_compression::compress_with_dictionary(
+            &buf,
+            topic,
+            dictionary_id,
+        )?);
+
+        self.header.set_compression(Compression::Zstd);
+        self.header.set_compression_dictionary();
+        Ok(Batch {
+            base_offset: self.base_offset,
+            batch_len: records.0.len() as i32,
+            header: self.header,
+            schema_id: self.schema_id,
+            records,
+        })
+    }
+}
+
 impl<R> Batch<R>
 where
     R: Encoder,
@@ -244,6 +294,43 @@ where
 }
 
 impl<R: BatchRecords> Batch<R> {
+    /// checksum of the batch, from the attributes to the end of the records.
+    ///
+    /// CRC32C uses the SSE4.2 or ARMv8 CRC instructions when the CPU supports them,
+    /// with a software fallback otherwise.
+    pub fn compute_crc(&self) -> Result<u32, Error> {
+        let mut header = Vec::with_capacity(BATCH_CRC_HEADER_SIZE + size_of::<SchemaId>());
+        self.encode_crc_header(&mut header)?;
+        let crc = crc32c::crc32c(&header);
+        match self.records.raw_bytes() {
+            Some(raw) => Ok(crc32c::crc32c_append(crc, raw)),
+            None => {
+                let records = self.records.as_bytes(0)?;
+                Ok(crc32c::crc32c_append(crc, &records))
+            }
+        }
+    }
+
+    /// true if the checksum in the header matches the content of the batch
+    pub fn validate_crc(&self) -> bool {
+        self.compute_crc().is_ok_and(|crc| crc == self.header.crc)
+    }
+
+    /// header fields covered by the checksum
+    fn encode_crc_header<T: BufMut>(&self, buf: &mut T) -> Result<(), Error> {
+        self.header.attributes.encode(buf, 0)?;
+        self.header.last_offset_delta.encode(buf, 0)?;
+        self.header.first_timestamp.encode(buf, 0)?;
+        self.header.max_time_stamp.encode(buf, 0)?;
+        self.header.producer_id.encode(buf, 0)?;
+        self.header.producer_epoch.encode(buf, 0)?;
+        self.header.first_sequence.encode(buf, 0)?;
+        if self.header.has_schema() {
+            self.schema_id.encode(buf, 0)?;
+        }
+        Ok(())
+    }
+
     /// Create a new empty batch
     pub fn new() -> Self {
         Self::default()
@@ -310,17 +397,18 @@ impl Batch<RawRecords> {
             if #[cfg(feature = "compress")] {
                 let compression = self.get_compression()?;
 
+                // records are decoded from `Bytes` so their keys and values share its buffer
                 if let Compression::None = compression {
-                    records.decode(&mut &self.records.0[..], 0)?;
+                    records.decode(&mut self.records.0.clone(), 0)?;
                 } else {
 
                     let decompressed = compression
                         .uncompress(&self.records.0[..])?
                         .ok_or(CompressionError::UnreachableError)?;
-                    records.decode(&mut &decompressed[..], 0)?;
+                    records.decode(&mut Bytes::from(decompressed), 0)?;
                 }
             } else {
-                records.decode(&mut &self.records.0[..], 0)?;
+                records.decode(&mut self.records.0.clone(), 0)?;
             }
         }
 
@@ -408,18 +496,18 @@ where
         self.header.partition_leader_epoch.encode(dest, version)?;
         self.header.magic.encode(dest, version)?;
 
-        let mut out: Vec<u8> = Vec::new();
-        let buf = &mut out;
-        self.header.attributes.encode(buf, version)?;
-        self.header.last_offset_delta.encode(buf, version)?;
-        self.header.first_timestamp.encode(buf, version)?;
-        self.header.max_time_stamp.encode(buf, version)?;
-        self.header.producer_id.encode(buf, version)?;
-        self.header.producer_epoch.encode(buf, version)?;
-        self.header.first_sequence.encode(buf, version)?;
-        if self.header.has_schema() {
-            self.schema_id.encode(buf, version)?;
+        // raw records are checksummed in place instead of being copied
+        if let Some(raw) = self.records.raw_bytes() {
+            let crc = self.compute_crc()?;
+            crc.encode(dest, version)?;
+            self.encode_crc_header(dest)?;
+            dest.put_slice(raw);
+            return Ok(());
         }
+
+        let mut out: Vec<u8> = Vec::with_capacity(self.write_size(version));
+        let buf = &mut out;
+        self.encode_crc_header(buf)?;
         self.records.encode(buf, version)?;
 
         let crc = crc32c::crc32c(&out);
@@ -466,7 +554,18 @@ impl BatchHeader {
 
     pub fn set_compression(&mut self, compression: Compression) {
         let compression_bits = compression as i16 & ATTR_COMPRESSION_CODEC_MASK;
-        self.attributes = (self.attributes & !ATTR_COMPRESSION_CODEC_MASK) | compression_bits;
+        self.attributes = (self.attributes
+            & !(ATTR_COMPRESSION_CODEC_MASK | ATTR_COMPRESSION_DICTIONARY))
+            | compression_bits;
+    }
+
+    /// records are compressed with a zstd dictionary, its id is in the header of the zstd frame
+    pub fn has_compression_dictionary(&self) -> bool {
+        self.attributes & ATTR_COMPRESSION_DICTIONARY != 0
+    }
+
+    pub fn set_compression_dictionary(&mut self) {
+        self.attributes |= ATTR_COMPRESSION_DICTIONARY;
     }
 
     pub fn has_schema(&self) -> bool {
@@ -636,6 +735,26 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_batch_crc() -> Result<(), IoError> {
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.records.push(Record::new("value"));
+        batch.header.first_timestamp = 1555478494747;
+        batch.header.max_time_stamp = 1555478494747;
+
+        let bytes = batch.as_bytes(0)?;
+        let mut decoded = Batch::<RawRecords>::default();
+        decoded.decode(&mut Cursor::new(&bytes), 0)?;
+        assert_eq!(decoded.header.crc, batch.compute_crc()?);
+        assert!(decoded.validate_crc());
+        // raw records encode to the same bytes
+        assert_eq!(decoded.as_bytes(0)?, bytes);
+
+        decoded.header.first_timestamp += 1;
+        assert!(!decoded.validate_crc());
+        Ok(())
+    }
+
     #[test]
     fn test_batch_offset_delta() {
         let mut batch = Batch::<MemoryRecords>::default();
@@ -920,4 +1039,25 @@ mod test {
         batch.header.set_schema_id();
         assert!(batch.header.has_schema());
     }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_compression_dictionary_attribute() {
+        let mut header = BatchHeader::default();
+        header.set_compression(Compression::Zstd);
+        header.set_compression_dictionary();
+        assert!(header.has_compression_dictionary());
+        assert_eq!(header.get_compression().unwrap(), Compression::Zstd);
+
+        header.set_compression(Compression::Gzip);
+        assert!(!header.has_compression_dictionary());
+    }
+
+    #[test]
+    fn test_increment_sequence() {
+        assert_eq!(increment_sequence(0, 5), 5);
+        assert_eq!(increment_sequence(i32::MAX - 1, 1), i32::MAX);
+        assert_eq!(increment_sequence(i32::MAX, 1), 0);
+        assert_eq!(increment_sequence(i32::MAX - 1, 3), 1);
+    }
 }
@@ -8,7 +8,6 @@ use std::ops::Deref;
 use std::str::Utf8Error;
 
 use bytes::Bytes;
-use bytes::BytesMut;
 use content_inspector::{inspect, ContentType};
 use tracing::{trace, warn};
 use once_cell::sync::Lazy;
@@ -218,14 +217,19 @@ impl Decoder for RecordData {
         let mut len: i64 = 0;
         len.decode_varint(src)?;
         let len = len as usize;
+        if src.remaining() < len {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                format!(
+                    "not enough bytes for record data, expected: {len}, found: {}",
+                    src.remaining()
+                ),
+            ));
+        }
 
-        // Take `len` bytes from `src` and put them into a new BytesMut buffer
-        let slice = src.take(len);
-        let mut bytes = BytesMut::with_capacity(len);
-        bytes.put(slice);
-
-        // Replace the inner Bytes buffer of this RecordData
-        self.0 = bytes.freeze();
+        // shares the buffer of `src` when it is `Bytes`, so records decoded from
+        // a fetch response point into it instead of being copied
+        self.0 = src.copy_to_bytes(len);
         Ok(())
     }
 }
@@ -413,12 +417,71 @@ impl RecordHeader {
     }
 }
 
+/// Key-value metadata attached to a record.
+///
+/// Headers are encoded after the value, as in Kafka. Records without headers keep the
+/// encoding used before headers existed, but records with headers can only be read by
+/// clients and SmartModules that understand them. SPUs strip the headers from the records
+/// they send to consumers of an older API version, and the SmartModule engine strips them
+/// from the input of SmartModules running at an older version.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RecordHeaderEntry {
+    pub key: String,
+    pub value: RecordData,
+}
+
+impl RecordHeaderEntry {
+    pub fn new(key: impl Into<String>, value: impl Into<RecordData>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    fn write_size(&self, version: Version) -> usize {
+        let key_len = self.key.len() as i64;
+        key_len.var_write_size() + self.key.len() + self.value.write_size(version)
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: BufMut,
+    {
+        let key_len = self.key.len() as i64;
+        key_len.encode_varint(dest)?;
+        dest.put_slice(self.key.as_bytes());
+        self.value.encode(dest, version)
+    }
+
+    fn decode<T>(src: &mut T, version: Version) -> Result<Self, Error>
+    where
+        T: Buf,
+    {
+        let mut key_len: i64 = 0;
+        key_len.decode_varint(src)?;
+        let key_len = key_len.max(0) as usize;
+        if src.remaining() < key_len {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "not enough for record header key",
+            ));
+        }
+        let mut key = vec![0; key_len];
+        src.copy_to_slice(&mut key);
+        let key = String::from_utf8(key)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "record header key is not utf8"))?;
+        let mut value = RecordData::default();
+        value.decode(src, version)?;
+        Ok(Self { key, value })
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct Record<B = RecordData> {
     pub preamble: RecordHeader,
     pub key: Option<B>,
     pub value: B,
-    pub headers: i64,
+    pub headers: Vec<RecordHeaderEntry>,
 }
 
 impl<B: Default> Record<B> {
@@ -456,6 +519,24 @@ impl<B: Default> Record<B> {
     pub fn into_key(self) -> Option<B> {
         self.key
     }
+
+    /// Returns the headers of this record
+    pub fn headers(&self) -> &[RecordHeaderEntry] {
+        &self.headers
+    }
+
+    /// Returns the value of the first header with the given key
+    pub fn header(&self, key: &str) -> Option<&RecordData> {
+        self.headers
+            .iter()
+            .find(|header| header.key == key)
+            .map(|header| &header.value)
+    }
+
+    /// Appends a header, existing headers with the same key are kept
+    pub fn add_header(&mut self, key: impl Into<String>, value: impl Into<RecordData>) {
+        self.headers.push(RecordHeaderEntry::new(key, value));
+    }
 }
 
 impl Record {
@@ -513,7 +594,12 @@ where
         let inner_size = self.preamble.write_size(version)
             + self.key.write_size(version)
             + self.value.write_size(version)
-            + self.headers.var_write_size();
+            + (self.headers.len() as i64).var_write_size()
+            + self
+                .headers
+                .iter()
+                .map(|header| header.write_size(version))
+                .sum::<usize>();
         let len: i64 = inner_size as i64;
         len.var_write_size() + inner_size
     }
@@ -526,7 +612,10 @@ where
         self.preamble.encode(&mut out, version)?;
         self.key.encode(&mut out, version)?;
         self.value.encode(&mut out, version)?;
-        self.headers.encode_varint(&mut out)?;
+        (self.headers.len() as i64).encode_varint(&mut out)?;
+        for header in &self.headers {
+            header.encode(&mut out, version)?;
+        }
         let len: i64 = out.len() as i64;
         trace!("record encode as {} bytes", len);
         len.encode_varint(dest)?;
@@ -559,7 +648,11 @@ where
         trace!("offset delta: {}", self.preamble.offset_delta);
         self.key.decode(src, version)?;
         self.value.decode(src, version)?;
-        self.headers.decode_varint(src)?;
+        let mut header_count: i64 = 0;
+        header_count.decode_varint(src)?;
+        self.headers = (0..header_count.max(0))
+            .map(|_| RecordHeaderEntry::decode(src, version))
+            .collect::<Result<_, _>>()?;
 
         Ok(())
     }
@@ -619,6 +712,27 @@ impl ConsumerRecord {
         self.inner().value().as_ref()
     }
 
+    /// Returns the contents of this Record's key without copying them.
+    /// It shares the buffer the record was received in, which is kept alive as long as it is.
+    pub fn key_bytes(&self) -> Option<Bytes> {
+        self.inner().key().map(|key| key.0.clone())
+    }
+
+    /// Returns the contents of this Record's value without copying them, see [`Self::key_bytes`]
+    pub fn value_bytes(&self) -> Bytes {
+        self.inner().value().0.clone()
+    }
+
+    /// Returns this Record's headers, in the order they were added
+    pub fn headers(&self) -> &[RecordHeaderEntry] {
+        self.inner().headers()
+    }
+
+    /// Returns the value of the first header with the given key as a byte slice
+    pub fn header(&self, key: &str) -> Option<&[u8]> {
+        self.inner().header(key).map(|it| it.as_ref())
+    }
+
     /// Return the timestamp of the Record
     pub fn timestamp(&self) -> Timestamp {
         if self.timestamp_base <= 0 {
@@ -728,6 +842,47 @@ mod test {
         assert_eq!(record.value.as_ref(), decoded.value.as_ref());
     }
 
+    #[test]
+    fn test_decode_shares_buffer() {
+        let record = Record::new_key_value("key", "value");
+        let mut encoded = Vec::new();
+        record.encode(&mut encoded, 0).unwrap();
+        let encoded = Bytes::from(encoded);
+        let range = encoded.as_ptr_range();
+
+        let decoded = Record::<RecordData>::decode_from(&mut encoded.clone(), 0).unwrap();
+        assert_eq!(decoded.value.as_ref(), b"value");
+        assert!(range.contains(&decoded.value.as_ref().as_ptr()));
+        assert!(range.contains(&decoded.key.unwrap().as_ref().as_ptr()));
+
+        let mut truncated = encoded.slice(..encoded.len() - 3);
+        assert!(Record::<RecordData>::decode_from(&mut truncated, 0).is_err());
+    }
+
+    #[test]
+    fn test_header_encoding() {
+        let mut record = Record::new_key_value("key", "value");
+        let mut no_headers = Vec::new();
+        record.encode(&mut no_headers, 0).unwrap();
+        assert_eq!(no_headers.last(), Some(&0));
+        assert_eq!(no_headers.len(), record.write_size(0));
+
+        record.add_header("source", "orders");
+        record.add_header("empty", "");
+        let mut encoded = Vec::new();
+        record.encode(&mut encoded, 0).unwrap();
+        assert_eq!(encoded.len(), record.write_size(0));
+
+        let decoded = Record::<RecordData>::decode_from(&mut Cursor::new(encoded), 0).unwrap();
+        assert_eq!(decoded.headers(), record.headers());
+        assert_eq!(
+            decoded.header("source").map(|v| v.as_ref()),
+            Some(b"orders".as_ref())
+        );
+        assert!(decoded.header("missing").is_none());
+        assert_eq!(decoded.value.as_ref(), b"value");
+    }
+
     // Test Specification:
     //
     // A record was encoded and written to a file, using the following code:
@@ -6,8 +6,10 @@ pub use self::data::*;
 
 mod batch;
 mod replica;
+mod trace;
 pub use batch::*;
 pub use replica::*;
+pub use trace::*;
 
 pub type Offset = i64;
 pub type Size = u32;
@@ -0,0 +1,253 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{ConsumerRecord, Record};
+
+/// Header carrying the W3C trace context of a record
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+const VERSION: u8 = 0;
+
+/// [W3C trace context](https://www.w3.org/TR/trace-context/#traceparent-header) of a record.
+///
+/// The span id is the one of the span that wrote the record: the producer when it is sent,
+/// then the SmartModule span of the SPU once the record is transformed. Consumers continue the
+/// trace with a child of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    flags: u8,
+}
+
+impl TraceContext {
+    pub const SAMPLED: u8 = 0x01;
+
+    /// context of an existing span, such as one of OpenTelemetry.
+    /// Returns `None` for the invalid all zero ids.
+    pub fn new(trace_id: [u8; 16], span_id: [u8; 8], flags: u8) -> Option<Self> {
+        let valid = trace_id.iter().any(|byte| *byte != 0) && span_id.iter().any(|byte| *byte != 0);
+        valid.then_some(Self {
+            trace_id,
+            span_id,
+            flags,
+        })
+    }
+
+    /// first span of a new sampled trace
+    pub fn new_root() -> Self {
+        let mut trace_id = [0; 16];
+        trace_id[..8].copy_from_slice(&random_id());
+        trace_id[8..].copy_from_slice(&random_id());
+        Self {
+            trace_id,
+            span_id: random_id(),
+            flags: Self::SAMPLED,
+        }
+    }
+
+    /// a new span of the same trace
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: random_id(),
+            ..*self
+        }
+    }
+
+    pub fn trace_id(&self) -> [u8; 16] {
+        self.trace_id
+    }
+
+    pub fn span_id(&self) -> [u8; 8] {
+        self.span_id
+    }
+
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.flags & Self::SAMPLED != 0
+    }
+
+    pub fn trace_id_hex(&self) -> String {
+        to_hex(&self.trace_id)
+    }
+
+    pub fn span_id_hex(&self) -> String {
+        to_hex(&self.span_id)
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{VERSION:02x}-{}-{}-{:02x}",
+            self.trace_id_hex(),
+            self.span_id_hex(),
+            self.flags
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTraceContext(String);
+
+impl fmt::Display for InvalidTraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid traceparent: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidTraceContext {}
+
+impl FromStr for TraceContext {
+    type Err = InvalidTraceContext;
+
+    /// parse a `traceparent` value, fields added by later versions are ignored
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidTraceContext(value.to_owned());
+        let mut fields = value.trim().split('-');
+        let (Some(version), Some(trace_id), Some(span_id), Some(flags)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid());
+        };
+        let version = from_hex::<1>(version).ok_or_else(invalid)?[0];
+        if version == 0xff || (version == VERSION && fields.next().is_some()) {
+            return Err(invalid());
+        }
+        Self::new(
+            from_hex(trace_id).ok_or_else(invalid)?,
+            from_hex(span_id).ok_or_else(invalid)?,
+            from_hex::<1>(flags).ok_or_else(invalid)?[0],
+        )
+        .ok_or_else(invalid)
+    }
+}
+
+impl<B: Default> Record<B> {
+    /// Returns the trace context of the `traceparent` header, if it is valid
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        parse_header(self.header(TRACEPARENT_HEADER)?)
+    }
+
+    /// Sets the `traceparent` header, replacing the existing one
+    pub fn set_trace_context(&mut self, context: &TraceContext) {
+        self.headers
+            .retain(|header| header.key != TRACEPARENT_HEADER);
+        self.add_header(TRACEPARENT_HEADER, context.to_string());
+    }
+}
+
+impl ConsumerRecord {
+    /// Returns the trace context the record was written with, consumers continue the trace
+    /// with a [`TraceContext::child`] of it
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        parse_header(self.header(TRACEPARENT_HEADER)?)
+    }
+}
+
+fn parse_header(value: &[u8]) -> Option<TraceContext> {
+    std::str::from_utf8(value).ok()?.parse().ok()
+}
+
+/// ids only need to be unique, keys of `RandomState` are random per process
+/// and each id mixes in a counter. On wasm32 the keys may be fixed, so ids of
+/// SmartModules are only unique within an instance.
+fn random_id() -> [u8; 8] {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Ok(now) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        hasher.write_u128(now.as_nanos());
+    }
+    // zero is the invalid id
+    hasher.finish().max(1).to_be_bytes()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// lowercase hex of exactly `N` bytes
+fn from_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 || hex.bytes().any(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(index * 2..index * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_traceparent() {
+        let value = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context: TraceContext = value.parse().expect("traceparent");
+        assert_eq!(context.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(context.span_id_hex(), "00f067aa0ba902b7");
+        assert!(context.is_sampled());
+        assert_eq!(context.to_string(), value);
+
+        // later versions may add fields
+        assert!(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-extra"
+                .parse::<TraceContext>()
+                .is_ok()
+        );
+
+        for invalid in [
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e47-00f067aa0ba902b7-01",
+            "not a traceparent",
+        ] {
+            assert!(invalid.parse::<TraceContext>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_child() {
+        let root = TraceContext::new_root();
+        let child = root.child();
+        assert_eq!(child.trace_id(), root.trace_id());
+        assert_ne!(child.span_id(), root.span_id());
+        assert_ne!(TraceContext::new_root().trace_id(), root.trace_id());
+        assert_eq!(root.to_string().parse::<TraceContext>(), Ok(root));
+    }
+
+    #[test]
+    fn test_record_trace_context() {
+        let mut record = Record::new("value");
+        assert_eq!(record.trace_context(), None);
+
+        let root = TraceContext::new_root();
+        record.add_header("user", "a");
+        record.set_trace_context(&root);
+        assert_eq!(record.trace_context(), Some(root));
+
+        let child = root.child();
+        record.set_trace_context(&child);
+        assert_eq!(record.trace_context(), Some(child));
+        assert_eq!(record.headers().len(), 2);
+
+        record.headers.pop();
+        record.add_header(TRACEPARENT_HEADER, "garbage");
+        assert_eq!(record.trace_context(), None);
+    }
+}
@@ -0,0 +1,10 @@
+use clap::Parser;
+use fluvio_run::edge::EdgeCmd;
+
+fn main() -> anyhow::Result<()> {
+    let cmd: EdgeCmd = EdgeCmd::parse();
+
+    fluvio_future::subscriber::init_tracer(None);
+
+    cmd.process()
+}
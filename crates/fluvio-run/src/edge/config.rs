@@ -0,0 +1,295 @@
+//!
+//! # Edge agent configuration
+//!
+//! Single TOML file describing every component run by the agent:
+//!
+//! ```toml
+//! [sc]
+//! public_addr = "0.0.0.0:9003"
+//! metadata_dir = "/var/lib/fluvio/metadata"
+//!
+//! [spu]
+//! id = 5001
+//! data_dir = "/var/lib/fluvio/data"
+//!
+//! [mirroring]
+//! # file exported from home cluster with `fluvio remote export`
+//! remote_export = "/etc/fluvio/edge-1.json"
+//!
+//! [[connectors]]
+//! name = "mqtt"
+//! executable = "/opt/fluvio/connectors/mqtt-source"
+//! config = "/etc/fluvio/mqtt.yaml"
+//! ```
+//!
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use fluvio_spu::ResourceProfile;
+use fluvio_types::defaults::{SC_PRIVATE_PORT, SC_PUBLIC_PORT};
+use fluvio_types::SpuId;
+
+const DEFAULT_DATA_DIR: &str = "/var/lib/fluvio";
+const DEFAULT_LOG_DIR: &str = "/var/log/fluvio";
+const DEFAULT_RUST_LOG: &str = "info";
+const DEFAULT_SPU_ID: SpuId = 5001;
+const DEFAULT_SPU_PUBLIC_PORT: u16 = 9010;
+const DEFAULT_SPU_PRIVATE_PORT: u16 = 9011;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EdgeConfig {
+    #[serde(default)]
+    pub supervisor: SupervisorConfig,
+    #[serde(default)]
+    pub sc: ScConfig,
+    #[serde(default)]
+    pub spu: SpuConfig,
+    #[serde(default)]
+    pub mirroring: Option<MirroringConfig>,
+    #[serde(default)]
+    pub connectors: Vec<ConnectorConfig>,
+}
+
+impl EdgeConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("unable to read edge config: {}", path.display()))?;
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("invalid edge config: {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for connector in &self.connectors {
+            if !names.insert(connector.name.as_str()) {
+                return Err(anyhow!("duplicate connector name: {}", connector.name));
+            }
+        }
+        Ok(())
+    }
+
+    /// address used by agent and connectors to reach the local SC
+    pub fn sc_client_addr(&self) -> String {
+        local_addr(&self.sc.public_addr)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SupervisorConfig {
+    /// directory for component logs
+    #[serde(default = "default_log_dir")]
+    pub log_dir: PathBuf,
+    /// directory for agent state, such as the connector profile
+    #[serde(default = "default_work_dir")]
+    pub work_dir: PathBuf,
+    /// RUST_LOG for SC and SPU
+    #[serde(default = "default_rust_log")]
+    pub rust_log: String,
+    /// delay before first restart of failed component, doubled on every failure
+    #[serde(default = "default_restart_delay", with = "humantime_serde")]
+    pub restart_delay: Duration,
+    #[serde(default = "default_max_restart_delay", with = "humantime_serde")]
+    pub max_restart_delay: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            log_dir: default_log_dir(),
+            work_dir: default_work_dir(),
+            rust_log: default_rust_log(),
+            restart_delay: default_restart_delay(),
+            max_restart_delay: default_max_restart_delay(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScConfig {
+    #[serde(default = "default_sc_public_addr")]
+    pub public_addr: String,
+    #[serde(default = "default_sc_private_addr")]
+    pub private_addr: String,
+    #[serde(default = "default_metadata_dir")]
+    pub metadata_dir: PathBuf,
+}
+
+impl Default for ScConfig {
+    fn default() -> Self {
+        Self {
+            public_addr: default_sc_public_addr(),
+            private_addr: default_sc_private_addr(),
+            metadata_dir: default_metadata_dir(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpuConfig {
+    #[serde(default = "default_spu_id")]
+    pub id: SpuId,
+    #[serde(default = "default_spu_public_port")]
+    pub public_port: u16,
+    #[serde(default = "default_spu_private_port")]
+    pub private_port: u16,
+    #[serde(default = "default_spu_data_dir")]
+    pub data_dir: PathBuf,
+    /// defaults to edge profile
+    #[serde(default = "default_resource_profile")]
+    pub resource_profile: ResourceProfile,
+}
+
+impl Default for SpuConfig {
+    fn default() -> Self {
+        Self {
+            id: default_spu_id(),
+            public_port: default_spu_public_port(),
+            private_port: default_spu_private_port(),
+            data_dir: default_spu_data_dir(),
+            resource_profile: default_resource_profile(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MirroringConfig {
+    /// remote metadata exported by home cluster
+    pub remote_export: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorConfig {
+    pub name: String,
+    pub executable: PathBuf,
+    pub config: PathBuf,
+    #[serde(default)]
+    pub secrets: Option<PathBuf>,
+    #[serde(default = "default_rust_log")]
+    pub rust_log: String,
+}
+
+/// replace unspecified bind address with loopback
+pub(crate) fn local_addr(bind_addr: &str) -> String {
+    match bind_addr.rsplit_once(':') {
+        Some(("0.0.0.0" | "[::]" | "", port)) => format!("127.0.0.1:{port}"),
+        _ => bind_addr.to_owned(),
+    }
+}
+
+fn default_log_dir() -> PathBuf {
+    PathBuf::from(DEFAULT_LOG_DIR)
+}
+
+fn default_work_dir() -> PathBuf {
+    Path::new(DEFAULT_DATA_DIR).join("edge")
+}
+
+fn default_rust_log() -> String {
+    DEFAULT_RUST_LOG.to_owned()
+}
+
+fn default_restart_delay() -> Duration {
+    Duration::from_secs(1)
+}
+
+fn default_max_restart_delay() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_sc_public_addr() -> String {
+    format!("0.0.0.0:{SC_PUBLIC_PORT}")
+}
+
+fn default_sc_private_addr() -> String {
+    format!("127.0.0.1:{SC_PRIVATE_PORT}")
+}
+
+fn default_metadata_dir() -> PathBuf {
+    Path::new(DEFAULT_DATA_DIR).join("metadata")
+}
+
+fn default_spu_id() -> SpuId {
+    DEFAULT_SPU_ID
+}
+
+fn default_spu_public_port() -> u16 {
+    DEFAULT_SPU_PUBLIC_PORT
+}
+
+fn default_spu_private_port() -> u16 {
+    DEFAULT_SPU_PRIVATE_PORT
+}
+
+fn default_spu_data_dir() -> PathBuf {
+    Path::new(DEFAULT_DATA_DIR).join("data")
+}
+
+fn default_resource_profile() -> ResourceProfile {
+    ResourceProfile::Edge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: EdgeConfig = toml::from_str(
+            r#"
+            [supervisor]
+            log_dir = "/tmp/edge"
+            restart_delay = "500ms"
+
+            [sc]
+            public_addr = "0.0.0.0:9103"
+
+            [spu]
+            resource_profile = "standard"
+
+            [mirroring]
+            remote_export = "/etc/fluvio/edge-1.json"
+
+            [[connectors]]
+            name = "mqtt"
+            executable = "/opt/mqtt-source"
+            config = "/etc/fluvio/mqtt.yaml"
+            "#,
+        )
+        .expect("parse");
+
+        assert_eq!(config.supervisor.log_dir, PathBuf::from("/tmp/edge"));
+        assert_eq!(config.supervisor.restart_delay, Duration::from_millis(500));
+        assert_eq!(config.supervisor.max_restart_delay, Duration::from_secs(60));
+        assert_eq!(config.sc_client_addr(), "127.0.0.1:9103");
+        assert_eq!(config.spu.id, DEFAULT_SPU_ID);
+        assert_eq!(config.spu.resource_profile, ResourceProfile::Standard);
+        assert_eq!(config.connectors.len(), 1);
+        assert!(config.connectors[0].secrets.is_none());
+    }
+
+    #[test]
+    fn test_default_config() {
+        let config: EdgeConfig = toml::from_str("").expect("parse");
+        assert_eq!(config.sc_client_addr(), "127.0.0.1:9003");
+        assert_eq!(config.spu.resource_profile, ResourceProfile::Edge);
+        assert!(config.mirroring.is_none());
+    }
+
+    #[test]
+    fn test_local_addr() {
+        assert_eq!(local_addr("0.0.0.0:9003"), "127.0.0.1:9003");
+        assert_eq!(local_addr("10.0.0.5:9003"), "10.0.0.5:9003");
+    }
+}
@@ -0,0 +1,306 @@
+//!
+//! # Fluvio Edge agent
+//!
+//! Runs a lightweight SC and SPU, the mirroring uplink to a home cluster and
+//! selected connectors from a single binary and configuration file.
+//! SC and SPU are run as child processes of the same executable so they can
+//! be restarted independently by the supervisor.
+//!
+mod config;
+mod supervisor;
+
+pub use config::EdgeConfig;
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use tracing::{debug, info, warn};
+
+use fluvio::config::Config;
+use fluvio::{Fluvio, FluvioAdmin, FluvioConfig};
+use fluvio_controlplane_metadata::spu::{CustomSpuSpec, Endpoint, IngressAddr, IngressPort};
+use fluvio_future::task::run_block_on;
+use fluvio_future::timer::sleep;
+use fluvio_sc::cli::ScOpt;
+use fluvio_sc_schema::mirror::{MirrorSpec, MirrorType};
+use fluvio_sc_schema::remote_file::RemoteMetadataExport;
+use fluvio_spu::SpuOpt;
+use fluvio_types::config_file::SaveLoadConfig;
+
+use self::config::local_addr;
+use self::supervisor::{Component, Supervisor};
+
+const DEFAULT_CONFIG_PATH: &str = "/etc/fluvio/edge.toml";
+const PROFILE_FILE: &str = "profile.toml";
+const SC_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Parser)]
+#[command(name = "fluvio-edge", version = crate::VERSION, about = "Fluvio Edge agent")]
+pub enum EdgeCmd {
+    /// Start SC, SPU, mirroring and connectors described by configuration
+    #[command(name = "start")]
+    Start(StartOpt),
+
+    /// Run SC, used by the agent itself
+    #[command(name = "sc", hide = true)]
+    SC(ScOpt),
+
+    /// Run SPU, used by the agent itself
+    #[command(name = "spu", hide = true)]
+    SPU(SpuOpt),
+}
+
+impl EdgeCmd {
+    pub fn process(self) -> Result<()> {
+        match self {
+            Self::Start(opt) => opt.process(),
+            Self::SC(opt) => {
+                fluvio_sc::start::main_loop(opt);
+                Ok(())
+            }
+            Self::SPU(opt) => {
+                fluvio_spu::main_loop(opt);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct StartOpt {
+    /// Path to agent configuration
+    #[arg(long, short = 'c', default_value = DEFAULT_CONFIG_PATH)]
+    config: PathBuf,
+}
+
+impl StartOpt {
+    pub fn process(self) -> Result<()> {
+        let config = EdgeConfig::load(&self.config)?;
+        let agent = EdgeAgent::new(config)?;
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let signal = shutdown.clone();
+        ctrlc::set_handler(move || signal.store(true, Ordering::SeqCst))?;
+
+        run_block_on(agent.run(shutdown))
+    }
+}
+
+struct EdgeAgent {
+    config: EdgeConfig,
+    launcher: PathBuf,
+}
+
+impl EdgeAgent {
+    fn new(config: EdgeConfig) -> Result<Self> {
+        let launcher = std::env::current_exe().context("unable to find agent executable")?;
+        for dir in [
+            &config.supervisor.log_dir,
+            &config.supervisor.work_dir,
+            &config.sc.metadata_dir,
+            &config.spu.data_dir,
+        ] {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("unable to create {}", dir.display()))?;
+        }
+        Ok(Self { config, launcher })
+    }
+
+    async fn run(self, shutdown: Arc<AtomicBool>) -> Result<()> {
+        let supervisor_config = &self.config.supervisor;
+        let mut supervisor = Supervisor::new(
+            supervisor_config.restart_delay,
+            supervisor_config.max_restart_delay,
+        );
+
+        supervisor.start(self.sc_component())?;
+
+        let client_config = FluvioConfig::new(self.config.sc_client_addr());
+        let admin = wait_for_sc(&client_config).await?;
+        info!("SC is ready");
+
+        self.register_spu(&admin).await?;
+        supervisor.start(self.spu_component())?;
+
+        if let Some(mirroring) = &self.config.mirroring {
+            connect_home(&admin, &mirroring.remote_export).await?;
+        }
+
+        let profile = self.write_profile()?;
+        for connector in &self.config.connectors {
+            supervisor.start(self.connector_component(connector, &profile)?)?;
+        }
+
+        println!("Fluvio edge agent started");
+        supervisor.run(shutdown).await;
+        println!("Fluvio edge agent stopped");
+        Ok(())
+    }
+
+    fn log_file(&self, name: &str) -> PathBuf {
+        self.config.supervisor.log_dir.join(format!("{name}.log"))
+    }
+
+    fn sc_component(&self) -> Component {
+        let sc = &self.config.sc;
+        let mut cmd = Command::new(&self.launcher);
+        cmd.arg("sc")
+            .arg("--local")
+            .arg(&sc.metadata_dir)
+            .arg("--bind-public")
+            .arg(&sc.public_addr)
+            .arg("--bind-private")
+            .arg(&sc.private_addr)
+            .env("RUST_LOG", &self.config.supervisor.rust_log);
+        Component::new("sc", cmd, self.log_file("sc"))
+    }
+
+    fn spu_component(&self) -> Component {
+        let spu = &self.config.spu;
+        let mut cmd = Command::new(&self.launcher);
+        cmd.arg("spu")
+            .arg("-i")
+            .arg(spu.id.to_string())
+            .arg("-p")
+            .arg(format!("0.0.0.0:{}", spu.public_port))
+            .arg("-v")
+            .arg(format!("0.0.0.0:{}", spu.private_port))
+            .arg("--sc-addr")
+            .arg(local_addr(&self.config.sc.private_addr))
+            .arg("--log-base-dir")
+            .arg(&spu.data_dir)
+            .arg("--resource-profile")
+            .arg(spu.resource_profile.to_string())
+            .env("RUST_LOG", &self.config.supervisor.rust_log);
+        let name = format!("spu-{}", spu.id);
+        let log_file = self.log_file(&name);
+        Component::new(name, cmd, log_file)
+    }
+
+    fn connector_component(
+        &self,
+        connector: &config::ConnectorConfig,
+        profile: &Path,
+    ) -> Result<Component> {
+        let canonical = |path: &Path| {
+            path.canonicalize().with_context(|| {
+                format!(
+                    "connector {}: invalid path {}",
+                    connector.name,
+                    path.display()
+                )
+            })
+        };
+        let mut cmd = Command::new(canonical(&connector.executable)?);
+        cmd.arg("--config").arg(canonical(&connector.config)?);
+        if let Some(secrets) = &connector.secrets {
+            cmd.arg("--secrets").arg(canonical(secrets)?);
+        }
+        // connectors use their own profile pointing to local SC
+        cmd.env("FLV_PROFILE_PATH", profile)
+            .env("RUST_LOG", &connector.rust_log);
+        let name = format!("connector-{}", connector.name);
+        let log_file = self.log_file(&name);
+        Ok(Component::new(name, cmd, log_file))
+    }
+
+    /// register SPU with SC unless it is already known
+    async fn register_spu(&self, admin: &FluvioAdmin) -> Result<()> {
+        let spu = &self.config.spu;
+        let name = format!("custom-spu-{}", spu.id);
+        if !admin
+            .list::<CustomSpuSpec, _>(vec![name.clone()])
+            .await?
+            .is_empty()
+        {
+            debug!(name, "custom spu already registered");
+            return Ok(());
+        }
+
+        let spec = CustomSpuSpec {
+            id: spu.id,
+            public_endpoint: IngressPort {
+                port: spu.public_port,
+                ingress: vec![IngressAddr::from_host("localhost".to_owned())],
+                ..Default::default()
+            },
+            private_endpoint: Endpoint {
+                port: spu.private_port,
+                host: "localhost".to_owned(),
+                ..Default::default()
+            },
+            rack: None,
+            public_endpoint_local: None,
+        };
+        info!(name, "registering custom spu");
+        admin.create(name, false, spec).await?;
+        Ok(())
+    }
+
+    /// profile used by connectors to reach local cluster
+    fn write_profile(&self) -> Result<PathBuf> {
+        let path = self.config.supervisor.work_dir.join(PROFILE_FILE);
+        Config::new_with_local_cluster(self.config.sc_client_addr())
+            .save_to(&path)
+            .with_context(|| format!("unable to write profile {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+async fn wait_for_sc(config: &FluvioConfig) -> Result<FluvioAdmin> {
+    let started = Instant::now();
+    loop {
+        match Fluvio::connect_with_config(config).await {
+            Ok(fluvio) => return Ok(fluvio.admin().await),
+            Err(err) if started.elapsed() < SC_STARTUP_TIMEOUT => {
+                debug!(%err, "SC not ready yet");
+                sleep(Duration::from_secs(1)).await;
+            }
+            Err(err) => {
+                return Err(anyhow!(
+                    "SC did not start within {}s: {err}",
+                    SC_STARTUP_TIMEOUT.as_secs()
+                ))
+            }
+        }
+    }
+}
+
+/// set up mirroring uplink using metadata exported by home cluster
+async fn connect_home(admin: &FluvioAdmin, remote_export: &Path) -> Result<()> {
+    let file = std::fs::File::open(remote_export)
+        .with_context(|| format!("unable to open {}", remote_export.display()))?;
+    let export: RemoteMetadataExport = serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|err| anyhow!("unable to load remote metadata: {err}"))?;
+    let home = export.home;
+    let home_id = home.id.clone();
+
+    if !admin
+        .list::<MirrorSpec, _>(vec![home_id.clone()])
+        .await?
+        .is_empty()
+    {
+        debug!(home_id, "home already connected");
+        return Ok(());
+    }
+
+    match admin
+        .create(
+            home_id.clone(),
+            false,
+            MirrorSpec {
+                mirror_type: MirrorType::Home(home),
+            },
+        )
+        .await
+    {
+        Ok(_) => info!(home_id, "connected with home cluster"),
+        Err(err) => warn!(home_id, %err, "unable to connect with home cluster"),
+    }
+    Ok(())
+}
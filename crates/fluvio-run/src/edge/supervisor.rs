@@ -0,0 +1,221 @@
+//!
+//! # Process supervisor
+//!
+//! Keeps edge components running, restarting failed ones with exponential backoff.
+//!
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tracing::{debug, error, info, warn};
+
+use fluvio_future::timer::sleep;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Process managed by the supervisor
+pub struct Component {
+    name: String,
+    command: Command,
+    log_file: PathBuf,
+    child: Option<Child>,
+    failures: u32,
+    started_at: Option<Instant>,
+    restart_at: Option<Instant>,
+}
+
+impl Component {
+    pub fn new(name: impl Into<String>, command: Command, log_file: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            command,
+            log_file: log_file.into(),
+            child: None,
+            failures: 0,
+            started_at: None,
+            restart_at: None,
+        }
+    }
+
+    fn spawn(&mut self) -> Result<()> {
+        let outputs = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_file)?;
+        let errors = outputs.try_clone()?;
+        let child = self
+            .command
+            .stdin(Stdio::null())
+            .stdout(Stdio::from(outputs))
+            .stderr(Stdio::from(errors))
+            .spawn()?;
+        info!(
+            name = %self.name,
+            pid = child.id(),
+            log = %self.log_file.display(),
+            "started component"
+        );
+        self.child = Some(child);
+        self.started_at = Some(Instant::now());
+        self.restart_at = None;
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            debug!(name = %self.name, pid = child.id(), "stopping component");
+            if let Err(err) = child.kill() {
+                debug!(name = %self.name, %err, "unable to kill component");
+            }
+            let _ = child.wait();
+        }
+    }
+}
+
+/// delay before next restart after `failures` consecutive failures
+fn backoff(initial: Duration, max: Duration, failures: u32) -> Duration {
+    initial
+        .checked_mul(1 << failures.min(16))
+        .unwrap_or(max)
+        .min(max)
+}
+
+pub struct Supervisor {
+    components: Vec<Component>,
+    restart_delay: Duration,
+    max_restart_delay: Duration,
+}
+
+impl Supervisor {
+    pub fn new(restart_delay: Duration, max_restart_delay: Duration) -> Self {
+        Self {
+            components: vec![],
+            restart_delay,
+            max_restart_delay,
+        }
+    }
+
+    /// start component and keep it running
+    pub fn start(&mut self, mut component: Component) -> Result<()> {
+        component.spawn()?;
+        self.components.push(component);
+        Ok(())
+    }
+
+    /// check components once, restarting the ones which are due
+    pub fn poll(&mut self) {
+        let now = Instant::now();
+        for component in self.components.iter_mut() {
+            if let Some(child) = component.child.as_mut() {
+                match child.try_wait() {
+                    Ok(None) => continue,
+                    Ok(Some(status)) => {
+                        // a component which ran long enough is considered healthy again
+                        let healthy = component
+                            .started_at
+                            .map(|started| started.elapsed() >= self.max_restart_delay)
+                            .unwrap_or(false);
+                        if healthy {
+                            component.failures = 0;
+                        }
+                        let delay = backoff(
+                            self.restart_delay,
+                            self.max_restart_delay,
+                            component.failures,
+                        );
+                        component.failures = component.failures.saturating_add(1);
+                        warn!(
+                            name = %component.name,
+                            %status,
+                            restart_in = ?delay,
+                            "component exited"
+                        );
+                        component.child = None;
+                        component.restart_at = Some(now + delay);
+                    }
+                    Err(err) => {
+                        error!(name = %component.name, %err, "unable to check component");
+                        continue;
+                    }
+                }
+            }
+
+            if component.restart_at.is_some_and(|at| at <= now) {
+                if let Err(err) = component.spawn() {
+                    let delay = backoff(
+                        self.restart_delay,
+                        self.max_restart_delay,
+                        component.failures,
+                    );
+                    component.failures = component.failures.saturating_add(1);
+                    error!(name = %component.name, %err, restart_in = ?delay, "unable to restart component");
+                    component.restart_at = Some(now + delay);
+                }
+            }
+        }
+    }
+
+    /// supervise components until shutdown is requested, then stop them in reverse start order
+    pub async fn run(mut self, shutdown: Arc<AtomicBool>) {
+        while !shutdown.load(Ordering::SeqCst) {
+            self.poll();
+            sleep(POLL_INTERVAL).await;
+        }
+        self.shutdown();
+    }
+
+    pub fn shutdown(&mut self) {
+        debug!("stopping components");
+        for component in self.components.iter_mut().rev() {
+            component.stop();
+        }
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let initial = Duration::from_secs(1);
+        let max = Duration::from_secs(30);
+        assert_eq!(backoff(initial, max, 0), Duration::from_secs(1));
+        assert_eq!(backoff(initial, max, 3), Duration::from_secs(8));
+        assert_eq!(backoff(initial, max, 5), max);
+        assert_eq!(backoff(initial, max, u32::MAX), max);
+    }
+
+    #[test]
+    fn test_restart_exited_component() {
+        let log = std::env::temp_dir().join("fluvio-edge-supervisor-test.log");
+        let mut supervisor = Supervisor::new(Duration::ZERO, Duration::from_secs(60));
+        supervisor
+            .start(Component::new("exit", Command::new("true"), &log))
+            .expect("start");
+
+        let first_pid = supervisor.components[0].child.as_ref().map(|c| c.id());
+        let mut restarted = false;
+        for _ in 0..100 {
+            supervisor.poll();
+            let pid = supervisor.components[0].child.as_ref().map(|c| c.id());
+            if pid.is_some() && pid != first_pid {
+                restarted = true;
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(restarted);
+        assert!(supervisor.components[0].failures > 0);
+    }
+}
@@ -1,12 +1,15 @@
 use clap::Parser;
 
 mod error;
+pub mod edge;
+mod service_host;
 
 pub use error::RunnerError;
 use error::Result;
 use fluvio_spu::SpuOpt;
 use fluvio_sc::cli::ScOpt;
 use fluvio_extension_common::FluvioExtensionMetadata;
+use service_host::ServiceHostOpt;
 
 const VERSION: &str = include_str!("../../../VERSION");
 
@@ -26,6 +29,10 @@ pub enum RunCmd {
     /// Print version information
     #[command(name = "version")]
     Version(VersionOpt),
+
+    /// Host the SC or a SPU installed as Windows service
+    #[command(name = "service-host", hide = true)]
+    ServiceHost(ServiceHostOpt),
 }
 
 impl RunCmd {
@@ -43,6 +50,9 @@ impl RunCmd {
             Self::Version(opt) => {
                 opt.process()?;
             }
+            Self::ServiceHost(opt) => {
+                opt.process()?;
+            }
         }
         Ok(())
     }
@@ -59,12 +69,12 @@ impl MetadataOpt {
     }
 
     pub fn metadata() -> FluvioExtensionMetadata {
-        FluvioExtensionMetadata {
-            title: "Fluvio Runner".into(),
-            package: Some("fluvio/fluvio-run".parse().unwrap()),
-            description: "Run Fluvio cluster components (SC and SPU)".into(),
-            version: semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
-        }
+        FluvioExtensionMetadata::new(
+            "Fluvio Runner",
+            "Run Fluvio cluster components (SC and SPU)",
+            semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
+        )
+        .with_package("fluvio/fluvio-run".parse().unwrap())
     }
 }
 
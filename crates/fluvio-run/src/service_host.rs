@@ -0,0 +1,339 @@
+//!
+//! # Windows service host
+//!
+//! Local cluster services on Windows run `fluvio-run service-host`, which reports the status
+//! of the service to the service control manager and runs the SC or SPU as its child process,
+//! appending its output to the log file. Stopping the service stops the child; a child which
+//! exits with a non zero code stops the service with an error, so the failure actions of the
+//! service restart it.
+//!
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::error::Result;
+use crate::RunnerError;
+
+#[derive(Debug, Parser)]
+pub struct ServiceHostOpt {
+    /// Name the service is registered with
+    #[arg(long)]
+    pub name: String,
+
+    /// File the output of the process is appended to
+    #[arg(long)]
+    pub log_file: PathBuf,
+
+    /// Environment variable of the process, as KEY=VALUE
+    #[arg(long = "env", value_parser = parse_env)]
+    pub envs: Vec<(String, String)>,
+
+    /// fluvio-run arguments of the process, e.g. `sc --local <dir>`
+    #[arg(last = true, required = true)]
+    pub args: Vec<String>,
+}
+
+fn parse_env(env: &str) -> std::result::Result<(String, String), String> {
+    env.split_once('=')
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("invalid environment variable '{env}', expected KEY=VALUE"))
+}
+
+impl ServiceHostOpt {
+    pub fn process(self) -> Result<()> {
+        #[cfg(windows)]
+        {
+            scm::run(self).map_err(|err| RunnerError::Other(format!("{err:#}")))
+        }
+
+        #[cfg(not(windows))]
+        {
+            Err(RunnerError::Other(format!(
+                "unable to host service {}: the service host is only supported on Windows",
+                self.name
+            )))
+        }
+    }
+}
+
+#[cfg(windows)]
+mod scm {
+    use std::ffi::{c_void, OsStr};
+    use std::fs::OpenOptions;
+    use std::os::windows::ffi::OsStrExt;
+    use std::process::{Child, Command, Stdio};
+    use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU32, Ordering};
+    use std::sync::{Mutex, OnceLock};
+    use std::time::Duration;
+
+    use anyhow::{anyhow, Result};
+    use tracing::{error, info, warn};
+
+    use super::ServiceHostOpt;
+
+    const SERVICE_WIN32_OWN_PROCESS: u32 = 0x10;
+    const SERVICE_STOPPED: u32 = 1;
+    const SERVICE_START_PENDING: u32 = 2;
+    const SERVICE_STOP_PENDING: u32 = 3;
+    const SERVICE_RUNNING: u32 = 4;
+    const SERVICE_ACCEPT_STOP: u32 = 0x1;
+    const SERVICE_ACCEPT_SHUTDOWN: u32 = 0x4;
+    const SERVICE_CONTROL_STOP: u32 = 1;
+    const SERVICE_CONTROL_INTERROGATE: u32 = 4;
+    const SERVICE_CONTROL_SHUTDOWN: u32 = 5;
+    const NO_ERROR: u32 = 0;
+    const ERROR_CALL_NOT_IMPLEMENTED: u32 = 120;
+    const ERROR_SERVICE_SPECIFIC_ERROR: u32 = 1066;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(500);
+    const WAIT_HINT_MS: u32 = 10_000;
+
+    #[repr(C)]
+    struct ServiceTableEntry {
+        name: *mut u16,
+        service_main: Option<unsafe extern "system" fn(u32, *mut *mut u16)>,
+    }
+
+    #[repr(C)]
+    struct ServiceStatus {
+        service_type: u32,
+        current_state: u32,
+        controls_accepted: u32,
+        win32_exit_code: u32,
+        service_specific_exit_code: u32,
+        check_point: u32,
+        wait_hint: u32,
+    }
+
+    type HandlerEx = unsafe extern "system" fn(u32, u32, *mut c_void, *mut c_void) -> u32;
+
+    #[link(name = "advapi32")]
+    extern "system" {
+        fn StartServiceCtrlDispatcherW(table: *const ServiceTableEntry) -> i32;
+        fn RegisterServiceCtrlHandlerExW(
+            name: *const u16,
+            handler: HandlerEx,
+            context: *mut c_void,
+        ) -> isize;
+        fn SetServiceStatus(handle: isize, status: *const ServiceStatus) -> i32;
+    }
+
+    /// state shared with the callbacks of the service control manager
+    struct Host {
+        opt: ServiceHostOpt,
+        status_handle: AtomicIsize,
+        check_point: AtomicU32,
+        stopping: AtomicBool,
+        child: Mutex<Option<Child>>,
+    }
+
+    static HOST: OnceLock<Host> = OnceLock::new();
+
+    /// run the service until it is stopped
+    pub fn run(opt: ServiceHostOpt) -> Result<()> {
+        let mut name = wide(&opt.name);
+        HOST.set(Host {
+            opt,
+            status_handle: AtomicIsize::new(0),
+            check_point: AtomicU32::new(0),
+            stopping: AtomicBool::new(false),
+            child: Mutex::new(None),
+        })
+        .map_err(|_| anyhow!("service host is already running"))?;
+
+        let table = [
+            ServiceTableEntry {
+                name: name.as_mut_ptr(),
+                service_main: Some(service_main),
+            },
+            ServiceTableEntry {
+                name: std::ptr::null_mut(),
+                service_main: None,
+            },
+        ];
+        // SAFETY: the table is terminated by a null entry and outlives the call, which returns
+        // once the service has stopped
+        if unsafe { StartServiceCtrlDispatcherW(table.as_ptr()) } == 0 {
+            return Err(anyhow!(
+                "unable to connect to the service control manager: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(())
+    }
+
+    unsafe extern "system" fn service_main(_argc: u32, _argv: *mut *mut u16) {
+        let Some(host) = HOST.get() else {
+            return;
+        };
+        let name = wide(&host.opt.name);
+        // SAFETY: name is a null terminated string and the handler lives as long as the process
+        let handle = unsafe {
+            RegisterServiceCtrlHandlerExW(name.as_ptr(), control_handler, std::ptr::null_mut())
+        };
+        if handle == 0 {
+            error!(
+                err = %std::io::Error::last_os_error(),
+                "unable to register service control handler"
+            );
+            return;
+        }
+        host.status_handle.store(handle, Ordering::SeqCst);
+        host.report(SERVICE_START_PENDING, NO_ERROR, 0);
+
+        let exit_code = match host.run_child() {
+            Ok(code) => code,
+            Err(err) => {
+                error!(%err, "unable to run service process");
+                Some(1)
+            }
+        };
+        match exit_code {
+            Some(code) if code != 0 && !host.stopping.load(Ordering::SeqCst) => {
+                warn!(code, "service process failed");
+                host.report(SERVICE_STOPPED, ERROR_SERVICE_SPECIFIC_ERROR, code as u32);
+            }
+            _ => host.report(SERVICE_STOPPED, NO_ERROR, 0),
+        }
+    }
+
+    unsafe extern "system" fn control_handler(
+        control: u32,
+        _event_type: u32,
+        _event_data: *mut c_void,
+        _context: *mut c_void,
+    ) -> u32 {
+        let Some(host) = HOST.get() else {
+            return ERROR_CALL_NOT_IMPLEMENTED;
+        };
+        match control {
+            SERVICE_CONTROL_STOP | SERVICE_CONTROL_SHUTDOWN => {
+                host.stop();
+                NO_ERROR
+            }
+            SERVICE_CONTROL_INTERROGATE => NO_ERROR,
+            _ => ERROR_CALL_NOT_IMPLEMENTED,
+        }
+    }
+
+    impl Host {
+        /// run the child until it exits, returns its exit code
+        fn run_child(&self) -> Result<Option<i32>> {
+            let outputs = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.opt.log_file)?;
+            let errors = outputs.try_clone()?;
+            let child = Command::new(std::env::current_exe()?)
+                .args(&self.opt.args)
+                .envs(self.opt.envs.iter().map(|(key, value)| (key, value)))
+                .stdin(Stdio::null())
+                .stdout(Stdio::from(outputs))
+                .stderr(Stdio::from(errors))
+                .spawn()?;
+            info!(name = %self.opt.name, pid = child.id(), "started service process");
+            *self.child()? = Some(child);
+            self.report(SERVICE_RUNNING, NO_ERROR, 0);
+
+            loop {
+                if let Some(child) = self.child()?.as_mut() {
+                    if let Some(status) = child.try_wait()? {
+                        return Ok(status.code());
+                    }
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+
+        fn stop(&self) {
+            self.stopping.store(true, Ordering::SeqCst);
+            self.report(SERVICE_STOP_PENDING, NO_ERROR, 0);
+            match self.child() {
+                Ok(mut child) => {
+                    if let Some(child) = child.as_mut() {
+                        if let Err(err) = child.kill() {
+                            warn!(%err, "unable to stop service process");
+                        }
+                    }
+                }
+                Err(err) => warn!(%err, "unable to stop service process"),
+            }
+        }
+
+        fn child(&self) -> Result<std::sync::MutexGuard<'_, Option<Child>>> {
+            self.child
+                .lock()
+                .map_err(|_| anyhow!("service process lock poisoned"))
+        }
+
+        fn report(&self, state: u32, win32_exit_code: u32, service_specific_exit_code: u32) {
+            let pending = matches!(state, SERVICE_START_PENDING | SERVICE_STOP_PENDING);
+            let status = ServiceStatus {
+                service_type: SERVICE_WIN32_OWN_PROCESS,
+                current_state: state,
+                controls_accepted: if state == SERVICE_RUNNING {
+                    SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN
+                } else {
+                    0
+                },
+                win32_exit_code,
+                service_specific_exit_code,
+                check_point: if pending {
+                    self.check_point.fetch_add(1, Ordering::SeqCst) + 1
+                } else {
+                    0
+                },
+                wait_hint: if pending { WAIT_HINT_MS } else { 0 },
+            };
+            let handle = self.status_handle.load(Ordering::SeqCst);
+            // SAFETY: handle was returned by RegisterServiceCtrlHandlerExW, status is valid for the call
+            if unsafe { SetServiceStatus(handle, &status) } == 0 {
+                warn!(
+                    state,
+                    err = %std::io::Error::last_os_error(),
+                    "unable to report service status"
+                );
+            }
+        }
+    }
+
+    /// null terminated UTF-16 string
+    fn wide(s: &str) -> Vec<u16> {
+        OsStr::new(s).encode_wide().chain(Some(0)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_service_host() {
+        let opt = ServiceHostOpt::try_parse_from([
+            "service-host",
+            "--name",
+            "fluvio-sc",
+            "--log-file",
+            r"C:\fluvio\flv_sc.log",
+            "--env",
+            "RUST_LOG=info,fluvio=debug",
+            "--",
+            "sc",
+            "--local",
+            r"C:\fluvio\data",
+        ])
+        .expect("parse");
+        assert_eq!(opt.name, "fluvio-sc");
+        assert_eq!(
+            opt.envs,
+            vec![("RUST_LOG".to_owned(), "info,fluvio=debug".to_owned())]
+        );
+        assert_eq!(opt.args, vec!["sc", "--local", r"C:\fluvio\data"]);
+
+        assert!(parse_env("RUST_LOG").is_err());
+        assert!(
+            ServiceHostOpt::try_parse_from(["service-host", "--name", "a", "--log-file", "b"])
+                .is_err()
+        );
+    }
+}
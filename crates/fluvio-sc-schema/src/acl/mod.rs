@@ -0,0 +1,11 @@
+pub use fluvio_controlplane_metadata::acl::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec};
+
+impl AdminSpec for AclSpec {}
+
+impl CreatableAdminSpec for AclSpec {}
+
+impl DeletableAdminSpec for AclSpec {
+    type DeleteKey = String;
+}
@@ -0,0 +1,11 @@
+pub use fluvio_controlplane_metadata::alert::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec};
+
+impl AdminSpec for AlertRuleSpec {}
+
+impl CreatableAdminSpec for AlertRuleSpec {}
+
+impl DeletableAdminSpec for AlertRuleSpec {
+    type DeleteKey = String;
+}
@@ -0,0 +1,16 @@
+pub use fluvio_controlplane_metadata::consumer_group::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
+
+impl AdminSpec for ConsumerGroupSpec {}
+
+impl CreatableAdminSpec for ConsumerGroupSpec {}
+
+impl DeletableAdminSpec for ConsumerGroupSpec {
+    type DeleteKey = String;
+}
+
+impl UpdatableAdminSpec for ConsumerGroupSpec {
+    type UpdateKey = String;
+    type UpdateAction = UpdateConsumerGroupAction;
+}
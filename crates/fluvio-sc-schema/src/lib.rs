@@ -10,6 +10,10 @@ pub mod shared;
 pub mod tableformat;
 pub mod mirror;
 pub mod mirroring;
+pub mod alert;
+pub mod consumer_group;
+pub mod schema;
+pub mod acl;
 
 pub mod remote_file;
 
@@ -0,0 +1,75 @@
+//!
+//! # Batch of objects
+//!
+//! Topics and SmartModules applied together by the SC, either all of them are created
+//! or none is left behind.
+//!
+
+use anyhow::Result;
+
+use fluvio_protocol::{Encoder, Decoder, Version};
+use fluvio_protocol::api::Request;
+
+use crate::{AdminPublicApiKey, Status, TryEncodableFrom};
+use crate::smartmodule::SmartModuleSpec;
+use crate::topic::TopicSpec;
+
+use super::{COMMON_VERSION, CommonCreateRequest, CreateRequest};
+
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub struct BatchRequest {
+    /// created after the SmartModules, so they can use any of them
+    pub topics: Vec<CreateRequest<TopicSpec>>,
+    pub smartmodules: Vec<CreateRequest<SmartModuleSpec>>,
+}
+
+impl BatchRequest {
+    pub fn with_topic(mut self, name: impl Into<String>, spec: TopicSpec) -> Self {
+        self.topics.push(CreateRequest::new(common(name), spec));
+        self
+    }
+
+    pub fn with_smartmodule(mut self, name: impl Into<String>, spec: SmartModuleSpec) -> Self {
+        self.smartmodules
+            .push(CreateRequest::new(common(name), spec));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.topics.is_empty() && self.smartmodules.is_empty()
+    }
+}
+
+fn common(name: impl Into<String>) -> CommonCreateRequest {
+    CommonCreateRequest {
+        name: name.into(),
+        ..Default::default()
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ObjectApiBatchRequest(BatchRequest);
+
+impl Request for ObjectApiBatchRequest {
+    const API_KEY: u16 = AdminPublicApiKey::Batch as u16;
+    const MIN_API_VERSION: i16 = COMMON_VERSION;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    /// status of the first object which failed, the batch has been rolled back then
+    type Response = Status;
+}
+
+impl TryEncodableFrom<BatchRequest> for ObjectApiBatchRequest {
+    fn try_encode_from(input: BatchRequest, _version: Version) -> Result<Self> {
+        Ok(Self(input))
+    }
+
+    fn downcast(&self) -> Result<Option<BatchRequest>> {
+        Ok(Some(self.0.clone()))
+    }
+}
+
+impl ObjectApiBatchRequest {
+    pub fn into_inner(self) -> BatchRequest {
+        self.0
+    }
+}
@@ -427,6 +427,10 @@ mod create {
     use crate::smartmodule::SmartModuleSpec;
     use crate::tableformat::TableFormatSpec;
     use crate::spg::SpuGroupSpec;
+    use crate::alert::AlertRuleSpec;
+    use crate::consumer_group::ConsumerGroupSpec;
+    use crate::schema::SchemaSpec;
+    use crate::acl::AclSpec;
 
     #[derive(Debug, Default, Encoder, Decoder)]
     pub struct ClassicObjectApiCreateRequest {
@@ -674,4 +678,10 @@ mod create {
             }
         }
     }
+
+    // not part of the classic protocol
+    impl ClassicCreatableAdminSpec for AlertRuleSpec {}
+    impl ClassicCreatableAdminSpec for ConsumerGroupSpec {}
+    impl ClassicCreatableAdminSpec for SchemaSpec {}
+    impl ClassicCreatableAdminSpec for AclSpec {}
 }
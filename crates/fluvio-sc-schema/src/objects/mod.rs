@@ -4,6 +4,7 @@ mod update;
 mod list;
 mod watch;
 mod metadata;
+mod batch;
 
 // backward compatibility with classic protocol. this should go away once we deprecate classic
 pub mod classic;
@@ -14,8 +15,9 @@ pub use delete::*;
 pub use list::*;
 pub use watch::*;
 pub use metadata::*;
+pub use batch::*;
 
-pub(crate) const COMMON_VERSION: i16 = 18; // from now, we use a single version for all objects
+pub(crate) const COMMON_VERSION: i16 = 27; // from now, we use a single version for all objects
 pub(crate) const DYN_OBJ: i16 = 11; // version indicate dynamic object
 
 #[cfg(test)]
@@ -19,8 +19,8 @@ use fluvio_protocol::link::versions::ApiVersionsRequest;
 use crate::mirroring::ObjectMirroringRequest;
 use crate::AdminPublicApiKey;
 use crate::objects::{
-    ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiUpdateRequest,
-    ObjectApiWatchRequest,
+    ObjectApiBatchRequest, ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest,
+    ObjectApiUpdateRequest, ObjectApiWatchRequest,
 };
 
 /// Non generic AdminRequest, This is typically used Decoding
@@ -33,6 +33,7 @@ pub enum AdminPublicDecodedRequest {
     WatchRequest(RequestMessage<ObjectApiWatchRequest>),
     MirroringRequest(RequestMessage<ObjectMirroringRequest>),
     UpdateRequest(RequestMessage<ObjectApiUpdateRequest>),
+    BatchRequest(Box<RequestMessage<ObjectApiBatchRequest>>),
 }
 
 impl Default for AdminPublicDecodedRequest {
@@ -93,6 +94,10 @@ impl ApiMessage for AdminPublicDecodedRequest {
                 header,
                 ObjectApiUpdateRequest::decode_from(src, version)?,
             ))),
+            AdminPublicApiKey::Batch => Ok(Self::BatchRequest(Box::new(RequestMessage::new(
+                header,
+                ObjectApiBatchRequest::decode_from(src, version)?,
+            )))),
         }
     }
 }
@@ -135,4 +140,37 @@ mod test {
 
         assert!(matches!(dec_req, AdminPublicDecodedRequest::ListRequest(_)));
     }
+
+    #[test]
+    fn test_batch_encode_decoding() {
+        use fluvio_protocol::api::Request;
+
+        use crate::objects::{BatchRequest, ObjectApiBatchRequest};
+
+        let batch =
+            BatchRequest::default().with_topic("orders", TopicSpec::new_computed(2, 1, None));
+        let batch_req =
+            ObjectApiBatchRequest::try_encode_from(batch, COMMON_VERSION).expect("encode");
+
+        let mut req_msg = RequestMessage::new_request(batch_req);
+        req_msg
+            .get_mut_header()
+            .set_client_id("test")
+            .set_api_version(COMMON_VERSION);
+
+        let mut src = vec![];
+        req_msg.encode(&mut src, 0).expect("encoding");
+
+        let dec_req: AdminPublicDecodedRequest =
+            AdminPublicDecodedRequest::decode_from(&mut Cursor::new(&src)).expect("decode");
+
+        let AdminPublicDecodedRequest::BatchRequest(dec_req) = dec_req else {
+            panic!("expected batch request");
+        };
+        assert_eq!(dec_req.header.api_key(), ObjectApiBatchRequest::API_KEY);
+        let batch = dec_req.request.into_inner();
+        assert!(batch.smartmodules.is_empty());
+        assert_eq!(batch.topics[0].common.name, "orders");
+        assert_eq!(batch.topics[0].request.replicas().partitions(), 2);
+    }
 }
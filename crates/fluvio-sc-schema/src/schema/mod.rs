@@ -0,0 +1,16 @@
+pub use fluvio_controlplane_metadata::schema::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
+
+impl AdminSpec for SchemaSpec {}
+
+impl CreatableAdminSpec for SchemaSpec {}
+
+impl DeletableAdminSpec for SchemaSpec {
+    type DeleteKey = String;
+}
+
+impl UpdatableAdminSpec for SchemaSpec {
+    type UpdateKey = String;
+    type UpdateAction = UpdateSchemaAction;
+}
@@ -4,10 +4,11 @@
 //! Command line interface to provision SC id and bind-to server/port.
 //! Parameters are overwritten in the following sequence:
 //!     1) default values
-//!     2) custom configuration if provided, or default configuration (if not)
-//!     3) cli parameters
+//!     2) config file, if provided with `--config`
+//!     3) environment variables and cli parameters
 //!
 
+use std::net::IpAddr;
 use std::path::Path;
 use std::process;
 use std::path::PathBuf;
@@ -23,9 +24,11 @@ use fluvio_types::print_cli_err;
 use fluvio_types::defaults::TLS_SERVER_SECRET_NAME;
 use fluvio_future::openssl::TlsAcceptor;
 use fluvio_future::openssl::SslVerifyMode;
+use fluvio_socket::{ReloadableTlsAcceptor, TcpConfig};
+use fluvio_service::metrics::MetricsCardinality;
 
 use crate::services::auth::basic::BasicRbacPolicy;
-use crate::config::ScConfig;
+use crate::config::{AclConfig, ScConfig, ScConfigFile};
 
 type Config = (ScConfig, Option<BasicRbacPolicy>);
 
@@ -36,6 +39,14 @@ pub struct ScOpt {
     #[command(flatten)]
     run_mode: ScOptRunMode,
 
+    /// TOML file with default values of the options below, named in snake case
+    #[arg(long, value_name = "path", env = "FLV_SC_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Check the configuration and exit without starting the SC
+    #[arg(long)]
+    pub validate_config: bool,
+
     #[arg(long)]
     /// Address for external service
     bind_public: Option<String>,
@@ -44,6 +55,33 @@ pub struct ScOpt {
     /// Address for internal service
     bind_private: Option<String>,
 
+    /// Address for the admin HTTP API, disabled by default
+    #[arg(long, value_name = "address")]
+    bind_admin_http: Option<String>,
+
+    /// Address for the Prometheus metrics endpoint, disabled by default
+    #[arg(long, value_name = "address", env = "FLV_SC_METRICS_ADDR")]
+    bind_metrics: Option<String>,
+
+    /// Topic keeping metrics per partition, others are aggregated per topic.
+    /// A trailing `*` matches by prefix, can be repeated. Every topic by default
+    #[arg(long, value_name = "pattern")]
+    metrics_partition_topic: Vec<String>,
+
+    /// Above this number of topics, the metrics of the topics not kept per partition
+    /// are aggregated into a single series
+    #[arg(long, value_name = "count")]
+    metrics_max_topics: Option<usize>,
+
+    /// Bearer token required by the admin HTTP API
+    #[arg(
+        long,
+        value_name = "token",
+        env = "FLV_SC_ADMIN_HTTP_TOKEN",
+        hide_env_values = true
+    )]
+    admin_http_token: Option<String>,
+
     // k8 namespace
     #[arg(short = 'n', long = "namespace", value_name = "namespace")]
     namespace: Option<String>,
@@ -58,6 +96,20 @@ pub struct ScOpt {
     )]
     x509_auth_scopes: Option<PathBuf>,
 
+    /// Accept plaintext from a service mesh sidecar, client identities are read from
+    /// PROXY protocol v2 headers
+    #[arg(long, conflicts_with = "tls")]
+    mesh_identity: bool,
+
+    /// Address of the public service behind the mesh identity proxy
+    #[arg(long, value_name = "address", requires = "mesh_identity")]
+    bind_mesh_internal_public: Option<String>,
+
+    /// Peer trusted to send PROXY protocol headers besides loopback, can be repeated.
+    /// Connections of other peers to the mesh identity proxy are closed
+    #[arg(long, value_name = "ip", requires = "mesh_identity")]
+    mesh_trusted_peer: Vec<IpAddr>,
+
     #[arg(
         long = "authorization-policy",
         value_name = "authorization policy path",
@@ -65,9 +117,38 @@ pub struct ScOpt {
     )]
     auth_policy: Option<PathBuf>,
 
+    /// Authorize clients with Acl objects instead of the authorization policy,
+    /// requires client identities from tls with authorization scopes or the mesh
+    #[arg(long, conflicts_with = "auth_policy")]
+    acl: bool,
+
+    /// Principal allowed everything regardless of Acls, can be repeated
+    #[arg(long, value_name = "principal", requires = "acl")]
+    acl_super_user: Vec<String>,
+
     /// only allow white list of controllers
     #[arg(long)]
     white_list: Vec<String>,
+
+    /// Oldest client version supported, older clients are warned or refused
+    #[arg(long, value_name = "version", env = "FLV_SC_MIN_CLIENT_VERSION")]
+    min_client_version: Option<semver::Version>,
+
+    /// Newest client version supported, newer clients are warned or refused
+    #[arg(long, value_name = "version", env = "FLV_SC_MAX_CLIENT_VERSION")]
+    max_client_version: Option<semver::Version>,
+
+    #[clap(flatten)]
+    tcp: TcpConfig,
+
+    /// Address of the TLS gateway routing clients to SPUs by server name, requires TLS
+    #[arg(long, value_name = "address", env = "FLV_SC_SPU_GATEWAY")]
+    bind_spu_gateway: Option<String>,
+
+    /// Fluvio client profile file the SC connects to its own public service with,
+    /// e.g. to produce alerts. Its TLS and credentials are needed when clients are authenticated
+    #[arg(long, value_name = "path", env = "FLV_SC_CLIENT_CONFIG")]
+    client_config: Option<PathBuf>,
 }
 
 #[derive(Debug, Args)]
@@ -107,10 +188,127 @@ impl ScOpt {
         }
     }
 
+    /// fill the options not given on the command line or by env from the config file
+    fn merge_config_file(&mut self, file: ScConfigFile) {
+        self.bind_public = self.bind_public.take().or(file.bind_public);
+        self.bind_private = self.bind_private.take().or(file.bind_private);
+        self.bind_admin_http = self.bind_admin_http.take().or(file.bind_admin_http);
+        self.bind_metrics = self.bind_metrics.take().or(file.bind_metrics);
+        if self.metrics_partition_topic.is_empty() {
+            self.metrics_partition_topic = file.metrics_partition_topic;
+        }
+        self.metrics_max_topics = self.metrics_max_topics.take().or(file.metrics_max_topics);
+        self.namespace = self.namespace.take().or(file.namespace);
+        self.x509_auth_scopes = self.x509_auth_scopes.take().or(file.authorization_scopes);
+        self.auth_policy = self.auth_policy.take().or(file.authorization_policy);
+        self.acl |= file.acl.unwrap_or_default();
+        if self.acl_super_user.is_empty() {
+            self.acl_super_user = file.acl_super_user;
+        }
+        self.mesh_identity |= file.mesh_identity.unwrap_or_default();
+        self.bind_mesh_internal_public = self
+            .bind_mesh_internal_public
+            .take()
+            .or(file.bind_mesh_internal_public);
+        if self.mesh_trusted_peer.is_empty() {
+            self.mesh_trusted_peer = file.mesh_trusted_peer;
+        }
+        if self.white_list.is_empty() {
+            self.white_list = file.white_list;
+        }
+        self.min_client_version = self.min_client_version.take().or(file.min_client_version);
+        self.max_client_version = self.max_client_version.take().or(file.max_client_version);
+        self.bind_spu_gateway = self.bind_spu_gateway.take().or(file.bind_spu_gateway);
+        self.client_config = self.client_config.take().or(file.client_config);
+    }
+
+    /// Apply the config file and the `FLV_SC__` variables, if any
+    pub fn load_config_file_or_exit(mut self) -> Self {
+        match ScConfigFile::load(self.config.as_deref()) {
+            Ok(Some(file)) => {
+                info!(path = ?self.config, "using config file and environment overrides");
+                self.merge_config_file(file);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                print_cli_err!(err);
+                process::exit(-1);
+            }
+        }
+        self
+    }
+
+    /// Conflicting options, all of them are reported. Options of the config file are not
+    /// checked by clap.
+    fn validate(&self) -> Result<()> {
+        let mut invalid = vec![];
+        if self.bind_admin_http.is_some()
+            && self
+                .admin_http_token
+                .as_deref()
+                .unwrap_or_default()
+                .is_empty()
+        {
+            invalid.push("admin HTTP API requires a token, set FLV_SC_ADMIN_HTTP_TOKEN".to_owned());
+        }
+        if self.bind_spu_gateway.is_some() && !self.tls.tls {
+            invalid.push("spu gateway requires tls".to_owned());
+        }
+        if self.mesh_identity && self.tls.tls {
+            invalid.push("mesh-identity can not be used with tls".to_owned());
+        }
+        if self.mesh_identity && self.bind_mesh_internal_public.is_none() {
+            invalid.push("mesh-identity requires bind-mesh-internal-public".to_owned());
+        }
+        if !self.mesh_identity && self.bind_mesh_internal_public.is_some() {
+            invalid.push("bind-mesh-internal-public requires mesh-identity".to_owned());
+        }
+        if !self.mesh_identity && !self.mesh_trusted_peer.is_empty() {
+            invalid.push("mesh-trusted-peer requires mesh-identity".to_owned());
+        }
+        if self.acl && self.auth_policy.is_some() {
+            invalid.push("acl can not be used with authorization-policy".to_owned());
+        }
+        if self.acl && !self.mesh_identity && !(self.tls.tls && self.x509_auth_scopes.is_some()) {
+            invalid.push(
+                "acl requires tls with authorization-scopes or mesh-identity to identify clients"
+                    .to_owned(),
+            );
+        }
+        if !self.acl && !self.acl_super_user.is_empty() {
+            invalid.push("acl-super-user requires acl".to_owned());
+        }
+        if self.tls.tls && self.tls.bind_non_tls_public.is_none() {
+            invalid.push("tls requires bind-non-tls-public".to_owned());
+        }
+        if let (Some(min), Some(max)) = (&self.min_client_version, &self.max_client_version) {
+            if min > max {
+                invalid.push(format!(
+                    "min-client-version {min} is above max-client-version {max}"
+                ));
+            }
+        }
+        if self.bind_public.is_some() && self.bind_public == self.bind_private {
+            invalid.push(format!(
+                "bind-public and bind-private are both {}",
+                self.bind_public.as_deref().unwrap_or_default()
+            ));
+        }
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "invalid configuration:\n  {}",
+                invalid.join("\n  ")
+            ))
+        }
+    }
+
     /// as sc configuration, 2nd part of tls configuration(proxy addr, tls config)
     /// 3rd part is path to read only metadata config
     #[allow(clippy::wrong_self_convention)]
     fn as_sc_config(self) -> Result<(Config, Option<(String, TlsConfig)>)> {
+        self.validate()?;
         let mut config = ScConfig::default();
 
         // apply our option
@@ -122,13 +320,38 @@ impl ScOpt {
             config.private_endpoint = private_addr;
         }
 
+        if let Some(admin_http_addr) = self.bind_admin_http {
+            config.admin_http_endpoint = Some(admin_http_addr);
+            config.admin_http_token = self.admin_http_token;
+        }
+
+        config.metrics_endpoint = self.bind_metrics;
+        config.client_config = self.client_config;
+        config.metrics_cardinality = MetricsCardinality {
+            partition_topics: self.metrics_partition_topic,
+            max_topics: self.metrics_max_topics,
+        };
+
         if let Some(namespace) = self.namespace {
             config.namespace = namespace
         }
 
         config.x509_auth_scopes = self.x509_auth_scopes;
+        if self.acl {
+            config.acl = Some(AclConfig {
+                super_users: self.acl_super_user.into_iter().collect(),
+            });
+        }
         config.white_list = self.white_list.into_iter().collect();
         config.read_only_metadata = self.run_mode.read_only.is_some();
+        if let Some(min) = self.min_client_version {
+            config.supported_client.min = Some(min);
+        }
+        if let Some(max) = self.max_client_version {
+            config.supported_client.max = Some(max);
+        }
+        self.tcp.validate()?;
+        config.tcp = self.tcp;
 
         // Set Configuration Authorization Policy
 
@@ -143,6 +366,18 @@ impl ScOpt {
 
         // if tls is on, we need to assign public service(internal) to another port
         // because public is used by proxy which forward traffic to internal public port
+        config.spu_gateway_endpoint = self.bind_spu_gateway;
+
+        // like tls, the mesh proxy takes over the public address
+        if self.mesh_identity {
+            let proxy_addr = config.public_endpoint.clone();
+            config.public_endpoint = self.bind_mesh_internal_public.ok_or_else(|| {
+                anyhow!("internal addr for public must be specified with mesh identity")
+            })?;
+            config.mesh_proxy_endpoint = Some(proxy_addr);
+            config.mesh_trusted_peers = self.mesh_trusted_peer;
+        }
+
         if tls.tls {
             let proxy_addr = config.public_endpoint.clone();
             debug!(proxy_addr, "tls proxy addr");
@@ -155,6 +390,9 @@ impl ScOpt {
                 .secret_name
                 .get_or_insert(TLS_SERVER_SECRET_NAME.to_string());
             info!("{:?}", tls);
+            if config.admin_http_endpoint.is_some() {
+                config.admin_http_tls = Some(tls.clone());
+            }
 
             Ok(((config, policy), Some((proxy_addr, tls))))
         } else {
@@ -171,9 +409,21 @@ impl ScOpt {
             Ok(config) => config,
         }
     }
+
+    /// Dry run of `--validate-config`, also loads the authorization policy and certificates
+    pub fn check_config_or_exit(self) {
+        let (_, tls_option) = self.parse_cli_or_exit();
+        if let Some((_, tls)) = tls_option {
+            if let Err(err) = tls.try_build_tls_acceptor() {
+                print_cli_err!(err);
+                process::exit(-1);
+            }
+        }
+        println!("configuration is valid");
+    }
 }
 
-#[derive(Debug, Parser, Clone, Default)]
+#[derive(Debug, Parser, Clone, Default, Eq, PartialEq)]
 pub struct TlsConfig {
     /// enable tls
     #[arg(long)]
@@ -234,4 +484,51 @@ impl TlsConfig {
 
         Ok(builder.build())
     }
+
+    /// acceptor rebuilt when the certificate, key or CA files are renewed
+    pub fn try_build_reloadable_acceptor(&self) -> Result<ReloadableTlsAcceptor> {
+        let files = [&self.server_cert, &self.server_key, &self.ca_cert]
+            .into_iter()
+            .flatten()
+            .map(PathBuf::from)
+            .collect();
+        let config = self.clone();
+        let acceptor = ReloadableTlsAcceptor::new(files, move || config.try_build_tls_acceptor())
+            .map_err(|err| anyhow!("{err}"))?;
+        Ok(acceptor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file() {
+        let file: ScConfigFile = toml::from_str(
+            r#"
+            bind_public = "0.0.0.0:9103"
+            white_list = ["spu"]
+            mesh_identity = true
+            min_client_version = "0.12.0"
+            max_client_version = "0.11.0"
+            "#,
+        )
+        .expect("config file");
+        let mut opt = ScOpt::parse_from(["sc", "--k8", "--bind-public", "0.0.0.0:9003"]);
+        opt.merge_config_file(file);
+        assert_eq!(opt.bind_public.as_deref(), Some("0.0.0.0:9003"));
+        assert_eq!(opt.white_list, vec!["spu".to_owned()]);
+
+        let err = opt.validate().expect_err("invalid").to_string();
+        assert!(
+            err.contains("mesh-identity requires bind-mesh-internal-public"),
+            "{err}"
+        );
+        assert!(err.contains("min-client-version 0.12.0"), "{err}");
+
+        let err = toml::from_str::<ScConfigFile>("bind_admin_http_token = \"secret\"")
+            .expect_err("unknown key");
+        assert!(err.to_string().contains("unknown field"));
+    }
 }
@@ -0,0 +1,65 @@
+//!
+//! # SC configuration file
+//!
+//! TOML file with the options of the command line, named after their long flag in snake case:
+//!
+//! ```toml
+//! bind_public = "0.0.0.0:9003"
+//! authorization_policy = "/etc/fluvio/policy.json"
+//! white_list = ["spu", "topic", "partition"]
+//! min_client_version = "0.11.0"
+//! ```
+//!
+//! The keys can be overridden by variables with the `FLV_SC__` prefix, `FLV_SC__BIND_PUBLIC`
+//! sets `bind_public`. Lists are written as TOML arrays: `FLV_SC__WHITE_LIST='["spu"]'`.
+//!
+//! Options are taken from, in order of precedence: the command line or the environment
+//! variable of the flag, its `FLV_SC__` variable, the config file, then the default.
+//! The run mode, TLS and TCP options and the admin HTTP token are only read from the
+//! command line.
+//!
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use fluvio_types::config_file::load_with_env_overrides;
+
+/// prefix of the variables overriding the keys of the config file
+pub(crate) const ENV_PREFIX: &str = "FLV_SC__";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ScConfigFile {
+    pub bind_public: Option<String>,
+    pub bind_private: Option<String>,
+    pub bind_admin_http: Option<String>,
+    pub bind_metrics: Option<String>,
+    #[serde(default)]
+    pub metrics_partition_topic: Vec<String>,
+    pub metrics_max_topics: Option<usize>,
+    pub namespace: Option<String>,
+    pub authorization_scopes: Option<PathBuf>,
+    pub authorization_policy: Option<PathBuf>,
+    pub acl: Option<bool>,
+    #[serde(default)]
+    pub acl_super_user: Vec<String>,
+    pub mesh_identity: Option<bool>,
+    pub bind_mesh_internal_public: Option<String>,
+    #[serde(default)]
+    pub mesh_trusted_peer: Vec<IpAddr>,
+    #[serde(default)]
+    pub white_list: Vec<String>,
+    pub min_client_version: Option<semver::Version>,
+    pub max_client_version: Option<semver::Version>,
+    pub bind_spu_gateway: Option<String>,
+    pub client_config: Option<PathBuf>,
+}
+
+impl ScConfigFile {
+    /// the config file overridden by the `FLV_SC__` variables, `None` without either
+    pub(crate) fn load(path: Option<&Path>) -> Result<Option<Self>> {
+        Ok(load_with_env_overrides(path, ENV_PREFIX, std::env::vars())?)
+    }
+}
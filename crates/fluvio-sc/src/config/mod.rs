@@ -1,8 +1,11 @@
+mod file;
 mod sc_config;
 
 pub use self::sc_config::ScConfig;
+pub use self::sc_config::AclConfig;
 pub use self::sc_config::ScConfigBuilder;
 pub use self::sc_config::DEFAULT_NAMESPACE;
+pub(crate) use self::file::ScConfigFile;
 
 macro_rules! whitelist {
     ($config:expr,$name:expr,$start:expr) => {
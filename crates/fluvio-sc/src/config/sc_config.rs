@@ -4,12 +4,20 @@
 //! Stores configuration parameter used by Streaming Controller module.
 //!
 use std::collections::HashSet;
+use std::net::IpAddr;
 use std::{io::Error as IoError, path::PathBuf};
 
+use fluvio_protocol::link::versions::ClientVersionRange;
+use fluvio_service::metrics::MetricsCardinality;
+use fluvio_socket::TcpConfig;
 use fluvio_types::defaults::SC_PUBLIC_PORT;
 use fluvio_types::defaults::SC_PRIVATE_PORT;
 
+use crate::cli::TlsConfig;
+
 pub const DEFAULT_NAMESPACE: &str = "default";
+/// oldest client platform version accepted by default, matches the minimum cluster version of clients
+pub const DEFAULT_MIN_CLIENT_VERSION: semver::Version = semver::Version::new(0, 9, 0);
 
 // -----------------------------------
 // Traits
@@ -29,6 +37,38 @@ pub struct ScConfig {
     pub namespace: String,
     pub x509_auth_scopes: Option<PathBuf>,
     pub white_list: HashSet<String>,
+    /// client versions advertised as supported
+    pub supported_client: ClientVersionRange,
+    /// address of the admin HTTP API, disabled when not set
+    pub admin_http_endpoint: Option<String>,
+    /// bearer token required by the admin HTTP API
+    pub admin_http_token: Option<String>,
+    /// tokens of the admin HTTP API bound to identities of the authorization policy
+    pub admin_http_tokens: Option<PathBuf>,
+    /// serve the admin HTTP API with TLS
+    pub admin_http_tls: Option<TlsConfig>,
+    /// tcp options for connections from SPUs and clients
+    pub tcp: TcpConfig,
+    /// address of the TLS gateway routing client connections to SPUs, disabled when not set
+    pub spu_gateway_endpoint: Option<String>,
+    /// address of the proxy reading client identities from the service mesh
+    pub mesh_proxy_endpoint: Option<String>,
+    /// peers besides loopback trusted to send identities to the mesh proxy
+    pub mesh_trusted_peers: Vec<IpAddr>,
+    /// address of the Prometheus metrics endpoint, disabled when not set
+    pub metrics_endpoint: Option<String>,
+    /// limits of the series of per partition metrics
+    pub metrics_cardinality: MetricsCardinality,
+    /// authorize clients with Acl objects, the authorization policy is used when not set
+    pub acl: Option<AclConfig>,
+    /// client profile file the SC connects to its own public service with, e.g. to produce alerts
+    pub client_config: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct AclConfig {
+    /// principals allowed everything
+    pub super_users: HashSet<String>,
 }
 
 impl ::std::default::Default for ScConfig {
@@ -40,6 +80,19 @@ impl ::std::default::Default for ScConfig {
             namespace: DEFAULT_NAMESPACE.to_owned(),
             x509_auth_scopes: None,
             white_list: HashSet::new(),
+            supported_client: ClientVersionRange::new(Some(DEFAULT_MIN_CLIENT_VERSION), None),
+            admin_http_endpoint: None,
+            admin_http_token: None,
+            admin_http_tokens: None,
+            admin_http_tls: None,
+            tcp: TcpConfig::default(),
+            spu_gateway_endpoint: None,
+            mesh_proxy_endpoint: None,
+            mesh_trusted_peers: vec![],
+            metrics_endpoint: None,
+            metrics_cardinality: MetricsCardinality::default(),
+            acl: None,
+            client_config: None,
         }
     }
 }
@@ -0,0 +1,185 @@
+//!
+//! # Alert Controller
+//!
+//! Periodically evaluates AlertRules against the SPU and partition stores,
+//! records the outcome in the rule status and notifies the rule actions
+//! when a rule starts firing or resolves.
+//!
+
+use std::time::{Duration, SystemTime};
+
+use tracing::{debug, info, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_sc_schema::alert::{AlertRuleResolution, AlertRuleSpec, AlertRuleStatus};
+use fluvio_stream_dispatcher::actions::WSAction;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::SharedContext;
+use crate::stores::StoreContext;
+use crate::stores::partition::PartitionSpec;
+use crate::stores::spu::SpuSpec;
+
+use super::metrics::metric_value;
+use super::notify::{AlertEvent, AlertNotifier, AlertState};
+
+const ALERT_CONTROLLER_INTERVAL: u64 = 10;
+
+pub struct AlertController<C: MetadataItem> {
+    rules: StoreContext<AlertRuleSpec, C>,
+    partitions: StoreContext<PartitionSpec, C>,
+    spus: StoreContext<SpuSpec, C>,
+    notifier: AlertNotifier,
+}
+
+impl<C: MetadataItem> AlertController<C> {
+    pub fn start(ctx: SharedContext<C>) {
+        let controller = Self {
+            rules: ctx.alert_rules().clone(),
+            partitions: ctx.partitions().clone(),
+            spus: ctx.spus().clone(),
+            notifier: AlertNotifier::new(ctx.config()),
+        };
+
+        info!("starting alert controller");
+        spawn(controller.dispatch_loop());
+    }
+
+    #[instrument(skip(self), name = "AlertControllerLoop")]
+    async fn dispatch_loop(mut self) {
+        loop {
+            self.evaluate_rules().await;
+            sleep(Duration::from_secs(ALERT_CONTROLLER_INTERVAL)).await;
+        }
+    }
+
+    async fn evaluate_rules(&mut self) {
+        let rules = self.rules.store().clone_values().await;
+        if rules.is_empty() {
+            return;
+        }
+        let partitions = self.partitions.store().clone_values().await;
+        let spus = self.spus.store().clone_values().await;
+        let now = now_millis();
+
+        for rule in rules {
+            let name = rule.key_owned();
+            let (status, transition) = match rule.spec.parse_expression() {
+                Ok(expr) => {
+                    let value = metric_value(&expr, &partitions, &spus);
+                    next_status(
+                        &rule.status,
+                        expr.is_met(value),
+                        value,
+                        rule.spec.for_secs,
+                        now,
+                    )
+                }
+                Err(err) => (AlertRuleStatus::invalid(err.to_string()), None),
+            };
+
+            if let (Some(state), Some(value)) = (transition, status.value) {
+                info!(%name, ?state, value, "alert rule transition");
+                let event = AlertEvent {
+                    rule: &name,
+                    state,
+                    expression: &rule.spec.expression,
+                    value,
+                    description: rule.spec.description.as_deref(),
+                    timestamp: now,
+                };
+                self.notifier.notify(&rule.spec.actions, &event);
+            }
+
+            if status != rule.status {
+                debug!(%name, %status, "updating alert rule status");
+                self.rules
+                    .send_action(WSAction::UpdateStatus((name, status)))
+                    .await;
+            }
+        }
+    }
+}
+
+/// status of a rule after an evaluation, with the notification to send if any
+fn next_status(
+    current: &AlertRuleStatus,
+    met: bool,
+    value: i64,
+    for_secs: u32,
+    now: i64,
+) -> (AlertRuleStatus, Option<AlertState>) {
+    let since = |resolution| {
+        if current.resolution == resolution {
+            current.since
+        } else {
+            Some(now)
+        }
+    };
+    let (resolution, transition) = match (met, current.resolution) {
+        (true, AlertRuleResolution::Firing) => (AlertRuleResolution::Firing, None),
+        (true, AlertRuleResolution::Pending)
+            if now - current.since.unwrap_or(now) >= i64::from(for_secs) * 1000 =>
+        {
+            (AlertRuleResolution::Firing, Some(AlertState::Firing))
+        }
+        (true, _) if for_secs == 0 => (AlertRuleResolution::Firing, Some(AlertState::Firing)),
+        (true, _) => (AlertRuleResolution::Pending, None),
+        (false, AlertRuleResolution::Firing) => {
+            (AlertRuleResolution::Inactive, Some(AlertState::Resolved))
+        }
+        (false, _) => (AlertRuleResolution::Inactive, None),
+    };
+    let status = AlertRuleStatus {
+        resolution,
+        value: Some(value),
+        since: since(resolution),
+        reason: None,
+    };
+    (status, transition)
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_rule_transitions() {
+        let init = AlertRuleStatus::default();
+
+        let (pending, transition) = next_status(&init, true, 5, 30, 1_000);
+        assert_eq!(pending.resolution, AlertRuleResolution::Pending);
+        assert_eq!(pending.since, Some(1_000));
+        assert!(transition.is_none());
+
+        let (still_pending, transition) = next_status(&pending, true, 6, 30, 20_000);
+        assert_eq!(still_pending.resolution, AlertRuleResolution::Pending);
+        assert_eq!(still_pending.since, Some(1_000));
+        assert!(transition.is_none());
+
+        let (firing, transition) = next_status(&still_pending, true, 7, 30, 31_000);
+        assert_eq!(firing.resolution, AlertRuleResolution::Firing);
+        assert_eq!(firing.since, Some(31_000));
+        assert!(matches!(transition, Some(AlertState::Firing)));
+
+        let (_, transition) = next_status(&firing, true, 8, 30, 41_000);
+        assert!(transition.is_none());
+
+        let (resolved, transition) = next_status(&firing, false, 0, 30, 51_000);
+        assert_eq!(resolved.resolution, AlertRuleResolution::Inactive);
+        assert!(matches!(transition, Some(AlertState::Resolved)));
+
+        let (immediate, transition) = next_status(&init, true, 1, 0, 1_000);
+        assert_eq!(immediate.resolution, AlertRuleResolution::Firing);
+        assert!(matches!(transition, Some(AlertState::Firing)));
+    }
+}
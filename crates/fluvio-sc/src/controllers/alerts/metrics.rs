@@ -0,0 +1,125 @@
+//!
+//! # Alert Metrics
+//!
+//! Computes the value of an alert metric from the SPU and partition stores.
+//!
+
+use fluvio_controlplane::PartitionMetadata;
+use fluvio_sc_schema::alert::{AlertExpr, AlertMetric};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::stores::spu::SpuMetadata;
+
+pub(crate) fn metric_value<C: MetadataItem>(
+    expr: &AlertExpr,
+    partitions: &[PartitionMetadata<C>],
+    spus: &[SpuMetadata<C>],
+) -> i64 {
+    let selected = partitions.iter().filter(|partition| {
+        expr.topic
+            .as_ref()
+            .map_or(true, |topic| &partition.key().topic == topic)
+    });
+
+    match expr.metric {
+        AlertMetric::OfflineSpus => {
+            spus.iter().filter(|spu| spu.status.is_offline()).count() as i64
+        }
+        AlertMetric::OfflinePartitions => selected
+            .filter(|partition| partition.status.is_offline())
+            .count() as i64,
+        AlertMetric::UnderReplicatedPartitions => selected
+            .filter(|partition| is_under_replicated(partition))
+            .count() as i64,
+        AlertMetric::ReplicaLag => selected
+            .flat_map(|partition| {
+                let leader = &partition.status.leader;
+                partition
+                    .status
+                    .replica_iter()
+                    .map(move |replica| replica.leader_lag(leader))
+            })
+            .max()
+            .unwrap_or(0),
+        AlertMetric::PartitionSize => selected
+            .map(|partition| partition.status.size)
+            .max()
+            .unwrap_or(0)
+            .max(0),
+    }
+}
+
+/// fewer followers caught up with the high watermark than assigned by the spec
+fn is_under_replicated<C: MetadataItem>(partition: &PartitionMetadata<C>) -> bool {
+    let followers = partition.spec.replicas.len().saturating_sub(1);
+    let leader_hw = partition.status.leader.hw;
+    let in_sync = partition
+        .status
+        .replica_iter()
+        .filter(|replica| replica.leo >= leader_hw)
+        .count();
+    in_sync < followers
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_controlplane_metadata::partition::{
+        PartitionResolution, PartitionSpec, PartitionStatus, ReplicaKey, ReplicaStatus,
+    };
+    use fluvio_controlplane_metadata::spu::{SpuSpec, SpuStatus};
+    use fluvio_stream_model::store::memory::MemoryMeta;
+    use fluvio_stream_model::store::MetadataStoreObject;
+
+    use super::*;
+
+    fn partition(
+        topic: &str,
+        replicas: Vec<i32>,
+        leader: (i64, i64),
+        followers: Vec<(i64, i64)>,
+    ) -> PartitionMetadata<MemoryMeta> {
+        let mut status = PartitionStatus::new(
+            ReplicaStatus::new(replicas[0], leader.0, leader.1),
+            replicas
+                .iter()
+                .skip(1)
+                .zip(followers)
+                .map(|(spu, (hw, leo))| ReplicaStatus::new(*spu, hw, leo))
+                .collect(),
+        );
+        status.resolution = PartitionResolution::Online;
+        status.size = leader.1 * 10;
+        MetadataStoreObject::new(
+            ReplicaKey::new(topic, 0u32),
+            PartitionSpec::new(replicas[0], replicas),
+            status,
+        )
+    }
+
+    #[test]
+    fn test_metric_values() {
+        let mut offline = partition("logs", vec![5001], (0, 0), vec![]);
+        offline.status.resolution = PartitionResolution::Offline;
+        let partitions = vec![
+            partition("orders", vec![5001, 5002], (100, 120), vec![(100, 100)]),
+            partition("orders", vec![5002, 5001], (50, 50), vec![(10, 20)]),
+            offline,
+        ];
+        let spus: Vec<SpuMetadata<MemoryMeta>> = vec![MetadataStoreObject::new(
+            "5003".to_owned(),
+            SpuSpec::default(),
+            SpuStatus::offline(),
+        )];
+
+        let value = |expr: &str| metric_value(&expr.parse().expect("expr"), &partitions, &spus);
+
+        assert_eq!(value("offline_partitions > 0"), 1);
+        assert_eq!(value(r#"offline_partitions{topic="orders"} > 0"#), 0);
+        assert_eq!(value("offline_spus > 0"), 1);
+        assert_eq!(value("under_replicated_partitions > 0"), 1);
+        assert_eq!(value("replica_lag > 0"), 30);
+        assert_eq!(value(r#"replica_lag{topic="logs"} > 0"#), 0);
+        assert_eq!(value("partition_size > 0"), 1200);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod controller;
+mod metrics;
+mod notify;
@@ -0,0 +1,194 @@
+//!
+//! # Alert Notifications
+//!
+//! Delivers alert events to webhooks and alert topics.
+//!
+//! Alert topics are produced with the client profile of `--client-config` when set, so
+//! the SC connects with the TLS and credentials clients must use. Without it, the SC
+//! connects to its own public service in plaintext.
+//!
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_lock::Mutex;
+use serde::Serialize;
+use tracing::{debug, error, warn};
+
+use fluvio::config::ConfigFile;
+use fluvio::{Fluvio, FluvioConfig, RecordKey};
+use fluvio_future::task::{spawn, spawn_blocking};
+use fluvio_sc_schema::alert::AlertAction;
+
+use crate::config::ScConfig;
+
+const WEBHOOK_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const WEBHOOK_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// payload posted to webhooks and produced to alert topics
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AlertEvent<'a> {
+    pub rule: &'a str,
+    pub state: AlertState,
+    pub expression: &'a str,
+    pub value: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    /// unix time in milliseconds
+    pub timestamp: i64,
+}
+
+/// Delivers events in the background, so slow webhooks or topics don't delay
+/// the evaluation of the rules
+#[derive(Clone)]
+pub(crate) struct AlertNotifier {
+    inner: Arc<Mutex<Delivery>>,
+}
+
+impl AlertNotifier {
+    pub fn new(config: &ScConfig) -> Self {
+        if config.client_config.is_none()
+            && (config.x509_auth_scopes.is_some() || config.mesh_proxy_endpoint.is_some())
+        {
+            warn!("alert topics need --client-config when clients are authenticated");
+        }
+        Self {
+            inner: Arc::new(Mutex::new(Delivery {
+                sc_config: config.clone(),
+                client: None,
+                agent: ureq::AgentBuilder::new()
+                    .timeout_connect(WEBHOOK_CONNECT_TIMEOUT)
+                    .timeout_read(WEBHOOK_READ_TIMEOUT)
+                    .build(),
+            })),
+        }
+    }
+
+    /// run every action in a background task, events are delivered in order
+    pub fn notify(&self, actions: &[AlertAction], event: &AlertEvent<'_>) {
+        let body = match serde_json::to_string(event) {
+            Ok(body) => body,
+            Err(err) => {
+                error!(%err, "unable to serialize alert event");
+                return;
+            }
+        };
+        let rule = event.rule.to_owned();
+        let actions = actions.to_vec();
+        let inner = self.inner.clone();
+        spawn(async move {
+            inner.lock().await.deliver(&rule, &actions, body).await;
+        });
+    }
+}
+
+struct Delivery {
+    sc_config: ScConfig,
+    client: Option<Fluvio>,
+    agent: ureq::Agent,
+}
+
+impl Delivery {
+    /// failures are logged so one broken action doesn't block the others
+    async fn deliver(&mut self, rule: &str, actions: &[AlertAction], body: String) {
+        for action in actions {
+            let result = match action {
+                AlertAction::Webhook { url } => {
+                    post_webhook(self.agent.clone(), url.clone(), body.clone()).await
+                }
+                AlertAction::Topic { topic } => self.produce(topic, &body).await,
+            };
+            match result {
+                Ok(()) => debug!(rule, %action, "alert delivered"),
+                Err(err) => error!(rule, %action, "alert delivery failed: {err:#}"),
+            }
+        }
+    }
+
+    async fn produce(&mut self, topic: &str, body: &str) -> Result<()> {
+        let client = match self.client.take() {
+            Some(client) => client,
+            None => Fluvio::connect_with_config(&client_config(&self.sc_config)?).await?,
+        };
+        let producer = client.topic_producer(topic).await?;
+        producer.send(RecordKey::NULL, body).await?;
+        producer.flush().await?;
+        self.client = Some(client);
+        Ok(())
+    }
+}
+
+/// cluster of the client config file, or the public endpoint of this SC
+fn client_config(sc_config: &ScConfig) -> Result<FluvioConfig> {
+    match &sc_config.client_config {
+        Some(path) => {
+            let file = ConfigFile::load(Some(path.display().to_string()))?;
+            Ok(file.config().current_cluster()?.clone())
+        }
+        None => {
+            let endpoint = sc_config
+                .public_endpoint
+                .replacen("0.0.0.0", "127.0.0.1", 1);
+            let mut config = FluvioConfig::new(endpoint);
+            config.use_spu_local_address = true;
+            Ok(config)
+        }
+    }
+}
+
+async fn post_webhook(agent: ureq::Agent, url: String, body: String) -> Result<()> {
+    spawn_blocking(move || {
+        agent
+            .post(&url)
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+            .map(|_| ())
+            .map_err(|err| anyhow!("webhook {url} failed: {err}"))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_alert_event_json() {
+        let event = AlertEvent {
+            rule: "lag",
+            state: AlertState::Firing,
+            expression: "replica_lag > 10",
+            value: 42,
+            description: None,
+            timestamp: 1000,
+        };
+        assert_eq!(
+            serde_json::to_value(&event).expect("json"),
+            serde_json::json!({
+                "rule": "lag",
+                "state": "firing",
+                "expression": "replica_lag > 10",
+                "value": 42,
+                "timestamp": 1000,
+            })
+        );
+        let sc_config = ScConfig {
+            public_endpoint: "0.0.0.0:9003".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            client_config(&sc_config).expect("config").endpoint,
+            "127.0.0.1:9003"
+        );
+    }
+}
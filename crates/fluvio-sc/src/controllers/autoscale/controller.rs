@@ -0,0 +1,101 @@
+//!
+//! # Autoscale Controller
+//!
+//! Periodically computes the load of SPU groups with autoscale and updates their
+//! replicas, the SPU group controller then adds or removes the SPUs. Partitions of
+//! new topics are placed on new SPUs by the scheduler; existing replicas are not
+//! moved, so SPUs hosting replicas are never removed.
+//!
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_stream_dispatcher::actions::WSAction;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::SharedContext;
+use crate::stores::StoreContext;
+use crate::stores::partition::PartitionSpec;
+use crate::stores::spg::SpuGroupSpec;
+
+use super::load::{desired_replicas, group_load};
+
+const AUTOSCALE_CONTROLLER_INTERVAL: u64 = 30;
+
+pub struct AutoscaleController<C: MetadataItem> {
+    spgs: StoreContext<SpuGroupSpec, C>,
+    partitions: StoreContext<PartitionSpec, C>,
+    /// leader bytes of each group at the last evaluation
+    samples: HashMap<String, (Instant, u64)>,
+}
+
+impl<C: MetadataItem> AutoscaleController<C> {
+    pub fn start(ctx: SharedContext<C>) {
+        let controller = Self {
+            spgs: ctx.spgs().clone(),
+            partitions: ctx.partitions().clone(),
+            samples: HashMap::new(),
+        };
+
+        info!("starting autoscale controller");
+        spawn(controller.dispatch_loop());
+    }
+
+    #[instrument(skip(self), name = "AutoscaleControllerLoop")]
+    async fn dispatch_loop(mut self) {
+        loop {
+            self.scale_groups().await;
+            sleep(Duration::from_secs(AUTOSCALE_CONTROLLER_INTERVAL)).await;
+        }
+    }
+
+    async fn scale_groups(&mut self) {
+        let groups: Vec<_> = self
+            .spgs
+            .store()
+            .clone_values()
+            .await
+            .into_iter()
+            .filter(|group| group.spec.autoscale.is_some())
+            .collect();
+        self.samples
+            .retain(|name, _| groups.iter().any(|group| group.key() == name));
+        if groups.is_empty() {
+            return;
+        }
+        let partitions = self.partitions.store().clone_values().await;
+        let now = Instant::now();
+
+        for group in groups {
+            let name = group.key_owned();
+            let Some(autoscale) = &group.spec.autoscale else {
+                continue;
+            };
+            let load = group_load(&group.spec, &partitions);
+            let throughput = self
+                .samples
+                .insert(name.clone(), (now, load.leader_bytes))
+                .map(|(at, bytes)| {
+                    // retention shrinks partitions, which is not negative throughput
+                    load.leader_bytes.saturating_sub(bytes) as f64
+                        / now.duration_since(at).as_secs_f64().max(1.0)
+                });
+
+            let current = group.spec.replicas;
+            let desired = desired_replicas(autoscale, current, throughput, &load);
+            debug!(%name, ?load, ?throughput, current, desired, "spu group load");
+            if desired != current {
+                info!(%name, current, desired, "scaling spu group");
+                let mut spec = group.spec.clone();
+                spec.replicas = desired;
+                self.spgs
+                    .send_action(WSAction::UpdateSpec((name, spec)))
+                    .await;
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//!
+//! # SPU Group Load
+//!
+//! Computes the load of the SPUs of a group from the partition store and the number
+//! of SPUs the group should have for its autoscale targets.
+//!
+
+use fluvio_controlplane::PartitionMetadata;
+use fluvio_controlplane_metadata::spg::{SpuGroupAutoscale, SpuGroupSpec};
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::SpuId;
+
+/// load within this ratio of the targets doesn't change the replicas
+const TOLERANCE: f64 = 0.1;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct GroupLoad {
+    /// bytes stored by the leaders on the SPUs of the group
+    pub leader_bytes: u64,
+    /// average percentage of the log volume in use per SPU
+    pub disk_usage: Option<f64>,
+    /// SPUs up to the last one hosting a replica, the group can't shrink below
+    pub replicas_in_use: u16,
+}
+
+pub(crate) fn group_load<C: MetadataItem>(
+    spec: &SpuGroupSpec,
+    partitions: &[PartitionMetadata<C>],
+) -> GroupLoad {
+    let replicas = usize::from(spec.replicas);
+    let index_of = |spu: SpuId| {
+        usize::try_from(spu - spec.min_id)
+            .ok()
+            .filter(|index| *index < replicas)
+    };
+
+    let mut load = GroupLoad::default();
+    let mut stored = vec![0u64; replicas];
+    for partition in partitions {
+        let size = partition.status.size.max(0) as u64;
+        if index_of(partition.spec.leader).is_some() {
+            load.leader_bytes += size;
+        }
+        for index in partition
+            .spec
+            .replicas
+            .iter()
+            .filter_map(|spu| index_of(*spu))
+        {
+            stored[index] += size;
+            load.replicas_in_use = load.replicas_in_use.max(index as u16 + 1);
+        }
+    }
+
+    if let Some(capacity) = spec
+        .spu_config
+        .real_storage_config()
+        .size_in_bytes()
+        .filter(|capacity| *capacity > 0 && replicas > 0)
+    {
+        let used = stored.iter().sum::<u64>() as f64 / replicas as f64;
+        load.disk_usage = Some(used * 100.0 / capacity as f64);
+    }
+    load
+}
+
+/// replicas for the load, `throughput` is the write rate in bytes per second to the leaders
+pub(crate) fn desired_replicas(
+    autoscale: &SpuGroupAutoscale,
+    current: u16,
+    throughput: Option<f64>,
+    load: &GroupLoad,
+) -> u16 {
+    let per_spu = f64::from(current.max(1));
+    let ratios = [
+        autoscale
+            .target_throughput
+            .zip(throughput)
+            .map(|(target, throughput)| throughput / per_spu / target as f64),
+        autoscale
+            .target_disk_usage
+            .zip(load.disk_usage)
+            .map(|(target, usage)| usage / f64::from(target)),
+    ];
+    let desired = match ratios.into_iter().flatten().reduce(f64::max) {
+        Some(ratio) if (ratio - 1.0).abs() > TOLERANCE => {
+            (f64::from(current) * ratio).ceil().min(f64::from(u16::MAX)) as u16
+        }
+        _ => current,
+    };
+    desired
+        .clamp(autoscale.min_replicas, autoscale.max_replicas)
+        .max(load.replicas_in_use.min(current))
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_controlplane_metadata::partition::{
+        PartitionSpec, PartitionStatus, ReplicaKey, ReplicaStatus,
+    };
+    use fluvio_controlplane_metadata::spg::{SpuConfig, StorageConfig};
+    use fluvio_stream_model::store::memory::MemoryMeta;
+    use fluvio_stream_model::store::MetadataStoreObject;
+
+    use super::*;
+
+    fn partition(partition: u32, replicas: Vec<i32>, size: i64) -> PartitionMetadata<MemoryMeta> {
+        let mut status = PartitionStatus::new(ReplicaStatus::new(replicas[0], 0, 0), vec![]);
+        status.size = size;
+        MetadataStoreObject::new(
+            ReplicaKey::new("orders", partition),
+            PartitionSpec::new(replicas[0], replicas),
+            status,
+        )
+    }
+
+    fn group(replicas: u16) -> SpuGroupSpec {
+        SpuGroupSpec {
+            replicas,
+            min_id: 5000,
+            spu_config: SpuConfig {
+                storage: Some(StorageConfig {
+                    size: Some("1000".to_owned()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            autoscale: None,
+        }
+    }
+
+    #[test]
+    fn test_group_load() {
+        let partitions = vec![
+            partition(0, vec![5000, 5001], 300),
+            partition(1, vec![5001, 5000], 100),
+            partition(2, vec![7000], 5000),
+        ];
+        let load = group_load(&group(3), &partitions);
+        assert_eq!(load.leader_bytes, 400);
+        assert_eq!(load.replicas_in_use, 2);
+        let disk_usage = load.disk_usage.expect("capacity is known");
+        assert!((disk_usage - 80.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_desired_replicas() {
+        let autoscale = SpuGroupAutoscale {
+            min_replicas: 2,
+            max_replicas: 6,
+            target_throughput: Some(1000),
+            target_disk_usage: Some(80),
+        };
+        let load = |disk_usage, replicas_in_use| GroupLoad {
+            leader_bytes: 0,
+            disk_usage: Some(disk_usage),
+            replicas_in_use,
+        };
+
+        // within tolerance
+        assert_eq!(
+            desired_replicas(&autoscale, 3, Some(3050.0), &load(10.0, 3)),
+            3
+        );
+        // throughput of 2000 per SPU
+        assert_eq!(
+            desired_replicas(&autoscale, 3, Some(6000.0), &load(10.0, 3)),
+            6
+        );
+        // disk usage wins over throughput, bounded by max
+        assert_eq!(
+            desired_replicas(&autoscale, 4, Some(100.0), &load(160.0, 4)),
+            6
+        );
+        // idle group shrinks to the SPUs holding replicas
+        assert_eq!(desired_replicas(&autoscale, 5, Some(0.0), &load(1.0, 4)), 4);
+        assert_eq!(desired_replicas(&autoscale, 5, Some(0.0), &load(1.0, 0)), 2);
+        // no samples yet
+        let no_disk = GroupLoad::default();
+        assert_eq!(desired_replicas(&autoscale, 1, None, &no_disk), 2);
+    }
+}
@@ -0,0 +1,55 @@
+//!
+//! # Consumer Group Controller
+//!
+//! Expires the members of consumer groups that stopped sending heartbeats and
+//! rebalances the groups when their members or the partitions of their topic change.
+//!
+
+use std::time::Duration;
+
+use tracing::{debug, info, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_stream_dispatcher::actions::WSAction;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::SharedContext;
+
+const CONSUMER_GROUP_CONTROLLER_INTERVAL: u64 = 2;
+
+pub struct ConsumerGroupController<C: MetadataItem> {
+    ctx: SharedContext<C>,
+}
+
+impl<C: MetadataItem> ConsumerGroupController<C> {
+    pub fn start(ctx: SharedContext<C>) {
+        let controller = Self { ctx };
+
+        info!("starting consumer group controller");
+        spawn(controller.dispatch_loop());
+    }
+
+    #[instrument(skip(self), name = "ConsumerGroupControllerLoop")]
+    async fn dispatch_loop(self) {
+        loop {
+            self.rebalance_groups().await;
+            sleep(Duration::from_secs(CONSUMER_GROUP_CONTROLLER_INTERVAL)).await;
+        }
+    }
+
+    async fn rebalance_groups(&self) {
+        let groups = self.ctx.consumer_groups();
+        let changes = self
+            .ctx
+            .group_coordinator()
+            .expire(groups, self.ctx.topics())
+            .await;
+        for (name, status) in changes {
+            debug!(%name, generation = status.generation, "updating consumer group");
+            groups
+                .send_action(WSAction::UpdateStatus((name, status)))
+                .await;
+        }
+    }
+}
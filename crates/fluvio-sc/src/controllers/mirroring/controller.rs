@@ -6,10 +6,10 @@ use adaptive_backoff::prelude::{
     ExponentialBackoffBuilder, BackoffBuilder, ExponentialBackoff, Backoff,
 };
 
-use fluvio::config::TlsPolicy;
+use fluvio::config::{FluvioConfig, TlsPolicy};
 use fluvio_socket::{AsyncResponse, ClientConfig, MultiplexerSocket, StreamSocket};
 use futures_util::StreamExt;
-use fluvio_future::{net::DomainConnector, task::spawn, timer::sleep};
+use fluvio_future::{task::spawn, timer::sleep};
 use fluvio_sc_schema::{
     core::MetadataItem,
     mirror::{ConnectionStatus, Home, MirrorPairStatus, MirrorSpec, MirrorStatus, MirrorType},
@@ -146,29 +146,29 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
         Ok(stream)
     }
 
-    // Build the home client
+    // Build the home client, through the proxy of the mirror or the environment if any
     async fn build_home_client(&self, home: &Home) -> Result<ClientConfig> {
-        let tlspolicy = option_tlspolicy(home);
-
-        // handling tls
-        if let Some(tlspolicy) = tlspolicy {
-            match DomainConnector::try_from(tlspolicy.clone()) {
-                Ok(connector) => Ok(ClientConfig::new(
-                    home.public_endpoint.clone(),
-                    connector,
-                    false,
-                )),
-                Err(err) => {
-                    error!(
-                        "error establishing tls with leader at: <{}> err: {}",
-                        home.public_endpoint.clone(),
-                        err
-                    );
-                    Err(err)
-                }
+        let mut cluster = FluvioConfig::new(home.public_endpoint.clone());
+        if let Some(tlspolicy) = option_tlspolicy(home) {
+            cluster = cluster.with_tls(tlspolicy);
+        }
+        if let Some(proxy) = &home.proxy {
+            cluster = cluster.with_proxy(proxy.as_str());
+        }
+
+        match cluster.connector() {
+            Ok(connector) => Ok(ClientConfig::new(
+                home.public_endpoint.clone(),
+                connector,
+                false,
+            )),
+            Err(err) => {
+                error!(
+                    "error creating connector to home at: <{}> err: {}",
+                    home.public_endpoint, err
+                );
+                Err(err)
             }
-        } else {
-            Ok(ClientConfig::with_addr(home.public_endpoint.clone()))
         }
     }
 
@@ -264,6 +264,7 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
         }
 
         remote_topic.set_compression_type(topic.spec.get_compression_type().clone());
+        remote_topic.set_compression_dictionary(topic.spec.get_compression_dictionary().cloned());
 
         remote_topic.set_deduplication(topic.spec.get_deduplication().cloned());
 
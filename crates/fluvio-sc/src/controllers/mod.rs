@@ -3,3 +3,6 @@ pub(crate) mod spus;
 pub(crate) mod topics;
 pub(crate) mod scheduler;
 pub(crate) mod mirroring;
+pub(crate) mod alerts;
+pub(crate) mod autoscale;
+pub(crate) mod consumer_groups;
@@ -222,6 +222,7 @@ where
             spec.set_storage(TopicStorageConfig {
                 segment_size: Some(OFFSET_TOPIC_SEGMENT_SIZE),
                 max_partition_size: Some(OFFSET_TOPIC_PARTITION_SIZE),
+                ..Default::default()
             });
             self.topics
                 .send_action(WSAction::UpdateSpec((
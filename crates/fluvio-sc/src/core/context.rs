@@ -6,6 +6,9 @@
 use std::sync::Arc;
 
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::alert::AlertRuleSpec;
+use fluvio_sc_schema::schema::SchemaSpec;
+use fluvio_sc_schema::acl::AclSpec;
 use fluvio_stream_model::core::MetadataItem;
 
 use crate::config::ScConfig;
@@ -15,6 +18,7 @@ use crate::stores::topic::*;
 use crate::stores::spg::*;
 use crate::stores::smartmodule::*;
 use crate::stores::tableformat::*;
+use crate::stores::consumer_group::*;
 use crate::stores::*;
 
 pub type SharedContext<C> = Arc<Context<C>>;
@@ -31,6 +35,11 @@ pub struct Context<C: MetadataItem> {
     smartmodules: StoreContext<SmartModuleSpec, C>,
     tableformats: StoreContext<TableFormatSpec, C>,
     mirrors: StoreContext<MirrorSpec, C>,
+    alert_rules: StoreContext<AlertRuleSpec, C>,
+    consumer_groups: StoreContext<ConsumerGroupSpec, C>,
+    schemas: StoreContext<SchemaSpec, C>,
+    acls: StoreContext<AclSpec, C>,
+    group_coordinator: GroupCoordinator,
     health: SharedHealthCheck,
     config: ScConfig,
 }
@@ -54,6 +63,11 @@ impl<C: MetadataItem> Context<C> {
             smartmodules: StoreContext::new(),
             tableformats: StoreContext::new(),
             mirrors: StoreContext::new(),
+            alert_rules: StoreContext::new(),
+            consumer_groups: StoreContext::new(),
+            schemas: StoreContext::new(),
+            acls: StoreContext::new(),
+            group_coordinator: GroupCoordinator::default(),
             health: HealthCheck::shared(),
             config,
         }
@@ -90,6 +104,27 @@ impl<C: MetadataItem> Context<C> {
         &self.mirrors
     }
 
+    pub fn alert_rules(&self) -> &StoreContext<AlertRuleSpec, C> {
+        &self.alert_rules
+    }
+
+    pub fn consumer_groups(&self) -> &StoreContext<ConsumerGroupSpec, C> {
+        &self.consumer_groups
+    }
+
+    pub fn schemas(&self) -> &StoreContext<SchemaSpec, C> {
+        &self.schemas
+    }
+
+    pub fn acls(&self) -> &StoreContext<AclSpec, C> {
+        &self.acls
+    }
+
+    /// members of the consumer groups
+    pub fn group_coordinator(&self) -> &GroupCoordinator {
+        &self.group_coordinator
+    }
+
     /// spu health channel
     pub fn health(&self) -> &SharedHealthCheck {
         &self.health
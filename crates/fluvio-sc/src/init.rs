@@ -7,10 +7,17 @@
 use std::sync::Arc;
 
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::alert::AlertRuleSpec;
+use fluvio_sc_schema::consumer_group::ConsumerGroupSpec;
+use fluvio_sc_schema::schema::SchemaSpec;
+use fluvio_sc_schema::acl::AclSpec;
 use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient};
 use fluvio_stream_model::core::MetadataItem;
 
 use crate::controllers::mirroring::controller::RemoteMirrorController;
+use crate::controllers::alerts::controller::AlertController;
+use crate::controllers::autoscale::controller::AutoscaleController;
+use crate::controllers::consumer_groups::controller::ConsumerGroupController;
 use crate::core::Context;
 use crate::core::SharedContext;
 use crate::controllers::partitions::PartitionController;
@@ -18,6 +25,7 @@ use crate::controllers::spus::SpuController;
 use crate::controllers::topics::controller::{TopicController, SystemTopicController};
 use crate::config::ScConfig;
 use crate::services::start_internal_server;
+use crate::services::start_sc_metrics_server;
 use crate::dispatcher::dispatcher::MetadataDispatcher;
 use crate::services::auth::basic::BasicRbacPolicy;
 
@@ -84,6 +92,30 @@ where
         ctx.mirrors().clone(),
     );
 
+    MetadataDispatcher::<AlertRuleSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.alert_rules().clone(),
+    );
+
+    MetadataDispatcher::<ConsumerGroupSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.consumer_groups().clone(),
+    );
+
+    MetadataDispatcher::<SchemaSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.schemas().clone(),
+    );
+
+    MetadataDispatcher::<AclSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.acls().clone(),
+    );
+
     start_main_loop_services(ctx, auth_policy).await
 }
 
@@ -108,6 +140,11 @@ where
     );
 
     whitelist!(config, "internal", start_internal_server(ctx.clone()));
+    whitelist!(
+        config,
+        "admin-http",
+        admin_http::start(ctx.clone(), auth_policy.clone())
+    );
     whitelist!(
         config,
         "public",
@@ -118,6 +155,14 @@ where
         "mirroring",
         RemoteMirrorController::start(ctx.clone())
     );
+    whitelist!(config, "alert", AlertController::start(ctx.clone()));
+    whitelist!(config, "metrics", start_sc_metrics_server(ctx.clone()));
+    whitelist!(config, "autoscale", AutoscaleController::start(ctx.clone()));
+    whitelist!(
+        config,
+        "consumer_group",
+        ConsumerGroupController::start(ctx.clone())
+    );
 
     mod pub_server {
 
@@ -130,6 +175,7 @@ where
 
         use fluvio_controlplane_metadata::core::MetadataItem;
         use crate::services::auth::{AuthGlobalContext, ReadOnlyAuthorization};
+        use crate::services::auth::acl::AclAuthorization;
         use crate::services::auth::basic::{BasicAuthorization, BasicRbacPolicy};
 
         pub fn start<C>(ctx: SharedContext<C>, auth_policy_option: Option<BasicRbacPolicy>)
@@ -137,7 +183,11 @@ where
             C: MetadataItem + 'static,
             C::UId: Send + Sync,
         {
-            if let Some(policy) = auth_policy_option {
+            if let Some(acl) = ctx.config().acl.clone() {
+                info!("using acl authorization");
+                let authorization = AclAuthorization::new(ctx.acls().clone(), acl.super_users);
+                start_public_server(AuthGlobalContext::new(ctx, Arc::new(authorization)));
+            } else if let Some(policy) = auth_policy_option {
                 info!("using basic authorization");
                 start_public_server(AuthGlobalContext::new(
                     ctx,
@@ -160,5 +210,84 @@ where
         }
     }
 
+    mod admin_http {
+
+        use tracing::error;
+
+        use fluvio_auth::root::RootAuthContext;
+        use fluvio_controlplane_metadata::core::MetadataItem;
+        use fluvio_socket::TLS_RELOAD_INTERVAL;
+
+        use crate::core::SharedContext;
+        use crate::services::{
+            start_admin_http_server, PolicyTokenAuthorization, SharedTokenAuthorization,
+        };
+        use crate::services::auth::ReadOnlyAuthContext;
+        use crate::services::auth::acl::AclAuthorization;
+        use crate::services::auth::basic::{BasicAuthorization, BasicRbacPolicy};
+
+        /// tokens bound to identities are authorized by the policy, the shared
+        /// token grants full access unless metadata is read only
+        pub fn start<C>(ctx: SharedContext<C>, auth_policy: Option<BasicRbacPolicy>)
+        where
+            C: MetadataItem + 'static,
+            C::UId: Send + Sync,
+        {
+            let config = ctx.config().clone();
+            let Some(addr) = config.admin_http_endpoint else {
+                return;
+            };
+            let tls = match config
+                .admin_http_tls
+                .map(|tls| tls.try_build_reloadable_acceptor())
+            {
+                Some(Ok(acceptor)) => {
+                    acceptor.watch(TLS_RELOAD_INTERVAL);
+                    Some(acceptor)
+                }
+                Some(Err(err)) => {
+                    error!("admin http server not started, invalid tls: {err:#}");
+                    return;
+                }
+                None => None,
+            };
+
+            match (
+                config.admin_http_tokens,
+                auth_policy,
+                config.admin_http_token,
+            ) {
+                (Some(tokens), _, _) if config.acl.is_some() => {
+                    let super_users = config.acl.unwrap_or_default().super_users;
+                    let authorization = AclAuthorization::new(ctx.acls().clone(), super_users);
+                    match PolicyTokenAuthorization::load(&tokens, authorization) {
+                        Ok(auth) => start_admin_http_server(ctx, auth, addr, tls),
+                        Err(err) => error!("admin http server not started: {err:#}"),
+                    }
+                }
+                (Some(tokens), Some(policy), _) => {
+                    let authorization = BasicAuthorization::new(policy);
+                    match PolicyTokenAuthorization::load(&tokens, authorization) {
+                        Ok(auth) => start_admin_http_server(ctx, auth, addr, tls),
+                        Err(err) => error!("admin http server not started: {err:#}"),
+                    }
+                }
+                (_, _, Some(token)) if config.read_only_metadata => start_admin_http_server(
+                    ctx,
+                    SharedTokenAuthorization::new(token, || ReadOnlyAuthContext {}),
+                    addr,
+                    tls,
+                ),
+                (_, _, Some(token)) => start_admin_http_server(
+                    ctx,
+                    SharedTokenAuthorization::new(token, || RootAuthContext {}),
+                    addr,
+                    tls,
+                ),
+                _ => error!("admin http server not started, no token configured"),
+            }
+        }
+    }
+
     ctx
 }
@@ -223,6 +223,7 @@ mod k8_convert {
         // storage is special because defaults are explicit.
         let storage = spu_template.real_storage_config();
         let size = storage.size;
+        let max_partition_size = storage.max_partition_size;
 
         let spu_pod_config = &spu_k8_config.spu_pod_config;
 
@@ -237,6 +238,12 @@ mod k8_convert {
         }
 
         env.append(&mut spu_pod_config.extra_env.clone());
+        env.extend(
+            spu_template
+                .env
+                .iter()
+                .map(|var| Env::key_value(&var.name, &var.value)),
+        );
 
         let mut volume_mounts = vec![VolumeMount {
             name: "data".to_owned(),
@@ -256,6 +263,10 @@ mod k8_convert {
             "--log-size".to_owned(),
             size.clone(),
         ];
+        if let Some(max_partition_size) = max_partition_size {
+            args.push("--max-partition-size".to_owned());
+            args.push(max_partition_size.to_string());
+        }
 
         if let Some(tls) = tls_config {
             args.push("--tls".to_owned());
@@ -0,0 +1,254 @@
+//!
+//! # Acl Authorization
+//!
+//! Clients are authorized by the Acl objects bound to their principal. Topic entries grant
+//! produce, consume or admin on the matching topics, cluster entries grant them on every
+//! topic and admin on the other objects. Objects can be listed by any client, except the
+//! Acls themselves which need cluster admin.
+//!
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{debug, instrument};
+
+use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
+use fluvio_auth::x509::X509Identity;
+use fluvio_controlplane_metadata::acl::{AclPermission, AclSpec};
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::stores::StoreContext;
+
+#[derive(Debug, Clone)]
+pub struct AclAuthorization<C: MetadataItem> {
+    acls: StoreContext<AclSpec, C>,
+    super_users: Arc<HashSet<String>>,
+}
+
+impl<C: MetadataItem> AclAuthorization<C> {
+    pub fn new(acls: StoreContext<AclSpec, C>, super_users: HashSet<String>) -> Self {
+        Self {
+            acls,
+            super_users: Arc::new(super_users),
+        }
+    }
+
+    /// auth context of an identity established outside of the admin protocol
+    pub fn auth_context(&self, identity: X509Identity) -> AclAuthContext<C> {
+        AclAuthContext {
+            principal: identity.principal,
+            acls: self.acls.clone(),
+            super_users: self.super_users.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl<C> Authorization for AclAuthorization<C>
+where
+    C: MetadataItem + 'static,
+{
+    type Context = AclAuthContext<C>;
+
+    #[instrument(level = "trace", skip(self, socket))]
+    async fn create_auth_context(
+        &self,
+        socket: &mut fluvio_socket::FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let identity = X509Identity::create_from_connection(socket)
+            .await
+            .map_err(|err| {
+                tracing::error!(%err, "failed to create x509 identity");
+                err
+            })?;
+        debug!(principal = %identity.principal, "acl identity");
+        Ok(self.auth_context(identity))
+    }
+}
+
+#[derive(Debug)]
+pub struct AclAuthContext<C: MetadataItem> {
+    principal: String,
+    acls: StoreContext<AclSpec, C>,
+    super_users: Arc<HashSet<String>>,
+}
+
+impl<C: MetadataItem> AclAuthContext<C> {
+    async fn any_acl(&self, allowed: impl Fn(&AclSpec) -> bool) -> bool {
+        self.super_users.contains(&self.principal)
+            || self
+                .acls
+                .store()
+                .read()
+                .await
+                .values()
+                .any(|acl| allowed(acl.spec()))
+    }
+
+    async fn is_cluster_admin(&self) -> bool {
+        self.any_acl(|acl| acl.allows_cluster(&self.principal, AclPermission::Admin))
+            .await
+    }
+}
+
+#[async_trait]
+impl<C> AuthContext for AclAuthContext<C>
+where
+    C: MetadataItem + 'static,
+{
+    async fn allow_type_action(
+        &self,
+        ty: ObjectType,
+        action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        let allowed = match (ty, action) {
+            (ObjectType::Acl, _) => self.is_cluster_admin().await,
+            (_, TypeAction::Read) => true,
+            // the name is checked by `allow_topic_action`
+            (ObjectType::Topic, TypeAction::Create) => {
+                self.any_acl(|acl| {
+                    acl.applies_to(&self.principal) && acl.grants(AclPermission::Admin)
+                })
+                .await
+            }
+            _ => self.is_cluster_admin().await,
+        };
+        Ok(allowed)
+    }
+
+    async fn allow_instance_action(
+        &self,
+        ty: ObjectType,
+        _action: InstanceAction,
+        key: &str,
+    ) -> Result<bool, AuthError> {
+        match ty {
+            ObjectType::Topic => self.allow_topic_action(key, AclPermission::Admin).await,
+            _ => Ok(self.is_cluster_admin().await),
+        }
+    }
+
+    async fn allow_topic_action(
+        &self,
+        topic: &str,
+        permission: AclPermission,
+    ) -> Result<bool, AuthError> {
+        Ok(self
+            .any_acl(|acl| acl.allows_topic(&self.principal, topic, permission))
+            .await)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+
+    use fluvio_controlplane_metadata::acl::AclResource;
+    use fluvio_stream_model::fixture::TestMeta;
+    use fluvio_stream_model::store::{LocalStore, MetadataStoreObject};
+
+    use super::*;
+
+    async fn authorization(acls: Vec<(&str, AclSpec)>) -> AclAuthorization<TestMeta> {
+        let store = LocalStore::<AclSpec, TestMeta>::default();
+        let _ = store
+            .sync_all(
+                acls.into_iter()
+                    .map(|(name, spec)| MetadataStoreObject::with_spec(name, spec))
+                    .collect(),
+            )
+            .await;
+        AclAuthorization::new(
+            StoreContext::new_with_store(Arc::new(store)),
+            HashSet::from(["root".to_owned()]),
+        )
+    }
+
+    fn identity(principal: &str) -> X509Identity {
+        X509Identity::new(principal.to_owned(), vec![])
+    }
+
+    #[fluvio_future::test]
+    async fn test_acl_context() {
+        let authorization = authorization(vec![
+            (
+                "orders-admin",
+                AclSpec::new(
+                    "app",
+                    AclResource::topic("orders-*"),
+                    vec![AclPermission::Admin],
+                ),
+            ),
+            (
+                "payments-read",
+                AclSpec::new(
+                    "app",
+                    AclResource::topic("payments"),
+                    vec![AclPermission::Consume],
+                ),
+            ),
+        ])
+        .await;
+
+        let app = authorization.auth_context(identity("app"));
+        assert!(app
+            .allow_type_action(ObjectType::Topic, TypeAction::Create)
+            .await
+            .unwrap());
+        assert!(app
+            .allow_type_action(ObjectType::Topic, TypeAction::Read)
+            .await
+            .unwrap());
+        assert!(!app
+            .allow_type_action(ObjectType::Acl, TypeAction::Read)
+            .await
+            .unwrap());
+        assert!(!app
+            .allow_type_action(ObjectType::SmartModule, TypeAction::Create)
+            .await
+            .unwrap());
+        assert!(app
+            .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "orders-eu")
+            .await
+            .unwrap());
+        assert!(!app
+            .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "payments")
+            .await
+            .unwrap());
+        assert!(app
+            .allow_topic_action("orders-eu", AclPermission::Produce)
+            .await
+            .unwrap());
+        assert!(app
+            .allow_topic_action("payments", AclPermission::Consume)
+            .await
+            .unwrap());
+        assert!(!app
+            .allow_topic_action("payments", AclPermission::Produce)
+            .await
+            .unwrap());
+
+        let other = authorization.auth_context(identity("other"));
+        assert!(!other
+            .allow_type_action(ObjectType::Topic, TypeAction::Create)
+            .await
+            .unwrap());
+        assert!(!other
+            .allow_topic_action("orders-eu", AclPermission::Consume)
+            .await
+            .unwrap());
+
+        let root = authorization.auth_context(identity("root"));
+        assert!(root
+            .allow_type_action(ObjectType::Acl, TypeAction::Create)
+            .await
+            .unwrap());
+        assert!(root
+            .allow_topic_action("payments", AclPermission::Produce)
+            .await
+            .unwrap());
+    }
+}
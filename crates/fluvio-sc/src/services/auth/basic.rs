@@ -19,6 +19,14 @@ impl BasicAuthorization {
             policy: Arc::new(policy),
         }
     }
+
+    /// auth context of an identity established outside of the admin protocol
+    pub fn auth_context(&self, identity: X509Identity) -> BasicAuthContext {
+        BasicAuthContext {
+            identity,
+            policy: self.policy.clone(),
+        }
+    }
 }
 
 #[async_trait]
@@ -253,6 +261,16 @@ mod policy {
                 ObjectType::TableFormat,
                 vec![ActionUrn::new(Action::All, None)],
             );
+            root_policy.insert(
+                ObjectType::AlertRule,
+                vec![ActionUrn::new(Action::All, None)],
+            );
+            root_policy.insert(
+                ObjectType::ConsumerGroup,
+                vec![ActionUrn::new(Action::All, None)],
+            );
+            root_policy.insert(ObjectType::Schema, vec![ActionUrn::new(Action::All, None)]);
+            root_policy.insert(ObjectType::Acl, vec![ActionUrn::new(Action::All, None)]);
             root_policy.insert(
                 ObjectType::Mirror,
                 vec![
@@ -1,4 +1,5 @@
 pub mod basic;
+pub mod acl;
 
 pub use common::*;
 
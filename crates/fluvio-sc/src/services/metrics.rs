@@ -0,0 +1,180 @@
+//!
+//! # Metrics
+//!
+//! Prometheus metrics of the cluster state known to the SC: SPUs, topics and the
+//! replication of their partitions as reported by the leaders. Series of partitions are
+//! limited by the metrics cardinality of the config.
+//!
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use tracing::error;
+
+use fluvio_controlplane_metadata::partition::PartitionResolution;
+use fluvio_future::task::spawn;
+use fluvio_service::metrics::{
+    start_metrics_server, Aggregation, MetricType, MetricsSource, MetricsText, PartitionSample,
+};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::SharedContext;
+
+/// serve Prometheus metrics if an endpoint is configured
+pub fn start_sc_metrics_server<C>(ctx: SharedContext<C>)
+where
+    C: MetadataItem + 'static,
+    C::UId: Send + Sync,
+{
+    let Some(endpoint) = ctx.config().metrics_endpoint.clone() else {
+        return;
+    };
+    spawn(async move {
+        if let Err(err) = start_metrics_server(&endpoint, ScMetricsSource(ctx)).await {
+            error!(%endpoint, "unable to start metrics server: {err}");
+        }
+    });
+}
+
+struct ScMetricsSource<C: MetadataItem>(SharedContext<C>);
+
+#[async_trait]
+impl<C> MetricsSource for ScMetricsSource<C>
+where
+    C: MetadataItem + 'static,
+    C::UId: Send + Sync,
+{
+    async fn collect(&self, text: &mut MetricsText) {
+        let ctx = &self.0;
+
+        let spus = ctx.spus().store().clone_values().await;
+        let online = spus.iter().filter(|spu| spu.status.is_online()).count();
+        text.family(
+            "fluvio_sc_spus",
+            MetricType::Gauge,
+            "SPUs registered in the cluster",
+        )
+        .sample("fluvio_sc_spus", &[("status", "online")], online)
+        .sample(
+            "fluvio_sc_spus",
+            &[("status", "offline")],
+            spus.len() - online,
+        );
+
+        let topics = ctx.topics().store().clone_values().await;
+        text.family(
+            "fluvio_sc_topics",
+            MetricType::Gauge,
+            "Topics of the cluster",
+        );
+        let mut resolutions: BTreeMap<&str, usize> = BTreeMap::new();
+        for topic in &topics {
+            *resolutions
+                .entry(topic.status.resolution.resolution_label())
+                .or_default() += 1;
+        }
+        for (resolution, count) in resolutions {
+            text.sample("fluvio_sc_topics", &[("status", resolution)], count);
+        }
+
+        let mut partitions = ctx.partitions().store().clone_values().await;
+        partitions.sort_by(|a, b| a.key.cmp(&b.key));
+        text.family(
+            "fluvio_sc_partitions",
+            MetricType::Gauge,
+            "Partitions of the cluster",
+        );
+        for resolution in [
+            PartitionResolution::Online,
+            PartitionResolution::Offline,
+            PartitionResolution::LeaderOffline,
+            PartitionResolution::ElectionLeaderFound,
+        ] {
+            let count = partitions
+                .iter()
+                .filter(|partition| partition.status.resolution == resolution)
+                .count();
+            text.sample(
+                "fluvio_sc_partitions",
+                &[("status", resolution_label(&resolution))],
+                count,
+            );
+        }
+
+        let partition_gauges: [(&str, &str, Aggregation, fn(&PartitionMetrics) -> i64); 4] = [
+            (
+                "fluvio_sc_partition_high_watermark",
+                "Offset after the last committed record of the partition",
+                Aggregation::Sum,
+                |partition| partition.hw,
+            ),
+            (
+                "fluvio_sc_partition_end_offset",
+                "Offset after the last record of the partition leader",
+                Aggregation::Sum,
+                |partition| partition.leo,
+            ),
+            (
+                "fluvio_sc_partition_replica_lag",
+                "Records of the partition leader not replicated by its slowest follower",
+                Aggregation::Max,
+                |partition| partition.replica_lag,
+            ),
+            (
+                "fluvio_sc_partition_size_bytes",
+                "Storage used by the partition leader",
+                Aggregation::Sum,
+                |partition| partition.size,
+            ),
+        ];
+        let partitions: Vec<PartitionMetrics> = partitions
+            .iter()
+            .map(|partition| {
+                let status = &partition.status;
+                let leader = &status.leader;
+                PartitionMetrics {
+                    topic: partition.key.topic.clone(),
+                    partition: partition.key.partition,
+                    hw: leader.hw,
+                    leo: leader.leo,
+                    replica_lag: status
+                        .replicas
+                        .iter()
+                        .map(|replica| (leader.leo - replica.leo).max(0))
+                        .max()
+                        .unwrap_or_default(),
+                    size: status.size,
+                }
+            })
+            .collect();
+        let cardinality = &ctx.config().metrics_cardinality;
+        for (name, help, aggregation, value) in partition_gauges {
+            let samples: Vec<PartitionSample> = partitions
+                .iter()
+                .map(|partition| {
+                    PartitionSample::new(&partition.topic, partition.partition, value(partition))
+                })
+                .collect();
+            text.family(name, MetricType::Gauge, help)
+                .partition_samples(name, cardinality, aggregation, &samples);
+        }
+    }
+}
+
+struct PartitionMetrics {
+    topic: String,
+    partition: u32,
+    hw: i64,
+    leo: i64,
+    replica_lag: i64,
+    size: i64,
+}
+
+fn resolution_label(resolution: &PartitionResolution) -> &'static str {
+    match resolution {
+        PartitionResolution::Online => "online",
+        PartitionResolution::Offline => "offline",
+        PartitionResolution::LeaderOffline => "leader-offline",
+        PartitionResolution::ElectionLeaderFound => "election-leader-found",
+    }
+}
@@ -1,8 +1,13 @@
 // pub mod send_channels;
 mod public_api;
 mod private_api;
+mod spu_gateway;
+mod metrics;
 
 pub mod auth;
 
 pub use public_api::start_public_server;
+pub use public_api::{start_admin_http_server, PolicyTokenAuthorization, SharedTokenAuthorization};
 pub use private_api::start_internal_server;
+pub use spu_gateway::start_spu_gateway;
+pub use metrics::start_sc_metrics_server;
@@ -22,6 +22,7 @@ where
     info!("starting internal services");
 
     let addr = ctx.config().private_endpoint.clone();
-    let server = FluvioApiServer::new(addr, ctx, ScInternalService::new());
+    let tcp = ctx.config().tcp.clone();
+    let server = FluvioApiServer::new(addr, ctx, ScInternalService::new()).with_tcp_config(tcp);
     server.run();
 }
@@ -14,12 +14,15 @@ use fluvio_controlplane::sc_api::register_spu::RegisterSpuResponse;
 use fluvio_controlplane::sc_api::remove::ReplicaRemovedRequest;
 use fluvio_controlplane::sc_api::update_lrs::UpdateLrsRequest;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
+use fluvio_controlplane::spu_api::update_acl::AclMsg;
+use fluvio_controlplane::spu_api::update_acl::UpdateAclRequest;
 use fluvio_controlplane::spu_api::update_mirror::MirrorMsg;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
 use fluvio_controlplane_metadata::message::Message;
+use fluvio_sc_schema::acl::AclSpec;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::ChangeListener;
@@ -134,6 +137,7 @@ where
     let mut partition_spec_listener = context.partitions().change_listener();
     let mut sm_spec_listener = context.smartmodules().change_listener();
     let mut mirror_spec_listener = context.mirrors().change_listener();
+    let mut acl_spec_listener = context.acls().change_listener();
 
     // send initial changes
 
@@ -147,6 +151,7 @@ where
         send_smartmodule_changes(&mut sm_spec_listener, &mut sink, spu_id).await?;
         send_replica_spec_changes(&mut partition_spec_listener, &mut sink, spu_id).await?;
         send_mirror_changes(&mut mirror_spec_listener, &mut sink, spu_id).await?;
+        send_acl_changes(&mut acl_spec_listener, &mut sink, spu_id).await?;
 
         trace!(spu_id, "waiting for SPU channel");
 
@@ -207,6 +212,10 @@ where
                 debug!("mirror lister changed");
             }
 
+            _ = acl_spec_listener.listen() => {
+                debug!("acl lister changed");
+            }
+
         }
     }
 
@@ -232,13 +241,14 @@ where
         if let Some(partition) = read_guard.get(&lrs_req.id) {
             let mut current_status = partition.inner().status().clone();
             let key = lrs_req.id.clone();
-            let new_status = PartitionStatus::new2(
+            let mut new_status = PartitionStatus::new2(
                 lrs_req.leader,
                 lrs_req.replicas,
                 lrs_req.size,
                 PartitionResolution::Online,
                 lrs_req.base_offset,
             );
+            new_status.storage_full = lrs_req.storage_full;
             current_status.merge(new_status);
 
             actions.push(WSAction::<PartitionSpec, C>::UpdateStatus((
@@ -559,3 +569,57 @@ async fn send_mirror_changes<C: MetadataItem>(
     sink.send_request(&message).await?;
     Ok(())
 }
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_acl_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<AclSpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateAclRequest::with_all(epoch, updates.into_iter().map(|acl| acl.into()).collect())
+    } else {
+        let mut changes: Vec<AclMsg> = updates
+            .into_iter()
+            .map(|acl| Message::update(acl.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|acl| Message::delete(acl.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateAclRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending acls to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
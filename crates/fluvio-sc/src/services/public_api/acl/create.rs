@@ -0,0 +1,119 @@
+//!
+//! # Create Acl Request
+//!
+//! Validates the principal, resource and permissions before storing the Acl in the KV store.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::acl::AclSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for acl request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_acl_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<AclSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, principal = %spec.principal, resource = %spec.resource, "creating acl");
+
+    if auth_ctx.global_ctx.acls().store().contains_key(&name).await {
+        debug!("acl already exists");
+        return Ok(Status::new(
+            name.to_string(),
+            ErrorCode::AclAlreadyExists,
+            Some(format!("acl '{name}' already defined")),
+        ));
+    }
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(AclSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Err(reason) = validate_acl(&spec) {
+        return Ok(Status::new(
+            name,
+            ErrorCode::AclInvalid(reason.clone()),
+            Some(reason),
+        ));
+    }
+
+    let status = process_acl_request(&auth_ctx.global_ctx, name, spec).await;
+    trace!("create acl response {:#?}", status);
+
+    Ok(status)
+}
+
+fn validate_acl(spec: &AclSpec) -> Result<(), String> {
+    if spec.principal.is_empty() {
+        return Err("acl principal is empty".to_owned());
+    }
+    if spec.permissions.is_empty() {
+        return Err("acl has no permissions".to_owned());
+    }
+    spec.resource.validate()
+}
+
+#[instrument(skip(ctx, name, spec))]
+async fn process_acl_request<C: MetadataItem>(
+    ctx: &Context<C>,
+    name: String,
+    spec: AclSpec,
+) -> Status {
+    if let Err(err) = ctx.acls().create_spec(name.clone(), spec).await {
+        let reason = err.to_string();
+        Status::new(name, ErrorCode::AclInvalid(reason.clone()), Some(reason))
+    } else {
+        info!(%name, "acl created");
+        Status::new_ok(name.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_sc_schema::acl::{AclPermission, AclResource};
+
+    use super::*;
+
+    #[test]
+    fn test_validate_acl() {
+        let mut spec = AclSpec::new(
+            "app",
+            AclResource::topic("orders*"),
+            vec![AclPermission::Consume],
+        );
+        assert!(validate_acl(&spec).is_ok());
+
+        spec.permissions.clear();
+        assert!(validate_acl(&spec).is_err());
+
+        let spec = AclSpec::new("", AclResource::Cluster, vec![AclPermission::Admin]);
+        assert!(validate_acl(&spec).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//!
+//! # Bearer Token Authorization
+//!
+//! Maps the bearer token of a request to the auth context used by the handlers.
+//! A single shared token grants the context of the SC, while a token file binds
+//! each token to an identity evaluated by the authorization policy or the Acls.
+//!
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use fluvio_auth::AuthContext;
+use fluvio_auth::x509::X509Identity;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::services::auth::acl::{AclAuthContext, AclAuthorization};
+use crate::services::auth::basic::{BasicAuthContext, BasicAuthorization};
+
+pub trait HttpAuthorization: Send + Sync + 'static {
+    type Context: AuthContext + Send + Sync;
+
+    /// auth context of the bearer token, None if the token is not valid
+    fn authorize(&self, token: &str) -> Option<Self::Context>;
+}
+
+/// Single token shared by every client
+pub struct SharedTokenAuthorization<AC> {
+    token: String,
+    context: fn() -> AC,
+}
+
+impl<AC> SharedTokenAuthorization<AC> {
+    pub fn new(token: String, context: fn() -> AC) -> Self {
+        Self { token, context }
+    }
+}
+
+impl<AC> HttpAuthorization for SharedTokenAuthorization<AC>
+where
+    AC: AuthContext + Send + Sync + 'static,
+{
+    type Context = AC;
+
+    fn authorize(&self, token: &str) -> Option<AC> {
+        token_matches(token, &self.token).then(self.context)
+    }
+}
+
+/// Authorization of identities that don't come from the admin protocol
+pub trait IdentityAuthorization: Send + Sync + 'static {
+    type Context: AuthContext + Send + Sync;
+
+    fn identity_context(&self, identity: X509Identity) -> Self::Context;
+}
+
+impl IdentityAuthorization for BasicAuthorization {
+    type Context = BasicAuthContext;
+
+    fn identity_context(&self, identity: X509Identity) -> BasicAuthContext {
+        self.auth_context(identity)
+    }
+}
+
+impl<C: MetadataItem + 'static> IdentityAuthorization for AclAuthorization<C> {
+    type Context = AclAuthContext<C>;
+
+    fn identity_context(&self, identity: X509Identity) -> AclAuthContext<C> {
+        self.auth_context(identity)
+    }
+}
+
+/// Tokens bound to identities, authorized by the policy or the Acls of their principal
+///
+/// The token file maps tokens to identities like
+/// `{"<token>": {"principal": "alice", "scopes": ["Admin"]}}`.
+pub struct PolicyTokenAuthorization<A> {
+    tokens: Vec<(String, X509Identity)>,
+    authorization: A,
+}
+
+impl<A: IdentityAuthorization> PolicyTokenAuthorization<A> {
+    pub fn load(path: &Path, authorization: A) -> Result<Self> {
+        let file = std::fs::read_to_string(path)
+            .with_context(|| format!("reading admin http tokens {}", path.display()))?;
+        let tokens: HashMap<String, X509Identity> = serde_json::from_str(&file)
+            .with_context(|| format!("parsing admin http tokens {}", path.display()))?;
+        Ok(Self {
+            tokens: tokens.into_iter().collect(),
+            authorization,
+        })
+    }
+}
+
+impl<A: IdentityAuthorization> HttpAuthorization for PolicyTokenAuthorization<A> {
+    type Context = A::Context;
+
+    fn authorize(&self, token: &str) -> Option<A::Context> {
+        // go through every token, so the time taken doesn't tell which one matched
+        let mut identity = None;
+        for (expected, candidate) in &self.tokens {
+            if token_matches(token, expected) {
+                identity = Some(candidate);
+            }
+        }
+        identity.map(|identity| self.authorization.identity_context(identity.clone()))
+    }
+}
+
+/// compare tokens without exiting on the first mismatch
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_auth::root::RootAuthContext;
+
+    use crate::services::auth::basic::BasicRbacPolicy;
+
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        assert!(token_matches("secret", "secret"));
+        assert!(!token_matches("secreT", "secret"));
+        assert!(!token_matches("secret2", "secret"));
+
+        let shared = SharedTokenAuthorization::new("secret".to_owned(), || RootAuthContext {});
+        assert!(shared.authorize("secret").is_some());
+        assert!(shared.authorize("").is_none());
+    }
+
+    #[test]
+    fn policy_tokens() {
+        let dir = std::env::temp_dir().join("fluvio-sc-admin-http-tokens");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tokens.json");
+        std::fs::write(
+            &path,
+            r#"{"t-alice": {"principal": "alice", "scopes": ["Admin"]}}"#,
+        )
+        .unwrap();
+
+        let authorization = PolicyTokenAuthorization::load(
+            &path,
+            BasicAuthorization::new(BasicRbacPolicy::default()),
+        )
+        .expect("load");
+        assert!(authorization.authorize("t-alice").is_some());
+        assert!(authorization.authorize("t-bob").is_none());
+    }
+}
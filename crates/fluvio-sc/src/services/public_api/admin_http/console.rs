@@ -0,0 +1,44 @@
+//!
+//! # Web Console
+//!
+//! Static single page console embedded in the SC binary. The page only calls the
+//! admin HTTP API, with the token entered by the user, so serving it requires no
+//! authentication.
+//!
+
+use super::http::HttpResponse;
+
+pub const CONSOLE_PATH: &str = "/console";
+
+const INDEX_HTML: &str = include_str!("console/index.html");
+const CONSOLE_JS: &str = include_str!("console/console.js");
+const CONSOLE_CSS: &str = include_str!("console/console.css");
+
+/// console asset served at path
+pub fn asset(path: &str) -> Option<HttpResponse> {
+    let rest = path.strip_prefix(CONSOLE_PATH)?;
+    let (content_type, body) = match rest {
+        "" | "/" | "/index.html" => ("text/html; charset=utf-8", INDEX_HTML),
+        "/console.js" => ("text/javascript; charset=utf-8", CONSOLE_JS),
+        "/console.css" => ("text/css; charset=utf-8", CONSOLE_CSS),
+        _ => return None,
+    };
+    Some(HttpResponse::asset(content_type, body.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn console_assets() {
+        let index = asset("/console").expect("index");
+        assert!(index.content_type.starts_with("text/html"));
+        assert!(String::from_utf8(index.body)
+            .unwrap()
+            .contains("console.js"));
+        assert!(asset("/console/console.js").is_some());
+        assert!(asset("/console/missing.js").is_none());
+        assert!(asset("/v1/topics").is_none());
+    }
+}
@@ -0,0 +1,252 @@
+"use strict";
+
+const TOKEN_KEY = "fluvio-console-token";
+
+function token() {
+  return sessionStorage.getItem(TOKEN_KEY);
+}
+
+function showError(message) {
+  const error = document.getElementById("error");
+  error.textContent = message;
+  error.hidden = !message;
+}
+
+async function api(method, path, body) {
+  const response = await fetch(path, {
+    method,
+    headers: {
+      "Authorization": `Bearer ${token()}`,
+      "Content-Type": "application/json",
+    },
+    body: body === undefined ? undefined : JSON.stringify(body),
+  });
+  if (response.status === 401) {
+    sessionStorage.removeItem(TOKEN_KEY);
+    showLogin();
+    throw new Error("invalid token");
+  }
+  const result = await response.json();
+  if (!response.ok) {
+    throw new Error(`${result.code}: ${result.message}`);
+  }
+  return result;
+}
+
+function cell(row, value, className) {
+  const td = row.insertCell();
+  td.textContent = value === undefined || value === null ? "" : String(value);
+  if (className) {
+    td.className = className;
+  }
+  return td;
+}
+
+function clearRows(tbody) {
+  while (tbody.rows.length) {
+    tbody.deleteRow(0);
+  }
+}
+
+function bytes(size) {
+  if (size < 0) {
+    return "";
+  }
+  const units = ["B", "KiB", "MiB", "GiB", "TiB"];
+  let unit = 0;
+  while (size >= 1024 && unit < units.length - 1) {
+    size /= 1024;
+    unit += 1;
+  }
+  return `${size.toFixed(unit ? 1 : 0)} ${units[unit]}`;
+}
+
+async function loadTopics() {
+  const topics = await api("GET", "/v1/topics");
+  const tbody = document.querySelector("#topics > table tbody");
+  clearRows(tbody);
+  for (const topic of topics) {
+    const replicas = topic.spec.replicas;
+    const computed = replicas.computed;
+    const row = tbody.insertRow();
+    row.className = "selectable";
+    cell(row, topic.name);
+    cell(row, Object.keys(replicas)[0]);
+    cell(row, computed ? computed.partitions : "");
+    cell(row, computed ? computed.replicationFactor : "");
+    cell(row, topic.status.resolution);
+    row.addEventListener("click", () => loadPartitions(topic.name).catch((err) => showError(err.message)));
+  }
+}
+
+async function loadPartitions(topic) {
+  const partitions = await api("GET", "/v1/partitions");
+  const section = document.getElementById("partitions");
+  section.querySelector("h2").textContent = `Partitions of ${topic}`;
+  const tbody = section.querySelector("tbody");
+  clearRows(tbody);
+  const prefix = `${topic}-`;
+  for (const partition of partitions) {
+    const suffix = partition.name.slice(prefix.length);
+    if (!partition.name.startsWith(prefix) || !/^\d+$/.test(suffix)) {
+      continue;
+    }
+    const { leader, replicas, size, resolution } = partition.status;
+    const lag = replicas.reduce((max, replica) => Math.max(max, leader.leo - replica.leo), 0);
+    const row = tbody.insertRow();
+    cell(row, suffix);
+    cell(row, leader.spu);
+    cell(row, leader.hw);
+    cell(row, leader.leo);
+    cell(row, lag);
+    cell(row, bytes(size));
+    cell(row, resolution, resolution === "Online" ? "online" : "offline");
+    const peek = document.createElement("button");
+    peek.textContent = "Peek";
+    peek.addEventListener("click", () => loadRecords(topic, suffix).catch((err) => showError(err.message)));
+    row.insertCell().appendChild(peek);
+  }
+  document.getElementById("records").hidden = true;
+  section.hidden = false;
+}
+
+async function loadRecords(topic, partition) {
+  const sample = await api(
+    "GET",
+    `/v1/topics/${encodeURIComponent(topic)}/sample?partition=${partition}&strategy=tail`,
+  );
+  const section = document.getElementById("records");
+  section.querySelector("h2").textContent = `Last records of ${topic}-${partition}`;
+  const tbody = section.querySelector("tbody");
+  clearRows(tbody);
+  for (const record of sample.records) {
+    const row = tbody.insertRow();
+    cell(row, record.offset);
+    cell(row, record.timestamp < 0 ? "" : new Date(record.timestamp).toISOString());
+    cell(row, record.key);
+    cell(row, record.value, "value");
+  }
+  section.hidden = false;
+}
+
+async function loadSpus() {
+  const spus = await api("GET", "/v1/spus");
+  const tbody = document.querySelector("#spus tbody");
+  clearRows(tbody);
+  for (const spu of spus) {
+    const endpoint = spu.spec.publicEndpoint;
+    const host = endpoint.ingress.map((ingress) => ingress.hostname || ingress.ip).join(",");
+    const row = tbody.insertRow();
+    cell(row, spu.name);
+    cell(row, spu.spec.spuId);
+    cell(row, spu.spec.spuType);
+    cell(row, spu.spec.rack);
+    cell(row, `${host}:${endpoint.port}`);
+    cell(row, spu.status.resolution, spu.status.resolution === "Online" ? "online" : "offline");
+  }
+}
+
+async function loadSmartModules() {
+  const smartmodules = await api("GET", "/v1/smartmodules?summary");
+  const tbody = document.querySelector("#smartmodules tbody");
+  clearRows(tbody);
+  for (const smartmodule of smartmodules) {
+    const row = tbody.insertRow();
+    cell(row, smartmodule.name);
+    const pkg = smartmodule.spec.meta && smartmodule.spec.meta.package;
+    cell(row, pkg ? `${pkg.group}/${pkg.name}@${pkg.version}` : "");
+    const remove = document.createElement("button");
+    remove.textContent = "Delete";
+    remove.addEventListener("click", async () => {
+      if (!confirm(`Delete SmartModule ${smartmodule.name}?`)) {
+        return;
+      }
+      try {
+        await api("DELETE", `/v1/smartmodules/${encodeURIComponent(smartmodule.name)}`);
+        await loadSmartModules();
+      } catch (err) {
+        showError(err.message);
+      }
+    });
+    row.insertCell().appendChild(remove);
+  }
+}
+
+function base64(buffer) {
+  let binary = "";
+  const bytes = new Uint8Array(buffer);
+  for (let i = 0; i < bytes.length; i += 0x8000) {
+    binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+  }
+  return btoa(binary);
+}
+
+async function uploadSmartModule(event) {
+  event.preventDefault();
+  const name = document.getElementById("sm-name").value;
+  const file = document.getElementById("sm-file").files[0];
+  const payload = base64(await file.arrayBuffer());
+  await api("POST", "/v1/smartmodules", {
+    name,
+    spec: { meta: null, wasm: { format: "BINARY", payload } },
+  });
+  event.target.reset();
+  await loadSmartModules();
+}
+
+const VIEWS = {
+  topics: loadTopics,
+  spus: loadSpus,
+  smartmodules: loadSmartModules,
+};
+
+async function showView(name) {
+  document.getElementById("login").hidden = true;
+  for (const view of document.querySelectorAll(".view")) {
+    view.hidden = view.id !== name;
+  }
+  document.getElementById("partitions").hidden = true;
+  document.getElementById("records").hidden = true;
+  showError("");
+  try {
+    await VIEWS[name]();
+  } catch (err) {
+    showError(err.message);
+  }
+}
+
+function showLogin() {
+  for (const view of document.querySelectorAll(".view")) {
+    view.hidden = true;
+  }
+  document.getElementById("login").hidden = false;
+}
+
+document.getElementById("login-form").addEventListener("submit", (event) => {
+  event.preventDefault();
+  sessionStorage.setItem(TOKEN_KEY, document.getElementById("token").value);
+  showView("topics");
+});
+
+document.getElementById("logout").addEventListener("click", () => {
+  sessionStorage.removeItem(TOKEN_KEY);
+  showLogin();
+});
+
+document.getElementById("upload-form").addEventListener("submit", (event) => {
+  uploadSmartModule(event).catch((err) => showError(err.message));
+});
+
+for (const button of document.querySelectorAll("nav button[data-view]")) {
+  button.addEventListener("click", () => {
+    if (token()) {
+      showView(button.dataset.view);
+    }
+  });
+}
+
+if (token()) {
+  showView("topics");
+} else {
+  showLogin();
+}
@@ -0,0 +1,314 @@
+//!
+//! # Minimal HTTP/1.1
+//!
+//! Just enough of HTTP/1.1 to serve JSON requests: one request per connection,
+//! bodies are delimited by `Content-Length` only. The head is read first so the
+//! request can be authenticated before its body is received.
+//!
+
+use std::future::Future;
+use std::io::{Error as IoError, ErrorKind};
+use std::time::Duration;
+
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use serde::Serialize;
+use tokio::select;
+
+use fluvio_future::timer::sleep;
+
+/// limit of the request line and headers
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+/// limit of request bodies, large enough for SmartModule binaries
+const MAX_BODY_SIZE: usize = 64 * 1024 * 1024;
+/// time for a client to send the request line and headers
+pub const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+/// time for a client to send the body, once authenticated
+pub const BODY_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Default)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    headers: Vec<(String, String)>,
+    content_length: usize,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// value of header, names are case insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// value of query parameter, parameters without value are empty
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.as_deref()?.split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key == name).then_some(value)
+        })
+    }
+
+    /// whether query parameter is present and not false
+    pub fn query_flag(&self, name: &str) -> bool {
+        self.query_param(name)
+            .map(|value| value != "false")
+            .unwrap_or(false)
+    }
+
+    /// token of `Authorization: Bearer <token>` header
+    pub fn bearer_token(&self) -> Option<&str> {
+        let (scheme, token) = self.header("authorization")?.split_once(' ')?;
+        scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+    }
+
+    /// parse request line and headers, without the terminating empty line
+    fn parse_head(head: &str) -> Result<Self, IoError> {
+        let mut lines = head.split("\r\n");
+        let request_line = lines.next().unwrap_or_default();
+        let mut parts = request_line.split(' ');
+        let (Some(method), Some(target), Some(version)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid(format!("invalid request line: {request_line}")));
+        };
+        if !version.starts_with("HTTP/1.") {
+            return Err(invalid(format!("unsupported version: {version}")));
+        }
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, Some(query.to_owned())),
+            None => (target, None),
+        };
+
+        let mut headers = vec![];
+        for line in lines {
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| invalid(format!("invalid header: {line}")))?;
+            headers.push((name.trim().to_owned(), value.trim().to_owned()));
+        }
+
+        Ok(Self {
+            method: method.to_owned(),
+            path: path.to_owned(),
+            query,
+            headers,
+            content_length: 0,
+            body: vec![],
+        })
+    }
+
+    /// read a whole request from the stream
+    #[cfg(test)]
+    pub async fn read_from<R: AsyncRead + Unpin>(stream: &mut R) -> Result<Self, IoError> {
+        let mut request = Self::read_head(stream).await?;
+        request.read_body(stream).await?;
+        Ok(request)
+    }
+
+    /// read request line and headers, the body is left unread except for the
+    /// bytes received along with the head
+    pub async fn read_head<R: AsyncRead + Unpin>(stream: &mut R) -> Result<Self, IoError> {
+        let mut buf = Vec::with_capacity(1024);
+        let mut chunk = [0u8; 4096];
+        let head_end = loop {
+            if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+                break pos;
+            }
+            if buf.len() > MAX_HEAD_SIZE {
+                return Err(invalid("request head too large".to_owned()));
+            }
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Err(IoError::new(
+                    ErrorKind::UnexpectedEof,
+                    "connection closed before end of request head",
+                ));
+            }
+            buf.extend_from_slice(&chunk[..read]);
+        };
+
+        let head = std::str::from_utf8(&buf[..head_end])
+            .map_err(|_| invalid("request head is not utf-8".to_owned()))?;
+        let mut request = Self::parse_head(head)?;
+
+        let content_length = match request.header("content-length") {
+            Some(length) => length
+                .parse::<usize>()
+                .map_err(|_| invalid(format!("invalid content-length: {length}")))?,
+            None => 0,
+        };
+        if content_length > MAX_BODY_SIZE {
+            return Err(invalid(format!("request body too large: {content_length}")));
+        }
+
+        let mut body = buf.split_off(head_end + 4);
+        body.truncate(content_length);
+        request.content_length = content_length;
+        request.body = body;
+        Ok(request)
+    }
+
+    /// read the rest of the body, the buffer grows with the bytes received
+    /// instead of being allocated for the announced length upfront
+    pub async fn read_body<R: AsyncRead + Unpin>(&mut self, stream: &mut R) -> Result<(), IoError> {
+        let mut chunk = [0u8; 16 * 1024];
+        while self.body.len() < self.content_length {
+            let wanted = chunk.len().min(self.content_length - self.body.len());
+            let read = stream.read(&mut chunk[..wanted]).await?;
+            if read == 0 {
+                return Err(IoError::new(
+                    ErrorKind::UnexpectedEof,
+                    "connection closed before end of request body",
+                ));
+            }
+            self.body.extend_from_slice(&chunk[..read]);
+        }
+        Ok(())
+    }
+}
+
+/// fail with `ErrorKind::TimedOut` if `future` doesn't complete in `duration`
+pub async fn with_timeout<T>(
+    duration: Duration,
+    future: impl Future<Output = Result<T, IoError>>,
+) -> Result<T, IoError> {
+    select! {
+        result = future => result,
+        _ = sleep(duration) => Err(IoError::new(
+            ErrorKind::TimedOut,
+            format!("request not received in {}s", duration.as_secs()),
+        )),
+    }
+}
+
+fn invalid(message: String) -> IoError {
+    IoError::new(ErrorKind::InvalidData, message)
+}
+
+#[derive(Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Self {
+                status,
+                content_type: "application/json",
+                body,
+            },
+            Err(err) => Self::error(500, "InternalError", &err.to_string()),
+        }
+    }
+
+    /// static file
+    #[cfg(feature = "console")]
+    pub fn asset(content_type: &'static str, body: &[u8]) -> Self {
+        Self {
+            status: 200,
+            content_type,
+            body: body.to_vec(),
+        }
+    }
+
+    pub fn error(status: u16, code: &str, message: &str) -> Self {
+        Self::json(
+            status,
+            &serde_json::json!({ "code": code, "message": message }),
+        )
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            409 => "Conflict",
+            _ => "Internal Server Error",
+        }
+    }
+
+    pub async fn write_to<W: AsyncWrite + Unpin>(&self, stream: &mut W) -> Result<(), IoError> {
+        let head = format!(
+            "HTTP/1.1 {} {}\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            self.status,
+            self.reason(),
+            self.content_type,
+            self.body.len()
+        );
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&self.body).await?;
+        stream.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::io::Cursor;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn read_request() {
+        let raw = b"POST /v1/topics?dryRun&force=false HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer secret\r\nContent-Length: 4\r\n\r\n{}{}";
+        let request = HttpRequest::read_from(&mut Cursor::new(raw.to_vec()))
+            .await
+            .expect("request");
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/topics");
+        assert_eq!(request.header("host"), Some("localhost"));
+        assert_eq!(request.bearer_token(), Some("secret"));
+        assert!(request.query_flag("dryRun"));
+        assert!(!request.query_flag("force"));
+        assert!(!request.query_flag("system"));
+        assert_eq!(request.body, b"{}{}");
+    }
+
+    #[fluvio_future::test]
+    async fn reject_invalid_request() {
+        let truncated = b"GET /v1/topics HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}";
+        assert!(HttpRequest::read_from(&mut Cursor::new(truncated.to_vec()))
+            .await
+            .is_err());
+
+        let no_version = b"GET /v1/topics\r\n\r\n";
+        assert!(
+            HttpRequest::read_from(&mut Cursor::new(no_version.to_vec()))
+                .await
+                .is_err()
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn read_head_before_body() {
+        let raw = b"POST /v1/topics HTTP/1.1\r\nContent-Length: 6\r\n\r\n{}";
+        let mut stream = Cursor::new(raw.to_vec());
+        let mut request = HttpRequest::read_head(&mut stream).await.expect("head");
+        assert_eq!(request.body, b"{}");
+
+        let mut rest = Cursor::new(b"{}{}".to_vec());
+        request.read_body(&mut rest).await.expect("body");
+        assert_eq!(request.body, b"{}{}{}");
+    }
+
+    #[fluvio_future::test]
+    async fn timeout_slow_request() {
+        let never = futures_util::future::pending::<Result<(), IoError>>();
+        let err = with_timeout(Duration::from_millis(10), never)
+            .await
+            .expect_err("timeout");
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+    }
+}
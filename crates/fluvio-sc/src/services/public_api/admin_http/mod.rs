@@ -0,0 +1,396 @@
+//!
+//! # Admin HTTP API
+//!
+//! JSON over HTTP front of the admin API, for UIs and infrastructure as code tools.
+//! Requests are authenticated with a bearer token and served by the same handlers
+//! as the binary admin protocol, so authorization and validation are shared. When
+//! the SC runs with TLS, the API is served with the same certificate.
+//!
+//! Besides the admin specs, the stable resources of `fluvio-admin-schema` are
+//! served below `/v1/resources` for infrastructure as code providers, and records
+//! of a topic can be previewed at `/v1/topics/{name}/sample`.
+//!
+//! Connectors are deployed outside of the SC and have no SC objects, so they are
+//! not part of this API.
+//!
+
+mod auth;
+#[cfg(feature = "console")]
+mod console;
+mod http;
+mod resources;
+mod routes;
+mod sample;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures_util::io::{AsyncRead, AsyncWrite};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument};
+
+use fluvio_auth::AuthContext;
+use fluvio_future::net::TcpListener;
+use fluvio_future::task::spawn;
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::{AdminSpec, Status};
+use fluvio_socket::ReloadableTlsAcceptor;
+use fluvio_sc_schema::objects::{
+    CommonCreateRequest, CreateRequest, ListFilters, ListResponse, Metadata,
+};
+use fluvio_sc_schema::smartmodule::SmartModuleSpec;
+use fluvio_sc_schema::spg::SpuGroupSpec;
+use fluvio_sc_schema::topic::TopicSpec;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::SharedContext;
+use crate::services::auth::AuthServiceContext;
+
+use self::http::{with_timeout, HttpRequest, HttpResponse, BODY_TIMEOUT, HEAD_TIMEOUT};
+use self::resources::SCHEMAS_PATH;
+use self::routes::{Action, Resource, Route, RouteMatch, OPENAPI_PATH};
+
+pub use self::auth::{HttpAuthorization, PolicyTokenAuthorization, SharedTokenAuthorization};
+
+/// start admin HTTP server, requests are served with the auth context of their token
+pub fn start_admin_http_server<A, C>(
+    global_ctx: SharedContext<C>,
+    auth: A,
+    addr: String,
+    tls: Option<ReloadableTlsAcceptor>,
+) where
+    A: HttpAuthorization,
+    C: MetadataItem + 'static,
+    C::UId: Send + Sync,
+{
+    info!(%addr, tls = tls.is_some(), "starting admin http server");
+    let server = AdminHttpServer {
+        global_ctx,
+        auth: Arc::new(auth),
+        tls,
+    };
+    spawn(async move {
+        if let Err(err) = server.run(addr).await {
+            error!("admin http server failed: {err:#}");
+        }
+    });
+}
+
+struct AdminHttpServer<A, C: MetadataItem> {
+    global_ctx: SharedContext<C>,
+    auth: Arc<A>,
+    tls: Option<ReloadableTlsAcceptor>,
+}
+
+impl<A, C: MetadataItem> Clone for AdminHttpServer<A, C> {
+    fn clone(&self) -> Self {
+        Self {
+            global_ctx: self.global_ctx.clone(),
+            auth: self.auth.clone(),
+            tls: self.tls.clone(),
+        }
+    }
+}
+
+impl<A, C> AdminHttpServer<A, C>
+where
+    A: HttpAuthorization,
+    C: MetadataItem + 'static,
+    C::UId: Send + Sync,
+{
+    async fn run(self, addr: String) -> Result<()> {
+        let listener = TcpListener::bind(&addr).await?;
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    // e.g. too many open files, the listener itself is still usable
+                    error!("admin http accept failed: {err}");
+                    continue;
+                }
+            };
+            debug!(%peer, "admin http connection");
+            let server = self.clone();
+            spawn(async move {
+                let result = match &server.tls {
+                    Some(acceptor) => match acceptor.current().accept(stream).await {
+                        Ok(tls_stream) => server.serve_connection(tls_stream).await,
+                        Err(err) => Err(anyhow::anyhow!("tls handshake failed: {err}")),
+                    },
+                    None => server.serve_connection(stream).await,
+                };
+                if let Err(err) = result {
+                    debug!(%peer, "admin http connection error: {err}");
+                }
+            });
+        }
+    }
+
+    async fn serve_connection<S>(&self, mut stream: S) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let response = match with_timeout(HEAD_TIMEOUT, HttpRequest::read_head(&mut stream)).await {
+            Ok(request) => self.respond(request, &mut stream).await,
+            Err(err) => read_error(err),
+        };
+        response.write_to(&mut stream).await?;
+        Ok(())
+    }
+
+    /// the body is only read once the request is authenticated
+    #[instrument(skip(self, request, stream), fields(method = %request.method, path = %request.path))]
+    async fn respond<S>(&self, mut request: HttpRequest, stream: &mut S) -> HttpResponse
+    where
+        S: AsyncRead + Unpin,
+    {
+        if request.method == "GET" && request.path == OPENAPI_PATH {
+            let mut document = routes::openapi_document(crate::VERSION.trim());
+            resources::extend_openapi(&mut document);
+            sample::extend_openapi(&mut document);
+            return HttpResponse::json(200, &document);
+        }
+        #[cfg(feature = "console")]
+        if request.method == "GET" {
+            if let Some(response) = console::asset(&request.path) {
+                return response;
+            }
+        }
+
+        let Some(auth_ctx) = request
+            .bearer_token()
+            .and_then(|token| self.auth.authorize(token))
+        else {
+            return HttpResponse::error(401, "Unauthorized", "missing or invalid bearer token");
+        };
+        if let Err(err) = with_timeout(BODY_TIMEOUT, request.read_body(stream)).await {
+            return read_error(err);
+        }
+        let ctx = AuthServiceContext::new(self.global_ctx.clone(), auth_ctx);
+
+        if request.method == "GET" && request.path == SCHEMAS_PATH {
+            return HttpResponse::json(200, &fluvio_admin_schema::schemas_document());
+        }
+        let sample_topic = match request.method.as_str() {
+            "GET" => sample::sample_topic(&request.path),
+            _ => None,
+        };
+        // changes wait for a running batch, like those of the admin API
+        let _guard = match request.method.as_str() {
+            "GET" => None,
+            _ => Some(super::batch::admin_guard().await),
+        };
+        let result = if let Some(topic) = sample_topic {
+            sample::dispatch_sample(&ctx, topic, &request).await
+        } else if let Some(route) = resources::find_resource_route(&request.method, &request.path) {
+            let (kind, action, name) = match route {
+                Ok(route) => route,
+                Err(response) => return response,
+            };
+            resources::dispatch_resource(&ctx, kind, action, name, &request).await
+        } else {
+            let (route, name) = match routes::find_route(&request.method, &request.path) {
+                RouteMatch::Found(route, name) => (route, name),
+                RouteMatch::MethodNotAllowed => {
+                    return HttpResponse::error(405, "MethodNotAllowed", "method not allowed")
+                }
+                RouteMatch::NotFound => {
+                    return HttpResponse::error(404, "NotFound", "no such route")
+                }
+            };
+            dispatch(&ctx, route, name, &request).await
+        };
+        match result {
+            Ok(response) => response,
+            Err(err) => HttpResponse::error(500, "InternalError", &format!("{err:#}")),
+        }
+    }
+}
+
+fn read_error(err: std::io::Error) -> HttpResponse {
+    match err.kind() {
+        std::io::ErrorKind::TimedOut => {
+            HttpResponse::error(408, "RequestTimeout", &err.to_string())
+        }
+        _ => HttpResponse::error(400, "InvalidRequest", &err.to_string()),
+    }
+}
+
+async fn dispatch<AC, C>(
+    ctx: &AuthServiceContext<AC, C>,
+    route: Route,
+    name: Option<String>,
+    request: &HttpRequest,
+) -> Result<HttpResponse>
+where
+    AC: AuthContext,
+    C: MetadataItem,
+{
+    use super::{partition, smartmodule, spg, spu, topic};
+
+    let filters = || -> ListFilters {
+        match name.as_deref().or(request.query_param("name")) {
+            Some(name) => name.into(),
+            None => ListFilters::default(),
+        }
+    };
+    let system = request.query_flag("system");
+
+    let response = match (route.resource, route.action) {
+        (Resource::Topic, Action::List | Action::Get) => list_or_get(
+            route.action,
+            topic::handle_fetch_topics_request(filters(), system, ctx).await?,
+        ),
+        (Resource::Partition, _) => list_or_get(
+            route.action,
+            partition::handle_fetch_request(filters(), system, ctx).await?,
+        ),
+        (Resource::Spu, _) => list_or_get(
+            route.action,
+            spu::handle_fetch_spus_request(filters(), ctx).await?,
+        ),
+        (Resource::SpuGroup, Action::List | Action::Get) => list_or_get(
+            route.action,
+            spg::handle_fetch_spu_groups_request(filters(), ctx).await?,
+        ),
+        (Resource::SmartModule, Action::List | Action::Get) => list_or_get(
+            route.action,
+            smartmodule::fetch_smart_modules(
+                filters().into(),
+                request.query_flag("summary"),
+                &ctx.auth,
+                ctx.global_ctx.smartmodules(),
+            )
+            .await?,
+        ),
+        (Resource::Topic, Action::Create) => match create_request::<TopicSpec>(request) {
+            Ok(req) => created(topic::handle_create_topics_request(req, ctx).await?),
+            Err(response) => response,
+        },
+        (Resource::SpuGroup, Action::Create) => match create_request::<SpuGroupSpec>(request) {
+            Ok(req) => created(spg::handle_create_spu_group_request(req, ctx).await?),
+            Err(response) => response,
+        },
+        (Resource::SmartModule, Action::Create) => {
+            match create_request::<SmartModuleSpec>(request) {
+                Ok(req) => created(smartmodule::handle_create_smartmodule_request(req, ctx).await?),
+                Err(response) => response,
+            }
+        }
+        (Resource::Topic, Action::Delete) => deleted(
+            topic::handle_delete_topic(
+                name.clone().unwrap_or_default(),
+                request.query_flag("force"),
+                ctx,
+            )
+            .await?,
+        ),
+        (Resource::SpuGroup, Action::Delete) => {
+            deleted(spg::handle_delete_spu_group(name.clone().unwrap_or_default(), ctx).await?)
+        }
+        (Resource::SmartModule, Action::Delete) => deleted(
+            smartmodule::handle_delete_smartmodule(name.clone().unwrap_or_default(), ctx).await?,
+        ),
+    };
+    Ok(response)
+}
+
+fn list_or_get<S>(action: Action, list: ListResponse<S>) -> HttpResponse
+where
+    S: AdminSpec,
+    S::Status: Encoder + Decoder + Debug,
+    Metadata<S>: Serialize,
+{
+    let mut objects = list.inner();
+    match action {
+        Action::Get => match objects.pop() {
+            Some(object) => HttpResponse::json(200, &object),
+            None => HttpResponse::error(404, "NotFound", "object not found"),
+        },
+        _ => HttpResponse::json(200, &objects),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "S: DeserializeOwned"))]
+struct CreateBody<S> {
+    name: String,
+    spec: S,
+}
+
+fn create_request<S: DeserializeOwned>(
+    request: &HttpRequest,
+) -> Result<CreateRequest<S>, HttpResponse> {
+    let body: CreateBody<S> = serde_json::from_slice(&request.body).map_err(|err| {
+        HttpResponse::error(400, "InvalidCreateRequest", &format!("invalid body: {err}"))
+    })?;
+    let common = CommonCreateRequest {
+        name: body.name,
+        dry_run: request.query_flag("dryRun"),
+        ..Default::default()
+    };
+    Ok(CreateRequest::new(common, body.spec))
+}
+
+#[derive(Serialize)]
+struct StatusBody<'a> {
+    name: &'a str,
+}
+
+fn created(status: Status) -> HttpResponse {
+    status_response(201, status)
+}
+
+fn deleted(status: Status) -> HttpResponse {
+    status_response(200, status)
+}
+
+fn status_response(ok: u16, status: Status) -> HttpResponse {
+    if status.error_code.is_ok() {
+        return HttpResponse::json(ok, &StatusBody { name: &status.name });
+    }
+    let http_status = match &status.error_code {
+        ErrorCode::PermissionDenied => 403,
+        ErrorCode::TopicNotFound
+        | ErrorCode::SpuNotFound
+        | ErrorCode::SmartModuleNotFound { .. } => 404,
+        ErrorCode::TopicAlreadyExists | ErrorCode::SpuAlreadyExists => 409,
+        _ => 400,
+    };
+    let message = status
+        .error_message
+        .clone()
+        .unwrap_or_else(|| status.error_code.to_string());
+    HttpResponse::error(http_status, &error_code_name(&status.error_code), &message)
+}
+
+/// the variant name, without the fields of struct variants
+fn error_code_name(error_code: &ErrorCode) -> String {
+    let code = format!("{error_code:?}");
+    code.split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_codes() {
+        let ok = status_response(201, Status::new_ok("t1".to_owned()));
+        assert_eq!(ok.status, 201);
+
+        let exists = status_response(
+            201,
+            Status::new("t1".to_owned(), ErrorCode::TopicAlreadyExists, None),
+        );
+        assert_eq!(exists.status, 409);
+        let body: serde_json::Value = serde_json::from_slice(&exists.body).unwrap();
+        assert_eq!(body["code"], "TopicAlreadyExists");
+    }
+}
@@ -0,0 +1,299 @@
+//!
+//! # Stable Resources
+//!
+//! Routes serving the flat resources of `fluvio-admin-schema`, which back
+//! infrastructure as code providers. Objects that can't be represented as a
+//! resource are left out of listings.
+//!
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use fluvio_admin_schema::{AdminResource, ResourceError, SpuGroupResource, TopicResource};
+use fluvio_auth::AuthContext;
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_sc_schema::AdminSpec;
+use fluvio_sc_schema::objects::{CommonCreateRequest, CreateRequest, ListFilters, Metadata};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+
+use crate::services::auth::AuthServiceContext;
+
+use super::http::{HttpRequest, HttpResponse};
+use super::routes::{Action, API_PREFIX};
+
+pub const SCHEMAS_PATH: &str = "/v1/schemas";
+const RESOURCES_SEGMENT: &str = "resources";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    Topic,
+    SpuGroup,
+}
+
+impl ResourceKind {
+    const ALL: [Self; 2] = [Self::Topic, Self::SpuGroup];
+
+    fn collection(&self) -> &'static str {
+        match self {
+            Self::Topic => TopicResource::schema().collection,
+            Self::SpuGroup => SpuGroupResource::schema().collection,
+        }
+    }
+
+    fn schema_name(&self) -> &'static str {
+        match self {
+            Self::Topic => "TopicResource",
+            Self::SpuGroup => "SpuGroupResource",
+        }
+    }
+
+    fn json_schema(&self) -> Value {
+        match self {
+            Self::Topic => TopicResource::schema().json_schema(),
+            Self::SpuGroup => SpuGroupResource::schema().json_schema(),
+        }
+    }
+}
+
+/// match paths below `/v1/resources`, returns None for other paths
+pub fn find_resource_route(
+    method: &str,
+    path: &str,
+) -> Option<Result<(ResourceKind, Action, Option<String>), HttpResponse>> {
+    let rest = path
+        .strip_prefix(API_PREFIX)?
+        .trim_matches('/')
+        .strip_prefix(RESOURCES_SEGMENT)?;
+    let mut segments = rest.trim_start_matches('/').splitn(2, '/');
+    let collection = segments.next().unwrap_or_default();
+    let name = segments.next().filter(|name| !name.is_empty());
+
+    let Some(kind) = ResourceKind::ALL
+        .into_iter()
+        .find(|kind| kind.collection() == collection)
+    else {
+        return Some(Err(HttpResponse::error(
+            404,
+            "NotFound",
+            "no such resource kind",
+        )));
+    };
+    let action = match (method, &name) {
+        ("GET", None) => Action::List,
+        ("POST", None) => Action::Create,
+        ("GET", Some(_)) => Action::Get,
+        ("DELETE", Some(_)) => Action::Delete,
+        _ => {
+            return Some(Err(HttpResponse::error(
+                405,
+                "MethodNotAllowed",
+                "method not allowed",
+            )))
+        }
+    };
+    Some(Ok((kind, action, name.map(str::to_owned))))
+}
+
+pub async fn dispatch_resource<AC, C>(
+    ctx: &AuthServiceContext<AC, C>,
+    kind: ResourceKind,
+    action: Action,
+    name: Option<String>,
+    request: &HttpRequest,
+) -> Result<HttpResponse>
+where
+    AC: AuthContext,
+    C: MetadataItem,
+{
+    use super::{spg, topic};
+
+    let filters = || -> ListFilters {
+        match &name {
+            Some(name) => name.as_str().into(),
+            None => ListFilters::default(),
+        }
+    };
+    let object_name = || name.clone().unwrap_or_default();
+
+    let response = match (kind, action) {
+        (ResourceKind::Topic, Action::List | Action::Get) => to_resources::<TopicResource>(
+            action,
+            topic::handle_fetch_topics_request(filters(), false, ctx)
+                .await?
+                .inner(),
+        ),
+        (ResourceKind::SpuGroup, Action::List | Action::Get) => to_resources::<SpuGroupResource>(
+            action,
+            spg::handle_fetch_spu_groups_request(filters(), ctx)
+                .await?
+                .inner(),
+        ),
+        (ResourceKind::Topic, Action::Create) => match create_request::<TopicResource>(request) {
+            Ok(req) => super::created(topic::handle_create_topics_request(req, ctx).await?),
+            Err(response) => response,
+        },
+        (ResourceKind::SpuGroup, Action::Create) => {
+            match create_request::<SpuGroupResource>(request) {
+                Ok(req) => super::created(spg::handle_create_spu_group_request(req, ctx).await?),
+                Err(response) => response,
+            }
+        }
+        (ResourceKind::Topic, Action::Delete) => {
+            super::deleted(topic::handle_delete_topic(object_name(), false, ctx).await?)
+        }
+        (ResourceKind::SpuGroup, Action::Delete) => {
+            super::deleted(spg::handle_delete_spu_group(object_name(), ctx).await?)
+        }
+    };
+    Ok(response)
+}
+
+fn to_resources<R>(action: Action, objects: Vec<Metadata<R::Spec>>) -> HttpResponse
+where
+    R: AdminResource,
+    R::Spec: AdminSpec,
+    <R::Spec as Spec>::Status: Encoder + Decoder,
+{
+    let mut resources = vec![];
+    for object in &objects {
+        match R::from_spec(&object.name, &object.spec) {
+            Ok(resource) => resources.push(resource),
+            Err(err) if action == Action::Get => return resource_error(err),
+            Err(err) => debug!(%err, "skipping object"),
+        }
+    }
+    match action {
+        Action::Get => match resources.pop() {
+            Some(resource) => HttpResponse::json(200, &resource),
+            None => HttpResponse::error(404, "NotFound", "resource not found"),
+        },
+        _ => HttpResponse::json(200, &resources),
+    }
+}
+
+fn create_request<R: AdminResource>(
+    request: &HttpRequest,
+) -> Result<CreateRequest<R::Spec>, HttpResponse> {
+    let resource: R = serde_json::from_slice(&request.body).map_err(|err| {
+        HttpResponse::error(400, "InvalidResource", &format!("invalid resource: {err}"))
+    })?;
+    let (name, spec) = resource.into_spec().map_err(resource_error)?;
+    let common = CommonCreateRequest {
+        name,
+        dry_run: request.query_flag("dryRun"),
+        ..Default::default()
+    };
+    Ok(CreateRequest::new(common, spec))
+}
+
+fn resource_error(err: ResourceError) -> HttpResponse {
+    let code = match err {
+        ResourceError::InvalidAttribute { .. } => "InvalidResource",
+        ResourceError::Unsupported { .. } => "UnsupportedResource",
+    };
+    HttpResponse::error(400, code, &err.to_string())
+}
+
+/// add resource routes and schemas to the OpenAPI document
+pub fn extend_openapi(document: &mut Value) {
+    document["paths"][SCHEMAS_PATH] = json!({
+        "get": {
+            "operationId": "getSchemas",
+            "summary": "Schemas of the resources",
+            "tags": [RESOURCES_SEGMENT],
+            "responses": { "200": { "description": "versioned resource schemas" } }
+        }
+    });
+    for kind in ResourceKind::ALL {
+        let collection = kind.collection();
+        let schema_name = kind.schema_name();
+        let resource_ref = json!({ "$ref": format!("#/components/schemas/{schema_name}") });
+        let status_ref = json!({ "$ref": "#/components/schemas/Status" });
+        let name_parameter = json!([{
+            "name": "name", "in": "path", "required": true, "schema": { "type": "string" }
+        }]);
+        let content = |schema: Value| json!({ "application/json": { "schema": schema } });
+
+        document["paths"][format!("{API_PREFIX}/{RESOURCES_SEGMENT}/{collection}")] = json!({
+            "get": {
+                "operationId": format!("list{schema_name}"),
+                "tags": [RESOURCES_SEGMENT],
+                "responses": { "200": {
+                    "description": "resources",
+                    "content": content(json!({ "type": "array", "items": resource_ref }))
+                } }
+            },
+            "post": {
+                "operationId": format!("create{schema_name}"),
+                "tags": [RESOURCES_SEGMENT],
+                "requestBody": { "required": true, "content": content(resource_ref.clone()) },
+                "responses": { "201": { "description": "created", "content": content(status_ref.clone()) } }
+            }
+        });
+        document["paths"][format!("{API_PREFIX}/{RESOURCES_SEGMENT}/{collection}/{{name}}")] = json!({
+            "get": {
+                "operationId": format!("get{schema_name}"),
+                "tags": [RESOURCES_SEGMENT],
+                "parameters": name_parameter,
+                "responses": { "200": { "description": "resource", "content": content(resource_ref) } }
+            },
+            "delete": {
+                "operationId": format!("delete{schema_name}"),
+                "tags": [RESOURCES_SEGMENT],
+                "parameters": name_parameter,
+                "responses": { "200": { "description": "deleted", "content": content(status_ref) } }
+            }
+        });
+        if let Some(schemas) = document["components"]["schemas"].as_object_mut() {
+            schemas.insert(schema_name.to_owned(), kind.json_schema());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::routes::openapi_document;
+
+    #[test]
+    fn resource_routes() {
+        let Some(Ok((kind, action, name))) = find_resource_route("GET", "/v1/resources/topics/t1")
+        else {
+            panic!("route not found");
+        };
+        assert_eq!(kind, ResourceKind::Topic);
+        assert_eq!(action, Action::Get);
+        assert_eq!(name.as_deref(), Some("t1"));
+
+        assert!(matches!(
+            find_resource_route("POST", "/v1/resources/spu-groups"),
+            Some(Ok((ResourceKind::SpuGroup, Action::Create, None)))
+        ));
+        assert!(matches!(
+            find_resource_route("PUT", "/v1/resources/topics/t1"),
+            Some(Err(HttpResponse { status: 405, .. }))
+        ));
+        assert!(matches!(
+            find_resource_route("GET", "/v1/resources/acls"),
+            Some(Err(HttpResponse { status: 404, .. }))
+        ));
+        assert!(find_resource_route("GET", "/v1/topics").is_none());
+    }
+
+    #[test]
+    fn resources_in_openapi() {
+        let mut document = openapi_document("0.0.0");
+        extend_openapi(&mut document);
+        assert_eq!(
+            document["paths"]["/v1/resources/topics/{name}"]["delete"]["operationId"],
+            "deleteTopicResource"
+        );
+        assert_eq!(
+            document["components"]["schemas"]["TopicResource"]["properties"]["compression_type"]
+                ["enum"][0],
+            "none"
+        );
+        assert!(document["paths"][SCHEMAS_PATH]["get"].is_object());
+    }
+}
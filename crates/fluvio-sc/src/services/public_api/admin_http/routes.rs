@@ -0,0 +1,385 @@
+//!
+//! # Admin HTTP Routes
+//!
+//! Routes are kept in a static table so the OpenAPI document is generated from
+//! the same source used for dispatching requests.
+//!
+
+use serde_json::{json, Map, Value};
+
+pub const API_PREFIX: &str = "/v1";
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Topic,
+    Partition,
+    Spu,
+    SpuGroup,
+    SmartModule,
+}
+
+impl Resource {
+    fn collection(&self) -> &'static str {
+        match self {
+            Self::Topic => "topics",
+            Self::Partition => "partitions",
+            Self::Spu => "spus",
+            Self::SpuGroup => "spu-groups",
+            Self::SmartModule => "smartmodules",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Topic => "Topic",
+            Self::Partition => "Partition",
+            Self::Spu => "Spu",
+            Self::SpuGroup => "SpuGroup",
+            Self::SmartModule => "SmartModule",
+        }
+    }
+
+    /// query parameters accepted by the list operation, besides `name`
+    fn list_flags(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Topic | Self::Partition => &[("system", "list system objects instead")],
+            Self::SmartModule => &[("summary", "omit the wasm binary")],
+            Self::Spu | Self::SpuGroup => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    List,
+    Get,
+    Create,
+    Delete,
+}
+
+impl Action {
+    fn method(&self) -> &'static str {
+        match self {
+            Self::List | Self::Get => "GET",
+            Self::Create => "POST",
+            Self::Delete => "DELETE",
+        }
+    }
+
+    fn has_name(&self) -> bool {
+        matches!(self, Self::Get | Self::Delete)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Route {
+    pub resource: Resource,
+    pub action: Action,
+}
+
+impl Route {
+    const fn new(resource: Resource, action: Action) -> Self {
+        Self { resource, action }
+    }
+
+    fn path(&self) -> String {
+        if self.action.has_name() {
+            format!("{API_PREFIX}/{}/{{name}}", self.resource.collection())
+        } else {
+            format!("{API_PREFIX}/{}", self.resource.collection())
+        }
+    }
+
+    fn operation_id(&self) -> String {
+        let verb = match self.action {
+            Action::List => "list",
+            Action::Get => "get",
+            Action::Create => "create",
+            Action::Delete => "delete",
+        };
+        format!("{verb}{}", self.resource.label())
+    }
+}
+
+/// every route served by the admin HTTP API
+pub const ROUTES: &[Route] = &[
+    Route::new(Resource::Topic, Action::List),
+    Route::new(Resource::Topic, Action::Get),
+    Route::new(Resource::Topic, Action::Create),
+    Route::new(Resource::Topic, Action::Delete),
+    Route::new(Resource::Partition, Action::List),
+    Route::new(Resource::Spu, Action::List),
+    Route::new(Resource::Spu, Action::Get),
+    Route::new(Resource::SpuGroup, Action::List),
+    Route::new(Resource::SpuGroup, Action::Get),
+    Route::new(Resource::SpuGroup, Action::Create),
+    Route::new(Resource::SpuGroup, Action::Delete),
+    Route::new(Resource::SmartModule, Action::List),
+    Route::new(Resource::SmartModule, Action::Get),
+    Route::new(Resource::SmartModule, Action::Create),
+    Route::new(Resource::SmartModule, Action::Delete),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RouteMatch {
+    Found(Route, Option<String>),
+    MethodNotAllowed,
+    NotFound,
+}
+
+/// find route serving method and path, with the name of the object when part of the path
+pub fn find_route(method: &str, path: &str) -> RouteMatch {
+    let Some(rest) = path.strip_prefix(API_PREFIX) else {
+        return RouteMatch::NotFound;
+    };
+    let mut segments = rest.trim_matches('/').splitn(2, '/');
+    let collection = segments.next().unwrap_or_default();
+    let name = segments.next().filter(|name| !name.is_empty());
+    if name.is_some_and(|name| name.contains('/')) {
+        return RouteMatch::NotFound;
+    }
+
+    let mut path_found = false;
+    for route in ROUTES {
+        if route.resource.collection() != collection || route.action.has_name() != name.is_some() {
+            continue;
+        }
+        path_found = true;
+        if route.action.method() == method {
+            return RouteMatch::Found(*route, name.map(str::to_owned));
+        }
+    }
+    if path_found {
+        RouteMatch::MethodNotAllowed
+    } else {
+        RouteMatch::NotFound
+    }
+}
+
+fn error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } }
+    })
+}
+
+fn operation(route: &Route) -> Value {
+    let label = route.resource.label();
+    let object_ref = json!({ "$ref": format!("#/components/schemas/{label}") });
+    let mut parameters = vec![];
+    if route.action.has_name() {
+        parameters.push(json!({
+            "name": "name", "in": "path", "required": true, "schema": { "type": "string" }
+        }));
+    }
+
+    let mut responses = Map::new();
+    let summary;
+    let mut request_body = None;
+    match route.action {
+        Action::List => {
+            summary = format!("List {}", route.resource.collection());
+            parameters.push(json!({
+                "name": "name", "in": "query", "required": false,
+                "description": "only list objects with this name",
+                "schema": { "type": "string" }
+            }));
+            for (flag, description) in route.resource.list_flags() {
+                parameters.push(json!({
+                    "name": flag, "in": "query", "required": false,
+                    "description": description, "schema": { "type": "boolean" }
+                }));
+            }
+            responses.insert(
+                "200".into(),
+                json!({
+                    "description": format!("{label} objects"),
+                    "content": { "application/json": { "schema": { "type": "array", "items": object_ref } } }
+                }),
+            );
+        }
+        Action::Get => {
+            summary = format!("Get {label}");
+            responses.insert(
+                "200".into(),
+                json!({
+                    "description": format!("{label} object"),
+                    "content": { "application/json": { "schema": object_ref } }
+                }),
+            );
+            responses.insert("404".into(), error_response("not found"));
+        }
+        Action::Create => {
+            summary = format!("Create {label}");
+            parameters.push(json!({
+                "name": "dryRun", "in": "query", "required": false,
+                "description": "validate without creating", "schema": { "type": "boolean" }
+            }));
+            request_body = Some(json!({
+                "required": true,
+                "content": { "application/json": { "schema": {
+                    "type": "object",
+                    "required": ["name", "spec"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "spec": { "$ref": format!("#/components/schemas/{label}Spec") }
+                    }
+                } } }
+            }));
+            responses.insert(
+                "201".into(),
+                json!({
+                    "description": "created",
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Status" } } }
+                }),
+            );
+            responses.insert("409".into(), error_response("already exists"));
+        }
+        Action::Delete => {
+            summary = format!("Delete {label}");
+            if route.resource == Resource::Topic {
+                parameters.push(json!({
+                    "name": "force", "in": "query", "required": false,
+                    "description": "delete system topics too", "schema": { "type": "boolean" }
+                }));
+            }
+            responses.insert(
+                "200".into(),
+                json!({
+                    "description": "deleted",
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Status" } } }
+                }),
+            );
+            responses.insert("404".into(), error_response("not found"));
+        }
+    }
+    responses.insert("400".into(), error_response("invalid request"));
+    responses.insert("401".into(), error_response("missing or invalid token"));
+    responses.insert("403".into(), error_response("not authorized"));
+
+    let mut operation = json!({
+        "operationId": route.operation_id(),
+        "summary": summary,
+        "tags": [route.resource.collection()],
+        "parameters": parameters,
+        "responses": responses,
+    });
+    if let Some(request_body) = request_body {
+        operation["requestBody"] = request_body;
+    }
+    operation
+}
+
+fn schemas() -> Map<String, Value> {
+    let mut schemas = Map::new();
+    schemas.insert(
+        "Error".into(),
+        json!({
+            "type": "object",
+            "required": ["code", "message"],
+            "properties": { "code": { "type": "string" }, "message": { "type": "string" } }
+        }),
+    );
+    schemas.insert(
+        "Status".into(),
+        json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": { "name": { "type": "string" } }
+        }),
+    );
+    let mut labels: Vec<&str> = ROUTES.iter().map(|route| route.resource.label()).collect();
+    labels.dedup();
+    for label in labels {
+        schemas.insert(
+            format!("{label}Spec"),
+            json!({
+                "type": "object",
+                "description": format!("{label} spec, same fields as the admin protocol in camelCase")
+            }),
+        );
+        schemas.insert(
+            label.into(),
+            json!({
+                "type": "object",
+                "required": ["name", "spec", "status"],
+                "properties": {
+                    "name": { "type": "string" },
+                    "spec": { "$ref": format!("#/components/schemas/{label}Spec") },
+                    "status": { "type": "object" }
+                }
+            }),
+        );
+    }
+    schemas
+}
+
+/// OpenAPI 3 document describing the routes
+pub fn openapi_document(version: &str) -> Value {
+    let mut paths = Map::new();
+    for route in ROUTES {
+        let path = paths
+            .entry(route.path())
+            .or_insert_with(|| Value::Object(Map::new()));
+        path[route.action.method().to_lowercase()] = operation(route);
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Fluvio SC Admin API",
+            "version": version,
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas(),
+            "securitySchemes": { "bearer": { "type": "http", "scheme": "bearer" } }
+        },
+        "security": [{ "bearer": [] }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_matching() {
+        assert_eq!(
+            find_route("GET", "/v1/topics"),
+            RouteMatch::Found(Route::new(Resource::Topic, Action::List), None)
+        );
+        assert_eq!(
+            find_route("DELETE", "/v1/smartmodules/jolt"),
+            RouteMatch::Found(
+                Route::new(Resource::SmartModule, Action::Delete),
+                Some("jolt".to_owned())
+            )
+        );
+        assert_eq!(
+            find_route("POST", "/v1/partitions"),
+            RouteMatch::MethodNotAllowed
+        );
+        assert_eq!(
+            find_route("GET", "/v1/partitions/p-0"),
+            RouteMatch::NotFound
+        );
+        assert_eq!(find_route("GET", "/v1/topics/a/b"), RouteMatch::NotFound);
+        assert_eq!(find_route("GET", "/v2/topics"), RouteMatch::NotFound);
+    }
+
+    #[test]
+    fn openapi_covers_routes() {
+        let document = openapi_document("0.0.0");
+        for route in ROUTES {
+            let operation = &document["paths"][route.path()][route.action.method().to_lowercase()];
+            assert_eq!(operation["operationId"], route.operation_id());
+        }
+        assert!(document["components"]["schemas"]["TopicSpec"].is_object());
+        assert_eq!(
+            document["paths"]["/v1/topics/{name}"]["delete"]["parameters"][1]["name"],
+            "force"
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//!
+//! # Topic Sampling
+//!
+//! `GET /v1/topics/{name}/sample` forwards a sample request to the leader SPU of
+//! the partition, so the console can preview a topic without a consumer. The SC
+//! connects to the public endpoint of the SPU without client certificate, so the
+//! token must grant `Consume` on the topic before the request is forwarded.
+//!
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_controlplane_metadata::acl::AclPermission;
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::topic::TopicSpec;
+use fluvio_socket::FluvioSocket;
+use fluvio_spu_schema::server::sample::{
+    SampleRecordsRequest, SampleRecordsResponse, SampleStrategy, SampledRecord,
+};
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::PartitionId;
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::spu::SpuLocalStorePolicy;
+
+use super::error_code_name;
+use super::http::{HttpRequest, HttpResponse};
+use super::routes::API_PREFIX;
+
+const DEFAULT_SAMPLE_COUNT: u32 = 10;
+
+/// topic of `/v1/topics/{name}/sample`
+pub fn sample_topic(path: &str) -> Option<&str> {
+    let topic = path
+        .strip_prefix(API_PREFIX)?
+        .strip_prefix("/topics/")?
+        .strip_suffix("/sample")?;
+    (!topic.is_empty() && !topic.contains('/')).then_some(topic)
+}
+
+pub async fn dispatch_sample<AC, C>(
+    ctx: &AuthServiceContext<AC, C>,
+    topic: &str,
+    request: &HttpRequest,
+) -> Result<HttpResponse>
+where
+    AC: AuthContext,
+    C: MetadataItem,
+{
+    let query = match SampleQuery::parse(request) {
+        Ok(query) => query,
+        Err(message) => return Ok(HttpResponse::error(400, "InvalidSampleRequest", &message)),
+    };
+
+    if !ctx
+        .auth
+        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Read)
+        .await?
+        || !ctx
+            .auth
+            .allow_topic_action(topic, AclPermission::Consume)
+            .await?
+    {
+        return Ok(HttpResponse::error(
+            403,
+            "PermissionDenied",
+            "permission denied",
+        ));
+    }
+
+    let replica = ReplicaKey::new(topic, query.partition);
+    let Some(partition) = ctx.global_ctx.partitions().store().value(&replica).await else {
+        return Ok(HttpResponse::error(
+            404,
+            "PartitionNotFound",
+            &format!("partition {replica} not found"),
+        ));
+    };
+    let leader = partition.inner().spec.leader;
+    let Some(spu) = ctx.global_ctx.spus().store().get_by_id(leader).await else {
+        return Ok(HttpResponse::error(
+            503,
+            "SpuNotFound",
+            &format!("leader spu {leader} not found"),
+        ));
+    };
+    let addr = match &spu.spec.public_endpoint_local {
+        Some(endpoint) => endpoint.to_string(),
+        None => spu.spec.public_endpoint.addr(),
+    };
+
+    debug!(%replica, %addr, "sampling records from leader");
+    let sample_request = SampleRecordsRequest::new(
+        replica.topic.clone(),
+        replica.partition,
+        query.count,
+        query.strategy,
+    );
+    let response = match send_sample_request(&addr, sample_request).await {
+        Ok(response) => response,
+        Err(err) => {
+            return Ok(HttpResponse::error(
+                502,
+                "SpuUnreachable",
+                &format!("sampling from spu {leader} at {addr} failed: {err}"),
+            ))
+        }
+    };
+    if response.error_code != ErrorCode::None {
+        return Ok(HttpResponse::error(
+            502,
+            &error_code_name(&response.error_code),
+            &response.error_code.to_string(),
+        ));
+    }
+
+    let records: Vec<SampleRecordBody> = response
+        .records
+        .into_iter()
+        .map(SampleRecordBody::from)
+        .collect();
+    Ok(HttpResponse::json(
+        200,
+        &SampleBody {
+            topic: &replica.topic,
+            partition: replica.partition,
+            start_offset: response.start_offset,
+            hw: response.hw,
+            records,
+        },
+    ))
+}
+
+async fn send_sample_request(
+    addr: &str,
+    request: SampleRecordsRequest,
+) -> Result<SampleRecordsResponse> {
+    let mut socket = FluvioSocket::connect(addr).await?;
+    let response = socket.send(&RequestMessage::new_request(request)).await?;
+    Ok(response.response)
+}
+
+struct SampleQuery {
+    partition: PartitionId,
+    count: u32,
+    strategy: SampleStrategy,
+}
+
+impl SampleQuery {
+    fn parse(request: &HttpRequest) -> Result<Self, String> {
+        let partition = match request.query_param("partition") {
+            Some(partition) => partition
+                .parse()
+                .map_err(|_| format!("invalid partition: {partition}"))?,
+            None => 0,
+        };
+        let count = match request.query_param("count") {
+            Some(count) => count
+                .parse()
+                .map_err(|_| format!("invalid count: {count}"))?,
+            None => DEFAULT_SAMPLE_COUNT,
+        };
+        let strategy = match request.query_param("strategy").unwrap_or("tail") {
+            "head" => SampleStrategy::Head,
+            "tail" => SampleStrategy::Tail,
+            "random" => SampleStrategy::Random,
+            other => return Err(format!("invalid strategy: {other}")),
+        };
+        Ok(Self {
+            partition,
+            count,
+            strategy,
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SampleBody<'a> {
+    topic: &'a str,
+    partition: PartitionId,
+    start_offset: i64,
+    hw: i64,
+    records: Vec<SampleRecordBody>,
+}
+
+/// sampled record, key and value are decoded as lossy UTF-8
+#[derive(Serialize)]
+struct SampleRecordBody {
+    offset: i64,
+    timestamp: i64,
+    key: Option<String>,
+    value: String,
+}
+
+impl From<SampledRecord> for SampleRecordBody {
+    fn from(record: SampledRecord) -> Self {
+        Self {
+            offset: record.offset,
+            timestamp: record.timestamp,
+            key: record
+                .key
+                .map(|key| key.as_utf8_lossy_string().into_owned()),
+            value: record.value.as_utf8_lossy_string().into_owned(),
+        }
+    }
+}
+
+/// OpenAPI operation of the sample route
+pub fn extend_openapi(document: &mut Value) {
+    let operation = json!({
+        "get": {
+            "operationId": "sampleTopic",
+            "summary": "Sample records of a topic partition",
+            "tags": ["topics"],
+            "parameters": [
+                { "name": "name", "in": "path", "required": true, "schema": { "type": "string" } },
+                { "name": "partition", "in": "query", "schema": { "type": "integer", "default": 0 } },
+                { "name": "count", "in": "query", "schema": { "type": "integer", "default": DEFAULT_SAMPLE_COUNT } },
+                { "name": "strategy", "in": "query", "schema": { "type": "string", "enum": ["head", "tail", "random"], "default": "tail" } },
+            ],
+            "responses": {
+                "200": { "description": "sampled records" },
+                "404": { "description": "partition not found" },
+                "502": { "description": "leader SPU failed to sample" },
+            },
+        }
+    });
+    document["paths"][format!("{API_PREFIX}/topics/{{name}}/sample")] = operation;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use fluvio_auth::x509::X509Identity;
+    use fluvio_controlplane_metadata::acl::{AclResource, AclSpec};
+    use fluvio_stream_model::fixture::TestMeta;
+    use fluvio_stream_model::store::{LocalStore, MetadataStoreObject};
+
+    use crate::config::ScConfig;
+    use crate::core::Context;
+    use crate::services::auth::acl::AclAuthorization;
+    use crate::stores::StoreContext;
+
+    use super::*;
+
+    #[test]
+    fn sample_paths() {
+        assert_eq!(sample_topic("/v1/topics/t1/sample"), Some("t1"));
+        assert_eq!(sample_topic("/v1/topics//sample"), None);
+        assert_eq!(sample_topic("/v1/topics/t1"), None);
+        assert_eq!(sample_topic("/v1/spus/t1/sample"), None);
+
+        let mut request = HttpRequest::default();
+        request.query = Some("partition=2&strategy=random".to_owned());
+        let query = SampleQuery::parse(&request).expect("query");
+        assert_eq!(query.partition, 2);
+        assert_eq!(query.count, DEFAULT_SAMPLE_COUNT);
+        assert_eq!(query.strategy, SampleStrategy::Random);
+
+        request.query = Some("strategy=middle".to_owned());
+        assert!(SampleQuery::parse(&request).is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn sample_requires_consume() {
+        let acls = LocalStore::<AclSpec, TestMeta>::default();
+        let _ = acls
+            .sync_all(vec![MetadataStoreObject::with_spec(
+                "orders-produce",
+                AclSpec::new(
+                    "app",
+                    AclResource::topic("orders"),
+                    vec![AclPermission::Produce],
+                ),
+            )])
+            .await;
+        let authorization =
+            AclAuthorization::new(StoreContext::new_with_store(Arc::new(acls)), HashSet::new());
+        let ctx = AuthServiceContext::new(
+            Context::<TestMeta>::shared_metadata(ScConfig::default()),
+            authorization.auth_context(X509Identity::new("app".to_owned(), vec![])),
+        );
+
+        let response = dispatch_sample(&ctx, "orders", &HttpRequest::default())
+            .await
+            .expect("response");
+        assert_eq!(response.status, 403);
+    }
+}
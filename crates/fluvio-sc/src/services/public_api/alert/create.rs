@@ -0,0 +1,132 @@
+//!
+//! # Create AlertRule Request
+//!
+//! Validates the rule expression before storing the AlertRule in the KV store.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::alert::{AlertAction, AlertRuleSpec};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for alert rule request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_alert_rule_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<AlertRuleSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating alert rule");
+
+    if auth_ctx
+        .global_ctx
+        .alert_rules()
+        .store()
+        .contains_key(&name)
+        .await
+    {
+        debug!("alert rule already exists");
+        return Ok(Status::new(
+            name.to_string(),
+            ErrorCode::AlertRuleAlreadyExists,
+            Some(format!("alert rule '{name}' already defined")),
+        ));
+    }
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(AlertRuleSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Err(reason) = validate_alert_rule(&spec) {
+        return Ok(Status::new(
+            name,
+            ErrorCode::AlertRuleInvalid(reason.clone()),
+            Some(reason),
+        ));
+    }
+
+    let status = process_alert_rule_request(&auth_ctx.global_ctx, name, spec).await;
+    trace!("create alert rule response {:#?}", status);
+
+    Ok(status)
+}
+
+fn validate_alert_rule(spec: &AlertRuleSpec) -> Result<(), String> {
+    spec.parse_expression().map_err(|err| err.to_string())?;
+    for action in &spec.actions {
+        match action {
+            AlertAction::Webhook { url }
+                if !(url.starts_with("http://") || url.starts_with("https://")) =>
+            {
+                return Err(format!("webhook url '{url}' must be http or https"));
+            }
+            AlertAction::Topic { topic } if topic.is_empty() => {
+                return Err("alert topic name is empty".to_owned());
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[instrument(skip(ctx, name, spec))]
+async fn process_alert_rule_request<C: MetadataItem>(
+    ctx: &Context<C>,
+    name: String,
+    spec: AlertRuleSpec,
+) -> Status {
+    if let Err(err) = ctx.alert_rules().create_spec(name.clone(), spec).await {
+        let reason = err.to_string();
+        Status::new(
+            name,
+            ErrorCode::AlertRuleInvalid(reason.clone()),
+            Some(reason),
+        )
+    } else {
+        info!(%name, "alert rule created");
+        Status::new_ok(name.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_validate_alert_rule() {
+        let mut spec = AlertRuleSpec::new("offline_partitions > 0");
+        assert!(validate_alert_rule(&spec).is_ok());
+
+        spec.actions.push(AlertAction::Webhook {
+            url: "ftp://hooks".to_owned(),
+        });
+        assert!(validate_alert_rule(&spec).is_err());
+
+        assert!(validate_alert_rule(&AlertRuleSpec::new("lag > 0")).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_sc_schema::Status;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::alert::AlertRuleSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete alert rule request
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_alert_rule<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    use fluvio_protocol::link::ErrorCode;
+
+    info!(%name, "deleting alert rule");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(AlertRuleSpec::OBJECT_TYPE, InstanceAction::Delete, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let status = if auth_ctx
+        .global_ctx
+        .alert_rules()
+        .store()
+        .value(&name)
+        .await
+        .is_some()
+    {
+        if let Err(err) = auth_ctx.global_ctx.alert_rules().delete(name.clone()).await {
+            Status::new(
+                name.clone(),
+                ErrorCode::Other(err.to_string()),
+                Some(err.to_string()),
+            )
+        } else {
+            info!(%name, "alert rule deleted");
+            Status::new_ok(name)
+        }
+    } else {
+        Status::new(
+            name,
+            ErrorCode::AlertRuleNotFound,
+            Some("not found".to_owned()),
+        )
+    };
+
+    trace!("flv delete alert rule resp {:#?}", status);
+
+    Ok(status)
+}
@@ -9,21 +9,26 @@ use fluvio_protocol::link::versions::{
     ApiVersionKey, ApiVersionsRequest, ApiVersionsResponse, PlatformVersion,
 };
 use fluvio_sc_schema::objects::{
-    ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiUpdateRequest,
-    ObjectApiWatchRequest,
+    ObjectApiBatchRequest, ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest,
+    ObjectApiUpdateRequest, ObjectApiWatchRequest,
 };
 use fluvio_sc_schema::AdminPublicApiKey;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::services::auth::AuthServiceContext;
 
 // Fluvi Client version 0.14.0 corresponds to Platform version 10.0.0
 
 static PLATFORM_VER: Lazy<Version> = Lazy::new(|| Version::parse(crate::VERSION).unwrap());
 
-#[instrument(skip(request))]
-pub async fn handle_api_versions_request(
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_api_versions_request<AC, C: MetadataItem>(
     request: RequestMessage<ApiVersionsRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
 ) -> Result<ResponseMessage<ApiVersionsResponse>> {
     let mut response = ApiVersionsResponse {
         platform_version: PlatformVersion::new(&PLATFORM_VER),
+        supported_client: auth_ctx.global_ctx.config().supported_client.clone(),
         ..Default::default()
     };
 
@@ -66,6 +71,12 @@ pub async fn handle_api_versions_request(
         ObjectApiUpdateRequest::MAX_API_VERSION,
     ));
 
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::Batch,
+        ObjectApiBatchRequest::MIN_API_VERSION,
+        ObjectApiBatchRequest::MAX_API_VERSION,
+    ));
+
     trace!("flv api versions response: {:#?}", response);
 
     Ok(request.new_response(response))
@@ -0,0 +1,357 @@
+//!
+//! # Batch Request
+//!
+//! Creates the topics and SmartModules of a batch, or none of them. Every object is validated
+//! before anything is written, topics count the SmartModules of the batch as loaded. SmartModules
+//! are applied first since topics may use them, and whatever was applied is reverted as soon as
+//! an object fails. Objects which could not be reverted are reported in the returned status.
+//!
+//! A batch holds the store exclusively while admin requests which change it share it, so no
+//! other change interleaves with a batch or its rollback.
+//!
+
+use std::collections::HashSet;
+
+use async_lock::{RwLock, RwLockReadGuard};
+use tracing::{info, debug, warn, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::{BatchRequest, CreateRequest, ObjectApiBatchRequest};
+use fluvio_sc_schema::smartmodule::SmartModuleSpec;
+use fluvio_sc_schema::topic::TopicSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+
+static STORE_LOCK: RwLock<()> = RwLock::new(());
+
+/// held by the admin requests which change the store, waits for a running batch
+pub(crate) async fn admin_guard() -> RwLockReadGuard<'static, ()> {
+    STORE_LOCK.read().await
+}
+
+const BATCH: &str = "batch";
+
+/// Handler for batch request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_batch_request<AC: AuthContext, C: MetadataItem>(
+    request: Box<RequestMessage<ObjectApiBatchRequest>>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<Status>> {
+    let (header, req) = request.get_header_request();
+    let batch = req.into_inner();
+
+    debug!(
+        topics = batch.topics.len(),
+        smartmodules = batch.smartmodules.len(),
+        "batch request"
+    );
+
+    let status = if batch.is_empty() {
+        Status::new_ok(BATCH.to_owned())
+    } else {
+        let _guard = STORE_LOCK.write().await;
+        apply_batch(batch, auth_ctx).await?
+    };
+
+    Ok(ResponseMessage::from_header(&header, status))
+}
+
+/// SmartModule applied by the batch with the spec it replaced
+struct AppliedSmartModule {
+    store_id: String,
+    previous: Option<SmartModuleSpec>,
+}
+
+async fn apply_batch<AC: AuthContext, C: MetadataItem>(
+    batch: BatchRequest,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let BatchRequest {
+        topics,
+        smartmodules,
+    } = batch;
+    let ctx = &auth_ctx.global_ctx;
+
+    if let Some(status) = check_duplicates(&topics, &smartmodules) {
+        return Ok(status);
+    }
+
+    for (object_type, count) in [
+        (TopicSpec::OBJECT_TYPE, topics.len()),
+        (SmartModuleSpec::OBJECT_TYPE, smartmodules.len()),
+    ] {
+        if count == 0 {
+            continue;
+        }
+        match auth_ctx
+            .auth
+            .allow_type_action(object_type, TypeAction::Create)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(Status::new(
+                    BATCH.to_owned(),
+                    ErrorCode::PermissionDenied,
+                    Some(String::from("permission denied")),
+                ))
+            }
+            Err(_) => return Err(anyhow!("authorization io error")),
+        }
+    }
+
+    // nothing is written before every object is validated
+    if let Some(sm) = smartmodules
+        .iter()
+        .find(|sm| matches!(&sm.request.meta, Some(meta) if !meta.package.is_valid()))
+    {
+        return Ok(Status::new(
+            sm.common.name.clone(),
+            ErrorCode::SmartModuleError,
+            Some("invalid SmartModule package".to_owned()),
+        ));
+    }
+
+    // topics may use the SmartModules of the batch, so those count as loaded
+    let pending_smartmodules: HashSet<String> =
+        smartmodules.iter().map(smartmodule_store_id).collect();
+    for topic in &topics {
+        let mut dry_run = topic.clone();
+        dry_run.common.dry_run = true;
+        match super::topic::handle_create_topic_with_smartmodules(
+            dry_run,
+            auth_ctx,
+            &pending_smartmodules,
+        )
+        .await
+        {
+            Ok(status) if !status.is_error() => {}
+            result => return result,
+        }
+    }
+
+    let mut applied = Vec::with_capacity(smartmodules.len());
+    for sm in smartmodules {
+        let store_id = smartmodule_store_id(&sm);
+        let previous = ctx
+            .smartmodules()
+            .store()
+            .value(&store_id)
+            .await
+            .map(|sm| sm.spec().clone());
+        if let Err(err) = ctx
+            .smartmodules()
+            .create_spec(store_id.clone(), sm.request)
+            .await
+        {
+            let status = Status::new(store_id, ErrorCode::SmartModuleError, Some(err.to_string()));
+            return rollback(ctx, Vec::new(), applied, Ok(status)).await;
+        }
+        applied.push(AppliedSmartModule { store_id, previous });
+    }
+
+    let mut created: Vec<String> = Vec::with_capacity(topics.len());
+    for mut topic in topics {
+        topic.common.dry_run = false;
+        let name = topic.common.name.clone();
+        match super::topic::handle_create_topic_with_smartmodules(
+            topic,
+            auth_ctx,
+            &pending_smartmodules,
+        )
+        .await
+        {
+            Ok(status) if !status.is_error() => created.push(name),
+            result => {
+                // a topic which failed to provision has been stored anyway
+                if let Ok(Status {
+                    error_code: ErrorCode::TopicNotProvisioned,
+                    ..
+                }) = &result
+                {
+                    created.push(name);
+                }
+                return rollback(ctx, created, applied, result).await;
+            }
+        }
+    }
+
+    info!(
+        topics = created.len(),
+        smartmodules = applied.len(),
+        "batch applied"
+    );
+
+    Ok(Status::new_ok(BATCH.to_owned()))
+}
+
+/// each object can appear only once, otherwise the second one would replace the first
+fn check_duplicates(
+    topics: &[CreateRequest<TopicSpec>],
+    smartmodules: &[CreateRequest<SmartModuleSpec>],
+) -> Option<Status> {
+    let mut names = HashSet::new();
+    if let Some(topic) = topics
+        .iter()
+        .find(|topic| !names.insert(topic.common.name.as_str()))
+    {
+        return Some(Status::new(
+            topic.common.name.clone(),
+            ErrorCode::TopicAlreadyExists,
+            Some(format!(
+                "Topic '{}' is more than once in the batch",
+                topic.common.name
+            )),
+        ));
+    }
+
+    let mut store_ids = HashSet::new();
+    smartmodules
+        .iter()
+        .map(smartmodule_store_id)
+        .find(|store_id| !store_ids.insert(store_id.clone()))
+        .map(|store_id| {
+            Status::new(
+                store_id.clone(),
+                ErrorCode::SmartModuleError,
+                Some(format!(
+                    "SmartModule '{store_id}' is more than once in the batch"
+                )),
+            )
+        })
+}
+
+/// if there is pkg associated with, it overrides the name
+fn smartmodule_store_id(sm: &CreateRequest<SmartModuleSpec>) -> String {
+    match &sm.request.meta {
+        Some(meta) => meta.store_id(),
+        None => sm.common.name.clone(),
+    }
+}
+
+/// reverts what was applied, the objects which could not be reverted are added to the failure
+async fn rollback<C: MetadataItem>(
+    ctx: &Context<C>,
+    created: Vec<String>,
+    applied: Vec<AppliedSmartModule>,
+    result: Result<Status>,
+) -> Result<Status> {
+    let mut failures = revert_topics(ctx, created).await;
+    failures.extend(revert_smartmodules(ctx, applied).await);
+    with_revert_failures(result, failures)
+}
+
+fn with_revert_failures(result: Result<Status>, failures: Vec<String>) -> Result<Status> {
+    if failures.is_empty() {
+        return result;
+    }
+    let reverts = format!("unable to revert {}", failures.join(", "));
+    match result {
+        Ok(mut status) => {
+            status.error_message = Some(match status.error_message.take() {
+                Some(message) => format!("{message}; {reverts}"),
+                None => reverts,
+            });
+            Ok(status)
+        }
+        Err(err) => Err(err.context(reverts)),
+    }
+}
+
+async fn revert_topics<C: MetadataItem>(ctx: &Context<C>, created: Vec<String>) -> Vec<String> {
+    let mut failures = Vec::new();
+    for name in created.into_iter().rev() {
+        if !ctx.topics().store().contains_key(&name).await {
+            continue;
+        }
+        if let Err(err) = ctx.topics().delete(name.clone()).await {
+            warn!(topic = %name, %err, "unable to revert topic of batch");
+            failures.push(format!("topic '{name}': {err}"));
+        } else {
+            info!(topic = %name, "reverted topic of batch");
+        }
+    }
+    failures
+}
+
+async fn revert_smartmodules<C: MetadataItem>(
+    ctx: &Context<C>,
+    applied: Vec<AppliedSmartModule>,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+    for AppliedSmartModule { store_id, previous } in applied.into_iter().rev() {
+        let result = match previous {
+            Some(spec) => ctx
+                .smartmodules()
+                .create_spec(store_id.clone(), spec)
+                .await
+                .map(|_| ()),
+            None => ctx.smartmodules().delete(store_id.clone()).await,
+        };
+        if let Err(err) = result {
+            warn!(%store_id, %err, "unable to revert smartmodule of batch");
+            failures.push(format!("SmartModule '{store_id}': {err}"));
+        } else {
+            info!(%store_id, "reverted smartmodule of batch");
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revert_failures_in_status() {
+        let failed = || {
+            Ok(Status::new(
+                "t1".to_owned(),
+                ErrorCode::TopicError,
+                Some("no spu".to_owned()),
+            ))
+        };
+
+        let status = with_revert_failures(failed(), Vec::new()).expect("status");
+        assert_eq!(status.error_message.as_deref(), Some("no spu"));
+
+        let status = with_revert_failures(failed(), vec!["SmartModule 'sm1': timeout".to_owned()])
+            .expect("status");
+        assert_eq!(status.error_code, ErrorCode::TopicError);
+        assert_eq!(
+            status.error_message.as_deref(),
+            Some("no spu; unable to revert SmartModule 'sm1': timeout")
+        );
+
+        let status = with_revert_failures(
+            Ok(Status::new(
+                "sm1".to_owned(),
+                ErrorCode::SmartModuleError,
+                None,
+            )),
+            vec!["topic 't1': timeout".to_owned()],
+        )
+        .expect("status");
+        assert_eq!(
+            status.error_message.as_deref(),
+            Some("unable to revert topic 't1': timeout")
+        );
+
+        let err = with_revert_failures(
+            Err(anyhow!("authorization io error")),
+            vec!["topic 't1': timeout".to_owned()],
+        )
+        .expect_err("error");
+        assert_eq!(
+            format!("{err:#}"),
+            "unable to revert topic 't1': timeout: authorization io error"
+        );
+    }
+}
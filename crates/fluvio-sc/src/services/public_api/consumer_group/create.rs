@@ -0,0 +1,123 @@
+//!
+//! # Create ConsumerGroup Request
+//!
+//! Validates the group spec before storing the ConsumerGroup in the KV store.
+//! The topic may not exist yet, the group stays invalid until it is created.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::consumer_group::ConsumerGroupSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for consumer group request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_consumer_group_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<ConsumerGroupSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating consumer group");
+
+    if auth_ctx
+        .global_ctx
+        .consumer_groups()
+        .store()
+        .contains_key(&name)
+        .await
+    {
+        debug!("consumer group already exists");
+        return Ok(Status::new(
+            name.to_string(),
+            ErrorCode::ConsumerGroupAlreadyExists,
+            Some(format!("consumer group '{name}' already defined")),
+        ));
+    }
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(ConsumerGroupSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Err(reason) = validate_consumer_group(&spec) {
+        return Ok(Status::new(
+            name,
+            ErrorCode::ConsumerGroupInvalid(reason.clone()),
+            Some(reason),
+        ));
+    }
+
+    let status = process_consumer_group_request(&auth_ctx.global_ctx, name, spec).await;
+    trace!("create consumer group response {:#?}", status);
+
+    Ok(status)
+}
+
+fn validate_consumer_group(spec: &ConsumerGroupSpec) -> Result<(), String> {
+    if spec.topic.is_empty() {
+        return Err("consumer group topic name is empty".to_owned());
+    }
+    if spec.session_timeout_ms == 0 {
+        return Err("session timeout must be greater than zero".to_owned());
+    }
+    Ok(())
+}
+
+#[instrument(skip(ctx, name, spec))]
+async fn process_consumer_group_request<C: MetadataItem>(
+    ctx: &Context<C>,
+    name: String,
+    spec: ConsumerGroupSpec,
+) -> Status {
+    if let Err(err) = ctx.consumer_groups().create_spec(name.clone(), spec).await {
+        let reason = err.to_string();
+        Status::new(
+            name,
+            ErrorCode::ConsumerGroupInvalid(reason.clone()),
+            Some(reason),
+        )
+    } else {
+        info!(%name, "consumer group created");
+        Status::new_ok(name.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_validate_consumer_group() {
+        let mut spec = ConsumerGroupSpec::new("orders");
+        assert!(validate_consumer_group(&spec).is_ok());
+
+        spec.session_timeout_ms = 0;
+        assert!(validate_consumer_group(&spec).is_err());
+
+        assert!(validate_consumer_group(&ConsumerGroupSpec::new("")).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_sc_schema::Status;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::consumer_group::ConsumerGroupSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete consumer group request
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_consumer_group<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    use fluvio_protocol::link::ErrorCode;
+
+    info!(%name, "deleting consumer group");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(
+            ConsumerGroupSpec::OBJECT_TYPE,
+            InstanceAction::Delete,
+            &name,
+        )
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let status = if auth_ctx
+        .global_ctx
+        .consumer_groups()
+        .store()
+        .value(&name)
+        .await
+        .is_some()
+    {
+        if let Err(err) = auth_ctx
+            .global_ctx
+            .consumer_groups()
+            .delete(name.clone())
+            .await
+        {
+            Status::new(
+                name.clone(),
+                ErrorCode::Other(err.to_string()),
+                Some(err.to_string()),
+            )
+        } else {
+            info!(%name, "consumer group deleted");
+            Status::new_ok(name)
+        }
+    } else {
+        Status::new(
+            name,
+            ErrorCode::ConsumerGroupNotFound,
+            Some("not found".to_owned()),
+        )
+    };
+
+    trace!("flv delete consumer group resp {:#?}", status);
+
+    Ok(status)
+}
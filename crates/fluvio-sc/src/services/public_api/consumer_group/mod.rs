@@ -0,0 +1,7 @@
+mod create;
+mod delete;
+mod update;
+
+pub use create::*;
+pub use delete::*;
+pub use update::*;
@@ -0,0 +1,60 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, instrument, trace};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::consumer_group::{ConsumerGroupSpec, UpdateConsumerGroupAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for join, heartbeat and leave of consumer group members
+#[instrument(skip(name, action, auth_ctx))]
+pub async fn handle_consumer_group_update_request<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    action: UpdateConsumerGroupAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    debug!(%name, ?action, "updating consumer group");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(
+            ConsumerGroupSpec::OBJECT_TYPE,
+            InstanceAction::Update,
+            &name,
+        )
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    let status = match ctx
+        .group_coordinator()
+        .apply(ctx.consumer_groups(), ctx.topics(), &name, action)
+        .await?
+    {
+        ErrorCode::None => Status::new_ok(name),
+        code => {
+            let message = code.to_string();
+            Status::new(name, code, Some(message))
+        }
+    };
+
+    trace!("flv update consumer group resp {:#?}", status);
+
+    Ok(status)
+}
@@ -8,6 +8,10 @@ use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::spu::CustomSpuSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
+use fluvio_controlplane_metadata::alert::AlertRuleSpec;
+use fluvio_controlplane_metadata::consumer_group::ConsumerGroupSpec;
+use fluvio_controlplane_metadata::schema::SchemaSpec;
+use fluvio_controlplane_metadata::acl::AclSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
@@ -22,6 +26,7 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
     request: Box<RequestMessage<ObjectApiCreateRequest>>,
     auth_context: &AuthServiceContext<AC, C>,
 ) -> Result<ResponseMessage<Status>> {
+    let _guard = super::batch::admin_guard().await;
     let (header, req) = request.get_header_request();
 
     debug!(?req, "create request");
@@ -38,6 +43,14 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_create_tableformat_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<MirrorSpec>> {
         super::mirror::handle_register_mirror(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<AlertRuleSpec>> {
+        super::alert::handle_create_alert_rule_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<ConsumerGroupSpec>> {
+        super::consumer_group::handle_create_consumer_group_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<SchemaSpec>> {
+        super::schema::handle_create_schema_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<AclSpec>> {
+        super::acl::handle_create_acl_request(create, auth_context).await?
     } else {
         error!("unknown create request: {:#?}", req);
         Status::new(
@@ -15,6 +15,10 @@ use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::spu::CustomSpuSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
+use fluvio_controlplane_metadata::alert::AlertRuleSpec;
+use fluvio_controlplane_metadata::consumer_group::ConsumerGroupSpec;
+use fluvio_controlplane_metadata::schema::SchemaSpec;
+use fluvio_controlplane_metadata::acl::AclSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
@@ -29,6 +33,7 @@ pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
     request: RequestMessage<ObjectApiDeleteRequest>,
     auth_ctx: &AuthServiceContext<AC, C>,
 ) -> Result<ResponseMessage<Status>> {
+    let _guard = super::batch::admin_guard().await;
     let (header, del_req) = request.get_header_request();
 
     debug!(?del_req, "del request");
@@ -46,6 +51,14 @@ pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_delete_tableformat(req.key(), auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<MirrorSpec>> {
         super::mirror::handle_unregister_mirror(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<AlertRuleSpec>> {
+        super::alert::handle_delete_alert_rule(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<ConsumerGroupSpec>> {
+        super::consumer_group::handle_delete_consumer_group(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<SchemaSpec>> {
+        super::schema::handle_delete_schema(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<AclSpec>> {
+        super::acl::handle_delete_acl(req.key(), auth_ctx).await?
     } else {
         error!("unknown create request: {:#?}", del_req);
         Status::new(
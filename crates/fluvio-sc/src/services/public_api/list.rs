@@ -5,6 +5,10 @@ use fluvio_controlplane_metadata::{
     partition::PartitionSpec,
     smartmodule::SmartModuleSpec,
     tableformat::TableFormatSpec,
+    alert::AlertRuleSpec,
+    consumer_group::ConsumerGroupSpec,
+    schema::SchemaSpec,
+    acl::AclSpec,
 };
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{debug, instrument};
@@ -81,6 +85,38 @@ pub async fn handle_list_request<AC: AuthContext, C: MetadataItem>(
             handle_list_mirror(req.name_filters, auth_ctx).await?,
             header.api_version(),
         )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<AlertRuleSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(
+                req.name_filters,
+                auth_ctx,
+                auth_ctx.global_ctx.alert_rules(),
+            )
+            .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<ConsumerGroupSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(
+                req.name_filters,
+                auth_ctx,
+                auth_ctx.global_ctx.consumer_groups(),
+            )
+            .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<SchemaSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(req.name_filters, auth_ctx, auth_ctx.global_ctx.schemas())
+                .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<AclSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(req.name_filters, auth_ctx, auth_ctx.global_ctx.acls())
+                .await?,
+            header.api_version(),
+        )?
     } else {
         return Err(anyhow::anyhow!("unsupported list request: {:#?}", req));
     };
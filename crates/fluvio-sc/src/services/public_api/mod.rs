@@ -6,6 +6,7 @@ mod topic;
 mod partition;
 mod api_version;
 mod create;
+mod batch;
 mod delete;
 mod update;
 mod list;
@@ -14,8 +15,14 @@ mod tableformat;
 mod derivedstream;
 mod mirror;
 mod mirroring;
+mod alert;
+mod consumer_group;
+mod schema;
+mod acl;
+mod admin_http;
 
 pub use server::start_public_server;
+pub use admin_http::{start_admin_http_server, PolicyTokenAuthorization, SharedTokenAuthorization};
 
 mod server {
 
@@ -41,8 +48,9 @@ mod server {
         <A as Authorization>::Context: Send + Sync,
     {
         let addr = ctx.global_ctx.config().public_endpoint.clone();
+        let tcp = ctx.global_ctx.config().tcp.clone();
         debug!("starting public api service");
-        let server = FluvioApiServer::new(addr, ctx, PublicService::new());
+        let server = FluvioApiServer::new(addr, ctx, PublicService::new()).with_tcp_config(tcp);
         server.run();
     }
 }
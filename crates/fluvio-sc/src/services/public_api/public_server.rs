@@ -85,7 +85,7 @@ where
 
             AdminPublicDecodedRequest::ApiVersionsRequest(request) => call_service!(
                 request,
-                super::api_version::handle_api_versions_request(request),
+                super::api_version::handle_api_versions_request(request, &service_context),
                 shared_sink,
                 "ApiVersionRequest"
             ),
@@ -96,6 +96,12 @@ where
                 shared_sink,
                 "create  handler"
             ),
+            AdminPublicDecodedRequest::BatchRequest(request) => call_service!(
+                request,
+                super::batch::handle_batch_request(request, &service_context),
+                shared_sink,
+                "batch handler"
+            ),
             AdminPublicDecodedRequest::UpdateRequest(request) => call_service!(
                 request,
                 super::update::handle_update_request(request, &service_context),
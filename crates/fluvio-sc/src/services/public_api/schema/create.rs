@@ -0,0 +1,147 @@
+//!
+//! # Create Schema Request
+//!
+//! Registers the first versions of the schema of a topic. The schema is named after
+//! its topic, which must exist.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::schema::{validate_definition, SchemaSpec};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for create schema request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_schema_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<SchemaSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating schema");
+
+    if auth_ctx
+        .global_ctx
+        .schemas()
+        .store()
+        .contains_key(&name)
+        .await
+    {
+        debug!("schema already exists");
+        return Ok(Status::new(
+            name.to_string(),
+            ErrorCode::SchemaAlreadyExists,
+            Some(format!("schema of topic '{name}' already registered")),
+        ));
+    }
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(SchemaSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if !auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .contains_key(&name)
+        .await
+    {
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::TopicNotFound,
+            Some(format!("topic '{name}' not found")),
+        ));
+    }
+
+    if let Err(reason) = validate_schema(&spec) {
+        return Ok(Status::new(
+            name,
+            ErrorCode::SchemaInvalid(reason.clone()),
+            Some(reason),
+        ));
+    }
+
+    let status = process_schema_request(&auth_ctx.global_ctx, name, spec).await;
+    trace!("create schema response {:#?}", status);
+
+    Ok(status)
+}
+
+fn validate_schema(spec: &SchemaSpec) -> Result<(), String> {
+    if spec.versions.is_empty() {
+        return Err("schema has no version".to_owned());
+    }
+    let mut previous = 0;
+    for version in &spec.versions {
+        if version.version <= previous {
+            return Err(format!(
+                "version {} does not follow version {previous}",
+                version.version
+            ));
+        }
+        previous = version.version;
+        validate_definition(spec.format, &version.definition)
+            .map_err(|reason| format!("version {}: {reason}", version.version))?;
+    }
+    Ok(())
+}
+
+#[instrument(skip(ctx, name, spec))]
+async fn process_schema_request<C: MetadataItem>(
+    ctx: &Context<C>,
+    name: String,
+    spec: SchemaSpec,
+) -> Status {
+    if let Err(err) = ctx.schemas().create_spec(name.clone(), spec).await {
+        let reason = err.to_string();
+        Status::new(name, ErrorCode::SchemaInvalid(reason.clone()), Some(reason))
+    } else {
+        info!(%name, "schema created");
+        Status::new_ok(name.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_sc_schema::schema::SchemaFormat;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_schema() {
+        let mut spec = SchemaSpec::new(SchemaFormat::JsonSchema, r#"{"type": "object"}"#);
+        assert!(validate_schema(&spec).is_ok());
+
+        spec.add_version("{");
+        assert!(validate_schema(&spec)
+            .unwrap_err()
+            .starts_with("version 2: invalid json"));
+
+        spec.versions.clear();
+        assert!(validate_schema(&spec).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, instrument, trace};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::schema::{check_compatibility, SchemaSpec, UpdateSchemaAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for registering versions and changing the compatibility of a schema
+#[instrument(skip(name, action, auth_ctx))]
+pub async fn handle_schema_update_request<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    action: UpdateSchemaAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    debug!(%name, "updating schema");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(SchemaSpec::OBJECT_TYPE, InstanceAction::Update, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let schemas = auth_ctx.global_ctx.schemas();
+    let Some(current) = schemas.store().value(&name).await else {
+        return Ok(Status::new(
+            name,
+            ErrorCode::SchemaNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    let mut spec = current.spec.clone();
+    let status = match apply_action(&mut spec, action) {
+        Ok(()) => match schemas.create_spec(name.clone(), spec).await {
+            Ok(_) => {
+                info!(%name, "schema updated");
+                Status::new_ok(name)
+            }
+            Err(err) => Status::new(
+                name,
+                ErrorCode::Other(err.to_string()),
+                Some(err.to_string()),
+            ),
+        },
+        Err(code) => {
+            let message = code.to_string();
+            Status::new(name, code, Some(message))
+        }
+    };
+
+    trace!("flv update schema resp {:#?}", status);
+
+    Ok(status)
+}
+
+fn apply_action(spec: &mut SchemaSpec, action: UpdateSchemaAction) -> Result<(), ErrorCode> {
+    match action {
+        UpdateSchemaAction::Register { definition } => {
+            if let Some(latest) = spec.latest() {
+                if latest.definition == definition {
+                    // registering the latest version again is a no-op
+                    return Ok(());
+                }
+                check_compatibility(
+                    spec.format,
+                    spec.compatibility,
+                    &latest.definition,
+                    &definition,
+                )
+                .map_err(|reason| ErrorCode::SchemaIncompatible {
+                    version: latest.version,
+                    reason,
+                })?;
+            }
+            let version = spec.add_version(definition);
+            debug!(version, "schema version registered");
+            Ok(())
+        }
+        UpdateSchemaAction::SetCompatibility(compatibility) => {
+            spec.compatibility = compatibility;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_sc_schema::schema::{SchemaCompatibility, SchemaFormat};
+
+    use super::*;
+
+    fn register(definition: &str) -> UpdateSchemaAction {
+        UpdateSchemaAction::Register {
+            definition: definition.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_register_schema_version() {
+        let v1 = r#"{"type": "object", "properties": {"id": {"type": "integer"}}}"#;
+        let v2 = r#"{"type": "object", "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}}"#;
+        let required = r#"{"type": "object", "required": ["name"]}"#;
+        let mut spec = SchemaSpec::new(SchemaFormat::JsonSchema, v1);
+
+        assert!(apply_action(&mut spec, register(v2)).is_ok());
+        assert_eq!(spec.latest().map(|it| it.version), Some(2));
+
+        // same definition as the latest version
+        assert!(apply_action(&mut spec, register(v2)).is_ok());
+        assert_eq!(spec.versions.len(), 2);
+
+        assert!(matches!(
+            apply_action(&mut spec, register(required)),
+            Err(ErrorCode::SchemaIncompatible { version: 2, .. })
+        ));
+
+        assert!(apply_action(
+            &mut spec,
+            UpdateSchemaAction::SetCompatibility(SchemaCompatibility::None)
+        )
+        .is_ok());
+        assert!(apply_action(&mut spec, register(required)).is_ok());
+        assert_eq!(spec.latest().map(|it| it.version), Some(3));
+    }
+}
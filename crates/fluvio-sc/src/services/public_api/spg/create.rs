@@ -53,6 +53,10 @@ pub async fn handle_create_spu_group_request<AC: AuthContext, C: MetadataItem>(
         return Err(anyhow!("authorization io error"));
     }
 
+    if let Some(Err(reason)) = spg.autoscale.as_ref().map(|autoscale| autoscale.validate()) {
+        return Ok(Status::new(name, ErrorCode::SpuError, Some(reason)));
+    }
+
     let status = process_custom_spu_request(&auth_ctx.global_ctx, name, create.timeout, spg).await;
     trace!("create spu-group response {:#?}", status);
 
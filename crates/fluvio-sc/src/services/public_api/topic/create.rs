@@ -10,17 +10,24 @@
 //! Assigned Topics allow the users to apply their custom-defined replica assignment.
 //! Mirror Topics are used for mirroring data from one topic to another.
 //!
+//! Clones take the spec of their source topic, with partitions assigned to the replicas of
+//! the source partitions, so SPUs can copy the sealed segments locally.
+//!
+
+use std::collections::HashSet;
 
 use tracing::{info, debug, trace, instrument};
 use anyhow::{anyhow, Result};
 
 use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
 use fluvio_controlplane_metadata::topic::ReplicaSpec;
 use fluvio_sc_schema::objects::CreateRequest;
 use fluvio_sc_schema::shared::validate_resource_name;
 use fluvio_sc_schema::Status;
 use fluvio_sc_schema::topic::TopicSpec;
 use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_controlplane_metadata::acl::AclPermission;
 use fluvio_controlplane_metadata::extended::SpecExt;
 use fluvio_controlplane_metadata::smartmodule::SmartModulePackageKey;
 use fluvio_stream_model::core::MetadataItem;
@@ -38,7 +45,17 @@ pub(crate) async fn handle_create_topics_request<AC: AuthContext, C: MetadataIte
     req: CreateRequest<TopicSpec>,
     auth_ctx: &AuthServiceContext<AC, C>,
 ) -> Result<Status> {
-    let (create, topic) = req.parts();
+    handle_create_topic_with_smartmodules(req, auth_ctx, &HashSet::new()).await
+}
+
+/// Same as [handle_create_topics_request], the SmartModules in `pending_smartmodules` count as
+/// loaded, so a batch can validate its topics before creating their SmartModules
+pub(crate) async fn handle_create_topic_with_smartmodules<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<TopicSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+    pending_smartmodules: &HashSet<String>,
+) -> Result<Status> {
+    let (create, mut topic) = req.parts();
     let name = create.name;
 
     info!( topic = %name,"creating topic");
@@ -60,8 +77,44 @@ pub(crate) async fn handle_create_topics_request<AC: AuthContext, C: MetadataIte
         return Err(anyhow!("authorization io error"));
     }
 
+    // acls are bound to topic names, a clone also reads the records of its source
+    let topic_authorized = auth_ctx
+        .auth
+        .allow_topic_action(&name, AclPermission::Admin)
+        .await;
+    let source_authorized = match topic.get_clone_from() {
+        Some(source) => {
+            auth_ctx
+                .auth
+                .allow_topic_action(source, AclPermission::Consume)
+                .await
+        }
+        None => Ok(true),
+    };
+    match (topic_authorized, source_authorized) {
+        (Ok(true), Ok(true)) => {}
+        (Ok(_), Ok(_)) => {
+            trace!("topic authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+        _ => return Err(anyhow!("authorization io error")),
+    }
+
+    if let Some(source) = topic.get_clone_from() {
+        topic = match clone_topic_spec(&name, source, &auth_ctx.global_ctx).await {
+            Ok(spec) => spec,
+            Err(status) => return Ok(status),
+        };
+    }
+
     // validate topic request
-    let mut status = validate_topic_request::<C>(&name, &topic, &auth_ctx.global_ctx).await;
+    let mut status =
+        validate_topic_request::<C>(&name, &topic, &auth_ctx.global_ctx, pending_smartmodules)
+            .await;
     if status.is_error() {
         return Ok(status);
     }
@@ -80,6 +133,7 @@ async fn validate_topic_request<C: MetadataItem>(
     name: &str,
     topic_spec: &TopicSpec,
     metadata: &Context<C>,
+    pending_smartmodules: &HashSet<String>,
 ) -> Status {
     debug!("validating topic: {}", name);
 
@@ -125,7 +179,9 @@ async fn validate_topic_request<C: MetadataItem>(
                 )
             }
         };
-        if !metadata.smartmodules().store().contains_key(&sm_fqdn).await {
+        if !pending_smartmodules.contains(&sm_fqdn)
+            && !metadata.smartmodules().store().contains_key(&sm_fqdn).await
+        {
             return Status::new(
                 sm_name.to_string(),
                 ErrorCode::DeduplicationSmartModuleNotLoaded,
@@ -137,6 +193,40 @@ async fn validate_topic_request<C: MetadataItem>(
         }
     }
 
+    // check if validation smartmodule is present
+    if let Some(validation) = topic_spec.get_validation() {
+        if validation.on_invalid.dead_letter_topic() == Some(name) {
+            return Status::new(
+                name.to_string(),
+                ErrorCode::TopicInvalidConfiguration,
+                Some("dead letter topic must be different from the topic".to_string()),
+            );
+        }
+        let sm_name = validation.transform.uses.as_str();
+        let sm_fqdn = match SmartModulePackageKey::from_qualified_name(sm_name) {
+            Ok(fqdn) => fqdn.store_id(),
+            Err(err) => {
+                return Status::new(
+                    sm_name.to_string(),
+                    ErrorCode::ValidationSmartModuleNameInvalid(err.to_string()),
+                    Some(err.to_string()),
+                )
+            }
+        };
+        if !pending_smartmodules.contains(&sm_fqdn)
+            && !metadata.smartmodules().store().contains_key(&sm_fqdn).await
+        {
+            return Status::new(
+                sm_name.to_string(),
+                ErrorCode::ValidationSmartModuleNotLoaded,
+                Some(format!(
+                    "{}\nHint: try `fluvio hub download {sm_name}` and repeat this operation",
+                    ErrorCode::ValidationSmartModuleNotLoaded
+                )),
+            );
+        }
+    }
+
     match topic_spec.replicas() {
         ReplicaSpec::Computed(param) => {
             let next_state = validate_computed_topic_parameters::<C>(param);
@@ -191,6 +281,56 @@ async fn validate_topic_request<C: MetadataItem>(
     }
 }
 
+/// spec of a clone of `source` with partitions on the same SPUs, leader first
+async fn clone_topic_spec<C: MetadataItem>(
+    name: &str,
+    source: &str,
+    metadata: &Context<C>,
+) -> Result<TopicSpec, Status> {
+    let error = |code: ErrorCode, reason: String| Status::new(name.to_owned(), code, Some(reason));
+
+    let Some(source_topic) = metadata.topics().store().value(source).await else {
+        return Err(error(
+            ErrorCode::TopicNotFound,
+            format!("source topic '{source}' not found"),
+        ));
+    };
+    let source_spec = &source_topic.inner().spec;
+    if source_spec.is_system() || matches!(source_spec.replicas(), ReplicaSpec::Mirror(_)) {
+        return Err(error(
+            ErrorCode::TopicInvalidConfiguration,
+            format!(
+                "{} topic '{source}' can't be cloned",
+                source_spec.type_label()
+            ),
+        ));
+    }
+
+    let mut maps = Vec::new();
+    for id in 0..source_spec.partitions() {
+        let Some(partition) = metadata
+            .partitions()
+            .store()
+            .value(&ReplicaKey::new(source, id))
+            .await
+        else {
+            return Err(error(
+                ErrorCode::TopicNotProvisioned,
+                format!("partition {id} of '{source}' is not provisioned"),
+            ));
+        };
+        let spec = &partition.inner().spec;
+        let mut replicas = vec![spec.leader];
+        replicas.extend(spec.followers());
+        maps.push((id, replicas));
+    }
+
+    let mut spec = source_spec.clone();
+    spec.set_replicas(ReplicaSpec::new_assigned(maps));
+    spec.set_clone_from(Some(source.to_owned()));
+    Ok(spec)
+}
+
 /// create new topic and wait until all partitions are fully provisioned
 /// if any partitions are not provisioned in time, this will generate error
 async fn process_topic_request<AC: AuthContext, C: MetadataItem>(
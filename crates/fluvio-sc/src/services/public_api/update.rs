@@ -11,6 +11,8 @@ use tracing::{instrument, trace, debug, error};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_controlplane_metadata::consumer_group::ConsumerGroupSpec;
+use fluvio_controlplane_metadata::schema::SchemaSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
 use fluvio_sc_schema::objects::{ObjectApiUpdateRequest, UpdateRequest};
@@ -24,6 +26,7 @@ pub async fn handle_update_request<AC: AuthContext, C: MetadataItem>(
     request: RequestMessage<ObjectApiUpdateRequest>,
     auth_ctx: &AuthServiceContext<AC, C>,
 ) -> Result<ResponseMessage<Status>> {
+    let _guard = super::batch::admin_guard().await;
     let (header, del_req) = request.get_header_request();
 
     debug!(?del_req, "del request");
@@ -31,6 +34,13 @@ pub async fn handle_update_request<AC: AuthContext, C: MetadataItem>(
     let status = if let Some(req) = del_req.downcast()? as Option<UpdateRequest<TopicSpec>> {
         let action = req.action.clone();
         super::topic::update::handle_topic_update_request(req.key(), action, auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<UpdateRequest<ConsumerGroupSpec>> {
+        let action = req.action.clone();
+        super::consumer_group::handle_consumer_group_update_request(req.key(), action, auth_ctx)
+            .await?
+    } else if let Some(req) = del_req.downcast()? as Option<UpdateRequest<SchemaSpec>> {
+        let action = req.action.clone();
+        super::schema::handle_schema_update_request(req.key(), action, auth_ctx).await?
     } else {
         error!("unknown update request: {:#?}", del_req);
         Status::new(
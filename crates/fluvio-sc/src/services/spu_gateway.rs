@@ -0,0 +1,306 @@
+//!
+//! # SPU Gateway
+//!
+//! Single TLS port in front of all SPUs, so a cluster can be exposed through one
+//! LoadBalancer or NodePort. Clients set the TLS server name of SPU connections to
+//! `<spu-name>.<domain>`; the gateway reads it from the ClientHello and forwards the
+//! connection, unterminated, to the SPU. Connections which don't name an SPU are
+//! forwarded to the TLS endpoint of the SC.
+//!
+use std::io::{Error as IoError, ErrorKind};
+use std::net::Shutdown;
+use std::sync::Arc;
+
+use futures_util::future::try_join;
+use futures_util::io::{copy, AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, error, info, instrument};
+
+use fluvio_controlplane_metadata::spu::SpuSpec;
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::task::spawn;
+use fluvio_socket::TcpConfig;
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_stream_model::store::LocalStore;
+
+const TLS_HANDSHAKE: u8 = 0x16;
+const CLIENT_HELLO: u8 = 0x01;
+const SERVER_NAME_EXTENSION: usize = 0x0000;
+const HOST_NAME: u8 = 0x00;
+/// max length of a TLS record, plus room for compression and padding
+const MAX_RECORD_LEN: usize = 16384 + 2048;
+
+/// start gateway on `addr`, connections without SPU are forwarded to `sc_endpoint`
+pub fn start_spu_gateway<C>(
+    spus: Arc<LocalStore<SpuSpec, C>>,
+    addr: String,
+    sc_endpoint: String,
+    tcp: TcpConfig,
+) where
+    C: MetadataItem + 'static,
+{
+    info!(%addr, %sc_endpoint, "starting spu gateway");
+    let gateway = Arc::new(SpuGateway {
+        spus,
+        sc_endpoint,
+        tcp,
+    });
+    spawn(async move {
+        if let Err(err) = gateway.run(addr).await {
+            error!("spu gateway failed: {err}");
+        }
+    });
+}
+
+struct SpuGateway<C: MetadataItem> {
+    spus: Arc<LocalStore<SpuSpec, C>>,
+    sc_endpoint: String,
+    tcp: TcpConfig,
+}
+
+impl<C> SpuGateway<C>
+where
+    C: MetadataItem + 'static,
+{
+    async fn run(self: Arc<Self>, addr: String) -> Result<(), IoError> {
+        let listener = TcpListener::bind(&addr).await?;
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let gateway = self.clone();
+            spawn(async move {
+                if let Err(err) = gateway.forward(stream).await {
+                    debug!(%peer, "spu gateway connection error: {err}");
+                }
+            });
+        }
+    }
+
+    #[instrument(skip(self, inbound))]
+    async fn forward(&self, mut inbound: TcpStream) -> Result<(), IoError> {
+        self.tcp.apply(&inbound)?;
+        let client_hello = read_client_hello(&mut inbound).await?;
+        let server_name = server_name(&client_hello)?;
+        let target = self.target(server_name.as_deref()).await;
+        debug!(?server_name, %target, "forwarding connection");
+
+        let mut outbound = self.tcp.connect(&target).await?;
+        outbound.write_all(&client_hello).await?;
+
+        let upstream = async {
+            copy(inbound.clone(), &mut outbound.clone()).await?;
+            outbound.shutdown(Shutdown::Write)
+        };
+        let downstream = async {
+            copy(outbound.clone(), &mut inbound.clone()).await?;
+            inbound.shutdown(Shutdown::Write)
+        };
+        try_join(upstream, downstream).await?;
+        Ok(())
+    }
+
+    /// endpoint of the SPU named by the first label of `server_name`
+    async fn target(&self, server_name: Option<&str>) -> String {
+        let spu_name = server_name
+            .and_then(|name| name.split_once('.'))
+            .map(|(label, _)| label.to_owned());
+        let spu = match spu_name {
+            Some(name) => self.spus.value(&name).await,
+            None => None,
+        };
+        match spu {
+            Some(spu) => match &spu.inner().spec.public_endpoint_local {
+                Some(endpoint) => endpoint.to_string(),
+                None => spu.inner().spec.public_endpoint.addr(),
+            },
+            None => self.sc_endpoint.clone(),
+        }
+    }
+}
+
+/// read the first TLS record, which carries the ClientHello
+async fn read_client_hello(stream: &mut TcpStream) -> Result<Vec<u8>, IoError> {
+    let mut record = vec![0; 5];
+    stream.read_exact(&mut record).await?;
+    if record[0] != TLS_HANDSHAKE {
+        return Err(invalid("not a TLS handshake"));
+    }
+    let len = u16::from_be_bytes([record[3], record[4]]) as usize;
+    if len > MAX_RECORD_LEN {
+        return Err(invalid("TLS record too large"));
+    }
+    record.resize(5 + len, 0);
+    stream.read_exact(&mut record[5..]).await?;
+    Ok(record)
+}
+
+/// server name indication of a ClientHello record
+fn server_name(record: &[u8]) -> Result<Option<String>, IoError> {
+    let mut record = Reader(record);
+    if record.u8()? != TLS_HANDSHAKE as usize {
+        return Err(invalid("not a TLS handshake"));
+    }
+    record.take(2)?; // record version
+    let mut handshake = record.vec16()?;
+    if handshake.u8()? != CLIENT_HELLO as usize {
+        return Err(invalid("not a ClientHello"));
+    }
+    let mut hello = handshake.vec24()?;
+    hello.take(2 + 32)?; // client version and random
+    hello.vec8()?; // session id
+    hello.vec16()?; // cipher suites
+    hello.vec8()?; // compression methods
+    if hello.0.is_empty() {
+        return Ok(None);
+    }
+
+    let mut extensions = hello.vec16()?;
+    while !extensions.0.is_empty() {
+        let extension_type = extensions.u16()?;
+        let mut extension = extensions.vec16()?;
+        if extension_type != SERVER_NAME_EXTENSION {
+            continue;
+        }
+        let mut names = extension.vec16()?;
+        while !names.0.is_empty() {
+            let name_type = names.u8()?;
+            let name = names.vec16()?;
+            if name_type == HOST_NAME as usize {
+                let name = std::str::from_utf8(name.0)
+                    .map_err(|_| invalid("server name is not valid utf8"))?;
+                return Ok(Some(name.to_ascii_lowercase()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn invalid(msg: &str) -> IoError {
+    IoError::new(ErrorKind::InvalidData, msg.to_owned())
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], IoError> {
+        if self.0.len() < len {
+            return Err(invalid("truncated ClientHello"));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn uint(&mut self, bytes: usize) -> Result<usize, IoError> {
+        Ok(self
+            .take(bytes)?
+            .iter()
+            .fold(0, |value, byte| (value << 8) | *byte as usize))
+    }
+
+    fn u8(&mut self) -> Result<usize, IoError> {
+        self.uint(1)
+    }
+
+    fn u16(&mut self) -> Result<usize, IoError> {
+        self.uint(2)
+    }
+
+    fn vec8(&mut self) -> Result<Reader<'a>, IoError> {
+        let len = self.uint(1)?;
+        self.take(len).map(Reader)
+    }
+
+    fn vec16(&mut self) -> Result<Reader<'a>, IoError> {
+        let len = self.uint(2)?;
+        self.take(len).map(Reader)
+    }
+
+    fn vec24(&mut self) -> Result<Reader<'a>, IoError> {
+        let len = self.uint(3)?;
+        self.take(len).map(Reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_controlplane_metadata::spu::{Endpoint, EncryptionEnum};
+    use fluvio_stream_model::store::MetadataStoreObject;
+
+    use super::*;
+
+    fn client_hello(server_name: Option<&str>) -> Vec<u8> {
+        fn with_len(len_bytes: usize, data: &[u8]) -> Vec<u8> {
+            let mut out = data.len().to_be_bytes()[8 - len_bytes..].to_vec();
+            out.extend_from_slice(data);
+            out
+        }
+
+        let mut extensions = vec![0x00, 0x0b]; // ec point formats
+        extensions.extend(with_len(2, &[0x01, 0x00]));
+        if let Some(name) = server_name {
+            let mut entry = vec![HOST_NAME];
+            entry.extend(with_len(2, name.as_bytes()));
+            extensions.extend([0x00, 0x00]);
+            extensions.extend(with_len(2, &with_len(2, &entry)));
+        }
+
+        let mut hello = vec![0x03, 0x03];
+        hello.extend([7; 32]);
+        hello.extend(with_len(1, &[1; 32]));
+        hello.extend(with_len(2, &[0x13, 0x01, 0x13, 0x02]));
+        hello.extend(with_len(1, &[0x00]));
+        hello.extend(with_len(2, &extensions));
+
+        let mut handshake = vec![CLIENT_HELLO];
+        handshake.extend(with_len(3, &hello));
+        let mut record = vec![TLS_HANDSHAKE, 0x03, 0x01];
+        record.extend(with_len(2, &handshake));
+        record
+    }
+
+    #[test]
+    fn test_server_name() {
+        assert_eq!(
+            server_name(&client_hello(Some("Main-0.fluvio.local"))).expect("parse"),
+            Some("main-0.fluvio.local".to_owned())
+        );
+        assert_eq!(server_name(&client_hello(None)).expect("parse"), None);
+
+        let truncated = client_hello(Some("main-0.fluvio.local"));
+        assert!(server_name(&truncated[..truncated.len() - 4]).is_err());
+        assert!(server_name(b"GET / HTTP/1.1\r\n").is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_gateway_target() {
+        let spu = SpuSpec {
+            id: 5001,
+            public_endpoint_local: Some(Endpoint {
+                host: "fluvio-spu-main-0.default.svc.cluster.local".to_owned(),
+                port: 9005,
+                encryption: EncryptionEnum::PLAINTEXT,
+            }),
+            ..Default::default()
+        };
+        let gateway = SpuGateway {
+            spus: Arc::new(LocalStore::bulk_new(vec![MetadataStoreObject::<
+                SpuSpec,
+                u32,
+            >::with_spec(
+                "main-0", spu
+            )])),
+            sc_endpoint: "127.0.0.1:9003".to_owned(),
+            tcp: TcpConfig::default(),
+        };
+
+        assert_eq!(
+            gateway.target(Some("main-0.fluvio.local")).await,
+            "fluvio-spu-main-0.default.svc.cluster.local:9005"
+        );
+        assert_eq!(gateway.target(Some("fluvio.local")).await, "127.0.0.1:9003");
+        assert_eq!(
+            gateway.target(Some("main-1.fluvio.local")).await,
+            "127.0.0.1:9003"
+        );
+        assert_eq!(gateway.target(None).await, "127.0.0.1:9003");
+    }
+}
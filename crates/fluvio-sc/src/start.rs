@@ -20,6 +20,12 @@ use crate::{
 };
 
 pub fn main_loop(opt: ScOpt) {
+    let opt = opt.load_config_file_or_exit();
+    if opt.validate_config {
+        opt.check_config_or_exit();
+        return;
+    }
+
     // parse configuration (program exits on error)
     println!("CLI Option: {opt:#?}");
 
@@ -97,6 +103,7 @@ fn k8_main_loop<C>(
 
         let ctx =
             crate::init::start_main_loop((sc_config.clone(), auth_policy), client.clone()).await;
+        gateway::start_if(&ctx, &tls_option);
 
         crate::k8::controllers::run_k8_operators(
             sc_config.namespace.clone(),
@@ -106,6 +113,7 @@ fn k8_main_loop<C>(
         )
         .await;
 
+        mesh::start_if(&sc_config).await;
         proxy::start_if(sc_config, tls_option).await;
 
         println!("Streaming Controller started successfully");
@@ -129,7 +137,9 @@ fn local_main_loop<C, M>(
     run_block_on(async move {
         info!("starting local main loop");
 
-        crate::init::start_main_loop((sc_config.clone(), auth_policy), client).await;
+        let ctx = crate::init::start_main_loop((sc_config.clone(), auth_policy), client).await;
+        gateway::start_if(&ctx, &tls_option);
+        mesh::start_if(&sc_config).await;
         proxy::start_if(sc_config, tls_option).await;
 
         println!("Streaming Controller started successfully");
@@ -145,35 +155,32 @@ mod proxy {
     use tracing::info;
 
     use fluvio_types::print_cli_err;
-    pub use fluvio_future::openssl::TlsAcceptor;
-
+    use fluvio_auth::tls_proxy::start_tls_proxy;
     use fluvio_auth::x509::X509Authenticator;
-    use flv_tls_proxy::{
-        start as proxy_start, start_with_authenticator as proxy_start_with_authenticator,
-    };
+    use flv_tls_proxy::authenticator::Authenticator;
+    use fluvio_socket::{ReloadableTlsAcceptor, TLS_RELOAD_INTERVAL};
 
     use crate::{config::ScConfig, cli::TlsConfig};
 
     pub async fn start_if(sc_config: ScConfig, tls_option: Option<(String, TlsConfig)>) {
         if let Some((proxy_port, tls_config)) = tls_option {
             let tls_acceptor = tls_config
-                .try_build_tls_acceptor()
+                .try_build_reloadable_acceptor()
                 .expect("can't build tls acceptor");
+            tls_acceptor.watch(TLS_RELOAD_INTERVAL);
             start_proxy(sc_config, (tls_acceptor, proxy_port)).await;
         }
     }
 
-    async fn start_proxy(config: ScConfig, acceptor: (TlsAcceptor, String)) {
+    async fn start_proxy(config: ScConfig, acceptor: (ReloadableTlsAcceptor, String)) {
         let (tls_acceptor, proxy_addr) = acceptor;
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        let result = if let Some(x509_auth_scopes) = config.x509_auth_scopes {
-            let authenticator = Box::new(X509Authenticator::new(&x509_auth_scopes));
-            proxy_start_with_authenticator(&proxy_addr, tls_acceptor, target, authenticator).await
-        } else {
-            proxy_start(&proxy_addr, tls_acceptor, target).await
-        };
+        let authenticator = config.x509_auth_scopes.map(|x509_auth_scopes| {
+            Box::new(X509Authenticator::new(&x509_auth_scopes)) as Box<dyn Authenticator>
+        });
+        let result = start_tls_proxy(&proxy_addr, tls_acceptor, target, authenticator).await;
 
         if let Err(err) = result {
             print_cli_err!(err);
@@ -182,6 +189,62 @@ mod proxy {
     }
 }
 
+mod mesh {
+    use std::process;
+    use tracing::info;
+
+    use fluvio_auth::mesh::{start_mesh_proxy, MeshAuthenticator};
+    use fluvio_types::print_cli_err;
+
+    use crate::config::ScConfig;
+
+    pub async fn start_if(config: &ScConfig) {
+        let Some(proxy_addr) = &config.mesh_proxy_endpoint else {
+            return;
+        };
+        info!("starting mesh identity proxy: {}", proxy_addr);
+        let result = match MeshAuthenticator::new(config.x509_auth_scopes.as_deref()) {
+            Ok(authenticator) => {
+                let authenticator =
+                    authenticator.with_trusted_peers(config.mesh_trusted_peers.clone());
+                start_mesh_proxy(proxy_addr, config.public_endpoint.clone(), authenticator).await
+            }
+            Err(err) => Err(std::io::Error::other(err)),
+        };
+        if let Err(err) = result {
+            print_cli_err!(err);
+            process::exit(-1);
+        }
+    }
+}
+
+mod gateway {
+    use fluvio_stream_model::core::MetadataItem;
+
+    use crate::{cli::TlsConfig, core::SharedContext, services::start_spu_gateway};
+
+    /// connections not routed to SPUs are forwarded to the TLS proxy of the SC
+    pub fn start_if<C>(ctx: &SharedContext<C>, tls_option: &Option<(String, TlsConfig)>)
+    where
+        C: MetadataItem + 'static,
+    {
+        let config = ctx.config();
+        let (Some(addr), Some((proxy_addr, _))) = (&config.spu_gateway_endpoint, tls_option) else {
+            return;
+        };
+        let sc_endpoint = match proxy_addr.rsplit_once(':') {
+            Some(("0.0.0.0", port)) => format!("127.0.0.1:{port}"),
+            _ => proxy_addr.clone(),
+        };
+        start_spu_gateway(
+            ctx.spus().store().clone(),
+            addr.clone(),
+            sc_endpoint,
+            config.tcp.clone(),
+        );
+    }
+}
+
 async fn create_memory_client(path: PathBuf) -> Result<Arc<MemoryClient>> {
     use std::ops::Deref;
     use fluvio_sc_schema::remote_file::RemoteMetadataFile;
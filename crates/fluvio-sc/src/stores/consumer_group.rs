@@ -0,0 +1,380 @@
+//!
+//! # Consumer Group Coordinator
+//!
+//! Group members and their heartbeats are only kept in the SC memory,
+//! the status of a group records its members with their partitions.
+//! After an SC restart the members in the status are taken over, those
+//! that don't send a heartbeat leave after the session timeout.
+//!
+//! Static members join with an instance id and don't leave when they stop.
+//! A member joining with the instance id of a member still in its session
+//! takes over its partitions, the replaced member is fenced. Its requests are
+//! rejected for a session timeout, the time it has to notice it was replaced.
+//!
+//! Partitions moving between members are assigned to their new member once
+//! the previous one reported them revoked, whatever the assignment strategy.
+//!
+
+use std::collections::HashMap;
+use std::io::Error as IoError;
+use std::time::{Duration, Instant};
+
+use async_lock::Mutex;
+use tracing::{debug, info};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::PartitionCount;
+
+pub use fluvio_controlplane_metadata::consumer_group::*;
+
+use super::topic::TopicSpec;
+use super::StoreContext;
+
+#[derive(Debug)]
+struct Session {
+    heartbeat: Instant,
+    instance_id: Option<String>,
+}
+
+impl Session {
+    fn new(instance_id: Option<String>) -> Self {
+        Self {
+            heartbeat: Instant::now(),
+            instance_id,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Members {
+    sessions: HashMap<String, Session>,
+    /// members replaced by a member with their instance id, and when
+    fenced: HashMap<String, Instant>,
+}
+
+impl Members {
+    fn join(&mut self, group: &str, member_id: String, instance_id: Option<String>) -> bool {
+        if let Some(instance_id) = &instance_id {
+            let replaced: Vec<String> = self
+                .sessions
+                .iter()
+                .filter(|(id, session)| {
+                    **id != member_id && session.instance_id.as_ref() == Some(instance_id)
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in replaced {
+                info!(group, %instance_id, replaced = %id, %member_id, "static member rejoined");
+                self.sessions.remove(&id);
+                self.fenced.insert(id, Instant::now());
+            }
+        }
+        info!(group, %member_id, "member joined");
+        self.sessions
+            .insert(member_id, Session::new(instance_id))
+            .is_none()
+    }
+
+    /// forget the members fenced for longer than `timeout`
+    fn prune_fenced(&mut self, timeout: Duration, now: Instant) {
+        self.fenced
+            .retain(|_, fenced_at| now.saturating_duration_since(*fenced_at) < timeout);
+    }
+
+    fn members(&self) -> Vec<ConsumerGroupMember> {
+        self.sessions
+            .iter()
+            .map(|(id, session)| ConsumerGroupMember::new(id, session.instance_id.clone()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GroupCoordinator {
+    /// last heartbeat of the members by group, the lock serializes rebalances
+    groups: Mutex<HashMap<String, Members>>,
+}
+
+impl GroupCoordinator {
+    /// Apply a membership change, the group is rebalanced if its members changed
+    pub async fn apply<C: MetadataItem>(
+        &self,
+        groups: &StoreContext<ConsumerGroupSpec, C>,
+        topics: &StoreContext<TopicSpec, C>,
+        name: &str,
+        action: UpdateConsumerGroupAction,
+    ) -> Result<ErrorCode, IoError> {
+        let Some(group) = groups.store().value(name).await else {
+            return Ok(ErrorCode::ConsumerGroupNotFound);
+        };
+        let mut all = self.groups.lock().await;
+        let members = all
+            .entry(name.to_owned())
+            .or_insert_with(|| taken_over(&group.status));
+
+        if members.fenced.contains_key(action.member_id()) {
+            return Ok(ErrorCode::ConsumerGroupMemberFenced);
+        }
+        let mut current = group.status.clone();
+        let changed = match action {
+            UpdateConsumerGroupAction::Join { member_id } => members.join(name, member_id, None),
+            UpdateConsumerGroupAction::JoinStatic {
+                member_id,
+                instance_id,
+            } => members.join(name, member_id, Some(instance_id)),
+            UpdateConsumerGroupAction::Heartbeat { member_id } => {
+                match members.sessions.get_mut(&member_id) {
+                    Some(session) => session.heartbeat = Instant::now(),
+                    None => return Ok(ErrorCode::ConsumerGroupMemberNotFound),
+                }
+                false
+            }
+            UpdateConsumerGroupAction::Leave { member_id } => {
+                if members.sessions.remove(&member_id).is_none() {
+                    return Ok(ErrorCode::ConsumerGroupMemberNotFound);
+                }
+                info!(group = name, %member_id, "member left");
+                true
+            }
+            UpdateConsumerGroupAction::Revoked {
+                member_id,
+                generation,
+            } => {
+                if !members.sessions.contains_key(&member_id) {
+                    return Ok(ErrorCode::ConsumerGroupMemberNotFound);
+                }
+                // an earlier generation may have revoked fewer partitions
+                let member = current
+                    .members
+                    .iter_mut()
+                    .find(|member| member.id == member_id && !member.revoking.is_empty());
+                match member {
+                    Some(member) if generation == current.generation => {
+                        debug!(group = name, %member_id, revoked = ?member.revoking, "partitions released");
+                        member.revoking.clear();
+                        true
+                    }
+                    _ => false,
+                }
+            }
+        };
+
+        if changed {
+            let status = rebalanced(&group.spec, &current, topics, members).await;
+            if status != group.status {
+                groups.update_status(group.key_owned(), status).await?;
+            }
+        }
+        Ok(ErrorCode::None)
+    }
+
+    /// Remove the members without heartbeat within the session timeout and rebalance
+    /// the groups whose members or topic changed
+    pub async fn expire<C: MetadataItem>(
+        &self,
+        groups: &StoreContext<ConsumerGroupSpec, C>,
+        topics: &StoreContext<TopicSpec, C>,
+    ) -> Vec<(String, ConsumerGroupStatus)> {
+        let mut all = self.groups.lock().await;
+        let current = groups.store().clone_values().await;
+        all.retain(|name, _| current.iter().any(|group| group.key() == name));
+
+        let now = Instant::now();
+        let mut changes = vec![];
+        for group in current {
+            let members = all
+                .entry(group.key_owned())
+                .or_insert_with(|| taken_over(&group.status));
+            let timeout = Duration::from_millis(group.spec.session_timeout_ms.into());
+            members.sessions.retain(|member_id, session| {
+                let alive = now.saturating_duration_since(session.heartbeat) < timeout;
+                if !alive {
+                    info!(group = %group.key(), %member_id, "member session expired");
+                }
+                alive
+            });
+            members.prune_fenced(timeout, now);
+
+            let status = rebalanced(&group.spec, &group.status, topics, members).await;
+            if status != group.status {
+                debug!(group = %group.key(), %status, generation = status.generation, "rebalanced");
+                changes.push((group.key_owned(), status));
+            }
+        }
+        changes
+    }
+}
+
+/// members recorded in the status, as if they just sent a heartbeat
+fn taken_over(status: &ConsumerGroupStatus) -> Members {
+    let sessions = status
+        .members
+        .iter()
+        .map(|member| (member.id.clone(), Session::new(member.instance_id.clone())))
+        .collect();
+    Members {
+        sessions,
+        ..Default::default()
+    }
+}
+
+async fn rebalanced<C: MetadataItem>(
+    spec: &ConsumerGroupSpec,
+    current: &ConsumerGroupStatus,
+    topics: &StoreContext<TopicSpec, C>,
+    members: &Members,
+) -> ConsumerGroupStatus {
+    let partitions = topics
+        .store()
+        .value(&spec.topic)
+        .await
+        .map(|topic| topic.spec.partitions());
+    next_status(spec, current, partitions, &members.members())
+}
+
+/// status with the partitions of the topic assigned to `members`, the generation
+/// changes with the assignment but not when a static member only changed its id.
+/// With a cooperative strategy, partitions moving between members are revoked in
+/// one generation and assigned in a later one.
+pub(crate) fn next_status(
+    spec: &ConsumerGroupSpec,
+    current: &ConsumerGroupStatus,
+    partitions: Option<PartitionCount>,
+    members: &[ConsumerGroupMember],
+) -> ConsumerGroupStatus {
+    let assignment = assign_partitions(
+        spec.strategy,
+        partitions.unwrap_or_default(),
+        members,
+        &current.members,
+    );
+    let layout = |members: &[ConsumerGroupMember]| {
+        members
+            .iter()
+            .map(|member| {
+                (
+                    member.key().to_owned(),
+                    member.partitions.clone(),
+                    member.revoking.clone(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let generation = if layout(&assignment) == layout(&current.members) {
+        current.generation
+    } else {
+        current.generation.wrapping_add(1)
+    };
+    let (resolution, reason) = match partitions {
+        None => (
+            ConsumerGroupResolution::Invalid,
+            Some(format!("topic '{}' not found", spec.topic)),
+        ),
+        Some(_) if members.is_empty() => (ConsumerGroupResolution::Empty, None),
+        Some(_) => (ConsumerGroupResolution::Stable, None),
+    };
+    ConsumerGroupStatus {
+        resolution,
+        generation,
+        members: assignment,
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_next_status() {
+        let spec = ConsumerGroupSpec::new("orders");
+        let members = vec![
+            ConsumerGroupMember::new("a", None),
+            ConsumerGroupMember::new("b", None),
+        ];
+
+        let stable = next_status(&spec, &ConsumerGroupStatus::default(), Some(4), &members);
+        assert_eq!(stable.resolution, ConsumerGroupResolution::Stable);
+        assert_eq!(stable.generation, 1);
+        assert_eq!(stable.assignment("b"), Some([2, 3].as_slice()));
+
+        // same members and partitions, no rebalance
+        assert_eq!(next_status(&spec, &stable, Some(4), &members), stable);
+
+        // partition 2 moves to "a" once "b" released it
+        let grown = next_status(&spec, &stable, Some(6), &members);
+        assert_eq!(grown.generation, 2);
+        assert_eq!(grown.assignment("a"), Some([0, 1].as_slice()));
+        assert_eq!(grown.members[1].revoking, [2]);
+
+        let empty = next_status(&spec, &grown, Some(6), &[]);
+        assert_eq!(empty.resolution, ConsumerGroupResolution::Empty);
+        assert!(empty.members.is_empty());
+
+        let invalid = next_status(&spec, &grown, None, &members);
+        assert_eq!(invalid.resolution, ConsumerGroupResolution::Invalid);
+        assert_eq!(invalid.assignment("a"), Some([].as_slice()));
+    }
+
+    #[test]
+    fn test_static_member_rejoin() {
+        let spec = ConsumerGroupSpec::new("orders");
+        let mut members = Members::default();
+        assert!(members.join("billing", "a".to_owned(), None));
+        assert!(members.join("billing", "x".to_owned(), Some("worker-1".to_owned())));
+        let stable = next_status(
+            &spec,
+            &ConsumerGroupStatus::default(),
+            Some(4),
+            &members.members(),
+        );
+        let partitions = stable.assignment("x").expect("assigned").to_vec();
+
+        // the restarted instance takes over the partitions in the same generation
+        members.join("billing", "y".to_owned(), Some("worker-1".to_owned()));
+        assert!(members.fenced.contains_key("x"));
+        let rejoined = next_status(&spec, &stable, Some(4), &members.members());
+        assert_eq!(rejoined.generation, stable.generation);
+        assert_eq!(rejoined.assignment("y"), Some(partitions.as_slice()));
+        assert_eq!(rejoined.assignment("x"), None);
+    }
+
+    #[test]
+    fn test_prune_fenced() {
+        let mut members = Members::default();
+        members.join("billing", "x".to_owned(), Some("worker-1".to_owned()));
+        members.join("billing", "y".to_owned(), Some("worker-1".to_owned()));
+        let timeout = Duration::from_secs(10);
+
+        members.prune_fenced(timeout, Instant::now());
+        assert!(members.fenced.contains_key("x"));
+
+        members.prune_fenced(timeout, Instant::now() + timeout);
+        assert!(members.fenced.is_empty());
+    }
+
+    #[test]
+    fn test_cooperative_generations() {
+        let mut spec = ConsumerGroupSpec::new("orders");
+        spec.strategy = AssignmentStrategy::CooperativeSticky;
+        let mut members: Vec<ConsumerGroupMember> = ["a", "b"]
+            .into_iter()
+            .map(|id| ConsumerGroupMember::new(id, None))
+            .collect();
+        let stable = next_status(&spec, &ConsumerGroupStatus::default(), Some(4), &members);
+
+        members.push(ConsumerGroupMember::new("c", None));
+        let revoking = next_status(&spec, &stable, Some(4), &members);
+        assert_eq!(revoking.generation, 2);
+        assert_eq!(revoking.assignment("c"), Some([].as_slice()));
+        assert_eq!(revoking.members[1].revoking, [3]);
+
+        let mut released = revoking.clone();
+        released.members[1].revoking.clear();
+        let handed_over = next_status(&spec, &released, Some(4), &members);
+        assert_eq!(handed_over.generation, 3);
+        assert_eq!(handed_over.assignment("c"), Some([3].as_slice()));
+    }
+}
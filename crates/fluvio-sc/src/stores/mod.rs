@@ -4,6 +4,7 @@ pub mod partition;
 pub mod spg;
 pub mod smartmodule;
 pub mod tableformat;
+pub mod consumer_group;
 
 pub use crate::dispatcher::store::*;
 
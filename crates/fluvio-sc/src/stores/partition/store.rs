@@ -391,6 +391,7 @@ impl PartitonStatusExtension for PartitionStatus {
     fn merge(&mut self, other: Self) {
         self.resolution = other.resolution;
         self.size = other.size;
+        self.storage_full = other.storage_full;
         if let Some(old) = self.leader.merge(&other.leader) {
             self.replicas.push(old); // move old leader to replicas
         }
@@ -0,0 +1,426 @@
+//!
+//! # Prometheus metrics endpoint
+//!
+//! Serves `GET /metrics` in the Prometheus text exposition format over plain HTTP/1.1,
+//! one request per connection. Metrics are collected from their source on each scrape.
+//!
+//! Series of partitions are limited by a [`MetricsCardinality`]: topics which are not
+//! listed are aggregated per topic, or into a single series once there are more topics
+//! than the threshold.
+//!
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Write};
+use std::io::Error as IoError;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{debug, error, info};
+
+use fluvio_future::net::TcpListener;
+use fluvio_future::task::spawn;
+
+pub const METRICS_PATH: &str = "/metrics";
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+/// limit of the request line and headers, scrapes have no body
+const MAX_HEAD_SIZE: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+        }
+    }
+}
+
+/// Limits of the series of per partition metrics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricsCardinality {
+    /// topics keeping a series per partition, a trailing `*` matches by prefix.
+    /// Every topic if empty
+    pub partition_topics: Vec<String>,
+    /// above this number of topics, the topics not listed share a single series
+    /// without topic and partition labels
+    pub max_topics: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SeriesLevel {
+    Partition,
+    Topic,
+    Aggregate,
+}
+
+impl MetricsCardinality {
+    fn level(&self, topic: &str, over_max_topics: bool) -> SeriesLevel {
+        let listed = self
+            .partition_topics
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => topic.starts_with(prefix),
+                None => pattern == topic,
+            });
+        if listed || (self.partition_topics.is_empty() && !over_max_topics) {
+            SeriesLevel::Partition
+        } else if over_max_topics {
+            SeriesLevel::Aggregate
+        } else {
+            SeriesLevel::Topic
+        }
+    }
+}
+
+/// How the values of partitions sharing a series are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregation {
+    fn apply(&self, current: i64, value: i64) -> i64 {
+        match self {
+            Self::Sum => current + value,
+            Self::Min => current.min(value),
+            Self::Max => current.max(value),
+        }
+    }
+}
+
+/// Value of a per partition metric
+#[derive(Debug, Clone)]
+pub struct PartitionSample<'a> {
+    pub topic: &'a str,
+    pub partition: u32,
+    /// labels besides the topic and partition, aggregated series keep them
+    pub labels: Vec<(&'static str, String)>,
+    pub value: i64,
+}
+
+impl<'a> PartitionSample<'a> {
+    pub fn new(topic: &'a str, partition: u32, value: i64) -> Self {
+        Self {
+            topic,
+            partition,
+            labels: vec![],
+            value,
+        }
+    }
+
+    pub fn with_label(mut self, label: &'static str, value: impl Into<String>) -> Self {
+        self.labels.push((label, value.into()));
+        self
+    }
+}
+
+/// Metric families in the text exposition format
+#[derive(Debug, Default)]
+pub struct MetricsText(String);
+
+impl MetricsText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// start a family, its samples must follow
+    pub fn family(&mut self, name: &str, kind: MetricType, help: &str) -> &mut Self {
+        let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+        let _ = writeln!(self.0, "# HELP {name} {help}");
+        let _ = writeln!(self.0, "# TYPE {name} {}", kind.as_str());
+        self
+    }
+
+    pub fn sample(
+        &mut self,
+        name: &str,
+        labels: &[(&str, &str)],
+        value: impl Display,
+    ) -> &mut Self {
+        self.0.push_str(name);
+        if !labels.is_empty() {
+            self.0.push('{');
+            for (index, (label, label_value)) in labels.iter().enumerate() {
+                if index > 0 {
+                    self.0.push(',');
+                }
+                let label_value = label_value
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n");
+                let _ = write!(self.0, "{label}=\"{label_value}\"");
+            }
+            self.0.push('}');
+        }
+        let _ = writeln!(self.0, " {value}");
+        self
+    }
+
+    /// samples of a per partition metric within the limits of `cardinality`
+    pub fn partition_samples(
+        &mut self,
+        name: &str,
+        cardinality: &MetricsCardinality,
+        aggregation: Aggregation,
+        samples: &[PartitionSample],
+    ) -> &mut Self {
+        let topics: HashSet<&str> = samples.iter().map(|sample| sample.topic).collect();
+        let over_max_topics = cardinality
+            .max_topics
+            .is_some_and(|max_topics| topics.len() > max_topics);
+
+        let mut aggregates: BTreeMap<(Option<&str>, &[(&str, String)]), i64> = BTreeMap::new();
+        for sample in samples {
+            let topic = match cardinality.level(sample.topic, over_max_topics) {
+                SeriesLevel::Partition => {
+                    let partition = sample.partition.to_string();
+                    let mut labels =
+                        vec![("topic", sample.topic), ("partition", partition.as_str())];
+                    labels.extend(
+                        sample
+                            .labels
+                            .iter()
+                            .map(|(label, value)| (*label, &**value)),
+                    );
+                    self.sample(name, &labels, sample.value);
+                    continue;
+                }
+                SeriesLevel::Topic => Some(sample.topic),
+                SeriesLevel::Aggregate => None,
+            };
+            aggregates
+                .entry((topic, sample.labels.as_slice()))
+                .and_modify(|value| *value = aggregation.apply(*value, sample.value))
+                .or_insert(sample.value);
+        }
+
+        for ((topic, extra_labels), value) in aggregates {
+            let mut labels: Vec<(&str, &str)> =
+                topic.map(|topic| ("topic", topic)).into_iter().collect();
+            labels.extend(
+                extra_labels
+                    .iter()
+                    .map(|(label, value)| (*label, value.as_str())),
+            );
+            self.sample(name, &labels, value);
+        }
+        self
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Collects the metrics of a scrape
+#[async_trait]
+pub trait MetricsSource: Send + Sync + 'static {
+    async fn collect(&self, metrics: &mut MetricsText);
+}
+
+/// bind `addr` and serve the metrics of `source` in the background
+pub async fn start_metrics_server<S: MetricsSource>(addr: &str, source: S) -> Result<(), IoError> {
+    let listener = TcpListener::bind(addr).await?;
+    info!(%addr, "metrics server started");
+    let source = Arc::new(source);
+    spawn(async move {
+        if let Err(err) = accept(listener, source).await {
+            error!("metrics server failed: {err}");
+        }
+    });
+    Ok(())
+}
+
+async fn accept<S: MetricsSource>(listener: TcpListener, source: Arc<S>) -> Result<(), IoError> {
+    loop {
+        let (mut stream, peer) = listener.accept().await?;
+        let source = source.clone();
+        spawn(async move {
+            if let Err(err) = serve(&mut stream, source.as_ref()).await {
+                debug!(%peer, "metrics connection error: {err}");
+            }
+        });
+    }
+}
+
+async fn serve<T, S>(stream: &mut T, source: &S) -> Result<(), IoError>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+    S: MetricsSource,
+{
+    let request_line = read_request_line(stream).await?;
+    let mut parts = request_line.split(' ');
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) if target.split('?').next() == Some(METRICS_PATH) => {
+            let mut metrics = MetricsText::new();
+            source.collect(&mut metrics).await;
+            ("200 OK", metrics.0)
+        }
+        (Some("GET"), Some(_)) => ("404 Not Found", "not found\n".to_owned()),
+        _ => ("405 Method Not Allowed", "method not allowed\n".to_owned()),
+    };
+
+    let head = format!(
+        "HTTP/1.1 {status}\r\ncontent-type: {CONTENT_TYPE}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.flush().await
+}
+
+/// read the request head, only its first line is used
+async fn read_request_line<T: AsyncRead + Unpin>(stream: &mut T) -> Result<String, IoError> {
+    let mut buf = Vec::with_capacity(512);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|window| window == b"\r\n\r\n") {
+        if buf.len() > MAX_HEAD_SIZE {
+            return Err(IoError::other("request head too large"));
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    }
+    let head = String::from_utf8_lossy(&buf);
+    Ok(head.lines().next().unwrap_or_default().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_future::net::TcpStream;
+
+    use super::*;
+
+    struct TestSource;
+
+    #[async_trait]
+    impl MetricsSource for TestSource {
+        async fn collect(&self, metrics: &mut MetricsText) {
+            metrics
+                .family("test_records_total", MetricType::Counter, "records seen")
+                .sample("test_records_total", &[("topic", "t\"1")], 3);
+        }
+    }
+
+    #[test]
+    fn test_metrics_text() {
+        let mut metrics = MetricsText::new();
+        metrics
+            .family("fluvio_leo", MetricType::Gauge, "end offset\nof partitions")
+            .sample("fluvio_leo", &[("topic", "a"), ("partition", "0")], 10)
+            .sample("fluvio_leo", &[], 1.5);
+        assert_eq!(
+            metrics.as_str(),
+            "# HELP fluvio_leo end offset\\nof partitions\n\
+             # TYPE fluvio_leo gauge\n\
+             fluvio_leo{topic=\"a\",partition=\"0\"} 10\n\
+             fluvio_leo 1.5\n"
+        );
+    }
+
+    #[test]
+    fn test_partition_samples() {
+        let samples = [
+            PartitionSample::new("orders", 0, 10),
+            PartitionSample::new("orders", 1, 5),
+            PartitionSample::new("payments", 0, 3),
+            PartitionSample::new("logs-a", 0, 2).with_label("follower", "5002"),
+            PartitionSample::new("logs-b", 0, 4).with_label("follower", "5002"),
+        ];
+        let render = |cardinality: MetricsCardinality, aggregation: Aggregation| {
+            let mut metrics = MetricsText::new();
+            metrics.partition_samples("m", &cardinality, aggregation, &samples);
+            metrics.as_str().to_owned()
+        };
+
+        let all = render(MetricsCardinality::default(), Aggregation::Sum);
+        assert_eq!(all.lines().count(), 5);
+        assert!(all.contains("m{topic=\"logs-a\",partition=\"0\",follower=\"5002\"} 2\n"));
+
+        // topics not listed are aggregated per topic
+        let listed = render(
+            MetricsCardinality {
+                partition_topics: vec!["orders".to_owned()],
+                max_topics: None,
+            },
+            Aggregation::Sum,
+        );
+        assert_eq!(
+            listed,
+            "m{topic=\"orders\",partition=\"0\"} 10\n\
+             m{topic=\"orders\",partition=\"1\"} 5\n\
+             m{topic=\"logs-a\",follower=\"5002\"} 2\n\
+             m{topic=\"logs-b\",follower=\"5002\"} 4\n\
+             m{topic=\"payments\"} 3\n"
+        );
+
+        // then into a single series above the threshold
+        let over = render(
+            MetricsCardinality {
+                partition_topics: vec!["logs-*".to_owned()],
+                max_topics: Some(3),
+            },
+            Aggregation::Max,
+        );
+        assert_eq!(
+            over,
+            "m{topic=\"logs-a\",partition=\"0\",follower=\"5002\"} 2\n\
+             m{topic=\"logs-b\",partition=\"0\",follower=\"5002\"} 4\n\
+             m 10\n"
+        );
+
+        let aggregated = render(
+            MetricsCardinality {
+                partition_topics: vec![],
+                max_topics: Some(3),
+            },
+            Aggregation::Sum,
+        );
+        assert_eq!(aggregated, "m 18\nm{follower=\"5002\"} 6\n");
+    }
+
+    #[fluvio_future::test]
+    async fn test_metrics_server() {
+        let port = portpicker::pick_unused_port().expect("No free ports left");
+        let addr = format!("127.0.0.1:{port}");
+        start_metrics_server(&addr, TestSource)
+            .await
+            .expect("metrics server");
+
+        let get = |request: &'static str| {
+            let addr = addr.clone();
+            async move {
+                let mut stream = TcpStream::connect(&addr).await.expect("connect");
+                stream.write_all(request.as_bytes()).await.expect("write");
+                let mut response = String::new();
+                stream
+                    .read_to_string(&mut response)
+                    .await
+                    .expect("response");
+                response
+            }
+        };
+
+        let response = get("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("content-type: text/plain; version=0.0.4\r\n"));
+        assert!(response.ends_with("test_records_total{topic=\"t\\\"1\"} 3\n"));
+
+        let response = get("GET /health HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let response = get("POST /metrics HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+}
@@ -14,7 +14,7 @@ use fluvio_future::net::{TcpListener, TcpStream};
 use fluvio_future::task::spawn;
 use fluvio_protocol::api::ApiMessage;
 use fluvio_protocol::Decoder as FluvioDecoder;
-use fluvio_socket::FluvioSocket;
+use fluvio_socket::{FluvioSocket, TcpConfig};
 use fluvio_types::event::StickyEvent;
 
 pub struct ConnectInfo {
@@ -51,6 +51,7 @@ pub struct FluvioApiServer<R, A, C, S> {
     context: C,
     service: Arc<S>,
     addr: String,
+    tcp: TcpConfig,
 }
 
 impl<R, A, C, S> fmt::Debug for FluvioApiServer<R, A, C, S> {
@@ -70,8 +71,15 @@ where
             service: Arc::new(service),
             context,
             addr,
+            tcp: TcpConfig::default(),
         }
     }
+
+    /// TCP options set on accepted connections
+    pub fn with_tcp_config(mut self, tcp: TcpConfig) -> Self {
+        self.tcp = tcp;
+        self
+    }
 }
 
 impl<R, A, C, S> FluvioApiServer<R, A, C, S>
@@ -106,6 +114,9 @@ where
             match incoming {
                 Ok(stream) => {
                     info!("Received connection, spawning request handler");
+                    if let Err(err) = self.tcp.apply(&stream) {
+                        error!("Error setting tcp options: {}", err);
+                    }
                     let context = self.context.clone();
                     let service = self.service.clone();
                     let host = self.addr.clone();
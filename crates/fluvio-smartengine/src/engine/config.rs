@@ -1,12 +1,15 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use derive_builder::Builder;
 
 use fluvio_protocol::Version;
-use fluvio_smartmodule::SMARTMODULE_TIMESTAMPS_VERSION;
+use fluvio_smartmodule::SMARTMODULE_HEADERS_VERSION;
 use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
 
-pub const DEFAULT_SMARTENGINE_VERSION: Version = SMARTMODULE_TIMESTAMPS_VERSION;
+use super::LookupState;
+
+pub const DEFAULT_SMARTENGINE_VERSION: Version = SMARTMODULE_HEADERS_VERSION;
 
 /// Initial seed data to passed, this will be send back as part of the output
 #[derive(Debug, Clone)]
@@ -40,6 +43,15 @@ pub struct SmartModuleConfig {
     pub(crate) version: Option<i16>,
     #[builder(default)]
     pub(crate) lookback: Option<Lookback>,
+    /// limits of the micro-batches passed to batch SmartModules
+    #[builder(default, setter(strip_option))]
+    pub(crate) micro_batch: Option<MicroBatch>,
+    /// state read by the `lookup` calls of the SmartModule, lookups find nothing without it
+    #[builder(default)]
+    pub(crate) lookup: Option<Arc<LookupState>>,
+    /// topic of the records the SmartModule fails on, the chain stops at the first failure without it
+    #[builder(default)]
+    pub(crate) dead_letter: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,6 +60,17 @@ pub enum Lookback {
     Age { age: Duration, last: u64 },
 }
 
+/// Bounds of the micro-batches a batch SmartModule is called with
+///
+/// Without bounds the SmartModule receives every batch whole.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MicroBatch {
+    /// maximum number of records per call
+    pub max_records: Option<usize>,
+    /// maximum difference between the timestamps of the records of a call
+    pub max_span: Option<Duration>,
+}
+
 impl SmartModuleConfigBuilder {
     /// add initial parameters
     pub fn param(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
@@ -86,6 +109,9 @@ impl From<crate::transformation::TransformationStep> for SmartModuleConfig {
                 .into(),
             version: None,
             lookback: step.lookback.map(|l| l.into()),
+            micro_batch: None,
+            lookup: None,
+            dead_letter: step.dead_letter.map(|dead_letter| dead_letter.topic),
         }
     }
 }
@@ -0,0 +1,73 @@
+use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
+use fluvio_smartmodule::Record;
+
+/// error reported by the SmartModule, or the trap of a panic
+pub const DEAD_LETTER_ERROR_HEADER: &str = "fluvio.dead-letter.error";
+/// offset of the record in the topic it was read from
+pub const DEAD_LETTER_OFFSET_HEADER: &str = "fluvio.dead-letter.offset";
+/// kind of the SmartModule that failed
+pub const DEAD_LETTER_KIND_HEADER: &str = "fluvio.dead-letter.kind";
+
+/// Record that failed a SmartModule configured with a dead letter topic.
+///
+/// The chain skips the record and keeps processing the next ones, the failures are
+/// collected with [`crate::SmartModuleChainInstance::take_dead_letters`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    /// topic the record is routed to
+    pub topic: String,
+    pub error: SmartModuleTransformRuntimeError,
+}
+
+impl DeadLetter {
+    /// failed record as it was given to the SmartModule, with the failure in its headers
+    pub fn to_record(&self) -> Record {
+        let mut record = Record::new(self.error.record_value.clone());
+        record.key.clone_from(&self.error.record_key);
+        record.add_header(DEAD_LETTER_ERROR_HEADER, self.error.hint.as_str());
+        record.add_header(DEAD_LETTER_OFFSET_HEADER, self.error.offset.to_string());
+        record.add_header(DEAD_LETTER_KIND_HEADER, self.error.kind.to_string());
+        record
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fluvio_protocol::link::smartmodule::SmartModuleKind;
+
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_record() {
+        let failed = Record::new_key_value("user-1", "not a number");
+        let dead_letter = DeadLetter {
+            topic: "failures".to_owned(),
+            error: SmartModuleTransformRuntimeError::new(
+                &failed,
+                10,
+                SmartModuleKind::Map,
+                fluvio_smartmodule::eyre!("invalid digit"),
+            ),
+        };
+
+        let record = dead_letter.to_record();
+        assert_eq!(
+            record.key().map(|key| key.as_ref()),
+            Some(b"user-1".as_ref())
+        );
+        assert_eq!(record.value().as_ref(), b"not a number");
+        assert!(record
+            .header(DEAD_LETTER_ERROR_HEADER)
+            .is_some_and(|it| it.as_ref().starts_with(b"invalid digit")));
+        assert_eq!(
+            record
+                .header(DEAD_LETTER_OFFSET_HEADER)
+                .map(|it| it.as_ref()),
+            Some(b"10".as_ref())
+        );
+        assert_eq!(
+            record.header(DEAD_LETTER_KIND_HEADER).map(|it| it.as_ref()),
+            Some(b"Map".as_ref())
+        );
+    }
+}
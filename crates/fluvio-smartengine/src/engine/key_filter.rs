@@ -0,0 +1,100 @@
+use anyhow::Result;
+use regex::bytes::Regex;
+
+use fluvio_smartmodule::Record;
+use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
+
+use super::Version;
+
+/// Filter on record keys evaluated natively, without a WASM module.
+/// Records without key never match.
+#[derive(Debug, Clone)]
+pub enum KeyFilter {
+    Equals(Vec<u8>),
+    Regex(Regex),
+}
+
+impl KeyFilter {
+    pub fn equals(key: impl Into<Vec<u8>>) -> Self {
+        Self::Equals(key.into())
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self> {
+        Ok(Self::Regex(Regex::new(pattern)?))
+    }
+
+    pub fn matches(&self, key: Option<&[u8]>) -> bool {
+        match (self, key) {
+            (Self::Equals(expected), Some(key)) => expected.as_slice() == key,
+            (Self::Regex(regex), Some(key)) => regex.is_match(key),
+            (_, None) => false,
+        }
+    }
+
+    /// keep the records of `input` matching all `filters`
+    pub(crate) fn apply(
+        filters: &[KeyFilter],
+        input: SmartModuleInput,
+        version: Version,
+    ) -> Result<SmartModuleInput> {
+        let base_offset = input.base_offset();
+        let base_timestamp = input.base_timestamp();
+        #[allow(deprecated)]
+        let records: Vec<Record> = input
+            .try_into_records(version)?
+            .into_iter()
+            .filter(|record| {
+                let key = record.key().map(|key| key.as_ref());
+                filters.iter().all(|filter| filter.matches(key))
+            })
+            .collect();
+        let mut output = SmartModuleInput::try_from_records(records, version)?;
+        output.set_base_offset(base_offset);
+        output.set_base_timestamp(base_timestamp);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DEFAULT_SMARTENGINE_VERSION;
+
+    use super::*;
+
+    #[test]
+    fn test_key_filter() {
+        let equals = KeyFilter::equals("order-1");
+        assert!(equals.matches(Some(b"order-1")));
+        assert!(!equals.matches(Some(b"order-10")));
+        assert!(!equals.matches(None));
+
+        let regex = KeyFilter::regex("^order-[0-9]$").expect("regex");
+        assert!(regex.matches(Some(b"order-1")));
+        assert!(!regex.matches(Some(b"order-10")));
+        assert!(KeyFilter::regex("(").is_err());
+    }
+
+    #[test]
+    fn test_apply_key_filters() {
+        let records = vec![
+            Record::new_key_value("order-1", "a"),
+            Record::new("no key"),
+            Record::new_key_value("user-1", "b"),
+            Record::new_key_value("order-2", "c"),
+        ];
+        let input = SmartModuleInput::try_from_records(records, DEFAULT_SMARTENGINE_VERSION)
+            .expect("input");
+        let filters = [KeyFilter::regex("^order-").expect("regex")];
+
+        let output =
+            KeyFilter::apply(&filters, input, DEFAULT_SMARTENGINE_VERSION).expect("filter");
+        #[allow(deprecated)]
+        let values: Vec<_> = output
+            .try_into_records(DEFAULT_SMARTENGINE_VERSION)
+            .expect("records")
+            .into_iter()
+            .map(|record| record.value().as_utf8_lossy_string().to_string())
+            .collect();
+        assert_eq!(values, ["a", "c"]);
+    }
+}
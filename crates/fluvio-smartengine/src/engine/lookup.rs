@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Key-value state SmartModules read with `fluvio_smartmodule::lookup`, such as
+/// the account tier of each user id.
+///
+/// The state is filled by the host and may be shared by several chains, updates
+/// are seen by the next lookup of every SmartModule reading it.
+#[derive(Debug, Default)]
+pub struct LookupState {
+    entries: RwLock<HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl LookupState {
+    pub fn new(entries: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>) -> Self {
+        Self {
+            entries: RwLock::new(entries.into_iter().collect()),
+        }
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.entries.read().unwrap().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: Vec<u8>, value: Vec<u8>) {
+        self.entries.write().unwrap().insert(key, value);
+    }
+
+    pub fn remove(&self, key: &[u8]) {
+        self.entries.write().unwrap().remove(key);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LookupState;
+
+    #[test]
+    fn test_lookup_state() {
+        let state = LookupState::new([(b"user-1".to_vec(), b"gold".to_vec())]);
+        assert_eq!(state.get(b"user-1"), Some(b"gold".to_vec()));
+        assert_eq!(state.get(b"user-2"), None);
+
+        state.insert(b"user-2".to_vec(), b"free".to_vec());
+        state.remove(b"user-1");
+        assert_eq!(state.get(b"user-1"), None);
+        assert_eq!(state.get(b"user-2"), Some(b"free".to_vec()));
+        assert_eq!(state.len(), 1);
+    }
+}
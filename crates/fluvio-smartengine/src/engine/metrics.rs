@@ -1,4 +1,5 @@
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,9 @@ pub struct SmartModuleChainMetrics {
     records_out: AtomicU64,
     invocation_count: AtomicU64,
     fuel_used: AtomicU64,
+    /// time spent running SmartModules
+    #[serde(default)]
+    execution_micros: AtomicU64,
 }
 
 impl SmartModuleChainMetrics {
@@ -24,6 +28,11 @@ impl SmartModuleChainMetrics {
         self.fuel_used.fetch_add(value, Ordering::SeqCst);
     }
 
+    pub fn add_execution_time(&self, value: Duration) {
+        self.execution_micros
+            .fetch_add(value.as_micros() as u64, Ordering::SeqCst);
+    }
+
     pub fn bytes_in(&self) -> u64 {
         self.bytes_in.load(Ordering::SeqCst)
     }
@@ -38,4 +47,8 @@ impl SmartModuleChainMetrics {
     pub fn invocation_count(&self) -> u64 {
         self.invocation_count.load(Ordering::SeqCst)
     }
+
+    pub fn execution_time(&self) -> Duration {
+        Duration::from_micros(self.execution_micros.load(Ordering::SeqCst))
+    }
 }
@@ -1,7 +1,11 @@
 /// SmartModule configuration
 
 mod config;
+mod dead_letter;
 mod error;
+mod key_filter;
+mod lookup;
+mod projection;
 mod wasmtime;
 
 #[cfg(test)]
@@ -9,10 +13,16 @@ mod fixture;
 
 pub mod metrics;
 
+pub use dead_letter::{
+    DeadLetter, DEAD_LETTER_ERROR_HEADER, DEAD_LETTER_KIND_HEADER, DEAD_LETTER_OFFSET_HEADER,
+};
 pub use error::EngineError;
+pub use key_filter::KeyFilter;
+pub use lookup::LookupState;
+pub use projection::Projection;
 pub use config::{
     SmartModuleConfig, SmartModuleConfigBuilder, SmartModuleConfigBuilderError,
-    SmartModuleInitialData, Lookback, DEFAULT_SMARTENGINE_VERSION,
+    SmartModuleInitialData, Lookback, MicroBatch, DEFAULT_SMARTENGINE_VERSION,
 };
 
 pub type WasmSlice = (i32, i32, u32);
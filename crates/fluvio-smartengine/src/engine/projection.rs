@@ -0,0 +1,129 @@
+use anyhow::{anyhow, Result};
+use serde_json::{Map, Value};
+
+use fluvio_smartmodule::{Record, RecordData};
+
+/// Projection of JSON record values on a list of JSON pointers, evaluated natively without
+/// a WASM module. Each value keeps only the fields found, nested along their pointers.
+/// Values that are not JSON are left as they are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection(Vec<Field>);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    pointer: String,
+    /// unescaped reference tokens of the pointer
+    path: Vec<String>,
+}
+
+impl Projection {
+    pub fn new<S: Into<String>>(pointers: impl IntoIterator<Item = S>) -> Result<Self> {
+        let fields = pointers
+            .into_iter()
+            .map(|pointer| {
+                let pointer = pointer.into();
+                if !pointer.is_empty() && !pointer.starts_with('/') {
+                    return Err(anyhow!("invalid JSON pointer: {pointer}"));
+                }
+                let path = pointer
+                    .split('/')
+                    .skip(1)
+                    .map(|token| token.replace("~1", "/").replace("~0", "~"))
+                    .collect();
+                Ok(Field { pointer, path })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self(fields))
+    }
+
+    /// replace the value of each record with its projection
+    pub(crate) fn apply(&self, records: &mut [Record]) {
+        for record in records {
+            if let Some(projected) = self.project(record.value.as_ref()) {
+                record.value = RecordData::from(projected);
+            }
+        }
+    }
+
+    fn project(&self, value: &[u8]) -> Option<Vec<u8>> {
+        let value: Value = serde_json::from_slice(value).ok()?;
+        let mut projected = Value::Object(Map::new());
+        for field in &self.0 {
+            if let Some(found) = value.pointer(&field.pointer) {
+                insert(&mut projected, &field.path, found.clone());
+            }
+        }
+        serde_json::to_vec(&projected).ok()
+    }
+}
+
+fn insert(target: &mut Value, path: &[String], value: Value) {
+    let Some((last, parents)) = path.split_last() else {
+        *target = value;
+        return;
+    };
+    let mut current = object(target);
+    for token in parents {
+        current = object(
+            current
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(Map::new())),
+        );
+    }
+    current.insert(last.clone(), value);
+}
+
+/// `value` as an object, replaced by an empty object if it is not one
+fn object(value: &mut Value) -> &mut Map<String, Value> {
+    if !value.is_object() {
+        *value = Value::Object(Map::new());
+    }
+    match value {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn project(pointers: &[&str], value: &Value) -> Value {
+        let projection = Projection::new(pointers.iter().copied()).expect("projection");
+        let mut records = vec![Record::new(serde_json::to_vec(value).expect("json"))];
+        projection.apply(&mut records);
+        serde_json::from_slice(records[0].value.as_ref()).expect("json")
+    }
+
+    #[test]
+    fn test_projection() {
+        let order = json!({
+            "id": 7,
+            "user": {"name": "ann", "email": "ann@example.com"},
+            "items": [{"sku": "a"}, {"sku": "b"}],
+            "a/b": true,
+        });
+
+        assert_eq!(
+            project(&["/id", "/user/name"], &order),
+            json!({"id": 7, "user": {"name": "ann"}})
+        );
+        assert_eq!(
+            project(&["/items/1/sku", "/a~1b", "/missing"], &order),
+            json!({"items": {"1": {"sku": "b"}}, "a/b": true})
+        );
+        assert_eq!(project(&[""], &order), order);
+    }
+
+    #[test]
+    fn test_projection_keeps_other_values() {
+        let projection = Projection::new(["/id"]).expect("projection");
+        let mut records = vec![Record::new("not json")];
+        projection.apply(&mut records);
+        assert_eq!(records[0].value.as_ref(), b"not json");
+
+        assert!(Projection::new(["id"]).is_err());
+    }
+}
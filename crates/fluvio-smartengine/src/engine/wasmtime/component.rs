@@ -0,0 +1,207 @@
+//! SmartModules built as WASM components implementing the `smartmodule` world of
+//! `wit/smartmodule.wit`.
+//!
+//! Core modules exporting `filter`, `map` and the other functions of the original ABI
+//! keep running through [`super::transforms`]. Components receive and return typed
+//! records instead of buffers encoded with the fluvio protocol.
+
+use std::io::Cursor;
+
+use anyhow::{anyhow, Result};
+use wasmtime::AsContextMut;
+
+use fluvio_protocol::{Decoder, Version};
+use fluvio_protocol::record::{Offset, Record};
+use fluvio_protocol::types::Timestamp;
+use fluvio_protocol::link::smartmodule::{
+    SmartModuleInitRuntimeError, SmartModuleKind, SmartModuleLookbackRuntimeError,
+    SmartModuleTransformRuntimeError,
+};
+use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleInput, SmartModuleOutput};
+
+use crate::engine::SmartModuleInitialData;
+
+use super::instance::{SmartModuleInstanceContext, SmartModuleTransform};
+use super::state::WasmState;
+
+wasmtime::component::bindgen!({
+    path: "wit",
+    world: "smartmodule",
+});
+
+const COMPONENT_LAYER: [u8; 2] = [0x01, 0x00];
+
+/// Whether the binary is a component rather than a core module
+///
+/// Both start with the `\0asm` magic. Core modules follow it with a 4 bytes version
+/// while components use a 2 bytes version and a layer of 1.
+pub(crate) fn is_component(bytes: &[u8]) -> bool {
+    bytes.len() >= 8 && bytes.starts_with(b"\0asm") && bytes[6..8] == COMPONENT_LAYER
+}
+
+pub(crate) struct ComponentTransform {
+    version: Version,
+}
+
+impl ComponentTransform {
+    /// Initialize the component, which keeps the accumulator of aggregates itself
+    pub(crate) fn instantiate(
+        ctx: &SmartModuleInstanceContext,
+        initial_data: SmartModuleInitialData,
+        store: &mut impl AsContextMut,
+    ) -> Result<Self> {
+        let smartmodule = ctx
+            .component()
+            .ok_or_else(|| anyhow!("SmartModule is not a component"))?;
+        let params: Vec<(String, String)> = ctx
+            .params()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let accumulator = match &initial_data {
+            SmartModuleInitialData::Aggregate { accumulator } => Some(accumulator.as_slice()),
+            SmartModuleInitialData::None => None,
+        };
+        smartmodule
+            .call_init(store, &params, accumulator)?
+            .map_err(|hint| SmartModuleInitRuntimeError { hint })?;
+        Ok(Self {
+            version: ctx.version(),
+        })
+    }
+}
+
+impl SmartModuleTransform for ComponentTransform {
+    fn process(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let smartmodule = ctx
+            .component()
+            .ok_or_else(|| anyhow!("SmartModule is not a component"))?;
+        let base_offset = input.base_offset();
+        let base_timestamp = input.base_timestamp();
+        let records = to_component_records(input, self.version)?;
+
+        let output = smartmodule.call_process(store, &records)?;
+        let successes = output
+            .successes
+            .into_iter()
+            .map(|record| from_component_record(record, base_offset, base_timestamp))
+            .collect();
+        let error = output.error.map(|error| SmartModuleTransformRuntimeError {
+            hint: error.hint,
+            offset: error.offset,
+            kind: SmartModuleKind::Generic,
+            record_key: error.key.map(Into::into),
+            record_value: error.value.into(),
+        });
+        Ok(SmartModuleOutput::with_error(successes, error))
+    }
+
+    fn name(&self) -> &str {
+        "component"
+    }
+}
+
+pub(crate) fn call_look_back(
+    input: SmartModuleInput,
+    ctx: &SmartModuleInstanceContext,
+    store: &mut impl AsContextMut,
+) -> Result<()> {
+    let smartmodule = ctx
+        .component()
+        .ok_or_else(|| anyhow!("SmartModule is not a component"))?;
+    let records = to_component_records(input, ctx.version())?;
+    smartmodule
+        .call_look_back(store, &records)?
+        .map_err(|error| SmartModuleLookbackRuntimeError {
+            hint: error.hint,
+            offset: error.offset,
+            record_key: error.key.map(Into::into),
+            record_value: error.value.into(),
+        })?;
+    Ok(())
+}
+
+fn to_component_records(
+    input: SmartModuleInput,
+    version: Version,
+) -> Result<Vec<SmartmoduleRecord>> {
+    let base_offset = input.base_offset();
+    let base_timestamp = input.base_timestamp();
+    let records: Vec<Record> =
+        Decoder::decode_from(&mut Cursor::new(input.into_raw_bytes()), version)?;
+    Ok(records
+        .into_iter()
+        .map(|record| SmartmoduleRecord {
+            offset: base_offset + record.offset_delta(),
+            timestamp: base_timestamp + record.timestamp_delta(),
+            key: record.key.map(|key| key.as_ref().to_vec()),
+            value: record.value.as_ref().to_vec(),
+        })
+        .collect())
+}
+
+fn from_component_record(
+    record: SmartmoduleRecord,
+    base_offset: Offset,
+    base_timestamp: Timestamp,
+) -> Record {
+    let mut output = Record {
+        key: record.key.map(Into::into),
+        value: record.value.into(),
+        ..Default::default()
+    };
+    let header = output.get_mut_header();
+    header.set_offset_delta(record.offset - base_offset);
+    header.set_timestamp_delta(record.timestamp - base_timestamp);
+    output
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::engine::config::DEFAULT_SMARTENGINE_VERSION;
+
+    use super::*;
+
+    #[test]
+    fn test_is_component() {
+        let core = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+        let component = [0x00, 0x61, 0x73, 0x6d, 0x0d, 0x00, 0x01, 0x00];
+        assert!(!is_component(&core));
+        assert!(is_component(&component));
+        assert!(!is_component(b"\0asm"));
+        assert!(!is_component(b"not wasm"));
+    }
+
+    #[test]
+    fn test_record_conversion() {
+        let records = vec![Record::new_key_value("a", "apple"), Record::new("banana")];
+        let mut input = SmartModuleInput::try_from_records(records, DEFAULT_SMARTENGINE_VERSION)
+            .expect("input");
+        input.set_base_offset(100);
+        input.set_base_timestamp(1000);
+        let converted = to_component_records(input, DEFAULT_SMARTENGINE_VERSION).expect("records");
+        assert_eq!(converted.len(), 2);
+        assert_eq!(converted[0].key.as_deref(), Some(b"a".as_ref()));
+        assert_eq!(converted[1].value, b"banana");
+
+        let record = from_component_record(
+            SmartmoduleRecord {
+                offset: 101,
+                timestamp: 1005,
+                key: None,
+                value: b"BANANA".to_vec(),
+            },
+            100,
+            1000,
+        );
+        assert_eq!(record.offset_delta(), 1);
+        assert_eq!(record.timestamp_delta(), 5);
+        assert_eq!(record.value.as_ref(), b"BANANA");
+    }
+}
@@ -1,16 +1,21 @@
 use std::fmt::{self, Debug};
 use std::future::Future;
+use std::time::Instant;
 
 use anyhow::Result;
-use fluvio_smartmodule::Record;
+use fluvio_smartmodule::{Record, SMARTMODULE_HEADERS_VERSION};
 use tracing::debug;
 use wasmtime::{Engine, Module};
+use wasmtime::component::Component;
 
+use fluvio_protocol::link::smartmodule::{SmartModuleKind, SmartModuleTransformRuntimeError};
 use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleInput, SmartModuleOutput};
 
 use crate::SmartModuleConfig;
 use crate::engine::config::{Lookback, DEFAULT_SMARTENGINE_VERSION};
+use crate::engine::{DeadLetter, KeyFilter, Projection, Version};
 
+use super::component::is_component;
 use super::init::SmartModuleInit;
 use super::instance::{SmartModuleInstance, SmartModuleInstanceContext};
 
@@ -31,6 +36,7 @@ impl SmartEngine {
     pub fn new() -> Self {
         let mut config = wasmtime::Config::default();
         config.consume_fuel(true);
+        config.wasm_component_model(true);
         Self(Engine::new(&config).expect("Config is static"))
     }
 
@@ -48,6 +54,9 @@ impl Debug for SmartEngine {
 /// Building SmartModule
 pub struct SmartModuleChainBuilder {
     smart_modules: Vec<(SmartModuleConfig, Vec<u8>)>,
+    key_filters: Vec<KeyFilter>,
+    key_filter_version: Version,
+    projection: Option<Projection>,
     store_limiter: StoreResourceLimiter,
 }
 
@@ -57,6 +66,17 @@ impl SmartModuleChainBuilder {
         self.smart_modules.push((config, bytes))
     }
 
+    /// Add a built-in key filter, key filters run before the SmartModules of the chain
+    pub fn add_key_filter(&mut self, filter: KeyFilter, version: Version) {
+        self.key_filters.push(filter);
+        self.key_filter_version = version;
+    }
+
+    /// Add a built-in projection, it runs on the output of the last SmartModule of the chain
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.projection = Some(projection);
+    }
+
     pub fn set_store_memory_limit(&mut self, max_memory_bytes: usize) {
         self.store_limiter.set_memory_size(max_memory_bytes);
     }
@@ -66,19 +86,39 @@ impl SmartModuleChainBuilder {
         let mut instances = Vec::with_capacity(self.smart_modules.len());
         let mut state = engine.new_state(self.store_limiter);
         for (config, bytes) in self.smart_modules {
-            let module = Module::new(&engine.0, bytes)?;
             let version = config.version();
-            let ctx = SmartModuleInstanceContext::instantiate(
-                &mut state,
-                module,
-                config.params,
-                version,
-                config.lookback,
-            )?;
+            let ctx = if is_component(&bytes) {
+                let component = Component::new(&engine.0, bytes)?;
+                SmartModuleInstanceContext::instantiate_component(
+                    &mut state,
+                    component,
+                    config.params,
+                    version,
+                    config.lookback,
+                )?
+            } else {
+                let module = Module::new(&engine.0, bytes)?;
+                SmartModuleInstanceContext::instantiate(
+                    &mut state,
+                    module,
+                    config.params,
+                    version,
+                    config.lookback,
+                    config.lookup,
+                )?
+            };
             let init = SmartModuleInit::try_instantiate(&ctx, &mut state)?;
             let look_back = SmartModuleLookBack::try_instantiate(&ctx, &mut state)?;
-            let transform = create_transform(&ctx, config.initial_data, &mut state)?;
-            let mut instance = SmartModuleInstance::new(ctx, init, look_back, transform, version);
+            let transform =
+                create_transform(&ctx, config.initial_data, config.micro_batch, &mut state)?;
+            let mut instance = SmartModuleInstance::new(
+                ctx,
+                init,
+                look_back,
+                transform,
+                version,
+                config.dead_letter,
+            );
 
             instance.call_init(&mut state)?;
             instances.push(instance);
@@ -87,6 +127,10 @@ impl SmartModuleChainBuilder {
         Ok(SmartModuleChainInstance {
             store: state,
             instances,
+            key_filters: self.key_filters,
+            key_filter_version: self.key_filter_version,
+            projection: self.projection,
+            dead_letters: Vec::new(),
         })
     }
 }
@@ -97,6 +141,9 @@ impl Default for SmartModuleChainBuilder {
         store_limiter.set_memory_size(DEFAULT_STORE_MEMORY_LIMIT);
         Self {
             smart_modules: Default::default(),
+            key_filters: Default::default(),
+            key_filter_version: DEFAULT_SMARTENGINE_VERSION,
+            projection: None,
             store_limiter,
         }
     }
@@ -114,6 +161,10 @@ impl<T: Into<Vec<u8>>> From<(SmartModuleConfig, T)> for SmartModuleChainBuilder
 pub struct SmartModuleChainInstance {
     store: WasmState,
     instances: Vec<SmartModuleInstance>,
+    key_filters: Vec<KeyFilter>,
+    key_filter_version: Version,
+    projection: Option<Projection>,
+    dead_letters: Vec<DeadLetter>,
 }
 
 impl Debug for SmartModuleChainInstance {
@@ -144,21 +195,30 @@ impl SmartModuleChainInstance {
         let base_offset = input.base_offset();
         let base_timestamp = input.base_timestamp();
 
+        let input = if self.key_filters.is_empty() {
+            input
+        } else {
+            KeyFilter::apply(&self.key_filters, input, self.key_filter_version)?
+        };
+
         if let Some((last, instances)) = self.instances.split_last_mut() {
             let mut next_input = input;
 
             for instance in instances {
                 // pass raw inputs to transform instance
                 // each raw input may result in multiple records
-                self.store.top_up_fuel();
-                let output = instance.process(next_input, &mut self.store)?;
-                let fuel_used = self.store.get_used_fuel();
-                debug!(fuel_used, "fuel used");
-                metric.add_fuel_used(fuel_used);
+                let mut output = process_instance(
+                    instance,
+                    next_input,
+                    &mut self.store,
+                    metric,
+                    &mut self.dead_letters,
+                )?;
 
                 if let Some(ref smerr) = output.error {
                     // encountered error, we stop processing and return partial output
                     tracing::error!(err=?smerr);
+                    project(self.projection.as_ref(), &mut output.successes);
                     return Ok(output);
                 } else {
                     next_input =
@@ -168,26 +228,35 @@ impl SmartModuleChainInstance {
                 }
             }
 
-            self.store.top_up_fuel();
-            let output = last.process(next_input, &mut self.store)?;
+            let mut output = process_instance(
+                last,
+                next_input,
+                &mut self.store,
+                metric,
+                &mut self.dead_letters,
+            )?;
             if let Some(ref smerr) = output.error {
                 tracing::error!(err=?smerr);
             }
-            let fuel_used = self.store.get_used_fuel();
-            debug!(fuel_used, "fuel used");
-            metric.add_fuel_used(fuel_used);
+            project(self.projection.as_ref(), &mut output.successes);
             let records_out = output.successes.len();
             metric.add_records_out(records_out as u64);
             debug!(records_out, "sm records out");
             Ok(output)
         } else {
             #[allow(deprecated)]
-            let records = input.try_into_records(DEFAULT_SMARTENGINE_VERSION)?;
+            let mut records = input.try_into_records(DEFAULT_SMARTENGINE_VERSION)?;
+            project(self.projection.as_ref(), &mut records);
 
             Ok(SmartModuleOutput::new(records))
         }
     }
 
+    /// Records that failed SmartModules with a dead letter topic since the last call
+    pub fn take_dead_letters(&mut self) -> Vec<DeadLetter> {
+        std::mem::take(&mut self.dead_letters)
+    }
+
     pub async fn look_back<F, R>(
         &mut self,
         read_fn: F,
@@ -203,8 +272,11 @@ impl SmartModuleChainInstance {
             if let Some(lookback) = instance.lookback() {
                 debug!("look_back on instance");
                 let records: Vec<Record> = read_fn(lookback).await?;
-                let input: SmartModuleInput =
+                let mut input: SmartModuleInput =
                     SmartModuleInput::try_from_records(records, instance.version())?;
+                if instance.version() < SMARTMODULE_HEADERS_VERSION {
+                    input.strip_record_headers()?;
+                }
 
                 metrics.add_bytes_in(input.raw_bytes().len() as u64);
                 self.store.top_up_fuel();
@@ -222,6 +294,121 @@ impl SmartModuleChainInstance {
     }
 }
 
+fn call_instance(
+    instance: &mut SmartModuleInstance,
+    input: SmartModuleInput,
+    store: &mut WasmState,
+    metric: &SmartModuleChainMetrics,
+) -> Result<SmartModuleOutput> {
+    store.top_up_fuel();
+    let start = Instant::now();
+    let output = instance.process(input, store);
+    metric.add_execution_time(start.elapsed());
+    let fuel_used = store.get_used_fuel();
+    debug!(fuel_used, "fuel used");
+    metric.add_fuel_used(fuel_used);
+    output
+}
+
+fn project(projection: Option<&Projection>, records: &mut [Record]) {
+    if let Some(projection) = projection {
+        projection.apply(records);
+    }
+}
+
+/// Without dead letter topic, the output stops at the first failure of the SmartModule.
+/// Otherwise failing records are added to `dead_letters` and the records after them
+/// are processed again. A trap does not tell the record it failed on, so the records
+/// are then processed one by one.
+fn process_instance(
+    instance: &mut SmartModuleInstance,
+    mut input: SmartModuleInput,
+    store: &mut WasmState,
+    metric: &SmartModuleChainMetrics,
+    dead_letters: &mut Vec<DeadLetter>,
+) -> Result<SmartModuleOutput> {
+    if instance.version() < SMARTMODULE_HEADERS_VERSION {
+        input.strip_record_headers()?;
+    }
+    let Some(topic) = instance.dead_letter().map(str::to_owned) else {
+        return call_instance(instance, input, store, metric);
+    };
+    let version = instance.version();
+    let base_offset = input.base_offset();
+    let base_timestamp = input.base_timestamp();
+    let records_input = |records: Vec<Record>| -> Result<SmartModuleInput> {
+        let mut input = SmartModuleInput::try_from_records(records, version)?;
+        input.set_base_offset(base_offset);
+        input.set_base_timestamp(base_timestamp);
+        Ok(input)
+    };
+
+    let mut successes = Vec::new();
+    let mut pending = Some(input);
+    while let Some(input) = pending.take() {
+        match call_instance(instance, input.clone(), store, metric) {
+            Ok(output) => {
+                successes.extend(output.successes);
+                let Some(error) = output.error else {
+                    break;
+                };
+                debug!(offset = error.offset, %topic, "record routed to dead letter topic");
+                let failed_offset = error.offset;
+                dead_letters.push(DeadLetter {
+                    topic: topic.clone(),
+                    error,
+                });
+
+                #[allow(deprecated)]
+                let remaining: Vec<Record> = input
+                    .try_into_records(version)?
+                    .into_iter()
+                    .filter(|record| base_offset + record.preamble.offset_delta() > failed_offset)
+                    .collect();
+                if !remaining.is_empty() {
+                    pending = Some(records_input(remaining)?);
+                }
+            }
+            Err(err) => {
+                #[allow(deprecated)]
+                for record in input.try_into_records(version)? {
+                    let trap = match call_instance(
+                        instance,
+                        records_input(vec![record.clone()])?,
+                        store,
+                        metric,
+                    ) {
+                        Ok(output) => {
+                            successes.extend(output.successes);
+                            if let Some(error) = output.error {
+                                dead_letters.push(DeadLetter {
+                                    topic: topic.clone(),
+                                    error,
+                                });
+                            }
+                            continue;
+                        }
+                        Err(trap) => trap,
+                    };
+                    debug!(%trap, %topic, "record routed to dead letter topic");
+                    dead_letters.push(DeadLetter {
+                        topic: topic.clone(),
+                        error: SmartModuleTransformRuntimeError::new(
+                            &record,
+                            base_offset,
+                            SmartModuleKind::Generic(Default::default()),
+                            fluvio_smartmodule::eyre!("{trap:#}"),
+                        ),
+                    });
+                }
+                debug!(%err, "SmartModule failed, records were processed one by one");
+            }
+        }
+    }
+
+    Ok(SmartModuleOutput::new(successes))
+}
+
 #[cfg(test)]
 mod test {
 
@@ -628,4 +815,55 @@ mod chaining_test {
             if max == max_memory
         ))
     }
+
+    const SM_MAP_DOUBLE: &str = "fluvio_wasm_map_double";
+
+    #[ignore]
+    #[test]
+    fn test_chain_dead_letter() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+        let metrics = SmartModuleChainMetrics::default();
+
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .dead_letter(Some("failures".to_owned()))
+                .build()
+                .unwrap(),
+            read_wasm_module(SM_MAP_DOUBLE),
+        );
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder().build().unwrap(),
+            read_wasm_module(SM_MAP_DOUBLE),
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        let input = vec![Record::new("1"), Record::new("x"), Record::new("3")];
+        let output = chain
+            .process(
+                SmartModuleInput::try_from_records(input, DEFAULT_SMARTENGINE_VERSION)
+                    .expect("input"),
+                &metrics,
+            )
+            .expect("process");
+
+        // the failing record is skipped by the first SmartModule only
+        assert!(output.error.is_none());
+        let values: Vec<&[u8]> = output
+            .successes
+            .iter()
+            .map(|record| record.value.as_ref())
+            .collect();
+        assert_eq!(values, vec![b"4".as_ref(), b"12".as_ref()]);
+
+        let dead_letters = chain.take_dead_letters();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].topic, "failures");
+        assert_eq!(dead_letters[0].error.offset, 1);
+        assert_eq!(dead_letters[0].error.record_value.as_ref(), b"x");
+        assert!(chain.take_dead_letters().is_empty());
+    }
 }
@@ -5,6 +5,7 @@ use std::fmt::{self, Debug};
 use tracing::debug;
 use anyhow::{Error, Result};
 use wasmtime::{Memory, Module, Caller, Extern, Instance, Func, AsContextMut, AsContext};
+use wasmtime::component::Component;
 
 use fluvio_protocol::{Encoder, Decoder, Version};
 
@@ -13,7 +14,9 @@ use fluvio_smartmodule::dataplane::smartmodule::{
 };
 
 use crate::engine::config::Lookback;
+use crate::engine::LookupState;
 
+use super::component::Smartmodule;
 use super::error::EngineError;
 use super::init::SmartModuleInit;
 use super::look_back::SmartModuleLookBack;
@@ -26,6 +29,7 @@ pub(crate) struct SmartModuleInstance {
     look_back: Option<SmartModuleLookBack>,
     transform: Box<dyn DowncastableTransform>,
     version: Version,
+    dead_letter: Option<String>,
 }
 
 impl SmartModuleInstance {
@@ -46,6 +50,7 @@ impl SmartModuleInstance {
         look_back: Option<SmartModuleLookBack>,
         transform: Box<dyn DowncastableTransform>,
         version: Version,
+        dead_letter: Option<String>,
     ) -> Self {
         Self {
             ctx,
@@ -53,6 +58,7 @@ impl SmartModuleInstance {
             look_back,
             transform,
             version,
+            dead_letter,
         }
     }
 
@@ -98,10 +104,20 @@ impl SmartModuleInstance {
     pub fn version(&self) -> Version {
         self.version
     }
+
+    pub(crate) fn dead_letter(&self) -> Option<&str> {
+        self.dead_letter.as_deref()
+    }
+}
+
+/// Instance of a core module using the original ABI or of a component
+enum WasmInstance {
+    Core(Instance),
+    Component(Box<Smartmodule>),
 }
 
 pub(crate) struct SmartModuleInstanceContext {
-    instance: Instance,
+    instance: WasmInstance,
     records_cb: Arc<RecordsCallBack>,
     params: SmartModuleExtraParams,
     version: Version,
@@ -116,13 +132,14 @@ impl Debug for SmartModuleInstanceContext {
 
 impl SmartModuleInstanceContext {
     /// instantiate new module instance that contain context
-    #[tracing::instrument(skip(state, module, params))]
+    #[tracing::instrument(skip(state, module, params, lookup))]
     pub(crate) fn instantiate(
         state: &mut WasmState,
         module: Module,
         params: SmartModuleExtraParams,
         version: Version,
         lookback: Option<Lookback>,
+        lookup: Option<Arc<LookupState>>,
     ) -> Result<Self, EngineError> {
         debug!("creating WasmModuleInstance");
         let cb = Arc::new(RecordsCallBack::new());
@@ -142,13 +159,13 @@ impl SmartModuleInstanceContext {
 
         debug!("instantiating WASMtime");
         let instance = state
-            .instantiate(&module, copy_records_fn)
+            .instantiate(&module, copy_records_fn, lookup)
             .map_err(|e| match e.downcast::<EngineError>() {
                 Ok(e) => e,
                 Err(e) => EngineError::Instantiate(e),
             })?;
         Ok(Self {
-            instance,
+            instance: WasmInstance::Core(instance),
             records_cb,
             params,
             version,
@@ -156,9 +173,49 @@ impl SmartModuleInstanceContext {
         })
     }
 
-    /// get wasm function from instance
+    /// instantiate component implementing the smartmodule world
+    #[tracing::instrument(skip(state, component, params))]
+    pub(crate) fn instantiate_component(
+        state: &mut WasmState,
+        component: Component,
+        params: SmartModuleExtraParams,
+        version: Version,
+        lookback: Option<Lookback>,
+    ) -> Result<Self, EngineError> {
+        debug!("instantiating WASMtime component");
+        let smartmodule = state
+            .instantiate_component(&component)
+            .map_err(EngineError::Instantiate)?;
+        Ok(Self {
+            instance: WasmInstance::Component(Box::new(smartmodule)),
+            records_cb: Arc::new(RecordsCallBack::new()),
+            params,
+            version,
+            lookback,
+        })
+    }
+
+    /// get wasm function from instance, components do not export any
     pub(crate) fn get_wasm_func(&self, store: &mut impl AsContextMut, name: &str) -> Option<Func> {
-        self.instance.get_func(store, name)
+        match &self.instance {
+            WasmInstance::Core(instance) => instance.get_func(store, name),
+            WasmInstance::Component(_) => None,
+        }
+    }
+
+    pub(crate) fn component(&self) -> Option<&Smartmodule> {
+        match &self.instance {
+            WasmInstance::Core(_) => None,
+            WasmInstance::Component(smartmodule) => Some(smartmodule.as_ref()),
+        }
+    }
+
+    pub(crate) fn params(&self) -> &SmartModuleExtraParams {
+        &self.params
+    }
+
+    pub(crate) fn version(&self) -> Version {
+        self.version
     }
 
     pub(crate) fn write_input<E: Encoder>(
@@ -174,7 +231,10 @@ impl SmartModuleInstanceContext {
             version = self.version,
             "input encoded"
         );
-        let array_ptr = memory::copy_memory_to_instance(store, &self.instance, &input_data)?;
+        let WasmInstance::Core(instance) = &self.instance else {
+            anyhow::bail!("components do not share memory with the engine");
+        };
+        let array_ptr = memory::copy_memory_to_instance(store, instance, &input_data)?;
         let length = input_data.len();
         Ok((array_ptr as i32, length as i32, self.version as u32))
     }
@@ -7,12 +7,17 @@ use fluvio_smartmodule::dataplane::smartmodule::{
 };
 use wasmtime::{AsContextMut, TypedFunc};
 
+use super::component;
 use super::instance::SmartModuleInstanceContext;
 
 const LOOKBACK_FN_NAME: &str = "look_back";
 type LookBackFn = TypedFunc<(i32, i32, u32), i32>;
 
-pub(crate) struct SmartModuleLookBack(LookBackFn);
+pub(crate) enum SmartModuleLookBack {
+    Core(LookBackFn),
+    /// components always export `look-back`
+    Component,
+}
 
 impl Debug for SmartModuleLookBack {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -25,12 +30,15 @@ impl SmartModuleLookBack {
         ctx: &SmartModuleInstanceContext,
         store: &mut impl AsContextMut,
     ) -> Result<Option<Self>> {
+        if ctx.component().is_some() {
+            return Ok(Some(Self::Component));
+        }
         match ctx.get_wasm_func(store, LOOKBACK_FN_NAME) {
             // check type signature
             Some(func) => func
                 .typed(&mut *store)
                 .or_else(|_| func.typed(store))
-                .map(Self)
+                .map(Self::Core)
                 .map(Some),
             None => Ok(None),
         }
@@ -42,8 +50,12 @@ impl SmartModuleLookBack {
         ctx: &mut SmartModuleInstanceContext,
         store: &mut impl AsContextMut,
     ) -> Result<()> {
+        let look_back_fn = match self {
+            Self::Core(look_back_fn) => look_back_fn,
+            Self::Component => return component::call_look_back(input, ctx, store),
+        };
         let slice = ctx.write_input(&input, &mut *store)?;
-        let output = self.0.call(&mut *store, slice)?;
+        let output = look_back_fn.call(&mut *store, slice)?;
 
         if output < 0 {
             let internal_error = SmartModuleLookbackErrorStatus::try_from(output)
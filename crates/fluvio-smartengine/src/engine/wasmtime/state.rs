@@ -1,11 +1,15 @@
 use std::cmp::max;
+use std::sync::Arc;
 
 use anyhow::Error;
 use wasmtime::{
-    AsContext, AsContextMut, Engine, Instance, IntoFunc, Module, Store, StoreContext,
-    StoreContextMut,
+    AsContext, AsContextMut, Caller, Engine, Extern, Instance, IntoFunc, Module, Store,
+    StoreContext, StoreContextMut,
 };
 
+use crate::engine::LookupState;
+
+use super::component::Smartmodule;
 use super::limiter::StoreResourceLimiter;
 
 // DO NOT INCREASE THIS VALUE HIGHER THAN i64::MAX / 2.
@@ -13,6 +17,12 @@ use super::limiter::StoreResourceLimiter;
 // up to a values close to i64:MAX
 const DEFAULT_FUEL: u64 = i64::MAX as u64 / 2;
 
+/// `lookup_value(key_ptr, key_len, value_ptr, value_capacity) -> i32` imported by
+/// SmartModules calling `lookup`. Returns -1 when the key is missing, otherwise the
+/// length of the value, which is copied only when it fits in the capacity.
+const LOOKUP_FN: &str = "lookup_value";
+const MISSING_KEY: i32 = -1;
+
 #[derive(Debug)]
 pub struct WasmState(Store<Context>);
 
@@ -69,6 +79,7 @@ impl WasmState {
         &mut self,
         module: &Module,
         host_fn: impl IntoFunc<<Self as AsContext>::Data, Params, Args>,
+        lookup: Option<Arc<LookupState>>,
     ) -> Result<Instance, Error> {
         let mut linker = wasmtime::Linker::new(module.engine());
         wasi_common::sync::add_to_linker(&mut linker, |c: &mut Context| &mut c.wasi_ctx)?;
@@ -81,8 +92,43 @@ impl WasmState {
             copy_records_fn_import.name(),
             host_fn,
         )?;
+        if let Some(lookup_fn_import) = module.imports().find(|import| import.name().eq(LOOKUP_FN))
+        {
+            linker.func_wrap(
+                lookup_fn_import.module(),
+                lookup_fn_import.name(),
+                move |mut caller: Caller<'_, Context>,
+                      key_ptr: i32,
+                      key_len: i32,
+                      value_ptr: i32,
+                      value_capacity: i32| {
+                    let memory = match caller.get_export("memory") {
+                        Some(Extern::Memory(mem)) => mem,
+                        _ => anyhow::bail!("failed to find host memory"),
+                    };
+                    let mut key = vec![0u8; key_len as u32 as usize];
+                    memory.read(&caller, key_ptr as u32 as usize, &mut key)?;
+                    let Some(value) = lookup.as_ref().and_then(|state| state.get(&key)) else {
+                        return Ok(MISSING_KEY);
+                    };
+                    if value.len() <= value_capacity as u32 as usize {
+                        memory.write(&mut caller, value_ptr as u32 as usize, &value)?;
+                    }
+                    Ok(i32::try_from(value.len())?)
+                },
+            )?;
+        }
         linker.instantiate(self, module)
     }
+
+    pub(crate) fn instantiate_component(
+        &mut self,
+        component: &wasmtime::component::Component,
+    ) -> Result<Smartmodule, Error> {
+        // the world has no imports, so no WASI is linked
+        let linker = wasmtime::component::Linker::new(component.engine());
+        Smartmodule::instantiate(self, component, &linker)
+    }
 }
 
 impl std::fmt::Debug for Context {
@@ -0,0 +1,257 @@
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::io::Cursor;
+
+use tracing::debug;
+use anyhow::Result;
+use wasmtime::{AsContextMut, TypedFunc};
+
+use fluvio_protocol::Decoder;
+use fluvio_protocol::record::Record;
+use fluvio_protocol::types::Timestamp;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleInput, SmartModuleOutput, SmartModuleTransformErrorStatus,
+};
+
+use crate::engine::MicroBatch;
+use crate::engine::wasmtime::{
+    instance::{SmartModuleInstanceContext, SmartModuleTransform},
+    state::WasmState,
+};
+
+pub(crate) const BATCH_FN_NAME: &str = "batch";
+
+type WasmBatchFn = TypedFunc<(i32, i32, u32), i32>;
+
+pub(crate) struct SmartModuleBatch {
+    batch_fn: WasmBatchFn,
+    micro_batch: Option<MicroBatch>,
+}
+
+impl Debug for SmartModuleBatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BatchFn")
+    }
+}
+
+impl SmartModuleBatch {
+    pub fn try_instantiate(
+        ctx: &SmartModuleInstanceContext,
+        micro_batch: Option<MicroBatch>,
+        store: &mut impl AsContextMut,
+    ) -> Result<Option<Self>> {
+        match ctx.get_wasm_func(&mut *store, BATCH_FN_NAME) {
+            // check type signature
+            Some(func) => func
+                .typed(&mut *store)
+                .or_else(|_| func.typed(store))
+                .map(|batch_fn| {
+                    Some(Self {
+                        batch_fn,
+                        micro_batch,
+                    })
+                }),
+            None => Ok(None),
+        }
+    }
+
+    fn call(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let slice = ctx.write_input(&input, &mut *store)?;
+        let batch_output = self.batch_fn.call(&mut *store, slice)?;
+
+        if batch_output < 0 {
+            let internal_error = SmartModuleTransformErrorStatus::try_from(batch_output)
+                .unwrap_or(SmartModuleTransformErrorStatus::UnknownError);
+            return Err(internal_error.into());
+        }
+
+        ctx.read_output(store)
+    }
+}
+
+impl SmartModuleTransform for SmartModuleBatch {
+    fn process(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let Some(micro_batch) = self.micro_batch else {
+            return self.call(input, ctx, store);
+        };
+
+        let base_offset = input.base_offset();
+        let base_timestamp = input.base_timestamp();
+        let records: Vec<Record> =
+            Decoder::decode_from(&mut Cursor::new(input.into_raw_bytes()), ctx.version())?;
+
+        let mut output = SmartModuleOutput::default();
+        for records in split(records, &micro_batch, base_timestamp) {
+            debug!(records = records.len(), "calling micro-batch");
+            // records keep their deltas, so the base of the whole batch still applies
+            let mut input = SmartModuleInput::try_from_records(records, ctx.version())?;
+            input.set_base_offset(base_offset);
+            input.set_base_timestamp(base_timestamp);
+
+            let micro_output = self.call(input, ctx, store)?;
+            output.successes.extend(micro_output.successes);
+            if micro_output.error.is_some() {
+                // later micro-batches are not processed, like the records after an error
+                output.error = micro_output.error;
+                break;
+            }
+        }
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        BATCH_FN_NAME
+    }
+}
+
+/// Split records in consecutive micro-batches within the bounds
+fn split(
+    records: Vec<Record>,
+    micro_batch: &MicroBatch,
+    base_timestamp: Timestamp,
+) -> Vec<Vec<Record>> {
+    let max_records = micro_batch.max_records.unwrap_or(usize::MAX).max(1);
+    let max_span = micro_batch
+        .max_span
+        .map(|span| span.as_millis() as Timestamp);
+
+    let mut micro_batches: Vec<Vec<Record>> = vec![];
+    let mut current: Vec<Record> = vec![];
+    let mut first_timestamp = base_timestamp;
+    for record in records {
+        let timestamp = base_timestamp + record.timestamp_delta();
+        let full = current.len() >= max_records;
+        let too_long = max_span
+            .map(|span| !current.is_empty() && timestamp - first_timestamp > span)
+            .unwrap_or(false);
+        if full || too_long {
+            micro_batches.push(std::mem::take(&mut current));
+        }
+        if current.is_empty() {
+            first_timestamp = timestamp;
+        }
+        current.push(record);
+    }
+    if !current.is_empty() {
+        micro_batches.push(current);
+    }
+    micro_batches
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use fluvio_protocol::record::Record;
+    use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
+
+    use crate::engine::{
+        MicroBatch, SmartEngine, SmartModuleChainBuilder, SmartModuleConfig,
+        metrics::SmartModuleChainMetrics,
+    };
+    use crate::engine::config::DEFAULT_SMARTENGINE_VERSION;
+    use crate::engine::fixture::read_wasm_module;
+
+    use super::{split, BATCH_FN_NAME};
+
+    const SM_BATCH_DEDUP: &str = "fluvio_smartmodule_batch_dedup";
+
+    fn record(timestamp_delta: i64) -> Record {
+        let mut record = Record::new(timestamp_delta.to_string());
+        record.get_mut_header().set_timestamp_delta(timestamp_delta);
+        record
+    }
+
+    fn timestamps(micro_batches: &[Vec<Record>]) -> Vec<Vec<i64>> {
+        micro_batches
+            .iter()
+            .map(|records| records.iter().map(|r| r.timestamp_delta()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_by_count() {
+        let records = (0..5).map(record).collect();
+        let micro_batch = MicroBatch {
+            max_records: Some(2),
+            max_span: None,
+        };
+        assert_eq!(
+            timestamps(&split(records, &micro_batch, 1000)),
+            vec![vec![0, 1], vec![2, 3], vec![4]]
+        );
+    }
+
+    #[test]
+    fn test_split_by_span() {
+        let records = [0, 5, 10, 11, 30].into_iter().map(record).collect();
+        let micro_batch = MicroBatch {
+            max_records: Some(10),
+            max_span: Some(Duration::from_millis(10)),
+        };
+        assert_eq!(
+            timestamps(&split(records, &micro_batch, 1000)),
+            vec![vec![0, 5, 10], vec![11], vec![30]]
+        );
+
+        let micro_batch = MicroBatch::default();
+        assert_eq!(split(vec![], &micro_batch, 0).len(), 0);
+        let records = (0..3).map(record).collect();
+        assert_eq!(split(records, &micro_batch, 0).len(), 1);
+    }
+
+    #[ignore]
+    #[test]
+    fn test_batch_dedup() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .micro_batch(MicroBatch {
+                    max_records: Some(3),
+                    max_span: None,
+                })
+                .build()
+                .unwrap(),
+            read_wasm_module(SM_BATCH_DEDUP),
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        assert_eq!(
+            chain.instances().first().expect("first").transform().name(),
+            BATCH_FN_NAME
+        );
+
+        let metrics = SmartModuleChainMetrics::default();
+
+        let input = ["a", "b", "a", "a", "c", "c"]
+            .into_iter()
+            .map(Record::new)
+            .collect();
+        let output = chain
+            .process(
+                SmartModuleInput::try_from_records(input, DEFAULT_SMARTENGINE_VERSION)
+                    .expect("input"),
+                &metrics,
+            )
+            .expect("process");
+        // duplicates are only dropped within a micro-batch
+        let values: Vec<&[u8]> = output.successes.iter().map(|r| r.value.as_ref()).collect();
+        assert_eq!(values, vec![b"a".as_ref(), b"b", b"a", b"c"]);
+    }
+}
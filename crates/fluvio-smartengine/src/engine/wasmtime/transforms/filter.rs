@@ -58,6 +58,44 @@ mod test {
         assert_eq!(output.successes[0].value.as_ref(), b"apple");
     }
 
+    #[ignore]
+    #[test]
+    fn test_filter_record_headers() {
+        use fluvio_smartmodule::SMARTMODULE_HEADERS_VERSION;
+
+        let engine = SmartEngine::new();
+        let metrics = SmartModuleChainMetrics::default();
+
+        for (version, expected_headers) in [
+            (SMARTMODULE_HEADERS_VERSION - 1, 0),
+            (SMARTMODULE_HEADERS_VERSION, 1),
+        ] {
+            let mut chain_builder = SmartModuleChainBuilder::default();
+            chain_builder.add_smart_module(
+                SmartModuleConfig::builder()
+                    .version(version)
+                    .build()
+                    .unwrap(),
+                read_wasm_module(SM_FILTER),
+            );
+            let mut chain = chain_builder
+                .initialize(&engine)
+                .expect("failed to build chain");
+
+            let mut record = Record::new("apple");
+            record.add_header("content-type", "text/plain");
+            let output = chain
+                .process(
+                    SmartModuleInput::try_from_records(vec![record], DEFAULT_SMARTENGINE_VERSION)
+                        .expect("input"),
+                    &metrics,
+                )
+                .expect("process");
+            assert_eq!(output.successes.len(), 1);
+            assert_eq!(output.successes[0].headers().len(), expected_headers);
+        }
+    }
+
     #[ignore]
     #[test]
     fn test_filter_with_init_invalid_param() {
@@ -1,12 +1,14 @@
 #[cfg(test)]
 mod test {
 
+    use std::sync::Arc;
+
     use fluvio_protocol::record::Record;
     use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
 
     use crate::engine::{
-        SmartEngine, SmartModuleChainBuilder, SmartModuleConfig, metrics::SmartModuleChainMetrics,
-        wasmtime::transforms::simple_transform::MAP_FN_NAME,
+        LookupState, SmartEngine, SmartModuleChainBuilder, SmartModuleConfig,
+        metrics::SmartModuleChainMetrics, wasmtime::transforms::simple_transform::MAP_FN_NAME,
     };
     use crate::engine::fixture::read_wasm_module;
     use crate::engine::config::DEFAULT_SMARTENGINE_VERSION;
@@ -46,4 +48,55 @@ mod test {
         assert_eq!(output.successes[0].value.as_ref(), b"APPLE");
         assert_eq!(output.successes[1].value.as_ref(), b"FRUIT");
     }
+
+    const SM_MAP_LOOKUP: &str = "fluvio_smartmodule_map_lookup";
+
+    #[ignore]
+    #[test]
+    fn test_map_lookup() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+        let state = Arc::new(LookupState::new([(b"user-1".to_vec(), b"gold".to_vec())]));
+
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .lookup(Some(state.clone()))
+                .build()
+                .unwrap(),
+            read_wasm_module(SM_MAP_LOOKUP),
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        let metrics = SmartModuleChainMetrics::default();
+        let input = vec![
+            Record::new_key_value("user-1", "unknown"),
+            Record::new_key_value("user-2", "unknown"),
+        ];
+        let output = chain
+            .process(
+                SmartModuleInput::try_from_records(input, DEFAULT_SMARTENGINE_VERSION)
+                    .expect("input"),
+                &metrics,
+            )
+            .expect("process");
+        assert_eq!(output.successes[0].value.as_ref(), b"gold");
+        assert_eq!(output.successes[1].value.as_ref(), b"unknown");
+
+        // updates of the state are seen by the next lookups
+        state.insert(b"user-2".to_vec(), b"free".to_vec());
+        let output = chain
+            .process(
+                SmartModuleInput::try_from_records(
+                    vec![Record::new_key_value("user-2", "unknown")],
+                    DEFAULT_SMARTENGINE_VERSION,
+                )
+                .expect("input"),
+                &metrics,
+            )
+            .expect("process");
+        assert_eq!(output.successes[0].value.as_ref(), b"free");
+    }
 }
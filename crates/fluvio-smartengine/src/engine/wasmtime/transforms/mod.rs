@@ -3,6 +3,7 @@ mod map;
 mod array_map;
 mod filter_map;
 mod aggregate;
+mod batch;
 pub(crate) use instance::create_transform;
 mod simple_transform;
 
@@ -13,19 +14,27 @@ mod instance {
 
     use crate::engine::{error::EngineError, SmartModuleInitialData};
     use super::super::instance::{SmartModuleInstanceContext, DowncastableTransform};
+    use super::super::component::ComponentTransform;
 
     use super::{
         simple_transform::{
             SimpleTansform, FILTER_FN_NAME, MAP_FN_NAME, FILTER_MAP_FN_NAME, ARRAY_MAP_FN_NAME,
         },
         aggregate::SmartModuleAggregate,
+        batch::SmartModuleBatch,
     };
+    use crate::engine::MicroBatch;
 
     pub(crate) fn create_transform(
         ctx: &SmartModuleInstanceContext,
         initial_data: SmartModuleInitialData,
+        micro_batch: Option<MicroBatch>,
         store: &mut impl AsContextMut,
     ) -> Result<Box<dyn DowncastableTransform>> {
+        if ctx.component().is_some() {
+            return ComponentTransform::instantiate(ctx, initial_data, store)
+                .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>);
+        }
         if let Some(tr) = SimpleTansform::try_instantiate(FILTER_FN_NAME, ctx, store)?
             .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
         {
@@ -42,6 +51,10 @@ mod instance {
             .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
         {
             Ok(tr)
+        } else if let Some(tr) = SmartModuleBatch::try_instantiate(ctx, micro_batch, store)?
+            .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
+        {
+            Ok(tr)
         } else if let Some(tr) = SmartModuleAggregate::try_instantiate(ctx, initial_data, store)?
             .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
         {
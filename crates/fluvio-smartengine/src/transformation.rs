@@ -54,6 +54,15 @@ pub struct TransformationStep {
     pub lookback: Option<Lookback>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub with: BTreeMap<String, JsonString>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter: Option<DeadLetterConfig>,
+}
+
+/// Routes the records the SmartModule fails on to `topic`, the records after them
+/// are still processed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DeadLetterConfig {
+    pub topic: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -202,7 +211,8 @@ mod tests {
                         with: BTreeMap::from([(
                             "spec".to_string(),
                             JsonString("[{\"operation\":\"shift\",\"spec\":{\"payload\":{\"device\":\"device\"}}},{\"operation\":\"default\",\"spec\":{\"device\":{\"type\":\"mobile\"}}}]".to_string())
-                        )])
+                        )]),
+                        dead_letter: None,
                     },
                     TransformationStep {
                         uses: "infinyon/jolt@0.1.0".to_string(),
@@ -210,7 +220,8 @@ mod tests {
                         with: BTreeMap::from([(
                             "spec".to_string(),
                             JsonString("[{\"operation\":\"shift\",\"spec\":{\"payload\":{\"device\":\"device\"}}},{\"operation\":\"default\",\"spec\":{\"device\":{\"type\":\"mobile\"}}}]".to_string())
-                        )])
+                        )]),
+                        dead_letter: None,
                     },
                     TransformationStep {
                         uses: "infinyon/json-sql@0.1.0".to_string(),
@@ -218,12 +229,33 @@ mod tests {
                         with: BTreeMap::from([(
                             "mapping".to_string(),
                             JsonString("{\"map-columns\":{\"device_id\":{\"json-key\":\"device.device_id\",\"value\":{\"default\":\"0\",\"required\":true,\"type\":\"int\"}},\"record\":{\"json-key\":\"$\",\"value\":{\"required\":true,\"type\":\"jsonb\"}}},\"table\":\"topic_message_demo\"}".to_string())
-                        )])
+                        )]),
+                        dead_letter: None,
                     }
                 ]
             }
         )
     }
+    #[test]
+    fn test_read_dead_letter() {
+        let config: TransformationConfig = serde_yaml::from_str(
+            r#"
+            transforms:
+              - uses: infinyon/jolt@0.1.0
+                dead_letter:
+                  topic: jolt-failures
+            "#,
+        )
+        .expect("config");
+
+        assert_eq!(
+            config.transforms[0].dead_letter,
+            Some(DeadLetterConfig {
+                topic: "jolt-failures".to_owned()
+            })
+        );
+    }
+
     #[test]
     fn test_from_empty_vec() {
         //given
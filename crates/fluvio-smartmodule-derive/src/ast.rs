@@ -32,6 +32,7 @@ pub enum SmartModuleKind {
     Map,
     ArrayMap,
     FilterMap,
+    Batch,
 }
 
 impl Display for SmartModuleKind {
@@ -44,6 +45,7 @@ impl Display for SmartModuleKind {
             SmartModuleKind::Map => "map",
             SmartModuleKind::ArrayMap => "array_map",
             SmartModuleKind::FilterMap => "filter_map",
+            SmartModuleKind::Batch => "batch",
         };
 
         write!(f, "{}", string)
@@ -63,6 +65,7 @@ impl SmartModuleKind {
             "map" => Some(Self::Map),
             "array_map" => Some(Self::ArrayMap),
             "filter_map" => Some(Self::FilterMap),
+            "batch" => Some(Self::Batch),
             "init" => Some(Self::Init),
             "look_back" => Some(Self::LookBack),
             _ => None,
@@ -100,6 +103,11 @@ impl RecordKind {
             })
             .filter_map(|type_ref| match type_ref.elem.as_ref() {
                 syn::Type::Path(path) => Some(&path.path),
+                // batches take a slice of records
+                syn::Type::Slice(slice) => match slice.elem.as_ref() {
+                    syn::Type::Path(path) => Some(&path.path),
+                    _ => None,
+                },
                 _ => None,
             })
             .any(|path| {
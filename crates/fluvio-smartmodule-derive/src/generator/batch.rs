@@ -0,0 +1,44 @@
+use quote::quote;
+use proc_macro2::TokenStream;
+
+use crate::{SmartModuleFn, SmartModuleKind};
+
+use super::transform::generate_transform;
+
+pub fn generate_batch_smartmodule(func: &SmartModuleFn) -> TokenStream {
+    let user_fn = &func.name;
+    let function_call = quote!(
+        super:: #user_fn(&records)
+    );
+
+    generate_transform(
+        SmartModuleKind::Batch,
+        func,
+        quote! {
+            let result = #function_call;
+
+            match result {
+                Ok(output_records) => {
+                    use fluvio_smartmodule::dataplane::record::RecordKey;
+
+                    for (output_key, output_value) in output_records {
+                        let key = RecordKey::from_option(output_key);
+                        let new_record = Record::new_key_value(key, output_value);
+                        output.successes.push(new_record.into());
+                    }
+                }
+                Err(err) => {
+                    // the error belongs to the whole batch, it is reported at its first record
+                    let first = records.first().cloned().unwrap_or_default();
+                    let error = SmartModuleTransformRuntimeError::new(
+                        &first.into(),
+                        base_offset,
+                        SmartModuleKind::Batch,
+                        err,
+                    );
+                    output.error = Some(error);
+                }
+            }
+        },
+    )
+}
@@ -3,6 +3,7 @@ mod map;
 mod array_map;
 mod filter_map;
 mod aggregate;
+mod batch;
 mod init;
 mod transform;
 mod look_back;
@@ -22,6 +23,7 @@ pub fn generate_smartmodule(config: &SmartModuleConfig, func: &SmartModuleFn) ->
         SmartModuleKind::FilterMap => self::filter_map::generate_filter_map_smartmodule(func),
         SmartModuleKind::Aggregate => self::aggregate::generate_aggregate_smartmodule(func),
         SmartModuleKind::ArrayMap => self::array_map::generate_array_map_smartmodule(func),
+        SmartModuleKind::Batch => self::batch::generate_batch_smartmodule(func),
         SmartModuleKind::Init => self::init::generate_init_smartmodule(func),
         SmartModuleKind::LookBack => self::look_back::generate_look_back_smartmodule(func),
     }
@@ -44,7 +46,8 @@ pub fn generate_records_code(sm_func: &SmartModuleFn, kind: &SmartModuleKind) ->
         | SmartModuleKind::FilterMap
         | SmartModuleKind::Map
         | SmartModuleKind::Filter
-        | SmartModuleKind::Aggregate => quote! {
+        | SmartModuleKind::Aggregate
+        | SmartModuleKind::Batch => quote! {
             use fluvio_smartmodule::dataplane::smartmodule::SmartModuleTransformErrorStatus;
 
             return SmartModuleTransformErrorStatus::DecodingBaseInput as i32;
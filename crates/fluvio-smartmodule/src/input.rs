@@ -13,6 +13,10 @@ use crate::SmartModuleRecord;
 /// This version is used for encoding and decoding [`SmartModuleInput`]
 pub const SMARTMODULE_TIMESTAMPS_VERSION: Version = 22;
 
+/// SmartModule Version with support for record headers, records are passed to
+/// SmartModules of older versions without them
+pub const SMARTMODULE_HEADERS_VERSION: Version = 25;
+
 #[derive(Debug, Default, Clone, Encoder, Decoder)]
 pub struct SmartModuleExtraParams {
     inner: BTreeMap<String, String>,
@@ -45,6 +49,10 @@ impl SmartModuleExtraParams {
         self.inner.insert(key, value);
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.inner.iter()
+    }
+
     pub fn lookback(&self) -> Option<&Lookback> {
         self.lookback.as_ref()
     }
@@ -167,6 +175,20 @@ impl SmartModuleInput {
         Ok(records)
     }
 
+    /// Removes the headers of the records, SmartModules older than
+    /// [`SMARTMODULE_HEADERS_VERSION`] would misread them.
+    pub fn strip_record_headers(&mut self) -> Result<(), std::io::Error> {
+        let mut records: Vec<Record> = Decoder::decode_from(&mut Cursor::new(&self.raw_bytes), 0)?;
+        if records.iter().all(|record| record.headers().is_empty()) {
+            return Ok(());
+        }
+        for record in &mut records {
+            record.headers.clear();
+        }
+        self.raw_bytes.clear();
+        records.encode(&mut self.raw_bytes, 0)
+    }
+
     /// Attempts to map the [`Record`] vector and build a `SmartModuleInput`
     /// instance from it.
     pub fn try_from_records(
@@ -242,6 +264,30 @@ mod tests {
         assert_eq!(records_decoded[2].value.as_ref(), b"banana");
     }
 
+    #[test]
+    fn test_strip_record_headers() {
+        let mut record = Record::new("apple");
+        record.add_header("content-type", "text/plain");
+        let mut sm_input =
+            SmartModuleInput::try_from_records(vec![record, Record::new("fruit")], 0)
+                .expect("records to input conversion failed");
+        sm_input.set_base_offset(10);
+
+        sm_input
+            .strip_record_headers()
+            .expect("headers should be stripped");
+
+        assert_eq!(sm_input.base_offset(), 10);
+        #[allow(deprecated)]
+        let records = sm_input
+            .try_into_records(SMARTMODULE_HEADERS_VERSION)
+            .expect("input to records conversion failed");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].value.as_ref(), b"apple");
+        assert!(records[0].headers().is_empty());
+        assert_eq!(records[1].value.as_ref(), b"fruit");
+    }
+
     #[test]
     fn sets_the_provided_value_as_timestamp() {
         let mut sm_input = SmartModuleInput::new(vec![0, 1, 2, 3], 0, 0);
@@ -21,9 +21,14 @@ pub type Result<T> = eyre::Result<T>;
 #[cfg(feature = "smartmodule")]
 pub mod memory;
 
+#[cfg(feature = "smartmodule")]
+mod lookup;
+#[cfg(feature = "smartmodule")]
+pub use lookup::lookup;
+
 pub use fluvio_protocol::record::{Offset, Record, RecordData};
 
-pub use crate::input::SMARTMODULE_TIMESTAMPS_VERSION;
+pub use crate::input::{SMARTMODULE_TIMESTAMPS_VERSION, SMARTMODULE_HEADERS_VERSION};
 
 /// remap to old data plane
 pub mod dataplane {
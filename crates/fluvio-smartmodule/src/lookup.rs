@@ -0,0 +1,40 @@
+/// capacity of the first buffer lookups copy values into
+const INITIAL_VALUE_CAPACITY: usize = 256;
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn lookup_value(key_ptr: i32, key_len: i32, value_ptr: i32, value_capacity: i32) -> i32;
+}
+
+/// native builds, such as the unit tests of a SmartModule, have no host state
+#[cfg(not(target_arch = "wasm32"))]
+unsafe fn lookup_value(_key_ptr: i32, _key_len: i32, _value_ptr: i32, _value_capacity: i32) -> i32 {
+    -1
+}
+
+/// Value of `key` in the lookup state the host provides to the SmartModule.
+///
+/// SPUs fill the state from the topic named by the `lookup-topic` param or the JSON
+/// file named by the `lookup-file` param, lookups find nothing without either.
+pub fn lookup(key: impl AsRef<[u8]>) -> Option<Vec<u8>> {
+    let key = key.as_ref();
+    let mut value: Vec<u8> = Vec::with_capacity(INITIAL_VALUE_CAPACITY);
+    loop {
+        let len = unsafe {
+            lookup_value(
+                key.as_ptr() as i32,
+                key.len() as i32,
+                value.as_mut_ptr() as i32,
+                value.capacity() as i32,
+            )
+        };
+        let len = usize::try_from(len).ok()?;
+        if len <= value.capacity() {
+            // the host copied `len` bytes into the buffer
+            unsafe { value.set_len(len) };
+            return Some(value);
+        }
+        // the value does not fit, retry with a buffer of its length
+        value.reserve_exact(len);
+    }
+}
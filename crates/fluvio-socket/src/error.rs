@@ -1,4 +1,5 @@
 use std::io::Error as IoError;
+use std::time::Duration;
 
 #[derive(thiserror::Error, Debug)]
 pub enum SocketError {
@@ -8,6 +9,8 @@ pub enum SocketError {
     SocketClosed,
     #[error("Socket is stale")]
     SocketStale,
+    #[error("Connection circuit is open, retry in {retry_in:?}")]
+    CircuitOpen { retry_in: Duration },
 }
 
 impl From<IoError> for SocketError {
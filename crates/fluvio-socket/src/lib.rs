@@ -5,6 +5,13 @@ mod socket;
 mod stream;
 mod versioned;
 mod stream_socket;
+#[cfg(not(target_arch = "wasm32"))]
+mod proxy;
+#[cfg(not(target_arch = "wasm32"))]
+mod reconnect;
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+mod tls_reload;
+mod tcp;
 
 #[cfg(test)]
 pub mod test_request;
@@ -18,6 +25,13 @@ pub use sink::*;
 pub use stream::*;
 pub use stream_socket::*;
 pub use versioned::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use proxy::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use reconnect::*;
+#[cfg(all(feature = "tls", not(target_arch = "wasm32")))]
+pub use tls_reload::{ReloadableTlsAcceptor, TLS_RELOAD_INTERVAL};
+pub use tcp::*;
 
 use fluvio_protocol::api::Request;
 use fluvio_protocol::api::RequestMessage;
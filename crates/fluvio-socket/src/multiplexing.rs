@@ -1,6 +1,5 @@
 use core::task::{Context, Poll};
 use std::collections::HashMap;
-use std::io::Cursor;
 use std::io::Error as IoError;
 use std::io::ErrorKind;
 use std::marker::PhantomData;
@@ -192,7 +191,7 @@ impl MultiplexerSocket {
 
                             debug!(correlation_id, len = response_bytes.len(),"receive serial message");
                             let response = R::Response::decode_from(
-                                &mut Cursor::new(&response_bytes),
+                                &mut response_bytes.clone(),
                                 req_msg.header.api_version(),
                             )?;
                             trace!("receive serial socket id: {}, response: {:#?}", correlation_id, response);
@@ -314,7 +313,7 @@ impl<R: Request> Stream for AsyncResponse<R> {
         };
 
         if let Some(bytes) = next {
-            if let Some(msg) = bytes {
+            if let Some(mut msg) = bytes {
                 use bytes::Buf;
                 let response_len = msg.len();
                 debug!(
@@ -324,8 +323,8 @@ impl<R: Request> Stream for AsyncResponse<R> {
                     "response len>>>"
                 );
 
-                let mut cursor = Cursor::new(msg);
-                let response = R::Response::decode_from(&mut cursor, this.header.api_version());
+                // decoding from `Bytes` lets the records share the response buffer
+                let response = R::Response::decode_from(&mut msg, this.header.api_version());
                 let value = match response {
                     Ok(value) => {
                         trace!("Received response bytes: {},  {:#?}", response_len, &value,);
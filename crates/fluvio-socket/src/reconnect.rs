@@ -0,0 +1,275 @@
+//!
+//! # Reconnect Backoff
+//!
+//! Clients that lose their connection, e.g. on an SPU restart, would otherwise all
+//! reconnect at the same moment and retry in lock step. [`Reconnector`] spreads the
+//! reconnects of an endpoint with a random delay, backs off exponentially with jitter
+//! after failed attempts and, after `failure_threshold` failures in a row, opens a
+//! circuit breaker that fails connects fast for `open_timeout`. One attempt is let
+//! through once the timeout elapsed: the circuit closes if it succeeds and opens again
+//! otherwise.
+//!
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use fluvio_future::timer::sleep;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::SocketError;
+
+const DEFAULT_INITIAL_DELAY: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(10);
+const DEFAULT_JITTER: f64 = 0.5;
+const DEFAULT_FAILURE_THRESHOLD: u32 = 8;
+const DEFAULT_OPEN_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReconnectPolicy {
+    /// delay after the first failed attempt, it doubles with each failure
+    #[serde(with = "humantime_serde")]
+    pub initial_delay: Duration,
+    #[serde(with = "humantime_serde")]
+    pub max_delay: Duration,
+    /// part of each delay that is random, from 0 to 1
+    pub jitter: f64,
+    /// failures in a row that open the circuit, 0 never opens it
+    pub failure_threshold: u32,
+    /// time the circuit stays open before a new attempt
+    #[serde(with = "humantime_serde")]
+    pub open_timeout: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: DEFAULT_INITIAL_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            jitter: DEFAULT_JITTER,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            open_timeout: DEFAULT_OPEN_TIMEOUT,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// jittered delay before the attempt following `failures` failures
+    fn delay(&self, failures: u32) -> Duration {
+        let base = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+            .min(self.max_delay);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        base.mul_f64(1.0 - jitter + jitter * random_unit())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// connects are attempted, after their backoff delay
+    Closed,
+    /// connects fail fast until the open timeout elapses
+    Open,
+    /// the next connect is a trial, its result opens or closes the circuit
+    HalfOpen,
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Closed => write!(f, "closed"),
+            Self::Open => write!(f, "open"),
+            Self::HalfOpen => write!(f, "half-open"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Attempts {
+    /// failed attempts in a row
+    failures: u32,
+    next_attempt: Option<Instant>,
+    open_until: Option<Instant>,
+}
+
+/// Reconnect backoff and circuit breaker of an endpoint
+#[derive(Debug, Default)]
+pub struct Reconnector {
+    policy: ReconnectPolicy,
+    attempts: Mutex<Attempts>,
+}
+
+impl Reconnector {
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        Self {
+            policy,
+            attempts: Mutex::default(),
+        }
+    }
+
+    pub fn policy(&self) -> &ReconnectPolicy {
+        &self.policy
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let attempts = self.attempts();
+        match attempts.open_until {
+            Some(until) if Instant::now() < until => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// failed attempts in a row
+    pub fn failures(&self) -> u32 {
+        self.attempts().failures
+    }
+
+    /// run `connect` once its backoff delay elapsed, recording its result
+    pub async fn connect<F, Fut, T, E>(&self, connect: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: From<SocketError>,
+    {
+        self.before_connect().await?;
+        let result = connect().await;
+        match &result {
+            Ok(_) => self.on_success(),
+            Err(_) => self.on_failure(),
+        }
+        result
+    }
+
+    /// wait for the backoff delay, fails fast while the circuit is open
+    pub async fn before_connect(&self) -> Result<(), SocketError> {
+        let now = Instant::now();
+        let wait_until = {
+            let attempts = self.attempts();
+            if let Some(until) = attempts.open_until.filter(|until| now < *until) {
+                return Err(SocketError::CircuitOpen {
+                    retry_in: until - now,
+                });
+            }
+            attempts.next_attempt
+        };
+        if let Some(wait) = wait_until.and_then(|at| at.checked_duration_since(now)) {
+            debug!(?wait, "waiting before connecting");
+            sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    pub fn on_success(&self) {
+        let mut attempts = self.attempts();
+        if attempts.failures > 0 {
+            debug!(failures = attempts.failures, "connected after failures");
+        }
+        // spread the reconnects of clients that lose the connection at the same time
+        let spread = self.policy.initial_delay.mul_f64(random_unit());
+        *attempts = Attempts {
+            next_attempt: Some(Instant::now() + spread),
+            ..Default::default()
+        };
+    }
+
+    pub fn on_failure(&self) {
+        let now = Instant::now();
+        let mut attempts = self.attempts();
+        attempts.failures = attempts.failures.saturating_add(1);
+        let threshold = self.policy.failure_threshold;
+        let trial_failed = attempts.open_until.is_some();
+        if trial_failed || (threshold > 0 && attempts.failures >= threshold) {
+            warn!(
+                failures = attempts.failures,
+                open_timeout = ?self.policy.open_timeout,
+                "opening connection circuit"
+            );
+            attempts.open_until = Some(now + self.policy.open_timeout);
+            attempts.next_attempt = None;
+        } else {
+            attempts.next_attempt = Some(now + self.policy.delay(attempts.failures));
+        }
+    }
+
+    fn attempts(&self) -> std::sync::MutexGuard<'_, Attempts> {
+        self.attempts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// random number in [0, 1)
+fn random_unit() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+            jitter: 0.5,
+            failure_threshold: 3,
+            open_timeout: Duration::from_millis(100),
+        }
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let policy = policy();
+        for (failures, base) in [(1, 10), (2, 20), (3, 40), (4, 50), (30, 50)] {
+            let delay = policy.delay(failures);
+            let base = Duration::from_millis(base);
+            assert!(delay >= base / 2 && delay <= base, "{failures}: {delay:?}");
+        }
+        let no_jitter = ReconnectPolicy {
+            jitter: 0.0,
+            ..policy
+        };
+        assert_eq!(no_jitter.delay(2), Duration::from_millis(20));
+    }
+
+    #[fluvio_future::test]
+    async fn test_circuit_breaker() {
+        let reconnector = Reconnector::new(policy());
+        let fail = || async { Err::<(), _>(SocketError::SocketClosed) };
+
+        for _ in 0..3 {
+            assert!(matches!(
+                reconnector.connect(fail).await,
+                Err(SocketError::SocketClosed)
+            ));
+        }
+        assert_eq!(reconnector.state(), CircuitState::Open);
+        assert!(matches!(
+            reconnector
+                .connect(|| async { Ok::<_, SocketError>(()) })
+                .await,
+            Err(SocketError::CircuitOpen { .. })
+        ));
+
+        sleep(Duration::from_millis(120)).await;
+        assert_eq!(reconnector.state(), CircuitState::HalfOpen);
+        // a failed trial opens the circuit again
+        assert!(reconnector.connect(fail).await.is_err());
+        assert_eq!(reconnector.state(), CircuitState::Open);
+
+        sleep(Duration::from_millis(120)).await;
+        assert!(reconnector
+            .connect(|| async { Ok::<_, SocketError>(()) })
+            .await
+            .is_ok());
+        assert_eq!(reconnector.state(), CircuitState::Closed);
+        assert_eq!(reconnector.failures(), 0);
+    }
+}
@@ -23,6 +23,9 @@ pub struct FluvioSink {
     inner: SinkFrame,
     fd: ConnectionFd,
     enable_zero_copy: bool,
+    /// reused to encode the headers of file slice responses
+    #[cfg(feature = "file")]
+    encode_buf: fluvio_protocol::EncodeBuffer,
 }
 
 impl fmt::Debug for FluvioSink {
@@ -51,6 +54,8 @@ impl FluvioSink {
         Self {
             fd,
             enable_zero_copy: true,
+            #[cfg(feature = "file")]
+            encode_buf: fluvio_protocol::EncodeBuffer::default(),
             inner: SinkFrame::new(sink.compat_write(), FluvioCodec::new()),
         }
     }
@@ -106,9 +111,10 @@ mod file {
 
     use std::io::Error as IoError;
     use std::io::ErrorKind;
+    use std::io::IoSlice;
     use std::os::fd::BorrowedFd;
 
-    use bytes::BytesMut;
+    use bytes::{Buf, Bytes, BytesMut};
     use fluvio_future::task::spawn_blocking;
     use futures_util::AsyncWriteExt;
     use nix::sys::uio::pread;
@@ -118,6 +124,11 @@ mod file {
 
     use super::*;
 
+    /// room reserved for the headers encoded around the file slices of a response
+    const FILE_HEADER_CAPACITY: usize = 1000;
+    /// max buffers passed to a single vectored write
+    const MAX_IO_SLICES: usize = 64;
+
     impl FluvioSink {
         /// write
         pub async fn encode_file_slices<T>(
@@ -129,12 +140,12 @@ mod file {
             T: FileWrite,
         {
             trace!("encoding file slices version: {}", version);
-            let mut buf = BytesMut::with_capacity(1000);
             let mut data: Vec<StoreValue> = vec![];
-            msg.file_encode(&mut buf, &mut data, version)?;
+            let buf = self.encode_buf.reserve(FILE_HEADER_CAPACITY);
+            msg.file_encode(buf, &mut data, version)?;
             trace!("encoded buffer len: {}", buf.len());
             // add remainder
-            data.push(StoreValue::Bytes(buf.freeze()));
+            data.push(StoreValue::Bytes(self.encode_buf.split()));
             self.write_store_values(data).await
         }
 
@@ -146,21 +157,19 @@ mod file {
             trace!("writing store values to socket values: {}", values.len());
 
             let mut total_bytes_written = 0usize;
+            // consecutive bytes are written together before the next file slice
+            let mut pending: Vec<Bytes> = vec![];
 
             for value in values {
                 match value {
                     StoreValue::Bytes(bytes) => {
-                        trace!("writing store bytes to socket len: {}", bytes.len());
+                        trace!("queueing store bytes len: {}", bytes.len());
                         // These bytes should be already encoded so don't need to pass
                         // through the FluvioCodec
-                        self.get_mut_tcp_sink()
-                            .get_mut()
-                            .get_mut()
-                            .write_all(&bytes)
-                            .await?;
-                        total_bytes_written += bytes.len();
+                        pending.push(bytes);
                     }
                     StoreValue::FileSlice(f_slice) => {
+                        total_bytes_written += self.write_buffers(&mut pending).await?;
                         if f_slice.is_empty() {
                             trace!("empty slice, skipping");
                         } else {
@@ -226,9 +235,50 @@ mod file {
                 }
             }
 
+            total_bytes_written += self.write_buffers(&mut pending).await?;
+
             trace!(total_bytes_written, "finish writing store values");
             Ok(total_bytes_written)
         }
+
+        /// write all `buffers` to socket with vectored writes
+        async fn write_buffers(&mut self, buffers: &mut Vec<Bytes>) -> Result<usize, IoError> {
+            buffers.retain(|buf| !buf.is_empty());
+            let total: usize = buffers.iter().map(Bytes::len).sum();
+            let writer = self.get_mut_tcp_sink().get_mut().get_mut();
+
+            let mut start = 0;
+            while start < buffers.len() {
+                let slices: Vec<IoSlice> = buffers[start..]
+                    .iter()
+                    .take(MAX_IO_SLICES)
+                    .map(|buf| IoSlice::new(buf))
+                    .collect();
+                let mut written = writer.write_vectored(&slices).await?;
+                if written == 0 {
+                    return Err(IoError::new(
+                        ErrorKind::WriteZero,
+                        "failed to write store bytes",
+                    ));
+                }
+                trace!(written, buffers = slices.len(), "wrote store bytes");
+                drop(slices);
+                // drop the written buffers, advance the partially written one
+                while written > 0 {
+                    let buf = &mut buffers[start];
+                    if written >= buf.len() {
+                        written -= buf.len();
+                        start += 1;
+                    } else {
+                        buf.advance(written);
+                        written = 0;
+                    }
+                }
+            }
+
+            buffers.clear();
+            Ok(total)
+        }
     }
 
     #[cfg(test)]
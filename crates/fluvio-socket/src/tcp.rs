@@ -0,0 +1,288 @@
+//!
+//! # TCP tuning
+//!
+//! Socket level options for links with high latency or bandwidth, such as mirroring
+//! between regions. Options that are not set keep the operating system defaults.
+//!
+use std::io::{Error as IoError, ErrorKind};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct TcpConfig {
+    /// Disable Nagle's algorithm on TCP connections (TCP_NODELAY)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "clap",
+        arg(long = "tcp-nodelay", value_name = "bool", env = "FLV_TCP_NODELAY")
+    )]
+    pub nodelay: Option<bool>,
+
+    /// Size of the TCP send buffer in bytes (SO_SNDBUF)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long = "tcp-send-buffer-size",
+            value_name = "bytes",
+            env = "FLV_TCP_SEND_BUFFER_SIZE"
+        )
+    )]
+    pub send_buffer_size: Option<usize>,
+
+    /// Size of the TCP receive buffer in bytes (SO_RCVBUF)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long = "tcp-recv-buffer-size",
+            value_name = "bytes",
+            env = "FLV_TCP_RECV_BUFFER_SIZE"
+        )
+    )]
+    pub recv_buffer_size: Option<usize>,
+
+    /// Idle time before TCP keepalive probes are sent, e.g. 60s
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long = "tcp-keepalive",
+            value_name = "duration",
+            value_parser = humantime::parse_duration,
+            env = "FLV_TCP_KEEPALIVE"
+        )
+    )]
+    pub keepalive: Option<Duration>,
+
+    /// Interval between TCP keepalive probes, requires keepalive
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long = "tcp-keepalive-interval",
+            value_name = "duration",
+            value_parser = humantime::parse_duration,
+            env = "FLV_TCP_KEEPALIVE_INTERVAL"
+        )
+    )]
+    pub keepalive_interval: Option<Duration>,
+
+    /// Time to wait for outbound TCP connections to be established
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long = "tcp-connect-timeout",
+            value_name = "duration",
+            value_parser = humantime::parse_duration,
+            env = "FLV_TCP_CONNECT_TIMEOUT"
+        )
+    )]
+    pub connect_timeout: Option<Duration>,
+}
+
+impl TcpConfig {
+    /// true if no option is set
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), IoError> {
+        if self.keepalive_interval.is_some() && self.keepalive.is_none() {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "tcp keepalive interval requires tcp keepalive",
+            ));
+        }
+        if self.send_buffer_size == Some(0) || self.recv_buffer_size == Some(0) {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "tcp buffer sizes must be greater than 0",
+            ));
+        }
+        Ok(())
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        use async_trait::async_trait;
+        use socket2::{SockRef, TcpKeepalive};
+        use tracing::debug;
+
+        use fluvio_future::net::{
+            AsConnectionFd, BoxReadConnection, BoxWriteConnection, ConnectionFd,
+            DefaultDomainConnector, DomainConnector, TcpDomainConnector, TcpStream,
+        };
+
+        impl TcpConfig {
+            /// set options on a connected or accepted stream
+            pub fn apply(&self, stream: &TcpStream) -> Result<(), IoError> {
+                if self.is_default() {
+                    return Ok(());
+                }
+                let socket = SockRef::from(stream);
+                if let Some(nodelay) = self.nodelay {
+                    socket.set_nodelay(nodelay)?;
+                }
+                if let Some(size) = self.send_buffer_size {
+                    socket.set_send_buffer_size(size)?;
+                }
+                if let Some(size) = self.recv_buffer_size {
+                    socket.set_recv_buffer_size(size)?;
+                }
+                if let Some(time) = self.keepalive {
+                    let mut keepalive = TcpKeepalive::new().with_time(time);
+                    if let Some(interval) = self.keepalive_interval {
+                        keepalive = keepalive.with_interval(interval);
+                    }
+                    socket.set_tcp_keepalive(&keepalive)?;
+                }
+                Ok(())
+            }
+
+            /// open tcp connection to `addr`, bounded by the connect timeout
+            pub async fn connect(&self, addr: &str) -> Result<TcpStream, IoError> {
+                let stream = match self.connect_timeout {
+                    Some(timeout) => tokio::select! {
+                        _ = fluvio_future::timer::sleep(timeout) => {
+                            return Err(IoError::new(
+                                ErrorKind::TimedOut,
+                                format!("timed out after {timeout:?} connecting to {addr}"),
+                            ));
+                        },
+                        stream = TcpStream::connect(addr) => stream?,
+                    },
+                    None => TcpStream::connect(addr).await?,
+                };
+                self.apply(&stream)?;
+                Ok(stream)
+            }
+        }
+
+        /// Plaintext connector applying [`TcpConfig`] to every connection
+        #[derive(Debug, Clone)]
+        pub struct TunedDomainConnector {
+            tcp: TcpConfig,
+            domain: String,
+        }
+
+        impl TunedDomainConnector {
+            pub fn new(tcp: TcpConfig) -> Self {
+                Self {
+                    tcp,
+                    domain: "localhost".to_owned(),
+                }
+            }
+        }
+
+        #[async_trait]
+        impl TcpDomainConnector for TunedDomainConnector {
+            async fn connect(
+                &self,
+                addr: &str,
+            ) -> Result<(BoxWriteConnection, BoxReadConnection, ConnectionFd), IoError> {
+                debug!(addr, tcp = ?self.tcp, "connecting with tcp config");
+                let stream = self.tcp.connect(addr).await?;
+                let fd = stream.as_connection_fd();
+                Ok((Box::new(stream.clone()), Box::new(stream), fd))
+            }
+
+            fn new_domain(&self, domain: String) -> DomainConnector {
+                Box::new(Self {
+                    tcp: self.tcp.clone(),
+                    domain,
+                })
+            }
+
+            fn domain(&self) -> &str {
+                &self.domain
+            }
+        }
+
+        /// plaintext connector honoring the environment proxy and `tcp`.
+        /// Connections through a proxy keep the default tcp options.
+        pub fn tuned_connector_for(addr: &str, tcp: &TcpConfig) -> DomainConnector {
+            if tcp.is_default() || crate::ProxyConfig::from_env(addr).is_some() {
+                return crate::plaintext_connector_for(addr);
+            }
+            Box::new(TunedDomainConnector::new(tcp.clone()))
+        }
+
+        /// connector for `tcp`, the default connector if no option is set
+        pub fn tcp_connector(tcp: &TcpConfig) -> DomainConnector {
+            if tcp.is_default() {
+                Box::<DefaultDomainConnector>::default()
+            } else {
+                Box::new(TunedDomainConnector::new(tcp.clone()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tcp_config_serde() {
+        let config: TcpConfig = toml::from_str(
+            r#"
+            nodelay = true
+            send_buffer_size = 4194304
+            keepalive = "60s"
+            keepalive_interval = "10s"
+            "#,
+        )
+        .expect("parse");
+        assert_eq!(config.nodelay, Some(true));
+        assert_eq!(config.send_buffer_size, Some(4194304));
+        assert_eq!(config.recv_buffer_size, None);
+        assert_eq!(config.keepalive, Some(Duration::from_secs(60)));
+        assert_eq!(config.keepalive_interval, Some(Duration::from_secs(10)));
+        assert!(config.validate().is_ok());
+        assert!(!config.is_default());
+        assert!(TcpConfig::default().is_default());
+
+        let invalid = TcpConfig {
+            keepalive_interval: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[fluvio_future::test]
+    async fn test_apply_tcp_config() {
+        use fluvio_future::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr").to_string();
+        let config = TcpConfig {
+            nodelay: Some(true),
+            keepalive: Some(Duration::from_secs(30)),
+            connect_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+
+        let stream = config.connect(&addr).await.expect("connect");
+        let socket = SockRef::from(&stream);
+        assert!(socket.nodelay().expect("nodelay"));
+        assert!(socket.keepalive().expect("keepalive"));
+    }
+}
@@ -0,0 +1,188 @@
+//!
+//! # Reloadable TLS acceptor
+//!
+//! Certificates issued by tools such as cert-manager are renewed in place. The acceptor is
+//! rebuilt when the modification time or size of one of its files changes, new connections
+//! are accepted with the renewed certificate while established ones keep their session
+//! until they close.
+//!
+use std::fmt::{self, Display};
+use std::io::Error as IoError;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use tracing::{debug, info, warn};
+
+use fluvio_future::openssl::TlsAcceptor;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+/// how often servers check their certificate files
+pub const TLS_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+type BuildAcceptor = dyn Fn() -> Result<TlsAcceptor, String> + Send + Sync;
+
+/// modification time and size of a file, `None` if it can't be read
+type FileStamp = Option<(SystemTime, u64)>;
+
+#[derive(Clone)]
+pub struct ReloadableTlsAcceptor {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    files: Vec<PathBuf>,
+    build: Box<BuildAcceptor>,
+    current: RwLock<(Arc<TlsAcceptor>, Vec<FileStamp>)>,
+}
+
+impl fmt::Debug for ReloadableTlsAcceptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ReloadableTlsAcceptor {{ files: {:?} }}",
+            self.inner.files
+        )
+    }
+}
+
+impl ReloadableTlsAcceptor {
+    /// build the acceptor, `build` is called again when one of `files` changes
+    pub fn new<F, E>(files: Vec<PathBuf>, build: F) -> Result<Self, IoError>
+    where
+        F: Fn() -> Result<TlsAcceptor, E> + Send + Sync + 'static,
+        E: Display,
+    {
+        let build: Box<BuildAcceptor> = Box::new(move || build().map_err(|err| err.to_string()));
+        let stamps = stamps(&files);
+        let acceptor = build().map_err(IoError::other)?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                files,
+                build,
+                current: RwLock::new((Arc::new(acceptor), stamps)),
+            }),
+        })
+    }
+
+    /// acceptor for a new connection
+    pub fn current(&self) -> Arc<TlsAcceptor> {
+        self.inner
+            .current
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .0
+            .clone()
+    }
+
+    /// rebuild the acceptor if its files changed, returns true if it was replaced.
+    /// An acceptor which fails to build, such as a certificate written before its key,
+    /// is retried on the next check while the previous one is kept.
+    pub fn reload_if_changed(&self) -> bool {
+        let stamps = stamps(&self.inner.files);
+        {
+            let current = self
+                .inner
+                .current
+                .read()
+                .unwrap_or_else(|err| err.into_inner());
+            if current.1 == stamps {
+                return false;
+            }
+        }
+
+        match (self.inner.build)() {
+            Ok(acceptor) => {
+                info!(files = ?self.inner.files, "tls certificates reloaded");
+                let mut current = self
+                    .inner
+                    .current
+                    .write()
+                    .unwrap_or_else(|err| err.into_inner());
+                *current = (Arc::new(acceptor), stamps);
+                true
+            }
+            Err(err) => {
+                warn!(%err, "tls certificates changed but can't be loaded, keeping previous ones");
+                false
+            }
+        }
+    }
+
+    /// check the files every `interval` in the background
+    pub fn watch(&self, interval: Duration) {
+        let acceptor = self.clone();
+        debug!(?interval, files = ?acceptor.inner.files, "watching tls certificates");
+        spawn(async move {
+            loop {
+                sleep(interval).await;
+                acceptor.reload_if_changed();
+            }
+        });
+    }
+}
+
+fn stamps(files: &[PathBuf]) -> Vec<FileStamp> {
+    files
+        .iter()
+        .map(|file| {
+            let metadata = std::fs::metadata(file).ok()?;
+            Some((metadata.modified().ok()?, metadata.len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::Path;
+
+    use fluvio_future::openssl::TlsAcceptor;
+
+    use super::*;
+
+    const CERTS_DIR: &str = "certs/certs";
+
+    fn build(cert: &Path, key: &Path) -> Result<TlsAcceptor, String> {
+        Ok(TlsAcceptor::builder()
+            .map_err(|err| err.to_string())?
+            .with_certifiate_and_key_from_pem_files(cert, key)
+            .map_err(|err| err.to_string())?
+            .build())
+    }
+
+    #[test]
+    fn test_reload_if_changed() {
+        let dir = std::env::temp_dir().join(format!("fluvio-tls-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let cert = dir.join("tls.crt");
+        let key = dir.join("tls.key");
+        let install = |name: &str| {
+            std::fs::copy(Path::new(CERTS_DIR).join(format!("{name}.crt")), &cert).expect("cert");
+            std::fs::copy(Path::new(CERTS_DIR).join(format!("{name}.key")), &key).expect("key");
+        };
+        install("server");
+
+        let acceptor = ReloadableTlsAcceptor::new(vec![cert.clone(), key.clone()], {
+            let (cert, key) = (cert.clone(), key.clone());
+            move || build(&cert, &key)
+        })
+        .expect("acceptor");
+        let first = acceptor.current();
+        assert!(!acceptor.reload_if_changed());
+        assert!(Arc::ptr_eq(&first, &acceptor.current()));
+
+        // a certificate without its key is not loaded
+        std::fs::copy(Path::new(CERTS_DIR).join("client.crt"), &cert).expect("cert");
+        assert!(!acceptor.reload_if_changed());
+        assert!(Arc::ptr_eq(&first, &acceptor.current()));
+
+        install("client");
+        assert!(acceptor.reload_if_changed());
+        assert!(!Arc::ptr_eq(&first, &acceptor.current()));
+        assert!(!acceptor.reload_if_changed());
+
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+}
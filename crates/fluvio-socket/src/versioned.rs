@@ -10,7 +10,9 @@ use tracing::{debug, instrument, info};
 
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::api::Request;
-use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVersionsResponse};
+use fluvio_protocol::link::versions::{
+    ApiVersions, ApiVersionsRequest, ApiVersionsResponse, ClientVersionRange,
+};
 use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
 use fluvio_future::retry::retry_if;
 
@@ -179,6 +181,7 @@ impl ClientConfig {
 pub struct Versions {
     api_versions: ApiVersions,
     platform_version: semver::Version,
+    supported_client: ClientVersionRange,
 }
 
 impl Versions {
@@ -186,9 +189,15 @@ impl Versions {
         Self {
             api_versions: version_response.api_keys,
             platform_version: version_response.platform_version.to_semver(),
+            supported_client: version_response.supported_client,
         }
     }
 
+    /// Client platform versions supported by the SC, unbounded if not advertised
+    pub fn supported_client_versions(&self) -> &ClientVersionRange {
+        &self.supported_client
+    }
+
     /// Tells the platform version reported by the SC
     ///
     /// The platform version refers to the value in the VERSION
@@ -371,7 +380,9 @@ fn is_retryable(err: &SocketError) -> bool {
                 | ErrorKind::Interrupted
         ),
 
-        SocketError::SocketClosed | SocketError::SocketStale => false,
+        SocketError::SocketClosed | SocketError::SocketStale | SocketError::CircuitOpen { .. } => {
+            false
+        }
     }
 }
 
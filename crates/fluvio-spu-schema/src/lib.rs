@@ -17,4 +17,8 @@ pub use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVe
 pub use isolation::*;
 
 /// Default API version for all API
-pub const COMMON_VERSION: i16 = 23;
+pub const COMMON_VERSION: i16 = 25;
+
+/// First version whose consumers read record headers.
+/// Records are sent to older consumers without their headers.
+pub const RECORD_HEADERS_API_VERSION: i16 = 25;
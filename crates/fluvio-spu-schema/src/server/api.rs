@@ -23,9 +23,11 @@ use super::fetch_offset::FetchOffsetsRequest;
 use super::stream_fetch::FileStreamFetchRequest;
 use super::consumer_offset::{
     UpdateConsumerOffsetRequest, DeleteConsumerOffsetRequest, FetchConsumerOffsetsRequest,
+    SetConsumerOffsetRequest,
 };
 use super::update_offset::UpdateOffsetsRequest;
 use super::mirror::StartMirrorRequest;
+use super::sample::SampleRecordsRequest;
 
 #[allow(clippy::large_enum_variant)]
 /// Request to Spu Server
@@ -41,7 +43,9 @@ pub enum SpuServerRequest {
     UpdateConsumerOffsetRequest(RequestMessage<UpdateConsumerOffsetRequest>),
     DeleteConsumerOffsetRequest(RequestMessage<DeleteConsumerOffsetRequest>),
     FetchConsumerOffsetsRequest(RequestMessage<FetchConsumerOffsetsRequest>),
+    SetConsumerOffsetRequest(RequestMessage<SetConsumerOffsetRequest>),
     StartMirrorRequest(RequestMessage<StartMirrorRequest>),
+    SampleRecordsRequest(RequestMessage<SampleRecordsRequest>),
 }
 
 impl fmt::Display for SpuServerRequest {
@@ -56,7 +60,9 @@ impl fmt::Display for SpuServerRequest {
             Self::UpdateConsumerOffsetRequest(_) => write!(f, "UpdateConsumerOffsetRequest"),
             Self::DeleteConsumerOffsetRequest(_) => write!(f, "DeleteConsumerOffsetRequest"),
             Self::FetchConsumerOffsetsRequest(_) => write!(f, "FetchConsumerOffsetsRequest"),
+            Self::SetConsumerOffsetRequest(_) => write!(f, "SetConsumerOffsetRequest"),
             Self::StartMirrorRequest(_) => write!(f, "StartMirrorRequest"),
+            Self::SampleRecordsRequest(_) => write!(f, "SampleRecordsRequest"),
         }
     }
 }
@@ -98,7 +104,13 @@ impl ApiMessage for SpuServerRequest {
             SpuServerApiKey::FetchConsumerOffsets => {
                 api_decode!(Self, FetchConsumerOffsetsRequest, src, header)
             }
+            SpuServerApiKey::SetConsumerOffset => {
+                api_decode!(Self, SetConsumerOffsetRequest, src, header)
+            }
             SpuServerApiKey::StartMirror => api_decode!(Self, StartMirrorRequest, src, header),
+            SpuServerApiKey::SampleRecords => {
+                api_decode!(Self, SampleRecordsRequest, src, header)
+            }
         }
     }
 }
@@ -23,6 +23,8 @@ pub enum SpuServerApiKey {
     UpdateConsumerOffset = 1006,
     DeleteConsumerOffset = 1007,
     FetchConsumerOffsets = 1008,
+    SetConsumerOffset = 1009,
+    SampleRecords = 1010,
 
     StartMirror = 2000,
 }
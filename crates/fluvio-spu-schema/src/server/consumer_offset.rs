@@ -62,6 +62,41 @@ pub struct DeleteConsumerOffsetResponse {
     pub error_code: ErrorCode,
 }
 
+/// Set the offset committed by a consumer for a replica
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct SetConsumerOffsetRequest {
+    pub replica_id: ReplicaKey,
+    pub consumer_id: String,
+    pub offset: Offset,
+}
+
+impl SetConsumerOffsetRequest {
+    pub fn new(
+        topic: impl Into<String>,
+        partition: PartitionId,
+        consumer_id: impl Into<String>,
+        offset: Offset,
+    ) -> Self {
+        let replica_id = ReplicaKey::new(topic, partition);
+        Self {
+            replica_id,
+            consumer_id: consumer_id.into(),
+            offset,
+        }
+    }
+}
+
+impl Request for SetConsumerOffsetRequest {
+    const API_KEY: u16 = SpuServerApiKey::SetConsumerOffset as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = SetConsumerOffsetResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct SetConsumerOffsetResponse {
+    pub error_code: ErrorCode,
+}
+
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct FetchConsumerOffsetsRequest;
 
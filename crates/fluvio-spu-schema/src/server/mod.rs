@@ -8,6 +8,7 @@ pub mod stream_fetch;
 pub mod update_offset;
 pub mod consumer_offset;
 pub mod mirror;
+pub mod sample;
 
 pub use self::api_key::*;
 
@@ -0,0 +1,76 @@
+//!
+//! # Sample Records
+//!
+//! API to read a few records of a partition without setting up a stream,
+//! used to preview the content of a topic.
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Offset, RecordData};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::{PartitionId, Timestamp};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// Which records of the partition are sampled
+#[derive(Debug, Encoder, Decoder, Clone, Copy, Eq, PartialEq, Default)]
+#[fluvio(encode_discriminant)]
+#[repr(u8)]
+pub enum SampleStrategy {
+    /// first records of the partition
+    #[default]
+    Head = 0,
+    /// last committed records of the partition
+    Tail = 1,
+    /// records at random offsets, in offset order
+    Random = 2,
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct SampleRecordsRequest {
+    pub topic: String,
+    pub partition: PartitionId,
+    /// maximum number of records returned
+    pub count: u32,
+    pub strategy: SampleStrategy,
+}
+
+impl Request for SampleRecordsRequest {
+    const API_KEY: u16 = SpuServerApiKey::SampleRecords as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = SampleRecordsResponse;
+}
+
+impl SampleRecordsRequest {
+    pub fn new(
+        topic: impl Into<String>,
+        partition: PartitionId,
+        count: u32,
+        strategy: SampleStrategy,
+    ) -> Self {
+        Self {
+            topic: topic.into(),
+            partition,
+            count,
+            strategy,
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct SampleRecordsResponse {
+    pub error_code: ErrorCode,
+    /// first readable offset of the partition
+    pub start_offset: Offset,
+    /// high watermark of the partition, records are sampled below it
+    pub hw: Offset,
+    pub records: Vec<SampledRecord>,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub struct SampledRecord {
+    pub offset: Offset,
+    pub timestamp: Timestamp,
+    pub key: Option<RecordData>,
+    pub value: RecordData,
+}
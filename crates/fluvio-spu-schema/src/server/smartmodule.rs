@@ -1,5 +1,6 @@
 #![allow(deprecated)]
 
+use std::collections::BTreeMap;
 use std::io::Read;
 use std::io;
 use std::fmt::{Debug, self};
@@ -23,6 +24,93 @@ pub struct SmartModuleInvocation {
     pub params: SmartModuleExtraParams,
 }
 
+/// Name of the key filter built into the SPU, invoked as a predefined filter
+pub const KEY_FILTER_SMARTMODULE: &str = "fluvio/key-filter@builtin";
+/// records whose key matches the regex are kept
+pub const KEY_FILTER_REGEX_PARAM: &str = "regex";
+/// records whose key is equal are kept
+pub const KEY_FILTER_EQUALS_PARAM: &str = "equals";
+
+/// Name of the projection built into the SPU, invoked as a predefined map
+pub const PROJECTION_SMARTMODULE: &str = "fluvio/projection@builtin";
+/// comma separated JSON pointers of the fields kept in record values
+pub const PROJECTION_FIELDS_PARAM: &str = "fields";
+
+/// compacted topic whose latest value per key fills the lookup state of the SmartModule
+pub const LOOKUP_TOPIC_PARAM: &str = "lookup-topic";
+/// file of the SPU with a JSON object filling the lookup state of the SmartModule
+pub const LOOKUP_FILE_PARAM: &str = "lookup-file";
+
+/// topic records failing the SmartModule are produced to, instead of failing the stream
+pub const DEAD_LETTER_TOPIC_PARAM: &str = "dead-letter-topic";
+
+impl SmartModuleInvocation {
+    /// keep records whose key matches `regex`, evaluated by the SPU without a WASM module
+    pub fn key_regex_filter(regex: impl Into<String>) -> Self {
+        Self::key_filter(KEY_FILTER_REGEX_PARAM, regex.into())
+    }
+
+    /// keep records whose key is `key`, evaluated by the SPU without a WASM module
+    pub fn key_equals_filter(key: impl Into<String>) -> Self {
+        Self::key_filter(KEY_FILTER_EQUALS_PARAM, key.into())
+    }
+
+    fn key_filter(param: &str, value: String) -> Self {
+        Self {
+            wasm: SmartModuleInvocationWasm::Predefined(KEY_FILTER_SMARTMODULE.to_owned()),
+            kind: SmartModuleKind::Filter,
+            params: SmartModuleExtraParams::new(BTreeMap::from([(param.to_owned(), value)]), None),
+        }
+    }
+
+    /// true for the key filter built into the SPU
+    pub fn is_key_filter(&self) -> bool {
+        matches!(&self.wasm, SmartModuleInvocationWasm::Predefined(name) if name == KEY_FILTER_SMARTMODULE)
+    }
+
+    /// keep only the fields of JSON record values at the `pointers`, evaluated by the SPU
+    /// after the other SmartModules. Pointers can not contain commas.
+    pub fn projection<S: AsRef<str>>(pointers: impl IntoIterator<Item = S>) -> Self {
+        let fields = pointers
+            .into_iter()
+            .map(|pointer| pointer.as_ref().to_owned())
+            .collect::<Vec<_>>()
+            .join(",");
+        Self {
+            wasm: SmartModuleInvocationWasm::Predefined(PROJECTION_SMARTMODULE.to_owned()),
+            kind: SmartModuleKind::Map,
+            params: SmartModuleExtraParams::new(
+                BTreeMap::from([(PROJECTION_FIELDS_PARAM.to_owned(), fields)]),
+                None,
+            ),
+        }
+    }
+
+    /// true for the projection built into the SPU
+    pub fn is_projection(&self) -> bool {
+        matches!(&self.wasm, SmartModuleInvocationWasm::Predefined(name) if name == PROJECTION_SMARTMODULE)
+    }
+
+    /// JSON pointers of the projection
+    pub fn projection_fields(&self) -> Vec<&str> {
+        self.params
+            .get(PROJECTION_FIELDS_PARAM)
+            .map(|fields| fields.split(',').collect())
+            .unwrap_or_default()
+    }
+
+    /// route records failing this SmartModule to `topic`, the next records are still processed
+    pub fn with_dead_letter_topic(mut self, topic: impl Into<String>) -> Self {
+        self.params
+            .insert(DEAD_LETTER_TOPIC_PARAM.to_owned(), topic.into());
+        self
+    }
+
+    pub fn dead_letter_topic(&self) -> Option<&str> {
+        self.params.get(DEAD_LETTER_TOPIC_PARAM).map(String::as_str)
+    }
+}
+
 #[derive(Clone, Encoder, Decoder)]
 pub enum SmartModuleInvocationWasm {
     /// Name of SmartModule
@@ -180,4 +268,11 @@ mod tests {
             panic!("not adhoc")
         }
     }
+    #[test]
+    fn test_projection_invocation() {
+        let invocation = SmartModuleInvocation::projection(["/id", "/user/name"]);
+        assert!(invocation.is_projection());
+        assert!(!invocation.is_key_filter());
+        assert_eq!(invocation.projection_fields(), ["/id", "/user/name"]);
+    }
 }
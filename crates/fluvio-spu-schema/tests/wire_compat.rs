@@ -0,0 +1,70 @@
+use fluvio_protocol::corpus::WireCorpus;
+use fluvio_spu_schema::COMMON_VERSION;
+use fluvio_spu_schema::errors::ErrorCode;
+use fluvio_spu_schema::server::fetch_offset::{
+    FetchOffsetPartitionResponse, FetchOffsetTopicResponse, FetchOffsetsRequest,
+    FetchOffsetsResponse,
+};
+use fluvio_spu_schema::server::update_offset::{
+    OffsetUpdate, OffsetUpdateStatus, UpdateOffsetsRequest, UpdateOffsetsResponse,
+};
+
+// Test Specification:
+//
+// Each message is checked against the golden encodings in `tests/corpus` for the
+// versions listed. Entries of older versions are never rewritten, they are the bytes
+// older clients and SPUs send and expect. When COMMON_VERSION is bumped, the entry
+// of the new version is missing and has to be captured with FLUVIO_UPDATE_CORPUS=1.
+
+fn corpus() -> WireCorpus {
+    WireCorpus::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus"))
+}
+
+#[test]
+fn test_fetch_offsets_wire_compat() {
+    let request = FetchOffsetsRequest::new("topic".to_owned(), 1, Some("consumer".to_owned()));
+    let response = FetchOffsetsResponse {
+        topics: vec![FetchOffsetTopicResponse {
+            name: "topic".to_owned(),
+            partitions: vec![FetchOffsetPartitionResponse {
+                error_code: ErrorCode::None,
+                partition_index: 1,
+                start_offset: 0,
+                last_stable_offset: 42,
+            }],
+        }],
+    };
+
+    corpus()
+        // consumer id is encoded from version 23
+        .check(
+            "fetch_offsets_request",
+            &request,
+            &[22, 23, 24, COMMON_VERSION],
+        )
+        .check(
+            "fetch_offsets_response",
+            &response,
+            &[0, 24, COMMON_VERSION],
+        )
+        .finish();
+}
+
+#[test]
+fn test_update_offsets_wire_compat() {
+    let request = UpdateOffsetsRequest::new(vec![OffsetUpdate {
+        offset: 10,
+        session_id: 3,
+    }]);
+    let response = UpdateOffsetsResponse {
+        status: vec![OffsetUpdateStatus {
+            session_id: 3,
+            error: ErrorCode::None,
+        }],
+    };
+
+    corpus()
+        .check("update_offsets_request", &request, &[0])
+        .check("update_offsets_response", &response, &[0])
+        .finish();
+}
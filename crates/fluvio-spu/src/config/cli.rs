@@ -4,7 +4,10 @@
 //! Command line interface to provision SPU id and configure various
 //! system parameters.
 //!
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::process;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use fluvio_future::openssl::SslVerifyMode;
@@ -14,14 +17,26 @@ use clap::Parser;
 
 use fluvio_types::print_cli_err;
 use fluvio_types::SpuId;
+use fluvio_types::defaults::SPU_PARTITION_MAX_BYTES_MIN;
 use fluvio_future::openssl::TlsAcceptor;
+use fluvio_socket::{ReloadableTlsAcceptor, TcpConfig};
+use fluvio_service::metrics::MetricsCardinality;
 
-use super::SpuConfig;
+use super::{AclConfig, ResourceProfile, SpuConfig};
+use super::file::SpuConfigFile;
 
 /// cli options
 #[derive(Debug, Default, Parser)]
 #[command(name = "fluvio-spu", about = "Streaming Processing Unit")]
 pub struct SpuOpt {
+    /// TOML file with default values of the options below, named in snake case
+    #[arg(long, value_name = "path", env = "FLV_SPU_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Check the configuration and exit without starting the SPU
+    #[arg(long)]
+    pub validate_config: bool,
+
     /// SPU unique identifier
     #[arg(short = 'i', long = "id", value_name = "integer")]
     pub id: Option<i32>,
@@ -38,9 +53,31 @@ pub struct SpuOpt {
     #[arg(long, value_name = "host:port", env = "FLV_SC_PRIVATE_HOST")]
     pub sc_addr: Option<String>,
 
+    /// Public address of the SC Server, required to route invalid records to dead letter topics
+    #[arg(long, value_name = "host:port", env = "FLV_SC_PUBLIC_HOST")]
+    pub sc_public_addr: Option<String>,
+
+    /// Serve Prometheus metrics at http://host:port/metrics, disabled by default
+    #[arg(long, value_name = "host:port", env = "FLV_METRICS_SERVER")]
+    pub metrics_server: Option<String>,
+
+    /// Topic keeping metrics per partition, others are aggregated per topic.
+    /// A trailing `*` matches by prefix, can be repeated. Every topic by default
+    #[arg(long, value_name = "pattern")]
+    pub metrics_partition_topic: Vec<String>,
+
+    /// Above this number of topics, the metrics of the topics not kept per partition
+    /// are aggregated into a single series
+    #[arg(long, value_name = "count")]
+    pub metrics_max_topics: Option<usize>,
+
     #[arg(long, value_name = "dir", env = "FLV_LOG_BASE_DIR")]
     pub log_base_dir: Option<String>,
 
+    /// Base directory of ephemeral topics, should be a memory backed file system such as tmpfs
+    #[arg(long, value_name = "dir", env = "FLV_EPHEMERAL_LOG_BASE_DIR")]
+    pub ephemeral_log_base_dir: Option<String>,
+
     #[arg(long, value_name = "log size", env = "FLV_LOG_SIZE")]
     pub log_size: Option<String>,
 
@@ -50,14 +87,37 @@ pub struct SpuOpt {
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_INTERVAL_BYTES")]
     pub index_max_interval_bytes: Option<u32>,
 
-    /// max bytes to transfer between leader and follower
-    #[arg(
-        long,
-        value_name = "integer",
-        env = "FLV_PEER_MAX_BYTES",
-        default_value = "1000000"
-    )]
-    pub peer_max_bytes: u32,
+    /// bytes read ahead of consumers replaying previous segments, 0 disables read ahead
+    #[arg(long, value_name = "integer", env = "FLV_LOG_READ_AHEAD_BYTES")]
+    pub read_ahead_bytes: Option<u32>,
+
+    /// Bytes of tiered segments cached in memory for consumers reading them. Defaults to 256MB
+    #[arg(long, value_name = "integer", env = "FLV_LOG_TIER_CACHE_BYTES")]
+    pub tier_cache_bytes: Option<u64>,
+
+    /// Bytes kept in partitions of topics without a max partition size, the oldest segments
+    /// are removed past it. Defaults to 100GB
+    #[arg(long, value_name = "integer", env = "FLV_LOG_MAX_PARTITION_SIZE")]
+    pub max_partition_size: Option<u64>,
+
+    /// Reject produces once the file system of the log base dir is this full, in percent.
+    /// Defaults to 95
+    #[arg(long, value_name = "percent", value_parser = clap::value_parser!(u8).range(1..=100), env = "FLV_LOG_DISK_HIGH_WATERMARK")]
+    pub disk_high_watermark: Option<u8>,
+
+    /// Accept produces again once the file system is less full than this, in percent.
+    /// Defaults to 90
+    #[arg(long, value_name = "percent", value_parser = clap::value_parser!(u8).range(1..=100), env = "FLV_LOG_DISK_LOW_WATERMARK")]
+    pub disk_low_watermark: Option<u8>,
+
+    /// Remove the oldest segments of the largest partitions while the disk is full,
+    /// regardless of their retention
+    #[arg(long, env = "FLV_LOG_EMERGENCY_RETENTION")]
+    pub emergency_retention: bool,
+
+    /// max bytes to transfer between leader and follower, defaults to 1000000
+    #[arg(long, value_name = "integer", env = "FLV_PEER_MAX_BYTES")]
+    pub peer_max_bytes: Option<u32>,
 
     #[arg(
         long,
@@ -66,13 +126,160 @@ pub struct SpuOpt {
     )]
     pub smart_engine_max_memory: Option<usize>,
 
+    /// Threads running SmartModules of fetch and produce requests, 0 runs them on the async executor
+    #[arg(long, value_name = "integer", env = "FLV_SMART_ENGINE_WORKER_THREADS")]
+    pub smart_engine_worker_threads: Option<usize>,
+
+    /// Resource usage profile, `edge` reduces threads and caches for constrained devices.
+    /// Defaults to `edge` on 32-bit ARM and `standard` otherwise
+    #[arg(long, value_enum, env = "FLV_SPU_RESOURCE_PROFILE")]
+    pub resource_profile: Option<ResourceProfile>,
+
+    /// Number of async executor threads, defaults to number of cores
+    /// (or 2 with the edge profile)
+    #[arg(long, value_name = "integer", env = "FLV_SPU_EXECUTOR_THREADS")]
+    pub executor_threads: Option<usize>,
+
+    /// Drain client connections older than this, e.g. 1h, so clients reconnect and are
+    /// rebalanced across SPUs
+    #[arg(long, value_name = "duration", value_parser = humantime::parse_duration, env = "FLV_SPU_MAX_CONNECTION_AGE")]
+    pub max_connection_age: Option<Duration>,
+
+    /// Close client connections without stream fetches after this long without requests
+    #[arg(long, value_name = "duration", value_parser = humantime::parse_duration, env = "FLV_SPU_CONNECTION_IDLE_TIMEOUT")]
+    pub connection_idle_timeout: Option<Duration>,
+
+    /// Time clients are given to move their streams off a draining connection, defaults to 30s
+    #[arg(long, value_name = "duration", value_parser = humantime::parse_duration, env = "FLV_SPU_CONNECTION_DRAIN_TIMEOUT")]
+    pub connection_drain_timeout: Option<Duration>,
+
+    /// Delete the offsets of consumers that have not committed for this long, defaults to 7d,
+    /// 0s keeps them
+    #[arg(long, value_name = "duration", value_parser = humantime::parse_duration, env = "FLV_SPU_CONSUMER_OFFSET_RETENTION")]
+    pub consumer_offset_retention: Option<Duration>,
+
+    /// Bytes per second each consumer application or consumer id can fetch from the SPU,
+    /// streams above it are slowed down. Unlimited by default
+    #[arg(long, value_name = "bytes", env = "FLV_SPU_CONSUMER_FETCH_QUOTA")]
+    pub consumer_fetch_quota: Option<u64>,
+
     #[clap(flatten)]
     tls: TlsConfig,
+
+    /// Scopes bound to the principals of client certificates, the TLS proxy then
+    /// identifies clients
+    #[arg(long = "authorization-scopes", value_name = "path")]
+    pub x509_auth_scopes: Option<PathBuf>,
+
+    /// Accept plaintext from a service mesh sidecar, client identities are read from
+    /// PROXY protocol v2 headers
+    #[arg(long, conflicts_with = "tls")]
+    pub mesh_identity: bool,
+
+    /// Address of the public service behind the mesh identity proxy
+    #[arg(long, value_name = "host:port", requires = "mesh_identity")]
+    pub bind_mesh_internal_public: Option<String>,
+
+    /// Peer trusted to send PROXY protocol headers besides loopback, can be repeated
+    #[arg(long, value_name = "ip", requires = "mesh_identity")]
+    pub mesh_trusted_peer: Vec<IpAddr>,
+
+    /// Authorize produce and consume with the Acls of the SC,
+    /// requires tls with authorization scopes or mesh identity
+    #[arg(long)]
+    pub acl: bool,
+
+    /// Principal allowed everything regardless of Acls, can be repeated
+    #[arg(long, value_name = "principal", requires = "acl")]
+    pub acl_super_user: Vec<String>,
+
+    #[clap(flatten)]
+    tcp: TcpConfig,
 }
 
 impl SpuOpt {
+    /// fill the options not given on the command line or by env from the config file
+    fn merge_config_file(&mut self, file: SpuConfigFile) {
+        self.id = self.id.or(file.id);
+        self.bind_public = self.bind_public.take().or(file.public_server);
+        self.bind_private = self.bind_private.take().or(file.private_server);
+        self.sc_addr = self.sc_addr.take().or(file.sc_addr);
+        self.sc_public_addr = self.sc_public_addr.take().or(file.sc_public_addr);
+        self.metrics_server = self.metrics_server.take().or(file.metrics_server);
+        if self.metrics_partition_topic.is_empty() {
+            self.metrics_partition_topic = file.metrics_partition_topic;
+        }
+        self.metrics_max_topics = self.metrics_max_topics.take().or(file.metrics_max_topics);
+        self.log_base_dir = self.log_base_dir.take().or(file.log_base_dir);
+        self.ephemeral_log_base_dir = self
+            .ephemeral_log_base_dir
+            .take()
+            .or(file.ephemeral_log_base_dir);
+        self.log_size = self.log_size.take().or(file.log_size);
+        self.index_max_bytes = self.index_max_bytes.or(file.index_max_bytes);
+        self.index_max_interval_bytes = self
+            .index_max_interval_bytes
+            .or(file.index_max_interval_bytes);
+        self.read_ahead_bytes = self.read_ahead_bytes.or(file.read_ahead_bytes);
+        self.tier_cache_bytes = self.tier_cache_bytes.or(file.tier_cache_bytes);
+        self.max_partition_size = self.max_partition_size.or(file.max_partition_size);
+        self.disk_high_watermark = self.disk_high_watermark.or(file.disk_high_watermark);
+        self.disk_low_watermark = self.disk_low_watermark.or(file.disk_low_watermark);
+        self.emergency_retention |= file.emergency_retention.unwrap_or_default();
+        self.peer_max_bytes = self.peer_max_bytes.or(file.peer_max_bytes);
+        self.smart_engine_max_memory = self
+            .smart_engine_max_memory
+            .or(file.smart_engine_max_memory);
+        self.smart_engine_worker_threads = self
+            .smart_engine_worker_threads
+            .or(file.smart_engine_worker_threads);
+        self.resource_profile = self.resource_profile.or(file.resource_profile);
+        self.executor_threads = self.executor_threads.or(file.executor_threads);
+        self.max_connection_age = self.max_connection_age.or(file.max_connection_age);
+        self.connection_idle_timeout = self
+            .connection_idle_timeout
+            .or(file.connection_idle_timeout);
+        self.connection_drain_timeout = self
+            .connection_drain_timeout
+            .or(file.connection_drain_timeout);
+        self.consumer_offset_retention = self
+            .consumer_offset_retention
+            .or(file.consumer_offset_retention);
+        self.consumer_fetch_quota = self.consumer_fetch_quota.or(file.consumer_fetch_quota);
+        self.x509_auth_scopes = self.x509_auth_scopes.take().or(file.authorization_scopes);
+        self.mesh_identity |= file.mesh_identity.unwrap_or_default();
+        self.bind_mesh_internal_public = self
+            .bind_mesh_internal_public
+            .take()
+            .or(file.bind_mesh_internal_public);
+        if self.mesh_trusted_peer.is_empty() {
+            self.mesh_trusted_peer = file.mesh_trusted_peer;
+        }
+        self.acl |= file.acl.unwrap_or_default();
+        if self.acl_super_user.is_empty() {
+            self.acl_super_user = file.acl_super_user;
+        }
+    }
+
+    /// Apply the config file and the `FLV_SPU__` variables, if any. Must be called before the
+    /// other options are used
+    pub fn load_config_file_or_exit(mut self) -> Self {
+        match SpuConfigFile::load(self.config.as_deref()) {
+            Ok(Some(file)) => {
+                info!(path = ?self.config, "using config file and environment overrides");
+                self.merge_config_file(file);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                print_cli_err!(err);
+                process::exit(-1);
+            }
+        }
+        self
+    }
+
     /// Validate SPU (Streaming Processing Unit) cli inputs and generate SpuConfig
-    fn get_spu_config(self) -> Result<(SpuConfig, Option<(TlsAcceptor, String)>)> {
+    fn get_spu_config(self) -> Result<(SpuConfig, Option<(ReloadableTlsAcceptor, String)>)> {
         let tls_acceptor = self.try_build_tls_acceptor()?;
         let (spu_config, tls_addr_opt) = self.as_spu_config()?;
         let tls_config = tls_acceptor.map(|it| (it, tls_addr_opt.unwrap()));
@@ -81,8 +288,6 @@ impl SpuOpt {
 
     #[allow(clippy::wrong_self_convention)]
     fn as_spu_config(self) -> Result<(SpuConfig, Option<String>)> {
-        use std::path::PathBuf;
-
         let mut config = SpuConfig {
             id: match self.id {
                 Some(id) => id,
@@ -94,16 +299,39 @@ impl SpuOpt {
             ..Default::default()
         };
 
+        let resource_profile = self.resource_profile.unwrap_or_default();
+        info!(profile = %resource_profile, "using resource profile");
+        resource_profile.apply(&mut config);
+
         if let Some(sc_endpoint) = self.sc_addr {
             info!("using sc endpoint from env var: {}", sc_endpoint);
             config.sc_endpoint = sc_endpoint;
         }
 
+        if let Some(sc_public_endpoint) = self.sc_public_addr {
+            info!("using sc public endpoint: {}", sc_public_endpoint);
+            config.sc_public_endpoint = Some(sc_public_endpoint);
+        }
+
+        if let Some(metrics_endpoint) = self.metrics_server {
+            info!("using metrics endpoint: {}", metrics_endpoint);
+            config.metrics_endpoint = Some(metrics_endpoint);
+        }
+        config.metrics_cardinality = MetricsCardinality {
+            partition_topics: self.metrics_partition_topic,
+            max_topics: self.metrics_max_topics,
+        };
+
         if let Some(log_base) = self.log_base_dir {
             info!("overriding log base: {}", log_base);
             config.log.base_dir = PathBuf::from(log_base);
         }
 
+        if let Some(ephemeral_base) = self.ephemeral_log_base_dir {
+            info!("overriding ephemeral log base: {}", ephemeral_base);
+            config.log.ephemeral_base_dir = PathBuf::from(ephemeral_base);
+        }
+
         if let Some(log_size) = self.log_size {
             info!("overriding log size {}", log_size);
             config.log.size = log_size;
@@ -119,6 +347,37 @@ impl SpuOpt {
             config.log.index_max_interval_bytes = index_max_interval_bytes;
         }
 
+        if let Some(read_ahead_bytes) = self.read_ahead_bytes {
+            info!("overriding read ahead bytes: {}", read_ahead_bytes);
+            config.log.read_ahead_bytes = read_ahead_bytes;
+        }
+
+        if let Some(tier_cache_bytes) = self.tier_cache_bytes {
+            info!("overriding tier cache bytes: {}", tier_cache_bytes);
+            config.log.tier_cache_bytes = tier_cache_bytes;
+        }
+
+        if let Some(max_partition_size) = self.max_partition_size {
+            if max_partition_size < SPU_PARTITION_MAX_BYTES_MIN {
+                return Err(anyhow!(
+                    "max partition size {max_partition_size} is less than minimum {SPU_PARTITION_MAX_BYTES_MIN}"
+                ));
+            }
+            info!("overriding max partition size: {}", max_partition_size);
+            config.log.max_partition_size = max_partition_size;
+        }
+
+        if let Some(high_watermark) = self.disk_high_watermark {
+            info!("overriding disk high watermark: {}%", high_watermark);
+            config.log.disk_high_watermark = high_watermark;
+        }
+
+        if let Some(low_watermark) = self.disk_low_watermark {
+            info!("overriding disk low watermark: {}%", low_watermark);
+            config.log.disk_low_watermark = low_watermark;
+        }
+        config.log.emergency_retention = self.emergency_retention;
+
         if let Some(public_addr) = self.bind_public {
             info!("overriding public addr: {}", public_addr);
             config.public_endpoint = public_addr;
@@ -136,12 +395,41 @@ impl SpuOpt {
                 .ok_or_else(|| anyhow!("non tls addr for public must be specified"))?;
         }
 
+        // options of the config file are not checked by clap
+        if self.mesh_identity && self.tls.tls {
+            return Err(anyhow!("mesh-identity can not be used with tls"));
+        }
+        if !self.mesh_identity
+            && (self.bind_mesh_internal_public.is_some() || !self.mesh_trusted_peer.is_empty())
+        {
+            return Err(anyhow!(
+                "bind-mesh-internal-public and mesh-trusted-peer require mesh-identity"
+            ));
+        }
+
+        // like tls, the mesh proxy takes over the public address
+        if self.mesh_identity {
+            let proxy_addr = config.public_endpoint.clone();
+            debug!("using mesh proxy addr: {}", proxy_addr);
+            config.public_endpoint = self.bind_mesh_internal_public.ok_or_else(|| {
+                anyhow!("internal addr for public must be specified with mesh identity")
+            })?;
+            config.mesh_proxy_endpoint = Some(proxy_addr);
+            config.mesh_trusted_peers = self.mesh_trusted_peer;
+        }
+
         if let Some(private_addr) = self.bind_private {
             info!("overriding private addr: {}", private_addr);
             config.private_endpoint = private_addr;
         }
 
-        config.peer_max_bytes = self.peer_max_bytes;
+        if let Some(peer_max_bytes) = self.peer_max_bytes {
+            info!("overriding peer max bytes: {}", peer_max_bytes);
+            config.peer_max_bytes = peer_max_bytes;
+        }
+
+        self.tcp.validate()?;
+        config.tcp = self.tcp;
 
         if let Some(smart_engine_max_memory) = self.smart_engine_max_memory {
             info!(
@@ -151,41 +439,112 @@ impl SpuOpt {
             config.smart_engine.store_max_memory = smart_engine_max_memory;
         }
 
+        if let Some(worker_threads) = self.smart_engine_worker_threads {
+            info!("overriding smart engine worker threads: {}", worker_threads);
+            config.smart_engine.worker_threads = worker_threads;
+        }
+
+        if let Some(max_age) = self.max_connection_age {
+            info!(?max_age, "overriding max connection age");
+            config.connection.max_age = Some(max_age);
+        }
+
+        if let Some(idle_timeout) = self.connection_idle_timeout {
+            info!(?idle_timeout, "overriding connection idle timeout");
+            config.connection.idle_timeout = Some(idle_timeout);
+        }
+
+        if let Some(drain_timeout) = self.connection_drain_timeout {
+            info!(?drain_timeout, "overriding connection drain timeout");
+            config.connection.drain_timeout = drain_timeout;
+        }
+
+        if let Some(retention) = self.consumer_offset_retention {
+            info!(?retention, "overriding consumer offset retention");
+            config.consumer_offset_retention = (!retention.is_zero()).then_some(retention);
+        }
+
+        if let Some(quota) = self.consumer_fetch_quota {
+            info!(quota, "using consumer fetch quota");
+            config.consumer_fetch_quota = Some(quota);
+        }
+
+        config.x509_auth_scopes = self.x509_auth_scopes;
+        let super_user_without_acl = !self.acl && !self.acl_super_user.is_empty();
+        if self.acl {
+            config.acl = Some(AclConfig {
+                super_users: self.acl_super_user.into_iter().collect(),
+            });
+        }
+
+        let mut invalid = validate(&config);
+        if self.executor_threads == Some(0) {
+            invalid.push("executor-threads must be greater than 0".to_owned());
+        }
+        if config.acl.is_some()
+            && config.mesh_proxy_endpoint.is_none()
+            && (tls_port.is_none() || config.x509_auth_scopes.is_none())
+        {
+            invalid.push(
+                "acl requires tls with authorization-scopes or mesh-identity to identify clients"
+                    .to_owned(),
+            );
+        }
+        if super_user_without_acl {
+            invalid.push("acl-super-user requires acl".to_owned());
+        }
+        if !invalid.is_empty() {
+            return Err(anyhow!(
+                "invalid configuration:\n  {}",
+                invalid.join("\n  ")
+            ));
+        }
+
         Ok((config, tls_port))
     }
 
-    fn try_build_tls_acceptor(&self) -> Result<Option<TlsAcceptor>> {
+    /// Size async executor thread pool.
+    ///
+    /// Must be called before the runtime is started; an existing
+    /// `ASYNC_STD_THREAD_COUNT` takes precedence over the profile default.
+    pub fn configure_executor(&self) {
+        const THREAD_COUNT_ENV: &str = "ASYNC_STD_THREAD_COUNT";
+
+        let threads = match self.executor_threads {
+            Some(threads) => threads,
+            None if std::env::var_os(THREAD_COUNT_ENV).is_some() => return,
+            None => match self.resource_profile.unwrap_or_default().executor_threads() {
+                Some(threads) => threads,
+                None => return,
+            },
+        };
+        info!(threads, "setting executor threads");
+        std::env::set_var(THREAD_COUNT_ENV, threads.to_string());
+    }
+
+    /// acceptor rebuilt when the certificate files are renewed
+    fn try_build_tls_acceptor(&self) -> Result<Option<ReloadableTlsAcceptor>> {
         let tls_config = &self.tls;
         if !tls_config.tls {
             return Ok(None);
         }
 
-        let server_crt_path = tls_config
-            .server_cert
-            .as_ref()
-            .ok_or_else(|| anyhow!("missing server cert"))?;
-        let server_key_path = tls_config
-            .server_key
-            .as_ref()
-            .ok_or_else(|| anyhow!("missing server key"))?;
-
-        let builder = (if tls_config.enable_client_cert {
-            let ca_path = tls_config
-                .ca_cert
-                .as_ref()
-                .ok_or_else(|| anyhow!("missing ca cert"))?;
-            TlsAcceptor::builder()?
-                .with_ssl_verify_mode(SslVerifyMode::PEER)
-                .with_ca_from_pem_file(ca_path)?
-        } else {
-            TlsAcceptor::builder()?
-        })
-        .with_certifiate_and_key_from_pem_files(server_crt_path, server_key_path)?;
-
-        Ok(Some(builder.build()))
+        let files = [
+            &tls_config.server_cert,
+            &tls_config.server_key,
+            &tls_config.ca_cert,
+        ]
+        .into_iter()
+        .flatten()
+        .map(PathBuf::from)
+        .collect();
+        let tls_config = tls_config.clone();
+        let acceptor = ReloadableTlsAcceptor::new(files, move || tls_config.build_acceptor())
+            .map_err(|err| anyhow!("{err}"))?;
+        Ok(Some(acceptor))
     }
 
-    pub fn process_spu_cli_or_exit(self) -> (SpuConfig, Option<(TlsAcceptor, String)>) {
+    pub fn process_spu_cli_or_exit(self) -> (SpuConfig, Option<(ReloadableTlsAcceptor, String)>) {
         match self.get_spu_config() {
             Err(err) => {
                 print_cli_err!(err);
@@ -196,6 +555,64 @@ impl SpuOpt {
     }
 }
 
+/// problems of the options that do not stop at the first one, so a dry run reports all of them
+fn validate(config: &SpuConfig) -> Vec<String> {
+    let log = &config.log;
+    let mut invalid = vec![];
+    for (name, percent) in [
+        ("disk-high-watermark", log.disk_high_watermark),
+        ("disk-low-watermark", log.disk_low_watermark),
+    ] {
+        if !(1..=100).contains(&percent) {
+            invalid.push(format!("{name} {percent}% is not between 1% and 100%"));
+        }
+    }
+    if log.disk_low_watermark > log.disk_high_watermark {
+        invalid.push(format!(
+            "disk-low-watermark {}% is above disk-high-watermark {}%",
+            log.disk_low_watermark, log.disk_high_watermark
+        ));
+    }
+    if log.index_max_interval_bytes > log.index_max_bytes {
+        invalid.push(format!(
+            "index-max-interval-bytes {} is above index-max-bytes {}",
+            log.index_max_interval_bytes, log.index_max_bytes
+        ));
+    }
+    if config.consumer_fetch_quota == Some(0) {
+        invalid.push("consumer-fetch-quota must be greater than 0".to_owned());
+    }
+    if config.peer_max_bytes == 0 {
+        invalid.push("peer-max-bytes must be greater than 0".to_owned());
+    }
+    if config.connection.drain_timeout.is_zero() {
+        invalid.push("connection-drain-timeout must be greater than 0s".to_owned());
+    }
+    for (name, addr) in [
+        ("public-server", &config.public_endpoint),
+        ("private-server", &config.private_endpoint),
+        ("sc-addr", &config.sc_endpoint),
+    ] {
+        if !addr.contains(':') {
+            invalid.push(format!("{name} {addr} is not host:port"));
+        }
+    }
+    let ephemeral_dir = &log.ephemeral_base_dir;
+    if !ephemeral_dir.exists() && !ephemeral_dir.parent().is_some_and(|parent| parent.is_dir()) {
+        invalid.push(format!(
+            "ephemeral-log-base-dir {} is missing, it should be on a tmpfs",
+            ephemeral_dir.display()
+        ));
+    }
+    if config.public_endpoint == config.private_endpoint {
+        invalid.push(format!(
+            "public-server and private-server are both {}",
+            config.public_endpoint
+        ));
+    }
+    invalid
+}
+
 /// find spu id from env, if not found, return error
 fn find_spu_id_from_env() -> Result<SpuId> {
     use std::env;
@@ -236,7 +653,7 @@ fn find_spu_id_from_env() -> Result<SpuId> {
 }
 
 /// same in the SC
-#[derive(Debug, Parser, Default)]
+#[derive(Debug, Parser, Default, Clone)]
 struct TlsConfig {
     /// enable tls
     #[arg(long)]
@@ -259,3 +676,161 @@ struct TlsConfig {
     /// TLS: address of non tls public service, required
     pub bind_non_tls_public: Option<String>,
 }
+
+impl TlsConfig {
+    fn build_acceptor(&self) -> Result<TlsAcceptor> {
+        let server_crt_path = self
+            .server_cert
+            .as_ref()
+            .ok_or_else(|| anyhow!("missing server cert"))?;
+        let server_key_path = self
+            .server_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("missing server key"))?;
+
+        let builder = (if self.enable_client_cert {
+            let ca_path = self
+                .ca_cert
+                .as_ref()
+                .ok_or_else(|| anyhow!("missing ca cert"))?;
+            TlsAcceptor::builder()?
+                .with_ssl_verify_mode(SslVerifyMode::PEER)
+                .with_ca_from_pem_file(ca_path)?
+        } else {
+            TlsAcceptor::builder()?
+        })
+        .with_certifiate_and_key_from_pem_files(server_crt_path, server_key_path)?;
+
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_types::defaults::{SPU_EDGE_LOG_SEGMENT_MAX_BYTES, SPU_EDGE_PEER_MAX_BYTES};
+
+    use super::*;
+
+    #[test]
+    fn test_edge_profile() {
+        let opt = SpuOpt::parse_from(["spu", "-i", "5001", "--resource-profile", "edge"]);
+        let (config, _) = opt.as_spu_config().expect("config");
+        assert_eq!(config.log.segment_max_bytes, SPU_EDGE_LOG_SEGMENT_MAX_BYTES);
+        assert_eq!(config.peer_max_bytes, SPU_EDGE_PEER_MAX_BYTES);
+
+        // explicit parameters take precedence over profile
+        let opt = SpuOpt::parse_from([
+            "spu",
+            "-i",
+            "5001",
+            "--resource-profile",
+            "edge",
+            "--peer-max-bytes",
+            "1000",
+        ]);
+        let (config, _) = opt.as_spu_config().expect("config");
+        assert_eq!(config.peer_max_bytes, 1000);
+    }
+
+    #[test]
+    fn test_disk_watermarks() {
+        let opt = SpuOpt::parse_from([
+            "spu",
+            "-i",
+            "5001",
+            "--disk-high-watermark",
+            "80",
+            "--disk-low-watermark",
+            "70",
+            "--emergency-retention",
+        ]);
+        let (config, _) = opt.as_spu_config().expect("config");
+        assert_eq!(config.log.disk_high_watermark, 80);
+        assert_eq!(config.log.disk_low_watermark, 70);
+        assert!(config.log.emergency_retention);
+
+        let opt = SpuOpt::parse_from(["spu", "-i", "5001", "--disk-high-watermark", "50"]);
+        assert!(opt.as_spu_config().is_err());
+    }
+
+    #[test]
+    fn test_config_file() {
+        let file: SpuConfigFile = toml::from_str(
+            r#"
+            id = 5002
+            peer_max_bytes = 2000
+            resource_profile = "edge"
+            emergency_retention = true
+            max_connection_age = "1h"
+            "#,
+        )
+        .expect("config file");
+
+        // options given on the command line take precedence
+        let mut opt = SpuOpt::parse_from(["spu", "-i", "5001"]);
+        opt.merge_config_file(file);
+        let (config, _) = opt.as_spu_config().expect("config");
+        assert_eq!(config.id, 5001);
+        assert_eq!(config.peer_max_bytes, 2000);
+        assert_eq!(config.log.segment_max_bytes, SPU_EDGE_LOG_SEGMENT_MAX_BYTES);
+        assert!(config.log.emergency_retention);
+        assert_eq!(config.connection.max_age, Some(Duration::from_secs(3600)));
+
+        let err = toml::from_str::<SpuConfigFile>("log_dir = \"/tmp\"").expect_err("unknown key");
+        assert!(err.to_string().contains("unknown field `log_dir`"));
+    }
+
+    #[test]
+    fn test_mesh_identity() {
+        let opt = SpuOpt::parse_from([
+            "spu",
+            "-i",
+            "5001",
+            "--public-server",
+            "0.0.0.0:9010",
+            "--mesh-identity",
+            "--bind-mesh-internal-public",
+            "127.0.0.1:9015",
+            "--mesh-trusted-peer",
+            "10.0.0.7",
+            "--acl",
+        ]);
+        let (config, _) = opt.as_spu_config().expect("config");
+        assert_eq!(config.public_endpoint, "127.0.0.1:9015");
+        assert_eq!(config.mesh_proxy_endpoint.as_deref(), Some("0.0.0.0:9010"));
+        assert_eq!(
+            config.mesh_trusted_peers,
+            vec!["10.0.0.7".parse::<IpAddr>().unwrap()]
+        );
+
+        let mut opt = SpuOpt::parse_from(["spu", "-i", "5001"]);
+        opt.merge_config_file(SpuConfigFile {
+            mesh_identity: Some(true),
+            ..Default::default()
+        });
+        let err = opt.as_spu_config().expect_err("invalid").to_string();
+        assert!(err.contains("mesh identity"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let mut opt = SpuOpt::parse_from([
+            "spu",
+            "-i",
+            "5001",
+            "--private-server",
+            "0.0.0.0:9005",
+            "--executor-threads",
+            "0",
+        ]);
+        opt.merge_config_file(SpuConfigFile {
+            disk_high_watermark: Some(120),
+            public_server: Some("0.0.0.0:9005".to_owned()),
+            ..Default::default()
+        });
+        let err = opt.as_spu_config().expect_err("invalid").to_string();
+        assert!(err.contains("disk-high-watermark 120%"), "{err}");
+        assert!(err.contains("public-server and private-server"), "{err}");
+        assert!(err.contains("executor-threads"), "{err}");
+    }
+}
@@ -0,0 +1,91 @@
+//!
+//! # SPU configuration file
+//!
+//! TOML file with the options of the command line, named after their long flag in snake case:
+//!
+//! ```toml
+//! log_base_dir = "/var/lib/fluvio/data"
+//! disk_high_watermark = 90
+//! emergency_retention = true
+//! max_connection_age = "1h"
+//! ```
+//!
+//! Each option can also be set by a variable named after its key with the `FLV_SPU__` prefix,
+//! such as `FLV_SPU__LOG_SIZE=10G`, which spares templating the file in containers.
+//!
+//! From highest to lowest precedence, an option is taken from:
+//! 1. the command line or the environment variable of its flag, such as `SPU_INDEX`
+//! 2. its `FLV_SPU__` variable
+//! 3. the config file
+//! 4. the default, or the resource profile
+//!
+//! TLS and TCP options are only read from the command line.
+//!
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use fluvio_types::config_file::load_with_env_overrides;
+
+use super::ResourceProfile;
+
+/// prefix of the variables overriding the keys of the config file
+pub(crate) const ENV_PREFIX: &str = "FLV_SPU__";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SpuConfigFile {
+    pub id: Option<i32>,
+    pub public_server: Option<String>,
+    pub private_server: Option<String>,
+    pub sc_addr: Option<String>,
+    pub sc_public_addr: Option<String>,
+    pub metrics_server: Option<String>,
+    #[serde(default)]
+    pub metrics_partition_topic: Vec<String>,
+    pub metrics_max_topics: Option<usize>,
+    pub log_base_dir: Option<String>,
+    pub ephemeral_log_base_dir: Option<String>,
+    pub log_size: Option<String>,
+    pub index_max_bytes: Option<u32>,
+    pub index_max_interval_bytes: Option<u32>,
+    pub read_ahead_bytes: Option<u32>,
+    pub tier_cache_bytes: Option<u64>,
+    pub max_partition_size: Option<u64>,
+    pub disk_high_watermark: Option<u8>,
+    pub disk_low_watermark: Option<u8>,
+    pub emergency_retention: Option<bool>,
+    pub peer_max_bytes: Option<u32>,
+    pub smart_engine_max_memory: Option<usize>,
+    pub smart_engine_worker_threads: Option<usize>,
+    pub resource_profile: Option<ResourceProfile>,
+    pub executor_threads: Option<usize>,
+    #[serde(default, with = "humantime_serde")]
+    pub max_connection_age: Option<Duration>,
+    #[serde(default, with = "humantime_serde")]
+    pub connection_idle_timeout: Option<Duration>,
+    #[serde(default, with = "humantime_serde")]
+    pub connection_drain_timeout: Option<Duration>,
+    #[serde(default, with = "humantime_serde")]
+    pub consumer_offset_retention: Option<Duration>,
+    pub consumer_fetch_quota: Option<u64>,
+    pub authorization_scopes: Option<PathBuf>,
+    pub mesh_identity: Option<bool>,
+    pub bind_mesh_internal_public: Option<String>,
+    #[serde(default)]
+    pub mesh_trusted_peer: Vec<IpAddr>,
+    pub acl: Option<bool>,
+    #[serde(default)]
+    pub acl_super_user: Vec<String>,
+}
+
+impl SpuConfigFile {
+    /// options of the config file and the `FLV_SPU__` variables, `None` if there are neither.
+    /// Unknown keys list the valid ones
+    pub(crate) fn load(path: Option<&Path>) -> Result<Option<Self>> {
+        Ok(load_with_env_overrides(path, ENV_PREFIX, std::env::vars())?)
+    }
+}
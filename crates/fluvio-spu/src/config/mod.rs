@@ -1,6 +1,7 @@
 mod cli;
+mod file;
 mod spu_config;
 
 pub use self::cli::SpuOpt;
 
-pub use self::spu_config::{SpuConfig, ReplicationConfig};
+pub use self::spu_config::{SpuConfig, ReplicationConfig, ResourceProfile, ConnectionConfig, AclConfig};
@@ -8,18 +8,26 @@
 //!     3) custom configuration or default configuration (from file)
 //!
 
+use std::collections::HashSet;
 use std::env;
+use std::net::IpAddr;
 use std::path::PathBuf;
+use std::time::Duration;
+
+use fluvio_service::metrics::MetricsCardinality;
+use fluvio_socket::TcpConfig;
 
 // defaults values
 use fluvio_types::defaults::SPU_PUBLIC_PORT;
 use fluvio_types::defaults::SPU_PRIVATE_PORT;
 use fluvio_types::defaults::SC_PRIVATE_PORT;
 use fluvio_types::defaults::SPU_LOG_BASE_DIR;
+use fluvio_types::defaults::SPU_EPHEMERAL_LOG_BASE_DIR;
 use fluvio_types::defaults::SPU_LOG_SIZE;
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_BYTES;
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_INTERVAL_BYTES;
 use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
+use fluvio_types::defaults::SPU_PARTITION_MAX_BYTES;
 use fluvio_types::defaults::SPU_RETRY_SC_TIMEOUT_MS;
 use fluvio_types::defaults::SPU_SMARTENGINE_STORE_MAX_BYTES;
 
@@ -27,13 +35,73 @@ use fluvio_types::defaults::SPU_SMARTENGINE_STORE_MAX_BYTES;
 
 use fluvio_types::defaults::SPU_MIN_IN_SYNC_REPLICAS;
 use fluvio_types::defaults::FLV_LOG_BASE_DIR;
+use fluvio_types::defaults::FLV_EPHEMERAL_LOG_BASE_DIR;
 use fluvio_types::defaults::FLV_LOG_SIZE;
 use fluvio_types::SpuId;
 use fluvio_storage::config::ReplicaConfig;
 use fluvio_types::defaults::{
     STORAGE_FLUSH_IDLE_MSEC, STORAGE_FLUSH_WRITE_COUNT, STORAGE_MAX_BATCH_SIZE,
+    STORAGE_READ_AHEAD_BYTES, STORAGE_TIER_CACHE_BYTES,
+};
+use fluvio_types::defaults::{
+    SPU_EDGE_EXECUTOR_THREADS, SPU_EDGE_LOG_INDEX_MAX_BYTES, SPU_EDGE_LOG_SEGMENT_MAX_BYTES,
+    SPU_EDGE_PEER_MAX_BYTES, SPU_EDGE_READ_AHEAD_BYTES, SPU_EDGE_SMARTENGINE_STORE_MAX_BYTES,
+    SPU_EDGE_SMARTENGINE_WORKER_THREADS,
 };
 
+/// Resource usage profile of SPU
+///
+/// `edge` trades throughput for a smaller footprint: fewer executor threads,
+/// smaller segments, indexes, peer transfers and SmartModule store.
+/// It is the default on 32-bit ARM targets.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceProfile {
+    Standard,
+    Edge,
+}
+
+impl Default for ResourceProfile {
+    fn default() -> Self {
+        if cfg!(target_arch = "arm") {
+            Self::Edge
+        } else {
+            Self::Standard
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Standard => write!(f, "standard"),
+            Self::Edge => write!(f, "edge"),
+        }
+    }
+}
+
+impl ResourceProfile {
+    /// apply profile defaults, explicit parameters are applied afterwards
+    pub fn apply(&self, config: &mut SpuConfig) {
+        if let Self::Edge = self {
+            config.log.index_max_bytes = SPU_EDGE_LOG_INDEX_MAX_BYTES;
+            config.log.segment_max_bytes = SPU_EDGE_LOG_SEGMENT_MAX_BYTES;
+            config.peer_max_bytes = SPU_EDGE_PEER_MAX_BYTES;
+            config.smart_engine.store_max_memory = SPU_EDGE_SMARTENGINE_STORE_MAX_BYTES;
+            config.smart_engine.worker_threads = SPU_EDGE_SMARTENGINE_WORKER_THREADS;
+            config.log.read_ahead_bytes = SPU_EDGE_READ_AHEAD_BYTES;
+        }
+    }
+
+    /// number of async executor threads, None to use runtime default (one per core)
+    pub fn executor_threads(&self) -> Option<usize> {
+        match self {
+            Self::Standard => None,
+            Self::Edge => Some(SPU_EDGE_EXECUTOR_THREADS),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ReplicationConfig {
     pub min_in_sync_replicas: u16,
@@ -50,6 +118,8 @@ impl Default for ReplicationConfig {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Log {
     pub base_dir: PathBuf,
+    /// base dir of ephemeral topics, should be a memory backed file system
+    pub ephemeral_base_dir: PathBuf,
     pub size: String,
     pub index_max_bytes: u32,
     pub index_max_interval_bytes: u32,
@@ -57,6 +127,18 @@ pub struct Log {
     pub flush_write_count: u32,
     pub flush_idle_msec: u32,
     pub max_batch_size: u32,
+    /// bytes read ahead of consumers replaying previous segments
+    pub read_ahead_bytes: u32,
+    /// bytes of tiered segments cached for consumers
+    pub tier_cache_bytes: u64,
+    /// size of partitions of topics without a max partition size
+    pub max_partition_size: u64,
+    /// percentage of the file system of the base dir past which produces are rejected
+    pub disk_high_watermark: u8,
+    /// percentage under which produces are accepted again after the disk was full
+    pub disk_low_watermark: u8,
+    /// remove the oldest segments of the largest partitions while the disk is full
+    pub emergency_retention: bool,
 }
 
 impl Default for Log {
@@ -65,6 +147,10 @@ impl Default for Log {
             base_dir: PathBuf::from(
                 env::var(FLV_LOG_BASE_DIR).unwrap_or_else(|_| SPU_LOG_BASE_DIR.to_owned()),
             ),
+            ephemeral_base_dir: PathBuf::from(
+                env::var(FLV_EPHEMERAL_LOG_BASE_DIR)
+                    .unwrap_or_else(|_| SPU_EPHEMERAL_LOG_BASE_DIR.to_owned()),
+            ),
             size: env::var(FLV_LOG_SIZE).unwrap_or_else(|_| SPU_LOG_SIZE.to_owned()),
             index_max_bytes: SPU_LOG_INDEX_MAX_BYTES,
             index_max_interval_bytes: SPU_LOG_INDEX_MAX_INTERVAL_BYTES,
@@ -72,6 +158,12 @@ impl Default for Log {
             flush_write_count: STORAGE_FLUSH_WRITE_COUNT,
             flush_idle_msec: STORAGE_FLUSH_IDLE_MSEC,
             max_batch_size: STORAGE_MAX_BATCH_SIZE,
+            read_ahead_bytes: STORAGE_READ_AHEAD_BYTES,
+            tier_cache_bytes: STORAGE_TIER_CACHE_BYTES,
+            max_partition_size: SPU_PARTITION_MAX_BYTES,
+            disk_high_watermark: DEFAULT_DISK_HIGH_WATERMARK,
+            disk_low_watermark: DEFAULT_DISK_LOW_WATERMARK,
+            emergency_retention: false,
         }
     }
 }
@@ -79,12 +171,43 @@ impl Default for Log {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct SmartEngineConfig {
     pub store_max_memory: usize,
+    /// threads running fetch and produce SmartModules, 0 runs them on the async executor
+    pub worker_threads: usize,
 }
 
 impl Default for SmartEngineConfig {
     fn default() -> Self {
         Self {
             store_max_memory: SPU_SMARTENGINE_STORE_MAX_BYTES,
+            worker_threads: std::thread::available_parallelism()
+                .map(usize::from)
+                .unwrap_or(1),
+        }
+    }
+}
+
+const DEFAULT_DISK_HIGH_WATERMARK: u8 = 95;
+const DEFAULT_DISK_LOW_WATERMARK: u8 = 90;
+const DEFAULT_CONNECTION_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_CONSUMER_OFFSET_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Lifetime of client connections
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ConnectionConfig {
+    /// connections older than this are drained so clients reconnect, possibly to another SPU
+    pub max_age: Option<Duration>,
+    /// connections without stream fetches are closed after this long without requests
+    pub idle_timeout: Option<Duration>,
+    /// time clients are given to move their streams off a draining connection
+    pub drain_timeout: Duration,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            max_age: None,
+            idle_timeout: None,
+            drain_timeout: DEFAULT_CONNECTION_DRAIN_TIMEOUT,
         }
     }
 }
@@ -103,6 +226,8 @@ pub struct SpuConfig {
     // sc (remote server) endpoint
     pub sc_endpoint: String,
     pub sc_retry_ms: u16,
+    /// public endpoint of the SC, used to produce to dead letter topics
+    pub sc_public_endpoint: Option<String>,
 
     // parameters
     pub replication: ReplicationConfig,
@@ -111,6 +236,41 @@ pub struct SpuConfig {
     pub peer_max_bytes: u32,
 
     pub smart_engine: SmartEngineConfig,
+
+    /// tcp options for connections to and from other SPUs and clients
+    pub tcp: TcpConfig,
+
+    pub connection: ConnectionConfig,
+
+    /// offsets of consumers that have not committed for this long are deleted, None keeps them
+    pub consumer_offset_retention: Option<Duration>,
+
+    /// bytes per second each consumer identity can fetch, unlimited if None
+    pub consumer_fetch_quota: Option<u64>,
+
+    /// address of the Prometheus metrics endpoint, disabled if None
+    pub metrics_endpoint: Option<String>,
+
+    /// limits of the series of per partition metrics
+    pub metrics_cardinality: MetricsCardinality,
+
+    /// scopes bound to the principals of client certificates, read by the TLS and mesh proxies
+    pub x509_auth_scopes: Option<PathBuf>,
+
+    /// address of the proxy reading client identities from the service mesh
+    pub mesh_proxy_endpoint: Option<String>,
+
+    /// peers besides loopback trusted to send identities to the mesh proxy
+    pub mesh_trusted_peers: Vec<IpAddr>,
+
+    /// authorize produce and consume with the Acls of the SC, everything is allowed if None
+    pub acl: Option<AclConfig>,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct AclConfig {
+    /// principals allowed everything
+    pub super_users: HashSet<String>,
 }
 
 impl Default for SpuConfig {
@@ -123,9 +283,20 @@ impl Default for SpuConfig {
             sc_endpoint: format!("localhost:{SC_PRIVATE_PORT}"),
             replication: ReplicationConfig::default(),
             sc_retry_ms: SPU_RETRY_SC_TIMEOUT_MS,
+            sc_public_endpoint: None,
             log: Log::default(),
             peer_max_bytes: fluvio_storage::FileReplica::PREFER_MAX_LEN,
             smart_engine: SmartEngineConfig::default(),
+            tcp: TcpConfig::default(),
+            connection: ConnectionConfig::default(),
+            consumer_offset_retention: Some(DEFAULT_CONSUMER_OFFSET_RETENTION),
+            consumer_fetch_quota: None,
+            metrics_endpoint: None,
+            metrics_cardinality: MetricsCardinality::default(),
+            x509_auth_scopes: None,
+            mesh_proxy_endpoint: None,
+            mesh_trusted_peers: vec![],
+            acl: None,
         }
     }
 }
@@ -161,6 +332,13 @@ impl SpuConfig {
     pub fn storage(&self) -> &Log {
         &self.log
     }
+
+    /// dir of the ephemeral replicas of this SPU
+    pub fn ephemeral_replicas_dir(&self) -> PathBuf {
+        self.log
+            .ephemeral_base_dir
+            .join(format!("spu-logs-{}", self.id))
+    }
 }
 
 impl From<&SpuConfig> for ReplicaConfig {
@@ -168,12 +346,16 @@ impl From<&SpuConfig> for ReplicaConfig {
         let log = &config.log;
         ReplicaConfig::builder()
             .base_dir(log.base_dir.join(format!("spu-logs-{}", config.id)))
+            .ephemeral_base_dir(config.ephemeral_replicas_dir())
             .index_max_bytes(log.index_max_bytes)
             .index_max_interval_bytes(log.index_max_interval_bytes)
             .segment_max_bytes(log.segment_max_bytes)
             .flush_write_count(log.flush_write_count)
             .flush_idle_msec(log.flush_idle_msec)
             .max_batch_size(log.max_batch_size)
+            .read_ahead_bytes(log.read_ahead_bytes)
+            .tier_cache_bytes(log.tier_cache_bytes)
+            .max_partition_size(log.max_partition_size)
             .build()
     }
 }
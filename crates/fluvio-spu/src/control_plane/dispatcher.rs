@@ -15,12 +15,14 @@ use flv_util::print_cli_err;
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_protocol::api::RequestMessage;
-use fluvio_socket::{FluvioSocket, FluvioSink};
+use fluvio_socket::{tcp_connector, FluvioSocket, FluvioSink};
 use fluvio_storage::FileReplica;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
+use fluvio_controlplane::spu_api::update_acl::UpdateAclRequest;
 
 use crate::core::SharedGlobalContext;
+use crate::core::disk::is_ephemeral;
 
 use super::message_sink::SharedLrsStatusUpdate;
 use super::SharedMirrorStatusUpdate;
@@ -33,6 +35,7 @@ struct DispatcherCounter {
     pub reconnect: u64,       // number of reconnect to sc
     pub smartmodule: u64,     // number of sm updates from sc
     pub mirror: u64,          // number of mirror updates from sc
+    pub acl: u64,             // number of acl updates from sc
 }
 
 /// Controller for handling connection to SC
@@ -167,6 +170,13 @@ impl ScDispatcher<FileReplica> {
                                 break;
                             }
                         },
+                        Some(Ok(InternalSpuRequest::UpdateAclRequest(request))) => {
+                            self.counter.acl += 1;
+                            if let Err(err) = self.handle_update_acl_request(request).await {
+                                error!(%err, "error handling update acl request", );
+                                break;
+                            }
+                        },
                         Some(Err(err)) => {
                             error!(%err, "Api error");
                             break;
@@ -189,7 +199,16 @@ impl ScDispatcher<FileReplica> {
     /// send status back to sc, if there is error return false
     #[instrument(skip(self))]
     async fn send_lrs_status_back_to_sc(&mut self, sc_sink: &mut FluvioSink) -> Result<()> {
-        let requests = self.status_update.remove_all().await;
+        let storage_full = self.ctx.disk().is_full();
+        let mut requests = self.status_update.remove_all().await;
+        for request in &mut requests {
+            request.storage_full = storage_full
+                && self
+                    .ctx
+                    .replica_localstore()
+                    .spec(&request.id)
+                    .is_some_and(|replica| !is_ephemeral(&replica));
+        }
 
         if requests.is_empty() {
             trace!("sending empty status");
@@ -271,7 +290,12 @@ impl ScDispatcher<FileReplica> {
                 "trying to create socket to sc",
 
             );
-            match FluvioSocket::connect(&sc_endpoint).await {
+            match FluvioSocket::connect_with_connector(
+                &sc_endpoint,
+                tcp_connector(&self.ctx.config().tcp).as_ref(),
+            )
+            .await
+            {
                 Ok(socket) => {
                     info!(spu_id, "connected to sc for spu");
                     self.counter.reconnect += 1;
@@ -427,4 +451,35 @@ impl ScDispatcher<FileReplica> {
 
         Ok(())
     }
+
+    ///
+    /// Handle Acl update sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_acl_request")]
+    async fn handle_update_acl_request(
+        &mut self,
+        req_msg: RequestMessage<UpdateAclRequest>,
+    ) -> anyhow::Result<()> {
+        let (_, request) = req_msg.get_header_request();
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received acl sync all"
+            );
+            self.ctx.acls_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received acl changes"
+            );
+            self.ctx.acls_localstore().apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished acl update");
+
+        Ok(())
+    }
 }
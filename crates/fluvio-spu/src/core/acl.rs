@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use fluvio_controlplane::spu_api::update_acl::Acl;
+use fluvio_controlplane_metadata::acl::AclPermission;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+pub type AclLocalStore = LocalStore<Acl>;
+
+pub type SharedAclLocalStore = Arc<AclLocalStore>;
+
+impl Spec for Acl {
+    const LABEL: &'static str = "Acl";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
+
+impl AclLocalStore {
+    /// true if an acl grants the permission on the topic to the principal
+    pub fn allows_topic(&self, principal: &str, topic: &str, permission: AclPermission) -> bool {
+        self.read()
+            .values()
+            .any(|acl| acl.spec.allows_topic(principal, topic, permission))
+    }
+}
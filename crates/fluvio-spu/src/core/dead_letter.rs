@@ -0,0 +1,151 @@
+//!
+//! # Dead Letter Producer
+//!
+//! Routes records rejected by a topic validation SmartModule to the dead letter topic,
+//! and records failing a SmartModule invoked with a dead letter topic to that topic.
+//! Dead letter topics may be led by any SPU, so records are produced through the SC
+//! public endpoint like any other client.
+//!
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use async_lock::Mutex;
+use tracing::debug;
+
+use fluvio::lineage::{Lineage, LineageSource};
+use fluvio::{Fluvio, FluvioConfig, TopicProducerConfigBuilder};
+use fluvio_protocol::record::{RawRecords, RecordKey, RecordSet, ReplicaKey};
+
+use crate::smartengine::DeadLetter;
+
+pub struct DeadLetterProducer {
+    sc_endpoint: Option<String>,
+    client: Mutex<Option<Fluvio>>,
+}
+
+impl fmt::Debug for DeadLetterProducer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DeadLetterProducer({:?})", self.sc_endpoint)
+    }
+}
+
+impl DeadLetterProducer {
+    pub fn new(sc_public_endpoint: Option<String>) -> Self {
+        Self {
+            sc_endpoint: sc_public_endpoint,
+            client: Mutex::new(None),
+        }
+    }
+
+    /// produce every record of the set to `topic`, keeping keys and values.
+    /// Records carry the `source` replica as lineage, they were never appended there.
+    pub async fn send(
+        &self,
+        topic: &str,
+        source: &ReplicaKey,
+        records: &RecordSet<RawRecords>,
+    ) -> Result<()> {
+        let mut connected = self.client.lock().await;
+        let client = self.connect(&mut connected, topic).await?;
+
+        let lineage = Lineage {
+            source: Some(LineageSource {
+                topic: source.topic.clone(),
+                partition: source.partition,
+                offset: None,
+            }),
+            ..Default::default()
+        };
+        let config = TopicProducerConfigBuilder::default()
+            .headers(lineage.to_headers())
+            .build()?;
+        let producer = client.topic_producer_with_config(topic, config).await?;
+        for batch in &records.batches {
+            for record in batch.memory_records()? {
+                producer
+                    .send(
+                        RecordKey::from_option(record.key().cloned()),
+                        record.value().clone(),
+                    )
+                    .await?;
+            }
+        }
+        producer.flush().await?;
+        Ok(())
+    }
+
+    /// produce the records that failed a SmartModule reading `source` to their dead letter
+    /// topics, each record carries the failure in its headers. Offsets are set in the lineage
+    /// when the records were `appended` to `source`, produced records never were.
+    pub async fn send_failures(
+        &self,
+        source: &ReplicaKey,
+        failures: Vec<DeadLetter>,
+        appended: bool,
+    ) -> Result<()> {
+        let mut by_topic: BTreeMap<String, Vec<DeadLetter>> = BTreeMap::new();
+        for failure in failures {
+            by_topic
+                .entry(failure.topic.clone())
+                .or_default()
+                .push(failure);
+        }
+
+        let mut connected = self.client.lock().await;
+        for (topic, failures) in by_topic {
+            let client = self.connect(&mut connected, &topic).await?;
+            let producer = client.topic_producer(&topic).await?;
+            for failure in failures {
+                let mut record = failure.to_record();
+                let lineage = Lineage {
+                    source: Some(LineageSource {
+                        topic: source.topic.clone(),
+                        partition: source.partition,
+                        offset: appended.then_some(failure.error.offset),
+                    }),
+                    ..Default::default()
+                };
+                for header in lineage.to_headers() {
+                    record.add_header(header.key, header.value);
+                }
+                producer
+                    .send_with_headers(
+                        RecordKey::from_option(record.key().cloned()),
+                        record.value().clone(),
+                        record
+                            .headers()
+                            .iter()
+                            .map(|header| (header.key.clone(), header.value.clone())),
+                    )
+                    .await?;
+            }
+            producer.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn connect<'a>(
+        &self,
+        connected: &'a mut Option<Fluvio>,
+        topic: &str,
+    ) -> Result<&'a Fluvio> {
+        let Some(sc_endpoint) = &self.sc_endpoint else {
+            return Err(anyhow!(
+                "SC public endpoint is not configured, dead letter topic {topic} is not reachable"
+            ));
+        };
+
+        let client = match connected.take() {
+            Some(client) => connected.insert(client),
+            None => {
+                debug!(%sc_endpoint, "connecting dead letter producer");
+                let mut config = FluvioConfig::new(sc_endpoint.clone());
+                config.use_spu_local_address = true;
+                connected.insert(Fluvio::connect_with_config(&config).await?)
+            }
+        };
+        Ok(client)
+    }
+}
@@ -0,0 +1,198 @@
+//!
+//! # Disk
+//!
+//! Tracks the space left on the file system of the log base dir. Produces are rejected with
+//! `StorageFull` once its usage crosses the high watermark, until it falls back under the low
+//! watermark. Ephemeral partitions are kept in memory and are not affected.
+//!
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{debug, info, warn};
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_storage::ReplicaStorage;
+use fluvio_storage::disk::DiskUsage;
+
+use crate::config::Log;
+
+use super::DefaultSharedGlobalContext;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default)]
+pub(crate) struct DiskState {
+    full: AtomicBool,
+    used_bytes: AtomicU64,
+    total_bytes: AtomicU64,
+}
+
+impl DiskState {
+    pub(crate) fn is_full(&self) -> bool {
+        self.full.load(Ordering::Acquire)
+    }
+
+    /// a write ran out of space, returns true if the disk was not known to be full
+    pub(crate) fn set_full(&self) -> bool {
+        !self.full.swap(true, Ordering::AcqRel)
+    }
+
+    /// usage of the last check
+    pub(crate) fn usage(&self) -> DiskUsage {
+        let total_bytes = self.total_bytes.load(Ordering::Relaxed);
+        DiskUsage {
+            total_bytes,
+            available_bytes: total_bytes.saturating_sub(self.used_bytes.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// record the usage, returns the new state when it changes
+    fn update(&self, usage: DiskUsage, config: &Log) -> Option<bool> {
+        self.used_bytes.store(usage.used_bytes(), Ordering::Relaxed);
+        self.total_bytes.store(usage.total_bytes, Ordering::Relaxed);
+        let used_percent = usage.used_percent();
+        if !self.is_full() && used_percent >= config.disk_high_watermark {
+            self.full.store(true, Ordering::Release);
+            Some(true)
+        } else if self.is_full() && used_percent < config.disk_low_watermark {
+            self.full.store(false, Ordering::Release);
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// the partition is kept in memory, the disk does not hold it
+pub(crate) fn is_ephemeral(replica: &Replica) -> bool {
+    replica
+        .storage
+        .as_ref()
+        .is_some_and(|storage| storage.is_ephemeral())
+}
+
+pub(crate) fn start_disk_monitor(ctx: DefaultSharedGlobalContext) {
+    spawn(async move {
+        loop {
+            sleep(CHECK_INTERVAL).await;
+            if let Err(err) = check_disk(&ctx).await {
+                warn!(%err, "unable to check disk usage");
+            }
+        }
+    });
+}
+
+async fn check_disk(ctx: &DefaultSharedGlobalContext) -> Result<()> {
+    let config = &ctx.config().log;
+    let usage = DiskUsage::of(&config.base_dir)?;
+    let used_percent = usage.used_percent();
+    debug!(
+        used_percent,
+        available = usage.available_bytes,
+        "disk usage"
+    );
+
+    match ctx.disk().update(usage, config) {
+        Some(true) => {
+            warn!(
+                used_percent,
+                high_watermark = config.disk_high_watermark,
+                dir = %config.base_dir.display(),
+                "disk is full, rejecting produces"
+            );
+            update_leader_status(ctx).await;
+        }
+        Some(false) => {
+            info!(
+                used_percent,
+                low_watermark = config.disk_low_watermark,
+                "disk space freed, accepting produces"
+            );
+            update_leader_status(ctx).await;
+        }
+        None => {}
+    }
+
+    if ctx.disk().is_full() && config.emergency_retention {
+        free_space(ctx).await;
+    }
+    Ok(())
+}
+
+/// report the state of the disk in the status of the partitions
+async fn update_leader_status(ctx: &DefaultSharedGlobalContext) {
+    let leaders: Vec<_> = ctx.leaders_state().read().await.values().cloned().collect();
+    for leader in leaders {
+        leader.update_status().await;
+    }
+}
+
+/// remove the oldest segment of the largest partition
+async fn free_space(ctx: &DefaultSharedGlobalContext) {
+    let leaders: Vec<_> = ctx.leaders_state().read().await.values().cloned().collect();
+    let mut largest = None;
+    let mut largest_size = 0;
+    for leader in leaders {
+        if is_ephemeral(leader.get_replica()) {
+            continue;
+        }
+        let size = leader.read().await.get_partition_size();
+        if size > largest_size {
+            largest_size = size;
+            largest = Some(leader);
+        }
+    }
+    let Some(leader) = largest else {
+        return;
+    };
+
+    let freed = leader.read().await.remove_oldest_segment().await;
+    if freed > 0 {
+        warn!(
+            replica = %leader.id(),
+            freed,
+            "emergency retention removed the oldest segment"
+        );
+        leader.update_status().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_disk_state() {
+        let config = Log {
+            disk_high_watermark: 90,
+            disk_low_watermark: 80,
+            ..Default::default()
+        };
+        let usage = |used_percent: u64| DiskUsage {
+            total_bytes: 100,
+            available_bytes: 100 - used_percent,
+        };
+        let state = DiskState::default();
+
+        assert_eq!(state.update(usage(85), &config), None);
+        assert!(!state.is_full());
+        assert_eq!(state.update(usage(90), &config), Some(true));
+        assert!(state.is_full());
+        assert_eq!(state.usage(), usage(90));
+
+        // stays full until it falls under the low watermark
+        assert_eq!(state.update(usage(85), &config), None);
+        assert!(state.is_full());
+        assert_eq!(state.update(usage(79), &config), Some(false));
+        assert!(!state.is_full());
+
+        assert!(state.set_full());
+        assert!(!state.set_full());
+        assert_eq!(state.update(usage(50), &config), Some(false));
+    }
+}
@@ -3,11 +3,14 @@
 //!
 //! Global Context maintains states need to be shared across in the SPU
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::fmt::Debug;
 
 use tracing::{debug, error, instrument};
 
+use fluvio_compression::DictionaryRegistration;
+use fluvio_protocol::record::ReplicaKey;
 use fluvio_types::SpuId;
 use fluvio_storage::ReplicaStorage;
 
@@ -23,10 +26,17 @@ use crate::replication::leader::{
 use crate::control_plane::{StatusLrsMessageSink, SharedLrsStatusUpdate};
 use crate::core::metrics::SpuMetrics;
 use crate::smartengine::SmartEngine;
+use crate::smartengine::pool::SmartModulePool;
+#[cfg(feature = "smartengine")]
+use crate::smartengine::lookup::LookupStates;
 
+use super::dead_letter::DeadLetterProducer;
+use super::disk::DiskState;
 use super::leader_client::LeaderConnections;
 use super::mirror::MirrorLocalStore;
 use super::mirror::SharedMirrorLocalStore;
+use super::acl::AclLocalStore;
+use super::acl::SharedAclLocalStore;
 use super::smartmodule::SmartModuleLocalStore;
 use super::spus::SharedSpuLocalStore;
 use super::SharedReplicaLocalStore;
@@ -49,10 +59,18 @@ pub struct GlobalContext<S> {
     lrs_status_update: SharedLrsStatusUpdate,
     mirror_status_update: SharedMirrorStatusUpdate,
     sm_engine: SmartEngine,
+    sm_pool: SmartModulePool,
     leaders: Arc<LeaderConnections>,
     mirrors: SharedMirrorLocalStore,
+    acls: SharedAclLocalStore,
     metrics: Arc<SpuMetrics>,
     consumer_offset: SharedConsumerOffsetStorages,
+    dead_letter: DeadLetterProducer,
+    disk: DiskState,
+    /// compression dictionaries of the topics of the replicas
+    compression_dictionaries: Mutex<HashMap<ReplicaKey, DictionaryRegistration>>,
+    #[cfg(feature = "smartengine")]
+    lookup_states: LookupStates,
 }
 
 // -----------------------------------
@@ -71,6 +89,10 @@ where
         let spus = SpuLocalStore::new_shared();
         let replicas = ReplicaStore::new_shared();
         let metrics = Arc::new(SpuMetrics::new());
+        let dead_letter = DeadLetterProducer::new(spu_config.sc_public_endpoint.clone());
+        #[cfg(feature = "smartengine")]
+        let lookup_states = LookupStates::new(spu_config.sc_public_endpoint.clone());
+        let sm_pool = SmartModulePool::new(spu_config.smart_engine.worker_threads);
 
         GlobalContext {
             spu_localstore: spus.clone(),
@@ -83,10 +105,17 @@ where
             lrs_status_update: StatusLrsMessageSink::shared(),
             mirror_status_update: StatusMirrorMessageSink::shared(),
             sm_engine: SmartEngine::new(),
+            sm_pool,
             leaders: LeaderConnections::shared(spus, replicas),
             mirrors: MirrorLocalStore::new_shared(),
+            acls: AclLocalStore::new_shared(),
             metrics,
             consumer_offset: SharedConsumerOffsetStorages::default(),
+            dead_letter,
+            disk: DiskState::default(),
+            compression_dictionaries: Mutex::default(),
+            #[cfg(feature = "smartengine")]
+            lookup_states,
         }
     }
 
@@ -119,10 +148,22 @@ where
         self.mirrors.clone()
     }
 
+    pub fn acls_localstore(&self) -> &AclLocalStore {
+        &self.acls
+    }
+
+    pub fn acls_localstore_owned(&self) -> SharedAclLocalStore {
+        self.acls.clone()
+    }
+
     pub fn leaders_state(&self) -> &ReplicaLeadersState<S> {
         &self.leaders_state
     }
 
+    pub fn leaders_state_owned(&self) -> SharedReplicaLeadersState<S> {
+        self.leaders_state.clone()
+    }
+
     pub fn followers_state(&self) -> &FollowersState<S> {
         &self.followers_state
     }
@@ -177,6 +218,10 @@ where
         self.sm_engine.clone()
     }
 
+    pub(crate) fn smartmodule_pool(&self) -> &SmartModulePool {
+        &self.sm_pool
+    }
+
     #[allow(unused)]
     pub fn leaders(&self) -> Arc<LeaderConnections> {
         self.leaders.clone()
@@ -189,10 +234,29 @@ where
     pub(crate) fn consumer_offset(&self) -> &SharedConsumerOffsetStorages {
         &self.consumer_offset
     }
+
+    pub(crate) fn consumer_offset_owned(&self) -> SharedConsumerOffsetStorages {
+        self.consumer_offset.clone()
+    }
+
+    pub(crate) fn dead_letter(&self) -> &DeadLetterProducer {
+        &self.dead_letter
+    }
+
+    pub(crate) fn disk(&self) -> &DiskState {
+        &self.disk
+    }
+
+    #[cfg(feature = "smartengine")]
+    pub(crate) fn lookup_states(&self) -> &LookupStates {
+        &self.lookup_states
+    }
 }
 
 mod file_replica {
 
+    use std::sync::PoisonError;
+
     use fluvio_controlplane::{
         sc_api::remove::ReplicaRemovedRequest, replica::Replica,
         spu_api::update_replica::UpdateReplicaRequest,
@@ -293,6 +357,19 @@ mod file_replica {
             for replica_action in actions.into_iter() {
                 debug!(action = ?replica_action,"applying");
 
+                match &replica_action {
+                    SpecChange::Add(replica) | SpecChange::Mod(replica, _)
+                        if !replica.is_being_deleted =>
+                    {
+                        self.register_compression_dictionary(replica)
+                    }
+                    SpecChange::Add(replica)
+                    | SpecChange::Mod(replica, _)
+                    | SpecChange::Delete(replica) => {
+                        self.unregister_compression_dictionary(replica)
+                    }
+                }
+
                 match replica_action {
                     SpecChange::Add(new_replica) => {
                         if new_replica.is_being_deleted {
@@ -479,4 +556,36 @@ mod file_replica {
             }
         }
     }
+
+    impl<S> GlobalContext<S> {
+        /// the dictionary of the topic decompresses the batches produced with it,
+        /// it stays registered until the replica is removed or its dictionary changes
+        fn register_compression_dictionary(&self, replica: &Replica) {
+            let Some(dictionary) = &replica.compression_dictionary else {
+                self.unregister_compression_dictionary(replica);
+                return;
+            };
+            match fluvio_compression::register_dictionary(&replica.id.topic, dictionary.as_bytes())
+            {
+                Ok(registration) => {
+                    // the previous registration is released after the new one is held,
+                    // so an unchanged dictionary stays registered
+                    self.compression_dictionaries
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .insert(replica.id.clone(), registration);
+                }
+                Err(err) => {
+                    error!(replica = %replica.id, %err, "invalid compression dictionary");
+                }
+            }
+        }
+
+        fn unregister_compression_dictionary(&self, replica: &Replica) {
+            self.compression_dictionaries
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&replica.id);
+        }
+    }
 }
@@ -1,9 +1,15 @@
 use std::{
-    sync::atomic::{AtomicU64, Ordering},
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, RwLock,
+    },
     ops::AddAssign,
+    time::{Duration, Instant},
 };
 
 use fluvio_protocol::record::Batch;
+use fluvio_service::metrics::{MetricType, MetricsText};
 use fluvio_spu_schema::fetch::FilePartitionResponse;
 use serde::Serialize;
 
@@ -14,6 +20,7 @@ pub(crate) struct SpuMetrics {
     inbound: Activity,
     outbound: Activity,
     smartmodule: SmartModuleChainMetrics,
+    consumers: ConsumerActivity,
 }
 
 impl SpuMetrics {
@@ -32,6 +39,106 @@ impl SpuMetrics {
     pub fn chain_metrics(&self) -> &SmartModuleChainMetrics {
         &self.smartmodule
     }
+
+    pub fn consumers(&self) -> &ConsumerActivity {
+        &self.consumers
+    }
+
+    /// Prometheus families of the counters
+    pub(crate) fn encode(&self, text: &mut MetricsText) {
+        self.inbound.encode(
+            text,
+            "fluvio_spu_produced",
+            "produced to the partitions of the SPU",
+        );
+        self.outbound.encode(
+            text,
+            "fluvio_spu_fetched",
+            "fetched from the partitions of the SPU",
+        );
+
+        let consumers = self
+            .consumers
+            .consumers
+            .read()
+            .expect("consumer metrics lock");
+        text.family(
+            "fluvio_spu_consumer_records_total",
+            MetricType::Counter,
+            "Records fetched by each consumer",
+        );
+        for (consumer, record) in consumers.iter() {
+            text.sample(
+                "fluvio_spu_consumer_records_total",
+                &[("consumer", consumer.as_str())],
+                record.record.records.load(Ordering::SeqCst),
+            );
+        }
+        text.family(
+            "fluvio_spu_consumer_bytes_total",
+            MetricType::Counter,
+            "Bytes fetched by each consumer",
+        );
+        for (consumer, record) in consumers.iter() {
+            text.sample(
+                "fluvio_spu_consumer_bytes_total",
+                &[("consumer", consumer.as_str())],
+                record.record.bytes.load(Ordering::SeqCst),
+            );
+        }
+
+        let smartmodule = &self.smartmodule;
+        text.family(
+            "fluvio_spu_smartmodule_invocations_total",
+            MetricType::Counter,
+            "SmartModule chain invocations",
+        )
+        .sample(
+            "fluvio_spu_smartmodule_invocations_total",
+            &[],
+            smartmodule.invocation_count(),
+        )
+        .family(
+            "fluvio_spu_smartmodule_bytes_in_total",
+            MetricType::Counter,
+            "Bytes of records passed to SmartModules",
+        )
+        .sample(
+            "fluvio_spu_smartmodule_bytes_in_total",
+            &[],
+            smartmodule.bytes_in(),
+        )
+        .family(
+            "fluvio_spu_smartmodule_records_out_total",
+            MetricType::Counter,
+            "Records output by SmartModules",
+        )
+        .sample(
+            "fluvio_spu_smartmodule_records_out_total",
+            &[],
+            smartmodule.records_out(),
+        )
+        .family(
+            "fluvio_spu_smartmodule_fuel_used_total",
+            MetricType::Counter,
+            "Fuel consumed by SmartModules",
+        )
+        .sample(
+            "fluvio_spu_smartmodule_fuel_used_total",
+            &[],
+            smartmodule.fuel_used(),
+        )
+        .family(
+            "fluvio_spu_smartmodule_execution_seconds_total",
+            MetricType::Counter,
+            "Time spent running SmartModules",
+        )
+        .sample(
+            "fluvio_spu_smartmodule_execution_seconds_total",
+            &[],
+            smartmodule.execution_time().as_secs_f64(),
+        );
+    }
 }
 
 #[derive(Default, Debug, Serialize)]
@@ -53,13 +160,123 @@ pub struct Activity {
     client: Record,
 }
 
-#[derive(Default, Debug)]
+/// consumer identities tracked on their own, the next ones share [`OTHER_CONSUMERS`]
+const MAX_CONSUMERS: usize = 1000;
+/// identity of the consumers above [`MAX_CONSUMERS`]
+const OTHER_CONSUMERS: &str = "_other";
+
+/// Outbound records per consumer identity, the application name or consumer id
+/// supplied by the client in the stream fetch request. Identities are also the unit
+/// of the fetch quota of the SPU.
+#[derive(Default, Debug, Serialize)]
+#[serde(transparent)]
+pub struct ConsumerActivity {
+    consumers: RwLock<HashMap<String, ConsumerRecord>>,
+}
+
+#[derive(Default, Debug, Serialize)]
+struct ConsumerRecord {
+    #[serde(flatten)]
+    record: Record,
+    #[serde(skip)]
+    quota: Mutex<QuotaBucket>,
+}
+
+impl ConsumerActivity {
+    pub(crate) fn increase_by_value(&self, consumer: &str, value: IncreaseValue) {
+        self.with_record(consumer, |record| {
+            record.record.increase(value.records, value.bytes)
+        });
+    }
+
+    /// the time to wait before sending more records to `consumer`, once `bytes` have been
+    /// sent with a quota of `quota` bytes per second
+    pub(crate) fn throttle(&self, consumer: &str, bytes: u64, quota: u64) -> Duration {
+        self.with_record(consumer, |record| {
+            record
+                .quota
+                .lock()
+                .expect("consumer quota lock")
+                .consume(bytes, quota, Instant::now())
+        })
+    }
+
+    fn with_record<T>(&self, consumer: &str, f: impl Fn(&ConsumerRecord) -> T) -> T {
+        {
+            let consumers = self.consumers.read().expect("consumer metrics lock");
+            if let Some(record) = consumers.get(consumer) {
+                return f(record);
+            }
+        }
+        let mut consumers = self.consumers.write().expect("consumer metrics lock");
+        let tracked = consumers.len() - usize::from(consumers.contains_key(OTHER_CONSUMERS));
+        let key = if consumers.contains_key(consumer) || tracked < MAX_CONSUMERS {
+            consumer
+        } else {
+            OTHER_CONSUMERS
+        };
+        f(consumers.entry(key.to_owned()).or_default())
+    }
+}
+
+/// Token bucket of the bytes a consumer can fetch, refilled at the quota rate
+/// up to one second of quota
+#[derive(Debug)]
+struct QuotaBucket {
+    /// negative once the consumer fetched more than its quota
+    available: f64,
+    updated: Instant,
+}
+
+impl Default for QuotaBucket {
+    fn default() -> Self {
+        Self {
+            available: f64::MAX,
+            updated: Instant::now(),
+        }
+    }
+}
+
+impl QuotaBucket {
+    fn consume(&mut self, bytes: u64, quota: u64, now: Instant) -> Duration {
+        let quota = quota.max(1) as f64;
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.available = (self.available + elapsed * quota).min(quota) - bytes as f64;
+        self.updated = now;
+        if self.available < 0.0 {
+            Duration::from_secs_f64(-self.available / quota)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
 pub(crate) struct IncreaseValue {
     records: u64,
     bytes: u64,
 }
 
 impl Activity {
+    /// records and bytes counters of `prefix`, labeled by the kind of peer
+    fn encode(&self, text: &mut MetricsText, prefix: &str, help: &str) {
+        let counters: [(&str, &str, fn(&Record) -> &AtomicU64); 2] = [
+            ("records", "Records", |record| &record.records),
+            ("bytes", "Bytes", |record| &record.bytes),
+        ];
+        for (unit, what, counter) in counters {
+            let name = format!("{prefix}_{unit}_total");
+            text.family(&name, MetricType::Counter, &format!("{what} {help}"));
+            for (source, record) in [("client", &self.client), ("connector", &self.connector)] {
+                text.sample(
+                    &name,
+                    &[("source", source)],
+                    counter(record).load(Ordering::SeqCst),
+                );
+            }
+        }
+    }
+
     pub(crate) fn increase(&self, connector: bool, records: u64, bytes: u64) {
         if connector {
             self.connector.increase(records, bytes);
@@ -90,10 +307,25 @@ impl Activity {
     }
 }
 
+#[cfg(test)]
+impl ConsumerActivity {
+    pub fn records(&self, consumer: &str) -> u64 {
+        self.consumers
+            .read()
+            .expect("consumer metrics lock")
+            .get(consumer)
+            .map_or(0, |record| record.record.records.load(Ordering::SeqCst))
+    }
+}
+
 impl IncreaseValue {
     pub(crate) fn new(records: u64, bytes: u64) -> Self {
         Self { records, bytes }
     }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
 }
 
 // Measuring of serialized data. `bytes` is length of file slice, `records` is an offset's change
@@ -170,4 +402,88 @@ mod tests {
         assert_eq!(activity.connector.records.load(Ordering::SeqCst), 1);
         assert_eq!(activity.connector.bytes.load(Ordering::SeqCst), 123);
     }
+
+    #[test]
+    fn test_increase_consumer_activity() {
+        //given
+        let consumers = ConsumerActivity::default();
+
+        //when
+        consumers.increase_by_value("app1", IncreaseValue::new(2, 20));
+        consumers.increase_by_value("app1", IncreaseValue::new(3, 30));
+        consumers.increase_by_value("app2", IncreaseValue::new(1, 10));
+
+        //then
+        assert_eq!(consumers.records("app1"), 5);
+        assert_eq!(consumers.records("app2"), 1);
+        assert_eq!(consumers.records("unknown"), 0);
+        let json = serde_json::to_value(&consumers).expect("json");
+        assert_eq!(json["app1"]["bytes"], 50);
+    }
+
+    #[test]
+    fn test_consumer_activity_is_bounded() {
+        //given
+        let consumers = ConsumerActivity::default();
+
+        //when
+        for index in 0..MAX_CONSUMERS + 10 {
+            consumers.increase_by_value(&format!("app{index}"), IncreaseValue::new(1, 10));
+        }
+        consumers.increase_by_value("app0", IncreaseValue::new(1, 10));
+
+        //then
+        assert_eq!(consumers.consumers.read().unwrap().len(), MAX_CONSUMERS + 1);
+        assert_eq!(consumers.records("app0"), 2);
+        assert_eq!(consumers.records(&format!("app{MAX_CONSUMERS}")), 0);
+        assert_eq!(consumers.records(OTHER_CONSUMERS), 10);
+    }
+
+    #[test]
+    fn test_quota_bucket() {
+        let start = Instant::now();
+        let mut bucket = QuotaBucket {
+            available: 0.0,
+            updated: start,
+        };
+
+        // a second of quota can be fetched at once
+        let after_1s = start + Duration::from_secs(1);
+        assert_eq!(bucket.consume(100, 100, after_1s), Duration::ZERO);
+
+        // over the quota, the consumer waits until the bytes are paid back
+        assert_eq!(
+            bucket.consume(50, 100, after_1s),
+            Duration::from_millis(500)
+        );
+
+        // idle consumers don't accumulate more than a second of quota
+        let after_10s = start + Duration::from_secs(10);
+        assert_eq!(bucket.consume(100, 100, after_10s), Duration::ZERO);
+        assert_eq!(bucket.consume(100, 100, after_10s), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_encode_prometheus() {
+        //given
+        let metrics = SpuMetrics::new();
+        metrics.inbound().increase(false, 3, 30);
+        metrics.outbound().increase(true, 2, 20);
+        metrics
+            .consumers()
+            .increase_by_value("app1", IncreaseValue::new(2, 20));
+
+        //when
+        let mut text = MetricsText::new();
+        metrics.encode(&mut text);
+
+        //then
+        let text = text.as_str();
+        assert!(text.contains("# TYPE fluvio_spu_produced_records_total counter\n"));
+        assert!(text.contains("fluvio_spu_produced_records_total{source=\"client\"} 3\n"));
+        assert!(text.contains("fluvio_spu_produced_bytes_total{source=\"connector\"} 0\n"));
+        assert!(text.contains("fluvio_spu_fetched_bytes_total{source=\"connector\"} 20\n"));
+        assert!(text.contains("fluvio_spu_consumer_records_total{consumer=\"app1\"} 2\n"));
+        assert!(text.contains("fluvio_spu_smartmodule_invocations_total 0\n"));
+    }
 }
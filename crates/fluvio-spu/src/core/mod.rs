@@ -1,14 +1,18 @@
 mod global_context;
 mod store;
 mod leader_client;
+mod dead_letter;
+pub(crate) mod disk;
 
 pub mod spus;
 pub mod replica;
 pub mod smartmodule;
 pub mod metrics;
 pub mod mirror;
+pub mod acl;
 
 pub use self::global_context::{GlobalContext, ReplicaChange};
+pub use self::dead_letter::DeadLetterProducer;
 pub use self::store::Spec;
 pub use self::store::LocalStore;
 pub use self::store::SpecChange;
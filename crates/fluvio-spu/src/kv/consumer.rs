@@ -1,5 +1,5 @@
 use std::{
-    time::SystemTime,
+    time::{Duration, SystemTime},
     sync::Arc,
     collections::{HashMap, hash_map::Entry},
     ops::AddAssign,
@@ -7,12 +7,16 @@ use std::{
 
 use anyhow::Result;
 use async_lock::RwLock;
-use tracing::trace;
+use tracing::{info, trace, warn};
 
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use fluvio_kv_storage::KVStorage;
 use fluvio_protocol::{record::ReplicaKey, Encoder, Decoder};
 use fluvio_storage::FileReplica;
+use fluvio_types::{PartitionId, defaults::CONSUMER_STORAGE_TOPIC};
 
+use crate::core::DefaultSharedGlobalContext;
 use crate::replication::leader::{
     LeaderKVStorage, FollowerNotifier, LeaderReplicaState, LeaderReplicaLog,
 };
@@ -20,8 +24,10 @@ use crate::replication::leader::{
 pub(crate) type TimestampSecs = u64;
 
 const DEFAULT_FLUSH_THRESHOLD: usize = 100;
+/// how often the leader of the consumer offsets looks for expired offsets
+const EXPIRY_INTERVAL: Duration = Duration::from_secs(10 * 60);
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct SharedConsumerOffsetStorages(
     Arc<RwLock<HashMap<ReplicaKey, SharableConsumerOffsetStorage>>>,
 );
@@ -180,6 +186,64 @@ impl SharableConsumerOffsetStorage {
     pub async fn list(&self) -> Result<Vec<(ConsumerOffsetKey, ConsumerOffset)>> {
         self.0.read().await.entries().await
     }
+
+    /// Delete every offset of the consumers whose last commit, on any replica, was before
+    /// `before`. Returns the deleted keys.
+    pub async fn expire(&self, before: TimestampSecs) -> Result<Vec<ConsumerOffsetKey>> {
+        let mut storage = self.0.write().await;
+        let entries = storage.entries().await?;
+
+        let mut last_commits: HashMap<&str, TimestampSecs> = HashMap::new();
+        for (key, offset) in &entries {
+            let last_commit = last_commits.entry(key.consumer_id.as_str()).or_default();
+            *last_commit = (*last_commit).max(offset.modified_time);
+        }
+        let expired: Vec<ConsumerOffsetKey> = entries
+            .iter()
+            .filter(|(key, _)| last_commits[key.consumer_id.as_str()] < before)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            storage.delete(key).await?;
+        }
+        Ok(expired)
+    }
+}
+
+/// Periodically delete the offsets of consumers that have not committed for longer than
+/// `retention`. Only the leader of the consumer offsets replica deletes them.
+pub(crate) fn start_offset_expiry(ctx: DefaultSharedGlobalContext, retention: Duration) {
+    spawn(async move {
+        loop {
+            sleep(EXPIRY_INTERVAL.min(retention)).await;
+            if let Err(err) = expire_offsets(&ctx, retention).await {
+                warn!(%err, "unable to expire consumer offsets");
+            }
+        }
+    });
+}
+
+async fn expire_offsets(ctx: &DefaultSharedGlobalContext, retention: Duration) -> Result<()> {
+    let consumers_replica_id =
+        ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
+    let Some(ref replica) = ctx.leaders_state().get(&consumers_replica_id).await else {
+        return Ok(());
+    };
+    let before = now_timestamp().saturating_sub(retention.as_secs());
+    let expired = ctx
+        .consumer_offset()
+        .get_or_insert(replica, ctx.follower_notifier())
+        .await?
+        .expire(before)
+        .await?;
+    for key in &expired {
+        info!(
+            consumer_id = %key.consumer_id,
+            replica = %key.replica_id,
+            "consumer offset expired"
+        );
+    }
+    Ok(())
 }
 
 fn now_timestamp() -> TimestampSecs {
@@ -247,6 +311,54 @@ mod tests {
         //then
     }
 
+    #[fluvio_future::test]
+    async fn test_expire_inactive_consumers() {
+        //given
+        let leader = create_offset_replica("test_expire_inactive_consumers").await;
+        let notifier = FollowerNotifier::shared();
+        let storage: SharableConsumerOffsetStorage =
+            ConsumerOffsetStorage::new(leader.clone(), notifier).into();
+        let now = now_timestamp();
+        let inactive_0 = ConsumerOffsetKey::new(("topic1", 0), "inactive");
+        let inactive_1 = ConsumerOffsetKey::new(("topic1", 1), "inactive");
+        let active_0 = ConsumerOffsetKey::new(("topic1", 0), "active");
+        let active_1 = ConsumerOffsetKey::new(("topic2", 0), "active");
+        storage
+            .put(inactive_0.clone(), ConsumerOffset::with(1, now - 1000))
+            .await
+            .expect("put");
+        storage
+            .put(inactive_1.clone(), ConsumerOffset::with(2, now - 900))
+            .await
+            .expect("put");
+        storage
+            .put(active_0.clone(), ConsumerOffset::with(3, now - 1000))
+            .await
+            .expect("put");
+        storage
+            .put(active_1.clone(), ConsumerOffset::with(4, now))
+            .await
+            .expect("put");
+
+        //when
+        let mut expired = storage.expire(now - 500).await.expect("expire");
+
+        //then
+        expired.sort();
+        assert_eq!(expired, vec![inactive_0, inactive_1]);
+        let mut remaining: Vec<ConsumerOffsetKey> = storage
+            .list()
+            .await
+            .expect("list")
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        remaining.sort();
+        assert_eq!(remaining, vec![active_0, active_1]);
+
+        leader.remove().await.expect("removed");
+    }
+
     async fn create_offset_replica(dir: impl AsRef<Path>) -> LeaderReplicaState<FileReplica> {
         let base_dir = temp_dir().join(dir);
         ensure_clean_dir(&base_dir);
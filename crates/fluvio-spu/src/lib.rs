@@ -17,7 +17,7 @@ cfg_if::cfg_if! {
     }
 }
 
-pub use config::SpuOpt;
+pub use config::{ResourceProfile, SpuOpt};
 
 const VERSION: &str = include_str!("../../../VERSION");
 
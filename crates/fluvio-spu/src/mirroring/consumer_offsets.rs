@@ -0,0 +1,116 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use tracing::{debug, trace};
+
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_storage::FileReplica;
+use fluvio_types::{PartitionId, defaults::CONSUMER_STORAGE_TOPIC};
+
+use crate::core::GlobalContext;
+use crate::kv::consumer::{
+    ConsumerOffset, ConsumerOffsetKey, SharableConsumerOffsetStorage, SharedConsumerOffsetStorages,
+};
+use crate::replication::leader::{FollowerNotifier, SharedReplicaLeadersState};
+
+use super::remote::sync::MirrorConsumerOffset;
+
+/// Consumer offsets of a mirrored partition.
+///
+/// The source sends the offsets committed by its consumers along with the records,
+/// the target keeps them so consumers can fail over to it and resume where they left.
+/// Only the offsets stored on this SPU are visible, they are skipped when another SPU
+/// leads the consumer offsets replica.
+pub(crate) struct MirrorConsumerOffsets {
+    leaders: SharedReplicaLeadersState<FileReplica>,
+    storages: SharedConsumerOffsetStorages,
+    notifier: Arc<FollowerNotifier>,
+    /// offsets last sent to the target
+    sent: Mutex<Vec<MirrorConsumerOffset>>,
+}
+
+impl MirrorConsumerOffsets {
+    pub(crate) fn new(ctx: &GlobalContext<FileReplica>) -> Self {
+        Self {
+            leaders: ctx.leaders_state_owned(),
+            storages: ctx.consumer_offset_owned(),
+            notifier: ctx.follower_notifier_owned(),
+            sent: Mutex::new(vec![]),
+        }
+    }
+
+    /// forget what was sent, the target gets all the offsets again
+    pub(crate) fn reset(&self) {
+        self.sent.lock().unwrap().clear();
+    }
+
+    /// Offsets of `replica` to send to the target if they changed since last sent.
+    /// They are capped to the last record of the target so consumers never skip records it has not received yet.
+    pub(crate) async fn changed(
+        &self,
+        replica: &ReplicaKey,
+        target_leo: Offset,
+    ) -> Result<Vec<MirrorConsumerOffset>> {
+        let Some(storage) = self.storage().await? else {
+            return Ok(vec![]);
+        };
+        let mut offsets: Vec<MirrorConsumerOffset> = storage
+            .list()
+            .await?
+            .into_iter()
+            .filter(|(key, _)| key.replica_id == *replica)
+            .map(|(key, value)| MirrorConsumerOffset {
+                consumer_id: key.consumer_id,
+                offset: value.offset.min(target_leo - 1),
+            })
+            .collect();
+        offsets.sort_by(|a, b| a.consumer_id.cmp(&b.consumer_id));
+
+        let mut sent = self.sent.lock().unwrap();
+        if *sent == offsets {
+            return Ok(vec![]);
+        }
+        *sent = offsets.clone();
+        Ok(offsets)
+    }
+
+    /// Store offsets received from the source for `replica`, an offset never moves backward.
+    pub(crate) async fn store(
+        &self,
+        replica: &ReplicaKey,
+        offsets: Vec<MirrorConsumerOffset>,
+    ) -> Result<()> {
+        if offsets.is_empty() {
+            return Ok(());
+        }
+        let Some(storage) = self.storage().await? else {
+            debug!(%replica, "consumer offsets are not stored on this spu, skipping mirrored offsets");
+            return Ok(());
+        };
+        for offset in offsets {
+            let key = ConsumerOffsetKey::new(replica.clone(), offset.consumer_id);
+            if let Some(current) = storage.get(&key).await? {
+                if current.offset >= offset.offset {
+                    continue;
+                }
+            }
+            trace!(
+                ?key,
+                offset = offset.offset,
+                "storing mirrored consumer offset"
+            );
+            storage.put(key, ConsumerOffset::new(offset.offset)).await?;
+        }
+        Ok(())
+    }
+
+    async fn storage(&self) -> Result<Option<SharableConsumerOffsetStorage>> {
+        let consumers_replica_id =
+            ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
+        let Some(ref replica) = self.leaders.get(&consumers_replica_id).await else {
+            return Ok(None);
+        };
+        let storage = self.storages.get_or_insert(replica, &self.notifier).await?;
+        Ok(Some(storage))
+    }
+}
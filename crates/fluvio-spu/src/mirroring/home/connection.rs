@@ -14,10 +14,12 @@ use fluvio_future::timer::sleep;
 use fluvio_protocol::{record::Offset, api::RequestMessage};
 use fluvio_spu_schema::server::mirror::StartMirrorRequest;
 use fluvio_socket::{ExclusiveFlvSink, FluvioStream};
+use fluvio_storage::SliceLease;
 use fluvio::Isolation;
 
 use crate::control_plane::SharedMirrorStatusUpdate;
 use crate::core::DefaultSharedGlobalContext;
+use crate::mirroring::consumer_offsets::MirrorConsumerOffsets;
 use crate::mirroring::remote::api_key::MirrorRemoteApiEnum;
 use crate::mirroring::remote::remote_api::RemoteMirrorRequest;
 use crate::mirroring::remote::sync::{DefaultRemotePartitionSyncRequest, MirrorPartitionSyncRequest};
@@ -70,6 +72,7 @@ pub(crate) struct MirrorHomeHandler {
     ctx: DefaultSharedGlobalContext,
     status_update: SharedMirrorStatusUpdate,
     remote_cluster_id: String,
+    consumer_offsets: MirrorConsumerOffsets,
 }
 
 impl fmt::Debug for MirrorHomeHandler {
@@ -144,6 +147,7 @@ impl MirrorHomeHandler {
                 ctx: auth_ctx.global_ctx.clone(),
                 status_update: mirror_status_update.clone(),
                 remote_cluster_id: remote_cluster_id.clone(),
+                consumer_offsets: MirrorConsumerOffsets::new(&auth_ctx.global_ctx),
             };
 
             if source {
@@ -267,11 +271,16 @@ impl MirrorHomeHandler {
         sink: &mut ExclusiveFlvSink,
         mut req: DefaultRemotePartitionSyncRequest,
     ) -> Result<()> {
-        let append_flag = self
-            .leader
-            .append_record_set(&mut req.records, self.ctx.follower_notifier())
+        if !req.records.batches.is_empty() {
+            let append_flag = self
+                .leader
+                .append_record_set(&mut req.records, self.ctx.follower_notifier())
+                .await?;
+            debug!(append_flag, "leader appended");
+        }
+        self.consumer_offsets
+            .store(self.leader.id(), req.consumer_offsets)
             .await?;
-        debug!(append_flag, "leader appended");
         self.send_offsets_to_remote(sink).await
     }
 
@@ -320,7 +329,9 @@ impl MirrorHomeHandler {
                                 return Err(anyhow!("received sync request from remote, this should not happen, since we are source"));
                             }
                             RemoteMirrorRequest::UpdateRemoteOffset(req) => {
-                                remote_updated_needed = self.update_from_remote(req)?;
+                                // even when caught up, consumer offsets may have been committed since
+                                self.update_from_remote(req)?;
+                                remote_updated_needed = true;
                             }
                          }
 
@@ -347,7 +358,12 @@ impl MirrorHomeHandler {
         remote_leo: Offset,
     ) -> Result<()> {
         debug!("updating home cluster");
-        if let Some(sync_request) = self.generate_home_records_as_source(remote_leo).await? {
+        // keeps a slice of a remote segment readable until it is sent
+        let mut lease = None;
+        if let Some(sync_request) = self
+            .generate_home_records_as_source(remote_leo, &mut lease)
+            .await?
+        {
             debug!(?sync_request, "home sync");
             let request = RequestMessage::new_request(sync_request)
                 .set_client_id(format!("leader: {}", self.leader.id()));
@@ -366,24 +382,35 @@ impl MirrorHomeHandler {
     async fn generate_home_records_as_source(
         &self,
         remote_leo: Offset,
+        lease: &mut Option<SliceLease>,
     ) -> Result<Option<HomeFilePartitionSyncRequest>> {
         const MAX_BYTES: u32 = 1024 * 1024; // 1MB
 
         // leader off should be always greater than remote leo
         let leader_offset = self.leader.as_offset();
 
-        // if remote mirror is all caught up, there is no need to send out update
-        if leader_offset.leo == remote_leo {
-            debug!("remote has caught up, just chilling out");
-            return Ok(None);
-        }
+        let consumer_offsets = self
+            .consumer_offsets
+            .changed(self.leader.id(), remote_leo)
+            .await?;
 
         let mut partition_response = MirrorPartitionSyncRequest {
             leo: leader_offset.leo,
             hw: leader_offset.hw,
+            consumer_offsets,
             ..Default::default()
         };
 
+        // if remote mirror is all caught up, there is no need to send out update
+        if leader_offset.leo == remote_leo {
+            if partition_response.consumer_offsets.is_empty() {
+                debug!("remote has caught up, just chilling out");
+                return Ok(None);
+            }
+            debug!("remote has caught up, sending consumer offsets");
+            return Ok(Some(partition_response.into()));
+        }
+
         if leader_offset.leo > remote_leo {
             match self
                 .leader
@@ -401,6 +428,7 @@ impl MirrorHomeHandler {
                     if let Some(file_slice) = slice.file_slice {
                         partition_response.records = file_slice.into();
                     }
+                    *lease = slice.lease;
                     Ok(Some(partition_response.into()))
                 }
                 Err(err) => {
@@ -1,7 +1,8 @@
 pub(crate) mod remote;
 pub(crate) mod home;
+pub(crate) mod consumer_offsets;
 
 #[cfg(test)]
 mod test;
 
-const COMMON_MIRROR_VERSION: i16 = 1;
+const COMMON_MIRROR_VERSION: i16 = 2;
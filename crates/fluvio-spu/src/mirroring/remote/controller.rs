@@ -14,14 +14,14 @@ use adaptive_backoff::prelude::{
     ExponentialBackoffBuilder, BackoffBuilder, ExponentialBackoff, Backoff,
 };
 
-use fluvio::config::TlsPolicy;
+use fluvio::config::{FluvioConfig, TlsPolicy};
 use futures_util::StreamExt;
 use fluvio_controlplane_metadata::{
     mirror::{Home, MirrorPairStatus, MirrorType},
     partition::RemotePartitionConfig,
 };
-use fluvio_storage::{ReplicaStorage, FileReplica};
-use fluvio_socket::{ClientConfig, FluvioSink, FluvioSocket};
+use fluvio_storage::{ReplicaStorage, FileReplica, SliceLease};
+use fluvio_socket::{ClientConfig, FluvioSink, FluvioSocket, TcpConfig};
 use fluvio_spu_schema::{Isolation, server::mirror::StartMirrorRequest};
 use fluvio_future::{net::DomainConnector, task::spawn, timer::sleep};
 use fluvio_protocol::{record::Offset, api::RequestMessage};
@@ -33,6 +33,7 @@ use crate::{
     mirroring::remote::update_offsets::UpdateRemoteOffsetRequest,
     replication::leader::{FollowerNotifier, ReplicaOffsetRequest, SharedLeaderState},
 };
+use crate::mirroring::consumer_offsets::MirrorConsumerOffsets;
 use crate::mirroring::home::{
     home_api::HomeMirrorRequest, api_key::MirrorHomeApiEnum,
     update_offsets::UpdateHomeOffsetRequest,
@@ -134,6 +135,8 @@ pub(crate) struct MirrorRemoteToHomeController<S> {
     max_bytes: u32,
     isolation: Isolation,
     follower_notifier: Arc<FollowerNotifier>,
+    tcp: TcpConfig,
+    consumer_offsets: MirrorConsumerOffsets,
 }
 
 impl<S> fmt::Debug for MirrorRemoteToHomeController<S>
@@ -176,6 +179,8 @@ where
             mirror_store: ctx.mirrors_localstore_owned(),
             status_update: ctx.mirror_status_update_owned(),
             follower_notifier: ctx.follower_notifier_owned(),
+            tcp: ctx.config().tcp.clone(),
+            consumer_offsets: MirrorConsumerOffsets::new(ctx),
         };
         spawn(controller.dispatch_loop());
         state
@@ -240,6 +245,7 @@ where
         let mut home_api_stream = home_stream.api_stream::<HomeMirrorRequest, MirrorHomeApiEnum>();
 
         self.send_initial_request(home, &mut home_sink).await?;
+        self.consumer_offsets.reset();
 
         // this flag is set to true, home need to be refreshed leader's offsets and any recordset.
         let mut home_updated_needed = false;
@@ -272,7 +278,9 @@ where
 
                         match home_msg {
                             HomeMirrorRequest::UpdateHomeOffset(req)=> {
-                                home_updated_needed = self.update_from_home_as_source(req)?;
+                                // home is refreshed even when caught up, in case consumers committed offsets
+                                self.update_from_home_as_source(req)?;
+                                home_updated_needed = true;
                             },
                             HomeMirrorRequest::SyncRecords(sync_request)=> {
                                 return Err(anyhow!("received sync record request from home, this should not happen, since we are source"));
@@ -466,7 +474,12 @@ where
     #[instrument]
     async fn update_remote_as_source(&self, sink: &mut FluvioSink, home_leo: Offset) -> Result<()> {
         debug!("updating home cluster");
-        if let Some(sync_request) = self.geneate_remote_record_as_source(home_leo).await? {
+        // keeps a slice of a remote segment readable until it is sent
+        let mut lease = None;
+        if let Some(sync_request) = self
+            .geneate_remote_record_as_source(home_leo, &mut lease)
+            .await?
+        {
             debug!(?sync_request, "home sync");
             let request = RequestMessage::new_request(sync_request)
                 .set_client_id(format!("leader: {}", self.leader.id()));
@@ -482,22 +495,33 @@ where
     async fn geneate_remote_record_as_source(
         &self,
         home_leo: Offset,
+        lease: &mut Option<SliceLease>,
     ) -> Result<Option<RemoteFilePartitionSyncRequest>> {
         // leader off should be always greater than remote leo
         let leader_offset = self.leader.as_offset();
 
-        // if remote mirror is all caught up, there is no need to send out update
-        if leader_offset.leo == home_leo {
-            debug!("home has caught up, just chilling out");
-            return Ok(None);
-        }
+        let consumer_offsets = self
+            .consumer_offsets
+            .changed(self.leader.id(), home_leo)
+            .await?;
 
         let mut partition_response = RemoteFilePartitionSyncRequest {
             leo: leader_offset.leo,
             hw: leader_offset.hw,
+            consumer_offsets,
             ..Default::default()
         };
 
+        // if remote mirror is all caught up, there is no need to send out update
+        if leader_offset.leo == home_leo {
+            if partition_response.consumer_offsets.is_empty() {
+                debug!("home has caught up, just chilling out");
+                return Ok(None);
+            }
+            debug!("home has caught up, sending consumer offsets");
+            return Ok(Some(partition_response));
+        }
+
         if leader_offset.leo > home_leo {
             match self
                 .leader
@@ -515,6 +539,7 @@ where
                     if let Some(file_slice) = slice.file_slice {
                         partition_response.records = file_slice.into();
                     }
+                    *lease = slice.lease;
                     Ok(Some(partition_response))
                 }
                 Err(err) => {
@@ -541,12 +566,16 @@ where
         &self,
         mut req: DefaultRemotePartitionSyncRequest,
     ) -> Result<()> {
-        let append_flag = self
-            .leader
-            .append_record_set(&mut req.records, &self.follower_notifier)
-            .await?;
-        debug!(append_flag, "leader appended");
-        Ok(())
+        if !req.records.batches.is_empty() {
+            let append_flag = self
+                .leader
+                .append_record_set(&mut req.records, &self.follower_notifier)
+                .await?;
+            debug!(append_flag, "leader appended");
+        }
+        self.consumer_offsets
+            .store(self.leader.id(), req.consumer_offsets)
+            .await
     }
 
     /// create socket to home, this will always succeed
@@ -568,23 +597,19 @@ where
                 "trying connect to home",
             );
 
-            let home_config = if let Some(tlspolicy) = &tlspolicy {
-                match DomainConnector::try_from(tlspolicy.clone()) {
-                    Ok(connector) => ClientConfig::new(endpoint, connector, false),
-                    Err(err) => {
-                        error!(
-                            "error establishing tls with leader at: <{}> err: {}",
-                            endpoint, err
-                        );
-                        self.update_status(MirrorPairStatus::DetailFailure(err.to_string()))
-                            .await
-                            .unwrap();
-                        self.backoff_and_wait(backoff).await;
-                        continue;
-                    }
+            let home_config = match home_connector(home, &tlspolicy, endpoint, &self.tcp) {
+                Ok(connector) => ClientConfig::new(endpoint, connector, false),
+                Err(err) => {
+                    error!(
+                        "error creating connector to home at: <{}> err: {}",
+                        endpoint, err
+                    );
+                    self.update_status(MirrorPairStatus::DetailFailure(err.to_string()))
+                        .await
+                        .unwrap();
+                    self.backoff_and_wait(backoff).await;
+                    continue;
                 }
-            } else {
-                ClientConfig::with_addr(endpoint.to_string())
             };
 
             let home_config = home_config.with_prefix_sni_domain(&self.remote_config.home_spu_key);
@@ -644,6 +669,25 @@ fn create_backoff() -> ExponentialBackoff {
         .unwrap()
 }
 
+/// connector to `endpoint` of the home, through the proxy of the mirror or the environment if any.
+/// TCP options are only applied to plaintext connections
+fn home_connector(
+    home: &Home,
+    tlspolicy: &Option<TlsPolicy>,
+    endpoint: &str,
+    tcp: &TcpConfig,
+) -> Result<DomainConnector> {
+    let mut cluster = FluvioConfig::new(endpoint);
+    cluster = match tlspolicy {
+        Some(tlspolicy) => cluster.with_tls(tlspolicy.clone()),
+        None => cluster.with_tcp(tcp.clone()),
+    };
+    if let Some(proxy) = &home.proxy {
+        cluster = cluster.with_proxy(proxy.as_str());
+    }
+    cluster.connector()
+}
+
 fn option_tlspolicy(home: &Home) -> Option<TlsPolicy> {
     use fluvio::config::{TlsCerts, TlsConfig};
 
@@ -29,6 +29,17 @@ where
     pub hw: i64,
     pub leo: i64,
     pub records: R,
+    /// offsets committed by consumers of the source partition, sent only when they change
+    #[fluvio(min_version = 2)]
+    pub consumer_offsets: Vec<MirrorConsumerOffset>,
+}
+
+/// Offset committed by a consumer on the source partition.
+/// Mirrored records keep their offsets so it applies as is on the target.
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct MirrorConsumerOffset {
+    pub consumer_id: String,
+    pub offset: i64,
 }
 
 impl<R> fmt::Display for MirrorPartitionSyncRequest<R>
@@ -66,6 +77,9 @@ impl FileWrite for RemoteFilePartitionSyncRequest {
         self.hw.encode(src, version)?;
         self.leo.encode(src, version)?;
         self.records.file_encode(src, data, version)?;
+        if version >= 2 {
+            self.consumer_offsets.encode(src, version)?;
+        }
         Ok(())
     }
 }
@@ -218,6 +218,7 @@ impl ReplicaConfig {
                     remote_id: self.remote_cluster,
                     public_endpoint: self.home_port,
                     client_tls: None,
+                    proxy: None,
                 }),
             },
         }]);
@@ -1,11 +1,18 @@
 use std::io::Error as IoError;
 
+use async_trait::async_trait;
 use futures_util::{StreamExt, AsyncWriteExt};
+use fluvio_service::metrics::{
+    start_metrics_server, Aggregation, MetricType, MetricsSource, MetricsText, PartitionSample,
+};
+use fluvio_storage::ReplicaStorage;
 use fluvio_types::defaults::SPU_MONITORING_UNIX_SOCKET;
 use fluvio_future::task::spawn;
 use fluvio_future::net::unix::UnixListener;
 use tracing::{error, info, debug};
 
+use fluvio_controlplane::sc_api::update_lrs::LrsRequest;
+
 use crate::core::{DefaultSharedGlobalContext, metrics::SpuMetrics};
 
 pub(crate) fn init_monitoring(ctx: DefaultSharedGlobalContext) {
@@ -64,3 +71,159 @@ async fn start_monitoring(ctx: DefaultSharedGlobalContext) -> Result<(), IoError
         fluvio_future::timer::sleep(std::time::Duration::from_secs(5)).await;
     }
 }
+
+/// serve Prometheus metrics if an endpoint is configured
+pub(crate) async fn init_metrics_server(ctx: DefaultSharedGlobalContext) {
+    let Some(endpoint) = ctx.config().metrics_endpoint.clone() else {
+        return;
+    };
+    if let Err(err) = start_metrics_server(&endpoint, SpuMetricsSource(ctx)).await {
+        error!(%endpoint, "unable to start metrics server: {}", err);
+    }
+}
+
+struct SpuMetricsSource(DefaultSharedGlobalContext);
+
+#[async_trait]
+impl MetricsSource for SpuMetricsSource {
+    async fn collect(&self, text: &mut MetricsText) {
+        let ctx = &self.0;
+        ctx.metrics().encode(text);
+
+        let leaders: Vec<_> = ctx.leaders_state().read().await.values().cloned().collect();
+        let mut partitions = Vec::with_capacity(leaders.len());
+        for leader in leaders {
+            partitions.push(leader.as_lrs_request().await);
+        }
+        partitions.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let followers: Vec<_> = ctx
+            .followers_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let mut follower_sizes = Vec::with_capacity(followers.len());
+        for follower in followers {
+            let size = follower.read().await.get_partition_size();
+            follower_sizes.push((follower.id().clone(), size));
+        }
+        follower_sizes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let partition_gauges: [(&str, &str, Aggregation, fn(&LrsRequest) -> i64); 5] = [
+            (
+                "fluvio_spu_partition_high_watermark",
+                "Offset after the last committed record of the partitions led by the SPU",
+                Aggregation::Sum,
+                |lrs| lrs.leader.hw,
+            ),
+            (
+                "fluvio_spu_partition_end_offset",
+                "Offset after the last record of the partitions led by the SPU",
+                Aggregation::Sum,
+                |lrs| lrs.leader.leo,
+            ),
+            (
+                "fluvio_spu_partition_start_offset",
+                "Offset of the first record kept by the partitions led by the SPU",
+                Aggregation::Sum,
+                |lrs| lrs.base_offset,
+            ),
+            (
+                "fluvio_spu_partition_in_sync_replicas",
+                "Replicas having all the records of the partitions led by the SPU, leader included",
+                // the least replicated partition
+                Aggregation::Min,
+                |lrs| {
+                    let followers = lrs
+                        .replicas
+                        .iter()
+                        .filter(|follower| follower.leo >= lrs.leader.leo)
+                        .count();
+                    followers as i64 + 1
+                },
+            ),
+            (
+                "fluvio_spu_partition_size_bytes",
+                "Storage used by the partitions led by the SPU",
+                Aggregation::Sum,
+                |lrs| lrs.size,
+            ),
+        ];
+        let cardinality = &ctx.config().metrics_cardinality;
+        for (name, help, aggregation, value) in partition_gauges {
+            let samples: Vec<PartitionSample> = partitions
+                .iter()
+                .map(|lrs| PartitionSample::new(&lrs.id.topic, lrs.id.partition, value(lrs)))
+                .collect();
+            text.family(name, MetricType::Gauge, help)
+                .partition_samples(name, cardinality, aggregation, &samples);
+        }
+
+        let follower_lags: Vec<PartitionSample> = partitions
+            .iter()
+            .flat_map(|lrs| {
+                lrs.replicas.iter().map(|follower| {
+                    PartitionSample::new(
+                        &lrs.id.topic,
+                        lrs.id.partition,
+                        (lrs.leader.leo - follower.leo).max(0),
+                    )
+                    .with_label("follower", follower.spu.to_string())
+                })
+            })
+            .collect();
+        text.family(
+            "fluvio_spu_partition_follower_lag",
+            MetricType::Gauge,
+            "Records of the partitions led by the SPU that a follower has not replicated yet",
+        )
+        .partition_samples(
+            "fluvio_spu_partition_follower_lag",
+            cardinality,
+            Aggregation::Max,
+            &follower_lags,
+        );
+
+        let follower_sizes: Vec<PartitionSample> = follower_sizes
+            .iter()
+            .map(|(id, size)| PartitionSample::new(&id.topic, id.partition, *size as i64))
+            .collect();
+        text.family(
+            "fluvio_spu_follower_size_bytes",
+            MetricType::Gauge,
+            "Storage used by the partitions the SPU follows",
+        )
+        .partition_samples(
+            "fluvio_spu_follower_size_bytes",
+            cardinality,
+            Aggregation::Sum,
+            &follower_sizes,
+        );
+
+        let disk = ctx.disk().usage();
+        text.family(
+            "fluvio_spu_disk_used_bytes",
+            MetricType::Gauge,
+            "Bytes used on the file system of the log base dir",
+        )
+        .sample("fluvio_spu_disk_used_bytes", &[], disk.used_bytes())
+        .family(
+            "fluvio_spu_disk_total_bytes",
+            MetricType::Gauge,
+            "Size of the file system of the log base dir",
+        )
+        .sample("fluvio_spu_disk_total_bytes", &[], disk.total_bytes)
+        .family(
+            "fluvio_spu_storage_full",
+            MetricType::Gauge,
+            "1 while produces are rejected because the disk is full",
+        )
+        .sample(
+            "fluvio_spu_storage_full",
+            &[],
+            u8::from(ctx.disk().is_full()),
+        );
+    }
+}
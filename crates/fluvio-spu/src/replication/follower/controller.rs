@@ -83,7 +83,7 @@ mod inner {
 
     use fluvio_future::task::spawn;
     use fluvio_future::timer::sleep;
-    use fluvio_socket::FluvioSocket;
+    use fluvio_socket::{tcp_connector, FluvioSocket};
     use fluvio_socket::FluvioSink;
     use fluvio_socket::SocketError;
     use fluvio_protocol::record::ReplicaKey;
@@ -322,7 +322,12 @@ mod inner {
                     "trying connect to leader",
                 );
 
-                match FluvioSocket::connect(&leader_endpoint).await {
+                match FluvioSocket::connect_with_connector(
+                    &leader_endpoint,
+                    tcp_connector(&self.config.tcp).as_ref(),
+                )
+                .await
+                {
                     Ok(mut socket) => {
                         debug!("connected to leader");
 
@@ -133,12 +133,13 @@ impl FollowerHandler {
         debug!(?replicas);
 
         let mut sync_request = FileSyncRequest::default();
+        let mut leases = Vec::new();
         let leaders = self.ctx.leaders_state();
 
         for replica in replicas {
             if let Some(leader) = leaders.get(&replica).await {
                 if let Some(topic_response) = leader
-                    .follower_updates(&self.follower_id, self.max_bytes)
+                    .follower_updates(&self.follower_id, self.max_bytes, &mut leases)
                     .await
                 {
                     sync_request.topics.push(topic_response);
@@ -159,6 +160,7 @@ impl FollowerHandler {
             sink.encode_file_slices(&request, request.header.api_version())
                 .await?;
         }
+        drop(leases);
         Ok(())
     }
 
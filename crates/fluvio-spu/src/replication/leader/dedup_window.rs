@@ -0,0 +1,207 @@
+use std::collections::{HashSet, VecDeque};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use tracing::debug;
+
+use fluvio::chunking::CHUNK_ID_HEADER;
+use fluvio_controlplane_metadata::topic::{DedupBy, DedupWindow};
+use fluvio_protocol::record::{Batch, RawRecords, Record, RecordSet};
+
+/// Fingerprints of the records appended recently, ordered by arrival.
+/// Duplicates do not extend the window of the original record.
+#[derive(Debug)]
+pub(crate) struct DedupWindowState {
+    by: DedupBy,
+    max_count: usize,
+    max_age: Option<Duration>,
+    seen: HashSet<u64>,
+    order: VecDeque<(u64, SystemTime)>,
+}
+
+impl DedupWindowState {
+    pub(crate) fn new(window: &DedupWindow) -> Self {
+        Self {
+            by: window.by,
+            max_count: window.bounds.count as usize,
+            max_age: window.bounds.age,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// remove duplicate records from the set, batches left empty are removed
+    pub(crate) fn dedup_record_set(&mut self, records: &mut RecordSet<RawRecords>) -> Result<()> {
+        let now = SystemTime::now();
+        let mut batches = Vec::with_capacity(records.batches.len());
+        for raw_batch in records.batches.drain(..) {
+            let mut batch: Batch = raw_batch.clone().try_into()?;
+            let total = batch.records().len();
+            let mut kept: Vec<Record> = std::mem::take(batch.mut_records())
+                .into_iter()
+                .filter(|record| !self.is_duplicate(record, now))
+                .collect();
+            if kept.len() == total {
+                batches.push(raw_batch);
+                continue;
+            }
+            debug!(dropped = total - kept.len(), "dropped duplicate records");
+            if !kept.is_empty() {
+                batch.add_records(&mut kept);
+                batches.push(batch.try_into()?);
+            }
+        }
+        records.batches = batches;
+        Ok(())
+    }
+
+    fn is_duplicate(&mut self, record: &Record, now: SystemTime) -> bool {
+        self.evict_expired(now);
+        // chunks of a record share its key, dropping some would leave it incomplete
+        if record.header(CHUNK_ID_HEADER).is_some() {
+            return false;
+        }
+        let Some(fingerprint) = self.fingerprint(record) else {
+            return false;
+        };
+        if !self.seen.insert(fingerprint) {
+            return true;
+        }
+        self.order.push_back((fingerprint, now));
+        if self.max_count > 0 && self.order.len() > self.max_count {
+            if let Some((oldest, _)) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        false
+    }
+
+    fn evict_expired(&mut self, now: SystemTime) {
+        let Some(max_age) = self.max_age else {
+            return;
+        };
+        while let Some((fingerprint, seen_at)) = self.order.front() {
+            if now.duration_since(*seen_at).unwrap_or_default() <= max_age {
+                break;
+            }
+            self.seen.remove(fingerprint);
+            self.order.pop_front();
+        }
+    }
+
+    fn fingerprint(&self, record: &Record) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+        match self.by {
+            DedupBy::Key => AsRef::<[u8]>::as_ref(record.key()?).hash(&mut hasher),
+            DedupBy::Hash => {
+                record.key().map(AsRef::<[u8]>::as_ref).hash(&mut hasher);
+                AsRef::<[u8]>::as_ref(record.value()).hash(&mut hasher);
+            }
+        }
+        Some(hasher.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio::chunking::CHUNK_INDEX_HEADER;
+    use fluvio_controlplane_metadata::topic::Bounds;
+
+    use super::*;
+
+    fn window(by: DedupBy, count: u64, age: Option<Duration>) -> DedupWindowState {
+        DedupWindowState::new(&DedupWindow {
+            bounds: Bounds { count, age },
+            by,
+        })
+    }
+
+    fn record_set(mut records: Vec<Record>) -> RecordSet<RawRecords> {
+        let mut batch = Batch::default();
+        batch.add_records(&mut records);
+        RecordSet {
+            batches: vec![batch.try_into().expect("raw batch")],
+        }
+    }
+
+    fn values(records: &RecordSet<RawRecords>) -> Vec<String> {
+        records
+            .batches
+            .iter()
+            .flat_map(|batch| batch.memory_records().expect("records"))
+            .map(|record| record.value().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_dedup_by_key() {
+        let mut state = window(DedupBy::Key, 10, None);
+        let mut records = record_set(vec![
+            Record::new_key_value("a", "1"),
+            Record::new_key_value("b", "2"),
+            Record::new_key_value("a", "3"),
+            Record::new("no key"),
+            Record::new("no key"),
+        ]);
+        state.dedup_record_set(&mut records).expect("dedup");
+        assert_eq!(values(&records), vec!["1", "2", "no key", "no key"]);
+
+        let mut redelivered = record_set(vec![Record::new_key_value("b", "2")]);
+        state.dedup_record_set(&mut redelivered).expect("dedup");
+        assert!(redelivered.batches.is_empty());
+    }
+
+    #[test]
+    fn test_dedup_by_hash() {
+        let mut state = window(DedupBy::Hash, 10, None);
+        let mut records = record_set(vec![
+            Record::new_key_value("a", "1"),
+            Record::new_key_value("a", "2"),
+            Record::new_key_value("a", "1"),
+            Record::new("1"),
+        ]);
+        state.dedup_record_set(&mut records).expect("dedup");
+        assert_eq!(values(&records), vec!["1", "2", "1"]);
+    }
+
+    #[test]
+    fn test_dedup_skips_chunks() {
+        let mut state = window(DedupBy::Key, 10, None);
+        let chunks = (0..2)
+            .map(|index| {
+                let mut record = Record::new_key_value("a", "chunk");
+                record.add_header(CHUNK_ID_HEADER, "1");
+                record.add_header(CHUNK_INDEX_HEADER, index.to_string());
+                record
+            })
+            .collect();
+        let mut records = record_set(chunks);
+        state.dedup_record_set(&mut records).expect("dedup");
+        assert_eq!(values(&records), vec!["chunk", "chunk"]);
+    }
+
+    #[test]
+    fn test_window_bounds() {
+        let mut state = window(DedupBy::Key, 2, None);
+        let now = SystemTime::now();
+        assert!(!state.is_duplicate(&Record::new_key_value("a", ""), now));
+        assert!(!state.is_duplicate(&Record::new_key_value("b", ""), now));
+        assert!(!state.is_duplicate(&Record::new_key_value("c", ""), now));
+        // "a" was pushed out of the window by "c"
+        assert!(!state.is_duplicate(&Record::new_key_value("a", ""), now));
+        assert!(state.is_duplicate(&Record::new_key_value("c", ""), now));
+
+        let mut state = window(DedupBy::Key, 0, Some(Duration::from_secs(60)));
+        assert!(!state.is_duplicate(&Record::new_key_value("a", ""), now));
+        assert!(state.is_duplicate(
+            &Record::new_key_value("a", ""),
+            now + Duration::from_secs(30)
+        ));
+        assert!(!state.is_duplicate(
+            &Record::new_key_value("a", ""),
+            now + Duration::from_secs(61)
+        ));
+    }
+}
@@ -1,13 +1,15 @@
 use std::ops::Deref;
+use std::sync::Arc;
 use async_lock::RwLock;
 use fluvio_controlplane::replica::Replica;
 use std::collections::HashMap;
 
-use tracing::{error, instrument};
+use tracing::{error, info, instrument, warn};
 use anyhow::Result;
 
 use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, ReplicaKey};
-use fluvio_storage::{FileReplica, ReplicaStorage};
+use fluvio_storage::{FileReplica, ReplicaStorage, ReplicaStorageConfig};
+use fluvio_storage::config::ReplicaConfig;
 
 use crate::{control_plane::SharedLrsStatusUpdate, core::GlobalContext};
 use crate::config::ReplicationConfig;
@@ -15,7 +17,7 @@ use crate::replication::follower::FollowerReplicaState;
 
 use super::{LeaderReplicaState, replica_state::SharedLeaderState};
 
-pub type SharedReplicaLeadersState<S> = ReplicaLeadersState<S>;
+pub type SharedReplicaLeadersState<S> = Arc<ReplicaLeadersState<S>>;
 
 /// Collection of replicas
 #[derive(Debug)]
@@ -37,7 +39,7 @@ impl<S> Deref for ReplicaLeadersState<S> {
 
 impl<S> ReplicaLeadersState<S> {
     pub fn new_shared() -> SharedReplicaLeadersState<S> {
-        Self::default()
+        Arc::new(Self::default())
     }
 }
 
@@ -114,6 +116,9 @@ impl ReplicaLeadersState<FileReplica> {
     ) -> Result<LeaderReplicaState<FileReplica>> {
         let replica_id = replica.id.clone();
 
+        if let Some(source) = &replica.clone_from {
+            self.snapshot_replica(ctx, &replica, source).await?;
+        }
         let leader_replica =
             LeaderReplicaState::create(replica, ctx.config(), status_update).await?;
         let leader_replica = leader_replica.init(ctx).await?;
@@ -121,6 +126,35 @@ impl ReplicaLeadersState<FileReplica> {
         Ok(leader_replica)
     }
 
+    /// copy the local leader of `source` into the storage of `replica`.
+    /// Followers of clones start empty and replicate from the leader.
+    async fn snapshot_replica(
+        &self,
+        ctx: &GlobalContext<FileReplica>,
+        replica: &Replica,
+        source: &ReplicaKey,
+    ) -> Result<()> {
+        let Some(source_leader) = self.get(source).await else {
+            warn!(%source, "clone source is not led by this spu, starting empty");
+            return Ok(());
+        };
+        let mut replica_config: ReplicaConfig = ctx.config().into();
+        replica_config.update_from_replica(replica);
+        let leo = source_leader
+            .read()
+            .await
+            .snapshot_to(
+                &replica_config.base_dir,
+                &replica.id.topic,
+                replica.id.partition,
+            )
+            .await?;
+        if let Some(leo) = leo {
+            info!(%source, leo, "replica cloned");
+        }
+        Ok(())
+    }
+
     #[instrument(
         skip(self,replica_id, leader_state),
         fields(replica = %replica_id)
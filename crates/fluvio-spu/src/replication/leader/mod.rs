@@ -7,6 +7,8 @@ mod update_offsets;
 mod actions;
 mod spu;
 mod kv;
+mod dedup_window;
+mod producer_sequences;
 
 pub use self::leaders_state::{ReplicaLeadersState, SharedReplicaLeadersState};
 pub use self::replica_state::{SharedFileLeaderState, SharedLeaderState, LeaderReplicaState};
@@ -16,5 +18,8 @@ pub use self::peer_api::LeaderPeerRequest;
 pub use self::update_offsets::UpdateOffsetRequest;
 pub use self::update_offsets::ReplicaOffsetRequest;
 pub use self::kv::{LeaderKVStorage, LeaderReplicaLog};
+pub(crate) use self::producer_sequences::SequenceCheck;
 
 pub use self::spu::*;
+#[cfg(test)]
+pub(crate) use self::replica_state::compute_hw;
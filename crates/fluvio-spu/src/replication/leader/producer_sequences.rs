@@ -0,0 +1,340 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_lock::{Mutex, MutexGuardArc};
+use tracing::debug;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{increment_sequence, Batch, Offset, RawRecords, RecordSet};
+
+/// batches remembered per producer, a retry can only repeat one of the requests in flight
+const RECENT_BATCHES: usize = 5;
+/// producers remembered per replica, the one that did not write for the longest is forgotten
+const MAX_PRODUCERS: usize = 1024;
+
+/// Sequence range of the records of an idempotent producer in a produce request.
+/// Batches of a request carry consecutive sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ProducerSequence {
+    producer_id: i64,
+    epoch: i16,
+    first_sequence: i32,
+    records: i32,
+}
+
+impl ProducerSequence {
+    /// None when the records were not sent by an idempotent producer
+    pub(crate) fn from_records(records: &RecordSet<RawRecords>) -> Option<Self> {
+        let header = records.batches.first()?.get_header();
+        if header.producer_id < 0 || header.first_sequence < 0 {
+            return None;
+        }
+        let records = records
+            .batches
+            .iter()
+            .map(|batch| batch.records_len() as i32)
+            .sum();
+        Some(Self {
+            producer_id: header.producer_id,
+            epoch: header.producer_epoch,
+            first_sequence: header.first_sequence,
+            records,
+        })
+    }
+
+    /// None when the batch was not sent by an idempotent producer
+    fn from_batch<R>(batch: &Batch<R>) -> Option<Self> {
+        let header = batch.get_header();
+        if header.producer_id < 0 || header.first_sequence < 0 {
+            return None;
+        }
+        Some(Self {
+            producer_id: header.producer_id,
+            epoch: header.producer_epoch,
+            first_sequence: header.first_sequence,
+            records: batch.records_len() as i32,
+        })
+    }
+
+    fn last_sequence(&self) -> i32 {
+        increment_sequence(self.first_sequence, self.records.max(1) - 1)
+    }
+}
+
+#[derive(Debug)]
+struct AppendedBatch {
+    first_sequence: i32,
+    base_offset: Offset,
+    leo: Offset,
+}
+
+#[derive(Debug)]
+struct ProducerState {
+    epoch: i16,
+    last_sequence: i32,
+    recent: VecDeque<AppendedBatch>,
+    last_write: Instant,
+}
+
+/// What to do with the records of an idempotent producer
+pub(crate) enum SequenceCheck {
+    /// append the records, then commit their sequence
+    Append(PendingSequence),
+    /// the records were already appended at these offsets
+    Duplicate { base_offset: Offset, leo: Offset },
+}
+
+/// Last sequences appended by the idempotent producers of a replica.
+///
+/// Sequences are kept in memory by the leader and rebuilt from the batch headers of the log
+/// when the leader initializes, so retries are still detected after a restart or a change
+/// of leader. The first sequence of a producer that was forgotten is accepted whatever its
+/// value.
+#[derive(Debug, Default)]
+pub(crate) struct ProducerSequences {
+    producers: HashMap<i64, ProducerState>,
+}
+
+impl ProducerSequences {
+    /// The lock is held by the pending sequence until it is committed or dropped,
+    /// so a retry waits for the outcome of the request it repeats.
+    pub(crate) async fn check(
+        sequences: &Arc<Mutex<Self>>,
+        sequence: ProducerSequence,
+    ) -> Result<SequenceCheck, ErrorCode> {
+        let guard = sequences.lock_arc().await;
+        let appended = guard.validate(&sequence)?;
+        match appended {
+            Some((base_offset, leo)) => {
+                debug!(
+                    producer_id = sequence.producer_id,
+                    first_sequence = sequence.first_sequence,
+                    base_offset,
+                    "duplicate records of idempotent producer"
+                );
+                Ok(SequenceCheck::Duplicate { base_offset, leo })
+            }
+            None => Ok(SequenceCheck::Append(PendingSequence { guard, sequence })),
+        }
+    }
+
+    /// offsets of the records when they were already appended
+    fn validate(&self, sequence: &ProducerSequence) -> Result<Option<(Offset, Offset)>, ErrorCode> {
+        let Some(state) = self.producers.get(&sequence.producer_id) else {
+            return Ok(None);
+        };
+        if sequence.epoch < state.epoch {
+            return Err(ErrorCode::ProducerFenced {
+                producer_id: sequence.producer_id,
+            });
+        }
+        if sequence.epoch > state.epoch {
+            return Ok(None);
+        }
+        if let Some(batch) = state
+            .recent
+            .iter()
+            .find(|batch| batch.first_sequence == sequence.first_sequence)
+        {
+            return Ok(Some((batch.base_offset, batch.leo)));
+        }
+        let expected = increment_sequence(state.last_sequence, 1);
+        if sequence.first_sequence != expected {
+            return Err(ErrorCode::ProducerSequenceOutOfOrder {
+                producer_id: sequence.producer_id,
+                expected,
+                received: sequence.first_sequence,
+            });
+        }
+        Ok(None)
+    }
+
+    /// replay a batch of the log, batches are replayed in the order of their offsets
+    pub(crate) fn replay<R>(&mut self, batch: &Batch<R>) {
+        if let Some(sequence) = ProducerSequence::from_batch(batch) {
+            self.record(
+                &sequence,
+                batch.get_base_offset(),
+                batch.computed_last_offset(),
+            );
+        }
+    }
+
+    fn record(&mut self, sequence: &ProducerSequence, base_offset: Offset, leo: Offset) {
+        if !self.producers.contains_key(&sequence.producer_id)
+            && self.producers.len() >= MAX_PRODUCERS
+        {
+            self.evict_idlest();
+        }
+        let state = self
+            .producers
+            .entry(sequence.producer_id)
+            .or_insert_with(|| ProducerState {
+                epoch: sequence.epoch,
+                last_sequence: sequence.first_sequence,
+                recent: VecDeque::with_capacity(RECENT_BATCHES),
+                last_write: Instant::now(),
+            });
+        if state.epoch != sequence.epoch {
+            state.epoch = sequence.epoch;
+            state.recent.clear();
+        }
+        state.last_sequence = sequence.last_sequence();
+        state.last_write = Instant::now();
+        if state.recent.len() == RECENT_BATCHES {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(AppendedBatch {
+            first_sequence: sequence.first_sequence,
+            base_offset,
+            leo,
+        });
+    }
+
+    fn evict_idlest(&mut self) {
+        if let Some(producer_id) = self
+            .producers
+            .iter()
+            .min_by_key(|(_, state)| state.last_write)
+            .map(|(producer_id, _)| *producer_id)
+        {
+            self.producers.remove(&producer_id);
+        }
+    }
+}
+
+/// Sequence of records being appended, forgotten unless committed
+pub(crate) struct PendingSequence {
+    guard: MutexGuardArc<ProducerSequences>,
+    sequence: ProducerSequence,
+}
+
+impl PendingSequence {
+    pub(crate) fn commit(mut self, base_offset: Offset, leo: Offset) {
+        self.guard.record(&self.sequence, base_offset, leo);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::{Batch, Record};
+
+    use super::*;
+
+    fn sequence(epoch: i16, first_sequence: i32, records: i32) -> ProducerSequence {
+        ProducerSequence {
+            producer_id: 7,
+            epoch,
+            first_sequence,
+            records,
+        }
+    }
+
+    #[test]
+    fn test_sequence_from_records() {
+        let mut batch = Batch::from(vec![Record::default(), Record::default()]);
+        let records = RecordSet {
+            batches: vec![batch.clone().try_into().expect("raw batch")],
+        };
+        assert!(ProducerSequence::from_records(&records).is_none());
+
+        let header = batch.get_mut_header();
+        header.producer_id = 7;
+        header.producer_epoch = 1;
+        header.first_sequence = 3;
+        let records = RecordSet {
+            batches: vec![batch.try_into().expect("raw batch")],
+        };
+        assert_eq!(
+            ProducerSequence::from_records(&records),
+            Some(sequence(1, 3, 2))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_sequence() {
+        let mut sequences = ProducerSequences::default();
+        let first = sequence(0, 0, 3);
+        assert_eq!(sequences.validate(&first), Ok(None));
+        sequences.record(&first, 10, 13);
+
+        // retry of the appended records
+        assert_eq!(sequences.validate(&first), Ok(Some((10, 13))));
+
+        let second = sequence(0, 3, 2);
+        assert_eq!(sequences.validate(&second), Ok(None));
+        sequences.record(&second, 13, 15);
+        assert_eq!(sequences.validate(&first), Ok(Some((10, 13))));
+        assert_eq!(sequences.validate(&second), Ok(Some((13, 15))));
+    }
+
+    #[test]
+    fn test_out_of_order_sequence() {
+        let mut sequences = ProducerSequences::default();
+        sequences.record(&sequence(0, 0, 3), 0, 3);
+        assert_eq!(
+            sequences.validate(&sequence(0, 5, 1)),
+            Err(ErrorCode::ProducerSequenceOutOfOrder {
+                producer_id: 7,
+                expected: 3,
+                received: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_producer_epoch() {
+        let mut sequences = ProducerSequences::default();
+        sequences.record(&sequence(1, 0, 3), 0, 3);
+
+        // a new epoch starts the sequence over
+        assert_eq!(sequences.validate(&sequence(2, 0, 1)), Ok(None));
+        sequences.record(&sequence(2, 0, 1), 3, 4);
+
+        assert_eq!(
+            sequences.validate(&sequence(1, 3, 1)),
+            Err(ErrorCode::ProducerFenced { producer_id: 7 })
+        );
+    }
+
+    #[test]
+    fn test_replay_batches() {
+        let mut sequences = ProducerSequences::default();
+        let mut batch = Batch::from(vec![Record::default(), Record::default()]);
+        sequences.replay(&batch);
+        assert!(sequences.producers.is_empty());
+
+        batch.base_offset = 10;
+        let header = batch.get_mut_header();
+        header.producer_id = 7;
+        header.first_sequence = 0;
+        sequences.replay(&batch);
+
+        // a retry of the replayed batch is a duplicate, the next sequence is expected after it
+        assert_eq!(sequences.validate(&sequence(0, 0, 2)), Ok(Some((10, 12))));
+        assert_eq!(
+            sequences.validate(&sequence(0, 3, 1)),
+            Err(ErrorCode::ProducerSequenceOutOfOrder {
+                producer_id: 7,
+                expected: 2,
+                received: 3
+            })
+        );
+        assert_eq!(sequences.validate(&sequence(0, 2, 1)), Ok(None));
+    }
+
+    #[test]
+    fn test_evict_idlest_producer() {
+        let mut sequences = ProducerSequences::default();
+        for producer_id in 0..MAX_PRODUCERS as i64 + 1 {
+            let sequence = ProducerSequence {
+                producer_id,
+                ..sequence(0, 0, 1)
+            };
+            sequences.record(&sequence, producer_id, producer_id + 1);
+        }
+        assert_eq!(sequences.producers.len(), MAX_PRODUCERS);
+        assert!(sequences.producers.contains_key(&(MAX_PRODUCERS as i64)));
+    }
+}
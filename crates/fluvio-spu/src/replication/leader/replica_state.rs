@@ -15,8 +15,11 @@ use async_lock::RwLock;
 use anyhow::{Result, Context};
 
 use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::smartmodule::{SmartModuleKind, SmartModuleTransformRuntimeError};
 use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, PartitionStatus, ReplicaStatus};
-use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig};
+use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig, SliceLease};
+use fluvio_storage::iterators::FileBatchIterator;
 use fluvio_types::{
     event::offsets::{SharedOffsetPublisher, WeakSharedOffsetPublisher, TOPIC_DELETED},
     SpuId,
@@ -31,13 +34,15 @@ use crate::{
     smartengine::{
         batch::process_record_set,
         context::{SharedSmartModuleContext, SmartModuleContext},
-        dedup_to_invocation,
+        dedup_to_invocation, validation_to_invocation,
     },
 };
 use crate::replication::follower::sync::{PeerFileTopicResponse, PeerFilePartitionResponse};
 use crate::storage::SharableReplicaStorage;
 
 use super::FollowerNotifier;
+use super::dedup_window::DedupWindowState;
+use super::producer_sequences::{ProducerSequence, ProducerSequences, SequenceCheck};
 
 pub type SharedLeaderState<S> = LeaderReplicaState<S>;
 pub type SharedFileLeaderState = LeaderReplicaState<FileReplica>;
@@ -53,6 +58,9 @@ pub struct LeaderReplicaState<S> {
     followers: Arc<RwLock<BTreeMap<SpuId, OffsetInfo>>>,
     status_update: SharedLrsStatusUpdate,
     sm_ctx: Option<SharedSmartModuleContext>,
+    validator: Option<SharedSmartModuleContext>,
+    dedup_window: Option<Arc<Mutex<DedupWindowState>>>,
+    producer_sequences: Arc<Mutex<ProducerSequences>>,
     consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
     mirror_controller_state: Option<SharedMirrorControllerState>,
 }
@@ -67,6 +75,9 @@ impl<S> Clone for LeaderReplicaState<S> {
             in_sync_replica: self.in_sync_replica,
             status_update: self.status_update.clone(),
             sm_ctx: self.sm_ctx.clone(),
+            validator: self.validator.clone(),
+            dedup_window: self.dedup_window.clone(),
+            producer_sequences: self.producer_sequences.clone(),
             consumer_offset_publishers: self.consumer_offset_publishers.clone(),
             mirror_controller_state: self.mirror_controller_state.clone(),
         }
@@ -122,6 +133,10 @@ where
         let follower_ids = HashSet::from_iter(replica.replicas.clone());
         let followers = ids_to_map(replica.leader, follower_ids);
         debug!(?followers, "leader followers");
+        let dedup_window = replica
+            .dedup_window
+            .as_ref()
+            .map(|window| Arc::new(Mutex::new(DedupWindowState::new(window))));
 
         debug!(
             in_sync_replica,
@@ -138,6 +153,9 @@ where
             in_sync_replica,
             status_update,
             sm_ctx: None,
+            validator: None,
+            dedup_window,
+            producer_sequences: Arc::new(Mutex::new(ProducerSequences::default())),
             consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
             mirror_controller_state: None,
         })
@@ -176,6 +194,33 @@ where
         &self.replica
     }
 
+    /// rebuild the sequences of the idempotent producers from the batches of the log
+    async fn load_producer_sequences(&self) -> Result<()> {
+        let (mut offset, _) = self.start_offset_info().await;
+        let leo = self.leo();
+        let mut sequences = self.producer_sequences.lock().await;
+        while offset < leo {
+            let slice = self
+                .read_records(offset, u32::MAX, Isolation::ReadUncommitted)
+                .await?;
+            let Some(file_slice) = slice.file_slice else {
+                break;
+            };
+            let mut next_offset = offset;
+            for file_batch in FileBatchIterator::from_raw_slice(file_slice) {
+                let batch = file_batch?.batch;
+                next_offset = batch.computed_last_offset();
+                sequences.replay(&batch);
+            }
+            if next_offset <= offset {
+                break;
+            }
+            offset = next_offset;
+        }
+        debug!(offset, "loaded producer sequences");
+        Ok(())
+    }
+
     /// override in sync replica
     #[allow(unused)]
     fn set_in_sync_replica(&mut self, replica_count: u16) {
@@ -240,11 +285,12 @@ where
     }
 
     /// compute follower that needs to be updated
-    /// based on leader's state
+    /// based on leader's state, the leases of the slices are kept until the updates are sent
     pub async fn follower_updates(
         &self,
         follower_id: &SpuId,
         max_bytes: u32,
+        leases: &mut Vec<SliceLease>,
     ) -> Option<PeerFileTopicResponse> {
         let leader_offset = self.as_offset();
 
@@ -278,6 +324,7 @@ where
                             if let Some(file_slice) = slice.file_slice {
                                 partition_response.records = file_slice.into();
                             }
+                            leases.extend(slice.lease);
                         }
                         Err(err) => {
                             error!(%err, "error reading records");
@@ -308,7 +355,7 @@ where
     }
 
     /// convert myself as
-    async fn as_lrs_request(&self) -> LrsRequest {
+    pub(crate) async fn as_lrs_request(&self) -> LrsRequest {
         let leader = (self.leader(), self.hw(), self.leo()).into();
         let replicas: Vec<ReplicaStatus> = self
             .followers
@@ -341,6 +388,19 @@ where
         self.status_update.send(lrs).await
     }
 
+    /// check the sequence of records sent by an idempotent producer, None for other producers
+    pub(crate) async fn check_producer_sequence(
+        &self,
+        records: &RecordSet<RawRecords>,
+    ) -> Result<Option<SequenceCheck>, ErrorCode> {
+        let Some(sequence) = ProducerSequence::from_records(records) else {
+            return Ok(None);
+        };
+        ProducerSequences::check(&self.producer_sequences, sequence)
+            .await
+            .map(Some)
+    }
+
     /// write records to storage
     /// then update our follower's leo
     #[instrument(skip(self, records, notifiers))]
@@ -349,6 +409,9 @@ where
         records: &mut RecordSet<RawRecords>,
         notifiers: &FollowerNotifier,
     ) -> Result<(Offset, Offset, usize)> {
+        if let Some(ref dedup_window) = self.dedup_window {
+            dedup_window.lock().await.dedup_record_set(records)?;
+        }
         self.transform(records).await?;
         if records.total_records() == 0 {
             return Ok((self.hw(), self.leo(), 0));
@@ -381,6 +444,31 @@ where
         Ok(())
     }
 
+    /// run the validation SmartModule of the topic, if any, over the records without changing them.
+    /// Returns the reason the records are invalid.
+    pub async fn validate_record_set(
+        &self,
+        records: &mut RecordSet<RawRecords>,
+    ) -> Result<Option<SmartModuleTransformRuntimeError>> {
+        let Some(ref validator) = self.validator else {
+            return Ok(None);
+        };
+        let (valid, sm_error) = process_record_set(validator.write().await.chain_mut(), records)?;
+        if sm_error.is_some() {
+            return Ok(sm_error);
+        }
+        let total = records.total_records();
+        let rejected = total.saturating_sub(valid.records().len());
+        if rejected > 0 {
+            return Ok(Some(SmartModuleTransformRuntimeError {
+                hint: format!("{rejected} of {total} records did not pass validation"),
+                kind: SmartModuleKind::Filter,
+                ..Default::default()
+            }));
+        }
+        Ok(None)
+    }
+
     async fn notify_followers(&self, notifier: &FollowerNotifier) {
         let leader_offset = self.as_offset();
         let followers = self.followers.read().await;
@@ -477,6 +565,10 @@ where
 {
     pub async fn init(self, ctx: &GlobalContext<FileReplica>) -> Result<LeaderReplicaState<S>> {
         let mut state = self.0;
+        state
+            .load_producer_sequences()
+            .await
+            .context("loading producer sequences failed")?;
         if let Some(dedup) = &state.replica.deduplication {
             debug!(?state.replica.deduplication, "init leader smartmodule context");
             let dedup_filter = dedup_to_invocation(dedup);
@@ -489,6 +581,14 @@ where
                 .context("leader smartmodule context lookback failed")?;
             state.sm_ctx = Some(Arc::new(RwLock::new(sm_ctx)));
         };
+        if let Some(validation) = &state.replica.validation {
+            debug!(?state.replica.validation, "init leader validation context");
+            let validator = validation_to_invocation(validation);
+            let sm_ctx = SmartModuleContext::try_from(vec![validator], COMMON_VERSION, ctx)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("SmartModule context is required here"))?;
+            state.validator = Some(Arc::new(RwLock::new(sm_ctx)));
+        };
         // start up mirror controller if mirror is source
         if let Some(mirror) = &state.replica.mirror {
             match mirror {
@@ -543,7 +643,7 @@ where
 ///         follower: leo(3,4)  =>   hw = 3  that is smallest leo that satisfy
 ///         follower: leo(4,4)  =>   hw = 4
 ///         follower: leo(6,7,9) =>  hw = 7,
-fn compute_hw(
+pub(crate) fn compute_hw(
     leader: &OffsetInfo,
     min_replica: u16,
     followers: &BTreeMap<SpuId, OffsetInfo>,
@@ -989,9 +1089,18 @@ mod test_leader {
         assert!(!follower_info.get(&5001).unwrap().is_valid()); // follower should be invalid sate;
         drop(follower_info);
 
-        assert!(state.follower_updates(&5003, MAX_BYTES).await.is_none()); // don't have 5003
-        assert!(state.follower_updates(&5001, MAX_BYTES).await.is_none()); // 5001 is still invalid
-        assert!(state.follower_updates(&5002, MAX_BYTES).await.is_none()); // 5002 is still invalid
+        assert!(state
+            .follower_updates(&5003, MAX_BYTES, &mut Vec::new())
+            .await
+            .is_none()); // don't have 5003
+        assert!(state
+            .follower_updates(&5001, MAX_BYTES, &mut Vec::new())
+            .await
+            .is_none()); // 5001 is still invalid
+        assert!(state
+            .follower_updates(&5002, MAX_BYTES, &mut Vec::new())
+            .await
+            .is_none()); // 5002 is still invalid
 
         // got updated from 5001 which just been initialized
         let mut followers = state.followers.write().await;
@@ -1001,9 +1110,12 @@ mod test_leader {
             .update(&OffsetInfo { leo: 0, hw: 0 });
         drop(followers);
 
-        assert!(state.follower_updates(&5002, MAX_BYTES).await.is_none()); // 5002 is still invalid
+        assert!(state
+            .follower_updates(&5002, MAX_BYTES, &mut Vec::new())
+            .await
+            .is_none()); // 5002 is still invalid
         let updates = state
-            .follower_updates(&5001, MAX_BYTES)
+            .follower_updates(&5001, MAX_BYTES, &mut Vec::new())
             .await
             .expect("some");
         assert_eq!(updates.name, "test");
@@ -1018,7 +1130,7 @@ mod test_leader {
             .update(&OffsetInfo { leo: 0, hw: 0 });
         drop(followers);
         let updates = state
-            .follower_updates(&5002, MAX_BYTES)
+            .follower_updates(&5002, MAX_BYTES, &mut Vec::new())
             .await
             .expect("some");
         assert_eq!(updates.name, "test");
@@ -1032,8 +1144,14 @@ mod test_leader {
             .expect("map")
             .update(&OffsetInfo { leo: 10, hw: 2 });
         drop(followers);
-        assert!(state.follower_updates(&5002, MAX_BYTES).await.is_none()); // 5002 is still invalid
-        assert!(state.follower_updates(&5001, MAX_BYTES).await.is_some()); // 5001 is still need to besync
+        assert!(state
+            .follower_updates(&5002, MAX_BYTES, &mut Vec::new())
+            .await
+            .is_none()); // 5002 is still invalid
+        assert!(state
+            .follower_updates(&5001, MAX_BYTES, &mut Vec::new())
+            .await
+            .is_some()); // 5001 is still need to besync
     }
 
     #[fluvio_future::test]
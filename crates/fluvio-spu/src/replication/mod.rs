@@ -3,3 +3,5 @@ pub(crate) mod leader;
 
 #[cfg(test)]
 pub(crate) mod test;
+#[cfg(test)]
+mod simulation;
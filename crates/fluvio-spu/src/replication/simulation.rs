@@ -0,0 +1,568 @@
+//!
+//! # Replication Simulation
+//!
+//! Deterministic model of a replicated partition driven by a seed: a leader, its
+//! followers, a simulated clock and a network that delays, reorders and loses
+//! messages. Followers restart and leaders crash while records are produced. The
+//! leader moves its high watermark with the same `compute_hw` and follower offset
+//! rules as the SPU.
+//!
+//! The SC of the model elects a live replica that followed the latest leader and
+//! holds every committed record, and followers of a new leader truncate to its high
+//! watermark. Leader epochs are meant to give the SC these guarantees.
+//!
+//! A failing run reports its seed, set `REPLICATION_SIM_SEED` to replay only that seed.
+//!
+
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use fluvio_protocol::record::Offset;
+use fluvio_storage::OffsetInfo;
+use fluvio_types::SpuId;
+
+use super::leader::compute_hw;
+
+const SEEDS: u64 = 200;
+const FAULT_STEPS: usize = 300;
+/// steps without faults in which every replica must catch up
+const QUIESCE_STEPS: usize = 200;
+const MAX_DELAY: u64 = 5;
+/// records sent to a follower at once
+const MAX_BATCH: usize = 8;
+/// trace lines printed for a failing run
+const TRACE_TAIL: usize = 40;
+
+const BASE_ID: SpuId = 5001;
+
+#[derive(Debug, Clone, Copy)]
+struct Faults {
+    /// chance that a message is lost
+    loss: f64,
+    /// chance per step that an online follower restarts
+    restart: f64,
+    /// chance per step that the leader crashes
+    crash: f64,
+}
+
+const NO_FAULTS: Faults = Faults {
+    loss: 0.0,
+    restart: 0.0,
+    crash: 0.0,
+};
+
+#[derive(Debug)]
+struct Node {
+    id: SpuId,
+    /// value of the record at each offset, the log survives restarts
+    log: Vec<u64>,
+    hw: Offset,
+    online: bool,
+    /// epoch of the leader that this node follows or is
+    epoch: u32,
+}
+
+impl Node {
+    fn leo(&self) -> Offset {
+        self.log.len() as Offset
+    }
+
+    fn offsets(&self) -> OffsetInfo {
+        OffsetInfo {
+            hw: self.hw,
+            leo: self.leo(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    /// follower offsets sent to the leader
+    Offsets {
+        from: usize,
+        epoch: u32,
+        offsets: OffsetInfo,
+    },
+    /// records from `start` and the leader hw sent to a follower
+    Records {
+        to: usize,
+        epoch: u32,
+        start: Offset,
+        records: Vec<u64>,
+        hw: Offset,
+    },
+}
+
+struct InFlight {
+    deliver_at: u64,
+    message: Message,
+}
+
+struct Simulation {
+    rng: StdRng,
+    clock: u64,
+    in_sync_replica: u16,
+    nodes: Vec<Node>,
+    leader: Option<usize>,
+    epoch: u32,
+    /// follower offsets known by the current leader
+    followers: BTreeMap<SpuId, OffsetInfo>,
+    network: Vec<InFlight>,
+    /// highest hw reported to the SC
+    committed: Offset,
+    /// produced records waiting for the hw
+    pending: BTreeMap<Offset, u64>,
+    acked: BTreeMap<Offset, u64>,
+    next_value: u64,
+    trace: Vec<String>,
+}
+
+impl Simulation {
+    fn new(seed: u64, replicas: usize, in_sync_replica: u16) -> Self {
+        let nodes = (0..replicas)
+            .map(|index| Node {
+                id: BASE_ID + index as SpuId,
+                log: vec![],
+                hw: 0,
+                online: true,
+                epoch: 0,
+            })
+            .collect();
+        let mut simulation = Self {
+            rng: StdRng::seed_from_u64(seed),
+            clock: 0,
+            in_sync_replica,
+            nodes,
+            leader: None,
+            epoch: 0,
+            followers: BTreeMap::new(),
+            network: vec![],
+            committed: 0,
+            pending: BTreeMap::new(),
+            acked: BTreeMap::new(),
+            next_value: 0,
+            trace: vec![],
+        };
+        simulation.make_leader(0);
+        simulation
+    }
+
+    fn run(&mut self, faults: Faults) -> Result<(), String> {
+        for _ in 0..FAULT_STEPS {
+            self.step(faults, true)?;
+        }
+        self.log("network healed".to_owned());
+        for _ in 0..QUIESCE_STEPS {
+            if let Some(index) = self.nodes.iter().position(|node| !node.online) {
+                self.restart(index);
+            }
+            self.step(NO_FAULTS, false)?;
+        }
+        self.check_converged()
+    }
+
+    fn step(&mut self, faults: Faults, produce: bool) -> Result<(), String> {
+        self.clock += 1;
+
+        if let Some(leader) = self.leader {
+            if self.rng.gen_bool(faults.crash) {
+                self.crash_leader(leader);
+            }
+        }
+        for index in 0..self.nodes.len() {
+            let node = &self.nodes[index];
+            if Some(index) == self.leader {
+                continue;
+            }
+            if node.online && self.rng.gen_bool(faults.restart) {
+                self.nodes[index].online = false;
+                self.log(format!("follower {} stopped", self.nodes[index].id));
+            } else if !node.online && self.rng.gen_bool(0.3) {
+                self.restart(index);
+            }
+        }
+        if self.leader.is_none() {
+            self.elect();
+        }
+
+        if produce && self.rng.gen_bool(0.5) {
+            self.produce();
+        }
+        self.deliver(faults)?;
+        self.replicate(faults);
+        self.check()
+    }
+
+    fn log(&mut self, event: String) {
+        self.trace.push(format!("[{}] {event}", self.clock));
+    }
+
+    fn send(&mut self, faults: Faults, message: Message) {
+        if self.rng.gen_bool(faults.loss) {
+            return;
+        }
+        let deliver_at = self.clock + self.rng.gen_range(1..=MAX_DELAY);
+        self.network.push(InFlight {
+            deliver_at,
+            message,
+        });
+    }
+
+    fn produce(&mut self) {
+        let Some(leader) = self.leader else {
+            return;
+        };
+        let value = self.next_value;
+        self.next_value += 1;
+        let node = &mut self.nodes[leader];
+        let offset = node.leo();
+        node.log.push(value);
+        // a single in sync replica commits on write
+        if self.in_sync_replica == 1 {
+            node.hw = node.leo();
+        }
+        self.pending.insert(offset, value);
+        self.on_hw_change();
+    }
+
+    /// the leader sends each known follower the records it misses
+    fn replicate(&mut self, faults: Faults) {
+        let Some(leader) = self.leader else {
+            return;
+        };
+        for index in 0..self.nodes.len() {
+            if index == leader {
+                continue;
+            }
+            let node = &self.nodes[index];
+            let (id, offsets) = (node.id, node.offsets());
+            if node.online && node.epoch == self.epoch && self.rng.gen_bool(0.3) {
+                let message = Message::Offsets {
+                    from: index,
+                    epoch: self.epoch,
+                    offsets,
+                };
+                self.send(faults, message);
+            }
+
+            let Some(known) = self.followers.get(&id).copied() else {
+                continue;
+            };
+            let leader_pos = self.nodes[leader].offsets();
+            if known.leo < 0 || !leader_pos.newer(&known) {
+                continue;
+            }
+            let start = known.leo as usize;
+            let end = (start + MAX_BATCH).min(self.nodes[leader].log.len());
+            let message = Message::Records {
+                to: index,
+                epoch: self.epoch,
+                start: known.leo,
+                records: self.nodes[leader].log[start..end].to_vec(),
+                hw: leader_pos.hw,
+            };
+            self.send(faults, message);
+        }
+    }
+
+    fn deliver(&mut self, faults: Faults) -> Result<(), String> {
+        let clock = self.clock;
+        let (due, later) = std::mem::take(&mut self.network)
+            .into_iter()
+            .partition::<Vec<_>, _>(|in_flight| in_flight.deliver_at <= clock);
+        self.network = later;
+        for in_flight in due {
+            match in_flight.message {
+                Message::Offsets {
+                    from,
+                    epoch,
+                    offsets,
+                } => self.on_offsets(from, epoch, offsets)?,
+                Message::Records {
+                    to,
+                    epoch,
+                    start,
+                    records,
+                    hw,
+                } => self.on_records(faults, to, epoch, start, records, hw)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// same checks as the leader replica state on follower updates
+    fn on_offsets(&mut self, from: usize, epoch: u32, offsets: OffsetInfo) -> Result<(), String> {
+        let Some(leader) = self.leader else {
+            return Ok(());
+        };
+        if epoch != self.epoch || !self.nodes[leader].online {
+            return Ok(());
+        }
+        let leader_pos = self.nodes[leader].offsets();
+        if offsets.newer(&leader_pos) {
+            return Ok(());
+        }
+        let id = self.nodes[from].id;
+        let Some(known) = self.followers.get_mut(&id) else {
+            return Err(format!("follower {id} unknown to the leader"));
+        };
+        if !known.update(&offsets) || leader_pos.is_committed() {
+            return Ok(());
+        }
+        if let Some(hw) = compute_hw(&leader_pos, self.in_sync_replica, &self.followers) {
+            self.check_in_sync(leader, hw)?;
+            self.nodes[leader].hw = hw;
+            self.on_hw_change();
+        }
+        Ok(())
+    }
+
+    /// same hw rules as the follower replica state
+    fn on_records(
+        &mut self,
+        faults: Faults,
+        to: usize,
+        epoch: u32,
+        start: Offset,
+        records: Vec<u64>,
+        hw: Offset,
+    ) -> Result<(), String> {
+        let node = &mut self.nodes[to];
+        if !node.online || node.epoch != epoch || start > node.leo() {
+            return Ok(());
+        }
+        let overlap = ((node.leo() - start) as usize).min(records.len());
+        if node.log[start as usize..start as usize + overlap] != records[..overlap] {
+            return Err(format!(
+                "follower {} diverged from the leader from offset {start}",
+                node.id
+            ));
+        }
+        node.log.extend_from_slice(&records[overlap..]);
+        if hw > node.hw && hw <= node.leo() {
+            node.hw = hw;
+        }
+        let message = Message::Offsets {
+            from: to,
+            epoch,
+            offsets: node.offsets(),
+        };
+        self.send(faults, message);
+        Ok(())
+    }
+
+    fn on_hw_change(&mut self) {
+        let Some(leader) = self.leader else {
+            return;
+        };
+        let hw = self.nodes[leader].hw;
+        self.committed = self.committed.max(hw);
+        let remaining = self.pending.split_off(&hw);
+        self.acked.append(&mut self.pending);
+        self.pending = remaining;
+    }
+
+    fn crash_leader(&mut self, leader: usize) {
+        self.nodes[leader].online = false;
+        self.leader = None;
+        self.log(format!("leader {} crashed", self.nodes[leader].id));
+        // records that are not committed are lost for the producer
+        self.pending.clear();
+        self.elect();
+    }
+
+    fn restart(&mut self, index: usize) {
+        self.nodes[index].online = true;
+        self.log(format!("replica {} restarted", self.nodes[index].id));
+        if let Some(leader) = self.leader {
+            if self.nodes[index].epoch != self.epoch {
+                self.follow(index, leader);
+            }
+        }
+    }
+
+    /// live replica of the latest epoch holding all committed records, longest log first
+    fn elect(&mut self) {
+        let candidate = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| {
+                node.online && node.epoch == self.epoch && node.leo() >= self.committed
+            })
+            .max_by_key(|(_, node)| (node.leo(), std::cmp::Reverse(node.id)))
+            .map(|(index, _)| index);
+        match candidate {
+            Some(index) => self.make_leader(index),
+            None => self.log("no leader candidate".to_owned()),
+        }
+    }
+
+    fn make_leader(&mut self, index: usize) {
+        self.epoch += 1;
+        self.leader = Some(index);
+        self.nodes[index].epoch = self.epoch;
+        self.followers = self
+            .nodes
+            .iter()
+            .filter(|node| node.id != self.nodes[index].id)
+            .map(|node| (node.id, OffsetInfo::default()))
+            .collect();
+        self.log(format!(
+            "replica {} leads epoch {} with {:?}",
+            self.nodes[index].id,
+            self.epoch,
+            self.nodes[index].offsets()
+        ));
+        for follower in 0..self.nodes.len() {
+            if follower != index && self.nodes[follower].online {
+                self.follow(follower, index);
+            }
+        }
+    }
+
+    /// followers drop the records the new leader may not have
+    fn follow(&mut self, index: usize, leader: usize) {
+        let leader_hw = self.nodes[leader].hw;
+        let node = &mut self.nodes[index];
+        let truncate = node.hw.min(leader_hw);
+        node.log.truncate(truncate as usize);
+        node.hw = truncate;
+        node.epoch = self.epoch;
+    }
+
+    /// followers counted for a new hw hold the records of the leader
+    fn check_in_sync(&self, leader: usize, hw: Offset) -> Result<(), String> {
+        let leader_log = &self.nodes[leader].log[..hw as usize];
+        let in_sync = self
+            .nodes
+            .iter()
+            .filter(|node| node.log.len() >= hw as usize && node.log[..hw as usize] == *leader_log)
+            .count();
+        let required = (self.in_sync_replica as usize).min(self.nodes.len());
+        if in_sync < required {
+            return Err(format!(
+                "hw {hw} held by {in_sync} replicas, {required} in sync replicas required"
+            ));
+        }
+        Ok(())
+    }
+
+    fn check(&self) -> Result<(), String> {
+        for node in &self.nodes {
+            if node.hw > node.leo() {
+                return Err(format!("replica {} hw is past its end", node.id));
+            }
+        }
+        let Some(leader) = self.leader else {
+            return Ok(());
+        };
+        let leader_log = &self.nodes[leader].log;
+        for (offset, value) in &self.acked {
+            if leader_log.get(*offset as usize) != Some(value) {
+                return Err(format!("acked record at offset {offset} lost"));
+            }
+        }
+        for node in &self.nodes {
+            if node.epoch != self.epoch {
+                continue;
+            }
+            let hw = node.hw as usize;
+            if leader_log.len() < hw || node.log[..hw] != leader_log[..hw] {
+                return Err(format!(
+                    "committed records of replica {} differ from the leader",
+                    node.id
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_converged(&self) -> Result<(), String> {
+        let Some(leader) = self.leader else {
+            return Err("no leader after the network healed".to_owned());
+        };
+        let leader_node = &self.nodes[leader];
+        if !leader_node.offsets().is_committed() {
+            return Err(format!(
+                "leader offsets {:?} not committed",
+                leader_node.offsets()
+            ));
+        }
+        for node in &self.nodes {
+            if node.log != leader_node.log || node.hw != leader_node.hw {
+                return Err(format!(
+                    "replica {} at {:?} did not catch up with the leader at {:?}",
+                    node.id,
+                    node.offsets(),
+                    leader_node.offsets()
+                ));
+            }
+        }
+        if !self.pending.is_empty() {
+            return Err(format!("{} records never acked", self.pending.len()));
+        }
+        Ok(())
+    }
+}
+
+/// runs every seed, or only `REPLICATION_SIM_SEED` if set
+fn simulate(replicas: usize, in_sync_replica: u16, faults: Faults) {
+    let seeds: Vec<u64> = match std::env::var("REPLICATION_SIM_SEED") {
+        Ok(seed) => vec![seed.parse().expect("seed")],
+        Err(_) => (0..SEEDS).collect(),
+    };
+    for seed in seeds {
+        let mut simulation = Simulation::new(seed, replicas, in_sync_replica);
+        if let Err(err) = simulation.run(faults) {
+            let start = simulation.trace.len().saturating_sub(TRACE_TAIL);
+            panic!(
+                "seed {seed}, replicas {replicas}, in sync {in_sync_replica}: {err}\n{}",
+                simulation.trace[start..].join("\n")
+            );
+        }
+    }
+}
+
+const FAULTS: Faults = Faults {
+    loss: 0.1,
+    restart: 0.02,
+    crash: 0.01,
+};
+
+#[test]
+fn test_replication_without_faults() {
+    simulate(3, 2, NO_FAULTS);
+}
+
+#[test]
+fn test_replication_message_loss() {
+    simulate(
+        3,
+        2,
+        Faults {
+            loss: 0.3,
+            ..NO_FAULTS
+        },
+    );
+}
+
+#[test]
+fn test_replication_faults() {
+    simulate(3, 2, FAULTS);
+    simulate(3, 3, FAULTS);
+    simulate(2, 1, FAULTS);
+    simulate(5, 3, FAULTS);
+}
+
+#[test]
+fn test_simulation_is_deterministic() {
+    let mut first = Simulation::new(7, 3, 2);
+    let mut second = Simulation::new(7, 3, 2);
+    first.run(FAULTS).expect("first run");
+    second.run(FAULTS).expect("second run");
+    assert_eq!(first.trace, second.trace);
+    assert_eq!(first.acked, second.acked);
+}
@@ -0,0 +1,101 @@
+//!
+//! # Acl Authorization
+//!
+//! Records and consumer offsets are authorized by the Acls the SC pushes to the SPU:
+//! consume to read records and offsets, produce to write records and set or delete offsets.
+//!
+//! The principal is the one of the client certificate, forwarded by the TLS proxy, or the
+//! one read by the mesh identity proxy. Bearer token principals are only used by the admin
+//! HTTP API of the SC, the SPU protocol carries no token so the Acls of token principals
+//! don't apply to SPU connections.
+//!
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{debug, instrument};
+
+use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
+use fluvio_auth::x509::X509Identity;
+use fluvio_controlplane_metadata::acl::AclPermission;
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_socket::FluvioSocket;
+
+use crate::core::acl::SharedAclLocalStore;
+
+#[derive(Debug, Clone)]
+pub struct AclAuthorization {
+    acls: SharedAclLocalStore,
+    super_users: Arc<HashSet<String>>,
+}
+
+impl AclAuthorization {
+    pub fn new(acls: SharedAclLocalStore, super_users: HashSet<String>) -> Self {
+        Self {
+            acls,
+            super_users: Arc::new(super_users),
+        }
+    }
+}
+
+#[async_trait]
+impl Authorization for AclAuthorization {
+    type Context = AclAuthContext;
+
+    #[instrument(level = "trace", skip(self, socket))]
+    async fn create_auth_context(
+        &self,
+        socket: &mut FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let identity = X509Identity::create_from_connection(socket)
+            .await
+            .map_err(|err| {
+                tracing::error!(%err, "failed to create x509 identity");
+                err
+            })?;
+        debug!(principal = %identity.principal, "acl identity");
+        Ok(AclAuthContext {
+            principal: identity.principal,
+            acls: self.acls.clone(),
+            super_users: self.super_users.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct AclAuthContext {
+    principal: String,
+    acls: SharedAclLocalStore,
+    super_users: Arc<HashSet<String>>,
+}
+
+#[async_trait]
+impl AuthContext for AclAuthContext {
+    // objects are managed by the SC, the SPU only serves records
+    async fn allow_type_action(
+        &self,
+        _ty: ObjectType,
+        _action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        Ok(true)
+    }
+
+    async fn allow_instance_action(
+        &self,
+        _ty: ObjectType,
+        _action: InstanceAction,
+        _key: &str,
+    ) -> Result<bool, AuthError> {
+        Ok(true)
+    }
+
+    async fn allow_topic_action(
+        &self,
+        topic: &str,
+        permission: AclPermission,
+    ) -> Result<bool, AuthError> {
+        Ok(self.super_users.contains(&self.principal)
+            || self.acls.allows_topic(&self.principal, topic, permission))
+    }
+}
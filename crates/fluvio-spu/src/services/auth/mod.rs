@@ -1,3 +1,5 @@
+pub mod acl;
+
 pub use common::*;
 
 mod common {
@@ -1,14 +1,29 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use fluvio_types::event::StickyEvent;
+
+use crate::config::ConnectionConfig;
 use crate::services::public::StreamPublishers;
 
+/// max age is cut by up to this part, so connections opened together are not
+/// drained together
+const MAX_AGE_JITTER: f64 = 0.1;
+
 #[derive(Debug)]
 pub(crate) struct ConnectionContext {
     stream_publishers: StreamPublishers,
+    /// set when the connection starts draining, ends the streams of the connection
+    drain_event: Arc<StickyEvent>,
 }
 
 impl ConnectionContext {
     pub(crate) fn new() -> Self {
         Self {
             stream_publishers: StreamPublishers::new(),
+            drain_event: StickyEvent::shared(),
         }
     }
 
@@ -19,4 +34,106 @@ impl ConnectionContext {
     pub(crate) fn stream_publishers_mut(&mut self) -> &mut StreamPublishers {
         &mut self.stream_publishers
     }
+
+    pub(crate) fn drain_event(&self) -> Arc<StickyEvent> {
+        self.drain_event.clone()
+    }
+
+    pub(crate) fn is_draining(&self) -> bool {
+        self.drain_event.is_set()
+    }
+}
+
+/// What to do once the deadline of a connection is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConnectionExpiry {
+    /// max age reached, streams move to a new connection before it is closed
+    Drain,
+    /// idle or drain timeout reached
+    Close,
+}
+
+/// Tracks max age and idle timeout of a client connection
+#[derive(Debug)]
+pub(crate) struct ConnectionLifetime {
+    idle_timeout: Option<Duration>,
+    drain_timeout: Duration,
+    drain_at: Option<Instant>,
+    last_request: Instant,
+    draining_until: Option<Instant>,
+}
+
+impl ConnectionLifetime {
+    pub(crate) fn new(config: &ConnectionConfig) -> Self {
+        let now = Instant::now();
+        let drain_at = config.max_age.map(|max_age| {
+            let jitter = rand::thread_rng().gen_range(0.0..MAX_AGE_JITTER);
+            now + max_age.mul_f64(1.0 - jitter)
+        });
+        Self {
+            idle_timeout: config.idle_timeout,
+            drain_timeout: config.drain_timeout,
+            drain_at,
+            last_request: now,
+            draining_until: None,
+        }
+    }
+
+    pub(crate) fn on_request(&mut self) {
+        self.last_request = Instant::now();
+    }
+
+    /// once draining, the connection is closed after the drain timeout
+    pub(crate) fn start_drain(&mut self) {
+        self.draining_until = Some(Instant::now() + self.drain_timeout);
+    }
+
+    /// next deadline of the connection, connections with streams are never idle
+    pub(crate) fn next_expiry(&self, has_streams: bool) -> Option<(Instant, ConnectionExpiry)> {
+        if let Some(until) = self.draining_until {
+            return Some((until, ConnectionExpiry::Close));
+        }
+        let idle_at = self
+            .idle_timeout
+            .filter(|_| !has_streams)
+            .map(|timeout| (self.last_request + timeout, ConnectionExpiry::Close));
+        let drain_at = self.drain_at.map(|at| (at, ConnectionExpiry::Drain));
+        [idle_at, drain_at]
+            .into_iter()
+            .flatten()
+            .min_by_key(|(at, _)| *at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_expiry() {
+        let config = ConnectionConfig {
+            max_age: Some(Duration::from_secs(100)),
+            idle_timeout: Some(Duration::from_secs(10)),
+            drain_timeout: Duration::from_secs(5),
+        };
+        let mut lifetime = ConnectionLifetime::new(&config);
+
+        let (idle_at, expiry) = lifetime.next_expiry(false).expect("idle");
+        assert_eq!(expiry, ConnectionExpiry::Close);
+        assert!(idle_at <= Instant::now() + Duration::from_secs(10));
+
+        // connections with streams are drained at their max age
+        let (drain_at, expiry) = lifetime.next_expiry(true).expect("max age");
+        assert_eq!(expiry, ConnectionExpiry::Drain);
+        let age = drain_at - Instant::now();
+        assert!(age > Duration::from_secs(89) && age <= Duration::from_secs(100));
+
+        lifetime.start_drain();
+        let (close_at, expiry) = lifetime.next_expiry(true).expect("drain timeout");
+        assert_eq!(expiry, ConnectionExpiry::Close);
+        assert!(close_at <= Instant::now() + Duration::from_secs(5));
+
+        let unlimited = ConnectionLifetime::new(&ConnectionConfig::default());
+        assert!(unlimited.next_expiry(false).is_none());
+    }
 }
@@ -7,6 +7,8 @@ use fluvio_spu_schema::server::consumer_offset::DeleteConsumerOffsetRequest;
 use fluvio_spu_schema::server::consumer_offset::DeleteConsumerOffsetResponse;
 use fluvio_spu_schema::server::consumer_offset::FetchConsumerOffsetsRequest;
 use fluvio_spu_schema::server::consumer_offset::FetchConsumerOffsetsResponse;
+use fluvio_spu_schema::server::consumer_offset::SetConsumerOffsetRequest;
+use fluvio_spu_schema::server::consumer_offset::SetConsumerOffsetResponse;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetRequest;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetResponse;
 use fluvio_spu_schema::server::consumer_offset::ConsumerOffset as ConsumerOffsetResponse;
@@ -18,7 +20,9 @@ use tracing::instrument;
 
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::acl::AclPermission;
 use fluvio_protocol::link::ErrorCode;
+use fluvio_auth::AuthContext;
 use tracing::trace;
 use tracing::warn;
 
@@ -30,15 +34,16 @@ use crate::replication::leader::LeaderReplicaState;
 use super::conn_context::ConnectionContext;
 use super::send_private_request_to_leader;
 
-#[instrument(skip(req_msg, ctx, conn_ctx))]
-pub(crate) async fn handle_update_consumer_offset_request(
+#[instrument(skip(req_msg, ctx, conn_ctx, auth))]
+pub(crate) async fn handle_update_consumer_offset_request<AC: AuthContext>(
     req_msg: RequestMessage<UpdateConsumerOffsetRequest>,
     ctx: DefaultSharedGlobalContext,
     conn_ctx: &mut ConnectionContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<UpdateConsumerOffsetResponse>, IoError> {
     let UpdateConsumerOffsetRequest { offset, session_id } = req_msg.request;
 
-    let (offset, error_code) = match handle_update(ctx, conn_ctx, offset, session_id).await {
+    let (offset, error_code) = match handle_update(ctx, conn_ctx, auth, offset, session_id).await {
         Ok(offset) => (offset, ErrorCode::None),
         Err(error) => (i64::default(), error),
     };
@@ -54,17 +59,23 @@ pub(crate) async fn handle_update_consumer_offset_request(
     )
 }
 
-#[instrument(skip(req_msg, ctx))]
-pub(crate) async fn handle_delete_consumer_offset_request(
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_delete_consumer_offset_request<AC: AuthContext>(
     req_msg: RequestMessage<DeleteConsumerOffsetRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<DeleteConsumerOffsetResponse>, IoError> {
     let DeleteConsumerOffsetRequest {
         consumer_id,
         replica_id,
     } = req_msg.request;
 
-    let error_code = match handle_delete(ctx, replica_id, consumer_id).await {
+    let allowed = check_topic_action(auth, &replica_id.topic, AclPermission::Produce).await;
+    let result = match allowed {
+        Ok(()) => handle_delete(ctx, replica_id, consumer_id).await,
+        Err(error_code) => Err(error_code),
+    };
+    let error_code = match result {
         Ok(_) => ErrorCode::None,
         Err(error_code) => error_code,
     };
@@ -80,12 +91,42 @@ pub(crate) async fn handle_delete_consumer_offset_request(
     )
 }
 
-#[instrument(skip(req_msg, ctx))]
-pub(crate) async fn handle_fetch_consumer_offsets_request(
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_set_consumer_offset_request<AC: AuthContext>(
+    req_msg: RequestMessage<SetConsumerOffsetRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<SetConsumerOffsetResponse>, IoError> {
+    let SetConsumerOffsetRequest {
+        replica_id,
+        consumer_id,
+        offset,
+    } = req_msg.request;
+
+    let allowed = check_topic_action(auth, &replica_id.topic, AclPermission::Produce).await;
+    let result = match allowed {
+        Ok(()) => handle_set(ctx, replica_id, consumer_id, offset).await,
+        Err(error_code) => Err(error_code),
+    };
+    let error_code = match result {
+        Ok(_) => ErrorCode::None,
+        Err(error_code) => error_code,
+    };
+
+    debug!(?error_code, "set consumer offset result");
+
+    let response = SetConsumerOffsetResponse { error_code };
+    Ok(RequestMessage::<SetConsumerOffsetRequest>::response_with_header(&req_msg.header, response))
+}
+
+/// only the offsets of topics the client can consume are listed
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_fetch_consumer_offsets_request<AC: AuthContext>(
     req_msg: RequestMessage<FetchConsumerOffsetsRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<FetchConsumerOffsetsResponse>, IoError> {
-    let (consumers, error_code) = match handle_fetch_consumers(ctx).await {
+    let (consumers, error_code) = match handle_fetch_consumers(ctx, auth).await {
         Ok(consumers) => (consumers, ErrorCode::None),
         Err(error_code) => (Vec::new(), error_code),
     };
@@ -104,15 +145,33 @@ pub(crate) async fn handle_fetch_consumer_offsets_request(
     )
 }
 
-async fn handle_update(
+/// error if the client is not allowed `permission` on the records of `topic`
+async fn check_topic_action<AC: AuthContext>(
+    auth: &AC,
+    topic: &str,
+    permission: AclPermission,
+) -> std::result::Result<(), ErrorCode> {
+    match auth.allow_topic_action(topic, permission).await {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            debug!(topic, %permission, "consumer offset action not allowed");
+            Err(ErrorCode::PermissionDenied)
+        }
+        Err(err) => Err(ErrorCode::Other(format!("authorization error: {err}"))),
+    }
+}
+
+async fn handle_update<AC: AuthContext>(
     ctx: DefaultSharedGlobalContext,
     conn_ctx: &mut ConnectionContext,
+    auth: &AC,
     offset: i64,
     session_id: u32,
 ) -> std::result::Result<i64, ErrorCode> {
     let Some(publisher) = conn_ctx.stream_publishers().get_publisher(session_id).await else {
         return Err(ErrorCode::FetchSessionNotFoud);
     };
+    check_topic_action(auth, &publisher.topic, AclPermission::Produce).await?;
     let consumers_replica_id =
         ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
 
@@ -183,8 +242,41 @@ async fn handle_delete(
         .map_err(|e| ErrorCode::Other(format!("unable to delete consumer: {e:?}")))
 }
 
-async fn handle_fetch_consumers(
+async fn handle_set(
     ctx: DefaultSharedGlobalContext,
+    target_replica: ReplicaKey,
+    consumer_id: String,
+    offset: i64,
+) -> std::result::Result<(), ErrorCode> {
+    let consumers_replica_id =
+        ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
+    let ReplicaKey { topic, partition } = target_replica;
+
+    if let Some(ref replica) = ctx.leaders_state().get(&consumers_replica_id).await {
+        trace!(consumer_id, offset, "set consumer offset locally");
+        update_offset_for_leader(ctx, replica, topic, partition, consumer_id, offset)
+            .await
+            .map_err(|err| {
+                error!("set consumer offset locally failed: {err:?}");
+                ErrorCode::Other(err.to_string())
+            })
+    } else {
+        trace!(consumer_id, offset, "set consumer offset remote");
+        update_offset_in_peer(
+            ctx,
+            &consumers_replica_id,
+            topic,
+            partition,
+            consumer_id,
+            offset,
+        )
+        .await
+    }
+}
+
+async fn handle_fetch_consumers<AC: AuthContext>(
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> std::result::Result<Vec<ConsumerOffsetResponse>, ErrorCode> {
     let consumers_replica_id =
         ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
@@ -192,24 +284,30 @@ async fn handle_fetch_consumers(
         return Err(ErrorCode::PartitionNotLeader);
     };
 
-    Ok(ctx
+    let consumers = ctx
         .consumer_offset()
         .get_or_insert(replica, ctx.follower_notifier())
         .await
         .map_err(|e| ErrorCode::Other(e.to_string()))?
         .list()
         .await
-        .map_err(|e| ErrorCode::Other(format!("unable to list consumers: {e:?}")))?
-        .into_iter()
-        .map(|(key, consumer)| {
-            ConsumerOffsetResponse::new(
-                key.consumer_id,
-                key.replica_id,
-                consumer.offset,
-                consumer.modified_time,
-            )
-        })
-        .collect())
+        .map_err(|e| ErrorCode::Other(format!("unable to list consumers: {e:?}")))?;
+
+    let mut allowed = Vec::with_capacity(consumers.len());
+    for (key, consumer) in consumers {
+        match check_topic_action(auth, &key.replica_id.topic, AclPermission::Consume).await {
+            Ok(()) => {}
+            Err(ErrorCode::PermissionDenied) => continue,
+            Err(error_code) => return Err(error_code),
+        }
+        allowed.push(ConsumerOffsetResponse::new(
+            key.consumer_id,
+            key.replica_id,
+            consumer.offset,
+            consumer.modified_time,
+        ));
+    }
+    Ok(allowed)
 }
 
 async fn update_offset_for_leader(
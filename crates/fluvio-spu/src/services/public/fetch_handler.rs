@@ -1,41 +1,86 @@
+use std::marker::PhantomData;
+
 use tracing::{debug, trace, instrument};
 use anyhow::Result;
 
 use fluvio_spu_schema::file::FileRecordSet;
+use fluvio_spu_schema::RECORD_HEADERS_API_VERSION;
 use fluvio_socket::ExclusiveFlvSink;
 use fluvio_socket::SocketError;
-use fluvio_protocol::{link::ErrorCode, api::RequestMessage};
+use fluvio_protocol::{
+    link::ErrorCode,
+    api::{RequestMessage, ResponseMessage},
+    record::{RecordSet, RawRecords},
+};
 use fluvio_spu_schema::fetch::{
-    FileFetchResponse, FileFetchRequest, FilePartitionResponse, FileTopicResponse,
+    FetchResponse, FileFetchResponse, FileFetchRequest, FilePartitionResponse, FileTopicResponse,
     FetchablePartitionResponse, FetchPartition, FetchableTopic, FetchableTopicResponse,
 };
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::acl::AclPermission;
+use fluvio_auth::AuthContext;
+use fluvio_storage::SliceLease;
+use fluvio_storage::iterators::FileBatchIterator;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::services::public::stream_fetch::strip_record_headers;
 use crate::traffic::TrafficType;
 
 /// perform log fetch request using zero copy write
 #[instrument(
-    skip(request, ctx, sink),
+    skip(request, ctx, sink, auth),
     fields(
         max_bytes = request.request.max_bytes,
     ),
 )]
-pub async fn handle_fetch_request(
+pub async fn handle_fetch_request<AC: AuthContext>(
     request: RequestMessage<FileFetchRequest>,
     ctx: DefaultSharedGlobalContext,
     sink: ExclusiveFlvSink,
+    auth: &AC,
 ) -> Result<()> {
     let (header, fetch_request) = request.get_header_request();
     trace!("Handling FileFetchRequest: {:#?}", fetch_request);
     let mut fetch_response = FileFetchResponse::default();
+    // slices of remote segments are readable until the response is sent
+    let mut leases = Vec::new();
 
     for topic_request in &fetch_request.topics {
-        let topic_response =
-            handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector()).await?;
+        if !auth
+            .allow_topic_action(&topic_request.name, AclPermission::Consume)
+            .await?
+        {
+            debug!(topic = %topic_request.name, "fetch not allowed");
+            fetch_response.topics.push(denied_topic(topic_request));
+            continue;
+        }
+        let topic_response = handle_fetch_topic(
+            &ctx,
+            &fetch_request,
+            topic_request,
+            header.is_connector(),
+            &mut leases,
+        )
+        .await?;
         fetch_response.topics.push(topic_response);
     }
 
+    if header.api_version() < RECORD_HEADERS_API_VERSION {
+        // Older consumers would misread header entries, records are sent without them
+        let fetch_response = strip_response_headers(&ctx, fetch_response).await?;
+        drop(leases);
+
+        let response = ResponseMessage::from_header(&header, fetch_response);
+        trace!(
+            "Sending FetchResponse without record headers: {:#?}",
+            response
+        );
+
+        let mut inner = sink.lock().await;
+        inner.send_response(&response, header.api_version()).await?;
+        return Ok(());
+    }
+
     let response =
         RequestMessage::<FileFetchRequest>::response_with_header(&header, fetch_response);
     trace!("Sending FileFetchResponse: {:#?}", response);
@@ -46,13 +91,72 @@ pub async fn handle_fetch_request(
         .await?;
 
     drop(inner);
+    drop(leases);
 
     trace!("Finished sending FileFetchResponse");
     Ok(())
 }
 
+/// Reads the records of every partition into memory and removes their headers
+async fn strip_response_headers(
+    ctx: &DefaultSharedGlobalContext,
+    fetch_response: FileFetchResponse,
+) -> Result<FetchResponse<RecordSet<RawRecords>>> {
+    let mut topics = Vec::with_capacity(fetch_response.topics.len());
+    for topic_response in fetch_response.topics {
+        let mut partitions = Vec::with_capacity(topic_response.partitions.len());
+        for partition_response in topic_response.partitions {
+            let raw_slice = partition_response.records.raw_slice();
+            let records = if partition_response.records.len() == 0 {
+                RecordSet::default()
+            } else {
+                ctx.smartmodule_pool()
+                    .run(move || strip_record_headers(FileBatchIterator::from_raw_slice(raw_slice)))
+                    .await??
+            };
+            partitions.push(FetchablePartitionResponse {
+                partition_index: partition_response.partition_index,
+                error_code: partition_response.error_code,
+                high_watermark: partition_response.high_watermark,
+                next_filter_offset: partition_response.next_filter_offset,
+                log_start_offset: partition_response.log_start_offset,
+                aborted: partition_response.aborted,
+                records,
+            });
+        }
+        topics.push(FetchableTopicResponse {
+            name: topic_response.name,
+            partitions,
+            data: PhantomData,
+        });
+    }
+
+    Ok(FetchResponse {
+        throttle_time_ms: fetch_response.throttle_time_ms,
+        error_code: fetch_response.error_code,
+        session_id: fetch_response.session_id,
+        topics,
+    })
+}
+
+fn denied_topic(topic_request: &FetchableTopic) -> FetchableTopicResponse<FileRecordSet> {
+    FileTopicResponse {
+        name: topic_request.name.clone(),
+        partitions: topic_request
+            .fetch_partitions
+            .iter()
+            .map(|partition| FilePartitionResponse {
+                partition_index: partition.partition_index,
+                error_code: ErrorCode::PermissionDenied,
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
 #[instrument(
-    skip(ctx, fetch_request, topic_request),
+    skip(ctx, fetch_request, topic_request, leases),
     fields(topic = %topic_request.name),
 )]
 async fn handle_fetch_topic(
@@ -60,6 +164,7 @@ async fn handle_fetch_topic(
     fetch_request: &FileFetchRequest,
     topic_request: &FetchableTopic,
     is_connector: bool,
+    leases: &mut Vec<SliceLease>,
 ) -> Result<FetchableTopicResponse<FileRecordSet>> {
     let topic = &topic_request.name;
 
@@ -76,6 +181,7 @@ async fn handle_fetch_topic(
             fetch_request,
             partition_request,
             is_connector,
+            leases,
         )
         .await?;
         topic_response.partitions.push(partition_response);
@@ -85,7 +191,7 @@ async fn handle_fetch_topic(
 }
 
 #[instrument(
-skip(ctx, replica_id, partition_request),
+skip(ctx, replica_id, partition_request, leases),
     fields(%replica_id)
 )]
 async fn handle_fetch_partition(
@@ -94,6 +200,7 @@ async fn handle_fetch_partition(
     fetch_request: &FileFetchRequest,
     partition_request: &FetchPartition,
     is_connector: bool,
+    leases: &mut Vec<SliceLease>,
 ) -> Result<FetchablePartitionResponse<FileRecordSet>, SocketError> {
     trace!("Fetching partition:");
     let fetch_offset = partition_request.fetch_offset;
@@ -134,6 +241,7 @@ async fn handle_fetch_partition(
                 );
                 partition_response.records = file_slice.into();
             }
+            leases.extend(slice.lease);
         }
         Err(err) => {
             debug!(%err,"Failed to read records for partition");
@@ -5,12 +5,14 @@ mod offset_request;
 mod offset_update;
 mod stream_fetch;
 mod consumer_handler;
+mod sample_handler;
 
 #[cfg(test)]
 mod tests;
 mod conn_context;
 
 use std::sync::Arc;
+use std::time::Instant;
 use async_trait::async_trait;
 use fluvio_auth::Authorization;
 use fluvio_protocol::api::Request;
@@ -20,13 +22,15 @@ use fluvio_protocol::record::ReplicaKey;
 use fluvio_spu_schema::server::mirror::StartMirrorRequest;
 use tracing::{info, debug, trace, instrument};
 use futures_util::StreamExt;
+use tokio::select;
 use anyhow::Result;
 
-use fluvio_socket::FluvioSocket;
+use fluvio_socket::{tcp_connector, FluvioSocket};
 use fluvio_service::{FluvioApiServer, FluvioService, ConnectInfo, call_service};
 use fluvio_spu_schema::server::SpuServerRequest;
 use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_types::event::StickyEvent;
+use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::mirroring::home::connection::MirrorHomeHandler;
@@ -34,14 +38,16 @@ use crate::services::auth::SpuAuthGlobalContext;
 use crate::services::auth::SpuAuthServiceContext;
 use crate::services::public::consumer_handler::handle_delete_consumer_offset_request;
 use crate::services::public::consumer_handler::handle_fetch_consumer_offsets_request;
+use crate::services::public::consumer_handler::handle_set_consumer_offset_request;
 use crate::services::public::consumer_handler::handle_update_consumer_offset_request;
 use self::api_versions::handle_api_version_request;
 use self::produce_handler::handle_produce_request;
 use self::fetch_handler::handle_fetch_request;
 use self::offset_request::handle_offset_request;
 use self::offset_update::handle_offset_update;
+use self::sample_handler::handle_sample_request;
 use self::stream_fetch::{StreamFetchHandler, publishers::StreamPublishers};
-use self::conn_context::ConnectionContext;
+use self::conn_context::{ConnectionContext, ConnectionExpiry, ConnectionLifetime};
 use std::fmt::Debug;
 
 pub(crate) type SpuPublicServer<A> =
@@ -114,11 +120,31 @@ where
             let mut conn_ctx = ConnectionContext::new();
 
             let context = &context.global_ctx;
+            let auth = &service_context.auth;
+            let mut lifetime = ConnectionLifetime::new(&context.config().connection);
 
             loop {
-                let event = event_stream.next().await;
+                let expiry = lifetime.next_expiry(!conn_ctx.stream_publishers().is_empty());
+                let event = select! {
+                    event = event_stream.next() => event,
+                    expiry = wait_expiry(expiry) => {
+                        match expiry {
+                            ConnectionExpiry::Drain => {
+                                info!(sink_id = shared_sink.id(), "max connection age reached, draining");
+                                lifetime.start_drain();
+                                conn_ctx.drain_event().notify();
+                                continue;
+                            }
+                            ConnectionExpiry::Close => {
+                                debug!(sink_id = shared_sink.id(), "connection expired, closing");
+                                break;
+                            }
+                        }
+                    }
+                };
                 match event {
                     Some(Ok(req_message)) => {
+                        lifetime.on_request();
                         debug!(%req_message,"received");
                         //  println!("req: {:#?}", req_message);
                         trace!(
@@ -135,17 +161,22 @@ where
                             ),
                             SpuServerRequest::ProduceRequest(request) => call_service!(
                                 request,
-                                handle_produce_request(request, context.clone()),
+                                handle_produce_request(request, context.clone(), auth),
                                 shared_sink,
                                 "ProduceRequest"
                             ),
                             SpuServerRequest::FileFetchRequest(request) => {
-                                handle_fetch_request(request, context.clone(), shared_sink.clone())
-                                    .await?
+                                handle_fetch_request(
+                                    request,
+                                    context.clone(),
+                                    shared_sink.clone(),
+                                    auth,
+                                )
+                                .await?
                             }
                             SpuServerRequest::FetchOffsetsRequest(request) => call_service!(
                                 request,
-                                handle_offset_request(request, context.clone()),
+                                handle_offset_request(request, context.clone(), auth),
                                 shared_sink,
                                 "FetchOffsetsRequest"
                             ),
@@ -156,6 +187,7 @@ where
                                     &mut conn_ctx,
                                     shared_sink.clone(),
                                     shutdown.clone(),
+                                    auth,
                                 )
                                 .await?;
                             }
@@ -171,7 +203,8 @@ where
                                     handle_update_consumer_offset_request(
                                         request,
                                         context.clone(),
-                                        &mut conn_ctx
+                                        &mut conn_ctx,
+                                        auth
                                     ),
                                     shared_sink,
                                     "UpdateConsumerRequest"
@@ -180,7 +213,11 @@ where
                             SpuServerRequest::DeleteConsumerOffsetRequest(request) => {
                                 call_service!(
                                     request,
-                                    handle_delete_consumer_offset_request(request, context.clone()),
+                                    handle_delete_consumer_offset_request(
+                                        request,
+                                        context.clone(),
+                                        auth
+                                    ),
                                     shared_sink,
                                     "DeleteConsumerRequest"
                                 )
@@ -188,11 +225,33 @@ where
                             SpuServerRequest::FetchConsumerOffsetsRequest(request) => {
                                 call_service!(
                                     request,
-                                    handle_fetch_consumer_offsets_request(request, context.clone()),
+                                    handle_fetch_consumer_offsets_request(
+                                        request,
+                                        context.clone(),
+                                        auth
+                                    ),
                                     shared_sink,
                                     "FetchConsumersRequest"
                                 )
                             }
+                            SpuServerRequest::SetConsumerOffsetRequest(request) => {
+                                call_service!(
+                                    request,
+                                    handle_set_consumer_offset_request(
+                                        request,
+                                        context.clone(),
+                                        auth
+                                    ),
+                                    shared_sink,
+                                    "SetConsumerRequest"
+                                )
+                            }
+                            SpuServerRequest::SampleRecordsRequest(request) => call_service!(
+                                request,
+                                handle_sample_request(request, context.clone(), auth),
+                                shared_sink,
+                                "SampleRecordsRequest"
+                            ),
                             SpuServerRequest::StartMirrorRequest(request) => {
                                 // send mirror mode, afer that mirror cycle will be started
                                 mirror_request = Some(request);
@@ -225,6 +284,17 @@ where
     }
 }
 
+/// wait for the deadline of the connection, forever without deadline
+async fn wait_expiry(expiry: Option<(Instant, ConnectionExpiry)>) -> ConnectionExpiry {
+    match expiry {
+        Some((at, expiry)) => {
+            sleep(at.saturating_duration_since(Instant::now())).await;
+            expiry
+        }
+        None => std::future::pending().await,
+    }
+}
+
 async fn send_private_request_to_leader<R: Request>(
     ctx: &DefaultSharedGlobalContext,
     replica_id: &ReplicaKey,
@@ -242,9 +312,12 @@ async fn send_private_request_to_leader<R: Request>(
         spu,
         leader_endpoint, "send private request to replica leader"
     );
-    let mut socket = FluvioSocket::connect(&leader_endpoint)
-        .await
-        .map_err(|e| ErrorCode::Other(e.to_string()))?;
+    let mut socket = FluvioSocket::connect_with_connector(
+        &leader_endpoint,
+        tcp_connector(&ctx.config().tcp).as_ref(),
+    )
+    .await
+    .map_err(|e| ErrorCode::Other(e.to_string()))?;
 
     let req_msg = RequestMessage::new_request(req);
     let response = socket
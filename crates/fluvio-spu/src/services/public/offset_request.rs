@@ -11,16 +11,19 @@ use fluvio_spu_schema::server::fetch_offset::FetchOffsetPartitionResponse;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_types::{PartitionId, defaults::CONSUMER_STORAGE_TOPIC};
+use fluvio_controlplane_metadata::acl::AclPermission;
+use fluvio_auth::AuthContext;
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::kv::consumer::ConsumerOffsetKey;
 use crate::services::internal::FetchConsumerOffsetRequest;
 use crate::services::public::send_private_request_to_leader;
 
-#[instrument(skip(req_msg, ctx))]
-pub async fn handle_offset_request(
+#[instrument(skip(req_msg, ctx, auth))]
+pub async fn handle_offset_request<AC: AuthContext>(
     req_msg: RequestMessage<FetchOffsetsRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<FetchOffsetsResponse>, IoError> {
     let request = req_msg.request();
     trace!("handling flv fetch request: {:#?}", request);
@@ -35,6 +38,24 @@ pub async fn handle_offset_request(
             ..Default::default()
         };
 
+        if !auth
+            .allow_topic_action(topic, AclPermission::Consume)
+            .await?
+        {
+            debug!(topic, "fetch offsets not allowed");
+            topic_response.partitions = topic_request
+                .partitions
+                .iter()
+                .map(|partition_req| FetchOffsetPartitionResponse {
+                    partition_index: partition_req.partition_index,
+                    error_code: ErrorCode::PermissionDenied,
+                    ..Default::default()
+                })
+                .collect();
+            response.topics.push(topic_response);
+            continue;
+        }
+
         for partition_req in &topic_request.partitions {
             let partition = &partition_req.partition_index;
             let mut partition_response = FetchOffsetPartitionResponse {
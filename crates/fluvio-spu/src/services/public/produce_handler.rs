@@ -1,7 +1,7 @@
 use std::time::Duration;
 
 use tokio::select;
-use tracing::{debug, trace, error};
+use tracing::{debug, trace, error, warn};
 use tracing::instrument;
 use anyhow::{anyhow, Result};
 
@@ -17,19 +17,24 @@ use fluvio_spu_schema::produce::{
 };
 use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 use fluvio_protocol::{api::RequestMessage, link::ErrorCode};
+use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
 use fluvio_protocol::api::ResponseMessage;
 use fluvio_protocol::record::RecordSet;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::acl::AclPermission;
+use fluvio_auth::AuthContext;
 
 use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
-use crate::replication::leader::SharedFileLeaderState;
+use crate::core::disk::is_ephemeral;
+use crate::replication::leader::{SequenceCheck, SharedFileLeaderState};
 use crate::smartengine::batch::process_batch;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::EngineError;
 use crate::smartengine::map_engine_error;
 use crate::smartengine::produce_batch::ProduceBatchIterator;
+use crate::smartengine::DeadLetter;
 
 use crate::traffic::TrafficType;
 
@@ -47,15 +52,16 @@ struct PartitionWriteResult {
 }
 
 #[instrument(
-    skip(request,ctx),
+    skip(request,ctx,auth),
     fields(
         id = request.header.correlation_id(),
         client = %request.header.client_id()
     )
 )]
-pub async fn handle_produce_request(
+pub async fn handle_produce_request<AC: AuthContext>(
     request: RequestMessage<DefaultProduceRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<ProduceResponse>> {
     let (header, produce_request) = request.get_header_request();
     trace!("Handling ProduceRequest: {:#?}", produce_request);
@@ -64,6 +70,14 @@ pub async fn handle_produce_request(
 
     let mut topic_results = Vec::with_capacity(produce_request.topics.len());
     for topic_request in produce_request.topics.into_iter() {
+        if !auth
+            .allow_topic_action(&topic_request.name, AclPermission::Produce)
+            .await?
+        {
+            debug!(topic = %topic_request.name, "produce not allowed");
+            topic_results.push(denied_topic(topic_request));
+            continue;
+        }
         let topic_result =
             handle_produce_topic(&ctx, topic_request, &smartmodules, &header).await?;
         topic_results.push(topic_result);
@@ -80,6 +94,23 @@ pub async fn handle_produce_request(
     Ok(RequestMessage::<DefaultProduceRequest>::response_with_header(&header, response))
 }
 
+fn denied_topic(topic_request: DefaultTopicRequest) -> TopicWriteResult {
+    let partitions = topic_request
+        .partitions
+        .iter()
+        .map(|partition| {
+            PartitionWriteResult::error(
+                ReplicaKey::new(topic_request.name.clone(), partition.partition_index),
+                ErrorCode::PermissionDenied,
+            )
+        })
+        .collect();
+    TopicWriteResult {
+        topic: topic_request.name,
+        partitions,
+    }
+}
+
 #[instrument(
     skip(ctx, topic_request, smartmodules, header),
     fields(topic = %topic_request.name),
@@ -124,7 +155,32 @@ async fn handle_produce_topic(
             }
         }
 
+        // sequences cover the records sent, before smartmodules transform them
+        let pending_sequence = match leader_state
+            .check_producer_sequence(&partition_request.records)
+            .await
+        {
+            Ok(None) => None,
+            Ok(Some(SequenceCheck::Append(pending))) => Some(pending),
+            Ok(Some(SequenceCheck::Duplicate { base_offset, leo })) => {
+                topic_result.partitions.push(PartitionWriteResult::ok(
+                    replica_id,
+                    base_offset,
+                    leo,
+                ));
+                continue;
+            }
+            Err(err) => {
+                debug!(%replica_id, %err, "records of idempotent producer rejected");
+                topic_result
+                    .partitions
+                    .push(PartitionWriteResult::error(replica_id, err));
+                continue;
+            }
+        };
+
         if let Err(err) = apply_smartmodules(
+            &replica_id,
             &mut partition_request,
             smartmodules,
             header.api_version(),
@@ -157,6 +213,12 @@ async fn handle_produce_topic(
             .await
         };
 
+        if let Some(pending) = pending_sequence {
+            if partition_response.error_code.is_ok() {
+                pending.commit(partition_response.base_offset, partition_response.leo);
+            }
+        }
+
         topic_result.partitions.push(partition_response);
     }
     Ok(topic_result)
@@ -183,13 +245,56 @@ async fn handle_produce_partition(
         }
     };
 
+    if ctx.disk().is_full() && !is_ephemeral(&replica_metadata) {
+        debug!(%replica_id, "disk is full, rejecting records");
+        return PartitionWriteResult::error(replica_id, ErrorCode::StorageFull);
+    }
+
     let mut records = partition_request.records;
 
-    if validate_records(&records, replica_metadata.compression_type).is_err() {
+    let transcode_to = replica_metadata
+        .storage
+        .as_ref()
+        .filter(|storage| storage.is_transcoded())
+        .and_then(|_| topic_compression(&replica_metadata.compression_type));
+
+    if transcode_to.is_none()
+        && validate_records(&records, replica_metadata.compression_type).is_err()
+    {
         error!(%replica_id, "Compression in batch not supported by this topic");
         return PartitionWriteResult::error(replica_id, ErrorCode::CompressionError);
     }
 
+    if let Some(batch) = records.batches.iter().find(|batch| !batch.validate_crc()) {
+        error!(%replica_id, crc = batch.header.crc, "record batch checksum mismatch");
+        return PartitionWriteResult::error(replica_id, ErrorCode::CorruptBatch);
+    }
+
+    if let Some(compression) = transcode_to {
+        if let Err(err) = transcode_records(&mut records, compression) {
+            error!(%replica_id, %compression, "unable to transcode records: {err:#}");
+            return PartitionWriteResult::error(replica_id, ErrorCode::CompressionError);
+        }
+    }
+
+    match leader_state.validate_record_set(&mut records).await {
+        Ok(None) => {}
+        Ok(Some(reason)) => {
+            let dead_letter = replica_metadata
+                .validation
+                .as_ref()
+                .and_then(|validation| validation.on_invalid.dead_letter_topic());
+            return handle_invalid_records(ctx, replica_id, dead_letter, &records, reason).await;
+        }
+        Err(err) => {
+            error!(%replica_id, "validation smartmodule failed: {err:#?}");
+            return PartitionWriteResult::error(
+                replica_id,
+                ErrorCode::Other(format!("validation smartmodule failed: {err}")),
+            );
+        }
+    }
+
     let write_result = leader_state
         .write_record_set(&mut records, ctx.follower_notifier())
         .await;
@@ -220,6 +325,13 @@ async fn handle_produce_partition(
                     error!(%replica_id, batch_size, max_segment_size, "Batch size exceeded max segment size");
                     PartitionWriteResult::error(replica_id, ErrorCode::MessageTooLarge)
                 }
+                Some(storage_err) if storage_err.is_storage_full() => {
+                    if ctx.disk().set_full() {
+                        warn!(%replica_id, "disk ran out of space, rejecting produces");
+                        leader_state.update_status().await;
+                    }
+                    PartitionWriteResult::error(replica_id, ErrorCode::StorageFull)
+                }
                 _ => {
                     error!(%replica_id, "Error writing to replica: {:#?}", err);
                     PartitionWriteResult::error(replica_id, ErrorCode::StorageError)
@@ -229,7 +341,48 @@ async fn handle_produce_partition(
     }
 }
 
+/// invalid records are never appended, they are either reported to the producer
+/// or routed to the dead letter topic of the validation
+async fn handle_invalid_records(
+    ctx: &DefaultSharedGlobalContext,
+    replica_id: ReplicaKey,
+    dead_letter: Option<&str>,
+    records: &RecordSet<RawRecords>,
+    reason: SmartModuleTransformRuntimeError,
+) -> PartitionWriteResult {
+    debug!(%replica_id, %reason, "records rejected by validation smartmodule");
+    if let Some(topic) = dead_letter {
+        match ctx.dead_letter().send(topic, &replica_id, records).await {
+            Ok(()) => return PartitionWriteResult::filtered(replica_id),
+            Err(err) => {
+                error!(%replica_id, topic, "unable to route invalid records to dead letter topic: {err:#}")
+            }
+        }
+    }
+    PartitionWriteResult::error(replica_id, ErrorCode::RecordValidationFailed(reason))
+}
+
+/// the other records are appended whether or not the failed ones reach their topics
+async fn route_dead_letters(
+    ctx: &DefaultSharedGlobalContext,
+    replica_id: &ReplicaKey,
+    dead_letters: Vec<DeadLetter>,
+) {
+    if dead_letters.is_empty() {
+        return;
+    }
+    debug!(%replica_id, count = dead_letters.len(), "routing records to dead letter topics");
+    if let Err(err) = ctx
+        .dead_letter()
+        .send_failures(replica_id, dead_letters, false)
+        .await
+    {
+        error!(%replica_id, "unable to route failed records to dead letter topic: {err:#}");
+    }
+}
+
 async fn apply_smartmodules(
+    replica_id: &ReplicaKey,
     partition_request: &mut PartitionProduceData<RecordSet<RawRecords>>,
     smartmodules: &[SmartModuleInvocation],
     api_version: i16,
@@ -244,17 +397,25 @@ async fn apply_smartmodules(
 
     sm_ctx.look_back(leader_state).await?;
 
-    let records = &partition_request.records;
-    let batches = &records.batches;
-
-    let mut batches = ProduceBatchIterator::new(batches);
+    let batches = partition_request.records.batches.clone();
+    let metrics = ctx.metrics();
+    let (result, dead_letters) = ctx
+        .smartmodule_pool()
+        .run(move || {
+            let mut batches = ProduceBatchIterator::new(&batches);
+            let result = process_batch(
+                sm_ctx.chain_mut(),
+                &mut batches,
+                usize::MAX,
+                metrics.chain_metrics(),
+            );
+            (result, sm_ctx.chain_mut().take_dead_letters())
+        })
+        .await
+        .map_err(|err| ErrorCode::Other(err.to_string()))?;
+    route_dead_letters(ctx, replica_id, dead_letters).await;
 
-    let sm_result = match process_batch(
-        sm_ctx.chain_mut(),
-        &mut batches,
-        usize::MAX,
-        ctx.metrics().chain_metrics(),
-    ) {
+    let sm_result = match result {
         Ok((result, sm_runtime_error)) => {
             if let Some(error) = sm_runtime_error {
                 return Err(ErrorCode::SmartModuleRuntimeError(error));
@@ -303,6 +464,40 @@ fn validate_records<R: BatchRecords>(
         Err(anyhow!("Compression not supported by topic"))
     }
 }
+
+/// codec of the batches stored in a topic with `compression`, `None` if any is allowed
+fn topic_compression(compression: &CompressionAlgorithm) -> Option<Compression> {
+    match compression {
+        CompressionAlgorithm::Any => None,
+        CompressionAlgorithm::None => Some(Compression::None),
+        CompressionAlgorithm::Gzip => Some(Compression::Gzip),
+        CompressionAlgorithm::Snappy => Some(Compression::Snappy),
+        CompressionAlgorithm::Lz4 => Some(Compression::Lz4),
+        CompressionAlgorithm::Zstd => Some(Compression::Zstd),
+    }
+}
+
+/// Recompress the batches that are not compressed with `compression`,
+/// so all the batches of the topic are stored the same way.
+fn transcode_records(records: &mut RecordSet<RawRecords>, compression: Compression) -> Result<()> {
+    for batch in records.batches.iter_mut() {
+        let current = batch.get_compression()?;
+        if current == compression {
+            continue;
+        }
+        trace!(from = %current, to = %compression, "transcoding batch");
+        let schema_id = batch.schema_id();
+        let mut decompressed = Batch::try_from(batch.clone())?;
+        decompressed.schema_id = schema_id;
+        decompressed.header.set_compression(compression);
+
+        let mut transcoded = Batch::<RawRecords>::try_from(decompressed)?;
+        transcoded.header.crc = transcoded.compute_crc()?;
+        *batch = transcoded;
+    }
+    Ok(())
+}
+
 /// For isolation = ReadCommitted wait until the replica's `hw` includes written records offsets or
 /// until `timeout` passes. In case of timeout, the partition response returns `RequestTimedOut`
 /// error code. The timeout is not shared between partitions.
@@ -0,0 +1,154 @@
+use std::io::Error as IoError;
+
+use rand::seq::index;
+use tracing::{debug, trace, instrument};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::Offset;
+use fluvio_auth::AuthContext;
+use fluvio_controlplane_metadata::acl::AclPermission;
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::server::sample::{
+    SampleRecordsRequest, SampleRecordsResponse, SampleStrategy, SampledRecord,
+};
+use fluvio_storage::FileReplica;
+use fluvio_storage::iterators::{FileBatchIterator, FileRecordIterator};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::replication::leader::LeaderReplicaState;
+
+/// upper bound of records returned by a single sample request
+const MAX_SAMPLE_RECORDS: u32 = 1000;
+
+const RECORDS_SERIALIZATION_VERSION: i16 = 0;
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub async fn handle_sample_request<AC: AuthContext>(
+    req_msg: RequestMessage<SampleRecordsRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<SampleRecordsResponse>, IoError> {
+    let request = req_msg.request();
+    trace!("handling sample request: {:#?}", request);
+
+    let mut response = SampleRecordsResponse::default();
+    let replica_id = ReplicaKey::new(request.topic.clone(), request.partition);
+    if !auth
+        .allow_topic_action(&request.topic, AclPermission::Consume)
+        .await?
+    {
+        debug!(%replica_id, "sample not allowed");
+        response.error_code = ErrorCode::PermissionDenied;
+        return Ok(req_msg.new_response(response));
+    }
+    match ctx.leaders_state().get(&replica_id).await {
+        Some(replica) => {
+            let (start_offset, hw) = replica.start_offset_info().await;
+            response.start_offset = start_offset;
+            response.hw = hw;
+            let count = request.count.min(MAX_SAMPLE_RECORDS);
+            match sample_records(&replica, start_offset, hw, count, request.strategy).await {
+                Ok(records) => response.records = records,
+                Err(err) => {
+                    debug!(%replica_id, %err, "failed to sample records");
+                    response.error_code = err;
+                }
+            }
+        }
+        None => {
+            debug!(%replica_id, "sample request is not for leader");
+            response.error_code = ErrorCode::PartitionNotLeader;
+        }
+    }
+
+    Ok(req_msg.new_response(response))
+}
+
+/// read `count` committed records between `start_offset` and `hw`, in offset order
+async fn sample_records(
+    replica: &LeaderReplicaState<FileReplica>,
+    start_offset: Offset,
+    hw: Offset,
+    count: u32,
+    strategy: SampleStrategy,
+) -> Result<Vec<SampledRecord>, ErrorCode> {
+    let count = Offset::from(count);
+    match strategy {
+        SampleStrategy::Head => {
+            read_range(replica, start_offset, hw.min(start_offset + count)).await
+        }
+        SampleStrategy::Tail => read_range(replica, start_offset.max(hw - count), hw).await,
+        SampleStrategy::Random => {
+            let mut records = Vec::new();
+            for offset in random_offsets(start_offset, hw, count) {
+                records.append(&mut read_range(replica, offset, offset + 1).await?);
+            }
+            Ok(records)
+        }
+    }
+}
+
+/// distinct offsets between `start_offset` and `hw`, sorted
+fn random_offsets(start_offset: Offset, hw: Offset, count: Offset) -> Vec<Offset> {
+    let len = (hw - start_offset).max(0) as usize;
+    let amount = (count.max(0) as usize).min(len);
+    let mut offsets: Vec<Offset> = index::sample(&mut rand::thread_rng(), len, amount)
+        .into_iter()
+        .map(|index| start_offset + index as Offset)
+        .collect();
+    offsets.sort_unstable();
+    offsets
+}
+
+/// read records with offsets in `from..to`, only decoding the batches covering them
+async fn read_range(
+    replica: &LeaderReplicaState<FileReplica>,
+    from: Offset,
+    to: Offset,
+) -> Result<Vec<SampledRecord>, ErrorCode> {
+    let mut records = Vec::new();
+    if from >= to {
+        return Ok(records);
+    }
+    let slice = replica
+        .read_records(from, u32::MAX, Isolation::ReadCommitted)
+        .await?;
+    let Some(file_slice) = slice.file_slice else {
+        return Ok(records);
+    };
+    let batches = FileBatchIterator::from_raw_slice(file_slice);
+    for item in FileRecordIterator::new(batches, RECORDS_SERIALIZATION_VERSION) {
+        let item = item.map_err(|err| ErrorCode::Other(err.to_string()))?;
+        if item.offset < from {
+            continue;
+        }
+        if item.offset >= to {
+            break;
+        }
+        records.push(SampledRecord {
+            offset: item.offset,
+            timestamp: item.timestamp,
+            key: item.record.key().cloned(),
+            value: item.record.value().clone(),
+        });
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_offsets() {
+        let offsets = random_offsets(10, 20, 5);
+        assert_eq!(offsets.len(), 5);
+        assert!(offsets.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(offsets.iter().all(|offset| (10..20).contains(offset)));
+
+        assert_eq!(random_offsets(10, 13, 5), vec![10, 11, 12]);
+        assert!(random_offsets(0, 0, 5).is_empty());
+    }
+}
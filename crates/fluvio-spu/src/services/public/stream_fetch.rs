@@ -1,3 +1,4 @@
+use std::io::Cursor;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -5,18 +6,22 @@ use tracing::{debug, error, instrument, trace, warn};
 use tokio::select;
 
 use fluvio_compression::CompressionError;
+use fluvio_auth::AuthContext;
+use fluvio_controlplane_metadata::acl::AclPermission;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_types::event::{
     offsets::{OffsetPublisher, INIT_OFFSET, TOPIC_DELETED},
     StickyEvent,
 };
 use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use fluvio_protocol::{
     api::{RequestMessage, RequestHeader},
     record::{RecordSet, Offset, RawRecords},
 };
 use fluvio_protocol::link::{ErrorCode, smartmodule::SmartModuleTransformRuntimeError};
-use fluvio_protocol::record::Batch;
+use fluvio_protocol::record::{Batch, Record};
+use fluvio_protocol::Decoder;
 use fluvio_socket::{ExclusiveFlvSink, SocketError};
 use fluvio_storage::iterators::FileBatchIterator;
 use fluvio_spu_schema::{
@@ -24,7 +29,7 @@ use fluvio_spu_schema::{
         DefaultStreamFetchRequest, FileStreamFetchRequest, StreamFetchRequest, StreamFetchResponse,
     },
     fetch::{FilePartitionResponse, FetchablePartitionResponse},
-    Isolation,
+    Isolation, RECORD_HEADERS_API_VERSION,
     file::FileRecordSet,
 };
 use fluvio_types::event::offsets::OffsetChangeListener;
@@ -34,6 +39,8 @@ use crate::replication::leader::SharedFileLeaderState;
 use crate::services::public::conn_context::ConnectionContext;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::batch::process_batch;
+use crate::smartengine::pool::SmartModulePool;
+use crate::smartengine::DeadLetter;
 use crate::core::metrics::SpuMetrics;
 use crate::traffic::TrafficType;
 
@@ -46,74 +53,104 @@ pub struct StreamFetchHandler {
     header: RequestHeader,
     sink: ExclusiveFlvSink,
     end_event: Arc<StickyEvent>,
+    /// set when the connection drains, the client resumes the stream on a new connection
+    drain_event: Arc<StickyEvent>,
     consumer_offset_listener: OffsetChangeListener,
     leader_state: SharedFileLeaderState,
     stream_id: u32,
     metrics: Arc<SpuMetrics>,
+    /// identity outbound metrics are attributed to
+    consumer: Option<String>,
+    smartmodule_pool: SmartModulePool,
+    ctx: DefaultSharedGlobalContext,
 }
 
 impl StreamFetchHandler {
     /// handle fluvio continuous fetch request
-    pub(crate) async fn start(
+    pub(crate) async fn start<AC: AuthContext>(
         request: RequestMessage<FileStreamFetchRequest>,
         ctx: DefaultSharedGlobalContext,
         conn_ctx: &mut ConnectionContext,
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
+        auth: &AC,
     ) -> Result<(), SocketError> {
         let (header, msg) = request.get_header_request();
         let replica = ReplicaKey::new(msg.topic.clone(), msg.partition);
 
-        if let Some(leader_state) = ctx.leaders_state().get(&replica).await {
-            let (stream_id, offset_publisher) = conn_ctx
-                .stream_publishers_mut()
-                .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
-                .await;
-            let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
-
-            leader_state
-                .register_offset_publisher(&offset_publisher.offset_publisher)
-                .await;
-
-            spawn(async move {
-                if let Err(err) = StreamFetchHandler::fetch(
-                    ctx,
-                    sink,
-                    end_event.clone(),
-                    leader_state,
-                    stream_id,
-                    header,
-                    replica,
-                    consumer_offset_listener,
-                    msg,
-                )
-                .await
-                {
-                    error!("error starting stream fetch handler: {:#?}", err);
-                    end_event.notify();
-                }
-            });
+        let allowed = auth
+            .allow_topic_action(&msg.topic, AclPermission::Consume)
+            .await
+            .map_err(std::io::Error::from)?;
+        let leader_state = if conn_ctx.is_draining() {
+            debug!(%replica, "connection draining, rejecting stream");
+            Err(ErrorCode::ConnectionDraining)
+        } else if !allowed {
+            debug!(%replica, "consume not allowed, rejecting stream");
+            Err(ErrorCode::PermissionDenied)
         } else {
-            debug!(topic = %replica.topic," no leader found, returning");
-            let response = StreamFetchResponse {
-                topic: replica.topic,
-                stream_id: 0,
-                partition: FilePartitionResponse {
-                    partition_index: replica.partition,
-                    error_code: ErrorCode::NotLeaderForPartition,
-                    ..Default::default()
-                },
-            };
+            ctx.leaders_state()
+                .get(&replica)
+                .await
+                .ok_or(ErrorCode::NotLeaderForPartition)
+        };
 
-            let response_msg =
-                RequestMessage::<FileStreamFetchRequest>::response_with_header(&header, response);
+        match leader_state {
+            Ok(leader_state) => {
+                let (stream_id, offset_publisher) = conn_ctx
+                    .stream_publishers_mut()
+                    .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
+                    .await;
+                let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
+                let drain_event = conn_ctx.drain_event();
+
+                leader_state
+                    .register_offset_publisher(&offset_publisher.offset_publisher)
+                    .await;
+
+                spawn(async move {
+                    if let Err(err) = StreamFetchHandler::fetch(
+                        ctx,
+                        sink,
+                        end_event.clone(),
+                        drain_event,
+                        leader_state,
+                        stream_id,
+                        header,
+                        replica,
+                        consumer_offset_listener,
+                        msg,
+                    )
+                    .await
+                    {
+                        error!("error starting stream fetch handler: {:#?}", err);
+                        end_event.notify();
+                    }
+                });
+            }
+            Err(error_code) => {
+                debug!(topic = %replica.topic, ?error_code, "stream not started, returning");
+                let response = StreamFetchResponse {
+                    topic: replica.topic,
+                    stream_id: 0,
+                    partition: FilePartitionResponse {
+                        partition_index: replica.partition,
+                        error_code,
+                        ..Default::default()
+                    },
+                };
 
-            trace!("sending back file fetch response msg: {:#?}", response_msg);
+                let response_msg = RequestMessage::<FileStreamFetchRequest>::response_with_header(
+                    &header, response,
+                );
 
-            let mut inner_sink = sink.lock().await;
-            inner_sink
-                .send_response(&response_msg, header.api_version())
-                .await?;
+                trace!("sending back file fetch response msg: {:#?}", response_msg);
+
+                let mut inner_sink = sink.lock().await;
+                inner_sink
+                    .send_response(&response_msg, header.api_version())
+                    .await?;
+            }
         }
 
         Ok(())
@@ -121,7 +158,7 @@ impl StreamFetchHandler {
 
     #[allow(clippy::too_many_arguments)]
     #[instrument(
-        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener),
+        skip(ctx,replica,end_event,drain_event,leader_state,header,msg,consumer_offset_listener),
         fields(
             replica = %replica,
             sink = sink.id()
@@ -131,6 +168,7 @@ impl StreamFetchHandler {
         ctx: DefaultSharedGlobalContext,
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
+        drain_event: Arc<StickyEvent>,
         leader_state: SharedFileLeaderState,
         stream_id: u32,
         header: RequestHeader,
@@ -168,6 +206,7 @@ impl StreamFetchHandler {
 
         let starting_offset = msg.fetch_offset;
         let isolation = msg.isolation;
+        let consumer = msg.application.or(msg.consumer_id);
 
         debug!(
             max_bytes,
@@ -184,12 +223,16 @@ impl StreamFetchHandler {
             max_bytes,
             sink: sink.clone(),
             end_event,
+            drain_event,
             header: header.clone(),
             consumer_offset_listener,
             stream_id,
             leader_state,
             max_fetch_bytes,
             metrics: ctx.metrics(),
+            consumer,
+            smartmodule_pool: ctx.smartmodule_pool().clone(),
+            ctx,
         };
 
         if let Err(err) = handler.process(starting_offset, sm_ctx).await {
@@ -222,9 +265,8 @@ impl StreamFetchHandler {
         starting_offset: Offset,
         mut sm_ctx: Option<SmartModuleContext>,
     ) -> Result<(), StreamFetchError> {
-        let (mut last_partition_offset, consumer_wait) = self
-            .send_back_records(starting_offset, sm_ctx.as_mut())
-            .await?;
+        let (mut last_partition_offset, consumer_wait) =
+            self.send_back_records(starting_offset, &mut sm_ctx).await?;
 
         let mut leader_offset_receiver = self.leader_state.offset_listener(&self.isolation);
         let mut counter: i32 = 0;
@@ -247,6 +289,11 @@ impl StreamFetchHandler {
                     break;
                 },
 
+                _ = self.drain_event.listen() => {
+                    debug!("connection draining, moving stream to a new connection");
+                    return Err(StreamFetchError::Fetch(ErrorCode::ConnectionDraining))
+                },
+
 
                 // Received offset update from consumer, i.e. consumer acknowledged to this offset
                 consumer_offset_update = self.consumer_offset_listener.listen() => {
@@ -276,7 +323,7 @@ impl StreamFetchHandler {
                         last_partition_offset,
                         "Consumer offset updated and is behind, need to send records",
                     );
-                    let (offset, wait) = self.send_back_records(consumer_offset_update, sm_ctx.as_mut()).await?;
+                    let (offset, wait) = self.send_back_records(consumer_offset_update, &mut sm_ctx).await?;
                     last_partition_offset = offset;
                     if wait {
                         last_known_consumer_offset = None;
@@ -317,7 +364,7 @@ impl StreamFetchHandler {
 
                     // We need to send the consumer all records since the last consumer offset
                     debug!(partition_offset_update, last_consumer_offset, "reading offset event");
-                    let (offset, wait) = self.send_back_records(last_consumer_offset, sm_ctx.as_mut()).await?;
+                    let (offset, wait) = self.send_back_records(last_consumer_offset, &mut sm_ctx).await?;
                     last_partition_offset = offset;
                     if wait {
                         last_known_consumer_offset = None;
@@ -350,7 +397,7 @@ impl StreamFetchHandler {
     async fn send_back_records(
         &mut self,
         starting_offset: Offset,
-        sm_ctx: Option<&mut SmartModuleContext>,
+        sm_ctx: &mut Option<SmartModuleContext>,
     ) -> Result<(Offset, bool), StreamFetchError> {
         let now = Instant::now();
 
@@ -362,7 +409,8 @@ impl StreamFetchHandler {
         // Read records from the leader starting from `offset`
         // Returns with the HW/LEO of the latest records available in the leader
         // This describes the range of records that can be read in this request
-        let read_end_offset = match self
+        // The lease keeps a slice of a remote segment readable until it is sent
+        let (read_end_offset, _lease) = match self
             .leader_state
             .read_records(starting_offset, self.max_fetch_bytes, self.isolation)
             .await
@@ -374,7 +422,7 @@ impl StreamFetchHandler {
                 if let Some(file_slice) = slice.file_slice {
                     file_partition_response.records = file_slice.into();
                 }
-                slice.end
+                (slice.end, slice.lease)
             }
             Err(err) => {
                 debug!(%err,"error reading records from leader");
@@ -400,22 +448,32 @@ impl StreamFetchHandler {
             return Ok((starting_offset, false));
         }
 
-        let (offset, wait, metrics_update) = match sm_ctx {
-            Some(sm_ctx) => {
+        let (offset, wait, metrics_update) = match sm_ctx.take() {
+            Some(mut ctx) => {
                 // If a SmartModule is provided, we need to read records from file to memory
                 // In-memory records are then processed by SmartModule and returned to consumer
 
-                let records = &file_partition_response.records;
-                let mut file_batch_iterator =
-                    FileBatchIterator::from_raw_slice(records.raw_slice());
-
-                let (batch, smartmodule_error) = process_batch(
-                    sm_ctx.chain_mut(),
-                    &mut file_batch_iterator,
-                    self.max_bytes as usize,
-                    self.metrics.chain_metrics(),
-                )
-                .map_err(|err| {
+                let raw_slice = file_partition_response.records.raw_slice();
+                let max_bytes = self.max_bytes as usize;
+                let metrics = self.metrics.clone();
+                let (mut ctx, result) = self
+                    .smartmodule_pool
+                    .run(move || {
+                        let mut file_batch_iterator = FileBatchIterator::from_raw_slice(raw_slice);
+                        let result = process_batch(
+                            ctx.chain_mut(),
+                            &mut file_batch_iterator,
+                            max_bytes,
+                            metrics.chain_metrics(),
+                        );
+                        (ctx, result)
+                    })
+                    .await
+                    .map_err(|err| StreamFetchError::Fetch(ErrorCode::Other(err.to_string())))?;
+                let dead_letters = ctx.chain_mut().take_dead_letters();
+                *sm_ctx = Some(ctx);
+                self.route_dead_letters(dead_letters);
+                let (batch, smartmodule_error) = result.map_err(|err| {
                     StreamFetchError::Fetch(ErrorCode::Other(format!("SmartModule err {err}")))
                 })?;
                 let metrics_update = IncreaseValue::from(&batch);
@@ -430,6 +488,44 @@ impl StreamFetchHandler {
                     .await?;
                 (offset, wait, metrics_update)
             }
+            None if self.header.api_version() < RECORD_HEADERS_API_VERSION => {
+                // Older consumers would misread header entries, records are sent without them
+                let raw_slice = file_partition_response.records.raw_slice();
+                let records = self
+                    .smartmodule_pool
+                    .run(move || strip_record_headers(FileBatchIterator::from_raw_slice(raw_slice)))
+                    .await
+                    .map_err(|err| StreamFetchError::Fetch(ErrorCode::Other(err.to_string())))??;
+                let metrics_update = IncreaseValue::from(&file_partition_response);
+
+                type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
+                let partition_response = DefaultPartitionResponse {
+                    partition_index: file_partition_response.partition_index,
+                    error_code: file_partition_response.error_code,
+                    high_watermark: file_partition_response.high_watermark,
+                    log_start_offset: file_partition_response.log_start_offset,
+                    records,
+                    ..Default::default()
+                };
+                let response = StreamFetchResponse {
+                    topic: self.replica.topic.clone(),
+                    stream_id: self.stream_id,
+                    partition: partition_response,
+                };
+                let response_msg =
+                    RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
+                        &self.header,
+                        response,
+                    );
+
+                let mut inner_sink = self.sink.lock().await;
+                inner_sink
+                    .send_response(&response_msg, self.header.api_version())
+                    .await?;
+                drop(inner_sink);
+
+                (next_offset, true, metrics_update)
+            }
             None => {
                 // If no SmartModule is provided, respond using raw file records
                 debug!("No SmartModule, sending back entire log");
@@ -467,15 +563,70 @@ impl StreamFetchHandler {
         self.metrics
             .outbound()
             .increase_by_value(self.header.is_connector(), metrics_update);
+        if let Some(consumer) = &self.consumer {
+            self.metrics
+                .consumers()
+                .increase_by_value(consumer, metrics_update);
+        }
+        self.throttle(metrics_update.bytes()).await;
         Ok((offset, wait))
     }
 
+    /// wait until the consumer is back within the fetch quota of the SPU.
+    /// Streams without a consumer identity are not limited.
+    async fn throttle(&self, sent_bytes: u64) {
+        let (Some(consumer), Some(quota)) =
+            (&self.consumer, self.ctx.config().consumer_fetch_quota)
+        else {
+            return;
+        };
+        let delay = self
+            .metrics
+            .consumers()
+            .throttle(consumer, sent_bytes, quota);
+        if delay.is_zero() {
+            return;
+        }
+        debug!(
+            consumer,
+            delay_ms = %delay.as_millis(),
+            "consumer over fetch quota"
+        );
+        select! {
+            _ = sleep(delay) => {},
+            _ = self.end_event.listen() => {},
+        }
+    }
+
+    /// failed records are produced in the background, the stream goes on with the next ones.
+    /// Records read again after a partial response may be routed more than once.
+    fn route_dead_letters(&self, dead_letters: Vec<DeadLetter>) {
+        if dead_letters.is_empty() {
+            return;
+        }
+        debug!(
+            count = dead_letters.len(),
+            "routing records to dead letter topics"
+        );
+        let ctx = self.ctx.clone();
+        let replica = self.replica.clone();
+        spawn(async move {
+            if let Err(err) = ctx
+                .dead_letter()
+                .send_failures(&replica, dead_letters, true)
+                .await
+            {
+                error!(%replica, "unable to route failed records to dead letter topic: {err:#}");
+            }
+        });
+    }
+
     #[instrument(skip(self, file_partition_response, batch, smartmodule_error))]
     async fn send_processed_response(
         &self,
         file_partition_response: FilePartitionResponse,
         next_offset: Offset,
-        batch: Batch,
+        mut batch: Batch,
         smartmodule_error: Option<SmartModuleTransformRuntimeError>,
     ) -> Result<(Offset, bool), StreamFetchError> {
         type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
@@ -509,6 +660,13 @@ impl StreamFetchHandler {
 
         //trace!("batch: {:#?}",batch);
 
+        if self.header.api_version() < RECORD_HEADERS_API_VERSION {
+            // headers added by the SmartModule would be misread as well
+            for record in batch.mut_records() {
+                record.headers.clear();
+            }
+        }
+
         let records = RecordSet::default().add(batch);
         let partition_response = DefaultPartitionResponse {
             partition_index: self.replica.partition,
@@ -543,6 +701,31 @@ impl StreamFetchHandler {
     }
 }
 
+/// Records of the batches with their headers removed, for consumers older than
+/// [`RECORD_HEADERS_API_VERSION`]
+pub(super) fn strip_record_headers(
+    batches: FileBatchIterator,
+) -> Result<RecordSet<RawRecords>, ErrorCode> {
+    let mut records = RecordSet::default();
+    for file_batch in batches {
+        let file_batch = file_batch.map_err(|err| ErrorCode::Other(err.to_string()))?;
+        let mut batch_records: Vec<Record> =
+            Decoder::decode_from(&mut Cursor::new(&file_batch.records), 0)
+                .map_err(|err| ErrorCode::Other(err.to_string()))?;
+        for record in &mut batch_records {
+            record.headers.clear();
+        }
+        let mut batch = file_batch.batch;
+        *batch.mut_records() = batch_records;
+        let batch: Batch<RawRecords> = batch.try_into().map_err(|err: CompressionError| {
+            error!(%err, "compression error");
+            ErrorCode::CompressionError
+        })?;
+        records = records.add(batch);
+    }
+    Ok(records)
+}
+
 async fn send_back_error(
     sink: &ExclusiveFlvSink,
     replica: &ReplicaKey,
@@ -671,5 +854,10 @@ pub mod publishers {
         pub async fn get_publisher(&self, stream_id: u32) -> Option<StreamPublisher> {
             self.publishers.get(&stream_id).cloned()
         }
+
+        /// no stream was started on the connection
+        pub fn is_empty(&self) -> bool {
+            self.publishers.is_empty()
+        }
     }
 }
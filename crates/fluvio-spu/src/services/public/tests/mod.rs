@@ -23,6 +23,7 @@ use super::{create_public_server, SpuPublicServer};
 
 mod stream_fetch;
 mod produce;
+mod offsets;
 
 /// create records that can be filtered
 fn create_filter_records(records: u16) -> RecordSet {
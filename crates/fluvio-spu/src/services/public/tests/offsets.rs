@@ -0,0 +1,99 @@
+use std::env::temp_dir;
+
+use async_trait::async_trait;
+
+use fluvio_auth::{AuthContext, AuthError, InstanceAction, TypeAction};
+use fluvio_controlplane_metadata::acl::AclPermission;
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::consumer_offset::{
+    DeleteConsumerOffsetRequest, SetConsumerOffsetRequest,
+};
+use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
+use flv_util::fixture::ensure_clean_dir;
+
+use crate::{
+    config::SpuConfig,
+    core::{DefaultSharedGlobalContext, GlobalContext},
+    services::public::{
+        consumer_handler::{
+            handle_delete_consumer_offset_request, handle_set_consumer_offset_request,
+        },
+        offset_request::handle_offset_request,
+    },
+};
+
+/// allows a single permission on every topic
+#[derive(Debug)]
+struct OnlyPermission(AclPermission);
+
+#[async_trait]
+impl AuthContext for OnlyPermission {
+    async fn allow_type_action(
+        &self,
+        _ty: ObjectType,
+        _action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        Ok(true)
+    }
+
+    async fn allow_instance_action(
+        &self,
+        _ty: ObjectType,
+        _action: InstanceAction,
+        _key: &str,
+    ) -> Result<bool, AuthError> {
+        Ok(true)
+    }
+
+    async fn allow_topic_action(
+        &self,
+        _topic: &str,
+        permission: AclPermission,
+    ) -> Result<bool, AuthError> {
+        Ok(permission == self.0)
+    }
+}
+
+fn create_context(name: &str) -> DefaultSharedGlobalContext {
+    let test_path = temp_dir().join(name);
+    ensure_clean_dir(&test_path);
+
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    GlobalContext::new_shared_context(spu_config)
+}
+
+#[fluvio_future::test]
+async fn test_consumer_offset_write_denied() {
+    let ctx = create_context("consumer_offset_write_denied");
+    let auth = OnlyPermission(AclPermission::Consume);
+
+    let request = RequestMessage::new_request(DeleteConsumerOffsetRequest::new("test", 0, "c1"));
+    let response = handle_delete_consumer_offset_request(request, ctx.clone(), &auth)
+        .await
+        .expect("response");
+    assert_eq!(response.response.error_code, ErrorCode::PermissionDenied);
+
+    let request = RequestMessage::new_request(SetConsumerOffsetRequest::new("test", 0, "c1", 10));
+    let response = handle_set_consumer_offset_request(request, ctx, &auth)
+        .await
+        .expect("response");
+    assert_eq!(response.response.error_code, ErrorCode::PermissionDenied);
+}
+
+#[fluvio_future::test]
+async fn test_fetch_offsets_denied() {
+    let ctx = create_context("fetch_offsets_denied");
+    let auth = OnlyPermission(AclPermission::Produce);
+
+    let request = RequestMessage::new_request(FetchOffsetsRequest::new("test".to_owned(), 0, None));
+    let response = handle_offset_request(request, ctx, &auth)
+        .await
+        .expect("response");
+    let topic = &response.response.topics[0];
+    assert_eq!(topic.name, "test");
+    assert_eq!(topic.partitions.len(), 1);
+    assert_eq!(topic.partitions[0].error_code, ErrorCode::PermissionDenied);
+}
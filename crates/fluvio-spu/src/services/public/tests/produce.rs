@@ -1,6 +1,6 @@
 use std::{env::temp_dir, time::Duration};
 
-use fluvio::{SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind};
+use fluvio::{Compression, SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind};
 use fluvio_controlplane::replica::Replica;
 use fluvio_smartmodule::{Record, dataplane::smartmodule::Lookback};
 use fluvio_storage::{FileReplica, iterators::FileBatchIterator};
@@ -12,7 +12,7 @@ use fluvio_protocol::{
     Decoder,
 };
 use fluvio_controlplane_metadata::topic::{
-    CompressionAlgorithm, Deduplication, Bounds, Filter, Transform,
+    CompressionAlgorithm, Deduplication, Bounds, Filter, Transform, TopicStorageConfig,
 };
 use fluvio_future::timer::sleep;
 use fluvio_socket::{MultiplexerSocket, FluvioSocket};
@@ -218,6 +218,94 @@ async fn test_produce_invalid_compression() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+
+#[fluvio_future::test(ignore)]
+async fn test_produce_transcoded_compression() {
+    let test_path = temp_dir().join("produce_transcoded_compression");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce";
+    let mut test = Replica::new((topic, 0), 5001, vec![5001]);
+    test.compression_type = CompressionAlgorithm::Gzip;
+    test.storage = Some(TopicStorageConfig {
+        transcode: true,
+        ..Default::default()
+    });
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let records_per_request = 9;
+    let records = create_filter_raw_records(records_per_request);
+
+    let mut produce_request = DefaultProduceRequest {
+        ..Default::default()
+    };
+
+    let partition_produce = DefaultPartitionRequest {
+        partition_index: 0,
+        records,
+    };
+    let topic_produce_request = TopicProduceData {
+        name: topic.to_owned(),
+        partitions: vec![partition_produce],
+        ..Default::default()
+    };
+
+    produce_request.topics.push(topic_produce_request);
+
+    let produce_response = client_socket
+        .send_and_receive(RequestMessage::new_request(produce_request))
+        .await
+        .expect("send offset");
+
+    // uncompressed batch is accepted and stored compressed with the codec of the topic
+    assert_eq!(produce_response.responses.len(), 1);
+    assert_eq!(produce_response.responses[0].partitions.len(), 1);
+    assert_eq!(
+        produce_response.responses[0].partitions[0].error_code,
+        ErrorCode::None
+    );
+
+    let slice = replica
+        .read_records(0i64, u32::MAX, Isolation::ReadUncommitted)
+        .await
+        .expect("read records");
+    let file_slice = slice.file_slice.expect("file slice");
+    let batches: Vec<_> = FileBatchIterator::from_raw_slice(file_slice)
+        .map(|batch| batch.expect("batch"))
+        .collect();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(
+        batches[0].batch.get_compression().expect("compression"),
+        Compression::Gzip
+    );
+    assert_eq!(batches[0].batch.records_len(), records_per_request as usize);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
 use crate::replication::test::TestConfig;
 use crate::services::create_internal_server;
 
@@ -3091,3 +3091,236 @@ async fn test_stream_fetch_sends_topic_delete_error_on_topic_delete() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_record_headers() {
+    use fluvio_spu_schema::{COMMON_VERSION, RECORD_HEADERS_API_VERSION};
+
+    let test_path = temp_dir().join("test_stream_fetch_record_headers");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "headers".to_owned();
+    let test = Replica::new((topic.clone(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let mut record = Record::new(TEST_RECORD);
+    record.add_header("content-type", "text/plain");
+    let mut records = RecordSet::default().add(
+        Batch::from(vec![record])
+            .try_into()
+            .expect("converted from memory records to raw"),
+    );
+    replica
+        .write_record_set(&mut records, ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    for (version, expected_headers) in [(RECORD_HEADERS_API_VERSION - 1, 0), (COMMON_VERSION, 1)] {
+        let stream_request = DefaultStreamFetchRequest::builder()
+            .topic(topic.clone())
+            .max_bytes(1000)
+            .build()
+            .expect("request");
+
+        let mut stream = client_socket
+            .create_stream(RequestMessage::new_request(stream_request), version)
+            .await
+            .expect("create stream");
+
+        let response = stream.next().await.expect("first").expect("response");
+        let partition = &response.partition;
+        assert_eq!(partition.error_code, ErrorCode::None);
+        assert_eq!(partition.records.batches.len(), 1);
+        let batch = &partition.records.batches[0];
+        assert_eq!(batch.base_offset, 0);
+        let records = batch.memory_records().expect("records");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].value().as_ref(), TEST_RECORD);
+        assert_eq!(records[0].headers().len(), expected_headers);
+    }
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_smartmodule_record_headers() {
+    use fluvio_spu_schema::{COMMON_VERSION, RECORD_HEADERS_API_VERSION};
+
+    let test_path = temp_dir().join("test_stream_fetch_smartmodule_record_headers");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "smartmodule-headers".to_owned();
+    let test = Replica::new((topic.clone(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    // kept by the filter
+    let mut record = Record::new(RecordData::from("apple"));
+    record.add_header("content-type", "text/plain");
+    let mut records = RecordSet::default().add(
+        Batch::from(vec![record])
+            .try_into()
+            .expect("converted from memory records to raw"),
+    );
+    replica
+        .write_record_set(&mut records, ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    for (version, expected_headers) in [(RECORD_HEADERS_API_VERSION - 1, 0), (COMMON_VERSION, 1)] {
+        let smartmodule = SmartModuleInvocation {
+            wasm: SmartModuleInvocationWasm::AdHoc(zip(read_wasm_module(FLUVIO_WASM_FILTER))),
+            kind: SmartModuleKind::Filter,
+            ..Default::default()
+        };
+        let stream_request = DefaultStreamFetchRequest::builder()
+            .topic(topic.clone())
+            .max_bytes(1000)
+            .smartmodules(vec![smartmodule])
+            .build()
+            .expect("request");
+
+        let mut stream = client_socket
+            .create_stream(RequestMessage::new_request(stream_request), version)
+            .await
+            .expect("create stream");
+
+        let response = stream.next().await.expect("first").expect("response");
+        let partition = &response.partition;
+        assert_eq!(partition.error_code, ErrorCode::None);
+        assert_eq!(partition.records.batches.len(), 1);
+        let records = partition.records.batches[0]
+            .memory_records()
+            .expect("records");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].value().as_ref(), "apple".as_bytes());
+        assert_eq!(records[0].headers().len(), expected_headers);
+    }
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_fetch_record_headers() {
+    use fluvio_spu_schema::{
+        COMMON_VERSION, RECORD_HEADERS_API_VERSION,
+        fetch::{FetchableTopic, FetchPartition},
+    };
+
+    let test_path = temp_dir().join("test_fetch_record_headers");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "fetch-headers".to_owned();
+    let test = Replica::new((topic.clone(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let mut record = Record::new(TEST_RECORD);
+    record.add_header("content-type", "text/plain");
+    let mut records = RecordSet::default().add(
+        Batch::from(vec![record])
+            .try_into()
+            .expect("converted from memory records to raw"),
+    );
+    replica
+        .write_record_set(&mut records, ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    for (version, expected_headers) in [(RECORD_HEADERS_API_VERSION - 1, 0), (COMMON_VERSION, 1)] {
+        let fetch_request = DefaultFetchRequest {
+            max_bytes: 1000,
+            topics: vec![FetchableTopic {
+                name: topic.clone(),
+                fetch_partitions: vec![FetchPartition {
+                    max_bytes: 1000,
+                    ..Default::default()
+                }],
+            }],
+            ..Default::default()
+        };
+        let mut request = RequestMessage::new_request(fetch_request);
+        request.get_mut_header().set_api_version(version);
+
+        let response = client_socket
+            .send_and_receive(request)
+            .await
+            .expect("fetch");
+        let partition = response.find_partition(&topic, 0).expect("partition");
+        assert_eq!(partition.error_code, ErrorCode::None);
+        assert_eq!(partition.records.batches.len(), 1);
+        let records = partition.records.batches[0].records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].value().as_ref(), TEST_RECORD);
+        assert_eq!(records[0].headers().len(), expected_headers);
+    }
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
@@ -1,11 +1,11 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::io::Error as IoError;
 
 use anyhow::Error;
-use tracing::{instrument, debug, trace};
+use tracing::{instrument, debug, debug_span, trace};
 
 use fluvio_compression::{Compression, CompressionError};
-use fluvio_protocol::record::{RecordSet, RawRecords};
+use fluvio_protocol::record::{RecordSet, RawRecords, Record, TraceContext};
 use fluvio_protocol::Encoder;
 use fluvio_protocol::{
     record::{Batch, MemoryRecords, Offset},
@@ -71,10 +71,12 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
         );
         let output = sm_chain_instance.process(input, metric)?;
 
-        debug!(smartmodule_execution_time = %now.elapsed().as_millis());
+        let elapsed = now.elapsed();
+        debug!(smartmodule_execution_time = %elapsed.as_millis());
 
         let maybe_error = output.error;
         let mut records = output.successes;
+        continue_traces(&mut records, elapsed);
 
         trace!("smartmodule processed records: {:#?}", records);
 
@@ -141,6 +143,47 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
     Ok((smartmodule_batch, None))
 }
 
+/// Add a span of the chain to the traces of the records, which become the parent of the
+/// consumer spans. Records created by array_map or aggregate SmartModules have no headers,
+/// their trace ends at the producer.
+fn continue_traces(records: &mut [Record], elapsed: Duration) {
+    // (parent, span, records)
+    let mut spans: Vec<(TraceContext, TraceContext, usize)> = vec![];
+    for record in records.iter_mut() {
+        let Some(parent) = record.trace_context() else {
+            continue;
+        };
+        let span = match spans.iter_mut().find(|(known, _, _)| *known == parent) {
+            Some((_, span, count)) => {
+                *count += 1;
+                *span
+            }
+            None => {
+                let span = parent.child();
+                spans.push((parent, span, 1));
+                span
+            }
+        };
+        record.set_trace_context(&span);
+    }
+
+    for (parent, span, count) in spans {
+        debug_span!(
+            "smartmodule_chain",
+            trace_id = %span.trace_id_hex(),
+            span_id = %span.span_id_hex(),
+            parent_id = %parent.span_id_hex(),
+        )
+        .in_scope(|| {
+            debug!(
+                records = count,
+                elapsed_ms = elapsed.as_millis() as u64,
+                "smartmodule chain traced"
+            )
+        });
+    }
+}
+
 fn set_compression(
     input_batch: &impl SmartModuleInputBatch,
     smartmodule_batch: &mut Batch<MemoryRecords>,
@@ -152,3 +195,39 @@ fn set_compression(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_continue_traces() {
+        let first = TraceContext::new_root();
+        let second = TraceContext::new_root();
+        let mut records: Vec<Record> = [Some(first), None, Some(second), Some(first)]
+            .into_iter()
+            .map(|context| {
+                let mut record = Record::new("value");
+                if let Some(context) = context {
+                    record.set_trace_context(&context);
+                }
+                record
+            })
+            .collect();
+
+        continue_traces(&mut records, Duration::ZERO);
+
+        let contexts: Vec<_> = records
+            .iter()
+            .map(|record| record.trace_context())
+            .collect();
+        assert_eq!(contexts[1], None);
+        let span = contexts[0].expect("span");
+        assert_eq!(span.trace_id(), first.trace_id());
+        assert_ne!(span.span_id(), first.span_id());
+        assert_eq!(contexts[3], Some(span));
+        assert_eq!(contexts[2].expect("span").trace_id(), second.trace_id());
+        assert_ne!(contexts[2], Some(span));
+    }
+}
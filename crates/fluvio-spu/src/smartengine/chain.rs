@@ -1,14 +1,22 @@
 #[cfg(feature = "smartengine")]
 use tracing::{debug, error};
+use std::sync::Arc;
+
 use fluvio_protocol::link::ErrorCode;
 use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 
 #[cfg(feature = "smartengine")]
-use fluvio_smartengine::{EngineError, SmartModuleConfig, SmartModuleInitialData};
+use fluvio_smartengine::{
+    EngineError, KeyFilter, Projection, SmartModuleConfig, SmartModuleInitialData,
+};
 
 #[cfg(feature = "smartengine")]
-use fluvio_spu_schema::server::smartmodule::{SmartModuleContextData, SmartModuleKind};
+use fluvio_spu_schema::server::smartmodule::{
+    SmartModuleContextData, SmartModuleKind, KEY_FILTER_EQUALS_PARAM, KEY_FILTER_REGEX_PARAM,
+    KEY_FILTER_SMARTMODULE, PROJECTION_SMARTMODULE,
+};
 
+use crate::smartengine::LookupState;
 use crate::smartengine::SmartModuleChainBuilder;
 use crate::smartengine::SmartEngine;
 use crate::smartengine::SmartModuleChainInstance;
@@ -17,6 +25,7 @@ use crate::smartengine::SmartModuleChainInstance;
 pub(crate) fn build_chain(
     mut _chain_builder: SmartModuleChainBuilder,
     _invocations: Vec<SmartModuleInvocation>,
+    _lookups: Vec<Option<Arc<LookupState>>>,
     _version: i16,
     _engine: SmartEngine,
 ) -> Result<SmartModuleChainInstance, ErrorCode> {
@@ -28,10 +37,25 @@ pub(crate) fn build_chain(
 pub(crate) fn build_chain(
     mut chain_builder: SmartModuleChainBuilder,
     invocations: Vec<SmartModuleInvocation>,
+    lookups: Vec<Option<Arc<LookupState>>>,
     version: i16,
     engine: SmartEngine,
 ) -> Result<SmartModuleChainInstance, ErrorCode> {
-    for invocation in invocations {
+    for (invocation, lookup) in invocations.into_iter().zip(lookups) {
+        if invocation.is_key_filter() {
+            chain_builder.add_key_filter(key_filter(&invocation)?, version);
+            continue;
+        }
+        if invocation.is_projection() {
+            let projection = Projection::new(invocation.projection_fields()).map_err(|err| {
+                ErrorCode::SmartModuleInvalid {
+                    error: err.to_string(),
+                    name: Some(PROJECTION_SMARTMODULE.to_owned()),
+                }
+            })?;
+            chain_builder.set_projection(projection);
+            continue;
+        }
         let raw = invocation
             .wasm
             .into_raw()
@@ -53,6 +77,7 @@ pub(crate) fn build_chain(
         };
 
         let lookback = invocation.params.lookback().map(Into::into);
+        let dead_letter = invocation.dead_letter_topic().map(str::to_owned);
 
         debug!("param: {:#?}", invocation.params);
         chain_builder.add_smart_module(
@@ -60,6 +85,8 @@ pub(crate) fn build_chain(
                 .params(invocation.params)
                 .version(version)
                 .lookback(lookback)
+                .lookup(lookup)
+                .dead_letter(dead_letter)
                 .initial_data(initial_data)
                 .build()
                 .map_err(|err| ErrorCode::SmartModuleInvalid {
@@ -86,3 +113,20 @@ pub(crate) fn build_chain(
     })?;
     Ok(chain)
 }
+
+#[cfg(feature = "smartengine")]
+fn key_filter(invocation: &SmartModuleInvocation) -> Result<KeyFilter, ErrorCode> {
+    let invalid = |error: String| ErrorCode::SmartModuleInvalid {
+        error,
+        name: Some(KEY_FILTER_SMARTMODULE.to_owned()),
+    };
+    if let Some(key) = invocation.params.get(KEY_FILTER_EQUALS_PARAM) {
+        Ok(KeyFilter::equals(key.as_bytes()))
+    } else if let Some(regex) = invocation.params.get(KEY_FILTER_REGEX_PARAM) {
+        KeyFilter::regex(regex).map_err(|err| invalid(err.to_string()))
+    } else {
+        Err(invalid(format!(
+            "expected `{KEY_FILTER_REGEX_PARAM}` or `{KEY_FILTER_EQUALS_PARAM}` parameter"
+        )))
+    }
+}
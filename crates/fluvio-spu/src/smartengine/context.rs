@@ -17,6 +17,7 @@ use crate::replication::leader::LeaderReplicaState;
 
 use crate::smartengine::chain;
 use crate::smartengine::Lookback;
+use crate::smartengine::LookupState;
 use crate::smartengine::SmartModuleChainBuilder;
 use crate::smartengine::SmartModuleChainInstance;
 use crate::smartengine::Version;
@@ -70,8 +71,11 @@ impl SmartModuleContext {
         }
 
         let mut fetched_invocations = Vec::with_capacity(invocations.len());
+        let mut lookups = Vec::with_capacity(invocations.len());
         for invocation in invocations {
-            fetched_invocations.push(resolve_invocation(invocation, ctx)?)
+            let invocation = resolve_invocation(invocation, ctx)?;
+            lookups.push(resolve_lookup(&invocation, ctx).await?);
+            fetched_invocations.push(invocation);
         }
         let mut chain_builder = SmartModuleChainBuilder::default();
         chain_builder.set_store_memory_limit(ctx.config().smart_engine.store_max_memory);
@@ -79,6 +83,7 @@ impl SmartModuleContext {
         let chain = chain::build_chain(
             chain_builder,
             fetched_invocations,
+            lookups,
             version,
             ctx.smartengine_owned(),
         )?;
@@ -95,6 +100,9 @@ fn resolve_invocation<R: ReplicaStorage>(
     invocation: SmartModuleInvocation,
     ctx: &GlobalContext<R>,
 ) -> Result<SmartModuleInvocation, ErrorCode> {
+    if invocation.is_key_filter() || invocation.is_projection() {
+        return Ok(invocation);
+    }
     if let SmartModuleInvocationWasm::Predefined(name) = invocation.wasm {
         if let Some(smartmodule) = ctx
             .smartmodule_localstore()
@@ -113,6 +121,22 @@ fn resolve_invocation<R: ReplicaStorage>(
     }
 }
 
+#[cfg(feature = "smartengine")]
+async fn resolve_lookup<R: ReplicaStorage>(
+    invocation: &SmartModuleInvocation,
+    ctx: &GlobalContext<R>,
+) -> Result<Option<Arc<LookupState>>, ErrorCode> {
+    ctx.lookup_states().resolve(&invocation.params).await
+}
+
+#[cfg(not(feature = "smartengine"))]
+async fn resolve_lookup<R: ReplicaStorage>(
+    _invocation: &SmartModuleInvocation,
+    _ctx: &GlobalContext<R>,
+) -> Result<Option<Arc<LookupState>>, ErrorCode> {
+    Ok(None)
+}
+
 async fn read_records<R: ReplicaStorage>(
     replica: &LeaderReplicaState<R>,
     lookback: Lookback,
@@ -163,9 +187,14 @@ async fn lookback_last_iterator<R: ReplicaStorage>(
     let batch_iter = FileBatchIterator::from_raw_slice(file_slice);
     let records_iter = FileRecordIterator::new(batch_iter, version);
 
-    Ok(Box::new(records_iter.filter(move |r| match r {
-        Ok(item) => item.offset >= offset,
-        Err(_) => true,
+    // records are read lazily, a slice of a remote segment must stay readable until then
+    let lease = slice.lease;
+    Ok(Box::new(records_iter.filter(move |r| {
+        let _ = &lease;
+        match r {
+            Ok(item) => item.offset >= offset,
+            Err(_) => true,
+        }
     })))
 }
 
@@ -0,0 +1,291 @@
+//!
+//! # SmartModule Lookup States
+//!
+//! Key-value states read by SmartModules with `lookup`, filled from the topic named by
+//! the `lookup-topic` param or the JSON object in the file named by the `lookup-file` param.
+//! A state is loaded once per SPU and shared by the SmartModules naming the same source
+//! until the last of them is dropped.
+//!
+//! Topics may be led by any SPU, so they are read through the SC public endpoint. Once the
+//! records present when the state is loaded are applied, the state follows the new records
+//! of the topic. Records without key are skipped and empty values remove their key, like
+//! the tombstones of compacted topics.
+//!
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_lock::Mutex;
+use futures_util::StreamExt;
+use tokio::select;
+use tracing::{debug, info, warn};
+
+use fluvio::consumer::ConsumerConfigExtBuilder;
+use fluvio::{Fluvio, FluvioConfig, Offset, SmartModuleExtraParams};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_smartengine::LookupState;
+use fluvio_spu_schema::server::smartmodule::{LOOKUP_FILE_PARAM, LOOKUP_TOPIC_PARAM};
+use fluvio_types::PartitionId;
+
+/// how often a topic follower checks that its state is still used
+const UNUSED_STATE_CHECK: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum LookupSource {
+    Topic(String),
+    File(PathBuf),
+}
+
+impl LookupSource {
+    /// None when the SmartModule has no lookup state
+    pub(crate) fn from_params(params: &SmartModuleExtraParams) -> Result<Option<Self>, ErrorCode> {
+        match (
+            params.get(LOOKUP_TOPIC_PARAM),
+            params.get(LOOKUP_FILE_PARAM),
+        ) {
+            (None, None) => Ok(None),
+            (Some(topic), None) => Ok(Some(Self::Topic(topic.clone()))),
+            (None, Some(path)) => Ok(Some(Self::File(path.into()))),
+            (Some(_), Some(_)) => Err(ErrorCode::SmartModuleInvalid {
+                error: format!(
+                    "only one of `{LOOKUP_TOPIC_PARAM}` and `{LOOKUP_FILE_PARAM}` may be set"
+                ),
+                name: None,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for LookupSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Topic(topic) => write!(f, "topic {topic}"),
+            Self::File(path) => write!(f, "file {}", path.display()),
+        }
+    }
+}
+
+pub(crate) struct LookupStates {
+    sc_endpoint: Option<String>,
+    client: Mutex<Option<Fluvio>>,
+    /// held while a state is loaded, so each source is loaded once
+    states: Mutex<HashMap<LookupSource, Weak<LookupState>>>,
+}
+
+impl fmt::Debug for LookupStates {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LookupStates({:?})", self.sc_endpoint)
+    }
+}
+
+impl LookupStates {
+    pub(crate) fn new(sc_public_endpoint: Option<String>) -> Self {
+        Self {
+            sc_endpoint: sc_public_endpoint,
+            client: Mutex::new(None),
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// state of the SmartModule invoked with `params`, loaded unless another SmartModule uses it
+    pub(crate) async fn resolve(
+        &self,
+        params: &SmartModuleExtraParams,
+    ) -> Result<Option<Arc<LookupState>>, ErrorCode> {
+        let Some(source) = LookupSource::from_params(params)? else {
+            return Ok(None);
+        };
+        self.get(&source).await.map(Some).map_err(|err| {
+            ErrorCode::SmartModuleChainInitError(format!(
+                "loading lookup state from {source}: {err}"
+            ))
+        })
+    }
+
+    async fn get(&self, source: &LookupSource) -> Result<Arc<LookupState>> {
+        let mut states = self.states.lock().await;
+        if let Some(state) = states.get(source).and_then(Weak::upgrade) {
+            return Ok(state);
+        }
+        states.retain(|_, state| state.strong_count() > 0);
+
+        let state = match source {
+            LookupSource::File(path) => Arc::new(state_from_json(&std::fs::read_to_string(path)?)?),
+            LookupSource::Topic(topic) => self.load_topic(topic).await?,
+        };
+        info!(%source, entries = state.len(), "lookup state loaded");
+        states.insert(source.clone(), Arc::downgrade(&state));
+        Ok(state)
+    }
+
+    /// applies the records of `topic` then follows it while the state is used
+    async fn load_topic(&self, topic: &str) -> Result<Arc<LookupState>> {
+        let Some(sc_endpoint) = &self.sc_endpoint else {
+            return Err(anyhow!(
+                "SC public endpoint is not configured, topic {topic} is not reachable"
+            ));
+        };
+
+        let mut connected = self.client.lock().await;
+        let client = match connected.take() {
+            Some(client) => connected.insert(client),
+            None => {
+                debug!(%sc_endpoint, "connecting lookup state consumer");
+                let mut config = FluvioConfig::new(sc_endpoint.clone());
+                config.use_spu_local_address = true;
+                connected.insert(Fluvio::connect_with_config(&config).await?)
+            }
+        };
+
+        let state = Arc::new(LookupState::default());
+        // next offset of each partition once the loaded records are applied
+        let mut loaded: HashMap<PartitionId, i64> = HashMap::new();
+        let mut records = Box::pin(
+            client
+                .consumer_with_config(
+                    ConsumerConfigExtBuilder::default()
+                        .topic(topic)
+                        .offset_start(Offset::beginning())
+                        .disable_continuous(true)
+                        .build()?,
+                )
+                .await?,
+        );
+        while let Some(record) = records.next().await {
+            let record = record?;
+            apply_record(&state, record.key(), record.value());
+            loaded.insert(record.partition(), record.offset() + 1);
+        }
+
+        let updates = client
+            .consumer_with_config(
+                ConsumerConfigExtBuilder::default()
+                    .topic(topic)
+                    .offset_start(Offset::beginning())
+                    .build()?,
+            )
+            .await?;
+        let following = Arc::downgrade(&state);
+        let topic = topic.to_owned();
+        spawn(async move {
+            let mut updates = Box::pin(updates);
+            loop {
+                select! {
+                    record = updates.next() => {
+                        let Some(record) = record else {
+                            warn!(%topic, "lookup state stopped following the topic");
+                            break;
+                        };
+                        let Some(state) = following.upgrade() else {
+                            break;
+                        };
+                        match record {
+                            // the loaded records are streamed again first
+                            Ok(record) if record.offset() < loaded.get(&record.partition()).copied().unwrap_or_default() => {}
+                            Ok(record) => apply_record(&state, record.key(), record.value()),
+                            Err(err) => {
+                                warn!(%topic, %err, "lookup state stopped following the topic");
+                                break;
+                            }
+                        }
+                    },
+                    _ = sleep(UNUSED_STATE_CHECK) => {
+                        if following.strong_count() == 0 {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!(%topic, "lookup state follower stopped");
+        });
+
+        Ok(state)
+    }
+}
+
+fn apply_record(state: &LookupState, key: Option<&[u8]>, value: &[u8]) {
+    let Some(key) = key else {
+        return;
+    };
+    if value.is_empty() {
+        state.remove(key);
+    } else {
+        state.insert(key.to_vec(), value.to_vec());
+    }
+}
+
+/// string values are kept as their text, other values as their JSON
+fn state_from_json(content: &str) -> Result<LookupState> {
+    let serde_json::Value::Object(entries) = serde_json::from_str(content)? else {
+        return Err(anyhow!("lookup file must hold a JSON object"));
+    };
+    Ok(LookupState::new(entries.into_iter().map(|(key, value)| {
+        let value = match value {
+            serde_json::Value::String(value) => value.into_bytes(),
+            value => value.to_string().into_bytes(),
+        };
+        (key.into_bytes(), value)
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn params(entries: &[(&str, &str)]) -> SmartModuleExtraParams {
+        let params: BTreeMap<String, String> = entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        params.into()
+    }
+
+    #[test]
+    fn test_lookup_source_from_params() {
+        assert_eq!(
+            LookupSource::from_params(&params(&[("key", "a")])),
+            Ok(None)
+        );
+        assert_eq!(
+            LookupSource::from_params(&params(&[(LOOKUP_TOPIC_PARAM, "accounts")])),
+            Ok(Some(LookupSource::Topic("accounts".to_owned())))
+        );
+        assert_eq!(
+            LookupSource::from_params(&params(&[(LOOKUP_FILE_PARAM, "/etc/tiers.json")])),
+            Ok(Some(LookupSource::File("/etc/tiers.json".into())))
+        );
+        assert!(LookupSource::from_params(&params(&[
+            (LOOKUP_TOPIC_PARAM, "accounts"),
+            (LOOKUP_FILE_PARAM, "/etc/tiers.json")
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn test_state_from_json() {
+        let state =
+            state_from_json(r#"{"user-1": "gold", "user-2": {"tier": "free"}}"#).expect("state");
+        assert_eq!(state.get(b"user-1"), Some(b"gold".to_vec()));
+        assert_eq!(state.get(b"user-2"), Some(br#"{"tier":"free"}"#.to_vec()));
+        assert!(state_from_json("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_apply_record() {
+        let state = LookupState::default();
+        apply_record(&state, Some(b"user-1"), b"gold");
+        apply_record(&state, None, b"ignored");
+        assert_eq!(state.len(), 1);
+
+        apply_record(&state, Some(b"user-1"), b"");
+        assert!(state.is_empty());
+    }
+}
@@ -3,19 +3,22 @@ use std::collections::BTreeMap;
 use fluvio::{
     SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind, SmartModuleExtraParams,
 };
-use fluvio_controlplane_metadata::topic::Deduplication;
+use fluvio_controlplane_metadata::topic::{Deduplication, Validation};
 use fluvio_protocol::link::ErrorCode;
 
 pub(crate) mod batch;
 pub(crate) mod file_batch;
 pub(crate) mod produce_batch;
 pub(crate) mod context;
+pub(crate) mod pool;
+#[cfg(feature = "smartengine")]
+pub(crate) mod lookup;
 mod chain;
 
 #[cfg(feature = "smartengine")]
 pub(crate) use fluvio_smartengine::{
-    EngineError, Lookback, SmartModuleChainBuilder, metrics::SmartModuleChainMetrics, SmartEngine,
-    SmartModuleChainInstance, Version,
+    DeadLetter, EngineError, Lookback, LookupState, SmartModuleChainBuilder,
+    metrics::SmartModuleChainMetrics, SmartEngine, SmartModuleChainInstance, Version,
 };
 
 // Stub structures to support a null smartengine config
@@ -31,6 +34,7 @@ mod null_smartengine {
     use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
     use fluvio_smartmodule::dataplane::smartmodule::SmartModuleOutput;
     use fluvio_smartmodule::Record;
+    use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
 
     // refactor to use more widely as a "flow" metric?
     // hack copy of smartmodule chain metrics
@@ -40,6 +44,8 @@ mod null_smartengine {
         records_out: AtomicU64,
         invocation_count: AtomicU64,
         fuel_used: AtomicU64,
+        #[serde(default)]
+        execution_micros: AtomicU64,
     }
 
     #[allow(dead_code)]
@@ -71,6 +77,10 @@ mod null_smartengine {
         pub fn invocation_count(&self) -> u64 {
             self.invocation_count.load(Ordering::SeqCst)
         }
+
+        pub fn execution_time(&self) -> Duration {
+            Duration::from_micros(self.execution_micros.load(Ordering::SeqCst))
+        }
     }
 
     #[derive(Clone, Debug, Default)]
@@ -92,6 +102,24 @@ mod null_smartengine {
     #[derive(Debug)]
     pub struct SmartModuleChainInstance;
 
+    #[derive(Debug, Default)]
+    pub struct LookupState;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct DeadLetter {
+        pub topic: String,
+        pub error: SmartModuleTransformRuntimeError,
+    }
+
+    impl DeadLetter {
+        pub fn to_record(&self) -> Record {
+            let mut record = Record::new(self.error.record_value.clone());
+            record.key.clone_from(&self.error.record_key);
+            record
+        }
+    }
+
     impl SmartModuleChainInstance {
         pub async fn look_back<F, R>(
             &mut self,
@@ -118,6 +146,10 @@ mod null_smartengine {
             let out = SmartModuleOutput::new(records);
             Ok(out)
         }
+
+        pub fn take_dead_letters(&mut self) -> Vec<DeadLetter> {
+            Vec::new()
+        }
     }
 
     pub type Version = i16;
@@ -171,6 +203,15 @@ pub(crate) fn dedup_to_invocation(dedup: &Deduplication) -> SmartModuleInvocatio
     }
 }
 
+/// validation modules are filters: records filtered out or failing are invalid
+pub(crate) fn validation_to_invocation(validation: &Validation) -> SmartModuleInvocation {
+    SmartModuleInvocation {
+        wasm: SmartModuleInvocationWasm::Predefined(validation.transform.uses.clone()),
+        kind: SmartModuleKind::Filter,
+        params: SmartModuleExtraParams::new(validation.transform.with.clone(), None),
+    }
+}
+
 pub(crate) fn map_engine_error(err: &EngineError) -> ErrorCode {
     match err {
         EngineError::UnknownSmartModule => ErrorCode::Other("Unknown SmartModule type".to_string()),
@@ -190,7 +231,7 @@ pub(crate) fn map_engine_error(err: &EngineError) -> ErrorCode {
 mod tests {
     use std::time::Duration;
 
-    use fluvio_controlplane_metadata::topic::{Bounds, Filter, Transform};
+    use fluvio_controlplane_metadata::topic::{Bounds, Filter, OnInvalidRecord, Transform};
 
     use super::*;
 
@@ -225,4 +266,26 @@ mod tests {
             Some(&"param_value".to_string())
         );
     }
+
+    #[test]
+    fn test_validation_to_inv() {
+        //when
+        let validation = Validation {
+            transform: Transform {
+                uses: "contract@0.1.0".to_string(),
+                with: BTreeMap::from([("schema".to_string(), "orders".to_string())]),
+            },
+            on_invalid: OnInvalidRecord::Reject,
+        };
+        let inv = validation_to_invocation(&validation);
+
+        //then
+        assert!(matches!(
+            inv.wasm,
+            SmartModuleInvocationWasm::Predefined(str) if str.eq("contract@0.1.0")
+        ));
+        assert!(matches!(inv.kind, SmartModuleKind::Filter));
+        assert_eq!(inv.params.get("schema"), Some(&"orders".to_string()));
+        assert!(inv.params.lookback().is_none());
+    }
 }
@@ -0,0 +1,107 @@
+//!
+//! # SmartModule Worker Pool
+//!
+//! SmartModules of fetch and produce requests run on dedicated threads instead of the
+//! async executor, so a CPU heavy transform doesn't stall the requests sharing its
+//! executor thread. Jobs wait in a bounded FIFO queue, a full queue holds back the
+//! submitting stream. Each stream waits for its job before submitting the next, so a
+//! stream has at most one job queued and streams are served in turn.
+//!
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::thread;
+
+use anyhow::{anyhow, Result};
+use async_channel::{bounded, Receiver, Sender};
+use tracing::{debug, error};
+
+/// queued jobs per worker thread
+const QUEUE_PER_WORKER: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+#[derive(Debug, Clone)]
+pub(crate) struct SmartModulePool {
+    /// `None` when SmartModules run inline on the executor
+    jobs: Option<Sender<Job>>,
+}
+
+impl SmartModulePool {
+    /// start `threads` workers, none runs SmartModules inline
+    pub(crate) fn new(threads: usize) -> Self {
+        if threads == 0 {
+            return Self { jobs: None };
+        }
+        let (sender, receiver) = bounded::<Job>(threads * QUEUE_PER_WORKER);
+        for id in 0..threads {
+            let receiver = receiver.clone();
+            if let Err(err) = thread::Builder::new()
+                .name(format!("smartmodule-{id}"))
+                .spawn(move || worker_loop(receiver))
+            {
+                error!(%err, "failed to start smartmodule worker, running inline");
+                return Self { jobs: None };
+            }
+        }
+        debug!(threads, "started smartmodule workers");
+        Self { jobs: Some(sender) }
+    }
+
+    /// run `job` on a worker and wait for its result
+    pub(crate) async fn run<F, T>(&self, job: F) -> Result<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let Some(jobs) = &self.jobs else {
+            return Ok(job());
+        };
+        let (result_sender, result) = bounded(1);
+        jobs.send(Box::new(move || {
+            let _ = result_sender.send_blocking(job());
+        }))
+        .await
+        .map_err(|_| anyhow!("smartmodule workers stopped"))?;
+        result
+            .recv()
+            .await
+            .map_err(|_| anyhow!("smartmodule worker failed"))
+    }
+}
+
+fn worker_loop(receiver: Receiver<Job>) {
+    while let Ok(job) = receiver.recv_blocking() {
+        // the result sender is dropped with the job, the waiting stream gets an error
+        if catch_unwind(AssertUnwindSafe(job)).is_err() {
+            error!("smartmodule job panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_run_on_workers() {
+        let pool = SmartModulePool::new(2);
+        let name = pool
+            .run(|| thread::current().name().map(str::to_owned))
+            .await
+            .expect("run");
+        assert!(name.is_some_and(|name| name.starts_with("smartmodule-")));
+
+        assert!(pool.run(|| panic!("bad module")).await.is_err());
+        assert_eq!(pool.run(|| 1 + 1).await.expect("run after panic"), 2);
+
+        let inline = SmartModulePool::new(0);
+        let current = thread::current().id();
+        assert_eq!(
+            inline
+                .run(move || thread::current().id() == current)
+                .await
+                .ok(),
+            Some(true)
+        );
+    }
+}
@@ -5,11 +5,14 @@ use fluvio_storage::FileReplica;
 
 use crate::config::{SpuConfig, SpuOpt};
 use crate::services::auth::SpuAuthGlobalContext;
+use crate::services::auth::acl::AclAuthorization;
 use crate::services::create_internal_server;
 use crate::services::public::create_public_server;
 use crate::core::DefaultSharedGlobalContext;
 use crate::core::GlobalContext;
 use crate::control_plane::ScDispatcher;
+use crate::kv::consumer::start_offset_expiry;
+use crate::core::disk::start_disk_monitor;
 
 type FileReplicaContext = GlobalContext<FileReplica>;
 
@@ -24,13 +27,35 @@ pub fn main_loop(opt: SpuOpt) {
     use fluvio_future::task::run_block_on;
     use fluvio_future::timer::sleep;
 
-    use crate::monitoring::init_monitoring;
+    use crate::monitoring::{init_metrics_server, init_monitoring};
+
+    let opt = opt.load_config_file_or_exit();
+    opt.configure_executor();
 
     // parse configuration (program exits on error)
+    let validate_only = opt.validate_config;
     let (spu_config, tls_acceptor_option) = opt.process_spu_cli_or_exit();
 
+    if validate_only {
+        println!("configuration of spu {} is valid", spu_config.id);
+        return;
+    }
+
     println!("starting spu server (id:{})", spu_config.id);
 
+    // ephemeral replicas are never recovered, what a previous run left on tmpfs is stale
+    let ephemeral_dir = spu_config.ephemeral_replicas_dir();
+    if ephemeral_dir.exists() {
+        if let Err(err) = std::fs::remove_dir_all(&ephemeral_dir) {
+            eprintln!(
+                "unable to remove ephemeral replicas in {}: {err}",
+                ephemeral_dir.display()
+            );
+            std::process::exit(-1);
+        }
+        info!(dir = %ephemeral_dir.display(), "removed ephemeral replicas of previous run");
+    }
+
     sysinfo::set_open_files_limit(0);
     let mut sys = System::new_all();
     sys.refresh_all();
@@ -47,8 +72,10 @@ pub fn main_loop(opt: SpuOpt) {
     run_block_on(async move {
         let ctx = create_services(spu_config.clone(), true, true);
 
+        init_metrics_server(ctx.clone()).await;
         init_monitoring(ctx);
 
+        mesh::start_if(&spu_config).await;
         if let Some(tls_config) = tls_acceptor_option {
             proxy::start_proxy(spu_config, tls_config).await;
         }
@@ -72,22 +99,41 @@ pub fn create_services(
 
     let public_ep_addr = ctx.config().public_socket_addr().to_owned();
     let private_ep_addr = ctx.config().private_socket_addr().to_owned();
+    let tcp = ctx.config().tcp.clone();
 
     if public {
-        let authorization = Arc::new(RootAuthorization::new());
-        let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
-        let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
-        pub_server.run();
+        if let Some(acl) = ctx.config().acl.clone() {
+            let authorization = Arc::new(AclAuthorization::new(
+                ctx.acls_localstore_owned(),
+                acl.super_users,
+            ));
+            let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
+            let pub_server =
+                create_public_server(public_ep_addr, auth_global_ctx).with_tcp_config(tcp.clone());
+            pub_server.run();
+        } else {
+            let authorization = Arc::new(RootAuthorization::new());
+            let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
+            let pub_server =
+                create_public_server(public_ep_addr, auth_global_ctx).with_tcp_config(tcp.clone());
+            pub_server.run();
+        }
     };
 
     if internal {
-        let priv_server = create_internal_server(private_ep_addr, ctx.clone());
+        let priv_server = create_internal_server(private_ep_addr, ctx.clone()).with_tcp_config(tcp);
         priv_server.run();
     };
 
     let sc_dispatcher = ScDispatcher::new(ctx.clone());
     sc_dispatcher.run();
 
+    if let Some(retention) = ctx.config().consumer_offset_retention {
+        start_offset_expiry(ctx.clone(), retention);
+    }
+
+    start_disk_monitor(ctx.clone());
+
     ctx
 }
 
@@ -97,18 +143,25 @@ mod proxy {
     use tracing::info;
 
     use flv_util::print_cli_err;
-    use fluvio_future::openssl::TlsAcceptor;
-    use flv_tls_proxy::start as proxy_start;
+    use fluvio_auth::tls_proxy::start_tls_proxy;
+    use fluvio_auth::x509::X509Authenticator;
+    use flv_tls_proxy::authenticator::Authenticator;
+    use fluvio_socket::{ReloadableTlsAcceptor, TLS_RELOAD_INTERVAL};
 
     use crate::config::SpuConfig;
 
-    pub async fn start_proxy(config: SpuConfig, acceptor: (TlsAcceptor, String)) {
+    pub async fn start_proxy(config: SpuConfig, acceptor: (ReloadableTlsAcceptor, String)) {
         let (tls_acceptor, proxy_addr) = acceptor;
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        //TODO: add X509Authenticator
-        if let Err(err) = proxy_start(&proxy_addr, tls_acceptor, target).await {
+        tls_acceptor.watch(TLS_RELOAD_INTERVAL);
+        let authenticator = config.x509_auth_scopes.map(|x509_auth_scopes| {
+            Box::new(X509Authenticator::new(&x509_auth_scopes)) as Box<dyn Authenticator>
+        });
+        let result = start_tls_proxy(&proxy_addr, tls_acceptor, target, authenticator).await;
+
+        if let Err(err) = result {
             print_cli_err!(err);
             process::exit(-1);
         } else {
@@ -117,3 +170,32 @@ mod proxy {
         }
     }
 }
+
+mod mesh {
+    use std::process;
+    use tracing::info;
+
+    use flv_util::print_cli_err;
+    use fluvio_auth::mesh::{start_mesh_proxy, MeshAuthenticator};
+
+    use crate::config::SpuConfig;
+
+    pub async fn start_if(config: &SpuConfig) {
+        let Some(proxy_addr) = &config.mesh_proxy_endpoint else {
+            return;
+        };
+        info!("starting mesh identity proxy: {}", proxy_addr);
+        let result = match MeshAuthenticator::new(config.x509_auth_scopes.as_deref()) {
+            Ok(authenticator) => {
+                let authenticator =
+                    authenticator.with_trusted_peers(config.mesh_trusted_peers.clone());
+                start_mesh_proxy(proxy_addr, config.public_endpoint.clone(), authenticator).await
+            }
+            Err(err) => Err(std::io::Error::other(err)),
+        };
+        if let Err(err) = result {
+            print_cli_err!(err);
+            process::exit(-1);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+//!
+//! # Block Cache
+//!
+//! Cache for segments whose bytes are fetched with range requests, such as segments
+//! kept in object storage. Objects are read in fixed size blocks; the blocks missing
+//! for a read, and the blocks following it, are requested in parallel so a sequential
+//! consumer pays the request latency once for several batches instead of per batch.
+//! Blocks are evicted least recently used first once the cache exceeds its capacity.
+//!
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Error as IoError;
+use std::sync::{Arc, OnceLock};
+
+use async_lock::Mutex;
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use tracing::{debug, trace};
+
+pub const DEFAULT_BLOCK_SIZE: u64 = 1_048_576; // 1Mb
+pub const DEFAULT_CACHE_CAPACITY: u64 = 268_435_456; // 256Mb
+pub const DEFAULT_READ_AHEAD_BLOCKS: u64 = 4;
+pub const DEFAULT_PARALLEL_REQUESTS: usize = 8;
+
+/// Object readable by byte ranges
+#[allow(clippy::len_without_is_empty)]
+#[async_trait]
+pub trait RangeSource: Send + Sync {
+    /// size of the object in bytes
+    fn len(&self) -> u64;
+
+    /// read `len` bytes at `position`
+    async fn read_range(&self, position: u64, len: u64) -> Result<Bytes, IoError>;
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BlockCacheConfig {
+    pub block_size: u64,
+    /// max bytes of blocks kept in the cache
+    pub capacity: u64,
+    /// blocks fetched after the last block of a read
+    pub read_ahead_blocks: u64,
+    /// max range requests in flight for a read
+    pub parallel_requests: usize,
+}
+
+impl Default for BlockCacheConfig {
+    fn default() -> Self {
+        Self {
+            block_size: DEFAULT_BLOCK_SIZE,
+            capacity: DEFAULT_CACHE_CAPACITY,
+            read_ahead_blocks: DEFAULT_READ_AHEAD_BLOCKS,
+            parallel_requests: DEFAULT_PARALLEL_REQUESTS,
+        }
+    }
+}
+
+type BlockKey = (String, u64);
+
+#[derive(Default)]
+struct Blocks {
+    blocks: HashMap<BlockKey, (Bytes, u64)>,
+    /// keys by last access
+    recent: BTreeMap<u64, BlockKey>,
+    tick: u64,
+    size: u64,
+}
+
+impl Blocks {
+    fn get(&mut self, key: &BlockKey) -> Option<Bytes> {
+        self.tick += 1;
+        let (block, access) = self.blocks.get_mut(key)?;
+        if let Some(key) = self.recent.remove(access) {
+            self.recent.insert(self.tick, key);
+        }
+        *access = self.tick;
+        Some(block.clone())
+    }
+
+    fn insert(&mut self, key: BlockKey, block: Bytes) {
+        self.tick += 1;
+        self.size += block.len() as u64;
+        self.recent.insert(self.tick, key.clone());
+        if let Some((old, access)) = self.blocks.insert(key, (block, self.tick)) {
+            self.size -= old.len() as u64;
+            self.recent.remove(&access);
+        }
+    }
+
+    fn evict_to(&mut self, capacity: u64) {
+        while self.size > capacity {
+            let Some((_, key)) = self.recent.pop_first() else {
+                break;
+            };
+            if let Some((block, _)) = self.blocks.remove(&key) {
+                trace!(object = key.0, block = key.1, "evicting block");
+                self.size -= block.len() as u64;
+            }
+        }
+    }
+}
+
+/// Block cache shared by the readers of remote segments
+pub struct BlockCache {
+    config: BlockCacheConfig,
+    blocks: Mutex<Blocks>,
+}
+
+impl BlockCache {
+    pub fn new(config: BlockCacheConfig) -> Self {
+        Self {
+            config,
+            blocks: Mutex::new(Blocks::default()),
+        }
+    }
+
+    /// cache shared by every replica of the process, created with the config of the first caller
+    pub fn shared(config: BlockCacheConfig) -> Arc<Self> {
+        static SHARED: OnceLock<Arc<BlockCache>> = OnceLock::new();
+        SHARED.get_or_init(|| Arc::new(Self::new(config))).clone()
+    }
+
+    /// bytes of blocks in the cache
+    pub async fn size(&self) -> u64 {
+        self.blocks.lock().await.size
+    }
+
+    /// read `len` bytes at `position` of `object`, fewer at the end of the object
+    pub async fn read<S: RangeSource>(
+        &self,
+        object: &str,
+        source: &S,
+        position: u64,
+        len: u64,
+    ) -> Result<Bytes, IoError> {
+        let object_len = source.len();
+        let end = position.saturating_add(len).min(object_len);
+        if position >= end {
+            return Ok(Bytes::new());
+        }
+        let block_size = self.config.block_size.max(1);
+        let first = position / block_size;
+        let last = (end - 1) / block_size;
+        let read_ahead_end =
+            (last + self.config.read_ahead_blocks).min((object_len - 1) / block_size);
+
+        let mut required = BTreeMap::new();
+        let missing: Vec<u64> = {
+            let mut cached = self.blocks.lock().await;
+            (first..=read_ahead_end)
+                .filter(|index| match cached.get(&(object.to_owned(), *index)) {
+                    Some(block) if *index <= last => {
+                        required.insert(*index, block);
+                        false
+                    }
+                    Some(_) => false,
+                    None => true,
+                })
+                .collect()
+        };
+        if !missing.is_empty() {
+            debug!(object, ?missing, "fetching blocks");
+        }
+
+        let fetched: Vec<(u64, Bytes)> = stream::iter(missing)
+            .map(|index| async move {
+                let start = index * block_size;
+                let block = source
+                    .read_range(start, block_size.min(object_len - start))
+                    .await?;
+                Ok::<_, IoError>((index, block))
+            })
+            .buffer_unordered(self.config.parallel_requests.max(1))
+            .try_collect()
+            .await?;
+
+        let mut cached = self.blocks.lock().await;
+        for (index, block) in fetched {
+            if index <= last {
+                required.insert(index, block.clone());
+            }
+            cached.insert((object.to_owned(), index), block);
+        }
+        cached.evict_to(self.config.capacity);
+        drop(cached);
+
+        let mut out = BytesMut::with_capacity((end - position) as usize);
+        for (index, block) in required {
+            let block_start = index * block_size;
+            let from = position.saturating_sub(block_start).min(block.len() as u64) as usize;
+            let to = (end - block_start).min(block.len() as u64) as usize;
+            out.extend_from_slice(&block[from..to]);
+        }
+        Ok(out.freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    struct MemorySource {
+        data: Vec<u8>,
+        requests: AtomicU64,
+    }
+
+    impl MemorySource {
+        fn new(len: usize) -> Self {
+            Self {
+                data: (0..len).map(|i| i as u8).collect(),
+                requests: AtomicU64::new(0),
+            }
+        }
+
+        fn requests(&self) -> u64 {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl RangeSource for MemorySource {
+        fn len(&self) -> u64 {
+            self.data.len() as u64
+        }
+
+        async fn read_range(&self, position: u64, len: u64) -> Result<Bytes, IoError> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let start = position as usize;
+            Ok(Bytes::copy_from_slice(
+                &self.data[start..start + len as usize],
+            ))
+        }
+    }
+
+    fn config(capacity: u64) -> BlockCacheConfig {
+        BlockCacheConfig {
+            block_size: 10,
+            capacity,
+            read_ahead_blocks: 2,
+            parallel_requests: 4,
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_read_ahead_blocks() {
+        let source = MemorySource::new(95);
+        let cache = BlockCache::new(config(1000));
+
+        let bytes = cache.read("seg-0", &source, 5, 10).await.expect("read");
+        assert_eq!(bytes.as_ref(), &source.data[5..15]);
+        // blocks 0 and 1 are read, 2 and 3 are read ahead
+        assert_eq!(source.requests(), 4);
+
+        let bytes = cache.read("seg-0", &source, 15, 20).await.expect("read");
+        assert_eq!(bytes.as_ref(), &source.data[15..35]);
+        // blocks 1 to 3 are cached, 4 and 5 are read ahead
+        assert_eq!(source.requests(), 6);
+
+        // reads are cut at the end of the object
+        let bytes = cache.read("seg-0", &source, 90, 20).await.expect("read");
+        assert_eq!(bytes.as_ref(), &source.data[90..95]);
+        assert!(cache
+            .read("seg-0", &source, 95, 1)
+            .await
+            .expect("read")
+            .is_empty());
+    }
+
+    #[fluvio_future::test]
+    async fn test_evict_least_recently_used() {
+        let source = MemorySource::new(100);
+        let cache = BlockCache::new(BlockCacheConfig {
+            read_ahead_blocks: 0,
+            ..config(30)
+        });
+
+        for position in [0, 10, 20] {
+            cache
+                .read("seg-0", &source, position, 10)
+                .await
+                .expect("read");
+        }
+        // touch block 0, then block 1 is the least recently used
+        cache.read("seg-0", &source, 0, 10).await.expect("read");
+        cache.read("seg-0", &source, 30, 10).await.expect("read");
+        assert_eq!(cache.size().await, 30);
+        assert_eq!(source.requests(), 4);
+
+        cache.read("seg-0", &source, 0, 10).await.expect("read");
+        assert_eq!(source.requests(), 4);
+        cache.read("seg-0", &source, 10, 10).await.expect("read");
+        assert_eq!(source.requests(), 5);
+    }
+}
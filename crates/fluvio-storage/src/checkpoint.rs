@@ -151,7 +151,9 @@ where
         self.offset = pos;
         self.offset.write_to(&mut contents);
         self.file.write_all(&contents).await?;
-        self.file.sync_all().await?;
+        if !self.option.ephemeral {
+            self.file.sync_all().await?;
+        }
         Ok(())
     }
 }
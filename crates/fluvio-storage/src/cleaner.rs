@@ -1,14 +1,18 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use std::ops::Div;
 use std::ops::Rem;
 
-use tracing::{debug, info, instrument};
+use async_lock::Mutex;
+use tracing::{debug, error, info, instrument};
 
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_types::event::StickyEvent;
 
+use fluvio_protocol::record::Offset;
+
+use crate::compaction::compact_segments;
 use crate::config::{SharedReplicaConfig, StorageConfig};
 use crate::replica::ReplicaSize;
 use crate::segments::SharedSegments;
@@ -16,6 +20,7 @@ use crate::segments::SharedSegments;
 /// Replica cleaner. This is a background task that periodically checks for expired segments and
 /// removes them. It also enforces max partition size by removing first segments if replica size is
 /// exceeded. In the future, this may be done by a central cleaner pool instead of per a replica.
+/// Sealed segments of compacted replicas are compacted as well.
 #[derive(Debug)]
 pub(crate) struct Cleaner {
     config: Arc<StorageConfig>,
@@ -23,6 +28,15 @@ pub(crate) struct Cleaner {
     segments: Arc<SharedSegments>,
     replica_size: Arc<ReplicaSize>,
     end_event: Arc<StickyEvent>,
+    compaction: Mutex<CompactionState>,
+}
+
+/// state of the last compaction, the segments are compacted again when a segment is sealed
+/// or a tombstone can be removed
+#[derive(Debug, Default)]
+struct CompactionState {
+    last_sealed: Option<Offset>,
+    tombstone_expiry: Option<SystemTime>,
 }
 
 impl Cleaner {
@@ -39,6 +53,7 @@ impl Cleaner {
             segments,
             replica_size,
             end_event,
+            compaction: Mutex::new(CompactionState::default()),
         });
 
         let cleaner_ref = cleaner.clone();
@@ -74,6 +89,9 @@ impl Cleaner {
                 _ = sleep(sleep_period) => {
                     self.enforce_size().await;
                     self.enforce_ttl().await;
+                    if self.replica_config.compact {
+                        self.enforce_compaction().await;
+                    }
                 }
             }
         }
@@ -110,6 +128,19 @@ impl Cleaner {
         }
     }
 
+    /// remove the first segment, returns the bytes freed
+    pub(crate) async fn remove_oldest(&self) -> u64 {
+        let oldest = self.segments.read().await.find_first(1);
+        if oldest.is_empty() {
+            return 0;
+        }
+        let before = self.replica_size.get();
+        self.segments.remove_segments(&oldest).await;
+        let read = self.segments.read().await;
+        self.replica_size.store_prev(read.occupied_memory());
+        before.saturating_sub(self.replica_size.get())
+    }
+
     #[instrument(skip(self))]
     async fn enforce_ttl(&self) {
         let retention_secs =
@@ -130,6 +161,28 @@ impl Cleaner {
             self.replica_size.store_prev(read.occupied_memory());
         }
     }
+
+    #[instrument(skip(self))]
+    async fn enforce_compaction(&self) {
+        let mut state = self.compaction.lock().await;
+        let last_sealed = self.segments.read().await.base_offsets().last().copied();
+        let tombstones_expired = state
+            .tombstone_expiry
+            .is_some_and(|expiry| expiry <= SystemTime::now());
+        if last_sealed == state.last_sealed && !tombstones_expired {
+            return;
+        }
+        match compact_segments(&self.replica_config, &self.segments).await {
+            Ok(compaction) => {
+                debug!(?compaction, "segments compacted");
+                state.last_sealed = last_sealed;
+                state.tombstone_expiry = compaction.tombstone_expiry;
+                let read = self.segments.read().await;
+                self.replica_size.store_prev(read.occupied_memory());
+            }
+            Err(err) => error!(%err, "compaction failed"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -200,6 +253,30 @@ mod tests {
         assert_eq!(read.occupied_memory(), replica_size.get());
     }
 
+    #[fluvio_future::test]
+    async fn test_remove_oldest() {
+        //given
+        let config = default_option();
+        let segments = shared_segments("cleaner-remove-oldest", 2, config.clone()).await;
+        let replica_size = Arc::new(ReplicaSize::default());
+        replica_size.store_prev(segments.read().await.occupied_memory());
+        let cleaner = test_cleaner(config, segments.clone(), replica_size.clone());
+        let before = replica_size.get();
+
+        //when
+        let freed = cleaner.remove_oldest().await;
+
+        //then
+        let read = segments.read().await;
+        assert_eq!(read.find_first(10), vec![600]);
+        assert_eq!(read.occupied_memory(), replica_size.get());
+        assert_eq!(freed, before - replica_size.get());
+        drop(read);
+
+        cleaner.remove_oldest().await;
+        assert_eq!(cleaner.remove_oldest().await, 0);
+    }
+
     #[fluvio_future::test]
     async fn test_enforce_ttl() {
         //given
@@ -287,6 +364,7 @@ mod tests {
             segments,
             replica_size,
             end_event: StickyEvent::shared(),
+            compaction: Default::default(),
         }
     }
 }
@@ -0,0 +1,447 @@
+//! Compaction of the sealed segments of compacted replicas
+//!
+//! A record is removed when a later record of the sealed segments has the same key, records
+//! superseded from the active segment are removed once it is sealed. Tombstones, records
+//! with a key and an empty value, are removed along with the records they supersede once
+//! their segment has been sealed longer than the delete retention. Records without a key
+//! and the last record of each segment are kept, so a reloaded segment keeps its end offset.
+//! Batches with a schema id are kept as they are.
+//!
+//! Kept records keep their offsets, a batch losing records is split into batches of
+//! consecutive records. Compacted segments are written in a working directory of the
+//! replica, then replace the files of the segment.
+
+use std::collections::HashMap;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use futures_lite::io::AsyncWriteExt;
+use tracing::{debug, info, instrument};
+
+use fluvio_future::fs::{create_dir_all, remove_dir_all, File};
+use fluvio_protocol::Encoder;
+use fluvio_protocol::record::{
+    Batch, BatchHeader, Offset, RawRecords, Record, Size, BATCH_FILE_HEADER_SIZE, BATCH_HEADER_SIZE,
+};
+
+use crate::batch::FileBatchStream;
+use crate::config::SharedReplicaConfig;
+use crate::mut_index::MutLogIndex;
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::segments::SharedSegments;
+use crate::util::generate_file_name;
+
+/// directory of the replica compacted segments are written to
+const WORK_DIR: &str = ".compaction";
+
+/// Outcome of a compaction of the sealed segments
+#[derive(Debug, Default)]
+pub(crate) struct Compaction {
+    pub(crate) rewritten: usize,
+    pub(crate) removed: usize,
+    /// when the oldest tombstone kept can be removed
+    pub(crate) tombstone_expiry: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+struct SealedSegment {
+    base_offset: Offset,
+    end_offset: Offset,
+    log: PathBuf,
+    sealed_at: SystemTime,
+}
+
+/// Compact the sealed segments of the replica
+#[instrument(skip(option, segments), fields(base_dir = %option.base_dir.display()))]
+pub(crate) async fn compact_segments(
+    option: &Arc<SharedReplicaConfig>,
+    segments: &SharedSegments,
+) -> Result<Compaction> {
+    let now = SystemTime::now();
+    let sealed: Vec<SealedSegment> = segments
+        .read()
+        .await
+        .iter()
+        .map(|segment| SealedSegment {
+            base_offset: segment.get_base_offset(),
+            end_offset: segment.get_end_offset(),
+            log: generate_file_name(
+                &option.base_dir,
+                segment.get_base_offset(),
+                MESSAGE_LOG_EXTENSION,
+            ),
+            sealed_at: now
+                - segment
+                    .get_msg_log()
+                    .modified_time_elapsed()
+                    .unwrap_or_default(),
+        })
+        .collect();
+
+    let mut latest: HashMap<Vec<u8>, Offset> = HashMap::new();
+    for segment in &sealed {
+        for_each_record(&segment.log, |offset, record| {
+            if record.key().is_some() {
+                latest.insert(key_bytes(record).to_vec(), offset);
+            }
+        })
+        .await?;
+    }
+
+    let delete_retention = Duration::from_secs(option.delete_retention_seconds.get() as u64);
+    let work_dir = option.base_dir.join(WORK_DIR);
+    let work_option = Arc::new(option.with_base_dir(work_dir.clone()));
+    let mut compaction = Compaction::default();
+    for segment in &sealed {
+        let retention = Retention {
+            latest: &latest,
+            end_offset: segment.end_offset,
+            tombstones_expired: segment.sealed_at + delete_retention <= now,
+        };
+
+        let mut removed = 0;
+        let mut tombstones = false;
+        for_each_record(&segment.log, |offset, record| {
+            if !retention.keep(offset, record) {
+                removed += 1;
+            } else if is_tombstone(record) && !retention.tombstones_expired {
+                // expired tombstones left are the last record of their segment
+                tombstones = true;
+            }
+        })
+        .await?;
+        if tombstones {
+            let expiry = segment.sealed_at + delete_retention;
+            compaction.tombstone_expiry = Some(
+                compaction
+                    .tombstone_expiry
+                    .map_or(expiry, |current| current.min(expiry)),
+            );
+        }
+        if removed == 0 {
+            continue;
+        }
+
+        if work_dir.exists() {
+            remove_dir_all(&work_dir).await?;
+        }
+        create_dir_all(&work_dir).await?;
+        write_compacted(segment, &retention, work_option.clone()).await?;
+        segments
+            .replace_segment(segment.base_offset, &work_dir, option.clone())
+            .await?;
+        info!(
+            base_offset = segment.base_offset,
+            removed, "segment compacted"
+        );
+        compaction.rewritten += 1;
+        compaction.removed += removed;
+    }
+    if work_dir.exists() {
+        remove_dir_all(&work_dir).await?;
+    }
+    Ok(compaction)
+}
+
+struct Retention<'a> {
+    latest: &'a HashMap<Vec<u8>, Offset>,
+    end_offset: Offset,
+    tombstones_expired: bool,
+}
+
+impl Retention<'_> {
+    fn keep(&self, offset: Offset, record: &Record) -> bool {
+        if offset == self.end_offset - 1 {
+            return true;
+        }
+        if record.key().is_none() {
+            return true;
+        }
+        if self.latest.get(key_bytes(record)) != Some(&offset) {
+            return false;
+        }
+        !(self.tombstones_expired && is_tombstone(record))
+    }
+}
+
+fn is_tombstone(record: &Record) -> bool {
+    record.key().is_some() && record.value().as_ref().is_empty()
+}
+
+fn key_bytes(record: &Record) -> &[u8] {
+    record.key().map(AsRef::as_ref).unwrap_or_default()
+}
+
+/// call `f` with the offset of each record of the log, batches with a schema are skipped
+async fn for_each_record<F>(log: &Path, mut f: F) -> Result<()>
+where
+    F: FnMut(Offset, &Record),
+{
+    let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(log).await?;
+    while let Some(batch_pos) = stream.try_next().await? {
+        let raw = batch_pos.inner();
+        if raw.get_header().has_schema() {
+            continue;
+        }
+        let base_offset = raw.get_base_offset();
+        let batch: Batch = raw.try_into()?;
+        for (relative, record) in batch.records().iter().enumerate() {
+            f(base_offset + relative as Offset, record);
+        }
+    }
+    Ok(())
+}
+
+/// write the kept records of `segment` to the log and index of `option`
+async fn write_compacted(
+    segment: &SealedSegment,
+    retention: &Retention<'_>,
+    option: Arc<SharedReplicaConfig>,
+) -> Result<()> {
+    let base_offset = segment.base_offset;
+    let source = std::fs::File::open(&segment.log)?;
+    let modified = source.metadata()?.modified()?;
+    let log_path = generate_file_name(&option.base_dir, base_offset, MESSAGE_LOG_EXTENSION);
+    let mut log = File::create(&log_path).await?;
+    let mut index = MutLogIndex::create(base_offset, option).await?;
+    let mut position: Size = 0;
+
+    let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&segment.log).await?;
+    while let Some(batch_pos) = stream.try_next().await? {
+        let file_pos = batch_pos.get_pos();
+        let raw = batch_pos.inner();
+        let batch_offset = raw.get_base_offset();
+
+        let len = raw.batch_len as usize + BATCH_FILE_HEADER_SIZE - BATCH_HEADER_SIZE;
+
+        let mut compacted: Vec<(Offset, Vec<u8>)> = Vec::new();
+        let kept = if raw.get_header().has_schema() {
+            None
+        } else {
+            let header = raw.get_header().clone();
+            let batch: Batch = raw.try_into()?;
+            let records = batch.own_records();
+            let keep: Vec<bool> = records
+                .iter()
+                .enumerate()
+                .map(|(relative, record)| retention.keep(batch_offset + relative as Offset, record))
+                .collect();
+            if keep.iter().all(|keep| *keep) {
+                None
+            } else {
+                Some((header, records, keep))
+            }
+        };
+
+        match kept {
+            // unchanged batches are copied as they are
+            None => {
+                let mut bytes = vec![0; len];
+                source.read_exact_at(&mut bytes, file_pos as u64)?;
+                compacted.push((batch_offset, bytes));
+            }
+            Some((header, records, keep)) => {
+                let mut run: Vec<Record> = Vec::new();
+                let mut run_start = 0;
+                for (relative, (record, keep)) in records.into_iter().zip(keep).enumerate() {
+                    if keep {
+                        if run.is_empty() {
+                            run_start = relative;
+                        }
+                        run.push(record);
+                        continue;
+                    }
+                    if !run.is_empty() {
+                        compacted.push(encode_run(
+                            &header,
+                            batch_offset + run_start as Offset,
+                            &mut run,
+                        )?);
+                    }
+                }
+                if !run.is_empty() {
+                    compacted.push(encode_run(
+                        &header,
+                        batch_offset + run_start as Offset,
+                        &mut run,
+                    )?);
+                }
+            }
+        }
+
+        for (offset, bytes) in compacted {
+            index
+                .write_index(
+                    (offset - base_offset) as Size,
+                    position,
+                    bytes.len() as Size,
+                )
+                .await?;
+            log.write_all(&bytes).await?;
+            position += bytes.len() as Size;
+        }
+    }
+    log.flush().await?;
+    log.sync_all().await?;
+    index.shrink().await?;
+    drop(index);
+
+    // retention and the delete horizon go by the time the segment was sealed
+    std::fs::File::options()
+        .write(true)
+        .open(&log_path)?
+        .set_modified(modified)?;
+    debug!(base_offset, position, "compacted segment written");
+    Ok(())
+}
+
+/// batch of consecutive records starting at `base_offset`, with the header of their batch
+fn encode_run(
+    header: &BatchHeader,
+    base_offset: Offset,
+    run: &mut Vec<Record>,
+) -> Result<(Offset, Vec<u8>)> {
+    let mut batch: Batch = Batch::default();
+    *batch.get_mut_header() = header.clone();
+    batch.set_base_offset(base_offset);
+    batch.add_records(run);
+    let batch: Batch<RawRecords> = batch.try_into()?;
+    let mut bytes = Vec::with_capacity(batch.write_size(0));
+    batch.encode(&mut bytes, 0)?;
+    Ok((base_offset, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::record::MemoryRecords;
+
+    use crate::config::ReplicaConfig;
+    use crate::segment::MutableSegment;
+    use crate::segments::SegmentList;
+
+    use super::*;
+
+    fn keyed(key: &str, value: &str) -> Record {
+        Record::new_key_value(key, value)
+    }
+
+    async fn create_segment(
+        option: Arc<SharedReplicaConfig>,
+        base_offset: Offset,
+        batches: Vec<Vec<Record>>,
+    ) -> crate::segment::ReadSegment {
+        let mut segment = MutableSegment::create(base_offset, option)
+            .await
+            .expect("create");
+        for records in batches {
+            let mut batch = Batch::from(records);
+            segment.append_batch(&mut batch).await.expect("append");
+        }
+        segment.convert_to_segment().await.expect("convert")
+    }
+
+    async fn read_log(log: &Path) -> Vec<(Offset, Option<String>, String)> {
+        let mut records = Vec::new();
+        let mut stream: FileBatchStream<MemoryRecords> =
+            FileBatchStream::open(log).await.expect("open");
+        while let Some(batch_pos) = stream.try_next().await.expect("batch") {
+            let batch = batch_pos.inner();
+            let base_offset = batch.get_base_offset();
+            for (relative, record) in batch.records().iter().enumerate() {
+                records.push((
+                    base_offset + relative as Offset,
+                    record.key().map(|key| key.to_string()),
+                    record.value().to_string(),
+                ));
+            }
+        }
+        records
+    }
+
+    #[fluvio_future::test]
+    async fn test_compact_segments() {
+        let rep_dir = temp_dir().join("compaction-segments");
+        ensure_new_dir(&rep_dir).expect("new");
+        let config = ReplicaConfig {
+            base_dir: rep_dir.clone(),
+            compact: true,
+            ..Default::default()
+        };
+        let option = config.shared();
+
+        let segments = SharedSegments::from(SegmentList::new());
+        segments
+            .add_segment(
+                create_segment(
+                    option.clone(),
+                    0,
+                    vec![
+                        vec![keyed("a", "1"), keyed("b", "2"), keyed("c", "3")],
+                        vec![keyed("a", "4")],
+                    ],
+                )
+                .await,
+            )
+            .await;
+        segments
+            .add_segment(
+                create_segment(
+                    option.clone(),
+                    4,
+                    vec![
+                        vec![keyed("b", ""), keyed("d", "6")],
+                        vec![Record::new("unkeyed")],
+                        vec![keyed("a", "8")],
+                    ],
+                )
+                .await,
+            )
+            .await;
+
+        // superseded records of the first segment, the last one is kept
+        let compaction = compact_segments(&option, &segments).await.expect("compact");
+        assert_eq!(compaction.rewritten, 1);
+        assert_eq!(compaction.removed, 2);
+        assert!(compaction.tombstone_expiry.is_some());
+        assert_eq!(
+            read_log(&generate_file_name(&rep_dir, 0, MESSAGE_LOG_EXTENSION)).await,
+            vec![
+                (2, Some("c".to_owned()), "3".to_owned()),
+                (3, Some("a".to_owned()), "4".to_owned()),
+            ]
+        );
+        let slice = segments
+            .find_slice(0, None)
+            .await
+            .expect("find")
+            .expect("slice");
+        assert!(slice.len() > 0);
+
+        let compaction = compact_segments(&option, &segments).await.expect("compact");
+        assert_eq!(compaction.rewritten, 0);
+
+        // tombstones past the delete retention
+        option.delete_retention_seconds.set(0);
+        let compaction = compact_segments(&option, &segments).await.expect("compact");
+        assert_eq!(compaction.rewritten, 1);
+        assert_eq!(compaction.removed, 1);
+        assert!(compaction.tombstone_expiry.is_none());
+        assert_eq!(
+            read_log(&generate_file_name(&rep_dir, 4, MESSAGE_LOG_EXTENSION)).await,
+            vec![
+                (5, Some("d".to_owned()), "6".to_owned()),
+                (6, None, "unkeyed".to_owned()),
+                (7, Some("a".to_owned()), "8".to_owned()),
+            ]
+        );
+        assert_eq!(segments.read().await.len(), 2);
+        assert!(!rep_dir.join(WORK_DIR).exists());
+    }
+}
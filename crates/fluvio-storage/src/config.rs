@@ -11,8 +11,10 @@ use serde::Deserialize;
 use fluvio_controlplane_metadata::topic::CleanupPolicy;
 use fluvio_types::defaults::{
     SPU_LOG_INDEX_MAX_BYTES, SPU_LOG_BASE_DIR, STORAGE_FLUSH_WRITE_COUNT, STORAGE_FLUSH_IDLE_MSEC,
-    STORAGE_MAX_BATCH_SIZE, STORAGE_MAX_REQUEST_SIZE, STORAGE_RETENTION_SECONDS,
-    SPU_PARTITION_MAX_BYTES,
+    STORAGE_MAX_BATCH_SIZE, STORAGE_MAX_REQUEST_SIZE, STORAGE_READ_AHEAD_BYTES,
+    STORAGE_RETENTION_SECONDS, SPU_PARTITION_MAX_BYTES, SPU_EPHEMERAL_LOG_BASE_DIR,
+    SPU_EPHEMERAL_PARTITION_MAX_BYTES, STORAGE_TIER_HOT_WINDOW_SECONDS, STORAGE_TIER_CACHE_BYTES,
+    STORAGE_COMPACT_DELETE_RETENTION_SECONDS,
 };
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_INTERVAL_BYTES;
 use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
@@ -57,6 +59,36 @@ pub struct ReplicaConfig {
     #[builder(default = "default_max_partition_size()")]
     #[serde(default = "default_max_partition_size")]
     pub max_partition_size: Size64,
+    #[builder(default = "default_ephemeral_base_dir()")]
+    #[serde(default = "default_ephemeral_base_dir")]
+    pub ephemeral_base_dir: PathBuf,
+    #[builder(default)]
+    #[serde(default)]
+    pub ephemeral: bool, // if true, data is kept on a memory backed file system and never synced
+    /// bytes paged in ahead of consumers reading previous segments, 0 disables read ahead
+    #[builder(default = "default_read_ahead_bytes()")]
+    #[serde(default = "default_read_ahead_bytes")]
+    pub read_ahead_bytes: Size,
+    /// object storage location sealed segments are offloaded to, like `s3://bucket/prefix`
+    #[builder(default)]
+    #[serde(default)]
+    pub tier: Option<String>,
+    /// seconds offloaded segments are kept on local disk
+    #[builder(default = "default_tier_hot_window_seconds()")]
+    #[serde(default = "default_tier_hot_window_seconds")]
+    pub tier_hot_window_seconds: Size,
+    /// bytes of remote segments kept in the block cache shared by the replicas
+    #[builder(default = "default_tier_cache_bytes()")]
+    #[serde(default = "default_tier_cache_bytes")]
+    pub tier_cache_bytes: Size64,
+    /// keep only the latest record of each key in sealed segments
+    #[builder(default)]
+    #[serde(default)]
+    pub compact: bool,
+    /// seconds tombstones of compacted replicas are kept
+    #[builder(default = "default_delete_retention_seconds()")]
+    #[serde(default = "default_delete_retention_seconds")]
+    pub delete_retention_seconds: Size,
 }
 
 impl fmt::Display for ReplicaConfig {
@@ -72,6 +104,11 @@ impl ReplicaStorageConfig for ReplicaConfig {
                 CleanupPolicy::Segment(segment) => {
                     self.retention_seconds = segment.retention_secs();
                 }
+                CleanupPolicy::Compact(compact) => {
+                    self.retention_seconds = compact.retention_secs();
+                    self.compact = true;
+                    self.delete_retention_seconds = compact.delete_retention_secs;
+                }
             }
         }
 
@@ -89,6 +126,35 @@ impl ReplicaStorageConfig for ReplicaConfig {
         {
             self.max_partition_size = max_partition_size;
         }
+
+        if let Some(storage) = replica
+            .storage
+            .as_ref()
+            .filter(|storage| storage.is_tiered())
+        {
+            self.tier.clone_from(&storage.tier);
+            if let Some(hot_window) = storage.tier_hot_window_secs {
+                self.tier_hot_window_seconds = hot_window;
+            }
+        }
+
+        if replica
+            .storage
+            .as_ref()
+            .is_some_and(|storage| storage.is_ephemeral())
+        {
+            self.ephemeral = true;
+            self.base_dir = self.ephemeral_base_dir.clone();
+            // memory is scarcer than disk, bound partitions unless the topic sets a size
+            if replica
+                .storage
+                .as_ref()
+                .and_then(|storage| storage.max_partition_size)
+                .is_none()
+            {
+                self.max_partition_size = SPU_EPHEMERAL_PARTITION_MAX_BYTES;
+            }
+        }
     }
 }
 
@@ -96,6 +162,10 @@ fn default_base_dir() -> PathBuf {
     PathBuf::from(SPU_LOG_BASE_DIR)
 }
 
+fn default_ephemeral_base_dir() -> PathBuf {
+    PathBuf::from(SPU_EPHEMERAL_LOG_BASE_DIR)
+}
+
 const fn default_update_hw() -> bool {
     true
 }
@@ -136,6 +206,22 @@ const fn default_max_partition_size() -> Size64 {
     SPU_PARTITION_MAX_BYTES
 }
 
+const fn default_read_ahead_bytes() -> Size {
+    STORAGE_READ_AHEAD_BYTES
+}
+
+const fn default_tier_hot_window_seconds() -> Size {
+    STORAGE_TIER_HOT_WINDOW_SECONDS
+}
+
+const fn default_tier_cache_bytes() -> Size64 {
+    STORAGE_TIER_CACHE_BYTES
+}
+
+const fn default_delete_retention_seconds() -> Size {
+    STORAGE_COMPACT_DELETE_RETENTION_SECONDS
+}
+
 impl ReplicaConfig {
     // Used to get a [`ConfigOptionBuilder`].
     pub fn builder() -> ReplicaConfigBuilder {
@@ -161,6 +247,14 @@ impl Default for ReplicaConfig {
             retention_seconds: default_retention_seconds(),
             max_partition_size: default_max_partition_size(),
             update_hw: true,
+            ephemeral_base_dir: default_ephemeral_base_dir(),
+            ephemeral: false,
+            read_ahead_bytes: default_read_ahead_bytes(),
+            tier: None,
+            tier_hot_window_seconds: default_tier_hot_window_seconds(),
+            tier_cache_bytes: default_tier_cache_bytes(),
+            compact: false,
+            delete_retention_seconds: default_delete_retention_seconds(),
         }
     }
 }
@@ -238,6 +332,43 @@ pub struct SharedReplicaConfig {
     pub update_hw: bool, // if true, enable hw update
     pub retention_seconds: SharedConfigU32Value,
     pub max_partition_size: SharedConfigU64Value,
+    pub ephemeral: bool,
+    pub read_ahead_bytes: SharedConfigU32Value,
+    pub tier: Option<String>,
+    pub tier_hot_window_seconds: SharedConfigU32Value,
+    pub tier_cache_bytes: SharedConfigU64Value,
+    pub compact: bool,
+    pub delete_retention_seconds: SharedConfigU32Value,
+}
+
+impl SharedReplicaConfig {
+    /// copy of the config for segments kept in `base_dir`
+    pub(crate) fn with_base_dir(&self, base_dir: PathBuf) -> Self {
+        Self {
+            base_dir,
+            index_max_bytes: SharedConfigU32Value::new(self.index_max_bytes.get()),
+            index_max_interval_bytes: SharedConfigU32Value::new(
+                self.index_max_interval_bytes.get(),
+            ),
+            segment_max_bytes: SharedConfigU32Value::new(self.segment_max_bytes.get()),
+            flush_write_count: SharedConfigU32Value::new(self.flush_write_count.get()),
+            flush_idle_msec: SharedConfigU32Value::new(self.flush_idle_msec.get()),
+            max_batch_size: SharedConfigU32Value::new(self.max_batch_size.get()),
+            max_request_size: SharedConfigU32Value::new(self.max_request_size.get()),
+            update_hw: self.update_hw,
+            retention_seconds: SharedConfigU32Value::new(self.retention_seconds.get()),
+            max_partition_size: SharedConfigU64Value::new(self.max_partition_size.get()),
+            ephemeral: self.ephemeral,
+            read_ahead_bytes: SharedConfigU32Value::new(self.read_ahead_bytes.get()),
+            tier: self.tier.clone(),
+            tier_hot_window_seconds: SharedConfigU32Value::new(self.tier_hot_window_seconds.get()),
+            tier_cache_bytes: SharedConfigU64Value::new(self.tier_cache_bytes.get()),
+            compact: self.compact,
+            delete_retention_seconds: SharedConfigU32Value::new(
+                self.delete_retention_seconds.get(),
+            ),
+        }
+    }
 }
 
 impl From<ReplicaConfig> for SharedReplicaConfig {
@@ -254,6 +385,13 @@ impl From<ReplicaConfig> for SharedReplicaConfig {
             update_hw: config.update_hw,
             retention_seconds: SharedConfigU32Value::new(config.retention_seconds),
             max_partition_size: SharedConfigU64Value::new(config.max_partition_size),
+            ephemeral: config.ephemeral,
+            read_ahead_bytes: SharedConfigU32Value::new(config.read_ahead_bytes),
+            tier: config.tier,
+            tier_hot_window_seconds: SharedConfigU32Value::new(config.tier_hot_window_seconds),
+            tier_cache_bytes: SharedConfigU64Value::new(config.tier_cache_bytes),
+            compact: config.compact,
+            delete_retention_seconds: SharedConfigU32Value::new(config.delete_retention_seconds),
         }
     }
 }
@@ -281,4 +419,70 @@ mod tests {
 
         assert_eq!(ReplicaConfig::default(), config);
     }
+
+    #[test]
+    fn test_ephemeral_replica() {
+        use fluvio_controlplane_metadata::topic::TopicStorageConfig;
+
+        let mut config = ReplicaConfig::builder()
+            .ephemeral_base_dir(PathBuf::from("/dev/shm/test"))
+            .build();
+        let mut replica = Replica {
+            storage: Some(TopicStorageConfig {
+                ephemeral: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        config.update_from_replica(&replica);
+        assert!(config.ephemeral);
+        assert_eq!(config.base_dir, PathBuf::from("/dev/shm/test"));
+        assert_eq!(config.max_partition_size, SPU_EPHEMERAL_PARTITION_MAX_BYTES);
+
+        let mut config = ReplicaConfig::default();
+        replica.storage = Some(TopicStorageConfig {
+            max_partition_size: Some(4096),
+            ..Default::default()
+        });
+        config.update_from_replica(&replica);
+        assert!(!config.ephemeral);
+        assert_eq!(config.base_dir, default_base_dir());
+        assert_eq!(config.max_partition_size, 4096);
+    }
+
+    #[test]
+    fn test_tiered_replica() {
+        use fluvio_controlplane_metadata::topic::TopicStorageConfig;
+
+        let mut config = ReplicaConfig::default();
+        let replica = Replica {
+            storage: Some(TopicStorageConfig {
+                tier: Some("s3://archive/topics".to_owned()),
+                tier_hot_window_secs: Some(600),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        config.update_from_replica(&replica);
+        assert_eq!(config.tier.as_deref(), Some("s3://archive/topics"));
+        assert_eq!(config.tier_hot_window_seconds, 600);
+        assert_eq!(config.base_dir, default_base_dir());
+    }
+
+    #[test]
+    fn test_compacted_replica() {
+        use fluvio_controlplane_metadata::topic::CompactPolicy;
+
+        let mut config = ReplicaConfig::default();
+        let replica = Replica {
+            cleanup_policy: Some(CleanupPolicy::Compact(CompactPolicy {
+                delete_retention_secs: 60,
+            })),
+            ..Default::default()
+        };
+        config.update_from_replica(&replica);
+        assert!(config.compact);
+        assert_eq!(config.delete_retention_seconds, 60);
+        assert_eq!(config.retention_seconds, u32::MAX);
+    }
 }
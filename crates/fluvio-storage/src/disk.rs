@@ -0,0 +1,61 @@
+use std::io::Error as IoError;
+use std::path::Path;
+
+use nix::sys::statvfs::statvfs;
+
+use crate::StorageError;
+
+/// Space of the file system holding a path
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub total_bytes: u64,
+    /// bytes available to unprivileged users
+    pub available_bytes: u64,
+}
+
+impl DiskUsage {
+    pub fn of(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let stat = statvfs(path.as_ref()).map_err(IoError::from)?;
+        let fragment_size = stat.fragment_size() as u64;
+        Ok(Self {
+            total_bytes: stat.blocks() as u64 * fragment_size,
+            available_bytes: stat.blocks_available() as u64 * fragment_size,
+        })
+    }
+
+    pub fn used_bytes(&self) -> u64 {
+        self.total_bytes.saturating_sub(self.available_bytes)
+    }
+
+    /// percentage of the file system in use, 0 if its size is unknown
+    pub fn used_percent(&self) -> u8 {
+        if self.total_bytes == 0 {
+            return 0;
+        }
+        (self.used_bytes() as u128 * 100 / self.total_bytes as u128) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use super::DiskUsage;
+
+    #[test]
+    fn test_disk_usage() {
+        let usage = DiskUsage::of(temp_dir()).expect("usage");
+        assert!(usage.total_bytes > 0);
+        assert!(usage.available_bytes <= usage.total_bytes);
+        assert!(usage.used_percent() <= 100);
+
+        let usage = DiskUsage {
+            total_bytes: 200,
+            available_bytes: 30,
+        };
+        assert_eq!(usage.used_bytes(), 170);
+        assert_eq!(usage.used_percent(), 85);
+        assert_eq!(DiskUsage::default().used_percent(), 0);
+    }
+}
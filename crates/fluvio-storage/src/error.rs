@@ -30,6 +30,13 @@ pub enum StorageError {
     EmptyBatch,
 }
 
+impl StorageError {
+    /// the file system ran out of space
+    pub fn is_storage_full(&self) -> bool {
+        matches!(self, Self::Io(err) if err.raw_os_error() == Some(libc::ENOSPC))
+    }
+}
+
 impl From<BoundedFileSinkError> for StorageError {
     fn from(error: BoundedFileSinkError) -> Self {
         match error {
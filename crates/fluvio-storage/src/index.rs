@@ -165,6 +165,17 @@ impl LogIndex {
         self.ptr as *const Entry
     }
 
+    /// hint the kernel to page in the whole index
+    pub(crate) fn will_need(&self) {
+        if self.len == 0 {
+            return;
+        }
+        let result = unsafe { libc::madvise(self.ptr, self.len as usize, libc::MADV_WILLNEED) };
+        if result != 0 {
+            debug!(path = %self.path.display(), "index read ahead advice failed");
+        }
+    }
+
     /// return file path to be removed
     pub fn clean(self) -> PathBuf {
         self.path
@@ -13,12 +13,17 @@ mod util;
 mod validator;
 mod file;
 pub mod config;
+pub mod block_cache;
+pub mod disk;
 #[cfg(feature = "iterators")]
 pub mod iterators;
 
 #[cfg(feature = "fixture")]
 pub mod fixture;
 mod cleaner;
+mod compaction;
+mod object_store;
+mod tier;
 
 pub use crate::error::StorageError;
 pub use crate::records::FileRecordsSlice;
@@ -29,6 +34,10 @@ pub use crate::replica::FileReplica;
 pub use inner::*;
 mod inner {
 
+    use std::any::Any;
+    use std::fmt;
+    use std::sync::Arc;
+
     use async_trait::async_trait;
     use anyhow::Result;
 
@@ -118,6 +127,25 @@ mod inner {
         pub start: Offset,   // start offset
         pub end: OffsetInfo, // end offset
         pub file_slice: Option<AsyncFileSlice>,
+        /// must be kept until the file slice has been sent
+        pub lease: Option<SliceLease>,
+    }
+
+    /// Keeps the file of a slice open. Slices of remote segments are read from files
+    /// of their own, closed once every lease is dropped.
+    #[derive(Clone)]
+    pub struct SliceLease(Arc<dyn Any + Send + Sync>);
+
+    impl SliceLease {
+        pub(crate) fn new<T: Any + Send + Sync>(file: Arc<T>) -> Self {
+            Self(file)
+        }
+    }
+
+    impl fmt::Debug for SliceLease {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "SliceLease({})", Arc::strong_count(&self.0))
+        }
     }
 
     /// some storage configuration
@@ -0,0 +1,823 @@
+//! Object storage that segments of tiered replicas are offloaded to
+//!
+//! `s3://bucket/prefix` and `gs://bucket/prefix` locations use the S3 API, signed with
+//! the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` of the SPU. `AWS_ENDPOINT_URL`
+//! points to other S3 compatible storage such as MinIO. `file:///path` locations keep
+//! the objects in a local directory.
+//!
+//! Only these static credentials are supported: they are read once, when the store is
+//! opened. Instance profiles, web identity tokens, SSO and the shared credentials file are
+//! not, and an `AWS_SESSION_TOKEN` is never refreshed, so temporary credentials stop working
+//! once they expire and the SPU has to be restarted with new ones.
+//!
+//! Segments are never held in memory as a whole: they are uploaded in parts of at most
+//! [`PART_SIZE`] and read back by range.
+
+use std::fmt;
+use std::fs::File;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+/// bytes sent per request when uploading a file, S3 requires at least 5Mb for each part but the last
+pub const PART_SIZE: u64 = 16_777_216; // 16Mb
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectInfo {
+    /// key relative to the prefix of the store
+    pub key: String,
+    pub last_modified: SystemTime,
+}
+
+#[async_trait]
+pub trait ObjectStore: fmt::Debug + Send + Sync {
+    /// upload the file at `path`, large files are sent in parts
+    async fn put_file(&self, key: &str, path: &Path) -> Result<()>;
+
+    /// size of the object in bytes
+    async fn size(&self, key: &str) -> Result<u64>;
+
+    /// read `len` bytes at `position`, fewer at the end of the object
+    async fn get_range(&self, key: &str, position: u64, len: u64) -> Result<Bytes>;
+
+    /// objects whose keys start with `prefix`
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>>;
+
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Store of the tier location of a topic
+pub fn open_object_store(location: &str) -> Result<Arc<dyn ObjectStore>> {
+    let (scheme, path) = location
+        .split_once("://")
+        .ok_or_else(|| anyhow!("tier {location} is not a location like s3://bucket/prefix"))?;
+    match scheme {
+        "file" => Ok(Arc::new(FileObjectStore::new(path))),
+        "s3" | "gs" => {
+            let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+            Ok(Arc::new(S3ObjectStore::from_env(scheme, bucket, prefix)?))
+        }
+        _ => Err(anyhow!("tier scheme {scheme} is not supported")),
+    }
+}
+
+/// Objects kept as files under a directory
+#[derive(Debug)]
+pub struct FileObjectStore {
+    root: PathBuf,
+}
+
+impl FileObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for FileObjectStore {
+    async fn put_file(&self, key: &str, path: &Path) -> Result<()> {
+        let object = self.path(key);
+        if let Some(parent) = object.parent() {
+            fluvio_future::fs::create_dir_all(parent).await?;
+        }
+        // the object only shows up complete
+        let working = object.with_extension("uploading");
+        let source = path.to_owned();
+        let copied = working.clone();
+        blocking::unblock(move || std::fs::copy(source, copied)).await?;
+        fluvio_future::fs::rename(&working, &object).await?;
+        Ok(())
+    }
+
+    async fn size(&self, key: &str) -> Result<u64> {
+        let path = self.path(key);
+        Ok(blocking::unblock(move || std::fs::metadata(path))
+            .await?
+            .len())
+    }
+
+    async fn get_range(&self, key: &str, position: u64, len: u64) -> Result<Bytes> {
+        let path = self.path(key);
+        Ok(read_file_range(path, position, len).await?.into())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let (dir, name_prefix) = match prefix.rsplit_once('/') {
+            Some((dir, name_prefix)) => (self.root.join(dir), name_prefix),
+            None => (self.root.clone(), prefix),
+        };
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return Ok(Vec::new());
+        };
+        let mut objects = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if metadata.is_file() && name.starts_with(name_prefix) && !name.ends_with(".uploading")
+            {
+                let key = relative_key(&self.root, &entry.path());
+                objects.push(ObjectInfo {
+                    key,
+                    last_modified: metadata.modified()?,
+                });
+            }
+        }
+        objects.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(objects)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match fluvio_future::fs::remove_file(self.path(key)).await {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            result => Ok(result?),
+        }
+    }
+}
+
+/// read `len` bytes at `position` of the file, fewer at its end
+async fn read_file_range(path: PathBuf, position: u64, len: u64) -> Result<Vec<u8>> {
+    blocking::unblock(move || {
+        let file = File::open(&path).with_context(|| format!("open {}", path.display()))?;
+        let end = file.metadata()?.len().min(position.saturating_add(len));
+        let mut data = vec![0; end.saturating_sub(position) as usize];
+        file.read_exact_at(&mut data, position)?;
+        Ok(data)
+    })
+    .await
+}
+
+fn relative_key(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Objects of a bucket of S3 or of storage with an S3 compatible API
+pub struct S3ObjectStore {
+    agent: ureq::Agent,
+    /// url of the bucket, without trailing slash
+    bucket_url: String,
+    host: String,
+    /// path of the bucket in the url, empty for virtual hosted buckets
+    bucket_path: String,
+    prefix: String,
+    region: String,
+    credentials: Credentials,
+}
+
+impl fmt::Debug for S3ObjectStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "S3ObjectStore({}/{})", self.bucket_url, self.prefix)
+    }
+}
+
+#[derive(Clone)]
+struct Credentials {
+    access_key: String,
+    secret_key: String,
+    session_token: Option<String>,
+}
+
+const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+impl S3ObjectStore {
+    fn from_env(scheme: &str, bucket: &str, prefix: &str) -> Result<Self> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let credentials = Credentials {
+            access_key: env("AWS_ACCESS_KEY_ID")
+                .ok_or_else(|| anyhow!("AWS_ACCESS_KEY_ID is required for tier {scheme}://"))?,
+            secret_key: env("AWS_SECRET_ACCESS_KEY")
+                .ok_or_else(|| anyhow!("AWS_SECRET_ACCESS_KEY is required for tier {scheme}://"))?,
+            session_token: env("AWS_SESSION_TOKEN"),
+        };
+        let (endpoint, region) = match (scheme, env("AWS_ENDPOINT_URL")) {
+            (_, Some(endpoint)) => (
+                Some(endpoint),
+                env("AWS_REGION").unwrap_or_else(|| "us-east-1".to_owned()),
+            ),
+            // interoperability API of Cloud Storage, with HMAC keys
+            ("gs", None) => (
+                Some("https://storage.googleapis.com".to_owned()),
+                "auto".to_owned(),
+            ),
+            _ => (
+                None,
+                env("AWS_REGION").unwrap_or_else(|| "us-east-1".to_owned()),
+            ),
+        };
+        Ok(Self::new(bucket, prefix, endpoint, region, credentials))
+    }
+
+    /// buckets of custom endpoints are addressed by path, S3 buckets by host
+    fn new(
+        bucket: &str,
+        prefix: &str,
+        endpoint: Option<String>,
+        region: String,
+        credentials: Credentials,
+    ) -> Self {
+        let (base, bucket_path) = match endpoint {
+            Some(endpoint) => (
+                endpoint.trim_end_matches('/').to_owned(),
+                format!("/{bucket}"),
+            ),
+            None => (
+                format!("https://{bucket}.s3.{region}.amazonaws.com"),
+                String::new(),
+            ),
+        };
+        let host = base
+            .split_once("://")
+            .map(|(_, host)| host)
+            .unwrap_or(&base)
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+        Self {
+            agent: ureq::AgentBuilder::new().build(),
+            bucket_url: format!("{base}{bucket_path}"),
+            host,
+            bucket_path,
+            prefix: prefix.trim_matches('/').to_owned(),
+            region,
+            credentials,
+        }
+    }
+
+    fn object_key(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}/{key}", self.prefix)
+        }
+    }
+
+    /// send a request signed with AWS signature version 4
+    async fn send(
+        &self,
+        method: &'static str,
+        key: Option<&str>,
+        query: Vec<(String, String)>,
+        extra_headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    ) -> Result<ureq::Response> {
+        let path = match key {
+            Some(key) => format!("{}/{}", self.bucket_path, uri_encode(key, false)),
+            None => format!("{}/", self.bucket_path),
+        };
+        let mut query: Vec<(String, String)> = query
+            .into_iter()
+            .map(|(name, value)| (uri_encode(&name, true), uri_encode(&value, true)))
+            .collect();
+        query.sort();
+        let query = query
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let payload_hash = if body.is_empty() {
+            EMPTY_SHA256.to_owned()
+        } else {
+            hex::encode(Sha256::digest(&body))
+        };
+        let amz_date = amz_date(SystemTime::now());
+        let mut headers = vec![
+            ("host".to_owned(), self.host.clone()),
+            ("x-amz-content-sha256".to_owned(), payload_hash.clone()),
+            ("x-amz-date".to_owned(), amz_date.clone()),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token".to_owned(), token.clone()));
+        }
+        headers.extend(extra_headers);
+        headers.sort();
+        let authorization = sign_v4(
+            &SignedRequest {
+                method,
+                path: &path,
+                query: &query,
+                headers: &headers,
+                payload_hash: &payload_hash,
+            },
+            &self.credentials,
+            &amz_date,
+            &self.region,
+            "s3",
+        );
+
+        let url = match key {
+            Some(key) => format!("{}/{}", self.bucket_url, uri_encode(key, false)),
+            None => format!("{}/", self.bucket_url),
+        };
+        let url = if query.is_empty() {
+            url
+        } else {
+            format!("{url}?{query}")
+        };
+        let mut request = self
+            .agent
+            .request(method, &url)
+            .set("authorization", &authorization);
+        for (name, value) in headers.iter().filter(|(name, _)| name != "host") {
+            request = request.set(name, value);
+        }
+        blocking::unblock(move || match request.send_bytes(&body) {
+            Ok(response) => Ok(response),
+            Err(ureq::Error::Status(code, response)) => {
+                let message = response.into_string().unwrap_or_default();
+                Err(anyhow!("{method} {url} failed with {code}: {message}"))
+            }
+            Err(err) => Err(anyhow!("{method} {url} failed: {err}")),
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl ObjectStore for S3ObjectStore {
+    async fn put_file(&self, key: &str, path: &Path) -> Result<()> {
+        let path = path.to_owned();
+        let size = blocking::unblock({
+            let path = path.clone();
+            move || std::fs::metadata(path)
+        })
+        .await?
+        .len();
+        let key = self.object_key(key);
+        if size <= PART_SIZE {
+            let data = read_file_range(path, 0, size).await?;
+            self.send("PUT", Some(&key), vec![], vec![], data).await?;
+            return Ok(());
+        }
+
+        let response = self
+            .send(
+                "POST",
+                Some(&key),
+                vec![("uploads".to_owned(), String::new())],
+                vec![],
+                vec![],
+            )
+            .await?;
+        let body = blocking::unblock(move || response.into_string()).await?;
+        let upload_id = xml_elements(&body, "UploadId")
+            .next()
+            .map(xml_unescape)
+            .ok_or_else(|| anyhow!("no upload id to upload {key}"))?;
+
+        match self.upload_parts(&key, &upload_id, path, size).await {
+            Ok(etags) => {
+                let parts: String = etags
+                    .iter()
+                    .enumerate()
+                    .map(|(index, etag)| {
+                        format!(
+                            "<Part><PartNumber>{}</PartNumber><ETag>{etag}</ETag></Part>",
+                            index + 1
+                        )
+                    })
+                    .collect();
+                self.send(
+                    "POST",
+                    Some(&key),
+                    vec![("uploadId".to_owned(), upload_id)],
+                    vec![],
+                    format!("<CompleteMultipartUpload>{parts}</CompleteMultipartUpload>")
+                        .into_bytes(),
+                )
+                .await?;
+                Ok(())
+            }
+            Err(err) => {
+                // the parts uploaded so far are billed until the upload is aborted
+                if let Err(abort_err) = self
+                    .send(
+                        "DELETE",
+                        Some(&key),
+                        vec![("uploadId".to_owned(), upload_id)],
+                        vec![],
+                        vec![],
+                    )
+                    .await
+                {
+                    debug!(%abort_err, key, "unable to abort upload");
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn size(&self, key: &str) -> Result<u64> {
+        let response = self
+            .send("HEAD", Some(&self.object_key(key)), vec![], vec![], vec![])
+            .await?;
+        response
+            .header("content-length")
+            .and_then(|len| len.parse().ok())
+            .ok_or_else(|| anyhow!("no content length for {key}"))
+    }
+
+    async fn get_range(&self, key: &str, position: u64, len: u64) -> Result<Bytes> {
+        if len == 0 {
+            return Ok(Bytes::new());
+        }
+        let range = format!("bytes={position}-{}", position + len - 1);
+        let response = self
+            .send(
+                "GET",
+                Some(&self.object_key(key)),
+                vec![],
+                vec![("range".to_owned(), range)],
+                vec![],
+            )
+            .await?;
+        blocking::unblock(move || {
+            let mut data = Vec::with_capacity(len as usize);
+            std::io::Read::read_to_end(
+                &mut std::io::Read::take(response.into_reader(), len),
+                &mut data,
+            )?;
+            Ok(data.into())
+        })
+        .await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+        let full_prefix = self.object_key(prefix);
+        let strip = if self.prefix.is_empty() {
+            0
+        } else {
+            self.prefix.len() + 1
+        };
+        let mut objects = Vec::new();
+        let mut continuation = None;
+        loop {
+            let mut query = vec![
+                ("list-type".to_owned(), "2".to_owned()),
+                ("prefix".to_owned(), full_prefix.clone()),
+            ];
+            if let Some(token) = continuation.take() {
+                query.push(("continuation-token".to_owned(), token));
+            }
+            let response = self.send("GET", None, query, vec![], vec![]).await?;
+            let body = blocking::unblock(move || response.into_string()).await?;
+            for contents in xml_elements(&body, "Contents") {
+                let (Some(key), Some(modified)) = (
+                    xml_elements(contents, "Key").next(),
+                    xml_elements(contents, "LastModified").next(),
+                ) else {
+                    continue;
+                };
+                let key = xml_unescape(key);
+                objects.push(ObjectInfo {
+                    key: key.get(strip..).unwrap_or_default().to_owned(),
+                    last_modified: humantime::parse_rfc3339(modified)?,
+                });
+            }
+            match xml_elements(&body, "NextContinuationToken").next() {
+                Some(token) => continuation = Some(xml_unescape(token)),
+                None => break,
+            }
+        }
+        Ok(objects)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.send(
+            "DELETE",
+            Some(&self.object_key(key)),
+            vec![],
+            vec![],
+            vec![],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+impl S3ObjectStore {
+    /// send the parts of a multipart upload one after the other, returns their etags
+    async fn upload_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+        path: PathBuf,
+        size: u64,
+    ) -> Result<Vec<String>> {
+        let mut etags = Vec::new();
+        let mut position = 0;
+        while position < size {
+            let data = read_file_range(path.clone(), position, PART_SIZE).await?;
+            position += data.len() as u64;
+            let response = self
+                .send(
+                    "PUT",
+                    Some(key),
+                    vec![
+                        ("partNumber".to_owned(), (etags.len() + 1).to_string()),
+                        ("uploadId".to_owned(), upload_id.to_owned()),
+                    ],
+                    vec![],
+                    data,
+                )
+                .await?;
+            let etag = response
+                .header("etag")
+                .ok_or_else(|| anyhow!("no etag for part {} of {key}", etags.len() + 1))?
+                .to_owned();
+            etags.push(etag);
+        }
+        debug!(key, parts = etags.len(), "parts uploaded");
+        Ok(etags)
+    }
+}
+
+struct SignedRequest<'a> {
+    method: &'a str,
+    /// encoded path
+    path: &'a str,
+    /// encoded and sorted query
+    query: &'a str,
+    /// lowercase names, sorted
+    headers: &'a [(String, String)],
+    payload_hash: &'a str,
+}
+
+/// `Authorization` header of the request
+fn sign_v4(
+    request: &SignedRequest<'_>,
+    credentials: &Credentials,
+    amz_date: &str,
+    region: &str,
+    service: &str,
+) -> String {
+    let date = &amz_date[..8];
+    let canonical_headers: String = request
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+        .collect();
+    let signed_headers = request
+        .headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{canonical_headers}\n{signed_headers}\n{}",
+        request.method, request.path, request.query, request.payload_hash
+    );
+    let scope = format!("{date}/{region}/{service}/aws4_request");
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let key = hmac_sha256(
+        format!("AWS4{}", credentials.secret_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    let key = hmac_sha256(&key, b"aws4_request");
+    let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        credentials.access_key
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(data);
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+/// `20150830T123600Z`
+fn amz_date(time: SystemTime) -> String {
+    humantime::format_rfc3339_seconds(time)
+        .to_string()
+        .replace(['-', ':'], "")
+}
+
+/// encode all but the unreserved characters, and `/` unless `encode_slash`
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// text of the elements named `name`, not nested in each other
+fn xml_elements<'a>(xml: &'a str, name: &str) -> impl Iterator<Item = &'a str> {
+    let open = format!("<{name}>");
+    let close = format!("</{name}>");
+    let mut rest = xml;
+    std::iter::from_fn(move || {
+        let start = rest.find(&open)? + open.len();
+        let end = start + rest[start..].find(&close)?;
+        let text = &rest[start..end];
+        rest = &rest[end + close.len()..];
+        Some(text)
+    })
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_clean_dir;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_file_object_store() {
+        let root = temp_dir().join("object-store-file");
+        ensure_clean_dir(&root);
+        let source = temp_dir().join("object-store-file-source");
+        ensure_clean_dir(&source);
+        let store = open_object_store(&format!("file://{}", root.display())).expect("store");
+
+        for (key, data) in [
+            ("orders-0/00000000000000000000.log", "first"),
+            ("orders-0/00000000000000000010.log", "second"),
+            ("orders-1/00000000000000000000.log", "other"),
+        ] {
+            let path = source.join(data);
+            std::fs::write(&path, data).expect("write");
+            store.put_file(key, &path).await.expect("put");
+        }
+
+        let keys: Vec<String> = store
+            .list("orders-0/")
+            .await
+            .expect("list")
+            .into_iter()
+            .map(|object| object.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "orders-0/00000000000000000000.log",
+                "orders-0/00000000000000000010.log"
+            ]
+        );
+        let key = "orders-0/00000000000000000010.log";
+        assert_eq!(store.size(key).await.expect("size"), 6);
+        assert_eq!(
+            store.get_range(key, 2, 3).await.expect("get").as_ref(),
+            b"con"
+        );
+        // ranges are cut at the end of the object
+        assert_eq!(
+            store.get_range(key, 4, 10).await.expect("get").as_ref(),
+            b"nd"
+        );
+        assert!(store.get_range(key, 6, 1).await.expect("get").is_empty());
+
+        store
+            .delete("orders-0/00000000000000000000.log")
+            .await
+            .expect("delete");
+        store
+            .delete("orders-0/00000000000000000000.log")
+            .await
+            .expect("delete missing");
+        assert_eq!(store.list("orders-0/").await.expect("list").len(), 1);
+        assert!(store.list("payments-0/").await.expect("list").is_empty());
+    }
+
+    #[test]
+    fn test_sign_v4() {
+        // get-vanilla of the AWS signature version 4 test suite
+        let credentials = Credentials {
+            access_key: "AKIDEXAMPLE".to_owned(),
+            secret_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_owned(),
+            session_token: None,
+        };
+        let headers = vec![
+            ("host".to_owned(), "example.amazonaws.com".to_owned()),
+            ("x-amz-date".to_owned(), "20150830T123600Z".to_owned()),
+        ];
+        let authorization = sign_v4(
+            &SignedRequest {
+                method: "GET",
+                path: "/",
+                query: "",
+                headers: &headers,
+                payload_hash: EMPTY_SHA256,
+            },
+            &credentials,
+            "20150830T123600Z",
+            "us-east-1",
+            "service",
+        );
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_s3_urls() {
+        let credentials = Credentials {
+            access_key: "key".to_owned(),
+            secret_key: "secret".to_owned(),
+            session_token: None,
+        };
+        let store = S3ObjectStore::new(
+            "archive",
+            "/fluvio/",
+            None,
+            "eu-west-1".to_owned(),
+            credentials.clone(),
+        );
+        assert_eq!(
+            store.bucket_url,
+            "https://archive.s3.eu-west-1.amazonaws.com"
+        );
+        assert_eq!(store.host, "archive.s3.eu-west-1.amazonaws.com");
+        assert_eq!(store.object_key("orders-0/1.log"), "fluvio/orders-0/1.log");
+
+        let store = S3ObjectStore::new(
+            "archive",
+            "",
+            Some("http://minio:9000/".to_owned()),
+            "us-east-1".to_owned(),
+            credentials,
+        );
+        assert_eq!(store.bucket_url, "http://minio:9000/archive");
+        assert_eq!(store.host, "minio:9000");
+        assert_eq!(store.bucket_path, "/archive");
+        assert_eq!(store.object_key("orders-0/1.log"), "orders-0/1.log");
+    }
+
+    #[test]
+    fn test_list_response() {
+        let body = "<ListBucketResult><Contents><Key>p/a&amp;b.log</Key>\
+            <LastModified>2009-10-12T17:50:30.000Z</LastModified></Contents>\
+            <Contents><Key>p/c.log</Key><LastModified>2009-10-12T17:50:31.000Z</LastModified>\
+            </Contents><NextContinuationToken>token</NextContinuationToken></ListBucketResult>";
+        let keys: Vec<String> = xml_elements(body, "Contents")
+            .filter_map(|contents| xml_elements(contents, "Key").next())
+            .map(xml_unescape)
+            .collect();
+        assert_eq!(keys, vec!["p/a&b.log", "p/c.log"]);
+        assert_eq!(
+            xml_elements(body, "NextContinuationToken").next(),
+            Some("token")
+        );
+        assert_eq!(uri_encode("a b/c+d", false), "a%20b/c%2Bd");
+        assert_eq!(uri_encode("a/b", true), "a%2Fb");
+        assert_eq!(
+            amz_date(humantime::parse_rfc3339("2015-08-30T12:36:00Z").unwrap()),
+            "20150830T123600Z"
+        );
+    }
+}
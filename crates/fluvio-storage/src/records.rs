@@ -110,6 +110,27 @@ impl FileRecordsSlice {
         }
     }
 
+    /// hint the kernel to read `len` bytes from `position` into the page cache.
+    /// The reads are only started, the call doesn't wait for them
+    pub(crate) fn will_need(&self, position: u64, len: u64) {
+        #[cfg(target_os = "linux")]
+        {
+            let result = unsafe {
+                libc::posix_fadvise(
+                    self.file.as_raw_fd(),
+                    position as libc::off_t,
+                    len as libc::off_t,
+                    libc::POSIX_FADV_WILLNEED,
+                )
+            };
+            if result != 0 {
+                debug!(path = %self.path.display(), result, "read ahead advice failed");
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = (position, len);
+    }
+
     pub(crate) async fn remove(self) -> Result<(), IoError> {
         info!(log_path = %self.path.display(),"removing log file");
         remove_file(&self.path).await
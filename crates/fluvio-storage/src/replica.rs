@@ -1,5 +1,6 @@
 use std::cmp::min;
 use std::mem;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -9,7 +10,8 @@ use anyhow::Result;
 
 use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_protocol::Encoder;
-use fluvio_future::fs::{create_dir_all, remove_dir_all};
+use fluvio_future::fs::{copy, create_dir_all, hard_link, metadata, remove_dir_all, rename, File};
+use futures_lite::io::{AsyncReadExt, AsyncWriteExt};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{Offset, ReplicaKey, Size, Size64};
@@ -23,6 +25,12 @@ use crate::config::{ReplicaConfig, SharedReplicaConfig, StorageConfig};
 use crate::ReplicaSlice;
 use crate::{StorageError, ReplicaStorage};
 use crate::cleaner::Cleaner;
+use crate::tier::Tier;
+use crate::util::generate_file_name;
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::index::EXTENSION as INDEX_EXTENSION;
+
+const REPLICATION_CHECKPOINT: &str = "replication.chk";
 
 /// Replica is public abstraction for commit log which are distributed.
 /// Internally it is stored as list of segments.  Each segment contains finite sets of record batches.
@@ -38,6 +46,7 @@ pub struct FileReplica {
     prev_segments: Arc<SharedSegments>,
     commit_checkpoint: CheckPoint<Offset>,
     cleaner: Arc<Cleaner>,
+    tier: Option<Arc<Tier>>,
     size: Arc<ReplicaSize>,
 }
 
@@ -85,6 +94,12 @@ impl ReplicaStorage for FileReplica {
     /// earliest offset
     fn get_log_start_offset(&self) -> Offset {
         let min_base_offset = self.prev_segments.min_offset();
+        let min_base_offset = match self.tier.as_ref().map(|tier| tier.min_offset()) {
+            Some(remote) if remote >= 0 && (min_base_offset < 0 || remote < min_base_offset) => {
+                remote
+            }
+            _ => min_base_offset,
+        };
         if min_base_offset < 0 {
             self.active_segment.get_base_offset()
         } else {
@@ -190,6 +205,9 @@ impl ReplicaStorage for FileReplica {
             .map_err(StorageError::Io)?;
 
         self.cleaner.shutdown();
+        if let Some(tier) = &self.tier {
+            tier.shutdown();
+        }
         Ok(())
     }
 }
@@ -222,9 +240,8 @@ impl FileReplica {
     where
         S: AsRef<str> + Send + 'static,
     {
-        let replica_dir = replica_config
-            .base_dir
-            .join(replica_dir_name(topic, partition));
+        let replica_name = replica_dir_name(topic, partition);
+        let replica_dir = replica_config.base_dir.join(&replica_name);
 
         info!("creating rep dir: {}", replica_dir.display());
         debug!("replica config: {:?}", replica_config);
@@ -254,8 +271,12 @@ impl FileReplica {
 
         let last_base_offset = active_segment.get_base_offset();
 
-        let mut commit_checkpoint: CheckPoint<Offset> =
-            CheckPoint::create(shared_config.clone(), "replication.chk", last_base_offset).await?;
+        let mut commit_checkpoint: CheckPoint<Offset> = CheckPoint::create(
+            shared_config.clone(),
+            REPLICATION_CHECKPOINT,
+            last_base_offset,
+        )
+        .await?;
 
         // ensure checkpoint is valid
         let hw = *commit_checkpoint.get_offset();
@@ -271,6 +292,21 @@ impl FileReplica {
         let size = Arc::new(ReplicaSize::default());
         size.store_active(active_segment.occupied_memory());
 
+        let tier = match &shared_config.tier {
+            Some(location) => Some(
+                Tier::start_new(
+                    location,
+                    &replica_name,
+                    storage_config.clone(),
+                    shared_config.clone(),
+                    segments.clone(),
+                    size.clone(),
+                )
+                .await?,
+            ),
+            None => None,
+        };
+
         let cleaner = Cleaner::start_new(
             storage_config,
             shared_config.clone(),
@@ -286,6 +322,7 @@ impl FileReplica {
             prev_segments: segments,
             commit_checkpoint,
             cleaner,
+            tier,
             size,
         })
     }
@@ -303,12 +340,94 @@ impl FileReplica {
         }
     }
 
+    /// Copy records up to the log end offset into the replica `topic`-`partition` under
+    /// `base_dir`, which must not exist yet. Sealed segments are immutable and hard linked,
+    /// the active segment is copied. Caller must prevent writes while the copy is made.
+    /// Returns the log end offset of the copy or `None` if the target already exists.
+    #[instrument(skip(self, base_dir))]
+    pub async fn snapshot_to(
+        &self,
+        base_dir: &Path,
+        topic: &str,
+        partition: Size,
+    ) -> Result<Option<Offset>> {
+        let target_dir = base_dir.join(replica_dir_name(topic, partition));
+        if metadata(&target_dir).await.is_ok() {
+            return Ok(None);
+        }
+        let working_dir = base_dir.join(format!(".{}", replica_dir_name(topic, partition)));
+        if metadata(&working_dir).await.is_ok() {
+            remove_dir_all(&working_dir).await?;
+        }
+        create_dir_all(&working_dir).await?;
+
+        let source_dir = &self.option.base_dir;
+        let segments = self.prev_segments.read().await;
+        for base_offset in segments.base_offsets() {
+            for extension in [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION] {
+                let source = generate_file_name(source_dir, base_offset, extension);
+                let target = generate_file_name(&working_dir, base_offset, extension);
+                if let Err(err) = hard_link(&source, &target).await {
+                    // links can't cross file systems
+                    debug!(%err, "hard link failed, copying {}", source.display());
+                    copy(&source, &target).await?;
+                }
+            }
+        }
+        drop(segments);
+
+        let active_base_offset = self.active_segment.get_base_offset();
+        let active_len = self.active_segment.get_msg_log().get_pos() as u64;
+        let source = File::open(generate_file_name(
+            source_dir,
+            active_base_offset,
+            MESSAGE_LOG_EXTENSION,
+        ))
+        .await?;
+        let mut target = File::create(generate_file_name(
+            &working_dir,
+            active_base_offset,
+            MESSAGE_LOG_EXTENSION,
+        ))
+        .await?;
+        futures_lite::io::copy(source.take(active_len), &mut target).await?;
+        target.flush().await?;
+        copy(
+            generate_file_name(source_dir, active_base_offset, INDEX_EXTENSION),
+            generate_file_name(&working_dir, active_base_offset, INDEX_EXTENSION),
+        )
+        .await?;
+
+        let mut checkpoint = File::create(working_dir.join(REPLICATION_CHECKPOINT)).await?;
+        checkpoint.write_all(&self.get_hw().to_be_bytes()).await?;
+        checkpoint.flush().await?;
+
+        // the target only shows up complete
+        rename(&working_dir, &target_dir).await?;
+
+        let leo = self.get_leo();
+        info!(
+            source = %source_dir.display(),
+            target = %target_dir.display(),
+            leo,
+            "replica snapshot created"
+        );
+        Ok(Some(leo))
+    }
+
     /// update high watermark to end
     #[instrument(skip(self))]
     pub async fn update_high_watermark_to_end(&mut self) -> Result<bool, StorageError> {
         self.update_high_watermark(self.get_leo()).await
     }
 
+    /// Remove the oldest sealed segment regardless of the retention of the replica, to free space
+    /// when the disk is full. Returns the bytes freed, 0 if only the active segment is left.
+    #[instrument(skip(self))]
+    pub async fn remove_oldest_segment(&self) -> u64 {
+        self.cleaner.remove_oldest().await
+    }
+
     /// read all uncommitted records
     #[allow(unused)]
     #[instrument(skip(self, max_len))]
@@ -366,13 +485,24 @@ impl FileReplica {
             }
         } else {
             debug!(start_offset, active_base_offset, "not in active sgments");
-            self.prev_segments
+            let local_slice = self
+                .prev_segments
                 .find_slice(start_offset, max_offset)
-                .await?
-                .ok_or_else(|| ErrorCode::OffsetEvicted {
-                    offset: start_offset,
-                    next_available: self.get_log_start_offset(),
-                })?
+                .await?;
+            let file_slice = match (local_slice, &self.tier) {
+                (None, Some(tier)) => match tier.find_slice(start_offset, max_len).await? {
+                    Some((file_slice, lease)) => {
+                        slice.lease = Some(lease);
+                        Some(file_slice)
+                    }
+                    None => None,
+                },
+                (local_slice, _) => local_slice,
+            };
+            file_slice.ok_or_else(|| ErrorCode::OffsetEvicted {
+                offset: start_offset,
+                next_available: self.get_log_start_offset(),
+            })?
         };
 
         let limited_slice = AsyncFileSlice::new(
@@ -381,6 +511,23 @@ impl FileReplica {
             min(file_slice.len(), max_len as u64),
         );
 
+        let read_ahead = self.option.read_ahead_bytes.get();
+        // slices of remote segments are read ahead by the block cache
+        if start_offset < active_base_offset
+            && read_ahead > 0
+            && !self.option.ephemeral
+            && slice.lease.is_none()
+        {
+            // consumers of previous segments replay history, the next read follows this slice
+            self.prev_segments
+                .read_ahead(
+                    start_offset,
+                    limited_slice.position() + limited_slice.len(),
+                    read_ahead as u64,
+                )
+                .await;
+        }
+
         debug!(
             fd = limited_slice.fd(),
             pos = limited_slice.position(),
@@ -710,6 +857,69 @@ mod tests {
         assert_eq!(seg1_metadata.len(), 8);
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_snapshot() {
+        let option = rollover_option("test_snapshot");
+        let mut replica = FileReplica::create_or_load_with_storage(
+            "test",
+            1,
+            START_OFFSET,
+            option.clone(),
+            storage_config(),
+        )
+        .await
+        .expect("create rep");
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+        replica
+            .update_high_watermark(START_OFFSET + 2)
+            .await
+            .expect("hw");
+
+        let leo = replica
+            .snapshot_to(&option.base_dir, "clone", 1)
+            .await
+            .expect("snapshot");
+        assert_eq!(leo, Some(START_OFFSET + 4));
+        assert_eq!(
+            replica
+                .snapshot_to(&option.base_dir, "clone", 1)
+                .await
+                .expect("snapshot"),
+            None
+        );
+
+        // source keeps working after the snapshot
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+
+        let clone = FileReplica::create_or_load_with_storage(
+            "clone",
+            1,
+            0,
+            option.clone(),
+            storage_config(),
+        )
+        .await
+        .expect("load clone");
+        assert_eq!(clone.get_log_start_offset(), START_OFFSET);
+        assert_eq!(clone.get_leo(), START_OFFSET + 4);
+        assert_eq!(clone.get_hw(), START_OFFSET + 2);
+        let slice = clone
+            .read_records(START_OFFSET, None, FileReplica::PREFER_MAX_LEN)
+            .await
+            .expect("read");
+        assert!(slice.file_slice.is_some());
+    }
+
     #[fluvio_future::test]
     async fn test_replica_commit() {
         let option = base_option("test_commit");
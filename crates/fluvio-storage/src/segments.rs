@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
-use std::ops::Bound::Included;
+use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::ffi::OsStr;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicI64;
 use std::time::Duration;
@@ -15,8 +16,10 @@ use fluvio_protocol::record::Offset;
 use fluvio_future::file_slice::AsyncFileSlice;
 
 use crate::config::SharedReplicaConfig;
+use crate::index::EXTENSION as INDEX_EXTENSION;
+use crate::records::MESSAGE_LOG_EXTENSION;
 use crate::segment::ReadSegment;
-use crate::util::log_path_get_offset;
+use crate::util::{generate_file_name, log_path_get_offset};
 
 const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqCst;
 
@@ -118,6 +121,36 @@ impl SharedSegments {
         }
     }
 
+    /// Replace the files of the segment at `base_offset` by the ones in `dir` and reopen it.
+    /// Readers of the segment are blocked while the files are swapped.
+    #[instrument(skip(self, option))]
+    pub(crate) async fn replace_segment(
+        &self,
+        base_offset: Offset,
+        dir: &Path,
+        option: Arc<SharedReplicaConfig>,
+    ) -> Result<()> {
+        let mut writer = self.write().await;
+        let Some(end_offset) = writer
+            .segments
+            .get(&base_offset)
+            .map(ReadSegment::get_end_offset)
+        else {
+            debug!("segment removed in the meantime");
+            return Ok(());
+        };
+        for extension in [INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+            fluvio_future::fs::rename(
+                generate_file_name(dir, base_offset, extension),
+                generate_file_name(&option.base_dir, base_offset, extension),
+            )
+            .await?;
+        }
+        let segment = ReadSegment::open_for_read(base_offset, end_offset, option).await?;
+        writer.add_segment(segment);
+        Ok(())
+    }
+
     /// find slice in the segments
     /// if not found, return OutOfRange error
     pub async fn find_slice(
@@ -139,6 +172,33 @@ impl SharedSegments {
         }
     }
 
+    /// Read ahead of a sequential consumer of the segment of `start_offset` whose next
+    /// read starts at `position`. Pages `len` bytes after it into the page cache, moving
+    /// on to the log and index of the next segment when the read ahead crosses the end
+    /// of the segment.
+    pub async fn read_ahead(&self, start_offset: Offset, position: u64, len: u64) {
+        let reader = self.read().await;
+        let Some((base_offset, segment)) = reader.find_segment(start_offset) else {
+            return;
+        };
+        let log = segment.get_msg_log();
+        log.will_need(position, len);
+
+        let remaining = (position + len).saturating_sub(log.get_len());
+        if remaining > 0 {
+            // the next one of the last segment is the active segment, which is hot already
+            if let Some(next) = reader.next_segment(*base_offset) {
+                trace!(
+                    base_offset = next.get_base_offset(),
+                    remaining,
+                    "read ahead next segment"
+                );
+                next.get_index().will_need();
+                next.get_msg_log().will_need(0, remaining);
+            }
+        }
+    }
+
     #[instrument(skip(self))]
     async fn remove_segment(&self, base_offset: &Offset) {
         let mut write = self.write().await;
@@ -174,6 +234,14 @@ impl SegmentList {
         self.segments.len()
     }
 
+    pub(crate) fn base_offsets(&self) -> Vec<Offset> {
+        self.segments.keys().copied().collect()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &ReadSegment> {
+        self.segments.values()
+    }
+
     pub fn occupied_memory(&self) -> Size64 {
         self.segments
             .values()
@@ -245,6 +313,14 @@ impl SegmentList {
         }
     }
 
+    /// segment following the one starting at `base_offset`
+    pub(crate) fn next_segment(&self, base_offset: Offset) -> Option<&ReadSegment> {
+        self.segments
+            .range((Excluded(base_offset), Unbounded))
+            .next()
+            .map(|(_, segment)| segment)
+    }
+
     pub(crate) fn find_expired_segments(&self, expired_duration: &Duration) -> Vec<Offset> {
         self.segments
             .iter()
@@ -442,6 +518,11 @@ mod tests {
         assert_eq!(list.find_segment(8000).expect("segment").0, &4000);
         assert!(list.find_segment(9000).is_none());
         assert!(list.find_segment(10000).is_none());
+
+        let next_base = |base| list.next_segment(base).map(ReadSegment::get_base_offset);
+        assert_eq!(next_base(100), Some(600));
+        assert_eq!(next_base(600), Some(4000));
+        assert_eq!(next_base(4000), None);
     }
 
     #[fluvio_future::test]
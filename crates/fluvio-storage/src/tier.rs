@@ -0,0 +1,783 @@
+//! Tiered storage of replicas
+//!
+//! Sealed segments of a tiered replica are uploaded to the [`ObjectStore`] of its tier
+//! location as `{topic}-{partition}/{base_offset}-{end_offset}.index` and `.log`, the log
+//! last so a listed log is always complete. Local copies are removed once they are uploaded,
+//! the sizes of the remote objects match them and they are older than the hot window.
+//! Remote segments are removed once they are older than the retention.
+//!
+//! Reads below the local segments go through the shared [`BlockCache`]: the batch is located
+//! with range reads of the remote index and batch headers, and only the bytes of the slice
+//! are fetched. Each slice is written to a file of its own under the replica directory,
+//! unlinked as soon as it is opened, so it stays readable until its [`SliceLease`] is dropped.
+//!
+//! Every replica of a partition uploads its segments. Replicas rolling over at the same
+//! offsets upload the same objects, while a segment of a replica that rolled elsewhere has
+//! keys of its own, so it never replaces the segment of another replica. Remote segments are
+//! kept when the replica is removed.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fs::File;
+use std::io::{Cursor, Error as IoError, ErrorKind};
+use std::os::unix::io::AsRawFd;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use async_lock::{Mutex, RwLock};
+use async_trait::async_trait;
+use bytes::Bytes;
+use tracing::{debug, error, info, instrument};
+
+use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_future::fs::{create_dir_all, remove_dir_all};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, Offset, Size, BATCH_FILE_HEADER_SIZE, BATCH_PREAMBLE_SIZE};
+use fluvio_types::event::StickyEvent;
+
+use crate::SliceLease;
+use crate::batch_header::FileEmptyRecords;
+use crate::block_cache::{BlockCache, BlockCacheConfig, RangeSource};
+use crate::config::{SharedReplicaConfig, StorageConfig};
+use crate::index::{EXTENSION as INDEX_EXTENSION, INDEX_ENTRY_SIZE};
+use crate::object_store::{open_object_store, ObjectStore};
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::replica::ReplicaSize;
+use crate::segments::SharedSegments;
+use crate::util::generate_file_name;
+
+/// directory of the replica the slices of remote segments are written to
+const CACHE_DIR: &str = ".tier-cache";
+/// remote segments whose sizes are kept, their bytes are in the block cache
+const CACHE_SEGMENTS: usize = 4;
+
+/// Offloads the sealed segments of a replica and reads them back.
+/// Like the [`crate::cleaner::Cleaner`], this runs a background task per replica.
+#[derive(Debug)]
+pub(crate) struct Tier {
+    store: Arc<dyn ObjectStore>,
+    location: String,
+    /// key prefix of the replica objects
+    prefix: String,
+    config: Arc<StorageConfig>,
+    replica_config: Arc<SharedReplicaConfig>,
+    segments: Arc<SharedSegments>,
+    replica_size: Arc<ReplicaSize>,
+    /// base and end offsets of the remote segments
+    remote: RwLock<BTreeSet<Span>>,
+    min_offset: AtomicI64,
+    /// remote segments read lately
+    segments_read: Mutex<VecDeque<Arc<RemoteSegment>>>,
+    block_cache: Arc<BlockCache>,
+    cache_dir: PathBuf,
+    slice_id: AtomicU64,
+    end_event: Arc<StickyEvent>,
+}
+
+/// base offset and end offset of a segment, the end is the offset after its last record
+type Span = (Offset, Offset);
+
+/// Segment in the object store, read by ranges
+struct RemoteSegment {
+    span: Span,
+    index: RemoteObject,
+    log: RemoteObject,
+}
+
+struct RemoteObject {
+    store: Arc<dyn ObjectStore>,
+    key: String,
+    /// key in the block cache, shared by the replicas of every tier location
+    cache_key: String,
+    len: u64,
+}
+
+#[async_trait]
+impl RangeSource for RemoteObject {
+    fn len(&self) -> u64 {
+        self.len
+    }
+
+    async fn read_range(&self, position: u64, len: u64) -> Result<Bytes, IoError> {
+        self.store
+            .get_range(&self.key, position, len)
+            .await
+            .map_err(|err| IoError::new(ErrorKind::Other, format!("{}: {err}", self.key)))
+    }
+}
+
+impl Tier {
+    pub(crate) async fn start_new(
+        location: &str,
+        replica_name: &str,
+        config: Arc<StorageConfig>,
+        replica_config: Arc<SharedReplicaConfig>,
+        segments: Arc<SharedSegments>,
+        replica_size: Arc<ReplicaSize>,
+    ) -> Result<Arc<Self>> {
+        let store = open_object_store(location)?;
+        let cache_dir = replica_config.base_dir.join(CACHE_DIR);
+        if cache_dir.exists() {
+            remove_dir_all(&cache_dir).await?;
+        }
+        create_dir_all(&cache_dir).await?;
+
+        let tier = Arc::new(Tier {
+            store,
+            location: location.trim_end_matches('/').to_owned(),
+            prefix: format!("{replica_name}/"),
+            config,
+            replica_config,
+            segments,
+            replica_size,
+            remote: RwLock::new(BTreeSet::new()),
+            min_offset: AtomicI64::new(-1),
+            segments_read: Mutex::new(VecDeque::new()),
+            block_cache: BlockCache::shared(BlockCacheConfig {
+                capacity: replica_config.tier_cache_bytes.get(),
+                ..Default::default()
+            }),
+            cache_dir,
+            slice_id: AtomicU64::new(0),
+            end_event: StickyEvent::shared(),
+        });
+        if let Err(err) = tier.list_remote().await {
+            error!(location, %err, "unable to list remote segments");
+        }
+
+        let tier_ref = tier.clone();
+        spawn(async move {
+            tier_ref.offload().await;
+        });
+        Ok(tier)
+    }
+
+    pub(crate) fn shutdown(&self) {
+        self.end_event.notify();
+    }
+
+    /// smallest base offset of the remote segments, -1 if there are none
+    pub(crate) fn min_offset(&self) -> Offset {
+        self.min_offset.load(Ordering::SeqCst)
+    }
+
+    #[instrument(skip(self), fields(prefix = %self.prefix))]
+    async fn offload(&self) {
+        use tokio::select;
+
+        let sleep_period = Duration::from_millis(self.config.cleaning_interval_ms as u64);
+
+        loop {
+            if self.end_event.is_set() {
+                info!("tier is terminated");
+                break;
+            }
+
+            select! {
+                _ = self.end_event.listen() => {
+                    info!("tier end event received");
+                    break;
+                },
+                _ = sleep(sleep_period) => {
+                    if let Err(err) = self.upload_segments().await {
+                        error!(%err, "segment upload failed");
+                    }
+                    self.enforce_hot_window().await;
+                    if let Err(err) = self.enforce_remote_ttl().await {
+                        error!(%err, "remote segment removal failed");
+                    }
+                }
+            }
+        }
+
+        info!("tier end");
+    }
+
+    async fn list_remote(&self) -> Result<Vec<(Span, SystemTime)>> {
+        let mut listed = Vec::new();
+        for object in self.store.list(&self.prefix).await? {
+            if let Some(span) = object_span(&object.key) {
+                listed.push((span, object.last_modified));
+            }
+        }
+        let mut remote = self.remote.write().await;
+        *remote = listed.iter().map(|(span, _)| *span).collect();
+        self.update_min_offset(&remote);
+        Ok(listed)
+    }
+
+    fn object_key(&self, (base_offset, end_offset): Span, extension: &str) -> String {
+        format!(
+            "{}{base_offset:020}-{end_offset:020}.{extension}",
+            self.prefix
+        )
+    }
+
+    fn update_min_offset(&self, remote: &BTreeSet<Span>) {
+        let min_offset = remote.first().map_or(-1, |(base_offset, _)| *base_offset);
+        self.min_offset.store(min_offset, Ordering::SeqCst);
+    }
+
+    /// spans of the local segments
+    async fn local_spans(&self) -> Vec<Span> {
+        self.segments
+            .read()
+            .await
+            .iter()
+            .map(|segment| (segment.get_base_offset(), segment.get_end_offset()))
+            .collect()
+    }
+
+    /// upload the sealed segments which are not remote yet
+    #[instrument(skip(self))]
+    async fn upload_segments(&self) -> Result<()> {
+        'segments: for span in self.local_spans().await {
+            if self.remote.read().await.contains(&span) {
+                continue;
+            }
+            let (base_offset, _) = span;
+            for extension in [INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+                let path =
+                    generate_file_name(&self.replica_config.base_dir, base_offset, extension);
+                match self
+                    .store
+                    .put_file(&self.object_key(span, extension), &path)
+                    .await
+                {
+                    Ok(()) => {}
+                    // removed by the cleaner in the meantime
+                    Err(err)
+                        if err
+                            .downcast_ref::<IoError>()
+                            .is_some_and(|err| err.kind() == ErrorKind::NotFound) =>
+                    {
+                        continue 'segments
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            info!(base_offset, end_offset = span.1, "segment uploaded");
+            let mut remote = self.remote.write().await;
+            remote.insert(span);
+            self.update_min_offset(&remote);
+        }
+        Ok(())
+    }
+
+    /// remove the local copies of uploaded segments older than the hot window
+    #[instrument(skip(self))]
+    async fn enforce_hot_window(&self) {
+        let hot_window =
+            Duration::from_secs(self.replica_config.tier_hot_window_seconds.get() as u64);
+        let expired = self
+            .segments
+            .read()
+            .await
+            .find_expired_segments(&hot_window);
+        let spans = self.local_spans().await;
+        let mut offloaded = Vec::new();
+        for span in spans
+            .into_iter()
+            .filter(|(base_offset, _)| expired.contains(base_offset))
+        {
+            if !self.remote.read().await.contains(&span) {
+                continue;
+            }
+            match self.is_offloaded(span).await {
+                Ok(true) => offloaded.push(span.0),
+                Ok(false) => error!(
+                    base_offset = span.0,
+                    "remote segment differs from the local one, keeping it"
+                ),
+                Err(err) => error!(base_offset = span.0, %err, "unable to check remote segment"),
+            }
+        }
+        debug!(
+            seconds = hot_window.as_secs(),
+            offloaded = offloaded.len(),
+            "segments past hot window"
+        );
+        if !offloaded.is_empty() {
+            self.segments.remove_segments(&offloaded).await;
+            let read = self.segments.read().await;
+            self.replica_size.store_prev(read.occupied_memory());
+        }
+    }
+
+    /// the remote objects of `span` have the sizes of the local files
+    async fn is_offloaded(&self, span: Span) -> Result<bool> {
+        for extension in [INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+            let path = generate_file_name(&self.replica_config.base_dir, span.0, extension);
+            let local_len = std::fs::metadata(&path)?.len();
+            if self.store.size(&self.object_key(span, extension)).await? != local_len {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// remove the remote segments uploaded before the retention
+    #[instrument(skip(self))]
+    async fn enforce_remote_ttl(&self) -> Result<()> {
+        let retention = Duration::from_secs(self.replica_config.retention_seconds.get() as u64);
+        let listed = self.list_remote().await?;
+        for (span, last_modified) in listed {
+            let expired = last_modified
+                .elapsed()
+                .map(|elapsed| elapsed > retention)
+                .unwrap_or(false);
+            if !expired {
+                continue;
+            }
+            info!(base_offset = span.0, "removing remote segment");
+            // the log first, a remote segment is only listed by its log
+            for extension in [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION] {
+                self.store.delete(&self.object_key(span, extension)).await?;
+            }
+            let mut remote = self.remote.write().await;
+            remote.remove(&span);
+            self.update_min_offset(&remote);
+            drop(remote);
+            self.segments_read
+                .lock()
+                .await
+                .retain(|segment| segment.span != span);
+        }
+        Ok(())
+    }
+
+    /// find slice of at most `max_len` bytes in the remote segment of `start_offset`.
+    /// Remote segments are sealed, so they end before any max offset of a read.
+    #[instrument(skip(self))]
+    pub(crate) async fn find_slice(
+        &self,
+        start_offset: Offset,
+        max_len: u32,
+    ) -> Result<Option<(AsyncFileSlice, SliceLease)>, ErrorCode> {
+        // the closest segment holding the offset, segments of replicas may overlap
+        let Some(span) = self
+            .remote
+            .read()
+            .await
+            .range(..=(start_offset, Offset::MAX))
+            .rev()
+            .find(|(_, end_offset)| *end_offset > start_offset)
+            .copied()
+        else {
+            return Ok(None);
+        };
+        let base_offset = span.0;
+        let remote_error =
+            |err: anyhow::Error| ErrorCode::Other(format!("remote segment {base_offset}: {err}"));
+
+        let segment = self.remote_segment(span).await.map_err(remote_error)?;
+        let Some(position) = self
+            .find_position(&segment, start_offset)
+            .await
+            .map_err(remote_error)?
+        else {
+            return Ok(None);
+        };
+        let len = (max_len as u64).min(segment.log.len - position);
+        let bytes = self
+            .block_cache
+            .read(&segment.log.cache_key, &segment.log, position, len)
+            .await
+            .map_err(|err| remote_error(err.into()))?;
+        let slice = self
+            .slice_file(base_offset, position, bytes)
+            .await
+            .map_err(remote_error)?;
+        Ok(Some(slice))
+    }
+
+    /// the sizes of the segment objects are looked up without holding the segments read,
+    /// so reads of other segments don't wait for the store
+    async fn remote_segment(&self, span: Span) -> Result<Arc<RemoteSegment>> {
+        if let Some(segment) = self
+            .segments_read
+            .lock()
+            .await
+            .iter()
+            .find(|segment| segment.span == span)
+        {
+            return Ok(segment.clone());
+        }
+
+        debug!(base_offset = span.0, "opening remote segment");
+        let segment = Arc::new(RemoteSegment {
+            span,
+            index: self.remote_object(span, INDEX_EXTENSION).await?,
+            log: self.remote_object(span, MESSAGE_LOG_EXTENSION).await?,
+        });
+        let mut segments_read = self.segments_read.lock().await;
+        if !segments_read.iter().any(|read| read.span == span) {
+            segments_read.push_back(segment.clone());
+            if segments_read.len() > CACHE_SEGMENTS {
+                segments_read.pop_front();
+            }
+        }
+        Ok(segment)
+    }
+
+    async fn remote_object(&self, span: Span, extension: &str) -> Result<RemoteObject> {
+        let key = self.object_key(span, extension);
+        let len = self.store.size(&key).await?;
+        Ok(RemoteObject {
+            store: self.store.clone(),
+            cache_key: format!("{}/{key}", self.location),
+            key,
+            len,
+        })
+    }
+
+    /// position of the batch holding `offset`, from the index entry before it and the
+    /// headers of the following batches
+    async fn find_position(&self, segment: &RemoteSegment, offset: Offset) -> Result<Option<u64>> {
+        let Ok(relative_offset) = Size::try_from(offset - segment.span.0) else {
+            return Ok(None);
+        };
+        let mut position = self.index_position(&segment.index, relative_offset).await?;
+        let log = &segment.log;
+        while position + BATCH_FILE_HEADER_SIZE as u64 <= log.len {
+            let header = self
+                .block_cache
+                .read(&log.cache_key, log, position, BATCH_FILE_HEADER_SIZE as u64)
+                .await?;
+            let mut batch = Batch::<FileEmptyRecords>::default();
+            batch.decode_from_file_buf(&mut Cursor::new(header), 0)?;
+            if batch.get_last_offset() >= offset {
+                return Ok(Some(position));
+            }
+            position += BATCH_PREAMBLE_SIZE as u64 + batch.batch_len as u64;
+        }
+        Ok(None)
+    }
+
+    /// position of the last index entry at or before `relative_offset`, 0 if there is none.
+    /// Entries are pairs of big endian relative offset and position, sorted by offset.
+    async fn index_position(&self, index: &RemoteObject, relative_offset: Size) -> Result<u64> {
+        let (mut low, mut high) = (0, index.len / INDEX_ENTRY_SIZE);
+        let mut position = 0;
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let entry = self
+                .block_cache
+                .read(
+                    &index.cache_key,
+                    index,
+                    middle * INDEX_ENTRY_SIZE,
+                    INDEX_ENTRY_SIZE,
+                )
+                .await?;
+            let entry_offset = Size::from_be_bytes(entry[0..4].try_into()?);
+            if entry_offset <= relative_offset {
+                position = Size::from_be_bytes(entry[4..8].try_into()?);
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        Ok(position as u64)
+    }
+
+    async fn slice_file(
+        &self,
+        base_offset: Offset,
+        position: u64,
+        bytes: Bytes,
+    ) -> Result<(AsyncFileSlice, SliceLease)> {
+        let id = self.slice_id.fetch_add(1, Ordering::Relaxed);
+        let path = self
+            .cache_dir
+            .join(format!("{base_offset:020}-{position}-{id}.slice"));
+        let len = bytes.len() as u64;
+        let file = blocking::unblock(move || {
+            std::fs::write(&path, &bytes)?;
+            let file = File::open(&path)?;
+            std::fs::remove_file(&path)?;
+            Ok::<_, IoError>(file)
+        })
+        .await?;
+        let slice = AsyncFileSlice::new(file.as_raw_fd(), 0, len);
+        Ok((slice, SliceLease::new(Arc::new(file))))
+    }
+}
+
+/// span of the key of a remote log, `{base_offset}-{end_offset}.log`
+fn object_span(key: &str) -> Option<Span> {
+    let stem = key
+        .rsplit('/')
+        .next()?
+        .strip_suffix(MESSAGE_LOG_EXTENSION)?
+        .strip_suffix('.')?;
+    let (base_offset, end_offset) = stem.split_once('-')?;
+    Some((base_offset.parse().ok()?, end_offset.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+    use std::sync::Arc;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::fixture::create_batch;
+
+    use crate::config::{ReplicaConfig, StorageConfig};
+    use crate::iterators::FileBatchIterator;
+    use crate::replica::ReplicaSize;
+    use crate::segment::MutableSegment;
+    use crate::segments::{SegmentList, SharedSegments};
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_tier_offload_and_fetch() {
+        let rep_dir = temp_dir().join("tier-offload");
+        let remote_dir = temp_dir().join("tier-offload-remote");
+        ensure_new_dir(&rep_dir).expect("new");
+        ensure_new_dir(&remote_dir).expect("new");
+
+        let config = ReplicaConfig {
+            base_dir: rep_dir,
+            tier: Some(format!("file://{}", remote_dir.display())),
+            tier_hot_window_seconds: 0,
+            ..Default::default()
+        };
+        let option = config.shared();
+        let segments = SharedSegments::from(SegmentList::new());
+        for base_offset in [0, 2] {
+            let mut segment = MutableSegment::create(base_offset, option.clone())
+                .await
+                .expect("create");
+            segment
+                .append_batch(&mut create_batch())
+                .await
+                .expect("append");
+            segments
+                .add_segment(segment.convert_to_segment().await.expect("convert"))
+                .await;
+        }
+
+        let tier = Tier::start_new(
+            option.tier.as_deref().unwrap(),
+            "orders-0",
+            Arc::new(StorageConfig::builder().build().expect("config")),
+            option.clone(),
+            segments.clone(),
+            Arc::new(ReplicaSize::default()),
+        )
+        .await
+        .expect("tier");
+        tier.shutdown();
+        assert_eq!(tier.min_offset(), -1);
+
+        tier.upload_segments().await.expect("upload");
+        assert_eq!(tier.min_offset(), 0);
+        assert!(remote_dir
+            .join("orders-0")
+            .join("00000000000000000002-00000000000000000004.log")
+            .exists());
+
+        tier.enforce_hot_window().await;
+        assert_eq!(segments.read().await.len(), 0);
+        assert!(segments.find_slice(2, None).await.expect("local").is_none());
+
+        let (slice, lease) = tier
+            .find_slice(3, u32::MAX)
+            .await
+            .expect("remote")
+            .expect("slice");
+        let batches: Vec<_> = FileBatchIterator::from_raw_slice(slice)
+            .collect::<Result<_, _>>()
+            .expect("batches");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].batch.base_offset, 2);
+        assert_eq!(tier.segments_read.lock().await.len(), 1);
+        // slices are unlinked once opened, the lease keeps them readable
+        assert_eq!(std::fs::read_dir(&tier.cache_dir).expect("dir").count(), 0);
+        drop(lease);
+
+        let (slice, _lease) = tier
+            .find_slice(0, 10)
+            .await
+            .expect("remote")
+            .expect("slice");
+        assert_eq!(slice.len(), 10);
+        assert!(tier
+            .find_slice(-1, u32::MAX)
+            .await
+            .expect("remote")
+            .is_none());
+        assert!(tier
+            .find_slice(4, u32::MAX)
+            .await
+            .expect("remote")
+            .is_none());
+
+        // a restarted replica lists what was uploaded
+        let restarted = Tier::start_new(
+            option.tier.as_deref().unwrap(),
+            "orders-0",
+            Arc::new(StorageConfig::builder().build().expect("config")),
+            option,
+            segments,
+            Arc::new(ReplicaSize::default()),
+        )
+        .await
+        .expect("tier");
+        restarted.shutdown();
+        assert_eq!(restarted.min_offset(), 0);
+        assert_eq!(
+            *restarted.remote.read().await,
+            BTreeSet::from([(0, 2), (2, 4)])
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_tier_reads_through_block_cache() {
+        let rep_dir = temp_dir().join("tier-block-cache");
+        let remote_dir = temp_dir().join("tier-block-cache-remote");
+        ensure_new_dir(&rep_dir).expect("new");
+        ensure_new_dir(&remote_dir).expect("new");
+
+        let config = ReplicaConfig {
+            base_dir: rep_dir,
+            tier: Some(format!("file://{}", remote_dir.display())),
+            tier_hot_window_seconds: 0,
+            ..Default::default()
+        };
+        let option = config.shared();
+        let segments = SharedSegments::from(SegmentList::new());
+        let mut segment = MutableSegment::create(0, option.clone())
+            .await
+            .expect("create");
+        for _ in 0..3 {
+            segment
+                .append_batch(&mut create_batch())
+                .await
+                .expect("append");
+        }
+        segments
+            .add_segment(segment.convert_to_segment().await.expect("convert"))
+            .await;
+
+        let tier = Tier::start_new(
+            option.tier.as_deref().unwrap(),
+            "payments-0",
+            Arc::new(StorageConfig::builder().build().expect("config")),
+            option,
+            segments.clone(),
+            Arc::new(ReplicaSize::default()),
+        )
+        .await
+        .expect("tier");
+        tier.shutdown();
+        tier.upload_segments().await.expect("upload");
+        tier.enforce_hot_window().await;
+
+        let (slice, _lease) = tier
+            .find_slice(2, u32::MAX)
+            .await
+            .expect("remote")
+            .expect("slice");
+        let batches: Vec<_> = FileBatchIterator::from_raw_slice(slice)
+            .collect::<Result<_, _>>()
+            .expect("batches");
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].batch.base_offset, 2);
+        assert!(tier.block_cache.size().await > 0);
+
+        // the blocks of the segment are cached, later reads don't reach the store
+        std::fs::remove_dir_all(remote_dir.join("payments-0")).expect("remove");
+        let (slice, _lease) = tier
+            .find_slice(4, u32::MAX)
+            .await
+            .expect("cached")
+            .expect("slice");
+        let batches: Vec<_> = FileBatchIterator::from_raw_slice(slice)
+            .collect::<Result<_, _>>()
+            .expect("batches");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].batch.base_offset, 4);
+    }
+
+    #[fluvio_future::test]
+    async fn test_tier_keeps_segments_differing_remotely() {
+        let rep_dir = temp_dir().join("tier-remote-size");
+        let remote_dir = temp_dir().join("tier-remote-size-remote");
+        ensure_new_dir(&rep_dir).expect("new");
+        ensure_new_dir(&remote_dir).expect("new");
+
+        let config = ReplicaConfig {
+            base_dir: rep_dir,
+            tier: Some(format!("file://{}", remote_dir.display())),
+            tier_hot_window_seconds: 0,
+            ..Default::default()
+        };
+        let option = config.shared();
+        let segments = SharedSegments::from(SegmentList::new());
+        let mut segment = MutableSegment::create(0, option.clone())
+            .await
+            .expect("create");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("append");
+        segments
+            .add_segment(segment.convert_to_segment().await.expect("convert"))
+            .await;
+
+        let tier = Tier::start_new(
+            option.tier.as_deref().unwrap(),
+            "events-0",
+            Arc::new(StorageConfig::builder().build().expect("config")),
+            option,
+            segments.clone(),
+            Arc::new(ReplicaSize::default()),
+        )
+        .await
+        .expect("tier");
+        tier.shutdown();
+        tier.upload_segments().await.expect("upload");
+
+        // another replica which rolled over elsewhere uploads a segment of its own
+        let other_log = remote_dir
+            .join("events-0")
+            .join("00000000000000000000-00000000000000000001.log");
+        std::fs::write(&other_log, b"other").expect("write");
+        tier.list_remote().await.expect("list");
+        assert_eq!(*tier.remote.read().await, BTreeSet::from([(0, 1), (0, 2)]));
+
+        // a truncated upload keeps the local copy
+        let log = remote_dir
+            .join("events-0")
+            .join("00000000000000000000-00000000000000000002.log");
+        let uploaded = std::fs::read(&log).expect("read");
+        std::fs::write(&log, &uploaded[..10]).expect("write");
+        tier.enforce_hot_window().await;
+        assert_eq!(segments.read().await.len(), 1);
+
+        std::fs::write(&log, &uploaded).expect("write");
+        tier.enforce_hot_window().await;
+        assert_eq!(segments.read().await.len(), 0);
+    }
+
+    #[test]
+    fn test_object_span() {
+        assert_eq!(
+            object_span("orders-0/00000000000000000002-00000000000000000004.log"),
+            Some((2, 4))
+        );
+        assert_eq!(
+            object_span("orders-0/00000000000000000002-00000000000000000004.index"),
+            None
+        );
+        assert_eq!(object_span("orders-0/00000000000000000002.log"), None);
+    }
+}
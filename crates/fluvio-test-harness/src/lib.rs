@@ -0,0 +1,268 @@
+//!
+//! # Fluvio Test Harness
+//!
+//! Runs integration tests of applications built on Fluvio against a local cluster.
+//! The cluster is started with the first test that needs it and removed once the last
+//! running test is done. A cluster that is already reachable with the current profile is
+//! used as is and never removed. Each test creates its topics with a unique name, they
+//! are deleted when the test ends.
+//!
+//! ```no_run
+//! use fluvio::RecordKey;
+//! use fluvio_test_harness::TestCluster;
+//!
+//! #[fluvio_future::test]
+//! async fn produce_and_consume() -> anyhow::Result<()> {
+//!     TestCluster::builder()
+//!         .partitions(2)
+//!         .run(|cluster| async move {
+//!             let topic = cluster.topic("orders").await?;
+//!             let producer = cluster.fluvio().topic_producer(&topic).await?;
+//!             producer.send(RecordKey::NULL, "order-1").await?;
+//!             producer.flush().await?;
+//!             Ok(())
+//!         })
+//!         .await
+//! }
+//! ```
+//!
+//! Tests running at the same time share the cluster, the settings of the test
+//! that starts it apply.
+//!
+
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_lock::Mutex;
+use once_cell::sync::Lazy;
+use tracing::{debug, info, warn};
+
+use fluvio::metadata::topic::TopicSpec;
+use fluvio::{Fluvio, FluvioConfig};
+use fluvio_cluster::{ClusterUninstallConfig, InstallationType, LocalConfig, LocalInstaller};
+
+const DEFAULT_SPUS: u16 = 1;
+const DEFAULT_PARTITIONS: u32 = 1;
+const DEFAULT_REPLICAS: u32 = 1;
+
+/// topic names are valid Kubernetes object names
+const MAX_TOPIC_NAME_LEN: usize = 63;
+/// length of the random suffix of topic names
+const TOPIC_SUFFIX_LEN: usize = 8;
+
+static VERSION: &str = include_str!("../../../VERSION");
+
+static CLUSTER: Lazy<Mutex<SharedCluster>> = Lazy::new(|| Mutex::new(SharedCluster::default()));
+
+/// Cluster used by the tests of this process
+#[derive(Default)]
+struct SharedCluster {
+    running: Option<Running>,
+    /// tests using the cluster
+    users: usize,
+}
+
+enum Running {
+    /// reachable with the current profile before the first test
+    External,
+    /// started by the harness
+    Installed(FluvioConfig),
+}
+
+/// Settings of the cluster and of the topics of a test
+#[derive(Debug, Clone)]
+pub struct TestClusterBuilder {
+    spus: u16,
+    partitions: u32,
+    replicas: u32,
+    use_running: bool,
+}
+
+impl Default for TestClusterBuilder {
+    fn default() -> Self {
+        Self {
+            spus: DEFAULT_SPUS,
+            partitions: DEFAULT_PARTITIONS,
+            replicas: DEFAULT_REPLICAS,
+            use_running: true,
+        }
+    }
+}
+
+impl TestClusterBuilder {
+    /// number of SPUs of a started cluster
+    pub fn spus(mut self, spus: u16) -> Self {
+        self.spus = spus;
+        self
+    }
+
+    /// partitions of the topics created by the test
+    pub fn partitions(mut self, partitions: u32) -> Self {
+        self.partitions = partitions;
+        self
+    }
+
+    /// replicas of the topics created by the test
+    pub fn replicas(mut self, replicas: u32) -> Self {
+        self.replicas = replicas;
+        self
+    }
+
+    /// Use a cluster reachable with the current profile instead of starting one, true by default
+    pub fn use_running(mut self, use_running: bool) -> Self {
+        self.use_running = use_running;
+        self
+    }
+
+    /// Connect to the cluster, starting it if no test is using it
+    pub async fn start(self) -> Result<TestCluster> {
+        let fluvio = {
+            let mut shared = CLUSTER.lock().await;
+            if shared.running.is_none() {
+                shared.running = Some(self.start_cluster().await?);
+            }
+            let fluvio = match &shared.running {
+                Some(Running::Installed(config)) => Fluvio::connect_with_config(config).await,
+                _ => Fluvio::connect().await,
+            }
+            .context("failed to connect to the test cluster")?;
+            shared.users += 1;
+            fluvio
+        };
+        Ok(TestCluster {
+            fluvio: Arc::new(fluvio),
+            topics: Arc::new(Mutex::new(Vec::new())),
+            partitions: self.partitions,
+            replicas: self.replicas,
+        })
+    }
+
+    /// Run `test` on the cluster, removing its topics afterwards even if the test fails
+    pub async fn run<F, Fut, T>(self, test: F) -> Result<T>
+    where
+        F: FnOnce(TestCluster) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let cluster = self.start().await?;
+        let result = test(cluster.clone()).await;
+        let teardown = cluster.teardown().await;
+        let value = result?;
+        teardown?;
+        Ok(value)
+    }
+
+    async fn start_cluster(&self) -> Result<Running> {
+        if self.use_running && Fluvio::connect().await.is_ok() {
+            info!("using running cluster");
+            return Ok(Running::External);
+        }
+
+        let version = semver::Version::parse(VERSION.trim())?;
+        let config = LocalConfig::builder(version)
+            .spu_replicas(self.spus)
+            .installation_type(InstallationType::Local)
+            .build()?;
+        info!(spus = self.spus, "starting test cluster");
+        let status = LocalInstaller::from_config(config)
+            .install()
+            .await
+            .context("failed to start the test cluster")?;
+        Ok(Running::Installed(FluvioConfig::new(status.address())))
+    }
+}
+
+/// Connection to the test cluster, created per test
+#[derive(Clone)]
+pub struct TestCluster {
+    fluvio: Arc<Fluvio>,
+    /// topics created by the test, deleted on teardown
+    topics: Arc<Mutex<Vec<String>>>,
+    partitions: u32,
+    replicas: u32,
+}
+
+impl TestCluster {
+    pub fn builder() -> TestClusterBuilder {
+        TestClusterBuilder::default()
+    }
+
+    pub fn fluvio(&self) -> &Fluvio {
+        &self.fluvio
+    }
+
+    /// Create a topic named `prefix` followed by a random suffix, returns its name
+    pub async fn topic(&self, prefix: &str) -> Result<String> {
+        let name = unique_topic_name(prefix);
+        let spec = TopicSpec::new_computed(self.partitions, self.replicas, None);
+        self.fluvio
+            .admin()
+            .await
+            .create(name.clone(), false, spec)
+            .await
+            .with_context(|| format!("failed to create topic {name}"))?;
+        debug!(%name, "created test topic");
+        self.topics.lock().await.push(name.clone());
+        Ok(name)
+    }
+
+    /// Delete the topics of the test, the last test using a started cluster removes it
+    pub async fn teardown(self) -> Result<()> {
+        let topics = std::mem::take(&mut *self.topics.lock().await);
+        let admin = self.fluvio.admin().await;
+        for topic in topics {
+            if let Err(err) = admin.delete::<TopicSpec>(topic.clone()).await {
+                warn!(%topic, %err, "failed to delete test topic");
+            }
+        }
+
+        let mut shared = CLUSTER.lock().await;
+        shared.users = shared.users.saturating_sub(1);
+        if shared.users > 0 {
+            return Ok(());
+        }
+        if let Some(Running::Installed(_)) = shared.running.take() {
+            info!("removing test cluster");
+            ClusterUninstallConfig::builder()
+                .build()?
+                .uninstaller()?
+                .uninstall()
+                .await
+                .context("failed to remove the test cluster")?;
+        }
+        Ok(())
+    }
+}
+
+/// topic names are lowercase alphanumerics and '-', at most 63 characters
+fn unique_topic_name(prefix: &str) -> String {
+    let prefix: String = prefix
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .take(MAX_TOPIC_NAME_LEN - TOPIC_SUFFIX_LEN - 1)
+        .collect();
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!("{prefix}-{}", &suffix[..TOPIC_SUFFIX_LEN])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_topic_name() {
+        let name = unique_topic_name("My_Orders");
+        assert!(name.starts_with("my-orders-"), "{name}");
+        assert_eq!(name.len(), "my-orders-".len() + TOPIC_SUFFIX_LEN);
+        assert_ne!(name, unique_topic_name("My_Orders"));
+
+        let long = unique_topic_name(&"a".repeat(100));
+        assert_eq!(long.len(), MAX_TOPIC_NAME_LEN);
+    }
+}
@@ -2,12 +2,13 @@ use std::fmt::Debug;
 use std::io::Error as IoError;
 use std::io::ErrorKind;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs::{File, read_to_string};
 
 use tracing::debug;
 use serde::{Serialize, de::DeserializeOwned};
 use thiserror::Error;
+use toml::{Table, Value};
 
 #[derive(Debug, Error)]
 pub enum LoadConfigError {
@@ -62,3 +63,218 @@ where
         Ok(config)
     }
 }
+
+#[derive(Debug, Error)]
+pub enum LayeredConfigError {
+    #[error("unable to read config file {}: {source}", path.display())]
+    Read { path: PathBuf, source: IoError },
+    #[error("invalid config file {}: {source}", path.display())]
+    File {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("invalid {prefix}* environment variables: {source}")]
+    Env {
+        prefix: String,
+        source: toml::de::Error,
+    },
+    #[error("environment variable {0} conflicts with another one")]
+    EnvConflict(String),
+}
+
+/// Options of a config file, if any, overridden by the environment variables starting
+/// with `prefix`.
+///
+/// `{prefix}LOG_SIZE` sets the `log_size` key and a double underscore descends into tables,
+/// `{prefix}STORAGE__SEGMENT_SIZE` sets `segment_size` of `[storage]`. Values are read as
+/// TOML so numbers, booleans and arrays keep their type, anything else is a string.
+/// Returns `None` when there is neither a file nor a variable.
+pub fn load_with_env_overrides<T: DeserializeOwned>(
+    path: Option<&Path>,
+    prefix: &str,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<Option<T>, LayeredConfigError> {
+    let mut table = match path {
+        Some(path) => {
+            let content = read_to_string(path).map_err(|source| LayeredConfigError::Read {
+                path: path.to_owned(),
+                source,
+            })?;
+            let file_error = |source| LayeredConfigError::File {
+                path: path.to_owned(),
+                source,
+            };
+            // errors of the file alone show the key and its line
+            toml::from_str::<T>(&content).map_err(file_error)?;
+            toml::from_str::<Table>(&content).map_err(file_error)?
+        }
+        None => Table::new(),
+    };
+
+    let overrides = env_overrides(prefix, vars)?;
+    if path.is_none() && overrides.is_empty() {
+        return Ok(None);
+    }
+    let env_error = |source| LayeredConfigError::Env {
+        prefix: prefix.to_owned(),
+        source,
+    };
+    Value::Table(overrides.clone())
+        .try_into::<T>()
+        .map_err(env_error)?;
+
+    merge_table(&mut table, overrides);
+    Value::Table(table).try_into().map(Some).map_err(env_error)
+}
+
+fn env_overrides(
+    prefix: &str,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<Table, LayeredConfigError> {
+    let mut overrides = Table::new();
+    for (name, value) in vars {
+        let Some(key) = name.strip_prefix(prefix).filter(|key| !key.is_empty()) else {
+            continue;
+        };
+        let mut keys: Vec<String> = key.split("__").map(str::to_lowercase).collect();
+        let last = keys.pop().unwrap_or_default();
+        let mut table = &mut overrides;
+        for key in keys {
+            table = table
+                .entry(key)
+                .or_insert_with(|| Value::Table(Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| LayeredConfigError::EnvConflict(name.clone()))?;
+        }
+        if table.get(&last).is_some_and(Value::is_table) {
+            return Err(LayeredConfigError::EnvConflict(name));
+        }
+        table.insert(last, parse_env_value(&value));
+    }
+    Ok(overrides)
+}
+
+fn parse_env_value(value: &str) -> Value {
+    toml::from_str::<Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(value.to_owned()))
+}
+
+/// tables are merged key by key, other values are replaced
+fn merge_table(base: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(value)) => merge_table(base, value),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct TestConfig {
+        log_size: Option<String>,
+        peer_max_bytes: Option<u32>,
+        emergency_retention: Option<bool>,
+        #[serde(default)]
+        white_list: Vec<String>,
+        storage: Option<TestStorage>,
+    }
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct TestStorage {
+        segment_size: Option<u64>,
+        dir: Option<String>,
+    }
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let dir = std::env::temp_dir().join(format!("fluvio-types-env-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let path = dir.join("config.toml");
+        std::fs::write(
+            &path,
+            "log_size = \"1G\"\npeer_max_bytes = 10\n[storage]\ndir = \"/data\"\n",
+        )
+        .expect("write");
+
+        let config: TestConfig = load_with_env_overrides(
+            Some(&path),
+            "FLV_TEST__",
+            vars(&[
+                ("FLV_TEST__PEER_MAX_BYTES", "20"),
+                ("FLV_TEST__EMERGENCY_RETENTION", "true"),
+                ("FLV_TEST__WHITE_LIST", "[\"spu\", \"topic\"]"),
+                ("FLV_TEST__STORAGE__SEGMENT_SIZE", "1024"),
+                ("OTHER", "1"),
+            ]),
+        )
+        .expect("config")
+        .expect("some");
+        assert_eq!(
+            config,
+            TestConfig {
+                log_size: Some("1G".to_owned()),
+                peer_max_bytes: Some(20),
+                emergency_retention: Some(true),
+                white_list: vec!["spu".to_owned(), "topic".to_owned()],
+                storage: Some(TestStorage {
+                    segment_size: Some(1024),
+                    dir: Some("/data".to_owned()),
+                }),
+            }
+        );
+
+        // values which are not TOML are strings
+        let config: TestConfig =
+            load_with_env_overrides(None, "FLV_TEST__", vars(&[("FLV_TEST__LOG_SIZE", "2G")]))
+                .expect("config")
+                .expect("some");
+        assert_eq!(config.log_size.as_deref(), Some("2G"));
+
+        assert!(
+            load_with_env_overrides::<TestConfig>(None, "FLV_TEST__", vars(&[]))
+                .expect("config")
+                .is_none()
+        );
+
+        let err = load_with_env_overrides::<TestConfig>(
+            None,
+            "FLV_TEST__",
+            vars(&[("FLV_TEST__LOG_DIR", "/tmp")]),
+        )
+        .expect_err("unknown key");
+        assert!(err.to_string().contains("FLV_TEST__*"), "{err}");
+        assert!(err.to_string().contains("log_dir"), "{err}");
+
+        let err = load_with_env_overrides::<TestConfig>(
+            None,
+            "FLV_TEST__",
+            vars(&[
+                ("FLV_TEST__STORAGE", "1"),
+                ("FLV_TEST__STORAGE__DIR", "/data"),
+            ]),
+        )
+        .expect_err("conflict");
+        assert!(matches!(err, LayeredConfigError::EnvConflict(_)), "{err}");
+
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+}
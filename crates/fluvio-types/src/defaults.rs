@@ -33,6 +33,11 @@ pub const SPU_CREDENTIALS_FILE: &str = "/etc/fluvio/.credentials/token_secret";
 pub const SPU_RETRY_SC_TIMEOUT_MS: u16 = 3000;
 pub const SPU_MIN_IN_SYNC_REPLICAS: u16 = 1;
 pub const SPU_LOG_BASE_DIR: &str = "/var/lib/fluvio/data";
+/// tmpfs on linux, other platforms have no memory backed file system by default
+#[cfg(target_os = "linux")]
+pub const SPU_EPHEMERAL_LOG_BASE_DIR: &str = "/dev/shm/fluvio";
+#[cfg(not(target_os = "linux"))]
+pub const SPU_EPHEMERAL_LOG_BASE_DIR: &str = "/tmp/fluvio-ephemeral";
 pub const SPU_LOG_SIZE: &str = "10Gi";
 pub const SPU_LOG_INDEX_MAX_BYTES: u32 = 10485760;
 pub const SPU_LOG_INDEX_MAX_INTERVAL_BYTES: u32 = 4096;
@@ -40,6 +45,7 @@ pub const SPU_LOG_SEGMENT_MAX_BYTES: u32 = 1073741824;
 pub const SPU_MONITORING_UNIX_SOCKET: &str = "/tmp/fluvio-spu.sock";
 
 pub const SPU_PARTITION_MAX_BYTES: u64 = 107_374_182_400; //100Gb
+pub const SPU_EPHEMERAL_PARTITION_MAX_BYTES: u64 = 1_073_741_824; //1Gb
 pub const SPU_PARTITION_MAX_BYTES_MIN: u64 = SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN as u64 * 2;
 
 pub const SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN: u32 = 1024; // crd
@@ -47,13 +53,27 @@ pub const SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN: u32 = 1024; // crd
 pub const STORAGE_RETENTION_SECONDS: u32 = 7 * 24 * 3600;
 
 pub const STORAGE_RETENTION_SECONDS_MIN: u32 = 10; // crd
+/// seconds tombstones of compacted topics are kept
+pub const STORAGE_COMPACT_DELETE_RETENTION_SECONDS: u32 = 24 * 3600;
 pub const STORAGE_FLUSH_WRITE_COUNT: u32 = 1;
 pub const STORAGE_FLUSH_IDLE_MSEC: u32 = 0;
 pub const STORAGE_MAX_BATCH_SIZE: u32 = 2_097_152;
 pub const STORAGE_MAX_REQUEST_SIZE: u32 = 33_554_432;
+pub const STORAGE_READ_AHEAD_BYTES: u32 = 8_388_608; //8Mb
+pub const STORAGE_TIER_HOT_WINDOW_SECONDS: u32 = 3600;
+pub const STORAGE_TIER_CACHE_BYTES: u64 = 268_435_456; //256Mb
 
 pub const SPU_SMARTENGINE_STORE_MAX_BYTES: usize = 1_073_741_824; //1Gb
 
+// SPU edge profile, for constrained devices such as ARM gateways
+pub const SPU_EDGE_LOG_INDEX_MAX_BYTES: u32 = 1_048_576; //1Mb
+pub const SPU_EDGE_LOG_SEGMENT_MAX_BYTES: u32 = 67_108_864; //64Mb
+pub const SPU_EDGE_PEER_MAX_BYTES: u32 = 262_144; //256Kb
+pub const SPU_EDGE_SMARTENGINE_STORE_MAX_BYTES: usize = 67_108_864; //64Mb
+pub const SPU_EDGE_EXECUTOR_THREADS: usize = 2;
+pub const SPU_EDGE_SMARTENGINE_WORKER_THREADS: usize = 1;
+pub const SPU_EDGE_READ_AHEAD_BYTES: u32 = 1_048_576; //1Mb
+
 pub const CONSUMER_STORAGE_TOPIC: &str = "consumer-offset";
 
 // CLI config
@@ -78,6 +98,7 @@ pub const FLV_SC_PRIVATE_PORT: &str = "FLV_SC_PRIVATE_PORT";
 pub const FLV_SC_RETRY_TIMEOUT_MS: &str = "FLV_SC_RETRY_TIMEOUT_MS";
 pub const FLV_REPLICA_IN_SYNC_REPLICA_MIN: &str = "FLV_REPLICA_IN_SYNC_REPLICA_MIN";
 pub const FLV_LOG_BASE_DIR: &str = "FLV_LOG_BASE_DIR";
+pub const FLV_EPHEMERAL_LOG_BASE_DIR: &str = "FLV_EPHEMERAL_LOG_BASE_DIR";
 pub const FLV_LOG_SIZE: &str = "FLV_LOG_SIZE";
 pub const FLV_LOG_INDEX_MAX_BYTES: &str = "FLV_LOG_INDEX_MAX_BYTES";
 pub const FLV_LOG_INDEX_MAX_INTERVAL_BYTES: &str = "FLV_LOG_INDEX_MAX_INTERVAL_BYTES";
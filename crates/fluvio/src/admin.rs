@@ -1,4 +1,3 @@
-use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::io::Error as IoError;
 use std::io::ErrorKind;
@@ -12,19 +11,22 @@ use fluvio_sc_schema::objects::UpdateRequest;
 use fluvio_sc_schema::UpdatableAdminSpec;
 use fluvio_protocol::{Decoder, Encoder};
 use fluvio_protocol::api::{Request, RequestMessage};
-use fluvio_future::net::DomainConnector;
 use fluvio_sc_schema::objects::{
     DeleteRequest, ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest,
     ObjectApiWatchRequest, Metadata, ListFilter, WatchRequest, WatchResponse, CreateRequest,
-    CommonCreateRequest,
+    CommonCreateRequest, BatchRequest, ObjectApiBatchRequest,
 };
 use fluvio_sc_schema::{AdminSpec, DeletableAdminSpec, CreatableAdminSpec, TryEncodableFrom};
 use fluvio_socket::{ClientConfig, VersionedSerialSocket, SerialFrame, MultiplexerSocket};
 
 use crate::FluvioConfig;
+use crate::cluster::{PartitionInfo, SpuInfo};
 use crate::config::ConfigFile;
 use crate::error::anyhow_version_error;
+use crate::fluvio::check_version_skew;
 use crate::metadata::objects::{ListResponse, ListRequest};
+use crate::metadata::partition::PartitionSpec;
+use crate::metadata::spu::SpuSpec;
 use crate::sync::MetadataStores;
 
 /// An interface for managing a Fluvio cluster
@@ -119,14 +121,16 @@ impl FluvioAdmin {
     /// ```
     #[instrument(skip(config))]
     pub async fn connect_with_config(config: &FluvioConfig) -> Result<Self> {
-        let connector = DomainConnector::try_from(config.tls.clone())?;
+        let connector = config.connector()?;
         let client_config =
             ClientConfig::new(&config.endpoint, connector, config.use_spu_local_address);
+        let skew_policy = config.version_skew_policy();
         let inner_client = client_config.connect().await?;
         debug!(addr = %inner_client.config().addr(), "connected to cluster");
 
         let (socket, config, versions) = inner_client.split();
         if let Some(watch_version) = versions.lookup_version::<ObjectApiWatchRequest>() {
+            check_version_skew(&versions, skew_policy)?;
             let socket = MultiplexerSocket::shared(socket);
             let metadata = MetadataStores::start(socket.clone(), watch_version).await?;
             let versioned_socket = VersionedSerialSocket::new(socket, config, versions);
@@ -189,6 +193,37 @@ impl FluvioAdmin {
         Ok(())
     }
 
+    /// Create every topic and SmartModule of the batch, or none of them
+    ///
+    /// For example, to provision a topic with the SmartModule validating its records:
+    ///
+    /// ```edition2021
+    /// use fluvio::Fluvio;
+    /// use fluvio::metadata::objects::BatchRequest;
+    /// use fluvio::metadata::smartmodule::SmartModuleSpec;
+    /// use fluvio::metadata::topic::TopicSpec;
+    ///
+    /// async fn provision(smartmodule: SmartModuleSpec, topic: TopicSpec) -> anyhow::Result<()> {
+    ///     let fluvio = Fluvio::connect().await?;
+    ///     let admin = fluvio.admin().await;
+    ///     let batch = BatchRequest::default()
+    ///         .with_smartmodule("validator", smartmodule)
+    ///         .with_topic("orders", topic);
+    ///     admin.create_batch(batch).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[instrument(skip(self, batch))]
+    pub async fn create_batch(&self, batch: BatchRequest) -> Result<()> {
+        debug!("sending batch request: {:#?}", batch);
+
+        self.send_receive_admin::<ObjectApiBatchRequest, _>(batch)
+            .await?
+            .as_result()?;
+
+        Ok(())
+    }
+
     /// Delete object by key
     /// key is dependent on spec, most are string but some allow multiple types
     ///
@@ -326,6 +361,41 @@ impl FluvioAdmin {
             .map(|out: ListResponse<S>| out.inner())
     }
 
+    /// SPUs of the cluster ordered by id
+    #[instrument(skip(self))]
+    pub async fn list_spus(&self) -> Result<Vec<SpuInfo>> {
+        let mut spus: Vec<SpuInfo> = self
+            .all::<SpuSpec>()
+            .await?
+            .into_iter()
+            .map(SpuInfo::from)
+            .collect();
+        spus.sort_by_key(|spu| spu.id);
+        Ok(spus)
+    }
+
+    /// Partition `partition` of `topic`, `None` if it does not exist
+    #[instrument(skip(self))]
+    pub async fn describe_partition(
+        &self,
+        topic: &str,
+        partition: u32,
+    ) -> Result<Option<PartitionInfo>> {
+        let name = format!("{topic}-{partition}");
+        // partitions of system topics are only listed on their own
+        for system in [false, true] {
+            let partitions = self
+                .list_with_config::<PartitionSpec, String>(
+                    ListRequest::new(name.as_str(), false).system(system),
+                )
+                .await?;
+            if let Some(found) = partitions.into_iter().find(|p| p.name == name) {
+                return Ok(PartitionInfo::from_metadata(found));
+            }
+        }
+        Ok(None)
+    }
+
     /// Watch stream of changes for metadata
     /// There is caching, this is just pass through
     #[instrument(skip(self))]
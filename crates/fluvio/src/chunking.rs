@@ -0,0 +1,289 @@
+//!
+//! # Record Chunking
+//!
+//! Records larger than the max request size of the producer are split into chunk records
+//! when the producer is configured with `chunking`. Chunks are sent to the same partition
+//! and carry the `fluvio.chunk.*` headers; the headers of the original record are kept on
+//! the last chunk.
+//!
+//! Consumers reassemble chunked records transparently, the reassembled record takes the
+//! offset of its last chunk. Chunks whose first chunk was not read, such as when a stream
+//! starts in the middle of a chunked record, are dropped. While a record is incomplete, the
+//! offsets committed by the consumer stay before its first chunk, so a consumer resuming
+//! from them reads the whole record again.
+//!
+//! Chunks are ordinary records to the SPU: SmartModules see each chunk on its own, with the
+//! `fluvio.chunk.*` headers, and never the reassembled record. A SmartModule that drops or
+//! rewrites some chunks of a record leaves it impossible to reassemble, its remaining
+//! chunks are then dropped by consumers. Deduplication windows of topics skip chunks, which
+//! share the key of the original record.
+//!
+
+use std::collections::HashMap;
+use std::hash::Hasher;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+use chrono::Utc;
+use siphasher::sip::SipHasher;
+use tracing::warn;
+
+use fluvio_protocol::record::{ConsumerRecord, Offset, Record, RecordData, RecordHeaderEntry};
+
+pub const CHUNK_ID_HEADER: &str = "fluvio.chunk.id";
+pub const CHUNK_INDEX_HEADER: &str = "fluvio.chunk.index";
+pub const CHUNK_COUNT_HEADER: &str = "fluvio.chunk.count";
+
+/// room left in each request for the batch header and the chunk headers
+pub(crate) const CHUNK_OVERHEAD: usize = 1024;
+/// chunked records being reassembled by a partition stream
+const MAX_PENDING_RECORDS: usize = 64;
+
+static CHUNK_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// split `record` into records with values of at most `chunk_size` bytes
+pub(crate) fn split_record(record: Record, chunk_size: usize) -> Vec<Record> {
+    let Record {
+        preamble,
+        key,
+        value,
+        headers,
+    } = record;
+    let value = value.as_ref();
+    let chunk_size = chunk_size.max(1);
+    let count = value.len().div_ceil(chunk_size);
+
+    let mut hasher = SipHasher::new();
+    hasher.write(value);
+    hasher.write_i64(Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    hasher.write_u64(CHUNK_SEQUENCE.fetch_add(1, Ordering::Relaxed));
+    let id = format!("{:016x}", hasher.finish());
+
+    value
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut chunk_headers = vec![
+                RecordHeaderEntry::new(CHUNK_ID_HEADER, id.as_str()),
+                RecordHeaderEntry::new(CHUNK_INDEX_HEADER, index.to_string()),
+                RecordHeaderEntry::new(CHUNK_COUNT_HEADER, count.to_string()),
+            ];
+            if index + 1 == count {
+                chunk_headers.extend(headers.iter().cloned());
+            }
+            Record {
+                preamble: preamble.clone(),
+                key: key.clone(),
+                value: RecordData::from(chunk.to_vec()),
+                headers: chunk_headers,
+            }
+        })
+        .collect()
+}
+
+/// size of `record` without its value
+pub(crate) fn envelope_size(record: &Record) -> usize {
+    record.key.as_ref().map_or(0, |key| key.len())
+        + record
+            .headers
+            .iter()
+            .map(|header| header.key.len() + header.value.len())
+            .sum::<usize>()
+}
+
+struct Chunk {
+    id: String,
+    index: usize,
+    count: usize,
+}
+
+impl Chunk {
+    fn of(record: &Record) -> Option<Self> {
+        let header = |key| record.header(key).and_then(|value| value.as_str().ok());
+        Some(Self {
+            id: header(CHUNK_ID_HEADER)?.to_owned(),
+            index: header(CHUNK_INDEX_HEADER)?.parse().ok()?,
+            count: header(CHUNK_COUNT_HEADER)?.parse().ok()?,
+        })
+    }
+}
+
+/// Highest offset a partition stream may commit, records from the first chunk of a
+/// pending record on are not committed
+#[derive(Clone)]
+pub(crate) struct CommitBound(Arc<AtomicI64>);
+
+impl CommitBound {
+    fn new() -> Self {
+        Self(Arc::new(AtomicI64::new(Offset::MAX)))
+    }
+
+    /// `offset` lowered to the bound
+    pub(crate) fn limit(&self, offset: Offset) -> Offset {
+        offset.min(self.0.load(Ordering::Relaxed))
+    }
+}
+
+struct PendingRecord {
+    value: Vec<u8>,
+    next_index: usize,
+    first_offset: Offset,
+}
+
+/// Reassembles the chunked records of a partition
+pub(crate) struct ChunkAssembler {
+    /// records being reassembled, by chunk id
+    pending: HashMap<String, PendingRecord>,
+    commit_bound: CommitBound,
+}
+
+impl Default for ChunkAssembler {
+    fn default() -> Self {
+        Self {
+            pending: HashMap::new(),
+            commit_bound: CommitBound::new(),
+        }
+    }
+}
+
+impl ChunkAssembler {
+    /// bound of the offsets committed for the records returned by [`ChunkAssembler::push`]
+    pub(crate) fn commit_bound(&self) -> CommitBound {
+        self.commit_bound.clone()
+    }
+
+    /// the record itself if it is not a chunk, the reassembled record for the last chunk
+    pub(crate) fn push(&mut self, record: ConsumerRecord) -> Option<ConsumerRecord> {
+        let output = self.assemble(record);
+        let bound = self
+            .pending
+            .values()
+            .map(|pending| pending.first_offset - 1)
+            .min()
+            .unwrap_or(Offset::MAX);
+        self.commit_bound.0.store(bound, Ordering::Relaxed);
+        output
+    }
+
+    fn assemble(&mut self, mut record: ConsumerRecord) -> Option<ConsumerRecord> {
+        let Some(chunk) = Chunk::of(&record.record) else {
+            return Some(record);
+        };
+        if chunk.index == 0 {
+            if self.pending.len() >= MAX_PENDING_RECORDS {
+                warn!(
+                    pending = self.pending.len(),
+                    "too many incomplete chunked records, dropping them"
+                );
+                self.pending.clear();
+            }
+            let pending = PendingRecord {
+                value: Vec::new(),
+                next_index: 0,
+                first_offset: record.offset,
+            };
+            self.pending.insert(chunk.id.clone(), pending);
+        }
+        let pending = self.pending.get_mut(&chunk.id)?;
+        if chunk.index != pending.next_index {
+            warn!(
+                id = chunk.id,
+                index = chunk.index,
+                "chunk out of sequence, dropping record"
+            );
+            self.pending.remove(&chunk.id);
+            return None;
+        }
+        pending
+            .value
+            .extend_from_slice(record.record.value.as_ref());
+        pending.next_index += 1;
+        if pending.next_index < chunk.count {
+            return None;
+        }
+
+        let pending = self.pending.remove(&chunk.id)?;
+        record.record.value = RecordData::from(pending.value);
+        record.record.headers.retain(|header| {
+            ![CHUNK_ID_HEADER, CHUNK_INDEX_HEADER, CHUNK_COUNT_HEADER]
+                .contains(&header.key.as_str())
+        });
+        Some(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::Batch;
+
+    use super::*;
+
+    fn consumer_records(records: Vec<Record>) -> Vec<ConsumerRecord> {
+        let mut records = records;
+        let mut batch = Batch::default();
+        batch.add_records(&mut records);
+        batch.into_consumer_records_iter(0).collect()
+    }
+
+    #[test]
+    fn test_split_and_reassemble() {
+        let mut record = Record::new(vec![7u8; 2500]);
+        record.add_header("trace", "abc");
+
+        let chunks = split_record(record, 1000);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[2].value().len(), 500);
+        assert!(chunks[0].header("trace").is_none());
+        assert!(chunks[2].header("trace").is_some());
+
+        let mut input = chunks.clone();
+        input.insert(1, Record::new("interleaved"));
+        let mut assembler = ChunkAssembler::default();
+        let output: Vec<_> = consumer_records(input)
+            .into_iter()
+            .filter_map(|record| assembler.push(record))
+            .collect();
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[0].record.value().as_ref(), b"interleaved");
+        assert_eq!(output[1].offset, 3);
+        assert_eq!(
+            output[1].record.value().as_ref(),
+            vec![7u8; 2500].as_slice()
+        );
+        assert_eq!(
+            output[1].record.headers,
+            vec![RecordHeaderEntry::new("trace", "abc")]
+        );
+
+        // a stream starting after the first chunk drops the rest of the record
+        let mut assembler = ChunkAssembler::default();
+        assert!(consumer_records(chunks[1..].to_vec())
+            .into_iter()
+            .all(|record| assembler.push(record).is_none()));
+    }
+
+    #[test]
+    fn test_commit_bound() {
+        let chunks = split_record(Record::new(vec![7u8; 2500]), 1000);
+        let mut input = chunks;
+        input.insert(1, Record::new("interleaved"));
+        let mut assembler = ChunkAssembler::default();
+        let bound = assembler.commit_bound();
+        let mut records = consumer_records(input).into_iter();
+
+        assert!(assembler.push(records.next().expect("chunk")).is_none());
+        let interleaved = assembler
+            .push(records.next().expect("record"))
+            .expect("record");
+        assert_eq!(interleaved.offset, 1);
+        // the interleaved record is read again with the chunked one after a restart
+        assert_eq!(bound.limit(interleaved.offset), -1);
+
+        assert!(assembler.push(records.next().expect("chunk")).is_none());
+        let reassembled = assembler
+            .push(records.next().expect("chunk"))
+            .expect("record");
+        assert_eq!(bound.limit(reassembled.offset), 3);
+    }
+}
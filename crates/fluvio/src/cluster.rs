@@ -0,0 +1,196 @@
+//!
+//! # Cluster
+//!
+//! SPUs and partitions as reported by the SC, returned by [`FluvioAdmin::list_spus`] and
+//! [`FluvioAdmin::describe_partition`].
+//!
+//! [`FluvioAdmin::list_spus`]: crate::FluvioAdmin::list_spus
+//! [`FluvioAdmin::describe_partition`]: crate::FluvioAdmin::describe_partition
+//!
+
+use fluvio_sc_schema::objects::Metadata;
+use fluvio_sc_schema::partition::{PartitionResolution, PartitionSpec, PartitionStatus};
+use fluvio_sc_schema::spu::SpuSpec;
+use fluvio_types::{PartitionId, SpuId};
+
+/// SPU registered in the cluster
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpuInfo {
+    pub id: SpuId,
+    pub name: String,
+    pub status: SpuState,
+    /// registered with a custom SPU rather than managed by the cluster
+    pub custom: bool,
+    /// `host:port` of clients
+    pub public_endpoint: String,
+    /// `host:port` of the SC and other SPUs
+    pub private_endpoint: String,
+    pub rack: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpuState {
+    Online,
+    Offline,
+    /// registered but not connected yet
+    Init,
+}
+
+impl From<Metadata<SpuSpec>> for SpuInfo {
+    fn from(spu: Metadata<SpuSpec>) -> Self {
+        let spec = spu.spec;
+        Self {
+            id: spec.id,
+            name: spu.name,
+            status: if spu.status.is_online() {
+                SpuState::Online
+            } else if spu.status.is_offline() {
+                SpuState::Offline
+            } else {
+                SpuState::Init
+            },
+            custom: spec.is_custom(),
+            public_endpoint: spec.public_endpoint.to_string(),
+            private_endpoint: format!(
+                "{}:{}",
+                spec.private_endpoint.host, spec.private_endpoint.port
+            ),
+            rack: spec.rack,
+        }
+    }
+}
+
+/// Partition of a topic with the replication reported by its leader
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionInfo {
+    pub topic: String,
+    pub partition: PartitionId,
+    pub status: PartitionState,
+    pub leader: SpuId,
+    /// every replica assigned to the partition, leader included
+    pub replicas: Vec<SpuId>,
+    /// replicas having every record of the leader, leader included
+    pub in_sync_replicas: Vec<SpuId>,
+    /// offset of the first record kept
+    pub base_offset: i64,
+    /// offset after the last committed record
+    pub high_watermark: i64,
+    /// offset after the last record of the leader
+    pub end_offset: i64,
+    /// storage used by the leader, `None` if it is unknown
+    pub size_bytes: Option<u64>,
+    /// replication of each follower
+    pub followers: Vec<ReplicaInfo>,
+    /// produces are rejected until the disk of the leader has space again
+    pub storage_full: bool,
+    pub being_deleted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionState {
+    Online,
+    Offline,
+    /// election failed, no suitable leader was found
+    LeaderOffline,
+    /// a new leader was elected and has not reported yet
+    ElectionLeaderFound,
+}
+
+/// Replication of a follower
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplicaInfo {
+    pub spu: SpuId,
+    pub high_watermark: i64,
+    pub end_offset: i64,
+    /// records of the leader the follower does not have yet
+    pub lag: i64,
+}
+
+impl PartitionInfo {
+    /// `None` if the name of the partition is not `<topic>-<partition>`
+    pub fn from_metadata(partition: Metadata<PartitionSpec>) -> Option<Self> {
+        let (topic, index) = partition.name.rsplit_once('-')?;
+        let index = index.parse().ok()?;
+        let spec = partition.spec;
+        let status: PartitionStatus = partition.status;
+        let leader = &status.leader;
+
+        let followers: Vec<ReplicaInfo> = status
+            .replicas
+            .iter()
+            .map(|replica| ReplicaInfo {
+                spu: replica.spu,
+                high_watermark: replica.hw,
+                end_offset: replica.leo,
+                lag: (leader.leo - replica.leo).max(0),
+            })
+            .collect();
+        let in_sync_replicas = std::iter::once(spec.leader)
+            .chain(
+                followers
+                    .iter()
+                    .filter(|follower| follower.lag == 0)
+                    .map(|follower| follower.spu),
+            )
+            .collect();
+
+        Some(Self {
+            topic: topic.to_owned(),
+            partition: index,
+            status: match status.resolution {
+                PartitionResolution::Online => PartitionState::Online,
+                PartitionResolution::Offline => PartitionState::Offline,
+                PartitionResolution::LeaderOffline => PartitionState::LeaderOffline,
+                PartitionResolution::ElectionLeaderFound => PartitionState::ElectionLeaderFound,
+            },
+            leader: spec.leader,
+            replicas: spec.replicas,
+            in_sync_replicas,
+            base_offset: status.base_offset,
+            high_watermark: leader.hw,
+            end_offset: leader.leo,
+            size_bytes: u64::try_from(status.size).ok(),
+            followers,
+            storage_full: status.storage_full,
+            being_deleted: status.is_being_deleted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_sc_schema::partition::ReplicaStatus;
+
+    use super::*;
+
+    #[test]
+    fn test_partition_info() {
+        let partition = Metadata {
+            name: "orders-eu-2".to_owned(),
+            spec: PartitionSpec::new(5001, vec![5001, 5002, 5003]),
+            status: PartitionStatus {
+                resolution: PartitionResolution::Online,
+                leader: ReplicaStatus::new(5001, 90, 100),
+                replicas: vec![
+                    ReplicaStatus::new(5002, 90, 100),
+                    ReplicaStatus::new(5003, 80, 85),
+                ],
+                size: PartitionStatus::SIZE_NOT_SUPPORTED,
+                base_offset: 10,
+                ..Default::default()
+            },
+        };
+
+        let info = PartitionInfo::from_metadata(partition).expect("partition name");
+        assert_eq!(info.topic, "orders-eu");
+        assert_eq!(info.partition, 2);
+        assert_eq!(info.status, PartitionState::Online);
+        assert_eq!(info.replicas, vec![5001, 5002, 5003]);
+        assert_eq!(info.in_sync_replicas, vec![5001, 5002]);
+        assert_eq!(info.high_watermark, 90);
+        assert_eq!(info.end_offset, 100);
+        assert_eq!(info.size_bytes, None);
+        assert_eq!(info.followers[1].lag, 15);
+    }
+}
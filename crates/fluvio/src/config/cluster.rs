@@ -6,9 +6,12 @@
 use serde::{Serialize, Deserialize};
 use toml::Table as Metadata;
 
+use fluvio_future::net::DomainConnector;
+use fluvio_socket::TcpConfig;
+
 use crate::{config::TlsPolicy, FluvioError};
 
-use super::ConfigFile;
+use super::{ConfigFile, ProxySettings, SpuEndpointOverrides};
 
 /// Fluvio Cluster Target Configuration
 /// This is part of profile
@@ -30,6 +33,24 @@ pub struct FluvioConfig {
     #[serde(default)]
     pub tls: TlsPolicy,
 
+    /// Outbound proxy used to reach the SC and SPUs, e.g. socks5://proxy:1080 or http://proxy:3128
+    /// If not set, proxy is read from FLUVIO_PROXY, ALL_PROXY or HTTPS_PROXY env vars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxySettings>,
+
+    /// TCP options for connections to the cluster, only applied to plaintext connections
+    #[serde(default, skip_serializing_if = "TcpConfig::is_default")]
+    pub tcp: TcpConfig,
+
+    /// Addresses used to reach SPUs instead of the addresses they advertise
+    #[serde(default, skip_serializing_if = "SpuEndpointOverrides::is_empty")]
+    pub spu_endpoints: SpuEndpointOverrides,
+
+    /// What to do when the cluster does not support this client version.
+    /// Can be overridden with the FLUVIO_VERSION_SKEW env var
+    #[serde(default, skip_serializing_if = "VersionSkewPolicy::is_default")]
+    pub version_skew: VersionSkewPolicy,
+
     /// Cluster custom metadata
     #[serde(default = "Metadata::new", skip_serializing_if = "Metadata::is_empty")]
     metadata: Metadata,
@@ -62,6 +83,10 @@ impl FluvioConfig {
             endpoint: addr.into(),
             use_spu_local_address: false,
             tls: TlsPolicy::Disabled,
+            proxy: None,
+            tcp: TcpConfig::default(),
+            spu_endpoints: SpuEndpointOverrides::default(),
+            version_skew: VersionSkewPolicy::default(),
             metadata: Metadata::new(),
             client_id: None,
         }
@@ -73,6 +98,103 @@ impl FluvioConfig {
         self
     }
 
+    /// Set outbound proxy for this cluster, an url or [`ProxySettings`].
+    pub fn with_proxy(mut self, proxy: impl Into<ProxySettings>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Set TCP options for connections to this cluster.
+    pub fn with_tcp(mut self, tcp: TcpConfig) -> Self {
+        self.tcp = tcp;
+        self
+    }
+
+    /// Set addresses used to reach SPUs of this cluster.
+    pub fn with_spu_endpoints(mut self, overrides: SpuEndpointOverrides) -> Self {
+        self.spu_endpoints = overrides;
+        self
+    }
+
+    /// Set policy applied when the cluster does not support this client version.
+    pub fn with_version_skew(mut self, policy: VersionSkewPolicy) -> Self {
+        self.version_skew = policy;
+        self
+    }
+
+    /// Version skew policy, FLUVIO_VERSION_SKEW env var takes precedence over the profile
+    pub fn version_skew_policy(&self) -> VersionSkewPolicy {
+        match std::env::var(VERSION_SKEW_ENV) {
+            Ok(value) => value.parse().unwrap_or_else(|_| {
+                tracing::warn!(%value, "invalid {VERSION_SKEW_ENV}, using profile setting");
+                self.version_skew
+            }),
+            Err(_) => self.version_skew,
+        }
+    }
+
+    /// Create connector to the cluster, honoring TLS and proxy settings
+    pub fn connector(&self) -> anyhow::Result<DomainConnector> {
+        cfg_if::cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                Ok(DomainConnector::try_from(self.tls.clone())?)
+            } else {
+                use fluvio_socket::{ProxyConfig, ProxyDomainConnector, TunedDomainConnector};
+
+                self.tcp.validate()?;
+                let proxy = match &self.proxy {
+                    Some(settings) => Some(
+                        settings
+                            .url
+                            .parse::<ProxyConfig>()?
+                            .with_no_proxy(settings.no_proxy.clone()),
+                    ),
+                    None => ProxyConfig::from_env(&self.endpoint),
+                };
+
+                match (proxy, &self.tls) {
+                    (None, TlsPolicy::Disabled) if !self.tcp.is_default() => {
+                        Ok(Box::new(TunedDomainConnector::new(self.tcp.clone())))
+                    }
+                    (None, tls) => {
+                        if !self.tcp.is_default() {
+                            tracing::warn!("tcp options are not applied to TLS connections");
+                        }
+                        Ok(DomainConnector::try_from(tls.clone())?)
+                    }
+                    (Some(proxy), TlsPolicy::Disabled) => {
+                        tracing::info!(%proxy, "connecting through proxy");
+                        Ok(Box::new(ProxyDomainConnector::new(proxy)))
+                    }
+                    #[cfg(feature = "openssl")]
+                    (Some(proxy), tls) => {
+                        let (connector, domain) = tls
+                            .openssl_connector()?
+                            .ok_or_else(|| anyhow::anyhow!("tls policy has no connector"))?;
+                        // anonymous TLS does not verify the name, the host is only sent as SNI
+                        let domain = domain.unwrap_or_else(|| {
+                            let host = self.endpoint.rsplit_once(':').map(|(host, _)| host);
+                            host.unwrap_or(&self.endpoint).to_owned()
+                        });
+                        tracing::info!(%proxy, "connecting through proxy with TLS");
+                        Ok(Box::new(fluvio_socket::ProxyTlsDomainConnector::new(
+                            proxy, connector, domain,
+                        )))
+                    }
+                    #[cfg(not(feature = "openssl"))]
+                    (Some(proxy), _) if self.proxy.is_some() => Err(anyhow::anyhow!(
+                        "proxy {proxy} is not supported for clusters with TLS enabled"
+                    )),
+                    #[cfg(not(feature = "openssl"))]
+                    (Some(proxy), tls) => {
+                        tracing::warn!(%proxy, "ignoring environment proxy for TLS enabled cluster");
+                        Ok(DomainConnector::try_from(tls.clone())?)
+                    }
+                }
+            }
+        }
+    }
+
     pub fn query_metadata_by_name<'de, T>(&self, name: &str) -> Option<T>
     where
         T: Deserialize<'de>,
@@ -105,10 +227,46 @@ impl FluvioConfig {
     }
 }
 
+pub const VERSION_SKEW_ENV: &str = "FLUVIO_VERSION_SKEW";
+
+/// Action taken when the client version is outside the range supported by the cluster
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSkewPolicy {
+    /// log a warning and continue
+    #[default]
+    Warn,
+    /// fail to connect
+    Refuse,
+    /// connect without checking
+    Ignore,
+}
+
+impl VersionSkewPolicy {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::str::FromStr for VersionSkewPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "warn" => Ok(Self::Warn),
+            "refuse" => Ok(Self::Refuse),
+            "ignore" => Ok(Self::Ignore),
+            other => Err(anyhow::anyhow!(
+                "invalid version skew policy: {other}, expected warn, refuse or ignore"
+            )),
+        }
+    }
+}
+
 impl TryFrom<FluvioConfig> for fluvio_socket::ClientConfig {
     type Error = anyhow::Error;
     fn try_from(config: FluvioConfig) -> Result<Self, Self::Error> {
-        let connector = fluvio_future::net::DomainConnector::try_from(config.tls.clone())?;
+        let connector = config.connector()?;
         Ok(Self::new(
             &config.endpoint,
             connector,
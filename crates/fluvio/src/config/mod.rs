@@ -2,7 +2,11 @@
 mod config;
 mod tls;
 mod cluster;
+mod spu_endpoints;
+mod proxy;
 
 pub use config::*;
 pub use tls::*;
 pub use cluster::*;
+pub use spu_endpoints::*;
+pub use proxy::*;
@@ -0,0 +1,210 @@
+//!
+//! # SPU Endpoint Overrides
+//!
+//! SPUs advertise the addresses they are reachable at inside the cluster, which may not
+//! be reachable from the client, e.g. through port forwarding or NAT. Overrides map
+//! advertised addresses to the addresses the client connects to.
+//!
+//! When the cluster runs an SPU gateway, all SPUs are reached through the gateway
+//! address. TLS connections carry the SPU name in the server name, which the gateway
+//! uses to pick the SPU.
+//!
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+
+use fluvio_types::SpuId;
+
+/// Resolves the address used to connect to an SPU
+pub trait SpuEndpointResolver: Debug + Send + Sync {
+    /// address to connect to, `None` to use the advertised address
+    fn resolve(&self, spu: SpuId, advertised: &str) -> Option<String>;
+}
+
+/// Static overrides stored in the cluster profile
+///
+/// ```toml
+/// [cluster.remote.spu_endpoints]
+/// gateway = "fluvio.example.com:9010"
+///
+/// [cluster.remote.spu_endpoints.endpoints]
+/// "fluvio-spg-main-0.fluvio-spg-main:9005" = "localhost:19005"
+/// "5001" = "localhost:19006"
+///
+/// [[cluster.remote.spu_endpoints.rewrites]]
+/// from = "*.fluvio-spg-main"
+/// to = "10.0.0.5"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpuEndpointOverrides {
+    /// advertised address (`host:port`) or SPU id mapped to the address to connect to
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub endpoints: BTreeMap<String, String>,
+    /// address of the SPU gateway, used when no endpoint matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    /// rules applied in order when no endpoint matches and there is no gateway
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rewrites: Vec<HostRewrite>,
+}
+
+/// Replaces the host of advertised addresses
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostRewrite {
+    /// host to match, `*.suffix` matches every host ending with `.suffix`
+    pub from: String,
+    /// replacement host, the advertised port is kept unless `to` is `host:port`
+    pub to: String,
+}
+
+impl SpuEndpointOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty() && self.gateway.is_none() && self.rewrites.is_empty()
+    }
+
+    pub fn with_endpoint(mut self, advertised: impl Into<String>, addr: impl Into<String>) -> Self {
+        self.endpoints.insert(advertised.into(), addr.into());
+        self
+    }
+
+    pub fn with_gateway(mut self, addr: impl Into<String>) -> Self {
+        self.gateway = Some(addr.into());
+        self
+    }
+
+    pub fn with_rewrite(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.rewrites.push(HostRewrite {
+            from: from.into(),
+            to: to.into(),
+        });
+        self
+    }
+}
+
+impl SpuEndpointResolver for SpuEndpointOverrides {
+    fn resolve(&self, spu: SpuId, advertised: &str) -> Option<String> {
+        if let Some(addr) = self
+            .endpoints
+            .get(advertised)
+            .or_else(|| self.endpoints.get(&spu.to_string()))
+        {
+            return Some(addr.clone());
+        }
+        if let Some(gateway) = &self.gateway {
+            return Some(gateway.clone());
+        }
+
+        let (host, port) = advertised.rsplit_once(':')?;
+        self.rewrites
+            .iter()
+            .find(|rewrite| rewrite.matches(host))
+            .map(|rewrite| {
+                if rewrite.to.contains(':') {
+                    rewrite.to.clone()
+                } else {
+                    format!("{}:{port}", rewrite.to)
+                }
+            })
+    }
+}
+
+impl HostRewrite {
+    fn matches(&self, host: &str) -> bool {
+        match self.from.strip_prefix("*.") {
+            Some(suffix) => host
+                .strip_suffix(suffix)
+                .is_some_and(|prefix| prefix.ends_with('.')),
+            None => self.from == host,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_endpoints() {
+        let overrides = SpuEndpointOverrides::default()
+            .with_endpoint("spu-0.internal:9005", "localhost:19005")
+            .with_endpoint("5001", "localhost:19006");
+
+        assert_eq!(
+            overrides.resolve(5000, "spu-0.internal:9005").as_deref(),
+            Some("localhost:19005")
+        );
+        assert_eq!(
+            overrides.resolve(5001, "spu-1.internal:9005").as_deref(),
+            Some("localhost:19006")
+        );
+        assert_eq!(overrides.resolve(5002, "spu-2.internal:9005"), None);
+    }
+
+    #[test]
+    fn test_host_rewrites() {
+        let overrides = SpuEndpointOverrides::default()
+            .with_rewrite("*.fluvio-spg-main", "10.0.0.5")
+            .with_rewrite("internal-host", "localhost:9999");
+
+        assert_eq!(
+            overrides
+                .resolve(5000, "fluvio-spg-main-0.fluvio-spg-main:9005")
+                .as_deref(),
+            Some("10.0.0.5:9005")
+        );
+        assert_eq!(
+            overrides.resolve(5000, "internal-host:9005").as_deref(),
+            Some("localhost:9999")
+        );
+        assert_eq!(overrides.resolve(5000, "fluvio-spg-main:9005"), None);
+        assert_eq!(overrides.resolve(5000, "other-fluvio-spg-main:9005"), None);
+    }
+
+    #[test]
+    fn test_gateway() {
+        let overrides = SpuEndpointOverrides::default()
+            .with_endpoint("5001", "localhost:19006")
+            .with_gateway("fluvio.example.com:9010")
+            .with_rewrite("*.svc", "127.0.0.1");
+
+        assert_eq!(
+            overrides.resolve(5000, "spu-0.svc:9005").as_deref(),
+            Some("fluvio.example.com:9010")
+        );
+        assert_eq!(
+            overrides.resolve(5001, "spu-1.svc:9005").as_deref(),
+            Some("localhost:19006")
+        );
+    }
+
+    #[test]
+    fn test_overrides_from_profile() {
+        use fluvio_types::config_file::SaveLoadConfig;
+
+        use crate::config::Config;
+
+        let toml = r#"version = "2"
+[profile.local]
+cluster = "local"
+
+[cluster.local]
+endpoint = "127.0.0.1:9003"
+
+[cluster.local.spu_endpoints.endpoints]
+"5001" = "localhost:19006"
+
+[[cluster.local.spu_endpoints.rewrites]]
+from = "*.svc"
+to = "127.0.0.1"
+"#;
+        let profile = Config::load_str(toml).expect("parse");
+        let config = profile.cluster("local").expect("cluster");
+        assert_eq!(
+            config.spu_endpoints,
+            SpuEndpointOverrides::default()
+                .with_endpoint("5001", "localhost:19006")
+                .with_rewrite("*.svc", "127.0.0.1")
+        );
+    }
+}
@@ -170,25 +170,26 @@ cfg_if::cfg_if! {
 
     } else if #[cfg(feature = "openssl")] {
 
-        impl TryFrom<TlsPolicy> for DomainConnector {
-            type Error = anyhow::Error;
-
-            fn try_from(config: TlsPolicy) -> Result<Self, Self::Error> {
+        impl TlsPolicy {
+            /// connector of the policy with the server name it verifies, `None` if TLS is
+            /// disabled. Anonymous TLS verifies no name
+            pub(crate) fn openssl_connector(
+                &self,
+            ) -> Result<Option<(fluvio_future::openssl::TlsConnector, Option<String>)>> {
 
                 use fluvio_future::net::certs::CertBuilder;
-                use fluvio_future::openssl:: {TlsDomainConnector,TlsConnector,TlsAnonymousConnector};
+                use fluvio_future::openssl::TlsConnector;
                 use fluvio_future::openssl::certs::{IdentityBuilder,X509PemBuilder,PrivateKeyBuilder};
 
 
-                match config {
-                    TlsPolicy::Disabled => Ok(Box::new(DefaultDomainConnector::new())),
+                match self {
+                    TlsPolicy::Disabled => Ok(None),
                     TlsPolicy::Anonymous => {
                         info!("Using anonymous TLS");
                         let builder = TlsConnector::builder()?
                                 .with_hostname_verification_disabled()?;
 
-                        let connector: TlsAnonymousConnector = builder.build().into();
-                        Ok(Box::new(connector))
+                        Ok(Some((builder.build(), None)))
 
                     }
                     TlsPolicy::Verified(TlsConfig::Files(tls)) => {
@@ -210,10 +211,7 @@ cfg_if::cfg_if! {
                             )?;
 
 
-                        Ok(Box::new(TlsDomainConnector::new(
-                            builder.build(),
-                            tls.domain,
-                        )))
+                        Ok(Some((builder.build(), Some(tls.domain.clone()))))
                     }
                     TlsPolicy::Verified(TlsConfig::Inline(tls)) => {
                         info!(
@@ -233,10 +231,27 @@ cfg_if::cfg_if! {
                             )?;
 
 
-                        Ok(Box::new(TlsDomainConnector::new(
-                            builder.build(),
-                            tls.domain,
-                        )))
+                        Ok(Some((builder.build(), Some(tls.domain.clone()))))
+                    }
+                }
+            }
+        }
+
+        impl TryFrom<TlsPolicy> for DomainConnector {
+            type Error = anyhow::Error;
+
+            fn try_from(config: TlsPolicy) -> Result<Self, Self::Error> {
+
+                use fluvio_future::openssl::{TlsDomainConnector, TlsAnonymousConnector};
+
+                match config.openssl_connector()? {
+                    None => Ok(Box::new(DefaultDomainConnector::new())),
+                    Some((connector, None)) => {
+                        let connector: TlsAnonymousConnector = connector.into();
+                        Ok(Box::new(connector))
+                    }
+                    Some((connector, Some(domain))) => {
+                        Ok(Box::new(TlsDomainConnector::new(connector, domain)))
                     }
                 }
             }
@@ -10,20 +10,30 @@ use crate::{FluvioError, Offset};
 
 use super::MAX_FETCH_BYTES;
 
-const DEFAULT_OFFSET_FLUSH_PERIOD: Duration = Duration::from_secs(10);
+pub(super) const DEFAULT_OFFSET_FLUSH_PERIOD: Duration = Duration::from_secs(10);
 
-/// Configures the behavior of consumer fetching and streaming
+/// Configures the behavior of consumer fetching and streaming,
+/// created with [`ConsumerConfig::builder`]
 #[derive(Debug, Builder, Clone)]
 #[builder(build_fn(private, name = "build_impl"))]
+#[non_exhaustive]
 pub struct ConsumerConfig {
     #[builder(default)]
     pub disable_continuous: bool,
+    /// Pin the stream to the end offset at open time, the stream ends once it is reached
+    /// even if producers keep appending
+    #[builder(default)]
+    pub snapshot: bool,
     #[builder(default = "*MAX_FETCH_BYTES")]
     pub max_bytes: i32,
     #[builder(default)]
     pub isolation: Isolation,
     #[builder(default)]
     pub smartmodule: Vec<SmartModuleInvocation>,
+    /// Name of the application reported to the SPU, used to attribute
+    /// fetch metrics. The offset consumer id is used when not set.
+    #[builder(default, setter(strip_option, into))]
+    pub application: Option<String>,
 }
 
 impl ConsumerConfig {
@@ -70,12 +80,18 @@ pub struct ConsumerConfigExt {
     pub offset_flush: Duration,
     #[builder(default)]
     disable_continuous: bool,
+    /// Pin each partition to its end offset at open time, reported by
+    /// [`ConsumerStream::snapshot_end_offsets`](crate::consumer::ConsumerStream::snapshot_end_offsets)
+    #[builder(default)]
+    pub snapshot: bool,
     #[builder(default = "*MAX_FETCH_BYTES")]
     pub max_bytes: i32,
     #[builder(default)]
     pub isolation: Isolation,
     #[builder(default)]
     pub smartmodule: Vec<SmartModuleInvocation>,
+    #[builder(default, setter(strip_option, into))]
+    pub application: Option<String>,
 }
 
 impl ConsumerConfigExt {
@@ -99,18 +115,22 @@ impl ConsumerConfigExt {
             offset_consumer,
             offset_start,
             disable_continuous,
+            snapshot,
             max_bytes,
             isolation,
             smartmodule,
             offset_strategy,
             offset_flush,
+            application,
         } = self;
 
         let config = ConsumerConfig {
             disable_continuous,
+            snapshot,
             max_bytes,
             isolation,
             smartmodule,
+            application,
         };
 
         (
@@ -141,16 +161,20 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             offset_strategy: _,
             offset_flush: _,
             disable_continuous,
+            snapshot,
             max_bytes,
             isolation,
             smartmodule,
+            application,
         } = value;
 
         Self {
             disable_continuous,
+            snapshot,
             max_bytes,
             isolation,
             smartmodule,
+            application,
         }
     }
 }
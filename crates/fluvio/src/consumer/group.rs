@@ -0,0 +1,446 @@
+//!
+//! # Consumer Groups
+//!
+//! Members of a consumer group share the partitions of its topic, the SC assigns each
+//! partition to one member and rebalances the group when members join or leave.
+//! Offsets are committed with the group name as consumer id, so a member taking over
+//! a partition resumes where the previous member stopped.
+//!
+//! Members configured with a group instance id are static: they don't leave the
+//! group when they stop, and get their partitions back without a rebalance when
+//! they join again within the session timeout. The stream of a member replaced by
+//! another one with its instance id ends after a `ConsumerGroupMemberFenced` error.
+//!
+//! Each assigned partition is streamed on its own. A rebalance stops all the
+//! partitions of the member, or with a cooperative strategy only the partitions
+//! leaving it. The member reports them released so the SC can assign them to their
+//! new members, a partition is never streamed by two members at once.
+//!
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::Result;
+use derive_builder::Builder;
+use futures_util::future::{select, Either};
+use futures_util::stream::{unfold, BoxStream, SelectAll};
+use futures_util::{Stream, StreamExt};
+use tracing::{debug, info, warn};
+
+use fluvio_future::timer::sleep;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::ApiError;
+use fluvio_sc_schema::consumer_group::{
+    ConsumerGroupSpec, ConsumerGroupStatus, UpdateConsumerGroupAction,
+};
+use fluvio_sc_schema::objects::Metadata;
+use fluvio_types::PartitionId;
+
+use crate::{Fluvio, FluvioAdmin, FluvioError, Offset};
+
+use super::config::DEFAULT_OFFSET_FLUSH_PERIOD;
+use super::{ConsumerConfigExt, OffsetManagementStrategy, Record};
+
+/// how often members look for a new assignment
+const STATUS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configures a member of a consumer group
+#[derive(Debug, Builder, Clone)]
+#[builder(build_fn(private, name = "build_impl"))]
+pub struct ConsumerGroupConfig {
+    #[builder(setter(into))]
+    pub group: String,
+    /// Unique among the members of the group, generated when not set
+    #[builder(default = "default_member_id()", setter(into))]
+    pub member_id: String,
+    /// Identity of a static member, kept across restarts of the consumer
+    #[builder(default, setter(into, strip_option))]
+    pub group_instance_id: Option<String>,
+    /// Where assigned partitions without committed offset start
+    #[builder(default = "Offset::beginning()")]
+    pub offset_start: Offset,
+    #[builder(default = "DEFAULT_OFFSET_FLUSH_PERIOD")]
+    pub offset_flush: Duration,
+}
+
+impl ConsumerGroupConfig {
+    pub fn builder() -> ConsumerGroupConfigBuilder {
+        ConsumerGroupConfigBuilder::default()
+    }
+}
+
+impl ConsumerGroupConfigBuilder {
+    pub fn build(&self) -> Result<ConsumerGroupConfig> {
+        let config = self.build_impl().map_err(|e| {
+            FluvioError::ConsumerConfig(format!("Missing required config option: {e}"))
+        })?;
+        Ok(config)
+    }
+}
+
+fn default_member_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    format!("member-{}-{nanos:08x}", std::process::id())
+}
+
+/// Partitions of this member changed by a rebalance of the group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rebalance {
+    pub generation: u32,
+    /// partitions of the member after the rebalance
+    pub partitions: Vec<PartitionId>,
+    /// partitions the member did not have before
+    pub assigned: Vec<PartitionId>,
+    /// partitions the member no longer has, their offsets were flushed
+    pub revoked: Vec<PartitionId>,
+}
+
+impl Rebalance {
+    fn new(generation: u32, previous: &[PartitionId], partitions: Vec<PartitionId>) -> Self {
+        let assigned = partitions
+            .iter()
+            .filter(|partition| !previous.contains(partition))
+            .copied()
+            .collect();
+        let revoked = previous
+            .iter()
+            .filter(|partition| !partitions.contains(partition))
+            .copied()
+            .collect();
+        Self {
+            generation,
+            partitions,
+            assigned,
+            revoked,
+        }
+    }
+}
+
+/// Membership of a consumer in a group, kept alive with heartbeats
+struct GroupMembership {
+    admin: FluvioAdmin,
+    group: String,
+    member_id: String,
+    instance_id: Option<String>,
+}
+
+impl GroupMembership {
+    async fn send(&self, action: UpdateConsumerGroupAction) -> Result<()> {
+        self.admin
+            .update::<ConsumerGroupSpec>(self.group.clone(), action)
+            .await
+    }
+
+    async fn join(&self) -> Result<()> {
+        info!(group = %self.group, member_id = %self.member_id, instance_id = ?self.instance_id, "joining consumer group");
+        let member_id = self.member_id.clone();
+        let action = match &self.instance_id {
+            Some(instance_id) => UpdateConsumerGroupAction::JoinStatic {
+                member_id,
+                instance_id: instance_id.clone(),
+            },
+            None => UpdateConsumerGroupAction::Join { member_id },
+        };
+        self.send(action).await
+    }
+
+    /// the SC forgets members after a restart or a missed session, they join again
+    async fn heartbeat(&self) -> Result<()> {
+        let heartbeat = self
+            .send(UpdateConsumerGroupAction::Heartbeat {
+                member_id: self.member_id.clone(),
+            })
+            .await;
+        match heartbeat {
+            Err(err)
+                if matches!(
+                    err.downcast_ref::<ApiError>(),
+                    Some(ApiError::Code(ErrorCode::ConsumerGroupMemberNotFound, _))
+                ) =>
+            {
+                self.join().await
+            }
+            other => other,
+        }
+    }
+
+    async fn leave(&self) -> Result<()> {
+        info!(group = %self.group, member_id = %self.member_id, "leaving consumer group");
+        self.send(UpdateConsumerGroupAction::Leave {
+            member_id: self.member_id.clone(),
+        })
+        .await
+    }
+
+    /// the partitions revoked from the member in `generation` are no longer consumed
+    async fn revoked(&self, generation: u32) -> Result<()> {
+        debug!(group = %self.group, member_id = %self.member_id, generation, "partitions released");
+        self.send(UpdateConsumerGroupAction::Revoked {
+            member_id: self.member_id.clone(),
+            generation,
+        })
+        .await
+    }
+
+    async fn fetch(&self) -> Result<Metadata<ConsumerGroupSpec>> {
+        self.admin
+            .list::<ConsumerGroupSpec, _>(vec![self.group.clone()])
+            .await?
+            .into_iter()
+            .find(|group| group.name == self.group)
+            .ok_or_else(|| ApiError::Code(ErrorCode::ConsumerGroupNotFound, None).into())
+    }
+}
+
+type RebalanceCallback<'a> = Box<dyn FnMut(&Rebalance) + Send + 'a>;
+
+/// Records of one assigned partition
+struct PartitionRecords<'a> {
+    partition: PartitionId,
+    inner: BoxStream<'a, Result<Record, ErrorCode>>,
+}
+
+impl Stream for PartitionRecords<'_> {
+    type Item = Result<Record, ErrorCode>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+struct GroupState<'a> {
+    fluvio: &'a Fluvio,
+    config: ConsumerGroupConfig,
+    membership: Arc<GroupMembership>,
+    on_rebalance: RebalanceCallback<'a>,
+    /// None until the first assignment is read
+    generation: Option<u32>,
+    partitions: Vec<PartitionId>,
+    records: SelectAll<PartitionRecords<'a>>,
+    next_check: Instant,
+    next_heartbeat: Instant,
+    /// set once the member was replaced, the stream ends
+    fenced: bool,
+}
+
+impl<'a> GroupState<'a> {
+    /// send the heartbeat when due and switch to the new assignment of the member
+    async fn sync(&mut self) -> Result<()> {
+        let now = Instant::now();
+        self.next_check = now + STATUS_CHECK_INTERVAL;
+        if now >= self.next_heartbeat {
+            self.membership.heartbeat().await?;
+        }
+        let group = self.membership.fetch().await?;
+        if now >= self.next_heartbeat {
+            let session = Duration::from_millis(group.spec.session_timeout_ms.into());
+            self.next_heartbeat = now + session / 3;
+        }
+
+        let ConsumerGroupStatus {
+            generation,
+            members,
+            ..
+        } = group.status;
+        let (partitions, revoking) = members
+            .into_iter()
+            .find(|member| member.id == self.config.member_id)
+            .map(|member| (member.partitions, member.revoking))
+            .unwrap_or_default();
+        if self.generation != Some(generation) {
+            let first = self.generation.replace(generation).is_none();
+            if first || partitions != self.partitions {
+                let rebalance = Rebalance::new(generation, &self.partitions, partitions);
+                debug!(?rebalance, "consumer group rebalanced");
+                self.switch(&group.spec, rebalance).await?;
+            }
+        }
+        // sent until the SC assigns the partitions, the report may have been lost
+        if !revoking.is_empty() {
+            self.membership.revoked(generation).await?;
+        }
+        Ok(())
+    }
+
+    async fn switch(&mut self, spec: &ConsumerGroupSpec, rebalance: Rebalance) -> Result<()> {
+        // dropping the streams commits and flushes the offsets of their partitions
+        let opened = if spec.strategy.is_cooperative() {
+            self.records = std::mem::take(&mut self.records)
+                .into_iter()
+                .filter(|records| rebalance.partitions.contains(&records.partition))
+                .collect();
+            &rebalance.assigned
+        } else {
+            self.records = SelectAll::new();
+            &rebalance.partitions
+        };
+        (self.on_rebalance)(&rebalance);
+        for partition in opened {
+            let records = self.open(&spec.topic, *partition).await?;
+            self.records.push(records);
+        }
+        self.partitions = rebalance.partitions;
+        Ok(())
+    }
+
+    async fn open(&self, topic: &str, partition: PartitionId) -> Result<PartitionRecords<'a>> {
+        let config = ConsumerConfigExt::builder()
+            .topic(topic)
+            .partition(partition)
+            .offset_consumer(self.config.group.clone())
+            .offset_start(self.config.offset_start.clone())
+            .offset_strategy(OffsetManagementStrategy::Auto)
+            .offset_flush(self.config.offset_flush)
+            .build()?;
+        let stream = self.fluvio.consumer_with_config(config).await?;
+        Ok(PartitionRecords {
+            partition,
+            inner: stream.boxed(),
+        })
+    }
+
+    async fn next_record(&mut self) -> Option<Result<Record, ErrorCode>> {
+        if self.fenced {
+            return None;
+        }
+        loop {
+            let wait = sleep(self.next_check.saturating_duration_since(Instant::now()));
+            let record = if self.records.is_empty() {
+                wait.await;
+                None
+            } else {
+                match select(self.records.next(), Box::pin(wait)).await {
+                    Either::Left((record, _)) => Some(record),
+                    Either::Right(_) => None,
+                }
+            };
+            match record {
+                Some(Some(record)) => return Some(record),
+                // ended partitions are not streamed until the next assignment
+                Some(None) => {}
+                None => match self.sync().await {
+                    Ok(()) => {}
+                    Err(err) if is_fenced(&err) => {
+                        warn!(group = %self.config.group, member_id = %self.config.member_id, "member was replaced, stopping");
+                        self.fenced = true;
+                        self.records = SelectAll::new();
+                        return Some(Err(ErrorCode::ConsumerGroupMemberFenced));
+                    }
+                    Err(err) => {
+                        warn!(group = %self.config.group, %err, "consumer group sync failed");
+                        return Some(Err(ErrorCode::Other(err.to_string())));
+                    }
+                },
+            }
+        }
+    }
+}
+
+fn is_fenced(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<ApiError>(),
+        Some(ApiError::Code(ErrorCode::ConsumerGroupMemberFenced, _))
+    )
+}
+
+/// Records of the partitions assigned to a member of a consumer group.
+///
+/// The member leaves the group when the stream is dropped, unless it is a static member.
+pub struct ConsumerGroupStream<'a> {
+    inner: BoxStream<'a, Result<Record, ErrorCode>>,
+    membership: Arc<GroupMembership>,
+}
+
+impl<'a> ConsumerGroupStream<'a> {
+    pub(crate) async fn join(
+        fluvio: &'a Fluvio,
+        config: ConsumerGroupConfig,
+        on_rebalance: impl FnMut(&Rebalance) + Send + 'a,
+    ) -> Result<Self> {
+        let membership = Arc::new(GroupMembership {
+            admin: fluvio.admin().await,
+            group: config.group.clone(),
+            member_id: config.member_id.clone(),
+            instance_id: config.group_instance_id.clone(),
+        });
+        membership.join().await?;
+
+        let now = Instant::now();
+        let state = GroupState {
+            fluvio,
+            config,
+            membership: membership.clone(),
+            on_rebalance: Box::new(on_rebalance),
+            generation: None,
+            partitions: vec![],
+            records: SelectAll::new(),
+            next_check: now,
+            next_heartbeat: now,
+            fenced: false,
+        };
+        let inner = unfold(state, |mut state| async move {
+            let record = state.next_record().await?;
+            Some((record, state))
+        })
+        .boxed();
+        Ok(Self { inner, membership })
+    }
+
+    pub fn member_id(&self) -> &str {
+        &self.membership.member_id
+    }
+}
+
+impl Stream for ConsumerGroupStream<'_> {
+    type Item = Result<Record, ErrorCode>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for ConsumerGroupStream<'_> {
+    fn drop(&mut self) {
+        if self.membership.instance_id.is_some() {
+            debug!(group = %self.membership.group, "static member keeps its partitions until the session timeout");
+            return;
+        }
+        let membership = self.membership.clone();
+        fluvio_future::task::spawn(async move {
+            if let Err(err) = membership.leave().await {
+                warn!(%err, "failed to leave consumer group");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fenced() {
+        let fenced =
+            anyhow::Error::from(ApiError::Code(ErrorCode::ConsumerGroupMemberFenced, None));
+        assert!(is_fenced(&fenced));
+        let not_found =
+            anyhow::Error::from(ApiError::Code(ErrorCode::ConsumerGroupMemberNotFound, None));
+        assert!(!is_fenced(&not_found));
+    }
+
+    #[test]
+    fn test_rebalance_partitions() {
+        let rebalance = Rebalance::new(3, &[0, 1, 2], vec![1, 2, 4]);
+        assert_eq!(rebalance.assigned, [4]);
+        assert_eq!(rebalance.revoked, [0]);
+
+        let first = Rebalance::new(1, &[], vec![0, 1]);
+        assert_eq!(first.assigned, [0, 1]);
+        assert!(first.revoked.is_empty());
+    }
+}
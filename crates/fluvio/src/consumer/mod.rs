@@ -3,30 +3,37 @@
 mod config;
 mod stream;
 mod offset;
+mod reset;
+#[cfg(not(target_arch = "wasm32"))]
+mod group;
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
 
 use anyhow::Result;
-use async_channel::Sender;
+use async_channel::{Receiver, Sender};
+use fluvio_socket::VersionedSerialSocket;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetRequest;
 use tracing::{debug, error, trace, instrument, info, warn};
-use futures_util::stream::{Stream, select_all};
+use futures_util::stream::{BoxStream, Stream, select_all};
 use once_cell::sync::Lazy;
 use futures_util::future::{Either, err, join_all};
 use futures_util::stream::{StreamExt, once, iter};
 use futures_util::FutureExt;
 
 use fluvio_types::PartitionId;
+use fluvio_types::event::StickyEvent;
 use fluvio_types::defaults::{FLUVIO_CLIENT_MAX_FETCH_BYTES, FLUVIO_MAX_SIZE_TOPIC_NAME};
 use fluvio_spu_schema::server::stream_fetch::{
     DefaultStreamFetchRequest, DefaultStreamFetchResponse, CHAIN_SMARTMODULE_API,
-    OFFSET_MANAGEMENT_API,
+    OFFSET_MANAGEMENT_API, CONSUMER_APPLICATION_API,
 };
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Batch;
 
 use crate::FluvioError;
+use crate::chunking::ChunkAssembler;
 use crate::metrics::ClientMetrics;
 use crate::offset::{Offset, fetch_offsets};
 use crate::spu::{SpuDirectory, SpuSocketPool};
@@ -35,6 +42,9 @@ pub use config::{ConsumerConfig, ConsumerConfigBuilder};
 pub use config::{ConsumerConfigExt, ConsumerConfigExtBuilder, OffsetManagementStrategy};
 pub use stream::{ConsumerStream, MultiplePartitionConsumerStream, SinglePartitionConsumerStream};
 pub use offset::ConsumerOffset;
+pub use reset::{OffsetReset, ConsumerOffsetReset};
+#[cfg(not(target_arch = "wasm32"))]
+pub use group::{ConsumerGroupConfig, ConsumerGroupConfigBuilder, ConsumerGroupStream, Rebalance};
 
 pub use fluvio_protocol::record::ConsumerRecord as Record;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
@@ -205,7 +215,7 @@ where
         offset: Offset,
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Record, ErrorCode>>> {
-        let (stream, start_offset, _) = self
+        let (stream, start_offset, snapshot_end, _) = self
             .inner_stream_batches_with_config(offset, config, None)
             .await?;
         let partition = self.partition;
@@ -216,7 +226,7 @@ where
                     batch
                         .into_consumer_records_iter(partition)
                         .filter_map(move |record| {
-                            if record.offset >= start_offset {
+                            if in_range(record.offset, start_offset, snapshot_end) {
                                 Some(Ok(record))
                             } else {
                                 None
@@ -264,14 +274,14 @@ where
         offset: Offset,
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Batch, ErrorCode>>> {
-        let (stream, _start_offset, _) = self
+        let (stream, _start_offset, _snapshot_end, _) = self
             .inner_stream_batches_with_config(offset, config, None)
             .await?;
         Ok(stream)
     }
 
     /// Continuously streams batches of messages, starting an offset in the consumer's partition
-    /// Returns both the stream and the start offset of the stream.
+    /// Returns the stream, the start offset and, for snapshot streams, the end offset of the stream.
     #[instrument(skip(self, offset, config))]
     async fn inner_stream_batches_with_config(
         &self,
@@ -281,9 +291,10 @@ where
     ) -> Result<(
         impl Stream<Item = Result<Batch, ErrorCode>>,
         fluvio_protocol::record::Offset,
+        Option<fluvio_protocol::record::Offset>,
         Sender<StreamToServer>,
     )> {
-        let (stream, start_offset, stream_to_server) =
+        let (stream, start_offset, snapshot_end, stream_to_server) =
             self.request_stream(offset, config, consumer_id).await?;
         let metrics = self.metrics.clone();
         let flattened =
@@ -334,13 +345,13 @@ where
                 Either::Left(iter(items))
             });
 
-        Ok((flattened, start_offset, stream_to_server))
+        Ok((flattened, start_offset, snapshot_end, stream_to_server))
     }
 
     /// Creates a stream of `DefaultStreamFetchResponse` for older consumers who rely
     /// on the internal structure of the fetch response. New clients should use the
     /// `stream` and `stream_with_config` methods.
-    /// Returns both the stream and the start offset of the stream.
+    /// Returns the stream, the start offset and, for snapshot streams, the end offset of the stream.
     #[instrument(skip(self, config))]
     async fn request_stream(
         &self,
@@ -350,9 +361,9 @@ where
     ) -> Result<(
         impl Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>>,
         fluvio_protocol::record::Offset,
+        Option<fluvio_protocol::record::Offset>,
         Sender<StreamToServer>,
     )> {
-        use fluvio_future::task::spawn;
         use futures_util::stream::empty;
 
         let replica = ReplicaKey::new(&self.topic, self.partition);
@@ -365,15 +376,73 @@ where
 
         debug!(start_absolute_offset, end_absolute_offset, record_count);
 
+        let (server_sender, server_recv) =
+            async_channel::bounded::<StreamToServer>(STREAM_TO_SERVER_CHANNEL_SIZE);
+        let next_offset = Arc::new(AtomicI64::new(start_absolute_offset));
+
+        let ft_stream = self
+            .open_stream(
+                serial_socket,
+                start_absolute_offset,
+                config.clone(),
+                consumer_id.clone(),
+                (server_sender.clone(), server_recv.clone()),
+                next_offset.clone(),
+            )
+            .await?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let ft_stream = Box::pin(self.clone().resume_on_drain(
+            ft_stream,
+            config.clone(),
+            consumer_id,
+            (server_sender.clone(), server_recv),
+            next_offset,
+        ));
+        let ft_stream = publish_stream::EndPublishSt::new(ft_stream, server_sender.clone());
+
+        let snapshot_end = config.snapshot.then_some(end_absolute_offset);
+        let stream = if let Some(end) = snapshot_end {
+            if start_absolute_offset >= end {
+                // nothing to read, don't wait for records produced after the snapshot
+                Either::Left(Either::Left(empty()))
+            } else {
+                Either::Left(Either::Right(TakeUntilOffset::new(ft_stream, end)))
+            }
+        } else if config.disable_continuous {
+            Either::Right(Either::Left(TakeRecords::new(ft_stream, record_count)))
+        } else {
+            Either::Right(Either::Right(ft_stream))
+        };
+
+        Ok((stream, start_absolute_offset, snapshot_end, server_sender))
+    }
+
+    /// Opens a stream from `offset` on the connection of `serial_socket`. Offsets consumed by the
+    /// stream are sent to the SPU through `server_channel` and tracked in `next_offset`.
+    async fn open_stream(
+        &self,
+        serial_socket: VersionedSerialSocket,
+        offset: fluvio_protocol::record::Offset,
+        config: ConsumerConfig,
+        consumer_id: Option<String>,
+        server_channel: (Sender<StreamToServer>, Receiver<StreamToServer>),
+        next_offset: Arc<AtomicI64>,
+    ) -> Result<BoxStream<'static, Result<DefaultStreamFetchResponse, ErrorCode>>> {
+        use fluvio_future::task::spawn;
+        use futures_util::stream::empty;
+
+        let replica = ReplicaKey::new(&self.topic, self.partition);
         let with_consumer_id = consumer_id.is_some();
+        let with_application = config.application.is_some();
         let stream_request = DefaultStreamFetchRequest::builder()
             .topic(self.topic.to_owned())
             .partition(self.partition)
-            .fetch_offset(start_absolute_offset)
+            .fetch_offset(offset)
             .isolation(config.isolation)
             .max_bytes(config.max_bytes)
             .smartmodules(config.smartmodule)
             .consumer_id(consumer_id)
+            .application(config.application)
             .build()?;
 
         let stream_fetch_version = serial_socket
@@ -387,16 +456,17 @@ where
         if with_consumer_id && stream_fetch_version < OFFSET_MANAGEMENT_API {
             warn!("SPU does not support Offset Management API");
         }
+        if with_application && stream_fetch_version < CONSUMER_APPLICATION_API {
+            warn!("SPU does not support consumer application metrics");
+        }
 
         let mut stream = self
             .pool
             .create_stream_with_version(&replica, stream_request, stream_fetch_version)
             .await?;
 
-        let (server_sender, server_recv) =
-            async_channel::bounded::<StreamToServer>(STREAM_TO_SERVER_CHANNEL_SIZE);
-
-        let server_sender_clone = server_sender.clone();
+        let (server_sender_clone, server_recv) = server_channel;
+        let drained = StickyEvent::shared();
 
         let ft_stream = async move {
             if let Some(Ok(raw_response)) = stream.next().await {
@@ -411,12 +481,29 @@ where
                     "first stream response"
                 );
 
+                if response.partition.error_code == ErrorCode::ConnectionDraining {
+                    debug!("connection draining, stream not started");
+                    serial_socket.new_socket().set_stale();
+                    return Either::Right(Either::Left(iter(vec![Ok(response)])));
+                }
+                if let Some(last_offset) = response.partition.next_offset_for_fetch() {
+                    next_offset.store(last_offset, Ordering::SeqCst);
+                }
+
                 // update stream with received offsets
+                let stale_socket = serial_socket.new_socket();
+                let loop_drained = drained.clone();
                 spawn(async move {
                     use fluvio_spu_schema::server::update_offset::{UpdateOffsetsRequest, OffsetUpdate};
+                    use tokio::select;
 
                     loop {
-                        match server_recv.recv().await {
+                        let message = select! {
+                            message = server_recv.recv() => message,
+                            // the stream continues on a new connection with its own loop
+                            _ = loop_drained.listen() => break,
+                        };
+                        match message {
                             Ok(StreamToServer::UpdateOffset(fetch_last_value)) => {
                                 debug!(fetch_last_value, stream_id, "received end fetch");
                                 debug!(
@@ -478,11 +565,17 @@ where
                         .await;
                 }
 
-                let server_sender_clone2 = server_sender_clone.clone();
                 let update_stream = StreamExt::map(stream, move |item| {
                     item.inspect(|response| {
-                        if let Some(last_offset) = response.partition.next_offset_for_fetch() {
+                        if response.partition.error_code == ErrorCode::ConnectionDraining {
+                            debug!(stream_id, "connection draining");
+                            drained.notify();
+                            // next streams to the spu open a new connection
+                            stale_socket.set_stale();
+                        } else if let Some(last_offset) = response.partition.next_offset_for_fetch()
+                        {
                             debug!(last_offset, stream_id, "received last offset from spu");
+                            next_offset.store(last_offset, Ordering::SeqCst);
                             let _ = server_sender_clone
                                 .try_send(StreamToServer::UpdateOffset(last_offset));
                         }
@@ -492,25 +585,88 @@ where
                         ErrorCode::Other(e.to_string())
                     })
                 });
-                Either::Left(
-                    iter(vec![Ok(response)]).chain(publish_stream::EndPublishSt::new(
-                        update_stream,
-                        server_sender_clone2,
-                    )),
-                )
+                Either::Left(iter(vec![Ok(response)]).chain(update_stream))
             } else {
                 info!("stream ended");
-                Either::Right(empty())
+                Either::Right(Either::Right(empty()))
             }
         };
 
-        let stream = if config.disable_continuous {
-            TakeRecords::new(ft_stream.flatten_stream().boxed(), record_count).boxed()
-        } else {
-            ft_stream.flatten_stream().boxed()
-        };
+        Ok(ft_stream.flatten_stream().boxed())
+    }
 
-        Ok((stream, start_absolute_offset, server_sender))
+    /// Moves the stream to a new connection when the SPU drains the connection it runs on,
+    /// e.g. once the connection reached its max age. The stream continues from the offset
+    /// following the last response, the draining response itself is not passed on.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn resume_on_drain(
+        self,
+        stream: BoxStream<'static, Result<DefaultStreamFetchResponse, ErrorCode>>,
+        config: ConsumerConfig,
+        consumer_id: Option<String>,
+        server_channel: (Sender<StreamToServer>, Receiver<StreamToServer>),
+        next_offset: Arc<AtomicI64>,
+    ) -> impl Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>> {
+        futures_util::stream::unfold(Some(stream), move |current| {
+            let consumer = self.clone();
+            let config = config.clone();
+            let consumer_id = consumer_id.clone();
+            let server_channel = server_channel.clone();
+            let next_offset = next_offset.clone();
+            async move {
+                let mut stream = current?;
+                loop {
+                    match stream.next().await {
+                        Some(Ok(response))
+                            if response.partition.error_code == ErrorCode::ConnectionDraining =>
+                        {
+                            let offset = next_offset.load(Ordering::SeqCst);
+                            info!(offset, "connection drained by spu, resuming stream");
+                            match consumer
+                                .reopen_stream(
+                                    offset,
+                                    config.clone(),
+                                    consumer_id.clone(),
+                                    server_channel.clone(),
+                                    next_offset.clone(),
+                                )
+                                .await
+                            {
+                                Ok(resumed) => stream = resumed,
+                                Err(err) => {
+                                    error!(?err, "failed to resume stream");
+                                    return Some((Err(ErrorCode::ConnectionDraining), None));
+                                }
+                            }
+                        }
+                        Some(item) => return Some((item, Some(stream))),
+                        None => return None,
+                    }
+                }
+            }
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn reopen_stream(
+        &self,
+        offset: fluvio_protocol::record::Offset,
+        config: ConsumerConfig,
+        consumer_id: Option<String>,
+        server_channel: (Sender<StreamToServer>, Receiver<StreamToServer>),
+        next_offset: Arc<AtomicI64>,
+    ) -> Result<BoxStream<'static, Result<DefaultStreamFetchResponse, ErrorCode>>> {
+        let replica = ReplicaKey::new(&self.topic, self.partition);
+        let serial_socket = self.pool.create_serial_socket(&replica).await?;
+        self.open_stream(
+            serial_socket,
+            offset,
+            config,
+            consumer_id,
+            server_channel,
+            next_offset,
+        )
+        .await
     }
 
     #[instrument(skip(self, config))]
@@ -519,7 +675,7 @@ where
         config: ConsumerConfigExt,
     ) -> Result<SinglePartitionConsumerStream<impl Stream<Item = Result<Record, ErrorCode>>>> {
         let (offset, config, consumer_id, strategy, flush_period) = config.into_parts();
-        let (stream, start_offset, stream_to_server) = self
+        let (stream, start_offset, snapshot_end, stream_to_server) = self
             .inner_stream_batches_with_config(offset, config, consumer_id)
             .await?;
         let partition = self.partition;
@@ -530,7 +686,7 @@ where
                     batch
                         .into_consumer_records_iter(partition)
                         .filter_map(move |record| {
-                            if record.offset >= start_offset {
+                            if in_range(record.offset, start_offset, snapshot_end) {
                                 Some(Ok(record))
                             } else {
                                 None
@@ -539,15 +695,39 @@ where
                 Either::Left(iter(records))
             }
         });
-        Ok(SinglePartitionConsumerStream::new(
-            flattened,
+        let mut assembler = ChunkAssembler::default();
+        let commit_bound = assembler.commit_bound();
+        let reassembled = flattened.filter_map(move |result| {
+            std::future::ready(match result {
+                Ok(record) => assembler.push(record).map(Ok),
+                Err(e) => Some(Err(e)),
+            })
+        });
+        let stream = SinglePartitionConsumerStream::new(
+            partition,
+            reassembled,
             strategy,
             flush_period,
             stream_to_server,
-        ))
+        )
+        .with_commit_bound(commit_bound);
+        Ok(match snapshot_end {
+            Some(end) => stream.with_snapshot_end(end),
+            None => stream,
+        })
     }
 }
 
+/// records before `start` belong to the first batch only, records from `end` on were
+/// produced after the snapshot was taken
+fn in_range(
+    offset: fluvio_protocol::record::Offset,
+    start: fluvio_protocol::record::Offset,
+    end: Option<fluvio_protocol::record::Offset>,
+) -> bool {
+    offset >= start && end.map_or(true, |end| offset < end)
+}
+
 /// Wrap an inner record stream and only stream until a given number of records have been fetched.
 ///
 /// This is used for "disable continuous" mode. In this mode, we first make a FetchOffsetPartitionResponse
@@ -609,6 +789,62 @@ where
     }
 }
 
+/// Wrap an inner response stream and end it once the responses reach the `end` offset.
+///
+/// This is used for snapshot streams, which are pinned to the last stable offset at the
+/// time the stream is opened. Unlike `TakeRecords`, it follows the offsets of the responses,
+/// so records skipped by SmartModule filters are accounted for.
+struct TakeUntilOffset<S> {
+    end: fluvio_protocol::record::Offset,
+    done: bool,
+    stream: S,
+}
+
+impl<S> TakeUntilOffset<S>
+where
+    S: Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>> + std::marker::Unpin,
+{
+    pub fn new(stream: S, end: fluvio_protocol::record::Offset) -> Self {
+        Self {
+            end,
+            done: false,
+            stream,
+        }
+    }
+}
+
+impl<S> Stream for TakeUntilOffset<S>
+where
+    S: Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>> + std::marker::Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        use std::{pin::Pin, task::Poll};
+        use futures_util::ready;
+        if self.done {
+            return Poll::Ready(None);
+        }
+        let next = ready!(Pin::new(&mut self.as_mut().stream).poll_next(cx));
+        match next {
+            Some(Ok(response)) => {
+                if response
+                    .partition
+                    .next_offset_for_fetch()
+                    .is_some_and(|next| next >= self.end)
+                {
+                    self.done = true;
+                }
+                Poll::Ready(Some(Ok(response)))
+            }
+            other => Poll::Ready(other),
+        }
+    }
+}
+
 mod publish_stream {
 
     use std::pin::Pin;
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use futures_util::StreamExt;
+use serde::Serialize;
+use tracing::debug;
+
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::PartitionId;
+
+use crate::offset::fetch_offsets;
+use crate::spu::SpuDirectory;
+use crate::Offset;
+
+use super::{ConsumerConfig, PartitionConsumer};
+
+/// Position a consumer offset is reset to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffsetReset {
+    /// first readable offset of the partition
+    Earliest,
+    /// end of the partition, only new records are consumed
+    Latest,
+    /// absolute offset, clamped to the readable range of the partition
+    Offset(i64),
+    /// first record with timestamp at or after the given time, in milliseconds since epoch
+    Timestamp(i64),
+}
+
+/// Change of a consumer offset made by a reset
+///
+/// Positions are the offsets of the next record to consume,
+/// one past the offset committed by the consumer.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ConsumerOffsetReset {
+    pub consumer_id: String,
+    pub topic: String,
+    pub partition: PartitionId,
+    /// None if the consumer has not committed any offset for this partition
+    pub current_position: Option<i64>,
+    pub new_position: i64,
+}
+
+impl ConsumerOffsetReset {
+    /// offset to commit so the consumer resumes from `new_position`
+    pub fn committed_offset(&self) -> i64 {
+        self.new_position - 1
+    }
+}
+
+impl<P> PartitionConsumer<P>
+where
+    P: SpuDirectory,
+{
+    /// Find offset of the next record to consume after resetting to `target`
+    pub(crate) async fn resolve_reset(&self, target: OffsetReset) -> Result<i64> {
+        let replica = ReplicaKey::new(&self.topic, self.partition);
+        let mut serial_socket = self.pool.create_serial_socket(&replica).await?;
+        let offsets = fetch_offsets(&mut serial_socket, &replica, None).await?;
+        let (start, end) = (offsets.start_offset, offsets.last_stable_offset);
+
+        let position = match target {
+            OffsetReset::Earliest => start,
+            OffsetReset::Latest => end,
+            OffsetReset::Offset(offset) => offset.clamp(start, end),
+            OffsetReset::Timestamp(timestamp) => {
+                self.search_timestamp(start, end, timestamp).await?
+            }
+        };
+        debug!(%replica, start, end, position, "resolved offset reset");
+        Ok(position)
+    }
+
+    /// binary search for the first record in `start..end` not older than `timestamp`
+    async fn search_timestamp(&self, mut start: i64, mut end: i64, timestamp: i64) -> Result<i64> {
+        while start < end {
+            let middle = start + (end - start) / 2;
+            let Some((offset, record_timestamp)) = self.record_at(middle).await? else {
+                end = middle;
+                continue;
+            };
+            if record_timestamp < timestamp {
+                // records up to the one found are older
+                start = offset + 1;
+            } else {
+                end = middle;
+            }
+        }
+        Ok(start)
+    }
+
+    /// read offset and timestamp of the first available record from `offset`
+    #[allow(deprecated)]
+    async fn record_at(&self, offset: i64) -> Result<Option<(i64, i64)>> {
+        let config = ConsumerConfig::builder().build()?;
+        let mut stream = self
+            .stream_with_config(Offset::absolute(offset)?, config)
+            .await?;
+        match stream.next().await {
+            Some(Ok(record)) => Ok(Some((record.offset(), record.timestamp()))),
+            Some(Err(err)) => Err(err.into()),
+            None => Ok(None),
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::task::Waker;
 use std::time::{Duration, SystemTime};
 
 use async_channel::Sender;
@@ -9,6 +11,11 @@ use futures_util::{future::try_join_all, ready, Future, FutureExt};
 use futures_util::Stream;
 use tracing::warn;
 
+use fluvio_protocol::record::Offset;
+use fluvio_types::PartitionId;
+
+use crate::chunking::CommitBound;
+
 use super::config::OffsetManagementStrategy;
 use super::{offset::OffsetLocalStore, StreamToServer};
 
@@ -20,15 +27,44 @@ pub trait ConsumerStream: Stream<Item = Result<Record, ErrorCode>> + Unpin {
 
     /// Send the committed offset to the server. The method waits for the server's acknowledgment before it finishes.
     fn offset_flush(&mut self) -> impl Future<Output = Result<(), ErrorCode>> + Send;
+
+    /// End offset of each partition of a snapshot stream, fixed when the stream was opened.
+    /// The stream ends once all records before these offsets were yielded.
+    /// Empty if the stream was not opened with `snapshot`.
+    fn snapshot_end_offsets(&self) -> BTreeMap<PartitionId, Offset> {
+        BTreeMap::new()
+    }
+
+    /// Stop yielding records of `partitions`, records of the other partitions keep
+    /// streaming. Records already received are kept, and no more are fetched from
+    /// the SPU until the partitions are resumed.
+    fn pause(&mut self, _partitions: &[PartitionId]) {}
+
+    /// Continue streaming the paused `partitions` with their kept records.
+    fn resume(&mut self, _partitions: &[PartitionId]) {}
+
+    /// Partitions stopped with [`ConsumerStream::pause`].
+    fn paused_partitions(&self) -> Vec<PartitionId> {
+        vec![]
+    }
 }
 
 pub struct MultiplePartitionConsumerStream<T> {
     partition_streams: futures_util::stream::SelectAll<SinglePartitionConsumerStream<T>>,
     offset_mgnts: Vec<Arc<OffsetManagement>>,
+    snapshot_ends: BTreeMap<PartitionId, Offset>,
+    /// dictionary of the topic, registered while the stream lives
+    #[cfg(feature = "compress")]
+    compression_dictionary: Option<fluvio_compression::DictionaryRegistration>,
 }
 
 pub struct SinglePartitionConsumerStream<T> {
+    partition: PartitionId,
     offset_mngt: Arc<OffsetManagement>,
+    snapshot_end: Option<(PartitionId, Offset)>,
+    /// waker of the last poll while paused, woken on resume
+    paused: Option<Option<Waker>>,
+    commit_bound: Option<CommitBound>,
     inner: T,
 }
 
@@ -51,20 +87,35 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> MultiplePartitionConsu
     {
         let mut partition_streams = Vec::new();
         let mut offset_mgnts = Vec::new();
+        let mut snapshot_ends = BTreeMap::new();
         for partition_stream in streams.into_iter() {
             offset_mgnts.push(partition_stream.offset_mngt.clone());
+            snapshot_ends.extend(partition_stream.snapshot_end);
             partition_streams.push(partition_stream);
         }
         let partition_streams = select_all(partition_streams);
         Self {
             partition_streams,
             offset_mgnts,
+            snapshot_ends,
+            #[cfg(feature = "compress")]
+            compression_dictionary: None,
         }
     }
+
+    #[cfg(feature = "compress")]
+    pub(crate) fn with_compression_dictionary(
+        mut self,
+        dictionary: Option<fluvio_compression::DictionaryRegistration>,
+    ) -> Self {
+        self.compression_dictionary = dictionary;
+        self
+    }
 }
 
 impl<T> SinglePartitionConsumerStream<T> {
     pub(super) fn new(
+        partition: PartitionId,
         inner: T,
         offset_strategy: OffsetManagementStrategy,
         flush_period: Duration,
@@ -82,10 +133,33 @@ impl<T> SinglePartitionConsumerStream<T> {
             },
         };
         Self {
+            partition,
             offset_mngt: Arc::new(offset_mngt),
+            snapshot_end: None,
+            paused: None,
+            commit_bound: None,
             inner,
         }
     }
+
+    pub(super) fn with_snapshot_end(mut self, end: Offset) -> Self {
+        self.snapshot_end = Some((self.partition, end));
+        self
+    }
+
+    /// keep the offsets of the stream before the chunked records being reassembled
+    pub(super) fn with_commit_bound(mut self, bound: CommitBound) -> Self {
+        self.commit_bound = Some(bound);
+        self
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        match (paused, self.paused.take()) {
+            (true, waker) => self.paused = Some(waker.flatten()),
+            (false, Some(Some(waker))) => waker.wake(),
+            (false, _) => {}
+        }
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> Stream
@@ -98,10 +172,19 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> Stream
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
         let self_mut = self.get_mut();
+        // not polling the inner stream holds back the offset updates the SPU waits for
+        if let Some(waker) = &mut self_mut.paused {
+            *waker = Some(cx.waker().clone());
+            return std::task::Poll::Pending;
+        }
         let pinned = std::pin::pin!(&mut self_mut.inner);
         match ready!(pinned.poll_next(cx)) {
             Some(Ok(last)) => {
-                self_mut.offset_mngt.update(last.offset);
+                let offset = match &self_mut.commit_bound {
+                    Some(bound) => bound.limit(last.offset),
+                    None => last.offset,
+                };
+                self_mut.offset_mngt.update(offset);
                 std::task::Poll::Ready(Some(Ok(last)))
             }
             other => std::task::Poll::Ready(other),
@@ -119,6 +202,26 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
     fn offset_flush(&mut self) -> impl Future<Output = Result<(), ErrorCode>> + Send {
         self.offset_mngt.flush()
     }
+
+    fn snapshot_end_offsets(&self) -> BTreeMap<PartitionId, Offset> {
+        self.snapshot_end.into_iter().collect()
+    }
+
+    fn pause(&mut self, partitions: &[PartitionId]) {
+        if partitions.contains(&self.partition) {
+            self.set_paused(true);
+        }
+    }
+
+    fn resume(&mut self, partitions: &[PartitionId]) {
+        if partitions.contains(&self.partition) {
+            self.set_paused(false);
+        }
+    }
+
+    fn paused_partitions(&self) -> Vec<PartitionId> {
+        self.paused.iter().map(|_| self.partition).collect()
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
@@ -135,6 +238,32 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
         let futures: Vec<_> = self.offset_mgnts.iter().map(|p| p.flush()).collect();
         try_join_all(futures).map(|r| r.map(|_| ()))
     }
+
+    fn snapshot_end_offsets(&self) -> BTreeMap<PartitionId, Offset> {
+        self.snapshot_ends.clone()
+    }
+
+    fn pause(&mut self, partitions: &[PartitionId]) {
+        for partition_stream in self.partition_streams.iter_mut() {
+            partition_stream.pause(partitions);
+        }
+    }
+
+    fn resume(&mut self, partitions: &[PartitionId]) {
+        for partition_stream in self.partition_streams.iter_mut() {
+            partition_stream.resume(partitions);
+        }
+    }
+
+    fn paused_partitions(&self) -> Vec<PartitionId> {
+        let mut paused: Vec<PartitionId> = self
+            .partition_streams
+            .iter()
+            .flat_map(|partition_stream| partition_stream.paused_partitions())
+            .collect();
+        paused.sort_unstable();
+        paused
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> Stream
@@ -247,7 +376,6 @@ mod tests {
     use fluvio_future::timer::sleep;
     use fluvio_protocol::record::Batch;
     use fluvio_smartmodule::RecordData;
-    use fluvio_types::PartitionId;
     use futures_util::{stream::Iter, StreamExt};
 
     use super::*;
@@ -257,6 +385,7 @@ mod tests {
         //given
         let (tx, _rx) = async_channel::unbounded();
         let partition_stream = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1", "2"]),
             Default::default(),
             Default::default(),
@@ -283,6 +412,7 @@ mod tests {
         //given
         let (tx, _rx) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1"]),
             Default::default(),
             Default::default(),
@@ -290,6 +420,7 @@ mod tests {
         );
         let (tx, _rx) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
+            1,
             records_stream(1, ["2", "4", "6"]),
             Default::default(),
             Default::default(),
@@ -297,6 +428,7 @@ mod tests {
         );
         let (tx, _rx) = async_channel::unbounded();
         let partition_stream3 = SinglePartitionConsumerStream::new(
+            2,
             records_stream(2, ["3", "5"]),
             Default::default(),
             Default::default(),
@@ -323,11 +455,48 @@ mod tests {
         assert_eq!(result, ["1", "2", "3", "4", "5", "6"]);
     }
 
+    #[fluvio_future::test]
+    async fn test_multi_partition_stream_pause_and_resume() {
+        //given
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream1 = SinglePartitionConsumerStream::new(
+            0,
+            records_stream(0, ["1", "3"]),
+            Default::default(),
+            Default::default(),
+            tx,
+        );
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream2 = SinglePartitionConsumerStream::new(
+            1,
+            records_stream(1, ["2", "4"]),
+            Default::default(),
+            Default::default(),
+            tx,
+        );
+        let mut multi_stream =
+            MultiplePartitionConsumerStream::new([partition_stream1, partition_stream2]);
+        //when
+        multi_stream.pause(&[1]);
+
+        //then
+        assert_eq!(multi_stream.paused_partitions(), [1]);
+        assert_eq!(ready_value(&mut multi_stream).as_deref(), Some("1"));
+        assert_eq!(ready_value(&mut multi_stream).as_deref(), Some("3"));
+        assert_eq!(ready_value(&mut multi_stream), None); // partition 1 is paused
+
+        multi_stream.resume(&[1]);
+        assert!(multi_stream.paused_partitions().is_empty());
+        assert_eq!(ready_value(&mut multi_stream).as_deref(), Some("2"));
+        assert_eq!(ready_value(&mut multi_stream).as_deref(), Some("4"));
+    }
+
     #[fluvio_future::test]
     async fn test_none_offset_strategy_raise_error_on_commit() {
         //given
         let (tx, _rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, []),
             OffsetManagementStrategy::None,
             Default::default(),
@@ -346,6 +515,7 @@ mod tests {
         //given
         let (tx, _rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, []),
             OffsetManagementStrategy::None,
             Default::default(),
@@ -364,6 +534,7 @@ mod tests {
         //given
         let (tx, rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1", "2", "3", "4"]),
             OffsetManagementStrategy::Manual,
             Default::default(),
@@ -403,6 +574,7 @@ mod tests {
         //given
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1"]),
             OffsetManagementStrategy::Manual,
             Default::default(),
@@ -410,6 +582,7 @@ mod tests {
         );
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
+            1,
             records_stream(1, ["2", "4", "6"]),
             OffsetManagementStrategy::Manual,
             Default::default(),
@@ -472,6 +645,7 @@ mod tests {
         //given
         let (tx, rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1", "2", "3", "4"]),
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1000),
@@ -503,6 +677,7 @@ mod tests {
         //given
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1"]),
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1000),
@@ -510,6 +685,7 @@ mod tests {
         );
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
+            1,
             records_stream(1, ["2", "4", "6"]),
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1000),
@@ -556,6 +732,7 @@ mod tests {
         //given
         let (tx, rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1", "2", "3", "4"]),
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1),
@@ -596,6 +773,7 @@ mod tests {
         //given
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1"]),
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1),
@@ -603,6 +781,7 @@ mod tests {
         );
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
+            1,
             records_stream(1, ["2", "4", "6"]),
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1),
@@ -658,6 +837,7 @@ mod tests {
         //given
         let (tx, rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1", "2", "3", "4"]),
             OffsetManagementStrategy::Manual,
             Default::default(),
@@ -697,6 +877,7 @@ mod tests {
         //given
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
+            0,
             records_stream(0, ["1"]),
             OffsetManagementStrategy::Manual,
             Default::default(),
@@ -704,6 +885,7 @@ mod tests {
         );
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
+            1,
             records_stream(1, ["2", "4", "6"]),
             OffsetManagementStrategy::Manual,
             Default::default(),
@@ -749,6 +931,59 @@ mod tests {
         assert_eq!(flush_res, Err(ErrorCode::SpuOffline), "{flush_res:?}");
     }
 
+    #[fluvio_future::test]
+    async fn test_snapshot_end_offsets() {
+        //given
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream1 = SinglePartitionConsumerStream::new(
+            0,
+            records_stream(0, ["1"]),
+            Default::default(),
+            Default::default(),
+            tx,
+        )
+        .with_snapshot_end(0, 1);
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream2 = SinglePartitionConsumerStream::new(
+            1,
+            records_stream(1, ["2", "4"]),
+            Default::default(),
+            Default::default(),
+            tx,
+        )
+        .with_snapshot_end(1, 2);
+        let (tx, _rx) = async_channel::unbounded();
+        let not_pinned = SinglePartitionConsumerStream::new(
+            2,
+            records_stream(2, []),
+            Default::default(),
+            Default::default(),
+            tx,
+        );
+        assert!(not_pinned.snapshot_end_offsets().is_empty());
+
+        //when
+        let multi_stream = MultiplePartitionConsumerStream::new([
+            partition_stream1,
+            partition_stream2,
+            not_pinned,
+        ]);
+
+        //then
+        assert_eq!(
+            multi_stream.snapshot_end_offsets(),
+            BTreeMap::from([(0, 1), (1, 2)])
+        );
+    }
+
+    /// value of the next record if it is ready
+    fn ready_value(
+        stream: &mut (impl Stream<Item = Result<Record, ErrorCode>> + Unpin),
+    ) -> Option<String> {
+        let record = stream.next().now_or_never()??;
+        Some(String::from_utf8_lossy(record.expect("record").as_ref()).to_string())
+    }
+
     fn records_stream(
         partition: PartitionId,
         input: impl IntoIterator<Item = &'static str>,
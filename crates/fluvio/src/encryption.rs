@@ -0,0 +1,468 @@
+//!
+//! # Record Encryption
+//!
+//! End-to-end encryption of record values. Producers seal each value in an
+//! envelope with AES-256-GCM before it leaves the client, so SPUs only store
+//! ciphertext; consumers holding the keys open the envelopes transparently.
+//!
+//! The envelope carries the id of the key used, so keys can be rotated by
+//! changing the current key of the [`KeyProvider`] while older records stay readable:
+//!
+//! ```text
+//! "FLVE" | version: u8 | key id length: u8 | key id | nonce: 12 bytes | ciphertext + tag
+//! ```
+//!
+//! Record keys are not encrypted since they are used for partitioning. Values which
+//! are not envelopes are refused by consumers unless plaintext is allowed, e.g. while
+//! a topic still has records produced before encryption was enabled.
+//!
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, Result};
+use async_lock::RwLock;
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use futures_util::{ready, Future, FutureExt, Stream, StreamExt};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{ConsumerRecord, Offset, Record, RecordData};
+use fluvio_types::PartitionId;
+
+use crate::consumer::ConsumerStream;
+
+const MAGIC: &[u8; 4] = b"FLVE";
+const ENVELOPE_VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+/// ids the provider failed to return are not looked up again for this long
+const UNKNOWN_KEY_TTL: Duration = Duration::from_secs(60);
+/// bound of the unknown ids remembered, envelopes with further ids are refused
+const MAX_UNKNOWN_KEYS: usize = 1024;
+
+/// AES-256 key identified by `id`
+#[derive(Clone)]
+pub struct EncryptionKey {
+    id: String,
+    material: [u8; 32],
+}
+
+impl EncryptionKey {
+    /// key ids are stored in every envelope and limited to 255 bytes
+    pub fn new(id: impl Into<String>, material: [u8; 32]) -> Result<Self> {
+        let id = id.into();
+        if id.is_empty() || id.len() > u8::MAX as usize {
+            return Err(anyhow!("encryption key id must be 1 to 255 bytes long"));
+        }
+        Ok(Self { id, material })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EncryptionKey({})", self.id)
+    }
+}
+
+/// Source of encryption keys, usually backed by a KMS.
+///
+/// Keys are fetched once per id by [`RecordEncryptor`], but `current_key` is called
+/// for every produced record, so remote providers should cache it.
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// key used to encrypt new records
+    async fn current_key(&self) -> Result<EncryptionKey>;
+
+    /// key with the given id, used to decrypt records
+    async fn key(&self, id: &str) -> Result<EncryptionKey>;
+}
+
+/// Provider over keys held in memory
+#[derive(Debug)]
+pub struct StaticKeyProvider {
+    current: EncryptionKey,
+    keys: HashMap<String, EncryptionKey>,
+}
+
+impl StaticKeyProvider {
+    pub fn new(current: EncryptionKey) -> Self {
+        let keys = HashMap::from([(current.id.clone(), current.clone())]);
+        Self { current, keys }
+    }
+
+    /// add a key that is only used to decrypt, such as a rotated key
+    pub fn with_key(mut self, key: EncryptionKey) -> Self {
+        self.keys.insert(key.id.clone(), key);
+        self
+    }
+}
+
+#[async_trait]
+impl KeyProvider for StaticKeyProvider {
+    async fn current_key(&self) -> Result<EncryptionKey> {
+        Ok(self.current.clone())
+    }
+
+    async fn key(&self, id: &str) -> Result<EncryptionKey> {
+        self.keys
+            .get(id)
+            .cloned()
+            .ok_or_else(|| anyhow!("unknown encryption key: {id}"))
+    }
+}
+
+/// Seals and opens record values with keys of a [`KeyProvider`]
+pub struct RecordEncryptor {
+    provider: Arc<dyn KeyProvider>,
+    ciphers: RwLock<HashMap<String, Aes256Gcm>>,
+    /// ids the provider failed to return, with the time of the failure
+    unknown_keys: RwLock<HashMap<String, Instant>>,
+    allow_plaintext: bool,
+}
+
+impl fmt::Debug for RecordEncryptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("RecordEncryptor")
+    }
+}
+
+impl RecordEncryptor {
+    pub fn new(provider: impl KeyProvider + 'static) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            ciphers: RwLock::new(HashMap::new()),
+            unknown_keys: RwLock::new(HashMap::new()),
+            allow_plaintext: false,
+        }
+    }
+
+    /// return values that are not envelopes as is instead of failing,
+    /// for topics with records produced before encryption was enabled
+    pub fn with_plaintext(mut self, allow: bool) -> Self {
+        self.allow_plaintext = allow;
+        self
+    }
+
+    /// true if the value is an encryption envelope
+    pub fn is_encrypted(value: &[u8]) -> bool {
+        value.starts_with(MAGIC)
+    }
+
+    /// seal the value with the current key
+    pub async fn encrypt(&self, value: &[u8]) -> Result<Vec<u8>> {
+        let key = self.provider.current_key().await?;
+        let cipher = cipher(&key)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let mut envelope = header(key.id());
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: value,
+                    aad: &envelope,
+                },
+            )
+            .map_err(|_| anyhow!("unable to encrypt record"))?;
+        envelope.extend_from_slice(&nonce);
+        envelope.extend_from_slice(&ciphertext);
+        Ok(envelope)
+    }
+
+    /// open an envelope, values that are not envelopes are only returned as is
+    /// if plaintext is allowed
+    pub async fn decrypt(&self, value: &[u8]) -> Result<Vec<u8>> {
+        if !Self::is_encrypted(value) {
+            if self.allow_plaintext {
+                return Ok(value.to_vec());
+            }
+            return Err(anyhow!("record is not encrypted"));
+        }
+        let rest = &value[MAGIC.len()..];
+        let (&version, rest) = rest
+            .split_first()
+            .ok_or_else(|| anyhow!("truncated encryption envelope"))?;
+        if version != ENVELOPE_VERSION {
+            return Err(anyhow!("unsupported encryption envelope version {version}"));
+        }
+        let (&id_len, rest) = rest
+            .split_first()
+            .ok_or_else(|| anyhow!("truncated encryption envelope"))?;
+        let id_len = id_len as usize;
+        if rest.len() < id_len + NONCE_LEN {
+            return Err(anyhow!("truncated encryption envelope"));
+        }
+        let (id, rest) = rest.split_at(id_len);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let id = std::str::from_utf8(id)?;
+        let aad = &value[..MAGIC.len() + 2 + id_len];
+
+        self.ensure_cipher(id).await?;
+        let ciphers = self.ciphers.read().await;
+        let Some(cipher) = ciphers.get(id) else {
+            return Err(anyhow!("unknown encryption key: {id}"));
+        };
+        cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| anyhow!("unable to decrypt record with key {id}"))
+    }
+
+    /// fetch the key of `id` from the provider, unless it already failed recently.
+    /// Ids come from the records, so failures are remembered to not call the provider
+    /// for every record with a bogus id
+    async fn ensure_cipher(&self, id: &str) -> Result<()> {
+        if self.ciphers.read().await.contains_key(id) {
+            return Ok(());
+        }
+        let now = Instant::now();
+        if self
+            .unknown_keys
+            .read()
+            .await
+            .get(id)
+            .is_some_and(|failed| now.duration_since(*failed) < UNKNOWN_KEY_TTL)
+        {
+            return Err(anyhow!("unknown encryption key: {id}"));
+        }
+        {
+            let mut unknown_keys = self.unknown_keys.write().await;
+            if unknown_keys.len() >= MAX_UNKNOWN_KEYS {
+                unknown_keys.retain(|_, failed| now.duration_since(*failed) < UNKNOWN_KEY_TTL);
+                if unknown_keys.len() >= MAX_UNKNOWN_KEYS {
+                    return Err(anyhow!(
+                        "too many unknown encryption keys, refusing key {id}"
+                    ));
+                }
+            }
+        }
+
+        let key = match self.provider.key(id).await {
+            Ok(key) => key,
+            Err(err) => {
+                self.unknown_keys.write().await.insert(id.to_owned(), now);
+                return Err(err);
+            }
+        };
+        let cipher = cipher(&key)?;
+        self.unknown_keys.write().await.remove(id);
+        self.ciphers.write().await.insert(id.to_owned(), cipher);
+        Ok(())
+    }
+
+    pub(crate) async fn encrypt_record(&self, mut record: Record) -> Result<Record> {
+        record.value = RecordData::from(self.encrypt(record.value.as_ref()).await?);
+        Ok(record)
+    }
+
+    async fn decrypt_consumer_record(&self, mut record: ConsumerRecord) -> Result<ConsumerRecord> {
+        record.record.value = RecordData::from(self.decrypt(record.record.value.as_ref()).await?);
+        Ok(record)
+    }
+}
+
+fn header(key_id: &str) -> Vec<u8> {
+    let mut header = Vec::with_capacity(MAGIC.len() + 2 + key_id.len());
+    header.extend_from_slice(MAGIC);
+    header.push(ENVELOPE_VERSION);
+    header.push(key_id.len() as u8);
+    header.extend_from_slice(key_id.as_bytes());
+    header
+}
+
+fn cipher(key: &EncryptionKey) -> Result<Aes256Gcm> {
+    Aes256Gcm::new_from_slice(&key.material).map_err(|_| anyhow!("invalid encryption key"))
+}
+
+/// Consumer stream yielding decrypted records, see [`decrypt_stream`]
+pub struct DecryptingStream<S> {
+    inner: S,
+    encryptor: Arc<RecordEncryptor>,
+    pending: Option<BoxFuture<'static, Result<ConsumerRecord, ErrorCode>>>,
+}
+
+/// decrypt the records of a consumer stream, offset management is delegated to `stream`
+pub fn decrypt_stream<S: ConsumerStream>(
+    stream: S,
+    encryptor: Arc<RecordEncryptor>,
+) -> DecryptingStream<S> {
+    DecryptingStream {
+        inner: stream,
+        encryptor,
+        pending: None,
+    }
+}
+
+impl<S: ConsumerStream> Stream for DecryptingStream<S> {
+    type Item = Result<ConsumerRecord, ErrorCode>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(pending) = this.pending.as_mut() {
+                let result = ready!(pending.poll_unpin(cx));
+                this.pending = None;
+                return Poll::Ready(Some(result));
+            }
+            match ready!(this.inner.poll_next_unpin(cx)) {
+                Some(Ok(record)) => {
+                    let encryptor = this.encryptor.clone();
+                    this.pending = Some(
+                        async move {
+                            encryptor
+                                .decrypt_consumer_record(record)
+                                .await
+                                .map_err(|err| ErrorCode::Other(format!("{err:#}")))
+                        }
+                        .boxed(),
+                    );
+                }
+                other => return Poll::Ready(other),
+            }
+        }
+    }
+}
+
+impl<S: ConsumerStream> ConsumerStream for DecryptingStream<S> {
+    fn offset_commit(&mut self) -> Result<(), ErrorCode> {
+        self.inner.offset_commit()
+    }
+
+    fn offset_flush(&mut self) -> impl Future<Output = Result<(), ErrorCode>> + Send {
+        self.inner.offset_flush()
+    }
+
+    fn snapshot_end_offsets(&self) -> BTreeMap<PartitionId, Offset> {
+        self.inner.snapshot_end_offsets()
+    }
+
+    fn pause(&mut self, partitions: &[PartitionId]) {
+        self.inner.pause(partitions)
+    }
+
+    fn resume(&mut self, partitions: &[PartitionId]) {
+        self.inner.resume(partitions)
+    }
+
+    fn paused_partitions(&self) -> Vec<PartitionId> {
+        self.inner.paused_partitions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(id: &str, byte: u8) -> EncryptionKey {
+        EncryptionKey::new(id, [byte; 32]).expect("key")
+    }
+
+    #[fluvio_future::test]
+    async fn test_encrypt_round_trip() {
+        let encryptor = RecordEncryptor::new(StaticKeyProvider::new(key("k1", 1)));
+
+        let envelope = encryptor.encrypt(b"secret").await.expect("encrypt");
+        assert!(RecordEncryptor::is_encrypted(&envelope));
+        assert!(!envelope.windows(6).any(|w| w == b"secret"));
+        assert_eq!(&envelope[4..8], &[ENVELOPE_VERSION, 2, b'k', b'1']);
+
+        let value = encryptor.decrypt(&envelope).await.expect("decrypt");
+        assert_eq!(value, b"secret");
+        assert!(encryptor.decrypt(b"plain").await.is_err());
+
+        let passthrough =
+            RecordEncryptor::new(StaticKeyProvider::new(key("k1", 1))).with_plaintext(true);
+        assert_eq!(
+            passthrough.decrypt(b"plain").await.expect("plain"),
+            b"plain"
+        );
+        assert_eq!(
+            passthrough.decrypt(&envelope).await.expect("decrypt"),
+            b"secret"
+        );
+    }
+
+    /// counts the lookups of keys
+    struct CountingProvider {
+        inner: StaticKeyProvider,
+        lookups: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl KeyProvider for CountingProvider {
+        async fn current_key(&self) -> Result<EncryptionKey> {
+            self.inner.current_key().await
+        }
+
+        async fn key(&self, id: &str) -> Result<EncryptionKey> {
+            self.lookups
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.key(id).await
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_unknown_key_is_looked_up_once() {
+        let producer = RecordEncryptor::new(StaticKeyProvider::new(key("bogus", 3)));
+        let envelope = producer.encrypt(b"secret").await.expect("encrypt");
+
+        let provider = Arc::new(CountingProvider {
+            inner: StaticKeyProvider::new(key("k1", 1)),
+            lookups: Default::default(),
+        });
+        let encryptor = RecordEncryptor {
+            provider: provider.clone(),
+            ciphers: RwLock::new(HashMap::new()),
+            unknown_keys: RwLock::new(HashMap::new()),
+            allow_plaintext: false,
+        };
+        for _ in 0..3 {
+            assert!(encryptor.decrypt(&envelope).await.is_err());
+        }
+        assert_eq!(
+            provider.lookups.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_key_rotation() {
+        let old = RecordEncryptor::new(StaticKeyProvider::new(key("k1", 1)));
+        let envelope = old.encrypt(b"secret").await.expect("encrypt");
+
+        let rotated =
+            RecordEncryptor::new(StaticKeyProvider::new(key("k2", 2)).with_key(key("k1", 1)));
+        assert_eq!(
+            rotated.decrypt(&envelope).await.expect("decrypt"),
+            b"secret"
+        );
+
+        let unauthorized = RecordEncryptor::new(StaticKeyProvider::new(key("k2", 2)));
+        assert!(unauthorized.decrypt(&envelope).await.is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_tampered_envelope() {
+        let encryptor = RecordEncryptor::new(StaticKeyProvider::new(key("k1", 1)));
+        let mut envelope = encryptor.encrypt(b"secret").await.expect("encrypt");
+        let last = envelope.len() - 1;
+        envelope[last] ^= 1;
+        assert!(encryptor.decrypt(&envelope).await.is_err());
+        assert!(encryptor.decrypt(&envelope[..10]).await.is_err());
+        assert!(EncryptionKey::new("", [0; 32]).is_err());
+    }
+}
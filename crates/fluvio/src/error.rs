@@ -5,6 +5,7 @@ use fluvio_types::SpuId;
 use semver::Version;
 
 use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
+use fluvio_protocol::link::versions::ClientVersionRange;
 use fluvio_compression::CompressionError;
 use fluvio_socket::SocketError;
 use fluvio_sc_schema::ApiError;
@@ -52,6 +53,12 @@ To interact with this cluster, please install the matching CLI version using the
         cluster_version: Version,
         client_maximum_version: Version,
     },
+    #[error("Client version {client_version} is not supported by the cluster, which supports clients {supported}
+Please install a client compatible with the cluster, or set FLUVIO_VERSION_SKEW=ignore to connect anyway")]
+    UnsupportedClientVersion {
+        client_version: Version,
+        supported: ClientVersionRange,
+    },
     #[error("Consumer config error: {0}")]
     ConsumerConfig(String),
     #[error("SmartModule runtime error {0}")]
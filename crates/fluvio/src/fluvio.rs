@@ -1,16 +1,16 @@
-use std::convert::TryFrom;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use semver::Version;
 use tokio::sync::OnceCell;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use fluvio_future::net::DomainConnector;
+use fluvio_protocol::link::versions::ClientVersionRange;
 use fluvio_sc_schema::partition::PartitionMirrorConfig;
 use fluvio_sc_schema::topic::{MirrorConfig, PartitionMap, ReplicaSpec};
 use fluvio_sc_schema::objects::ObjectApiWatchRequest;
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionId, SpuId};
 use fluvio_socket::{
     ClientConfig, Versions, VersionedSerialSocket, SharedMultiplexerSocket, MultiplexerSocket,
 };
@@ -19,13 +19,19 @@ use crate::admin::FluvioAdmin;
 use crate::error::anyhow_version_error;
 use crate::consumer::{
     MultiplePartitionConsumer, PartitionSelectionStrategy, ConsumerStream,
-    MultiplePartitionConsumerStream, Record, ConsumerConfigExt, ConsumerOffset,
+    MultiplePartitionConsumerStream, Record, ConsumerConfigExt, ConsumerOffset, OffsetReset,
+    ConsumerOffsetReset,
 };
 use crate::metrics::ClientMetrics;
 use crate::producer::{TopicProducerPool, TopicProducerConfig};
+use crate::schema::SchemaClient;
 use crate::sync::MetadataStores;
 use crate::spu::{SpuPool, SpuSocketPool};
 use crate::{TopicProducer, PartitionConsumer, FluvioError, FluvioConfig};
+use crate::{SampleStrategy, SampledRecord};
+use crate::config::{SpuEndpointResolver, VersionSkewPolicy};
+#[cfg(not(target_arch = "wasm32"))]
+use fluvio_socket::{CircuitState, ReconnectPolicy};
 
 /// An interface for interacting with Fluvio streaming
 pub struct Fluvio {
@@ -36,6 +42,9 @@ pub struct Fluvio {
     metadata: MetadataStores,
     watch_version: i16,
     metric: Arc<ClientMetrics>,
+    spu_endpoint_resolver: Option<Arc<dyn SpuEndpointResolver>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    reconnect_policy: ReconnectPolicy,
 }
 
 impl Fluvio {
@@ -74,7 +83,7 @@ impl Fluvio {
     /// # }
     /// ```
     pub async fn connect_with_config(config: &FluvioConfig) -> Result<Self> {
-        let connector = DomainConnector::try_from(config.tls.clone())?;
+        let connector = config.connector()?;
         info!(
             fluvio_crate_version = env!("CARGO_PKG_VERSION"),
             "Connecting to Fluvio cluster"
@@ -111,6 +120,9 @@ impl Fluvio {
         if let Some(client_id) = &config.client_id {
             client_config.set_client_id(client_id.to_owned());
         }
+        let skew_policy = config.version_skew_policy();
+        let spu_endpoint_resolver = (!config.spu_endpoints.is_empty())
+            .then(|| Arc::new(config.spu_endpoints.clone()) as Arc<dyn SpuEndpointResolver>);
         let inner_client = client_config.connect().await?;
         debug!("connected to cluster");
 
@@ -120,6 +132,7 @@ impl Fluvio {
         if let Some(watch_version) = versions.lookup_version::<ObjectApiWatchRequest>() {
             debug!(platform = %versions.platform_version(),"checking platform version");
             check_platform_compatible(versions.platform_version())?;
+            check_version_skew(&versions, skew_policy)?;
 
             let socket = MultiplexerSocket::shared(socket);
             let metadata = MetadataStores::start(socket.clone(), watch_version).await?;
@@ -133,6 +146,9 @@ impl Fluvio {
                 metadata,
                 watch_version,
                 metric: Arc::new(ClientMetrics::new()),
+                spu_endpoint_resolver,
+                #[cfg(not(target_arch = "wasm32"))]
+                reconnect_policy: ReconnectPolicy::default(),
             })
         } else {
             let platform_version = versions.platform_version().to_string();
@@ -140,14 +156,51 @@ impl Fluvio {
         }
     }
 
+    /// Resolve SPU addresses with `resolver` instead of the overrides of the profile.
+    ///
+    /// Only applies to SPU connections opened after the call, so it should be set
+    /// right after connecting.
+    pub fn with_spu_endpoint_resolver(
+        mut self,
+        resolver: impl SpuEndpointResolver + 'static,
+    ) -> Self {
+        self.spu_endpoint_resolver = Some(Arc::new(resolver));
+        self.spu_pool = OnceCell::new();
+        self
+    }
+
+    /// Back off reconnects to SPUs with `policy`.
+    ///
+    /// Like [`Fluvio::with_spu_endpoint_resolver`], only applies to SPU connections
+    /// opened after the call.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = policy;
+        self.spu_pool = OnceCell::new();
+        self
+    }
+
+    /// State of the connection circuit to SPU `spu`.
+    ///
+    /// The circuit opens after repeated failed connects to the SPU; while open, producers
+    /// and consumers of its partitions fail fast with `SocketError::CircuitOpen`.
+    /// `None` if no connection to the SPU was attempted yet.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spu_circuit_state(&self, spu: SpuId) -> Option<CircuitState> {
+        self.spu_pool.get()?.circuit_state(spu)
+    }
+
     /// lazy get spu pool
     async fn spu_pool(&self) -> Result<Arc<SpuSocketPool>> {
         self.spu_pool
             .get_or_try_init(|| async {
                 let metadata =
                     MetadataStores::start(self.socket.clone(), self.watch_version).await?;
-                let pool = SpuSocketPool::start(self.config.clone(), metadata);
-                Ok(Arc::new(pool?))
+                let pool = SpuSocketPool::start(self.config.clone(), metadata)?
+                    .with_endpoint_resolver(self.spu_endpoint_resolver.clone());
+                #[cfg(not(target_arch = "wasm32"))]
+                let pool = pool.with_reconnect_policy(self.reconnect_policy.clone());
+                Ok(Arc::new(pool))
             })
             .await
             .cloned()
@@ -204,7 +257,20 @@ impl Fluvio {
             return Err(FluvioError::TopicNotFound(topic).into());
         }
 
-        TopicProducer::new(topic, spu_pool, Arc::new(config), self.metric.clone()).await
+        let schema_validator = if config.schema_validation {
+            Some(Arc::new(
+                self.schema_client().await.validator(&topic).await?,
+            ))
+        } else {
+            None
+        };
+
+        let producer =
+            TopicProducer::new(topic, spu_pool, Arc::new(config), self.metric.clone()).await?;
+        Ok(match schema_validator {
+            Some(validator) => producer.with_schema_validator(validator),
+            None => producer,
+        })
     }
 
     /// Creates a new `PartitionConsumer` for the given topic and partition
@@ -271,7 +337,7 @@ impl Fluvio {
     ///
     /// If the `offset_consumer` property of `ConsumerConfigExt` is specified, the Fluvio fetches
     /// the offset by id and starts the stream from the next available record. If the offset does not exist, the Fluvio creates it.
-    /// To read all existing consumers offsets one could use [`Self::consumer_offsets()`] function, to delete - [`Self::delete_consumer_offset()`] function.
+    /// To read all existing consumers offsets one could use [`Self::consumer_offsets()`] function, the offset of one consumer - [`Self::committed_offset()`], to delete - [`Self::delete_consumer_offset()`] function.
     ///
     /// The Fluvio saves offsets once one called [`ConsumerStream::offset_commit()`] method followed by [`ConsumerStream::offset_flush()`].
     /// There is support for auto-commits if [`crate::consumer::OffsetManagementStrategy::Auto`] is used.
@@ -344,6 +410,13 @@ impl Fluvio {
             .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?
             .spec;
 
+        // batches of the topic may be compressed with its dictionary
+        #[cfg(feature = "compress")]
+        let compression_dictionary = topic_spec
+            .get_compression_dictionary()
+            .map(|dictionary| fluvio_compression::register_dictionary(topic, dictionary.as_bytes()))
+            .transpose()?;
+
         let mirror_partition = if let Some(ref mirror) = &config.mirror {
             match topic_spec.replicas() {
                 ReplicaSpec::Mirror(MirrorConfig::Home(home_mirror_config)) => {
@@ -389,7 +462,76 @@ impl Fluvio {
                 PartitionConsumer::new(topic.clone(), partition, spu_pool.clone(), self.metrics());
             partition_streams.push(consumer.consumer_stream_with_config(config.clone()).await?);
         }
-        Ok(MultiplePartitionConsumerStream::new(partition_streams))
+        let stream = MultiplePartitionConsumerStream::new(partition_streams);
+        #[cfg(feature = "compress")]
+        let stream = stream.with_compression_dictionary(compression_dictionary);
+        Ok(stream)
+    }
+
+    /// Joins a consumer group and streams the records of the partitions assigned to
+    /// this member. `on_rebalance` is called with the new partitions of the member
+    /// each time the group is rebalanced, before records of the new assignment are
+    /// yielded. The member leaves the group when the stream is dropped, static members
+    /// with a group instance id keep their partitions until the session timeout.
+    ///
+    /// ```no_run
+    /// # use fluvio::{Fluvio, consumer::ConsumerGroupConfig};
+    /// use futures_util::StreamExt;
+    /// # async fn example(fluvio: &Fluvio) -> anyhow::Result<()> {
+    /// let config = ConsumerGroupConfig::builder().group("billing").build()?;
+    /// let mut stream = fluvio
+    ///     .consumer_group(config, |rebalance| {
+    ///         println!("assigned partitions: {:?}", rebalance.partitions);
+    ///     })
+    ///     .await?;
+    /// while let Some(Ok(record)) = stream.next().await {
+    ///     println!("{}", String::from_utf8_lossy(record.as_ref()));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn consumer_group<'a>(
+        &'a self,
+        config: crate::consumer::ConsumerGroupConfig,
+        on_rebalance: impl FnMut(&crate::consumer::Rebalance) + Send + 'a,
+    ) -> Result<crate::consumer::ConsumerGroupStream<'a>> {
+        crate::consumer::ConsumerGroupStream::join(self, config, on_rebalance).await
+    }
+
+    /// Reads a few committed records of a partition without starting a stream.
+    ///
+    /// Records are returned in offset order. The SPU caps the number of
+    /// records returned by a single sample.
+    pub async fn sample_records(
+        &self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+        count: u32,
+        strategy: SampleStrategy,
+    ) -> Result<Vec<SampledRecord>> {
+        use fluvio_protocol::{link::ErrorCode, record::ReplicaKey};
+        use fluvio_spu_schema::server::sample::SampleRecordsRequest;
+        use crate::spu::SpuDirectory;
+
+        let replica = ReplicaKey::new(topic, partition);
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket(&replica).await?;
+        let response = socket
+            .send_receive(SampleRecordsRequest::new(
+                replica.topic.clone(),
+                partition,
+                count,
+                strategy,
+            ))
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!(
+                "sample records of {replica} failed with: {}",
+                response.error_code
+            );
+        }
+        Ok(response.records)
     }
 
     /// Returns all consumers offsets that currently available in the cluster.
@@ -419,6 +561,26 @@ impl Fluvio {
             .collect())
     }
 
+    /// Returns the offset committed by a consumer for the given replica,
+    /// None if the consumer has not committed any offset for it.
+    pub async fn committed_offset(
+        &self,
+        consumer_id: &str,
+        replica_id: impl Into<fluvio_protocol::record::ReplicaKey>,
+    ) -> Result<Option<i64>> {
+        let replica_id = replica_id.into();
+        Ok(self
+            .consumer_offsets()
+            .await?
+            .into_iter()
+            .find(|offset| {
+                offset.consumer_id == consumer_id
+                    && offset.topic == replica_id.topic
+                    && offset.partition == replica_id.partition
+            })
+            .map(|offset| offset.offset))
+    }
+
     /// Delete a consumer offset for the given name and the replica.
     pub async fn delete_consumer_offset(
         &self,
@@ -452,6 +614,131 @@ impl Fluvio {
         Ok(())
     }
 
+    /// Delete the offsets committed by a consumer on every partition of `topic`, or of every
+    /// topic if `topic` is None. Returns the deleted offsets.
+    pub async fn delete_consumer_offsets(
+        &self,
+        consumer_id: &str,
+        topic: Option<&str>,
+    ) -> Result<Vec<ConsumerOffset>> {
+        let offsets: Vec<ConsumerOffset> = self
+            .consumer_offsets()
+            .await?
+            .into_iter()
+            .filter(|offset| offset.consumer_id == consumer_id)
+            .filter(|offset| topic.map_or(true, |topic| offset.topic == topic))
+            .collect();
+        for offset in &offsets {
+            debug!(?offset, "deleting consumer offset");
+            self.delete_consumer_offset(
+                &*offset.consumer_id,
+                (offset.topic.clone(), offset.partition),
+            )
+            .await?;
+        }
+        Ok(offsets)
+    }
+
+    /// Set the offset committed by a consumer for the given replica.
+    ///
+    /// The consumer resumes from the record after `offset`.
+    pub async fn set_consumer_offset(
+        &self,
+        consumer_id: impl Into<String>,
+        replica_id: impl Into<fluvio_protocol::record::ReplicaKey>,
+        offset: i64,
+    ) -> Result<()> {
+        use fluvio_protocol::{link::ErrorCode, record::ReplicaKey};
+
+        use crate::spu::SpuDirectory;
+
+        let spu_pool = self.spu_pool().await?;
+        let consumers_replica_id = ReplicaKey::new(
+            fluvio_types::defaults::CONSUMER_STORAGE_TOPIC,
+            <PartitionId as Default>::default(),
+        );
+        let socket = spu_pool.create_serial_socket(&consumers_replica_id).await?;
+        let response = socket
+            .send_receive(
+                fluvio_spu_schema::server::consumer_offset::SetConsumerOffsetRequest {
+                    replica_id: replica_id.into(),
+                    consumer_id: consumer_id.into(),
+                    offset,
+                },
+            )
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("set consumer offset failed with: {}", response.error_code);
+        }
+        Ok(())
+    }
+
+    /// Move the offsets of a consumer on a topic to `target`.
+    ///
+    /// Resets the given partition, or every partition of the topic if `partition` is None.
+    /// Offsets are only changed if `execute` is true, otherwise the planned changes are returned.
+    pub async fn reset_consumer_offsets(
+        &self,
+        consumer_id: impl Into<String>,
+        topic: impl Into<String>,
+        partition: Option<PartitionId>,
+        target: OffsetReset,
+        execute: bool,
+    ) -> Result<Vec<ConsumerOffsetReset>> {
+        let consumer_id = consumer_id.into();
+        let topic = topic.into();
+
+        let partitions = match partition {
+            Some(partition) => vec![partition],
+            None => {
+                let spu_pool = self.spu_pool().await?;
+                let topic_spec = spu_pool
+                    .metadata
+                    .topics()
+                    .lookup_by_key(&topic)
+                    .await?
+                    .ok_or_else(|| FluvioError::TopicNotFound(topic.clone()))?
+                    .spec;
+                (0..topic_spec.partitions() as PartitionId).collect()
+            }
+        };
+
+        let committed = self.consumer_offsets().await?;
+        let mut resets = Vec::with_capacity(partitions.len());
+        for partition in partitions {
+            let consumer = self.partition_consumer(topic.clone(), partition).await?;
+            let new_position = consumer.resolve_reset(target).await?;
+            let current_position = committed
+                .iter()
+                .find(|offset| {
+                    offset.consumer_id == consumer_id
+                        && offset.topic == topic
+                        && offset.partition == partition
+                })
+                .map(|offset| offset.offset + 1);
+            resets.push(ConsumerOffsetReset {
+                consumer_id: consumer_id.clone(),
+                topic: topic.clone(),
+                partition,
+                current_position,
+                new_position,
+            });
+        }
+
+        if execute {
+            for reset in &resets {
+                debug!(?reset, "resetting consumer offset");
+                self.set_consumer_offset(
+                    &*reset.consumer_id,
+                    (reset.topic.clone(), reset.partition),
+                    reset.committed_offset(),
+                )
+                .await?;
+            }
+        }
+        Ok(resets)
+    }
+
     /// Provides an interface for managing a Fluvio cluster
     ///
     /// # Example
@@ -469,6 +756,11 @@ impl Fluvio {
         FluvioAdmin::new(socket, metadata)
     }
 
+    /// Registers and reads the schemas of topics, see [`crate::schema`]
+    pub async fn schema_client(&self) -> SchemaClient {
+        SchemaClient::new(self.admin().await)
+    }
+
     /// Reports the Platform Version of the connected cluster.
     ///
     /// The "Platform Version" is the value of the VERSION file when
@@ -479,6 +771,11 @@ impl Fluvio {
         self.versions.platform_version()
     }
 
+    /// Checks this client against the client versions supported by the cluster
+    pub fn check_client_version(&self) -> Result<(), FluvioError> {
+        check_client_supported(self.versions.supported_client_versions())
+    }
+
     /// create serial connection
     fn create_serial_client(&self) -> VersionedSerialSocket {
         VersionedSerialSocket::new(
@@ -510,6 +807,40 @@ fn check_platform_compatible(cluster_version: &Version) -> Result<(), FluvioErro
     Ok(())
 }
 
+/// The cluster supports this client if the client platform version is within
+/// the range advertised by the SC.
+fn check_client_supported(supported: &ClientVersionRange) -> Result<(), FluvioError> {
+    let client_version =
+        Version::parse(crate::VERSION.trim()).expect("Fluvio client 'VERSION' must be semver");
+
+    if !supported.contains(&client_version) {
+        return Err(FluvioError::UnsupportedClientVersion {
+            client_version,
+            supported: supported.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// apply `policy` when cluster does not support this client
+pub(crate) fn check_version_skew(
+    versions: &Versions,
+    policy: VersionSkewPolicy,
+) -> Result<(), FluvioError> {
+    if policy == VersionSkewPolicy::Ignore {
+        return Ok(());
+    }
+    match check_client_supported(versions.supported_client_versions()) {
+        Err(err) if policy == VersionSkewPolicy::Refuse => Err(err),
+        Err(err) => {
+            warn!(%err, "client version skew");
+            Ok(())
+        }
+        Ok(()) => Ok(()),
+    }
+}
+
 #[cfg(test)]
 #[cfg(target_arch = "wasm32")]
 mod wasm_tests {
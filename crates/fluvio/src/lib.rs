@@ -12,22 +12,30 @@ mod offset;
 mod producer;
 mod sync;
 
+pub mod chunking;
+pub mod cluster;
 pub mod config;
 pub mod consumer;
+pub mod lineage;
+pub mod marker;
 pub mod metrics;
+pub mod schema;
 pub mod spu;
+#[cfg(feature = "encryption")]
+pub mod encryption;
 
 pub use error::FluvioError;
 pub use config::FluvioConfig;
 pub use producer::{
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducer, TopicProducerPool, RecordKey,
-    ProduceOutput, FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy,
-    RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
+    ProduceOutput, BatchProduceOutput, FutureRecordMetadata, RecordMetadata, DeliverySemantic,
+    RetryPolicy, RetryStrategy, Partitioner, PartitionerConfig, ProducerError, TraceContext,
 };
 #[cfg(feature = "smartengine")]
 pub use producer::{SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData};
 
 pub use fluvio_spu_schema::Isolation;
+pub use fluvio_spu_schema::server::sample::{SampleStrategy, SampledRecord};
 
 pub use consumer::{
     PartitionConsumer, ConsumerConfig, MultiplePartitionConsumer, PartitionSelectionStrategy,
@@ -40,6 +48,8 @@ pub use crate::admin::FluvioAdmin;
 pub use crate::fluvio::Fluvio;
 
 pub use fluvio_compression::Compression;
+#[cfg(not(target_arch = "wasm32"))]
+pub use fluvio_socket::{CircuitState, ReconnectPolicy};
 
 use fluvio_types::PartitionId;
 use tracing::instrument;
@@ -196,6 +206,22 @@ pub mod metadata {
         pub use fluvio_sc_schema::tableformat::*;
     }
 
+    pub mod alert {
+        pub use fluvio_sc_schema::alert::*;
+    }
+
+    pub mod consumer_group {
+        pub use fluvio_sc_schema::consumer_group::*;
+    }
+
+    pub mod schema {
+        pub use fluvio_sc_schema::schema::*;
+    }
+
+    pub mod acl {
+        pub use fluvio_sc_schema::acl::*;
+    }
+
     pub mod core {
         pub use fluvio_sc_schema::core::*;
     }
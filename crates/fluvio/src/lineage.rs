@@ -0,0 +1,158 @@
+//!
+//! # Record Lineage
+//!
+//! Provenance of records written by connectors and pipelines, carried in record headers
+//! prefixed with `fluvio.lineage.`.
+//!
+
+use std::fmt;
+use std::hash::Hasher;
+
+use fluvio_protocol::record::{Offset, Record, RecordHeaderEntry};
+use fluvio_types::PartitionId;
+use siphasher::sip::SipHasher;
+
+pub const LINEAGE_HEADER_PREFIX: &str = "fluvio.lineage.";
+pub const CONNECTOR_HEADER: &str = "fluvio.lineage.connector";
+pub const TRANSFORMS_HEADER: &str = "fluvio.lineage.transforms";
+pub const SOURCE_TOPIC_HEADER: &str = "fluvio.lineage.source-topic";
+pub const SOURCE_PARTITION_HEADER: &str = "fluvio.lineage.source-partition";
+pub const SOURCE_OFFSET_HEADER: &str = "fluvio.lineage.source-offset";
+
+/// Where a record comes from
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Lineage {
+    /// name of the connector that produced the record
+    pub connector: Option<String>,
+    /// hash of the transformation chain applied, see [`Lineage::transforms_hash`]
+    pub transforms: Option<String>,
+    pub source: Option<LineageSource>,
+}
+
+/// Record the lineage record was derived from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineageSource {
+    pub topic: String,
+    pub partition: PartitionId,
+    /// offset is unknown when the source record was never appended
+    pub offset: Option<Offset>,
+}
+
+impl Lineage {
+    /// stable hash of a serialized transformation chain
+    pub fn transforms_hash(chain: &[u8]) -> String {
+        let mut hasher = SipHasher::new();
+        hasher.write(chain);
+        format!("{:016x}", hasher.finish())
+    }
+
+    pub fn to_headers(&self) -> Vec<RecordHeaderEntry> {
+        let mut headers = Vec::new();
+        if let Some(connector) = &self.connector {
+            headers.push(RecordHeaderEntry::new(CONNECTOR_HEADER, connector.as_str()));
+        }
+        if let Some(transforms) = &self.transforms {
+            headers.push(RecordHeaderEntry::new(
+                TRANSFORMS_HEADER,
+                transforms.as_str(),
+            ));
+        }
+        if let Some(source) = &self.source {
+            headers.push(RecordHeaderEntry::new(
+                SOURCE_TOPIC_HEADER,
+                source.topic.as_str(),
+            ));
+            headers.push(RecordHeaderEntry::new(
+                SOURCE_PARTITION_HEADER,
+                source.partition.to_string(),
+            ));
+            if let Some(offset) = source.offset {
+                headers.push(RecordHeaderEntry::new(
+                    SOURCE_OFFSET_HEADER,
+                    offset.to_string(),
+                ));
+            }
+        }
+        headers
+    }
+
+    /// lineage of a record, `None` if it has no lineage headers
+    pub fn from_record(record: &Record) -> Option<Self> {
+        let header = |key| {
+            record
+                .header(key)
+                .and_then(|value| value.as_str().ok())
+                .map(str::to_owned)
+        };
+        let source = header(SOURCE_TOPIC_HEADER).map(|topic| LineageSource {
+            topic,
+            partition: header(SOURCE_PARTITION_HEADER)
+                .and_then(|partition| partition.parse().ok())
+                .unwrap_or_default(),
+            offset: header(SOURCE_OFFSET_HEADER).and_then(|offset| offset.parse().ok()),
+        });
+        let lineage = Self {
+            connector: header(CONNECTOR_HEADER),
+            transforms: header(TRANSFORMS_HEADER),
+            source,
+        };
+        (lineage != Self::default()).then_some(lineage)
+    }
+}
+
+impl fmt::Display for Lineage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(source) = &self.source {
+            match source.offset {
+                Some(offset) => parts.push(format!(
+                    "source={}/{}@{offset}",
+                    source.topic, source.partition
+                )),
+                None => parts.push(format!("source={}/{}", source.topic, source.partition)),
+            }
+        }
+        if let Some(connector) = &self.connector {
+            parts.push(format!("connector={connector}"));
+        }
+        if let Some(transforms) = &self.transforms {
+            parts.push(format!("transforms={transforms}"));
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lineage_headers() {
+        let lineage = Lineage {
+            connector: Some("http-source".to_owned()),
+            transforms: Some(Lineage::transforms_hash(b"[]")),
+            source: Some(LineageSource {
+                topic: "orders".to_owned(),
+                partition: 1,
+                offset: Some(42),
+            }),
+        };
+        let mut record = Record::new("value");
+        record.headers = lineage.to_headers();
+        record.add_header("other", "ignored");
+
+        assert_eq!(Lineage::from_record(&record), Some(lineage.clone()));
+        assert_eq!(
+            lineage.to_string(),
+            format!(
+                "source=orders/1@42 connector=http-source transforms={}",
+                Lineage::transforms_hash(b"[]")
+            )
+        );
+        assert!(Lineage::from_record(&Record::new("value")).is_none());
+        assert_eq!(
+            Lineage::transforms_hash(b"[]"),
+            Lineage::transforms_hash(b"[]")
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//!
+//! # Stream Markers
+//!
+//! Control records that pipeline stages exchange alongside data records, such as event
+//! time watermarks or the end of a backfill. A marker is a record with an empty value and
+//! the `fluvio.marker` header, so it takes an offset like any other record and is ordered
+//! with the data records of its partition.
+//!
+//! [`TopicProducer::send_marker`](crate::TopicProducer::send_marker) writes a marker to
+//! every partition of the topic. Consumers use [`with_markers`] to tell markers apart
+//! from data records and [`WatermarkTracker`] to follow the event time of a topic.
+//!
+//! SmartModules see markers like any other record, filters may drop them.
+//!
+
+use std::collections::BTreeMap;
+
+use futures_util::{Stream, StreamExt};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{ConsumerRecord, Offset, Record, RecordHeaderEntry};
+use fluvio_types::{PartitionId, Timestamp};
+
+pub const MARKER_HEADER: &str = "fluvio.marker";
+pub const MARKER_VALUE_HEADER: &str = "fluvio.marker.value";
+
+const WATERMARK: &str = "watermark";
+const COMPLETE: &str = "complete";
+
+/// Control marker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Marker {
+    /// all records with an event time up to this timestamp, in milliseconds, were produced
+    Watermark(Timestamp),
+    /// the producer finished the named stream, e.g. a backfill
+    Complete(String),
+}
+
+impl Marker {
+    pub fn to_record(&self) -> Record {
+        let (kind, value) = match self {
+            Self::Watermark(event_time) => (WATERMARK, event_time.to_string()),
+            Self::Complete(name) => (COMPLETE, name.clone()),
+        };
+        let mut record = Record::new(Vec::<u8>::new());
+        record.headers = vec![
+            RecordHeaderEntry::new(MARKER_HEADER, kind),
+            RecordHeaderEntry::new(MARKER_VALUE_HEADER, value),
+        ];
+        record
+    }
+
+    /// marker carried by a record, `None` for data records
+    pub fn from_record(record: &Record) -> Option<Self> {
+        let header = |key| record.header(key).and_then(|value| value.as_str().ok());
+        let value = header(MARKER_VALUE_HEADER).unwrap_or_default();
+        match header(MARKER_HEADER)? {
+            WATERMARK => value.parse().ok().map(Self::Watermark),
+            COMPLETE => Some(Self::Complete(value.to_owned())),
+            _ => None,
+        }
+    }
+}
+
+/// Item of a consumer stream wrapped with [`with_markers`]
+pub enum ConsumerEvent {
+    Record(ConsumerRecord),
+    Marker {
+        partition: PartitionId,
+        offset: Offset,
+        marker: Marker,
+    },
+}
+
+impl From<ConsumerRecord> for ConsumerEvent {
+    fn from(record: ConsumerRecord) -> Self {
+        match Marker::from_record(&record.record) {
+            Some(marker) => Self::Marker {
+                partition: record.partition,
+                offset: record.offset,
+                marker,
+            },
+            None => Self::Record(record),
+        }
+    }
+}
+
+/// separate markers from the data records of a consumer stream
+pub fn with_markers<S>(stream: S) -> impl Stream<Item = Result<ConsumerEvent, ErrorCode>>
+where
+    S: Stream<Item = Result<ConsumerRecord, ErrorCode>>,
+{
+    stream.map(|item| item.map(ConsumerEvent::from))
+}
+
+/// Event time of a topic, the lowest watermark of its partitions
+#[derive(Debug, Clone)]
+pub struct WatermarkTracker {
+    partitions: BTreeMap<PartitionId, Option<Timestamp>>,
+}
+
+impl WatermarkTracker {
+    /// track the watermarks of `partitions`
+    pub fn new(partitions: impl IntoIterator<Item = PartitionId>) -> Self {
+        Self {
+            partitions: partitions.into_iter().map(|id| (id, None)).collect(),
+        }
+    }
+
+    /// update the watermark of `partition`, watermarks never move back
+    pub fn observe(&mut self, partition: PartitionId, marker: &Marker) {
+        if let Marker::Watermark(event_time) = marker {
+            let watermark = self.partitions.entry(partition).or_default();
+            *watermark = Some(watermark.map_or(*event_time, |current| current.max(*event_time)));
+        }
+    }
+
+    /// lowest watermark, `None` until every partition reported one
+    pub fn watermark(&self) -> Option<Timestamp> {
+        self.partitions
+            .values()
+            .copied()
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_records() {
+        let watermark = Marker::Watermark(1_700_000_000_000);
+        let complete = Marker::Complete("backfill-2024".to_owned());
+
+        let record = watermark.to_record();
+        assert!(record.value().as_ref().is_empty());
+        assert_eq!(Marker::from_record(&record), Some(watermark));
+        assert_eq!(Marker::from_record(&complete.to_record()), Some(complete));
+        assert_eq!(Marker::from_record(&Record::new("data")), None);
+
+        let mut unknown = Record::new(Vec::<u8>::new());
+        unknown.add_header(MARKER_HEADER, "other");
+        assert_eq!(Marker::from_record(&unknown), None);
+    }
+
+    #[test]
+    fn test_watermark_tracker() {
+        let mut tracker = WatermarkTracker::new([0, 1]);
+        tracker.observe(0, &Marker::Watermark(20));
+        assert_eq!(tracker.watermark(), None);
+
+        tracker.observe(1, &Marker::Watermark(10));
+        tracker.observe(1, &Marker::Complete("backfill".to_owned()));
+        assert_eq!(tracker.watermark(), Some(10));
+
+        tracker.observe(1, &Marker::Watermark(30));
+        tracker.observe(0, &Marker::Watermark(5));
+        assert_eq!(tracker.watermark(), Some(20));
+    }
+}
@@ -1,10 +1,20 @@
+use std::fmt;
+use std::sync::Mutex;
+
+use hdrhistogram::Histogram;
 use serde::{Serialize, Deserialize};
 
+/// highest latency tracked by the histograms, in microseconds
+const MAX_LATENCY_MICROS: u64 = 60_000_000;
+const LATENCY_PRECISION: u8 = 3;
+
 #[derive(Default, Debug, Deserialize, Serialize)]
 pub struct ClientMetrics {
     consumer: RecordCounter,
     producer_connector: RecordCounter,
     producer_client: RecordCounter,
+    #[serde(skip)]
+    producer_ack_latency: LatencyHistogram,
     #[cfg(feature = "smartengine")]
     smartmodule: fluvio_smartengine::metrics::SmartModuleChainMetrics,
 }
@@ -31,12 +41,75 @@ impl ClientMetrics {
         &self.producer_client
     }
 
+    /// latency between sending a produce request and its acknowledgement,
+    /// recorded for `AtLeastOnce` delivery
+    #[inline]
+    pub fn producer_ack_latency(&self) -> &LatencyHistogram {
+        &self.producer_ack_latency
+    }
+
     #[cfg(feature = "smartengine")]
     pub(crate) fn chain_metrics(&self) -> &fluvio_smartengine::metrics::SmartModuleChainMetrics {
         &self.smartmodule
     }
 }
 
+/// HDR histogram of latencies in microseconds, values above one minute are
+/// recorded as one minute
+pub struct LatencyHistogram {
+    histogram: Mutex<Histogram<u64>>,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        let histogram = Histogram::new_with_bounds(1, MAX_LATENCY_MICROS, LATENCY_PRECISION)
+            .expect("valid histogram bounds");
+        Self {
+            histogram: Mutex::new(histogram),
+        }
+    }
+}
+
+impl fmt::Debug for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let histogram = self.snapshot();
+        f.debug_struct("LatencyHistogram")
+            .field("count", &histogram.len())
+            .field("p50", &histogram.value_at_quantile(0.5))
+            .field("p99", &histogram.value_at_quantile(0.99))
+            .field("max", &histogram.max())
+            .finish()
+    }
+}
+
+impl LatencyHistogram {
+    /// record `count` samples of `micros` with a single lock
+    pub(crate) fn record_n(&self, micros: u64, count: u64) {
+        if let Ok(mut histogram) = self.histogram.lock() {
+            histogram.saturating_record_n(micros, count);
+        }
+    }
+
+    /// copy of the latencies recorded so far
+    pub fn snapshot(&self) -> Histogram<u64> {
+        match self.histogram.lock() {
+            Ok(histogram) => histogram.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// copy of the latencies recorded so far, then clear them
+    pub fn take(&self) -> Histogram<u64> {
+        let mut histogram = match self.histogram.lock() {
+            Ok(histogram) => histogram,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let snapshot = histogram.clone();
+        histogram.reset();
+        snapshot
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(any(target_arch = "wasm32", target_arch = "arm"))] {
 
@@ -78,3 +151,22 @@ cfg_if::cfg_if! {
 
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_histogram() {
+        let latency = LatencyHistogram::default();
+        latency.record_n(1_000, 3);
+        latency.record_n(5_000, 1);
+        latency.record_n(u64::MAX, 1);
+
+        let histogram = latency.take();
+        assert_eq!(histogram.len(), 5);
+        assert_eq!(histogram.value_at_quantile(0.5), 1_000);
+        assert!(histogram.max() >= MAX_LATENCY_MICROS);
+        assert!(latency.snapshot().is_empty());
+    }
+}
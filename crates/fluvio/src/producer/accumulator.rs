@@ -59,6 +59,7 @@ pub(crate) struct RecordAccumulator {
     queue_size: usize,
     batches: Arc<RwLock<HashMap<PartitionId, BatchHandler>>>,
     compression: Compression,
+    compression_dictionary: Option<u32>,
 }
 
 impl RecordAccumulator {
@@ -77,10 +78,17 @@ impl RecordAccumulator {
             max_request_size,
             batch_size,
             compression,
+            compression_dictionary: None,
             queue_size,
         }
     }
 
+    /// id of the registered zstd dictionary batches are compressed with
+    pub(crate) fn with_compression_dictionary(mut self, dictionary_id: Option<u32>) -> Self {
+        self.compression_dictionary = dictionary_id;
+        self
+    }
+
     pub(crate) async fn add_partition(
         &self,
         partition_id: PartitionId,
@@ -187,6 +195,7 @@ impl RecordAccumulator {
 
         let mut batch =
             ProducerBatch::new(self.max_request_size, self.batch_size, self.compression);
+        batch.compression_dictionary = self.compression_dictionary;
 
         match batch.push_record(record) {
             Ok(ProduceBatchStatus::Added(push_record)) => {
@@ -238,6 +247,7 @@ pub(crate) struct ProducerBatch {
     pub(crate) notify: Sender<ProducePartitionResponseFuture>,
     batch_metadata: Arc<BatchMetadata>,
     batch: MemoryBatch,
+    compression_dictionary: Option<u32>,
 }
 impl ProducerBatch {
     fn new(write_limit: usize, batch_limit: usize, compression: Compression) -> Self {
@@ -249,6 +259,7 @@ impl ProducerBatch {
             notify: sender,
             batch_metadata,
             batch,
+            compression_dictionary: None,
         }
     }
 
@@ -273,6 +284,10 @@ impl ProducerBatch {
         self.batch.elapsed()
     }
 
+    pub(crate) fn compression_dictionary(&self) -> Option<u32> {
+        self.compression_dictionary
+    }
+
     pub(crate) fn batch(self) -> Batch {
         self.batch.into()
     }
@@ -330,6 +345,11 @@ impl ProducePartitionResponseFuture {
         }
     }
 
+    /// the partition responded with an error, unknown until resolved for fire and forget
+    pub(crate) fn is_ready_error(&self) -> bool {
+        matches!(&self.inner, Either::Right(Some((_, error))) if error.is_error())
+    }
+
     /// Returns a future that firstly will resolve [`ProduceResponse`] from the given `response_fut`,
     /// and then will look up the partition response using `num`. [`ProduceResponseFuture`] is usually
     /// shared between other [`ProducePartitionResponseFuture`] and will be resolved only once and
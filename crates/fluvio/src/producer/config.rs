@@ -9,6 +9,8 @@ use fluvio_spu_schema::Isolation;
 use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 
 use fluvio_compression::Compression;
+use fluvio_protocol::record::RecordHeaderEntry;
+use fluvio_sc_schema::topic::CompressionDictionary;
 use fluvio_types::PartitionId;
 use serde::{Serialize, Deserialize};
 
@@ -21,6 +23,7 @@ const DEFAULT_TIMEOUT_MS: u64 = 1500;
 const DEFAULT_BATCH_SIZE_BYTES: usize = 16_384;
 const DEFAULT_BATCH_QUEUE_SIZE: usize = 100;
 const DEFAULT_MAX_REQUEST_SIZE: usize = 1_048_576;
+const DEFAULT_MAX_INFLIGHT_REQUESTS: usize = 5;
 
 const DEFAULT_RETRIES_TIMEOUT: Duration = Duration::from_secs(300);
 const DEFAULT_INITIAL_DELAY: Duration = Duration::from_millis(20);
@@ -39,6 +42,14 @@ fn default_batch_queue_size() -> usize {
     DEFAULT_BATCH_QUEUE_SIZE
 }
 
+fn default_max_inflight_requests() -> usize {
+    DEFAULT_MAX_INFLIGHT_REQUESTS
+}
+
+fn default_strict_ordering() -> bool {
+    true
+}
+
 fn default_linger_duration() -> Duration {
     Duration::from_millis(DEFAULT_LINGER_MS)
 }
@@ -96,6 +107,12 @@ pub struct TopicProducerConfig {
     #[allow(dead_code)]
     pub(crate) compression: Option<Compression>,
 
+    /// Trained zstd dictionary the records are compressed with, instead of the dictionary
+    /// of the topic. It requires zstd compression. Consumers decompress the records with
+    /// the dictionary of the topic, so it is usually the same one.
+    #[builder(setter(strip_option), default)]
+    pub(crate) compression_dictionary: Option<CompressionDictionary>,
+
     /// Max time duration that the server is allowed to process the batch.
     #[builder(default = "default_timeout()")]
     pub(crate) timeout: Duration,
@@ -118,6 +135,47 @@ pub struct TopicProducerConfig {
 
     #[builder(default)]
     pub(crate) smartmodules: Vec<SmartModuleInvocation>,
+
+    /// Headers appended to every record sent, such as [`crate::lineage::Lineage`] headers.
+    #[builder(default)]
+    pub(crate) headers: Vec<RecordHeaderEntry>,
+
+    /// Split records larger than `max_request_size` into chunks, which consumers reassemble,
+    /// instead of failing with [`ProducerError::RecordTooLarge`](crate::ProducerError::RecordTooLarge).
+    /// See [`crate::chunking`].
+    #[builder(default)]
+    pub(crate) chunking: bool,
+
+    /// Max produce requests sent to a partition without their response.
+    ///
+    /// Requests of a partition are sent in order on a single connection and written in
+    /// that order. With [`DeliverySemantic::AtLeastOnce`], a retried request lands after the
+    /// requests sent while it was retried, with `idempotence` the SPU rejects these requests
+    /// as out of order instead. Unless `strict_ordering` is disabled or `idempotence` is
+    /// enabled, the limit is 1 when requests may be retried.
+    #[builder(default = "default_max_inflight_requests()")]
+    pub(crate) max_inflight_requests: usize,
+
+    /// Keep the records of a partition in order across retries, see `max_inflight_requests`.
+    #[builder(default = "default_strict_ordering()")]
+    pub(crate) strict_ordering: bool,
+
+    /// Attach a producer id and a sequence number per partition to the batches sent.
+    /// The SPU appends each sequence once, so retries do not duplicate records.
+    #[builder(default)]
+    pub(crate) idempotence: bool,
+
+    /// Check the value of every record sent against the latest version of the schema
+    /// of the topic, see [`crate::schema`]. Records that do not match fail with
+    /// [`ProducerError::SchemaValidation`](crate::ProducerError::SchemaValidation).
+    #[builder(default)]
+    pub(crate) schema_validation: bool,
+
+    /// Start a trace for every record sent without one, stored as a W3C `traceparent`
+    /// header. Records sent with [`TopicProducer::send_with_trace_context`](crate::TopicProducer::send_with_trace_context)
+    /// keep their context.
+    #[builder(default)]
+    pub(crate) trace_propagation: bool,
 }
 
 impl TopicProducerConfigBuilder {
@@ -147,6 +205,46 @@ impl TopicProducerConfig {
         self.compression
     }
 
+    pub fn compression_dictionary(&self) -> Option<&CompressionDictionary> {
+        self.compression_dictionary.as_ref()
+    }
+
+    pub fn headers(&self) -> &[RecordHeaderEntry] {
+        &self.headers
+    }
+
+    pub fn chunking(&self) -> bool {
+        self.chunking
+    }
+
+    /// In flight requests allowed per partition, after the ordering constraints
+    pub fn max_inflight_requests(&self) -> usize {
+        match self.delivery_semantic {
+            DeliverySemantic::AtLeastOnce(policy)
+                if self.strict_ordering && !self.idempotence && policy.max_retries > 0 =>
+            {
+                1
+            }
+            _ => self.max_inflight_requests.max(1),
+        }
+    }
+
+    pub fn strict_ordering(&self) -> bool {
+        self.strict_ordering
+    }
+
+    pub fn idempotence(&self) -> bool {
+        self.idempotence
+    }
+
+    pub fn schema_validation(&self) -> bool {
+        self.schema_validation
+    }
+
+    pub fn trace_propagation(&self) -> bool {
+        self.trace_propagation
+    }
+
     pub fn timeout(&self) -> Duration {
         self.timeout
     }
@@ -173,10 +271,18 @@ impl Default for TopicProducerConfig {
             batch_queue_size: default_batch_queue_size(),
             partitioner: default_partitioner(),
             compression: None,
+            compression_dictionary: None,
             timeout: default_timeout(),
             isolation: default_isolation(),
             delivery_semantic: default_delivery(),
             smartmodules: vec![],
+            headers: vec![],
+            chunking: false,
+            max_inflight_requests: default_max_inflight_requests(),
+            strict_ordering: default_strict_ordering(),
+            idempotence: false,
+            schema_validation: false,
+            trace_propagation: false,
         }
     }
 }
@@ -304,6 +410,45 @@ impl RetryPolicy {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_max_inflight_requests() {
+        let config = TopicProducerConfigBuilder::default()
+            .max_inflight_requests(8)
+            .build()
+            .expect("config");
+        // retries could reorder the records
+        assert_eq!(config.max_inflight_requests(), 1);
+
+        let config = TopicProducerConfigBuilder::default()
+            .max_inflight_requests(8)
+            .strict_ordering(false)
+            .build()
+            .expect("config");
+        assert_eq!(config.max_inflight_requests(), 8);
+
+        // the SPU keeps the records of an idempotent producer in order
+        let config = TopicProducerConfigBuilder::default()
+            .max_inflight_requests(8)
+            .idempotence(true)
+            .build()
+            .expect("config");
+        assert_eq!(config.max_inflight_requests(), 8);
+
+        let config = TopicProducerConfigBuilder::default()
+            .max_inflight_requests(8)
+            .delivery_semantic(DeliverySemantic::AtMostOnce)
+            .build()
+            .expect("config");
+        assert_eq!(config.max_inflight_requests(), 8);
+
+        let config = TopicProducerConfigBuilder::default()
+            .max_inflight_requests(0)
+            .delivery_semantic(DeliverySemantic::AtMostOnce)
+            .build()
+            .expect("config");
+        assert_eq!(config.max_inflight_requests(), 1);
+    }
+
     #[test]
     fn test_retry_policy_fixed_iter() {
         //given
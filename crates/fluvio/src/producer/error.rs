@@ -27,4 +27,6 @@ pub enum ProducerError {
     ProduceRequestRetryTimeout(#[from] TimeoutError),
     #[error("the batch enqueue timeout limit reached")]
     BatchQueueWaitTimeout,
+    #[error("record does not match schema version {version}: {reason}")]
+    SchemaValidation { version: u32, reason: String },
 }
@@ -1,16 +1,17 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use tracing::instrument;
+use tracing::{instrument, warn};
 use async_lock::RwLock;
 use anyhow::Result;
 
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::record::Record;
+use fluvio_protocol::Encoder;
 use fluvio_compression::Compression;
 #[cfg(feature = "compress")]
 use fluvio_sc_schema::topic::CompressionAlgorithm;
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionCount, PartitionId};
 use fluvio_types::event::StickyEvent;
 
 mod accumulator;
@@ -24,7 +25,7 @@ mod memory_batch;
 
 pub mod event;
 
-pub use fluvio_protocol::record::{RecordKey, RecordData};
+pub use fluvio_protocol::record::{RecordKey, RecordData, TraceContext};
 
 use crate::spu::SpuPool;
 use crate::spu::SpuSocketPool;
@@ -43,7 +44,7 @@ pub use self::config::{
 };
 pub use self::error::ProducerError;
 use self::event::EventHandler;
-pub use self::output::ProduceOutput;
+pub use self::output::{BatchProduceOutput, ProduceOutput};
 use self::partition_producer::PartitionProducer;
 pub use self::record::{FutureRecordMetadata, RecordMetadata};
 
@@ -206,6 +207,12 @@ where
     inner: Arc<InnerTopicProducer<S>>,
     #[cfg(feature = "smartengine")]
     sm_chain: Option<Arc<RwLock<fluvio_smartengine::SmartModuleChainInstance>>>,
+    /// producers of the dead letter topics of the chain, by topic
+    #[cfg(feature = "smartengine")]
+    dead_letter_producers: BTreeMap<String, Arc<InnerTopicProducer<S>>>,
+    #[cfg(feature = "encryption")]
+    encryptor: Option<Arc<crate::encryption::RecordEncryptor>>,
+    schema_validator: Option<Arc<crate::schema::SchemaValidator>>,
     #[allow(unused)]
     metrics: Arc<ClientMetrics>,
 }
@@ -220,6 +227,9 @@ where
     record_accumulator: Arc<RecordAccumulator>,
     producer_pool: Arc<RwLock<ProducerPool>>,
     metrics: Arc<ClientMetrics>,
+    /// dictionary batches are compressed with, registered while the producer lives
+    #[cfg(feature = "compress")]
+    _compression_dictionary: Option<fluvio_compression::DictionaryRegistration>,
 }
 
 impl<S> InnerTopicProducer<S>
@@ -232,7 +242,7 @@ where
         Ok(())
     }
 
-    async fn push_record(self: Arc<Self>, record: Record) -> Result<PushRecord> {
+    async fn partition_count(&self) -> Result<PartitionCount> {
         let topics = self.spu_pool.topics();
 
         let topic_spec = topics
@@ -240,16 +250,51 @@ where
             .await?
             .ok_or_else(|| FluvioError::TopicNotFound(self.topic.to_string()))?
             .spec;
-        let partition_count = topic_spec.partitions();
+        Ok(topic_spec.partitions())
+    }
+
+    async fn partition_for(&self, record: &Record) -> Result<PartitionId> {
+        let partition_count = self.partition_count().await?;
         let partition_config = PartitionerConfig { partition_count };
 
         let key = record.key.as_ref().map(|k| k.as_ref());
         let value = record.value.as_ref();
-        let partition = self
+        Ok(self
             .config
             .partitioner
-            .partition(&partition_config, key, value);
+            .partition(&partition_config, key, value))
+    }
+
+    async fn push_record(self: Arc<Self>, record: Record) -> Result<PushRecord> {
+        let partition = self.partition_for(&record).await?;
+        self.push_record_to(record, partition).await
+    }
+
+    /// split a record too large for a request, all chunks go to the same partition
+    async fn push_chunked_record(self: Arc<Self>, record: Record) -> Result<Vec<PushRecord>> {
+        let chunk_size = self.config.max_request_size.saturating_sub(
+            crate::chunking::envelope_size(&record) + crate::chunking::CHUNK_OVERHEAD,
+        );
+        if chunk_size == 0 {
+            return Err(ProducerError::RecordTooLarge(
+                record.write_size(0),
+                self.config.max_request_size,
+            )
+            .into());
+        }
+        let partition = self.partition_for(&record).await?;
+        let mut push_records = Vec::new();
+        for chunk in crate::chunking::split_record(record, chunk_size) {
+            push_records.push(self.clone().push_record_to(chunk, partition).await?);
+        }
+        Ok(push_records)
+    }
 
+    async fn push_record_to(
+        self: Arc<Self>,
+        record: Record,
+        partition: PartitionId,
+    ) -> Result<PushRecord> {
         let mut producer_pool = self.producer_pool.write().await;
 
         if let Some(error) = producer_pool.last_error(partition).await {
@@ -313,6 +358,13 @@ cfg_if::cfg_if! {
                 Ok(self)
             }
 
+            /// Produces the records failing a SmartModule of the chain configured with `topic`
+            /// as dead letter topic with `producer`. Sending fails when such records have no producer.
+            pub fn with_dead_letter_producer(mut self, topic: impl Into<String>, producer: TopicProducer<S>) -> Self {
+                self.dead_letter_producers.insert(topic.into(), producer.inner);
+                self
+            }
+
             /// Adds a SmartModule filter to this TopicProducer
             pub async fn with_filter(
                 self,
@@ -405,9 +457,17 @@ where
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "compress")] {
+                let dictionary = config
+                    .compression_dictionary()
+                    .or(topic_spec.get_compression_dictionary())
+                    .cloned();
                 let compression = determine_producer_compression_algo(config.clone(), topic_spec)?;
+                let compression_dictionary =
+                    register_compression_dictionary(&topic, &config, compression, dictionary)?;
+                let dictionary_id = compression_dictionary.as_ref().map(|dictionary| dictionary.id());
             } else {
                 let compression = Compression::None;
+                let dictionary_id = None;
             }
         }
 
@@ -417,7 +477,8 @@ where
             config.batch_queue_size,
             partition_count,
             compression,
-        );
+        )
+        .with_compression_dictionary(dictionary_id);
         let producer_pool = ProducerPool::new(
             config.clone(),
             topic.clone(),
@@ -434,9 +495,16 @@ where
                 producer_pool: Arc::new(RwLock::new(producer_pool)),
                 record_accumulator: Arc::new(record_accumulator),
                 metrics: metrics.clone(),
+                #[cfg(feature = "compress")]
+                _compression_dictionary: compression_dictionary,
             }),
             #[cfg(feature = "smartengine")]
             sm_chain: Default::default(),
+            #[cfg(feature = "smartengine")]
+            dead_letter_producers: Default::default(),
+            #[cfg(feature = "encryption")]
+            encryptor: Default::default(),
+            schema_validator: None,
             metrics,
         })
     }
@@ -449,6 +517,19 @@ where
         &self.inner.config
     }
 
+    /// Encrypt the value of every record sent by this producer, see [`crate::encryption`]
+    #[cfg(feature = "encryption")]
+    pub fn with_encryption(mut self, encryptor: Arc<crate::encryption::RecordEncryptor>) -> Self {
+        self.encryptor = Some(encryptor);
+        self
+    }
+
+    /// Check the value of every record sent by this producer, see [`crate::schema`]
+    pub fn with_schema_validator(mut self, validator: Arc<crate::schema::SchemaValidator>) -> Self {
+        self.schema_validator = Some(validator);
+        self
+    }
+
     /// Send all the queued records in the producer batches.
     ///
     /// # Example
@@ -494,11 +575,84 @@ where
     ) -> Result<ProduceOutput> {
         let record_key = key.into();
         let record_value = value.into();
-        let record = Record::from((record_key, record_value));
+        self.send_record(Record::from((record_key, record_value)))
+            .await
+    }
+
+    /// Sends a key/value record with headers to this producer's Topic.
+    ///
+    /// Headers are kept in order and a key may be repeated. The headers of the
+    /// producer config are added after them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::{TopicProducerPool, FluvioError};
+    /// # async fn example(producer: &TopicProducerPool) -> anyhow::Result<()> {
+    /// producer
+    ///     .send_with_headers("Key", "Value", [("content-type", "text/plain")])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, key, value, headers),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_with_headers(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+        headers: impl IntoIterator<Item = (impl Into<String>, impl Into<RecordData>)>,
+    ) -> Result<ProduceOutput> {
+        let mut record = Record::from((key.into(), value.into()));
+        for (header_key, header_value) in headers {
+            record.add_header(header_key, header_value);
+        }
+        self.send_record(record).await
+    }
+
+    /// Sends a key/value record as part of an existing trace.
+    ///
+    /// The context is stored in the `traceparent` header of the record, SmartModules of
+    /// the SPU continue the trace and consumers read it with [`crate::consumer::Record::trace_context`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::{TopicProducerPool, TraceContext};
+    /// # async fn example(producer: &TopicProducerPool) -> anyhow::Result<()> {
+    /// let context: TraceContext = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse()?;
+    /// producer
+    ///     .send_with_trace_context("Key", "Value", &context)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, key, value, context),
+        fields(topic = %self.inner.topic, trace_id = %context.trace_id_hex()),
+    )]
+    pub async fn send_with_trace_context(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+        context: &TraceContext,
+    ) -> Result<ProduceOutput> {
+        let mut record = Record::from((key.into(), value.into()));
+        record.set_trace_context(context);
+        self.send_record(record).await
+    }
 
+    async fn send_record(&self, mut record: Record) -> Result<ProduceOutput> {
+        // before the chain so SmartModules of the producer are part of the trace
+        if self.inner.config.trace_propagation && record.trace_context().is_none() {
+            record.set_trace_context(&TraceContext::new_root());
+        }
         cfg_if::cfg_if! {
             if #[cfg(feature = "smartengine")] {
                 let mut entries = vec![record];
+                let mut dead_letters = Vec::new();
 
                 use chrono::Utc;
 
@@ -519,6 +673,7 @@ where
 
                     let output = sm_chain.process(sm_input,metrics).map_err(|e| FluvioError::Other(format!("SmartEngine - {e:?}")))?;
                     entries = output.successes;
+                    dead_letters = sm_chain.take_dead_letters();
                 }
             } else {
                 let  entries = vec![record];
@@ -526,7 +681,46 @@ where
         }
 
         let mut results = ProduceOutput::default();
-        for record in entries {
+        #[cfg(feature = "smartengine")]
+        for dead_letter in dead_letters {
+            let producer = self
+                .dead_letter_producers
+                .get(&dead_letter.topic)
+                .ok_or_else(|| {
+                    FluvioError::Other(format!(
+                        "no producer for dead letter topic {}",
+                        dead_letter.topic
+                    ))
+                })?;
+            let mut record = dead_letter.to_record();
+            record
+                .headers
+                .extend(producer.config.headers.iter().cloned());
+            let push_record = producer.clone().push_record(record).await?;
+            results.add(push_record.future);
+        }
+        for mut record in entries {
+            if let Some(validator) = &self.schema_validator {
+                validator.validate(record.value.as_ref())?;
+            }
+            // after the chain so SmartModules never see producer headers
+            record
+                .headers
+                .extend(self.inner.config.headers.iter().cloned());
+            #[cfg(feature = "encryption")]
+            let record = match &self.encryptor {
+                Some(encryptor) => encryptor.encrypt_record(record).await?,
+                None => record,
+            };
+            if self.inner.config.chunking
+                && record.write_size(0) + crate::chunking::CHUNK_OVERHEAD
+                    > self.inner.config.max_request_size
+            {
+                for push_record in self.inner.clone().push_chunked_record(record).await? {
+                    results.add(push_record.future);
+                }
+                continue;
+            }
             let push_record = self.inner.clone().push_record(record).await?;
             results.add(push_record.future);
         }
@@ -550,6 +744,80 @@ where
         Ok(results)
     }
 
+    /// Sends key/value records and flushes them, the outcome of each record is kept.
+    ///
+    /// Unlike [`TopicProducer::send_all`], a record that can't be sent doesn't stop
+    /// the following records. Partitions have at most `max_inflight_requests` batches
+    /// in flight as configured in the producer config, the others wait in the batch queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::{TopicProducerPool, FluvioError};
+    /// # async fn example(producer: &TopicProducerPool) -> anyhow::Result<()> {
+    /// let records = [("a", "1"), ("b", "2")];
+    /// let output = producer.send_batch(records).await;
+    /// for (record, result) in records.iter().zip(output.wait().await) {
+    ///     match result {
+    ///         Ok(metadata) => println!("{record:?} at {}", metadata.offset()),
+    ///         Err(err) => println!("{record:?} failed: {err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, records),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_batch(
+        &self,
+        records: impl IntoIterator<Item = (impl Into<RecordKey>, impl Into<RecordData>)>,
+    ) -> BatchProduceOutput {
+        let mut outputs = vec![];
+        for (key, value) in records {
+            outputs.push(self.send(key, value).await);
+        }
+        // failed batches are reported by the records they hold
+        if let Err(err) = self.flush().await {
+            warn!(%err, "flush of the batch failed");
+        }
+        BatchProduceOutput::new(outputs)
+    }
+
+    /// Sends a marker to every partition of this producer's Topic, see [`crate::marker`].
+    ///
+    /// Markers skip the SmartModule chain and encryption of the producer, so that every
+    /// consumer can read them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::{TopicProducerPool, FluvioError};
+    /// # async fn example(producer: &TopicProducerPool) -> anyhow::Result<()> {
+    /// use fluvio::marker::Marker;
+    /// producer.send_marker(&Marker::Complete("backfill".to_owned())).await?;
+    /// producer.flush().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, marker),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_marker(&self, marker: &crate::marker::Marker) -> Result<ProduceOutput> {
+        let mut results = ProduceOutput::default();
+        for partition in 0..self.inner.partition_count().await? {
+            let push_record = self
+                .inner
+                .clone()
+                .push_record_to(marker.to_record(), partition)
+                .await?;
+            results.add(push_record.future);
+        }
+        Ok(results)
+    }
+
     /// Clear partition producers errors in order to make partition producers available.
     /// This is needed once an error is present in order to send new records again.
     pub async fn clear_errors(&self) {
@@ -567,6 +835,13 @@ fn determine_producer_compression_algo(
     config: Arc<TopicProducerConfig>,
     topic_spec: fluvio_sc_schema::topic::TopicSpec,
 ) -> Result<Compression> {
+    // the SPU recompresses batches with the compression type of the topic
+    if let Some(compression) = config
+        .compression
+        .filter(|_| topic_spec.is_compression_transcoded())
+    {
+        return Ok(compression);
+    }
     let result = match topic_spec.get_compression_type() {
         CompressionAlgorithm::Any => config.compression.unwrap_or_default(),
         CompressionAlgorithm::Gzip => match config.compression {
@@ -605,6 +880,34 @@ fn determine_producer_compression_algo(
     Ok(result)
 }
 
+/// Dictionary batches are compressed with, only zstd batches use one.
+#[cfg(feature = "compress")]
+fn register_compression_dictionary(
+    topic: &str,
+    config: &TopicProducerConfig,
+    compression: Compression,
+    dictionary: Option<fluvio_sc_schema::topic::CompressionDictionary>,
+) -> Result<Option<fluvio_compression::DictionaryRegistration>> {
+    let Some(dictionary) = dictionary else {
+        return Ok(None);
+    };
+    if compression != Compression::Zstd {
+        if config.compression_dictionary().is_some() {
+            return Err(
+                FluvioError::Producer(ProducerError::InvalidConfiguration(format!(
+                    "Compression dictionary requires zstd compression, not {compression}"
+                )))
+                .into(),
+            );
+        }
+        return Ok(None);
+    }
+    Ok(Some(fluvio_compression::register_dictionary(
+        topic,
+        dictionary.as_bytes(),
+    )?))
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -2,6 +2,38 @@ use crate::producer::record::{RecordMetadata, FutureRecordMetadata};
 use crate::producer::error::ProducerError;
 use crate::error::Result;
 
+/// Outcome of the records sent with `TopicProducer::send_batch`, one per record
+/// in the order the records were given
+pub struct BatchProduceOutput {
+    outputs: Vec<Result<ProduceOutput>>,
+}
+
+impl BatchProduceOutput {
+    pub(crate) fn new(outputs: Vec<Result<ProduceOutput>>) -> Self {
+        Self { outputs }
+    }
+
+    /// Number of records sent
+    pub fn len(&self) -> usize {
+        self.outputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outputs.is_empty()
+    }
+
+    /// Wait for the metadata of every record, the partition and offset where it was
+    /// written, or the error that kept it from being sent or written.
+    /// The results are in the order the records were given.
+    pub async fn wait(self) -> Vec<Result<RecordMetadata>> {
+        let records = self
+            .outputs
+            .into_iter()
+            .map(|output| async move { output?.wait().await });
+        futures_util::future::join_all(records).await
+    }
+}
+
 /// Struct returned by of TopicProduce::send call, it is used
 ///  to gather the record metadata associated to each send call.
 #[derive(Default)]
@@ -1,10 +1,15 @@
-use std::sync::Arc;
-
-use async_lock::RwLock;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_channel::Sender;
+use async_lock::{RwLock, Semaphore};
+use chrono::Utc;
+use futures_util::future::BoxFuture;
 use tracing::{debug, info, instrument, error, trace};
 
 use fluvio_protocol::record::ReplicaKey;
-use fluvio_protocol::record::{RawRecords, Batch};
+use fluvio_protocol::record::{increment_sequence, RawRecords, Batch};
 use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultTopicRequest, DefaultProduceRequest};
 use fluvio_future::timer::sleep;
 use fluvio_types::SpuId;
@@ -22,6 +27,45 @@ use super::{PartitionProducerParams, ProducerError};
 use super::accumulator::{BatchEvents, BatchesDeque};
 use super::event::EventHandler;
 
+/// Producer id and next sequence number of the batches of an idempotent producer
+#[derive(Debug)]
+struct BatchSequencer {
+    producer_id: i64,
+    epoch: i16,
+    next_sequence: i32,
+}
+
+impl BatchSequencer {
+    fn new() -> Self {
+        let id = RandomState::new().build_hasher().finish();
+        Self {
+            producer_id: (id & i64::MAX as u64) as i64,
+            epoch: 0,
+            next_sequence: 0,
+        }
+    }
+
+    fn assign(&mut self, batch: &mut Batch) {
+        let records = batch.records_len() as i32;
+        let header = batch.get_mut_header();
+        header.producer_id = self.producer_id;
+        header.producer_epoch = self.epoch;
+        header.first_sequence = self.next_sequence;
+        self.next_sequence = increment_sequence(self.next_sequence, records);
+    }
+
+    /// records that were not appended leave a gap in the sequence,
+    /// the next epoch starts the sequence over
+    fn reset(&mut self) {
+        if self.epoch == i16::MAX {
+            *self = Self::new();
+        } else {
+            self.epoch += 1;
+            self.next_sequence = 0;
+        }
+    }
+}
+
 /// Struct that is responsible for sending produce requests to the SPU in a given partition.
 pub(crate) struct PartitionProducer<S>
 where
@@ -34,6 +78,9 @@ where
     batch_events: Arc<BatchEvents>,
     last_error: Arc<RwLock<Option<ProducerError>>>,
     metrics: Arc<ClientMetrics>,
+    /// permits for the requests in flight
+    inflight: Arc<Semaphore>,
+    sequencer: Option<Arc<Mutex<BatchSequencer>>>,
 }
 
 impl<S> PartitionProducer<S>
@@ -45,6 +92,11 @@ where
         replica: ReplicaKey,
         last_error: Arc<RwLock<Option<ProducerError>>>,
     ) -> Self {
+        let inflight = Arc::new(Semaphore::new(params.config.max_inflight_requests()));
+        let sequencer = params
+            .config
+            .idempotence()
+            .then(|| Arc::new(Mutex::new(BatchSequencer::new())));
         Self {
             config: params.config,
             replica,
@@ -53,6 +105,8 @@ where
             batch_events: params.batch_events,
             last_error,
             metrics: params.client_metric,
+            inflight,
+            sequencer,
         }
     }
 
@@ -197,9 +251,22 @@ where
                 ..Default::default()
             };
             let notify = p_batch.notify.clone();
-            let batch = p_batch.batch();
+            let dictionary = p_batch.compression_dictionary();
+            let mut batch = p_batch.batch();
+            if let Some(sequencer) = &self.sequencer {
+                sequencer
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .assign(&mut batch);
+            }
 
-            let raw_batch: Batch<RawRecords> = batch.try_into()?;
+            let raw_batch: Batch<RawRecords> = match dictionary {
+                #[cfg(feature = "compress")]
+                Some(dictionary_id) => {
+                    batch.compress_with_dictionary(&self.replica.topic, dictionary_id)?
+                }
+                _ => batch.try_into()?,
+            };
 
             let producer_metrics = self.metrics.producer_client();
             producer_metrics.add_records(raw_batch.records_len() as u64);
@@ -215,7 +282,70 @@ where
         request.smartmodules.clone_from(&self.config.smartmodules);
         request.topics.push(topic_request);
 
-        let (response, _) = self.send_to_socket(spu_socket, request).await?;
+        // waits while the max requests are in flight
+        let permit = self.inflight.acquire_arc().await;
+        let send = Self::send_and_notify(
+            self.config.clone(),
+            self.metrics.clone(),
+            spu_socket,
+            request,
+            batch_notifiers,
+            self.sequencer.clone(),
+        );
+        if self.config.max_inflight_requests() == 1 {
+            send.await?;
+            drop(permit);
+        } else {
+            let last_error = self.last_error.clone();
+            fluvio_future::task::spawn(async move {
+                if let Err(e) = send.await {
+                    error!("Failed to send produce request: {}", e);
+                    *last_error.write().await = Some(ProducerError::Internal(e.to_string()));
+                }
+                drop(permit);
+            });
+        }
+
+        if force {
+            self.wait_inflight().await;
+        }
+        Ok(())
+    }
+
+    /// wait until the requests in flight got their response
+    async fn wait_inflight(&self) {
+        let mut permits = Vec::with_capacity(self.config.max_inflight_requests());
+        for _ in 0..self.config.max_inflight_requests() {
+            permits.push(self.inflight.acquire().await);
+        }
+    }
+
+    /// send `request` and hand the partition responses to the batches, returns once the
+    /// SPU responded
+    async fn send_and_notify(
+        config: Arc<TopicProducerConfig>,
+        metrics: Arc<ClientMetrics>,
+        socket: VersionedSerialSocket,
+        request: DefaultProduceRequest,
+        batch_notifiers: Vec<Sender<ProducePartitionResponseFuture>>,
+        sequencer: Option<Arc<Mutex<BatchSequencer>>>,
+    ) -> Result<()> {
+        let sent = Self::send_to_socket(&config, &metrics, socket, request).await;
+        if let Some(sequencer) = sequencer {
+            let failed = match &sent {
+                Ok((response, _)) => response
+                    .iter()
+                    .any(ProducePartitionResponseFuture::is_ready_error),
+                Err(_) => true,
+            };
+            if failed {
+                sequencer
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .reset();
+            }
+        }
+        let (response, pending) = sent?;
 
         for (batch_notifier, partition_response_fut) in
             batch_notifiers.into_iter().zip(response.into_iter())
@@ -225,33 +355,49 @@ where
             }
         }
 
+        if let Some(pending) = pending {
+            pending.await;
+        }
         Ok(())
     }
 
+    /// the partition responses, and for fire and forget requests the pending response
     async fn send_to_socket(
-        &self,
+        config: &TopicProducerConfig,
+        metrics: &ClientMetrics,
         socket: VersionedSerialSocket,
         request: DefaultProduceRequest,
-    ) -> Result<(Vec<ProducePartitionResponseFuture>, Option<i64>)> {
+    ) -> Result<(
+        Vec<ProducePartitionResponseFuture>,
+        Option<BoxFuture<'static, ()>>,
+    )> {
         let partition_count: usize = request.topics.iter().map(|t| t.partitions.len()).sum();
-        let mut last_offset = None;
-        trace!(%partition_count, ?self.config.delivery_semantic);
-        let response: Vec<ProducePartitionResponseFuture> = match self.config.delivery_semantic {
+        let mut pending = None;
+        trace!(%partition_count, ?config.delivery_semantic);
+        let response: Vec<ProducePartitionResponseFuture> = match config.delivery_semantic {
             DeliverySemantic::AtMostOnce => {
                 use futures_util::FutureExt;
                 let async_response = socket.send_async(request).await?;
                 let shared = FutureExt::map(async_response, Arc::new).boxed().shared();
+                pending = Some(shared.clone().map(|_| ()).boxed());
                 (0..partition_count)
                     .map(|index| ProducePartitionResponseFuture::from(shared.clone(), index))
                     .collect()
             }
             DeliverySemantic::AtLeastOnce(policy) => {
                 use fluvio_future::retry::RetryExt;
+                let sent_at = Utc::now();
                 let produce_response = socket
                     .send_receive_with_retry(request, policy.iter())
                     .timeout(policy.timeout)
                     .await
                     .map_err(|timeout_err| FluvioError::Producer(timeout_err.into()))??;
+                let ack_latency = (Utc::now() - sent_at)
+                    .num_microseconds()
+                    .unwrap_or(i64::MAX);
+                metrics
+                    .producer_ack_latency()
+                    .record_n(ack_latency.max(0) as u64, partition_count as u64);
 
                 let mut futures = Vec::with_capacity(partition_count);
                 for topic in produce_response.responses.into_iter() {
@@ -260,12 +406,38 @@ where
                             partition.base_offset,
                             partition.error_code,
                         ));
-                        last_offset = Some(partition.base_offset);
                     }
                 }
                 futures
             }
         };
-        Ok((response, last_offset))
+        Ok((response, pending))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::Record;
+
+    use super::*;
+
+    #[test]
+    fn test_batch_sequences() {
+        let mut sequencer = BatchSequencer::new();
+        assert!(sequencer.producer_id >= 0);
+
+        let mut first = Batch::from(vec![Record::default(), Record::default()]);
+        sequencer.assign(&mut first);
+        let mut second = Batch::from(vec![Record::default()]);
+        sequencer.assign(&mut second);
+        assert_eq!(first.get_header().first_sequence, 0);
+        assert_eq!(second.get_header().first_sequence, 2);
+        assert_eq!(second.get_header().producer_id, sequencer.producer_id);
+
+        sequencer.reset();
+        let mut third = Batch::from(vec![Record::default()]);
+        sequencer.assign(&mut third);
+        assert_eq!(third.get_header().producer_epoch, 1);
+        assert_eq!(third.get_header().first_sequence, 0);
     }
 }
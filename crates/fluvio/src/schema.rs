@@ -0,0 +1,339 @@
+//!
+//! # Schema Registry
+//!
+//! The SC keeps the versions of the schema of each topic. A version is registered only
+//! when it is compatible with the latest version under the compatibility rule of the
+//! schema, see [`check_compatibility`].
+//!
+//! Producers created with [`TopicProducerConfigBuilder::schema_validation`] check the
+//! value of each record against the latest version when the producer is created.
+//! Only JSON Schema values can be validated by the producer.
+//!
+//! [`TopicProducerConfigBuilder::schema_validation`]: crate::TopicProducerConfigBuilder::schema_validation
+//!
+
+use anyhow::Result;
+use serde_json::Value;
+use tracing::debug;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::ApiError;
+use fluvio_sc_schema::objects::Metadata;
+pub use fluvio_sc_schema::schema::{
+    check_compatibility, validate_definition, SchemaCompatibility, SchemaFormat, SchemaSpec,
+    SchemaVersion, UpdateSchemaAction,
+};
+
+use crate::producer::ProducerError;
+use crate::FluvioAdmin;
+
+/// Registers and reads the schemas of topics
+pub struct SchemaClient {
+    admin: FluvioAdmin,
+}
+
+impl SchemaClient {
+    pub fn new(admin: FluvioAdmin) -> Self {
+        Self { admin }
+    }
+
+    /// Registers `definition` as the next version of the schema of `topic` and
+    /// returns the version number.
+    ///
+    /// The schema is created with `compatibility` when the topic has none yet,
+    /// otherwise `format` must be the format of the schema and the SC rejects
+    /// definitions that are not compatible with the latest version.
+    pub async fn register(
+        &self,
+        topic: &str,
+        format: SchemaFormat,
+        compatibility: SchemaCompatibility,
+        definition: impl Into<String>,
+    ) -> Result<u32> {
+        let definition = definition.into();
+        let Some(current) = self.get(topic).await? else {
+            let mut spec = SchemaSpec::new(format, definition);
+            spec.compatibility = compatibility;
+            self.admin.create(topic.to_owned(), false, spec).await?;
+            return Ok(1);
+        };
+        if current.format != format {
+            return Err(ApiError::Code(
+                ErrorCode::SchemaInvalid(format!(
+                    "schema of topic '{topic}' is {}, not {format}",
+                    current.format
+                )),
+                None,
+            )
+            .into());
+        }
+        self.admin
+            .update::<SchemaSpec>(
+                topic.to_owned(),
+                UpdateSchemaAction::Register { definition },
+            )
+            .await?;
+        let registered = self
+            .latest(topic)
+            .await?
+            .map(|latest| latest.version)
+            .unwrap_or_default();
+        debug!(topic, registered, "schema registered");
+        Ok(registered)
+    }
+
+    /// Changes the rule checked when versions are registered
+    pub async fn set_compatibility(
+        &self,
+        topic: &str,
+        compatibility: SchemaCompatibility,
+    ) -> Result<()> {
+        self.admin
+            .update::<SchemaSpec>(
+                topic.to_owned(),
+                UpdateSchemaAction::SetCompatibility(compatibility),
+            )
+            .await
+    }
+
+    pub async fn list(&self) -> Result<Vec<Metadata<SchemaSpec>>> {
+        self.admin.all::<SchemaSpec>().await
+    }
+
+    /// Schema of `topic`, None if it has none
+    pub async fn get(&self, topic: &str) -> Result<Option<SchemaSpec>> {
+        Ok(self
+            .admin
+            .list::<SchemaSpec, _>(vec![topic.to_owned()])
+            .await?
+            .into_iter()
+            .find(|schema| schema.name == topic)
+            .map(|schema| schema.spec))
+    }
+
+    pub async fn latest(&self, topic: &str) -> Result<Option<SchemaVersion>> {
+        Ok(self
+            .get(topic)
+            .await?
+            .and_then(|spec| spec.latest().cloned()))
+    }
+
+    /// Checks `definition` against the latest version of the schema of `topic`,
+    /// without registering it
+    pub async fn check_compatibility(&self, topic: &str, definition: &str) -> Result<()> {
+        let spec = self
+            .get(topic)
+            .await?
+            .ok_or(ApiError::Code(ErrorCode::SchemaNotFound, None))?;
+        if let Some(latest) = spec.latest() {
+            check_compatibility(
+                spec.format,
+                spec.compatibility,
+                &latest.definition,
+                definition,
+            )
+            .map_err(|reason| {
+                ApiError::Code(
+                    ErrorCode::SchemaIncompatible {
+                        version: latest.version,
+                        reason,
+                    },
+                    None,
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Validator of record values for the latest version of the schema of `topic`
+    pub async fn validator(&self, topic: &str) -> Result<SchemaValidator> {
+        let spec = self
+            .get(topic)
+            .await?
+            .ok_or(ApiError::Code(ErrorCode::SchemaNotFound, None))?;
+        SchemaValidator::try_from(&spec)
+    }
+}
+
+/// Checks record values against a version of a JSON Schema
+#[derive(Debug, Clone)]
+pub struct SchemaValidator {
+    version: u32,
+    schema: Value,
+}
+
+impl TryFrom<&SchemaSpec> for SchemaValidator {
+    type Error = anyhow::Error;
+
+    fn try_from(spec: &SchemaSpec) -> Result<Self> {
+        let latest = spec
+            .latest()
+            .ok_or(ApiError::Code(ErrorCode::SchemaNotFound, None))?;
+        if spec.format != SchemaFormat::JsonSchema {
+            return Err(ProducerError::InvalidConfiguration(format!(
+                "values can not be validated against {} schemas",
+                spec.format
+            ))
+            .into());
+        }
+        Ok(Self {
+            version: latest.version,
+            schema: serde_json::from_str(&latest.definition)?,
+        })
+    }
+}
+
+impl SchemaValidator {
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn validate(&self, value: &[u8]) -> Result<(), ProducerError> {
+        let error = |reason: String| ProducerError::SchemaValidation {
+            version: self.version,
+            reason,
+        };
+        let value: Value =
+            serde_json::from_slice(value).map_err(|err| error(format!("invalid json: {err}")))?;
+        validate_json(&self.schema, &value, "$").map_err(error)
+    }
+}
+
+fn json_type_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        _ => true,
+    }
+}
+
+/// the keywords describing the shape of values: type, enum, const, required,
+/// properties, additionalProperties and items
+fn validate_json(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let schema = match schema {
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => return Err(format!("{path} is not allowed")),
+        Value::Object(schema) => schema,
+        _ => return Ok(()),
+    };
+
+    match schema.get("type") {
+        Some(Value::String(name)) if !json_type_matches(name, value) => {
+            return Err(format!("{path} is not of type {name}"));
+        }
+        Some(Value::Array(names))
+            if !names
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|name| json_type_matches(name, value)) =>
+        {
+            return Err(format!("{path} is not of an accepted type"));
+        }
+        _ => {}
+    }
+
+    if let Some(Value::Array(values)) = schema.get("enum") {
+        if !values.contains(value) {
+            return Err(format!("{path} is not one of the enum values"));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            return Err(format!("{path} is not {expected}"));
+        }
+    }
+
+    if let Value::Object(object) = value {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            if let Some(missing) = required
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|name| !object.contains_key(*name))
+            {
+                return Err(format!("{path}.{missing} is required"));
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (name, property) in object {
+            let property_path = format!("{path}.{name}");
+            match properties.and_then(|properties| properties.get(name)) {
+                Some(property_schema) => validate_json(property_schema, property, &property_path)?,
+                None => {
+                    if let Some(additional) = schema.get("additionalProperties") {
+                        validate_json(additional, property, &property_path)?;
+                    }
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            validate_json(item_schema, item, &format!("{path}[{index}]"))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(definition: &str) -> SchemaValidator {
+        SchemaValidator::try_from(&SchemaSpec::new(SchemaFormat::JsonSchema, definition))
+            .expect("validator")
+    }
+
+    #[test]
+    fn test_validate_json_values() {
+        let validator = validator(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer"},
+                    "tier": {"enum": ["free", "paid"]},
+                    "tags": {"type": "array", "items": {"type": "string"}}
+                },
+                "required": ["id"],
+                "additionalProperties": false
+            }"#,
+        );
+
+        assert!(validator
+            .validate(br#"{"id": 1, "tier": "paid", "tags": ["a"]}"#)
+            .is_ok());
+
+        let reason = |value: &[u8]| match validator.validate(value) {
+            Err(ProducerError::SchemaValidation { version: 1, reason }) => reason,
+            other => panic!("unexpected {other:?}"),
+        };
+        assert_eq!(reason(br#"{"tier": "free"}"#), "$.id is required");
+        assert_eq!(reason(br#"{"id": 1.5}"#), "$.id is not of type integer");
+        assert_eq!(
+            reason(br#"{"id": 1, "tier": "gold"}"#),
+            "$.tier is not one of the enum values"
+        );
+        assert_eq!(
+            reason(br#"{"id": 1, "tags": [1]}"#),
+            "$.tags[0] is not of type string"
+        );
+        assert_eq!(
+            reason(br#"{"id": 1, "name": "a"}"#),
+            "$.name is not allowed"
+        );
+        assert!(reason(b"not json").starts_with("invalid json"));
+    }
+
+    #[test]
+    fn test_validator_formats() {
+        let avro = SchemaSpec::new(SchemaFormat::Avro, r#"{"type": "string"}"#);
+        assert!(SchemaValidator::try_from(&avro).is_err());
+        assert!(SchemaValidator::try_from(&SchemaSpec::default()).is_err());
+    }
+}
@@ -15,7 +15,10 @@ use fluvio_socket::{
     AsyncResponse, ClientConfig, MultiplexerSocket, SocketError, StreamSocket,
     VersionedSerialSocket,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use fluvio_socket::{CircuitState, ReconnectPolicy, Reconnector};
 use crate::FluvioError;
+use crate::config::SpuEndpointResolver;
 use crate::sync::{MetadataStores, StoreContext};
 
 /// used for connecting to spu
@@ -48,6 +51,50 @@ pub struct SpuSocketPool {
     config: Arc<ClientConfig>,
     pub(crate) metadata: MetadataStores,
     spu_clients: Arc<Mutex<HashMap<SpuId, StreamSocket>>>,
+    endpoint_resolver: Option<Arc<dyn SpuEndpointResolver>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    reconnect_policy: ReconnectPolicy,
+    /// reconnect backoff by SPU, kept across connections so reconnects after a restart are spread
+    #[cfg(not(target_arch = "wasm32"))]
+    reconnectors: std::sync::Mutex<HashMap<SpuId, Arc<Reconnector>>>,
+}
+
+impl SpuSocketPool {
+    /// connect to SPUs through the addresses given by `resolver`
+    pub(crate) fn with_endpoint_resolver(
+        mut self,
+        resolver: Option<Arc<dyn SpuEndpointResolver>>,
+    ) -> Self {
+        self.endpoint_resolver = resolver;
+        self
+    }
+
+    /// back off reconnects to SPUs with `policy`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = policy;
+        self
+    }
+
+    /// state of the connection circuit to SPU `leader`, `None` if never connected
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn circuit_state(&self, leader: SpuId) -> Option<CircuitState> {
+        self.reconnectors
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&leader)
+            .map(|reconnector| reconnector.state())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reconnector(&self, leader: SpuId) -> Arc<Reconnector> {
+        self.reconnectors
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(leader)
+            .or_insert_with(|| Arc::new(Reconnector::new(self.reconnect_policy.clone())))
+            .clone()
+    }
 }
 
 impl Drop for SpuSocketPool {
@@ -92,6 +139,11 @@ impl SpuPool for SpuSocketPool {
             metadata,
             config,
             spu_clients: Arc::new(Mutex::new(HashMap::new())),
+            endpoint_resolver: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            reconnect_policy: ReconnectPolicy::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            reconnectors: Default::default(),
         })
     }
 
@@ -102,7 +154,7 @@ impl SpuPool for SpuSocketPool {
 
         let mut client_config = self.config.with_prefix_sni_domain(spu.key());
 
-        let spu_addr = match spu.spec.public_endpoint_local {
+        let mut spu_addr = match spu.spec.public_endpoint_local {
             Some(local) if self.config.use_spu_local_address() => {
                 let host = local.host;
                 let port = local.port;
@@ -110,9 +162,23 @@ impl SpuPool for SpuSocketPool {
             }
             _ => spu.spec.public_endpoint.addr(),
         };
+        if let Some(addr) = self
+            .endpoint_resolver
+            .as_ref()
+            .and_then(|resolver| resolver.resolve(spu.spec.id, &spu_addr))
+        {
+            debug!(leader = spu.spec.id, advertised = %spu_addr, %addr, "spu endpoint overridden");
+            spu_addr = addr;
+        }
 
         debug!(leader = spu.spec.id,addr = %spu_addr,"try connecting to spu");
         client_config.set_addr(spu_addr);
+        #[cfg(not(target_arch = "wasm32"))]
+        let versioned_socket = self
+            .reconnector(leader)
+            .connect(|| client_config.connect())
+            .await?;
+        #[cfg(target_arch = "wasm32")]
         let versioned_socket = client_config.connect().await?;
         let (socket, config, versions) = versioned_socket.split();
         Ok(StreamSocket::new(
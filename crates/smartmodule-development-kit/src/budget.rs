@@ -0,0 +1,312 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use bytesize::ByteSize;
+use clap::Parser;
+use toml::Value;
+use wasmparser::{Parser as WasmParser, Payload};
+
+use fluvio_smartengine::{SmartEngine, SmartModuleChainBuilder, SmartModuleConfig};
+
+/// table of SmartModule.toml with the budget of the package
+const BUDGET_TABLE: &str = "budget";
+/// the median of these runs is reported
+const INSTANTIATION_RUNS: usize = 3;
+
+/// Limits on the built SmartModule, which SPUs keep in memory for every
+/// consumer and producer running it.
+///
+/// Budgets are read from the `[budget]` table of SmartModule.toml:
+///
+/// ```toml
+/// [budget]
+/// max-size = "512 KiB"
+/// max-imports = 16
+/// max-instantiation = "50ms"
+/// ```
+#[derive(Debug, Default, Parser)]
+pub(crate) struct BudgetOpt {
+    /// Max size of the WASM file, such as 512KiB. Overrides SmartModule.toml
+    #[arg(long, value_name = "SIZE")]
+    max_size: Option<ByteSize>,
+
+    /// Max functions, memories, tables and globals imported by the module.
+    /// Overrides SmartModule.toml
+    #[arg(long, value_name = "COUNT")]
+    max_imports: Option<usize>,
+
+    /// Max time to compile, instantiate and init the module, such as 50ms.
+    /// Overrides SmartModule.toml
+    #[arg(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+    max_instantiation: Option<Duration>,
+
+    /// Report the module without failing when a budget is exceeded
+    #[arg(long)]
+    no_budget_check: bool,
+}
+
+impl BudgetOpt {
+    /// Prints the report of the module at `wasm_path` and fails when it exceeds its budget
+    pub(crate) fn check(&self, wasm_path: &Path, sm_toml: Option<&Path>) -> Result<()> {
+        let budget = match sm_toml {
+            Some(path) => Budget::from_toml(&std::fs::read_to_string(path)?)
+                .with_context(|| format!("invalid budget in {}", path.display()))?,
+            None => Budget::default(),
+        }
+        .with_overrides(self);
+
+        let wasm = crate::read_bytes_from_path(&wasm_path.to_path_buf())?;
+        let report = ModuleReport::measure(wasm)?;
+        report.print(&budget);
+
+        let exceeded = report.exceeded(&budget);
+        if exceeded.is_empty() || self.no_budget_check {
+            return Ok(());
+        }
+        bail!("SmartModule exceeds its budget: {}", exceeded.join(", "))
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Budget {
+    max_size: Option<ByteSize>,
+    max_imports: Option<usize>,
+    max_instantiation: Option<Duration>,
+}
+
+impl Budget {
+    fn from_toml(content: &str) -> Result<Self> {
+        let sm_toml: Value = toml::from_str(content)?;
+        let Some(table) = sm_toml.get(BUDGET_TABLE) else {
+            return Ok(Self::default());
+        };
+        let table = table
+            .as_table()
+            .ok_or_else(|| anyhow!("[{BUDGET_TABLE}] must be a table"))?;
+
+        let max_size = match table.get("max-size") {
+            None => None,
+            Some(Value::Integer(bytes)) => Some(ByteSize::b(u64::try_from(*bytes)?)),
+            Some(Value::String(size)) => {
+                Some(size.parse().map_err(|err| anyhow!("max-size: {err}"))?)
+            }
+            Some(_) => bail!("max-size must be a size such as \"512 KiB\""),
+        };
+        let max_imports = match table.get("max-imports") {
+            None => None,
+            Some(Value::Integer(count)) => Some(usize::try_from(*count)?),
+            Some(_) => bail!("max-imports must be an integer"),
+        };
+        let max_instantiation = match table.get("max-instantiation") {
+            None => None,
+            Some(Value::String(duration)) => Some(humantime::parse_duration(duration)?),
+            Some(_) => bail!("max-instantiation must be a duration such as \"50ms\""),
+        };
+
+        Ok(Self {
+            max_size,
+            max_imports,
+            max_instantiation,
+        })
+    }
+
+    fn with_overrides(self, opt: &BudgetOpt) -> Self {
+        Self {
+            max_size: opt.max_size.or(self.max_size),
+            max_imports: opt.max_imports.or(self.max_imports),
+            max_instantiation: opt.max_instantiation.or(self.max_instantiation),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ModuleReport {
+    size: ByteSize,
+    /// `module::name` of the imports
+    imports: Vec<String>,
+    instantiation: Result<Duration, String>,
+}
+
+impl ModuleReport {
+    fn measure(wasm: Vec<u8>) -> Result<Self> {
+        let size = ByteSize::b(wasm.len() as u64);
+        let imports = module_imports(&wasm)?;
+        let instantiation = measure_instantiation(wasm).map_err(|err| err.to_string());
+        Ok(Self {
+            size,
+            imports,
+            instantiation,
+        })
+    }
+
+    fn print(&self, budget: &Budget) {
+        let of = |limit: Option<String>| {
+            limit
+                .map(|limit| format!(" (budget {limit})"))
+                .unwrap_or_default()
+        };
+        println!(
+            "SmartModule size: {}{}",
+            binary_size(self.size),
+            of(budget.max_size.map(binary_size))
+        );
+        println!(
+            "Imports: {}{}",
+            self.imports.len(),
+            of(budget.max_imports.map(|it| it.to_string()))
+        );
+        for import in &self.imports {
+            println!("    {import}");
+        }
+        match &self.instantiation {
+            Ok(duration) => println!(
+                "Instantiation: {duration:?}{}",
+                of(budget
+                    .max_instantiation
+                    .map(|it| humantime::format_duration(it).to_string()))
+            ),
+            Err(err) => println!("Instantiation failed: {err}"),
+        }
+    }
+
+    /// descriptions of the budgets exceeded
+    fn exceeded(&self, budget: &Budget) -> Vec<String> {
+        let mut exceeded = vec![];
+        if let Some(max_size) = budget.max_size {
+            if self.size > max_size {
+                exceeded.push(format!(
+                    "size {} > {}",
+                    binary_size(self.size),
+                    binary_size(max_size)
+                ));
+            }
+        }
+        if let Some(max_imports) = budget.max_imports {
+            if self.imports.len() > max_imports {
+                exceeded.push(format!("imports {} > {max_imports}", self.imports.len()));
+            }
+        }
+        if let Some(max_instantiation) = budget.max_instantiation {
+            match &self.instantiation {
+                Ok(duration) if *duration > max_instantiation => exceeded.push(format!(
+                    "instantiation {duration:?} > {}",
+                    humantime::format_duration(max_instantiation)
+                )),
+                Ok(_) => {}
+                Err(_) => exceeded.push("instantiation could not be measured".to_owned()),
+            }
+        }
+        exceeded
+    }
+}
+
+fn binary_size(size: ByteSize) -> String {
+    size.to_string_as(true)
+}
+
+fn module_imports(wasm: &[u8]) -> Result<Vec<String>> {
+    let mut imports = vec![];
+    for payload in WasmParser::new(0).parse_all(wasm) {
+        if let Payload::ImportSection(reader) = payload? {
+            for import in reader {
+                let import = import?;
+                imports.push(format!("{}::{}", import.module, import.name));
+            }
+        }
+    }
+    Ok(imports)
+}
+
+/// median time to compile, instantiate and init the module without params
+fn measure_instantiation(wasm: Vec<u8>) -> Result<Duration> {
+    let engine = SmartEngine::new();
+    let mut runs = Vec::with_capacity(INSTANTIATION_RUNS);
+    for _ in 0..INSTANTIATION_RUNS {
+        let builder =
+            SmartModuleChainBuilder::from((SmartModuleConfig::builder().build()?, wasm.clone()));
+        let start = Instant::now();
+        builder.initialize(&engine)?;
+        runs.push(start.elapsed());
+    }
+    runs.sort();
+    Ok(runs[runs.len() / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// module importing the function `env::f`
+    const IMPORTING_MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: () -> ()
+        0x02, 0x09, 0x01, 0x03, b'e', b'n', b'v', 0x01, b'f', 0x00, 0x00, // import section
+    ];
+
+    #[test]
+    fn test_budget_from_toml() {
+        let budget = Budget::from_toml(
+            r#"
+            [package]
+            name = "filter"
+
+            [budget]
+            max-size = "512 KiB"
+            max-imports = 16
+            max-instantiation = "50ms"
+            "#,
+        )
+        .expect("budget");
+        assert_eq!(
+            budget,
+            Budget {
+                max_size: Some(ByteSize::kib(512)),
+                max_imports: Some(16),
+                max_instantiation: Some(Duration::from_millis(50)),
+            }
+        );
+
+        assert_eq!(
+            Budget::from_toml("[package]\nname = \"filter\"").expect("no budget"),
+            Budget::default()
+        );
+        assert!(Budget::from_toml("[budget]\nmax-imports = \"many\"").is_err());
+
+        let opt = BudgetOpt {
+            max_imports: Some(4),
+            ..Default::default()
+        };
+        let budget = budget.with_overrides(&opt);
+        assert_eq!(budget.max_imports, Some(4));
+        assert_eq!(budget.max_size, Some(ByteSize::kib(512)));
+    }
+
+    #[test]
+    fn test_module_imports() {
+        assert_eq!(
+            module_imports(IMPORTING_MODULE).expect("imports"),
+            vec!["env::f".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_exceeded_budget() {
+        let report = ModuleReport {
+            size: ByteSize::kib(600),
+            imports: vec!["env::f".to_owned()],
+            instantiation: Err("missing export".to_owned()),
+        };
+        assert!(report.exceeded(&Budget::default()).is_empty());
+
+        let budget = Budget {
+            max_size: Some(ByteSize::kib(512)),
+            max_imports: Some(1),
+            max_instantiation: Some(Duration::from_millis(50)),
+        };
+        let exceeded = report.exceeded(&budget);
+        assert_eq!(exceeded.len(), 2);
+        assert!(exceeded[0].starts_with("size "));
+        assert_eq!(exceeded[1], "instantiation could not be measured");
+    }
+}
@@ -7,7 +7,9 @@ use clap::Parser;
 use cargo_builder::package::PackageInfo;
 use cargo_builder::cargo::Cargo;
 
+use crate::budget::BudgetOpt;
 use crate::cmd::PackageCmd;
+use crate::publish::find_smartmodule_toml;
 use crate::ENV_SMDK_NOWASI;
 
 pub(crate) const BUILD_TARGET: &str = "wasm32-unknown-unknown";
@@ -26,6 +28,9 @@ pub struct BuildCmd {
     /// Build a non wasi target (only use if needed for backward compatiblity)
     #[arg(long, env = ENV_SMDK_NOWASI, hide_short_help = true)]
     nowasi: bool,
+
+    #[clap(flatten)]
+    budget: BudgetOpt,
 }
 
 impl BuildCmd {
@@ -46,6 +51,14 @@ impl BuildCmd {
             .extra_arguments(self.extra_arguments)
             .build()?;
 
-        cargo.run()
+        cargo.run()?;
+
+        let wasm_path = if self.nowasi {
+            p.target_wasm32_path()?
+        } else {
+            p.target_wasm32_wasi_path()?
+        };
+        let sm_toml = find_smartmodule_toml(&p).ok();
+        self.budget.check(&wasm_path, sm_toml.as_deref())
     }
 }
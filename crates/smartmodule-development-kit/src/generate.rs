@@ -16,6 +16,7 @@ use lib_cargo_crate::{Info, InfoOpts};
 static SMART_MODULE_TEMPLATE: Dir<'static> =
     include_dir!("$CARGO_MANIFEST_DIR/../../smartmodule/cargo_template");
 const FLUVIO_SMARTMODULE_CRATE_NAME: &str = "fluvio-smartmodule";
+const FLUVIO_SMARTENGINE_CRATE_NAME: &str = "fluvio-smartengine";
 const FLUVIO_SMARTMODULE_REPO: &str = "https://github.com/infinyon/fluvio.git";
 
 /// Generate new SmartModule project
@@ -29,6 +30,11 @@ pub struct GenerateCmd {
     #[arg(long, env = "SMDK_PROJECT_GROUP", value_name = "GROUP")]
     project_group: Option<String>,
 
+    /// Description of the SmartModule, set in Cargo.toml and SmartModule.toml.
+    /// Skip prompt if value given.
+    #[arg(long, env = "SMDK_DESCRIPTION", value_name = "TEXT")]
+    description: Option<String>,
+
     /// Local path to generate the SmartModule project.
     /// Default to directory with project name, created in current directory
     #[arg(long, env = "SMDK_DESTINATION", value_name = "PATH")]
@@ -236,19 +242,18 @@ impl GenerateCmd {
         } else if self.develop {
             CargoSmDependSource::Git(FLUVIO_SMARTMODULE_REPO.to_string())
         } else {
-            let latest_sm_crate_info =
-                Info::new().fetch(vec![FLUVIO_SMARTMODULE_CRATE_NAME], &InfoOpts::default())?;
-            let version = &latest_sm_crate_info[0].krate.crate_data.max_version;
-
-            CargoSmDependSource::CratesIo(version.to_string())
+            CargoSmDependSource::CratesIo(latest_crate_version(FLUVIO_SMARTMODULE_CRATE_NAME)?)
         };
+        let engine_dep_source = sm_dep_source.sibling_crate(FLUVIO_SMARTENGINE_CRATE_NAME)?;
 
         let mut maybe_user_input = SmdkTemplateUserValues::new();
         maybe_user_input
             .with_project_group(group.clone())
             .with_smart_module_type(self.sm_type)
             .with_smart_module_params(sm_params)
+            .with_project_description(self.description)
             .with_smart_module_cargo_dependency(Some(sm_dep_source))
+            .with_smart_engine_cargo_dependency(Some(engine_dep_source))
             .with_smart_module_public(self.sm_public);
 
         // cargo generate template source
@@ -296,8 +301,10 @@ impl GenerateCmd {
 enum SmdkTemplateValue {
     UseParams(bool),
     SmCargoDependency(CargoSmDependSource),
+    EngineCargoDependency(CargoSmDependSource),
     SmType(SmartModuleType),
     ProjectGroup(String),
+    ProjectDescription(String),
     SmPublic(bool),
 }
 
@@ -307,6 +314,9 @@ impl std::fmt::Display for SmdkTemplateValue {
             SmdkTemplateValue::SmCargoDependency(dependency) => {
                 write!(f, "fluvio-smartmodule-cargo-dependency={dependency}")
             }
+            SmdkTemplateValue::EngineCargoDependency(dependency) => {
+                write!(f, "fluvio-smartengine-cargo-dependency={dependency}")
+            }
             SmdkTemplateValue::SmType(sm_type) => {
                 write!(f, "smartmodule-type={sm_type}")
             }
@@ -316,6 +326,9 @@ impl std::fmt::Display for SmdkTemplateValue {
             SmdkTemplateValue::ProjectGroup(group) => {
                 write!(f, "project-group={group}")
             }
+            SmdkTemplateValue::ProjectDescription(description) => {
+                write!(f, "project-description={description}")
+            }
             SmdkTemplateValue::SmPublic(public) => {
                 write!(f, "smartmodule-public={public}")
             }
@@ -468,6 +481,19 @@ impl SmdkTemplateUserValues {
         self
     }
 
+    /// dev-dependency of the generated tests, which run the SmartModule in the engine
+    fn with_smart_engine_cargo_dependency(
+        &mut self,
+        dependency: Option<CargoSmDependSource>,
+    ) -> &mut Self {
+        if let Some(d) = dependency {
+            debug!("fluvio-smartengine Cargo.toml value: {d:#?}");
+            self.values
+                .push(SmdkTemplateValue::EngineCargoDependency(d));
+        }
+        self
+    }
+
     fn with_smart_module_type(&mut self, sm_type: Option<SmartModuleType>) -> &mut Self {
         if let Some(t) = sm_type {
             debug!("User provided SmartModule type: {t:#?}");
@@ -492,6 +518,14 @@ impl SmdkTemplateUserValues {
         self
     }
 
+    fn with_project_description(&mut self, description: Option<String>) -> &mut Self {
+        if let Some(d) = description {
+            debug!("User project description: {d:#?}");
+            self.values.push(SmdkTemplateValue::ProjectDescription(d));
+        }
+        self
+    }
+
     fn with_smart_module_public(&mut self, public: Option<bool>) -> &mut Self {
         if let Some(p) = public {
             debug!("User project public: {p:#?}");
@@ -519,6 +553,23 @@ enum CargoSmDependSource {
     Path(PathBuf),
 }
 
+impl CargoSmDependSource {
+    /// Source of the crate `name` released along `fluvio-smartmodule`
+    fn sibling_crate(&self, name: &str) -> Result<Self> {
+        Ok(match self {
+            // crates are versioned independently
+            Self::CratesIo(_) => Self::CratesIo(latest_crate_version(name)?),
+            Self::Path(path) => Self::Path(path.with_file_name(name)),
+            git => git.clone(),
+        })
+    }
+}
+
+fn latest_crate_version(name: &str) -> Result<String> {
+    let info = Info::new().fetch(vec![name], &InfoOpts::default())?;
+    Ok(info[0].krate.crate_data.max_version.to_string())
+}
+
 impl std::fmt::Display for CargoSmDependSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -663,6 +714,16 @@ mod test {
                     );
                 }
 
+                SmdkTemplateValue::EngineCargoDependency(_) => {
+                    assert_eq!(
+                        &SmdkTemplateValue::EngineCargoDependency(CargoSmDependSource::CratesIo(
+                            "0.8.2".to_string()
+                        ))
+                        .to_string(),
+                        "fluvio-smartengine-cargo-dependency=\"0.8.2\""
+                    );
+                }
+
                 SmdkTemplateValue::SmType(_) => {
                     assert_eq!(
                         &SmdkTemplateValue::SmType(SmartModuleType::FilterMap).to_string(),
@@ -677,6 +738,13 @@ mod test {
                         "project-group=ExampleGroupName"
                     );
                 }
+                SmdkTemplateValue::ProjectDescription(_) => {
+                    assert_eq!(
+                        &SmdkTemplateValue::ProjectDescription("Keeps records".to_string())
+                            .to_string(),
+                        "project-description=Keeps records"
+                    );
+                }
                 SmdkTemplateValue::SmPublic(_) => {
                     assert_eq!(
                         &SmdkTemplateValue::SmPublic(true).to_string(),
@@ -702,6 +770,10 @@ mod test {
             .with_smart_module_cargo_dependency(Some(CargoSmDependSource::CratesIo(
                 test_version_number.clone(),
             )))
+            .with_smart_engine_cargo_dependency(Some(CargoSmDependSource::Path(
+                "../fluvio-smartengine".into(),
+            )))
+            .with_project_description(Some("Keeps records".to_string()))
             .with_smart_module_public(Some(false));
 
         let values_vec = values.to_vec();
@@ -721,6 +793,15 @@ mod test {
                     );
                 }
 
+                SmdkTemplateValue::EngineCargoDependency(_) => {
+                    assert_eq!(
+                        v,
+                        SmdkTemplateValue::EngineCargoDependency(CargoSmDependSource::Path(
+                            "../fluvio-smartengine".into()
+                        ))
+                    );
+                }
+
                 SmdkTemplateValue::SmType(_) => {
                     assert_eq!(v, SmdkTemplateValue::SmType(SmartModuleType::Aggregate));
                 }
@@ -731,10 +812,31 @@ mod test {
                         SmdkTemplateValue::ProjectGroup("ExampleGroupName".to_string())
                     );
                 }
+                SmdkTemplateValue::ProjectDescription(_) => {
+                    assert_eq!(
+                        v,
+                        SmdkTemplateValue::ProjectDescription("Keeps records".to_string())
+                    );
+                }
                 SmdkTemplateValue::SmPublic(_) => {
                     assert_eq!(v, SmdkTemplateValue::SmPublic(false));
                 }
             }
         }
     }
+
+    #[test]
+    fn test_engine_dependency_source() {
+        let git = CargoSmDependSource::GitTag {
+            url: FLUVIO_SMARTMODULE_REPO.to_string(),
+            tag: "v0.11.0".to_string(),
+        };
+        assert_eq!(git.sibling_crate("fluvio-smartengine").unwrap(), git);
+
+        let path = CargoSmDependSource::Path("/src/fluvio/crates/fluvio-smartmodule".into());
+        assert_eq!(
+            path.sibling_crate("fluvio-smartengine").unwrap(),
+            CargoSmDependSource::Path("/src/fluvio/crates/fluvio-smartengine".into())
+        );
+    }
 }
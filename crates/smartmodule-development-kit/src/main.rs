@@ -1,4 +1,5 @@
 mod build;
+mod budget;
 mod cmd;
 mod generate;
 mod test;
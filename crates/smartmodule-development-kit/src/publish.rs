@@ -16,6 +16,7 @@ use hubutil::{
 };
 
 use crate::ENV_SMDK_NOWASI;
+use crate::budget::BudgetOpt;
 use crate::cmd::PackageCmd;
 use crate::hub::set_hubid;
 
@@ -54,6 +55,17 @@ pub struct PublishCmd {
     /// Relative path to this connector package README
     #[clap(long, default_value = "./README.md")]
     readme: PathBuf,
+
+    /// Attach the build provenance, git commit and builder, to the package
+    #[arg(long)]
+    provenance: bool,
+
+    /// Attach a software bill of materials to the package, implies --provenance
+    #[arg(long, value_name = "PATH")]
+    sbom: Option<PathBuf>,
+
+    #[clap(flatten)]
+    budget: BudgetOpt,
 }
 
 impl PublishCmd {
@@ -72,7 +84,7 @@ impl PublishCmd {
 
                 let hubdir = self.run_in_cargo_project()?;
                 let package_meta_path = self.package_meta_path(&hubdir);
-                let pkgdata = package_assemble(package_meta_path, &access)?;
+                let pkgdata = package_assemble(package_meta_path, &access, self)?;
                 package_push(self, &pkgdata, &access)?;
                 Self::cleanup(&hubdir)?;
             }
@@ -81,7 +93,7 @@ impl PublishCmd {
             (true, false) => {
                 let hubdir = self.run_in_cargo_project()?;
                 let package_meta_path = self.package_meta_path(&hubdir);
-                package_assemble(package_meta_path, &access)?;
+                package_assemble(package_meta_path, &access, self)?;
             }
 
             // --push only, needs ipkg file or expects to be run in project folder
@@ -118,6 +130,14 @@ impl PublishCmd {
 
         Self::cleanup(&hubdir)?;
 
+        let wasm_path = if self.nowasi {
+            package_info.target_wasm32_path()?
+        } else {
+            package_info.target_wasm32_wasi_path()?
+        };
+        let sm_toml = find_smartmodule_toml(&package_info)?;
+        self.budget.check(&wasm_path, Some(&sm_toml))?;
+
         init_package_template(
             &package_info,
             &InitPackageTemplateOptions {
@@ -181,16 +201,29 @@ impl PublishCmd {
     }
 }
 
-pub fn package_assemble<P: AsRef<Path>>(pkgmeta: P, access: &HubAccess) -> Result<String> {
-    let pkgname = hubutil::package_assemble_and_sign(
-        &pkgmeta,
-        access,
-        pkgmeta
-            .as_ref()
-            .parent()
-            .ok_or_else(|| anyhow::anyhow!("invalid package meta path"))?,
-        None,
-    )?;
+pub fn package_assemble<P: AsRef<Path>>(
+    pkgmeta: P,
+    access: &HubAccess,
+    opts: &PublishCmd,
+) -> Result<String> {
+    let outdir = pkgmeta
+        .as_ref()
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid package meta path"))?;
+    let pkgname = if opts.provenance || opts.sbom.is_some() {
+        let builder = format!("smdk {}", env!("CARGO_PKG_VERSION"));
+        let provenance = hubutil::provenance_from_git(outdir, &builder);
+        hubutil::package_assemble_and_sign_with_provenance(
+            &pkgmeta,
+            access,
+            outdir,
+            None,
+            provenance,
+            opts.sbom.as_deref(),
+        )?
+    } else {
+        hubutil::package_assemble_and_sign(&pkgmeta, access, outdir, None)?
+    };
     println!("Package {pkgname} created");
     Ok(pkgname)
 }
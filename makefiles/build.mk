@@ -19,18 +19,23 @@ build-cli-minimal: install_rustup_target
 	cargo build --bin fluvio -p fluvio-cli $(RELEASE_FLAG) $(TARGET_FLAG) $(VERBOSE_FLAG) \
 	    --no-default-features --features consumer,producer-file-io
 
-# note: careful that the if statement branches are leading spaces, tabs
-ifeq ($(TARGET), armv7-unknown-linux-gnueabihf)
-  fluvio_run_extra=--no-default-features --features rustls
-else
-  fluvio_run_extra=
-endif
+# armv7 targets use rustls instead of openssl
+ARMV7_TARGETS=armv7-unknown-linux-gnueabihf armv7-unknown-linux-musleabihf
+fluvio_run_extra=$(if $(filter $(ARMV7_TARGETS),$(TARGET)),--no-default-features --features rustls,)
 build-cluster: install_rustup_target
 	cargo build --bin fluvio-run -p fluvio-run $(RELEASE_FLAG) $(TARGET_FLAG) $(VERBOSE_FLAG) $(DEBUG_SMARTMODULE_FLAG) $(fluvio_run_extra)
 
 build-run:
 	cargo build --bin fluvio-run -p fluvio-run $(RELEASE_FLAG) $(TARGET_FLAG) $(VERBOSE_FLAG) $(DEBUG_SMARTMODULE_FLAG) $(fluvio_run_extra)
 
+build-edge-agent: install_rustup_target
+	cargo build --bin fluvio-edge -p fluvio-run $(RELEASE_FLAG) $(TARGET_FLAG) $(VERBOSE_FLAG) $(fluvio_run_extra)
+
+# static binaries for constrained ARM gateways, sized for footprint rather than speed
+build-edge: TARGET=armv7-unknown-linux-musleabihf
+build-edge: CARGO_PROFILE=release-edge
+build-edge: build-cli-minimal build-cluster build-edge-agent
+
 build-test:	install_rustup_target
 	cargo build --bin fluvio-test -p fluvio-test $(RELEASE_FLAG) $(TARGET_FLAG) $(VERBOSE_FLAG)
 
@@ -1,18 +1,32 @@
 {% if smartmodule-params %}
-use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleExtraParams{% if smartmodule-type == "filter" %}, SmartModuleInitError{% endif %}};
-{% if smartmodule-type == "filter" %}
 use std::sync::OnceLock;
+
+use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleExtraParams{% if smartmodule-type == "filter" %}, SmartModuleInitError{% endif %}};
 use fluvio_smartmodule::eyre;
 {% endif %}
-{% endif %}
 {% if smartmodule-type == "filter" %}
 use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord};
 
 #[smartmodule(filter)]
 pub fn filter(record: &SmartModuleRecord) -> Result<bool> {
     let string = std::str::from_utf8(record.value.as_ref())?;
-    Ok(string.contains('a'))
+{% if smartmodule-params %}    let criteria = CRITERIA.get().map(String::as_str).unwrap_or_default();
+    Ok(string.contains(criteria)){% else %}    Ok(string.contains('a')){% endif %}
+}
+{% if smartmodule-params %}
+static CRITERIA: OnceLock<String> = OnceLock::new();
+
+#[smartmodule(init)]
+fn init(params: SmartModuleExtraParams) -> Result<()> {
+    // You can refer to the example SmartModules in Fluvio's GitHub Repository
+    // https://github.com/infinyon/fluvio/tree/master/smartmodule
+    if let Some(key) = params.get("key") {
+        CRITERIA.set(key.clone()).map_err(|err| eyre!("failed setting key: {:#?}", err))
+    } else {
+        Err(SmartModuleInitError::MissingParam("key".to_string()).into())
+    }
 }
+{% endif %}
 {% elsif smartmodule-type == "map" %}
 use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord, RecordData};
 
@@ -22,10 +36,24 @@ pub fn map(record: &SmartModuleRecord) -> Result<(Option<RecordData>, RecordData
 
     let string = std::str::from_utf8(record.value.as_ref())?;
     let int = string.parse::<i32>()?;
-    let value = (int * 2).to_string();
+{% if smartmodule-params %}    let value = (int * FACTOR.get().copied().unwrap_or(2)).to_string();{% else %}    let value = (int * 2).to_string();{% endif %}
 
     Ok((key, value.into()))
 }
+{% if smartmodule-params %}
+static FACTOR: OnceLock<i32> = OnceLock::new();
+
+#[smartmodule(init)]
+fn init(params: SmartModuleExtraParams) -> Result<()> {
+    // You can refer to the example SmartModules in Fluvio's GitHub Repository
+    // https://github.com/infinyon/fluvio/tree/master/smartmodule
+    let factor = match params.get("factor") {
+        Some(factor) => factor.parse()?,
+        None => 2,
+    };
+    FACTOR.set(factor).map_err(|err| eyre!("failed setting factor: {:#?}", err))
+}
+{% endif %}
 {% elsif smartmodule-type == "filter-map" %}
 use fluvio_smartmodule::{smartmodule, SmartModuleRecord, RecordData, Result};
 
@@ -34,15 +62,32 @@ pub fn filter_map(record: &SmartModuleRecord) -> Result<Option<(Option<RecordDat
     let key = record.key.clone();
     let string = String::from_utf8_lossy(record.value.as_ref()).to_string();
     let int: i32 = string.parse()?;
+{% if smartmodule-params %}    let divisor = DIVISOR.get().copied().unwrap_or(2);{% else %}    let divisor = 2;{% endif %}
 
-    if int % 2 == 0 {
-        let output = int / 2;
+    if int % divisor == 0 {
+        let output = int / divisor;
         Ok(Some((key.clone(), RecordData::from(output.to_string()))))
     } else {
         Ok(None)
     }
 }
+{% if smartmodule-params %}
+static DIVISOR: OnceLock<i32> = OnceLock::new();
 
+#[smartmodule(init)]
+fn init(params: SmartModuleExtraParams) -> Result<()> {
+    // You can refer to the example SmartModules in Fluvio's GitHub Repository
+    // https://github.com/infinyon/fluvio/tree/master/smartmodule
+    let divisor = match params.get("divisor") {
+        Some(divisor) => divisor.parse()?,
+        None => 2,
+    };
+    if divisor == 0 {
+        return Err(eyre!("divisor must not be 0"));
+    }
+    DIVISOR.set(divisor).map_err(|err| eyre!("failed setting divisor: {:#?}", err))
+}
+{% endif %}
 {% elsif smartmodule-type == "array-map" %}
 use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord, RecordData};
 
@@ -58,12 +103,25 @@ pub fn array_map(record: &SmartModuleRecord) -> Result<Vec<(Option<RecordData>,
         .collect::<core::result::Result<_, _>>()?;
 
     // Create one record from each JSON string to send
+{% if smartmodule-params %}    let key = KEY.get().cloned().flatten();{% else %}    let key: Option<String> = None;{% endif %}
     let kvs: Vec<(Option<RecordData>, RecordData)> = strings
         .into_iter()
-        .map(|s| (None, RecordData::from(s)))
+        .map(|s| (key.clone().map(RecordData::from), RecordData::from(s)))
         .collect();
     Ok(kvs)
 }
+{% if smartmodule-params %}
+/// key of the records sent, none when the `key` param is not set
+static KEY: OnceLock<Option<String>> = OnceLock::new();
+
+#[smartmodule(init)]
+fn init(params: SmartModuleExtraParams) -> Result<()> {
+    // You can refer to the example SmartModules in Fluvio's GitHub Repository
+    // https://github.com/infinyon/fluvio/tree/master/smartmodule
+    KEY.set(params.get("key").cloned())
+        .map_err(|err| eyre!("failed setting key: {:#?}", err))
+}
+{% endif %}
 {% elsif smartmodule-type == "aggregate" %}
 use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord, RecordData};
 
@@ -75,34 +133,31 @@ pub fn aggregate(accumulator: RecordData, current: &SmartModuleRecord) -> Result
 
     // Parse the strings into integers
     let accumulator_int = accumulator_string.trim().parse::<i32>().unwrap_or(0);
-    let current_int = current_string.trim().parse::<i32>()?;
+{% if smartmodule-params %}    let current_int = match current_string.trim().parse::<i32>() {
+        Ok(int) => int,
+        // keep the sum unchanged for records that are not integers
+        Err(_) if IGNORE_INVALID.get().copied().unwrap_or_default() => 0,
+        Err(err) => return Err(err.into()),
+    };{% else %}    let current_int = current_string.trim().parse::<i32>()?;{% endif %}
 
     // Take the sum of the two integers and return it as a string
     let sum = accumulator_int + current_int;
     Ok(sum.to_string().into())
 }
-{% endif %}
-
 {% if smartmodule-params %}
-{% if smartmodule-type == "filter" %}
-static CRITERIA: OnceLock<String> = OnceLock::new();
+static IGNORE_INVALID: OnceLock<bool> = OnceLock::new();
 
 #[smartmodule(init)]
 fn init(params: SmartModuleExtraParams) -> Result<()> {
     // You can refer to the example SmartModules in Fluvio's GitHub Repository
     // https://github.com/infinyon/fluvio/tree/master/smartmodule
-    if let Some(key) = params.get("key") {
-        CRITERIA.set(key.clone()).map_err(|err| eyre!("failed setting key: {:#?}", err))
-    } else {
-        Err(SmartModuleInitError::MissingParam("key".to_string()).into())
-    }
-}
-{% else %}
-#[smartmodule(init)]
-fn init(_params: SmartModuleExtraParams) -> Result<()> {
-    // You can refer to the example SmartModules in Fluvio's GitHub Repository
-    // https://github.com/infinyon/fluvio/tree/master/smartmodule
-    todo!("Provide initialization logic for your SmartModule")
+    let ignore_invalid = match params.get("ignore-invalid") {
+        Some(ignore_invalid) => ignore_invalid.parse()?,
+        None => false,
+    };
+    IGNORE_INVALID
+        .set(ignore_invalid)
+        .map_err(|err| eyre!("failed setting ignore-invalid: {:#?}", err))
 }
 {% endif %}
 {% endif %}
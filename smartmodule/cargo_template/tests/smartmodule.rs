@@ -0,0 +1,90 @@
+//! Runs the SmartModule built by `smdk build` in the SmartEngine used by the SPU
+
+use std::path::PathBuf;
+
+use fluvio_smartengine::metrics::SmartModuleChainMetrics;
+use fluvio_smartengine::{
+    SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance, SmartModuleConfig,
+    DEFAULT_SMARTENGINE_VERSION,
+};
+use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
+use fluvio_smartmodule::Record;
+
+const WASM_FILE: &str = "wasm32-wasi/release-lto/{{crate_name}}.wasm";
+
+/// the SmartModule may be built in the target directory of a workspace
+fn read_wasm() -> Vec<u8> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let path = std::env::var_os("CARGO_TARGET_DIR")
+        .map(|dir| PathBuf::from(dir).join(WASM_FILE))
+        .into_iter()
+        .chain(
+            manifest_dir
+                .ancestors()
+                .map(|dir| dir.join("target").join(WASM_FILE)),
+        )
+        .find(|path| path.exists())
+        .expect("SmartModule is not built, run `smdk build` first");
+    std::fs::read(path).expect("read SmartModule")
+}
+
+fn chain(config: SmartModuleConfig) -> SmartModuleChainInstance {
+    let engine = SmartEngine::new();
+    SmartModuleChainBuilder::from((config, read_wasm()))
+        .initialize(&engine)
+        .expect("initialize SmartModule")
+}
+
+/// values of the records output for records with `values`
+fn process(chain: &mut SmartModuleChainInstance, values: &[&str]) -> Vec<String> {
+    let records = values.iter().map(|value| Record::new(*value)).collect();
+    let input = SmartModuleInput::try_from_records(records, DEFAULT_SMARTENGINE_VERSION)
+        .expect("input");
+    let output = chain
+        .process(input, &SmartModuleChainMetrics::default())
+        .expect("process");
+    assert!(output.error.is_none(), "{:?}", output.error);
+    output
+        .successes
+        .iter()
+        .map(|record| String::from_utf8_lossy(record.value.as_ref()).to_string())
+        .collect()
+}
+{% if smartmodule-type == "filter" %}
+#[test]
+fn test_filter() {
+{% if smartmodule-params %}    let mut chain = chain(SmartModuleConfig::builder().param("key", "um").build().unwrap());
+    assert_eq!(process(&mut chain, &["apple", "plum"]), vec!["plum"]);{% else %}    let mut chain = chain(SmartModuleConfig::builder().build().unwrap());
+    assert_eq!(process(&mut chain, &["apple", "plum"]), vec!["apple"]);{% endif %}
+}
+{% elsif smartmodule-type == "map" %}
+#[test]
+fn test_map() {
+{% if smartmodule-params %}    let mut chain = chain(SmartModuleConfig::builder().param("factor", "3").build().unwrap());
+    assert_eq!(process(&mut chain, &["1", "2"]), vec!["3", "6"]);{% else %}    let mut chain = chain(SmartModuleConfig::builder().build().unwrap());
+    assert_eq!(process(&mut chain, &["1", "2"]), vec!["2", "4"]);{% endif %}
+}
+{% elsif smartmodule-type == "filter-map" %}
+#[test]
+fn test_filter_map() {
+{% if smartmodule-params %}    let mut chain = chain(SmartModuleConfig::builder().param("divisor", "3").build().unwrap());
+    assert_eq!(process(&mut chain, &["3", "4", "6"]), vec!["1", "2"]);{% else %}    let mut chain = chain(SmartModuleConfig::builder().build().unwrap());
+    assert_eq!(process(&mut chain, &["2", "3", "4"]), vec!["1", "2"]);{% endif %}
+}
+{% elsif smartmodule-type == "array-map" %}
+#[test]
+fn test_array_map() {
+{% if smartmodule-params %}    let mut chain = chain(SmartModuleConfig::builder().param("key", "letter").build().unwrap());{% else %}    let mut chain = chain(SmartModuleConfig::builder().build().unwrap());{% endif %}
+    assert_eq!(
+        process(&mut chain, &[r#"["a", "b"]"#]),
+        vec![r#""a""#, r#""b""#]
+    );
+}
+{% elsif smartmodule-type == "aggregate" %}
+#[test]
+fn test_aggregate() {
+{% if smartmodule-params %}    let mut chain = chain(SmartModuleConfig::builder().param("ignore-invalid", "true").build().unwrap());
+    assert_eq!(process(&mut chain, &["1", "x", "2"]), vec!["1", "1", "3"]);{% else %}    let mut chain = chain(SmartModuleConfig::builder().build().unwrap());
+    assert_eq!(process(&mut chain, &["1", "2", "3"]), vec!["1", "3", "6"]);{% endif %}
+}
+{% endif %}
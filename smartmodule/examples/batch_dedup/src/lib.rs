@@ -0,0 +1,33 @@
+//! This SmartModule drops the records repeating a value already seen in their batch.
+//!
+//! Batch SmartModules are called once with all the records of a batch, so state like the
+//! set of seen values does not have to outlive a call.
+//!
+//! To test this SmartModule, set up a test Topic:
+//!
+//! ```text
+//! $ fluvio topic create batch-dedup
+//! ```
+//!
+//! Then, make sure you have compiled the SmartModule examples, and run the consumer:
+//!
+//! ```text
+//! $ cd smartmodule/examples
+//! $ cargo build --release
+//! $ fluvio consume batch-dedup -B --smartmodule-path=target/wasm32-wasi/release/fluvio_smartmodule_batch_dedup.wasm
+//! ```
+
+use std::collections::HashSet;
+
+use fluvio_smartmodule::{smartmodule, SmartModuleRecord, RecordData, Result};
+
+#[smartmodule(batch)]
+pub fn batch(records: &[SmartModuleRecord]) -> Result<Vec<(Option<RecordData>, RecordData)>> {
+    let mut seen = HashSet::new();
+    let records = records
+        .iter()
+        .filter(|record| seen.insert(record.value.as_ref()))
+        .map(|record| (record.key.clone(), record.value.clone()))
+        .collect();
+    Ok(records)
+}
@@ -0,0 +1,14 @@
+use fluvio_smartmodule::{smartmodule, lookup, SmartModuleRecord, RecordData, Result};
+
+/// Replaces the value of each record by the value of its key in the lookup state,
+/// records without an entry are kept as they are
+#[smartmodule(map)]
+pub fn map(record: &SmartModuleRecord) -> Result<(Option<RecordData>, RecordData)> {
+    let key = record.key.clone();
+    let value = key
+        .as_ref()
+        .and_then(lookup)
+        .map(RecordData::from)
+        .unwrap_or_else(|| record.value.clone());
+    Ok((key, value))
+}
@@ -416,7 +416,7 @@ smdk_via_stdin() {
     assert_success
 
     # Test
-    run $SMDK_BIN test --verbose --text 'a' -e key=value
+    run $SMDK_BIN test --verbose --text 'a' -e key=a
     assert_output --partial "1 records outputted"
     assert_success
 }
@@ -812,7 +812,7 @@ smdk_via_stdin() {
     assert_success
 
     # Test
-    run smdk_via_stdin 'a' -e key=value --verbose
+    run smdk_via_stdin 'a' -e key=a --verbose
     assert_output --partial "1 records outputted"
     assert_success
 }